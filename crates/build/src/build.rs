@@ -1,11 +1,13 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
-use cargo_metadata::camino::Utf8PathBuf;
+use cargo_metadata::{camino::Utf8PathBuf, Metadata};
 
 use crate::{
     command::{docker::create_docker_command, local::create_local_command, utils::execute_command},
-    utils::{cargo_rerun_if_changed, copy_elf_to_output_dir, current_datetime},
+    elf::expose_elf,
+    patches::{find_unpatched_crates, UnpatchedCrate},
+    utils::{cargo_rerun_if_changed, copy_elf_to_output_dir, current_datetime, output_elf_path},
     BuildArgs,
 };
 
@@ -37,11 +39,23 @@ pub fn execute_build_program(
     let mut program_metadata_cmd = cargo_metadata::MetadataCommand::new();
     let program_metadata = program_metadata_cmd.manifest_path(program_metadata_file).exec()?;
 
+    // Warn about, or patch, dependencies that are much slower without their precompile-accelerated
+    // forks.
+    let unpatched = find_unpatched_crates(&program_metadata, &args.allow_unpatched);
+    let patch_args = if args.auto_patch {
+        unpatched.iter().flat_map(UnpatchedCrate::cargo_config_args).collect()
+    } else {
+        for krate in &unpatched {
+            eprintln!("[sp1] warning: {}", krate.warning());
+        }
+        vec![]
+    };
+
     // Get the command corresponding to Docker or local build.
     let cmd = if args.docker {
-        create_docker_command(args, &program_dir, &program_metadata)?
+        create_docker_command(args, &program_dir, &program_metadata, &patch_args)?
     } else {
-        create_local_command(args, &program_dir, &program_metadata)
+        create_local_command(args, &program_dir, &program_metadata, &patch_args)
     };
 
     execute_command(cmd, args.docker)?;
@@ -58,6 +72,7 @@ pub(crate) fn build_program_internal(path: &str, args: Option<BuildArgs>) {
     let metadata = metadata_cmd.manifest_path(metadata_file).exec().unwrap();
     let root_package = metadata.root_package();
     let root_package_name = root_package.as_ref().map(|p| p.name.as_str()).unwrap_or("Program");
+    let args = args.unwrap_or_default();
 
     // Skip the program build if the SP1_SKIP_PROGRAM_BUILD environment variable is set to true.
     let skip_program_build = std::env::var("SP1_SKIP_PROGRAM_BUILD")
@@ -69,12 +84,20 @@ pub(crate) fn build_program_internal(path: &str, args: Option<BuildArgs>) {
             root_package_name,
             current_datetime()
         );
+        expose_existing_elf(&args, &metadata, program_dir);
         return;
     }
 
     // Activate the build command if the dependencies change.
     cargo_rerun_if_changed(&metadata, program_dir);
 
+    // Surface the slow dependencies as cargo warnings, since the output of the build is hidden.
+    if !args.auto_patch {
+        for krate in find_unpatched_crates(&metadata, &args.allow_unpatched) {
+            println!("cargo:warning={}", krate.warning());
+        }
+    }
+
     // Check if RUSTC_WORKSPACE_WRAPPER is set to clippy-driver (i.e. if `cargo clippy` is the
     // current compiler). If so, don't execute `cargo prove build` because it breaks
     // rust-analyzer's `cargo clippy` feature.
@@ -83,18 +106,25 @@ pub(crate) fn build_program_internal(path: &str, args: Option<BuildArgs>) {
         .unwrap_or(false);
     if is_clippy_driver {
         println!("cargo:warning=Skipping build due to clippy invocation.");
+        expose_existing_elf(&args, &metadata, program_dir);
         return;
     }
 
     // Build the program with the given arguments.
-    let path_output = if let Some(args) = args {
-        execute_build_program(&args, Some(program_dir.to_path_buf()))
-    } else {
-        execute_build_program(&BuildArgs::default(), Some(program_dir.to_path_buf()))
-    };
-    if let Err(err) = path_output {
-        panic!("Failed to build SP1 program: {}.", err);
+    let path_output = execute_build_program(&args, Some(program_dir.to_path_buf()));
+    match path_output {
+        Ok(elf_path) => expose_elf(&elf_path, program_dir, &args),
+        Err(err) => panic!("Failed to build SP1 program: {}.", err),
     }
 
     println!("cargo:warning={} built at {}", root_package_name, current_datetime());
 }
+
+/// Expose the ELF of a previous build, if any, so that [`include_elf!`](crate::include_elf) keeps
+/// working when the build is skipped.
+fn expose_existing_elf(args: &BuildArgs, metadata: &Metadata, program_dir: &Path) {
+    let elf_path = output_elf_path(args, metadata);
+    if elf_path.exists() {
+        expose_elf(&elf_path, program_dir, args);
+    }
+}
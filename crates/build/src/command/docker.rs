@@ -21,6 +21,7 @@ pub(crate) fn create_docker_command(
     args: &BuildArgs,
     program_dir: &Utf8PathBuf,
     program_metadata: &cargo_metadata::Metadata,
+    patch_args: &[String],
 ) -> Result<Command> {
     let image = get_docker_image(&args.tag);
     let canonicalized_program_dir: Utf8PathBuf = program_dir
@@ -90,7 +91,7 @@ pub(crate) fn create_docker_command(
     ];
 
     // Add the SP1 program build arguments.
-    docker_args.extend_from_slice(&get_program_build_args(args));
+    docker_args.extend_from_slice(&get_program_build_args(args, patch_args));
 
     let mut command = Command::new("docker");
     command.current_dir(canonicalized_program_dir.clone()).args(&docker_args);
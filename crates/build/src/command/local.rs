@@ -11,6 +11,7 @@ pub(crate) fn create_local_command(
     args: &BuildArgs,
     program_dir: &Utf8PathBuf,
     program_metadata: &cargo_metadata::Metadata,
+    patch_args: &[String],
 ) -> Command {
     let mut command = Command::new("cargo");
     let canonicalized_program_dir =
@@ -42,6 +43,6 @@ pub(crate) fn create_local_command(
         .env("CARGO_ENCODED_RUSTFLAGS", get_rust_compiler_flags())
         .env_remove("RUSTC")
         .env("CARGO_TARGET_DIR", program_metadata.target_directory.join(HELPER_TARGET_SUBDIR))
-        .args(get_program_build_args(args));
+        .args(get_program_build_args(args, patch_args));
     command
 }
@@ -8,7 +8,10 @@ use std::{
 use crate::{BuildArgs, BUILD_TARGET};
 
 /// Get the arguments to build the program with the arguments from the [`BuildArgs`] struct.
-pub(crate) fn get_program_build_args(args: &BuildArgs) -> Vec<String> {
+///
+/// The `patch_args` are `--config` arguments patching dependencies with their
+/// precompile-accelerated forks.
+pub(crate) fn get_program_build_args(args: &BuildArgs, patch_args: &[String]) -> Vec<String> {
     let mut build_args = vec![
         "build".to_string(),
         "--release".to_string(),
@@ -38,14 +41,19 @@ pub(crate) fn get_program_build_args(args: &BuildArgs) -> Vec<String> {
         build_args.push("--locked".to_string());
     }
 
+    build_args.extend_from_slice(patch_args);
+
     build_args
 }
 
 /// Rust flags for compilation of C libraries.
+///
+/// The zkVM implements the `a` extension, so atomics compile to the RV32A instructions instead of
+/// being lowered to plain loads and stores.
 pub(crate) fn get_rust_compiler_flags() -> String {
     let rust_flags = [
         "-C".to_string(),
-        "passes=loweratomic".to_string(),
+        "target-feature=+a".to_string(),
         "-C".to_string(),
         "link-arg=-Ttext=0x00200800".to_string(),
         "-C".to_string(),
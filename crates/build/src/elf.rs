@@ -0,0 +1,85 @@
+use std::{path::Path, process::Command};
+
+use cargo_metadata::camino::Utf8Path;
+
+use crate::{utils::current_datetime, BuildArgs};
+
+/// Metadata about an ELF built by [`build_program`](crate::build_program), available at runtime
+/// through [`elf_metadata!`](crate::elf_metadata).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElfMetadata {
+    /// The name of the ELF.
+    pub name: &'static str,
+    /// The commit hash of the repository of the program, or `unknown` outside of a git repository.
+    pub commit: &'static str,
+    /// The version of the toolchain the ELF was built with, or the Docker image tag.
+    pub toolchain: &'static str,
+    /// The local time at which the ELF was built.
+    pub built_at: &'static str,
+}
+
+/// Embeds the ELF named `$name` built by [`build_program`](crate::build_program) in the build
+/// script of the current crate, as a `&'static [u8]`.
+///
+/// The name is the `elf_name` of the [`BuildArgs`](crate::BuildArgs), or the binary name, or
+/// `riscv32im-succinct-zkvm-elf` by default.
+///
+/// ```ignore
+/// const ELF: &[u8] = sp1_build::include_elf!("fibonacci-program");
+/// ```
+#[macro_export]
+macro_rules! include_elf {
+    ($name:literal) => {
+        include_bytes!(env!(concat!("SP1_ELF_", $name)))
+    };
+}
+
+/// The [`ElfMetadata`](crate::ElfMetadata) of the ELF named `$name` built by
+/// [`build_program`](crate::build_program) in the build script of the current crate.
+#[macro_export]
+macro_rules! elf_metadata {
+    ($name:literal) => {
+        $crate::ElfMetadata {
+            name: $name,
+            commit: env!(concat!("SP1_ELF_COMMIT_", $name)),
+            toolchain: env!(concat!("SP1_ELF_TOOLCHAIN_", $name)),
+            built_at: env!(concat!("SP1_ELF_BUILT_AT_", $name)),
+        }
+    };
+}
+
+/// Expose the ELF at `elf_path` and its metadata to the crate being built, for
+/// [`include_elf!`](crate::include_elf) and [`elf_metadata!`](crate::elf_metadata).
+pub(crate) fn expose_elf(elf_path: &Utf8Path, program_dir: &Path, args: &BuildArgs) {
+    let name = elf_path.file_name().unwrap();
+    println!("cargo:rustc-env=SP1_ELF_{}={}", name, elf_path);
+    println!("cargo:rustc-env=SP1_ELF_COMMIT_{}={}", name, commit_hash(program_dir));
+    println!("cargo:rustc-env=SP1_ELF_TOOLCHAIN_{}={}", name, toolchain_version(args));
+    println!("cargo:rustc-env=SP1_ELF_BUILT_AT_{}={}", name, current_datetime());
+}
+
+/// The commit hash of the repository containing `program_dir`.
+fn commit_hash(program_dir: &Path) -> String {
+    let output = Command::new("git").args(["rev-parse", "HEAD"]).current_dir(program_dir).output();
+    match output {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+        _ => "unknown".to_string(),
+    }
+}
+
+/// The version of the `succinct` toolchain, or the Docker image used for the build.
+fn toolchain_version(args: &BuildArgs) -> String {
+    if args.docker {
+        return format!("docker:{}", args.tag);
+    }
+    let output =
+        Command::new("rustc").env("RUSTUP_TOOLCHAIN", "succinct").arg("--version").output();
+    match output {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+        _ => "unknown".to_string(),
+    }
+}
@@ -1,8 +1,11 @@
 mod build;
 mod command;
+mod elf;
+mod patches;
 mod utils;
 use build::build_program_internal;
 pub use build::execute_build_program;
+pub use elf::ElfMetadata;
 
 use clap::Parser;
 
@@ -61,6 +64,19 @@ pub struct BuildArgs {
         default_value = DEFAULT_OUTPUT_DIR
     )]
     pub output_directory: String,
+    #[clap(
+        long,
+        action,
+        help = "Replace known slow dependencies with their precompile-accelerated forks"
+    )]
+    pub auto_patch: bool,
+    #[clap(
+        long,
+        action,
+        value_delimiter = ',',
+        help = "Comma separated list of crates to leave unpatched without warning"
+    )]
+    pub allow_unpatched: Vec<String>,
 }
 
 // Implement default args to match clap defaults.
@@ -76,6 +92,8 @@ impl Default for BuildArgs {
             output_directory: DEFAULT_OUTPUT_DIR.to_string(),
             locked: false,
             no_default_features: false,
+            auto_patch: false,
+            allow_unpatched: vec![],
         }
     }
 }
@@ -92,6 +110,9 @@ impl Default for BuildArgs {
 /// This function is useful for automatically rebuilding the program during development
 /// when changes are made to the source code or its dependencies.
 ///
+/// The ELF can then be embedded in the crate with [`include_elf!`], and metadata about its build
+/// read with [`elf_metadata!`].
+///
 /// Set the `SP1_SKIP_PROGRAM_BUILD` environment variable to `true` to skip building the program.
 pub fn build_program(path: &str) {
     build_program_internal(path, None)
@@ -0,0 +1,147 @@
+use cargo_metadata::Metadata;
+
+/// A crate with a fork that routes its hot paths through the SP1 precompiles.
+pub(crate) struct PrecompilePatch {
+    /// The name of the crate on crates.io.
+    pub(crate) name: &'static str,
+    /// The repository of the patched fork.
+    pub(crate) git: &'static str,
+    /// The patched versions of the crate, and the branch of the fork for each.
+    pub(crate) branches: &'static [(&'static str, &'static str)],
+    /// The precompile used by the patched fork.
+    pub(crate) precompile: &'static str,
+}
+
+/// The crates that are known to be much slower in the zkVM without their patched fork.
+///
+/// `k256` does its signature verification through `ecdsa`, which is the crate that is patched.
+pub(crate) const PRECOMPILE_PATCHES: &[PrecompilePatch] = &[
+    PrecompilePatch {
+        name: "sha2",
+        git: "https://github.com/sp1-patches/RustCrypto-hashes",
+        branches: &[
+            ("0.9.8", "patch-sha2-v0.9.8"),
+            ("0.10.6", "patch-sha2-v0.10.6"),
+            ("0.10.8", "patch-sha2-v0.10.8"),
+        ],
+        precompile: "sha256",
+    },
+    PrecompilePatch {
+        name: "sha3",
+        git: "https://github.com/sp1-patches/RustCrypto-hashes",
+        branches: &[
+            ("0.9.8", "patch-sha3-v0.9.8"),
+            ("0.10.6", "patch-sha3-v0.10.6"),
+            ("0.10.8", "patch-sha3-v0.10.8"),
+        ],
+        precompile: "keccak256",
+    },
+    PrecompilePatch {
+        name: "tiny-keccak",
+        git: "https://github.com/sp1-patches/tiny-keccak",
+        branches: &[("2.0.2", "patch-v2.0.2")],
+        precompile: "keccak256",
+    },
+    PrecompilePatch {
+        name: "ecdsa",
+        git: "https://github.com/sp1-patches/signatures",
+        branches: &[("0.16.9", "patch-ecdsa-v0.16.9")],
+        precompile: "secp256k1 verify",
+    },
+    PrecompilePatch {
+        name: "crypto-bigint",
+        git: "https://github.com/sp1-patches/RustCrypto-bigint",
+        branches: &[("0.5.5", "patch-v0.5.5")],
+        precompile: "bigint",
+    },
+    PrecompilePatch {
+        name: "curve25519-dalek",
+        git: "https://github.com/sp1-patches/curve25519-dalek",
+        branches: &[("4.1.3", "patch-curve25519-v4.1.3")],
+        precompile: "ed25519 verify",
+    },
+];
+
+/// A dependency of the program that is pulled from crates.io instead of its patched fork.
+pub(crate) struct UnpatchedCrate {
+    pub(crate) patch: &'static PrecompilePatch,
+    pub(crate) version: String,
+    /// The branch of the fork for this version, if there is one.
+    pub(crate) branch: Option<&'static str>,
+}
+
+impl UnpatchedCrate {
+    /// The key of the `[patch.crates-io]` entry for this crate, e.g. `sha2-v0-10-8`.
+    fn patch_key(&self) -> String {
+        format!("{}-v{}", self.patch.name, self.version.replace('.', "-"))
+    }
+
+    /// The `[patch.crates-io]` entry that replaces this crate with its fork.
+    pub(crate) fn patch_entry(&self) -> Option<String> {
+        self.branch.map(|branch| {
+            format!(
+                "{} = {{ git = \"{}\", package = \"{}\", branch = \"{}\" }}",
+                self.patch_key(),
+                self.patch.git,
+                self.patch.name,
+                branch
+            )
+        })
+    }
+
+    /// The `--config` arguments to pass to cargo to apply the patch without editing the manifest.
+    pub(crate) fn cargo_config_args(&self) -> Vec<String> {
+        let Some(branch) = self.branch else {
+            return vec![];
+        };
+        let key = format!("patch.crates-io.{}", self.patch_key());
+        [("git", self.patch.git), ("package", self.patch.name), ("branch", branch)]
+            .into_iter()
+            .flat_map(|(field, value)| {
+                ["--config".to_string(), format!("{}.{}=\"{}\"", key, field, value)]
+            })
+            .collect()
+    }
+
+    /// A message explaining the slowdown and how to avoid it.
+    pub(crate) fn warning(&self) -> String {
+        let suggestion = match self.patch_entry() {
+            Some(entry) => {
+                format!("add `{}` under `[patch.crates-io]`, or build with `--auto-patch`", entry)
+            }
+            None => format!(
+                "no patched fork exists for this version, consider upgrading to one of {}",
+                self.patch.branches.iter().map(|(v, _)| *v).collect::<Vec<_>>().join(", ")
+            ),
+        };
+        format!(
+            "{} v{} is not using the {} precompile and will be much slower in the zkVM: {}. \
+             Pass `--allow-unpatched {}` to silence this warning.",
+            self.patch.name, self.version, self.patch.precompile, suggestion, self.patch.name
+        )
+    }
+}
+
+/// Finds the dependencies of the program that have a precompile-accelerated fork, but are pulled
+/// from crates.io.
+///
+/// Crates listed in `allowed` are skipped.
+pub(crate) fn find_unpatched_crates(
+    metadata: &Metadata,
+    allowed: &[String],
+) -> Vec<UnpatchedCrate> {
+    let mut unpatched = metadata
+        .packages
+        .iter()
+        .filter(|package| package.source.as_ref().is_some_and(|source| source.is_crates_io()))
+        .filter(|package| !allowed.iter().any(|name| name == package.name.as_str()))
+        .filter_map(|package| {
+            let patch = PRECOMPILE_PATCHES.iter().find(|patch| patch.name == package.name)?;
+            let version = package.version.to_string();
+            let branch = patch.branches.iter().find(|(v, _)| *v == version).map(|(_, b)| *b);
+            Some(UnpatchedCrate { patch, version, branch })
+        })
+        .collect::<Vec<_>>();
+    unpatched.sort_by(|a, b| (a.patch.name, &a.version).cmp(&(b.patch.name, &b.version)));
+    unpatched
+}
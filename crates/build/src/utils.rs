@@ -37,6 +37,17 @@ pub(crate) fn copy_elf_to_output_dir(
         .join("release")
         .join(original_elf_file_name);
 
+    let result_elf_path = output_elf_path(args, program_metadata);
+    fs::create_dir_all(result_elf_path.parent().unwrap())?;
+
+    // Copy the ELF to the specified output directory.
+    fs::copy(original_elf_path, &result_elf_path)?;
+
+    Ok(result_elf_path)
+}
+
+/// The path the ELF is copied to once built.
+pub(crate) fn output_elf_path(args: &BuildArgs, program_metadata: &Metadata) -> Utf8PathBuf {
     // The order of precedence for the ELF name is:
     // 1. --elf_name flag
     // 2. --binary flag + -elf suffix (defaults to riscv32im-succinct-zkvm-elf)
@@ -51,13 +62,7 @@ pub(crate) fn copy_elf_to_output_dir(
     };
 
     let elf_dir = program_metadata.target_directory.parent().unwrap().join(&args.output_directory);
-    fs::create_dir_all(&elf_dir)?;
-    let result_elf_path = elf_dir.join(elf_name);
-
-    // Copy the ELF to the specified output directory.
-    fs::copy(original_elf_path, &result_elf_path)?;
-
-    Ok(result_elf_path)
+    elf_dir.join(elf_name)
 }
 
 pub(crate) fn current_datetime() -> String {
@@ -74,7 +74,7 @@ impl BuildToolchainCmd {
         // Build the toolchain (stage 1).
         Command::new("python3")
             .env("RUST_TARGET_PATH", &temp_dir)
-            .env("CARGO_TARGET_RISCV32IM_SUCCINCT_ZKVM_ELF_RUSTFLAGS", "-Cpasses=loweratomic")
+            .env("CARGO_TARGET_RISCV32IM_SUCCINCT_ZKVM_ELF_RUSTFLAGS", "-Ctarget-feature=+a")
             .args(["x.py", "build"])
             .current_dir(&rust_dir)
             .run()?;
@@ -82,7 +82,7 @@ impl BuildToolchainCmd {
         // Build the toolchain (stage 2).
         Command::new("python3")
             .env("RUST_TARGET_PATH", &temp_dir)
-            .env("CARGO_TARGET_RISCV32IM_SUCCINCT_ZKVM_ELF_RUSTFLAGS", "-Cpasses=loweratomic")
+            .env("CARGO_TARGET_RISCV32IM_SUCCINCT_ZKVM_ELF_RUSTFLAGS", "-Ctarget-feature=+a")
             .args(["x.py", "build", "--stage", "2"])
             .current_dir(&rust_dir)
             .run()?;
@@ -6,8 +6,15 @@ use sp1_core_machine::{
     io::SP1Stdin,
     utils::{setup_logger, setup_tracer},
 };
-use sp1_sdk::ProverClient;
-use std::{env, fs::File, io::Read, path::PathBuf, str::FromStr, time::Instant};
+use sp1_sdk::{ProofProvenance, ProverClient, SignedProof, SigningKey};
+use std::{
+    env,
+    fs::{self, File},
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Instant, SystemTime},
+};
 
 use crate::util::{elapsed, write_status};
 
@@ -58,6 +65,11 @@ pub struct ProveCmd {
     #[clap(long, action)]
     output: Option<PathBuf>,
 
+    /// Sign the output proof with the ed25519 secret key in this file, as 32 hex-encoded bytes.
+    /// The proof is then saved as a signed envelope with its provenance.
+    #[clap(long, action, requires = "output")]
+    signing_key: Option<PathBuf>,
+
     #[clap(long, action)]
     profile: bool,
 
@@ -70,6 +82,7 @@ pub struct ProveCmd {
 
 impl ProveCmd {
     pub fn run(&self) -> Result<()> {
+        let signing_key = self.signing_key.as_deref().map(read_signing_key).transpose()?;
         let elf_path = execute_build_program(&self.build_args, None)?;
 
         if !self.profile {
@@ -110,10 +123,17 @@ impl ProveCmd {
         let start_time = Instant::now();
         let client = ProverClient::new();
         let (pk, _) = client.setup(&elf);
+        let (started_at, proving_start) = (SystemTime::now(), Instant::now());
         let proof = client.prove(&pk, stdin).run().unwrap();
+        let provenance = ProofProvenance::new(started_at, proving_start.elapsed());
 
         if let Some(ref path) = self.output {
-            proof.save(path.to_str().unwrap()).expect("failed to save proof");
+            match signing_key {
+                Some(ref key) => SignedProof::sign(proof, provenance, key)
+                    .save(path)
+                    .expect("failed to save signed proof"),
+                None => proof.save(path.to_str().unwrap()).expect("failed to save proof"),
+            }
         }
 
         let elapsed = elapsed(start_time.elapsed());
@@ -123,3 +143,13 @@ impl ProveCmd {
         Ok(())
     }
 }
+
+/// Reads an ed25519 secret key, encoded as 32 hex bytes, from a file.
+fn read_signing_key(path: &Path) -> Result<SigningKey> {
+    let contents = fs::read_to_string(path)?;
+    let contents = contents.trim();
+    let bytes = hex::decode(contents.strip_prefix("0x").unwrap_or(contents))?;
+    let bytes: [u8; 32] =
+        bytes.try_into().map_err(|_| anyhow::anyhow!("signing key must be 32 bytes"))?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
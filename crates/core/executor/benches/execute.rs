@@ -0,0 +1,33 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use sp1_core_executor::{
+    programs::tests::{fibonacci_program, ssz_withdrawals_program},
+    Executor,
+};
+use sp1_stark::SP1CoreOpts;
+
+/// Compares the threaded-code interpreter of [`Executor::run_fast`] with the tracing interpreter
+/// of [`Executor::run`].
+fn execute(c: &mut Criterion) {
+    let programs =
+        [("fibonacci", fibonacci_program()), ("ssz_withdrawals", ssz_withdrawals_program())];
+
+    let mut group = c.benchmark_group("execute");
+    for (name, program) in programs {
+        group.bench_with_input(BenchmarkId::new("run", name), &program, |b, program| {
+            b.iter(|| {
+                let mut executor = Executor::new(program.clone(), SP1CoreOpts::default());
+                executor.run().unwrap();
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("run_fast", name), &program, |b, program| {
+            b.iter(|| {
+                let mut executor = Executor::new(program.clone(), SP1CoreOpts::default());
+                executor.run_fast().unwrap();
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, execute);
+criterion_main!(benches);
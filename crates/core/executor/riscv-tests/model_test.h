@@ -0,0 +1,38 @@
+// The RISCOF model environment of the SP1 zkVM.
+//
+// The zkVM has no privileged architecture, so the tests run without trap handlers and halt with
+// the HALT syscall: `t0` holds the syscall code, which is zero, and `a0` the exit code.
+
+#ifndef _SP1_MODEL_TEST_H
+#define _SP1_MODEL_TEST_H
+
+#define RVMODEL_BOOT
+
+#define RVMODEL_HALT                                                                               \
+  li t0, 0;                                                                                        \
+  li a0, 0;                                                                                        \
+  ecall;
+
+#define RVMODEL_DATA_BEGIN                                                                         \
+  .align 4;                                                                                        \
+  .global begin_signature;                                                                         \
+  begin_signature:
+
+#define RVMODEL_DATA_END                                                                           \
+  .align 4;                                                                                        \
+  .global end_signature;                                                                           \
+  end_signature:
+
+#define RVMODEL_IO_INIT
+#define RVMODEL_IO_WRITE_STR(_R, _STR)
+#define RVMODEL_IO_CHECK()
+#define RVMODEL_IO_ASSERT_GPR_EQ(_S, _R, _I)
+#define RVMODEL_IO_ASSERT_SFPR_EQ(_F, _R, _I)
+#define RVMODEL_IO_ASSERT_DFPR_EQ(_D, _R, _I)
+
+#define RVMODEL_SET_MSW_INT
+#define RVMODEL_CLEAR_MSW_INT
+#define RVMODEL_CLEAR_MTIMER_INT
+#define RVMODEL_CLEAR_MEXT_INT
+
+#endif
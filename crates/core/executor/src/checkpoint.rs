@@ -0,0 +1,197 @@
+//! The checkpoints passed from the execution of a program to the generation of its traces.
+//!
+//! The prover executes a program once without tracing, saving the state of the executor at the
+//! start of each batch of shards, then re-executes each batch from its checkpoint to generate its
+//! traces. Since re-execution is deterministic, a [`Checkpoint`] is enough to reproduce the records
+//! of its shards, so the execution can run on one machine and the tracing and proving of the
+//! checkpoints on others.
+//!
+//! A checkpoint is written as [`CHECKPOINT_MAGIC`], the [`CHECKPOINT_VERSION`] as a little-endian
+//! `u16`, and the bincode encoding of the [`Checkpoint`]. It carries its index and the digest of
+//! the program it was taken from, so it does not depend on where it is stored or on the process
+//! that wrote it. Paged inputs and files are not part of a checkpoint: they are restored from the
+//! stdin with [`Executor::write_paged_inputs`] and [`Executor::write_files`].
+
+use std::io::{Read, Write};
+
+use serde::{Deserialize, Serialize};
+use sp1_stark::SP1CoreOpts;
+use thiserror::Error;
+
+use crate::{ExecutionState, Executor, Program};
+
+/// The bytes starting every encoded checkpoint.
+pub const CHECKPOINT_MAGIC: [u8; 4] = *b"SP1C";
+
+/// The version of the checkpoint encoding, incremented whenever [`ExecutionState`] changes.
+pub const CHECKPOINT_VERSION: u16 = 3;
+
+/// Errors that reading or recovering a checkpoint can return.
+#[derive(Error, Debug)]
+pub enum CheckpointError {
+    /// The checkpoint could not be read or written.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The checkpoint could not be encoded or decoded.
+    #[error("encoding error: {0}")]
+    Encoding(#[from] bincode::Error),
+
+    /// The bytes do not start with [`CHECKPOINT_MAGIC`].
+    #[error("not a checkpoint")]
+    InvalidMagic,
+
+    /// The checkpoint was written with another version of the encoding.
+    #[error("unsupported checkpoint version {0}, expected {CHECKPOINT_VERSION}")]
+    UnsupportedVersion(u16),
+
+    /// The checkpoint was taken from the execution of another program.
+    #[error("the checkpoint was taken from another program")]
+    ProgramMismatch,
+}
+
+/// The state of the executor at the start of a batch of shards.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// The digest of the program, see [`Program::digest`].
+    pub program_digest: [u8; 32],
+    /// The index of the checkpoint in the execution.
+    pub index: usize,
+    /// Whether the program halts in the batch of shards of this checkpoint.
+    pub done: bool,
+    /// The state of the executor.
+    pub state: ExecutionState,
+}
+
+impl Checkpoint {
+    /// Create the `index`-th checkpoint of the execution of the program with the given digest.
+    #[must_use]
+    pub const fn new(
+        program_digest: [u8; 32],
+        index: usize,
+        state: ExecutionState,
+        done: bool,
+    ) -> Self {
+        Self { program_digest, index, done, state }
+    }
+
+    /// Write the checkpoint to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checkpoint cannot be encoded or written.
+    pub fn write_to(&self, mut writer: impl Write) -> Result<(), CheckpointError> {
+        writer.write_all(&CHECKPOINT_MAGIC)?;
+        writer.write_all(&CHECKPOINT_VERSION.to_le_bytes())?;
+        bincode::serialize_into(&mut writer, self)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Read a checkpoint written by [`Checkpoint::write_to`] from `reader`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checkpoint cannot be read or decoded, or if it was written with
+    /// another version of the encoding.
+    pub fn read_from(mut reader: impl Read) -> Result<Self, CheckpointError> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != CHECKPOINT_MAGIC {
+            return Err(CheckpointError::InvalidMagic);
+        }
+        let mut version = [0u8; 2];
+        reader.read_exact(&mut version)?;
+        let version = u16::from_le_bytes(version);
+        if version != CHECKPOINT_VERSION {
+            return Err(CheckpointError::UnsupportedVersion(version));
+        }
+        Ok(bincode::deserialize_from(reader)?)
+    }
+
+    /// Encode the checkpoint to bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checkpoint cannot be encoded.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, CheckpointError> {
+        let mut bytes = Vec::new();
+        self.write_to(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Decode a checkpoint from bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are not a checkpoint of this version.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CheckpointError> {
+        Self::read_from(bytes)
+    }
+
+    /// Recover an executor that re-executes the batch of shards of this checkpoint.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CheckpointError::ProgramMismatch`] if the checkpoint was not taken from the
+    /// execution of `program`.
+    pub fn recover<'a>(
+        self,
+        program: Program,
+        opts: SP1CoreOpts,
+    ) -> Result<Executor<'a>, CheckpointError> {
+        if program.digest() != self.program_digest {
+            return Err(CheckpointError::ProgramMismatch);
+        }
+        Ok(Executor::recover(program, self.state, opts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_stark::SP1CoreOpts;
+
+    use super::{Checkpoint, CheckpointError, CHECKPOINT_VERSION};
+    use crate::{programs::tests::fibonacci_program, Executor};
+
+    #[test]
+    fn test_checkpoint_roundtrip() {
+        let program = fibonacci_program();
+        let mut opts = SP1CoreOpts::default();
+        opts.shard_size = 1 << 10;
+        opts.shard_batch_size = 1;
+
+        let mut runtime = Executor::new(program.clone(), opts.clone());
+        let (state, done) = runtime.execute_state().unwrap();
+        let bytes = Checkpoint::new(program.digest(), 0, state, done).to_bytes().unwrap();
+
+        // A checkpoint re-executes to the same records as the original execution.
+        let checkpoint = Checkpoint::from_bytes(&bytes).unwrap();
+        assert_eq!(checkpoint.index, 0);
+        let mut recovered = checkpoint.recover(program.clone(), opts.clone()).unwrap();
+        let (records, _) = recovered.execute_record().unwrap();
+        let mut expected = Executor::new(program, opts);
+        let (expected_records, _) = expected.execute_record().unwrap();
+        assert_eq!(records.len(), expected_records.len());
+        assert_eq!(recovered.state.global_clk, expected.state.global_clk);
+
+        let mut bytes = bytes;
+        bytes[4..6].copy_from_slice(&(CHECKPOINT_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            Checkpoint::from_bytes(&bytes),
+            Err(CheckpointError::UnsupportedVersion(v)) if v == CHECKPOINT_VERSION + 1
+        ));
+        assert!(matches!(Checkpoint::from_bytes(b"ELF"), Err(CheckpointError::Io(_))));
+    }
+
+    #[test]
+    fn test_checkpoint_program_mismatch() {
+        let program = fibonacci_program();
+        let state = Executor::new(program.clone(), SP1CoreOpts::default()).state;
+        let checkpoint = Checkpoint::new([0; 32], 0, state, false);
+        assert!(matches!(
+            checkpoint.recover(program, SP1CoreOpts::default()),
+            Err(CheckpointError::ProgramMismatch)
+        ));
+    }
+}
@@ -0,0 +1,255 @@
+//! A harness running the RISC-V architectural tests on the [`Executor`].
+//!
+//! The tests of [riscv-arch-test](https://github.com/riscv-non-isa/riscv-arch-test), which RISCOF
+//! runs, write their results to a region of memory between the `begin_signature` and
+//! `end_signature` symbols. A test passes when this signature matches the one produced by the
+//! reference model, which RISCOF writes as one hex word per line in a `.reference_output` file.
+//!
+//! The tests must be built with the model environment in `riscv-tests/` at the root of this crate,
+//! which links them at the zkVM's text base and halts with the `HALT` syscall. [`run_suite`] then
+//! checks every `<name>.elf` of a directory against the `<name>.reference_output` next to it.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use elf::{endian::LittleEndian, ElfBytes};
+use sp1_stark::SP1CoreOpts;
+use thiserror::Error;
+
+use crate::{ExecutionError, Executor, Program, SP1Context};
+
+/// The symbol at the start of the signature region.
+pub const BEGIN_SIGNATURE: &str = "begin_signature";
+
+/// The symbol at the end of the signature region.
+pub const END_SIGNATURE: &str = "end_signature";
+
+/// The extension of the test ELFs in a suite directory.
+pub const ELF_EXTENSION: &str = "elf";
+
+/// The extension of the reference signatures in a suite directory.
+pub const REFERENCE_EXTENSION: &str = "reference_output";
+
+/// Errors that a conformance test can return.
+#[derive(Error, Debug)]
+pub enum ConformanceError {
+    /// The test ELF could not be parsed or loaded.
+    #[error("invalid test ELF: {0}")]
+    InvalidElf(String),
+
+    /// The test ELF does not define one of the signature symbols.
+    #[error("the test ELF does not define `{0}`")]
+    MissingSymbol(&'static str),
+
+    /// The signature region is empty or not word-aligned.
+    #[error("invalid signature region 0x{begin:08x}..0x{end:08x}")]
+    InvalidSignatureRegion {
+        /// The address of `begin_signature`.
+        begin: u32,
+        /// The address of `end_signature`.
+        end: u32,
+    },
+
+    /// The test failed to execute.
+    #[error("execution failed: {0}")]
+    Execution(#[from] ExecutionError),
+
+    /// The reference signature could not be read or parsed.
+    #[error("invalid reference signature: {0}")]
+    InvalidReference(String),
+
+    /// The signature does not have as many words as the reference.
+    #[error("the signature has {actual} words, but the reference has {expected}")]
+    LengthMismatch {
+        /// The number of words of the reference signature.
+        expected: usize,
+        /// The number of words of the signature.
+        actual: usize,
+    },
+
+    /// A word of the signature differs from the reference.
+    #[error(
+        "signature word {index} at 0x{addr:08x} is 0x{actual:08x}, but the reference is \
+         0x{expected:08x}"
+    )]
+    Mismatch {
+        /// The index of the word in the signature.
+        index: usize,
+        /// The address of the word.
+        addr: u32,
+        /// The word of the reference signature.
+        expected: u32,
+        /// The word of the signature.
+        actual: u32,
+    },
+}
+
+/// Returns the addresses of the `begin_signature` and `end_signature` symbols of a test ELF.
+pub fn signature_region(elf: &[u8]) -> Result<(u32, u32), ConformanceError> {
+    let invalid = |e: elf::ParseError| ConformanceError::InvalidElf(e.to_string());
+    let elf = ElfBytes::<LittleEndian>::minimal_parse(elf).map_err(invalid)?;
+    let (symbols, strings) = elf
+        .symbol_table()
+        .map_err(invalid)?
+        .ok_or(ConformanceError::MissingSymbol(BEGIN_SIGNATURE))?;
+
+    let mut begin = None;
+    let mut end = None;
+    for symbol in symbols.iter() {
+        match strings.get(symbol.st_name as usize).map_err(invalid)? {
+            BEGIN_SIGNATURE => begin = Some(symbol.st_value as u32),
+            END_SIGNATURE => end = Some(symbol.st_value as u32),
+            _ => {}
+        }
+    }
+    let begin = begin.ok_or(ConformanceError::MissingSymbol(BEGIN_SIGNATURE))?;
+    let end = end.ok_or(ConformanceError::MissingSymbol(END_SIGNATURE))?;
+    if begin >= end || begin % 4 != 0 || end % 4 != 0 {
+        return Err(ConformanceError::InvalidSignatureRegion { begin, end });
+    }
+    Ok((begin, end))
+}
+
+/// Runs a test ELF for at most `max_cycles` cycles and returns its signature.
+pub fn run_signature(elf: &[u8], max_cycles: u64) -> Result<Vec<u32>, ConformanceError> {
+    let (begin, end) = signature_region(elf)?;
+    let program = Program::from(elf).map_err(|e| ConformanceError::InvalidElf(e.to_string()))?;
+    let context = SP1Context::builder().max_cycles(max_cycles).build();
+    let mut runtime = Executor::with_context(program, SP1CoreOpts::default(), context);
+    runtime.run_fast()?;
+    Ok((begin..end).step_by(4).map(|addr| runtime.word(addr)).collect())
+}
+
+/// Parses a reference signature, written as one hex word per line.
+pub fn parse_reference(reference: &str) -> Result<Vec<u32>, ConformanceError> {
+    reference
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            u32::from_str_radix(line, 16)
+                .map_err(|_| ConformanceError::InvalidReference(format!("invalid word `{line}`")))
+        })
+        .collect()
+}
+
+/// Runs a test ELF and checks its signature against the reference signature.
+pub fn check_signature(
+    elf: &[u8],
+    reference: &str,
+    max_cycles: u64,
+) -> Result<(), ConformanceError> {
+    let expected = parse_reference(reference)?;
+    let actual = run_signature(elf, max_cycles)?;
+    compare_signatures(signature_region(elf)?.0, &expected, &actual)
+}
+
+/// Compares a signature starting at `begin` with the reference signature, word by word.
+fn compare_signatures(
+    begin: u32,
+    expected: &[u32],
+    actual: &[u32],
+) -> Result<(), ConformanceError> {
+    if let Some((index, (&expected, &actual))) =
+        expected.iter().zip(actual).enumerate().find(|(_, (e, a))| e != a)
+    {
+        let addr = begin + 4 * index as u32;
+        return Err(ConformanceError::Mismatch { index, addr, expected, actual });
+    }
+    if expected.len() != actual.len() {
+        return Err(ConformanceError::LengthMismatch {
+            expected: expected.len(),
+            actual: actual.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Runs every `<name>.elf` of `dir` and checks it against `<name>.reference_output`, returning
+/// the result of each test, sorted by path.
+pub fn run_suite(
+    dir: &Path,
+    max_cycles: u64,
+) -> std::io::Result<Vec<(PathBuf, Result<(), ConformanceError>)>> {
+    let mut elfs = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    elfs.retain(|path| path.extension().is_some_and(|extension| extension == ELF_EXTENSION));
+    elfs.sort();
+
+    Ok(elfs
+        .into_iter()
+        .map(|path| {
+            let result = run_test(&path, max_cycles);
+            (path, result)
+        })
+        .collect())
+}
+
+/// Runs the test ELF at `path` and checks it against the reference signature next to it.
+fn run_test(path: &Path, max_cycles: u64) -> Result<(), ConformanceError> {
+    let elf = fs::read(path)
+        .map_err(|e| ConformanceError::InvalidElf(format!("{}: {e}", path.display())))?;
+    let reference_path = path.with_extension(REFERENCE_EXTENSION);
+    let reference = fs::read_to_string(&reference_path).map_err(|e| {
+        ConformanceError::InvalidReference(format!("{}: {e}", reference_path.display()))
+    })?;
+    check_signature(&elf, &reference, max_cycles)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{compare_signatures, parse_reference, run_suite, ConformanceError};
+
+    /// The directory of the built suite, with a `<name>.elf` and a `<name>.reference_output` for
+    /// each test.
+    const SUITE_DIR_VAR: &str = "RISCV_TESTS_DIR";
+
+    #[test]
+    fn test_parse_reference() {
+        let words = parse_reference("deadbeef\n00000001\r\n\nFFFFFFFF\n").unwrap();
+        assert_eq!(words, [0xdeadbeef, 1, 0xffffffff]);
+        assert!(matches!(parse_reference("0x12"), Err(ConformanceError::InvalidReference(_))));
+    }
+
+    #[test]
+    fn test_compare_signatures() {
+        assert!(compare_signatures(0x1000, &[1, 2], &[1, 2]).is_ok());
+        assert!(matches!(
+            compare_signatures(0x1000, &[1, 2], &[1, 3]),
+            Err(ConformanceError::Mismatch { index: 1, addr: 0x1004, expected: 2, actual: 3 })
+        ));
+        assert!(matches!(
+            compare_signatures(0x1000, &[1, 2], &[1]),
+            Err(ConformanceError::LengthMismatch { expected: 2, actual: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_riscv_arch_tests() {
+        let Ok(dir) = std::env::var(SUITE_DIR_VAR) else {
+            eprintln!("skipping the architectural tests: {SUITE_DIR_VAR} is not set");
+            return;
+        };
+        let results = run_suite(Path::new(&dir), 1 << 24).unwrap();
+        assert!(!results.is_empty(), "no test ELFs in {dir}");
+
+        let failures = results
+            .iter()
+            .filter_map(|(path, result)| {
+                result.as_ref().err().map(|e| format!("{}: {e}", path.display()))
+            })
+            .collect::<Vec<_>>();
+        assert!(
+            failures.is_empty(),
+            "{} of {} tests failed:\n{}",
+            failures.len(),
+            results.len(),
+            failures.join("\n")
+        );
+    }
+}
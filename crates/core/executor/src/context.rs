@@ -6,6 +6,8 @@ use hashbrown::HashMap;
 use crate::{
     hook::{hookify, BoxedHook, HookEnv, HookRegistry},
     subproof::SubproofVerifier,
+    ExecutionObserver, InputSource, PrivacyMode, ProofMonitor, StackGuard, SyscallTracer,
+    UnknownEcallPolicy,
 };
 
 /// Context to run a program inside SP1.
@@ -21,6 +23,28 @@ pub struct SP1Context<'a> {
 
     /// The maximum number of cpu cycles to use for execution.
     pub max_cycles: Option<u64>,
+
+    /// The monitor used to cancel the proving job and report its progress.
+    pub monitor: Option<Arc<ProofMonitor>>,
+
+    /// The guard region below the guest stack, if stack overflows should be detected.
+    pub stack_guard: Option<StackGuard>,
+
+    /// The tracer to which the syscalls made by the guest are written, if any.
+    pub syscall_tracer: Option<SyscallTracer<'a>>,
+
+    /// The observer notified of the shards, syscalls and memory pages of the execution, if any.
+    pub observer: Option<Arc<dyn ExecutionObserver + 'a>>,
+
+    /// Whether private guest data is redacted from logs and error messages.
+    pub privacy_mode: PrivacyMode,
+
+    /// The source from which stdin elements are pulled when the guest reads past the written
+    /// inputs, if any.
+    pub input_source: Option<InputSource<'a>>,
+
+    /// What the executor does with ecalls whose syscall number it does not know.
+    pub unknown_ecall_policy: UnknownEcallPolicy,
 }
 
 /// A builder for [`SP1Context`].
@@ -30,6 +54,13 @@ pub struct SP1ContextBuilder<'a> {
     hook_registry_entries: Vec<(u32, BoxedHook<'a>)>,
     subproof_verifier: Option<Arc<dyn SubproofVerifier + 'a>>,
     max_cycles: Option<u64>,
+    monitor: Option<Arc<ProofMonitor>>,
+    stack_guard: Option<StackGuard>,
+    syscall_tracer: Option<SyscallTracer<'a>>,
+    observer: Option<Arc<dyn ExecutionObserver + 'a>>,
+    privacy_mode: PrivacyMode,
+    input_source: Option<InputSource<'a>>,
+    unknown_ecall_policy: UnknownEcallPolicy,
 }
 
 impl<'a> SP1Context<'a> {
@@ -68,7 +99,25 @@ impl<'a> SP1ContextBuilder<'a> {
             });
         let subproof_verifier = take(&mut self.subproof_verifier);
         let cycle_limit = take(&mut self.max_cycles);
-        SP1Context { hook_registry, subproof_verifier, max_cycles: cycle_limit }
+        let monitor = take(&mut self.monitor);
+        let stack_guard = take(&mut self.stack_guard);
+        let syscall_tracer = take(&mut self.syscall_tracer);
+        let observer = take(&mut self.observer);
+        let privacy_mode = take(&mut self.privacy_mode);
+        let input_source = take(&mut self.input_source);
+        let unknown_ecall_policy = take(&mut self.unknown_ecall_policy);
+        SP1Context {
+            hook_registry,
+            subproof_verifier,
+            max_cycles: cycle_limit,
+            monitor,
+            stack_guard,
+            syscall_tracer,
+            observer,
+            privacy_mode,
+            input_source,
+            unknown_ecall_policy,
+        }
     }
 
     /// Add a runtime [Hook](super::Hook) into the context.
@@ -110,21 +159,86 @@ impl<'a> SP1ContextBuilder<'a> {
         self.max_cycles = Some(max_cycles);
         self
     }
+
+    /// Set the monitor through which the proving job can be cancelled and its progress tracked.
+    pub fn monitor(&mut self, monitor: Arc<ProofMonitor>) -> &mut Self {
+        self.monitor = Some(monitor);
+        self
+    }
+
+    /// Fail the execution with a stack overflow when the guest writes into the given guard region.
+    pub fn stack_guard(&mut self, stack_guard: StackGuard) -> &mut Self {
+        self.stack_guard = Some(stack_guard);
+        self
+    }
+
+    /// Write the syscalls made by the guest to the given tracer. See [`SyscallTracer`].
+    pub fn syscall_tracer(&mut self, syscall_tracer: SyscallTracer<'a>) -> &mut Self {
+        self.syscall_tracer = Some(syscall_tracer);
+        self
+    }
+
+    /// Notify the given observer of the shards, syscalls and memory pages of the execution. See
+    /// [`ExecutionObserver`].
+    pub fn observer(&mut self, observer: Arc<dyn ExecutionObserver + 'a>) -> &mut Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Redact private guest data from logs and error messages. See [`PrivacyMode`].
+    pub fn privacy_mode(&mut self, privacy_mode: PrivacyMode) -> &mut Self {
+        self.privacy_mode = privacy_mode;
+        self
+    }
+
+    /// Pull stdin elements from the given source when the guest reads past the written inputs.
+    /// See [`InputSource`].
+    pub fn input_source(&mut self, input_source: InputSource<'a>) -> &mut Self {
+        self.input_source = Some(input_source);
+        self
+    }
+
+    /// Ignore ecalls with unknown syscall numbers, or forward them to a handler on the host,
+    /// instead of stopping the execution. See [`UnknownEcallPolicy`].
+    ///
+    /// Executions that make such ecalls cannot be proven.
+    #[cfg(feature = "unsound_experimental")]
+    pub fn unknown_ecall_policy(&mut self, policy: UnknownEcallPolicy) -> &mut Self {
+        self.unknown_ecall_policy = policy;
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
-    use crate::{subproof::DefaultSubproofVerifier, SP1Context};
+    use crate::{subproof::DefaultSubproofVerifier, PrivacyMode, SP1Context, UnknownEcallPolicy};
 
     #[test]
     fn defaults() {
-        let SP1Context { hook_registry, subproof_verifier, max_cycles: cycle_limit } =
-            SP1Context::builder().build();
+        let SP1Context {
+            hook_registry,
+            subproof_verifier,
+            max_cycles: cycle_limit,
+            monitor,
+            stack_guard,
+            syscall_tracer,
+            observer,
+            privacy_mode,
+            input_source,
+            unknown_ecall_policy,
+        } = SP1Context::builder().build();
         assert!(hook_registry.is_none());
         assert!(subproof_verifier.is_none());
         assert!(cycle_limit.is_none());
+        assert!(monitor.is_none());
+        assert!(stack_guard.is_none());
+        assert!(syscall_tracer.is_none());
+        assert!(observer.is_none());
+        assert_eq!(privacy_mode, PrivacyMode::Disabled);
+        assert!(input_source.is_none());
+        assert!(matches!(unknown_ecall_policy, UnknownEcallPolicy::Error));
     }
 
     #[test]
@@ -1,12 +1,142 @@
-use std::{cmp::min, collections::BTreeMap};
+use std::cmp::min;
 
 use elf::{
     abi::{EM_RISCV, ET_EXEC, PF_X, PT_LOAD},
     endian::LittleEndian,
-    file::Class,
     ElfBytes,
 };
 use sp1_primitives::consts::{MAXIMUM_MEMORY_SIZE, WORD_SIZE};
+use thiserror::Error;
+
+use crate::{MemoryImage, PT_SP1_ROM};
+
+/// The target triple of the programs the zkVM runs.
+const BUILD_TARGET: &str = "riscv32im-succinct-zkvm-elf";
+
+/// The extensions of the base instruction set that the zkVM implements. The `zmmul` extension is a
+/// subset of `m`, and `zaamo` and `zalrsc` split `a`.
+const SUPPORTED_EXTENSIONS: [&str; 5] = ["m", "a", "zmmul", "zaamo", "zalrsc"];
+
+/// The magic bytes at the start of every ELF file.
+const ELF_MAGIC: [u8; 4] = [0x7f, b'E', b'L', b'F'];
+
+/// The offsets of the class and the data encoding in the identification bytes of the header.
+const EI_CLASS: usize = 4;
+const EI_DATA: usize = 5;
+
+/// The values of the class and the data encoding of the header.
+const ELFCLASS32: u8 = 1;
+const ELFCLASS64: u8 = 2;
+const ELFDATA2LSB: u8 = 1;
+const ELFDATA2MSB: u8 = 2;
+
+/// The RISC-V flags of the header, from the RISC-V ELF psABI.
+const EF_RISCV_RVC: u32 = 0x0001;
+const EF_RISCV_FLOAT_ABI: u32 = 0x0006;
+const EF_RISCV_RVE: u32 = 0x0008;
+
+/// The section of the RISC-V attributes, and the tags of the attributes the loader reads.
+const RISCV_ATTRIBUTES_SECTION: &str = ".riscv.attributes";
+const TAG_FILE: u64 = 1;
+const TAG_RISCV_ARCH: u64 = 5;
+
+/// An ELF that was not built for the zkVM.
+///
+/// Each error names the mismatch with `riscv32im-succinct-zkvm-elf`, the target of the programs
+/// built with `cargo prove build`.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ElfError {
+    /// The file does not start with the ELF magic bytes.
+    #[error(
+        "not an ELF file; expected a program built for `{target}` with `cargo prove build`",
+        target = BUILD_TARGET
+    )]
+    NotElf,
+
+    /// The ELF is a 64-bit ELF.
+    #[error(
+        "the ELF is 64-bit, but the zkVM runs 32-bit programs; rebuild it for `{target}`",
+        target = BUILD_TARGET
+    )]
+    Elf64,
+
+    /// The ELF has an invalid class.
+    #[error("the ELF has an invalid class {0}; rebuild it for `{target}`", target = BUILD_TARGET)]
+    InvalidClass(u8),
+
+    /// The ELF is big-endian.
+    #[error(
+        "the ELF is big-endian, but the zkVM is little-endian; rebuild it for `{target}`",
+        target = BUILD_TARGET
+    )]
+    BigEndian,
+
+    /// The ELF has an invalid data encoding.
+    #[error(
+        "the ELF has an invalid data encoding {0}; rebuild it for `{target}`",
+        target = BUILD_TARGET
+    )]
+    InvalidEndianness(u8),
+
+    /// The ELF targets another machine than RISC-V.
+    #[error(
+        "the ELF targets machine {0:#x}, but the zkVM runs RISC-V programs; rebuild it for \
+         `{target}`",
+        target = BUILD_TARGET
+    )]
+    Machine(u16),
+
+    /// The ELF is not an executable, e.g. it is an object file or a shared library.
+    #[error(
+        "the ELF has type {0:#x}, but the zkVM runs executables; build a binary crate for \
+         `{target}`",
+        target = BUILD_TARGET
+    )]
+    NotExecutable(u16),
+
+    /// The ELF uses a hardware floating-point ABI.
+    #[error(
+        "the ELF uses the {0} float ABI, but the zkVM has no floating-point registers; rebuild it \
+         for `{target}`, which uses the soft-float ABI",
+        target = BUILD_TARGET
+    )]
+    FloatAbi(&'static str),
+
+    /// The ELF may contain compressed instructions.
+    #[error(
+        "the ELF uses compressed instructions (the `c` extension), which the zkVM does not \
+         implement; rebuild it for `{target}`",
+        target = BUILD_TARGET
+    )]
+    CompressedInstructions,
+
+    /// The ELF targets the embedded base instruction set, with 16 registers.
+    #[error(
+        "the ELF targets RV32E, but the zkVM implements RV32I; rebuild it for `{target}`",
+        target = BUILD_TARGET
+    )]
+    EmbeddedBase,
+
+    /// The architecture in the RISC-V attributes of the ELF could not be parsed.
+    #[error(
+        "the ELF has an invalid architecture `{0}`; rebuild it for `{target}`",
+        target = BUILD_TARGET
+    )]
+    InvalidArch(String),
+
+    /// The ELF requires extensions that the zkVM does not implement.
+    #[error(
+        "the ELF targets `{arch}`, which requires the extensions {unsupported:?} that the zkVM \
+         does not implement; rebuild it for `{target}`, which targets `rv32im`",
+        target = BUILD_TARGET
+    )]
+    UnsupportedExtensions {
+        /// The architecture in the RISC-V attributes of the ELF.
+        arch: String,
+        /// The extensions of the architecture the zkVM does not implement.
+        unsupported: Vec<String>,
+    },
+}
 
 /// RISC-V 32IM ELF (Executable and Linkable Format) File.
 ///
@@ -15,6 +145,7 @@ use sp1_primitives::consts::{MAXIMUM_MEMORY_SIZE, WORD_SIZE};
 ///
 /// - Base Integer Instruction Set (I)
 /// - Integer Multiplication and Division (M)
+/// - Atomic Instructions (A)
 ///
 /// This format is commonly used in embedded systems and is supported by many compilers.
 #[derive(Debug, Clone)]
@@ -26,7 +157,9 @@ pub(crate) struct Elf {
     /// The base address of the program.
     pub(crate) pc_base: u32,
     /// The initial memory image, useful for global constants.
-    pub(crate) memory_image: BTreeMap<u32, u32>,
+    pub(crate) memory_image: MemoryImage,
+    /// The contents of the ROM segment, if any.
+    pub(crate) rom: Vec<u8>,
 }
 
 impl Elf {
@@ -36,9 +169,10 @@ impl Elf {
         instructions: Vec<u32>,
         pc_start: u32,
         pc_base: u32,
-        memory_image: BTreeMap<u32, u32>,
+        memory_image: MemoryImage,
+        rom: Vec<u8>,
     ) -> Self {
-        Self { instructions, pc_start, pc_base, memory_image }
+        Self { instructions, pc_start, pc_base, memory_image, rom }
     }
 
     /// Parse the ELF file into a vector of 32-bit encoded instructions and the first memory
@@ -50,18 +184,18 @@ impl Elf {
     ///
     /// Reference: [Executable and Linkable Format](https://en.wikipedia.org/wiki/Executable_and_Linkable_Format)
     pub(crate) fn decode(input: &[u8]) -> eyre::Result<Self> {
-        let mut image: BTreeMap<u32, u32> = BTreeMap::new();
+        let mut image = MemoryImage::new();
+
+        // Check the class and the endianness before parsing the rest of the header with them.
+        validate_ident(input)?;
 
-        // Parse the ELF file assuming that it is little-endian..
+        // Parse the ELF file, which is little-endian.
         let elf = ElfBytes::<LittleEndian>::minimal_parse(input)?;
 
-        // Some sanity checks to make sure that the ELF file is valid.
-        if elf.ehdr.class != Class::ELF32 {
-            eyre::bail!("must be a 32-bit elf");
-        } else if elf.ehdr.e_machine != EM_RISCV {
-            eyre::bail!("must be a riscv machine");
-        } else if elf.ehdr.e_type != ET_EXEC {
-            eyre::bail!("must be executable");
+        // Make sure that the ELF was built for the zkVM.
+        validate_header(elf.ehdr.e_machine, elf.ehdr.e_type, elf.ehdr.e_flags)?;
+        if let Some(arch) = riscv_arch(&elf)? {
+            validate_arch(&arch)?;
         }
 
         // Get the entrypoint of the ELF file as an u32.
@@ -110,21 +244,23 @@ impl Elf {
             // Get the offset to the segment.
             let offset: u32 = segment.p_offset.try_into()?;
 
-            // Read the segment and decode each word as an instruction.
-            for i in (0..mem_size).step_by(WORD_SIZE) {
-                let addr = vaddr.checked_add(i).ok_or_else(|| eyre::eyre!("vaddr overflow"))?;
-                if addr == MAXIMUM_MEMORY_SIZE {
+            // Make sure the segment fits below the maximum address. The last word of the segment
+            // starts at `vaddr + mem_size - 1` rounded down to a word.
+            if mem_size > 0 {
+                let last = vaddr
+                    .checked_add((mem_size - 1) / WORD_SIZE as u32 * WORD_SIZE as u32)
+                    .ok_or_else(|| eyre::eyre!("vaddr overflow"))?;
+                if vaddr <= MAXIMUM_MEMORY_SIZE && MAXIMUM_MEMORY_SIZE <= last {
                     eyre::bail!(
-                        "address [0x{addr:08x}] exceeds maximum address for guest programs [0x{MAXIMUM_MEMORY_SIZE:08x}]"
+                        "address [0x{MAXIMUM_MEMORY_SIZE:08x}] exceeds maximum address for guest programs [0x{MAXIMUM_MEMORY_SIZE:08x}]"
                     );
                 }
+            }
 
-                // If we are reading past the end of the file, then break.
-                if i >= file_size {
-                    image.insert(addr, 0);
-                    continue;
-                }
-
+            // Read the segment and decode each word as an instruction. The words past the end of
+            // the file are zero, and are added to the image as a run of zeros.
+            let mut words = Vec::new();
+            for i in (0..min(file_size, mem_size)).step_by(WORD_SIZE) {
                 // Get the word as an u32 but make sure we don't read past the end of the file.
                 let mut word = 0;
                 let len = min(file_size - i, WORD_SIZE as u32);
@@ -135,13 +271,295 @@ impl Elf {
                         .ok_or_else(|| eyre::eyre!("failed to read segment offset"))?;
                     word |= u32::from(*byte) << (j * 8);
                 }
-                image.insert(addr, word);
+                words.push(word);
                 if (segment.p_flags & PF_X) != 0 {
                     instructions.push(word);
                 }
             }
+            let zeros = mem_size.div_ceil(WORD_SIZE as u32) - words.len() as u32;
+            image.insert_segment(vaddr, words, zeros)?;
         }
 
-        Ok(Elf::new(instructions, entry, base_address, image))
+        // Read the contents of the ROM, which are not loaded into memory.
+        let rom = match segments.iter().find(|x| x.p_type == PT_SP1_ROM) {
+            Some(segment) => {
+                let offset = usize::try_from(segment.p_offset)?;
+                let size = usize::try_from(segment.p_filesz)?;
+                offset
+                    .checked_add(size)
+                    .and_then(|end| input.get(offset..end))
+                    .ok_or_else(|| eyre::eyre!("failed to read rom segment"))?
+                    .to_vec()
+            }
+            None => Vec::new(),
+        };
+
+        Ok(Elf::new(instructions, entry, base_address, image, rom))
+    }
+}
+
+/// Checks the identification bytes of the header: the magic bytes, the class and the endianness.
+fn validate_ident(input: &[u8]) -> Result<(), ElfError> {
+    if input.len() < 16 || input[..4] != ELF_MAGIC {
+        return Err(ElfError::NotElf);
+    }
+    match input[EI_CLASS] {
+        ELFCLASS32 => {}
+        ELFCLASS64 => return Err(ElfError::Elf64),
+        class => return Err(ElfError::InvalidClass(class)),
+    }
+    match input[EI_DATA] {
+        ELFDATA2LSB => Ok(()),
+        ELFDATA2MSB => Err(ElfError::BigEndian),
+        data => Err(ElfError::InvalidEndianness(data)),
+    }
+}
+
+/// Checks the machine, the type and the RISC-V flags of the header.
+fn validate_header(machine: u16, ty: u16, flags: u32) -> Result<(), ElfError> {
+    if machine != EM_RISCV {
+        return Err(ElfError::Machine(machine));
+    }
+    if ty != ET_EXEC {
+        return Err(ElfError::NotExecutable(ty));
+    }
+    match flags & EF_RISCV_FLOAT_ABI {
+        0 => {}
+        0x2 => return Err(ElfError::FloatAbi("single")),
+        0x4 => return Err(ElfError::FloatAbi("double")),
+        _ => return Err(ElfError::FloatAbi("quad")),
+    }
+    if flags & EF_RISCV_RVC != 0 {
+        return Err(ElfError::CompressedInstructions);
+    }
+    if flags & EF_RISCV_RVE != 0 {
+        return Err(ElfError::EmbeddedBase);
+    }
+    Ok(())
+}
+
+/// Reads the architecture from the RISC-V attributes of the ELF, e.g. `rv32i2p1_m2p0`.
+///
+/// Returns `None` if the ELF has no attributes, as with older toolchains, or if they do not name
+/// the architecture.
+fn riscv_arch(elf: &ElfBytes<'_, LittleEndian>) -> eyre::Result<Option<String>> {
+    let Some(header) = elf.section_header_by_name(RISCV_ATTRIBUTES_SECTION)? else {
+        return Ok(None);
+    };
+    let (data, _) = elf.section_data(&header)?;
+    Ok(parse_riscv_attributes(data))
+}
+
+/// Parses the architecture from the contents of a `.riscv.attributes` section.
+///
+/// The section is a version byte `A`, followed by subsections made of a length, a vendor name and
+/// tagged sub-subsections. The file-wide attributes of the `riscv` vendor are tagged `Tag_File`,
+/// and are pairs of a tag and a value: a string for odd tags, and a ULEB128 number for even ones.
+fn parse_riscv_attributes(data: &[u8]) -> Option<String> {
+    let (&version, mut data) = data.split_first()?;
+    if version != b'A' {
+        return None;
+    }
+    while data.len() >= 4 {
+        let len = u32::from_le_bytes(data[..4].try_into().unwrap()) as usize;
+        if len < 4 || len > data.len() {
+            return None;
+        }
+        let (subsection, rest) = data.split_at(len);
+        data = rest;
+        let (vendor, mut subsection) = read_string(&subsection[4..])?;
+        if vendor != "riscv" {
+            continue;
+        }
+        while !subsection.is_empty() {
+            let (tag, rest) = read_uleb128(subsection)?;
+            let len = u32::from_le_bytes(rest.get(..4)?.try_into().unwrap()) as usize;
+            // The length counts the tag and itself.
+            let header_len = subsection.len() - rest.len() + 4;
+            if len < header_len || len > subsection.len() {
+                return None;
+            }
+            let mut attributes = &subsection[header_len..len];
+            subsection = &subsection[len..];
+            if tag != TAG_FILE {
+                continue;
+            }
+            while !attributes.is_empty() {
+                let (tag, rest) = read_uleb128(attributes)?;
+                if tag % 2 == 1 {
+                    let (value, rest) = read_string(rest)?;
+                    if tag == TAG_RISCV_ARCH {
+                        return Some(value.to_string());
+                    }
+                    attributes = rest;
+                } else {
+                    attributes = read_uleb128(rest)?.1;
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Reads a null-terminated string.
+fn read_string(data: &[u8]) -> Option<(&str, &[u8])> {
+    let end = data.iter().position(|byte| *byte == 0)?;
+    Some((std::str::from_utf8(&data[..end]).ok()?, &data[end + 1..]))
+}
+
+/// Reads a ULEB128-encoded number.
+fn read_uleb128(data: &[u8]) -> Option<(u64, &[u8])> {
+    let mut value = 0u64;
+    for (i, byte) in data.iter().enumerate().take(10) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, &data[i + 1..]));
+        }
+    }
+    None
+}
+
+/// Checks that the architecture only requires the extensions the zkVM implements.
+///
+/// The architecture is a base, such as `rv32i2p1`, followed by extensions separated by
+/// underscores, each with an optional version such as `m2p0` or `zmmul1p0`.
+fn validate_arch(arch: &str) -> Result<(), ElfError> {
+    let invalid = || ElfError::InvalidArch(arch.to_string());
+    let mut components = arch.split('_');
+    let base = components.next().and_then(|base| base.strip_prefix("rv")).ok_or_else(invalid)?;
+    let xlen_len = base.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+    if &base[..xlen_len] == "64" || &base[..xlen_len] == "128" {
+        return Err(ElfError::Elf64);
+    } else if &base[..xlen_len] != "32" {
+        return Err(invalid());
+    }
+
+    // The base and the single-letter extensions that follow it without an underscore.
+    let mut extensions = single_letter_extensions(&base[xlen_len..]).into_iter();
+    let mut extensions = match extensions.next().as_deref() {
+        Some("i") => extensions.collect::<Vec<_>>(),
+        Some("e") => return Err(ElfError::EmbeddedBase),
+        // The `g` base is short for `imafd_zicsr_zifencei`.
+        Some("g") => ["m", "a", "f", "d", "zicsr", "zifencei"]
+            .into_iter()
+            .map(String::from)
+            .chain(extensions)
+            .collect(),
+        _ => return Err(invalid()),
+    };
+    for component in components {
+        if component.starts_with(['z', 's', 'x']) {
+            extensions.push(strip_version(component).to_string());
+        } else {
+            extensions.extend(single_letter_extensions(component));
+        }
+    }
+
+    let unsupported = extensions
+        .into_iter()
+        .filter(|extension| !SUPPORTED_EXTENSIONS.contains(&extension.as_str()))
+        .collect::<Vec<_>>();
+    if unsupported.is_empty() {
+        Ok(())
+    } else {
+        Err(ElfError::UnsupportedExtensions { arch: arch.to_string(), unsupported })
+    }
+}
+
+/// Strips the version from a multi-letter extension, such as `zmmul1p0`.
+fn strip_version(extension: &str) -> &str {
+    let name = extension.trim_end_matches(|c: char| c.is_ascii_digit());
+    match name.strip_suffix('p') {
+        Some(major) if major.ends_with(|c: char| c.is_ascii_digit()) => {
+            major.trim_end_matches(|c: char| c.is_ascii_digit())
+        }
+        _ => name,
+    }
+}
+
+/// Splits single-letter extensions with versions, such as `i2p1m2p0`, into their letters.
+fn single_letter_extensions(component: &str) -> Vec<String> {
+    let mut extensions = Vec::new();
+    let mut previous = None;
+    for c in component.chars() {
+        // A `p` after a digit separates the major and minor versions.
+        let is_version =
+            c.is_ascii_digit() || (c == 'p' && previous.is_some_and(|p: char| p.is_ascii_digit()));
+        if !is_version {
+            extensions.push(c.to_string());
+        }
+        previous = Some(c);
+    }
+    extensions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::programs::tests::FIBONACCI_ELF;
+
+    /// The offsets of the machine and the flags in the header of a 32-bit ELF.
+    const E_MACHINE: usize = 18;
+    const E_FLAGS: usize = 36;
+
+    fn decode_err(elf: &[u8]) -> ElfError {
+        Elf::decode(elf).unwrap_err().downcast::<ElfError>().unwrap()
+    }
+
+    #[test]
+    fn test_validate_header() {
+        assert!(Elf::decode(FIBONACCI_ELF).is_ok());
+        assert_eq!(decode_err(b"#!/bin/sh"), ElfError::NotElf);
+
+        let mut elf = FIBONACCI_ELF.to_vec();
+        elf[EI_CLASS] = ELFCLASS64;
+        assert_eq!(decode_err(&elf), ElfError::Elf64);
+
+        let mut elf = FIBONACCI_ELF.to_vec();
+        elf[EI_DATA] = ELFDATA2MSB;
+        assert_eq!(decode_err(&elf), ElfError::BigEndian);
+
+        // x86-64.
+        let mut elf = FIBONACCI_ELF.to_vec();
+        elf[E_MACHINE..E_MACHINE + 2].copy_from_slice(&0x3eu16.to_le_bytes());
+        assert_eq!(decode_err(&elf), ElfError::Machine(0x3e));
+
+        let mut elf = FIBONACCI_ELF.to_vec();
+        elf[E_FLAGS..E_FLAGS + 4].copy_from_slice(&0x4u32.to_le_bytes());
+        assert_eq!(decode_err(&elf), ElfError::FloatAbi("double"));
+
+        let mut elf = FIBONACCI_ELF.to_vec();
+        elf[E_FLAGS..E_FLAGS + 4].copy_from_slice(&EF_RISCV_RVC.to_le_bytes());
+        assert_eq!(decode_err(&elf), ElfError::CompressedInstructions);
+    }
+
+    #[test]
+    fn test_validate_arch() {
+        assert_eq!(validate_arch("rv32i2p1_m2p0"), Ok(()));
+        assert_eq!(validate_arch("rv32i2p1_m2p0_zmmul1p0"), Ok(()));
+        assert_eq!(validate_arch("rv32im"), Ok(()));
+        assert_eq!(validate_arch("rv32i2p1_m2p0_a2p1"), Ok(()));
+        assert_eq!(validate_arch("rv32ima_zaamo1p0_zalrsc1p0"), Ok(()));
+        assert_eq!(validate_arch("rv64i2p1_m2p0"), Err(ElfError::Elf64));
+        assert_eq!(validate_arch("rv32e2p0"), Err(ElfError::EmbeddedBase));
+        assert_eq!(
+            validate_arch("rv32i2p1_m2p0_a2p1_c2p0_zicsr2p0"),
+            Err(ElfError::UnsupportedExtensions {
+                arch: "rv32i2p1_m2p0_a2p1_c2p0_zicsr2p0".to_string(),
+                unsupported: vec!["c".to_string(), "zicsr".to_string()],
+            })
+        );
+        assert_eq!(validate_arch("x86_64"), Err(ElfError::InvalidArch("x86_64".to_string())));
+    }
+
+    #[test]
+    fn test_parse_riscv_attributes() {
+        let elf = ElfBytes::<LittleEndian>::minimal_parse(FIBONACCI_ELF).unwrap();
+        assert_eq!(riscv_arch(&elf).unwrap(), Some("rv32i2p1_m2p0".to_string()));
+
+        // A `riscv` subsection with the stack alignment and the architecture.
+        let data = b"A\x20\0\0\0riscv\0\x01\x16\0\0\0\x04\x10\x05rv32i2p1_m2p0\0";
+        assert_eq!(parse_riscv_attributes(data), Some("rv32i2p1_m2p0".to_string()));
+        assert_eq!(parse_riscv_attributes(b"A"), None);
     }
 }
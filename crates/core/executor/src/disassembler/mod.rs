@@ -3,5 +3,6 @@
 mod elf;
 mod rrs;
 
+pub use elf::ElfError;
 pub(crate) use elf::*;
 pub(crate) use rrs::*;
@@ -369,7 +369,8 @@ impl InstructionProcessor for InstructionTranspiler {
     }
 
     fn process_fence(&mut self, _: IType) -> Self::InstructionResult {
-        Instruction::unimp()
+        // With a single hart, all memory accesses are already ordered.
+        Instruction::new(Opcode::ADD, 0, 0, 0, true, true)
     }
 
     fn process_mret(&mut self) -> Self::InstructionResult {
@@ -381,6 +382,40 @@ impl InstructionProcessor for InstructionTranspiler {
     }
 }
 
+/// The major opcode of the RV32A instructions.
+const OPCODE_AMO: u32 = 0b010_1111;
+
+/// The `funct3` of the word-sized RV32A instructions.
+const FUNCT3_AMO_W: u32 = 0b010;
+
+/// Transpile an RV32A instruction, which `rrs_lib` does not decode.
+///
+/// The zkVM has a single hart, so the `aq` and `rl` ordering bits are ignored.
+fn transpile_atomic(instruction_u32: u32) -> Option<Instruction> {
+    if instruction_u32 & 0x7f != OPCODE_AMO {
+        return None;
+    }
+    let dec_insn = RType::new(instruction_u32);
+    if dec_insn.funct3 != FUNCT3_AMO_W {
+        return None;
+    }
+    let opcode = match dec_insn.funct7 >> 2 {
+        0b00010 => Opcode::LR,
+        0b00011 => Opcode::SC,
+        0b00001 => Opcode::AMOSWAP,
+        0b00000 => Opcode::AMOADD,
+        0b00100 => Opcode::AMOXOR,
+        0b01100 => Opcode::AMOAND,
+        0b01000 => Opcode::AMOOR,
+        0b10000 => Opcode::AMOMIN,
+        0b10100 => Opcode::AMOMAX,
+        0b11000 => Opcode::AMOMINU,
+        0b11100 => Opcode::AMOMAXU,
+        _ => return None,
+    };
+    Some(Instruction::from_r_type(opcode, &dec_insn))
+}
+
 /// Transpile the [`Instruction`]s from the 32-bit encoded instructions.
 ///
 /// # Panics
@@ -391,7 +426,9 @@ pub(crate) fn transpile(instructions_u32: &[u32]) -> Vec<Instruction> {
     let mut instructions = Vec::new();
     let mut transpiler = InstructionTranspiler;
     for instruction_u32 in instructions_u32 {
-        let instruction = process_instruction(&mut transpiler, *instruction_u32).unwrap();
+        let instruction = transpile_atomic(*instruction_u32)
+            .or_else(|| process_instruction(&mut transpiler, *instruction_u32))
+            .unwrap();
         instructions.push(instruction);
     }
     instructions
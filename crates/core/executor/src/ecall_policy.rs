@@ -0,0 +1,41 @@
+//! What the executor does with ecalls whose syscall number it does not know.
+//!
+//! By default, such an ecall stops the execution with [`ExecutionError::UnsupportedSyscall`]. With
+//! the `unsound_experimental` feature, it can instead be ignored or forwarded to a handler on the
+//! host, so that a guest can prototype a new syscall before a chip exists for it. Executions that
+//! make such ecalls cannot be proven.
+//!
+//! [`ExecutionError::UnsupportedSyscall`]: crate::ExecutionError::UnsupportedSyscall
+
+#[cfg(feature = "unsound_experimental")]
+use std::sync::Arc;
+
+#[cfg(feature = "unsound_experimental")]
+use crate::syscalls::SyscallContext;
+
+/// A host handler for ecalls with unknown syscall numbers.
+///
+/// The handler is called with the syscall number and the values of registers `a0` and `a1`, and
+/// may read and write the guest's memory through the context. Like [`Syscall::execute`], it
+/// returns the value to write to `t0`, which keeps the syscall number if it returns `None`.
+///
+/// [`Syscall::execute`]: crate::syscalls::Syscall::execute
+#[cfg(feature = "unsound_experimental")]
+pub type EcallHandler =
+    Arc<dyn Fn(&mut SyscallContext, u32, u32, u32) -> Option<u32> + Send + Sync>;
+
+/// The policy for ecalls with unknown syscall numbers.
+#[derive(Clone, Default)]
+pub enum UnknownEcallPolicy {
+    /// Stop the execution with an error.
+    #[default]
+    Error,
+
+    /// Skip the ecall, leaving the syscall number in `t0`.
+    #[cfg(feature = "unsound_experimental")]
+    Ignore,
+
+    /// Forward the ecall to a handler on the host.
+    #[cfg(feature = "unsound_experimental")]
+    Forward(EcallHandler),
+}
@@ -10,8 +10,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::{ByteOpcode, Opcode};
 
-/// The number of different byte operations.
-pub const NUM_BYTE_OPS: usize = 9;
+/// The number of different byte operations of the byte table.
+pub const NUM_BYTE_OPS: usize = 8;
+
+/// The maximum number of bits of a value checked with [`ByteOpcode::Range`].
+pub const MAX_RANGE_CHECK_BITS: usize = 16;
 
 /// Byte Lookup Event.
 ///
@@ -67,19 +70,25 @@ pub trait ByteRecord {
         });
     }
 
-    /// Adds a `ByteLookupEvent` to verify `a` is indeed u16.
-    fn add_u16_range_check(&mut self, shard: u32, channel: u8, a: u16) {
+    /// Adds a `ByteLookupEvent` to verify `a` has at most `bits` bits.
+    fn add_range_check(&mut self, shard: u32, channel: u8, a: u16, bits: u8) {
+        debug_assert!(bits as usize <= MAX_RANGE_CHECK_BITS && u32::from(a) < 1 << bits);
         self.add_byte_lookup_event(ByteLookupEvent {
             shard,
             channel,
-            opcode: ByteOpcode::U16Range,
+            opcode: ByteOpcode::Range,
             a1: a,
             a2: 0,
-            b: 0,
+            b: bits,
             c: 0,
         });
     }
 
+    /// Adds a `ByteLookupEvent` to verify `a` is indeed u16.
+    fn add_u16_range_check(&mut self, shard: u32, channel: u8, a: u16) {
+        self.add_range_check(shard, channel, a, 16);
+    }
+
     /// Adds `ByteLookupEvent`s to verify that all the bytes in the input slice are indeed bytes.
     fn add_u8_range_checks(&mut self, shard: u32, channel: u8, bytes: &[u8]) {
         let mut index = 0;
@@ -230,7 +239,7 @@ impl From<Opcode> for ByteOpcode {
 }
 
 impl ByteOpcode {
-    /// Get all the byte opcodes.
+    /// Get all the opcodes of the byte table, i.e. all of them except [`ByteOpcode::Range`].
     #[must_use]
     pub fn all() -> Vec<Self> {
         let opcodes = vec![
@@ -242,7 +251,6 @@ impl ByteOpcode {
             ByteOpcode::ShrCarry,
             ByteOpcode::LTU,
             ByteOpcode::MSB,
-            ByteOpcode::U16Range,
         ];
         assert_eq!(opcodes.len(), NUM_BYTE_OPS);
         opcodes
@@ -38,6 +38,8 @@ pub struct CpuEvent {
     pub memory: Option<u32>,
     /// The memory record.
     pub memory_record: Option<MemoryRecordEnum>,
+    /// The reservation record of the load-reserved and store-conditional instructions.
+    pub reservation_record: Option<MemoryRecordEnum>,
     /// The exit code.
     pub exit_code: u32,
     /// The ALU lookup id.
@@ -48,6 +50,8 @@ pub struct CpuEvent {
     pub memory_add_lookup_id: LookupId,
     /// The memory sub lookup id.
     pub memory_sub_lookup_id: LookupId,
+    /// The memory atomic operation lookup id.
+    pub memory_atomic_lookup_id: LookupId,
     /// The branch gt lookup id.
     pub branch_gt_lookup_id: LookupId,
     /// The branch lt lookup id.
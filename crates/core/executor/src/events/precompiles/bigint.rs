@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::{
+    memory::{MemoryReadRecord, MemoryWriteRecord},
+    FieldOperation, LookupId,
+};
+
+/// The number of words in a bigint limb. Bigints are processed in 256-bit limbs.
+pub const BIGINT_LIMB_WORDS: usize = 8;
+
+/// The maximum number of limbs of a bigint operand, i.e. operands of up to 4096 bits.
+pub const BIGINT_MAX_LIMBS: usize = 16;
+
+/// Bigint Event.
+///
+/// This event is emitted when a bigint add, sub or mul operation is performed.
+///
+/// The operands are read from `operands_ptr`, which points to the number of limbs `n` followed by
+/// `x` and `y`, each made of `n` limbs. The result is written to `z_ptr`, and is made of `n` limbs
+/// for add and sub (computed modulo `2^(256 * n)`) and `2 * n` limbs for mul.
+///
+/// The operation is carried out one limb at a time, one row of the bigint chip per limb for add
+/// and sub and one row per pair of limbs for mul. The memory records are ordered by row.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BigIntEvent {
+    /// The lookup identifer.
+    pub lookup_id: LookupId,
+    /// The shard number.
+    pub shard: u32,
+    /// The channel number.
+    pub channel: u8,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The operation, one of add, sub or mul.
+    pub op: FieldOperation,
+    /// The pointer to the result.
+    pub z_ptr: u32,
+    /// The pointer to the number of limbs and the operands.
+    pub operands_ptr: u32,
+    /// The number of limbs of each operand.
+    pub num_limbs: u32,
+    /// The x value as a list of words.
+    pub x: Vec<u32>,
+    /// The y value as a list of words.
+    pub y: Vec<u32>,
+    /// The memory record for the number of limbs.
+    pub num_limbs_memory_record: MemoryReadRecord,
+    /// The memory records for the x limb of each row.
+    pub x_memory_records: Vec<MemoryReadRecord>,
+    /// The memory records for the y limb, for each row for add and sub and for each pass over x
+    /// for mul.
+    pub y_memory_records: Vec<MemoryReadRecord>,
+    /// The memory records for the result limb of each row.
+    pub z_memory_records: Vec<MemoryWriteRecord>,
+    /// The memory records for the high limb written at the end of each pass over x for mul.
+    pub hi_memory_records: Vec<MemoryWriteRecord>,
+}
+
+impl BigIntEvent {
+    /// The number of rows of the bigint chip used by this event.
+    #[must_use]
+    pub fn num_rows(&self) -> usize {
+        let num_limbs = self.num_limbs as usize;
+        match self.op {
+            FieldOperation::Mul => num_limbs * num_limbs,
+            _ => num_limbs,
+        }
+    }
+}
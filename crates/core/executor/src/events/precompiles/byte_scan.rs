@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::{
+    memory::{MemoryReadRecord, MemoryWriteRecord},
+    LookupId,
+};
+
+/// The maximum number of words processed by one byte scan operation.
+pub const BYTE_SCAN_MAX_WORDS: usize = 32;
+
+/// The number of words of the header of a byte scan operation: the number of words, the source
+/// pointer and the delimiter.
+pub const BYTE_SCAN_HEADER_WORDS: usize = 3;
+
+/// A byte processing operation on a word-aligned buffer.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum ByteScanOperation {
+    /// Writes the number of bytes of the source before the first delimiter, or the length of the
+    /// source if it does not contain the delimiter.
+    Find,
+    /// Copies the source words to the destination.
+    Copy,
+    /// Decodes the source words as ASCII hex digits, two digits per destination byte with the high
+    /// nibble first.
+    HexDecode,
+}
+
+impl ByteScanOperation {
+    /// The number of source words read for `num_words` words of the operation.
+    #[must_use]
+    pub const fn num_src_words(self, num_words: usize) -> usize {
+        match self {
+            ByteScanOperation::Find | ByteScanOperation::Copy => num_words,
+            ByteScanOperation::HexDecode => 2 * num_words,
+        }
+    }
+
+    /// The number of destination words written for `num_words` words of the operation.
+    #[must_use]
+    pub const fn num_dst_words(self, num_words: usize) -> usize {
+        match self {
+            ByteScanOperation::Find => 1,
+            ByteScanOperation::Copy | ByteScanOperation::HexDecode => num_words,
+        }
+    }
+}
+
+/// Decodes an ASCII hex digit, in lower or upper case.
+#[must_use]
+pub const fn hex_digit_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Byte Scan Event.
+///
+/// This event is emitted when a byte scan operation is performed. The header at `operands_ptr`
+/// holds the number of words `n`, the source pointer and the delimiter (only used by find). The
+/// operation reads `n` source words (`2 * n` for hex decode) and writes its result to `dst_ptr`.
+///
+/// The operation is carried out one word at a time, one row of the byte scan chip per word. The
+/// memory records are ordered by row.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ByteScanEvent {
+    /// The lookup id.
+    pub lookup_id: LookupId,
+    /// The shard number.
+    pub shard: u32,
+    /// The channel number.
+    pub channel: u8,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The operation to perform.
+    pub op: ByteScanOperation,
+    /// The pointer to the result.
+    pub dst_ptr: u32,
+    /// The pointer to the header.
+    pub operands_ptr: u32,
+    /// The number of words of the operation.
+    pub num_words: u32,
+    /// The pointer to the source words.
+    pub src_ptr: u32,
+    /// The delimiter, zero unless the operation is find.
+    pub delimiter: u32,
+    /// The source words.
+    pub src: Vec<u32>,
+    /// The result words.
+    pub dst: Vec<u32>,
+    /// The memory records for the header.
+    pub header_memory_records: [MemoryReadRecord; BYTE_SCAN_HEADER_WORDS],
+    /// The memory records for the source words.
+    pub src_memory_records: Vec<MemoryReadRecord>,
+    /// The memory records for the result words.
+    pub dst_memory_records: Vec<MemoryWriteRecord>,
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::{LookupId, MemoryWriteRecord};
+
+/// The number of coefficients processed by a single Dilithium operation, one per word.
+pub const DILITHIUM_BLOCK_WORDS: usize = 8;
+
+/// The modulus `q = 2^23 - 2^13 + 1` of the coefficients of Dilithium polynomials.
+pub const DILITHIUM_Q: u32 = 8_380_417;
+
+/// A lane-wise modular arithmetic operation on blocks of Dilithium coefficients.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum DilithiumOperation {
+    /// `x = x + y mod q`.
+    Add,
+    /// `x = x - y mod q`.
+    Sub,
+    /// `x = x * y mod q`.
+    Mul,
+    /// `(x, y) = (x + y mod q, x - y mod q)`, the butterfly of the number-theoretic transform.
+    Butterfly,
+}
+
+/// Dilithium Event.
+///
+/// This event is emitted when a lane-wise operation is performed on two blocks of
+/// [`DILITHIUM_BLOCK_WORDS`] coefficients modulo [`DILITHIUM_Q`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DilithiumOpEvent {
+    /// The lookup id.
+    pub lookup_id: LookupId,
+    /// The shard number.
+    pub shard: u32,
+    /// The channel number.
+    pub channel: u8,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The operation to perform.
+    pub op: DilithiumOperation,
+    /// The pointer to the x block.
+    pub x_ptr: u32,
+    /// The x block.
+    pub x: Vec<u32>,
+    /// The pointer to the y block.
+    pub y_ptr: u32,
+    /// The y block.
+    pub y: Vec<u32>,
+    /// The memory records for the x block.
+    pub x_memory_records: Vec<MemoryWriteRecord>,
+    /// The memory records for the y block, which is only modified by the butterfly.
+    pub y_memory_records: Vec<MemoryWriteRecord>,
+}
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
-use sp1_curves::{edwards::WORDS_FIELD_ELEMENT, COMPRESSED_POINT_BYTES, NUM_BYTES_FIELD_ELEMENT};
+use sp1_curves::{
+    edwards::{WORDS_CURVE_POINT, WORDS_FIELD_ELEMENT},
+    COMPRESSED_POINT_BYTES, NUM_BYTES_FIELD_ELEMENT,
+};
 
 use crate::events::{
     memory::{MemoryReadRecord, MemoryWriteRecord},
@@ -32,3 +35,30 @@ pub struct EdDecompressEvent {
     /// The memory records for the y coordinate.
     pub y_memory_records: [MemoryReadRecord; WORDS_FIELD_ELEMENT],
 }
+
+/// Edwards Scalar Multiplication Event.
+///
+/// This event is emitted when an edwards scalar multiplication operation is performed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdScalarMulEvent {
+    /// The lookup identifer.
+    pub lookup_id: LookupId,
+    /// The shard number.
+    pub shard: u32,
+    /// The channel number.
+    pub channel: u8,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the point.
+    pub p_ptr: u32,
+    /// The point as a list of words.
+    pub p: [u32; WORDS_CURVE_POINT],
+    /// The pointer to the scalar.
+    pub scalar_ptr: u32,
+    /// The scalar as a list of words.
+    pub scalar: [u32; WORDS_FIELD_ELEMENT],
+    /// The memory records for the point.
+    pub p_memory_records: [MemoryWriteRecord; WORDS_CURVE_POINT],
+    /// The memory records for the scalar.
+    pub scalar_memory_records: [MemoryReadRecord; WORDS_FIELD_ELEMENT],
+}
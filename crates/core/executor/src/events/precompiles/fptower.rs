@@ -97,3 +97,31 @@ pub struct Fp2MulEvent {
     /// The memory records for the y operand.
     pub y_memory_records: Vec<MemoryReadRecord>,
 }
+
+/// The maximum number of field elements inverted by one batch inversion.
+pub const FP_BATCH_INV_MAX_LEN: usize = 64;
+
+/// Emulated Field Batch Inversion Events.
+///
+/// This event is emitted when the `len` field elements at `x_ptr` are replaced by their inverses.
+/// The inverses are computed with Montgomery's trick, and checked one element per row of the
+/// batch inversion chip. The memory records are ordered by element.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FpBatchInvEvent {
+    /// The lookup id.
+    pub lookup_id: LookupId,
+    /// The shard number.
+    pub shard: u32,
+    /// The channel number.
+    pub channel: u8,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the field elements.
+    pub x_ptr: u32,
+    /// The number of field elements.
+    pub len: u32,
+    /// The field elements, concatenated.
+    pub x: Vec<u32>,
+    /// The memory records for the field elements.
+    pub x_memory_records: Vec<MemoryWriteRecord>,
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::{memory::MemoryWriteRecord, LookupId};
+
+/// The maximum number of words written by one hint words operation.
+pub const HINT_WORDS_MAX_WORDS: usize = 32;
+
+/// Hint Words Event.
+///
+/// This event is emitted when the next element of the input stream, made of `num_words` words, is
+/// written to memory at `dst_ptr`. Unlike a hint read, the destination may already be initialized,
+/// so the words are written through the memory argument, one row of the hint words chip per word.
+/// The memory records are ordered by row.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HintWordsEvent {
+    /// The lookup id.
+    pub lookup_id: LookupId,
+    /// The shard number.
+    pub shard: u32,
+    /// The channel number.
+    pub channel: u8,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the destination words.
+    pub dst_ptr: u32,
+    /// The number of words.
+    pub num_words: u32,
+    /// The words of the hint.
+    pub words: Vec<u32>,
+    /// The memory records for the destination words.
+    pub dst_memory_records: Vec<MemoryWriteRecord>,
+}
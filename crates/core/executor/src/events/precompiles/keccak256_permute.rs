@@ -7,6 +7,10 @@ use crate::events::{
 
 pub(crate) const STATE_SIZE: usize = 25;
 
+/// The number of rows of a keccak-256 permutation syscall, one for each of the 24 rounds of
+/// keccak-f[1600].
+pub const KECCAK_PERMUTE_NUM_ROWS: usize = 24;
+
 /// Keccak-256 Permutation Event.
 ///
 /// This event is emitted when a keccak-256 permutation operation is performed.
@@ -30,4 +34,33 @@ pub struct KeccakPermuteEvent {
     pub state_write_records: Vec<MemoryWriteRecord>,
     /// The address of the state.
     pub state_addr: u32,
+    /// The number of later calls in the shard with the same pre-state, which reuse the result of
+    /// this one. See [`KeccakPermuteMemoEvent`].
+    pub memo_uses: u32,
+}
+
+/// Memoized Keccak-256 Permutation Event.
+///
+/// This event is emitted instead of a [`KeccakPermuteEvent`] by a keccak-256 permutation whose
+/// pre-state was already permuted earlier in the shard, if the executor memoizes syscalls. The
+/// state is overwritten with the post-state of the earlier call in a single row, which receives
+/// the pair of states from the rows of the earlier call instead of proving the 24 rounds again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeccakPermuteMemoEvent {
+    /// The lookup identifer.
+    pub lookup_id: LookupId,
+    /// The shard number.
+    pub shard: u32,
+    /// The channel number.
+    pub channel: u8,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pre-state as a list of u64 words.
+    pub pre_state: [u64; STATE_SIZE],
+    /// The post-state as a list of u64 words.
+    pub post_state: [u64; STATE_SIZE],
+    /// The memory records overwriting the pre-state with the post-state.
+    pub state_write_records: Vec<MemoryWriteRecord>,
+    /// The address of the state.
+    pub state_addr: u32,
 }
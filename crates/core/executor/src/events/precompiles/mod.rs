@@ -1,15 +1,29 @@
+mod bigint;
+mod byte_scan;
+mod dilithium;
 mod ec;
 mod edwards;
 mod fptower;
+mod hint_words;
 mod keccak256_permute;
+mod msm;
+mod ripemd160_compress;
 mod sha256_compress;
 mod sha256_extend;
 mod uint256;
+mod wide_alu;
 
+pub use bigint::*;
+pub use byte_scan::*;
+pub use dilithium::*;
 pub use ec::*;
 pub use edwards::*;
 pub use fptower::*;
+pub use hint_words::*;
 pub use keccak256_permute::*;
+pub use msm::*;
+pub use ripemd160_compress::*;
 pub use sha256_compress::*;
 pub use sha256_extend::*;
 pub use uint256::*;
+pub use wide_alu::*;
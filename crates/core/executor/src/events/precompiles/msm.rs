@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    events::{
+        memory::{MemoryReadRecord, MemoryWriteRecord},
+        LookupId,
+    },
+    syscalls::SyscallContext,
+};
+
+/// The number of bits of the scalars consumed by a single MSM syscall.
+pub const MSM_WINDOW_BITS: usize = 4;
+
+/// The number of points in the table of multiples of each point of an MSM.
+pub const MSM_TABLE_SIZE: usize = 1 << MSM_WINDOW_BITS;
+
+/// The maximum number of rows of a single MSM syscall, one for each doubling and each point.
+pub const MSM_MAX_ROWS: usize = 256;
+
+/// The maximum number of points of a single MSM syscall.
+pub const MSM_MAX_POINTS: usize = MSM_MAX_ROWS - MSM_WINDOW_BITS;
+
+/// The number of words of the descriptor of an MSM syscall: the number of points, the pointer to
+/// the tables of multiples and the pointer to the digits.
+pub const MSM_DESCRIPTOR_WORDS: usize = 3;
+
+/// MSM Window Event.
+///
+/// This event is emitted when one window of a multi-scalar multiplication is performed, that is
+/// `acc = [2^MSM_WINDOW_BITS] acc + sum_i table_i[digit_i]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MsmEvent {
+    /// The lookup identifer.
+    pub lookup_id: LookupId,
+    /// The shard number.
+    pub shard: u32,
+    /// The channel number.
+    pub channel: u8,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the accumulator.
+    pub acc_ptr: u32,
+    /// The accumulator as a list of words.
+    pub acc: Vec<u32>,
+    /// The pointer to the descriptor.
+    pub desc_ptr: u32,
+    /// The descriptor: the number of points, the pointer to the tables and the pointer to the
+    /// digits.
+    pub desc: [u32; MSM_DESCRIPTOR_WORDS],
+    /// The digit of the scalar of each point for this window.
+    pub digits: Vec<u32>,
+    /// The multiple of each point selected by its digit, as a list of words.
+    pub entries: Vec<Vec<u32>>,
+    /// The memory records for the accumulator.
+    pub acc_memory_records: Vec<MemoryWriteRecord>,
+    /// The memory records for the descriptor.
+    pub desc_memory_records: [MemoryReadRecord; MSM_DESCRIPTOR_WORDS],
+    /// The memory records for the digits.
+    pub digit_memory_records: Vec<MemoryReadRecord>,
+    /// The memory records for the selected multiples.
+    pub entry_memory_records: Vec<Vec<MemoryReadRecord>>,
+}
+
+/// Create an MSM window event.
+///
+/// `arg1` points to the accumulator, and `arg2` to the descriptor `[n, table_ptr, digits_ptr]`.
+/// The `i`-th point has a table of [`MSM_TABLE_SIZE`] multiples starting at
+/// `table_ptr + i * MSM_TABLE_SIZE * num_words * 4`, and its digit for this window is the word at
+/// `digits_ptr + i * 4`. The accumulator is doubled [`MSM_WINDOW_BITS`] times with `add`, the
+/// selected multiples are added to it, and the result is written back to the accumulator.
+pub fn create_msm_event(
+    rt: &mut SyscallContext,
+    arg1: u32,
+    arg2: u32,
+    num_words: usize,
+    add: impl Fn(&[u32], &[u32]) -> Vec<u32>,
+) -> MsmEvent {
+    let start_clk = rt.clk;
+    let acc_ptr = arg1;
+    assert!(acc_ptr % 4 == 0, "acc_ptr must be 4-byte aligned");
+    let desc_ptr = arg2;
+    assert!(desc_ptr % 4 == 0, "desc_ptr must be 4-byte aligned");
+
+    let acc = rt.slice_unsafe(acc_ptr, num_words);
+
+    let (desc_memory_records, desc) = rt.mr_slice(desc_ptr, MSM_DESCRIPTOR_WORDS);
+    let desc: [u32; MSM_DESCRIPTOR_WORDS] = desc.try_into().unwrap();
+    let [n, table_ptr, digits_ptr] = desc;
+    assert!(n as usize <= MSM_MAX_POINTS, "an MSM syscall takes at most {MSM_MAX_POINTS} points");
+    assert!(table_ptr % 4 == 0, "table_ptr must be 4-byte aligned");
+    assert!(digits_ptr % 4 == 0, "digits_ptr must be 4-byte aligned");
+
+    let (digit_memory_records, digits) = rt.mr_slice(digits_ptr, n as usize);
+    let entry_size = (num_words * 4) as u32;
+    let (entry_memory_records, entries): (Vec<_>, Vec<_>) = digits
+        .iter()
+        .enumerate()
+        .map(|(i, &digit)| {
+            assert!((digit as usize) < MSM_TABLE_SIZE, "MSM digits must be below {MSM_TABLE_SIZE}");
+            let index = i as u32 * MSM_TABLE_SIZE as u32 + digit;
+            rt.mr_slice(table_ptr + index * entry_size, num_words)
+        })
+        .unzip();
+
+    // When we write to the accumulator, we want the clk to be incremented because it could
+    // overlap with the other inputs.
+    rt.clk += 1;
+
+    let mut result = acc.clone();
+    for _ in 0..MSM_WINDOW_BITS {
+        result = add(&result, &result);
+    }
+    for entry in &entries {
+        result = add(&result, entry);
+    }
+    let acc_memory_records = rt.mw_slice(acc_ptr, &result);
+
+    MsmEvent {
+        lookup_id: rt.syscall_lookup_id,
+        shard: rt.current_shard(),
+        channel: rt.current_channel(),
+        clk: start_clk,
+        acc_ptr,
+        acc,
+        desc_ptr,
+        desc,
+        digits,
+        entries,
+        acc_memory_records,
+        desc_memory_records: desc_memory_records.try_into().unwrap(),
+        digit_memory_records,
+        entry_memory_records,
+    }
+}
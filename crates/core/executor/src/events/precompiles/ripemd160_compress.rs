@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::{
+    memory::{MemoryReadRecord, MemoryWriteRecord},
+    LookupId,
+};
+
+/// The number of rows of a RIPEMD-160 compress syscall: 5 rows reading the state, one row for each
+/// of the 80 steps and 5 rows writing the state back.
+pub const RIPEMD160_COMPRESS_NUM_ROWS: usize = 90;
+
+/// RIPEMD-160 Compress Event.
+///
+/// This event is emitted when a RIPEMD-160 compress operation is performed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ripemd160CompressEvent {
+    /// The lookup identifer.
+    pub lookup_id: LookupId,
+    /// The shard number.
+    pub shard: u32,
+    /// The channel number.
+    pub channel: u8,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the message block.
+    pub w_ptr: u32,
+    /// The pointer to the state.
+    pub h_ptr: u32,
+    /// The message block, as 16 little-endian words.
+    pub w: Vec<u32>,
+    /// The state before the compression.
+    pub h: [u32; 5],
+    /// The memory records for the state reads.
+    pub h_read_records: [MemoryReadRecord; 5],
+    /// The memory records for the message block reads.
+    pub w_i_read_records: Vec<MemoryReadRecord>,
+    /// The memory records for the state writes.
+    pub h_write_records: [MemoryWriteRecord; 5],
+}
@@ -5,6 +5,10 @@ use crate::events::{
     LookupId,
 };
 
+/// The number of rows of a SHA-256 compress syscall: 8 rows reading the state, one row for each of
+/// the 64 rounds and 8 rows writing the state back.
+pub const SHA_COMPRESS_NUM_ROWS: usize = 80;
+
 /// SHA-256 Compress Event.
 ///
 /// This event is emitted when a SHA-256 compress operation is performed.
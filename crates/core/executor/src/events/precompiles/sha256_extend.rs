@@ -3,6 +3,10 @@ use serde::{Deserialize, Serialize};
 use crate::events::memory::{MemoryReadRecord, MemoryWriteRecord};
 use crate::events::LookupId;
 
+/// The number of rows of a SHA-256 extend syscall, one for each of the words 16 to 63 of the
+/// message schedule.
+pub const SHA_EXTEND_NUM_ROWS: usize = 48;
+
 /// SHA-256 Extend Event.
 ///
 /// This event is emitted when a SHA-256 extend operation is performed.
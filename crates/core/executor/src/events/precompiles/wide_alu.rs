@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::{
+    memory::{MemoryReadRecord, MemoryWriteRecord},
+    LookupId,
+};
+
+/// The number of words of an operand of a wide ALU operation.
+pub const WIDE_ALU_NUM_WORDS: usize = 2;
+
+/// An arithmetic operation on a pair of 64-bit integers.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum WideAluOperation {
+    /// `x = x + y mod 2^64`.
+    Add,
+    /// `x = x - y mod 2^64`.
+    Sub,
+    /// `x = x * y mod 2^64`.
+    Mul,
+    /// `x = 1` if `x < y` as unsigned integers, `x = 0` otherwise.
+    Ltu,
+    /// `x = 1` if `x < y` as signed integers, `x = 0` otherwise.
+    Lt,
+}
+
+impl WideAluOperation {
+    /// Computes the result of the operation on `x` and `y`.
+    #[must_use]
+    pub fn execute(self, x: u64, y: u64) -> u64 {
+        match self {
+            WideAluOperation::Add => x.wrapping_add(y),
+            WideAluOperation::Sub => x.wrapping_sub(y),
+            WideAluOperation::Mul => x.wrapping_mul(y),
+            WideAluOperation::Ltu => u64::from(x < y),
+            WideAluOperation::Lt => u64::from((x as i64) < (y as i64)),
+        }
+    }
+}
+
+/// Wide ALU Event.
+///
+/// This event is emitted when an arithmetic operation is performed on two 64-bit integers, each
+/// stored in memory as [`WIDE_ALU_NUM_WORDS`] little-endian words.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WideAluEvent {
+    /// The lookup id.
+    pub lookup_id: LookupId,
+    /// The shard number.
+    pub shard: u32,
+    /// The channel number.
+    pub channel: u8,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The operation to perform.
+    pub op: WideAluOperation,
+    /// The pointer to the x value.
+    pub x_ptr: u32,
+    /// The x value.
+    pub x: u64,
+    /// The pointer to the y value.
+    pub y_ptr: u32,
+    /// The y value.
+    pub y: u64,
+    /// The memory records for the x value.
+    pub x_memory_records: Vec<MemoryWriteRecord>,
+    /// The memory records for the y value.
+    pub y_memory_records: Vec<MemoryReadRecord>,
+}
@@ -4,8 +4,9 @@ use std::{
     sync::Arc,
 };
 
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
+use sp1_primitives::consts::{AddressSpace, Rv32};
 use sp1_stark::SP1CoreOpts;
 use thiserror::Error;
 
@@ -14,16 +15,25 @@ use crate::{
     events::{
         create_alu_lookup_id, create_alu_lookups, AluEvent, CpuEvent, LookupId,
         MemoryAccessPosition, MemoryInitializeFinalizeEvent, MemoryReadRecord, MemoryRecord,
-        MemoryWriteRecord,
+        MemoryRecordEnum, MemoryWriteRecord, KECCAK_PERMUTE_NUM_ROWS, RIPEMD160_COMPRESS_NUM_ROWS,
+        SHA_COMPRESS_NUM_ROWS, SHA_EXTEND_NUM_ROWS,
     },
+    fast::FastInstruction,
     hook::{HookEnv, HookRegistry},
+    input_source::InputSource,
     memory::{Entry, PagedMemory},
+    memory_history::MemoryHistory,
+    observer::{ExecutionObserver, LOG_OBSERVED_PAGE_SIZE},
+    privacy::PrivacyMode,
     record::{ExecutionRecord, MemoryAccessRecord},
     report::ExecutionReport,
+    rom::RomTree,
+    stack::StackGuard,
     state::{ExecutionState, ForkState},
     subproof::{DefaultSubproofVerifier, SubproofVerifier},
+    syscall_trace::{SyscallTracer, TraceEvent},
     syscalls::{default_syscall_map, Syscall, SyscallCode, SyscallContext},
-    Instruction, Opcode, Program, Register,
+    Instruction, Opcode, Program, Register, UnknownEcallPolicy,
 };
 
 /// An executor for the SP1 RISC-V zkVM.
@@ -61,6 +71,12 @@ pub struct Executor<'a> {
     /// A buffer for writing trace events to a file.
     pub trace_buf: Option<BufWriter<File>>,
 
+    /// The panic reported by the guest through [`FD_PANIC`], as `(location, message)`.
+    pub guest_panic: Option<(String, String)>,
+
+    /// The reason code and message of the `ABORT` syscall, returned as an error right after it.
+    pub guest_abort: Option<(u32, String)>,
+
     /// Whether the runtime is in constrained mode or not.
     ///
     /// In unconstrained mode, any events, clock, register, or memory changes are reset after
@@ -101,6 +117,41 @@ pub struct Executor<'a> {
     /// Memory addresses that were touched in this batch of shards. Used to minimize the size of
     /// checkpoints.
     pub memory_checkpoint: PagedMemory<Option<MemoryRecord>>,
+
+    /// The history of memory accesses, recorded only when set to `Some` before execution.
+    pub memory_history: Option<MemoryHistory>,
+
+    /// The guard region below the guest stack, if stack overflows should be detected.
+    pub stack_guard: Option<StackGuard>,
+
+    /// Whether private guest data is redacted from logs and error messages.
+    pub privacy_mode: PrivacyMode,
+
+    /// The tracer to which syscalls, and optionally basic blocks, are written.
+    pub syscall_tracer: Option<SyscallTracer<'a>>,
+
+    /// The observer notified of the shards, syscalls and memory pages of the execution.
+    pub observer: Option<Arc<dyn ExecutionObserver + 'a>>,
+
+    /// The source from which stdin elements are pulled when the guest reads past the written
+    /// inputs.
+    pub input_source: Option<InputSource<'a>>,
+
+    /// What to do with ecalls whose syscall number is unknown.
+    pub unknown_ecall_policy: UnknownEcallPolicy,
+
+    /// The memory pages already reported to the observer.
+    pub(crate) observed_pages: HashSet<u32>,
+
+    /// The index of the keccak permute event of each pre-state permuted in the current shard, if
+    /// [`SP1CoreOpts::memoize_syscalls`] is set.
+    pub(crate) keccak_memo: Option<HashMap<[u64; 25], usize>>,
+
+    /// The program decoded for the threaded-code interpreter, built on first use.
+    pub(crate) fast_program: Option<Arc<[Option<FastInstruction>]>>,
+
+    /// The Merkle tree of the pages of the ROM, built on first use.
+    pub(crate) rom_tree: Option<Arc<RomTree>>,
 }
 
 /// The different modes the executor can run in.
@@ -148,6 +199,60 @@ pub enum ExecutionError {
     /// The program ended in unconstrained mode.
     #[error("program ended in unconstrained mode")]
     EndInUnconstrained(),
+
+    /// The guest wrote into the guard region below its stack.
+    #[error("stack overflow at pc 0x{pc:08x} with a stack depth of {depth} bytes")]
+    StackOverflow {
+        /// The program counter of the faulting store.
+        pc: u32,
+        /// The depth of the stack at the time of the store, in bytes.
+        depth: u32,
+    },
+
+    /// The execution accessed memory above the usable memory ceiling.
+    #[error("memory access at 0x{addr:08x} exceeds the {max_addr_bits}-bit address space")]
+    MemoryOutOfBounds {
+        /// The address of the access.
+        addr: u32,
+        /// The number of bits of the usable address space.
+        max_addr_bits: usize,
+    },
+
+    /// The guest touched more memory than allowed by `SP1CoreOpts::max_memory_bytes`.
+    #[error(
+        "memory usage of {peak_bytes} bytes exceeds the limit of {limit_bytes} bytes, \
+         at pc 0x{pc:08x}"
+    )]
+    MemoryLimitExceeded {
+        /// The number of bytes of memory touched when the limit was exceeded.
+        peak_bytes: u64,
+        /// The maximum number of bytes of memory.
+        limit_bytes: u64,
+        /// The program counter of the instruction that touched the memory above the limit.
+        pc: u32,
+    },
+
+    /// The guest panicked.
+    #[error("guest panicked at {location}: {message}")]
+    GuestPanic {
+        /// The panic message.
+        message: String,
+        /// The source location of the panic, as `file:line:column`.
+        location: String,
+    },
+
+    /// The guest aborted with the `ABORT` syscall, through `sp1_zkvm::abort`.
+    #[error("guest aborted with code {code}: {message}")]
+    GuestAbort {
+        /// The reason code.
+        code: u32,
+        /// The message.
+        message: String,
+    },
+
+    /// The execution trace could not be written.
+    #[error("failed to write the syscall trace: {0}")]
+    TraceWrite(String),
 }
 
 macro_rules! assert_valid_memory_access {
@@ -193,6 +298,7 @@ impl<'a> Executor<'a> {
         let subproof_verifier =
             context.subproof_verifier.unwrap_or_else(|| Arc::new(DefaultSubproofVerifier::new()));
         let hook_registry = context.hook_registry.unwrap_or_default();
+        let keccak_memo = opts.memoize_syscalls.then(HashMap::new);
 
         Self {
             record,
@@ -205,6 +311,8 @@ impl<'a> Executor<'a> {
             cycle_tracker: HashMap::new(),
             io_buf: HashMap::new(),
             trace_buf,
+            guest_panic: None,
+            guest_abort: None,
             unconstrained: false,
             unconstrained_state: ForkState::default(),
             syscall_map,
@@ -217,6 +325,17 @@ impl<'a> Executor<'a> {
             opts,
             max_cycles: context.max_cycles,
             memory_checkpoint: PagedMemory::new_preallocated(),
+            memory_history: None,
+            stack_guard: context.stack_guard,
+            privacy_mode: context.privacy_mode,
+            syscall_tracer: context.syscall_tracer,
+            observer: context.observer,
+            input_source: context.input_source,
+            unknown_ecall_policy: context.unknown_ecall_policy,
+            observed_pages: HashSet::new(),
+            keccak_memo,
+            fast_program: None,
+            rom_tree: None,
         }
     }
 
@@ -382,9 +501,11 @@ impl<'a> Executor<'a> {
         let record: &mut MemoryRecord = match entry {
             Entry::Occupied(entry) => entry.into_mut(),
             Entry::Vacant(entry) => {
-                // If addr has a specific value to be initialized with, use that, otherwise 0.
-                let value = self.state.uninitialized_memory.get(addr).unwrap_or(&0);
-                entry.insert(MemoryRecord { value: *value, shard: 0, timestamp: 0 })
+                let value = initial_value(&self.program, &self.state.uninitialized_memory, addr);
+                if addr >= 32 {
+                    self.state.touched_memory_words += 1;
+                }
+                entry.insert(MemoryRecord { value, shard: 0, timestamp: 0 })
             }
         };
         let value = record.value;
@@ -394,7 +515,10 @@ impl<'a> Executor<'a> {
         record.timestamp = timestamp;
 
         // Construct the memory read record.
-        MemoryReadRecord::new(value, shard, timestamp, prev_shard, prev_timestamp)
+        let record = MemoryReadRecord::new(value, shard, timestamp, prev_shard, prev_timestamp);
+        self.record_memory_history(addr, record.into());
+        self.observe_memory_page(addr);
+        record
     }
 
     /// Write a word to memory and create an access record.
@@ -427,10 +551,12 @@ impl<'a> Executor<'a> {
         let record: &mut MemoryRecord = match entry {
             Entry::Occupied(entry) => entry.into_mut(),
             Entry::Vacant(entry) => {
-                // If addr has a specific value to be initialized with, use that, otherwise 0.
-                let value = self.state.uninitialized_memory.get(addr).unwrap_or(&0);
+                let value = initial_value(&self.program, &self.state.uninitialized_memory, addr);
+                if addr >= 32 {
+                    self.state.touched_memory_words += 1;
+                }
 
-                entry.insert(MemoryRecord { value: *value, shard: 0, timestamp: 0 })
+                entry.insert(MemoryRecord { value, shard: 0, timestamp: 0 })
             }
         };
         let prev_value = record.value;
@@ -441,10 +567,85 @@ impl<'a> Executor<'a> {
         record.timestamp = timestamp;
 
         // Construct the memory write record.
-        MemoryWriteRecord::new(value, shard, timestamp, prev_value, prev_shard, prev_timestamp)
+        let record =
+            MemoryWriteRecord::new(value, shard, timestamp, prev_value, prev_shard, prev_timestamp);
+        self.record_memory_history(addr, record.into());
+        self.observe_memory_page(addr);
+        record
+    }
+
+    /// Fail with a stack overflow if a store to `addr` falls into the stack guard region.
+    fn check_stack_guard(&mut self, addr: u32) -> Result<(), ExecutionError> {
+        match self.stack_guard {
+            Some(guard) if guard.contains(addr) => Err(ExecutionError::StackOverflow {
+                pc: self.state.pc,
+                depth: guard.depth(self.register(Register::X2)),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail if `addr` is above the usable memory ceiling set by
+    /// [`SP1CoreOpts::max_memory_addr_bits`].
+    pub(crate) fn check_memory_ceiling(&self, addr: u32) -> Result<(), ExecutionError> {
+        check_memory_ceiling(addr, self.opts.max_memory_addr_bits)
+    }
+
+    /// Fail if `addr` is in the word of the reservation of the load-reserved and store-conditional
+    /// instructions, which only they may access.
+    fn check_reservation_access(&self, opcode: Opcode, addr: u32) -> Result<(), ExecutionError> {
+        if align(addr) == RESERVATION_ADDR {
+            return Err(ExecutionError::InvalidMemoryAccess(opcode, addr));
+        }
+        Ok(())
+    }
+
+    /// Fail if the memory touched by the program exceeds [`SP1CoreOpts::max_memory_bytes`], blaming
+    /// the instruction at `pc`.
+    pub(crate) fn check_memory_limit(&self, pc: u32) -> Result<(), ExecutionError> {
+        let Some(limit_bytes) = self.opts.max_memory_bytes else {
+            return Ok(());
+        };
+        let peak_bytes = self.state.touched_memory_words * 4;
+        if peak_bytes > limit_bytes {
+            return Err(ExecutionError::MemoryLimitExceeded { peak_bytes, limit_bytes, pc });
+        }
+        Ok(())
+    }
+
+    /// Record a memory access in the memory history, if it is enabled.
+    fn record_memory_history(&mut self, addr: u32, record: MemoryRecordEnum) {
+        if self.unconstrained {
+            return;
+        }
+        if let Some(history) = &mut self.memory_history {
+            history.record(addr, self.state.pc, record);
+        }
+    }
+
+    /// Pull the next stdin element from the input source, if every written element was read.
+    pub(crate) fn pull_input(&mut self) {
+        if self.state.input_stream_ptr < self.state.input_stream.len() {
+            return;
+        }
+        if let Some(element) = self.input_source.as_ref().and_then(InputSource::next_element) {
+            self.state.input_stream.push(element);
+        }
+    }
+
+    /// Report the page of a memory access to the observer, if it is the first access to the page.
+    fn observe_memory_page(&mut self, addr: u32) {
+        // The registers and the reservation are stored in the first addresses of memory.
+        if self.observer.is_none() || self.unconstrained || addr <= RESERVATION_ADDR {
+            return;
+        }
+        let page = addr >> LOG_OBSERVED_PAGE_SIZE;
+        if self.observed_pages.insert(page) {
+            let observer = self.observer.as_ref().unwrap();
+            observer.on_memory_page_touch(self.state.current_shard, self.state.global_clk, page);
+        }
     }
 
-    /// Read from memory, assuming that all addresses are aligned.
     pub fn mr_cpu(&mut self, addr: u32, position: MemoryAccessPosition) -> u32 {
         // Assert that the address is aligned.
         assert_valid_memory_access!(addr, position);
@@ -500,6 +701,22 @@ impl<'a> Executor<'a> {
         }
     }
 
+    /// Write the reservation of the load-reserved and store-conditional instructions, and return
+    /// the previous reservation.
+    ///
+    /// The reservation is accessed at the timestamp of the memory access of the instruction.
+    fn reservation_rw(&mut self, value: u32) -> u32 {
+        let timestamp = self.timestamp(&MemoryAccessPosition::Memory);
+        let record = self.mw(RESERVATION_ADDR, value, self.shard(), timestamp);
+
+        // If we're not in unconstrained mode, record the access for the current cycle.
+        if !self.unconstrained && self.executor_mode == ExecutorMode::Trace {
+            assert!(self.memory_accesses.reservation.is_none());
+            self.memory_accesses.reservation = Some(record.into());
+        }
+        record.prev_value
+    }
+
     /// Read from a register.
     pub fn rr(&mut self, register: Register, position: MemoryAccessPosition) -> u32 {
         self.mr_cpu(register as u32, position)
@@ -551,11 +768,13 @@ impl<'a> Executor<'a> {
             c_record: record.c,
             memory: memory_store_value,
             memory_record: record.memory,
+            reservation_record: record.reservation,
             exit_code,
             alu_lookup_id: lookup_id,
             syscall_lookup_id,
             memory_add_lookup_id: create_alu_lookup_id(),
             memory_sub_lookup_id: create_alu_lookup_id(),
+            memory_atomic_lookup_id: create_alu_lookup_id(),
             branch_lt_lookup_id: create_alu_lookup_id(),
             branch_gt_lookup_id: create_alu_lookup_id(),
             branch_add_lookup_id: create_alu_lookup_id(),
@@ -664,6 +883,16 @@ impl<'a> Executor<'a> {
         (a, b, c, addr, memory_value)
     }
 
+    /// Fetch the input operand values for an atomic memory instruction.
+    fn atomic_rr(&mut self, instruction: &Instruction) -> (Register, u32, u32, u32, u32) {
+        let (rd, rs1, rs2) = instruction.r_type();
+        let c = self.rr(rs2, MemoryAccessPosition::C);
+        let b = self.rr(rs1, MemoryAccessPosition::B);
+        let addr = b;
+        let memory_value = self.word(align(addr));
+        (rd, b, c, addr, memory_value)
+    }
+
     /// Fetch the input operand values for a branch instruction.
     fn branch_rr(&mut self, instruction: &Instruction) -> (u32, u32, u32) {
         let (rs1, rs2, imm) = instruction.b_type();
@@ -767,6 +996,8 @@ impl<'a> Executor<'a> {
             // Load instructions.
             Opcode::LB => {
                 (rd, b, c, addr, memory_read_value) = self.load_rr(instruction);
+                self.check_memory_ceiling(addr)?;
+                self.check_reservation_access(Opcode::LB, addr)?;
                 let value = (memory_read_value).to_le_bytes()[(addr % 4) as usize];
                 a = ((value as i8) as i32) as u32;
                 memory_store_value = Some(memory_read_value);
@@ -774,6 +1005,8 @@ impl<'a> Executor<'a> {
             }
             Opcode::LH => {
                 (rd, b, c, addr, memory_read_value) = self.load_rr(instruction);
+                self.check_memory_ceiling(addr)?;
+                self.check_reservation_access(Opcode::LH, addr)?;
                 if addr % 2 != 0 {
                     return Err(ExecutionError::InvalidMemoryAccess(Opcode::LH, addr));
                 }
@@ -788,6 +1021,8 @@ impl<'a> Executor<'a> {
             }
             Opcode::LW => {
                 (rd, b, c, addr, memory_read_value) = self.load_rr(instruction);
+                self.check_memory_ceiling(addr)?;
+                self.check_reservation_access(Opcode::LW, addr)?;
                 if addr % 4 != 0 {
                     return Err(ExecutionError::InvalidMemoryAccess(Opcode::LW, addr));
                 }
@@ -797,6 +1032,8 @@ impl<'a> Executor<'a> {
             }
             Opcode::LBU => {
                 (rd, b, c, addr, memory_read_value) = self.load_rr(instruction);
+                self.check_memory_ceiling(addr)?;
+                self.check_reservation_access(Opcode::LBU, addr)?;
                 let value = (memory_read_value).to_le_bytes()[(addr % 4) as usize];
                 a = value as u32;
                 memory_store_value = Some(memory_read_value);
@@ -804,6 +1041,8 @@ impl<'a> Executor<'a> {
             }
             Opcode::LHU => {
                 (rd, b, c, addr, memory_read_value) = self.load_rr(instruction);
+                self.check_memory_ceiling(addr)?;
+                self.check_reservation_access(Opcode::LHU, addr)?;
                 if addr % 2 != 0 {
                     return Err(ExecutionError::InvalidMemoryAccess(Opcode::LHU, addr));
                 }
@@ -820,6 +1059,9 @@ impl<'a> Executor<'a> {
             // Store instructions.
             Opcode::SB => {
                 (a, b, c, addr, memory_read_value) = self.store_rr(instruction);
+                self.check_memory_ceiling(addr)?;
+                self.check_reservation_access(Opcode::SB, addr)?;
+                self.check_stack_guard(addr)?;
                 let value = match addr % 4 {
                     0 => (a & 0x0000_00FF) + (memory_read_value & 0xFFFF_FF00),
                     1 => ((a & 0x0000_00FF) << 8) + (memory_read_value & 0xFFFF_00FF),
@@ -832,6 +1074,9 @@ impl<'a> Executor<'a> {
             }
             Opcode::SH => {
                 (a, b, c, addr, memory_read_value) = self.store_rr(instruction);
+                self.check_memory_ceiling(addr)?;
+                self.check_reservation_access(Opcode::SH, addr)?;
+                self.check_stack_guard(addr)?;
                 if addr % 2 != 0 {
                     return Err(ExecutionError::InvalidMemoryAccess(Opcode::SH, addr));
                 }
@@ -845,6 +1090,9 @@ impl<'a> Executor<'a> {
             }
             Opcode::SW => {
                 (a, b, c, addr, _) = self.store_rr(instruction);
+                self.check_memory_ceiling(addr)?;
+                self.check_reservation_access(Opcode::SW, addr)?;
+                self.check_stack_guard(addr)?;
                 if addr % 4 != 0 {
                     return Err(ExecutionError::InvalidMemoryAccess(Opcode::SW, addr));
                 }
@@ -853,6 +1101,64 @@ impl<'a> Executor<'a> {
                 self.mw_cpu(align(addr), value, MemoryAccessPosition::Memory);
             }
 
+            // Atomic memory instructions.
+            Opcode::AMOSWAP
+            | Opcode::AMOADD
+            | Opcode::AMOXOR
+            | Opcode::AMOAND
+            | Opcode::AMOOR
+            | Opcode::AMOMIN
+            | Opcode::AMOMAX
+            | Opcode::AMOMINU
+            | Opcode::AMOMAXU
+            | Opcode::SC
+            | Opcode::LR => {
+                (rd, b, c, addr, memory_read_value) = self.atomic_rr(instruction);
+                self.check_memory_ceiling(addr)?;
+                self.check_reservation_access(instruction.opcode, addr)?;
+                self.check_stack_guard(addr)?;
+                if addr % 4 != 0 {
+                    return Err(ExecutionError::InvalidMemoryAccess(instruction.opcode, addr));
+                }
+
+                // A store-conditional only stores if its address is reserved by the last
+                // load-reserved, and it clears the reservation either way.
+                let sc_failed =
+                    instruction.opcode == Opcode::SC && self.reservation_rw(0) != addr + 1;
+                if instruction.opcode == Opcode::LR {
+                    self.reservation_rw(addr + 1);
+                }
+
+                let value = match instruction.opcode {
+                    Opcode::LR => memory_read_value,
+                    Opcode::SC => {
+                        if sc_failed {
+                            memory_read_value
+                        } else {
+                            c
+                        }
+                    }
+                    Opcode::AMOSWAP => c,
+                    Opcode::AMOADD => memory_read_value.wrapping_add(c),
+                    Opcode::AMOXOR => memory_read_value ^ c,
+                    Opcode::AMOAND => memory_read_value & c,
+                    Opcode::AMOOR => memory_read_value | c,
+                    Opcode::AMOMIN => (memory_read_value as i32).min(c as i32) as u32,
+                    Opcode::AMOMAX => (memory_read_value as i32).max(c as i32) as u32,
+                    Opcode::AMOMINU => memory_read_value.min(c),
+                    Opcode::AMOMAXU => memory_read_value.max(c),
+                    _ => unreachable!(),
+                };
+                a = if instruction.opcode == Opcode::SC {
+                    u32::from(sc_failed)
+                } else {
+                    memory_read_value
+                };
+                memory_store_value = Some(value);
+                self.mw_cpu(align(addr), value, MemoryAccessPosition::Memory);
+                self.rw(rd, a);
+            }
+
             // B-type instructions.
             Opcode::BEQ => {
                 (a, b, c) = self.branch_rr(instruction);
@@ -916,6 +1222,14 @@ impl<'a> Executor<'a> {
             }
 
             // System instructions.
+            Opcode::ECALL if self.is_unknown_ecall() => {
+                let t0 = Register::X5;
+                let syscall_id = self.register(t0);
+                c = self.rr(Register::X11, MemoryAccessPosition::C);
+                b = self.rr(Register::X10, MemoryAccessPosition::B);
+                a = self.execute_unknown_ecall(syscall_id, b, c)?;
+                self.rw(t0, a);
+            }
             Opcode::ECALL => {
                 // We peek at register x5 to get the syscall id. The reason we don't `self.rr` this
                 // register is that we write to it later.
@@ -936,12 +1250,31 @@ impl<'a> Executor<'a> {
                 // non-zero memory interactions when generating a proof.
 
                 if self.unconstrained
-                    && (syscall != SyscallCode::EXIT_UNCONSTRAINED && syscall != SyscallCode::WRITE)
+                    && (syscall != SyscallCode::EXIT_UNCONSTRAINED
+                        && syscall != SyscallCode::WRITE
+                        && syscall != SyscallCode::ABORT)
                 {
                     return Err(ExecutionError::InvalidSyscallUsage(syscall_id as u64));
                 }
 
                 let syscall_impl = self.get_syscall(syscall).cloned();
+                if let Some(syscall_impl) = &syscall_impl {
+                    self.trace_event(TraceEvent::Syscall {
+                        code: syscall,
+                        args: [b, c],
+                        pc: self.state.pc,
+                        cycle: self.state.global_clk,
+                        extra_cycles: syscall_impl.num_extra_cycles(),
+                        shard: self.shard(),
+                    })?;
+                    match &self.observer {
+                        Some(observer) if !self.unconstrained => {
+                            let (shard, cycle) = (self.shard(), self.state.global_clk);
+                            observer.on_syscall(shard, cycle, syscall, [b, c]);
+                        }
+                        _ => {}
+                    }
+                }
                 let mut precompile_rt = SyscallContext::new(self);
                 precompile_rt.syscall_lookup_id = syscall_lookup_id;
                 let (precompile_next_pc, precompile_cycles, returned_exit_code) =
@@ -956,8 +1289,21 @@ impl<'a> Executor<'a> {
                             a = syscall_id;
                         }
 
-                        // If the syscall is `HALT` and the exit code is non-zero, return an error.
+                        // If the guest aborted, stop the execution right away.
+                        if let Some((code, message)) = precompile_rt.rt.guest_abort.take() {
+                            let message =
+                                precompile_rt.rt.privacy_mode.redact(&message).to_string();
+                            return Err(ExecutionError::GuestAbort { code, message });
+                        }
+
+                        // If the syscall is `HALT` and the exit code is non-zero, return an error,
+                        // including the panic reported by the guest if there is one.
                         if syscall == SyscallCode::HALT && precompile_rt.exit_code != 0 {
+                            if let Some((location, message)) = precompile_rt.rt.guest_panic.take() {
+                                let message =
+                                    precompile_rt.rt.privacy_mode.redact(&message).to_string();
+                                return Err(ExecutionError::GuestPanic { message, location });
+                            }
                             return Err(ExecutionError::HaltWithNonZeroExitCode(
                                 precompile_rt.exit_code,
                             ));
@@ -971,6 +1317,7 @@ impl<'a> Executor<'a> {
                     } else {
                         return Err(ExecutionError::UnsupportedSyscall(syscall_id));
                     };
+                let memoized = precompile_rt.memoized;
 
                 // Allow the syscall impl to modify state.clk/pc (exit unconstrained does this)
                 clk = self.state.clk;
@@ -980,14 +1327,44 @@ impl<'a> Executor<'a> {
                 next_pc = precompile_next_pc;
                 self.state.clk += precompile_cycles;
                 exit_code = returned_exit_code;
+                if syscall.should_send() != 0 {
+                    self.state.last_precompile_cycles = precompile_cycles;
+                }
 
-                // Update the syscall counts.
+                // Update the syscall counts. Memoized calls are proven by chips with a row per
+                // call, so they are counted apart from the calls of their syscall.
                 let syscall_for_count = syscall.count_map();
-                let syscall_count = self.state.syscall_counts.entry(syscall_for_count).or_insert(0);
+                let syscall_count = if memoized {
+                    &mut self.state.memoized_syscall_count
+                } else {
+                    self.state.syscall_counts.entry(syscall_for_count).or_insert(0)
+                };
                 let (threshold, multiplier) = match syscall_for_count {
-                    SyscallCode::KECCAK_PERMUTE => (self.opts.split_opts.keccak, 24),
-                    SyscallCode::SHA_EXTEND => (self.opts.split_opts.sha_extend, 48),
-                    SyscallCode::SHA_COMPRESS => (self.opts.split_opts.sha_compress, 80),
+                    _ if memoized => (self.opts.split_opts.deferred, 1),
+                    SyscallCode::KECCAK_PERMUTE => {
+                        (self.opts.split_opts.keccak, KECCAK_PERMUTE_NUM_ROWS)
+                    }
+                    SyscallCode::SHA_EXTEND => {
+                        (self.opts.split_opts.sha_extend, SHA_EXTEND_NUM_ROWS)
+                    }
+                    SyscallCode::SHA_COMPRESS => {
+                        (self.opts.split_opts.sha_compress, SHA_COMPRESS_NUM_ROWS)
+                    }
+                    SyscallCode::RIPEMD160_COMPRESS => {
+                        (self.opts.split_opts.ripemd160_compress, RIPEMD160_COMPRESS_NUM_ROWS)
+                    }
+                    SyscallCode::ED_SCALAR_MUL => (self.opts.split_opts.ed_scalar_mul, 256),
+                    // MSM events have a variable number of rows, so their chips number events
+                    // rather than rows.
+                    SyscallCode::ED_MSM | SyscallCode::SECP256K1_MSM | SyscallCode::BN254_MSM => {
+                        (self.opts.split_opts.msm, 1)
+                    }
+                    SyscallCode::BIGINT_ADD => (self.opts.split_opts.bigint, 1),
+                    SyscallCode::BYTE_SCAN_FIND => (self.opts.split_opts.byte_scan, 1),
+                    SyscallCode::HINT_READ_WORDS => (self.opts.split_opts.hint_words, 1),
+                    SyscallCode::BN254_FP_BATCH_INV | SyscallCode::BLS12381_FP_BATCH_INV => {
+                        (self.opts.split_opts.fp_batch_inv, 1)
+                    }
                     _ => (self.opts.split_opts.deferred, 1),
                 };
                 let nonce = (((*syscall_count as usize) % threshold) * multiplier) as u32;
@@ -1101,6 +1478,7 @@ impl<'a> Executor<'a> {
     #[inline]
     fn execute_cycle(&mut self) -> Result<bool, ExecutionError> {
         // Fetch the instruction at the current program counter.
+        let pc = self.state.pc;
         let instruction = self.fetch();
 
         // Log the current state of the runtime.
@@ -1108,6 +1486,7 @@ impl<'a> Executor<'a> {
 
         // Execute the instruction.
         self.execute_instruction(&instruction)?;
+        self.check_memory_limit(pc)?;
 
         // Increment the clock.
         self.state.global_clk += 1;
@@ -1115,6 +1494,7 @@ impl<'a> Executor<'a> {
         // If there's not enough cycles left for another instruction, move to the next shard.
         // We multiply by 4 because clk is incremented by 4 for each normal instruction.
         if !self.unconstrained && self.max_syscall_cycles + self.state.clk >= self.shard_size {
+            self.observe_shard_end();
             self.state.current_shard += 1;
             self.state.clk = 0;
             self.state.channel = 0;
@@ -1136,9 +1516,49 @@ impl<'a> Executor<'a> {
             log::error!("program ended in unconstrained mode at clk {}", self.state.global_clk);
             return Err(ExecutionError::EndInUnconstrained());
         }
+
+        // A new basic block starts after every branch, jump and syscall.
+        if !done
+            && (instruction.is_branch_instruction()
+                || instruction.is_jump_instruction()
+                || instruction.is_ecall_instruction())
+        {
+            self.trace_block()?;
+        }
         Ok(done)
     }
 
+    /// Write `event` to the syscall tracer, if there is one and the executor is not in
+    /// unconstrained mode.
+    fn trace_event(&self, event: TraceEvent) -> Result<(), ExecutionError> {
+        match &self.syscall_tracer {
+            Some(tracer) if !self.unconstrained => {
+                tracer.write(&event).map_err(|e| ExecutionError::TraceWrite(e.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Write the start of a basic block at the current program counter, if basic blocks are
+    /// traced.
+    fn trace_block(&self) -> Result<(), ExecutionError> {
+        if !self.syscall_tracer.as_ref().is_some_and(SyscallTracer::basic_blocks) {
+            return Ok(());
+        }
+        self.trace_event(TraceEvent::Block {
+            pc: self.state.pc,
+            cycle: self.state.global_clk,
+            shard: self.shard(),
+        })
+    }
+
+    /// Report the end of the current shard to the observer, if there is one.
+    fn observe_shard_end(&self) {
+        if let Some(observer) = &self.observer {
+            observer.on_shard_end(self.state.current_shard, self.state.global_clk, &self.record);
+        }
+    }
+
     /// Bump the record.
     pub fn bump_record(&mut self) {
         let removed_record =
@@ -1146,6 +1566,10 @@ impl<'a> Executor<'a> {
         let public_values = removed_record.public_values;
         self.record.public_values = public_values;
         self.records.push(removed_record);
+        // The memoized calls of a shard only refer to the events of its own record.
+        if let Some(memo) = &mut self.keccak_memo {
+            memo.clear();
+        }
     }
 
     /// Execute up to `self.shard_batch_size` cycles, returning the events emitted and whether the
@@ -1206,10 +1630,13 @@ impl<'a> Executor<'a> {
         self.state.clk = 0;
         self.state.channel = 0;
 
+        // The runs of zeros of the image are left out, see `initial_value`.
         tracing::debug!("loading memory image");
-        for (&addr, value) in &self.program.memory_image {
-            self.state.memory.insert(addr, MemoryRecord { value: *value, shard: 0, timestamp: 0 });
+        for (addr, value) in self.program.memory_image.words() {
+            self.state.memory.insert(addr, MemoryRecord { value, shard: 0, timestamp: 0 });
         }
+        self.state.touched_memory_words =
+            self.state.memory.keys().filter(|&addr| addr >= 32).count() as u64;
     }
 
     /// Executes the program without tracing and without emitting events.
@@ -1248,6 +1675,7 @@ impl<'a> Executor<'a> {
         // If it's the first cycle, initialize the program.
         if self.state.global_clk == 0 {
             self.initialize();
+            self.trace_block()?;
         }
 
         // Loop until we've executed `self.shard_batch_size` shards if `self.shard_batch_size` is
@@ -1256,7 +1684,10 @@ impl<'a> Executor<'a> {
         let mut current_shard = self.state.current_shard;
         let mut num_shards_executed = 0;
         loop {
-            if self.execute_cycle()? {
+            // Unconstrained mode may be entered by a syscall, so this is checked on every run.
+            let cycle_done =
+                if self.fast_path_enabled() { self.execute_fast()? } else { self.execute_cycle()? };
+            if cycle_done {
                 done = true;
                 break;
             }
@@ -1275,11 +1706,15 @@ impl<'a> Executor<'a> {
 
         // Push the remaining execution record, if there are any CPU events.
         if !self.record.cpu_events.is_empty() {
+            self.observe_shard_end();
             self.bump_record();
         }
 
         if done {
-            self.postprocess();
+            self.postprocess()?;
+            if let Some(tracer) = &self.syscall_tracer {
+                tracer.flush().map_err(|e| ExecutionError::TraceWrite(e.to_string()))?;
+            }
 
             // Push the remaining execution record with memory initialize & finalize events.
             self.bump_record();
@@ -1310,16 +1745,16 @@ impl<'a> Executor<'a> {
         Ok(done)
     }
 
-    fn postprocess(&mut self) {
+    fn postprocess(&mut self) -> Result<(), ExecutionError> {
         // Flush remaining stdout/stderr
         for (fd, buf) in &self.io_buf {
             if !buf.is_empty() {
                 match fd {
                     1 => {
-                        println!("stdout: {buf}");
+                        println!("stdout: {}", self.privacy_mode.redact(buf));
                     }
                     2 => {
-                        println!("stderr: {buf}");
+                        println!("stderr: {}", self.privacy_mode.redact(buf));
                     }
                     _ => {}
                 }
@@ -1362,6 +1797,7 @@ impl<'a> Executor<'a> {
 
         // Count the number of touched memory addresses manually, since `PagedMemory` doesn't
         // already know its length.
+        let max_addr_bits = self.opts.max_memory_addr_bits;
         self.report.touched_memory_addresses = 0;
         for addr in self.state.memory.keys() {
             self.report.touched_memory_addresses += 1;
@@ -1370,13 +1806,18 @@ impl<'a> Executor<'a> {
                 continue;
             }
 
-            // Program memory is initialized in the MemoryProgram chip and doesn't require any
-            // events, so we only send init events for other memory addresses.
-            if !self.record.program.memory_image.contains_key(&addr) {
-                let initial_value = self.state.uninitialized_memory.get(addr).unwrap_or(&0);
+            // Memory touched by precompiles is only checked here, since they access it directly.
+            check_memory_ceiling(addr, max_addr_bits)?;
+
+            // The explicit words of the program memory are initialized in the MemoryProgram chip
+            // and don't require any events, so we only send init events for other memory
+            // addresses, including the words of the runs of zeros of the image.
+            if !self.record.program.memory_image.has_word(addr) {
+                let initial_value =
+                    initial_value(&self.program, &self.state.uninitialized_memory, addr);
                 memory_initialize_events.push(MemoryInitializeFinalizeEvent::initialize(
                     addr,
-                    *initial_value,
+                    initial_value,
                     true,
                 ));
             }
@@ -1385,12 +1826,42 @@ impl<'a> Executor<'a> {
             memory_finalize_events
                 .push(MemoryInitializeFinalizeEvent::finalize_from_record(addr, &record));
         }
+        Ok(())
     }
 
     fn get_syscall(&mut self, code: SyscallCode) -> Option<&Arc<dyn Syscall>> {
         self.syscall_map.get(&code)
     }
 
+    /// Whether the current instruction, an ecall, has a syscall number the executor does not know.
+    fn is_unknown_ecall(&mut self) -> bool {
+        SyscallCode::try_from_u32(self.register(Register::X5)).is_none()
+    }
+
+    /// Execute an ecall with an unknown syscall number according to the [`UnknownEcallPolicy`],
+    /// and return the value to write to `t0`.
+    #[cfg_attr(not(feature = "unsound_experimental"), allow(unused_variables))]
+    fn execute_unknown_ecall(
+        &mut self,
+        syscall_id: u32,
+        b: u32,
+        c: u32,
+    ) -> Result<u32, ExecutionError> {
+        if self.unconstrained {
+            return Err(ExecutionError::InvalidSyscallUsage(syscall_id as u64));
+        }
+        match self.unknown_ecall_policy.clone() {
+            UnknownEcallPolicy::Error => Err(ExecutionError::UnsupportedSyscall(syscall_id)),
+            #[cfg(feature = "unsound_experimental")]
+            UnknownEcallPolicy::Ignore => Ok(syscall_id),
+            #[cfg(feature = "unsound_experimental")]
+            UnknownEcallPolicy::Forward(handler) => {
+                let mut ctx = SyscallContext::new(self);
+                Ok(handler(&mut ctx, syscall_id, b, c).unwrap_or(syscall_id))
+            }
+        }
+    }
+
     #[inline]
     fn log(&mut self, _: &Instruction) {
         // Write the current program counter to the trace buffer for the cycle tracer.
@@ -1419,6 +1890,34 @@ pub const fn align(addr: u32) -> u32 {
     addr - addr % 4
 }
 
+/// The value of the word at `addr` before the program first accesses it.
+///
+/// The words of the runs of zeros of the memory image are not loaded with the image, so they are
+/// zero here, even if a hint was written to them. Other words take the value written by a hint, if
+/// any, and otherwise zero.
+fn initial_value(program: &Program, uninitialized_memory: &PagedMemory<u32>, addr: u32) -> u32 {
+    if program.memory_image.is_zero_initialized(addr) {
+        return 0;
+    }
+    uninitialized_memory.get(addr).copied().unwrap_or(0)
+}
+
+/// Fail if `addr` does not fit in an address space of `max_addr_bits` bits.
+fn check_memory_ceiling(addr: u32, max_addr_bits: usize) -> Result<(), ExecutionError> {
+    if max_addr_bits < Rv32::ADDR_BITS && addr >> max_addr_bits != 0 {
+        return Err(ExecutionError::MemoryOutOfBounds { addr, max_addr_bits });
+    }
+    Ok(())
+}
+
+/// The address of the reservation of the load-reserved and store-conditional instructions, the
+/// first address after the registers.
+///
+/// It holds one more than the reserved address, which is aligned, or zero if no address is
+/// reserved. No load, store or atomic instruction may access its word, so that only LR and SC
+/// change it.
+pub const RESERVATION_ADDR: u32 = 32;
+
 // TODO: FIX
 /// The number of different byte lookup channels.
 pub const NUM_BYTE_LOOKUP_CHANNELS: u8 = 16;
@@ -1426,16 +1925,23 @@ pub const NUM_BYTE_LOOKUP_CHANNELS: u8 = 16;
 #[cfg(test)]
 mod tests {
 
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
     use sp1_stark::SP1CoreOpts;
 
     use crate::programs::tests::{
-        fibonacci_program, panic_program, simple_memory_program, simple_program,
-        ssz_withdrawals_program,
+        atomic_memory_program, fibonacci_program, panic_program, simple_memory_program,
+        simple_program, ssz_withdrawals_program,
     };
 
-    use crate::Register;
+    use crate::{
+        disassembler::transpile,
+        syscalls::{SyscallCode, MAX_ABORT_MESSAGE_LEN},
+        InputSource, Register, RomTree, SP1Context, FD_PANIC, RESERVATION_ADDR, ROM_DATA_START,
+        ROM_PAGE_SIZE, ROM_START,
+    };
 
-    use super::{Executor, Instruction, Opcode, Program};
+    use super::{ExecutionError, Executor, Instruction, Opcode, Program};
 
     fn _assert_send<T: Send>() {}
 
@@ -2036,4 +2542,559 @@ mod tests {
         assert_eq!(runtime.register(Register::X12), 0x12346525);
         assert_eq!(runtime.register(Register::X11), 0x65256525);
     }
+
+    #[test]
+    fn test_memory_ceiling() {
+        // The highest address accessed by the program is 0x43627533, which needs 31 bits.
+        let mut opts = SP1CoreOpts::default();
+        opts.max_memory_addr_bits = 31;
+        let mut runtime = Executor::new(simple_memory_program(), opts.clone());
+        runtime.run().unwrap();
+
+        opts.max_memory_addr_bits = 30;
+        let mut runtime = Executor::new(simple_memory_program(), opts);
+        assert!(matches!(
+            runtime.run(),
+            Err(ExecutionError::MemoryOutOfBounds { addr: 0x4362_7530, max_addr_bits: 30 })
+        ));
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        let mut runtime = Executor::new(simple_memory_program(), SP1CoreOpts::default());
+        runtime.run().unwrap();
+        let peak_bytes = runtime.state.touched_memory_words * 4;
+
+        let mut opts = SP1CoreOpts::default();
+        opts.max_memory_bytes = Some(peak_bytes);
+        let mut runtime = Executor::new(simple_memory_program(), opts.clone());
+        runtime.run().unwrap();
+
+        // The last word touched by the program goes over the limit.
+        opts.max_memory_bytes = Some(peak_bytes - 4);
+        let mut runtime = Executor::new(simple_memory_program(), opts);
+        let Err(ExecutionError::MemoryLimitExceeded { peak_bytes: peak, limit_bytes, pc }) =
+            runtime.run()
+        else {
+            panic!("expected the memory limit to be exceeded");
+        };
+        assert_eq!((peak, limit_bytes), (peak_bytes, peak_bytes - 4));
+        let instruction =
+            runtime.program.instructions[((pc - runtime.program.pc_base) / 4) as usize];
+        assert!(instruction.is_memory_instruction());
+    }
+
+    #[test]
+    fn test_zero_runs_stay_sparse() {
+        //     addi x29, x0, 0x2000
+        //     lw x13, 0(x29)
+        //     addi x28, x0, 7
+        //     sw x28, 4(x29)
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 0x2000, false, true),
+            Instruction::new(Opcode::LW, 13, 29, 0, false, true),
+            Instruction::new(Opcode::ADD, 28, 0, 7, false, true),
+            Instruction::new(Opcode::SW, 28, 29, 4, false, true),
+        ];
+        let mut program = Program::new(instructions, 0, 0);
+        program.memory_image.insert_segment(0x1000, vec![5], 1 << 20).unwrap();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        // Only the explicit word and the touched words of the run of zeros are tracked.
+        assert_eq!(runtime.register(Register::X13), 0);
+        assert_eq!(runtime.word(0x2004), 7);
+        assert!(runtime.state.memory.get(0x1000).is_some());
+        assert!(runtime.state.memory.get(0x1004).is_none());
+
+        // The touched words of the run of zeros are initialized to zero by init events, and the
+        // explicit word by the program memory.
+        let init = runtime
+            .records
+            .iter()
+            .flat_map(|record| &record.memory_initialize_events)
+            .filter(|event| event.addr >= 0x1000)
+            .map(|event| (event.addr, event.value))
+            .collect::<Vec<_>>();
+        assert_eq!(init.len(), 2);
+        assert!(init.contains(&(0x2000, 0)) && init.contains(&(0x2004, 0)));
+        let finalized =
+            runtime.records.iter().map(|record| record.memory_finalize_events.len()).sum::<usize>();
+        assert!(finalized < 64);
+    }
+
+    #[test]
+    fn test_atomic_memory_program_run() {
+        let program = atomic_memory_program();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        assert_eq!(runtime.register(Register::X15), 1);
+        assert_eq!(runtime.register(Register::X14), 0);
+        assert_eq!(runtime.register(Register::X26), 0);
+        assert_eq!(runtime.register(Register::X13), 1);
+        assert_eq!(runtime.register(Register::X25), 5);
+        assert_eq!(runtime.register(Register::X24), 10);
+        assert_eq!(runtime.register(Register::X23), 0xffff_fffd);
+        assert_eq!(runtime.register(Register::X22), 0xffff_fffd);
+        assert_eq!(runtime.register(Register::X21), 5);
+        assert_eq!(runtime.register(Register::X20), 5);
+        assert_eq!(runtime.register(Register::X19), 0xffff_fff8);
+        assert_eq!(runtime.register(Register::X18), 0xffff_fffd);
+        assert_eq!(runtime.register(Register::X17), 5);
+        assert_eq!(runtime.register(Register::X16), 0xffff_fffd);
+    }
+
+    #[test]
+    fn test_atomic_instructions_transpiled() {
+        // The sequences rustc emits on `riscv32ima` for `AtomicU32::fetch_add` and for
+        // `AtomicU32::compare_exchange`.
+        //     lui a0, 0x1
+        //     addi a1, x0, 3
+        //     amoadd.w.aqrl a2, a1, (a0)
+        //     addi a3, x0, 3
+        //     addi a4, x0, 9
+        // 1:  lr.w.aqrl a5, (a0)
+        //     bne a5, a3, 2f
+        //     sc.w.rl a6, a4, (a0)
+        //     bnez a6, 1b
+        // 2:  lw a7, 0(a0)
+        let instructions = transpile(&[
+            0x0000_1537,
+            0x0030_0593,
+            0x06b5_262f,
+            0x0030_0693,
+            0x0090_0713,
+            0x1605_27af,
+            0x00d7_9663,
+            0x1ae5_282f,
+            0xfe08_1ae3,
+            0x0005_2883,
+        ]);
+        assert_eq!(instructions[2].opcode, Opcode::AMOADD);
+        assert_eq!(instructions[5].opcode, Opcode::LR);
+        assert_eq!(instructions[7].opcode, Opcode::SC);
+
+        let program = Program::new(instructions, 0, 0);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+        assert_eq!(runtime.register(Register::X12), 0);
+        assert_eq!(runtime.register(Register::X15), 3);
+        assert_eq!(runtime.register(Register::X16), 0);
+        assert_eq!(runtime.register(Register::X17), 9);
+    }
+
+    #[test]
+    fn test_store_conditional_reservation() {
+        //     addi x29, x0, 0x1000
+        //     addi x28, x0, 7
+        //     lr.w x10, (x29)
+        //     sw x28, 4(x29)
+        //     sc.w x11, x28, (x29)
+        //     lr.w x10, (x29)
+        //     addi x30, x29, 4
+        //     sc.w x12, x28, (x30)
+        //     lw x13, 4(x29)
+        //     sc.w x14, x0, (x29)
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 0x1000, false, true),
+            Instruction::new(Opcode::ADD, 28, 0, 7, false, true),
+            Instruction::new(Opcode::LR, 10, 29, 0, false, false),
+            Instruction::new(Opcode::SW, 28, 29, 4, false, true),
+            Instruction::new(Opcode::SC, 11, 29, 28, false, false),
+            Instruction::new(Opcode::LR, 10, 29, 0, false, false),
+            Instruction::new(Opcode::ADD, 30, 29, 4, false, true),
+            Instruction::new(Opcode::SC, 12, 30, 28, false, false),
+            Instruction::new(Opcode::LW, 13, 29, 4, false, true),
+            Instruction::new(Opcode::SC, 14, 29, 0, false, false),
+        ];
+        let program = Program::new(instructions, 0, 0);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        // A store to another address keeps the reservation.
+        assert_eq!(runtime.register(Register::X11), 0);
+        assert_eq!(runtime.word(0x1000), 7);
+        // An SC to another address than the reserved one fails and clears the reservation.
+        assert_eq!(runtime.register(Register::X12), 1);
+        assert_eq!(runtime.register(Register::X13), 7);
+        assert_eq!(runtime.register(Register::X14), 1);
+        assert_eq!(runtime.word(0x1000), 7);
+        assert_eq!(runtime.word(RESERVATION_ADDR), 0);
+    }
+
+    #[test]
+    fn test_store_to_reservation() {
+        //     addi x29, x0, 0x1000
+        //     addi x28, x0, 0x1001
+        //     sw x28, 32(x0)
+        //     sc.w x11, x28, (x29)
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 0x1000, false, true),
+            Instruction::new(Opcode::ADD, 28, 0, 0x1001, false, true),
+            Instruction::new(Opcode::SW, 28, 0, RESERVATION_ADDR, false, true),
+            Instruction::new(Opcode::SC, 11, 29, 28, false, false),
+        ];
+        let program = Program::new(instructions, 0, 0);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+
+        // A plain store cannot forge the reservation of the SC.
+        assert!(matches!(
+            runtime.run(),
+            Err(ExecutionError::InvalidMemoryAccess(Opcode::SW, RESERVATION_ADDR))
+        ));
+        assert_eq!(runtime.word(0x1000), 0);
+
+        //     lbu x10, 33(x0)
+        let instructions =
+            vec![Instruction::new(Opcode::LBU, 10, 0, RESERVATION_ADDR + 1, false, true)];
+        let program = Program::new(instructions, 0, 0);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        assert!(matches!(runtime.run(), Err(ExecutionError::InvalidMemoryAccess(Opcode::LBU, 33))));
+    }
+
+    #[test]
+    fn test_paged_input() {
+        //     addi x5, x0, PAGED_INPUT_MAP
+        //     addi x10, x0, 0x10000
+        //     addi x11, x0, 1
+        //     ecall
+        //     lw x12, 4(x10)
+        //     addi x5, x0, PAGED_INPUT_LEN
+        //     addi x10, x0, 0
+        //     addi x11, x0, 0x20000
+        //     ecall
+        //     lw x13, 0(x11)
+        //     lw x14, 4(x11)
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::PAGED_INPUT_MAP as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0x10000, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 1, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::LW, 12, 10, 4, false, true),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::PAGED_INPUT_LEN as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0x20000, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::LW, 13, 11, 0, false, true),
+            Instruction::new(Opcode::LW, 14, 11, 4, false, true),
+        ];
+        let program = Program::new(instructions, 0, 0);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        let input: Vec<u8> = (0..5000u32).map(|i| i as u8).collect();
+        runtime.write_paged_inputs(&[input.into()]);
+        runtime.run().unwrap();
+
+        // The word at offset 4 of the second page holds the bytes 4100..4104 of the input.
+        assert_eq!(runtime.register(Register::X12), u32::from_le_bytes([4, 5, 6, 7]));
+        assert_eq!(runtime.register(Register::X13), 5000);
+        assert_eq!(runtime.register(Register::X14), 0);
+        // Only the word that was read entered memory.
+        assert!(runtime.state.memory.get(0x10000 + 4).is_some());
+        assert!(runtime.state.memory.get(0x10000 + 8).is_none());
+    }
+
+    #[test]
+    fn test_rom_map() {
+        //     addi x5, x0, ROM_MAP
+        //     addi x10, x0, 1
+        //     addi x11, x0, 0x20000
+        //     ecall
+        //     addi x13, x0, ROM_DATA_START + ROM_PAGE_SIZE
+        //     lw x12, 4(x13)
+        //     lw x14, 0(x11)
+        //     addi x13, x0, ROM_START
+        //     lw x15, 0(x13)
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::ROM_MAP as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 1, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0x20000, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 13, 0, ROM_DATA_START + ROM_PAGE_SIZE, false, true),
+            Instruction::new(Opcode::LW, 12, 13, 4, false, true),
+            Instruction::new(Opcode::LW, 14, 11, 0, false, true),
+            Instruction::new(Opcode::ADD, 13, 0, ROM_START, false, true),
+            Instruction::new(Opcode::LW, 15, 13, 0, false, true),
+        ];
+        let rom: Vec<u8> = (0..5000u32).map(|i| i as u8).collect();
+        let program = Program::new(instructions, 0, 0).with_rom(&rom).unwrap();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        // The word at offset 4 of the second page holds the bytes 4100..4104 of the ROM.
+        assert_eq!(runtime.register(Register::X12), u32::from_le_bytes([4, 5, 6, 7]));
+        let sibling = RomTree::new(&rom).path(1)[0];
+        assert_eq!(
+            runtime.register(Register::X14),
+            u32::from_le_bytes(sibling[..4].try_into().unwrap())
+        );
+        assert_eq!(runtime.register(Register::X15), 5000);
+        // Only the words that were read entered memory.
+        assert!(runtime.state.memory.get(ROM_DATA_START + ROM_PAGE_SIZE + 4).is_some());
+        assert!(runtime.state.memory.get(ROM_DATA_START + ROM_PAGE_SIZE + 8).is_none());
+        assert!(runtime.state.memory.get(ROM_DATA_START).is_none());
+    }
+
+    #[test]
+    fn test_hint_remaining() {
+        //     addi x5, x0, HINT_REMAINING
+        //     addi x10, x0, 0x20000
+        //     ecall
+        //     lw x12, 0(x10)
+        //     addi x5, x0, HINT_READ
+        //     addi x10, x0, 0x10000
+        //     addi x11, x0, 3
+        //     ecall
+        //     addi x5, x0, HINT_REMAINING
+        //     addi x10, x0, 0x20004
+        //     ecall
+        //     lw x13, 0(x10)
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HINT_REMAINING as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0x20000, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::LW, 12, 10, 0, false, true),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HINT_READ as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0x10000, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 3, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HINT_REMAINING as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0x20004, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::LW, 13, 10, 0, false, true),
+        ];
+        let program = Program::new(instructions, 0, 0);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.write_stdin_slice(&[1, 2, 3]);
+        runtime.write_stdin_slice(&[4]);
+        runtime.run().unwrap();
+
+        assert_eq!(runtime.register(Register::X12), 2);
+        assert_eq!(runtime.register(Register::X13), 1);
+    }
+
+    #[test]
+    fn test_cycle_info() {
+        //     addi x5, x0, SHA_EXTEND
+        //     addi x10, x0, 0x10000
+        //     ecall
+        //     addi x5, x0, CYCLE_INFO
+        //     addi x10, x0, 0x20000
+        //     ecall
+        //     lw x12, 0(x10)
+        //     lw x13, 4(x10)
+        //     lw x14, 8(x10)
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::SHA_EXTEND as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0x10000, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::CYCLE_INFO as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0x20000, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::LW, 12, 10, 0, false, true),
+            Instruction::new(Opcode::LW, 13, 10, 4, false, true),
+            Instruction::new(Opcode::LW, 14, 10, 8, false, true),
+        ];
+        let program = Program::new(instructions, 0, 0);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        // Five instructions were executed before the second ecall, the last precompile being
+        // `SHA_EXTEND`.
+        assert_eq!(runtime.register(Register::X12), 5);
+        assert_eq!(runtime.register(Register::X13), 0);
+        assert_eq!(runtime.register(Register::X14), 48);
+    }
+
+    #[test]
+    fn test_abort() {
+        //     addi x29, x0, 0x6f6f6f42
+        //     addi x30, x0, 0x10000
+        //     sw x29, 0(x30)
+        //     addi x5, x0, ABORT
+        //     addi x10, x0, 7
+        //     addi x11, x0, 0x10000
+        //     addi x12, x0, 3
+        //     ecall
+        //     addi x13, x0, 1
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 0x6f6f_6f42, false, true),
+            Instruction::new(Opcode::ADD, 30, 0, 0x10000, false, true),
+            Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::ABORT as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 7, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0x10000, false, true),
+            Instruction::new(Opcode::ADD, 12, 0, 3, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 13, 0, 1, false, true),
+        ];
+        let program = Program::new(instructions, 0, 0);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        match runtime.run() {
+            Err(ExecutionError::GuestAbort { code, message }) => {
+                assert_eq!(code, 7);
+                assert_eq!(message, "Boo");
+            }
+            result => panic!("expected an abort, got {result:?}"),
+        }
+        assert_eq!(runtime.register(Register::X13), 0);
+    }
+
+    #[test]
+    fn test_abort_long_message() {
+        //     addi x5, x0, ABORT
+        //     addi x10, x0, 7
+        //     addi x11, x0, 0x10000
+        //     addi x12, x0, 0xffffffff
+        //     ecall
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::ABORT as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 7, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0x10000, false, true),
+            Instruction::new(Opcode::ADD, 12, 0, u32::MAX, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let program = Program::new(instructions, 0, 0);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        match runtime.run() {
+            Err(ExecutionError::GuestAbort { code, message }) => {
+                assert_eq!(code, 7);
+                assert_eq!(message.len(), MAX_ABORT_MESSAGE_LEN as usize);
+            }
+            result => panic!("expected an abort, got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn test_guest_panic() {
+        //     addi x29, x0, 0x0a313a61
+        //     addi x30, x0, 0x10000
+        //     sw x29, 0(x30)
+        //     addi x29, x0, 0x216f6f42
+        //     sw x29, 4(x30)
+        //     addi x5, x0, WRITE
+        //     addi x10, x0, FD_PANIC
+        //     addi x11, x0, 0x10000
+        //     addi x12, x0, 8
+        //     ecall
+        //     addi x5, x0, HALT
+        //     addi x10, x0, 1
+        //     ecall
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 0x0a31_3a61, false, true),
+            Instruction::new(Opcode::ADD, 30, 0, 0x10000, false, true),
+            Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            Instruction::new(Opcode::ADD, 29, 0, 0x216f_6f42, false, true),
+            Instruction::new(Opcode::SW, 29, 30, 4, false, true),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::WRITE as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, FD_PANIC, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0x10000, false, true),
+            Instruction::new(Opcode::ADD, 12, 0, 8, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HALT as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 1, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let program = Program::new(instructions, 0, 0);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        match runtime.run() {
+            Err(ExecutionError::GuestPanic { message, location }) => {
+                assert_eq!(location, "a:1");
+                assert_eq!(message, "Boo!");
+            }
+            result => panic!("expected a panic, got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn test_input_source() {
+        //     addi x5, x0, HINT_LEN
+        //     ecall
+        //     add x12, x0, x5
+        //     addi x5, x0, HINT_READ
+        //     addi x10, x0, 0x10000
+        //     addi x11, x0, 3
+        //     ecall
+        //     addi x5, x0, HINT_REMAINING
+        //     addi x10, x0, 0x20000
+        //     ecall
+        //     lw x13, 0(x10)
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HINT_LEN as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 12, 0, 5, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HINT_READ as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0x10000, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 3, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HINT_REMAINING as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0x20000, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::LW, 13, 10, 0, false, true),
+        ];
+        let program = Program::new(instructions, 0, 0);
+
+        // The elements are only produced when the guest reads past the written ones.
+        let pulled = AtomicUsize::new(0);
+        let elements = (0..10u8).map(|i| {
+            pulled.fetch_add(1, Ordering::Relaxed);
+            vec![i, i, i]
+        });
+        let context = SP1Context::builder().input_source(InputSource::new(elements)).build();
+        let mut runtime = Executor::with_context(program, SP1CoreOpts::default(), context);
+        runtime.run().unwrap();
+
+        assert_eq!(runtime.register(Register::X12), 3);
+        assert_eq!(runtime.register(Register::X13), 1);
+        assert_eq!(pulled.load(Ordering::Relaxed), 2);
+    }
+
+    /// A program making an ecall with the unknown syscall number `0x00_00_01_FF`, with `a0 = 7`
+    /// and `a1 = 5`, and copying the value left in `t0` to `x12`.
+    fn unknown_ecall_program() -> Program {
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, 0x00_00_01_FF, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 7, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 5, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 12, 0, 5, false, false),
+        ];
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_unknown_ecall() {
+        let mut runtime = Executor::new(unknown_ecall_program(), SP1CoreOpts::default());
+        assert!(matches!(runtime.run(), Err(ExecutionError::UnsupportedSyscall(0x00_00_01_FF))));
+    }
+
+    #[cfg(feature = "unsound_experimental")]
+    #[test]
+    fn test_unknown_ecall_policy() {
+        use std::sync::Arc;
+
+        use crate::{syscalls::SyscallContext, UnknownEcallPolicy};
+
+        let context =
+            SP1Context::builder().unknown_ecall_policy(UnknownEcallPolicy::Ignore).build();
+        let mut runtime =
+            Executor::with_context(unknown_ecall_program(), SP1CoreOpts::default(), context);
+        runtime.run().unwrap();
+        assert_eq!(runtime.register(Register::X12), 0x00_00_01_FF);
+
+        // The handler writes the product of its arguments to memory and returns their sum.
+        let policy = UnknownEcallPolicy::Forward(Arc::new(
+            |ctx: &mut SyscallContext, syscall_id: u32, a0: u32, a1: u32| {
+                assert_eq!(syscall_id, 0x00_00_01_FF);
+                ctx.mw(0x10000, a0 * a1);
+                Some(a0 + a1)
+            },
+        ));
+        let context = SP1Context::builder().unknown_ecall_policy(policy).build();
+        let mut runtime =
+            Executor::with_context(unknown_ecall_program(), SP1CoreOpts::default(), context);
+        runtime.run().unwrap();
+        assert_eq!(runtime.register(Register::X12), 12);
+        assert_eq!(runtime.word(0x10000), 35);
+    }
 }
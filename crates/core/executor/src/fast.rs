@@ -0,0 +1,580 @@
+//! A threaded-code interpreter for executing programs without recording events.
+//!
+//! In [`ExecutorMode::Simple`] nothing is traced, so most instructions only update the registers
+//! and the program counter. The program is decoded once into a table holding, for each instruction,
+//! a handler specialized to its opcode and its pre-decoded operands. Straight-line runs of such
+//! instructions are then dispatched through the table with the registers held in a local register
+//! file, instead of going through [`Executor::execute_instruction`] and the memory map for every
+//! register access. Loads and stores access memory directly through the executor, while atomics
+//! and syscalls fall back to the regular interpreter.
+
+use std::sync::Arc;
+
+use crate::{
+    align,
+    events::{MemoryAccessPosition, MemoryRecord},
+    ExecutionError, Executor, ExecutorMode, Instruction, Opcode, Register,
+    NUM_BYTE_LOOKUP_CHANNELS,
+};
+
+/// The handler of an instruction which only accesses the registers, returning the next program
+/// counter.
+type RegisterHandler = fn(&mut RegisterFile, &FastInstruction, u32, u32) -> u32;
+
+/// The handler of a load or a store, which also accesses memory.
+type MemoryHandler =
+    fn(&mut Executor<'_>, &mut RegisterFile, &FastInstruction) -> Result<(), ExecutionError>;
+
+/// The handler of an instruction.
+#[derive(Clone, Copy)]
+enum Handler {
+    /// An instruction which only accesses the registers.
+    Register(RegisterHandler),
+    /// A load or a store, after which execution always moves to the next instruction.
+    Memory(MemoryHandler),
+}
+
+/// An instruction decoded for the threaded-code interpreter.
+#[derive(Clone, Copy)]
+pub(crate) struct FastInstruction {
+    handler: Handler,
+    opcode: Opcode,
+    op_a: u32,
+    op_b: u32,
+    op_c: u32,
+    imm_b: bool,
+    imm_c: bool,
+}
+
+/// The registers held locally while running a block of instructions, together with the timestamp
+/// of their last access in the current shard.
+struct RegisterFile {
+    values: [u32; 32],
+    timestamps: [Option<u32>; 32],
+}
+
+impl RegisterFile {
+    #[inline]
+    fn read(&mut self, register: u32, clk: u32, position: MemoryAccessPosition) -> u32 {
+        let register = register as usize;
+        self.timestamps[register] = Some(clk + position as u32);
+        self.values[register]
+    }
+
+    /// Writes to a register, keeping `%x0` at zero.
+    #[inline]
+    fn write(&mut self, register: u32, value: u32, clk: u32) {
+        let register = register as usize;
+        self.timestamps[register] = Some(clk + MemoryAccessPosition::A as u32);
+        self.values[register] = if register == 0 { 0 } else { value };
+    }
+
+    /// Writes to a register through a store to its address, which unlike [`Self::write`] may write
+    /// to `%x0`.
+    #[inline]
+    fn store(&mut self, register: u32, value: u32, clk: u32) {
+        let register = register as usize;
+        self.timestamps[register] = Some(clk + MemoryAccessPosition::Memory as u32);
+        self.values[register] = value;
+    }
+
+    /// Reads the operands of an ALU instruction, in the same order as [`Executor::alu_rr`].
+    #[inline]
+    fn alu_operands(&mut self, instruction: &FastInstruction, clk: u32) -> (u32, u32) {
+        if !instruction.imm_c {
+            let c = self.read(instruction.op_c, clk, MemoryAccessPosition::C);
+            let b = self.read(instruction.op_b, clk, MemoryAccessPosition::B);
+            (b, c)
+        } else if !instruction.imm_b {
+            (self.read(instruction.op_b, clk, MemoryAccessPosition::B), instruction.op_c)
+        } else {
+            (instruction.op_b, instruction.op_c)
+        }
+    }
+
+    /// Reads the operands of a branch instruction, in the same order as
+    /// [`Executor::branch_rr`].
+    #[inline]
+    fn branch_operands(&mut self, instruction: &FastInstruction, clk: u32) -> (u32, u32) {
+        let b = self.read(instruction.op_b, clk, MemoryAccessPosition::B);
+        let a = self.read(instruction.op_a, clk, MemoryAccessPosition::A);
+        (a, b)
+    }
+}
+
+macro_rules! alu_handler {
+    ($name:ident, |$b:ident, $c:ident| $result:expr) => {
+        fn $name(
+            registers: &mut RegisterFile,
+            instruction: &FastInstruction,
+            pc: u32,
+            clk: u32,
+        ) -> u32 {
+            let ($b, $c) = registers.alu_operands(instruction, clk);
+            registers.write(instruction.op_a, $result, clk);
+            pc.wrapping_add(4)
+        }
+    };
+}
+
+macro_rules! branch_handler {
+    ($name:ident, |$a:ident, $b:ident| $condition:expr) => {
+        fn $name(
+            registers: &mut RegisterFile,
+            instruction: &FastInstruction,
+            pc: u32,
+            clk: u32,
+        ) -> u32 {
+            let ($a, $b) = registers.branch_operands(instruction, clk);
+            if $condition {
+                pc.wrapping_add(instruction.op_c)
+            } else {
+                pc.wrapping_add(4)
+            }
+        }
+    };
+}
+
+alu_handler!(add, |b, c| b.wrapping_add(c));
+alu_handler!(sub, |b, c| b.wrapping_sub(c));
+alu_handler!(xor, |b, c| b ^ c);
+alu_handler!(or, |b, c| b | c);
+alu_handler!(and, |b, c| b & c);
+alu_handler!(sll, |b, c| b.wrapping_shl(c));
+alu_handler!(srl, |b, c| b.wrapping_shr(c));
+alu_handler!(sra, |b, c| (b as i32).wrapping_shr(c) as u32);
+alu_handler!(slt, |b, c| u32::from((b as i32) < (c as i32)));
+alu_handler!(sltu, |b, c| u32::from(b < c));
+alu_handler!(mul, |b, c| b.wrapping_mul(c));
+alu_handler!(mulh, |b, c| (((b as i32) as i64).wrapping_mul((c as i32) as i64) >> 32) as u32);
+alu_handler!(mulhu, |b, c| ((b as u64).wrapping_mul(c as u64) >> 32) as u32);
+alu_handler!(mulhsu, |b, c| (((b as i32) as i64).wrapping_mul(c as i64) >> 32) as u32);
+alu_handler!(div, |b, c| if c == 0 { u32::MAX } else { (b as i32).wrapping_div(c as i32) as u32 });
+alu_handler!(divu, |b, c| if c == 0 { u32::MAX } else { b.wrapping_div(c) });
+alu_handler!(rem, |b, c| if c == 0 { b } else { (b as i32).wrapping_rem(c as i32) as u32 });
+alu_handler!(remu, |b, c| if c == 0 { b } else { b.wrapping_rem(c) });
+
+branch_handler!(beq, |a, b| a == b);
+branch_handler!(bne, |a, b| a != b);
+branch_handler!(blt, |a, b| (a as i32) < (b as i32));
+branch_handler!(bge, |a, b| (a as i32) >= (b as i32));
+branch_handler!(bltu, |a, b| a < b);
+branch_handler!(bgeu, |a, b| a >= b);
+
+fn jal(registers: &mut RegisterFile, instruction: &FastInstruction, pc: u32, clk: u32) -> u32 {
+    registers.write(instruction.op_a, pc + 4, clk);
+    pc.wrapping_add(instruction.op_b)
+}
+
+fn jalr(registers: &mut RegisterFile, instruction: &FastInstruction, pc: u32, clk: u32) -> u32 {
+    let b = registers.read(instruction.op_b, clk, MemoryAccessPosition::B);
+    registers.write(instruction.op_a, pc + 4, clk);
+    b.wrapping_add(instruction.op_c)
+}
+
+fn auipc(registers: &mut RegisterFile, instruction: &FastInstruction, pc: u32, clk: u32) -> u32 {
+    registers.write(instruction.op_a, pc.wrapping_add(instruction.op_b), clk);
+    pc.wrapping_add(4)
+}
+
+/// Reads the word at the aligned address `addr` for a load, from the register file if `addr` is
+/// the address of a register.
+#[inline]
+fn load_word(executor: &mut Executor<'_>, registers: &mut RegisterFile, addr: u32) -> u32 {
+    if addr < 32 {
+        registers.read(addr, executor.state.clk, MemoryAccessPosition::Memory)
+    } else {
+        executor.mr_cpu(addr, MemoryAccessPosition::Memory)
+    }
+}
+
+/// Writes the word at the aligned address `addr` for a store, to the register file if `addr` is
+/// the address of a register.
+#[inline]
+fn store_word(executor: &mut Executor<'_>, registers: &mut RegisterFile, addr: u32, value: u32) {
+    if addr < 32 {
+        registers.store(addr, value, executor.state.clk);
+    } else {
+        executor.mw_cpu(addr, value, MemoryAccessPosition::Memory);
+    }
+}
+
+/// Reads the operand of a load and the word holding the loaded value, in the same order as
+/// [`Executor::load_rr`], returning the address of the load and the word.
+#[inline]
+fn load_operands(
+    executor: &mut Executor<'_>,
+    registers: &mut RegisterFile,
+    instruction: &FastInstruction,
+) -> Result<(u32, u32), ExecutionError> {
+    let b = registers.read(instruction.op_b, executor.state.clk, MemoryAccessPosition::B);
+    let addr = b.wrapping_add(instruction.op_c);
+    let word = load_word(executor, registers, align(addr));
+    executor.check_memory_ceiling(addr)?;
+    Ok((addr, word))
+}
+
+/// Reads the operands of a store, in the same order as [`Executor::store_rr`], returning the
+/// stored value, the address of the store and the word it is stored into.
+#[inline]
+fn store_operands(
+    executor: &mut Executor<'_>,
+    registers: &mut RegisterFile,
+    instruction: &FastInstruction,
+) -> Result<(u32, u32, u32), ExecutionError> {
+    let clk = executor.state.clk;
+    let b = registers.read(instruction.op_b, clk, MemoryAccessPosition::B);
+    let a = registers.read(instruction.op_a, clk, MemoryAccessPosition::A);
+    let addr = b.wrapping_add(instruction.op_c);
+    let word = if align(addr) < 32 {
+        registers.values[align(addr) as usize]
+    } else {
+        executor.word(align(addr))
+    };
+    executor.check_memory_ceiling(addr)?;
+
+    // The stack pointer is read from the register file, as the memory map is not up to date.
+    if let Some(guard) = executor.stack_guard.filter(|guard| guard.contains(addr)) {
+        let sp = registers.values[Register::X2 as usize];
+        return Err(ExecutionError::StackOverflow {
+            pc: executor.state.pc,
+            depth: guard.depth(sp),
+        });
+    }
+    Ok((a, addr, word))
+}
+
+/// Extracts the half word at `addr` from `word`, failing if `addr` is not aligned to two bytes.
+#[inline]
+fn half_word(opcode: Opcode, addr: u32, word: u32) -> Result<u32, ExecutionError> {
+    if addr % 2 != 0 {
+        return Err(ExecutionError::InvalidMemoryAccess(opcode, addr));
+    }
+    Ok(if (addr >> 1) % 2 == 0 { word & 0x0000_FFFF } else { word >> 16 })
+}
+
+fn lb(
+    executor: &mut Executor<'_>,
+    registers: &mut RegisterFile,
+    instruction: &FastInstruction,
+) -> Result<(), ExecutionError> {
+    let (addr, word) = load_operands(executor, registers, instruction)?;
+    let value = word.to_le_bytes()[(addr % 4) as usize];
+    registers.write(instruction.op_a, ((value as i8) as i32) as u32, executor.state.clk);
+    Ok(())
+}
+
+fn lbu(
+    executor: &mut Executor<'_>,
+    registers: &mut RegisterFile,
+    instruction: &FastInstruction,
+) -> Result<(), ExecutionError> {
+    let (addr, word) = load_operands(executor, registers, instruction)?;
+    let value = word.to_le_bytes()[(addr % 4) as usize];
+    registers.write(instruction.op_a, value as u32, executor.state.clk);
+    Ok(())
+}
+
+fn lh(
+    executor: &mut Executor<'_>,
+    registers: &mut RegisterFile,
+    instruction: &FastInstruction,
+) -> Result<(), ExecutionError> {
+    let (addr, word) = load_operands(executor, registers, instruction)?;
+    let value = half_word(Opcode::LH, addr, word)?;
+    registers.write(instruction.op_a, ((value as i16) as i32) as u32, executor.state.clk);
+    Ok(())
+}
+
+fn lhu(
+    executor: &mut Executor<'_>,
+    registers: &mut RegisterFile,
+    instruction: &FastInstruction,
+) -> Result<(), ExecutionError> {
+    let (addr, word) = load_operands(executor, registers, instruction)?;
+    let value = half_word(Opcode::LHU, addr, word)?;
+    registers.write(instruction.op_a, value, executor.state.clk);
+    Ok(())
+}
+
+fn lw(
+    executor: &mut Executor<'_>,
+    registers: &mut RegisterFile,
+    instruction: &FastInstruction,
+) -> Result<(), ExecutionError> {
+    let (addr, word) = load_operands(executor, registers, instruction)?;
+    if addr % 4 != 0 {
+        return Err(ExecutionError::InvalidMemoryAccess(Opcode::LW, addr));
+    }
+    registers.write(instruction.op_a, word, executor.state.clk);
+    Ok(())
+}
+
+fn sb(
+    executor: &mut Executor<'_>,
+    registers: &mut RegisterFile,
+    instruction: &FastInstruction,
+) -> Result<(), ExecutionError> {
+    let (a, addr, word) = store_operands(executor, registers, instruction)?;
+    let shift = (addr % 4) * 8;
+    let value = ((a & 0x0000_00FF) << shift) + (word & !(0x0000_00FF << shift));
+    store_word(executor, registers, align(addr), value);
+    Ok(())
+}
+
+fn sh(
+    executor: &mut Executor<'_>,
+    registers: &mut RegisterFile,
+    instruction: &FastInstruction,
+) -> Result<(), ExecutionError> {
+    let (a, addr, word) = store_operands(executor, registers, instruction)?;
+    if addr % 2 != 0 {
+        return Err(ExecutionError::InvalidMemoryAccess(Opcode::SH, addr));
+    }
+    let shift = ((addr >> 1) % 2) * 16;
+    let value = ((a & 0x0000_FFFF) << shift) + (word & !(0x0000_FFFF << shift));
+    store_word(executor, registers, align(addr), value);
+    Ok(())
+}
+
+fn sw(
+    executor: &mut Executor<'_>,
+    registers: &mut RegisterFile,
+    instruction: &FastInstruction,
+) -> Result<(), ExecutionError> {
+    let (a, addr, _) = store_operands(executor, registers, instruction)?;
+    if addr % 4 != 0 {
+        return Err(ExecutionError::InvalidMemoryAccess(Opcode::SW, addr));
+    }
+    store_word(executor, registers, align(addr), a);
+    Ok(())
+}
+
+impl FastInstruction {
+    /// Decodes an instruction, or returns `None` if it must be run by the regular interpreter.
+    fn decode(instruction: &Instruction) -> Option<Self> {
+        let handler = match instruction.opcode {
+            Opcode::ADD => Handler::Register(add),
+            Opcode::SUB => Handler::Register(sub),
+            Opcode::XOR => Handler::Register(xor),
+            Opcode::OR => Handler::Register(or),
+            Opcode::AND => Handler::Register(and),
+            Opcode::SLL => Handler::Register(sll),
+            Opcode::SRL => Handler::Register(srl),
+            Opcode::SRA => Handler::Register(sra),
+            Opcode::SLT => Handler::Register(slt),
+            Opcode::SLTU => Handler::Register(sltu),
+            Opcode::MUL => Handler::Register(mul),
+            Opcode::MULH => Handler::Register(mulh),
+            Opcode::MULHU => Handler::Register(mulhu),
+            Opcode::MULHSU => Handler::Register(mulhsu),
+            Opcode::DIV => Handler::Register(div),
+            Opcode::DIVU => Handler::Register(divu),
+            Opcode::REM => Handler::Register(rem),
+            Opcode::REMU => Handler::Register(remu),
+            Opcode::BEQ => Handler::Register(beq),
+            Opcode::BNE => Handler::Register(bne),
+            Opcode::BLT => Handler::Register(blt),
+            Opcode::BGE => Handler::Register(bge),
+            Opcode::BLTU => Handler::Register(bltu),
+            Opcode::BGEU => Handler::Register(bgeu),
+            Opcode::JAL => Handler::Register(jal),
+            Opcode::JALR => Handler::Register(jalr),
+            Opcode::AUIPC => Handler::Register(auipc),
+            Opcode::LB => Handler::Memory(lb),
+            Opcode::LBU => Handler::Memory(lbu),
+            Opcode::LH => Handler::Memory(lh),
+            Opcode::LHU => Handler::Memory(lhu),
+            Opcode::LW => Handler::Memory(lw),
+            Opcode::SB => Handler::Memory(sb),
+            Opcode::SH => Handler::Memory(sh),
+            Opcode::SW => Handler::Memory(sw),
+            _ => return None,
+        };
+        Some(Self {
+            handler,
+            opcode: instruction.opcode,
+            op_a: instruction.op_a,
+            op_b: instruction.op_b,
+            op_c: instruction.op_c,
+            imm_b: instruction.imm_b,
+            imm_c: instruction.imm_c,
+        })
+    }
+}
+
+impl<'a> Executor<'a> {
+    /// Whether the threaded-code interpreter can be used for the next instructions.
+    ///
+    /// It is only used when no events, checkpoints, memory history or traces are recorded, and no
+    /// observer is set, so that a memory access only has to update the memory map.
+    pub(crate) fn fast_path_enabled(&self) -> bool {
+        self.executor_mode == ExecutorMode::Simple
+            && !self.unconstrained
+            && self.trace_buf.is_none()
+            && self.memory_history.is_none()
+            && self.syscall_tracer.is_none()
+            && self.observer.is_none()
+    }
+
+    /// Executes a run of at least one instruction, returning whether the program has finished.
+    ///
+    /// The run stops before an instruction that the threaded-code interpreter does not handle, and
+    /// after the last instruction of a shard.
+    pub(crate) fn execute_fast(&mut self) -> Result<bool, ExecutionError> {
+        let table = self.fast_program.get_or_insert_with(|| {
+            self.program.instructions.iter().map(FastInstruction::decode).collect::<Vec<_>>().into()
+        });
+        let table = Arc::clone(table);
+
+        let mut registers = RegisterFile { values: [0; 32], timestamps: [None; 32] };
+        for register in 0..32 {
+            registers.values[register as usize] = match self.state.memory.get(register) {
+                Some(record) => record.value,
+                None => *self.state.uninitialized_memory.get(register).unwrap_or(&0),
+            };
+        }
+
+        let pc_base = self.program.pc_base;
+        let num_instructions = (table.len() * 4) as u32;
+        let mut executed = false;
+        loop {
+            let index = ((self.state.pc - pc_base) / 4) as usize;
+            let Some(instruction) = table[index] else {
+                break;
+            };
+
+            if self.print_report {
+                self.report.opcode_counts[instruction.opcode] += 1;
+            }
+            if self.state.global_clk % 10_000_000 == 0 {
+                log::info!("clk = {} pc = 0x{:x?}", self.state.global_clk, self.state.pc);
+            }
+
+            let pc = self.state.pc;
+            self.state.pc = match instruction.handler {
+                Handler::Register(handler) => {
+                    handler(&mut registers, &instruction, pc, self.state.clk)
+                }
+                Handler::Memory(handler) => {
+                    let result = handler(self, &mut registers, &instruction)
+                        .and_then(|()| self.check_memory_limit(pc));
+                    if let Err(error) = result {
+                        self.flush_registers(&registers);
+                        return Err(error);
+                    }
+                    pc.wrapping_add(4)
+                }
+            };
+            self.state.clk += 4;
+            self.state.channel = (self.state.channel + 1) % NUM_BYTE_LOOKUP_CHANNELS;
+            self.state.global_clk += 1;
+            executed = true;
+
+            // The registers are written back before moving to the next shard, so that their
+            // records carry the shard they were accessed in.
+            let end_of_shard = self.max_syscall_cycles + self.state.clk >= self.shard_size;
+            if end_of_shard {
+                self.flush_registers(&registers);
+                self.state.current_shard += 1;
+                self.state.clk = 0;
+                self.state.channel = 0;
+                self.bump_record();
+            }
+
+            if let Some(max_cycles) = self.max_cycles {
+                if self.state.global_clk >= max_cycles {
+                    if !end_of_shard {
+                        self.flush_registers(&registers);
+                    }
+                    return Err(ExecutionError::ExceededCycleLimit(max_cycles));
+                }
+            }
+
+            let done =
+                self.state.pc == 0 || self.state.pc.wrapping_sub(pc_base) >= num_instructions;
+            if done || end_of_shard {
+                if !end_of_shard {
+                    self.flush_registers(&registers);
+                }
+                return Ok(done);
+            }
+        }
+
+        if !executed {
+            return self.execute_cycle();
+        }
+        self.flush_registers(&registers);
+        Ok(false)
+    }
+
+    /// Writes the registers accessed in a run of the threaded-code interpreter back to memory.
+    fn flush_registers(&mut self, registers: &RegisterFile) {
+        let shard = self.shard();
+        for register in 0..32 {
+            if let Some(timestamp) = registers.timestamps[register] {
+                let value = registers.values[register];
+                self.state.memory.insert(register as u32, MemoryRecord { value, shard, timestamp });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_stark::SP1CoreOpts;
+
+    use crate::{
+        programs::tests::{
+            atomic_memory_program, fibonacci_program, simple_memory_program,
+            ssz_withdrawals_program,
+        },
+        Executor, Program,
+    };
+
+    /// Runs `program` with and without the threaded-code interpreter and compares the final
+    /// states, including the shard and timestamp of the last access of every register and memory
+    /// word.
+    fn assert_fast_matches_trace(program: Program) {
+        let mut opts = SP1CoreOpts::default();
+        opts.shard_size = 1 << 12;
+
+        let mut fast = Executor::new(program.clone(), opts.clone());
+        fast.run_fast().unwrap();
+        let mut traced = Executor::new(program, opts);
+        traced.run().unwrap();
+
+        assert_eq!(fast.state.pc, traced.state.pc);
+        assert_eq!(fast.state.global_clk, traced.state.global_clk);
+        assert_eq!(fast.state.current_shard, traced.state.current_shard);
+        assert_eq!(fast.registers(), traced.registers());
+        assert_eq!(fast.state.touched_memory_words, traced.state.touched_memory_words);
+        let record = |executor: &Executor, addr| {
+            executor.state.memory.get(addr).map(|r| (r.value, r.shard, r.timestamp))
+        };
+        for addr in fast.state.memory.keys().chain(traced.state.memory.keys()) {
+            assert_eq!(record(&fast, addr), record(&traced, addr));
+        }
+        assert_eq!(fast.report, traced.report);
+    }
+
+    #[test]
+    fn test_fast_fibonacci() {
+        assert_fast_matches_trace(fibonacci_program());
+    }
+
+    #[test]
+    fn test_fast_ssz_withdrawals() {
+        assert_fast_matches_trace(ssz_withdrawals_program());
+    }
+
+    #[test]
+    fn test_fast_memory() {
+        assert_fast_matches_trace(simple_memory_program());
+    }
+
+    #[test]
+    fn test_fast_atomic_memory() {
+        assert_fast_matches_trace(atomic_memory_program());
+    }
+}
@@ -4,6 +4,7 @@ use std::sync::{Arc, RwLock, RwLockWriteGuard};
 
 use hashbrown::HashMap;
 use sp1_curves::k256::{Invert, RecoveryId, Signature, VerifyingKey};
+use sp1_primitives::io::PublicValuesDomain;
 
 use crate::Executor;
 
@@ -13,6 +14,24 @@ pub type BoxedHook<'a> = Arc<RwLock<dyn Hook + Send + Sync + 'a>>;
 /// The file descriptor through which to access `hook_ecrecover`.
 pub const FD_ECRECOVER_HOOK: u32 = 5;
 
+/// The file descriptor through which the guest reports the location and message of a panic.
+pub const FD_PANIC: u32 = 6;
+
+/// The file descriptor through which to access `hook_fs_read`.
+pub const FD_FS_READ: u32 = 7;
+
+/// The file descriptor through which to access `hook_public_values_domain`.
+pub const FD_PUBLIC_VALUES_DOMAIN: u32 = 8;
+
+/// The file descriptor through which to access `hook_env_var`.
+pub const FD_ENV_VAR: u32 = 9;
+
+/// The file descriptor through which to access `hook_args`.
+pub const FD_ARGS: u32 = 10;
+
+/// The file descriptor through which to access `hook_cycle_count`.
+pub const FD_CYCLE_COUNT: u32 = 11;
+
 /// A runtime hook. May be called during execution by writing to a specified file descriptor,
 /// accepting and returning arbitrary data.
 pub trait Hook {
@@ -76,6 +95,11 @@ impl<'a> Default for HookRegistry<'a> {
             // Note: To ensure any `fd` value is synced with `zkvm/precompiles/src/io.rs`,
             // add an assertion to the test `hook_fds_match` below.
             (FD_ECRECOVER_HOOK, hookify(hook_ecrecover)),
+            (FD_FS_READ, hookify(hook_fs_read)),
+            (FD_PUBLIC_VALUES_DOMAIN, hookify(hook_public_values_domain)),
+            (FD_ENV_VAR, hookify(hook_env_var)),
+            (FD_ARGS, hookify(hook_args)),
+            (FD_CYCLE_COUNT, hookify(hook_cycle_count)),
         ]);
 
         Self { table }
@@ -141,6 +165,68 @@ pub fn hook_ecrecover(_: HookEnv, buf: &[u8]) -> Vec<Vec<u8>> {
     vec![bytes.to_vec(), s_inverse.to_bytes().to_vec()]
 }
 
+/// Looks up a file registered with `SP1Stdin::write_file` by its path.
+///
+/// Returns `[1]` followed by the contents of the file if it exists, and `[0]` otherwise.
+///
+/// WARNING: The contents of the file are not constrained by the zkVM. Programs that depend on
+/// them must commit to them or check them against a committed digest.
+#[must_use]
+pub fn hook_fs_read(env: HookEnv, buf: &[u8]) -> Vec<Vec<u8>> {
+    let path = String::from_utf8_lossy(buf);
+    match env.runtime.state.files.get(path.as_ref()) {
+        Some(data) => vec![vec![1], data.to_vec()],
+        None => vec![vec![0]],
+    }
+}
+
+/// Returns the domain separator and nonce registered with `SP1Stdin::write_public_values_domain`,
+/// encoded with [`PublicValuesDomain::to_bytes`], or zeros if none was registered.
+///
+/// The program writes them to its public values, so they are constrained by the public values
+/// digest rather than by the zkVM.
+#[must_use]
+pub fn hook_public_values_domain(env: HookEnv, _: &[u8]) -> Vec<Vec<u8>> {
+    let domain = env.runtime.state.public_values_domain.unwrap_or_default();
+    vec![domain.to_bytes().to_vec()]
+}
+
+/// Looks up an environment variable set with `SP1Stdin::env` by its name.
+///
+/// Returns `[1]` followed by the value of the variable if it is set, and `[0]` otherwise.
+///
+/// WARNING: The value is not constrained by the zkVM.
+#[must_use]
+pub fn hook_env_var(env: HookEnv, buf: &[u8]) -> Vec<Vec<u8>> {
+    let key = String::from_utf8_lossy(buf);
+    match env.runtime.state.env_vars.get(key.as_ref()) {
+        Some(value) => vec![vec![1], value.as_bytes().to_vec()],
+        None => vec![vec![0]],
+    }
+}
+
+/// Returns the arguments set with `SP1Stdin::args`: their number as a little-endian `u32`,
+/// followed by each argument.
+///
+/// WARNING: The arguments are not constrained by the zkVM.
+#[must_use]
+pub fn hook_args(env: HookEnv, _: &[u8]) -> Vec<Vec<u8>> {
+    let args = &env.runtime.state.args;
+    let count = u32::try_from(args.len()).expect("too many arguments");
+    std::iter::once(count.to_le_bytes().to_vec())
+        .chain(args.iter().map(|arg| arg.as_bytes().to_vec()))
+        .collect()
+}
+
+/// Returns the number of cycles executed so far as a little-endian `u64`, from which the guest
+/// derives `sp1_zkvm::time::Instant`.
+///
+/// WARNING: The cycle count is not constrained by the zkVM.
+#[must_use]
+pub fn hook_cycle_count(env: HookEnv, _: &[u8]) -> Vec<Vec<u8>> {
+    vec![env.runtime.state.global_clk.to_le_bytes().to_vec()]
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -149,6 +235,60 @@ pub mod tests {
     pub fn hook_fds_match() {
         use sp1_zkvm::lib::io;
         assert_eq!(FD_ECRECOVER_HOOK, io::FD_ECRECOVER_HOOK);
+        assert_eq!(FD_FS_READ, io::FD_FS_READ);
+        assert_eq!(FD_PUBLIC_VALUES_DOMAIN, io::FD_PUBLIC_VALUES_DOMAIN);
+        assert_eq!(FD_ENV_VAR, io::FD_ENV_VAR);
+        assert_eq!(FD_ARGS, io::FD_ARGS);
+        assert_eq!(FD_CYCLE_COUNT, io::FD_CYCLE_COUNT);
+    }
+
+    #[test]
+    pub fn hook_fs_read_finds_registered_files() {
+        use sp1_stark::SP1CoreOpts;
+
+        use crate::programs::tests::simple_program;
+
+        let mut runtime = Executor::new(simple_program(), SP1CoreOpts::default());
+        runtime.state.files.insert("data/input.txt".to_string(), b"hello".as_slice().into());
+
+        let found = runtime.hook(FD_FS_READ, b"data/input.txt").unwrap();
+        assert_eq!(found, vec![vec![1], b"hello".to_vec()]);
+        let missing = runtime.hook(FD_FS_READ, b"data/missing.txt").unwrap();
+        assert_eq!(missing, vec![vec![0]]);
+    }
+
+    #[test]
+    pub fn hook_env_var_and_args_return_registered_values() {
+        use sp1_stark::SP1CoreOpts;
+
+        use crate::programs::tests::simple_program;
+
+        let mut runtime = Executor::new(simple_program(), SP1CoreOpts::default());
+        runtime.write_env_vars(&HashMap::from([("LOG_LEVEL".to_string(), "debug".to_string())]));
+        runtime.write_args(&["--verbose".to_string(), "input.txt".to_string()]);
+
+        let found = runtime.hook(FD_ENV_VAR, b"LOG_LEVEL").unwrap();
+        assert_eq!(found, vec![vec![1], b"debug".to_vec()]);
+        let missing = runtime.hook(FD_ENV_VAR, b"HOME").unwrap();
+        assert_eq!(missing, vec![vec![0]]);
+
+        let args = runtime.hook(FD_ARGS, &[]).unwrap();
+        assert_eq!(
+            args,
+            vec![2u32.to_le_bytes().to_vec(), b"--verbose".to_vec(), b"input.txt".to_vec()]
+        );
+    }
+
+    #[test]
+    pub fn hook_cycle_count_returns_global_clk() {
+        use sp1_stark::SP1CoreOpts;
+
+        use crate::programs::tests::simple_program;
+
+        let mut runtime = Executor::new(simple_program(), SP1CoreOpts::default());
+        runtime.state.global_clk = 1234;
+        let cycles = runtime.hook(FD_CYCLE_COUNT, &[]).unwrap();
+        assert_eq!(cycles, vec![1234u64.to_le_bytes().to_vec()]);
     }
 
     #[test]
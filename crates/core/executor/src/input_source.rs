@@ -0,0 +1,45 @@
+//! Inputs supplied to the guest while it runs.
+//!
+//! By default, the whole stdin is written to the executor before execution. When an
+//! [`InputSource`] is set in the [`SP1Context`](crate::SP1Context), the executor instead pulls the
+//! next element from it whenever the guest reads past the inputs written so far, so the host can
+//! produce inputs lazily: for example, to answer the guest in an interactive protocol, or to feed
+//! an input that does not fit in memory.
+
+use std::sync::{mpsc::Receiver, Arc, Mutex};
+
+/// A source of stdin elements, pulled one at a time as the guest reads them.
+///
+/// Each element is read by the guest like one written with `SP1Stdin::write_vec`, so typed values
+/// read with `sp1_zkvm::io::read` must be serialized with `bincode`.
+#[derive(Clone)]
+pub struct InputSource<'a> {
+    source: Arc<Mutex<dyn Iterator<Item = Vec<u8>> + Send + 'a>>,
+}
+
+impl<'a> InputSource<'a> {
+    /// Create a source pulling the elements of `elements`. The source is exhausted when the
+    /// iterator ends.
+    #[must_use]
+    pub fn new<I>(elements: I) -> Self
+    where
+        I: IntoIterator<Item = Vec<u8>>,
+        I::IntoIter: Send + 'a,
+    {
+        Self { source: Arc::new(Mutex::new(elements.into_iter())) }
+    }
+
+    /// Create a source receiving the elements from a channel. Execution blocks until the next
+    /// element is sent, and the source is exhausted when every sender is dropped.
+    ///
+    /// This is the way to feed inputs produced by another thread or by an async task.
+    #[must_use]
+    pub fn from_channel(receiver: Receiver<Vec<u8>>) -> Self {
+        Self::new(receiver)
+    }
+
+    /// Pull the next element, or `None` if the source is exhausted.
+    pub fn next_element(&self) -> Option<Vec<u8>> {
+        self.source.lock().unwrap().next()
+    }
+}
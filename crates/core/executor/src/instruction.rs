@@ -88,6 +88,26 @@ impl Instruction {
         )
     }
 
+    /// Returns if the instruction is an atomic memory instruction.
+    ///
+    #[must_use]
+    pub const fn is_atomic_instruction(&self) -> bool {
+        matches!(
+            self.opcode,
+            Opcode::AMOSWAP
+                | Opcode::AMOADD
+                | Opcode::AMOXOR
+                | Opcode::AMOAND
+                | Opcode::AMOOR
+                | Opcode::AMOMIN
+                | Opcode::AMOMAX
+                | Opcode::AMOMINU
+                | Opcode::AMOMAXU
+                | Opcode::SC
+                | Opcode::LR
+        )
+    }
+
     /// Returns if the instruction is a branch instruction.
     #[must_use]
     pub const fn is_branch_instruction(&self) -> bool {
@@ -1,6 +1,7 @@
-use std::io::Read;
+use std::{io::Read, sync::Arc};
 
 use serde::{de::DeserializeOwned, Serialize};
+use sp1_primitives::io::PublicValuesDomain;
 use sp1_stark::{baby_bear_poseidon2::BabyBearPoseidon2, ShardProof, StarkVerifyingKey};
 
 use super::Executor;
@@ -32,6 +33,37 @@ impl<'a> Executor<'a> {
         }
     }
 
+    /// Register large inputs that the program maps into its memory one page at a time.
+    pub fn write_paged_inputs(&mut self, inputs: &[Arc<[u8]>]) {
+        self.state.paged_inputs.extend(inputs.iter().cloned());
+    }
+
+    /// Register named files that the program reads with `sp1_zkvm::fs::read`.
+    pub fn write_files<'b>(
+        &mut self,
+        files: impl IntoIterator<Item = (&'b String, &'b Arc<[u8]>)>,
+    ) {
+        self.state.files.extend(files.into_iter().map(|(path, data)| (path.clone(), data.clone())));
+    }
+
+    /// Set the domain separator and nonce that the program writes at the start of its public
+    /// values when built with the `replay-protection` feature of `sp1-zkvm`.
+    pub fn write_public_values_domain(&mut self, domain: Option<PublicValuesDomain>) {
+        self.state.public_values_domain = domain;
+    }
+
+    /// Set environment variables that the program reads with `sp1_zkvm::env::var`.
+    pub fn write_env_vars<'b>(&mut self, vars: impl IntoIterator<Item = (&'b String, &'b String)>) {
+        self.state
+            .env_vars
+            .extend(vars.into_iter().map(|(key, value)| (key.clone(), value.clone())));
+    }
+
+    /// Set the arguments that the program reads with `sp1_zkvm::env::args`.
+    pub fn write_args(&mut self, args: &[String]) {
+        self.state.args = args.to_vec();
+    }
+
     /// Write a proof and verifying key to the proof stream.
     pub fn write_proof(
         &mut self,
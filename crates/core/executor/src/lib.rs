@@ -19,32 +19,59 @@
 #![allow(clippy::explicit_iter_loop)]
 #![warn(missing_docs)]
 
+#[cfg(feature = "riscv-tests")]
+mod checkpoint;
+pub mod conformance;
 mod context;
 mod disassembler;
+mod ecall_policy;
 pub mod events;
 mod executor;
+mod fast;
 mod hook;
+mod input_source;
 mod instruction;
 mod io;
 mod memory;
+mod memory_history;
+mod memory_image;
+mod monitor;
+mod observer;
 mod opcode;
+mod privacy;
 mod program;
 #[cfg(any(test, feature = "programs"))]
 pub mod programs;
 mod record;
+pub mod reference;
 mod register;
 mod report;
+mod rom;
+mod stack;
 mod state;
 pub mod subproof;
+mod syscall_trace;
 pub mod syscalls;
 
+pub use checkpoint::*;
 pub use context::*;
+pub use disassembler::ElfError;
+pub use ecall_policy::*;
 pub use executor::*;
 pub use hook::*;
+pub use input_source::*;
 pub use instruction::*;
+pub use memory_history::*;
+pub use memory_image::*;
+pub use monitor::*;
+pub use observer::*;
 pub use opcode::*;
+pub use privacy::*;
 pub use program::*;
 pub use record::*;
 pub use register::*;
 pub use report::*;
+pub use rom::*;
+pub use stack::*;
 pub use state::*;
+pub use syscall_trace::*;
@@ -0,0 +1,158 @@
+//! A history of the memory accesses of an execution, for inspecting guest memory over time.
+//!
+//! The memory records emitted by the executor only link each access to the previous one, which
+//! makes it hard to find out where a corrupted value came from. When
+//! [`Executor::memory_history`](crate::Executor::memory_history) is set, the executor also
+//! records every access to memory and registers in a [`MemoryHistory`], which can then be queried
+//! for the value of an address at a given timestamp, or for the last write to it.
+//!
+//! Timestamps are `(shard, clk)` pairs, ordered lexicographically, as in the memory records.
+//! Accesses made in unconstrained mode are not recorded, since their effects are reverted.
+
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::events::MemoryRecordEnum;
+
+/// An access to a memory address, with the program counter of the instruction that made it.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MemoryAccess {
+    /// The program counter of the instruction, or of the syscall, that made the access.
+    pub pc: u32,
+    /// The memory record of the access.
+    pub record: MemoryRecordEnum,
+}
+
+/// The accesses to each memory address during an execution, in the order they were made.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryHistory {
+    accesses: HashMap<u32, Vec<MemoryAccess>>,
+}
+
+impl MemoryAccess {
+    /// The shard and timestamp of the access.
+    #[must_use]
+    pub const fn timestamp(&self) -> (u32, u32) {
+        match self.record {
+            MemoryRecordEnum::Read(record) => (record.shard, record.timestamp),
+            MemoryRecordEnum::Write(record) => (record.shard, record.timestamp),
+        }
+    }
+
+    /// The value of the address after the access.
+    #[must_use]
+    pub const fn value(&self) -> u32 {
+        self.record.value()
+    }
+
+    /// The value of the address before the access.
+    #[must_use]
+    pub const fn prev_value(&self) -> u32 {
+        match self.record {
+            MemoryRecordEnum::Read(record) => record.value,
+            MemoryRecordEnum::Write(record) => record.prev_value,
+        }
+    }
+
+    /// Whether the access is a write.
+    #[must_use]
+    pub const fn is_write(&self) -> bool {
+        matches!(self.record, MemoryRecordEnum::Write(_))
+    }
+}
+
+impl MemoryHistory {
+    /// Create an empty history.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an access to `addr`.
+    pub(crate) fn record(&mut self, addr: u32, pc: u32, record: MemoryRecordEnum) {
+        self.accesses.entry(addr).or_default().push(MemoryAccess { pc, record });
+    }
+
+    /// The accesses to `addr`, ordered by timestamp.
+    #[must_use]
+    pub fn accesses(&self, addr: u32) -> &[MemoryAccess] {
+        self.accesses.get(&addr).map_or(&[], Vec::as_slice)
+    }
+
+    /// The value of `addr` once every access to it up to and including timestamp `clk` of `shard`
+    /// has been made.
+    ///
+    /// Returns `None` if `addr` was never accessed, since its value is then not known to the
+    /// history.
+    #[must_use]
+    pub fn value_at(&self, addr: u32, shard: u32, clk: u32) -> Option<u32> {
+        let accesses = self.accesses(addr);
+        let first = accesses.first()?;
+        let end = accesses.partition_point(|access| access.timestamp() <= (shard, clk));
+        match end.checked_sub(1) {
+            Some(last) => Some(accesses[last].value()),
+            None => Some(first.prev_value()),
+        }
+    }
+
+    /// The last write to `addr` strictly before timestamp `clk` of `shard`, if any.
+    #[must_use]
+    pub fn last_write_before(&self, addr: u32, shard: u32, clk: u32) -> Option<&MemoryAccess> {
+        let accesses = self.accesses(addr);
+        let end = accesses.partition_point(|access| access.timestamp() < (shard, clk));
+        accesses[..end].iter().rev().find(|access| access.is_write())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_stark::SP1CoreOpts;
+
+    use super::*;
+    use crate::{Executor, Instruction, Opcode, Program};
+
+    #[test]
+    fn test_memory_history() {
+        //     addi x29, x0, 5
+        //     addi x30, x0, 0x100
+        //     sw x29, 0(x30)
+        //     addi x29, x0, 7
+        //     sw x29, 0(x30)
+        //     lw x31, 0(x30)
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 5, false, true),
+            Instruction::new(Opcode::ADD, 30, 0, 0x100, false, true),
+            Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            Instruction::new(Opcode::ADD, 29, 0, 7, false, true),
+            Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            Instruction::new(Opcode::LW, 31, 30, 0, false, true),
+        ];
+        let program = Program::new(instructions, 0, 0);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.memory_history = Some(MemoryHistory::new());
+        runtime.run().unwrap();
+        let history = runtime.memory_history.unwrap();
+
+        let accesses = history.accesses(0x100);
+        assert_eq!(accesses.len(), 3);
+        assert_eq!(accesses.iter().map(|access| access.pc).collect::<Vec<_>>(), [8, 16, 20]);
+        let (shard, first) = accesses[0].timestamp();
+        let (_, second) = accesses[1].timestamp();
+
+        assert_eq!(history.value_at(0x100, shard, first - 1), Some(0));
+        assert_eq!(history.value_at(0x100, shard, first), Some(5));
+        assert_eq!(history.value_at(0x100, shard, second - 1), Some(5));
+        assert_eq!(history.value_at(0x100, shard, second), Some(7));
+        assert_eq!(history.value_at(0x104, shard, second), None);
+
+        assert!(history.last_write_before(0x100, shard, first).is_none());
+        let write = history.last_write_before(0x100, shard, second).unwrap();
+        assert_eq!((write.pc, write.value()), (8, 5));
+        let write = history.last_write_before(0x100, shard + 1, 0).unwrap();
+        assert_eq!((write.pc, write.value()), (16, 7));
+
+        // Registers are recorded at their index.
+        let write = history.last_write_before(31, shard + 1, 0).unwrap();
+        assert_eq!((write.pc, write.value()), (20, 7));
+    }
+}
@@ -0,0 +1,345 @@
+//! The initial memory image of a program.
+
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+use serde::{Deserialize, Serialize};
+use sp1_primitives::consts::WORD_SIZE;
+use tiny_keccak::{Hasher, Keccak};
+
+/// The initial memory image of a program, mapping word-aligned addresses to words.
+///
+/// The image is stored as a set of intervals of contiguous words, each made of explicit words
+/// followed by a run of zero words. This keeps the zero-initialized sections of a program, such as
+/// its BSS, in constant space, whereas a flat map would store an entry for each of their words.
+///
+/// The runs of zeros are never expanded: the executor only tracks the words of the
+/// [zero region](Self::zero_region) that the program touches, and these words are initialized
+/// like any other memory, with the initial value constrained to be zero, instead of by the
+/// program memory chip.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryImage {
+    /// The intervals of the image, keyed by their start address.
+    intervals: BTreeMap<u32, Interval>,
+    /// The total number of words in the image.
+    len: usize,
+}
+
+/// A contiguous run of words, made of `words` followed by `zeros` zero words.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Interval {
+    words: Vec<u32>,
+    zeros: u32,
+}
+
+impl Interval {
+    /// The number of words in the interval.
+    fn len(&self) -> u32 {
+        self.words.len() as u32 + self.zeros
+    }
+
+    /// The word at index `i` of the interval.
+    fn get(&self, i: u32) -> u32 {
+        self.words.get(i as usize).copied().unwrap_or(0)
+    }
+
+    /// The address following the last word of the interval starting at `start`, if any.
+    fn end(&self, start: u32) -> Option<u32> {
+        start.checked_add(self.len() * WORD_SIZE as u32)
+    }
+}
+
+impl MemoryImage {
+    /// Create an empty memory image.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { intervals: BTreeMap::new(), len: 0 }
+    }
+
+    /// The number of words in the image.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the image is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The interval containing `addr`, with its start address.
+    fn interval(&self, addr: u32) -> Option<(u32, &Interval)> {
+        let (&start, interval) = self.intervals.range(..=addr).next_back()?;
+        if interval.end(start).is_some_and(|end| addr >= end) {
+            return None;
+        }
+        Some((start, interval))
+    }
+
+    /// The word at `addr`, if it is in the image.
+    #[must_use]
+    pub fn get(&self, addr: u32) -> Option<u32> {
+        let (start, interval) = self.interval(addr)?;
+        Some(interval.get((addr - start) / WORD_SIZE as u32))
+    }
+
+    /// Whether `addr` is in the image.
+    #[must_use]
+    pub fn contains(&self, addr: u32) -> bool {
+        self.interval(addr).is_some()
+    }
+
+    /// Whether `addr` is an explicit word of the image, rather than part of a run of zeros.
+    #[must_use]
+    pub fn has_word(&self, addr: u32) -> bool {
+        self.interval(addr).is_some_and(|(start, interval)| {
+            ((addr - start) / WORD_SIZE as u32) < interval.words.len() as u32
+        })
+    }
+
+    /// The number of explicit words of the image.
+    #[must_use]
+    pub fn num_words(&self) -> usize {
+        self.intervals.values().map(|interval| interval.words.len()).sum()
+    }
+
+    /// The first and last addresses of the runs of zeros of the image, if it has any.
+    ///
+    /// Every word of this region which is not an explicit word of the image starts at zero, even
+    /// if it lies between two runs of zeros.
+    #[must_use]
+    pub fn zero_region(&self) -> Option<RangeInclusive<u32>> {
+        let mut runs = self.intervals.iter().filter(|(_, interval)| interval.zeros > 0).map(
+            |(&start, interval)| {
+                let first = start + interval.words.len() as u32 * WORD_SIZE as u32;
+                first..=first + (interval.zeros - 1) * WORD_SIZE as u32
+            },
+        );
+        let first = runs.next()?;
+        let last = runs.last().unwrap_or_else(|| first.clone());
+        Some(*first.start()..=*last.end())
+    }
+
+    /// Whether the word at `addr` starts at zero without being an explicit word of the image.
+    ///
+    /// See [`Self::zero_region`].
+    #[must_use]
+    pub fn is_zero_initialized(&self, addr: u32) -> bool {
+        self.zero_region().is_some_and(|region| region.contains(&addr)) && !self.has_word(addr)
+    }
+
+    /// Set the word at `addr`, returning the previous word if `addr` was already in the image.
+    pub fn insert(&mut self, addr: u32, value: u32) -> Option<u32> {
+        debug_assert_eq!(addr % WORD_SIZE as u32, 0, "unaligned address {addr:08x}");
+        if let Some((start, _)) = self.interval(addr) {
+            let interval = self.intervals.get_mut(&start).unwrap();
+            let i = ((addr - start) / WORD_SIZE as u32) as usize;
+            if i < interval.words.len() {
+                return Some(std::mem::replace(&mut interval.words[i], value));
+            }
+
+            // The word is in the run of zeros. Extend the explicit words if it is the first of
+            // the run, and otherwise split the run so that it is never expanded.
+            let skipped = (i - interval.words.len()) as u32;
+            if skipped == 0 {
+                interval.words.push(value);
+                interval.zeros -= 1;
+            } else {
+                let remaining = interval.zeros - skipped - 1;
+                interval.zeros = skipped;
+                self.intervals.insert(addr, Interval { words: vec![value], zeros: remaining });
+            }
+            return Some(0);
+        }
+
+        // Extend the previous interval if the word directly follows it.
+        self.len += 1;
+        if let Some((&start, interval)) = self.intervals.range_mut(..addr).next_back() {
+            if interval.zeros == 0 && interval.end(start) == Some(addr) {
+                interval.words.push(value);
+                return None;
+            }
+        }
+        self.intervals.insert(addr, Interval { words: vec![value], zeros: 0 });
+        None
+    }
+
+    /// Add the words `words` followed by `zeros` zero words at `addr`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the segment overlaps with the image or does not fit in
+    /// the address space.
+    pub fn insert_segment(&mut self, addr: u32, words: Vec<u32>, zeros: u32) -> eyre::Result<()> {
+        let interval = Interval { words, zeros };
+        if interval.len() == 0 {
+            return Ok(());
+        }
+        let end = interval
+            .end(addr)
+            .ok_or_else(|| eyre::eyre!("segment at 0x{addr:08x} overflows the address space"))?;
+        if self.contains(addr) || self.intervals.range(addr..end).next().is_some() {
+            eyre::bail!("segment at 0x{addr:08x}..0x{end:08x} overlaps the memory image");
+        }
+        self.len += interval.len() as usize;
+        self.intervals.insert(addr, interval);
+        Ok(())
+    }
+
+    /// Iterate over the addresses and words of the image, in address order.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.iter_from(0)
+    }
+
+    /// Iterate over the addresses and explicit words of the image, in address order, skipping the
+    /// runs of zeros.
+    pub fn words(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.intervals.iter().flat_map(|(&start, interval)| {
+            interval
+                .words
+                .iter()
+                .enumerate()
+                .map(move |(i, &word)| (start + (i * WORD_SIZE) as u32, word))
+        })
+    }
+
+    /// Iterate over the addresses and words of the image starting at `addr`, in address order.
+    pub fn iter_from(&self, addr: u32) -> impl Iterator<Item = (u32, u32)> + '_ {
+        let first = self.interval(addr).map_or(addr, |(start, _)| start);
+        self.intervals.range(first..).flat_map(move |(&start, interval)| {
+            let skip = addr.saturating_sub(start).div_ceil(WORD_SIZE as u32);
+            (skip..interval.len()).map(move |i| (start + i * WORD_SIZE as u32, interval.get(i)))
+        })
+    }
+
+    /// A digest of the image, which only depends on its contents and not on how it was built.
+    ///
+    /// Runs of zero words are hashed by their length, so the cost of the digest is proportional
+    /// to the number of explicit words rather than to the size of the image.
+    #[must_use]
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = Keccak::v256();
+        let mut zeros = 0u64;
+        let flush_zeros = |hasher: &mut Keccak, zeros: &mut u64| {
+            if *zeros > 0 {
+                hasher.update(&[1]);
+                hasher.update(&zeros.to_le_bytes());
+                *zeros = 0;
+            }
+        };
+
+        let mut next = None;
+        for (&start, interval) in &self.intervals {
+            if next != Some(start) {
+                flush_zeros(&mut hasher, &mut zeros);
+                hasher.update(&[2]);
+                hasher.update(&start.to_le_bytes());
+            }
+            for &word in &interval.words {
+                if word == 0 {
+                    zeros += 1;
+                } else {
+                    flush_zeros(&mut hasher, &mut zeros);
+                    hasher.update(&[0]);
+                    hasher.update(&word.to_le_bytes());
+                }
+            }
+            zeros += u64::from(interval.zeros);
+            next = interval.end(start);
+        }
+        flush_zeros(&mut hasher, &mut zeros);
+
+        let mut digest = [0u8; 32];
+        hasher.finalize(&mut digest);
+        digest
+    }
+}
+
+impl PartialEq for MemoryImage {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for MemoryImage {}
+
+impl FromIterator<(u32, u32)> for MemoryImage {
+    fn from_iter<I: IntoIterator<Item = (u32, u32)>>(iter: I) -> Self {
+        let mut image = Self::new();
+        for (addr, value) in iter {
+            image.insert(addr, value);
+        }
+        image
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_image() {
+        let mut image = MemoryImage::new();
+        image.insert_segment(0x100, vec![1, 2, 0, 3], 1 << 20).unwrap();
+        assert_eq!(image.len(), 4 + (1 << 20));
+        assert_eq!(image.get(0x100), Some(1));
+        assert_eq!(image.get(0x10c), Some(3));
+        assert_eq!(image.get(0x110), Some(0));
+        assert_eq!(image.get(0x100 + (4 + (1 << 20)) * 4), None);
+        assert_eq!(image.get(0xfc), None);
+        assert!(image.insert_segment(0x104, vec![5], 0).is_err());
+        assert!(image.insert_segment(0xf0, vec![5; 8], 0).is_err());
+
+        // Writing into the run of zeros splits it.
+        assert_eq!(image.insert(0x200, 7), Some(0));
+        assert_eq!(image.insert(0x110, 8), Some(0));
+        assert_eq!(image.insert(0x100, 9), Some(1));
+        assert_eq!(image.insert(0x80, 10), None);
+        assert_eq!(image.get(0x200), Some(7));
+        assert_eq!(image.get(0x1fc), Some(0));
+        assert_eq!(image.get(0x204), Some(0));
+        assert_eq!(image.len(), 5 + (1 << 20));
+
+        let words = image.iter_from(0x100).take(6).collect::<Vec<_>>();
+        assert_eq!(words, [(0x100, 9), (0x104, 2), (0x108, 0), (0x10c, 3), (0x110, 8), (0x114, 0)]);
+        assert_eq!(image.iter().next(), Some((0x80, 10)));
+    }
+
+    #[test]
+    fn test_memory_image_zero_region() {
+        let mut image = MemoryImage::new();
+        assert_eq!(image.zero_region(), None);
+        image.insert_segment(0x100, vec![1, 2], 1 << 20).unwrap();
+        image.insert_segment(0x80, vec![3], 0).unwrap();
+        let end = 0x108 + ((1 << 20) - 1) * 4;
+        assert_eq!(image.zero_region(), Some(0x108..=end));
+
+        // Only the explicit words are listed, and the zero region excludes them.
+        image.insert(0x200, 4);
+        assert_eq!(image.num_words(), 4);
+        let words = image.words().collect::<Vec<_>>();
+        assert_eq!(words, [(0x80, 3), (0x100, 1), (0x104, 2), (0x200, 4)]);
+        assert!(image.has_word(0x200) && !image.has_word(0x204) && !image.has_word(0x84));
+        assert!(image.is_zero_initialized(0x108) && image.is_zero_initialized(end));
+        assert!(!image.is_zero_initialized(0x200) && !image.is_zero_initialized(end + 4));
+        assert!(!image.is_zero_initialized(0x84));
+    }
+
+    #[test]
+    fn test_memory_image_digest() {
+        // The same contents built in different ways have the same digest.
+        let mut a = MemoryImage::new();
+        a.insert_segment(0x100, vec![1, 0], 3).unwrap();
+        a.insert_segment(0x114, vec![2], 0).unwrap();
+        let b = [(0x100, 1), (0x104, 0), (0x108, 0), (0x10c, 0), (0x110, 0), (0x114, 2)]
+            .into_iter()
+            .collect::<MemoryImage>();
+        assert_eq!(a, b);
+        assert_eq!(a.digest(), b.digest());
+
+        let mut c = b.clone();
+        c.insert(0x10c, 4);
+        assert_ne!(a, c);
+        assert_ne!(a.digest(), c.digest());
+    }
+}
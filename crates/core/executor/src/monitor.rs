@@ -0,0 +1,100 @@
+use std::{
+    sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    time::Duration,
+};
+
+/// A handle shared between a proving job and its owner to cancel the job and track its progress.
+///
+/// Cancellation is cooperative: the prover checks [`ProofMonitor::is_cancelled`] between shards
+/// and between recursion layers, and stops with an error once it is set. Work that is already in
+/// flight when the job is cancelled is not interrupted.
+#[derive(Debug, Default)]
+pub struct ProofMonitor {
+    cancelled: AtomicBool,
+    shards_proved: AtomicUsize,
+    total_shards: AtomicUsize,
+    commit_time_nanos: AtomicU64,
+}
+
+/// A snapshot of the progress of a proving job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofProgress {
+    /// The number of core shards proven so far.
+    pub shards_proved: usize,
+    /// The total number of core shards, once execution has finished and it is known.
+    pub total_shards: Option<usize>,
+}
+
+impl ProofMonitor {
+    /// Create a new monitor for a job that has not started yet.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request the job to stop at its next cancellation point.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether the job has been cancelled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// The current progress of the job.
+    #[must_use]
+    pub fn progress(&self) -> ProofProgress {
+        let total_shards = self.total_shards.load(Ordering::SeqCst);
+        ProofProgress {
+            shards_proved: self.shards_proved.load(Ordering::SeqCst),
+            total_shards: (total_shards > 0).then_some(total_shards),
+        }
+    }
+
+    /// Record that a core shard has been proven.
+    pub fn shard_proved(&self) {
+        self.shards_proved.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Set the total number of core shards of the job.
+    pub fn set_total_shards(&self, total_shards: usize) {
+        self.total_shards.store(total_shards, Ordering::SeqCst);
+    }
+
+    /// The time from the start of the core proof until every shard was committed to, once the
+    /// commit phase has finished. The rest of the core proof is spent opening the shards.
+    #[must_use]
+    pub fn commit_time(&self) -> Option<Duration> {
+        let nanos = self.commit_time_nanos.load(Ordering::SeqCst);
+        (nanos > 0).then(|| Duration::from_nanos(nanos))
+    }
+
+    /// Record the time taken by the commit phase of the core proof.
+    pub fn set_commit_time(&self, commit_time: Duration) {
+        let nanos = u64::try_from(commit_time.as_nanos()).unwrap_or(u64::MAX).max(1);
+        self.commit_time_nanos.store(nanos, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monitor_progress() {
+        let monitor = ProofMonitor::new();
+        assert_eq!(monitor.progress(), ProofProgress { shards_proved: 0, total_shards: None });
+        monitor.set_total_shards(3);
+        monitor.shard_proved();
+        monitor.shard_proved();
+        assert_eq!(monitor.progress(), ProofProgress { shards_proved: 2, total_shards: Some(3) });
+        assert_eq!(monitor.commit_time(), None);
+        monitor.set_commit_time(Duration::from_millis(5));
+        assert_eq!(monitor.commit_time(), Some(Duration::from_millis(5)));
+        assert!(!monitor.is_cancelled());
+        monitor.cancel();
+        assert!(monitor.is_cancelled());
+    }
+}
@@ -0,0 +1,105 @@
+//! Callbacks on the events of an execution.
+//!
+//! When an [`ExecutionObserver`] is set in the [`SP1Context`](crate::SP1Context), the executor
+//! calls it as the guest runs, so that execution data can be streamed into external systems (for
+//! example, an indexer of the syscalls or of the memory touched by a program) without
+//! post-processing whole [`ExecutionRecord`]s.
+//!
+//! Events in unconstrained mode are not observed, since their effects are reverted. The executor
+//! uses a slower interpreter while an observer is set.
+
+use crate::{syscalls::SyscallCode, ExecutionRecord};
+
+/// The base 2 logarithm of the size in bytes of the pages reported to
+/// [`ExecutionObserver::on_memory_page_touch`].
+pub(crate) const LOG_OBSERVED_PAGE_SIZE: u32 = 12;
+
+/// Callbacks on the events of an execution. Every callback does nothing by default.
+///
+/// The observer is shared with the executor, so callbacks take `&self`: observers that collect
+/// data use interior mutability, and can be read once execution has finished through another
+/// handle to them.
+pub trait ExecutionObserver: Send + Sync {
+    /// Called when the guest leaves a shard, with the events of the shard.
+    ///
+    /// The public values of `record` are not set yet, since they are only known once the batch of
+    /// shards it belongs to has been executed.
+    fn on_shard_end(&self, shard: u32, cycle: u64, record: &ExecutionRecord) {
+        let _ = (shard, cycle, record);
+    }
+
+    /// Called before a syscall is executed, with its arguments from registers `a0` and `a1`.
+    fn on_syscall(&self, shard: u32, cycle: u64, code: SyscallCode, args: [u32; 2]) {
+        let _ = (shard, cycle, code, args);
+    }
+
+    /// Called the first time the guest, or a syscall on its behalf, accesses a memory page. Pages
+    /// are 4 KiB, and `page` is the address of the page divided by its size.
+    ///
+    /// Accesses to the registers, which are stored at the lowest addresses, are not reported.
+    fn on_memory_page_touch(&self, shard: u32, cycle: u64, page: u32) {
+        let _ = (shard, cycle, page);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    };
+
+    use hashbrown::HashSet;
+    use sp1_stark::SP1CoreOpts;
+
+    use super::ExecutionObserver;
+    use crate::{
+        programs::tests::fibonacci_program, syscalls::SyscallCode, ExecutionRecord, Executor,
+        SP1Context,
+    };
+
+    #[derive(Default)]
+    struct Collector {
+        shards: AtomicU32,
+        syscalls: Mutex<Vec<SyscallCode>>,
+        pages: Mutex<Vec<u32>>,
+    }
+
+    impl ExecutionObserver for Collector {
+        fn on_shard_end(&self, shard: u32, _: u64, record: &ExecutionRecord) {
+            assert_eq!(shard, self.shards.fetch_add(1, Ordering::SeqCst) + 1);
+            assert!(!record.cpu_events.is_empty());
+        }
+
+        fn on_syscall(&self, _: u32, _: u64, code: SyscallCode, _: [u32; 2]) {
+            self.syscalls.lock().unwrap().push(code);
+        }
+
+        fn on_memory_page_touch(&self, _: u32, _: u64, page: u32) {
+            self.pages.lock().unwrap().push(page);
+        }
+    }
+
+    #[test]
+    fn test_execution_observer() {
+        let collector = Arc::new(Collector::default());
+        let context = SP1Context::builder().observer(collector.clone()).build();
+        let mut opts = SP1CoreOpts::default();
+        opts.shard_size = 1 << 10;
+        let mut runtime = Executor::with_context(fibonacci_program(), opts, context);
+        runtime.run().unwrap();
+
+        let cpu_shards = runtime.records.iter().filter(|r| !r.cpu_events.is_empty()).count();
+        assert!(cpu_shards > 1);
+        assert_eq!(collector.shards.load(Ordering::SeqCst) as usize, cpu_shards);
+
+        let syscalls = collector.syscalls.lock().unwrap();
+        assert_eq!(syscalls.last(), Some(&SyscallCode::HALT));
+
+        // Every page is reported once.
+        let pages = collector.pages.lock().unwrap();
+        let unique = pages.iter().collect::<HashSet<_>>();
+        assert_eq!(unique.len(), pages.len());
+        assert!(!pages.is_empty());
+    }
+}
@@ -101,6 +101,29 @@ pub enum Opcode {
     REMU = 37,
     /// Unimplemented instruction.
     UNIMP = 39,
+    /// rd ← m32(rs1), m32(rs1) ← rs2, pc ← pc + 4
+    AMOSWAP = 40,
+    /// rd ← m32(rs1), m32(rs1) ← m32(rs1) + rs2, pc ← pc + 4
+    AMOADD = 41,
+    /// rd ← m32(rs1), m32(rs1) ← m32(rs1) ^ rs2, pc ← pc + 4
+    AMOXOR = 42,
+    /// rd ← m32(rs1), m32(rs1) ← m32(rs1) & rs2, pc ← pc + 4
+    AMOAND = 43,
+    /// rd ← m32(rs1), m32(rs1) ← m32(rs1) | rs2, pc ← pc + 4
+    AMOOR = 44,
+    /// rd ← m32(rs1), m32(rs1) ← min(m32(rs1), rs2) (signed), pc ← pc + 4
+    AMOMIN = 45,
+    /// rd ← m32(rs1), m32(rs1) ← max(m32(rs1), rs2) (signed), pc ← pc + 4
+    AMOMAX = 46,
+    /// rd ← m32(rs1), m32(rs1) ← min(m32(rs1), rs2) (unsigned), pc ← pc + 4
+    AMOMINU = 47,
+    /// rd ← m32(rs1), m32(rs1) ← max(m32(rs1), rs2) (unsigned), pc ← pc + 4
+    AMOMAXU = 48,
+    /// m32(rs1) ← rs2 and rd ← 0 if rs1 is reserved, rd ← 1 otherwise, clears the reservation,
+    /// pc ← pc + 4
+    SC = 49,
+    /// rd ← m32(rs1), reserves rs1, pc ← pc + 4
+    LR = 50,
 }
 
 /// Byte Opcode.
@@ -127,8 +150,12 @@ pub enum ByteOpcode {
     LTU = 6,
     /// Most Significant Bit.
     MSB = 7,
-    /// Unsigned 16-bit Range Check.
-    U16Range = 8,
+    /// Unsigned Range Check of a value of at most [`MAX_RANGE_CHECK_BITS`] bits.
+    ///
+    /// Unlike the other opcodes, this one is handled by the range table rather than the byte table.
+    ///
+    /// [`MAX_RANGE_CHECK_BITS`]: crate::events::MAX_RANGE_CHECK_BITS
+    Range = 8,
 }
 
 impl Opcode {
@@ -174,6 +201,17 @@ impl Opcode {
             Opcode::REM => "rem",
             Opcode::REMU => "remu",
             Opcode::UNIMP => "unimp",
+            Opcode::AMOSWAP => "amoswap.w",
+            Opcode::AMOADD => "amoadd.w",
+            Opcode::AMOXOR => "amoxor.w",
+            Opcode::AMOAND => "amoand.w",
+            Opcode::AMOOR => "amoor.w",
+            Opcode::AMOMIN => "amomin.w",
+            Opcode::AMOMAX => "amomax.w",
+            Opcode::AMOMINU => "amominu.w",
+            Opcode::AMOMAXU => "amomaxu.w",
+            Opcode::SC => "sc.w",
+            Opcode::LR => "lr.w",
         }
     }
 
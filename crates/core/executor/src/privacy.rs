@@ -0,0 +1,68 @@
+//! Redaction of private guest data in host logs and error messages.
+//!
+//! When proving over sensitive inputs, the operator of a prover may not be allowed to see them.
+//! With [`PrivacyMode::Redacted`] set in the [`SP1Context`](crate::SP1Context), the host does not
+//! echo data derived from the guest's inputs: the guest's stdout and stderr, its panic messages,
+//! the digests of the proofs it verifies, and dumps of its traces.
+
+use std::fmt::{self, Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+/// Whether the host may write private guest data to its logs and error messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PrivacyMode {
+    /// Guest data is logged as is.
+    #[default]
+    Disabled,
+    /// Guest data is replaced by [`REDACTED`] in logs and error messages, and debug dumps are not
+    /// written.
+    Redacted,
+}
+
+/// The placeholder written instead of redacted guest data.
+pub const REDACTED: &str = "<redacted>";
+
+impl PrivacyMode {
+    /// Whether guest data should be redacted.
+    #[must_use]
+    pub const fn is_redacted(self) -> bool {
+        matches!(self, PrivacyMode::Redacted)
+    }
+
+    /// Wraps a value so that it displays as [`REDACTED`] if guest data should be redacted.
+    pub fn redact<T: Display + ?Sized>(self, value: &T) -> Redactable<'_, T> {
+        Redactable { value, mode: self }
+    }
+}
+
+/// A value that displays as [`REDACTED`] in [`PrivacyMode::Redacted`]. See
+/// [`PrivacyMode::redact`].
+#[derive(Debug, Clone, Copy)]
+pub struct Redactable<'a, T: ?Sized> {
+    value: &'a T,
+    mode: PrivacyMode,
+}
+
+impl<T: Display + ?Sized> Display for Redactable<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.mode.is_redacted() {
+            f.write_str(REDACTED)
+        } else {
+            self.value.fmt(f)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redact() {
+        assert_eq!(PrivacyMode::Disabled.redact("secret").to_string(), "secret");
+        assert_eq!(PrivacyMode::Redacted.redact("secret").to_string(), REDACTED);
+        assert_eq!(PrivacyMode::Redacted.redact(&42).to_string(), REDACTED);
+        assert_eq!(PrivacyMode::default(), PrivacyMode::Disabled);
+    }
+}
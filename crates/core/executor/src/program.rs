@@ -1,14 +1,16 @@
 //! Programs that can be executed by the SP1 zkVM.
 
-use std::{collections::BTreeMap, fs::File, io::Read};
+use std::{fs::File, io::Read};
 
 use p3_field::Field;
 use serde::{Deserialize, Serialize};
 use sp1_stark::air::MachineProgram;
+use tiny_keccak::{Hasher, Keccak};
 
 use crate::{
     disassembler::{transpile, Elf},
     instruction::Instruction,
+    MemoryImage,
 };
 
 /// A program that can be executed by the SP1 zkVM.
@@ -24,21 +26,25 @@ pub struct Program {
     /// The base address of the program.
     pub pc_base: u32,
     /// The initial memory image, useful for global constants.
-    pub memory_image: BTreeMap<u32, u32>,
+    pub memory_image: MemoryImage,
+    /// The contents of the ROM, which the guest maps on demand. See [`crate::embed_rom`].
+    #[serde(default)]
+    pub rom: Vec<u8>,
 }
 
 impl Program {
     /// Create a new [Program].
     #[must_use]
     pub const fn new(instructions: Vec<Instruction>, pc_start: u32, pc_base: u32) -> Self {
-        Self { instructions, pc_start, pc_base, memory_image: BTreeMap::new() }
+        Self { instructions, pc_start, pc_base, memory_image: MemoryImage::new(), rom: Vec::new() }
     }
 
     /// Disassemble a RV32IM ELF to a program that be executed by the VM.
     ///
     /// # Errors
     ///
-    /// This function may return an error if the ELF is not valid.
+    /// This function may return an error if the ELF is not valid. If the ELF was not built for the
+    /// zkVM, the error is an [`ElfError`](crate::ElfError) naming the mismatch.
     pub fn from(input: &[u8]) -> eyre::Result<Self> {
         // Decode the bytes as an ELF.
         let elf = Elf::decode(input)?;
@@ -47,12 +53,15 @@ impl Program {
         let instructions = transpile(&elf.instructions);
 
         // Return the program.
-        Ok(Program {
+        let program = Program {
             instructions,
             pc_start: elf.pc_start,
             pc_base: elf.pc_base,
             memory_image: elf.memory_image,
-        })
+            rom: elf.rom,
+        };
+        program.check_rom()?;
+        Ok(program)
     }
 
     /// Disassemble a RV32IM ELF to a program that be executed by the VM from a file path.
@@ -65,6 +74,25 @@ impl Program {
         File::open(path)?.read_to_end(&mut elf_code)?;
         Program::from(&elf_code)
     }
+
+    /// A digest of the program, which identifies it across processes and machines.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the instructions cannot be serialized.
+    #[must_use]
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = Keccak::v256();
+        hasher.update(&self.pc_start.to_le_bytes());
+        hasher.update(&self.pc_base.to_le_bytes());
+        hasher.update(
+            &bincode::serialize(&self.instructions).expect("failed to serialize instructions"),
+        );
+        hasher.update(&self.memory_image.digest());
+        let mut digest = [0u8; 32];
+        hasher.finalize(&mut digest);
+        digest
+    }
 }
 
 impl<F: Field> MachineProgram<F> for Program {
@@ -204,4 +204,34 @@ pub mod tests {
         ];
         Program::new(instructions, 0, 0)
     }
+
+    #[must_use]
+    pub fn atomic_memory_program() -> Program {
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 0x27654320, false, true),
+            Instruction::new(Opcode::ADD, 28, 0, 5, false, true),
+            Instruction::new(Opcode::ADD, 27, 0, 0xffff_fffd, false, true),
+            // SC fails without a reservation, and succeeds after LR to store 5. The reservation is
+            // then cleared, so a second SC fails.
+            Instruction::new(Opcode::SC, 15, 29, 27, false, false),
+            Instruction::new(Opcode::LR, 14, 29, 0, false, false),
+            Instruction::new(Opcode::SC, 26, 29, 28, false, false),
+            Instruction::new(Opcode::SC, 13, 29, 27, false, false),
+            // AMOADD returns 5 and stores 10.
+            Instruction::new(Opcode::AMOADD, 25, 29, 28, false, false),
+            // AMOMIN (signed) keeps -3, AMOMAXU keeps 0xffff_fffd.
+            Instruction::new(Opcode::AMOMIN, 24, 29, 27, false, false),
+            Instruction::new(Opcode::AMOMAXU, 23, 29, 28, false, false),
+            // AMOMAX (signed) stores 5, AMOMINU keeps 5.
+            Instruction::new(Opcode::AMOMAX, 22, 29, 28, false, false),
+            Instruction::new(Opcode::AMOMINU, 21, 29, 27, false, false),
+            // AMOXOR, AMOOR, AMOAND and AMOSWAP.
+            Instruction::new(Opcode::AMOXOR, 20, 29, 27, false, false),
+            Instruction::new(Opcode::AMOOR, 19, 29, 28, false, false),
+            Instruction::new(Opcode::AMOAND, 18, 29, 28, false, false),
+            Instruction::new(Opcode::AMOSWAP, 17, 29, 27, false, false),
+            Instruction::new(Opcode::LW, 16, 29, 0, false, true),
+        ];
+        Program::new(instructions, 0, 0)
+    }
 }
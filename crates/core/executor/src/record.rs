@@ -8,11 +8,12 @@ use serde::{Deserialize, Serialize};
 
 use super::{program::Program, Opcode};
 use crate::events::{
-    add_sharded_byte_lookup_events, AluEvent, ByteLookupEvent, ByteRecord, CpuEvent,
-    EdDecompressEvent, EllipticCurveAddEvent, EllipticCurveDecompressEvent,
-    EllipticCurveDoubleEvent, Fp2AddSubEvent, Fp2MulEvent, FpOpEvent, KeccakPermuteEvent, LookupId,
-    MemoryInitializeFinalizeEvent, MemoryRecordEnum, ShaCompressEvent, ShaExtendEvent,
-    Uint256MulEvent,
+    add_sharded_byte_lookup_events, AluEvent, BigIntEvent, ByteLookupEvent, ByteRecord,
+    ByteScanEvent, CpuEvent, DilithiumOpEvent, EdDecompressEvent, EdScalarMulEvent,
+    EllipticCurveAddEvent, EllipticCurveDecompressEvent, EllipticCurveDoubleEvent, Fp2AddSubEvent,
+    Fp2MulEvent, FpBatchInvEvent, FpOpEvent, HintWordsEvent, KeccakPermuteEvent,
+    KeccakPermuteMemoEvent, LookupId, MemoryInitializeFinalizeEvent, MemoryRecordEnum, MsmEvent,
+    Ripemd160CompressEvent, ShaCompressEvent, ShaExtendEvent, Uint256MulEvent, WideAluEvent,
 };
 
 /// A record of the execution of a program.
@@ -46,12 +47,24 @@ pub struct ExecutionRecord {
     pub sha_extend_events: Vec<ShaExtendEvent>,
     /// A trace of the sha256 compress events.
     pub sha_compress_events: Vec<ShaCompressEvent>,
+    /// A trace of the ripemd160 compress events.
+    pub ripemd160_compress_events: Vec<Ripemd160CompressEvent>,
     /// A trace of the keccak256 permute events.
     pub keccak_permute_events: Vec<KeccakPermuteEvent>,
+    /// A trace of the keccak256 permute events which reuse the result of an earlier permutation.
+    pub keccak_permute_memo_events: Vec<KeccakPermuteMemoEvent>,
     /// A trace of the edwards add events.
     pub ed_add_events: Vec<EllipticCurveAddEvent>,
     /// A trace of the edwards decompress events.
     pub ed_decompress_events: Vec<EdDecompressEvent>,
+    /// A trace of the edwards scalar mul events.
+    pub ed_scalar_mul_events: Vec<EdScalarMulEvent>,
+    /// A trace of the edwards MSM events.
+    pub ed_msm_events: Vec<MsmEvent>,
+    /// A trace of the secp256k1 MSM events.
+    pub secp256k1_msm_events: Vec<MsmEvent>,
+    /// A trace of the bn254 MSM events.
+    pub bn254_msm_events: Vec<MsmEvent>,
     /// A trace of the secp256k1 add events.
     pub secp256k1_add_events: Vec<EllipticCurveAddEvent>,
     /// A trace of the secp256k1 double events.
@@ -68,6 +81,16 @@ pub struct ExecutionRecord {
     pub bls12381_double_events: Vec<EllipticCurveDoubleEvent>,
     /// A trace of the uint256 mul events.
     pub uint256_mul_events: Vec<Uint256MulEvent>,
+    /// A trace of the bigint add, sub and mul events.
+    pub bigint_events: Vec<BigIntEvent>,
+    /// A trace of the byte scan find, copy and hex decode events.
+    pub byte_scan_events: Vec<ByteScanEvent>,
+    /// A trace of the hint words events.
+    pub hint_words_events: Vec<HintWordsEvent>,
+    /// A trace of the dilithium add, sub, mul and butterfly events.
+    pub dilithium_events: Vec<DilithiumOpEvent>,
+    /// A trace of the wide ALU add, sub, mul and comparison events.
+    pub wide_alu_events: Vec<WideAluEvent>,
     /// A trace of the memory initialize events.
     pub memory_initialize_events: Vec<MemoryInitializeFinalizeEvent>,
     /// A trace of the memory finalize events.
@@ -80,12 +103,16 @@ pub struct ExecutionRecord {
     pub bls12381_fp2_addsub_events: Vec<Fp2AddSubEvent>,
     /// A trace of the bls12381 fp2 mul events.
     pub bls12381_fp2_mul_events: Vec<Fp2MulEvent>,
+    /// A trace of the bls12381 fp batch inversion events.
+    pub bls12381_fp_batch_inv_events: Vec<FpBatchInvEvent>,
     /// A trace of the bn254 fp events.
     pub bn254_fp_events: Vec<FpOpEvent>,
     /// A trace of the bn254 fp2 add/sub events.
     pub bn254_fp2_addsub_events: Vec<Fp2AddSubEvent>,
     /// A trace of the bn254 fp2 mul events.
     pub bn254_fp2_mul_events: Vec<Fp2MulEvent>,
+    /// A trace of the bn254 fp batch inversion events.
+    pub bn254_fp_batch_inv_events: Vec<FpBatchInvEvent>,
     /// The public values.
     pub public_values: PublicValues<u32, u32>,
     /// The nonce lookup.
@@ -149,24 +176,37 @@ impl ExecutionRecord {
     pub fn defer(&mut self) -> ExecutionRecord {
         ExecutionRecord {
             keccak_permute_events: std::mem::take(&mut self.keccak_permute_events),
+            keccak_permute_memo_events: std::mem::take(&mut self.keccak_permute_memo_events),
             secp256k1_add_events: std::mem::take(&mut self.secp256k1_add_events),
             secp256k1_double_events: std::mem::take(&mut self.secp256k1_double_events),
             bn254_fp_events: std::mem::take(&mut self.bn254_fp_events),
             bn254_fp2_addsub_events: std::mem::take(&mut self.bn254_fp2_addsub_events),
             bn254_fp2_mul_events: std::mem::take(&mut self.bn254_fp2_mul_events),
+            bn254_fp_batch_inv_events: std::mem::take(&mut self.bn254_fp_batch_inv_events),
             bn254_add_events: std::mem::take(&mut self.bn254_add_events),
             bn254_double_events: std::mem::take(&mut self.bn254_double_events),
             bls12381_add_events: std::mem::take(&mut self.bls12381_add_events),
             bls12381_double_events: std::mem::take(&mut self.bls12381_double_events),
             sha_extend_events: std::mem::take(&mut self.sha_extend_events),
             sha_compress_events: std::mem::take(&mut self.sha_compress_events),
+            ripemd160_compress_events: std::mem::take(&mut self.ripemd160_compress_events),
             ed_add_events: std::mem::take(&mut self.ed_add_events),
             ed_decompress_events: std::mem::take(&mut self.ed_decompress_events),
+            ed_scalar_mul_events: std::mem::take(&mut self.ed_scalar_mul_events),
+            ed_msm_events: std::mem::take(&mut self.ed_msm_events),
+            secp256k1_msm_events: std::mem::take(&mut self.secp256k1_msm_events),
+            bn254_msm_events: std::mem::take(&mut self.bn254_msm_events),
             k256_decompress_events: std::mem::take(&mut self.k256_decompress_events),
             uint256_mul_events: std::mem::take(&mut self.uint256_mul_events),
+            bigint_events: std::mem::take(&mut self.bigint_events),
+            byte_scan_events: std::mem::take(&mut self.byte_scan_events),
+            hint_words_events: std::mem::take(&mut self.hint_words_events),
+            dilithium_events: std::mem::take(&mut self.dilithium_events),
+            wide_alu_events: std::mem::take(&mut self.wide_alu_events),
             bls12381_fp_events: std::mem::take(&mut self.bls12381_fp_events),
             bls12381_fp2_addsub_events: std::mem::take(&mut self.bls12381_fp2_addsub_events),
             bls12381_fp2_mul_events: std::mem::take(&mut self.bls12381_fp2_mul_events),
+            bls12381_fp_batch_inv_events: std::mem::take(&mut self.bls12381_fp_batch_inv_events),
             bls12381_decompress_events: std::mem::take(&mut self.bls12381_decompress_events),
             memory_initialize_events: std::mem::take(&mut self.memory_initialize_events),
             memory_finalize_events: std::mem::take(&mut self.memory_finalize_events),
@@ -176,8 +216,13 @@ impl ExecutionRecord {
 
     /// Splits the deferred [`ExecutionRecord`] into multiple [`ExecutionRecord`]s, each which
     /// contain a "reasonable" number of deferred events.
+    ///
+    /// Full chunks of events of a chip get a shard of their own. On the last split, the remaining
+    /// events of every chip are packed together into as few shards as possible instead, so that a
+    /// program using many precompiles a few times does not pay for a padded shard per precompile.
     pub fn split(&mut self, last: bool, opts: SplitOpts) -> Vec<ExecutionRecord> {
         let mut shards = Vec::new();
+        let mut remainders = Vec::new();
 
         macro_rules! split_events {
             ($self:ident, $events:ident, $shards:ident, $threshold:expr, $exact:expr) => {
@@ -185,15 +230,17 @@ impl ExecutionRecord {
                 let chunks = events.chunks_exact($threshold);
                 if !$exact {
                     $self.$events = chunks.remainder().to_vec();
-                } else {
-                    let remainder = chunks.remainder().to_vec();
-                    if !remainder.is_empty() {
-                        $shards.push(ExecutionRecord {
+                } else if !chunks.remainder().is_empty() {
+                    // A full chunk of events takes about `opts.deferred` rows.
+                    let rows = chunks.remainder().len() * (opts.deferred / $threshold).max(1);
+                    remainders.push((
+                        rows,
+                        ExecutionRecord {
                             $events: chunks.remainder().to_vec(),
                             program: self.program.clone(),
                             ..Default::default()
-                        });
-                    }
+                        },
+                    ));
                 }
                 let mut event_shards = chunks
                     .map(|chunk| ExecutionRecord {
@@ -207,6 +254,7 @@ impl ExecutionRecord {
         }
 
         split_events!(self, keccak_permute_events, shards, opts.keccak, last);
+        split_events!(self, keccak_permute_memo_events, shards, opts.deferred, last);
         split_events!(self, secp256k1_add_events, shards, opts.deferred, last);
         split_events!(self, secp256k1_double_events, shards, opts.deferred, last);
         split_events!(self, bn254_add_events, shards, opts.deferred, last);
@@ -215,19 +263,33 @@ impl ExecutionRecord {
         split_events!(self, bls12381_double_events, shards, opts.deferred, last);
         split_events!(self, sha_extend_events, shards, opts.sha_extend, last);
         split_events!(self, sha_compress_events, shards, opts.sha_compress, last);
+        split_events!(self, ripemd160_compress_events, shards, opts.ripemd160_compress, last);
         split_events!(self, ed_add_events, shards, opts.deferred, last);
         split_events!(self, ed_decompress_events, shards, opts.deferred, last);
+        split_events!(self, ed_scalar_mul_events, shards, opts.ed_scalar_mul, last);
+        split_events!(self, ed_msm_events, shards, opts.msm, last);
+        split_events!(self, secp256k1_msm_events, shards, opts.msm, last);
+        split_events!(self, bn254_msm_events, shards, opts.msm, last);
         split_events!(self, k256_decompress_events, shards, opts.deferred, last);
         split_events!(self, uint256_mul_events, shards, opts.deferred, last);
+        split_events!(self, bigint_events, shards, opts.bigint, last);
+        split_events!(self, byte_scan_events, shards, opts.byte_scan, last);
+        split_events!(self, hint_words_events, shards, opts.hint_words, last);
+        split_events!(self, dilithium_events, shards, opts.deferred, last);
+        split_events!(self, wide_alu_events, shards, opts.deferred, last);
         split_events!(self, bls12381_decompress_events, shards, opts.deferred, last);
         split_events!(self, bls12381_fp_events, shards, opts.deferred, last);
         split_events!(self, bls12381_fp2_addsub_events, shards, opts.deferred, last);
         split_events!(self, bls12381_fp2_mul_events, shards, opts.deferred, last);
+        split_events!(self, bls12381_fp_batch_inv_events, shards, opts.fp_batch_inv, last);
         split_events!(self, bn254_fp_events, shards, opts.deferred, last);
         split_events!(self, bn254_fp2_addsub_events, shards, opts.deferred, last);
         split_events!(self, bn254_fp2_mul_events, shards, opts.deferred, last);
+        split_events!(self, bn254_fp_batch_inv_events, shards, opts.fp_batch_inv, last);
         // _ = last_pct;
 
+        shards.extend(pack_records(remainders, opts.deferred));
+
         if last {
             // shards.push(last_shard);
 
@@ -275,6 +337,29 @@ impl ExecutionRecord {
     }
 }
 
+/// Packs records, given with their estimated number of rows, into as few records as possible
+/// holding at most `capacity` rows each.
+///
+/// This is the first-fit decreasing heuristic: the largest records are placed first, each in the
+/// first packed record with enough room left. A record larger than `capacity` is kept on its own.
+fn pack_records(
+    mut records: Vec<(usize, ExecutionRecord)>,
+    capacity: usize,
+) -> Vec<ExecutionRecord> {
+    records.sort_by_key(|(rows, _)| std::cmp::Reverse(*rows));
+    let mut packed: Vec<(usize, ExecutionRecord)> = Vec::new();
+    for (rows, mut record) in records {
+        match packed.iter_mut().find(|(used, _)| used + rows <= capacity) {
+            Some((used, bin)) => {
+                *used += rows;
+                bin.append(&mut record);
+            }
+            None => packed.push((rows, record)),
+        }
+    }
+    packed.into_iter().map(|(_, record)| record).collect()
+}
+
 /// A memory access record.
 #[derive(Debug, Copy, Clone, Default)]
 pub struct MemoryAccessRecord {
@@ -286,6 +371,8 @@ pub struct MemoryAccessRecord {
     pub c: Option<MemoryRecordEnum>,
     /// The memory access of the `memory` register.
     pub memory: Option<MemoryRecordEnum>,
+    /// The access to the reservation of the load-reserved and store-conditional instructions.
+    pub reservation: Option<MemoryRecordEnum>,
 }
 
 impl MachineRecord for ExecutionRecord {
@@ -304,9 +391,18 @@ impl MachineRecord for ExecutionRecord {
         stats.insert("lt_events".to_string(), self.lt_events.len());
         stats.insert("sha_extend_events".to_string(), self.sha_extend_events.len());
         stats.insert("sha_compress_events".to_string(), self.sha_compress_events.len());
+        stats.insert("ripemd160_compress_events".to_string(), self.ripemd160_compress_events.len());
         stats.insert("keccak_permute_events".to_string(), self.keccak_permute_events.len());
+        stats.insert(
+            "keccak_permute_memo_events".to_string(),
+            self.keccak_permute_memo_events.len(),
+        );
         stats.insert("ed_add_events".to_string(), self.ed_add_events.len());
         stats.insert("ed_decompress_events".to_string(), self.ed_decompress_events.len());
+        stats.insert("ed_scalar_mul_events".to_string(), self.ed_scalar_mul_events.len());
+        stats.insert("ed_msm_events".to_string(), self.ed_msm_events.len());
+        stats.insert("secp256k1_msm_events".to_string(), self.secp256k1_msm_events.len());
+        stats.insert("bn254_msm_events".to_string(), self.bn254_msm_events.len());
         stats.insert("secp256k1_add_events".to_string(), self.secp256k1_add_events.len());
         stats.insert("secp256k1_double_events".to_string(), self.secp256k1_double_events.len());
         stats.insert("bn254_add_events".to_string(), self.bn254_add_events.len());
@@ -315,15 +411,25 @@ impl MachineRecord for ExecutionRecord {
         stats.insert("bls12381_add_events".to_string(), self.bls12381_add_events.len());
         stats.insert("bls12381_double_events".to_string(), self.bls12381_double_events.len());
         stats.insert("uint256_mul_events".to_string(), self.uint256_mul_events.len());
+        stats.insert("bigint_events".to_string(), self.bigint_events.len());
+        stats.insert("byte_scan_events".to_string(), self.byte_scan_events.len());
+        stats.insert("hint_words_events".to_string(), self.hint_words_events.len());
+        stats.insert("dilithium_events".to_string(), self.dilithium_events.len());
+        stats.insert("wide_alu_events".to_string(), self.wide_alu_events.len());
         stats.insert("bls12381_fp_event".to_string(), self.bls12381_fp_events.len());
         stats.insert(
             "bls12381_fp2_addsub_events".to_string(),
             self.bls12381_fp2_addsub_events.len(),
         );
         stats.insert("bls12381_fp2_mul_events".to_string(), self.bls12381_fp2_mul_events.len());
+        stats.insert(
+            "bls12381_fp_batch_inv_events".to_string(),
+            self.bls12381_fp_batch_inv_events.len(),
+        );
         stats.insert("bn254_fp_events".to_string(), self.bn254_fp_events.len());
         stats.insert("bn254_fp2_addsub_events".to_string(), self.bn254_fp2_addsub_events.len());
         stats.insert("bn254_fp2_mul_events".to_string(), self.bn254_fp2_mul_events.len());
+        stats.insert("bn254_fp_batch_inv_events".to_string(), self.bn254_fp_batch_inv_events.len());
         stats.insert(
             "bls12381_decompress_events".to_string(),
             self.bls12381_decompress_events.len(),
@@ -354,9 +460,15 @@ impl MachineRecord for ExecutionRecord {
         self.lt_events.append(&mut other.lt_events);
         self.sha_extend_events.append(&mut other.sha_extend_events);
         self.sha_compress_events.append(&mut other.sha_compress_events);
+        self.ripemd160_compress_events.append(&mut other.ripemd160_compress_events);
         self.keccak_permute_events.append(&mut other.keccak_permute_events);
+        self.keccak_permute_memo_events.append(&mut other.keccak_permute_memo_events);
         self.ed_add_events.append(&mut other.ed_add_events);
         self.ed_decompress_events.append(&mut other.ed_decompress_events);
+        self.ed_scalar_mul_events.append(&mut other.ed_scalar_mul_events);
+        self.ed_msm_events.append(&mut other.ed_msm_events);
+        self.secp256k1_msm_events.append(&mut other.secp256k1_msm_events);
+        self.bn254_msm_events.append(&mut other.bn254_msm_events);
         self.secp256k1_add_events.append(&mut other.secp256k1_add_events);
         self.secp256k1_double_events.append(&mut other.secp256k1_double_events);
         self.bn254_add_events.append(&mut other.bn254_add_events);
@@ -365,12 +477,19 @@ impl MachineRecord for ExecutionRecord {
         self.bls12381_add_events.append(&mut other.bls12381_add_events);
         self.bls12381_double_events.append(&mut other.bls12381_double_events);
         self.uint256_mul_events.append(&mut other.uint256_mul_events);
+        self.bigint_events.append(&mut other.bigint_events);
+        self.byte_scan_events.append(&mut other.byte_scan_events);
+        self.hint_words_events.append(&mut other.hint_words_events);
+        self.dilithium_events.append(&mut other.dilithium_events);
+        self.wide_alu_events.append(&mut other.wide_alu_events);
         self.bls12381_fp_events.append(&mut other.bls12381_fp_events);
         self.bls12381_fp2_addsub_events.append(&mut other.bls12381_fp2_addsub_events);
         self.bls12381_fp2_mul_events.append(&mut other.bls12381_fp2_mul_events);
+        self.bls12381_fp_batch_inv_events.append(&mut other.bls12381_fp_batch_inv_events);
         self.bn254_fp_events.append(&mut other.bn254_fp_events);
         self.bn254_fp2_addsub_events.append(&mut other.bn254_fp2_addsub_events);
         self.bn254_fp2_mul_events.append(&mut other.bn254_fp2_mul_events);
+        self.bn254_fp_batch_inv_events.append(&mut other.bn254_fp_batch_inv_events);
         self.bls12381_decompress_events.append(&mut other.bls12381_decompress_events);
 
         self.bls12381_decompress_events.append(&mut other.bls12381_decompress_events);
@@ -438,3 +557,29 @@ impl ByteRecord for ExecutionRecord {
         add_sharded_byte_lookup_events(&mut self.byte_lookups, new_events);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A record of `len` memory initialization events.
+    fn record(len: usize) -> ExecutionRecord {
+        ExecutionRecord {
+            memory_initialize_events: (0..len)
+                .map(|i| MemoryInitializeFinalizeEvent::initialize(4 * i as u32, 0, true))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pack_records() {
+        let records = [6, 3, 5, 2, 4, 12].into_iter().map(|rows| (rows, record(rows))).collect();
+        let packed = pack_records(records, 10);
+
+        // The oversized record stays alone, then 6 + 4, 5 + 3 + 2.
+        let sizes =
+            packed.iter().map(|record| record.memory_initialize_events.len()).collect::<Vec<_>>();
+        assert_eq!(sizes, vec![12, 10, 10]);
+    }
+}
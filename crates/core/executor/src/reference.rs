@@ -0,0 +1,411 @@
+//! A naive reference interpreter for differential testing of the [`Executor`].
+//!
+//! The reference interpreter implements the RV32IM semantics directly from the specification, with
+//! no tracing, sharding or syscalls, so that its results can be compared against the executor's on
+//! randomly generated programs. Divergences in the `M` extension (`DIV`, `REM`, `MULH`, ...) are
+//! of particular interest, since the ALU chips are constrained against the executor's semantics.
+
+use hashbrown::HashMap;
+use rand::{seq::SliceRandom, Rng};
+use sp1_stark::SP1CoreOpts;
+use thiserror::Error;
+
+use crate::{Executor, Instruction, Opcode, Program};
+
+/// Interesting operand values for the `M` extension, biased towards overflow and sign edge cases.
+pub const EDGE_CASE_VALUES: [u32; 12] = [
+    0,
+    1,
+    2,
+    0xffff_ffff,
+    0xffff_fffe,
+    0x7fff_ffff,
+    0x8000_0000,
+    0x8000_0001,
+    0x0000_ffff,
+    0xffff_0000,
+    0x0001_0000,
+    0x5555_5555,
+];
+
+/// The ALU opcodes exercised by [`random_alu_program`].
+pub const ALU_OPCODES: [Opcode; 18] = [
+    Opcode::ADD,
+    Opcode::SUB,
+    Opcode::XOR,
+    Opcode::OR,
+    Opcode::AND,
+    Opcode::SLL,
+    Opcode::SRL,
+    Opcode::SRA,
+    Opcode::SLT,
+    Opcode::SLTU,
+    Opcode::MUL,
+    Opcode::MULH,
+    Opcode::MULHU,
+    Opcode::MULHSU,
+    Opcode::DIV,
+    Opcode::DIVU,
+    Opcode::REM,
+    Opcode::REMU,
+];
+
+/// Errors that the [`ReferenceExecutor`] can return.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ReferenceError {
+    /// The program accessed memory at a misaligned address.
+    #[error("misaligned memory access for opcode {0} and address {1}")]
+    MisalignedAccess(Opcode, u32),
+
+    /// The program executed an instruction that the reference interpreter does not support.
+    #[error("unsupported opcode {0}")]
+    UnsupportedOpcode(Opcode),
+
+    /// The program did not halt within the cycle limit.
+    #[error("exceeded cycle limit of {0}")]
+    ExceededCycleLimit(u64),
+}
+
+/// A divergence between the [`Executor`] and the [`ReferenceExecutor`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum Divergence {
+    /// Exactly one of the two engines failed.
+    #[error("reference error: {reference:?}, executor error: {executor:?}")]
+    Error {
+        /// The error of the reference interpreter, if any.
+        reference: Option<String>,
+        /// The error of the executor, if any.
+        executor: Option<String>,
+    },
+
+    /// The final program counters differ.
+    #[error("pc: reference {reference:#x}, executor {executor:#x}")]
+    Pc {
+        /// The final program counter of the reference interpreter.
+        reference: u32,
+        /// The final program counter of the executor.
+        executor: u32,
+    },
+
+    /// A register differs.
+    #[error("register x{register}: reference {reference:#x}, executor {executor:#x}")]
+    Register {
+        /// The index of the register.
+        register: usize,
+        /// The value of the register in the reference interpreter.
+        reference: u32,
+        /// The value of the register in the executor.
+        executor: u32,
+    },
+
+    /// A memory word differs.
+    #[error("memory {addr:#x}: reference {reference:#x}, executor {executor:#x}")]
+    Memory {
+        /// The address of the word.
+        addr: u32,
+        /// The value of the word in the reference interpreter.
+        reference: u32,
+        /// The value of the word in the executor.
+        executor: u32,
+    },
+}
+
+/// A naive RV32IM interpreter, used as a reference for the [`Executor`].
+pub struct ReferenceExecutor<'a> {
+    program: &'a Program,
+    /// The general purpose registers.
+    pub registers: [u32; 32],
+    /// The memory, indexed by word-aligned address.
+    pub memory: HashMap<u32, u32>,
+    /// The program counter.
+    pub pc: u32,
+}
+
+impl<'a> ReferenceExecutor<'a> {
+    /// Create a new [`ReferenceExecutor`] at the start of the program.
+    #[must_use]
+    pub fn new(program: &'a Program) -> Self {
+        Self {
+            program,
+            registers: [0; 32],
+            memory: program.memory_image.words().collect(),
+            pc: program.pc_start,
+        }
+    }
+
+    /// Whether the program has ended, following the same convention as the executor. This is only
+    /// checked after each instruction, since the program may start at `pc = 0`.
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.pc == 0
+            || self.pc.wrapping_sub(self.program.pc_base)
+                >= (self.program.instructions.len() * 4) as u32
+    }
+
+    /// Run the program until it ends, for at most `max_cycles` instructions.
+    pub fn run(&mut self, max_cycles: u64) -> Result<(), ReferenceError> {
+        for _ in 0..max_cycles {
+            self.step()?;
+            if self.is_done() {
+                return Ok(());
+            }
+        }
+        Err(ReferenceError::ExceededCycleLimit(max_cycles))
+    }
+
+    /// Execute the instruction at the current program counter.
+    pub fn step(&mut self) -> Result<(), ReferenceError> {
+        let instruction =
+            self.program.instructions[((self.pc - self.program.pc_base) / 4) as usize];
+        let mut next_pc = self.pc.wrapping_add(4);
+
+        if instruction.is_alu_instruction() {
+            let b = if instruction.imm_b { instruction.op_b } else { self.reg(instruction.op_b) };
+            let c = if instruction.imm_c { instruction.op_c } else { self.reg(instruction.op_c) };
+            let a = alu(instruction.opcode, b, c);
+            self.set_reg(instruction.op_a, a);
+        } else if instruction.is_memory_instruction() {
+            let addr = self.reg(instruction.op_b).wrapping_add(instruction.op_c);
+            self.memory_op(&instruction, addr)?;
+        } else if instruction.is_branch_instruction() {
+            let (a, b) = (self.reg(instruction.op_a), self.reg(instruction.op_b));
+            let taken = match instruction.opcode {
+                Opcode::BEQ => a == b,
+                Opcode::BNE => a != b,
+                Opcode::BLT => (a as i32) < (b as i32),
+                Opcode::BGE => (a as i32) >= (b as i32),
+                Opcode::BLTU => a < b,
+                Opcode::BGEU => a >= b,
+                _ => unreachable!(),
+            };
+            if taken {
+                next_pc = self.pc.wrapping_add(instruction.op_c);
+            }
+        } else {
+            match instruction.opcode {
+                Opcode::JAL => {
+                    next_pc = self.pc.wrapping_add(instruction.op_b);
+                    self.set_reg(instruction.op_a, self.pc.wrapping_add(4));
+                }
+                Opcode::JALR => {
+                    next_pc = self.reg(instruction.op_b).wrapping_add(instruction.op_c);
+                    self.set_reg(instruction.op_a, self.pc.wrapping_add(4));
+                }
+                Opcode::AUIPC => {
+                    self.set_reg(instruction.op_a, self.pc.wrapping_add(instruction.op_b));
+                }
+                opcode => return Err(ReferenceError::UnsupportedOpcode(opcode)),
+            }
+        }
+
+        self.pc = next_pc;
+        Ok(())
+    }
+
+    fn reg(&self, register: u32) -> u32 {
+        self.registers[register as usize]
+    }
+
+    fn set_reg(&mut self, register: u32, value: u32) {
+        if register != 0 {
+            self.registers[register as usize] = value;
+        }
+    }
+
+    fn memory_op(&mut self, instruction: &Instruction, addr: u32) -> Result<(), ReferenceError> {
+        let width = match instruction.opcode {
+            Opcode::LB | Opcode::LBU | Opcode::SB => 1,
+            Opcode::LH | Opcode::LHU | Opcode::SH => 2,
+            Opcode::LW | Opcode::SW => 4,
+            _ => unreachable!(),
+        };
+        if addr % width != 0 {
+            return Err(ReferenceError::MisalignedAccess(instruction.opcode, addr));
+        }
+
+        let word_addr = addr - addr % 4;
+        let shift = 8 * (addr % 4);
+        let mask = if width == 4 { u32::MAX } else { ((1 << (8 * width)) - 1) << shift };
+        let word = self.memory.get(&word_addr).copied().unwrap_or(0);
+        let value = (word & mask) >> shift;
+        match instruction.opcode {
+            Opcode::LB => self.set_reg(instruction.op_a, value as u8 as i8 as u32),
+            Opcode::LH => self.set_reg(instruction.op_a, value as u16 as i16 as u32),
+            Opcode::LW | Opcode::LBU | Opcode::LHU => self.set_reg(instruction.op_a, value),
+            _ => {
+                let store = (self.reg(instruction.op_a) << shift) & mask;
+                self.memory.insert(word_addr, (word & !mask) | store);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Computes an ALU operation following the RV32IM specification.
+#[must_use]
+pub fn alu(opcode: Opcode, b: u32, c: u32) -> u32 {
+    let (sb, sc) = (i128::from(b as i32), i128::from(c as i32));
+    let (ub, uc) = (i128::from(b), i128::from(c));
+    let shamt = c & 0x1f;
+    match opcode {
+        Opcode::ADD => b.wrapping_add(c),
+        Opcode::SUB => b.wrapping_sub(c),
+        Opcode::XOR => b ^ c,
+        Opcode::OR => b | c,
+        Opcode::AND => b & c,
+        Opcode::SLL => b << shamt,
+        Opcode::SRL => b >> shamt,
+        Opcode::SRA => ((b as i32) >> shamt) as u32,
+        Opcode::SLT => u32::from(sb < sc),
+        Opcode::SLTU => u32::from(b < c),
+        Opcode::MUL => (ub * uc) as u32,
+        Opcode::MULH => ((sb * sc) >> 32) as u32,
+        Opcode::MULHU => ((ub * uc) >> 32) as u32,
+        Opcode::MULHSU => ((sb * uc) >> 32) as u32,
+        // Division by zero returns all ones, and the signed overflow `-2^31 / -1` returns `-2^31`.
+        Opcode::DIV if c == 0 => u32::MAX,
+        Opcode::DIV if sb == i128::from(i32::MIN) && sc == -1 => b,
+        Opcode::DIV => (sb / sc) as u32,
+        Opcode::DIVU if c == 0 => u32::MAX,
+        Opcode::DIVU => b / c,
+        // The remainder of a division by zero is the dividend, and of `-2^31 / -1` is zero.
+        Opcode::REM if c == 0 => b,
+        Opcode::REM if sb == i128::from(i32::MIN) && sc == -1 => 0,
+        Opcode::REM => (sb % sc) as u32,
+        Opcode::REMU if c == 0 => b,
+        Opcode::REMU => b % c,
+        _ => panic!("not an alu opcode: {opcode}"),
+    }
+}
+
+/// Generates a random program of `num_instructions` ALU instructions over edge case operands.
+///
+/// The registers are first seeded with values drawn from [`EDGE_CASE_VALUES`] or uniformly at
+/// random, and the ALU instructions then operate on random registers, or on an immediate for the
+/// last operand. Writes to `x0` are generated too.
+#[must_use]
+pub fn random_alu_program<R: Rng>(rng: &mut R, num_instructions: usize) -> Program {
+    fn random_value<R: Rng>(rng: &mut R) -> u32 {
+        if rng.gen_bool(0.75) {
+            *EDGE_CASE_VALUES.choose(rng).unwrap()
+        } else {
+            rng.gen()
+        }
+    }
+
+    let mut instructions = (1..32)
+        .map(|register| Instruction::new(Opcode::ADD, register, 0, random_value(rng), false, true))
+        .collect::<Vec<_>>();
+    for _ in 0..num_instructions {
+        let opcode = *ALU_OPCODES.choose(rng).unwrap();
+        let (rd, rs1) = (rng.gen_range(0..32), rng.gen_range(1..32));
+        let instruction = if rng.gen_bool(0.25) {
+            Instruction::new(opcode, rd, rs1, random_value(rng), false, true)
+        } else {
+            Instruction::new(opcode, rd, rs1, rng.gen_range(0..32), false, false)
+        };
+        instructions.push(instruction);
+    }
+
+    Program::new(instructions, 0, 0)
+}
+
+/// Runs `program` on both the [`Executor`] and the [`ReferenceExecutor`], and compares the final
+/// program counter, registers, and the memory written by the program.
+pub fn check_differential(program: &Program, max_cycles: u64) -> Result<(), Divergence> {
+    let mut reference = ReferenceExecutor::new(program);
+    let reference_result = reference.run(max_cycles);
+
+    let mut executor = Executor::new(program.clone(), SP1CoreOpts::default());
+    executor.max_cycles = Some(max_cycles);
+    let executor_result = executor.run_fast();
+
+    match (reference_result, executor_result) {
+        (Ok(()), Ok(())) => {}
+        (Err(_), Err(_)) => return Ok(()),
+        (reference, executor) => {
+            return Err(Divergence::Error {
+                reference: reference.err().map(|e| e.to_string()),
+                executor: executor.err().map(|e| e.to_string()),
+            })
+        }
+    }
+
+    if reference.pc != executor.state.pc {
+        return Err(Divergence::Pc { reference: reference.pc, executor: executor.state.pc });
+    }
+    let registers = executor.registers();
+    for (register, (&expected, &actual)) in
+        reference.registers.iter().zip(registers.iter()).enumerate()
+    {
+        if expected != actual {
+            return Err(Divergence::Register { register, reference: expected, executor: actual });
+        }
+    }
+    for (&addr, &expected) in &reference.memory {
+        let actual = executor.word(addr);
+        if expected != actual {
+            return Err(Divergence::Memory { addr, reference: expected, executor: actual });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{alu, check_differential, random_alu_program, ALU_OPCODES, EDGE_CASE_VALUES};
+    use crate::{programs::tests::simple_memory_program, Instruction, Opcode, Program};
+
+    #[test]
+    fn test_alu_m_extension_edge_cases() {
+        let min = i32::MIN as u32;
+        let minus_one = -1i32 as u32;
+        assert_eq!(alu(Opcode::DIV, min, minus_one), min);
+        assert_eq!(alu(Opcode::REM, min, minus_one), 0);
+        assert_eq!(alu(Opcode::DIV, 7, 0), u32::MAX);
+        assert_eq!(alu(Opcode::DIVU, 7, 0), u32::MAX);
+        assert_eq!(alu(Opcode::REM, min, 0), min);
+        assert_eq!(alu(Opcode::REMU, 7, 0), 7);
+        assert_eq!(alu(Opcode::DIV, -7i32 as u32, 2), -3i32 as u32);
+        assert_eq!(alu(Opcode::REM, -7i32 as u32, 2), minus_one);
+        assert_eq!(alu(Opcode::MULH, min, min), 0x4000_0000);
+        assert_eq!(alu(Opcode::MULHU, u32::MAX, u32::MAX), 0xffff_fffe);
+        assert_eq!(alu(Opcode::MULHSU, minus_one, u32::MAX), u32::MAX);
+        assert_eq!(alu(Opcode::MULHSU, min, u32::MAX), 0x8000_0000);
+    }
+
+    #[test]
+    fn test_differential_edge_cases() {
+        for opcode in ALU_OPCODES {
+            let mut instructions = vec![];
+            for b in EDGE_CASE_VALUES {
+                for c in EDGE_CASE_VALUES {
+                    instructions.extend([
+                        Instruction::new(Opcode::ADD, 29, 0, b, false, true),
+                        Instruction::new(Opcode::ADD, 30, 0, c, false, true),
+                        Instruction::new(opcode, 31, 29, 30, false, false),
+                    ]);
+                }
+            }
+            check_differential(&Program::new(instructions, 0, 0), 1 << 16).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_differential_memory() {
+        check_differential(&simple_memory_program(), 1 << 16).unwrap();
+    }
+
+    #[test]
+    fn fuzz_differential_alu() {
+        for seed in 0..256 {
+            let program = random_alu_program(&mut StdRng::seed_from_u64(seed), 256);
+            if let Err(divergence) = check_differential(&program, 1 << 16) {
+                panic!("divergence for seed {seed}: {divergence}");
+            }
+        }
+    }
+}
@@ -0,0 +1,288 @@
+//! Read-only data segments (ROM) embedded in programs.
+//!
+//! A ROM is a byte blob that the guest maps into its address space one page at a time. Only a
+//! header is part of the program's initial memory image, and so of the verifying key: the length
+//! of the blob in bytes at [`ROM_START`], followed by the SHA-256 Merkle root of its pages (see
+//! [`RomTree`]). The blob itself stays on the host, and the guest maps the pages it reads at
+//! [`ROM_DATA_START`] with the `ROM_MAP` syscall, which also writes the Merkle path of the page.
+//! The guest checks every page it maps against the root before reading it, so a ROM of any size
+//! only costs the preprocessed rows of its header, and the memory rows of the pages that are read.
+//!
+//! The ROM can either be embedded in an ELF with [`embed_rom`], which makes it available to any
+//! prover that takes an ELF, or added to a [`Program`] with [`Program::with_rom`].
+
+use elf::{abi::PT_LOAD, endian::LittleEndian, file::Class, ElfBytes};
+use sha2::{Digest, Sha256};
+use sp1_primitives::consts::WORD_SIZE;
+
+use crate::Program;
+
+/// The address at which the ROM header is mapped in the guest's address space.
+pub const ROM_START: u32 = 0x7000_0000;
+
+/// The size of a page of the ROM, in bytes.
+pub const ROM_PAGE_SIZE: u32 = 4096;
+
+/// The address at which the pages of the ROM are mapped, one page after the header.
+pub const ROM_DATA_START: u32 = ROM_START + ROM_PAGE_SIZE;
+
+/// The maximum size of a ROM in bytes, which ends below the heap limit.
+pub const MAX_ROM_SIZE: usize = 0x0800_0000 - ROM_PAGE_SIZE as usize;
+
+/// The number of words of the ROM header: the length of the ROM and the root of its pages.
+pub const ROM_HEADER_NUM_WORDS: usize = 9;
+
+/// The type of the program header of the non-loadable segment holding the ROM in an ELF, in the
+/// range of OS-specific segment types.
+pub const PT_SP1_ROM: u32 = 0x6053_5031;
+
+/// The `PF_R` flag of a program header.
+const PF_R: u32 = 0x4;
+
+/// The offsets of the program header fields in the ELF32 file header.
+const E_PHOFF: usize = 28;
+const E_PHENTSIZE: usize = 42;
+const E_PHNUM: usize = 44;
+
+/// The size of an ELF32 program header.
+const PHDR_SIZE: usize = 32;
+
+/// The SHA-256 Merkle tree of the pages of a ROM.
+///
+/// The leaves are the digests of the pages, the last of which is padded with zeros, followed by
+/// the digests of zero pages up to a power of two. Every other node is the digest of its two
+/// children, concatenated.
+#[derive(Debug, Clone)]
+pub struct RomTree {
+    /// The levels of the tree, from the leaves to the root.
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl RomTree {
+    /// Builds the tree of the pages of `rom`.
+    #[must_use]
+    pub fn new(rom: &[u8]) -> Self {
+        let num_pages = rom_num_pages(rom.len());
+        let zero_page = Sha256::digest([0u8; ROM_PAGE_SIZE as usize]).into();
+        let mut leaves = rom
+            .chunks(ROM_PAGE_SIZE as usize)
+            .map(|page| {
+                let mut hasher = Sha256::new();
+                hasher.update(page);
+                hasher.update(&[0u8; ROM_PAGE_SIZE as usize][page.len()..]);
+                hasher.finalize().into()
+            })
+            .collect::<Vec<[u8; 32]>>();
+        leaves.resize(num_pages.next_power_of_two(), zero_page);
+
+        let mut levels = vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            let level =
+                levels.last().unwrap().chunks_exact(2).map(|pair| hash_pair(pair)).collect();
+            levels.push(level);
+        }
+        Self { levels }
+    }
+
+    /// The root of the tree.
+    #[must_use]
+    pub fn root(&self) -> [u8; 32] {
+        self.levels.last().unwrap()[0]
+    }
+
+    /// The siblings of the leaf of `page` on its path to the root, from the leaf up.
+    #[must_use]
+    pub fn path(&self, page: usize) -> Vec<[u8; 32]> {
+        let depth = self.levels.len() - 1;
+        (0..depth).map(|level| self.levels[level][(page >> level) ^ 1]).collect()
+    }
+}
+
+/// The digest of the concatenation of two nodes.
+fn hash_pair(pair: &[[u8; 32]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(pair[0]);
+    hasher.update(pair[1]);
+    hasher.finalize().into()
+}
+
+/// The number of pages of a ROM of `len` bytes, which is at least one.
+#[must_use]
+pub fn rom_num_pages(len: usize) -> usize {
+    len.div_ceil(ROM_PAGE_SIZE as usize).max(1)
+}
+
+/// The words of the ROM header: the length of `rom` followed by the root of its pages.
+fn rom_header(rom: &[u8]) -> eyre::Result<Vec<u32>> {
+    if rom.len() > MAX_ROM_SIZE {
+        eyre::bail!("rom of {} bytes exceeds the maximum size of {MAX_ROM_SIZE} bytes", rom.len());
+    }
+    let root = RomTree::new(rom).root();
+    let mut header = Vec::with_capacity(ROM_HEADER_NUM_WORDS);
+    header.push(rom.len() as u32);
+    header.extend(
+        root.chunks_exact(WORD_SIZE).map(|word| u32::from_le_bytes(word.try_into().unwrap())),
+    );
+    Ok(header)
+}
+
+/// Embeds `rom` in an ELF: its header as a new loadable segment at [`ROM_START`], and its contents
+/// as a segment of type [`PT_SP1_ROM`], which is not loaded into memory.
+///
+/// The segments and a copy of the program header table extended with them are appended to the
+/// file, so the existing segments are left untouched.
+///
+/// # Errors
+///
+/// This function returns an error if the ELF is not a valid 32-bit ELF, if it already has a
+/// segment at or above [`ROM_START`], or if the ROM is too large.
+pub fn embed_rom(elf: &[u8], rom: &[u8]) -> eyre::Result<Vec<u8>> {
+    let parsed = ElfBytes::<LittleEndian>::minimal_parse(elf)?;
+    if parsed.ehdr.class != Class::ELF32 {
+        eyre::bail!("must be a 32-bit elf");
+    }
+    let segments = parsed.segments().ok_or_else(|| eyre::eyre!("failed to get segments"))?;
+    if segments.iter().any(|s| {
+        s.p_type == PT_SP1_ROM || (s.p_type == PT_LOAD && s.p_vaddr + s.p_memsz > ROM_START as u64)
+    }) {
+        eyre::bail!("elf already has a segment in the rom region at 0x{ROM_START:08x}");
+    }
+
+    let phoff = parsed.ehdr.e_phoff as usize;
+    let phnum = parsed.ehdr.e_phnum as usize;
+    if parsed.ehdr.e_phentsize as usize != PHDR_SIZE {
+        eyre::bail!("unexpected program header size {}", parsed.ehdr.e_phentsize);
+    }
+    let phdrs = elf
+        .get(phoff..phoff + phnum * PHDR_SIZE)
+        .ok_or_else(|| eyre::eyre!("program header table out of bounds"))?;
+
+    let header = rom_header(rom)?.into_iter().flat_map(u32::to_le_bytes).collect::<Vec<_>>();
+    let mut output = elf.to_vec();
+    output.resize(output.len().next_multiple_of(WORD_SIZE), 0);
+    let header_offset = output.len() as u32;
+    output.extend_from_slice(&header);
+    let rom_offset = output.len() as u32;
+    output.extend_from_slice(rom);
+    output.resize(output.len().next_multiple_of(WORD_SIZE), 0);
+
+    // Append the program header table with the new segments.
+    let new_phoff = output.len() as u32;
+    output.extend_from_slice(phdrs);
+    let len = rom.len() as u32;
+    for (p_type, offset, vaddr, size) in [
+        (PT_LOAD, header_offset, ROM_START, header.len() as u32),
+        (PT_SP1_ROM, rom_offset, ROM_DATA_START, len),
+    ] {
+        for field in [p_type, offset, vaddr, vaddr, size, size, PF_R, WORD_SIZE as u32] {
+            output.extend_from_slice(&field.to_le_bytes());
+        }
+    }
+
+    // Point the file header to the new table.
+    output[E_PHOFF..E_PHOFF + 4].copy_from_slice(&new_phoff.to_le_bytes());
+    output[E_PHENTSIZE..E_PHENTSIZE + 2].copy_from_slice(&(PHDR_SIZE as u16).to_le_bytes());
+    let new_phnum = u16::try_from(phnum + 2)?;
+    output[E_PHNUM..E_PHNUM + 2].copy_from_slice(&new_phnum.to_le_bytes());
+
+    Ok(output)
+}
+
+impl Program {
+    /// Maps the header of `rom` at [`ROM_START`] in the initial memory image of the program, and
+    /// keeps its contents to be mapped by the guest.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the program already has data in the ROM region, or if the
+    /// ROM is too large.
+    pub fn with_rom(mut self, rom: &[u8]) -> eyre::Result<Self> {
+        if self.memory_image.iter_from(ROM_START).next().is_some() {
+            eyre::bail!("program already has data in the rom region at 0x{ROM_START:08x}");
+        }
+        self.memory_image.insert_segment(ROM_START, rom_header(rom)?, 0)?;
+        self.rom = rom.to_vec();
+        Ok(self)
+    }
+
+    /// The ROM of the program, if any.
+    #[must_use]
+    pub fn rom(&self) -> Option<&[u8]> {
+        self.memory_image.get(ROM_START).map(|_| self.rom.as_slice())
+    }
+
+    /// Checks that the contents of the ROM match the header in the memory image.
+    pub(crate) fn check_rom(&self) -> eyre::Result<()> {
+        let header = (0..ROM_HEADER_NUM_WORDS as u32)
+            .map(|i| self.memory_image.get(ROM_START + i * WORD_SIZE as u32))
+            .collect::<Option<Vec<_>>>();
+        match header {
+            None if self.rom.is_empty() => Ok(()),
+            Some(header) if header == rom_header(&self.rom)? => Ok(()),
+            _ => eyre::bail!("the rom does not match its header at 0x{ROM_START:08x}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::programs::tests::FIBONACCI_ELF;
+
+    #[test]
+    fn test_embed_rom() {
+        let rom = (0..10001u32).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+        let elf = embed_rom(FIBONACCI_ELF, &rom).unwrap();
+        let program = Program::from(&elf).unwrap();
+        let original = Program::from(FIBONACCI_ELF).unwrap();
+
+        assert_eq!(program.instructions.len(), original.instructions.len());
+        assert_eq!(program.pc_start, original.pc_start);
+        assert_eq!(program.pc_base, original.pc_base);
+        assert_eq!(program.rom(), Some(rom.as_slice()));
+        assert_eq!(original.rom(), None);
+
+        // Only the header of the ROM is part of the memory image.
+        let header = program.memory_image.iter_from(ROM_START).collect::<Vec<_>>();
+        assert_eq!(header.len(), ROM_HEADER_NUM_WORDS);
+        assert_eq!(header[0], (ROM_START, rom.len() as u32));
+
+        // Embedding the ROM in the ELF or in the program yields the same program.
+        let with_rom = original.with_rom(&rom).unwrap();
+        assert_eq!(program.memory_image, with_rom.memory_image);
+        assert_eq!(program.rom, with_rom.rom);
+
+        assert!(embed_rom(&elf, &rom).is_err());
+
+        // A ROM that does not match its header is rejected.
+        let mut tampered = elf.clone();
+        let offset = elf.windows(16).position(|w| w == &rom[..16]).unwrap();
+        tampered[offset] ^= 1;
+        assert!(Program::from(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_rom_tree() {
+        let rom = (0..3 * ROM_PAGE_SIZE + 5).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        let tree = RomTree::new(&rom);
+        assert_eq!(rom_num_pages(rom.len()), 4);
+        assert_eq!(rom_num_pages(0), 1);
+
+        for (page, contents) in rom.chunks(ROM_PAGE_SIZE as usize).enumerate() {
+            let mut padded = contents.to_vec();
+            padded.resize(ROM_PAGE_SIZE as usize, 0);
+            let path = tree.path(page);
+            assert_eq!(path.len(), 2);
+            let mut node: [u8; 32] = Sha256::digest(&padded).into();
+            for (level, sibling) in path.iter().enumerate() {
+                node = if (page >> level) & 1 == 0 {
+                    hash_pair(&[node, *sibling])
+                } else {
+                    hash_pair(&[*sibling, node])
+                };
+            }
+            assert_eq!(node, tree.root());
+        }
+        assert_ne!(RomTree::new(&rom[1..]).root(), tree.root());
+    }
+}
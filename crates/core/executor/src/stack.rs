@@ -0,0 +1,93 @@
+//! Detection of guest stack overflows.
+//!
+//! The guest stack starts at [`STACK_TOP`] and grows down towards the program's data, so a deep
+//! recursion silently overwrites whatever lies below it. A [`StackGuard`] reserves a region below
+//! the stack that the guest must never write to: the executor fails with
+//! [`ExecutionError::StackOverflow`](crate::ExecutionError::StackOverflow) on the first store into
+//! it.
+
+use serde::{Deserialize, Serialize};
+
+/// The initial stack pointer of guests, as set by the entrypoint of `sp1-zkvm`.
+pub const STACK_TOP: u32 = 0x0020_0400;
+
+/// A guard region below the guest stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StackGuard {
+    /// The initial stack pointer.
+    pub stack_top: u32,
+    /// The size of the stack in bytes, i.e. the distance from `stack_top` to the guard region.
+    pub stack_size: u32,
+    /// The size of the guard region in bytes.
+    pub guard_size: u32,
+}
+
+impl Default for StackGuard {
+    fn default() -> Self {
+        Self { stack_top: STACK_TOP, stack_size: 0x0010_0000, guard_size: 0x1000 }
+    }
+}
+
+impl StackGuard {
+    /// Create a guard of `guard_size` bytes below a stack of `stack_size` bytes starting at
+    /// [`STACK_TOP`].
+    #[must_use]
+    pub const fn new(stack_size: u32, guard_size: u32) -> Self {
+        Self { stack_top: STACK_TOP, stack_size, guard_size }
+    }
+
+    /// The guard region, as a range of addresses.
+    #[must_use]
+    pub fn region(&self) -> std::ops::Range<u32> {
+        let end = self.stack_top.saturating_sub(self.stack_size);
+        end.saturating_sub(self.guard_size)..end
+    }
+
+    /// Whether a write to `addr` falls into the guard region.
+    #[must_use]
+    pub fn contains(&self, addr: u32) -> bool {
+        self.region().contains(&addr)
+    }
+
+    /// The depth of the stack, in bytes, when the stack pointer is `sp`.
+    #[must_use]
+    pub const fn depth(&self, sp: u32) -> u32 {
+        self.stack_top.wrapping_sub(sp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_stark::SP1CoreOpts;
+
+    use super::*;
+    use crate::{ExecutionError, Executor, Instruction, Opcode, Program, SP1Context};
+
+    #[test]
+    fn test_stack_overflow() {
+        let guard = StackGuard::new(0x100, 0x10);
+        assert_eq!(guard.region(), STACK_TOP - 0x110..STACK_TOP - 0x100);
+
+        //     addi x2, x0, STACK_TOP - 0x100
+        //     sw x0, 0(x2)
+        //     sw x0, -4(x2)
+        let sp = STACK_TOP - 0x100;
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 2, 0, sp, false, true),
+            Instruction::new(Opcode::SW, 0, 2, 0, false, true),
+            Instruction::new(Opcode::SW, 0, 2, -4i32 as u32, false, true),
+        ];
+        let program = Program::new(instructions, 0, 0);
+
+        // Without a guard, the program runs to completion.
+        let mut runtime = Executor::new(program.clone(), SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        let context = SP1Context::builder().stack_guard(guard).build();
+        let mut runtime = Executor::with_context(program, SP1CoreOpts::default(), context);
+        assert!(matches!(
+            runtime.run(),
+            Err(ExecutionError::StackOverflow { pc: 8, depth: 0x100 })
+        ));
+    }
+}
@@ -1,10 +1,12 @@
 use std::{
     fs::File,
     io::{Seek, Write},
+    sync::Arc,
 };
 
 use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
+use sp1_primitives::io::PublicValuesDomain;
 use sp1_stark::{baby_bear_poseidon2::BabyBearPoseidon2, ShardProof, StarkVerifyingKey};
 
 use crate::{
@@ -39,6 +41,10 @@ pub struct ExecutionState {
     /// + timestamp that each memory address was accessed.
     pub memory: PagedMemory<MemoryRecord>,
 
+    /// The number of words of `memory` outside of the registers, which is checked against
+    /// [`sp1_stark::SP1CoreOpts::max_memory_bytes`].
+    pub touched_memory_words: u64,
+
     /// Uninitialized memory addresses that have a specific value they should be initialized with.
     /// `SyscallHintRead` uses this to write hint data into uninitialized memory.
     pub uninitialized_memory: PagedMemory<u32>,
@@ -49,6 +55,27 @@ pub struct ExecutionState {
     /// A ptr to the current position in the input stream incremented by `HINT_READ` opcode.
     pub input_stream_ptr: usize,
 
+    /// Large inputs that the program maps into its memory one page at a time with
+    /// `PAGED_INPUT_MAP`. They are not serialized into checkpoints, so they have to be restored
+    /// when recovering an executor from one.
+    #[serde(skip)]
+    pub paged_inputs: Vec<Arc<[u8]>>,
+
+    /// Named files that the program reads through the `FD_FS_READ` hook. Like paged inputs, they
+    /// are not serialized into checkpoints.
+    #[serde(skip)]
+    pub files: HashMap<String, Arc<[u8]>>,
+
+    /// The domain separator and nonce that the program writes at the start of its public values
+    /// through the `FD_PUBLIC_VALUES_DOMAIN` hook.
+    pub public_values_domain: Option<PublicValuesDomain>,
+
+    /// Environment variables that the program reads through the `FD_ENV_VAR` hook.
+    pub env_vars: HashMap<String, String>,
+
+    /// Arguments that the program reads through the `FD_ARGS` hook.
+    pub args: Vec<String>,
+
     /// A stream of proofs inputted to the program.
     pub proof_stream: Vec<(ShardProof<BabyBearPoseidon2>, StarkVerifyingKey<BabyBearPoseidon2>)>,
 
@@ -64,6 +91,13 @@ pub struct ExecutionState {
 
     /// Keeps track of how many times a certain syscall has been called.
     pub syscall_counts: HashMap<SyscallCode, u64>,
+
+    /// Keeps track of how many syscalls reused the result of an earlier call, since they are
+    /// proven by chips of their own.
+    pub memoized_syscall_count: u64,
+
+    /// The number of extra cycles of the last precompile call, returned by `CYCLE_INFO`.
+    pub last_precompile_cycles: u32,
 }
 
 impl ExecutionState {
@@ -78,14 +112,22 @@ impl ExecutionState {
             channel: 0,
             pc: pc_start,
             memory: PagedMemory::new_preallocated(),
+            touched_memory_words: 0,
             uninitialized_memory: PagedMemory::default(),
             input_stream: Vec::new(),
             input_stream_ptr: 0,
+            paged_inputs: Vec::new(),
+            files: HashMap::new(),
+            public_values_domain: None,
+            env_vars: HashMap::new(),
+            args: Vec::new(),
             public_values_stream: Vec::new(),
             public_values_stream_ptr: 0,
             proof_stream: Vec::new(),
             proof_stream_ptr: 0,
             syscall_counts: HashMap::new(),
+            memoized_syscall_count: 0,
+            last_precompile_cycles: 0,
         }
     }
 }
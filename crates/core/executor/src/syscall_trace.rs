@@ -0,0 +1,152 @@
+//! Tracing of the syscalls made by a guest, as JSON lines.
+//!
+//! When a [`SyscallTracer`] is set in the [`SP1Context`](crate::SP1Context), the executor writes
+//! one [`TraceEvent`] per line for every syscall the guest makes and, optionally, for every basic
+//! block it enters. This is enough to replay or analyze the behavior of a guest without forking
+//! the executor.
+//!
+//! Syscalls made in unconstrained mode are not traced, since their effects are reverted.
+
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::syscalls::SyscallCode;
+
+/// An event written by a [`SyscallTracer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TraceEvent {
+    /// A syscall.
+    Syscall {
+        /// The syscall.
+        code: SyscallCode,
+        /// The arguments of the syscall, from registers `a0` and `a1`.
+        args: [u32; 2],
+        /// The program counter of the `ecall` instruction.
+        pc: u32,
+        /// The number of cycles executed before the syscall.
+        cycle: u64,
+        /// The number of extra cycles taken by the syscall.
+        extra_cycles: u32,
+        /// The shard in which the syscall was made.
+        shard: u32,
+    },
+    /// The start of a basic block, i.e. the first instruction after a branch, a jump or a syscall.
+    Block {
+        /// The program counter of the first instruction of the block.
+        pc: u32,
+        /// The number of cycles executed before the block.
+        cycle: u64,
+        /// The shard in which the block starts.
+        shard: u32,
+    },
+}
+
+/// A writer of [`TraceEvent`]s as JSON lines.
+#[derive(Clone)]
+pub struct SyscallTracer<'a> {
+    writer: Arc<Mutex<dyn Write + Send + 'a>>,
+    basic_blocks: bool,
+}
+
+impl<'a> SyscallTracer<'a> {
+    /// Create a tracer writing the syscalls of the guest to `writer`.
+    ///
+    /// The writer is not buffered, so wrapping files in a [`std::io::BufWriter`] is recommended.
+    #[must_use]
+    pub fn new(writer: impl Write + Send + 'a) -> Self {
+        Self { writer: Arc::new(Mutex::new(writer)), basic_blocks: false }
+    }
+
+    /// Also write an event for every basic block the guest enters.
+    #[must_use]
+    pub fn with_basic_blocks(mut self) -> Self {
+        self.basic_blocks = true;
+        self
+    }
+
+    /// Whether basic blocks are traced.
+    #[must_use]
+    pub fn basic_blocks(&self) -> bool {
+        self.basic_blocks
+    }
+
+    /// Write `event` as a line.
+    pub(crate) fn write(&self, event: &TraceEvent) -> std::io::Result<()> {
+        let mut writer = self.writer.lock().unwrap();
+        serde_json::to_writer(&mut *writer, event)?;
+        writer.write_all(b"\n")
+    }
+
+    /// Flush the underlying writer.
+    pub(crate) fn flush(&self) -> std::io::Result<()> {
+        self.writer.lock().unwrap().flush()
+    }
+}
+
+impl std::fmt::Debug for SyscallTracer<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SyscallTracer").field("basic_blocks", &self.basic_blocks).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SyscallTracer, TraceEvent};
+    use crate::{programs::tests::fibonacci_program, syscalls::SyscallCode, Executor, SP1Context};
+    use sp1_stark::SP1CoreOpts;
+    use std::{
+        io::Write,
+        sync::{Arc, Mutex},
+    };
+
+    /// A writer into a shared buffer, so that it can be inspected after execution.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn trace(tracer: impl FnOnce(SharedBuffer) -> SyscallTracer<'static>) -> Vec<TraceEvent> {
+        let buffer = SharedBuffer::default();
+        let context = SP1Context::builder().syscall_tracer(tracer(buffer.clone())).build();
+        let mut runtime =
+            Executor::with_context(fibonacci_program(), SP1CoreOpts::default(), context);
+        runtime.run_fast().unwrap();
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        output.lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_syscall_trace() {
+        let events = trace(SyscallTracer::new);
+        assert!(!events.is_empty());
+        assert!(events.iter().all(|event| matches!(event, TraceEvent::Syscall { .. })));
+        assert!(matches!(events.last(), Some(TraceEvent::Syscall { code: SyscallCode::HALT, .. })));
+
+        let cycles = events.iter().map(|event| match event {
+            TraceEvent::Syscall { cycle, .. } | TraceEvent::Block { cycle, .. } => *cycle,
+        });
+        assert!(cycles.clone().zip(cycles.skip(1)).all(|(a, b)| a < b));
+    }
+
+    #[test]
+    fn test_syscall_trace_basic_blocks() {
+        let events = trace(|buffer| SyscallTracer::new(buffer).with_basic_blocks());
+        let syscalls =
+            events.iter().filter(|event| matches!(event, TraceEvent::Syscall { .. })).count();
+        assert_eq!(syscalls, trace(SyscallTracer::new).len());
+        assert!(matches!(events.first(), Some(TraceEvent::Block { cycle: 0, .. })));
+    }
+}
@@ -84,6 +84,24 @@ pub enum SyscallCode {
     /// Executes the `HINT_READ` precompile.
     HINT_READ = 0x00_00_00_F1,
 
+    /// Executes the `PAGED_INPUT_LEN` precompile.
+    PAGED_INPUT_LEN = 0x00_00_00_F2,
+
+    /// Executes the `PAGED_INPUT_MAP` precompile.
+    PAGED_INPUT_MAP = 0x00_00_00_F3,
+
+    /// Executes the `HINT_REMAINING` precompile.
+    HINT_REMAINING = 0x00_00_00_F4,
+
+    /// Executes the `CYCLE_INFO` precompile.
+    CYCLE_INFO = 0x00_00_00_F5,
+
+    /// Aborts the execution with a reason code and a message.
+    ABORT = 0x00_00_00_F6,
+
+    /// Executes the `ROM_MAP` precompile.
+    ROM_MAP = 0x00_00_00_F7,
+
     /// Executes the `UINT256_MUL` precompile.
     UINT256_MUL = 0x00_01_01_1D,
 
@@ -128,13 +146,92 @@ pub enum SyscallCode {
 
     /// Executes the `BN254_FP2_MUL` precompile.
     BN254_FP2_MUL = 0x00_01_01_2B,
+
+    /// Executes the `ED_SCALAR_MUL` precompile.
+    ED_SCALAR_MUL = 0x00_01_01_2C,
+
+    /// Executes the `BIGINT_ADD` precompile.
+    BIGINT_ADD = 0x00_01_01_2D,
+
+    /// Executes the `BIGINT_SUB` precompile.
+    BIGINT_SUB = 0x00_01_01_2E,
+
+    /// Executes the `BIGINT_MUL` precompile.
+    BIGINT_MUL = 0x00_10_01_2F,
+
+    /// Executes the `DILITHIUM_ADD` precompile.
+    DILITHIUM_ADD = 0x00_01_01_30,
+
+    /// Executes the `DILITHIUM_SUB` precompile.
+    DILITHIUM_SUB = 0x00_01_01_31,
+
+    /// Executes the `DILITHIUM_MUL` precompile.
+    DILITHIUM_MUL = 0x00_01_01_32,
+
+    /// Executes the `DILITHIUM_BUTTERFLY` precompile.
+    DILITHIUM_BUTTERFLY = 0x00_01_01_33,
+
+    /// Executes the `WIDE_ALU_ADD` precompile.
+    WIDE_ALU_ADD = 0x00_01_01_34,
+
+    /// Executes the `WIDE_ALU_SUB` precompile.
+    WIDE_ALU_SUB = 0x00_01_01_35,
+
+    /// Executes the `WIDE_ALU_MUL` precompile.
+    WIDE_ALU_MUL = 0x00_01_01_36,
+
+    /// Executes the `WIDE_ALU_LTU` precompile.
+    WIDE_ALU_LTU = 0x00_01_01_37,
+
+    /// Executes the `WIDE_ALU_LT` precompile.
+    WIDE_ALU_LT = 0x00_01_01_38,
+
+    /// Executes one window of an MSM on the Edwards curve ed25519.
+    ED_MSM = 0x00_01_01_39,
+
+    /// Executes one window of an MSM on the secp256k1 curve.
+    SECP256K1_MSM = 0x00_01_01_3A,
+
+    /// Executes one window of an MSM on the bn254 curve.
+    BN254_MSM = 0x00_01_01_3B,
+
+    /// Executes the `RIPEMD160_COMPRESS` precompile.
+    RIPEMD160_COMPRESS = 0x00_01_01_3C,
+
+    /// Executes the `BN254_FP_BATCH_INV` precompile.
+    BN254_FP_BATCH_INV = 0x00_01_01_3D,
+
+    /// Executes the `BLS12381_FP_BATCH_INV` precompile.
+    BLS12381_FP_BATCH_INV = 0x00_01_01_3E,
+
+    /// Executes the `BYTE_SCAN_FIND` precompile.
+    BYTE_SCAN_FIND = 0x00_01_01_3F,
+
+    /// Executes the `BYTE_SCAN_COPY` precompile.
+    BYTE_SCAN_COPY = 0x00_01_01_40,
+
+    /// Executes the `BYTE_SCAN_HEX_DECODE` precompile.
+    BYTE_SCAN_HEX_DECODE = 0x00_01_01_41,
+
+    /// Executes the `HINT_READ_WORDS` precompile.
+    HINT_READ_WORDS = 0x00_01_01_42,
 }
 
 impl SyscallCode {
     /// Create a [`SyscallCode`] from a u32.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not a known syscall number.
     #[must_use]
     pub fn from_u32(value: u32) -> Self {
-        match value {
+        Self::try_from_u32(value).unwrap_or_else(|| panic!("invalid syscall number: {value}"))
+    }
+
+    /// Create a [`SyscallCode`] from a u32, or `None` if the value is not a known syscall number.
+    #[must_use]
+    pub fn try_from_u32(value: u32) -> Option<Self> {
+        let code = match value {
             0x00_00_00_00 => SyscallCode::HALT,
             0x00_00_00_02 => SyscallCode::WRITE,
             0x00_00_00_03 => SyscallCode::ENTER_UNCONSTRAINED,
@@ -156,6 +253,12 @@ impl SyscallCode {
             0x00_00_00_1B => SyscallCode::VERIFY_SP1_PROOF,
             0x00_00_00_F0 => SyscallCode::HINT_LEN,
             0x00_00_00_F1 => SyscallCode::HINT_READ,
+            0x00_00_00_F2 => SyscallCode::PAGED_INPUT_LEN,
+            0x00_00_00_F3 => SyscallCode::PAGED_INPUT_MAP,
+            0x00_00_00_F4 => SyscallCode::HINT_REMAINING,
+            0x00_00_00_F5 => SyscallCode::CYCLE_INFO,
+            0x00_00_00_F6 => SyscallCode::ABORT,
+            0x00_00_00_F7 => SyscallCode::ROM_MAP,
             0x00_01_01_1D => SyscallCode::UINT256_MUL,
             0x00_01_01_20 => SyscallCode::BLS12381_FP_ADD,
             0x00_01_01_21 => SyscallCode::BLS12381_FP_SUB,
@@ -170,8 +273,32 @@ impl SyscallCode {
             0x00_01_01_2A => SyscallCode::BN254_FP2_SUB,
             0x00_01_01_2B => SyscallCode::BN254_FP2_MUL,
             0x00_00_01_1C => SyscallCode::BLS12381_DECOMPRESS,
-            _ => panic!("invalid syscall number: {value}"),
-        }
+            0x00_01_01_2C => SyscallCode::ED_SCALAR_MUL,
+            0x00_01_01_2D => SyscallCode::BIGINT_ADD,
+            0x00_01_01_2E => SyscallCode::BIGINT_SUB,
+            0x00_10_01_2F => SyscallCode::BIGINT_MUL,
+            0x00_01_01_30 => SyscallCode::DILITHIUM_ADD,
+            0x00_01_01_31 => SyscallCode::DILITHIUM_SUB,
+            0x00_01_01_32 => SyscallCode::DILITHIUM_MUL,
+            0x00_01_01_33 => SyscallCode::DILITHIUM_BUTTERFLY,
+            0x00_01_01_34 => SyscallCode::WIDE_ALU_ADD,
+            0x00_01_01_35 => SyscallCode::WIDE_ALU_SUB,
+            0x00_01_01_36 => SyscallCode::WIDE_ALU_MUL,
+            0x00_01_01_37 => SyscallCode::WIDE_ALU_LTU,
+            0x00_01_01_38 => SyscallCode::WIDE_ALU_LT,
+            0x00_01_01_39 => SyscallCode::ED_MSM,
+            0x00_01_01_3A => SyscallCode::SECP256K1_MSM,
+            0x00_01_01_3B => SyscallCode::BN254_MSM,
+            0x00_01_01_3C => SyscallCode::RIPEMD160_COMPRESS,
+            0x00_01_01_3D => SyscallCode::BN254_FP_BATCH_INV,
+            0x00_01_01_3E => SyscallCode::BLS12381_FP_BATCH_INV,
+            0x00_01_01_3F => SyscallCode::BYTE_SCAN_FIND,
+            0x00_01_01_40 => SyscallCode::BYTE_SCAN_COPY,
+            0x00_01_01_41 => SyscallCode::BYTE_SCAN_HEX_DECODE,
+            0x00_01_01_42 => SyscallCode::HINT_READ_WORDS,
+            _ => return None,
+        };
+        Some(code)
     }
 
     /// Get the system call identifier.
@@ -203,6 +330,17 @@ impl SyscallCode {
             SyscallCode::BLS12381_FP_SUB => SyscallCode::BLS12381_FP_ADD,
             SyscallCode::BLS12381_FP_MUL => SyscallCode::BLS12381_FP_ADD,
             SyscallCode::BLS12381_FP2_SUB => SyscallCode::BLS12381_FP2_ADD,
+            SyscallCode::BIGINT_SUB => SyscallCode::BIGINT_ADD,
+            SyscallCode::BIGINT_MUL => SyscallCode::BIGINT_ADD,
+            SyscallCode::DILITHIUM_SUB => SyscallCode::DILITHIUM_ADD,
+            SyscallCode::DILITHIUM_MUL => SyscallCode::DILITHIUM_ADD,
+            SyscallCode::DILITHIUM_BUTTERFLY => SyscallCode::DILITHIUM_ADD,
+            SyscallCode::WIDE_ALU_SUB => SyscallCode::WIDE_ALU_ADD,
+            SyscallCode::WIDE_ALU_MUL => SyscallCode::WIDE_ALU_ADD,
+            SyscallCode::WIDE_ALU_LTU => SyscallCode::WIDE_ALU_ADD,
+            SyscallCode::WIDE_ALU_LT => SyscallCode::WIDE_ALU_ADD,
+            SyscallCode::BYTE_SCAN_COPY => SyscallCode::BYTE_SCAN_FIND,
+            SyscallCode::BYTE_SCAN_HEX_DECODE => SyscallCode::BYTE_SCAN_FIND,
             _ => *self,
         }
     }
@@ -20,6 +20,8 @@ pub struct SyscallContext<'a, 'b: 'a> {
     pub rt: &'a mut Executor<'b>,
     /// The syscall lookup id.
     pub syscall_lookup_id: LookupId,
+    /// Whether the syscall reused the result of an earlier call.
+    pub memoized: bool,
 }
 
 impl<'a, 'b> SyscallContext<'a, 'b> {
@@ -34,6 +36,7 @@ impl<'a, 'b> SyscallContext<'a, 'b> {
             exit_code: 0,
             rt: runtime,
             syscall_lookup_id: LookupId::default(),
+            memoized: false,
         }
     }
 
@@ -1,3 +1,5 @@
+use crate::Register;
+
 use super::{context::SyscallContext, Syscall};
 
 pub(crate) struct HaltSyscall;
@@ -9,3 +11,23 @@ impl Syscall for HaltSyscall {
         None
     }
 }
+
+/// The maximum number of bytes of the message of an `ABORT` syscall. Longer messages are truncated.
+pub const MAX_ABORT_MESSAGE_LEN: u32 = 1024;
+
+pub(crate) struct AbortSyscall;
+
+impl Syscall for AbortSyscall {
+    /// Records the reason code and the message of length `a2` at `message_ptr`, which the executor
+    /// returns as an `ExecutionError::GuestAbort` right after the syscall.
+    ///
+    /// The message is read without memory events, since an aborted execution is never proven. Its
+    /// length is chosen by the guest, so only its first [`MAX_ABORT_MESSAGE_LEN`] bytes are read.
+    fn execute(&self, ctx: &mut SyscallContext, code: u32, message_ptr: u32) -> Option<u32> {
+        let rt = &mut ctx.rt;
+        let len = rt.register(Register::X12).min(MAX_ABORT_MESSAGE_LEN);
+        let bytes = (0..len).map(|i| rt.byte(message_ptr.wrapping_add(i))).collect::<Vec<u8>>();
+        rt.guest_abort = Some((code, String::from_utf8_lossy(&bytes).into_owned()));
+        None
+    }
+}
@@ -4,6 +4,7 @@ pub(crate) struct HintLenSyscall;
 
 impl Syscall for HintLenSyscall {
     fn execute(&self, ctx: &mut SyscallContext, _arg1: u32, _arg2: u32) -> Option<u32> {
+        ctx.rt.pull_input();
         if ctx.rt.state.input_stream_ptr >= ctx.rt.state.input_stream.len() {
             panic!(
                 "failed reading stdin due to insufficient input data: input_stream_ptr={}, input_stream_len={}",
@@ -19,6 +20,7 @@ pub(crate) struct HintReadSyscall;
 
 impl Syscall for HintReadSyscall {
     fn execute(&self, ctx: &mut SyscallContext, ptr: u32, len: u32) -> Option<u32> {
+        ctx.rt.pull_input();
         if ctx.rt.state.input_stream_ptr >= ctx.rt.state.input_stream.len() {
             panic!(
                 "failed reading stdin due to insufficient input data: input_stream_ptr={}, input_stream_len={}",
@@ -54,3 +56,56 @@ impl Syscall for HintReadSyscall {
         None
     }
 }
+
+pub(crate) struct CycleInfoSyscall;
+
+impl Syscall for CycleInfoSyscall {
+    /// Writes the number of cycles executed before this one, as a little-endian `u64`, and the
+    /// number of extra cycles of the last precompile call, at `ptr`.
+    ///
+    /// Like `HINT_REMAINING`, the result is written to uninitialized memory, so it is not
+    /// constrained by the proof.
+    fn execute(&self, ctx: &mut SyscallContext, ptr: u32, _arg2: u32) -> Option<u32> {
+        assert_eq!(ptr % 4, 0, "cycle info address not aligned to 4 bytes");
+        let clk = ctx.rt.state.global_clk;
+        let words = [clk as u32, (clk >> 32) as u32, ctx.rt.state.last_precompile_cycles];
+        for (i, word) in words.into_iter().enumerate() {
+            ctx.rt
+                .state
+                .uninitialized_memory
+                .entry(ptr + 4 * i as u32)
+                .and_modify(|_| panic!("cycle info address is initialized already"))
+                .or_insert(word);
+        }
+        None
+    }
+}
+
+pub(crate) struct HintRemainingSyscall;
+
+impl Syscall for HintRemainingSyscall {
+    /// Writes the number of elements of the hint stream that were not read yet at `ptr`.
+    ///
+    /// Like `PAGED_INPUT_LEN`, the result is written to memory rather than returned in `t0`, since
+    /// the CPU only allows `ENTER_UNCONSTRAINED` and `HINT_LEN` to write a result register.
+    ///
+    /// With an input source, at most one element is pulled ahead, so the count is only non-zero
+    /// when the source is not exhausted.
+    fn execute(&self, ctx: &mut SyscallContext, ptr: u32, _arg2: u32) -> Option<u32> {
+        assert!(
+            !ctx.rt.unconstrained,
+            "hint remaining should not be used in a unconstrained block"
+        );
+        assert_eq!(ptr % 4, 0, "hint remaining address not aligned to 4 bytes");
+        ctx.rt.pull_input();
+        let remaining =
+            ctx.rt.state.input_stream.len().saturating_sub(ctx.rt.state.input_stream_ptr) as u32;
+        ctx.rt
+            .state
+            .uninitialized_memory
+            .entry(ptr)
+            .and_modify(|_| panic!("hint remaining address is initialized already"))
+            .or_insert(remaining);
+        None
+    }
+}
@@ -6,7 +6,9 @@ mod context;
 mod deferred;
 mod halt;
 mod hint;
+mod paged_input;
 mod precompiles;
+mod rom;
 mod unconstrained;
 mod verify;
 mod write;
@@ -15,23 +17,36 @@ use std::sync::Arc;
 
 use commit::CommitSyscall;
 use deferred::CommitDeferredSyscall;
-use halt::HaltSyscall;
+use halt::{AbortSyscall, HaltSyscall};
 use hashbrown::HashMap;
 
 pub use code::*;
 pub use context::*;
-use hint::{HintLenSyscall, HintReadSyscall};
+pub use halt::MAX_ABORT_MESSAGE_LEN;
+use hint::{CycleInfoSyscall, HintLenSyscall, HintReadSyscall, HintRemainingSyscall};
+use paged_input::{PagedInputLenSyscall, PagedInputMapSyscall};
+pub use paged_input::{PAGED_INPUT_PAGE_BITS, PAGED_INPUT_PAGE_SIZE};
 use precompiles::{
-    edwards::{add::EdwardsAddAssignSyscall, decompress::EdwardsDecompressSyscall},
-    fptower::{Fp2AddSubSyscall, Fp2MulSyscall, FpOpSyscall},
+    bigint::BigIntSyscall,
+    byte_scan::ByteScanSyscall,
+    dilithium::DilithiumOpSyscall,
+    edwards::{
+        add::EdwardsAddAssignSyscall, decompress::EdwardsDecompressSyscall, msm::EdwardsMsmSyscall,
+        scalar_mul::EdwardsScalarMulSyscall,
+    },
+    fptower::{Fp2AddSubSyscall, Fp2MulSyscall, FpBatchInvSyscall, FpOpSyscall},
+    hint_words::HintReadWordsSyscall,
     keccak256::permute::Keccak256PermuteSyscall,
+    ripemd160::Ripemd160CompressSyscall,
     sha256::{compress::Sha256CompressSyscall, extend::Sha256ExtendSyscall},
     uint256::Uint256MulSyscall,
     weierstrass::{
         add::WeierstrassAddAssignSyscall, decompress::WeierstrassDecompressSyscall,
-        double::WeierstrassDoubleAssignSyscall,
+        double::WeierstrassDoubleAssignSyscall, msm::WeierstrassMsmSyscall,
     },
+    wide_alu::WideAluSyscall,
 };
+use rom::RomMapSyscall;
 
 use sp1_curves::{
     edwards::ed25519::{Ed25519, Ed25519Parameters},
@@ -45,7 +60,7 @@ use unconstrained::{EnterUnconstrainedSyscall, ExitUnconstrainedSyscall};
 use verify::VerifySyscall;
 use write::WriteSyscall;
 
-use crate::events::FieldOperation;
+use crate::events::{ByteScanOperation, DilithiumOperation, FieldOperation, WideAluOperation};
 
 /// A system call in the SP1 RISC-V zkVM.
 ///
@@ -78,6 +93,8 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
 
     syscall_map.insert(SyscallCode::SHA_COMPRESS, Arc::new(Sha256CompressSyscall));
 
+    syscall_map.insert(SyscallCode::RIPEMD160_COMPRESS, Arc::new(Ripemd160CompressSyscall));
+
     syscall_map.insert(SyscallCode::ED_ADD, Arc::new(EdwardsAddAssignSyscall::<Ed25519>::new()));
 
     syscall_map.insert(
@@ -85,8 +102,76 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
         Arc::new(EdwardsDecompressSyscall::<Ed25519Parameters>::new()),
     );
 
+    syscall_map
+        .insert(SyscallCode::ED_SCALAR_MUL, Arc::new(EdwardsScalarMulSyscall::<Ed25519>::new()));
+
+    syscall_map.insert(SyscallCode::ED_MSM, Arc::new(EdwardsMsmSyscall::<Ed25519>::new()));
+
+    syscall_map
+        .insert(SyscallCode::SECP256K1_MSM, Arc::new(WeierstrassMsmSyscall::<Secp256k1>::new()));
+
+    syscall_map.insert(SyscallCode::BN254_MSM, Arc::new(WeierstrassMsmSyscall::<Bn254>::new()));
+
     syscall_map.insert(SyscallCode::KECCAK_PERMUTE, Arc::new(Keccak256PermuteSyscall));
 
+    syscall_map.insert(SyscallCode::BIGINT_ADD, Arc::new(BigIntSyscall::new(FieldOperation::Add)));
+
+    syscall_map.insert(SyscallCode::BIGINT_SUB, Arc::new(BigIntSyscall::new(FieldOperation::Sub)));
+
+    syscall_map.insert(SyscallCode::BIGINT_MUL, Arc::new(BigIntSyscall::new(FieldOperation::Mul)));
+
+    syscall_map.insert(
+        SyscallCode::BYTE_SCAN_FIND,
+        Arc::new(ByteScanSyscall::new(ByteScanOperation::Find)),
+    );
+
+    syscall_map.insert(
+        SyscallCode::BYTE_SCAN_COPY,
+        Arc::new(ByteScanSyscall::new(ByteScanOperation::Copy)),
+    );
+
+    syscall_map.insert(
+        SyscallCode::BYTE_SCAN_HEX_DECODE,
+        Arc::new(ByteScanSyscall::new(ByteScanOperation::HexDecode)),
+    );
+
+    syscall_map.insert(SyscallCode::HINT_READ_WORDS, Arc::new(HintReadWordsSyscall));
+
+    syscall_map.insert(
+        SyscallCode::DILITHIUM_ADD,
+        Arc::new(DilithiumOpSyscall::new(DilithiumOperation::Add)),
+    );
+
+    syscall_map.insert(
+        SyscallCode::DILITHIUM_SUB,
+        Arc::new(DilithiumOpSyscall::new(DilithiumOperation::Sub)),
+    );
+
+    syscall_map.insert(
+        SyscallCode::DILITHIUM_MUL,
+        Arc::new(DilithiumOpSyscall::new(DilithiumOperation::Mul)),
+    );
+
+    syscall_map.insert(
+        SyscallCode::DILITHIUM_BUTTERFLY,
+        Arc::new(DilithiumOpSyscall::new(DilithiumOperation::Butterfly)),
+    );
+
+    syscall_map
+        .insert(SyscallCode::WIDE_ALU_ADD, Arc::new(WideAluSyscall::new(WideAluOperation::Add)));
+
+    syscall_map
+        .insert(SyscallCode::WIDE_ALU_SUB, Arc::new(WideAluSyscall::new(WideAluOperation::Sub)));
+
+    syscall_map
+        .insert(SyscallCode::WIDE_ALU_MUL, Arc::new(WideAluSyscall::new(WideAluOperation::Mul)));
+
+    syscall_map
+        .insert(SyscallCode::WIDE_ALU_LTU, Arc::new(WideAluSyscall::new(WideAluOperation::Ltu)));
+
+    syscall_map
+        .insert(SyscallCode::WIDE_ALU_LT, Arc::new(WideAluSyscall::new(WideAluOperation::Lt)));
+
     syscall_map.insert(
         SyscallCode::SECP256K1_ADD,
         Arc::new(WeierstrassAddAssignSyscall::<Secp256k1>::new()),
@@ -150,6 +235,11 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
     syscall_map
         .insert(SyscallCode::BLS12381_FP2_MUL, Arc::new(Fp2MulSyscall::<Bls12381BaseField>::new()));
 
+    syscall_map.insert(
+        SyscallCode::BLS12381_FP_BATCH_INV,
+        Arc::new(FpBatchInvSyscall::<Bls12381BaseField>::new()),
+    );
+
     syscall_map.insert(
         SyscallCode::BN254_FP_ADD,
         Arc::new(FpOpSyscall::<Bn254BaseField>::new(FieldOperation::Add)),
@@ -178,6 +268,11 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
     syscall_map
         .insert(SyscallCode::BN254_FP2_MUL, Arc::new(Fp2MulSyscall::<Bn254BaseField>::new()));
 
+    syscall_map.insert(
+        SyscallCode::BN254_FP_BATCH_INV,
+        Arc::new(FpBatchInvSyscall::<Bn254BaseField>::new()),
+    );
+
     syscall_map.insert(SyscallCode::ENTER_UNCONSTRAINED, Arc::new(EnterUnconstrainedSyscall));
 
     syscall_map.insert(SyscallCode::EXIT_UNCONSTRAINED, Arc::new(ExitUnconstrainedSyscall));
@@ -194,6 +289,18 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
 
     syscall_map.insert(SyscallCode::HINT_READ, Arc::new(HintReadSyscall));
 
+    syscall_map.insert(SyscallCode::PAGED_INPUT_LEN, Arc::new(PagedInputLenSyscall));
+
+    syscall_map.insert(SyscallCode::PAGED_INPUT_MAP, Arc::new(PagedInputMapSyscall));
+
+    syscall_map.insert(SyscallCode::HINT_REMAINING, Arc::new(HintRemainingSyscall));
+
+    syscall_map.insert(SyscallCode::CYCLE_INFO, Arc::new(CycleInfoSyscall));
+
+    syscall_map.insert(SyscallCode::ABORT, Arc::new(AbortSyscall));
+
+    syscall_map.insert(SyscallCode::ROM_MAP, Arc::new(RomMapSyscall));
+
     syscall_map.insert(
         SyscallCode::BLS12381_DECOMPRESS,
         Arc::new(WeierstrassDecompressSyscall::<Bls12381>::new()),
@@ -0,0 +1,79 @@
+//! Paged inputs: large input blobs that the guest maps into its memory one page at a time.
+//!
+//! Unlike the hint stream, which copies each input into guest memory as a whole, a paged input
+//! stays on the host. The guest reads the length of an input with `PAGED_INPUT_LEN` and maps the
+//! pages it needs with `PAGED_INPUT_MAP`. Since both only write to the uninitialized memory of
+//! the executor, only the words that the guest actually reads enter the memory argument.
+
+use super::{Syscall, SyscallContext};
+
+/// The size of a page of a paged input, in bytes.
+pub const PAGED_INPUT_PAGE_SIZE: u32 = 4096;
+
+/// The number of bits of the page index in the argument of `PAGED_INPUT_MAP`, whose upper bits
+/// hold the index of the paged input.
+pub const PAGED_INPUT_PAGE_BITS: u32 = 20;
+
+pub(crate) struct PagedInputLenSyscall;
+
+impl Syscall for PagedInputLenSyscall {
+    /// Writes the length of the paged input `index` at `ptr`, as a little-endian `u64`.
+    ///
+    /// The length is written to memory rather than returned in `t0`, since the CPU only allows
+    /// `ENTER_UNCONSTRAINED` and `HINT_LEN` to write a result register.
+    fn execute(&self, ctx: &mut SyscallContext, index: u32, ptr: u32) -> Option<u32> {
+        assert!(
+            !ctx.rt.unconstrained,
+            "paged input len should not be used in a unconstrained block"
+        );
+        assert_eq!(ptr % 4, 0, "paged input len address not aligned to 4 bytes");
+        let Some(input) = ctx.rt.state.paged_inputs.get(index as usize) else {
+            panic!("paged input {index} was not registered");
+        };
+        let len = input.len() as u64;
+        for (addr, word) in [(ptr, len as u32), (ptr + 4, (len >> 32) as u32)] {
+            ctx.rt
+                .state
+                .uninitialized_memory
+                .entry(addr)
+                .and_modify(|_| panic!("paged input len address is initialized already"))
+                .or_insert(word);
+        }
+        None
+    }
+}
+
+pub(crate) struct PagedInputMapSyscall;
+
+impl Syscall for PagedInputMapSyscall {
+    /// Maps the page `page & (2^PAGED_INPUT_PAGE_BITS - 1)` of the paged input
+    /// `page >> PAGED_INPUT_PAGE_BITS` at `ptr`. Bytes past the end of the input are zero.
+    fn execute(&self, ctx: &mut SyscallContext, ptr: u32, page: u32) -> Option<u32> {
+        assert!(
+            !ctx.rt.unconstrained,
+            "paged input map should not be used in a unconstrained block"
+        );
+        assert_eq!(ptr % 4, 0, "paged input map address not aligned to 4 bytes");
+        let index = (page >> PAGED_INPUT_PAGE_BITS) as usize;
+        let page = page & ((1 << PAGED_INPUT_PAGE_BITS) - 1);
+        let Some(input) = ctx.rt.state.paged_inputs.get(index).cloned() else {
+            panic!("paged input {index} was not registered");
+        };
+
+        let start = page as usize * PAGED_INPUT_PAGE_SIZE as usize;
+        assert!(start < input.len(), "page {page} is past the end of paged input {index}");
+        for i in (0..PAGED_INPUT_PAGE_SIZE).step_by(4) {
+            let offset = start + i as usize;
+            let bytes = core::array::from_fn(|j| input.get(offset + j).copied().unwrap_or(0));
+            // Like hints, the page is written to uninitialized memory, so that only the words the
+            // guest reads are initialized.
+            ctx.rt
+                .state
+                .uninitialized_memory
+                .entry(ptr + i)
+                .and_modify(|_| panic!("paged input map address is initialized already"))
+                .or_insert(u32::from_le_bytes(bytes));
+        }
+        None
+    }
+}
@@ -0,0 +1,161 @@
+use num::{BigUint, One, Zero};
+
+use sp1_primitives::consts::{bytes_to_words_le, words_to_bytes_le_vec, WORD_SIZE};
+
+use crate::{
+    events::{BigIntEvent, FieldOperation, BIGINT_LIMB_WORDS, BIGINT_MAX_LIMBS},
+    syscalls::{Syscall, SyscallContext},
+};
+
+pub(crate) struct BigIntSyscall {
+    op: FieldOperation,
+}
+
+impl BigIntSyscall {
+    pub const fn new(op: FieldOperation) -> Self {
+        Self { op }
+    }
+}
+
+/// Decodes the `i`-th limb of a list of words.
+fn limb(words: &[u32], i: usize) -> BigUint {
+    BigUint::from_bytes_le(&words_to_bytes_le_vec(
+        &words[i * BIGINT_LIMB_WORDS..(i + 1) * BIGINT_LIMB_WORDS],
+    ))
+}
+
+/// Encodes a limb as a list of words.
+fn limb_to_words(value: &BigUint) -> [u32; BIGINT_LIMB_WORDS] {
+    let mut bytes = value.to_bytes_le();
+    bytes.resize(BIGINT_LIMB_WORDS * WORD_SIZE, 0u8);
+    bytes_to_words_le::<BIGINT_LIMB_WORDS>(&bytes)
+}
+
+impl Syscall for BigIntSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        match self.op {
+            FieldOperation::Mul => BIGINT_MAX_LIMBS as u32,
+            _ => 1,
+        }
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let clk = rt.clk;
+
+        let z_ptr = arg1;
+        if z_ptr % 4 != 0 {
+            panic!();
+        }
+        let operands_ptr = arg2;
+        if operands_ptr % 4 != 0 {
+            panic!();
+        }
+
+        // Read the number of limbs, which precedes the operands.
+        let (num_limbs_memory_record, num_limbs) = rt.mr(operands_ptr);
+        let n = num_limbs as usize;
+        assert!((1..=BIGINT_MAX_LIMBS).contains(&n), "invalid number of bigint limbs: {n}");
+
+        let limb_bytes = (BIGINT_LIMB_WORDS * WORD_SIZE) as u32;
+        let x_ptr = operands_ptr + WORD_SIZE as u32;
+        let y_ptr = x_ptr + num_limbs * limb_bytes;
+        let z_len = if self.op == FieldOperation::Mul { 2 * n } else { n };
+
+        // The result is written while the operands are still being read, so they must not overlap.
+        let z_end = z_ptr + z_len as u32 * limb_bytes;
+        let operands_end = y_ptr + num_limbs * limb_bytes;
+        assert!(
+            z_end <= operands_ptr || operands_end <= z_ptr,
+            "the bigint result must not overlap the operands"
+        );
+
+        let x = rt.slice_unsafe(x_ptr, n * BIGINT_LIMB_WORDS);
+        let y = rt.slice_unsafe(y_ptr, n * BIGINT_LIMB_WORDS);
+
+        let modulus = BigUint::one() << (limb_bytes * 8);
+        let mut x_memory_records = Vec::new();
+        let mut y_memory_records = Vec::new();
+        let mut z_memory_records = Vec::new();
+        let mut hi_memory_records = Vec::new();
+        match self.op {
+            FieldOperation::Add | FieldOperation::Sub => {
+                // One limb at a time, propagating the carry (or borrow) to the next limb.
+                let mut carry = BigUint::zero();
+                for i in 0..n {
+                    let limb_offset = i as u32 * limb_bytes;
+                    let (records, _) = rt.mr_slice(x_ptr + limb_offset, BIGINT_LIMB_WORDS);
+                    x_memory_records.extend(records);
+                    let (records, _) = rt.mr_slice(y_ptr + limb_offset, BIGINT_LIMB_WORDS);
+                    y_memory_records.extend(records);
+
+                    let (x_i, y_i) = (limb(&x, i), limb(&y, i));
+                    let z_i = if self.op == FieldOperation::Add {
+                        let sum = x_i + y_i + &carry;
+                        carry = &sum >> (limb_bytes * 8);
+                        sum % &modulus
+                    } else {
+                        let subtrahend = y_i + &carry;
+                        carry = BigUint::from(u8::from(x_i < subtrahend));
+                        (x_i + &modulus * &carry - subtrahend) % &modulus
+                    };
+                    z_memory_records.extend(rt.mw_slice(z_ptr + limb_offset, &limb_to_words(&z_i)));
+                }
+            }
+            FieldOperation::Mul => {
+                // Schoolbook multiplication. The `j`-th pass multiplies x by the `j`-th limb of y
+                // and accumulates it into the result in memory, one cycle per pass.
+                let mut z = vec![BigUint::zero(); 2 * n];
+                for j in 0..n {
+                    let (records, _) =
+                        rt.mr_slice(y_ptr + j as u32 * limb_bytes, BIGINT_LIMB_WORDS);
+                    y_memory_records.extend(records);
+
+                    let y_j = limb(&y, j);
+                    let mut carry = BigUint::zero();
+                    for i in 0..n {
+                        let (records, _) =
+                            rt.mr_slice(x_ptr + i as u32 * limb_bytes, BIGINT_LIMB_WORDS);
+                        x_memory_records.extend(records);
+
+                        let acc = &z[i + j] + limb(&x, i) * &y_j + &carry;
+                        carry = &acc >> (limb_bytes * 8);
+                        z[i + j] = acc % &modulus;
+                        z_memory_records.extend(rt.mw_slice(
+                            z_ptr + (i + j) as u32 * limb_bytes,
+                            &limb_to_words(&z[i + j]),
+                        ));
+                    }
+                    z[j + n] = carry;
+                    hi_memory_records.extend(
+                        rt.mw_slice(z_ptr + (j + n) as u32 * limb_bytes, &limb_to_words(&z[j + n])),
+                    );
+                    rt.clk += 1;
+                }
+            }
+            FieldOperation::Div => unreachable!(),
+        }
+
+        let lookup_id = rt.syscall_lookup_id;
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        rt.record_mut().bigint_events.push(BigIntEvent {
+            lookup_id,
+            shard,
+            channel,
+            clk,
+            op: self.op,
+            z_ptr,
+            operands_ptr,
+            num_limbs,
+            x,
+            y,
+            num_limbs_memory_record,
+            x_memory_records,
+            y_memory_records,
+            z_memory_records,
+            hi_memory_records,
+        });
+
+        None
+    }
+}
@@ -0,0 +1,114 @@
+use sp1_primitives::consts::{bytes_to_words_le_vec, words_to_bytes_le_vec, WORD_SIZE};
+
+use crate::{
+    events::{
+        hex_digit_value, ByteScanEvent, ByteScanOperation, BYTE_SCAN_HEADER_WORDS,
+        BYTE_SCAN_MAX_WORDS,
+    },
+    syscalls::{Syscall, SyscallContext},
+};
+
+pub(crate) struct ByteScanSyscall {
+    op: ByteScanOperation,
+}
+
+impl ByteScanSyscall {
+    pub const fn new(op: ByteScanOperation) -> Self {
+        Self { op }
+    }
+}
+
+/// Whether the word ranges `[a, a + 4 * a_len)` and `[b, b + 4 * b_len)` do not overlap.
+fn disjoint(a: u32, a_len: usize, b: u32, b_len: usize) -> bool {
+    let (a, b) = (a as u64, b as u64);
+    a + (a_len * WORD_SIZE) as u64 <= b || b + (b_len * WORD_SIZE) as u64 <= a
+}
+
+impl Syscall for ByteScanSyscall {
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let clk = rt.clk;
+
+        let dst_ptr = arg1;
+        if dst_ptr % 4 != 0 {
+            panic!();
+        }
+        let operands_ptr = arg2;
+        if operands_ptr % 4 != 0 {
+            panic!();
+        }
+
+        // Read the header, made of the number of words, the source pointer and the delimiter.
+        let (header_memory_records, header) = rt.mr_slice(operands_ptr, BYTE_SCAN_HEADER_WORDS);
+        let [num_words, src_ptr, delimiter] = [header[0], header[1], header[2]];
+        let n = num_words as usize;
+        assert!((1..=BYTE_SCAN_MAX_WORDS).contains(&n), "invalid number of byte scan words: {n}");
+        if src_ptr % 4 != 0 {
+            panic!();
+        }
+        let delimiter = if self.op == ByteScanOperation::Find {
+            assert!(delimiter <= u8::MAX as u32, "invalid byte scan delimiter: {delimiter}");
+            delimiter
+        } else {
+            0
+        };
+
+        // All the words are accessed in the same cycle, so the result must not overlap the header
+        // or the source, and the source must not overlap the header.
+        let (src_len, dst_len) = (self.op.num_src_words(n), self.op.num_dst_words(n));
+        assert!(
+            disjoint(dst_ptr, dst_len, operands_ptr, BYTE_SCAN_HEADER_WORDS)
+                && disjoint(dst_ptr, dst_len, src_ptr, src_len)
+                && disjoint(src_ptr, src_len, operands_ptr, BYTE_SCAN_HEADER_WORDS),
+            "the byte scan result, source and header must not overlap"
+        );
+
+        let (src_memory_records, src) = rt.mr_slice(src_ptr, src_len);
+
+        let dst = match self.op {
+            ByteScanOperation::Find => {
+                let bytes = words_to_bytes_le_vec(&src);
+                let position = bytes.iter().position(|&b| b as u32 == delimiter);
+                vec![position.unwrap_or(bytes.len()) as u32]
+            }
+            ByteScanOperation::Copy => src.clone(),
+            ByteScanOperation::HexDecode => {
+                let bytes = words_to_bytes_le_vec(&src)
+                    .chunks_exact(2)
+                    .map(|pair| {
+                        let digit = |c: u8| {
+                            hex_digit_value(c)
+                                .unwrap_or_else(|| panic!("invalid hex digit: {c:#04x}"))
+                        };
+                        digit(pair[0]) << 4 | digit(pair[1])
+                    })
+                    .collect::<Vec<_>>();
+                bytes_to_words_le_vec(&bytes)
+            }
+        };
+
+        let dst_memory_records = rt.mw_slice(dst_ptr, &dst);
+
+        let lookup_id = rt.syscall_lookup_id;
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        rt.record_mut().byte_scan_events.push(ByteScanEvent {
+            lookup_id,
+            shard,
+            channel,
+            clk,
+            op: self.op,
+            dst_ptr,
+            operands_ptr,
+            num_words,
+            src_ptr,
+            delimiter,
+            src,
+            dst,
+            header_memory_records: header_memory_records.try_into().unwrap(),
+            src_memory_records,
+            dst_memory_records,
+        });
+
+        None
+    }
+}
@@ -0,0 +1,93 @@
+use sp1_primitives::consts::WORD_SIZE;
+
+use crate::{
+    events::{DilithiumOpEvent, DilithiumOperation, DILITHIUM_BLOCK_WORDS, DILITHIUM_Q},
+    syscalls::{Syscall, SyscallContext},
+};
+
+pub(crate) struct DilithiumOpSyscall {
+    op: DilithiumOperation,
+}
+
+impl DilithiumOpSyscall {
+    pub const fn new(op: DilithiumOperation) -> Self {
+        Self { op }
+    }
+}
+
+impl Syscall for DilithiumOpSyscall {
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let clk = rt.clk;
+        let x_ptr = arg1;
+        if x_ptr % 4 != 0 {
+            panic!();
+        }
+        let y_ptr = arg2;
+        if y_ptr % 4 != 0 {
+            panic!();
+        }
+
+        // The butterfly writes both blocks, so they must not overlap.
+        let block_bytes = (DILITHIUM_BLOCK_WORDS * WORD_SIZE) as u32;
+        if self.op == DilithiumOperation::Butterfly {
+            assert!(
+                x_ptr + block_bytes <= y_ptr || y_ptr + block_bytes <= x_ptr,
+                "the blocks of a dilithium butterfly must not overlap"
+            );
+        }
+
+        let x = rt.slice_unsafe(x_ptr, DILITHIUM_BLOCK_WORDS);
+        let y = rt.slice_unsafe(y_ptr, DILITHIUM_BLOCK_WORDS);
+
+        assert!(
+            x.iter().chain(y.iter()).all(|&coeff| coeff < DILITHIUM_Q),
+            "dilithium coefficients must be reduced modulo q"
+        );
+
+        let q = u64::from(DILITHIUM_Q);
+        let (x_result, diff): (Vec<u32>, Vec<u32>) = x
+            .iter()
+            .zip(y.iter())
+            .map(|(&a, &b)| {
+                let (a, b) = (u64::from(a), u64::from(b));
+                let diff = (a + q - b) % q;
+                let result = match self.op {
+                    DilithiumOperation::Add | DilithiumOperation::Butterfly => (a + b) % q,
+                    DilithiumOperation::Sub => diff,
+                    DilithiumOperation::Mul => (a * b) % q,
+                };
+                (result as u32, diff as u32)
+            })
+            .unzip();
+
+        // The y block is only modified by the butterfly, otherwise it is written back unchanged.
+        let y_result = if self.op == DilithiumOperation::Butterfly { diff } else { y.clone() };
+        let y_memory_records = rt.mw_slice(y_ptr, &y_result);
+
+        rt.clk += 1;
+        let x_memory_records = rt.mw_slice(x_ptr, &x_result);
+
+        let lookup_id = rt.syscall_lookup_id;
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        rt.record_mut().dilithium_events.push(DilithiumOpEvent {
+            lookup_id,
+            shard,
+            channel,
+            clk,
+            op: self.op,
+            x_ptr,
+            x,
+            y_ptr,
+            y,
+            x_memory_records,
+            y_memory_records,
+        });
+
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
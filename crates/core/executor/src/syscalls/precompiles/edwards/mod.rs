@@ -1,2 +1,4 @@
 pub mod add;
 pub mod decompress;
+pub mod msm;
+pub mod scalar_mul;
@@ -0,0 +1,37 @@
+use std::marker::PhantomData;
+
+use sp1_curves::{
+    edwards::{EdwardsParameters, WORDS_CURVE_POINT},
+    AffinePoint, EllipticCurve,
+};
+
+use crate::{
+    events::create_msm_event,
+    syscalls::{Syscall, SyscallContext},
+};
+
+pub(crate) struct EdwardsMsmSyscall<E: EllipticCurve + EdwardsParameters> {
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EllipticCurve + EdwardsParameters> EdwardsMsmSyscall<E> {
+    /// Create a new instance of the [`EdwardsMsmSyscall`].
+    pub const fn new() -> Self {
+        Self { _phantom: PhantomData }
+    }
+}
+
+impl<E: EllipticCurve + EdwardsParameters> Syscall for EdwardsMsmSyscall<E> {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        // Points are in affine coordinates, since the twisted Edwards addition is complete.
+        let event = create_msm_event(rt, arg1, arg2, WORDS_CURVE_POINT, |p, q| {
+            (AffinePoint::<E>::from_words_le(p) + AffinePoint::<E>::from_words_le(q)).to_words_le()
+        });
+        rt.record_mut().ed_msm_events.push(event);
+        None
+    }
+}
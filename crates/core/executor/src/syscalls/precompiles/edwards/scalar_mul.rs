@@ -0,0 +1,85 @@
+use std::marker::PhantomData;
+
+use sp1_curves::{
+    edwards::{EdwardsParameters, WORDS_CURVE_POINT, WORDS_FIELD_ELEMENT},
+    AffinePoint, EllipticCurve,
+};
+
+use crate::{
+    events::{EdScalarMulEvent, MemoryReadRecord, MemoryWriteRecord},
+    syscalls::{Syscall, SyscallContext},
+};
+
+pub(crate) struct EdwardsScalarMulSyscall<E: EllipticCurve + EdwardsParameters> {
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EllipticCurve + EdwardsParameters> EdwardsScalarMulSyscall<E> {
+    /// Create a new instance of the [`EdwardsScalarMulSyscall`].
+    pub const fn new() -> Self {
+        Self { _phantom: PhantomData }
+    }
+}
+
+impl<E: EllipticCurve + EdwardsParameters> Syscall for EdwardsScalarMulSyscall<E> {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let start_clk = rt.clk;
+        let p_ptr = arg1;
+        assert!(p_ptr % 4 == 0, "p_ptr must be 4-byte aligned");
+        let scalar_ptr = arg2;
+        assert!(scalar_ptr % 4 == 0, "scalar_ptr must be 4-byte aligned");
+
+        let p: [u32; WORDS_CURVE_POINT] =
+            rt.slice_unsafe(p_ptr, WORDS_CURVE_POINT).try_into().unwrap();
+
+        let (scalar_memory_records_vec, scalar_vec) = rt.mr_slice(scalar_ptr, WORDS_FIELD_ELEMENT);
+        let scalar_memory_records: [MemoryReadRecord; WORDS_FIELD_ELEMENT] =
+            scalar_memory_records_vec.try_into().unwrap();
+        let scalar: [u32; WORDS_FIELD_ELEMENT] = scalar_vec.try_into().unwrap();
+
+        // When we write to p, we want the clk to be incremented because p and the scalar could
+        // overlap.
+        rt.clk += 1;
+
+        // Compute the result with the same double-and-add schedule used by the chip, so that the
+        // result is well defined for every 256-bit scalar (including those above the group order).
+        let mut base = AffinePoint::<E>::from_words_le(&p);
+        let mut result = E::ec_neutral().unwrap();
+        for &word in scalar.iter() {
+            for i in 0..32 {
+                if (word >> i) & 1 == 1 {
+                    result = &result + &base;
+                }
+                base = E::ec_double(&base);
+            }
+        }
+        let result_affine = result;
+        let result_words = result_affine.to_words_le();
+
+        let p_memory_records_vec = rt.mw_slice(p_ptr, &result_words);
+        let p_memory_records: [MemoryWriteRecord; WORDS_CURVE_POINT] =
+            p_memory_records_vec.try_into().unwrap();
+
+        let lookup_id = rt.syscall_lookup_id;
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        rt.record_mut().ed_scalar_mul_events.push(EdScalarMulEvent {
+            lookup_id,
+            shard,
+            channel,
+            clk: start_clk,
+            p_ptr,
+            p,
+            scalar_ptr,
+            scalar,
+            p_memory_records,
+            scalar_memory_records,
+        });
+
+        None
+    }
+}
@@ -0,0 +1,90 @@
+use num::{BigUint, One, Zero};
+use sp1_curves::{
+    params::NumWords,
+    weierstrass::{FieldType, FpOpField},
+};
+use std::marker::PhantomData;
+use typenum::Unsigned;
+
+use crate::{
+    events::{FpBatchInvEvent, FP_BATCH_INV_MAX_LEN},
+    syscalls::{Syscall, SyscallContext},
+};
+
+pub struct FpBatchInvSyscall<P> {
+    _marker: PhantomData<P>,
+}
+
+impl<P> FpBatchInvSyscall<P> {
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+}
+
+/// Inverts the given non-zero elements with Montgomery's trick: the running products of the
+/// elements are inverted with a single modular exponentiation, and the inverse of each element
+/// is then recovered with two multiplications.
+///
+/// # Panics
+///
+/// Panics if one of the elements is zero modulo `modulus`.
+fn batch_inverse(elements: &[BigUint], modulus: &BigUint) -> Vec<BigUint> {
+    let mut products = Vec::with_capacity(elements.len());
+    let mut acc = BigUint::one();
+    for element in elements {
+        assert!(!(element % modulus).is_zero(), "cannot invert zero");
+        products.push(acc.clone());
+        acc = (acc * element) % modulus;
+    }
+
+    // As the modulus is prime, we can use Fermat's little theorem to compute the inverse.
+    let mut acc_inv = acc.modpow(&(modulus - 2u32), modulus);
+    let mut inverses = vec![BigUint::zero(); elements.len()];
+    for (i, element) in elements.iter().enumerate().rev() {
+        inverses[i] = (&acc_inv * &products[i]) % modulus;
+        acc_inv = (acc_inv * element) % modulus;
+    }
+    inverses
+}
+
+impl<P: FpOpField> Syscall for FpBatchInvSyscall<P> {
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let clk = rt.clk;
+        let x_ptr = arg1;
+        if x_ptr % 4 != 0 {
+            panic!();
+        }
+        let len = arg2;
+        assert!(
+            (1..=FP_BATCH_INV_MAX_LEN).contains(&(len as usize)),
+            "invalid number of field elements to invert: {len}"
+        );
+
+        let num_words = <P as NumWords>::WordsFieldElement::USIZE;
+
+        let x = rt.slice_unsafe(x_ptr, len as usize * num_words);
+
+        let modulus = &BigUint::from_bytes_le(P::MODULUS);
+        let elements = x.chunks_exact(num_words).map(BigUint::from_slice).collect::<Vec<_>>();
+        let mut result = Vec::with_capacity(x.len());
+        for inverse in batch_inverse(&elements, modulus) {
+            let mut words = inverse.to_u32_digits();
+            words.resize(num_words, 0);
+            result.extend(words);
+        }
+
+        let x_memory_records = rt.mw_slice(x_ptr, &result);
+
+        let lookup_id = rt.syscall_lookup_id;
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        let event =
+            FpBatchInvEvent { lookup_id, shard, channel, clk, x_ptr, len, x, x_memory_records };
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => rt.record_mut().bn254_fp_batch_inv_events.push(event),
+            FieldType::Bls12381 => rt.record_mut().bls12381_fp_batch_inv_events.push(event),
+        }
+
+        None
+    }
+}
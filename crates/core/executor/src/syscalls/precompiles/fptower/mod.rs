@@ -1,7 +1,9 @@
+mod batch_inv;
 mod fp;
 mod fp2_addsub;
 mod fp2_mul;
 
+pub use batch_inv::*;
 pub use fp::*;
 pub use fp2_addsub::*;
 pub use fp2_mul::*;
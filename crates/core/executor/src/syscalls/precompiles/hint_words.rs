@@ -0,0 +1,58 @@
+use sp1_primitives::consts::bytes_to_words_le_vec;
+
+use crate::{
+    events::{HintWordsEvent, HINT_WORDS_MAX_WORDS},
+    syscalls::{Syscall, SyscallContext},
+};
+
+pub(crate) struct HintReadWordsSyscall;
+
+impl Syscall for HintReadWordsSyscall {
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let clk = rt.clk;
+
+        let dst_ptr = arg1;
+        if dst_ptr % 4 != 0 {
+            panic!();
+        }
+        let num_words = arg2;
+        let n = num_words as usize;
+        assert!((1..=HINT_WORDS_MAX_WORDS).contains(&n), "invalid number of hint words: {n}");
+        assert!(
+            !rt.rt.unconstrained,
+            "hint read words should not be used in a unconstrained block"
+        );
+
+        // The next element of the input stream must hold exactly the words to write.
+        rt.rt.pull_input();
+        let state = &mut rt.rt.state;
+        let Some(element) = state.input_stream.get(state.input_stream_ptr) else {
+            panic!(
+                "failed reading stdin due to insufficient input data: input_stream_ptr={}, input_stream_len={}",
+                state.input_stream_ptr,
+                state.input_stream.len()
+            );
+        };
+        assert_eq!(element.len(), n * 4, "hint words input stream read length mismatch");
+        let words = bytes_to_words_le_vec(element);
+        state.input_stream_ptr += 1;
+
+        let dst_memory_records = rt.mw_slice(dst_ptr, &words);
+
+        let lookup_id = rt.syscall_lookup_id;
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        rt.record_mut().hint_words_events.push(HintWordsEvent {
+            lookup_id,
+            shard,
+            channel,
+            clk,
+            dst_ptr,
+            num_words,
+            words,
+            dst_memory_records,
+        });
+
+        None
+    }
+}
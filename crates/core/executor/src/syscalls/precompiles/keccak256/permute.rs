@@ -1,5 +1,5 @@
 use crate::{
-    events::KeccakPermuteEvent,
+    events::{KeccakPermuteEvent, KeccakPermuteMemoEvent},
     syscalls::{Syscall, SyscallContext},
 };
 
@@ -24,6 +24,10 @@ impl Syscall for Keccak256PermuteSyscall {
             panic!("Expected arg2 to be 0, got {arg2}");
         }
 
+        if memoized_permute(rt, state_ptr) {
+            return None;
+        }
+
         let mut state_read_records = Vec::new();
         let mut state_write_records = Vec::new();
 
@@ -56,10 +60,14 @@ impl Syscall for Keccak256PermuteSyscall {
         let write_records = rt.mw_slice(state_ptr, values_to_write.as_slice());
         state_write_records.extend_from_slice(&write_records);
 
-        // Push the Keccak permute event.
+        // Push the Keccak permute event, and let later calls on the same pre-state reuse it.
         let shard = rt.current_shard();
         let channel = rt.current_channel();
         let lookup_id = rt.syscall_lookup_id;
+        let index = rt.record_mut().keccak_permute_events.len();
+        if let Some(memo) = &mut rt.rt.keccak_memo {
+            memo.insert(saved_state.as_slice().try_into().unwrap(), index);
+        }
         rt.record_mut().keccak_permute_events.push(KeccakPermuteEvent {
             lookup_id,
             shard,
@@ -70,8 +78,58 @@ impl Syscall for Keccak256PermuteSyscall {
             state_read_records,
             state_write_records,
             state_addr: state_ptr,
+            memo_uses: 0,
         });
 
         None
     }
 }
+
+/// Overwrites the state at `state_ptr` with the result of an earlier permutation of it in the
+/// shard, if the executor memoizes syscalls and there is one.
+///
+/// The state is not read, since the prior values of the write records hold it.
+fn memoized_permute(rt: &mut SyscallContext, state_ptr: u32) -> bool {
+    if rt.rt.keccak_memo.is_none() {
+        return false;
+    }
+    let pre_state: [u64; STATE_SIZE] = rt
+        .slice_unsafe(state_ptr, STATE_NUM_WORDS)
+        .chunks_exact(2)
+        .map(|values| values[0] as u64 + ((values[1] as u64) << 32))
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+    let Some(&index) = rt.rt.keccak_memo.as_ref().and_then(|memo| memo.get(&pre_state)) else {
+        return false;
+    };
+    let event = &mut rt.record_mut().keccak_permute_events[index];
+    event.memo_uses += 1;
+    let post_state = event.post_state;
+
+    // Write at the same clock as an unmemoized call.
+    let start_clk = rt.clk;
+    rt.clk += 1;
+    let values_to_write = post_state
+        .iter()
+        .flat_map(|word| [(word & 0xFFFFFFFF) as u32, (word >> 32) as u32])
+        .collect::<Vec<_>>();
+    let state_write_records = rt.mw_slice(state_ptr, &values_to_write);
+
+    let shard = rt.current_shard();
+    let channel = rt.current_channel();
+    let lookup_id = rt.syscall_lookup_id;
+    rt.record_mut().keccak_permute_memo_events.push(KeccakPermuteMemoEvent {
+        lookup_id,
+        shard,
+        channel,
+        clk: start_clk,
+        pre_state,
+        post_state,
+        state_write_records,
+        state_addr: state_ptr,
+    });
+    rt.memoized = true;
+
+    true
+}
@@ -0,0 +1,152 @@
+use crate::{
+    events::Ripemd160CompressEvent,
+    syscalls::{Syscall, SyscallContext},
+};
+
+/// The message word selected at each step of the left line.
+pub const RIPEMD160_R: [usize; 80] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5,
+    2, 14, 11, 8, 3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12, 1, 9, 11, 10, 0, 8, 12, 4,
+    13, 3, 7, 15, 14, 5, 6, 2, 4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+];
+
+/// The message word selected at each step of the right line.
+pub const RIPEMD160_R_PRIME: [usize; 80] = [
+    5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12, 6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12,
+    4, 9, 1, 2, 15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13, 8, 6, 4, 1, 3, 11, 15, 0, 5,
+    12, 2, 13, 9, 7, 10, 14, 12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+];
+
+/// The left rotation applied at each step of the left line.
+pub const RIPEMD160_S: [u32; 80] = [
+    11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8, 7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15,
+    9, 11, 7, 13, 12, 11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5, 11, 12, 14, 15, 14,
+    15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12, 9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+];
+
+/// The left rotation applied at each step of the right line.
+pub const RIPEMD160_S_PRIME: [u32; 80] = [
+    8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6, 9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12,
+    7, 6, 15, 13, 11, 9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5, 15, 5, 8, 11, 14, 14,
+    6, 14, 6, 9, 12, 9, 12, 5, 15, 8, 8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+];
+
+/// The constant added in each round of the left line.
+pub const RIPEMD160_K: [u32; 5] = [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xa953fd4e];
+
+/// The constant added in each round of the right line.
+pub const RIPEMD160_K_PRIME: [u32; 5] =
+    [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x7a6d76e9, 0x00000000];
+
+/// The boolean function of the given round, applied to `x`, `y` and `z`.
+///
+/// The left line uses the functions of rounds 0 to 4 in order, the right line in reverse order.
+#[must_use]
+pub const fn ripemd160_f(round: usize, x: u32, y: u32, z: u32) -> u32 {
+    match round {
+        0 => x ^ y ^ z,
+        1 => (x & y) | (!x & z),
+        2 => (x | !y) ^ z,
+        3 => (x & z) | (y & !z),
+        _ => x ^ (y | !z),
+    }
+}
+
+/// Compresses the message block `w` into the state `h`, returning the new state.
+#[must_use]
+pub fn ripemd160_compress(h: [u32; 5], w: &[u32]) -> [u32; 5] {
+    let [mut a, mut b, mut c, mut d, mut e] = h;
+    let [mut a_p, mut b_p, mut c_p, mut d_p, mut e_p] = h;
+    for j in 0..80 {
+        let round = j / 16;
+
+        let t = a
+            .wrapping_add(ripemd160_f(round, b, c, d))
+            .wrapping_add(w[RIPEMD160_R[j]])
+            .wrapping_add(RIPEMD160_K[round])
+            .rotate_left(RIPEMD160_S[j])
+            .wrapping_add(e);
+        (a, b, c, d, e) = (e, t, b, c.rotate_left(10), d);
+
+        let t = a_p
+            .wrapping_add(ripemd160_f(4 - round, b_p, c_p, d_p))
+            .wrapping_add(w[RIPEMD160_R_PRIME[j]])
+            .wrapping_add(RIPEMD160_K_PRIME[round])
+            .rotate_left(RIPEMD160_S_PRIME[j])
+            .wrapping_add(e_p);
+        (a_p, b_p, c_p, d_p, e_p) = (e_p, t, b_p, c_p.rotate_left(10), d_p);
+    }
+    [
+        h[1].wrapping_add(c).wrapping_add(d_p),
+        h[2].wrapping_add(d).wrapping_add(e_p),
+        h[3].wrapping_add(e).wrapping_add(a_p),
+        h[4].wrapping_add(a).wrapping_add(b_p),
+        h[0].wrapping_add(b).wrapping_add(c_p),
+    ]
+}
+
+pub(crate) struct Ripemd160CompressSyscall;
+
+impl Syscall for Ripemd160CompressSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let w_ptr = arg1;
+        let h_ptr = arg2;
+        assert_ne!(w_ptr, h_ptr);
+
+        let start_clk = rt.clk;
+        let mut h_read_records = Vec::new();
+        let mut w_i_read_records = Vec::new();
+        let mut h_write_records = Vec::new();
+
+        // Execute the "initialize" phase where we read in the h values.
+        let mut hx = [0u32; 5];
+        for i in 0..5 {
+            let (record, value) = rt.mr(h_ptr + i as u32 * 4);
+            h_read_records.push(record);
+            hx[i] = value;
+        }
+
+        let mut original_w = Vec::new();
+        for i in 0..16 {
+            let (record, w_i) = rt.mr(w_ptr + i * 4);
+            original_w.push(w_i);
+            w_i_read_records.push(record);
+        }
+
+        let h_out = ripemd160_compress(hx, &original_w);
+
+        // Increment the clk by 1 before writing to h, since we've already read h at the start_clk
+        // during the initialization phase.
+        rt.clk += 1;
+
+        // Execute the "finalize" phase.
+        for i in 0..5 {
+            let record = rt.mw(h_ptr + i as u32 * 4, h_out[i]);
+            h_write_records.push(record);
+        }
+
+        // Push the RIPEMD-160 compress event.
+        let lookup_id = rt.syscall_lookup_id;
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        rt.record_mut().ripemd160_compress_events.push(Ripemd160CompressEvent {
+            lookup_id,
+            shard,
+            channel,
+            clk: start_clk,
+            w_ptr,
+            h_ptr,
+            w: original_w,
+            h: hx,
+            h_read_records: h_read_records.try_into().unwrap(),
+            w_i_read_records,
+            h_write_records: h_write_records.try_into().unwrap(),
+        });
+
+        None
+    }
+}
@@ -0,0 +1,46 @@
+use std::marker::PhantomData;
+
+use sp1_curves::{
+    params::NumWords,
+    weierstrass::{projective::ProjectivePoint, WeierstrassParameters},
+    CurveType, EllipticCurve,
+};
+use typenum::Unsigned;
+
+use crate::{
+    events::create_msm_event,
+    syscalls::{Syscall, SyscallContext},
+};
+
+pub(crate) struct WeierstrassMsmSyscall<E: EllipticCurve + WeierstrassParameters> {
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EllipticCurve + WeierstrassParameters> WeierstrassMsmSyscall<E> {
+    /// Create a new instance of the [`WeierstrassMsmSyscall`].
+    pub const fn new() -> Self {
+        Self { _phantom: PhantomData }
+    }
+}
+
+impl<E: EllipticCurve + WeierstrassParameters> Syscall for WeierstrassMsmSyscall<E> {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        // Points are in projective coordinates, which have complete addition formulas.
+        let num_words = 3 * <E::BaseField as NumWords>::WordsFieldElement::USIZE;
+        let event = create_msm_event(rt, arg1, arg2, num_words, |p, q| {
+            ProjectivePoint::<E>::from_words_le(p)
+                .complete_add(&ProjectivePoint::from_words_le(q))
+                .to_words_le()
+        });
+        match E::CURVE_TYPE {
+            CurveType::Secp256k1 => rt.record_mut().secp256k1_msm_events.push(event),
+            CurveType::Bn254 => rt.record_mut().bn254_msm_events.push(event),
+            _ => panic!("Unsupported curve"),
+        }
+        None
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{
+    events::{WideAluEvent, WideAluOperation, WIDE_ALU_NUM_WORDS},
+    syscalls::{Syscall, SyscallContext},
+};
+
+pub(crate) struct WideAluSyscall {
+    op: WideAluOperation,
+}
+
+impl WideAluSyscall {
+    pub const fn new(op: WideAluOperation) -> Self {
+        Self { op }
+    }
+}
+
+impl Syscall for WideAluSyscall {
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let clk = rt.clk;
+        let x_ptr = arg1;
+        if x_ptr % 4 != 0 {
+            panic!();
+        }
+        let y_ptr = arg2;
+        if y_ptr % 4 != 0 {
+            panic!();
+        }
+
+        // Read x unsafely since the result is written to it later, and y as a normal read.
+        let x_words = rt.slice_unsafe(x_ptr, WIDE_ALU_NUM_WORDS);
+        let (y_memory_records, y_words) = rt.mr_slice(y_ptr, WIDE_ALU_NUM_WORDS);
+
+        let x = u64::from(x_words[0]) | u64::from(x_words[1]) << 32;
+        let y = u64::from(y_words[0]) | u64::from(y_words[1]) << 32;
+        let result = self.op.execute(x, y);
+
+        // Increment clk so that the write is not at the same cycle as the read, since x and y
+        // could be the same.
+        rt.clk += 1;
+        let x_memory_records = rt.mw_slice(x_ptr, &[result as u32, (result >> 32) as u32]);
+
+        let lookup_id = rt.syscall_lookup_id;
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        rt.record_mut().wide_alu_events.push(WideAluEvent {
+            lookup_id,
+            shard,
+            channel,
+            clk,
+            op: self.op,
+            x_ptr,
+            x,
+            y_ptr,
+            y,
+            x_memory_records,
+            y_memory_records,
+        });
+
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
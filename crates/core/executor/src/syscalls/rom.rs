@@ -0,0 +1,50 @@
+//! Mapping the pages of the ROM into the guest's memory.
+//!
+//! Like the pages of paged inputs, the pages of the ROM are only written to the uninitialized
+//! memory of the executor, so only the words that the guest reads enter the memory argument. The
+//! guest checks each page against the root in the ROM header, which is part of the program.
+
+use std::sync::Arc;
+
+use super::{Syscall, SyscallContext};
+use crate::{rom_num_pages, RomTree, ROM_DATA_START, ROM_PAGE_SIZE};
+
+pub(crate) struct RomMapSyscall;
+
+impl Syscall for RomMapSyscall {
+    /// Maps the page `page` of the ROM at `ROM_DATA_START + page * ROM_PAGE_SIZE`, and writes the
+    /// siblings of the page on its path to the root of the [`RomTree`] at `ptr`. Bytes past the
+    /// end of the ROM are zero.
+    fn execute(&self, ctx: &mut SyscallContext, page: u32, ptr: u32) -> Option<u32> {
+        assert!(!ctx.rt.unconstrained, "rom map should not be used in a unconstrained block");
+        assert_eq!(ptr % 4, 0, "rom map address not aligned to 4 bytes");
+        let program = ctx.rt.program.clone();
+        let rom = program.rom.as_slice();
+        assert!(
+            (page as usize) < rom_num_pages(rom.len()),
+            "page {page} is past the end of the rom"
+        );
+        let tree = ctx.rt.rom_tree.get_or_insert_with(|| Arc::new(RomTree::new(rom))).clone();
+
+        let start = page as usize * ROM_PAGE_SIZE as usize;
+        let page_words = (0..ROM_PAGE_SIZE).step_by(4).map(|i| {
+            let offset = start + i as usize;
+            let bytes = core::array::from_fn(|j| rom.get(offset + j).copied().unwrap_or(0));
+            (ROM_DATA_START + page * ROM_PAGE_SIZE + i, u32::from_le_bytes(bytes))
+        });
+        let path_words = tree.path(page as usize).into_iter().flatten().collect::<Vec<_>>();
+        let path_words = path_words
+            .chunks_exact(4)
+            .enumerate()
+            .map(|(i, word)| (ptr + 4 * i as u32, u32::from_le_bytes(word.try_into().unwrap())));
+        for (addr, word) in page_words.chain(path_words) {
+            ctx.rt
+                .state
+                .uninitialized_memory
+                .entry(addr)
+                .and_modify(|_| panic!("rom map address is initialized already"))
+                .or_insert(word);
+        }
+        None
+    }
+}
@@ -42,7 +42,9 @@ impl Syscall for ExitUnconstrainedSyscall {
                         ctx.rt.state.memory.insert(addr, value);
                     }
                     None => {
-                        ctx.rt.state.memory.remove(addr);
+                        if ctx.rt.state.memory.remove(addr).is_some() && addr >= 32 {
+                            ctx.rt.state.touched_memory_words -= 1;
+                        }
                     }
                 }
             }
@@ -26,13 +26,14 @@ impl Syscall for VerifySyscall {
         let vkey_bytes: [u32; 8] = vkey.try_into().unwrap();
         let pv_digest_bytes: [u32; 8] = pv_digest.try_into().unwrap();
 
+        let privacy_mode = ctx.rt.privacy_mode;
         ctx.rt
             .subproof_verifier
             .verify_deferred_proof(proof, proof_vk, vkey_bytes, pv_digest_bytes)
             .unwrap_or_else(|e| {
                 panic!(
                     "Failed to verify proof {proof_index} with digest {}: {}",
-                    hex::encode(bytemuck::cast_slice(&pv_digest_bytes)),
+                    privacy_mode.redact(&hex::encode(bytemuck::cast_slice(&pv_digest_bytes))),
                     e
                 )
             });
@@ -1,6 +1,6 @@
 use sp1_primitives::consts::num_to_comma_separated;
 
-use crate::{Executor, Register};
+use crate::{Executor, Register, FD_PANIC};
 
 use super::{Syscall, SyscallContext};
 
@@ -23,6 +23,10 @@ impl Syscall for WriteSyscall {
     /// If fd = 4:
     /// - Update the input stream.
     ///
+    /// If fd = 6:
+    /// - Record the panic location and message, separated by a newline, to be reported when the
+    ///   guest halts.
+    ///
     /// If the fd matches a hook in the hook registry, invoke the hook.
     ///
     /// Else, log a warning.
@@ -30,6 +34,7 @@ impl Syscall for WriteSyscall {
     fn execute(&self, ctx: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
         let a2 = Register::X12;
         let rt = &mut ctx.rt;
+        let privacy_mode = rt.privacy_mode;
         let fd = arg1;
         let write_buf = arg2;
         let nbytes = rt.register(a2);
@@ -44,7 +49,9 @@ impl Syscall for WriteSyscall {
                     // If the string does not match any known command, print it to stdout.
                     let flush_s = update_io_buf(ctx, fd, s);
                     if !flush_s.is_empty() {
-                        flush_s.into_iter().for_each(|line| println!("stdout: {}", line));
+                        flush_s
+                            .into_iter()
+                            .for_each(|line| println!("stdout: {}", privacy_mode.redact(&line)));
                     }
                 }
             }
@@ -52,12 +59,18 @@ impl Syscall for WriteSyscall {
             let s = core::str::from_utf8(slice).unwrap();
             let flush_s = update_io_buf(ctx, fd, s);
             if !flush_s.is_empty() {
-                flush_s.into_iter().for_each(|line| println!("stderr: {}", line));
+                flush_s
+                    .into_iter()
+                    .for_each(|line| println!("stderr: {}", privacy_mode.redact(&line)));
             }
         } else if fd == 3 {
             rt.state.public_values_stream.extend_from_slice(slice);
         } else if fd == 4 {
             rt.state.input_stream.push(slice.to_vec());
+        } else if fd == FD_PANIC {
+            let s = String::from_utf8_lossy(slice);
+            let (location, message) = s.split_once('\n').unwrap_or(("<unknown>", &s));
+            rt.guest_panic = Some((location.to_string(), message.to_string()));
         } else if let Some(mut hook) = rt.hook_registry.get(fd) {
             let res = hook.invoke_hook(rt.hook_env(), slice);
             // Add result vectors to the beginning of the stream.
@@ -168,9 +168,9 @@ pub trait MemoryAirBuilder: BaseAirBuilder {
 
         // Send the range checks for the limbs.
         self.send_byte(
-            Self::Expr::from_canonical_u8(ByteOpcode::U16Range as u8),
+            Self::Expr::from_canonical_u8(ByteOpcode::Range as u8),
             limb_16,
-            Self::Expr::zero(),
+            Self::Expr::from_canonical_u8(16),
             Self::Expr::zero(),
             shard.clone(),
             channel.clone(),
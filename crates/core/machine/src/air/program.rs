@@ -6,7 +6,10 @@ use sp1_stark::{
     InteractionKind,
 };
 
-use crate::cpu::columns::{InstructionCols, OpcodeSelectorCols};
+use crate::{
+    cpu::columns::{InstructionCols, OpcodeSelectorCols},
+    program::OPCODE_INTERACTION,
+};
 
 /// A trait which contains methods related to program interactions in an AIR.
 pub trait ProgramAirBuilder: BaseAirBuilder {
@@ -15,14 +18,12 @@ pub trait ProgramAirBuilder: BaseAirBuilder {
         &mut self,
         pc: impl Into<Self::Expr>,
         instruction: InstructionCols<impl Into<Self::Expr> + Copy>,
-        selectors: OpcodeSelectorCols<impl Into<Self::Expr> + Copy>,
         shard: impl Into<Self::Expr> + Copy,
         multiplicity: impl Into<Self::Expr>,
     ) {
         let values = once(pc.into())
             .chain(once(instruction.opcode.into()))
             .chain(instruction.into_iter().map(|x| x.into()))
-            .chain(selectors.into_iter().map(|x| x.into()))
             .chain(once(shard.into()))
             .collect();
 
@@ -34,17 +35,40 @@ pub trait ProgramAirBuilder: BaseAirBuilder {
         &mut self,
         pc: impl Into<Self::Expr>,
         instruction: InstructionCols<impl Into<Self::Expr> + Copy>,
-        selectors: OpcodeSelectorCols<impl Into<Self::Expr> + Copy>,
         shard: impl Into<Self::Expr> + Copy,
         multiplicity: impl Into<Self::Expr>,
     ) {
         let values: Vec<<Self as AirBuilder>::Expr> = once(pc.into())
             .chain(once(instruction.opcode.into()))
             .chain(instruction.into_iter().map(|x| x.into()))
-            .chain(selectors.into_iter().map(|x| x.into()))
             .chain(once(shard.into()))
             .collect();
 
         self.receive(AirInteraction::new(values, multiplicity.into(), InteractionKind::Program));
     }
+
+    /// Sends the selectors of an opcode.
+    fn send_opcode(
+        &mut self,
+        opcode: impl Into<Self::Expr>,
+        selectors: OpcodeSelectorCols<impl Into<Self::Expr> + Copy>,
+        multiplicity: impl Into<Self::Expr>,
+    ) {
+        let values = once(opcode.into()).chain(selectors.into_iter().map(|x| x.into())).collect();
+
+        self.send(AirInteraction::new(values, multiplicity.into(), OPCODE_INTERACTION));
+    }
+
+    /// Receives the selectors of an opcode.
+    fn receive_opcode(
+        &mut self,
+        opcode: impl Into<Self::Expr>,
+        selectors: OpcodeSelectorCols<impl Into<Self::Expr> + Copy>,
+        multiplicity: impl Into<Self::Expr>,
+    ) {
+        let values: Vec<<Self as AirBuilder>::Expr> =
+            once(opcode.into()).chain(selectors.into_iter().map(|x| x.into())).collect();
+
+        self.receive(AirInteraction::new(values, multiplicity.into(), OPCODE_INTERACTION));
+    }
 }
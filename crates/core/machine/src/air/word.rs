@@ -2,7 +2,7 @@ use std::array;
 
 use itertools::Itertools;
 use p3_field::AbstractField;
-use sp1_core_executor::ByteOpcode;
+use sp1_core_executor::{events::MAX_RANGE_CHECK_BITS, ByteOpcode};
 use sp1_primitives::consts::WORD_SIZE;
 use sp1_stark::{air::ByteAirBuilder, Word};
 
@@ -94,15 +94,28 @@ pub trait WordAirBuilder: ByteAirBuilder {
         mult: impl Into<Self::Expr> + Clone,
     ) {
         input.iter().for_each(|limb| {
-            self.send_byte(
-                Self::Expr::from_canonical_u8(ByteOpcode::U16Range as u8),
-                *limb,
-                Self::Expr::zero(),
-                Self::Expr::zero(),
-                shard.clone(),
-                channel.clone(),
-                mult.clone(),
-            );
+            self.range_check(*limb, 16, shard.clone(), channel.clone(), mult.clone());
         });
     }
+
+    /// Check that the given value has at most `bits` bits, with a lookup into the range table.
+    fn range_check(
+        &mut self,
+        value: impl Into<Self::Expr>,
+        bits: usize,
+        shard: impl Into<Self::Expr>,
+        channel: impl Into<Self::Expr>,
+        mult: impl Into<Self::Expr>,
+    ) {
+        assert!(bits <= MAX_RANGE_CHECK_BITS, "cannot range check {bits} bits");
+        self.send_byte(
+            Self::Expr::from_canonical_u8(ByteOpcode::Range as u8),
+            value,
+            Self::Expr::from_canonical_usize(bits),
+            Self::Expr::zero(),
+            shard,
+            channel,
+            mult,
+        );
+    }
 }
@@ -148,6 +148,10 @@ impl<F: PrimeField> MachineAir<F> for AddSubChip {
     fn included(&self, shard: &Self::Record) -> bool {
         !shard.add_events.is_empty() || !shard.sub_events.is_empty()
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.add_events.len() + shard.sub_events.len())
+    }
 }
 
 impl AddSubChip {
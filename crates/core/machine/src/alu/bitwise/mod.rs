@@ -125,6 +125,10 @@ impl<F: PrimeField> MachineAir<F> for BitwiseChip {
     fn included(&self, shard: &Self::Record) -> bool {
         !shard.bitwise_events.is_empty()
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.bitwise_events.len())
+    }
 }
 
 impl BitwiseChip {
@@ -536,6 +536,10 @@ impl<F: PrimeField> MachineAir<F> for DivRemChip {
     fn included(&self, shard: &Self::Record) -> bool {
         !shard.divrem_events.is_empty()
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.divrem_events.len())
+    }
 }
 
 impl<F> BaseAir<F> for DivRemChip {
@@ -163,6 +163,10 @@ impl<F: PrimeField32> MachineAir<F> for LtChip {
     fn included(&self, shard: &Self::Record) -> bool {
         !shard.lt_events.is_empty()
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.lt_events.len())
+    }
 }
 
 impl LtChip {
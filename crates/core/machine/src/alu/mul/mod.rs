@@ -287,6 +287,10 @@ impl<F: PrimeField> MachineAir<F> for MulChip {
     fn included(&self, shard: &Self::Record) -> bool {
         !shard.mul_events.is_empty()
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.mul_events.len())
+    }
 }
 
 impl<F> BaseAir<F> for MulChip {
@@ -185,6 +185,10 @@ impl<F: PrimeField> MachineAir<F> for ShiftLeft {
     fn included(&self, shard: &Self::Record) -> bool {
         !shard.shift_left_events.is_empty()
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.shift_left_events.len())
+    }
 }
 
 impl ShiftLeft {
@@ -221,6 +221,10 @@ impl<F: PrimeField> MachineAir<F> for ShiftRightChip {
     fn included(&self, shard: &Self::Record) -> bool {
         !shard.shift_right_events.is_empty()
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.shift_right_events.len())
+    }
 }
 
 impl ShiftRightChip {
@@ -78,15 +78,7 @@ impl<AB: SP1AirBuilder + PairBuilder> Air<AB> for ByteChip<AB::F> {
                         channel_f,
                         mult,
                     ),
-                    ByteOpcode::U16Range => builder.receive_byte(
-                        field_op,
-                        local.value_u16,
-                        AB::F::zero(),
-                        AB::F::zero(),
-                        shard,
-                        channel_f,
-                        mult,
-                    ),
+                    ByteOpcode::Range => unreachable!("range checks use the range table"),
                 }
             }
         }
@@ -39,9 +39,6 @@ pub struct BytePreprocessedCols<T> {
 
     /// The most significant bit of `b`.
     pub msb: T,
-
-    /// A u16 value used for `U16Range`.
-    pub value_u16: T,
 }
 
 /// For each byte operation in the preprocessed table, a corresponding ByteMultCols row tracks the
@@ -21,7 +21,7 @@ use self::{
 use crate::bytes::trace::NUM_ROWS;
 
 /// The number of different byte operations.
-pub const NUM_BYTE_OPS: usize = 9;
+pub const NUM_BYTE_OPS: usize = 8;
 
 /// The number of different byte lookup channels.
 pub const NUM_BYTE_LOOKUP_CHANNELS: u8 = 16;
@@ -101,11 +101,7 @@ impl<F: Field> ByteChip<F> {
                             col.msb = F::from_bool(msb);
                             ByteLookupEvent::new(shard, channel, *opcode, msb as u16, 0, b, 0)
                         }
-                        ByteOpcode::U16Range => {
-                            let v = ((b as u32) << 8) + c as u32;
-                            col.value_u16 = F::from_canonical_u32(v);
-                            ByteLookupEvent::new(shard, channel, *opcode, v as u16, 0, 0, 0)
-                        }
+                        ByteOpcode::Range => unreachable!("range checks use the range table"),
                     };
                 }
             }
@@ -45,11 +45,11 @@ impl<F: Field> MachineAir<F> for ByteChip<F> {
 
         let shard = input.public_values.execution_shard;
         for (lookup, mult) in input.byte_lookups.get(&shard).unwrap_or(&HashMap::new()).iter() {
-            let row = if lookup.opcode != ByteOpcode::U16Range {
-                (((lookup.b as u16) << 8) + lookup.c as u16) as usize
-            } else {
-                lookup.a1 as usize
-            };
+            // Range checks are handled by the range table.
+            if lookup.opcode == ByteOpcode::Range {
+                continue;
+            }
+            let row = (((lookup.b as u16) << 8) + lookup.c as u16) as usize;
             let index = lookup.opcode as usize;
             let channel = lookup.channel as usize;
 
@@ -64,4 +64,9 @@ impl<F: Field> MachineAir<F> for ByteChip<F> {
     fn included(&self, _shard: &Self::Record) -> bool {
         true
     }
+
+    fn num_real_rows(&self, _shard: &Self::Record) -> Option<usize> {
+        // Every row of the byte table is real, whether or not it is looked up.
+        Some(NUM_ROWS)
+    }
 }
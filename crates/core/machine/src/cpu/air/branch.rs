@@ -24,10 +24,8 @@ impl CpuChip {
     ) -> AB::Expr {
         opcode_selectors.is_beq
             + opcode_selectors.is_bne
-            + opcode_selectors.is_blt
-            + opcode_selectors.is_bge
-            + opcode_selectors.is_bltu
-            + opcode_selectors.is_bgeu
+            + opcode_selectors.is_branch_lt
+            + opcode_selectors.is_branch_ge
     }
 
     /// Verifies all the branching related columns.
@@ -145,25 +143,25 @@ impl CpuChip {
 
             // When the opcode is BLT or BLTU and we are branching, assert that a_lt_b is true.
             builder
-                .when((local.selectors.is_blt + local.selectors.is_bltu) * local.branching)
+                .when(local.selectors.is_branch_lt * local.branching)
                 .assert_one(branch_cols.a_lt_b);
 
             // When the opcode is BLT or BLTU and we are not branching, assert that either a_eq_b
             // or a_gt_b is true.
             builder
-                .when(local.selectors.is_blt + local.selectors.is_bltu)
+                .when(local.selectors.is_branch_lt)
                 .when_not(local.branching)
                 .assert_one(branch_cols.a_eq_b + branch_cols.a_gt_b);
 
             // When the opcode is BGE or BGEU and we are branching, assert that a_gt_b is true.
             builder
-                .when((local.selectors.is_bge + local.selectors.is_bgeu) * local.branching)
+                .when(local.selectors.is_branch_ge * local.branching)
                 .assert_one(branch_cols.a_gt_b + branch_cols.a_eq_b);
 
             // When the opcode is BGE or BGEU and we are not branching, assert that either a_eq_b
             // or a_lt_b is true.
             builder
-                .when(local.selectors.is_bge + local.selectors.is_bgeu)
+                .when(local.selectors.is_branch_ge)
                 .when_not(local.branching)
                 .assert_one(branch_cols.a_lt_b);
         }
@@ -176,12 +174,10 @@ impl CpuChip {
         //  To prevent this ALU send to be arbitrarily large when is_branch_instruction is false.
         builder.when_not(is_branch_instruction.clone()).assert_zero(local.branching);
 
-        // Calculate a_lt_b <==> a < b (using appropriate signedness).
-        let use_signed_comparison = local.selectors.is_blt + local.selectors.is_bge;
+        // Calculate a_lt_b <==> a < b (using appropriate signedness). The comparison opcode, SLT
+        // or SLTU, is given by the opcode table.
         builder.send_alu(
-            use_signed_comparison.clone() * Opcode::SLT.as_field::<AB::F>()
-                + (AB::Expr::one() - use_signed_comparison.clone())
-                    * Opcode::SLTU.as_field::<AB::F>(),
+            local.selectors.aux_alu_opcode,
             Word::extend_var::<AB>(branch_cols.a_lt_b),
             local.op_a_val(),
             local.op_b_val(),
@@ -193,8 +189,7 @@ impl CpuChip {
 
         // Calculate a_gt_b <==> a > b (using appropriate signedness).
         builder.send_alu(
-            use_signed_comparison.clone() * Opcode::SLT.as_field::<AB::F>()
-                + (AB::Expr::one() - use_signed_comparison) * Opcode::SLTU.as_field::<AB::F>(),
+            local.selectors.aux_alu_opcode,
             Word::extend_var::<AB>(branch_cols.a_gt_b),
             local.op_b_val(),
             local.op_a_val(),
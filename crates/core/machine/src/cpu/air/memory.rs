@@ -9,9 +9,9 @@ use crate::{
         CpuChip,
     },
     memory::MemoryCols,
-    operations::BabyBearWordRangeChecker,
+    operations::{BabyBearWordRangeChecker, IsEqualWordOperation},
 };
-use sp1_core_executor::{events::MemoryAccessPosition, Opcode};
+use sp1_core_executor::{events::MemoryAccessPosition, Opcode, RESERVATION_ADDR};
 
 impl CpuChip {
     /// Computes whether the opcode is a memory instruction.
@@ -19,14 +19,22 @@ impl CpuChip {
         &self,
         opcode_selectors: &OpcodeSelectorCols<AB::Var>,
     ) -> AB::Expr {
-        opcode_selectors.is_lb
-            + opcode_selectors.is_lbu
-            + opcode_selectors.is_lh
-            + opcode_selectors.is_lhu
-            + opcode_selectors.is_lw
-            + opcode_selectors.is_sb
-            + opcode_selectors.is_sh
-            + opcode_selectors.is_sw
+        self.is_load_instruction::<AB>(opcode_selectors)
+            + self.is_store_instruction::<AB>(opcode_selectors)
+            + self.is_atomic_instruction::<AB>(opcode_selectors)
+    }
+
+    /// Computes whether the opcode is an atomic memory instruction.
+    pub(crate) fn is_atomic_instruction<AB: SP1AirBuilder>(
+        &self,
+        opcode_selectors: &OpcodeSelectorCols<AB::Var>,
+    ) -> AB::Expr {
+        opcode_selectors.is_lr
+            + opcode_selectors.is_sc
+            + opcode_selectors.is_amoswap
+            + opcode_selectors.is_alu_atomic
+            + opcode_selectors.is_atomic_min
+            + opcode_selectors.is_atomic_max
     }
 
     /// Computes whether the opcode is a load instruction.
@@ -34,11 +42,7 @@ impl CpuChip {
         &self,
         opcode_selectors: &OpcodeSelectorCols<AB::Var>,
     ) -> AB::Expr {
-        opcode_selectors.is_lb
-            + opcode_selectors.is_lbu
-            + opcode_selectors.is_lh
-            + opcode_selectors.is_lhu
-            + opcode_selectors.is_lw
+        opcode_selectors.is_byte_load + opcode_selectors.is_half_load + opcode_selectors.is_lw
     }
 
     /// Computes whether the opcode is a store instruction.
@@ -52,7 +56,8 @@ impl CpuChip {
     /// Constrains the addr_aligned, addr_offset, and addr_word memory columns.
     ///
     /// This method will do the following:
-    /// 1. Calculate that the unaligned address is correctly computed to be op_b.value + op_c.value.
+    /// 1. Calculate that the unaligned address is correctly computed to be op_b.value + op_c.value,
+    ///    or op_b.value for atomic instructions, whose op_c is the source register.
     /// 2. Calculate that the address offset is address % 4.
     /// 3. Assert the validity of the aligned address given the address offset and the unaligned
     ///    address.
@@ -66,6 +71,7 @@ impl CpuChip {
         let memory_columns = local.opcode_specific_columns.memory();
 
         // Send to the ALU table to verify correct calculation of addr_word.
+        let is_atomic_instruction = self.is_atomic_instruction::<AB>(&local.selectors);
         builder.send_alu(
             AB::Expr::from_canonical_u32(Opcode::ADD as u32),
             memory_columns.addr_word,
@@ -74,9 +80,14 @@ impl CpuChip {
             local.shard,
             local.channel,
             memory_columns.addr_word_nonce,
-            is_memory_instruction.clone(),
+            is_memory_instruction.clone() - is_atomic_instruction.clone(),
         );
 
+        // Atomic instructions have no offset, so addr_word is op_b.
+        builder
+            .when(is_atomic_instruction)
+            .assert_word_eq(memory_columns.addr_word, local.op_b_val());
+
         // Range check the addr_word to be a valid babybear word.
         BabyBearWordRangeChecker::<AB::F>::range_check(
             builder,
@@ -102,6 +113,13 @@ impl CpuChip {
             memory_columns.addr_word.reduce::<AB>(),
         );
 
+        // Memory instructions never access the word of the reservation, which only LR and SC
+        // access through their own columns, so that a store cannot forge a reservation.
+        builder.when(is_memory_instruction.clone()).assert_one(
+            (memory_columns.addr_aligned - AB::Expr::from_canonical_u32(RESERVATION_ADDR))
+                * memory_columns.reservation_diff_inv,
+        );
+
         // Verify that the least significant byte of addr_word - addr_offset is divisible by 4.
         let offset = [
             memory_columns.offset_is_one,
@@ -187,11 +205,9 @@ impl CpuChip {
         );
 
         // Assert that correct value of `mem_value_is_pos_not_x0`.
-        let mem_value_is_pos = (local.selectors.is_lb + local.selectors.is_lh)
-            * (AB::Expr::one() - memory_columns.most_sig_byte_decomp[7])
-            + local.selectors.is_lbu
-            + local.selectors.is_lhu
-            + local.selectors.is_lw;
+        let mem_value_is_pos = self.is_load_instruction::<AB>(&local.selectors)
+            - (local.selectors.is_lb + local.selectors.is_lh)
+                * memory_columns.most_sig_byte_decomp[7];
         builder.assert_eq(
             local.mem_value_is_pos_not_x0,
             mem_value_is_pos * (AB::Expr::one() - local.instruction.op_a_0),
@@ -269,6 +285,121 @@ impl CpuChip {
             .assert_word_eq(mem_val.map(|x| x.into()), a_val.map(|x| x.into()));
     }
 
+    /// Evaluates constraints related to atomic memory instructions.
+    ///
+    /// An atomic instruction reads the word at op_b, writes it to op_a, and stores the result of
+    /// combining it with op_c. LR stores the word back as is and reserves op_b, and SC only stores
+    /// op_c if op_b is reserved, writes whether it failed to op_a and clears the reservation.
+    pub(crate) fn eval_memory_atomic<AB: SP1CoreAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &CpuCols<AB::Var>,
+    ) {
+        let memory_columns = local.opcode_specific_columns.memory();
+        let selectors = &local.selectors;
+        let is_atomic = self.is_atomic_instruction::<AB>(selectors);
+
+        // Atomic instructions only operate on aligned words.
+        let offset_is_zero = AB::Expr::one()
+            - memory_columns.offset_is_one
+            - memory_columns.offset_is_two
+            - memory_columns.offset_is_three;
+        builder.when(is_atomic.clone()).assert_one(offset_is_zero);
+
+        let mem_val = *memory_columns.memory_access.value();
+        let prev_mem_val = *memory_columns.memory_access.prev_value();
+        let c_val = local.op_c_val();
+
+        // LR and SC access the reservation at the timestamp of the memory access. The reservation
+        // holds one more than the reserved address, which is aligned, so the addition does not
+        // carry.
+        builder.eval_memory_access(
+            local.shard,
+            local.channel,
+            local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::Memory as u32),
+            AB::Expr::from_canonical_u32(RESERVATION_ADDR),
+            &memory_columns.reservation_access,
+            selectors.is_lr + selectors.is_sc,
+        );
+        let addr_reservation = Word([
+            memory_columns.addr_word[0] + AB::Expr::one(),
+            memory_columns.addr_word[1].into(),
+            memory_columns.addr_word[2].into(),
+            memory_columns.addr_word[3].into(),
+        ]);
+        let reservation = *memory_columns.reservation_access.value();
+        builder.when(selectors.is_lr).assert_word_eq(reservation, addr_reservation.clone());
+        builder.when(selectors.is_sc).assert_word_zero(reservation);
+
+        // SC succeeds if and only if the previous reservation is its address.
+        let prev_reservation = *memory_columns.reservation_access.prev_value();
+        IsEqualWordOperation::<AB::F>::eval(
+            builder,
+            prev_reservation.map(|x| x.into()),
+            addr_reservation,
+            memory_columns.sc_reserved,
+            selectors.is_sc.into(),
+        );
+        let sc_reserved = memory_columns.sc_reserved.is_diff_zero.result;
+
+        // The AMO instructions and LR return the previous memory value, and SC returns zero if it
+        // succeeds and one otherwise.
+        builder
+            .when((is_atomic - selectors.is_sc) * (AB::Expr::one() - local.instruction.op_a_0))
+            .assert_word_eq(local.op_a_val(), prev_mem_val);
+        let sc_failed = Word::extend_expr::<AB>(AB::Expr::one() - sc_reserved);
+        builder
+            .when(selectors.is_sc * (AB::Expr::one() - local.instruction.op_a_0))
+            .assert_word_eq(local.op_a_val(), sc_failed);
+
+        // LR stores the previous memory value back, AMOSWAP stores op_c as is, and SC stores op_c
+        // if it succeeds.
+        builder.when(selectors.is_lr).assert_word_eq(mem_val, prev_mem_val);
+        builder.when(selectors.is_amoswap).assert_word_eq(mem_val, c_val);
+        let sc_val = Word(core::array::from_fn(|i| {
+            sc_reserved * c_val[i] + (AB::Expr::one() - sc_reserved) * prev_mem_val[i]
+        }));
+        builder.when(selectors.is_sc).assert_word_eq(mem_val, sc_val);
+
+        // The arithmetic and bitwise AMOs store the result of the matching ALU operation, whose
+        // opcode is given by the opcode table.
+        builder.send_alu(
+            selectors.aux_alu_opcode,
+            mem_val,
+            prev_mem_val,
+            c_val,
+            local.shard,
+            local.channel,
+            memory_columns.atomic_nonce,
+            selectors.is_alu_atomic,
+        );
+
+        // The min/max AMOs compare the previous memory value with op_c and store one of them.
+        // The comparison is SLT or SLTU, as given by the opcode table.
+        let is_min_max = selectors.is_atomic_min + selectors.is_atomic_max;
+        builder.when(is_min_max.clone()).assert_bool(memory_columns.atomic_lt);
+        builder.send_alu(
+            selectors.aux_alu_opcode,
+            Word::extend_var::<AB>(memory_columns.atomic_lt),
+            prev_mem_val,
+            c_val,
+            local.shard,
+            local.channel,
+            memory_columns.atomic_nonce,
+            is_min_max,
+        );
+
+        let lt = memory_columns.atomic_lt;
+        let min_val = Word(core::array::from_fn(|i| {
+            lt * prev_mem_val[i] + (AB::Expr::one() - lt) * c_val[i]
+        }));
+        let max_val = Word(core::array::from_fn(|i| {
+            lt * c_val[i] + (AB::Expr::one() - lt) * prev_mem_val[i]
+        }));
+        builder.when(selectors.is_atomic_min).assert_word_eq(mem_val, min_val);
+        builder.when(selectors.is_atomic_max).assert_word_eq(mem_val, max_val);
+    }
+
     /// This function is used to evaluate the unsigned memory value for the load memory
     /// instructions.
     pub(crate) fn eval_unsigned_mem_value<AB: SP1AirBuilder>(
@@ -296,12 +427,12 @@ impl CpuChip {
 
         // When the instruciton is LB or LBU, just use the lower byte.
         builder
-            .when(local.selectors.is_lb + local.selectors.is_lbu)
+            .when(local.selectors.is_byte_load)
             .assert_word_eq(byte_value, local.unsigned_mem_val.map(|x| x.into()));
 
         // When the instruction is LH or LHU, use the lower half.
         builder
-            .when(local.selectors.is_lh + local.selectors.is_lhu)
+            .when(local.selectors.is_half_load)
             .assert_zero(memory_columns.offset_is_one + memory_columns.offset_is_three);
 
         // When the instruction is LW, ensure that the offset is zero.
@@ -316,7 +447,7 @@ impl CpuChip {
             AB::Expr::zero(),
         ]);
         builder
-            .when(local.selectors.is_lh + local.selectors.is_lhu)
+            .when(local.selectors.is_half_load)
             .assert_word_eq(half_value, local.unsigned_mem_val.map(|x| x.into()));
 
         // When the instruction is LW, just use the word.
@@ -381,3 +512,62 @@ impl CpuChip {
             .assert_eq(memory_columns.addr_offset, AB::Expr::from_canonical_u8(3));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+    use p3_matrix::dense::RowMajorMatrix;
+    use sp1_core_executor::{ExecutionRecord, Executor, Instruction, Program};
+    use sp1_stark::{
+        air::MachineAir, baby_bear_poseidon2::BabyBearPoseidon2, constraints_hold_on_row,
+        MachineRecord, SP1CoreOpts, StarkGenericConfig,
+    };
+
+    use super::*;
+
+    type Challenge = <BabyBearPoseidon2 as StarkGenericConfig>::Challenge;
+
+    #[test]
+    fn test_store_to_reservation_fails() {
+        //     addi x29, x0, 36
+        //     addi x28, x0, 0x1001
+        //     sw x28, 0(x29)
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, RESERVATION_ADDR + 4, false, true),
+            Instruction::new(Opcode::ADD, 28, 0, 0x1001, false, true),
+            Instruction::new(Opcode::SW, 28, 29, 0, false, true),
+        ];
+        let program = Program::new(instructions, 0, 0);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+        let mut record = runtime.records[0].clone();
+        let public_values = record.public_values::<BabyBear>();
+        let store_row = 2;
+        assert_eq!(record.cpu_events[store_row].instruction.opcode, Opcode::SW);
+
+        let trace: RowMajorMatrix<BabyBear> =
+            CpuChip.generate_trace(&record, &mut ExecutionRecord::default());
+        assert!(constraints_hold_on_row::<_, Challenge, _>(
+            &CpuChip,
+            None,
+            &trace,
+            store_row,
+            &public_values
+        ));
+
+        // Move the store to the word of the reservation, which the executor refuses to do, so
+        // that an SC to 0x1000 would succeed without an LR.
+        let event = &mut record.cpu_events[store_row];
+        event.c = RESERVATION_ADDR.wrapping_sub(event.b);
+        event.instruction.op_c = event.c;
+        let trace: RowMajorMatrix<BabyBear> =
+            CpuChip.generate_trace(&record, &mut ExecutionRecord::default());
+        assert!(!constraints_hold_on_row::<_, Challenge, _>(
+            &CpuChip,
+            None,
+            &trace,
+            store_row,
+            &public_values
+        ));
+    }
+}
@@ -9,7 +9,7 @@ use p3_field::AbstractField;
 use p3_matrix::Matrix;
 use sp1_core_executor::ByteOpcode;
 use sp1_stark::{
-    air::{BaseAirBuilder, PublicValues, SP1AirBuilder, SP1_PROOF_NUM_PV_ELTS},
+    air::{BaseAirBuilder, PublicValues, SP1AirBuilder, PUBLIC_VALUES_LAYOUT},
     Word,
 };
 
@@ -23,7 +23,7 @@ use crate::{
 };
 use sp1_core_executor::Opcode;
 
-use super::columns::{eval_channel_selectors, OPCODE_SELECTORS_COL_MAP};
+use super::columns::eval_channel_selectors;
 
 impl<AB> Air<AB> for CpuChip
 where
@@ -36,19 +36,14 @@ where
         let (local, next) = (main.row_slice(0), main.row_slice(1));
         let local: &CpuCols<AB::Var> = (*local).borrow();
         let next: &CpuCols<AB::Var> = (*next).borrow();
-        let public_values_slice: [AB::Expr; SP1_PROOF_NUM_PV_ELTS] =
-            core::array::from_fn(|i| builder.public_values()[i].into());
-        let public_values: &PublicValues<Word<AB::Expr>, AB::Expr> =
-            public_values_slice.as_slice().borrow();
+        let public_values: PublicValues<Word<AB::Expr>, AB::Expr> =
+            PUBLIC_VALUES_LAYOUT.read(builder.public_values());
 
         // Program constraints.
-        builder.send_program(
-            local.pc,
-            local.instruction,
-            local.selectors,
-            local.shard,
-            local.is_real,
-        );
+        builder.send_program(local.pc, local.instruction, local.shard, local.is_real);
+
+        // Opcode constraints.
+        builder.send_opcode(local.instruction.opcode, local.selectors, local.is_real);
 
         // Compute some flags for which type of instruction we are dealing with.
         let is_memory_instruction: AB::Expr = self.is_memory_instruction::<AB>(&local.selectors);
@@ -62,6 +57,7 @@ where
         self.eval_memory_address_and_access::<AB>(builder, local, is_memory_instruction.clone());
         self.eval_memory_load::<AB>(builder, local);
         self.eval_memory_store::<AB>(builder, local);
+        self.eval_memory_atomic::<AB>(builder, local);
 
         // Channel constraints.
         eval_channel_selectors(
@@ -106,7 +102,7 @@ where
         );
 
         // HALT ecall and UNIMPL instruction.
-        self.eval_halt_unimpl(builder, local, next, public_values);
+        self.eval_halt_unimpl(builder, local, next, &public_values);
 
         // Check that the shard and clk is updated correctly.
         self.eval_shard_clk(builder, local, next);
@@ -115,21 +111,17 @@ where
         self.eval_pc(builder, local, next, is_branch_instruction.clone());
 
         // Check public values constraints.
-        self.eval_public_values(builder, local, next, public_values);
+        self.eval_public_values(builder, local, next, &public_values);
 
         // Check that the is_real flag is correct.
         self.eval_is_real(builder, local, next);
 
         // Check that when `is_real=0` that all flags that send interactions are zero.
-        local.selectors.into_iter().enumerate().for_each(|(i, selector)| {
-            if i == OPCODE_SELECTORS_COL_MAP.imm_b {
-                builder.when(AB::Expr::one() - local.is_real).assert_one(local.selectors.imm_b);
-            } else if i == OPCODE_SELECTORS_COL_MAP.imm_c {
-                builder.when(AB::Expr::one() - local.is_real).assert_one(local.selectors.imm_c);
-            } else {
-                builder.when(AB::Expr::one() - local.is_real).assert_zero(selector);
-            }
+        local.selectors.into_iter().for_each(|selector| {
+            builder.when(AB::Expr::one() - local.is_real).assert_zero(selector);
         });
+        builder.when(AB::Expr::one() - local.is_real).assert_one(local.instruction.imm_b);
+        builder.when(AB::Expr::one() - local.is_real).assert_one(local.instruction.imm_c);
     }
 }
 
@@ -272,9 +264,9 @@ impl CpuChip {
 
         // Verify that the shard value is within 16 bits.
         builder.send_byte(
-            AB::Expr::from_canonical_u8(ByteOpcode::U16Range as u8),
+            AB::Expr::from_canonical_u8(ByteOpcode::Range as u8),
             local.shard,
-            AB::Expr::zero(),
+            AB::Expr::from_canonical_u8(16),
             AB::Expr::zero(),
             local.shard,
             local.channel,
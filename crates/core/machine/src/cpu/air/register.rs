@@ -18,10 +18,10 @@ impl CpuChip {
     ) {
         // Load immediates into b and c, if the immediate flags are on.
         builder
-            .when(local.selectors.imm_b)
+            .when(local.instruction.imm_b)
             .assert_word_eq(local.op_b_val(), local.instruction.op_b);
         builder
-            .when(local.selectors.imm_c)
+            .when(local.instruction.imm_c)
             .assert_word_eq(local.op_c_val(), local.instruction.op_c);
 
         // If they are not immediates, read `b` and `c` from memory.
@@ -31,7 +31,7 @@ impl CpuChip {
             local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::B as u32),
             local.instruction.op_b[0],
             &local.op_b_access,
-            AB::Expr::one() - local.selectors.imm_b,
+            AB::Expr::one() - local.instruction.imm_b,
         );
 
         builder.eval_memory_access(
@@ -40,7 +40,7 @@ impl CpuChip {
             local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::C as u32),
             local.instruction.op_c[0],
             &local.op_c_access,
-            AB::Expr::one() - local.selectors.imm_c,
+            AB::Expr::one() - local.instruction.imm_c,
         );
 
         // If we are writing to register 0, then the new value should be zero.
@@ -2,7 +2,10 @@ use sp1_derive::AlignedBorrow;
 use sp1_stark::Word;
 use std::mem::size_of;
 
-use crate::{memory::MemoryReadWriteCols, operations::BabyBearWordRangeChecker};
+use crate::{
+    memory::MemoryReadWriteCols,
+    operations::{BabyBearWordRangeChecker, IsEqualWordOperation},
+};
 
 pub const NUM_MEMORY_COLUMNS: usize = size_of::<MemoryColumns<u8>>();
 
@@ -21,6 +24,9 @@ pub struct MemoryColumns<T> {
     pub addr_word_range_checker: BabyBearWordRangeChecker<T>,
 
     pub addr_aligned: T,
+    /// The inverse of `addr_aligned - RESERVATION_ADDR`, which shows that the instruction does
+    /// not access the word of the reservation.
+    pub reservation_diff_inv: T,
     /// The LE bit decomp of the least significant byte of address aligned.
     pub aa_least_sig_byte_decomp: [T; 6],
     pub addr_offset: T,
@@ -34,6 +40,15 @@ pub struct MemoryColumns<T> {
     // determine the sign for that value (used for LB and LH).
     pub most_sig_byte_decomp: [T; 8],
 
+    /// Whether the previous memory value is less than `op_c`, for AMOMIN[U] and AMOMAX[U].
+    pub atomic_lt: T,
+
+    /// The access to the reservation of LR and SC, at `RESERVATION_ADDR`.
+    pub reservation_access: MemoryReadWriteCols<T>,
+    /// Whether the previous reservation is the address of an SC, i.e. whether the SC succeeds.
+    pub sc_reserved: IsEqualWordOperation<T>,
+
     pub addr_word_nonce: T,
     pub unsigned_mem_val_nonce: T,
+    pub atomic_nonce: T,
 }
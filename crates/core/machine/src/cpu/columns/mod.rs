@@ -76,16 +76,16 @@ pub struct CpuCols<T: Copy> {
     ///
     /// > is_beq & a_eq_b ||
     /// > is_bne & (a_lt_b | a_gt_b) ||
-    /// > (is_blt | is_bltu) & a_lt_b ||
-    /// > (is_bge | is_bgeu) & (a_eq_b | a_gt_b)
+    /// > is_branch_lt & a_lt_b ||
+    /// > is_branch_ge & (a_eq_b | a_gt_b)
     pub branching: T,
 
     /// The not branching column is equal to:
     ///
     /// > is_beq & !a_eq_b ||
     /// > is_bne & !(a_lt_b | a_gt_b) ||
-    /// > (is_blt | is_bltu) & !a_lt_b ||
-    /// > (is_bge | is_bgeu) & !(a_eq_b | a_gt_b)
+    /// > is_branch_lt & !a_lt_b ||
+    /// > is_branch_ge & !(a_eq_b | a_gt_b)
     pub not_branching: T,
 
     /// Flag for load mem instructions where the value is negative and not writing to x0.
@@ -99,7 +99,7 @@ pub struct CpuCols<T: Copy> {
     ///
     /// (
     ///     ((is_lb | is_lh) & (most_sig_byte_decomp[7] == 0)) |
-    ///     is_lbu | is_lhu | is_lw
+    ///     (is_byte_load & !is_lb) | (is_half_load & !is_lh) | is_lw
     /// ) &
     /// (not writing to x0)
     pub mem_value_is_pos_not_x0: T,
@@ -1,5 +1,5 @@
 use p3_field::PrimeField;
-use sp1_core_executor::{Instruction, Opcode};
+use sp1_core_executor::Opcode;
 use sp1_derive::AlignedBorrow;
 use std::{
     mem::{size_of, transmute},
@@ -20,38 +20,48 @@ const fn make_selectors_col_map() -> OpcodeSelectorCols<usize> {
 }
 
 /// The column layout for opcode selectors.
+///
+/// The selectors are fixed by the opcode, so they are decoded in the preprocessed opcode table
+/// ([`crate::program::OpcodeChip`]) and looked up by the CPU along with the opcode of each row.
+/// Rather than a flag per opcode, they encode the roles the CPU constraints check, which are all
+/// linear in the opcode flags, so that opcodes which are only constrained together share a
+/// selector. The opcode of the ALU operation checked by a branch or an atomic instruction is a
+/// single column shared by both.
 #[derive(AlignedBorrow, Clone, Copy, Default, Debug)]
 #[repr(C)]
 pub struct OpcodeSelectorCols<T> {
-    /// Whether op_b is an immediate value.
-    pub imm_b: T,
-
-    /// Whether op_c is an immediate value.
-    pub imm_c: T,
-
     /// Table selectors for opcodes.
     pub is_alu: T,
 
     /// Table selectors for opcodes.
     pub is_ecall: T,
 
-    /// Memory Instructions.
+    /// Load Instructions: LB and LBU, LH and LHU, and the signed LB, LH and LW.
+    pub is_byte_load: T,
+    pub is_half_load: T,
     pub is_lb: T,
-    pub is_lbu: T,
     pub is_lh: T,
-    pub is_lhu: T,
     pub is_lw: T,
+
+    /// Store Instructions.
     pub is_sb: T,
     pub is_sh: T,
     pub is_sw: T,
 
-    /// Branch Instructions.
+    /// Atomic Memory Instructions: LR, SC, AMOSWAP, the arithmetic and bitwise AMOs (AMOADD,
+    /// AMOXOR, AMOAND and AMOOR), and the min and max AMOs, signed or not.
+    pub is_lr: T,
+    pub is_sc: T,
+    pub is_amoswap: T,
+    pub is_alu_atomic: T,
+    pub is_atomic_min: T,
+    pub is_atomic_max: T,
+
+    /// Branch Instructions: BEQ, BNE, BLT and BLTU, and BGE and BGEU.
     pub is_beq: T,
     pub is_bne: T,
-    pub is_blt: T,
-    pub is_bge: T,
-    pub is_bltu: T,
-    pub is_bgeu: T,
+    pub is_branch_lt: T,
+    pub is_branch_ge: T,
 
     /// Jump Instructions.
     pub is_jalr: T,
@@ -60,47 +70,95 @@ pub struct OpcodeSelectorCols<T> {
     /// Miscellaneous.
     pub is_auipc: T,
     pub is_unimpl: T,
+
+    /// The opcode of the ALU operation of an atomic instruction, or of the comparison of a branch
+    /// instruction, and zero for the other instructions.
+    pub aux_alu_opcode: T,
 }
 
 impl<F: PrimeField> OpcodeSelectorCols<F> {
-    pub fn populate(&mut self, instruction: Instruction) {
-        self.imm_b = F::from_bool(instruction.imm_b);
-        self.imm_c = F::from_bool(instruction.imm_c);
-
-        if instruction.is_alu_instruction() {
-            self.is_alu = F::one();
-        } else if instruction.is_ecall_instruction() {
-            self.is_ecall = F::one();
-        } else if instruction.is_memory_instruction() {
-            match instruction.opcode {
-                Opcode::LB => self.is_lb = F::one(),
-                Opcode::LBU => self.is_lbu = F::one(),
-                Opcode::LHU => self.is_lhu = F::one(),
-                Opcode::LH => self.is_lh = F::one(),
-                Opcode::LW => self.is_lw = F::one(),
-                Opcode::SB => self.is_sb = F::one(),
-                Opcode::SH => self.is_sh = F::one(),
-                Opcode::SW => self.is_sw = F::one(),
-                _ => unreachable!(),
+    pub fn populate(&mut self, opcode: Opcode) {
+        match opcode {
+            Opcode::ADD
+            | Opcode::SUB
+            | Opcode::XOR
+            | Opcode::OR
+            | Opcode::AND
+            | Opcode::SLL
+            | Opcode::SRL
+            | Opcode::SRA
+            | Opcode::SLT
+            | Opcode::SLTU
+            | Opcode::MUL
+            | Opcode::MULH
+            | Opcode::MULHU
+            | Opcode::MULHSU
+            | Opcode::DIV
+            | Opcode::DIVU
+            | Opcode::REM
+            | Opcode::REMU => self.is_alu = F::one(),
+            Opcode::ECALL => self.is_ecall = F::one(),
+            Opcode::LB => {
+                self.is_byte_load = F::one();
+                self.is_lb = F::one();
+            }
+            Opcode::LBU => self.is_byte_load = F::one(),
+            Opcode::LH => {
+                self.is_half_load = F::one();
+                self.is_lh = F::one();
+            }
+            Opcode::LHU => self.is_half_load = F::one(),
+            Opcode::LW => self.is_lw = F::one(),
+            Opcode::SB => self.is_sb = F::one(),
+            Opcode::SH => self.is_sh = F::one(),
+            Opcode::SW => self.is_sw = F::one(),
+            Opcode::LR
+            | Opcode::SC
+            | Opcode::AMOSWAP
+            | Opcode::AMOADD
+            | Opcode::AMOXOR
+            | Opcode::AMOAND
+            | Opcode::AMOOR
+            | Opcode::AMOMIN
+            | Opcode::AMOMINU
+            | Opcode::AMOMAX
+            | Opcode::AMOMAXU => {
+                let (selector, aux_alu_opcode) = match opcode {
+                    Opcode::LR => (&mut self.is_lr, None),
+                    Opcode::SC => (&mut self.is_sc, None),
+                    Opcode::AMOSWAP => (&mut self.is_amoswap, None),
+                    Opcode::AMOADD => (&mut self.is_alu_atomic, Some(Opcode::ADD)),
+                    Opcode::AMOXOR => (&mut self.is_alu_atomic, Some(Opcode::XOR)),
+                    Opcode::AMOAND => (&mut self.is_alu_atomic, Some(Opcode::AND)),
+                    Opcode::AMOOR => (&mut self.is_alu_atomic, Some(Opcode::OR)),
+                    Opcode::AMOMIN => (&mut self.is_atomic_min, Some(Opcode::SLT)),
+                    Opcode::AMOMINU => (&mut self.is_atomic_min, Some(Opcode::SLTU)),
+                    Opcode::AMOMAX => (&mut self.is_atomic_max, Some(Opcode::SLT)),
+                    Opcode::AMOMAXU => (&mut self.is_atomic_max, Some(Opcode::SLTU)),
+                    _ => unreachable!(),
+                };
+                *selector = F::one();
+                self.aux_alu_opcode = aux_alu_opcode.map_or(F::zero(), Opcode::as_field);
             }
-        } else if instruction.is_branch_instruction() {
-            match instruction.opcode {
-                Opcode::BEQ => self.is_beq = F::one(),
-                Opcode::BNE => self.is_bne = F::one(),
-                Opcode::BLT => self.is_blt = F::one(),
-                Opcode::BGE => self.is_bge = F::one(),
-                Opcode::BLTU => self.is_bltu = F::one(),
-                Opcode::BGEU => self.is_bgeu = F::one(),
-                _ => unreachable!(),
+            Opcode::BEQ | Opcode::BNE | Opcode::BLT | Opcode::BGE | Opcode::BLTU | Opcode::BGEU => {
+                let (selector, aux_alu_opcode) = match opcode {
+                    Opcode::BEQ => (&mut self.is_beq, Opcode::SLTU),
+                    Opcode::BNE => (&mut self.is_bne, Opcode::SLTU),
+                    Opcode::BLT => (&mut self.is_branch_lt, Opcode::SLT),
+                    Opcode::BGE => (&mut self.is_branch_ge, Opcode::SLT),
+                    Opcode::BLTU => (&mut self.is_branch_lt, Opcode::SLTU),
+                    Opcode::BGEU => (&mut self.is_branch_ge, Opcode::SLTU),
+                    _ => unreachable!(),
+                };
+                *selector = F::one();
+                self.aux_alu_opcode = aux_alu_opcode.as_field();
             }
-        } else if instruction.opcode == Opcode::JAL {
-            self.is_jal = F::one();
-        } else if instruction.opcode == Opcode::JALR {
-            self.is_jalr = F::one();
-        } else if instruction.opcode == Opcode::AUIPC {
-            self.is_auipc = F::one();
-        } else if instruction.opcode == Opcode::UNIMP {
-            self.is_unimpl = F::one();
+            Opcode::JAL => self.is_jal = F::one(),
+            Opcode::JALR => self.is_jalr = F::one(),
+            Opcode::AUIPC => self.is_auipc = F::one(),
+            Opcode::UNIMP => self.is_unimpl = F::one(),
+            // EBREAK is not supported, so it has no selector and the CPU proves no row for it.
+            Opcode::EBREAK => {}
         }
     }
 }
@@ -111,28 +169,31 @@ impl<T> IntoIterator for OpcodeSelectorCols<T> {
 
     fn into_iter(self) -> Self::IntoIter {
         let columns = vec![
-            self.imm_b,
-            self.imm_c,
             self.is_alu,
             self.is_ecall,
+            self.is_byte_load,
+            self.is_half_load,
             self.is_lb,
-            self.is_lbu,
             self.is_lh,
-            self.is_lhu,
             self.is_lw,
             self.is_sb,
             self.is_sh,
             self.is_sw,
+            self.is_lr,
+            self.is_sc,
+            self.is_amoswap,
+            self.is_alu_atomic,
+            self.is_atomic_min,
+            self.is_atomic_max,
             self.is_beq,
             self.is_bne,
-            self.is_blt,
-            self.is_bge,
-            self.is_bltu,
-            self.is_bgeu,
+            self.is_branch_lt,
+            self.is_branch_ge,
             self.is_jalr,
             self.is_jal,
             self.is_auipc,
             self.is_unimpl,
+            self.aux_alu_opcode,
         ];
         assert_eq!(columns.len(), NUM_OPCODE_SELECTOR_COLS);
         columns.into_iter()
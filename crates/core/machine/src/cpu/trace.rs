@@ -6,9 +6,9 @@ use sp1_core_executor::{
         MemoryRecordEnum,
     },
     syscalls::SyscallCode,
-    ByteOpcode::{self, U16Range},
-    ExecutionRecord, Opcode, Program,
+    ByteOpcode, ExecutionRecord, Opcode, Program,
     Register::X0,
+    RESERVATION_ADDR,
 };
 use sp1_primitives::consts::WORD_SIZE;
 use sp1_stark::{air::MachineAir, Word};
@@ -103,6 +103,10 @@ impl<F: PrimeField32> MachineAir<F> for CpuChip {
     fn included(&self, input: &Self::Record) -> bool {
         !input.cpu_events.is_empty()
     }
+
+    fn num_real_rows(&self, input: &Self::Record) -> Option<usize> {
+        Some(input.cpu_events.len())
+    }
 }
 
 impl CpuChip {
@@ -128,7 +132,7 @@ impl CpuChip {
         cols.pc = F::from_canonical_u32(event.pc);
         cols.next_pc = F::from_canonical_u32(event.next_pc);
         cols.instruction.populate(event.instruction);
-        cols.selectors.populate(event.instruction);
+        cols.selectors.populate(event.instruction.opcode);
         *cols.op_a_access.value_mut() = event.a.into();
         *cols.op_b_access.value_mut() = event.b.into();
         *cols.op_c_access.value_mut() = event.c.into();
@@ -178,6 +182,9 @@ impl CpuChip {
         if let Some(record) = event.memory_record {
             memory_columns.memory_access.populate(event.channel, record, blu_events)
         }
+        if let Some(record) = event.reservation_record {
+            memory_columns.reservation_access.populate(event.channel, record, blu_events)
+        }
 
         // Populate memory, branch, jump, and auipc specific fields.
         self.populate_memory(cols, event, &mut new_alu_events, blu_events, nonce_lookup);
@@ -216,24 +223,8 @@ impl CpuChip {
 
         cols.channel_selectors.populate(event.channel);
 
-        blu_events.add_byte_lookup_event(ByteLookupEvent::new(
-            event.shard,
-            event.channel,
-            U16Range,
-            event.shard as u16,
-            0,
-            0,
-            0,
-        ));
-        blu_events.add_byte_lookup_event(ByteLookupEvent::new(
-            event.shard,
-            event.channel,
-            U16Range,
-            clk_16bit_limb,
-            0,
-            0,
-            0,
-        ));
+        blu_events.add_u16_range_check(event.shard, event.channel, event.shard as u16);
+        blu_events.add_u16_range_check(event.shard, event.channel, clk_16bit_limb);
         blu_events.add_byte_lookup_event(ByteLookupEvent::new(
             event.shard,
             event.channel,
@@ -254,27 +245,22 @@ impl CpuChip {
         blu_events: &mut impl ByteRecord,
         nonce_lookup: &HashMap<LookupId, u32>,
     ) {
-        if !matches!(
-            event.instruction.opcode,
-            Opcode::LB
-                | Opcode::LH
-                | Opcode::LW
-                | Opcode::LBU
-                | Opcode::LHU
-                | Opcode::SB
-                | Opcode::SH
-                | Opcode::SW
-        ) {
+        let is_atomic = event.instruction.is_atomic_instruction();
+        if !event.instruction.is_memory_instruction() && !is_atomic {
             return;
         }
 
         // Populate addr_word and addr_aligned columns.
         let memory_columns = cols.opcode_specific_columns.memory_mut();
-        let memory_addr = event.b.wrapping_add(event.c);
+        let memory_addr = if is_atomic { event.b } else { event.b.wrapping_add(event.c) };
         let aligned_addr = memory_addr - memory_addr % WORD_SIZE as u32;
         memory_columns.addr_word = memory_addr.into();
         memory_columns.addr_word_range_checker.populate(memory_addr);
         memory_columns.addr_aligned = F::from_canonical_u32(aligned_addr);
+        memory_columns.reservation_diff_inv = (F::from_canonical_u32(aligned_addr)
+            - F::from_canonical_u32(RESERVATION_ADDR))
+        .try_inverse()
+        .unwrap_or_default();
 
         // Populate the aa_least_sig_byte_decomp columns.
         assert!(aligned_addr % 4 == 0);
@@ -283,24 +269,26 @@ impl CpuChip {
         memory_columns.aa_least_sig_byte_decomp = array::from_fn(|i| F::from_bool(bits[i + 2]));
 
         // Add event to ALU check to check that addr == b + c
-        let add_event = AluEvent {
-            lookup_id: event.memory_add_lookup_id,
-            shard: event.shard,
-            channel: event.channel,
-            clk: event.clk,
-            opcode: Opcode::ADD,
-            a: memory_addr,
-            b: event.b,
-            c: event.c,
-            sub_lookups: create_alu_lookups(),
-        };
-        new_alu_events
-            .entry(Opcode::ADD)
-            .and_modify(|op_new_events| op_new_events.push(add_event))
-            .or_insert(vec![add_event]);
-        memory_columns.addr_word_nonce = F::from_canonical_u32(
-            nonce_lookup.get(&event.memory_add_lookup_id).copied().unwrap_or_default(),
-        );
+        if !is_atomic {
+            let add_event = AluEvent {
+                lookup_id: event.memory_add_lookup_id,
+                shard: event.shard,
+                channel: event.channel,
+                clk: event.clk,
+                opcode: Opcode::ADD,
+                a: memory_addr,
+                b: event.b,
+                c: event.c,
+                sub_lookups: create_alu_lookups(),
+            };
+            new_alu_events
+                .entry(Opcode::ADD)
+                .and_modify(|op_new_events| op_new_events.push(add_event))
+                .or_insert(vec![add_event]);
+            memory_columns.addr_word_nonce = F::from_canonical_u32(
+                nonce_lookup.get(&event.memory_add_lookup_id).copied().unwrap_or_default(),
+            );
+        }
 
         // Populate memory offsets.
         let addr_offset = (memory_addr % WORD_SIZE as u32) as u8;
@@ -385,6 +373,63 @@ impl CpuChip {
             );
         }
 
+        // For the atomic instructions, add the ALU event that computes the stored value.
+        if is_atomic {
+            let prev_mem_value = match event.memory_record {
+                Some(MemoryRecordEnum::Write(record)) => record.prev_value,
+                _ => unreachable!(),
+            };
+            if event.instruction.opcode == Opcode::SC {
+                let prev_reservation = match event.reservation_record {
+                    Some(MemoryRecordEnum::Write(record)) => record.prev_value,
+                    _ => unreachable!(),
+                };
+                memory_columns.sc_reserved.populate(prev_reservation, memory_addr + 1);
+            }
+            let alu_event = match event.instruction.opcode {
+                Opcode::AMOADD | Opcode::AMOXOR | Opcode::AMOAND | Opcode::AMOOR => {
+                    let opcode = match event.instruction.opcode {
+                        Opcode::AMOADD => Opcode::ADD,
+                        Opcode::AMOXOR => Opcode::XOR,
+                        Opcode::AMOAND => Opcode::AND,
+                        _ => Opcode::OR,
+                    };
+                    Some((opcode, mem_value))
+                }
+                Opcode::AMOMIN | Opcode::AMOMAX => {
+                    let lt = (prev_mem_value as i32) < (event.c as i32);
+                    memory_columns.atomic_lt = F::from_bool(lt);
+                    Some((Opcode::SLT, lt as u32))
+                }
+                Opcode::AMOMINU | Opcode::AMOMAXU => {
+                    let lt = prev_mem_value < event.c;
+                    memory_columns.atomic_lt = F::from_bool(lt);
+                    Some((Opcode::SLTU, lt as u32))
+                }
+                _ => None,
+            };
+            if let Some((opcode, a)) = alu_event {
+                let alu_event = AluEvent {
+                    lookup_id: event.memory_atomic_lookup_id,
+                    shard: event.shard,
+                    channel: event.channel,
+                    clk: event.clk,
+                    opcode,
+                    a,
+                    b: prev_mem_value,
+                    c: event.c,
+                    sub_lookups: create_alu_lookups(),
+                };
+                memory_columns.atomic_nonce = F::from_canonical_u32(
+                    nonce_lookup.get(&event.memory_atomic_lookup_id).copied().unwrap_or_default(),
+                );
+                new_alu_events
+                    .entry(opcode)
+                    .and_modify(|op_new_events| op_new_events.push(alu_event))
+                    .or_insert(vec![alu_event]);
+            }
+        }
+
         // Add event to byte lookup for byte range checking each byte in the memory addr
         let addr_bytes = memory_addr.to_le_bytes();
         for byte_pair in addr_bytes.chunks_exact(2) {
@@ -720,8 +765,8 @@ impl CpuChip {
         };
 
         rows[n_real_rows..].par_iter_mut().for_each(|padded_row| {
-            padded_row[CPU_COL_MAP.selectors.imm_b] = F::one();
-            padded_row[CPU_COL_MAP.selectors.imm_c] = F::one();
+            padded_row[CPU_COL_MAP.instruction.imm_b] = F::one();
+            padded_row[CPU_COL_MAP.instruction.imm_c] = F::one();
         });
     }
 }
@@ -1,4 +1,11 @@
+use std::{collections::BTreeMap, sync::Arc};
+
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp1_core_executor::events::HINT_WORDS_MAX_WORDS;
+use sp1_primitives::{
+    consts::WORD_SIZE,
+    io::{stdin_commitment, PublicValuesDomain, STDIN_COMMITMENT_SIZE},
+};
 use sp1_stark::{baby_bear_poseidon2::BabyBearPoseidon2, ShardProof, StarkVerifyingKey};
 
 /// Standard input for the prover.
@@ -9,17 +16,72 @@ pub struct SP1Stdin {
     pub buffer: Vec<Vec<u8>>,
     pub ptr: usize,
     pub proofs: Vec<(ShardProof<BabyBearPoseidon2>, StarkVerifyingKey<BabyBearPoseidon2>)>,
+    /// Large inputs that the program maps into its memory one page at a time, instead of reading
+    /// them from the buffer as a whole.
+    #[serde(default)]
+    pub paged_inputs: Vec<Arc<[u8]>>,
+    /// Named files that the program reads with `sp1_zkvm::fs::read`, indexed by their path.
+    #[serde(default)]
+    pub files: BTreeMap<String, Arc<[u8]>>,
+    /// The domain separator and nonce that programs built with the `replay-protection` feature of
+    /// `sp1-zkvm` write at the start of their public values.
+    #[serde(default)]
+    pub public_values_domain: Option<PublicValuesDomain>,
+    /// Environment variables that the program reads with `sp1_zkvm::env::var`.
+    #[serde(default)]
+    pub env_vars: BTreeMap<String, String>,
+    /// Arguments that the program reads with `sp1_zkvm::env::args`.
+    #[serde(default)]
+    pub args: Vec<String>,
 }
 
 impl SP1Stdin {
     /// Create a new `SP1Stdin`.
     pub const fn new() -> Self {
-        Self { buffer: Vec::new(), ptr: 0, proofs: Vec::new() }
+        Self {
+            buffer: Vec::new(),
+            ptr: 0,
+            proofs: Vec::new(),
+            paged_inputs: Vec::new(),
+            files: BTreeMap::new(),
+            public_values_domain: None,
+            env_vars: BTreeMap::new(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Create a builder for a `SP1Stdin`.
+    pub const fn builder() -> SP1StdinBuilder {
+        SP1StdinBuilder::new()
     }
 
     /// Create a `SP1Stdin` from a slice of bytes.
     pub fn from(data: &[u8]) -> Self {
-        Self { buffer: vec![data.to_vec()], ptr: 0, proofs: Vec::new() }
+        Self {
+            buffer: vec![data.to_vec()],
+            ptr: 0,
+            proofs: Vec::new(),
+            paged_inputs: Vec::new(),
+            files: BTreeMap::new(),
+            public_values_domain: None,
+            env_vars: BTreeMap::new(),
+            args: Vec::new(),
+        }
+    }
+
+    /// The commitment to the buffer that programs built with the `commit-stdin` feature of
+    /// `sp1-zkvm` append to their public values.
+    ///
+    /// The programs hash every element they read from the hint stream, starting with the public
+    /// values domain if one is set. The commitment therefore only matches programs that read no
+    /// results of hooks, files, environment variables or arguments, and write no hints.
+    ///
+    /// See [`sp1_primitives::io::SP1PublicValues::stdin_commitment`].
+    pub fn commitment(&self) -> [u8; STDIN_COMMITMENT_SIZE] {
+        let domain = self.public_values_domain.as_ref().map(PublicValuesDomain::to_bytes);
+        stdin_commitment(
+            domain.iter().map(|d| d.as_slice()).chain(self.buffer.iter().map(Vec::as_slice)),
+        )
     }
 
     /// Read a value from the buffer.
@@ -48,10 +110,35 @@ impl SP1Stdin {
         self.buffer.push(slice.to_vec());
     }
 
+    /// Write a slice of bytes to the buffer, padded with zeros to a multiple of the word size so
+    /// that the program can read it back as a slice of words.
+    pub fn write_slice_aligned(&mut self, slice: &[u8]) {
+        let mut vec = slice.to_vec();
+        vec.resize(slice.len().next_multiple_of(WORD_SIZE), 0);
+        self.buffer.push(vec);
+    }
+
+    /// Write a slice of words to the buffer, for the program to read back with
+    /// `sp1_zkvm::io::read_words`.
+    ///
+    /// The number of words is written first, then the words in elements of at most
+    /// `HINT_WORDS_MAX_WORDS` words, which the program writes straight into its memory with one
+    /// `HINT_READ_WORDS` syscall each.
+    pub fn write_words(&mut self, words: &[u32]) {
+        self.buffer.push((words.len() as u32).to_le_bytes().to_vec());
+        for chunk in words.chunks(HINT_WORDS_MAX_WORDS) {
+            self.buffer.push(chunk.iter().flat_map(|word| word.to_le_bytes()).collect());
+        }
+    }
+
     pub fn write_vec(&mut self, vec: Vec<u8>) {
         self.buffer.push(vec);
     }
 
+    /// Register a deferred proof and its verifying key.
+    ///
+    /// The proof is not written to the buffer: it is witnessed when the program verifies it, and
+    /// its verification is deferred to the recursion.
     pub fn write_proof(
         &mut self,
         proof: ShardProof<BabyBearPoseidon2>,
@@ -59,6 +146,254 @@ impl SP1Stdin {
     ) {
         self.proofs.push((proof, vk));
     }
+
+    /// Set the domain separator and nonce that programs built with the `replay-protection` feature
+    /// of `sp1-zkvm` write at the start of their public values, so that verifiers can reject proofs
+    /// generated for another context.
+    pub fn write_public_values_domain(&mut self, domain_separator: [u8; 32], nonce: u64) {
+        self.public_values_domain = Some(PublicValuesDomain::new(domain_separator, nonce));
+    }
+
+    /// Write the seed from which programs built with the `aslr` feature of `sp1-zkvm` randomize
+    /// the placement of their stack and heap. The seed is inserted as the first element of the
+    /// buffer, since the program reads it before `main`.
+    pub fn write_layout_seed(&mut self, seed: u64) {
+        self.buffer.insert(0, seed.to_le_bytes().to_vec());
+    }
+
+    /// Register a paged input and return its index, which the program passes to
+    /// `sp1_zkvm::io::PagedInput::open`.
+    ///
+    /// The input is not copied into the program's memory: the program maps the pages it reads on
+    /// demand, so only those pages are part of the proof.
+    pub fn write_paged(&mut self, data: impl Into<Arc<[u8]>>) -> u32 {
+        self.paged_inputs.push(data.into());
+        (self.paged_inputs.len() - 1) as u32
+    }
+
+    /// Register a file that the program reads with `sp1_zkvm::fs::read(path)`, replacing any file
+    /// previously registered under the same path.
+    ///
+    /// Unlike the buffer, files are looked up by name, so the order in which the program reads them
+    /// does not have to match the order in which they are written.
+    pub fn write_file(&mut self, path: impl Into<String>, data: impl Into<Arc<[u8]>>) {
+        self.files.insert(path.into(), data.into());
+    }
+
+    /// Set an environment variable that the program reads with `sp1_zkvm::env::var(key)`,
+    /// replacing any previous value.
+    pub fn env(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.env_vars.insert(key.into(), value.into());
+    }
+
+    /// Set the arguments that the program reads with `sp1_zkvm::env::args`, replacing any
+    /// previous arguments.
+    pub fn args<S: Into<String>>(&mut self, args: impl IntoIterator<Item = S>) {
+        self.args = args.into_iter().map(Into::into).collect();
+    }
+}
+
+/// A builder for [`SP1Stdin`].
+///
+/// ```ignore
+/// let stdin = SP1Stdin::builder().write(&n).write_slice(&bytes).write_proof(proof, vk).build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SP1StdinBuilder {
+    stdin: SP1Stdin,
+}
+
+impl SP1StdinBuilder {
+    /// Create a new, empty `SP1StdinBuilder`.
+    pub const fn new() -> Self {
+        Self { stdin: SP1Stdin::new() }
+    }
+
+    /// Write a value to the buffer. See [`SP1Stdin::write`].
+    #[must_use]
+    pub fn write<T: Serialize>(mut self, data: &T) -> Self {
+        self.stdin.write(data);
+        self
+    }
+
+    /// Write a slice of bytes to the buffer. See [`SP1Stdin::write_slice`].
+    #[must_use]
+    pub fn write_slice(mut self, slice: &[u8]) -> Self {
+        self.stdin.write_slice(slice);
+        self
+    }
+
+    /// Write a word aligned slice of bytes to the buffer. See [`SP1Stdin::write_slice_aligned`].
+    #[must_use]
+    pub fn write_slice_aligned(mut self, slice: &[u8]) -> Self {
+        self.stdin.write_slice_aligned(slice);
+        self
+    }
+
+    /// Write a slice of words to the buffer. See [`SP1Stdin::write_words`].
+    #[must_use]
+    pub fn write_words(mut self, words: &[u32]) -> Self {
+        self.stdin.write_words(words);
+        self
+    }
+
+    /// Write a vec of bytes to the buffer.
+    #[must_use]
+    pub fn write_vec(mut self, vec: Vec<u8>) -> Self {
+        self.stdin.write_vec(vec);
+        self
+    }
+
+    /// Register a deferred proof and its verifying key. See [`SP1Stdin::write_proof`].
+    #[must_use]
+    pub fn write_proof(
+        mut self,
+        proof: ShardProof<BabyBearPoseidon2>,
+        vk: StarkVerifyingKey<BabyBearPoseidon2>,
+    ) -> Self {
+        self.stdin.write_proof(proof, vk);
+        self
+    }
+
+    /// Register several deferred proofs and their verifying keys, in order.
+    #[must_use]
+    pub fn write_proofs(
+        mut self,
+        proofs: impl IntoIterator<
+            Item = (ShardProof<BabyBearPoseidon2>, StarkVerifyingKey<BabyBearPoseidon2>),
+        >,
+    ) -> Self {
+        self.stdin.proofs.extend(proofs);
+        self
+    }
+
+    /// Register a paged input. See [`SP1Stdin::write_paged`].
+    #[must_use]
+    pub fn write_paged(mut self, data: impl Into<Arc<[u8]>>) -> Self {
+        self.stdin.write_paged(data);
+        self
+    }
+
+    /// Register a file. See [`SP1Stdin::write_file`].
+    #[must_use]
+    pub fn write_file(mut self, path: impl Into<String>, data: impl Into<Arc<[u8]>>) -> Self {
+        self.stdin.write_file(path, data);
+        self
+    }
+
+    /// Set the domain separator and nonce of the public values. See
+    /// [`SP1Stdin::write_public_values_domain`].
+    #[must_use]
+    pub fn write_public_values_domain(mut self, domain_separator: [u8; 32], nonce: u64) -> Self {
+        self.stdin.write_public_values_domain(domain_separator, nonce);
+        self
+    }
+
+    /// Write the seed of the memory layout. See [`SP1Stdin::write_layout_seed`].
+    #[must_use]
+    pub fn write_layout_seed(mut self, seed: u64) -> Self {
+        self.stdin.write_layout_seed(seed);
+        self
+    }
+
+    /// Set an environment variable. See [`SP1Stdin::env`].
+    #[must_use]
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.stdin.env(key, value);
+        self
+    }
+
+    /// Set the arguments. See [`SP1Stdin::args`].
+    #[must_use]
+    pub fn args<S: Into<String>>(mut self, args: impl IntoIterator<Item = S>) -> Self {
+        self.stdin.args(args);
+        self
+    }
+
+    /// Build the `SP1Stdin`.
+    pub fn build(self) -> SP1Stdin {
+        self.stdin
+    }
+}
+
+impl From<SP1StdinBuilder> for SP1Stdin {
+    fn from(builder: SP1StdinBuilder) -> Self {
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SP1Stdin;
+
+    #[test]
+    fn test_write_slice_aligned() {
+        let mut stdin = SP1Stdin::new();
+        stdin.write_slice_aligned(&[1, 2, 3, 4, 5]);
+        stdin.write_slice_aligned(&[1, 2, 3, 4]);
+        stdin.write_slice_aligned(&[]);
+        assert_eq!(stdin.buffer, vec![vec![1, 2, 3, 4, 5, 0, 0, 0], vec![1, 2, 3, 4], vec![]]);
+    }
+
+    #[test]
+    fn test_write_words() {
+        let mut stdin = SP1Stdin::new();
+        let words = (0..40u32).collect::<Vec<_>>();
+        stdin.write_words(&words);
+        stdin.write_words(&[]);
+        assert_eq!(stdin.buffer.len(), 4);
+        assert_eq!(stdin.buffer[0], 40u32.to_le_bytes());
+        assert_eq!(stdin.buffer[1].len(), 32 * 4);
+        assert_eq!(stdin.buffer[2], (32..40u32).flat_map(u32::to_le_bytes).collect::<Vec<_>>());
+        assert_eq!(stdin.buffer[3], 0u32.to_le_bytes());
+    }
+
+    #[test]
+    fn test_write_layout_seed() {
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&42u32);
+        stdin.write_layout_seed(0x0102_0304_0506_0708);
+        assert_eq!(stdin.buffer[0], [8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(stdin.buffer.len(), 2);
+    }
+
+    #[test]
+    fn test_commitment() {
+        use sp1_primitives::io::{stdin_commitment, PublicValuesDomain};
+
+        let mut stdin = SP1Stdin::new();
+        stdin.write_slice(&[1, 2, 3]);
+        stdin.write_layout_seed(7);
+        let seed = 7u64.to_le_bytes();
+        assert_eq!(stdin.commitment(), stdin_commitment([seed.as_slice(), &[1, 2, 3]]));
+
+        // The programs read the public values domain before the other elements.
+        stdin.write_public_values_domain([1; 32], 2);
+        let domain = PublicValuesDomain::new([1; 32], 2).to_bytes();
+        assert_eq!(
+            stdin.commitment(),
+            stdin_commitment([domain.as_slice(), seed.as_slice(), &[1, 2, 3]])
+        );
+    }
+
+    #[test]
+    fn test_builder() {
+        let mut expected = SP1Stdin::new();
+        expected.write(&42u32);
+        expected.write_slice(&[1, 2, 3]);
+        expected.write_slice_aligned(&[1, 2, 3]);
+        expected.write_file("input.txt", b"hello".as_slice());
+
+        let mut stdin = SP1Stdin::builder()
+            .write(&42u32)
+            .write_slice(&[1, 2, 3])
+            .write_slice_aligned(&[1, 2, 3])
+            .write_file("input.txt", b"hello".as_slice())
+            .build();
+        assert_eq!(stdin.buffer, expected.buffer);
+        assert_eq!(stdin.files, expected.files);
+        assert_eq!(stdin.read::<u32>(), 42);
+    }
 }
 
 pub mod proof_serde {
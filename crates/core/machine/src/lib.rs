@@ -21,6 +21,7 @@ pub mod io;
 pub mod memory;
 pub mod operations;
 pub mod program;
+pub mod range;
 pub mod riscv;
 pub mod syscall;
 pub mod utils;
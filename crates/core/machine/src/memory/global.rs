@@ -1,5 +1,6 @@
 use core::{
     borrow::{Borrow, BorrowMut},
+    marker::PhantomData,
     mem::size_of,
 };
 use std::array;
@@ -9,14 +10,16 @@ use p3_field::{AbstractField, PrimeField32};
 use p3_matrix::{dense::RowMajorMatrix, Matrix};
 use sp1_core_executor::{events::MemoryInitializeFinalizeEvent, ExecutionRecord, Program};
 use sp1_derive::AlignedBorrow;
+use sp1_primitives::consts::{AddressSpace, Rv32};
 use sp1_stark::{
     air::{
         AirInteraction, BaseAirBuilder, MachineAir, PublicValues, SP1AirBuilder,
-        SP1_PROOF_NUM_PV_ELTS,
+        PUBLIC_VALUES_LAYOUT,
     },
     InteractionKind, Word,
 };
 
+use super::program::ZERO_REGION_INTERACTION;
 use crate::{
     operations::{AssertLtColsBits, BabyBearBitDecomposition, IsZeroOperation},
     utils::pad_to_power_of_two,
@@ -30,14 +33,28 @@ pub enum MemoryChipType {
 }
 
 /// A memory chip that can initialize or finalize values in memory.
-pub struct MemoryChip {
+///
+/// The chip is generic over the address space, but its columns are laid out for [`Rv32`], which
+/// is the only address space it is implemented for.
+pub struct MemoryChip<A: AddressSpace = Rv32> {
     pub kind: MemoryChipType,
+    /// The number of bits of the usable address space. The bits of every address above this
+    /// ceiling are constrained to be zero.
+    pub max_addr_bits: usize,
+    _address_space: PhantomData<A>,
 }
 
-impl MemoryChip {
-    /// Creates a new memory chip with a certain type.
+impl<A: AddressSpace> MemoryChip<A> {
+    /// Creates a new memory chip with a certain type, over the whole address space.
     pub const fn new(kind: MemoryChipType) -> Self {
-        Self { kind }
+        Self::with_max_addr_bits(kind, A::ADDR_BITS)
+    }
+
+    /// Creates a new memory chip with a certain type, which only accepts addresses below
+    /// `1 << max_addr_bits`.
+    pub const fn with_max_addr_bits(kind: MemoryChipType, max_addr_bits: usize) -> Self {
+        assert!(max_addr_bits <= A::ADDR_BITS, "memory ceiling exceeds the address space");
+        Self { kind, max_addr_bits, _address_space: PhantomData }
     }
 }
 
@@ -78,6 +95,12 @@ impl<F: PrimeField32> MachineAir<F> for MemoryChip {
             MemoryChipType::Finalize => input.public_values.previous_finalize_addr_bits,
         };
 
+        // The bounds of the zero region, as sent by the MemoryProgram chip.
+        let zero_region = input.program.memory_image.zero_region().unwrap_or(0..=0);
+        let (zero_first, zero_last) = (*zero_region.start(), *zero_region.end());
+        let zero_first_bits: [_; 32] = array::from_fn(|i| (zero_first >> i) & 1);
+        let zero_last_bits: [_; 32] = array::from_fn(|i| (zero_last >> i) & 1);
+
         memory_events.sort_by_key(|event| event.addr);
         let rows: Vec<[F; NUM_MEMORY_INIT_COLS]> = (0..memory_events.len()) // OPT: change this to par_iter
             .map(|i| {
@@ -123,6 +146,25 @@ impl<F: PrimeField32> MachineAir<F> for MemoryChip {
                     cols.is_last_addr = F::one();
                 }
 
+                if self.kind == MemoryChipType::Initialize && used == 1 {
+                    cols.zero_region_first = F::from_canonical_u32(zero_first);
+                    cols.zero_region_first_bits.populate(zero_first);
+                    cols.zero_region_last = F::from_canonical_u32(zero_last);
+                    cols.zero_region_last_bits.populate(zero_last);
+
+                    let addr_bits: [_; 32] = array::from_fn(|i| (addr >> i) & 1);
+                    if addr < zero_first {
+                        cols.is_below_zero_region = F::one();
+                        cols.below_zero_region_cols.populate(&addr_bits, &zero_first_bits);
+                    } else if addr > zero_last {
+                        cols.is_above_zero_region = F::one();
+                        cols.above_zero_region_cols.populate(&zero_last_bits, &addr_bits);
+                    } else {
+                        debug_assert_eq!(value, 0, "nonzero initial value at 0x{addr:08x}");
+                        cols.is_in_zero_region = F::one();
+                    }
+                }
+
                 row
             })
             .collect::<Vec<_>>();
@@ -143,6 +185,14 @@ impl<F: PrimeField32> MachineAir<F> for MemoryChip {
             MemoryChipType::Finalize => !shard.memory_finalize_events.is_empty(),
         }
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        let events = match self.kind {
+            MemoryChipType::Initialize => &shard.memory_initialize_events,
+            MemoryChipType::Finalize => &shard.memory_finalize_events,
+        };
+        Some(events.len())
+    }
 }
 
 #[derive(AlignedBorrow, Debug, Clone, Copy)]
@@ -180,6 +230,25 @@ pub struct MemoryInitCols<T> {
 
     /// A flag to inidicate the last non-padded address. An auxiliary column needed for degree 3.
     pub is_last_addr: T,
+
+    /// The first address of the zero region of the memory image, for the initialization chip.
+    pub zero_region_first: T,
+    pub zero_region_first_bits: BabyBearBitDecomposition<T>,
+
+    /// The last address of the zero region of the memory image, for the initialization chip.
+    pub zero_region_last: T,
+    pub zero_region_last_bits: BabyBearBitDecomposition<T>,
+
+    /// Whether the address is below the zero region, with the assertion that it is.
+    pub is_below_zero_region: T,
+    pub below_zero_region_cols: AssertLtColsBits<T, 32>,
+
+    /// Whether the address is above the zero region, with the assertion that it is.
+    pub is_above_zero_region: T,
+    pub above_zero_region_cols: AssertLtColsBits<T, 32>,
+
+    /// Whether the address is in the zero region, so that its initial value is zero.
+    pub is_in_zero_region: T,
 }
 
 pub(crate) const NUM_MEMORY_INIT_COLS: usize = size_of::<MemoryInitCols<u8>>();
@@ -238,6 +307,12 @@ where
             local.is_real.into(),
         );
 
+        // Constrain the address to the usable address space. Padding rows have a zero address, so
+        // the constraint can be applied to every row.
+        for bit in &local.addr_bits.bits[self.max_addr_bits..] {
+            builder.assert_zero(*bit);
+        }
+
         // Assertion for increasing address. We need to make two types of less-than assertions,
         // first we ned to assert that the addr < addr' when the next row is real. Then we need to
         // make assertions with regards to public values.
@@ -267,10 +342,8 @@ where
 
         let local_addr_bits = local.addr_bits.bits;
 
-        let public_values_array: [AB::Expr; SP1_PROOF_NUM_PV_ELTS] =
-            array::from_fn(|i| builder.public_values()[i].into());
-        let public_values: &PublicValues<Word<AB::Expr>, AB::Expr> =
-            public_values_array.as_slice().borrow();
+        let public_values: PublicValues<Word<AB::Expr>, AB::Expr> =
+            PUBLIC_VALUES_LAYOUT.read(builder.public_values());
 
         let prev_addr_bits = match self.kind {
             MemoryChipType::Initialize => &public_values.previous_init_addr_bits,
@@ -316,6 +389,50 @@ where
 
         if self.kind == MemoryChipType::Initialize {
             builder.when(local.is_real).assert_eq(local.timestamp, AB::F::one());
+
+            // The words of the zero region of the memory image are not initialized by the
+            // MemoryProgram chip, so their initial value must be constrained here. Every address
+            // is shown to be either below the region, above it, or in it with a zero value.
+            builder.receive(AirInteraction::new(
+                vec![local.zero_region_first.into(), local.zero_region_last.into()],
+                local.is_real.into(),
+                ZERO_REGION_INTERACTION,
+            ));
+            BabyBearBitDecomposition::<AB::F>::range_check(
+                builder,
+                local.zero_region_first,
+                local.zero_region_first_bits,
+                local.is_real.into(),
+            );
+            BabyBearBitDecomposition::<AB::F>::range_check(
+                builder,
+                local.zero_region_last,
+                local.zero_region_last_bits,
+                local.is_real.into(),
+            );
+
+            builder.assert_bool(local.is_below_zero_region);
+            builder.assert_bool(local.is_above_zero_region);
+            builder.assert_bool(local.is_in_zero_region);
+            builder.assert_eq(
+                local.is_below_zero_region + local.is_above_zero_region + local.is_in_zero_region,
+                local.is_real,
+            );
+            local.below_zero_region_cols.eval(
+                builder,
+                &local.addr_bits.bits,
+                &local.zero_region_first_bits.bits,
+                local.is_below_zero_region,
+            );
+            local.above_zero_region_cols.eval(
+                builder,
+                &local.zero_region_last_bits.bits,
+                &local.addr_bits.bits,
+                local.is_above_zero_region,
+            );
+            for bit in local.value {
+                builder.when(local.is_in_zero_region).assert_zero(bit);
+            }
         }
 
         // Constraints related to register %x0.
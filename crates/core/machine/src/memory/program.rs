@@ -9,7 +9,7 @@ use p3_matrix::{dense::RowMajorMatrix, Matrix};
 use sp1_core_executor::{ExecutionRecord, Program};
 use sp1_derive::AlignedBorrow;
 use sp1_stark::{
-    air::{AirInteraction, MachineAir, PublicValues, SP1AirBuilder, SP1_PROOF_NUM_PV_ELTS},
+    air::{AirInteraction, MachineAir, PublicValues, SP1AirBuilder, PUBLIC_VALUES_LAYOUT},
     InteractionKind, Word,
 };
 
@@ -19,6 +19,11 @@ pub const NUM_MEMORY_PROGRAM_PREPROCESSED_COLS: usize =
     size_of::<MemoryProgramPreprocessedCols<u8>>();
 pub const NUM_MEMORY_PROGRAM_MULT_COLS: usize = size_of::<MemoryProgramMultCols<u8>>();
 
+/// The interaction through which [`MemoryProgramChip`] gives the bounds of the zero region of the
+/// memory image to [`MemoryChip`](super::MemoryChip), which constrains the initial value of the
+/// words it initializes in this region to be zero.
+pub(crate) const ZERO_REGION_INTERACTION: InteractionKind = InteractionKind::Custom(0);
+
 /// The column layout for the chip.
 #[derive(AlignedBorrow, Clone, Copy, Default)]
 #[repr(C)]
@@ -26,6 +31,13 @@ pub struct MemoryProgramPreprocessedCols<T> {
     pub addr: T,
     pub value: Word<T>,
     pub is_real: T,
+
+    /// Whether the row holds the bounds of the zero region rather than a word.
+    pub is_zero_region: T,
+    /// The first address of the zero region.
+    pub zero_region_first: T,
+    /// The last address of the zero region.
+    pub zero_region_last: T,
 }
 
 /// Multiplicity columns.
@@ -39,11 +51,19 @@ pub struct MemoryProgramMultCols<T> {
 
     /// Whether the shard is the first shard.
     pub is_first_shard: IsZeroOperation<T>,
+
+    /// The number of words of the shard initialized in the zero region.
+    pub zero_region_multiplicity: T,
 }
 
 /// Chip that initializes memory that is provided from the program. The table is preprocessed and
 /// receives each row in the first shard. This prevents any of these addresses from being
 /// overwritten through the normal MemoryInit.
+///
+/// Only the explicit words of the memory image have a row. The runs of zeros, such as the BSS of
+/// the program, are initialized through the normal MemoryInit when they are touched: the last row
+/// of the table holds the bounds of the zero region of the image, which MemoryInit looks up to
+/// constrain the initial value of the words in this region to be zero.
 #[derive(Default)]
 pub struct MemoryProgramChip;
 
@@ -67,11 +87,11 @@ impl<F: PrimeField> MachineAir<F> for MemoryProgramChip {
     }
 
     fn generate_preprocessed_trace(&self, program: &Self::Program) -> Option<RowMajorMatrix<F>> {
-        let program_memory = program.memory_image.clone();
-        // Note that BTreeMap is guaranteed to be sorted by key. This makes the row order
+        // Note that the memory image is iterated in address order. This makes the row order
         // deterministic.
-        let rows = program_memory
-            .into_iter()
+        let mut rows = program
+            .memory_image
+            .words()
             .map(|(addr, word)| {
                 let mut row = [F::zero(); NUM_MEMORY_PROGRAM_PREPROCESSED_COLS];
                 let cols: &mut MemoryProgramPreprocessedCols<F> = row.as_mut_slice().borrow_mut();
@@ -82,6 +102,16 @@ impl<F: PrimeField> MachineAir<F> for MemoryProgramChip {
             })
             .collect::<Vec<_>>();
 
+        // Without runs of zeros, the zero region is the address zero, which always starts at
+        // zero since it is the register %x0.
+        let zero_region = program.memory_image.zero_region().unwrap_or(0..=0);
+        let mut row = [F::zero(); NUM_MEMORY_PROGRAM_PREPROCESSED_COLS];
+        let cols: &mut MemoryProgramPreprocessedCols<F> = row.as_mut_slice().borrow_mut();
+        cols.is_zero_region = F::one();
+        cols.zero_region_first = F::from_canonical_u32(*zero_region.start());
+        cols.zero_region_last = F::from_canonical_u32(*zero_region.end());
+        rows.push(row);
+
         // Convert the trace to a row major matrix.
         let mut trace = RowMajorMatrix::new(
             rows.into_iter().flatten().collect::<Vec<_>>(),
@@ -103,13 +133,10 @@ impl<F: PrimeField> MachineAir<F> for MemoryProgramChip {
         input: &ExecutionRecord,
         _output: &mut ExecutionRecord,
     ) -> RowMajorMatrix<F> {
-        let program_memory_addrs = input.program.memory_image.keys().copied().collect::<Vec<_>>();
-
         let mult = if input.public_values.shard == 1 { F::one() } else { F::zero() };
 
-        // Generate the trace rows for each event.
-        let rows = program_memory_addrs
-            .into_iter()
+        // Generate the trace rows for each explicit word of the memory image.
+        let mut rows = (0..input.program.memory_image.num_words())
             .map(|_| {
                 let mut row = [F::zero(); NUM_MEMORY_PROGRAM_MULT_COLS];
                 let cols: &mut MemoryProgramMultCols<F> = row.as_mut_slice().borrow_mut();
@@ -119,6 +146,14 @@ impl<F: PrimeField> MachineAir<F> for MemoryProgramChip {
             })
             .collect::<Vec<_>>();
 
+        // The zero region is looked up once for every word initialized by the shard.
+        let num_initialized =
+            input.memory_initialize_events.iter().filter(|event| event.used == 1).count();
+        let mut row = [F::zero(); NUM_MEMORY_PROGRAM_MULT_COLS];
+        let cols: &mut MemoryProgramMultCols<F> = row.as_mut_slice().borrow_mut();
+        cols.zero_region_multiplicity = F::from_canonical_usize(num_initialized);
+        rows.push(row);
+
         // Convert the trace to a row major matrix.
         let mut trace = RowMajorMatrix::new(
             rows.into_iter().flatten().collect::<Vec<_>>(),
@@ -134,6 +169,10 @@ impl<F: PrimeField> MachineAir<F> for MemoryProgramChip {
     fn included(&self, _: &Self::Record) -> bool {
         true
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.program.memory_image.num_words() + 1)
+    }
 }
 
 impl<F> BaseAir<F> for MemoryProgramChip {
@@ -157,10 +196,8 @@ where
         let mult_local: &MemoryProgramMultCols<AB::Var> = (*mult_local).borrow();
 
         // Get shard from public values and evaluate whether it is the first shard.
-        let public_values_slice: [AB::Expr; SP1_PROOF_NUM_PV_ELTS] =
-            core::array::from_fn(|i| builder.public_values()[i].into());
-        let public_values: &PublicValues<Word<AB::Expr>, AB::Expr> =
-            public_values_slice.as_slice().borrow();
+        let public_values: PublicValues<Word<AB::Expr>, AB::Expr> =
+            PUBLIC_VALUES_LAYOUT.read(builder.public_values());
 
         // Constrain `is_first_shard` to be 1 if and only if the shard is the first shard.
         IsZeroOperation::<AB::F>::eval(
@@ -188,5 +225,15 @@ where
             mult_local.multiplicity.into(),
             InteractionKind::Memory,
         ));
+
+        // Only the row holding the bounds of the zero region may send them.
+        builder
+            .when_not(prep_local.is_zero_region)
+            .assert_zero(mult_local.zero_region_multiplicity);
+        builder.send(AirInteraction::new(
+            vec![prep_local.zero_region_first.into(), prep_local.zero_region_last.into()],
+            mult_local.zero_region_multiplicity.into(),
+            ZERO_REGION_INTERACTION,
+        ));
     }
 }
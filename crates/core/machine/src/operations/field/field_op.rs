@@ -173,6 +173,50 @@ impl<F: PrimeField32, P: FieldParameters> FieldOpCols<F, P> {
     ) -> BigUint {
         self.populate_with_modulus(record, shard, channel, a, b, &P::modulus(), op)
     }
+
+    /// Populate these columns for an arbitrary operation `p_op = p_result + carry * modulus`. This
+    /// is the counterpart of [`FieldOpCols::eval_with_polynomials`], where `result` is the value
+    /// of the result columns, which need not coincide with `p_result`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn populate_with_polynomials(
+        &mut self,
+        record: &mut impl ByteRecord,
+        shard: u32,
+        channel: u8,
+        p_op: &Polynomial<F>,
+        p_result: &Polynomial<F>,
+        result: &BigUint,
+        carry: &BigUint,
+        modulus: &BigUint,
+    ) {
+        let p_modulus_limbs =
+            modulus.to_bytes_le().iter().map(|x| F::from_canonical_u8(*x)).collect::<Vec<F>>();
+        let p_modulus: Polynomial<F> = p_modulus_limbs.iter().into();
+        let p_carry: Polynomial<F> = P::to_limbs_field::<F, _>(carry).into();
+        let p_vanishing: Polynomial<F> = p_op - p_result - &p_carry * &p_modulus;
+
+        let p_witness = compute_root_quotient_and_shift(
+            &p_vanishing,
+            P::WITNESS_OFFSET,
+            P::NB_BITS_PER_LIMB as u32,
+            P::NB_WITNESS_LIMBS,
+        );
+        let (mut p_witness_low, mut p_witness_high) = split_u16_limbs_to_u8_limbs(&p_witness);
+
+        self.result = P::to_limbs_field::<F, _>(result);
+        self.carry = p_carry.into();
+
+        p_witness_low.resize(P::Witness::USIZE, F::zero());
+        p_witness_high.resize(P::Witness::USIZE, F::zero());
+        self.witness_low = Limbs(p_witness_low.try_into().unwrap());
+        self.witness_high = Limbs(p_witness_high.try_into().unwrap());
+
+        // Range checks
+        record.add_u8_range_checks_field(shard, channel, &self.result.0);
+        record.add_u8_range_checks_field(shard, channel, &self.carry.0);
+        record.add_u8_range_checks_field(shard, channel, &self.witness_low.0);
+        record.add_u8_range_checks_field(shard, channel, &self.witness_high.0);
+    }
 }
 
 impl<V: Copy, P: FieldParameters> FieldOpCols<V, P> {
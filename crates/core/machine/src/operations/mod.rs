@@ -17,6 +17,7 @@ mod is_equal_word;
 mod is_zero;
 mod is_zero_word;
 mod lt;
+mod multi_row;
 mod not;
 mod or;
 mod xor;
@@ -33,6 +34,7 @@ pub use is_equal_word::*;
 pub use is_zero::*;
 pub use is_zero_word::*;
 pub use lt::*;
+pub use multi_row::*;
 pub use not::*;
 pub use or::*;
 pub use xor::*;
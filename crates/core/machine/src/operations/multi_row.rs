@@ -0,0 +1,71 @@
+//! Constraints delimiting the operations of a chip whose operations span several consecutive rows.
+//!
+//! The rows of such a chip have an `is_first_row` and an `is_last_row` flag marking the row an
+//! operation starts and ends on, and a `nonce` counting the operations, so that the first row of
+//! each operation can receive its syscall. Padding rows, where `is_real` is zero, only come after
+//! the last operation or between two operations.
+use std::borrow::BorrowMut;
+
+use p3_air::AirBuilder;
+use p3_field::{AbstractField, Field};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+
+use sp1_stark::air::SP1AirBuilder;
+
+/// The columns of a row that delimit the operations of a multi-row chip.
+#[derive(Clone, Copy, Debug)]
+pub struct MultiRowFlags<T> {
+    /// Whether the row belongs to an operation.
+    pub is_real: T,
+    /// Whether the row is the first row of an operation.
+    pub is_first_row: T,
+    /// Whether the row is the last row of an operation.
+    pub is_last_row: T,
+    /// The index of the operation of the row in the shard.
+    pub nonce: T,
+}
+
+/// Writes the nonces of `trace`, whose rows already have their other flags, given a function
+/// returning the `is_first_row` flag and the nonce column of the columns of a row.
+pub fn populate_multi_row_nonces<F: Field, C>(
+    trace: &mut RowMajorMatrix<F>,
+    flags: impl Fn(&mut C) -> (F, &mut F),
+) where
+    [F]: BorrowMut<C>,
+{
+    let width = trace.width();
+    let mut nonce = F::zero();
+    for (i, row) in trace.values.chunks_exact_mut(width).enumerate() {
+        let (is_first_row, row_nonce) = flags(row.borrow_mut());
+        if i > 0 {
+            nonce += is_first_row;
+        }
+        *row_nonce = nonce;
+    }
+}
+
+/// Constrains the flags and the nonce of the rows `local` and `next` of a multi-row chip.
+///
+/// The constraints only relate `is_last_row` to the start of the next operation: the chip must
+/// constrain it to be set on the actual last row of each operation.
+pub fn eval_multi_row_flags<AB: SP1AirBuilder>(
+    builder: &mut AB,
+    local: MultiRowFlags<AB::Var>,
+    next: MultiRowFlags<AB::Var>,
+) {
+    builder.assert_bool(local.is_first_row);
+
+    // An operation starts on the first real row of the trace, and on every real row that
+    // follows a padding row or the last row of an operation.
+    builder.when_first_row().assert_eq(local.is_first_row, local.is_real);
+    builder.when_transition().assert_eq(
+        next.is_first_row,
+        next.is_real * (AB::Expr::one() - local.is_real + local.is_last_row),
+    );
+    // The trace cannot end in the middle of an operation.
+    builder.when_last_row().assert_eq(local.is_last_row, local.is_real);
+
+    // The nonce increments at the first row of every operation.
+    builder.when_first_row().assert_zero(local.nonce);
+    builder.when_transition().assert_eq(local.nonce + next.is_first_row, next.nonce);
+}
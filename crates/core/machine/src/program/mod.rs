@@ -1,3 +1,7 @@
+mod opcode;
+
+pub use opcode::*;
+
 use core::{
     borrow::{Borrow, BorrowMut},
     mem::size_of,
@@ -12,10 +16,7 @@ use sp1_core_executor::{ExecutionRecord, Program};
 use sp1_derive::AlignedBorrow;
 use sp1_stark::air::{MachineAir, SP1AirBuilder};
 
-use crate::{
-    cpu::columns::{InstructionCols, OpcodeSelectorCols},
-    utils::pad_to_power_of_two,
-};
+use crate::{cpu::columns::InstructionCols, utils::pad_to_power_of_two};
 
 /// The number of preprocessed program columns.
 pub const NUM_PROGRAM_PREPROCESSED_COLS: usize = size_of::<ProgramPreprocessedCols<u8>>();
@@ -29,7 +30,6 @@ pub const NUM_PROGRAM_MULT_COLS: usize = size_of::<ProgramMultiplicityCols<u8>>(
 pub struct ProgramPreprocessedCols<T> {
     pub pc: T,
     pub instruction: InstructionCols<T>,
-    pub selectors: OpcodeSelectorCols<T>,
 }
 
 /// The column layout for the chip.
@@ -76,7 +76,6 @@ impl<F: PrimeField> MachineAir<F> for ProgramChip {
                 let cols: &mut ProgramPreprocessedCols<F> = row.as_mut_slice().borrow_mut();
                 cols.pc = F::from_canonical_u32(pc);
                 cols.instruction.populate(instruction);
-                cols.selectors.populate(instruction);
 
                 row
             })
@@ -145,6 +144,10 @@ impl<F: PrimeField> MachineAir<F> for ProgramChip {
     fn included(&self, _: &Self::Record) -> bool {
         true
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.program.instructions.len())
+    }
 }
 
 impl<F> BaseAir<F> for ProgramChip {
@@ -170,7 +173,6 @@ where
         builder.receive_program(
             prep_local.pc,
             prep_local.instruction,
-            prep_local.selectors,
             mult_local.shard,
             mult_local.multiplicity,
         );
@@ -180,7 +182,7 @@ where
 #[cfg(test)]
 mod tests {
 
-    use std::{collections::BTreeMap, sync::Arc};
+    use std::sync::Arc;
 
     use p3_baby_bear::BabyBear;
 
@@ -202,12 +204,7 @@ mod tests {
             Instruction::new(Opcode::ADD, 31, 30, 29, false, false),
         ];
         let shard = ExecutionRecord {
-            program: Arc::new(Program {
-                instructions,
-                pc_start: 0,
-                pc_base: 0,
-                memory_image: BTreeMap::new(),
-            }),
+            program: Arc::new(Program::new(instructions, 0, 0)),
             ..Default::default()
         };
         let chip = ProgramChip::new();
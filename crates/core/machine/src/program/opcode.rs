@@ -0,0 +1,310 @@
+use core::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+
+use enum_map::Enum;
+use hashbrown::HashMap;
+use p3_air::{Air, BaseAir, PairBuilder};
+use p3_field::PrimeField;
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use sp1_core_executor::{ExecutionRecord, Opcode, Program};
+use sp1_derive::AlignedBorrow;
+use sp1_stark::{
+    air::{MachineAir, SP1AirBuilder},
+    InteractionKind,
+};
+
+use crate::{air::ProgramAirBuilder, cpu::columns::OpcodeSelectorCols, utils::pad_to_power_of_two};
+
+/// The number of preprocessed opcode table columns.
+pub const NUM_OPCODE_PREPROCESSED_COLS: usize = size_of::<OpcodePreprocessedCols<u8>>();
+
+/// The number of columns for the opcode table multiplicities.
+pub const NUM_OPCODE_MULT_COLS: usize = size_of::<OpcodeMultiplicityCols<u8>>();
+
+/// The interaction through which the CPU looks up the selectors of the opcode of each of its rows
+/// in [`OpcodeChip`].
+pub(crate) const OPCODE_INTERACTION: InteractionKind = InteractionKind::Custom(2);
+
+/// The column layout for the chip.
+#[derive(AlignedBorrow, Clone, Copy, Default)]
+#[repr(C)]
+pub struct OpcodePreprocessedCols<T> {
+    pub opcode: T,
+    pub selectors: OpcodeSelectorCols<T>,
+}
+
+/// The column layout for the chip.
+#[derive(AlignedBorrow, Clone, Copy, Default)]
+#[repr(C)]
+pub struct OpcodeMultiplicityCols<T> {
+    pub multiplicity: T,
+}
+
+/// A chip that decodes every opcode into the selectors of the CPU, with a preprocessed row for
+/// each opcode.
+#[derive(Default)]
+pub struct OpcodeChip;
+
+impl OpcodeChip {
+    pub const fn new() -> Self {
+        Self {}
+    }
+
+    /// The opcodes of the rows of the table, in order.
+    pub fn opcodes() -> impl Iterator<Item = Opcode> {
+        (0..Opcode::LENGTH).map(Opcode::from_usize)
+    }
+}
+
+impl<F: PrimeField> MachineAir<F> for OpcodeChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Opcode".to_string()
+    }
+
+    fn preprocessed_width(&self) -> usize {
+        NUM_OPCODE_PREPROCESSED_COLS
+    }
+
+    fn generate_preprocessed_trace(&self, _program: &Self::Program) -> Option<RowMajorMatrix<F>> {
+        let rows = Self::opcodes()
+            .map(|opcode| {
+                let mut row = [F::zero(); NUM_OPCODE_PREPROCESSED_COLS];
+                let cols: &mut OpcodePreprocessedCols<F> = row.as_mut_slice().borrow_mut();
+                cols.opcode = opcode.as_field();
+                cols.selectors.populate(opcode);
+                row
+            })
+            .collect::<Vec<_>>();
+
+        // Convert the trace to a row major matrix.
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_OPCODE_PREPROCESSED_COLS,
+        );
+
+        // Pad the trace to a power of two.
+        pad_to_power_of_two::<NUM_OPCODE_PREPROCESSED_COLS, F>(&mut trace.values);
+
+        Some(trace)
+    }
+
+    fn generate_dependencies(&self, _input: &ExecutionRecord, _output: &mut ExecutionRecord) {
+        // Do nothing since this chip has no dependencies.
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        // Collect the number of times each opcode is executed from the cpu events.
+        let mut opcode_counts = HashMap::new();
+        input.cpu_events.iter().for_each(|event| {
+            *opcode_counts.entry(event.instruction.opcode).or_insert(0) += 1;
+        });
+
+        let rows = Self::opcodes()
+            .map(|opcode| {
+                let mut row = [F::zero(); NUM_OPCODE_MULT_COLS];
+                let cols: &mut OpcodeMultiplicityCols<F> = row.as_mut_slice().borrow_mut();
+                cols.multiplicity =
+                    F::from_canonical_usize(*opcode_counts.get(&opcode).unwrap_or(&0));
+                row
+            })
+            .collect::<Vec<_>>();
+
+        // Convert the trace to a row major matrix.
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_OPCODE_MULT_COLS,
+        );
+
+        // Pad the trace to a power of two.
+        pad_to_power_of_two::<NUM_OPCODE_MULT_COLS, F>(&mut trace.values);
+
+        trace
+    }
+
+    fn included(&self, _: &Self::Record) -> bool {
+        true
+    }
+
+    fn num_real_rows(&self, _shard: &Self::Record) -> Option<usize> {
+        Some(Opcode::LENGTH)
+    }
+}
+
+impl<F> BaseAir<F> for OpcodeChip {
+    fn width(&self) -> usize {
+        NUM_OPCODE_MULT_COLS
+    }
+}
+
+impl<AB> Air<AB> for OpcodeChip
+where
+    AB: SP1AirBuilder + PairBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let preprocessed = builder.preprocessed();
+
+        let prep_local = preprocessed.row_slice(0);
+        let prep_local: &OpcodePreprocessedCols<AB::Var> = (*prep_local).borrow();
+        let mult_local = main.row_slice(0);
+        let mult_local: &OpcodeMultiplicityCols<AB::Var> = (*mult_local).borrow();
+
+        // Constrain the interaction with the CPU table.
+        builder.receive_opcode(prep_local.opcode, prep_local.selectors, mult_local.multiplicity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+    use p3_field::AbstractField;
+    use p3_matrix::{dense::RowMajorMatrix, Matrix};
+    use sp1_core_executor::{
+        programs::tests::{atomic_memory_program, simple_memory_program},
+        ExecutionRecord, Executor, Opcode,
+    };
+    use sp1_stark::{
+        air::MachineAir, baby_bear_poseidon2::BabyBearPoseidon2, LookupBalance, SP1CoreOpts,
+    };
+
+    use super::*;
+    use crate::{
+        cpu::{
+            columns::{CPU_COL_MAP, NUM_CPU_COLS, OPCODE_SELECTORS_COL_MAP},
+            CpuChip,
+        },
+        riscv::RiscvAir,
+    };
+
+    /// The selectors an opcode should decode to, and the opcode of its auxiliary ALU operation.
+    fn expected_selectors(opcode: Opcode) -> (Vec<usize>, Option<Opcode>) {
+        let map = OPCODE_SELECTORS_COL_MAP;
+        match opcode {
+            Opcode::ADD
+            | Opcode::SUB
+            | Opcode::XOR
+            | Opcode::OR
+            | Opcode::AND
+            | Opcode::SLL
+            | Opcode::SRL
+            | Opcode::SRA
+            | Opcode::SLT
+            | Opcode::SLTU
+            | Opcode::MUL
+            | Opcode::MULH
+            | Opcode::MULHU
+            | Opcode::MULHSU
+            | Opcode::DIV
+            | Opcode::DIVU
+            | Opcode::REM
+            | Opcode::REMU => (vec![map.is_alu], None),
+            Opcode::LB => (vec![map.is_byte_load, map.is_lb], None),
+            Opcode::LBU => (vec![map.is_byte_load], None),
+            Opcode::LH => (vec![map.is_half_load, map.is_lh], None),
+            Opcode::LHU => (vec![map.is_half_load], None),
+            Opcode::LW => (vec![map.is_lw], None),
+            Opcode::SB => (vec![map.is_sb], None),
+            Opcode::SH => (vec![map.is_sh], None),
+            Opcode::SW => (vec![map.is_sw], None),
+            Opcode::BEQ => (vec![map.is_beq], Some(Opcode::SLTU)),
+            Opcode::BNE => (vec![map.is_bne], Some(Opcode::SLTU)),
+            Opcode::BLT => (vec![map.is_branch_lt], Some(Opcode::SLT)),
+            Opcode::BLTU => (vec![map.is_branch_lt], Some(Opcode::SLTU)),
+            Opcode::BGE => (vec![map.is_branch_ge], Some(Opcode::SLT)),
+            Opcode::BGEU => (vec![map.is_branch_ge], Some(Opcode::SLTU)),
+            Opcode::JAL => (vec![map.is_jal], None),
+            Opcode::JALR => (vec![map.is_jalr], None),
+            Opcode::AUIPC => (vec![map.is_auipc], None),
+            Opcode::ECALL => (vec![map.is_ecall], None),
+            Opcode::EBREAK => (vec![], None),
+            Opcode::UNIMP => (vec![map.is_unimpl], None),
+            Opcode::LR => (vec![map.is_lr], None),
+            Opcode::SC => (vec![map.is_sc], None),
+            Opcode::AMOSWAP => (vec![map.is_amoswap], None),
+            Opcode::AMOADD => (vec![map.is_alu_atomic], Some(Opcode::ADD)),
+            Opcode::AMOXOR => (vec![map.is_alu_atomic], Some(Opcode::XOR)),
+            Opcode::AMOAND => (vec![map.is_alu_atomic], Some(Opcode::AND)),
+            Opcode::AMOOR => (vec![map.is_alu_atomic], Some(Opcode::OR)),
+            Opcode::AMOMIN => (vec![map.is_atomic_min], Some(Opcode::SLT)),
+            Opcode::AMOMINU => (vec![map.is_atomic_min], Some(Opcode::SLTU)),
+            Opcode::AMOMAX => (vec![map.is_atomic_max], Some(Opcode::SLT)),
+            Opcode::AMOMAXU => (vec![map.is_atomic_max], Some(Opcode::SLTU)),
+        }
+    }
+
+    #[test]
+    fn test_opcode_table_decoding() {
+        let trace: RowMajorMatrix<BabyBear> =
+            OpcodeChip.generate_preprocessed_trace(&Program::default()).unwrap();
+        assert!(trace.height().is_power_of_two());
+
+        for (row, opcode) in OpcodeChip::opcodes().enumerate() {
+            let row = trace.row_slice(row);
+            let cols: &OpcodePreprocessedCols<BabyBear> = (*row).borrow();
+            assert_eq!(cols.opcode, opcode.as_field::<BabyBear>());
+
+            let (flags, aux_alu_opcode) = expected_selectors(opcode);
+            for (i, selector) in cols.selectors.into_iter().enumerate() {
+                let expected = if i == OPCODE_SELECTORS_COL_MAP.aux_alu_opcode {
+                    aux_alu_opcode.map_or(BabyBear::zero(), Opcode::as_field)
+                } else {
+                    BabyBear::from_bool(flags.contains(&i))
+                };
+                assert_eq!(selector, expected, "selector {i} of {opcode}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_opcode_lookup() {
+        let machine = RiscvAir::machine(BabyBearPoseidon2::new());
+
+        for program in [simple_memory_program(), atomic_memory_program()] {
+            let (pk, _) = machine.setup(&program);
+            let mut runtime = Executor::new(program, SP1CoreOpts::default());
+            runtime.run().unwrap();
+            let record = &runtime.records[0];
+
+            // The imbalances of the opcode lookups between the CPU and the opcode table.
+            let opcode_imbalances = |cpu: RowMajorMatrix<BabyBear>| {
+                let table = OpcodeChip.generate_trace(record, &mut ExecutionRecord::default());
+                let traces = [("CPU".to_string(), cpu), ("Opcode".to_string(), table)];
+                let mut balance = LookupBalance::new();
+                balance.add_shard(&machine, &pk, &traces);
+                balance
+                    .imbalances()
+                    .into_iter()
+                    .filter(|imbalance| imbalance.kind == OPCODE_INTERACTION)
+                    .count()
+            };
+
+            let cpu: RowMajorMatrix<BabyBear> =
+                CpuChip.generate_trace(record, &mut ExecutionRecord::default());
+            assert_eq!(opcode_imbalances(cpu.clone()), 0);
+
+            // A load or store claiming the selectors of another one does not balance.
+            let row = record
+                .cpu_events
+                .iter()
+                .position(|event| event.instruction.is_memory_instruction())
+                .unwrap();
+            let mut forged = cpu;
+            let values = &mut forged.values[row * NUM_CPU_COLS..(row + 1) * NUM_CPU_COLS];
+            let selectors = CPU_COL_MAP.selectors;
+            for selector in [selectors.is_lw, selectors.is_sw] {
+                values[selector] = BabyBear::one() - values[selector];
+            }
+            assert_eq!(opcode_imbalances(forged), 2);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, BaseAir, PairBuilder};
+use p3_field::{AbstractField, Field};
+use p3_matrix::Matrix;
+use sp1_core_executor::ByteOpcode;
+use sp1_stark::air::SP1AirBuilder;
+
+use super::{
+    columns::{RangeMultCols, RangePreprocessedCols, NUM_RANGE_MULT_COLS},
+    RangeChip,
+};
+use crate::bytes::NUM_BYTE_LOOKUP_CHANNELS;
+
+impl<F: Field> BaseAir<F> for RangeChip<F> {
+    fn width(&self) -> usize {
+        NUM_RANGE_MULT_COLS
+    }
+}
+
+impl<AB: SP1AirBuilder + PairBuilder> Air<AB> for RangeChip<AB::F> {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local_mult = main.row_slice(0);
+        let local_mult: &RangeMultCols<AB::Var> = (*local_mult).borrow();
+
+        let prep = builder.preprocessed();
+        let prep = prep.row_slice(0);
+        let local: &RangePreprocessedCols<AB::Var> = (*prep).borrow();
+
+        // Receive the range checks on each channel.
+        let opcode = ByteOpcode::Range.as_field::<AB::F>();
+        for channel in 0..NUM_BYTE_LOOKUP_CHANNELS {
+            builder.receive_byte(
+                opcode,
+                local.value,
+                local.bits,
+                AB::F::zero(),
+                local_mult.shard,
+                AB::F::from_canonical_u8(channel),
+                local_mult.multiplicities[channel as usize],
+            );
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use sp1_derive::AlignedBorrow;
+use std::mem::size_of;
+
+use crate::bytes::NUM_BYTE_LOOKUP_CHANNELS;
+
+/// The number of preprocessed columns for `RangeChip`.
+pub const NUM_RANGE_PREPROCESSED_COLS: usize = size_of::<RangePreprocessedCols<u8>>();
+
+/// The number of multiplicity columns for `RangeChip`.
+pub const NUM_RANGE_MULT_COLS: usize = size_of::<RangeMultCols<u8>>();
+
+#[derive(Debug, Clone, Copy, AlignedBorrow)]
+#[repr(C)]
+pub struct RangePreprocessedCols<T> {
+    /// The value being range checked.
+    pub value: T,
+
+    /// The number of bits the value fits in.
+    pub bits: T,
+}
+
+/// For each row of the preprocessed table, tracks the number of times the range check is used on
+/// each channel.
+#[derive(Debug, Clone, Copy, AlignedBorrow)]
+#[repr(C)]
+pub struct RangeMultCols<T> {
+    /// Shard number is tracked so that the multiplicities do not overflow.
+    pub shard: T,
+
+    /// The multiplicities of the range check on each channel.
+    pub multiplicities: [T; NUM_BYTE_LOOKUP_CHANNELS as usize],
+}
@@ -0,0 +1,78 @@
+pub mod air;
+pub mod columns;
+pub mod trace;
+
+use core::borrow::BorrowMut;
+use std::marker::PhantomData;
+
+use p3_field::Field;
+use p3_matrix::dense::RowMajorMatrix;
+use sp1_core_executor::events::MAX_RANGE_CHECK_BITS;
+
+use self::columns::{RangePreprocessedCols, NUM_RANGE_PREPROCESSED_COLS};
+
+/// The number of rows of the range table.
+pub const NUM_ROWS: usize = 1 << (MAX_RANGE_CHECK_BITS + 1);
+
+/// A chip for range checking values of up to [`MAX_RANGE_CHECK_BITS`] bits.
+///
+/// The chip contains a preprocessed table of all pairs `(value, bits)` with `value < 2^bits`, so
+/// that other chips can range check a value of any width with a single lookup instead of
+/// decomposing it into bits or bytes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RangeChip<F>(PhantomData<F>);
+
+/// The row of the range table containing `value` with `bits` bits.
+///
+/// The rows are sorted by number of bits, and the values of each number of bits are contiguous.
+pub const fn range_row(value: u16, bits: u8) -> usize {
+    (1 << bits) - 1 + value as usize
+}
+
+impl<F: Field> RangeChip<F> {
+    /// Creates the preprocessed range trace.
+    ///
+    /// The last row, which is not covered by any number of bits, is left as `(0, 0)`.
+    pub fn trace() -> RowMajorMatrix<F> {
+        let mut trace = RowMajorMatrix::new(
+            vec![F::zero(); NUM_ROWS * NUM_RANGE_PREPROCESSED_COLS],
+            NUM_RANGE_PREPROCESSED_COLS,
+        );
+
+        for bits in 0..=MAX_RANGE_CHECK_BITS as u8 {
+            for value in 0..(1u32 << bits) {
+                let value = value as u16;
+                let cols: &mut RangePreprocessedCols<F> =
+                    trace.row_mut(range_row(value, bits)).borrow_mut();
+                cols.value = F::from_canonical_u16(value);
+                cols.bits = F::from_canonical_u8(bits);
+            }
+        }
+
+        trace
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::borrow::Borrow;
+
+    use p3_baby_bear::BabyBear;
+    use p3_field::AbstractField;
+    use p3_matrix::Matrix;
+
+    use super::*;
+
+    #[test]
+    fn test_range_trace() {
+        let trace = RangeChip::<BabyBear>::trace();
+        assert_eq!(trace.height(), NUM_ROWS);
+        for (value, bits) in [(0, 0), (1, 1), (5, 3), (0xfff, 12), (0xffff, 16)] {
+            let row = trace.row_slice(range_row(value, bits));
+            let cols: &RangePreprocessedCols<BabyBear> = (*row).borrow();
+            assert_eq!(cols.value, BabyBear::from_canonical_u16(value));
+            assert_eq!(cols.bits, BabyBear::from_canonical_u8(bits));
+        }
+        assert_eq!(range_row(0xffff, 16), NUM_ROWS - 2);
+    }
+}
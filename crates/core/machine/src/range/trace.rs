@@ -0,0 +1,68 @@
+use std::borrow::BorrowMut;
+
+use hashbrown::HashMap;
+use p3_field::Field;
+use p3_matrix::dense::RowMajorMatrix;
+use sp1_core_executor::{ByteOpcode, ExecutionRecord, Program};
+use sp1_stark::air::MachineAir;
+
+use super::{
+    columns::{RangeMultCols, NUM_RANGE_MULT_COLS, NUM_RANGE_PREPROCESSED_COLS},
+    range_row, RangeChip, NUM_ROWS,
+};
+
+impl<F: Field> MachineAir<F> for RangeChip<F> {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Range".to_string()
+    }
+
+    fn preprocessed_width(&self) -> usize {
+        NUM_RANGE_PREPROCESSED_COLS
+    }
+
+    fn generate_preprocessed_trace(&self, _program: &Self::Program) -> Option<RowMajorMatrix<F>> {
+        Some(Self::trace())
+    }
+
+    fn generate_dependencies(&self, _input: &ExecutionRecord, _output: &mut ExecutionRecord) {
+        // Do nothing since this chip has no dependencies.
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut trace = RowMajorMatrix::new(
+            vec![F::zero(); NUM_RANGE_MULT_COLS * NUM_ROWS],
+            NUM_RANGE_MULT_COLS,
+        );
+
+        // The range checks are recorded as byte lookups with the `Range` opcode.
+        let shard = input.public_values.execution_shard;
+        for (lookup, mult) in input.byte_lookups.get(&shard).unwrap_or(&HashMap::new()).iter() {
+            if lookup.opcode != ByteOpcode::Range {
+                continue;
+            }
+            let cols: &mut RangeMultCols<F> =
+                trace.row_mut(range_row(lookup.a1, lookup.b)).borrow_mut();
+            cols.multiplicities[lookup.channel as usize] += F::from_canonical_usize(*mult);
+            cols.shard = F::from_canonical_u32(shard);
+        }
+
+        trace
+    }
+
+    fn included(&self, _shard: &Self::Record) -> bool {
+        true
+    }
+
+    fn num_real_rows(&self, _shard: &Self::Record) -> Option<usize> {
+        // Every row of the range table is real, whether or not it is looked up.
+        Some(NUM_ROWS)
+    }
+}
@@ -0,0 +1,162 @@
+use hashbrown::{HashMap, HashSet};
+use p3_field::PrimeField32;
+use sp1_primitives::consts::{AddressSpace, Rv32};
+use sp1_stark::{
+    air::{MachineAir, PUBLIC_VALUES_LAYOUT},
+    Chip, StarkGenericConfig, StarkMachine,
+};
+use strum::IntoEnumIterator;
+
+use super::{RiscvAir, RiscvAirDiscriminants};
+
+/// The number of bits of the chip set identifier packed into each field element.
+pub(crate) const CHIP_SET_ID_BITS_PER_ELEMENT: usize = 16;
+
+/// A builder for a RISC-V machine with a custom set of precompile chips.
+///
+/// Guests that never call some precompiles can exclude their chips from the machine, which shrinks
+/// the setup time and the width of the recursive verifier of its shards. A program that calls an
+/// excluded precompile cannot be proven with the resulting machine.
+///
+/// Excluding chips changes the [`StarkMachine::chip_set_id`] of the machine, which is observed by
+/// the prover and the verifier of every shard, so proofs only verify against a machine built with
+/// the same set of chips.
+#[derive(Debug, Clone)]
+pub struct RiscvMachineBuilder {
+    max_addr_bits: usize,
+    excluded: HashSet<RiscvAirDiscriminants>,
+}
+
+impl Default for RiscvMachineBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RiscvMachineBuilder {
+    /// A builder for the machine with every chip, over the full address space.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { max_addr_bits: Rv32::ADDR_BITS, excluded: HashSet::new() }
+    }
+
+    /// Only accept memory addresses below `1 << max_addr_bits`, to match
+    /// [`sp1_stark::SP1CoreOpts::max_memory_addr_bits`].
+    #[must_use]
+    pub fn max_addr_bits(mut self, max_addr_bits: usize) -> Self {
+        self.max_addr_bits = max_addr_bits;
+        self
+    }
+
+    /// Excludes the chip of a precompile from the machine.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `precompile` is not a precompile, since every other chip is needed to prove any
+    /// program.
+    #[must_use]
+    pub fn exclude(mut self, precompile: RiscvAirDiscriminants) -> Self {
+        assert!(is_precompile(precompile), "{precompile:?} is not a precompile chip");
+        self.excluded.insert(precompile);
+        self
+    }
+
+    /// Excludes the chips of every precompile except the given ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of `precompiles` is not a precompile.
+    #[must_use]
+    pub fn only_precompiles(
+        mut self,
+        precompiles: impl IntoIterator<Item = RiscvAirDiscriminants>,
+    ) -> Self {
+        let included = precompiles.into_iter().collect::<HashSet<_>>();
+        if let Some(air) = included.iter().find(|air| !is_precompile(**air)) {
+            panic!("{air:?} is not a precompile chip");
+        }
+        self.excluded = RiscvAirDiscriminants::iter()
+            .filter(|air| is_precompile(*air) && !included.contains(air))
+            .collect();
+        self
+    }
+
+    /// Whether the chip of the given AIR is included in the machine.
+    #[must_use]
+    pub fn includes(&self, air: RiscvAirDiscriminants) -> bool {
+        !self.excluded.contains(&air)
+    }
+
+    /// The chips of the machine and their costs.
+    pub fn chips_and_costs<F: PrimeField32>(
+        &self,
+    ) -> (Vec<Chip<F, RiscvAir<F>>>, HashMap<RiscvAirDiscriminants, u64>) {
+        let (chips, mut costs) =
+            RiscvAir::get_chips_and_costs_with_max_addr_bits(self.max_addr_bits);
+        let chips = chips
+            .into_iter()
+            .filter(|chip| self.includes(RiscvAirDiscriminants::from(chip.air())))
+            .collect();
+        costs.retain(|air, _| self.includes(*air));
+        (chips, costs)
+    }
+
+    /// The identifier of the chip set of the machine: a bitmap of the excluded precompiles, in the
+    /// order of the sorted names of all chips, packed into field elements.
+    ///
+    /// The identifier is empty when no chip is excluded, so that the machine with every chip keeps
+    /// the same transcript as [`RiscvAir::machine`].
+    pub fn chip_set_id<F: PrimeField32>(&self) -> Vec<F> {
+        if self.excluded.is_empty() {
+            return Vec::new();
+        }
+        // Sorting by name keeps the identifier independent of the declaration order of the AIRs.
+        let mut chips = RiscvAir::<F>::chips()
+            .iter()
+            .map(|chip| (chip.name(), !self.includes(RiscvAirDiscriminants::from(chip.air()))))
+            .collect::<Vec<_>>();
+        chips.sort();
+        chips
+            .chunks(CHIP_SET_ID_BITS_PER_ELEMENT)
+            .map(|chunk| {
+                let packed = chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0u32, |acc, (i, (_, excluded))| acc | (u32::from(*excluded) << i));
+                F::from_canonical_u32(packed)
+            })
+            .collect()
+    }
+
+    /// Builds the machine.
+    pub fn machine<SC: StarkGenericConfig>(&self, config: SC) -> StarkMachine<SC, RiscvAir<SC::Val>>
+    where
+        SC::Val: PrimeField32,
+    {
+        let (chips, _) = self.chips_and_costs();
+        StarkMachine::new(config, chips, PUBLIC_VALUES_LAYOUT.num_elements)
+            .with_chip_set_id(self.chip_set_id())
+    }
+}
+
+/// Whether the AIR is a precompile, and so can be excluded from a machine.
+const fn is_precompile(air: RiscvAirDiscriminants) -> bool {
+    !matches!(
+        air,
+        RiscvAirDiscriminants::Program
+            | RiscvAirDiscriminants::Opcode
+            | RiscvAirDiscriminants::Cpu
+            | RiscvAirDiscriminants::Add
+            | RiscvAirDiscriminants::Bitwise
+            | RiscvAirDiscriminants::Mul
+            | RiscvAirDiscriminants::DivRem
+            | RiscvAirDiscriminants::Lt
+            | RiscvAirDiscriminants::ShiftLeft
+            | RiscvAirDiscriminants::ShiftRight
+            | RiscvAirDiscriminants::ByteLookup
+            | RiscvAirDiscriminants::RangeLookup
+            | RiscvAirDiscriminants::MemoryInit
+            | RiscvAirDiscriminants::MemoryFinal
+            | RiscvAirDiscriminants::ProgramMemory
+    )
+}
@@ -1,5 +1,12 @@
 use p3_baby_bear::BabyBear;
-use sp1_core_executor::{syscalls::SyscallCode, ExecutionReport, Opcode};
+use sp1_core_executor::{
+    events::{
+        BIGINT_MAX_LIMBS, BYTE_SCAN_MAX_WORDS, FP_BATCH_INV_MAX_LEN, HINT_WORDS_MAX_WORDS,
+        MSM_MAX_ROWS,
+    },
+    syscalls::SyscallCode,
+    ExecutionReport, Opcode,
+};
 
 use crate::riscv::RiscvAirDiscriminants;
 
@@ -26,7 +33,7 @@ pub trait CostEstimator {
 impl CostEstimator for ExecutionReport {
     fn estimate_area(&self) -> u64 {
         let mut total_area = 0;
-        let mut total_chips = 3;
+        let mut total_chips = 4;
         let (chips, costs) = RiscvAir::<BabyBear>::get_chips_and_costs();
 
         let cpu_events = self.total_instruction_count();
@@ -41,6 +48,11 @@ impl CostEstimator for ExecutionReport {
         total_area += (sha_compress_events as u64) * costs[&RiscvAirDiscriminants::Sha256Compress];
         total_chips += 1;
 
+        let ripemd160_compress_events = self.syscall_counts[SyscallCode::RIPEMD160_COMPRESS];
+        total_area +=
+            (ripemd160_compress_events as u64) * costs[&RiscvAirDiscriminants::Ripemd160Compress];
+        total_chips += 1;
+
         let ed_add_events = self.syscall_counts[SyscallCode::ED_ADD];
         total_area += (ed_add_events as u64) * costs[&RiscvAirDiscriminants::Ed25519Add];
         total_chips += 1;
@@ -50,6 +62,19 @@ impl CostEstimator for ExecutionReport {
             (ed_decompress_events as u64) * costs[&RiscvAirDiscriminants::Ed25519Decompress];
         total_chips += 1;
 
+        let ed_scalar_mul_events = self.syscall_counts[SyscallCode::ED_SCALAR_MUL];
+        total_area +=
+            (ed_scalar_mul_events as u64) * costs[&RiscvAirDiscriminants::Ed25519ScalarMul];
+        total_chips += 1;
+
+        // The number of rows of an MSM window depends on its number of points, so assume the
+        // maximum.
+        let ed_msm_events = self.syscall_counts[SyscallCode::ED_MSM];
+        total_area += (ed_msm_events as u64)
+            * MSM_MAX_ROWS as u64
+            * costs[&RiscvAirDiscriminants::Ed25519Msm];
+        total_chips += 1;
+
         let k256_decompress_events = self.syscall_counts[SyscallCode::SECP256K1_DECOMPRESS];
         total_area +=
             (k256_decompress_events as u64) * costs[&RiscvAirDiscriminants::K256Decompress];
@@ -64,6 +89,12 @@ impl CostEstimator for ExecutionReport {
             (secp256k1_double_events as u64) * costs[&RiscvAirDiscriminants::Secp256k1Double];
         total_chips += 1;
 
+        let secp256k1_msm_events = self.syscall_counts[SyscallCode::SECP256K1_MSM];
+        total_area += (secp256k1_msm_events as u64)
+            * MSM_MAX_ROWS as u64
+            * costs[&RiscvAirDiscriminants::Secp256k1Msm];
+        total_chips += 1;
+
         let keccak256_permute_events = self.syscall_counts[SyscallCode::KECCAK_PERMUTE];
         total_area += (keccak256_permute_events as u64) * costs[&RiscvAirDiscriminants::KeccakP];
         total_chips += 1;
@@ -76,6 +107,12 @@ impl CostEstimator for ExecutionReport {
         total_area += (bn254_double_events as u64) * costs[&RiscvAirDiscriminants::Bn254Double];
         total_chips += 1;
 
+        let bn254_msm_events = self.syscall_counts[SyscallCode::BN254_MSM];
+        total_area += (bn254_msm_events as u64)
+            * MSM_MAX_ROWS as u64
+            * costs[&RiscvAirDiscriminants::Bn254Msm];
+        total_chips += 1;
+
         let bls12381_add_events = self.syscall_counts[SyscallCode::BLS12381_ADD];
         total_area += (bls12381_add_events as u64) * costs[&RiscvAirDiscriminants::Bls12381Add];
         total_chips += 1;
@@ -89,6 +126,39 @@ impl CostEstimator for ExecutionReport {
         total_area += (uint256_mul_events as u64) * costs[&RiscvAirDiscriminants::Uint256Mul];
         total_chips += 1;
 
+        // The number of rows of a bigint operation depends on its width, so assume the maximum.
+        let bigint_addsub_events = self.syscall_counts[SyscallCode::BIGINT_ADD]
+            + self.syscall_counts[SyscallCode::BIGINT_SUB];
+        let bigint_mul_events = self.syscall_counts[SyscallCode::BIGINT_MUL];
+        let bigint_rows = (bigint_addsub_events as u64) * BIGINT_MAX_LIMBS as u64
+            + (bigint_mul_events as u64) * (BIGINT_MAX_LIMBS * BIGINT_MAX_LIMBS) as u64;
+        total_area += bigint_rows * costs[&RiscvAirDiscriminants::BigInt];
+        total_chips += 1;
+
+        // The number of rows of a byte scan depends on its length, so assume the maximum.
+        let byte_scan_events = self.syscall_counts[SyscallCode::BYTE_SCAN_FIND]
+            + self.syscall_counts[SyscallCode::BYTE_SCAN_COPY]
+            + self.syscall_counts[SyscallCode::BYTE_SCAN_HEX_DECODE];
+        total_area += (byte_scan_events as u64)
+            * BYTE_SCAN_MAX_WORDS as u64
+            * costs[&RiscvAirDiscriminants::ByteScan];
+        total_chips += 1;
+
+        // Likewise for the number of rows of a hint words operation.
+        let hint_words_events = self.syscall_counts[SyscallCode::HINT_READ_WORDS];
+        total_area += (hint_words_events as u64)
+            * HINT_WORDS_MAX_WORDS as u64
+            * costs[&RiscvAirDiscriminants::HintWords];
+        total_chips += 1;
+
+        let dilithium_events = self.syscall_counts[SyscallCode::DILITHIUM_ADD];
+        total_area += (dilithium_events as u64) * costs[&RiscvAirDiscriminants::Dilithium];
+        total_chips += 1;
+
+        let wide_alu_events = self.syscall_counts[SyscallCode::WIDE_ALU_ADD];
+        total_area += (wide_alu_events as u64) * costs[&RiscvAirDiscriminants::WideAlu];
+        total_chips += 1;
+
         let bls12381_fp_events = self.syscall_counts[SyscallCode::BLS12381_FP_ADD]
             + self.syscall_counts[SyscallCode::BLS12381_FP_SUB]
             + self.syscall_counts[SyscallCode::BLS12381_FP_MUL];
@@ -122,6 +192,19 @@ impl CostEstimator for ExecutionReport {
         total_area += (bn254_fp2_mul_events as u64) * costs[&RiscvAirDiscriminants::Bn254Fp2Mul];
         total_chips += 1;
 
+        // The number of rows of a batch inversion depends on its length, so assume the maximum.
+        let bls12381_fp_batch_inv_events = self.syscall_counts[SyscallCode::BLS12381_FP_BATCH_INV];
+        total_area += (bls12381_fp_batch_inv_events as u64)
+            * FP_BATCH_INV_MAX_LEN as u64
+            * costs[&RiscvAirDiscriminants::Bls12381FpBatchInv];
+        total_chips += 1;
+
+        let bn254_fp_batch_inv_events = self.syscall_counts[SyscallCode::BN254_FP_BATCH_INV];
+        total_area += (bn254_fp_batch_inv_events as u64)
+            * FP_BATCH_INV_MAX_LEN as u64
+            * costs[&RiscvAirDiscriminants::Bn254FpBatchInv];
+        total_chips += 1;
+
         let bls12381_decompress_events = self.syscall_counts[SyscallCode::BLS12381_DECOMPRESS];
         total_area +=
             (bls12381_decompress_events as u64) * costs[&RiscvAirDiscriminants::Bls12381Decompress];
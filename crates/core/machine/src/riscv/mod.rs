@@ -1,15 +1,22 @@
+mod builder;
 pub mod cost;
 
+pub use builder::RiscvMachineBuilder;
+
 use crate::{
     memory::{MemoryChipType, MemoryProgramChip},
-    syscall::precompiles::fptower::{Fp2AddSubAssignChip, Fp2MulAssignChip, FpOpChip},
+    syscall::precompiles::{
+        edwards::ED_SCALAR_MUL_NUM_ROWS,
+        fptower::{Fp2AddSubAssignChip, Fp2MulAssignChip, FpBatchInvChip, FpOpChip},
+    },
 };
 use hashbrown::HashMap;
 use p3_field::PrimeField32;
 pub use riscv_chips::*;
 use sp1_curves::weierstrass::{bls12_381::Bls12381BaseField, bn254::Bn254BaseField};
+use sp1_primitives::consts::{AddressSpace, Rv32};
 use sp1_stark::{
-    air::{MachineAir, SP1_PROOF_NUM_PV_ELTS},
+    air::{MachineAir, PUBLIC_VALUES_LAYOUT},
     Chip, StarkGenericConfig, StarkMachine,
 };
 use strum_macros::{EnumDiscriminants, EnumIter};
@@ -22,15 +29,23 @@ pub(crate) mod riscv_chips {
         bytes::ByteChip,
         cpu::CpuChip,
         memory::MemoryChip,
-        program::ProgramChip,
+        program::{OpcodeChip, ProgramChip},
+        range::RangeChip,
         syscall::precompiles::{
-            edwards::{EdAddAssignChip, EdDecompressChip},
-            keccak256::KeccakPermuteChip,
+            bigint::BigIntChip,
+            byte_scan::ByteScanChip,
+            dilithium::DilithiumOpChip,
+            edwards::{EdAddAssignChip, EdDecompressChip, EdScalarMulChip},
+            hint_words::HintWordsChip,
+            keccak256::{KeccakPermuteChip, KeccakPermuteMemoChip},
+            msm::{EdMsmChip, WeierstrassMsmChip},
+            ripemd160::Ripemd160CompressChip,
             sha256::{ShaCompressChip, ShaExtendChip},
             uint256::Uint256MulChip,
             weierstrass::{
                 WeierstrassAddAssignChip, WeierstrassDecompressChip, WeierstrassDoubleAssignChip,
             },
+            wide_alu::WideAluChip,
         },
     };
     pub use sp1_curves::{
@@ -52,6 +67,9 @@ pub(crate) mod riscv_chips {
 pub enum RiscvAir<F: PrimeField32> {
     /// An AIR that containts a preprocessed program table and a lookup for the instructions.
     Program(ProgramChip),
+    /// An AIR that contains a preprocessed table of the selectors of each opcode, looked up by
+    /// the CPU.
+    Opcode(OpcodeChip),
     /// An AIR for the RISC-V CPU. Each row represents a cpu cycle.
     Cpu(CpuChip),
     /// An AIR for the RISC-V Add and SUB instruction.
@@ -70,6 +88,8 @@ pub enum RiscvAir<F: PrimeField32> {
     ShiftRight(ShiftRightChip),
     /// A lookup table for byte operations.
     ByteLookup(ByteChip<F>),
+    /// A lookup table for range checks.
+    RangeLookup(RangeChip<F>),
     /// A table for initializing the memory state.
     MemoryInit(MemoryChip),
     /// A table for finalizing the memory state.
@@ -80,28 +100,50 @@ pub enum RiscvAir<F: PrimeField32> {
     Sha256Extend(ShaExtendChip),
     /// A precompile for sha256 compress.
     Sha256Compress(ShaCompressChip),
+    /// A precompile for ripemd160 compress.
+    Ripemd160Compress(Ripemd160CompressChip),
     /// A precompile for addition on the Elliptic curve ed25519.
     Ed25519Add(EdAddAssignChip<EdwardsCurve<Ed25519Parameters>>),
     /// A precompile for decompressing a point on the Edwards curve ed25519.
     Ed25519Decompress(EdDecompressChip<Ed25519Parameters>),
+    /// A precompile for scalar multiplication on the Elliptic curve ed25519.
+    Ed25519ScalarMul(EdScalarMulChip<EdwardsCurve<Ed25519Parameters>>),
+    /// A precompile for a window of a multi-scalar multiplication on the Elliptic curve ed25519.
+    Ed25519Msm(EdMsmChip<EdwardsCurve<Ed25519Parameters>>),
     /// A precompile for decompressing a point on the K256 curve.
     K256Decompress(WeierstrassDecompressChip<SwCurve<Secp256k1Parameters>>),
     /// A precompile for addition on the Elliptic curve secp256k1.
     Secp256k1Add(WeierstrassAddAssignChip<SwCurve<Secp256k1Parameters>>),
     /// A precompile for doubling a point on the Elliptic curve secp256k1.
     Secp256k1Double(WeierstrassDoubleAssignChip<SwCurve<Secp256k1Parameters>>),
+    /// A precompile for a window of a multi-scalar multiplication on the Elliptic curve secp256k1.
+    Secp256k1Msm(WeierstrassMsmChip<SwCurve<Secp256k1Parameters>>),
     /// A precompile for the Keccak permutation.
     KeccakP(KeccakPermuteChip),
+    /// A precompile for a Keccak permutation reusing the result of an earlier one.
+    KeccakPMemo(KeccakPermuteMemoChip),
     /// A precompile for addition on the Elliptic curve bn254.
     Bn254Add(WeierstrassAddAssignChip<SwCurve<Bn254Parameters>>),
     /// A precompile for doubling a point on the Elliptic curve bn254.
     Bn254Double(WeierstrassDoubleAssignChip<SwCurve<Bn254Parameters>>),
+    /// A precompile for a window of a multi-scalar multiplication on the Elliptic curve bn254.
+    Bn254Msm(WeierstrassMsmChip<SwCurve<Bn254Parameters>>),
     /// A precompile for addition on the Elliptic curve bls12_381.
     Bls12381Add(WeierstrassAddAssignChip<SwCurve<Bls12381Parameters>>),
     /// A precompile for doubling a point on the Elliptic curve bls12_381.
     Bls12381Double(WeierstrassDoubleAssignChip<SwCurve<Bls12381Parameters>>),
     /// A precompile for uint256 mul.
     Uint256Mul(Uint256MulChip),
+    /// A precompile for variable-width bigint add, sub and mul.
+    BigInt(BigIntChip),
+    /// A precompile for byte scan find, copy and hex decode.
+    ByteScan(ByteScanChip),
+    /// A precompile for writing hinted words to memory.
+    HintWords(HintWordsChip),
+    /// A precompile for Dilithium add, sub, mul and butterfly.
+    Dilithium(DilithiumOpChip),
+    /// A precompile for 64-bit add, sub, mul and comparisons.
+    WideAlu(WideAluChip),
     /// A precompile for decompressing a point on the BLS12-381 curve.
     Bls12381Decompress(WeierstrassDecompressChip<SwCurve<Bls12381Parameters>>),
     /// A precompile for BLS12-381 fp operation.
@@ -116,13 +158,28 @@ pub enum RiscvAir<F: PrimeField32> {
     Bn254Fp2Mul(Fp2MulAssignChip<Bn254BaseField>),
     /// A precompile for BN-254 fp2 addition/subtraction.
     Bn254Fp2AddSub(Fp2AddSubAssignChip<Bn254BaseField>),
+    /// A precompile for BLS12-381 fp batch inversion.
+    Bls12381FpBatchInv(FpBatchInvChip<Bls12381BaseField>),
+    /// A precompile for BN-254 fp batch inversion.
+    Bn254FpBatchInv(FpBatchInvChip<Bn254BaseField>),
 }
 
 impl<F: PrimeField32> RiscvAir<F> {
     #[instrument("construct RiscvAir machine", level = "debug", skip_all)]
     pub fn machine<SC: StarkGenericConfig<Val = F>>(config: SC) -> StarkMachine<SC, Self> {
         let chips = Self::chips();
-        StarkMachine::new(config, chips, SP1_PROOF_NUM_PV_ELTS)
+        StarkMachine::new(config, chips, PUBLIC_VALUES_LAYOUT.num_elements)
+    }
+
+    /// Construct a machine whose memory chips only accept addresses below `1 << max_addr_bits`,
+    /// to match [`sp1_stark::SP1CoreOpts::max_memory_addr_bits`].
+    #[instrument("construct RiscvAir machine", level = "debug", skip_all)]
+    pub fn machine_with_max_addr_bits<SC: StarkGenericConfig<Val = F>>(
+        config: SC,
+        max_addr_bits: usize,
+    ) -> StarkMachine<SC, Self> {
+        let (chips, _) = Self::get_chips_and_costs_with_max_addr_bits(max_addr_bits);
+        StarkMachine::new(config, chips, PUBLIC_VALUES_LAYOUT.num_elements)
     }
 
     /// Get all the different RISC-V AIRs.
@@ -139,6 +196,14 @@ impl<F: PrimeField32> RiscvAir<F> {
 
     /// Get all the different RISC-V AIRs.
     pub fn get_chips_and_costs() -> (Vec<Chip<F, Self>>, HashMap<RiscvAirDiscriminants, u64>) {
+        Self::get_chips_and_costs_with_max_addr_bits(Rv32::ADDR_BITS)
+    }
+
+    /// Get all the different RISC-V AIRs, with memory chips over an address space of
+    /// `max_addr_bits` bits.
+    pub fn get_chips_and_costs_with_max_addr_bits(
+        max_addr_bits: usize,
+    ) -> (Vec<Chip<F, Self>>, HashMap<RiscvAirDiscriminants, u64>) {
         let mut costs: HashMap<RiscvAirDiscriminants, u64> = HashMap::new();
 
         // The order of the chips is used to determine the order of trace generation.
@@ -150,6 +215,9 @@ impl<F: PrimeField32> RiscvAir<F> {
         let program = Chip::new(RiscvAir::Program(ProgramChip::default()));
         chips.push(program);
 
+        let opcode = Chip::new(RiscvAir::Opcode(OpcodeChip::default()));
+        chips.push(opcode);
+
         let sha_extend = Chip::new(RiscvAir::Sha256Extend(ShaExtendChip::default()));
         costs.insert(RiscvAirDiscriminants::Sha256Extend, 48 * sha_extend.cost());
         chips.push(sha_extend);
@@ -158,6 +226,11 @@ impl<F: PrimeField32> RiscvAir<F> {
         costs.insert(RiscvAirDiscriminants::Sha256Compress, 80 * sha_compress.cost());
         chips.push(sha_compress);
 
+        let ripemd160_compress =
+            Chip::new(RiscvAir::Ripemd160Compress(Ripemd160CompressChip::default()));
+        costs.insert(RiscvAirDiscriminants::Ripemd160Compress, 90 * ripemd160_compress.cost());
+        chips.push(ripemd160_compress);
+
         let ed_add_assign = Chip::new(RiscvAir::Ed25519Add(EdAddAssignChip::<
             EdwardsCurve<Ed25519Parameters>,
         >::new()));
@@ -170,6 +243,20 @@ impl<F: PrimeField32> RiscvAir<F> {
         costs.insert(RiscvAirDiscriminants::Ed25519Decompress, ed_decompress.cost());
         chips.push(ed_decompress);
 
+        let ed_scalar_mul = Chip::new(RiscvAir::Ed25519ScalarMul(EdScalarMulChip::<
+            EdwardsCurve<Ed25519Parameters>,
+        >::new()));
+        costs.insert(
+            RiscvAirDiscriminants::Ed25519ScalarMul,
+            ED_SCALAR_MUL_NUM_ROWS as u64 * ed_scalar_mul.cost(),
+        );
+        chips.push(ed_scalar_mul);
+
+        let ed_msm =
+            Chip::new(RiscvAir::Ed25519Msm(EdMsmChip::<EdwardsCurve<Ed25519Parameters>>::new()));
+        costs.insert(RiscvAirDiscriminants::Ed25519Msm, ed_msm.cost());
+        chips.push(ed_msm);
+
         let k256_decompress = Chip::new(RiscvAir::K256Decompress(WeierstrassDecompressChip::<
             SwCurve<Secp256k1Parameters>,
         >::with_lsb_rule()));
@@ -189,10 +276,21 @@ impl<F: PrimeField32> RiscvAir<F> {
         costs.insert(RiscvAirDiscriminants::Secp256k1Double, secp256k1_double_assign.cost());
         chips.push(secp256k1_double_assign);
 
+        let secp256k1_msm = Chip::new(RiscvAir::Secp256k1Msm(WeierstrassMsmChip::<
+            SwCurve<Secp256k1Parameters>,
+        >::new()));
+        costs.insert(RiscvAirDiscriminants::Secp256k1Msm, secp256k1_msm.cost());
+        chips.push(secp256k1_msm);
+
         let keccak_permute = Chip::new(RiscvAir::KeccakP(KeccakPermuteChip::new()));
         costs.insert(RiscvAirDiscriminants::KeccakP, 24 * keccak_permute.cost());
         chips.push(keccak_permute);
 
+        let keccak_permute_memo = Chip::new(RiscvAir::KeccakPMemo(KeccakPermuteMemoChip::new()))
+            .with_boolean_multiplicities();
+        costs.insert(RiscvAirDiscriminants::KeccakPMemo, keccak_permute_memo.cost());
+        chips.push(keccak_permute_memo);
+
         let bn254_add_assign = Chip::new(RiscvAir::Bn254Add(WeierstrassAddAssignChip::<
             SwCurve<Bn254Parameters>,
         >::new()));
@@ -205,6 +303,11 @@ impl<F: PrimeField32> RiscvAir<F> {
         costs.insert(RiscvAirDiscriminants::Bn254Double, bn254_double_assign.cost());
         chips.push(bn254_double_assign);
 
+        let bn254_msm =
+            Chip::new(RiscvAir::Bn254Msm(WeierstrassMsmChip::<SwCurve<Bn254Parameters>>::new()));
+        costs.insert(RiscvAirDiscriminants::Bn254Msm, bn254_msm.cost());
+        chips.push(bn254_msm);
+
         let bls12381_add = Chip::new(RiscvAir::Bls12381Add(WeierstrassAddAssignChip::<
             SwCurve<Bls12381Parameters>,
         >::new()));
@@ -221,6 +324,26 @@ impl<F: PrimeField32> RiscvAir<F> {
         costs.insert(RiscvAirDiscriminants::Uint256Mul, uint256_mul.cost());
         chips.push(uint256_mul);
 
+        let bigint = Chip::new(RiscvAir::BigInt(BigIntChip::default()));
+        costs.insert(RiscvAirDiscriminants::BigInt, bigint.cost());
+        chips.push(bigint);
+
+        let byte_scan = Chip::new(RiscvAir::ByteScan(ByteScanChip::default()));
+        costs.insert(RiscvAirDiscriminants::ByteScan, byte_scan.cost());
+        chips.push(byte_scan);
+
+        let hint_words = Chip::new(RiscvAir::HintWords(HintWordsChip::default()));
+        costs.insert(RiscvAirDiscriminants::HintWords, hint_words.cost());
+        chips.push(hint_words);
+
+        let dilithium = Chip::new(RiscvAir::Dilithium(DilithiumOpChip));
+        costs.insert(RiscvAirDiscriminants::Dilithium, dilithium.cost());
+        chips.push(dilithium);
+
+        let wide_alu = Chip::new(RiscvAir::WideAlu(WideAluChip::default()));
+        costs.insert(RiscvAirDiscriminants::WideAlu, wide_alu.cost());
+        chips.push(wide_alu);
+
         let bls12381_fp = Chip::new(RiscvAir::Bls12381Fp(FpOpChip::<Bls12381BaseField>::new()));
         costs.insert(RiscvAirDiscriminants::Bls12381Fp, bls12381_fp.cost());
         chips.push(bls12381_fp);
@@ -249,6 +372,16 @@ impl<F: PrimeField32> RiscvAir<F> {
         costs.insert(RiscvAirDiscriminants::Bn254Fp2Mul, bn254_fp2_mul.cost());
         chips.push(bn254_fp2_mul);
 
+        let bls12381_fp_batch_inv =
+            Chip::new(RiscvAir::Bls12381FpBatchInv(FpBatchInvChip::<Bls12381BaseField>::new()));
+        costs.insert(RiscvAirDiscriminants::Bls12381FpBatchInv, bls12381_fp_batch_inv.cost());
+        chips.push(bls12381_fp_batch_inv);
+
+        let bn254_fp_batch_inv =
+            Chip::new(RiscvAir::Bn254FpBatchInv(FpBatchInvChip::<Bn254BaseField>::new()));
+        costs.insert(RiscvAirDiscriminants::Bn254FpBatchInv, bn254_fp_batch_inv.cost());
+        chips.push(bn254_fp_batch_inv);
+
         let bls12381_decompress =
             Chip::new(RiscvAir::Bls12381Decompress(WeierstrassDecompressChip::<
                 SwCurve<Bls12381Parameters>,
@@ -284,13 +417,17 @@ impl<F: PrimeField32> RiscvAir<F> {
         costs.insert(RiscvAirDiscriminants::Lt, lt.cost());
         chips.push(lt);
 
-        let memory_init =
-            Chip::new(RiscvAir::MemoryInit(MemoryChip::new(MemoryChipType::Initialize)));
+        let memory_init = Chip::new(RiscvAir::MemoryInit(MemoryChip::with_max_addr_bits(
+            MemoryChipType::Initialize,
+            max_addr_bits,
+        )));
         costs.insert(RiscvAirDiscriminants::MemoryInit, memory_init.cost());
         chips.push(memory_init);
 
-        let memory_finalize =
-            Chip::new(RiscvAir::MemoryFinal(MemoryChip::new(MemoryChipType::Finalize)));
+        let memory_finalize = Chip::new(RiscvAir::MemoryFinal(MemoryChip::with_max_addr_bits(
+            MemoryChipType::Finalize,
+            max_addr_bits,
+        )));
         costs.insert(RiscvAirDiscriminants::MemoryFinal, memory_finalize.cost());
         chips.push(memory_finalize);
 
@@ -302,6 +439,10 @@ impl<F: PrimeField32> RiscvAir<F> {
         costs.insert(RiscvAirDiscriminants::ByteLookup, byte.cost());
         chips.push(byte);
 
+        let range = Chip::new(RiscvAir::RangeLookup(RangeChip::default()));
+        costs.insert(RiscvAirDiscriminants::RangeLookup, range.cost());
+        chips.push(range);
+
         (chips, costs)
     }
 }
@@ -326,20 +467,34 @@ pub mod tests {
 
     use crate::{
         io::SP1Stdin,
-        riscv::RiscvAir,
+        riscv::{builder::CHIP_SET_ID_BITS_PER_ELEMENT, RiscvAir, RiscvMachineBuilder},
         utils,
-        utils::{prove, run_test, setup_logger},
+        utils::{prove, prove_with_context, run_test, setup_logger},
     };
 
+    use p3_air::BaseAir;
+    use p3_baby_bear::BabyBear;
+    use p3_commit::{PolynomialSpace, TwoAdicMultiplicativeCoset};
+    use p3_field::{AbstractExtensionField, AbstractField, PrimeField32};
+    use p3_matrix::dense::RowMajorMatrix;
+
     use sp1_core_executor::{
         programs::tests::{
-            fibonacci_program, simple_memory_program, simple_program, ssz_withdrawals_program,
+            atomic_memory_program, fibonacci_program, simple_memory_program, simple_program,
+            ssz_withdrawals_program,
         },
-        Instruction, Opcode, Program,
+        Instruction, Opcode, Program, SP1Context,
     };
     use sp1_stark::{
-        baby_bear_poseidon2::BabyBearPoseidon2, CpuProver, SP1CoreOpts, StarkProvingKey,
-        StarkVerifyingKey,
+        air::{MachineAir, SP1_PROOF_NUM_PV_ELTS},
+        baby_bear_blake3::BabyBearBlake3,
+        baby_bear_keccak::BabyBearKeccak,
+        baby_bear_poseidon2::BabyBearPoseidon2,
+        batched_quotient_values, quotient_values,
+        verifier::test_utils::{corrupt_shard_proof, shard_proof_corruptions},
+        CpuProver, MachineProver, MachineVerificationError, MachineVerifier, PackedChallenge,
+        QuotientChipData, SP1CoreOpts, ShardInvariant, StarkGenericConfig, StarkProvingKey,
+        StarkVerifyingKey, VerificationError,
     };
 
     #[test]
@@ -485,6 +640,313 @@ pub mod tests {
         prove::<_, CpuProver<_, _>>(program, &stdin, BabyBearPoseidon2::new(), opts).unwrap();
     }
 
+    #[test]
+    fn test_fibonacci_prove_zk() {
+        setup_logger();
+        let program = fibonacci_program();
+        let stdin = SP1Stdin::new();
+        let (proof, _, _) = prove::<_, CpuProver<_, _>>(
+            program.clone(),
+            &stdin,
+            BabyBearPoseidon2::zk(),
+            SP1CoreOpts::default(),
+        )
+        .unwrap();
+        assert!(proof.shard_proofs.iter().all(|shard_proof| shard_proof.masking.is_some()));
+
+        let machine = RiscvAir::machine(BabyBearPoseidon2::zk());
+        let (_, vk) = machine.setup(&program);
+        let mut challenger = machine.config().challenger();
+        machine.verify(&vk, &proof, &mut challenger).unwrap();
+    }
+
+    #[test]
+    fn test_fibonacci_prove_blake3() {
+        setup_logger();
+        let program = fibonacci_program();
+        let (proof, _, _) = prove::<_, CpuProver<_, _>>(
+            program.clone(),
+            &SP1Stdin::new(),
+            BabyBearBlake3::new(),
+            SP1CoreOpts::default(),
+        )
+        .unwrap();
+
+        let machine = RiscvAir::machine(BabyBearBlake3::new());
+        let (_, vk) = machine.setup(&program);
+        let mut challenger = machine.config().challenger();
+        machine.verify(&vk, &proof, &mut challenger).unwrap();
+    }
+
+    #[test]
+    fn test_fibonacci_prove_keccak() {
+        setup_logger();
+        let program = fibonacci_program();
+        let (proof, _, _) = prove::<_, CpuProver<_, _>>(
+            program.clone(),
+            &SP1Stdin::new(),
+            BabyBearKeccak::new(),
+            SP1CoreOpts::default(),
+        )
+        .unwrap();
+
+        let machine = RiscvAir::machine(BabyBearKeccak::new());
+        let (_, vk) = machine.setup(&program);
+        let mut challenger = machine.config().challenger();
+        machine.verify(&vk, &proof, &mut challenger).unwrap();
+    }
+
+    #[test]
+    fn test_proof_shape_mismatch() {
+        setup_logger();
+        let program = simple_program();
+        let (proof, _, _) = prove::<_, CpuProver<_, _>>(
+            program.clone(),
+            &SP1Stdin::new(),
+            BabyBearPoseidon2::new(),
+            SP1CoreOpts::default(),
+        )
+        .unwrap();
+
+        let machine = RiscvAir::machine(BabyBearPoseidon2::new());
+        let (_, vk) = machine.setup(&program);
+        let shape = proof.shard_proofs[0].shape();
+        assert!(machine.check_shape(&vk, &shape).is_ok());
+
+        // The verifying key of a larger program commits to a taller program table.
+        let (_, other_vk) = machine.setup(&ssz_withdrawals_program());
+        let error = other_vk.accepts(&shape).unwrap_err();
+        assert!(error.log_degree_mismatches.iter().any(|(name, _, _)| name == "Program"));
+
+        let mut challenger = machine.config().challenger();
+        let result = machine.verify(&other_vk, &proof, &mut challenger);
+        assert!(matches!(result, Err(MachineVerificationError::IncompatibleShape(0, _))));
+    }
+
+    #[test]
+    fn test_chip_presence() {
+        setup_logger();
+        let program = simple_program();
+        let (proof, _, _) = prove::<_, CpuProver<_, _>>(
+            program.clone(),
+            &SP1Stdin::new(),
+            BabyBearPoseidon2::new(),
+            SP1CoreOpts::default(),
+        )
+        .unwrap();
+
+        // Chips without events are omitted from the proof.
+        let machine = RiscvAir::machine(BabyBearPoseidon2::new());
+        let shard_proof = &proof.shard_proofs[0];
+        let chip_presence = machine.chip_presence(&shard_proof.chip_ordering);
+        assert!(chip_presence.iter().any(|present| !present));
+        assert_eq!(
+            chip_presence.iter().filter(|present| **present).count(),
+            shard_proof.opened_values.chips.len()
+        );
+
+        // A chip ordering that assigns the same opening to two chips is rejected.
+        let (_, vk) = machine.setup(&program);
+        let mut tampered = proof.clone();
+        let program_index = tampered.shard_proofs[0].chip_ordering["Program"];
+        tampered.shard_proofs[0].chip_ordering.insert("CPU".to_string(), program_index);
+        let mut challenger = machine.config().challenger();
+        let result = machine.verify(&vk, &tampered, &mut challenger);
+        assert!(matches!(
+            result,
+            Err(MachineVerificationError::InvalidShardProof(
+                VerificationError::InvalidChipOrdering
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_corrupted_proofs_are_rejected() {
+        setup_logger();
+        let program = simple_program();
+        let (proof, _, _) = prove::<_, CpuProver<_, _>>(
+            program.clone(),
+            &SP1Stdin::new(),
+            BabyBearPoseidon2::new(),
+            SP1CoreOpts::default(),
+        )
+        .unwrap();
+
+        let machine = RiscvAir::machine(BabyBearPoseidon2::new());
+        let (_, vk) = machine.setup(&program);
+        let corruptions = shard_proof_corruptions(&proof.shard_proofs[0]);
+        assert!(!corruptions.is_empty());
+        for corruption in corruptions {
+            let mut corrupted = proof.clone();
+            corrupt_shard_proof(&mut corrupted.shard_proofs[0], &corruption);
+            let mut challenger = machine.config().challenger();
+            let result = machine.verify(&vk, &corrupted, &mut challenger);
+            assert!(result.is_err(), "proof with corrupted {corruption} was accepted");
+        }
+    }
+
+    #[test]
+    fn test_cpu_shard_invariants_are_enforced() {
+        setup_logger();
+        let program = simple_program();
+        let (proof, _, _) = prove::<_, CpuProver<_, _>>(
+            program.clone(),
+            &SP1Stdin::new(),
+            BabyBearPoseidon2::new(),
+            SP1CoreOpts::default(),
+        )
+        .unwrap();
+        let machine = RiscvAir::machine(BabyBearPoseidon2::new());
+        let (_, vk) = machine.setup(&program);
+        let log_degree_cpu = proof.shard_proofs[0].log_degree_cpu();
+        MachineVerifier::new(&machine, log_degree_cpu)
+            .verify_full_proof(&vk, &proof.shard_proofs)
+            .unwrap();
+
+        // A "CPU" log degree above the maximum is rejected.
+        let result = MachineVerifier::new(&machine, log_degree_cpu - 1)
+            .verify_full_proof(&vk, &proof.shard_proofs);
+        assert!(matches!(
+            result,
+            Err(MachineVerificationError::ShardInvariantViolated(0, ShardInvariant::CpuLogDegree))
+        ));
+
+        // A first shard without a "CPU" is rejected.
+        let mut tampered = proof.shard_proofs.clone();
+        tampered[0].chip_ordering.remove("CPU");
+        let result =
+            MachineVerifier::new(&machine, log_degree_cpu).verify_full_proof(&vk, &tampered);
+        assert!(matches!(
+            result,
+            Err(MachineVerificationError::ShardInvariantViolated(
+                0,
+                ShardInvariant::CpuInFirstShard
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_batched_quotient_values() {
+        type SC = BabyBearPoseidon2;
+        type Challenge = <SC as StarkGenericConfig>::Challenge;
+
+        let log_degree = 4;
+        let trace_domain = TwoAdicMultiplicativeCoset { log_n: log_degree, shift: BabyBear::one() };
+        let quotient_domain = trace_domain.create_disjoint_domain(1 << (log_degree + 3));
+        let quotient_size = quotient_domain.size();
+
+        // The traces need not satisfy the constraints for the quotient values to agree.
+        let mut seed = 0u32;
+        let mut matrix = |width: usize| {
+            let values = (0..width * quotient_size)
+                .map(|_| {
+                    seed = seed.wrapping_mul(0x9E37_79B9).wrapping_add(0x7F4A_7C15);
+                    BabyBear::from_canonical_u32(seed >> 5)
+                })
+                .collect();
+            RowMajorMatrix::new(values, width)
+        };
+        let chips = RiscvAir::<BabyBear>::chips();
+        let chips = chips.iter().take(6).collect::<Vec<_>>();
+        assert!(chips.iter().any(|chip| chip.preprocessed_width() > 0));
+        let data = chips
+            .iter()
+            .enumerate()
+            .map(|(i, chip)| QuotientChipData {
+                chip: *chip,
+                cumulative_sum: Challenge::from_canonical_usize(i + 1),
+                preprocessed_trace_on_quotient_domain: (chip.preprocessed_width() > 0)
+                    .then(|| matrix(chip.preprocessed_width())),
+                main_trace_on_quotient_domain: matrix(chip.width()),
+                permutation_trace_on_quotient_domain: matrix(
+                    chip.permutation_width() * <Challenge as AbstractExtensionField<BabyBear>>::D,
+                ),
+            })
+            .collect::<Vec<_>>();
+        let perm_challenges = [Challenge::from_canonical_u32(3), Challenge::from_canonical_u32(5)]
+            .map(PackedChallenge::<SC>::from_f);
+        let alpha = Challenge::from_canonical_u32(7);
+        let public_values =
+            (0..SP1_PROOF_NUM_PV_ELTS as u32).map(BabyBear::from_canonical_u32).collect::<Vec<_>>();
+
+        let expected = data
+            .iter()
+            .map(|data| {
+                quotient_values(
+                    data.chip,
+                    data.cumulative_sum,
+                    trace_domain,
+                    quotient_domain,
+                    data.preprocessed_trace_on_quotient_domain.clone(),
+                    data.main_trace_on_quotient_domain.clone(),
+                    data.permutation_trace_on_quotient_domain.clone(),
+                    &perm_challenges,
+                    alpha,
+                    &public_values,
+                )
+            })
+            .collect::<Vec<_>>();
+        let batched = batched_quotient_values(
+            data,
+            trace_domain,
+            quotient_domain,
+            &perm_challenges,
+            alpha,
+            &public_values,
+        );
+        assert_eq!(batched, expected);
+    }
+
+    #[test]
+    fn test_custom_machine() {
+        setup_logger();
+        let program = simple_program();
+        let builder = RiscvMachineBuilder::new().only_precompiles([]);
+        let machine = builder.machine(BabyBearPoseidon2::new());
+        assert!(machine.chips().len() < RiscvAir::<BabyBear>::chips().len());
+        assert!(!builder.chip_set_id::<BabyBear>().is_empty());
+        assert!(RiscvMachineBuilder::new().chip_set_id::<BabyBear>().is_empty());
+
+        // The identifier flags the excluded chips in the order of their sorted names.
+        let id = builder.chip_set_id::<BabyBear>();
+        let mut names =
+            RiscvAir::<BabyBear>::chips().iter().map(|chip| chip.name()).collect::<Vec<_>>();
+        names.sort();
+        let flagged = names
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| {
+                let bits = id[i / CHIP_SET_ID_BITS_PER_ELEMENT].as_canonical_u32();
+                (bits >> (i % CHIP_SET_ID_BITS_PER_ELEMENT)) & 1 == 1
+            })
+            .map(|(_, name)| name.clone())
+            .collect::<Vec<_>>();
+        let included = machine.chips().iter().map(|chip| chip.name()).collect::<Vec<_>>();
+        let excluded =
+            names.iter().filter(|name| !included.contains(name)).cloned().collect::<Vec<_>>();
+        assert_eq!(flagged, excluded);
+
+        let prover = CpuProver::new(machine);
+        let (pk, vk) = prover.setup(&program);
+        let (proof, _, _) = prove_with_context(
+            &prover,
+            &pk,
+            program.clone(),
+            &SP1Stdin::new(),
+            SP1CoreOpts::default(),
+            SP1Context::default(),
+        )
+        .unwrap();
+        let mut challenger = prover.config().challenger();
+        prover.machine().verify(&vk, &proof, &mut challenger).unwrap();
+
+        // The proof does not verify against a machine with another set of chips.
+        let machine = RiscvAir::machine(BabyBearPoseidon2::new());
+        let (_, vk) = machine.setup(&program);
+        let mut challenger = machine.config().challenger();
+        assert!(machine.verify(&vk, &proof, &mut challenger).is_err());
+    }
+
     #[test]
     fn test_fibonacci_prove_batch() {
         setup_logger();
@@ -506,6 +968,26 @@ pub mod tests {
         run_test::<CpuProver<_, _>>(program).unwrap();
     }
 
+    #[test]
+    fn test_atomic_memory_program_prove() {
+        setup_logger();
+        let program = atomic_memory_program();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    #[ignore = "needs the ELF built by `make` in `tests/`"]
+    fn test_atomics_guest_prove() {
+        setup_logger();
+        let elf = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../../tests/atomics/elf/riscv32im-succinct-zkvm-elf"
+        ))
+        .unwrap();
+        let program = Program::from(&elf).unwrap();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
     #[test]
     fn test_ssz_withdrawal() {
         setup_logger();
@@ -0,0 +1,650 @@
+use core::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use num::{BigUint, Zero};
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{AbstractField, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::{IntoParallelRefIterator, ParallelIterator, ParallelSlice};
+use sp1_core_executor::{
+    events::{
+        BigIntEvent, ByteLookupEvent, ByteRecord, FieldOperation, BIGINT_LIMB_WORDS,
+        BIGINT_MAX_LIMBS,
+    },
+    syscalls::SyscallCode,
+    ByteOpcode, ExecutionRecord, Program,
+};
+use sp1_curves::{
+    params::{FieldParameters, Limbs, NumLimbs},
+    uint256::U256Field,
+};
+use sp1_derive::AlignedBorrow;
+use sp1_stark::{
+    air::{BaseAirBuilder, MachineAir, Polynomial, SP1AirBuilder},
+    Word,
+};
+
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::{value_as_limbs, MemoryCols, MemoryReadCols, MemoryWriteCols},
+    operations::{
+        eval_multi_row_flags, field::field_op::FieldOpCols, populate_multi_row_nonces,
+        IsZeroOperation, MultiRowFlags,
+    },
+    utils::{limbs_from_access, limbs_from_prev_access, pad_rows, words_to_bytes_le_vec},
+};
+
+pub const NUM_BIGINT_COLS: usize = size_of::<BigIntCols<u8>>();
+
+/// The number of bytes in a bigint limb.
+const LIMB_BYTES: usize = BIGINT_LIMB_WORDS * 4;
+
+type LimbCols<T> = Limbs<T, <U256Field as NumLimbs>::Limbs>;
+
+/// A set of columns to compute one limb of a bigint add, sub or mul.
+///
+/// The operands are made of `num_limbs` 256-bit limbs, and each row computes
+/// `acc + x_i * y_j + carry_in = z + 2^256 * carry_out` for mul, `x_i + y_i + carry_in = z +
+/// 2^256 * carry_out` for add and `z + y_i + carry_in = x_i + 2^256 * carry_out` for sub, where
+/// `carry_out` is passed on to the next row.
+///
+/// Add and sub use one row per limb. Mul is a schoolbook multiplication using one row per pair of
+/// limbs: the `j`-th pass over the limbs of x multiplies them by the `j`-th limb of y, and
+/// accumulates the products into the result, which is read back from memory. The memory accesses
+/// of the `j`-th pass happen at `clk + j`. The syscall is received on the first row.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct BigIntCols<T> {
+    pub is_real: T,
+    pub shard: T,
+    pub channel: T,
+    pub clk: T,
+    pub nonce: T,
+    pub z_ptr: T,
+    pub operands_ptr: T,
+
+    pub is_add: T,
+    pub is_sub: T,
+    pub is_mul: T,
+
+    /// The number of limbs of each operand, read from memory on the first row.
+    pub num_limbs: T,
+    pub num_limbs_access: MemoryReadCols<T>,
+
+    /// Whether this is the first row of a real operation.
+    pub is_first_row: T,
+    /// Whether this is the last row of a real operation.
+    pub is_last_row: T,
+
+    /// The index of the current limb of x.
+    pub i: T,
+    /// The index of the current pass over the limbs of x. Always zero for add and sub.
+    pub j: T,
+    /// The index of the current limb of y.
+    pub y_index: T,
+
+    /// Whether `i` is the index of the last limb.
+    pub is_last_limb: IsZeroOperation<T>,
+    /// Whether `j` is the index of the last pass.
+    pub is_last_pass: IsZeroOperation<T>,
+    /// Whether this is the first row of a pass.
+    pub is_pass_start: T,
+    /// Whether this is the last row of a pass, equal to `is_real * is_last_limb`.
+    pub is_pass_end: T,
+    /// Whether this row is part of the first pass.
+    pub is_first_pass: T,
+
+    /// Whether the limb of y is read on this row. For mul, it is only read at the start of a pass
+    /// and carried over to the other rows of the pass.
+    pub is_y_read: T,
+    /// Whether the high limb of the pass is written on this row, at the end of a pass for mul.
+    pub is_hi_write: T,
+    /// Whether the result limb is accumulated into its previous value, i.e. after the first pass
+    /// of a mul.
+    pub is_accumulate: T,
+
+    pub x_access: [MemoryReadCols<T>; BIGINT_LIMB_WORDS],
+    pub y_access: [MemoryReadCols<T>; BIGINT_LIMB_WORDS],
+    pub z_access: [MemoryWriteCols<T>; BIGINT_LIMB_WORDS],
+    pub hi_access: [MemoryWriteCols<T>; BIGINT_LIMB_WORDS],
+
+    /// The carry (or borrow) coming from the previous row.
+    pub carry_in: LimbCols<T>,
+
+    /// The result limb, with the outgoing carry (or borrow) in `output.carry`.
+    pub output: FieldOpCols<T, U256Field>,
+}
+
+impl<T: Copy> BigIntCols<T> {
+    /// The columns delimiting the operations of the chip.
+    pub fn multi_row_flags(&self) -> MultiRowFlags<T> {
+        MultiRowFlags {
+            is_real: self.is_real,
+            is_first_row: self.is_first_row,
+            is_last_row: self.is_last_row,
+            nonce: self.nonce,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct BigIntChip;
+
+impl BigIntChip {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl<F: PrimeField32> MachineAir<F> for BigIntChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "BigInt".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = input
+            .bigint_events
+            .par_iter()
+            .map(|event| {
+                let mut blu = Vec::new();
+                self.event_to_rows(event, &mut blu)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        pad_rows(&mut rows, Self::padding_row);
+
+        // Convert the trace to a row major matrix.
+        let mut trace =
+            RowMajorMatrix::new(rows.into_iter().flatten().collect::<Vec<_>>(), NUM_BIGINT_COLS);
+
+        // Write the nonces to the trace.
+        populate_multi_row_nonces(&mut trace, |cols: &mut BigIntCols<F>| {
+            (cols.is_first_row, &mut cols.nonce)
+        });
+
+        trace
+    }
+
+    fn generate_dependencies(&self, input: &Self::Record, output: &mut Self::Record) {
+        let chunk_size = std::cmp::max(input.bigint_events.len() / num_cpus::get(), 1);
+
+        let blu_batches = input
+            .bigint_events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<u32, HashMap<ByteLookupEvent, usize>> = HashMap::new();
+                events.iter().for_each(|event| {
+                    self.event_to_rows::<F>(event, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_sharded_byte_lookup_events(blu_batches.iter().collect_vec());
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.bigint_events.is_empty()
+    }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.bigint_events.iter().map(|event| event.num_rows()).sum())
+    }
+}
+
+/// Decodes the `i`-th limb of a list of words.
+fn limb(words: &[u32], i: usize) -> BigUint {
+    BigUint::from_bytes_le(&words_to_bytes_le_vec(
+        &words[i * BIGINT_LIMB_WORDS..(i + 1) * BIGINT_LIMB_WORDS],
+    ))
+}
+
+impl BigIntChip {
+    /// Create the rows of a bigint operation from an event.
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &BigIntEvent,
+        blu: &mut impl ByteRecord,
+    ) -> Vec<[F; NUM_BIGINT_COLS]> {
+        let n = event.num_limbs as usize;
+        let is_mul = event.op == FieldOperation::Mul;
+        let num_passes = if is_mul { n } else { 1 };
+        let modulus = U256Field::modulus();
+
+        // The result limbs of a mul, accumulated over the passes.
+        let mut z = vec![BigUint::zero(); 2 * n];
+
+        let mut rows = Vec::with_capacity(event.num_rows());
+        for j in 0..num_passes {
+            let mut carry = BigUint::zero();
+            for i in 0..n {
+                let row_index = j * n + i;
+                let mut row = [F::zero(); NUM_BIGINT_COLS];
+                let cols: &mut BigIntCols<F> = row.as_mut_slice().borrow_mut();
+
+                // Populate basic columns.
+                cols.is_real = F::one();
+                cols.shard = F::from_canonical_u32(event.shard);
+                cols.channel = F::from_canonical_u8(event.channel);
+                cols.clk = F::from_canonical_u32(event.clk);
+                cols.z_ptr = F::from_canonical_u32(event.z_ptr);
+                cols.operands_ptr = F::from_canonical_u32(event.operands_ptr);
+                cols.is_add = F::from_bool(event.op == FieldOperation::Add);
+                cols.is_sub = F::from_bool(event.op == FieldOperation::Sub);
+                cols.is_mul = F::from_bool(is_mul);
+                cols.num_limbs = F::from_canonical_u32(event.num_limbs);
+
+                // Populate the position of the row.
+                let is_first_row = row_index == 0;
+                let is_pass_end = i == n - 1;
+                cols.is_first_row = F::from_bool(is_first_row);
+                cols.is_last_row = F::from_bool(is_pass_end && j == num_passes - 1);
+                cols.i = F::from_canonical_usize(i);
+                cols.j = F::from_canonical_usize(j);
+                let y_index = if is_mul { j } else { i };
+                cols.y_index = F::from_canonical_usize(y_index);
+                cols.is_last_limb.populate_from_field_element(cols.i + F::one() - cols.num_limbs);
+                cols.is_last_pass.populate_from_field_element(cols.j + F::one() - cols.num_limbs);
+                cols.is_pass_start = F::from_bool(i == 0);
+                cols.is_pass_end = F::from_bool(is_pass_end);
+                cols.is_first_pass = F::from_bool(j == 0);
+                cols.is_y_read = F::from_bool(!is_mul || i == 0);
+                cols.is_hi_write = F::from_bool(is_mul && is_pass_end);
+                cols.is_accumulate = F::from_bool(is_mul && j > 0);
+
+                // Populate the memory accesses.
+                if is_first_row {
+                    cols.num_limbs_access.populate(
+                        event.channel,
+                        event.num_limbs_memory_record,
+                        blu,
+                    );
+                }
+                for k in 0..BIGINT_LIMB_WORDS {
+                    let w = row_index * BIGINT_LIMB_WORDS + k;
+                    cols.x_access[k].populate(event.channel, event.x_memory_records[w], blu);
+                    cols.z_access[k].populate(event.channel, event.z_memory_records[w], blu);
+                    if !is_mul {
+                        cols.y_access[k].populate(event.channel, event.y_memory_records[w], blu);
+                    } else if i == 0 {
+                        let w = j * BIGINT_LIMB_WORDS + k;
+                        cols.y_access[k].populate(event.channel, event.y_memory_records[w], blu);
+                    } else {
+                        *cols.y_access[k].value_mut() =
+                            Word::from(event.y[y_index * BIGINT_LIMB_WORDS + k]);
+                    }
+                    if is_mul && is_pass_end {
+                        let w = j * BIGINT_LIMB_WORDS + k;
+                        cols.hi_access[k].populate(event.channel, event.hi_memory_records[w], blu);
+                    }
+                }
+
+                // Populate the operation.
+                let x_i = limb(&event.x, i);
+                let y_limb = limb(&event.y, y_index);
+                cols.carry_in = U256Field::to_limbs_field::<F, F>(&carry);
+                let p_x: Polynomial<F> = U256Field::to_limbs_field::<F, F>(&x_i).into();
+                let p_y: Polynomial<F> = U256Field::to_limbs_field::<F, F>(&y_limb).into();
+                let p_carry_in: Polynomial<F> = cols.carry_in.clone().into();
+                let (p_op, p_result, result, carry_out) = match event.op {
+                    FieldOperation::Add => {
+                        let sum = &x_i + &y_limb + &carry;
+                        let result = &sum % &modulus;
+                        let p_result: Polynomial<F> =
+                            U256Field::to_limbs_field::<F, F>(&result).into();
+                        (&(&p_x + &p_y) + &p_carry_in, p_result, result, sum / &modulus)
+                    }
+                    FieldOperation::Sub => {
+                        let subtrahend = &y_limb + &carry;
+                        let borrow = BigUint::from(u8::from(x_i < subtrahend));
+                        let result = (&x_i + &modulus * &borrow - subtrahend) % &modulus;
+                        let p_z: Polynomial<F> = U256Field::to_limbs_field::<F, F>(&result).into();
+                        (&(&p_z + &p_y) + &p_carry_in, p_x, result, borrow)
+                    }
+                    FieldOperation::Mul => {
+                        let acc = if j > 0 { z[i + j].clone() } else { BigUint::zero() };
+                        let p_acc: Polynomial<F> = U256Field::to_limbs_field::<F, F>(&acc).into();
+                        let total = &acc + &x_i * &y_limb + &carry;
+                        let result = &total % &modulus;
+                        let p_result: Polynomial<F> =
+                            U256Field::to_limbs_field::<F, F>(&result).into();
+                        z[i + j] = result.clone();
+                        (
+                            &(&p_acc + &(&p_x * &p_y)) + &p_carry_in,
+                            p_result,
+                            result,
+                            total / &modulus,
+                        )
+                    }
+                    FieldOperation::Div => unreachable!(),
+                };
+                cols.output.populate_with_polynomials(
+                    blu,
+                    event.shard,
+                    event.channel,
+                    &p_op,
+                    &p_result,
+                    &result,
+                    &carry_out,
+                    &modulus,
+                );
+
+                // The carry is passed on within a pass, and becomes the high limb at its end.
+                carry = if is_pass_end { BigUint::zero() } else { carry_out.clone() };
+                if is_mul && is_pass_end {
+                    z[j + n] = carry_out;
+                }
+
+                rows.push(row);
+            }
+        }
+
+        // Send the range check on the number of limbs.
+        blu.add_byte_lookup_event(ByteLookupEvent {
+            shard: event.shard,
+            channel: event.channel,
+            opcode: ByteOpcode::LTU,
+            a1: 1,
+            a2: 0,
+            b: (event.num_limbs - 1) as u8,
+            c: BIGINT_MAX_LIMBS as u8,
+        });
+
+        rows
+    }
+
+    /// Create a padding row, which computes `0 + 0 = 0`.
+    fn padding_row<F: PrimeField32>() -> [F; NUM_BIGINT_COLS] {
+        let mut row = [F::zero(); NUM_BIGINT_COLS];
+        let cols: &mut BigIntCols<F> = row.as_mut_slice().borrow_mut();
+        let zero = Polynomial::from_coefficients(&[F::zero()]);
+        cols.output.populate_with_polynomials(
+            &mut vec![],
+            0,
+            0,
+            &zero,
+            &zero,
+            &BigUint::zero(),
+            &BigUint::zero(),
+            &U256Field::modulus(),
+        );
+        row
+    }
+}
+
+impl<F> BaseAir<F> for BigIntChip {
+    fn width(&self) -> usize {
+        NUM_BIGINT_COLS
+    }
+}
+
+impl<AB> Air<AB> for BigIntChip
+where
+    AB: SP1AirBuilder,
+    Limbs<AB::Var, <U256Field as NumLimbs>::Limbs>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &BigIntCols<AB::Var> = (*local).borrow();
+        let next: &BigIntCols<AB::Var> = (*next).borrow();
+
+        eval_multi_row_flags(builder, local.multi_row_flags(), next.multi_row_flags());
+
+        self.eval_flags(builder, local, next);
+
+        self.eval_operation(builder, local, next);
+
+        self.eval_memory(builder, local);
+
+        // Receive the arguments.
+        let syscall_id = local.is_add
+            * AB::F::from_canonical_u32(SyscallCode::BIGINT_ADD.syscall_id())
+            + local.is_sub * AB::F::from_canonical_u32(SyscallCode::BIGINT_SUB.syscall_id())
+            + local.is_mul * AB::F::from_canonical_u32(SyscallCode::BIGINT_MUL.syscall_id());
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            syscall_id,
+            local.z_ptr,
+            local.operands_ptr,
+            local.is_first_row,
+        );
+    }
+}
+
+impl BigIntChip {
+    /// Constrains the flags and counters that track the position of a row within an operation.
+    fn eval_flags<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &BigIntCols<AB::Var>,
+        next: &BigIntCols<AB::Var>,
+    ) {
+        builder.assert_bool(local.is_real);
+        builder.assert_bool(local.is_add);
+        builder.assert_bool(local.is_sub);
+        builder.assert_bool(local.is_mul);
+        builder.assert_eq(local.is_add + local.is_sub + local.is_mul, local.is_real);
+        builder.assert_bool(local.is_pass_start);
+        builder.assert_bool(local.is_first_pass);
+
+        // The position of the first row.
+        let mut first_row = builder.when(local.is_first_row);
+        first_row.assert_zero(local.i);
+        first_row.assert_zero(local.j);
+        first_row.assert_one(local.is_pass_start);
+        first_row.assert_one(local.is_first_pass);
+
+        // The end of a pass, and the end of the operation.
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.i + AB::Expr::one() - local.num_limbs,
+            local.is_last_limb,
+            local.is_real.into(),
+        );
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.j + AB::Expr::one() - local.num_limbs,
+            local.is_last_pass,
+            local.is_real.into(),
+        );
+        builder.assert_eq(local.is_pass_end, local.is_real * local.is_last_limb.result);
+        builder.assert_eq(
+            local.is_last_row,
+            local.is_pass_end
+                * (AB::Expr::one() - local.is_mul + local.is_mul * local.is_last_pass.result),
+        );
+        builder.when(local.is_pass_start).assert_zero(local.i);
+
+        // The derived flags.
+        builder.assert_eq(
+            local.y_index,
+            local.is_mul * local.j + (local.is_add + local.is_sub) * local.i,
+        );
+        builder.assert_eq(
+            local.is_y_read,
+            local.is_add + local.is_sub + local.is_mul * local.is_pass_start,
+        );
+        builder.assert_eq(local.is_hi_write, local.is_mul * local.is_pass_end);
+        builder
+            .assert_eq(local.is_accumulate, local.is_mul * (AB::Expr::one() - local.is_first_pass));
+
+        // Within an operation, the columns describing the syscall are constant, and the counters
+        // advance to the next limb, or to the next pass at the end of a pass.
+        let within_op = local.is_real - local.is_last_row;
+        let mut within_op = builder.when_transition().when(within_op);
+        within_op.assert_eq(next.is_real, local.is_real);
+        within_op.assert_eq(next.shard, local.shard);
+        within_op.assert_eq(next.channel, local.channel);
+        within_op.assert_eq(next.clk, local.clk);
+        within_op.assert_eq(next.z_ptr, local.z_ptr);
+        within_op.assert_eq(next.operands_ptr, local.operands_ptr);
+        within_op.assert_eq(next.is_add, local.is_add);
+        within_op.assert_eq(next.is_sub, local.is_sub);
+        within_op.assert_eq(next.is_mul, local.is_mul);
+        within_op.assert_eq(next.num_limbs, local.num_limbs);
+        within_op.assert_eq(next.is_pass_start, local.is_pass_end);
+        within_op
+            .assert_eq(next.i, (local.i + AB::Expr::one()) * (AB::Expr::one() - local.is_pass_end));
+        within_op.assert_eq(next.j, local.j + local.is_pass_end);
+        within_op.assert_eq(
+            next.is_first_pass,
+            local.is_first_pass * (AB::Expr::one() - local.is_pass_end),
+        );
+    }
+
+    /// Constrains the limb operation of a row, and the carry passed on to the next row.
+    fn eval_operation<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &BigIntCols<AB::Var>,
+        next: &BigIntCols<AB::Var>,
+    ) where
+        Limbs<AB::Var, <U256Field as NumLimbs>::Limbs>: Copy,
+    {
+        let x_limbs: LimbCols<AB::Var> = limbs_from_access(&local.x_access);
+        let y_limbs: LimbCols<AB::Var> = limbs_from_access(&local.y_access);
+        let acc_limbs: LimbCols<AB::Var> = limbs_from_prev_access(&local.z_access);
+
+        let p_x: Polynomial<AB::Expr> = x_limbs.into();
+        let p_y: Polynomial<AB::Expr> = y_limbs.into();
+        let p_acc: Polynomial<AB::Expr> = acc_limbs.into();
+        let p_carry_in: Polynomial<AB::Expr> = local.carry_in.into();
+        let p_res: Polynomial<AB::Expr> = local.output.result.into();
+
+        // add: x + y + carry_in = result + 2^256 * carry_out.
+        // sub: result + y + carry_in = x + 2^256 * carry_out.
+        // mul: acc + x * y + carry_in = result + 2^256 * carry_out.
+        let p_op = (p_x.clone() + p_y.clone() + p_carry_in.clone()) * local.is_add.into()
+            + (p_res.clone() + p_y.clone() + p_carry_in.clone()) * local.is_sub.into()
+            + (p_x.clone() * p_y + p_carry_in) * local.is_mul.into()
+            + p_acc * local.is_accumulate.into();
+        let p_result = p_res * (local.is_add + local.is_mul).into() + p_x * local.is_sub.into();
+
+        let mut coeff_2_256 = vec![AB::Expr::zero(); LIMB_BYTES];
+        coeff_2_256.push(AB::Expr::one());
+        let p_modulus = Polynomial::from_coefficients(&coeff_2_256);
+
+        local.output.eval_with_polynomials(
+            builder,
+            p_op,
+            p_modulus,
+            p_result,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // The first row has no incoming carry.
+        for k in 0..LIMB_BYTES {
+            builder.when(local.is_first_row).assert_zero(local.carry_in[k]);
+        }
+
+        // Within a pass, the carry is passed on to the next row, and a new pass starts without
+        // carry. The limb of y is kept for the whole pass of a mul.
+        let within_op = local.is_real - local.is_last_row;
+        let mut within_op = builder.when_transition().when(within_op);
+        for k in 0..LIMB_BYTES {
+            within_op.assert_eq(
+                next.carry_in[k],
+                local.output.carry[k] * (AB::Expr::one() - local.is_pass_end),
+            );
+        }
+        let mut within_pass = within_op.when(local.is_mul - local.is_hi_write);
+        for k in 0..BIGINT_LIMB_WORDS {
+            within_pass.assert_word_eq(*next.y_access[k].value(), *local.y_access[k].value());
+        }
+    }
+
+    /// Constrains the memory accesses of a row.
+    fn eval_memory<AB: SP1AirBuilder>(&self, builder: &mut AB, local: &BigIntCols<AB::Var>) {
+        // The number of limbs is read on the first row, and must be between 1 and the maximum.
+        let num_limbs_value = *local.num_limbs_access.value();
+        let mut first_row = builder.when(local.is_first_row);
+        first_row.assert_eq(local.num_limbs, num_limbs_value[0]);
+        first_row.assert_zero(num_limbs_value[1]);
+        first_row.assert_zero(num_limbs_value[2]);
+        first_row.assert_zero(num_limbs_value[3]);
+        builder.send_byte(
+            ByteOpcode::LTU.as_field::<AB::F>(),
+            AB::F::one(),
+            local.num_limbs - AB::Expr::one(),
+            AB::F::from_canonical_usize(BIGINT_MAX_LIMBS),
+            local.shard,
+            local.channel,
+            local.is_first_row,
+        );
+        builder.eval_memory_access(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.operands_ptr,
+            &local.num_limbs_access,
+            local.is_first_row,
+        );
+
+        // The memory accesses of the `j`-th pass happen at `clk + j`.
+        let clk = local.clk + local.j;
+        let limb_bytes = AB::F::from_canonical_usize(LIMB_BYTES);
+        let x_ptr = local.operands_ptr + AB::F::from_canonical_u32(4);
+        let y_ptr = x_ptr.clone() + local.num_limbs * limb_bytes;
+
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            clk.clone(),
+            x_ptr + local.i * limb_bytes,
+            &local.x_access,
+            local.is_real,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            clk.clone(),
+            y_ptr + local.y_index * limb_bytes,
+            &local.y_access,
+            local.is_y_read,
+        );
+
+        // The result limb is written, and at the end of a pass of a mul so is the high limb.
+        builder
+            .when(local.is_real)
+            .assert_all_eq(local.output.result, value_as_limbs(&local.z_access));
+        builder
+            .when(local.is_hi_write)
+            .assert_all_eq(local.output.carry, value_as_limbs(&local.hi_access));
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            clk.clone(),
+            local.z_ptr + (local.i + local.j) * limb_bytes,
+            &local.z_access,
+            local.is_real,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            clk,
+            local.z_ptr + (local.j + local.num_limbs) * limb_bytes,
+            &local.hi_access,
+            local.is_hi_write,
+        );
+    }
+}
@@ -0,0 +1,82 @@
+mod air;
+
+pub use air::*;
+
+#[cfg(test)]
+mod tests {
+    use num::{BigUint, One};
+    use sp1_core_executor::{syscalls::SyscallCode, Executor, Instruction, Opcode, Program};
+    use sp1_stark::{CpuProver, SP1CoreOpts};
+
+    use crate::utils::{self, run_test};
+
+    const Z_PTR: u32 = 100;
+    const OPERANDS_PTR: u32 = 2000;
+
+    fn to_words(value: &BigUint, num_words: usize) -> Vec<u32> {
+        let mut words = value.to_u32_digits();
+        words.resize(num_words, 0);
+        words
+    }
+
+    fn bigint_program(syscall: SyscallCode, x: &BigUint, y: &BigUint, num_limbs: u32) -> Program {
+        let num_words = 8 * num_limbs as usize;
+        let mut operands = vec![num_limbs];
+        operands.extend(to_words(x, num_words));
+        operands.extend(to_words(y, num_words));
+
+        let mut instructions = vec![];
+        for (i, word) in operands.iter().enumerate() {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, OPERANDS_PTR + i as u32 * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, syscall as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, Z_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, OPERANDS_PTR, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+
+        Program::new(instructions, 0, 0)
+    }
+
+    fn execute(syscall: SyscallCode, x: &BigUint, y: &BigUint, num_limbs: u32) -> BigUint {
+        let program = bigint_program(syscall, x, y, num_limbs);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        let num_words = if syscall == SyscallCode::BIGINT_MUL { 16 } else { 8 } * num_limbs;
+        let words = (0..num_words).map(|i| runtime.word(Z_PTR + i * 4)).collect::<Vec<_>>();
+        BigUint::from_slice(&words)
+    }
+
+    #[test]
+    fn test_bigint_execute() {
+        utils::setup_logger();
+        let num_limbs = 3;
+        let modulus = BigUint::one() << (256 * num_limbs);
+        let x = &modulus - BigUint::from(0x1234_5678u32);
+        let y = (BigUint::one() << 300) + BigUint::from(0xdead_beefu32);
+
+        assert_eq!(execute(SyscallCode::BIGINT_ADD, &x, &y, num_limbs), (&x + &y) % &modulus);
+        assert_eq!(
+            execute(SyscallCode::BIGINT_SUB, &y, &x, num_limbs),
+            (&y + &modulus - &x) % &modulus
+        );
+        assert_eq!(execute(SyscallCode::BIGINT_MUL, &x, &y, num_limbs), &x * &y);
+    }
+
+    #[test]
+    fn test_bigint_prove() {
+        utils::setup_logger();
+        let x = (BigUint::one() << 500) - BigUint::one();
+        let y = (BigUint::one() << 300) + BigUint::from(0xdead_beefu32);
+        for syscall in [SyscallCode::BIGINT_ADD, SyscallCode::BIGINT_SUB, SyscallCode::BIGINT_MUL] {
+            let program = bigint_program(syscall, &x, &y, 2);
+            run_test::<CpuProver<_, _>>(program).unwrap();
+        }
+    }
+}
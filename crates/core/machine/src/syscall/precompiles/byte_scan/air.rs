@@ -0,0 +1,561 @@
+use core::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{AbstractField, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::{IntoParallelRefIterator, ParallelIterator, ParallelSlice};
+use sp1_core_executor::{
+    events::{
+        hex_digit_value, ByteLookupEvent, ByteRecord, ByteScanEvent, ByteScanOperation,
+        BYTE_SCAN_HEADER_WORDS, BYTE_SCAN_MAX_WORDS,
+    },
+    syscalls::SyscallCode,
+    ByteOpcode, ExecutionRecord, Program,
+};
+use sp1_derive::AlignedBorrow;
+use sp1_primitives::consts::WORD_SIZE;
+use sp1_stark::air::{BaseAirBuilder, MachineAir, SP1AirBuilder};
+
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::{MemoryCols, MemoryReadCols, MemoryWriteCols},
+    operations::{eval_multi_row_flags, populate_multi_row_nonces, IsZeroOperation, MultiRowFlags},
+    utils::pad_rows,
+};
+
+pub const NUM_BYTE_SCAN_COLS: usize = size_of::<ByteScanCols<u8>>();
+
+/// The number of hex digits decoded by a row, from two source words.
+const HEX_DIGITS: usize = 2 * WORD_SIZE;
+
+/// A set of columns to process one word of a byte scan find, copy or hex decode.
+///
+/// An operation on `num_words` words uses `num_words` rows. The `i`-th row reads the `i`-th source
+/// word (the `2i`-th and `2i + 1`-th for hex decode) and, for copy and hex decode, writes the
+/// `i`-th result word. Find counts the bytes before the first delimiter across the rows, and writes
+/// the count on the last row. The header is read and the syscall is received on the first row.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct ByteScanCols<T> {
+    pub is_real: T,
+    pub shard: T,
+    pub channel: T,
+    pub clk: T,
+    pub nonce: T,
+    pub dst_ptr: T,
+    pub operands_ptr: T,
+
+    pub is_find: T,
+    pub is_copy: T,
+    pub is_hex_decode: T,
+
+    /// The number of words, the source pointer and the delimiter, read on the first row.
+    pub header_access: [MemoryReadCols<T>; BYTE_SCAN_HEADER_WORDS],
+    pub num_words: T,
+    pub src_ptr: T,
+    pub delimiter: T,
+
+    /// Whether this is the first row of a real operation.
+    pub is_first_row: T,
+    /// Whether this is the last row of a real operation.
+    pub is_last_row: T,
+
+    /// The index of the word.
+    pub i: T,
+    /// Whether `i` is the index of the last word.
+    pub is_last: IsZeroOperation<T>,
+
+    /// The source words. The second one is only read for hex decode.
+    pub src_access: [MemoryReadCols<T>; 2],
+    /// The result word.
+    pub dst_access: MemoryWriteCols<T>,
+    /// Whether the result word is written on this row, on every row for copy and hex decode and on
+    /// the last row for find.
+    pub is_dst_write: T,
+
+    /// Whether each byte of the source word is the delimiter.
+    pub is_delimiter: [IsZeroOperation<T>; WORD_SIZE],
+    /// Whether the delimiter was found in the previous rows.
+    pub seen_before: T,
+    /// Whether the delimiter was found up to each byte of the source word.
+    pub seen: [T; WORD_SIZE],
+    /// The number of bytes before the delimiter in the previous rows.
+    pub count: T,
+
+    /// The value of each hex digit of the source words.
+    pub nibbles: [T; HEX_DIGITS],
+    /// Whether each hex digit is a lower case letter.
+    pub is_lower: [T; HEX_DIGITS],
+    /// Whether each hex digit is an upper case letter.
+    pub is_upper: [T; HEX_DIGITS],
+}
+
+impl<T: Copy> ByteScanCols<T> {
+    /// The columns delimiting the operations of the chip.
+    pub fn multi_row_flags(&self) -> MultiRowFlags<T> {
+        MultiRowFlags {
+            is_real: self.is_real,
+            is_first_row: self.is_first_row,
+            is_last_row: self.is_last_row,
+            nonce: self.nonce,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ByteScanChip;
+
+impl ByteScanChip {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl<F: PrimeField32> MachineAir<F> for ByteScanChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "ByteScan".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = input
+            .byte_scan_events
+            .par_iter()
+            .map(|event| {
+                let mut blu = Vec::new();
+                self.event_to_rows(event, &mut blu)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        pad_rows(&mut rows, || [F::zero(); NUM_BYTE_SCAN_COLS]);
+
+        // Convert the trace to a row major matrix.
+        let mut trace =
+            RowMajorMatrix::new(rows.into_iter().flatten().collect::<Vec<_>>(), NUM_BYTE_SCAN_COLS);
+
+        // Write the nonces to the trace.
+        populate_multi_row_nonces(&mut trace, |cols: &mut ByteScanCols<F>| {
+            (cols.is_first_row, &mut cols.nonce)
+        });
+
+        trace
+    }
+
+    fn generate_dependencies(&self, input: &Self::Record, output: &mut Self::Record) {
+        let chunk_size = std::cmp::max(input.byte_scan_events.len() / num_cpus::get(), 1);
+
+        let blu_batches = input
+            .byte_scan_events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<u32, HashMap<ByteLookupEvent, usize>> = HashMap::new();
+                events.iter().for_each(|event| {
+                    self.event_to_rows::<F>(event, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_sharded_byte_lookup_events(blu_batches.iter().collect_vec());
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.byte_scan_events.is_empty()
+    }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.byte_scan_events.iter().map(|event| event.num_words as usize).sum())
+    }
+}
+
+impl ByteScanChip {
+    /// Create the rows of a byte scan operation from an event.
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &ByteScanEvent,
+        blu: &mut impl ByteRecord,
+    ) -> Vec<[F; NUM_BYTE_SCAN_COLS]> {
+        let n = event.num_words as usize;
+        let is_find = event.op == ByteScanOperation::Find;
+        let is_hex_decode = event.op == ByteScanOperation::HexDecode;
+        let src_words_per_row = if is_hex_decode { 2 } else { 1 };
+
+        let mut seen_before = false;
+        let mut count = 0u32;
+        let mut rows = Vec::with_capacity(n);
+        for i in 0..n {
+            let mut row = [F::zero(); NUM_BYTE_SCAN_COLS];
+            let cols: &mut ByteScanCols<F> = row.as_mut_slice().borrow_mut();
+
+            // Populate basic columns.
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.channel = F::from_canonical_u8(event.channel);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.dst_ptr = F::from_canonical_u32(event.dst_ptr);
+            cols.operands_ptr = F::from_canonical_u32(event.operands_ptr);
+            cols.is_find = F::from_bool(is_find);
+            cols.is_copy = F::from_bool(event.op == ByteScanOperation::Copy);
+            cols.is_hex_decode = F::from_bool(is_hex_decode);
+            cols.num_words = F::from_canonical_u32(event.num_words);
+            cols.src_ptr = F::from_canonical_u32(event.src_ptr);
+            cols.delimiter = F::from_canonical_u32(event.delimiter);
+
+            // Populate the position of the row.
+            let is_first_row = i == 0;
+            let is_last_row = i == n - 1;
+            cols.is_first_row = F::from_bool(is_first_row);
+            cols.is_last_row = F::from_bool(is_last_row);
+            cols.i = F::from_canonical_usize(i);
+            cols.is_last.populate_from_field_element(cols.i + F::one() - cols.num_words);
+
+            // Populate the memory accesses.
+            if is_first_row {
+                for (access, record) in
+                    cols.header_access.iter_mut().zip_eq(event.header_memory_records)
+                {
+                    access.populate(event.channel, record, blu);
+                }
+            }
+            for k in 0..src_words_per_row {
+                let w = i * src_words_per_row + k;
+                cols.src_access[k].populate(event.channel, event.src_memory_records[w], blu);
+            }
+            let is_dst_write = !is_find || is_last_row;
+            cols.is_dst_write = F::from_bool(is_dst_write);
+            if is_dst_write {
+                let w = if is_find { 0 } else { i };
+                cols.dst_access.populate(event.channel, event.dst_memory_records[w], blu);
+            }
+
+            // Populate the operation.
+            match event.op {
+                ByteScanOperation::Find => {
+                    cols.seen_before = F::from_bool(seen_before);
+                    cols.count = F::from_canonical_u32(count);
+                    let mut seen = seen_before;
+                    for (k, byte) in event.src[i].to_le_bytes().into_iter().enumerate() {
+                        let diff = F::from_canonical_u8(byte) - cols.delimiter;
+                        seen |= cols.is_delimiter[k].populate_from_field_element(diff) == 1;
+                        cols.seen[k] = F::from_bool(seen);
+                        count += u32::from(!seen);
+                    }
+                    seen_before = seen;
+                }
+                ByteScanOperation::Copy => {}
+                ByteScanOperation::HexDecode => {
+                    let chars = event.src[2 * i..2 * i + 2]
+                        .iter()
+                        .flat_map(|word| word.to_le_bytes())
+                        .collect::<Vec<_>>();
+                    for (k, &c) in chars.iter().enumerate() {
+                        let nibble = hex_digit_value(c).unwrap();
+                        let is_letter = nibble >= 10;
+                        cols.nibbles[k] = F::from_canonical_u8(nibble);
+                        cols.is_lower[k] = F::from_bool(c.is_ascii_lowercase());
+                        cols.is_upper[k] = F::from_bool(c.is_ascii_uppercase());
+                        blu.add_byte_lookup_event(ByteLookupEvent {
+                            shard: event.shard,
+                            channel: event.channel,
+                            opcode: ByteOpcode::LTU,
+                            a1: 1,
+                            a2: 0,
+                            b: if is_letter { nibble - 10 } else { nibble },
+                            c: if is_letter { 6 } else { 10 },
+                        });
+                    }
+                }
+            }
+
+            rows.push(row);
+        }
+
+        // Send the range check on the number of words.
+        blu.add_byte_lookup_event(ByteLookupEvent {
+            shard: event.shard,
+            channel: event.channel,
+            opcode: ByteOpcode::LTU,
+            a1: 1,
+            a2: 0,
+            b: (event.num_words - 1) as u8,
+            c: BYTE_SCAN_MAX_WORDS as u8,
+        });
+
+        rows
+    }
+}
+
+impl<F> BaseAir<F> for ByteScanChip {
+    fn width(&self) -> usize {
+        NUM_BYTE_SCAN_COLS
+    }
+}
+
+impl<AB> Air<AB> for ByteScanChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &ByteScanCols<AB::Var> = (*local).borrow();
+        let next: &ByteScanCols<AB::Var> = (*next).borrow();
+
+        eval_multi_row_flags(builder, local.multi_row_flags(), next.multi_row_flags());
+
+        self.eval_flags(builder, local, next);
+
+        self.eval_find(builder, local, next);
+
+        self.eval_hex_decode(builder, local);
+
+        // A copy writes the source word unchanged.
+        builder
+            .when(local.is_copy)
+            .assert_word_eq(*local.dst_access.value(), *local.src_access[0].value());
+
+        self.eval_memory(builder, local);
+
+        // Receive the arguments.
+        let syscall_id = local.is_find
+            * AB::F::from_canonical_u32(SyscallCode::BYTE_SCAN_FIND.syscall_id())
+            + local.is_copy * AB::F::from_canonical_u32(SyscallCode::BYTE_SCAN_COPY.syscall_id())
+            + local.is_hex_decode
+                * AB::F::from_canonical_u32(SyscallCode::BYTE_SCAN_HEX_DECODE.syscall_id());
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            syscall_id,
+            local.dst_ptr,
+            local.operands_ptr,
+            local.is_first_row,
+        );
+    }
+}
+
+impl ByteScanChip {
+    /// Constrains the flags and counters that track the position of a row within an operation.
+    fn eval_flags<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &ByteScanCols<AB::Var>,
+        next: &ByteScanCols<AB::Var>,
+    ) {
+        builder.assert_bool(local.is_real);
+        builder.assert_bool(local.is_find);
+        builder.assert_bool(local.is_copy);
+        builder.assert_bool(local.is_hex_decode);
+        builder.assert_eq(local.is_find + local.is_copy + local.is_hex_decode, local.is_real);
+
+        // The operation ends on the row of its last word.
+        builder.when(local.is_first_row).assert_zero(local.i);
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.i + AB::Expr::one() - local.num_words,
+            local.is_last,
+            local.is_real.into(),
+        );
+        builder.assert_eq(local.is_last_row, local.is_real * local.is_last.result);
+
+        // Find writes its result on the last row, the other operations on every row.
+        builder.assert_eq(
+            local.is_dst_write,
+            local.is_copy + local.is_hex_decode + local.is_find * local.is_last_row,
+        );
+
+        // Within an operation, the columns describing the syscall are constant, and the index
+        // advances to the next word.
+        let within_op = local.is_real - local.is_last_row;
+        let mut within_op = builder.when_transition().when(within_op);
+        within_op.assert_eq(next.is_real, local.is_real);
+        within_op.assert_eq(next.shard, local.shard);
+        within_op.assert_eq(next.channel, local.channel);
+        within_op.assert_eq(next.clk, local.clk);
+        within_op.assert_eq(next.dst_ptr, local.dst_ptr);
+        within_op.assert_eq(next.operands_ptr, local.operands_ptr);
+        within_op.assert_eq(next.is_find, local.is_find);
+        within_op.assert_eq(next.is_copy, local.is_copy);
+        within_op.assert_eq(next.is_hex_decode, local.is_hex_decode);
+        within_op.assert_eq(next.num_words, local.num_words);
+        within_op.assert_eq(next.src_ptr, local.src_ptr);
+        within_op.assert_eq(next.delimiter, local.delimiter);
+        within_op.assert_eq(next.i, local.i + AB::Expr::one());
+    }
+
+    /// Constrains the search for the delimiter, and the count written at the end of a find.
+    fn eval_find<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &ByteScanCols<AB::Var>,
+        next: &ByteScanCols<AB::Var>,
+    ) {
+        let src = *local.src_access[0].value();
+        for k in 0..WORD_SIZE {
+            IsZeroOperation::<AB::F>::eval(
+                builder,
+                src[k] - local.delimiter,
+                local.is_delimiter[k],
+                local.is_find.into(),
+            );
+        }
+
+        // The delimiter is seen up to a byte if it was seen up to the previous byte, or if the byte
+        // is the delimiter.
+        let mut find = builder.when(local.is_find);
+        let mut seen_prev: AB::Expr = local.seen_before.into();
+        let mut not_seen = AB::Expr::zero();
+        for k in 0..WORD_SIZE {
+            let is_delimiter = local.is_delimiter[k].result;
+            find.assert_eq(
+                local.seen[k],
+                seen_prev.clone() + is_delimiter - seen_prev.clone() * is_delimiter,
+            );
+            seen_prev = local.seen[k].into();
+            not_seen = not_seen + AB::Expr::one() - local.seen[k];
+        }
+
+        // The search starts from scratch, and carries on to the next row.
+        let mut first_row = builder.when(local.is_first_row);
+        first_row.assert_zero(local.seen_before);
+        first_row.assert_zero(local.count);
+        let within_op = local.is_find - local.is_last_row;
+        let mut within_op = builder.when_transition().when(within_op);
+        within_op.assert_eq(next.seen_before, local.seen[WORD_SIZE - 1]);
+        within_op.assert_eq(next.count, local.count + not_seen.clone());
+
+        // The count of the bytes before the delimiter is written on the last row. It is at most
+        // `4 * BYTE_SCAN_MAX_WORDS`, so it fits in a byte.
+        let result = *local.dst_access.value();
+        let mut last_row = builder.when(local.is_find * local.is_last_row);
+        last_row.assert_eq(result[0], local.count + not_seen);
+        last_row.assert_zero(result[1]);
+        last_row.assert_zero(result[2]);
+        last_row.assert_zero(result[3]);
+    }
+
+    /// Constrains the decoding of the hex digits of the source words into the result word.
+    fn eval_hex_decode<AB: SP1AirBuilder>(&self, builder: &mut AB, local: &ByteScanCols<AB::Var>) {
+        for k in 0..HEX_DIGITS {
+            let c = local.src_access[k / WORD_SIZE].value()[k % WORD_SIZE];
+            let is_letter = local.is_lower[k] + local.is_upper[k];
+
+            // The digit is `'0' + nibble`, `'a' + nibble - 10` or `'A' + nibble - 10`.
+            let mut hex = builder.when(local.is_hex_decode);
+            hex.assert_bool(local.is_lower[k]);
+            hex.assert_bool(local.is_upper[k]);
+            hex.assert_bool(is_letter.clone());
+            hex.assert_eq(
+                c,
+                local.nibbles[k]
+                    + AB::F::from_canonical_u8(b'0')
+                    + local.is_lower[k] * AB::F::from_canonical_u8(b'a' - b'0' - 10)
+                    + local.is_upper[k] * AB::F::from_canonical_u8(b'A' - b'0' - 10),
+            );
+
+            // The nibble is below 10 for a decimal digit, and between 10 and 15 for a letter.
+            builder.send_byte(
+                ByteOpcode::LTU.as_field::<AB::F>(),
+                AB::F::one(),
+                local.nibbles[k] - is_letter.clone() * AB::F::from_canonical_u8(10),
+                AB::Expr::from_canonical_u8(10) - is_letter * AB::F::from_canonical_u8(4),
+                local.shard,
+                local.channel,
+                local.is_hex_decode,
+            );
+        }
+
+        // Each byte of the result is made of two digits, the high nibble first.
+        let result = *local.dst_access.value();
+        for k in 0..WORD_SIZE {
+            builder.when(local.is_hex_decode).assert_eq(
+                result[k],
+                local.nibbles[2 * k] * AB::F::from_canonical_u8(16) + local.nibbles[2 * k + 1],
+            );
+        }
+    }
+
+    /// Constrains the memory accesses of a row.
+    fn eval_memory<AB: SP1AirBuilder>(&self, builder: &mut AB, local: &ByteScanCols<AB::Var>) {
+        // The header is read on the first row. The number of words must be between 1 and the
+        // maximum, and the delimiter of a find must be a byte.
+        let num_words_value = *local.header_access[0].value();
+        let mut first_row = builder.when(local.is_first_row);
+        first_row.assert_eq(local.num_words, num_words_value[0]);
+        first_row.assert_zero(num_words_value[1]);
+        first_row.assert_zero(num_words_value[2]);
+        first_row.assert_zero(num_words_value[3]);
+        first_row.assert_eq(local.src_ptr, local.header_access[1].value().reduce::<AB>());
+        let delimiter_value = *local.header_access[2].value();
+        let mut first_find_row = builder.when(local.is_first_row * local.is_find);
+        first_find_row.assert_eq(local.delimiter, delimiter_value[0]);
+        first_find_row.assert_zero(delimiter_value[1]);
+        first_find_row.assert_zero(delimiter_value[2]);
+        first_find_row.assert_zero(delimiter_value[3]);
+        builder.send_byte(
+            ByteOpcode::LTU.as_field::<AB::F>(),
+            AB::F::one(),
+            local.num_words - AB::Expr::one(),
+            AB::F::from_canonical_usize(BYTE_SCAN_MAX_WORDS),
+            local.shard,
+            local.channel,
+            local.is_first_row,
+        );
+        for (k, access) in local.header_access.iter().enumerate() {
+            builder.eval_memory_access(
+                local.shard,
+                local.channel,
+                local.clk,
+                local.operands_ptr + AB::F::from_canonical_usize(k * WORD_SIZE),
+                access,
+                local.is_first_row,
+            );
+        }
+
+        // Hex decode reads two source words per row, the other operations one.
+        let word_bytes = AB::F::from_canonical_usize(WORD_SIZE);
+        let src_addr =
+            local.src_ptr + local.i * (AB::Expr::one() + local.is_hex_decode) * word_bytes;
+        builder.eval_memory_access(
+            local.shard,
+            local.channel,
+            local.clk,
+            src_addr.clone(),
+            &local.src_access[0],
+            local.is_real,
+        );
+        builder.eval_memory_access(
+            local.shard,
+            local.channel,
+            local.clk,
+            src_addr + word_bytes,
+            &local.src_access[1],
+            local.is_hex_decode,
+        );
+
+        // Find writes a single word, the other operations one word per row.
+        builder.eval_memory_access(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.dst_ptr + local.i * (AB::Expr::one() - local.is_find) * word_bytes,
+            &local.dst_access,
+            local.is_dst_write,
+        );
+    }
+}
@@ -0,0 +1,88 @@
+mod air;
+
+pub use air::*;
+
+#[cfg(test)]
+mod tests {
+    use sp1_core_executor::{syscalls::SyscallCode, Executor, Instruction, Opcode, Program};
+    use sp1_primitives::consts::{bytes_to_words_le_vec, words_to_bytes_le_vec};
+    use sp1_stark::{CpuProver, SP1CoreOpts};
+
+    use crate::utils::{self, run_test};
+
+    const DST_PTR: u32 = 100;
+    const OPERANDS_PTR: u32 = 1000;
+    const SRC_PTR: u32 = 2000;
+
+    fn byte_scan_program(syscall: SyscallCode, src: &[u8], delimiter: u8) -> Program {
+        let src = bytes_to_words_le_vec(src);
+        let num_words =
+            if syscall == SyscallCode::BYTE_SCAN_HEX_DECODE { src.len() / 2 } else { src.len() };
+        let header = [num_words as u32, SRC_PTR, delimiter as u32];
+
+        let mut instructions = vec![];
+        let words = header
+            .iter()
+            .enumerate()
+            .map(|(i, &word)| (OPERANDS_PTR + i as u32 * 4, word))
+            .chain(src.iter().enumerate().map(|(i, &word)| (SRC_PTR + i as u32 * 4, word)));
+        for (addr, word) in words {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 29, 0, word, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, addr, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, syscall as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, DST_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, OPERANDS_PTR, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+
+        Program::new(instructions, 0, 0)
+    }
+
+    fn execute(syscall: SyscallCode, src: &[u8], delimiter: u8, num_dst_words: u32) -> Vec<u8> {
+        let program = byte_scan_program(syscall, src, delimiter);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        let words = (0..num_dst_words).map(|i| runtime.word(DST_PTR + i * 4)).collect::<Vec<_>>();
+        words_to_bytes_le_vec(&words)
+    }
+
+    #[test]
+    fn test_byte_scan_execute() {
+        utils::setup_logger();
+        let src = b"{\"key\": \"value\"}";
+
+        let find = execute(SyscallCode::BYTE_SCAN_FIND, src, b':', 1);
+        assert_eq!(find, 6u32.to_le_bytes());
+        let find = execute(SyscallCode::BYTE_SCAN_FIND, src, b'#', 1);
+        assert_eq!(find, (src.len() as u32).to_le_bytes());
+
+        assert_eq!(execute(SyscallCode::BYTE_SCAN_COPY, src, 0, 4), src);
+
+        let hex = b"00ff7Fa0DEADbeef";
+        assert_eq!(
+            execute(SyscallCode::BYTE_SCAN_HEX_DECODE, hex, 0, 2),
+            [0x00, 0xff, 0x7f, 0xa0, 0xde, 0xad, 0xbe, 0xef]
+        );
+    }
+
+    #[test]
+    fn test_byte_scan_prove() {
+        utils::setup_logger();
+        let src = b"[\"0x1234\", \"0xabcd\", \"0xEF01\"]  ";
+        for (syscall, src, delimiter) in [
+            (SyscallCode::BYTE_SCAN_FIND, &src[..], b','),
+            (SyscallCode::BYTE_SCAN_FIND, &src[..], b'#'),
+            (SyscallCode::BYTE_SCAN_COPY, &src[..], 0),
+            (SyscallCode::BYTE_SCAN_HEX_DECODE, &b"0123456789abcdefABCDEF0a1b2c3d4e"[..], 0),
+        ] {
+            let program = byte_scan_program(syscall, src, delimiter);
+            run_test::<CpuProver<_, _>>(program).unwrap();
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use core::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+
+use itertools::Itertools;
+use num::BigUint;
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{AbstractField, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use sp1_core_executor::{
+    events::{ByteRecord, DilithiumOperation, FieldOperation, DILITHIUM_BLOCK_WORDS},
+    syscalls::SyscallCode,
+    ExecutionRecord, Program,
+};
+use sp1_curves::{
+    dilithium::DilithiumField,
+    params::{FieldParameters, Limbs, NumLimbs},
+};
+use sp1_derive::AlignedBorrow;
+use sp1_stark::air::{BaseAirBuilder, MachineAir, Polynomial, SP1AirBuilder};
+
+use crate::{
+    air::MemoryAirBuilder,
+    memory::{MemoryCols, MemoryWriteCols},
+    operations::field::{field_op::FieldOpCols, range::FieldLtCols},
+    utils::{limbs_from_prev_access, pad_rows},
+};
+
+pub const NUM_DILITHIUM_COLS: usize = size_of::<DilithiumOpCols<u8>>();
+
+type CoeffLimbs<T> = Limbs<T, <DilithiumField as NumLimbs>::Limbs>;
+
+/// A set of columns for a lane-wise operation on two blocks of Dilithium coefficients.
+///
+/// Each of the [`DILITHIUM_BLOCK_WORDS`] lanes computes `x op y mod q` into `x_result`, and
+/// `x - y mod q` into `y_result`, which is only written back to memory by the butterfly. The
+/// results are checked to be reduced, so that they can be fed to the next operation.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct DilithiumOpCols<T> {
+    pub is_real: T,
+    pub shard: T,
+    pub channel: T,
+    pub nonce: T,
+    pub clk: T,
+    pub is_add: T,
+    pub is_sub: T,
+    pub is_mul: T,
+    pub is_butterfly: T,
+    pub x_ptr: T,
+    pub y_ptr: T,
+    pub x_access: [MemoryWriteCols<T>; DILITHIUM_BLOCK_WORDS],
+    pub y_access: [MemoryWriteCols<T>; DILITHIUM_BLOCK_WORDS],
+    pub(crate) x_result: [FieldOpCols<T, DilithiumField>; DILITHIUM_BLOCK_WORDS],
+    pub(crate) x_range: [FieldLtCols<T, DilithiumField>; DILITHIUM_BLOCK_WORDS],
+    pub(crate) y_result: [FieldOpCols<T, DilithiumField>; DILITHIUM_BLOCK_WORDS],
+    pub(crate) y_range: [FieldLtCols<T, DilithiumField>; DILITHIUM_BLOCK_WORDS],
+}
+
+/// A chip for the Dilithium add, sub, mul and butterfly precompiles.
+#[derive(Default)]
+pub struct DilithiumOpChip;
+
+impl DilithiumOpChip {
+    /// Populates the field operation columns of every lane, and returns the results.
+    fn populate_field_ops<F: PrimeField32>(
+        record: &mut impl ByteRecord,
+        shard: u32,
+        channel: u8,
+        cols: &mut DilithiumOpCols<F>,
+        x: &[u32],
+        y: &[u32],
+        op: DilithiumOperation,
+    ) -> (Vec<BigUint>, Vec<BigUint>) {
+        let modulus = DilithiumField::modulus();
+        let field_op = match op {
+            DilithiumOperation::Add | DilithiumOperation::Butterfly => FieldOperation::Add,
+            DilithiumOperation::Sub => FieldOperation::Sub,
+            DilithiumOperation::Mul => FieldOperation::Mul,
+        };
+        (0..DILITHIUM_BLOCK_WORDS)
+            .map(|i| {
+                let (a, b) = (BigUint::from(x[i]), BigUint::from(y[i]));
+                let x_result = cols.x_result[i]
+                    .populate_with_modulus(record, shard, channel, &a, &b, &modulus, field_op);
+                let y_result = cols.y_result[i].populate_with_modulus(
+                    record,
+                    shard,
+                    channel,
+                    &a,
+                    &b,
+                    &modulus,
+                    FieldOperation::Sub,
+                );
+                (x_result, y_result)
+            })
+            .unzip()
+    }
+}
+
+impl<F: PrimeField32> MachineAir<F> for DilithiumOpChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "DilithiumOp".to_string()
+    }
+
+    fn generate_trace(&self, input: &Self::Record, output: &mut Self::Record) -> RowMajorMatrix<F> {
+        let mut rows = Vec::new();
+        let mut new_byte_lookup_events = Vec::new();
+        let modulus = DilithiumField::modulus();
+
+        for event in input.dilithium_events.iter() {
+            let mut row = [F::zero(); NUM_DILITHIUM_COLS];
+            let cols: &mut DilithiumOpCols<F> = row.as_mut_slice().borrow_mut();
+
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.channel = F::from_canonical_u8(event.channel);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.is_add = F::from_bool(event.op == DilithiumOperation::Add);
+            cols.is_sub = F::from_bool(event.op == DilithiumOperation::Sub);
+            cols.is_mul = F::from_bool(event.op == DilithiumOperation::Mul);
+            cols.is_butterfly = F::from_bool(event.op == DilithiumOperation::Butterfly);
+            cols.x_ptr = F::from_canonical_u32(event.x_ptr);
+            cols.y_ptr = F::from_canonical_u32(event.y_ptr);
+
+            let (x_results, y_results) = Self::populate_field_ops(
+                &mut new_byte_lookup_events,
+                event.shard,
+                event.channel,
+                cols,
+                &event.x,
+                &event.y,
+                event.op,
+            );
+            for i in 0..DILITHIUM_BLOCK_WORDS {
+                cols.x_range[i].populate(
+                    &mut new_byte_lookup_events,
+                    event.shard,
+                    event.channel,
+                    &x_results[i],
+                    &modulus,
+                );
+                if event.op == DilithiumOperation::Butterfly {
+                    cols.y_range[i].populate(
+                        &mut new_byte_lookup_events,
+                        event.shard,
+                        event.channel,
+                        &y_results[i],
+                        &modulus,
+                    );
+                }
+            }
+
+            // Populate the memory access columns.
+            for i in 0..DILITHIUM_BLOCK_WORDS {
+                cols.y_access[i].populate(
+                    event.channel,
+                    event.y_memory_records[i],
+                    &mut new_byte_lookup_events,
+                );
+                cols.x_access[i].populate(
+                    event.channel,
+                    event.x_memory_records[i],
+                    &mut new_byte_lookup_events,
+                );
+            }
+
+            rows.push(row);
+        }
+
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || {
+            let mut row = [F::zero(); NUM_DILITHIUM_COLS];
+            let cols: &mut DilithiumOpCols<F> = row.as_mut_slice().borrow_mut();
+            let zero = [0u32; DILITHIUM_BLOCK_WORDS];
+            cols.is_add = F::one();
+            Self::populate_field_ops(
+                &mut vec![],
+                0,
+                0,
+                cols,
+                &zero,
+                &zero,
+                DilithiumOperation::Add,
+            );
+            row
+        });
+
+        // Convert the trace to a row major matrix.
+        let mut trace =
+            RowMajorMatrix::new(rows.into_iter().flatten().collect::<Vec<_>>(), NUM_DILITHIUM_COLS);
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut DilithiumOpCols<F> =
+                trace.values[i * NUM_DILITHIUM_COLS..(i + 1) * NUM_DILITHIUM_COLS].borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.dilithium_events.is_empty()
+    }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.dilithium_events.len())
+    }
+}
+
+impl<F> BaseAir<F> for DilithiumOpChip {
+    fn width(&self) -> usize {
+        NUM_DILITHIUM_COLS
+    }
+}
+
+impl<AB> Air<AB> for DilithiumOpChip
+where
+    AB: SP1AirBuilder,
+    CoeffLimbs<AB::Var>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &DilithiumOpCols<AB::Var> = (*local).borrow();
+        let next = main.row_slice(1);
+        let next: &DilithiumOpCols<AB::Var> = (*next).borrow();
+
+        // Check that nonce is incremented.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder.when_transition().assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        // Check that operations flags are boolean.
+        builder.assert_bool(local.is_add);
+        builder.assert_bool(local.is_sub);
+        builder.assert_bool(local.is_mul);
+        builder.assert_bool(local.is_butterfly);
+
+        // Check that only one of them is set.
+        builder.assert_eq(
+            local.is_add + local.is_sub + local.is_mul + local.is_butterfly,
+            AB::Expr::one(),
+        );
+
+        let modulus_coeffs = DilithiumField::MODULUS
+            .iter()
+            .map(|&limb| AB::Expr::from_canonical_u8(limb))
+            .collect_vec();
+        let p_modulus = Polynomial::from_coefficients(&modulus_coeffs);
+
+        for i in 0..DILITHIUM_BLOCK_WORDS {
+            let x: CoeffLimbs<AB::Var> = limbs_from_prev_access(&local.x_access[i..i + 1]);
+            let y: CoeffLimbs<AB::Var> = limbs_from_prev_access(&local.y_access[i..i + 1]);
+
+            // The butterfly computes `x + y` in the x lane.
+            local.x_result[i].eval_variable(
+                builder,
+                &x,
+                &y,
+                &p_modulus,
+                local.is_add + local.is_butterfly,
+                local.is_sub,
+                local.is_mul,
+                AB::F::zero(),
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+            local.x_range[i].eval(
+                builder,
+                &local.x_result[i].result,
+                &p_modulus,
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+            builder
+                .when(local.is_real)
+                .assert_all_eq(local.x_result[i].result, *local.x_access[i].value());
+
+            // The butterfly overwrites the y lane with `x - y`, other operations leave it as is.
+            local.y_result[i].eval_with_modulus(
+                builder,
+                &x,
+                &y,
+                &p_modulus,
+                FieldOperation::Sub,
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+            local.y_range[i].eval(
+                builder,
+                &local.y_result[i].result,
+                &p_modulus,
+                local.shard,
+                local.channel,
+                local.is_butterfly,
+            );
+            builder
+                .when(local.is_butterfly)
+                .assert_all_eq(local.y_result[i].result, *local.y_access[i].value());
+            builder
+                .when(local.is_real - local.is_butterfly)
+                .assert_all_eq(*local.y_access[i].value(), *local.y_access[i].prev_value());
+        }
+
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk.into(),
+            local.y_ptr,
+            &local.y_access,
+            local.is_real,
+        );
+        // The x block is accessed at `clk + 1` since x and y could be the same.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk + AB::F::one(),
+            local.x_ptr,
+            &local.x_access,
+            local.is_real,
+        );
+
+        // Select the correct syscall id based on the operation flags.
+        let syscall_id_felt = local.is_add
+            * AB::F::from_canonical_u32(SyscallCode::DILITHIUM_ADD.syscall_id())
+            + local.is_sub * AB::F::from_canonical_u32(SyscallCode::DILITHIUM_SUB.syscall_id())
+            + local.is_mul * AB::F::from_canonical_u32(SyscallCode::DILITHIUM_MUL.syscall_id())
+            + local.is_butterfly
+                * AB::F::from_canonical_u32(SyscallCode::DILITHIUM_BUTTERFLY.syscall_id());
+
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            syscall_id_felt,
+            local.x_ptr,
+            local.y_ptr,
+            local.is_real,
+        );
+    }
+}
@@ -0,0 +1,89 @@
+mod air;
+
+pub use air::*;
+
+#[cfg(test)]
+mod tests {
+    use sp1_core_executor::{
+        events::{DILITHIUM_BLOCK_WORDS, DILITHIUM_Q},
+        syscalls::SyscallCode,
+        Executor, Instruction, Opcode, Program,
+    };
+    use sp1_stark::{CpuProver, SP1CoreOpts};
+
+    use crate::utils::{self, run_test};
+
+    const X_PTR: u32 = 100;
+    const Y_PTR: u32 = 1000;
+
+    fn dilithium_program(syscall: SyscallCode, x: &[u32], y: &[u32]) -> Program {
+        let mut instructions = vec![];
+        for (ptr, block) in [(X_PTR, x), (Y_PTR, y)] {
+            for (i, word) in block.iter().enumerate() {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr + i as u32 * 4, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, syscall as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, X_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, Y_PTR, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+
+        Program::new(instructions, 0, 0)
+    }
+
+    fn execute(syscall: SyscallCode, x: &[u32], y: &[u32]) -> (Vec<u32>, Vec<u32>) {
+        let program = dilithium_program(syscall, x, y);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        let read = |ptr: u32| {
+            (0..DILITHIUM_BLOCK_WORDS as u32).map(|i| runtime.word(ptr + i * 4)).collect::<Vec<_>>()
+        };
+        (read(X_PTR), read(Y_PTR))
+    }
+
+    fn blocks() -> (Vec<u32>, Vec<u32>) {
+        let x = (0..DILITHIUM_BLOCK_WORDS as u32).map(|i| DILITHIUM_Q - 1 - i * 12_345).collect();
+        let y = (0..DILITHIUM_BLOCK_WORDS as u32).map(|i| i * 1_000_003 % DILITHIUM_Q).collect();
+        (x, y)
+    }
+
+    #[test]
+    fn test_dilithium_execute() {
+        utils::setup_logger();
+        let (x, y) = blocks();
+        let q = DILITHIUM_Q;
+        let lanes = |f: &dyn Fn(u32, u32) -> u32| {
+            x.iter().zip(y.iter()).map(|(&a, &b)| f(a, b)).collect::<Vec<_>>()
+        };
+        let sum = lanes(&|a, b| (a + b) % q);
+        let diff = lanes(&|a, b| (a + q - b) % q);
+        let prod = lanes(&|a, b| (u64::from(a) * u64::from(b) % u64::from(q)) as u32);
+
+        assert_eq!(execute(SyscallCode::DILITHIUM_ADD, &x, &y), (sum.clone(), y.clone()));
+        assert_eq!(execute(SyscallCode::DILITHIUM_SUB, &x, &y), (diff.clone(), y.clone()));
+        assert_eq!(execute(SyscallCode::DILITHIUM_MUL, &x, &y), (prod, y.clone()));
+        assert_eq!(execute(SyscallCode::DILITHIUM_BUTTERFLY, &x, &y), (sum, diff));
+    }
+
+    #[test]
+    fn test_dilithium_prove() {
+        utils::setup_logger();
+        let (x, y) = blocks();
+        for syscall in [
+            SyscallCode::DILITHIUM_ADD,
+            SyscallCode::DILITHIUM_SUB,
+            SyscallCode::DILITHIUM_MUL,
+            SyscallCode::DILITHIUM_BUTTERFLY,
+        ] {
+            let program = dilithium_program(syscall, &x, &y);
+            run_test::<CpuProver<_, _>>(program).unwrap();
+        }
+    }
+}
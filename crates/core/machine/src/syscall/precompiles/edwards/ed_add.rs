@@ -191,6 +191,10 @@ impl<F: PrimeField32, E: EllipticCurve + EdwardsParameters> MachineAir<F> for Ed
     fn included(&self, shard: &Self::Record) -> bool {
         !shard.ed_add_events.is_empty()
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.ed_add_events.len())
+    }
 }
 
 impl<E: EllipticCurve + EdwardsParameters> EdAddAssignChip<E> {
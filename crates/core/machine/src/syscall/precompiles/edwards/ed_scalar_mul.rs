@@ -0,0 +1,789 @@
+use core::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+use std::marker::PhantomData;
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use num::BigUint;
+
+use crate::air::MemoryAirBuilder;
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{AbstractField, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::{IntoParallelRefIterator, ParallelIterator, ParallelSlice};
+use sp1_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, EdScalarMulEvent, FieldOperation},
+    syscalls::SyscallCode,
+    ExecutionRecord, Program,
+};
+use sp1_curves::{
+    edwards::{
+        ed25519::Ed25519BaseField, EdwardsParameters, NUM_LIMBS, WORDS_CURVE_POINT,
+        WORDS_FIELD_ELEMENT,
+    },
+    params::{FieldParameters, Limbs, NumLimbs},
+    AffinePoint, EllipticCurve,
+};
+use sp1_derive::AlignedBorrow;
+use sp1_stark::air::{BaseAirBuilder, MachineAir, SP1AirBuilder};
+
+use crate::{
+    memory::{MemoryCols, MemoryReadCols, MemoryWriteCols},
+    operations::field::{
+        field_den::FieldDenCols, field_inner_product::FieldInnerProductCols, field_op::FieldOpCols,
+    },
+    utils::words_to_bytes_le,
+};
+
+pub const NUM_ED_SCALAR_MUL_COLS: usize = size_of::<EdScalarMulCols<u8>>();
+
+/// The number of rows used by a single scalar multiplication, one for each bit of the scalar.
+pub const ED_SCALAR_MUL_NUM_ROWS: usize = 256;
+
+/// The number of bytes in the scalar.
+const NUM_SCALAR_BYTES: usize = ED_SCALAR_MUL_NUM_ROWS / 8;
+
+type EdLimbs<T> = Limbs<T, <Ed25519BaseField as NumLimbs>::Limbs>;
+
+/// A set of columns to compute the complete twisted Edwards addition `(x3, y3) = (x1, y1) + (x2,
+/// y2)`.
+///
+/// The result is available in `x3_ins.result` and `y3_ins.result`.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct EdAddCols<T> {
+    pub(crate) x3_numerator: FieldInnerProductCols<T, Ed25519BaseField>,
+    pub(crate) y3_numerator: FieldInnerProductCols<T, Ed25519BaseField>,
+    pub(crate) x1_mul_y1: FieldOpCols<T, Ed25519BaseField>,
+    pub(crate) x2_mul_y2: FieldOpCols<T, Ed25519BaseField>,
+    pub(crate) f: FieldOpCols<T, Ed25519BaseField>,
+    pub(crate) d_mul_f: FieldOpCols<T, Ed25519BaseField>,
+    pub(crate) x3_ins: FieldDenCols<T, Ed25519BaseField>,
+    pub(crate) y3_ins: FieldDenCols<T, Ed25519BaseField>,
+}
+
+/// A set of columns to compute one step of a double-and-add scalar multiplication on the Edwards
+/// curve ed25519.
+///
+/// A scalar multiplication `p = [scalar] p` is spread over [`ED_SCALAR_MUL_NUM_ROWS`] rows. The
+/// `i`-th row holds the accumulator `acc = [scalar mod 2^i] p` and the base `base = [2^i] p`, and
+/// computes `acc + [bit_i] base` and `base + base`, which become the accumulator and base of the
+/// next row. The syscall is received on the first row and memory is accessed on the last row.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct EdScalarMulCols<T> {
+    pub is_real: T,
+    pub shard: T,
+    pub channel: T,
+    pub clk: T,
+    pub nonce: T,
+    pub p_ptr: T,
+    pub scalar_ptr: T,
+
+    /// Whether this is the first row of a real scalar multiplication.
+    pub is_first_row: T,
+    /// Whether this is the last row of a real scalar multiplication.
+    pub is_last_row: T,
+
+    /// A one-hot encoding of the index of the current bit within the current scalar byte.
+    pub bit_flags: [T; 8],
+    /// A one-hot encoding of the index of the current scalar byte.
+    pub byte_flags: [T; NUM_SCALAR_BYTES],
+    /// The bit decomposition of the current scalar byte.
+    pub byte_bits: [T; 8],
+    /// The current bit of the scalar.
+    pub bit: T,
+
+    /// The input point, kept constant across the rows of a scalar multiplication.
+    pub p_x: EdLimbs<T>,
+    pub p_y: EdLimbs<T>,
+    /// The scalar bytes, kept constant across the rows of a scalar multiplication.
+    pub scalar: [T; NUM_SCALAR_BYTES],
+
+    pub acc_x: EdLimbs<T>,
+    pub acc_y: EdLimbs<T>,
+    pub base_x: EdLimbs<T>,
+    pub base_y: EdLimbs<T>,
+    /// Either `base` or the neutral element, depending on the current bit.
+    pub selected_x: EdLimbs<T>,
+    pub selected_y: EdLimbs<T>,
+
+    pub p_access: [MemoryWriteCols<T>; WORDS_CURVE_POINT],
+    pub scalar_access: [MemoryReadCols<T>; WORDS_FIELD_ELEMENT],
+
+    pub(crate) acc_add: EdAddCols<T>,
+    pub(crate) base_double: EdAddCols<T>,
+}
+
+#[derive(Default)]
+pub struct EdScalarMulChip<E> {
+    _marker: PhantomData<E>,
+}
+
+impl<E: EllipticCurve + EdwardsParameters> EdScalarMulChip<E> {
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+}
+
+impl<F: PrimeField32> EdAddCols<F> {
+    /// Populates the columns computing `p + q` and returns the result.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn populate<E: EdwardsParameters>(
+        &mut self,
+        record: &mut impl ByteRecord,
+        shard: u32,
+        channel: u8,
+        p_x: &BigUint,
+        p_y: &BigUint,
+        q_x: &BigUint,
+        q_y: &BigUint,
+    ) -> (BigUint, BigUint) {
+        let x3_numerator = self.x3_numerator.populate(
+            record,
+            shard,
+            channel,
+            &[p_x.clone(), q_x.clone()],
+            &[q_y.clone(), p_y.clone()],
+        );
+        let y3_numerator = self.y3_numerator.populate(
+            record,
+            shard,
+            channel,
+            &[p_y.clone(), p_x.clone()],
+            &[q_y.clone(), q_x.clone()],
+        );
+        let x1_mul_y1 =
+            self.x1_mul_y1.populate(record, shard, channel, p_x, p_y, FieldOperation::Mul);
+        let x2_mul_y2 =
+            self.x2_mul_y2.populate(record, shard, channel, q_x, q_y, FieldOperation::Mul);
+        let f =
+            self.f.populate(record, shard, channel, &x1_mul_y1, &x2_mul_y2, FieldOperation::Mul);
+
+        let d = E::d_biguint();
+        let d_mul_f = self.d_mul_f.populate(record, shard, channel, &f, &d, FieldOperation::Mul);
+
+        let x3 = self.x3_ins.populate(record, shard, channel, &x3_numerator, &d_mul_f, true);
+        let y3 = self.y3_ins.populate(record, shard, channel, &y3_numerator, &d_mul_f, false);
+        (x3, y3)
+    }
+}
+
+impl<V: Copy> EdAddCols<V> {
+    /// Constrains the columns to compute `(x1, y1) + (x2, y2)`.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn eval<AB: SP1AirBuilder<Var = V>, E: EllipticCurve + EdwardsParameters>(
+        &self,
+        builder: &mut AB,
+        x1: EdLimbs<AB::Var>,
+        y1: EdLimbs<AB::Var>,
+        x2: EdLimbs<AB::Var>,
+        y2: EdLimbs<AB::Var>,
+        shard: impl Into<AB::Expr> + Clone,
+        channel: impl Into<AB::Expr> + Clone,
+        is_real: impl Into<AB::Expr> + Clone,
+    ) where
+        V: Into<AB::Expr>,
+    {
+        // x3_numerator = x1 * y2 + x2 * y1.
+        self.x3_numerator.eval(
+            builder,
+            &[x1, x2],
+            &[y2, y1],
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+
+        // y3_numerator = y1 * y2 + x1 * x2.
+        self.y3_numerator.eval(
+            builder,
+            &[y1, x1],
+            &[y2, x2],
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+
+        // f = x1 * x2 * y1 * y2.
+        self.x1_mul_y1.eval(
+            builder,
+            &x1,
+            &y1,
+            FieldOperation::Mul,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        self.x2_mul_y2.eval(
+            builder,
+            &x2,
+            &y2,
+            FieldOperation::Mul,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        self.f.eval(
+            builder,
+            &self.x1_mul_y1.result,
+            &self.x2_mul_y2.result,
+            FieldOperation::Mul,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+
+        // d * f.
+        let d_biguint = E::d_biguint();
+        let d_const = E::BaseField::to_limbs_field::<AB::Expr, _>(&d_biguint);
+        self.d_mul_f.eval(
+            builder,
+            &self.f.result,
+            &d_const,
+            FieldOperation::Mul,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+
+        // x3 = x3_numerator / (1 + d * f).
+        self.x3_ins.eval(
+            builder,
+            &self.x3_numerator.result,
+            &self.d_mul_f.result,
+            true,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+
+        // y3 = y3_numerator / (1 - d * f).
+        self.y3_ins.eval(
+            builder,
+            &self.y3_numerator.result,
+            &self.d_mul_f.result,
+            false,
+            shard,
+            channel,
+            is_real,
+        );
+    }
+}
+
+impl<F: PrimeField32, E: EllipticCurve + EdwardsParameters> MachineAir<F> for EdScalarMulChip<E> {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "EdScalarMul".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = input
+            .ed_scalar_mul_events
+            .par_iter()
+            .map(|event| {
+                let mut blu = Vec::new();
+                self.event_to_rows(event, &mut blu)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        // Pad the trace to a power of two. Since the number of rows per event is a power of two,
+        // the padding rows keep cycling through the same bit positions as real rows.
+        let num_real_rows = rows.len();
+        let num_padded_rows = std::cmp::max(num_real_rows.next_power_of_two(), 16);
+        for i in num_real_rows..num_padded_rows {
+            rows.push(Self::padding_row(i % ED_SCALAR_MUL_NUM_ROWS));
+        }
+
+        // Convert the trace to a row major matrix.
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_ED_SCALAR_MUL_COLS,
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut EdScalarMulCols<F> = trace.values
+                [i * NUM_ED_SCALAR_MUL_COLS..(i + 1) * NUM_ED_SCALAR_MUL_COLS]
+                .borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn generate_dependencies(&self, input: &Self::Record, output: &mut Self::Record) {
+        let chunk_size = std::cmp::max(input.ed_scalar_mul_events.len() / num_cpus::get(), 1);
+
+        let blu_batches = input
+            .ed_scalar_mul_events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<u32, HashMap<ByteLookupEvent, usize>> = HashMap::new();
+                events.iter().for_each(|event| {
+                    self.event_to_rows::<F>(event, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_sharded_byte_lookup_events(blu_batches.iter().collect_vec());
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.ed_scalar_mul_events.is_empty()
+    }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(ED_SCALAR_MUL_NUM_ROWS * shard.ed_scalar_mul_events.len())
+    }
+}
+
+impl<E: EllipticCurve + EdwardsParameters> EdScalarMulChip<E> {
+    /// Create the rows of a scalar multiplication from an event.
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &EdScalarMulEvent,
+        blu: &mut impl ByteRecord,
+    ) -> Vec<[F; NUM_ED_SCALAR_MUL_COLS]> {
+        let p = AffinePoint::<E>::from_words_le(&event.p);
+        let scalar_bytes: [u8; NUM_SCALAR_BYTES] = words_to_bytes_le(&event.scalar);
+
+        let (mut acc_x, mut acc_y) = E::neutral();
+        let (mut base_x, mut base_y) = (p.x.clone(), p.y.clone());
+
+        let mut rows = Vec::with_capacity(ED_SCALAR_MUL_NUM_ROWS);
+        for i in 0..ED_SCALAR_MUL_NUM_ROWS {
+            let mut row = [F::zero(); NUM_ED_SCALAR_MUL_COLS];
+            let cols: &mut EdScalarMulCols<F> = row.as_mut_slice().borrow_mut();
+
+            // Populate basic columns.
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.channel = F::from_canonical_u8(event.channel);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.p_ptr = F::from_canonical_u32(event.p_ptr);
+            cols.scalar_ptr = F::from_canonical_u32(event.scalar_ptr);
+            cols.is_first_row = F::from_bool(i == 0);
+            cols.is_last_row = F::from_bool(i == ED_SCALAR_MUL_NUM_ROWS - 1);
+            Self::populate_flags(cols, i);
+
+            // Populate the scalar bits.
+            for (j, byte) in scalar_bytes.iter().enumerate() {
+                cols.scalar[j] = F::from_canonical_u8(*byte);
+            }
+            let byte = scalar_bytes[i / 8];
+            for k in 0..8 {
+                cols.byte_bits[k] = F::from_canonical_u8((byte >> k) & 1);
+            }
+            let bit = (byte >> (i % 8)) & 1 == 1;
+            cols.bit = F::from_bool(bit);
+
+            // Populate the points.
+            cols.p_x = Ed25519BaseField::to_limbs_field::<F, F>(&p.x);
+            cols.p_y = Ed25519BaseField::to_limbs_field::<F, F>(&p.y);
+            cols.acc_x = Ed25519BaseField::to_limbs_field::<F, F>(&acc_x);
+            cols.acc_y = Ed25519BaseField::to_limbs_field::<F, F>(&acc_y);
+            cols.base_x = Ed25519BaseField::to_limbs_field::<F, F>(&base_x);
+            cols.base_y = Ed25519BaseField::to_limbs_field::<F, F>(&base_y);
+            let (selected_x, selected_y) =
+                if bit { (base_x.clone(), base_y.clone()) } else { E::neutral() };
+            cols.selected_x = Ed25519BaseField::to_limbs_field::<F, F>(&selected_x);
+            cols.selected_y = Ed25519BaseField::to_limbs_field::<F, F>(&selected_y);
+
+            // Populate the double-and-add step.
+            let (next_acc_x, next_acc_y) = cols.acc_add.populate::<E>(
+                blu,
+                event.shard,
+                event.channel,
+                &acc_x,
+                &acc_y,
+                &selected_x,
+                &selected_y,
+            );
+            let (next_base_x, next_base_y) = cols.base_double.populate::<E>(
+                blu,
+                event.shard,
+                event.channel,
+                &base_x,
+                &base_y,
+                &base_x,
+                &base_y,
+            );
+
+            // Populate the memory access columns on the last row.
+            if i == ED_SCALAR_MUL_NUM_ROWS - 1 {
+                for j in 0..WORDS_FIELD_ELEMENT {
+                    cols.scalar_access[j].populate(
+                        event.channel,
+                        event.scalar_memory_records[j],
+                        blu,
+                    );
+                }
+                for j in 0..WORDS_CURVE_POINT {
+                    cols.p_access[j].populate(event.channel, event.p_memory_records[j], blu);
+                }
+            }
+
+            (acc_x, acc_y) = (next_acc_x, next_acc_y);
+            (base_x, base_y) = (next_base_x, next_base_y);
+            rows.push(row);
+        }
+
+        rows
+    }
+
+    /// Create a padding row at the given position within a scalar multiplication.
+    ///
+    /// Padding rows multiply the neutral element, so that the transition constraints between
+    /// consecutive padding rows are satisfied.
+    fn padding_row<F: PrimeField32>(i: usize) -> [F; NUM_ED_SCALAR_MUL_COLS] {
+        let mut row = [F::zero(); NUM_ED_SCALAR_MUL_COLS];
+        let cols: &mut EdScalarMulCols<F> = row.as_mut_slice().borrow_mut();
+        Self::populate_flags(cols, i);
+
+        let (x, y) = E::neutral();
+        let neutral_x = Ed25519BaseField::to_limbs_field::<F, F>(&x);
+        let neutral_y = Ed25519BaseField::to_limbs_field::<F, F>(&y);
+        cols.acc_x = neutral_x.clone();
+        cols.acc_y = neutral_y.clone();
+        cols.base_x = neutral_x.clone();
+        cols.base_y = neutral_y.clone();
+        cols.selected_x = neutral_x;
+        cols.selected_y = neutral_y;
+        cols.acc_add.populate::<E>(&mut vec![], 0, 0, &x, &y, &x, &y);
+        cols.base_double.populate::<E>(&mut vec![], 0, 0, &x, &y, &x, &y);
+        row
+    }
+
+    /// Populate the one-hot flags for the `i`-th row of a scalar multiplication.
+    fn populate_flags<F: PrimeField32>(cols: &mut EdScalarMulCols<F>, i: usize) {
+        cols.bit_flags[i % 8] = F::one();
+        cols.byte_flags[i / 8] = F::one();
+    }
+}
+
+impl<F, E: EllipticCurve + EdwardsParameters> BaseAir<F> for EdScalarMulChip<E> {
+    fn width(&self) -> usize {
+        NUM_ED_SCALAR_MUL_COLS
+    }
+}
+
+impl<AB, E: EllipticCurve + EdwardsParameters> Air<AB> for EdScalarMulChip<E>
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &EdScalarMulCols<AB::Var> = (*local).borrow();
+        let next: &EdScalarMulCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder.when_transition().assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        self.eval_flags(builder, local, next);
+
+        self.eval_scalar_bits(builder, local);
+
+        self.eval_double_and_add(builder, local, next);
+
+        self.eval_memory(builder, local);
+
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::ED_SCALAR_MUL.syscall_id()),
+            local.p_ptr,
+            local.scalar_ptr,
+            local.is_first_row,
+        );
+    }
+}
+
+impl<E: EllipticCurve + EdwardsParameters> EdScalarMulChip<E> {
+    /// Constrains the one-hot flags that track the current bit of the scalar.
+    fn eval_flags<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &EdScalarMulCols<AB::Var>,
+        next: &EdScalarMulCols<AB::Var>,
+    ) {
+        builder.assert_bool(local.is_real);
+
+        // Verify that the flags are one-hot encodings.
+        let mut bit_flags_sum = AB::Expr::zero();
+        for i in 0..8 {
+            builder.assert_bool(local.bit_flags[i]);
+            bit_flags_sum += local.bit_flags[i].into();
+        }
+        builder.assert_one(bit_flags_sum);
+
+        let mut byte_flags_sum = AB::Expr::zero();
+        for i in 0..NUM_SCALAR_BYTES {
+            builder.assert_bool(local.byte_flags[i]);
+            byte_flags_sum += local.byte_flags[i].into();
+        }
+        builder.assert_one(byte_flags_sum);
+
+        // The first row starts at the first bit of the first byte.
+        builder.when_first_row().assert_one(local.bit_flags[0]);
+        builder.when_first_row().assert_one(local.byte_flags[0]);
+
+        // The bit flags rotate every row, and the byte flags rotate at the end of every byte.
+        for i in 0..8 {
+            builder.when_transition().assert_eq(next.bit_flags[(i + 1) % 8], local.bit_flags[i]);
+        }
+        let end_of_byte = local.bit_flags[7];
+        for i in 0..NUM_SCALAR_BYTES {
+            builder.when_transition().assert_eq(
+                next.byte_flags[(i + 1) % NUM_SCALAR_BYTES],
+                local.byte_flags[(i + 1) % NUM_SCALAR_BYTES] * (AB::Expr::one() - end_of_byte)
+                    + local.byte_flags[i] * end_of_byte,
+            );
+        }
+
+        builder.assert_eq(
+            local.is_first_row,
+            local.is_real * local.byte_flags[0] * local.bit_flags[0],
+        );
+        builder.assert_eq(
+            local.is_last_row,
+            local.is_real * local.byte_flags[NUM_SCALAR_BYTES - 1] * local.bit_flags[7],
+        );
+
+        // The columns describing the syscall are constant within a scalar multiplication.
+        let mut within_mul = builder.when_transition();
+        let mut within_mul = within_mul.when(AB::Expr::one() - local.is_last_row);
+        within_mul.assert_eq(next.is_real, local.is_real);
+        within_mul.assert_eq(next.shard, local.shard);
+        within_mul.assert_eq(next.channel, local.channel);
+        within_mul.assert_eq(next.clk, local.clk);
+        within_mul.assert_eq(next.p_ptr, local.p_ptr);
+        within_mul.assert_eq(next.scalar_ptr, local.scalar_ptr);
+    }
+
+    /// Constrains the current bit of the scalar.
+    fn eval_scalar_bits<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &EdScalarMulCols<AB::Var>,
+    ) {
+        // The bits decompose the current scalar byte.
+        let mut byte = AB::Expr::zero();
+        for k in 0..8 {
+            builder.assert_bool(local.byte_bits[k]);
+            byte += local.byte_bits[k] * AB::F::from_canonical_u32(1 << k);
+        }
+        let mut current_byte = AB::Expr::zero();
+        for j in 0..NUM_SCALAR_BYTES {
+            current_byte += local.byte_flags[j] * local.scalar[j];
+        }
+        builder.assert_eq(byte, current_byte);
+
+        // The current bit is selected by the bit flags.
+        let mut bit = AB::Expr::zero();
+        for k in 0..8 {
+            bit += local.bit_flags[k] * local.byte_bits[k];
+        }
+        builder.assert_eq(local.bit, bit);
+    }
+
+    /// Constrains one step of the double-and-add algorithm.
+    fn eval_double_and_add<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &EdScalarMulCols<AB::Var>,
+        next: &EdScalarMulCols<AB::Var>,
+    ) {
+        // On the first row, the accumulator is the neutral element and the base is the input.
+        for k in 0..NUM_LIMBS {
+            builder.when(local.is_first_row).assert_zero(local.acc_x[k]);
+            if k == 0 {
+                builder.when(local.is_first_row).assert_one(local.acc_y[k]);
+            } else {
+                builder.when(local.is_first_row).assert_zero(local.acc_y[k]);
+            }
+            builder.when(local.is_first_row).assert_eq(local.base_x[k], local.p_x[k]);
+            builder.when(local.is_first_row).assert_eq(local.base_y[k], local.p_y[k]);
+        }
+
+        // The selected point is the base if the bit is set, and the neutral element otherwise.
+        for k in 0..NUM_LIMBS {
+            builder.assert_eq(local.selected_x[k], local.bit * local.base_x[k]);
+            if k == 0 {
+                builder.assert_eq(
+                    local.selected_y[k],
+                    local.bit * local.base_y[k] + (AB::Expr::one() - local.bit),
+                );
+            } else {
+                builder.assert_eq(local.selected_y[k], local.bit * local.base_y[k]);
+            }
+        }
+
+        // acc + selected.
+        local.acc_add.eval::<AB, E>(
+            builder,
+            local.acc_x,
+            local.acc_y,
+            local.selected_x,
+            local.selected_y,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // base + base.
+        local.base_double.eval::<AB, E>(
+            builder,
+            local.base_x,
+            local.base_y,
+            local.base_x,
+            local.base_y,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // The results are carried over to the next row within a scalar multiplication.
+        let mut within_mul = builder.when_transition();
+        let mut within_mul = within_mul.when(AB::Expr::one() - local.is_last_row);
+        within_mul.assert_all_eq(next.acc_x, local.acc_add.x3_ins.result);
+        within_mul.assert_all_eq(next.acc_y, local.acc_add.y3_ins.result);
+        within_mul.assert_all_eq(next.base_x, local.base_double.x3_ins.result);
+        within_mul.assert_all_eq(next.base_y, local.base_double.y3_ins.result);
+        within_mul.assert_all_eq(next.p_x, local.p_x);
+        within_mul.assert_all_eq(next.p_y, local.p_y);
+        within_mul.assert_all_eq(next.scalar, local.scalar);
+    }
+
+    /// Constrains the memory accesses, which happen on the last row.
+    fn eval_memory<AB: SP1AirBuilder>(&self, builder: &mut AB, local: &EdScalarMulCols<AB::Var>) {
+        // The scalar is read from memory.
+        for i in 0..WORDS_FIELD_ELEMENT {
+            for j in 0..4 {
+                builder
+                    .when(local.is_last_row)
+                    .assert_eq(local.scalar_access[i].value()[j], local.scalar[i * 4 + j]);
+            }
+        }
+
+        // The point is read from and written back to memory.
+        for i in 0..WORDS_FIELD_ELEMENT {
+            for j in 0..4 {
+                let k = i * 4 + j;
+                let mut when_last_row = builder.when(local.is_last_row);
+                when_last_row.assert_eq(local.p_access[i].prev_value()[j], local.p_x[k]);
+                when_last_row.assert_eq(
+                    local.p_access[i + WORDS_FIELD_ELEMENT].prev_value()[j],
+                    local.p_y[k],
+                );
+                when_last_row
+                    .assert_eq(local.p_access[i].value()[j], local.acc_add.x3_ins.result[k]);
+                when_last_row.assert_eq(
+                    local.p_access[i + WORDS_FIELD_ELEMENT].value()[j],
+                    local.acc_add.y3_ins.result[k],
+                );
+            }
+        }
+
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk.into(),
+            local.scalar_ptr,
+            &local.scalar_access,
+            local.is_last_row,
+        );
+
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk + AB::F::from_canonical_u32(1),
+            local.p_ptr,
+            &local.p_access,
+            local.is_last_row,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use sp1_core_executor::{syscalls::SyscallCode, Executor, Instruction, Opcode, Program};
+    use sp1_curves::{
+        edwards::{ed25519::Ed25519, EdwardsParameters},
+        AffinePoint,
+    };
+    use sp1_stark::{CpuProver, SP1CoreOpts};
+
+    use crate::utils::{self, run_test};
+
+    const P_PTR: u32 = 100;
+    const SCALAR_PTR: u32 = 200;
+
+    fn ed_scalar_mul_program(scalar: &[u32; 8]) -> Program {
+        let (x, y) = <Ed25519 as EdwardsParameters>::generator();
+        let p = AffinePoint::<Ed25519>::new(x, y).to_words_le();
+
+        let mut instructions = vec![];
+        for (i, word) in p.iter().enumerate() {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, P_PTR + i as u32 * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        for (i, word) in scalar.iter().enumerate() {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, SCALAR_PTR + i as u32 * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::ED_SCALAR_MUL as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, P_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, SCALAR_PTR, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_ed_scalar_mul_execute() {
+        utils::setup_logger();
+        let scalar = [0xdead_beef, 0, 7, 0, 0, 0, 0, 0x8000_0001];
+        let program = ed_scalar_mul_program(&scalar);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        let (x, y) = <Ed25519 as EdwardsParameters>::generator();
+        let expected = &AffinePoint::<Ed25519>::new(x, y) * &BigUint::from_slice(&scalar);
+        let result = (0..16).map(|i| runtime.word(P_PTR + i * 4)).collect::<Vec<_>>();
+        assert_eq!(result, expected.to_words_le());
+    }
+
+    #[test]
+    fn test_ed_scalar_mul_prove() {
+        utils::setup_logger();
+        let program = ed_scalar_mul_program(&[5, 0, 0, 0, 0, 0, 0, 0]);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
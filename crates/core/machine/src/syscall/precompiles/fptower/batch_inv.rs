@@ -0,0 +1,371 @@
+use std::{
+    borrow::{Borrow, BorrowMut},
+    marker::PhantomData,
+    mem::size_of,
+};
+
+use generic_array::GenericArray;
+use hashbrown::HashMap;
+use itertools::Itertools;
+use num::{BigUint, One, Zero};
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{AbstractField, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::{IntoParallelRefIterator, ParallelIterator, ParallelSlice};
+use sp1_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, FieldOperation, FpBatchInvEvent, FP_BATCH_INV_MAX_LEN},
+    syscalls::SyscallCode,
+    ByteOpcode, ExecutionRecord, Program,
+};
+use sp1_curves::{
+    params::{FieldParameters, Limbs, NumLimbs},
+    weierstrass::{FieldType, FpOpField},
+};
+use sp1_derive::AlignedBorrow;
+use sp1_stark::air::{BaseAirBuilder, MachineAir, Polynomial, SP1AirBuilder};
+
+use crate::{
+    air::MemoryAirBuilder,
+    memory::{value_as_limbs, MemoryWriteCols},
+    operations::{
+        eval_multi_row_flags,
+        field::{field_op::FieldOpCols, range::FieldLtCols},
+        populate_multi_row_nonces, IsZeroOperation, MultiRowFlags,
+    },
+    utils::{limbs_from_prev_access, pad_rows, words_to_bytes_le_vec},
+};
+
+pub const fn num_fp_batch_inv_cols<P: FpOpField>() -> usize {
+    size_of::<FpBatchInvCols<u8, P>>()
+}
+
+pub struct FpBatchInvChip<P> {
+    _marker: PhantomData<P>,
+}
+
+/// A set of columns to check the inverse of one element of a field batch inversion.
+///
+/// A batch inversion of `len` elements uses `len` rows. The `i`-th row replaces the `i`-th
+/// element `x` by its inverse, and checks that `x * inverse = 1` and that the inverse is reduced.
+/// The syscall is received on the first row.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct FpBatchInvCols<T, P: FpOpField> {
+    pub is_real: T,
+    pub shard: T,
+    pub channel: T,
+    pub nonce: T,
+    pub clk: T,
+    pub x_ptr: T,
+
+    /// The number of elements of the batch.
+    pub len: T,
+
+    /// Whether this is the first row of a real batch.
+    pub is_first_row: T,
+    /// Whether this is the last row of a real batch.
+    pub is_last_row: T,
+
+    /// The index of the element in the batch.
+    pub i: T,
+    /// Whether `i` is the index of the last element.
+    pub is_last: IsZeroOperation<T>,
+
+    pub x_access: GenericArray<MemoryWriteCols<T>, P::WordsFieldElement>,
+
+    /// The inverse of the element, computed as `1 / x`.
+    pub(crate) inverse: FieldOpCols<T, P>,
+    /// Checks that the inverse is less than the modulus.
+    pub(crate) inverse_range: FieldLtCols<T, P>,
+}
+
+impl<T: Copy, P: FpOpField> FpBatchInvCols<T, P> {
+    /// The columns delimiting the operations of the chip.
+    pub fn multi_row_flags(&self) -> MultiRowFlags<T> {
+        MultiRowFlags {
+            is_real: self.is_real,
+            is_first_row: self.is_first_row,
+            is_last_row: self.is_last_row,
+            nonce: self.nonce,
+        }
+    }
+}
+
+impl<P: FpOpField> FpBatchInvChip<P> {
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+
+    fn events(record: &ExecutionRecord) -> &[FpBatchInvEvent] {
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => &record.bn254_fp_batch_inv_events,
+            FieldType::Bls12381 => &record.bls12381_fp_batch_inv_events,
+        }
+    }
+}
+
+impl<F: PrimeField32, P: FpOpField> MachineAir<F> for FpBatchInvChip<P> {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => "Bn254FpBatchInv".to_string(),
+            FieldType::Bls12381 => "Bls12381FpBatchInv".to_string(),
+        }
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = Self::events(input)
+            .par_iter()
+            .map(|event| {
+                let mut blu = Vec::new();
+                Self::event_to_rows(event, &mut blu)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        pad_rows(&mut rows, Self::padding_row);
+
+        let num_cols = num_fp_batch_inv_cols::<P>();
+        let mut trace =
+            RowMajorMatrix::new(rows.into_iter().flatten().collect::<Vec<_>>(), num_cols);
+
+        // Write the nonces to the trace.
+        populate_multi_row_nonces(&mut trace, |cols: &mut FpBatchInvCols<F, P>| {
+            (cols.is_first_row, &mut cols.nonce)
+        });
+
+        trace
+    }
+
+    fn generate_dependencies(&self, input: &Self::Record, output: &mut Self::Record) {
+        let events = Self::events(input);
+        let chunk_size = std::cmp::max(events.len() / num_cpus::get(), 1);
+
+        let blu_batches = events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<u32, HashMap<ByteLookupEvent, usize>> = HashMap::new();
+                events.iter().for_each(|event| {
+                    Self::event_to_rows::<F>(event, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_sharded_byte_lookup_events(blu_batches.iter().collect_vec());
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !Self::events(shard).is_empty()
+    }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(Self::events(shard).iter().map(|event| event.len as usize).sum())
+    }
+}
+
+impl<P: FpOpField> FpBatchInvChip<P> {
+    /// Create the rows of a batch inversion from an event.
+    fn event_to_rows<F: PrimeField32>(
+        event: &FpBatchInvEvent,
+        blu: &mut impl ByteRecord,
+    ) -> Vec<Vec<F>> {
+        let num_words = P::NB_LIMBS / 4;
+        let modulus = BigUint::from_bytes_le(P::MODULUS);
+
+        let mut rows = Vec::with_capacity(event.len as usize);
+        for i in 0..event.len as usize {
+            let mut row = vec![F::zero(); num_fp_batch_inv_cols::<P>()];
+            let cols: &mut FpBatchInvCols<F, P> = row.as_mut_slice().borrow_mut();
+
+            // Populate basic columns.
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.channel = F::from_canonical_u8(event.channel);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.x_ptr = F::from_canonical_u32(event.x_ptr);
+            cols.len = F::from_canonical_u32(event.len);
+
+            // Populate the position of the row.
+            cols.is_first_row = F::from_bool(i == 0);
+            cols.is_last_row = F::from_bool(i == event.len as usize - 1);
+            cols.i = F::from_canonical_usize(i);
+            cols.is_last.populate_from_field_element(cols.i + F::one() - cols.len);
+
+            // Populate the memory accesses.
+            for k in 0..num_words {
+                let w = i * num_words + k;
+                cols.x_access[k].populate(event.channel, event.x_memory_records[w], blu);
+            }
+
+            // Populate the inverse.
+            let x = BigUint::from_bytes_le(&words_to_bytes_le_vec(
+                &event.x[i * num_words..(i + 1) * num_words],
+            ));
+            let inverse = cols.inverse.populate_with_modulus(
+                blu,
+                event.shard,
+                event.channel,
+                &BigUint::one(),
+                &x,
+                &modulus,
+                FieldOperation::Div,
+            );
+            cols.inverse_range.populate(blu, event.shard, event.channel, &inverse, &modulus);
+
+            rows.push(row);
+        }
+
+        // Send the range check on the number of elements.
+        blu.add_byte_lookup_event(ByteLookupEvent {
+            shard: event.shard,
+            channel: event.channel,
+            opcode: ByteOpcode::LTU,
+            a1: 1,
+            a2: 0,
+            b: (event.len - 1) as u8,
+            c: FP_BATCH_INV_MAX_LEN as u8,
+        });
+
+        rows
+    }
+
+    /// Create a padding row, which computes `0 / 0 = 0`.
+    fn padding_row<F: PrimeField32>() -> Vec<F> {
+        let mut row = vec![F::zero(); num_fp_batch_inv_cols::<P>()];
+        let cols: &mut FpBatchInvCols<F, P> = row.as_mut_slice().borrow_mut();
+        let zero = BigUint::zero();
+        cols.inverse.populate_with_modulus(
+            &mut vec![],
+            0,
+            0,
+            &zero,
+            &zero,
+            &BigUint::from_bytes_le(P::MODULUS),
+            FieldOperation::Div,
+        );
+        row
+    }
+}
+
+impl<F, P: FpOpField> BaseAir<F> for FpBatchInvChip<P> {
+    fn width(&self) -> usize {
+        num_fp_batch_inv_cols::<P>()
+    }
+}
+
+impl<AB, P: FpOpField> Air<AB> for FpBatchInvChip<P>
+where
+    AB: SP1AirBuilder,
+    Limbs<AB::Var, <P as NumLimbs>::Limbs>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &FpBatchInvCols<AB::Var, P> = (*local).borrow();
+        let next: &FpBatchInvCols<AB::Var, P> = (*next).borrow();
+
+        eval_multi_row_flags(builder, local.multi_row_flags(), next.multi_row_flags());
+
+        builder.assert_bool(local.is_real);
+
+        // The batch ends on the row of its last element.
+        builder.when(local.is_first_row).assert_zero(local.i);
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.i + AB::Expr::one() - local.len,
+            local.is_last,
+            local.is_real.into(),
+        );
+        builder.assert_eq(local.is_last_row, local.is_real * local.is_last.result);
+
+        // Within a batch, the columns describing the syscall are constant, and the index advances
+        // to the next element.
+        let within_op = local.is_real - local.is_last_row;
+        let mut within_op = builder.when_transition().when(within_op);
+        within_op.assert_eq(next.is_real, local.is_real);
+        within_op.assert_eq(next.shard, local.shard);
+        within_op.assert_eq(next.channel, local.channel);
+        within_op.assert_eq(next.clk, local.clk);
+        within_op.assert_eq(next.x_ptr, local.x_ptr);
+        within_op.assert_eq(next.len, local.len);
+        within_op.assert_eq(next.i, local.i + AB::Expr::one());
+
+        // The number of elements must be between 1 and the maximum.
+        builder.send_byte(
+            ByteOpcode::LTU.as_field::<AB::F>(),
+            AB::F::one(),
+            local.len - AB::Expr::one(),
+            AB::F::from_canonical_usize(FP_BATCH_INV_MAX_LEN),
+            local.shard,
+            local.channel,
+            local.is_first_row,
+        );
+
+        // Check that `x * inverse = 1`. The numerator is `is_real` so that padding rows compute
+        // `0 / 0 = 0`.
+        let x: Limbs<AB::Var, <P as NumLimbs>::Limbs> = limbs_from_prev_access(&local.x_access);
+        let modulus_coeffs =
+            P::MODULUS.iter().map(|&limbs| AB::Expr::from_canonical_u8(limbs)).collect_vec();
+        let p_modulus = Polynomial::from_coefficients(&modulus_coeffs);
+        let p_one = Polynomial::from_coefficients(&[local.is_real.into()]);
+        local.inverse.eval_with_modulus(
+            builder,
+            &p_one,
+            &x,
+            &p_modulus,
+            FieldOperation::Div,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // Check that the inverse is reduced, so that it is unique.
+        let modulus_limbs = P::to_limbs_field::<AB::Expr, AB::F>(&P::modulus());
+        local.inverse_range.eval(
+            builder,
+            &local.inverse.result,
+            &modulus_limbs,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // The element is replaced by its inverse.
+        builder
+            .when(local.is_real)
+            .assert_all_eq(local.inverse.result, value_as_limbs(&local.x_access));
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk.into(),
+            local.x_ptr + local.i * AB::F::from_canonical_usize(P::NB_LIMBS),
+            &local.x_access,
+            local.is_real,
+        );
+
+        let syscall_id = match P::FIELD_TYPE {
+            FieldType::Bn254 => SyscallCode::BN254_FP_BATCH_INV.syscall_id(),
+            FieldType::Bls12381 => SyscallCode::BLS12381_FP_BATCH_INV.syscall_id(),
+        };
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(syscall_id),
+            local.x_ptr,
+            local.len,
+            local.is_first_row,
+        );
+    }
+}
@@ -185,6 +185,14 @@ impl<F: PrimeField32, P: FpOpField> MachineAir<F> for FpOpChip<P> {
             FieldType::Bls12381 => !shard.bls12381_fp_events.is_empty(),
         }
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        let events = match P::FIELD_TYPE {
+            FieldType::Bn254 => &shard.bn254_fp_events,
+            FieldType::Bls12381 => &shard.bls12381_fp_events,
+        };
+        Some(events.len())
+    }
 }
 
 impl<F, P: FpOpField> BaseAir<F> for FpOpChip<P> {
@@ -246,6 +246,14 @@ impl<F: PrimeField32, P: FpOpField> MachineAir<F> for Fp2MulAssignChip<P> {
             FieldType::Bls12381 => !shard.bls12381_fp2_mul_events.is_empty(),
         }
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        let events = match P::FIELD_TYPE {
+            FieldType::Bn254 => &shard.bn254_fp2_mul_events,
+            FieldType::Bls12381 => &shard.bls12381_fp2_mul_events,
+        };
+        Some(events.len())
+    }
 }
 
 impl<F, P: FpOpField> BaseAir<F> for Fp2MulAssignChip<P> {
@@ -1,25 +1,90 @@
+mod batch_inv;
 mod fp;
 mod fp2_addsub;
 mod fp2_mul;
 
+pub use batch_inv::*;
 pub use fp::*;
 pub use fp2_addsub::*;
 pub use fp2_mul::*;
 
 #[cfg(test)]
 mod tests {
-    use sp1_stark::CpuProver;
+    use num::{BigUint, One};
+    use sp1_curves::{
+        params::FieldParameters,
+        weierstrass::{bls12_381::Bls12381BaseField, bn254::Bn254BaseField},
+    };
+    use sp1_stark::{CpuProver, SP1CoreOpts};
 
     use sp1_core_executor::{
         programs::tests::{
             BLS12381_FP2_ADDSUB_ELF, BLS12381_FP2_MUL_ELF, BLS12381_FP_ELF, BN254_FP2_ADDSUB_ELF,
             BN254_FP2_MUL_ELF, BN254_FP_ELF,
         },
-        Program,
+        syscalls::SyscallCode,
+        Executor, Instruction, Opcode, Program,
     };
 
     use crate::utils;
 
+    const X_PTR: u32 = 100;
+
+    /// A program that inverts the given elements of `num_words` words in place.
+    fn batch_inv_program(syscall: SyscallCode, elements: &[BigUint], num_words: usize) -> Program {
+        let mut instructions = vec![];
+        for (i, element) in elements.iter().enumerate() {
+            let mut words = element.to_u32_digits();
+            words.resize(num_words, 0);
+            for (j, word) in words.into_iter().enumerate() {
+                let ptr = X_PTR + ((i * num_words + j) * 4) as u32;
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, syscall as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, X_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, elements.len() as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    /// Some non-zero elements, including one that is not reduced.
+    fn batch_inv_elements<P: FieldParameters>() -> Vec<BigUint> {
+        let modulus = P::modulus();
+        vec![
+            BigUint::one(),
+            BigUint::from(0xdead_beefu32),
+            &modulus - BigUint::one(),
+            (BigUint::one() << 200) + BigUint::from(7u32),
+            &modulus + BigUint::from(2u32),
+        ]
+    }
+
+    fn test_batch_inv<P: FieldParameters>(syscall: SyscallCode, num_words: usize) {
+        let modulus = P::modulus();
+        let elements = batch_inv_elements::<P>();
+        let program = batch_inv_program(syscall, &elements, num_words);
+
+        let mut runtime = Executor::new(program.clone(), SP1CoreOpts::default());
+        runtime.run().unwrap();
+        for (i, element) in elements.iter().enumerate() {
+            let words = (0..num_words)
+                .map(|j| runtime.word(X_PTR + ((i * num_words + j) * 4) as u32))
+                .collect::<Vec<_>>();
+            let inverse = BigUint::from_slice(&words);
+            assert!(inverse < modulus);
+            assert_eq!((element * inverse) % &modulus, BigUint::one());
+        }
+
+        utils::run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
     #[test]
     fn test_bls12381_fp() {
         utils::setup_logger();
@@ -61,4 +126,16 @@ mod tests {
         let program = Program::from(BN254_FP2_MUL_ELF).unwrap();
         utils::run_test::<CpuProver<_, _>>(program).unwrap();
     }
+
+    #[test]
+    fn test_bls12381_fp_batch_inv() {
+        utils::setup_logger();
+        test_batch_inv::<Bls12381BaseField>(SyscallCode::BLS12381_FP_BATCH_INV, 12);
+    }
+
+    #[test]
+    fn test_bn254_fp_batch_inv() {
+        utils::setup_logger();
+        test_batch_inv::<Bn254BaseField>(SyscallCode::BN254_FP_BATCH_INV, 8);
+    }
 }
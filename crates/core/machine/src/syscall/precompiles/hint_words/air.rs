@@ -0,0 +1,295 @@
+use core::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{AbstractField, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::{IntoParallelRefIterator, ParallelIterator, ParallelSlice};
+use sp1_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, HintWordsEvent, HINT_WORDS_MAX_WORDS},
+    syscalls::SyscallCode,
+    ByteOpcode, ExecutionRecord, Program,
+};
+use sp1_derive::AlignedBorrow;
+use sp1_primitives::consts::WORD_SIZE;
+use sp1_stark::air::{BaseAirBuilder, MachineAir, SP1AirBuilder};
+
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::{MemoryCols, MemoryWriteCols},
+    operations::{eval_multi_row_flags, populate_multi_row_nonces, IsZeroOperation, MultiRowFlags},
+    utils::pad_rows,
+};
+
+pub const NUM_HINT_WORDS_COLS: usize = size_of::<HintWordsCols<u8>>();
+
+/// A set of columns to write one hinted word to memory.
+///
+/// An operation on `num_words` words uses `num_words` rows. The `i`-th row writes the `i`-th word
+/// of the hint at `dst_ptr + 4 * i`. The hint itself is not constrained beyond its bytes being in
+/// range, and the syscall is received on the first row.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct HintWordsCols<T> {
+    pub is_real: T,
+    pub shard: T,
+    pub channel: T,
+    pub clk: T,
+    pub nonce: T,
+    pub dst_ptr: T,
+    pub num_words: T,
+
+    /// Whether this is the first row of a real operation.
+    pub is_first_row: T,
+    /// Whether this is the last row of a real operation.
+    pub is_last_row: T,
+
+    /// The index of the word.
+    pub i: T,
+    /// Whether `i` is the index of the last word.
+    pub is_last: IsZeroOperation<T>,
+
+    /// The hinted word.
+    pub dst_access: MemoryWriteCols<T>,
+}
+
+impl<T: Copy> HintWordsCols<T> {
+    /// The columns delimiting the operations of the chip.
+    pub fn multi_row_flags(&self) -> MultiRowFlags<T> {
+        MultiRowFlags {
+            is_real: self.is_real,
+            is_first_row: self.is_first_row,
+            is_last_row: self.is_last_row,
+            nonce: self.nonce,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct HintWordsChip;
+
+impl HintWordsChip {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl<F: PrimeField32> MachineAir<F> for HintWordsChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "HintWords".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = input
+            .hint_words_events
+            .par_iter()
+            .map(|event| {
+                let mut blu = Vec::new();
+                self.event_to_rows(event, &mut blu)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        pad_rows(&mut rows, || [F::zero(); NUM_HINT_WORDS_COLS]);
+
+        // Convert the trace to a row major matrix.
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_HINT_WORDS_COLS,
+        );
+
+        // Write the nonces to the trace.
+        populate_multi_row_nonces(&mut trace, |cols: &mut HintWordsCols<F>| {
+            (cols.is_first_row, &mut cols.nonce)
+        });
+
+        trace
+    }
+
+    fn generate_dependencies(&self, input: &Self::Record, output: &mut Self::Record) {
+        let chunk_size = std::cmp::max(input.hint_words_events.len() / num_cpus::get(), 1);
+
+        let blu_batches = input
+            .hint_words_events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<u32, HashMap<ByteLookupEvent, usize>> = HashMap::new();
+                events.iter().for_each(|event| {
+                    self.event_to_rows::<F>(event, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_sharded_byte_lookup_events(blu_batches.iter().collect_vec());
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.hint_words_events.is_empty()
+    }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.hint_words_events.iter().map(|event| event.num_words as usize).sum())
+    }
+}
+
+impl HintWordsChip {
+    /// Create the rows of a hint words operation from an event.
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &HintWordsEvent,
+        blu: &mut impl ByteRecord,
+    ) -> Vec<[F; NUM_HINT_WORDS_COLS]> {
+        let n = event.num_words as usize;
+
+        let mut rows = Vec::with_capacity(n);
+        for (i, (word, record)) in
+            event.words.iter().zip_eq(event.dst_memory_records.iter()).enumerate()
+        {
+            let mut row = [F::zero(); NUM_HINT_WORDS_COLS];
+            let cols: &mut HintWordsCols<F> = row.as_mut_slice().borrow_mut();
+
+            // Populate basic columns.
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.channel = F::from_canonical_u8(event.channel);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.dst_ptr = F::from_canonical_u32(event.dst_ptr);
+            cols.num_words = F::from_canonical_u32(event.num_words);
+
+            // Populate the position of the row.
+            cols.is_first_row = F::from_bool(i == 0);
+            cols.is_last_row = F::from_bool(i == n - 1);
+            cols.i = F::from_canonical_usize(i);
+            cols.is_last.populate_from_field_element(cols.i + F::one() - cols.num_words);
+
+            // Populate the memory access, and range check the bytes of the word.
+            cols.dst_access.populate(event.channel, *record, blu);
+            blu.add_u8_range_checks(event.shard, event.channel, &word.to_le_bytes());
+
+            rows.push(row);
+        }
+
+        // Send the range check on the number of words.
+        blu.add_byte_lookup_event(ByteLookupEvent {
+            shard: event.shard,
+            channel: event.channel,
+            opcode: ByteOpcode::LTU,
+            a1: 1,
+            a2: 0,
+            b: (event.num_words - 1) as u8,
+            c: HINT_WORDS_MAX_WORDS as u8,
+        });
+
+        rows
+    }
+}
+
+impl<F> BaseAir<F> for HintWordsChip {
+    fn width(&self) -> usize {
+        NUM_HINT_WORDS_COLS
+    }
+}
+
+impl<AB> Air<AB> for HintWordsChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &HintWordsCols<AB::Var> = (*local).borrow();
+        let next: &HintWordsCols<AB::Var> = (*next).borrow();
+
+        eval_multi_row_flags(builder, local.multi_row_flags(), next.multi_row_flags());
+
+        self.eval_flags(builder, local, next);
+
+        // The number of words must be between 1 and the maximum.
+        builder.send_byte(
+            ByteOpcode::LTU.as_field::<AB::F>(),
+            AB::F::one(),
+            local.num_words - AB::Expr::one(),
+            AB::F::from_canonical_usize(HINT_WORDS_MAX_WORDS),
+            local.shard,
+            local.channel,
+            local.is_first_row,
+        );
+
+        // The hinted word is free, but its bytes must be in range for the instructions that read
+        // it back.
+        builder.slice_range_check_u8(
+            &local.dst_access.value().0,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        builder.eval_memory_access(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.dst_ptr + local.i * AB::F::from_canonical_usize(WORD_SIZE),
+            &local.dst_access,
+            local.is_real,
+        );
+
+        // Receive the arguments.
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::HINT_READ_WORDS.syscall_id()),
+            local.dst_ptr,
+            local.num_words,
+            local.is_first_row,
+        );
+    }
+}
+
+impl HintWordsChip {
+    /// Constrains the flags and counters that track the position of a row within an operation.
+    fn eval_flags<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &HintWordsCols<AB::Var>,
+        next: &HintWordsCols<AB::Var>,
+    ) {
+        builder.assert_bool(local.is_real);
+
+        // The operation ends on the row of its last word.
+        builder.when(local.is_first_row).assert_zero(local.i);
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.i + AB::Expr::one() - local.num_words,
+            local.is_last,
+            local.is_real.into(),
+        );
+        builder.assert_eq(local.is_last_row, local.is_real * local.is_last.result);
+
+        // Within an operation, the columns describing the syscall are constant, and the index
+        // advances to the next word.
+        let within_op = local.is_real - local.is_last_row;
+        let mut within_op = builder.when_transition().when(within_op);
+        within_op.assert_eq(next.is_real, local.is_real);
+        within_op.assert_eq(next.shard, local.shard);
+        within_op.assert_eq(next.channel, local.channel);
+        within_op.assert_eq(next.clk, local.clk);
+        within_op.assert_eq(next.dst_ptr, local.dst_ptr);
+        within_op.assert_eq(next.num_words, local.num_words);
+        within_op.assert_eq(next.i, local.i + AB::Expr::one());
+    }
+}
@@ -0,0 +1,72 @@
+mod air;
+
+pub use air::*;
+
+#[cfg(test)]
+mod tests {
+    use sp1_core_executor::{
+        syscalls::SyscallCode, Executor, Instruction, Opcode, Program, Register,
+    };
+    use sp1_stark::{CpuProver, SP1CoreOpts};
+
+    use crate::{
+        io::SP1Stdin,
+        utils::{self, run_test_io},
+    };
+
+    const DST_PTR: u32 = 100;
+
+    /// A program overwriting `num_words` initialized words at `DST_PTR` with a hint, and reading
+    /// the first one back.
+    fn hint_words_program(num_words: u32) -> Program {
+        let mut instructions = vec![];
+        for i in 0..num_words {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 29, 0, 0xdead_beef, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, DST_PTR + i * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HINT_READ_WORDS as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, DST_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, num_words, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 30, 0, DST_PTR, false, true),
+            Instruction::new(Opcode::LW, 29, 30, 0, false, true),
+        ]);
+
+        Program::new(instructions, 0, 0)
+    }
+
+    /// The stdin holding the words as a single element, without the length written by
+    /// [`SP1Stdin::write_words`].
+    fn stdin(words: &[u32]) -> SP1Stdin {
+        let mut stdin = SP1Stdin::new();
+        stdin.write_slice(&words.iter().flat_map(|word| word.to_le_bytes()).collect::<Vec<_>>());
+        stdin
+    }
+
+    #[test]
+    fn test_hint_words_execute() {
+        utils::setup_logger();
+        let words = (0..5).map(|i| 0x0102_0304 * i).collect::<Vec<_>>();
+        let mut runtime = Executor::new(hint_words_program(5), SP1CoreOpts::default());
+        runtime.write_vecs(&stdin(&words).buffer);
+        runtime.run().unwrap();
+
+        for (i, &word) in words.iter().enumerate() {
+            assert_eq!(runtime.word(DST_PTR + i as u32 * 4), word);
+        }
+        assert_eq!(runtime.register(Register::X29), words[0]);
+    }
+
+    #[test]
+    fn test_hint_words_prove() {
+        utils::setup_logger();
+        for num_words in [1, 7, 32] {
+            let words = (0..num_words).map(|i| u32::MAX - i * 0x0101_0101).collect::<Vec<_>>();
+            run_test_io::<CpuProver<_, _>>(hint_words_program(num_words), stdin(&words)).unwrap();
+        }
+    }
+}
@@ -5,11 +5,11 @@ use p3_field::AbstractField;
 use p3_keccak_air::{KeccakAir, NUM_KECCAK_COLS, NUM_ROUNDS, U64_LIMBS};
 use p3_matrix::Matrix;
 use sp1_core_executor::syscalls::SyscallCode;
-use sp1_stark::air::{SP1AirBuilder, SubAirBuilder};
+use sp1_stark::air::{AirInteraction, SP1AirBuilder, SubAirBuilder};
 
 use super::{
     columns::{KeccakMemCols, NUM_KECCAK_MEM_COLS},
-    KeccakPermuteChip, STATE_NUM_WORDS, STATE_SIZE,
+    KeccakPermuteChip, KECCAK_PERMUTE_INTERACTION, STATE_NUM_WORDS, STATE_SIZE,
 };
 use crate::{
     air::{MemoryAirBuilder, WordAirBuilder},
@@ -124,6 +124,18 @@ where
             }
         }
 
+        // Send the pre-state and post-state to the memoized calls reusing them. The preimage
+        // columns hold the pre-state throughout the 24 rows, so the final step row has both.
+        let pre_state = (0..STATE_SIZE).flat_map(|i| local.keccak.preimage[i / 5][i % 5]);
+        let post_state = (0..STATE_SIZE).flat_map(|i| {
+            (0..U64_LIMBS).map(move |limb| local.keccak.a_prime_prime_prime(i / 5, i % 5, limb))
+        });
+        builder.send(AirInteraction::new(
+            pre_state.chain(post_state).map(Into::into).collect(),
+            final_step * local.memo_uses,
+            KECCAK_PERMUTE_INTERACTION,
+        ));
+
         // Range check all the values in `state_mem` to be bytes.
         for i in 0..STATE_NUM_WORDS {
             builder.slice_range_check_u8(
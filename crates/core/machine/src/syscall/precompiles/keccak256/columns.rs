@@ -32,6 +32,9 @@ pub(crate) struct KeccakMemCols<T> {
     // If row is real and first cycle of 24-cycle
     pub receive_ecall: T,
 
+    /// The number of memoized calls reusing the result of the permutation.
+    pub memo_uses: T,
+
     pub is_real: T,
 }
 
@@ -0,0 +1,203 @@
+use core::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{AbstractField, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use sp1_core_executor::{
+    events::{ByteRecord, KeccakPermuteMemoEvent},
+    syscalls::SyscallCode,
+    ExecutionRecord, Program,
+};
+use sp1_derive::AlignedBorrow;
+use sp1_stark::{
+    air::{AirInteraction, MachineAir, SP1AirBuilder},
+    Word,
+};
+
+use super::{KECCAK_PERMUTE_INTERACTION, STATE_NUM_WORDS};
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::{MemoryCols, MemoryWriteCols},
+    utils::pad_rows,
+};
+
+pub const NUM_KECCAK_PERMUTE_MEMO_COLS: usize = size_of::<KeccakPermuteMemoCols<u8>>();
+
+/// A set of columns for a keccak permutation which reuses the result of an earlier one.
+///
+/// The state is overwritten with the post-state in a single row, and the pair of states is
+/// received from the final step row of the earlier permutation in [`super::KeccakPermuteChip`].
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct KeccakPermuteMemoCols<T> {
+    pub shard: T,
+    pub channel: T,
+    pub clk: T,
+    pub nonce: T,
+    pub state_addr: T,
+
+    /// The memory columns for the state, whose previous values hold the pre-state.
+    pub state_mem: [MemoryWriteCols<T>; STATE_NUM_WORDS],
+
+    pub is_real: T,
+}
+
+#[derive(Default)]
+pub struct KeccakPermuteMemoChip;
+
+impl KeccakPermuteMemoChip {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl<F: PrimeField32> MachineAir<F> for KeccakPermuteMemoChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "KeccakPermuteMemo".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut new_byte_lookup_events = Vec::new();
+        let mut rows = input
+            .keccak_permute_memo_events
+            .iter()
+            .map(|event| self.event_to_row(event, &mut new_byte_lookup_events))
+            .collect::<Vec<_>>();
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || [F::zero(); NUM_KECCAK_PERMUTE_MEMO_COLS]);
+
+        // Convert the trace to a row major matrix.
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_KECCAK_PERMUTE_MEMO_COLS,
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut KeccakPermuteMemoCols<F> = trace.values
+                [i * NUM_KECCAK_PERMUTE_MEMO_COLS..(i + 1) * NUM_KECCAK_PERMUTE_MEMO_COLS]
+                .borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.keccak_permute_memo_events.is_empty()
+    }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.keccak_permute_memo_events.len())
+    }
+}
+
+impl KeccakPermuteMemoChip {
+    /// Create the row of a memoized permutation from an event.
+    fn event_to_row<F: PrimeField32>(
+        &self,
+        event: &KeccakPermuteMemoEvent,
+        blu: &mut impl ByteRecord,
+    ) -> [F; NUM_KECCAK_PERMUTE_MEMO_COLS] {
+        let mut row = [F::zero(); NUM_KECCAK_PERMUTE_MEMO_COLS];
+        let cols: &mut KeccakPermuteMemoCols<F> = row.as_mut_slice().borrow_mut();
+
+        cols.is_real = F::one();
+        cols.shard = F::from_canonical_u32(event.shard);
+        cols.channel = F::from_canonical_u8(event.channel);
+        cols.clk = F::from_canonical_u32(event.clk);
+        cols.state_addr = F::from_canonical_u32(event.state_addr);
+
+        // Populate the memory accesses, and range check the bytes of both states.
+        for (mem, record) in cols.state_mem.iter_mut().zip(event.state_write_records.iter()) {
+            mem.populate(event.channel, *record, blu);
+            blu.add_u8_range_checks(event.shard, event.channel, &record.prev_value.to_le_bytes());
+            blu.add_u8_range_checks(event.shard, event.channel, &record.value.to_le_bytes());
+        }
+
+        row
+    }
+}
+
+impl<F> BaseAir<F> for KeccakPermuteMemoChip {
+    fn width(&self) -> usize {
+        NUM_KECCAK_PERMUTE_MEMO_COLS
+    }
+}
+
+impl<AB> Air<AB> for KeccakPermuteMemoChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &KeccakPermuteMemoCols<AB::Var> = (*local).borrow();
+        let next: &KeccakPermuteMemoCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder.when_transition().assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        builder.assert_bool(local.is_real);
+
+        // Overwrite the state at the clock at which an unmemoized permutation writes it.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk + AB::Expr::one(),
+            local.state_addr,
+            &local.state_mem,
+            local.is_real,
+        );
+
+        // Range check both states to be bytes, so that their limbs are 16 bits like those of the
+        // keccak columns.
+        for mem in local.state_mem.iter() {
+            builder.slice_range_check_u8(
+                &mem.prev_value().0,
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+            builder.slice_range_check_u8(&mem.value().0, local.shard, local.channel, local.is_real);
+        }
+
+        // Receive the pre-state and post-state from the permutation whose result is reused, as
+        // 16-bit limbs from the least significant word of each lane.
+        let expr_2_pow_8 = AB::Expr::from_canonical_u32(1 << 8);
+        let limbs = |word: &Word<AB::Var>| {
+            [word[0] + word[1] * expr_2_pow_8.clone(), word[2] + word[3] * expr_2_pow_8.clone()]
+        };
+        let pre_state = local.state_mem.iter().flat_map(|mem| limbs(mem.prev_value()));
+        let post_state = local.state_mem.iter().flat_map(|mem| limbs(mem.value()));
+        builder.receive(AirInteraction::new(
+            pre_state.chain(post_state).collect(),
+            local.is_real.into(),
+            KECCAK_PERMUTE_INTERACTION,
+        ));
+
+        // Receive the arguments.
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::KECCAK_PERMUTE.syscall_id()),
+            local.state_addr,
+            AB::Expr::zero(),
+            local.is_real,
+        );
+    }
+}
@@ -1,14 +1,22 @@
 mod air;
 pub mod columns;
+mod memo;
 mod trace;
 
 use p3_keccak_air::KeccakAir;
+use sp1_stark::InteractionKind;
+
+pub use memo::*;
 
 pub(crate) const STATE_SIZE: usize = 25;
 
 // The permutation state is 25 u64's.  Our word size is 32 bits, so it is 50 words.
 pub const STATE_NUM_WORDS: usize = STATE_SIZE * 2;
 
+/// The interaction through which [`KeccakPermuteChip`] gives the pre-state and post-state of a
+/// permutation, as 16-bit limbs, to each [`KeccakPermuteMemoChip`] row reusing its result.
+pub(crate) const KECCAK_PERMUTE_INTERACTION: InteractionKind = InteractionKind::Custom(1);
+
 pub struct KeccakPermuteChip {
     p3_keccak: KeccakAir,
 }
@@ -22,9 +30,16 @@ impl KeccakPermuteChip {
 #[cfg(test)]
 pub mod permute_tests {
     use sp1_core_executor::{syscalls::SyscallCode, Executor, Instruction, Opcode, Program};
-    use sp1_stark::{CpuProver, SP1CoreOpts};
+    use sp1_stark::{
+        baby_bear_poseidon2::BabyBearPoseidon2, CpuProver, SP1CoreOpts, StarkGenericConfig,
+    };
 
-    use crate::utils::{self, run_test, tests::KECCAK_PERMUTE_ELF};
+    use super::STATE_NUM_WORDS;
+    use crate::{
+        io::SP1Stdin,
+        riscv::RiscvAir,
+        utils::{self, prove, run_test, tests::KECCAK_PERMUTE_ELF},
+    };
 
     pub fn keccak_permute_program() -> Program {
         let digest_ptr = 100;
@@ -44,6 +59,71 @@ pub mod permute_tests {
         Program::new(instructions, 0, 0)
     }
 
+    /// A program permuting the same state twice, writing it back in between.
+    fn keccak_permute_twice_program() -> Program {
+        let digest_ptr = 100;
+        let mut instructions = vec![Instruction::new(Opcode::ADD, 29, 0, 1, false, true)];
+        for _ in 0..2 {
+            for i in 0..STATE_NUM_WORDS as u32 {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 30, 0, digest_ptr + i * 4, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+            instructions.extend(vec![
+                Instruction::new(
+                    Opcode::ADD,
+                    5,
+                    0,
+                    SyscallCode::KECCAK_PERMUTE as u32,
+                    false,
+                    true,
+                ),
+                Instruction::new(Opcode::ADD, 10, 0, digest_ptr, false, true),
+                Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            ]);
+        }
+
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_keccak_permute_memoized_execute() {
+        utils::setup_logger();
+        let program = keccak_permute_twice_program();
+        let mut runtime = Executor::new(program.clone(), SP1CoreOpts::default());
+        runtime.run().unwrap();
+        let mut memoized = Executor::new(program, SP1CoreOpts::default().memoize_syscalls());
+        memoized.run().unwrap();
+
+        for i in 0..STATE_NUM_WORDS as u32 {
+            assert_eq!(memoized.word(100 + i * 4), runtime.word(100 + i * 4));
+        }
+
+        let events = |runtime: &Executor| {
+            let permutes = runtime.records.iter().flat_map(|r| &r.keccak_permute_events);
+            let memoized = runtime.records.iter().flat_map(|r| &r.keccak_permute_memo_events);
+            (permutes.map(|event| event.memo_uses).collect::<Vec<_>>(), memoized.count())
+        };
+        assert_eq!(events(&runtime), (vec![0, 0], 0));
+        assert_eq!(events(&memoized), (vec![1], 1));
+    }
+
+    #[test]
+    fn test_keccak_permute_memoized_prove() {
+        utils::setup_logger();
+        let program = keccak_permute_twice_program();
+        let config = BabyBearPoseidon2::new();
+        let opts = SP1CoreOpts::default().memoize_syscalls();
+        let (proof, _, _) =
+            prove::<_, CpuProver<_, _>>(program.clone(), &SP1Stdin::new(), config, opts).unwrap();
+
+        let machine = RiscvAir::machine(BabyBearPoseidon2::new());
+        let mut challenger = machine.config().challenger();
+        let (_, vk) = machine.setup(&program);
+        machine.verify(&vk, &proof, &mut challenger).unwrap();
+    }
+
     #[test]
     pub fn test_keccak_permute_program_execute() {
         utils::setup_logger();
@@ -69,6 +69,7 @@ impl<F: PrimeField32> MachineAir<F> for KeccakPermuteChip {
                             cols.channel = F::from_canonical_u8(channel);
                             cols.clk = F::from_canonical_u32(start_clk);
                             cols.state_addr = F::from_canonical_u32(event.state_addr);
+                            cols.memo_uses = F::from_canonical_u32(event.memo_uses);
                             cols.is_real = F::one();
 
                             // If this is the first row, then populate read memory accesses
@@ -169,4 +170,8 @@ impl<F: PrimeField32> MachineAir<F> for KeccakPermuteChip {
     fn included(&self, shard: &Self::Record) -> bool {
         !shard.keccak_permute_events.is_empty()
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(NUM_ROUNDS * shard.keccak_permute_events.len())
+    }
 }
@@ -1,6 +1,13 @@
+pub mod bigint;
+pub mod byte_scan;
+pub mod dilithium;
 pub mod edwards;
 pub mod fptower;
+pub mod hint_words;
 pub mod keccak256;
+pub mod msm;
+pub mod ripemd160;
 pub mod sha256;
 pub mod uint256;
 pub mod weierstrass;
+pub mod wide_alu;
@@ -0,0 +1,377 @@
+use core::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+use std::marker::PhantomData;
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{AbstractField, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::{
+    IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator, ParallelSlice,
+};
+use sp1_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, MsmEvent, MSM_WINDOW_BITS},
+    syscalls::SyscallCode,
+    ExecutionRecord, Program,
+};
+use sp1_curves::{
+    edwards::{ed25519::Ed25519BaseField, EdwardsParameters, NUM_LIMBS, WORDS_CURVE_POINT},
+    params::{FieldParameters, Limbs, NumLimbs},
+    AffinePoint, EllipticCurve,
+};
+use sp1_derive::AlignedBorrow;
+use sp1_stark::air::{BaseAirBuilder, MachineAir, SP1AirBuilder};
+
+use super::{msm_num_rows, MsmScheduleCols};
+use crate::{
+    air::MemoryAirBuilder,
+    memory::{MemoryCols, MemoryReadCols, MemoryWriteCols},
+    syscall::precompiles::edwards::EdAddCols,
+};
+
+pub const NUM_ED_MSM_COLS: usize = size_of::<EdMsmCols<u8>>();
+
+type EdLimbs<T> = Limbs<T, <Ed25519BaseField as NumLimbs>::Limbs>;
+
+/// A set of columns to compute one row of an MSM window on the Edwards curve ed25519.
+///
+/// Points are in affine coordinates, since the twisted Edwards addition is complete. Each row
+/// computes `acc + rhs`, where `rhs` is `acc` on doubling rows and the multiple read from the table
+/// on add rows. The result becomes the accumulator of the next row, and is written back to memory
+/// on the last row.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct EdMsmCols<T> {
+    pub schedule: MsmScheduleCols<T>,
+
+    /// The accumulator read from memory, kept constant across the rows of a window.
+    pub acc_in_x: EdLimbs<T>,
+    pub acc_in_y: EdLimbs<T>,
+
+    pub acc_x: EdLimbs<T>,
+    pub acc_y: EdLimbs<T>,
+    /// Either `acc` or the multiple read from the table, depending on the row.
+    pub rhs_x: EdLimbs<T>,
+    pub rhs_y: EdLimbs<T>,
+
+    pub entry_access: [MemoryReadCols<T>; WORDS_CURVE_POINT],
+    pub acc_access: [MemoryWriteCols<T>; WORDS_CURVE_POINT],
+
+    pub(crate) add: EdAddCols<T>,
+}
+
+#[derive(Default)]
+pub struct EdMsmChip<E> {
+    _marker: PhantomData<E>,
+}
+
+impl<E: EllipticCurve + EdwardsParameters> EdMsmChip<E> {
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+}
+
+impl<F: PrimeField32, E: EllipticCurve + EdwardsParameters> MachineAir<F> for EdMsmChip<E> {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "EdMsm".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let events = &input.ed_msm_events;
+        let mut rows = events
+            .par_iter()
+            .enumerate()
+            .map(|(nonce, event)| {
+                let mut blu = Vec::new();
+                self.event_to_rows(event, nonce, &mut blu)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        // Pad the trace to a power of two.
+        let num_real_rows = rows.len();
+        let num_padded_rows = std::cmp::max(num_real_rows.next_power_of_two(), 16);
+        rows.resize_with(num_padded_rows, || Self::padding_row(events.len()));
+
+        RowMajorMatrix::new(rows.into_iter().flatten().collect::<Vec<_>>(), NUM_ED_MSM_COLS)
+    }
+
+    fn generate_dependencies(&self, input: &Self::Record, output: &mut Self::Record) {
+        let chunk_size = std::cmp::max(input.ed_msm_events.len() / num_cpus::get(), 1);
+
+        let blu_batches = input
+            .ed_msm_events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<u32, HashMap<ByteLookupEvent, usize>> = HashMap::new();
+                events.iter().for_each(|event| {
+                    self.event_to_rows::<F>(event, 0, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_sharded_byte_lookup_events(blu_batches.iter().collect_vec());
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.ed_msm_events.is_empty()
+    }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.ed_msm_events.iter().map(msm_num_rows).sum())
+    }
+}
+
+impl<E: EllipticCurve + EdwardsParameters> EdMsmChip<E> {
+    /// Create the rows of an MSM window from an event.
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &MsmEvent,
+        nonce: usize,
+        blu: &mut impl ByteRecord,
+    ) -> Vec<[F; NUM_ED_MSM_COLS]> {
+        let acc_in = AffinePoint::<E>::from_words_le(&event.acc);
+        let (mut acc_x, mut acc_y) = (acc_in.x.clone(), acc_in.y.clone());
+
+        let num_rows = msm_num_rows(event);
+        let mut rows = Vec::with_capacity(num_rows);
+        for row_index in 0..num_rows {
+            let mut row = [F::zero(); NUM_ED_MSM_COLS];
+            let cols: &mut EdMsmCols<F> = row.as_mut_slice().borrow_mut();
+            cols.schedule.populate(event, nonce, row_index, blu);
+
+            cols.acc_in_x = Ed25519BaseField::to_limbs_field::<F, F>(&acc_in.x);
+            cols.acc_in_y = Ed25519BaseField::to_limbs_field::<F, F>(&acc_in.y);
+            cols.acc_x = Ed25519BaseField::to_limbs_field::<F, F>(&acc_x);
+            cols.acc_y = Ed25519BaseField::to_limbs_field::<F, F>(&acc_y);
+
+            // Double on the first rows, then add the multiple of each point.
+            let (rhs_x, rhs_y) = if row_index < MSM_WINDOW_BITS {
+                (acc_x.clone(), acc_y.clone())
+            } else {
+                let i = row_index - MSM_WINDOW_BITS;
+                for (access, record) in
+                    cols.entry_access.iter_mut().zip(&event.entry_memory_records[i])
+                {
+                    access.populate(event.channel, *record, blu);
+                }
+                let entry = AffinePoint::<E>::from_words_le(&event.entries[i]);
+                (entry.x, entry.y)
+            };
+            cols.rhs_x = Ed25519BaseField::to_limbs_field::<F, F>(&rhs_x);
+            cols.rhs_y = Ed25519BaseField::to_limbs_field::<F, F>(&rhs_y);
+
+            (acc_x, acc_y) = cols.add.populate::<E>(
+                blu,
+                event.shard,
+                event.channel,
+                &acc_x,
+                &acc_y,
+                &rhs_x,
+                &rhs_y,
+            );
+
+            // The result is written back to memory on the last row.
+            if row_index == num_rows - 1 {
+                for (access, record) in cols.acc_access.iter_mut().zip(&event.acc_memory_records) {
+                    access.populate(event.channel, *record, blu);
+                }
+            }
+
+            rows.push(row);
+        }
+
+        rows
+    }
+
+    /// Create a padding row, which doubles the neutral element.
+    fn padding_row<F: PrimeField32>(nonce: usize) -> [F; NUM_ED_MSM_COLS] {
+        let mut row = [F::zero(); NUM_ED_MSM_COLS];
+        let cols: &mut EdMsmCols<F> = row.as_mut_slice().borrow_mut();
+        cols.schedule.nonce = F::from_canonical_usize(nonce);
+
+        let (x, y) = E::neutral();
+        let neutral_x = Ed25519BaseField::to_limbs_field::<F, F>(&x);
+        let neutral_y = Ed25519BaseField::to_limbs_field::<F, F>(&y);
+        cols.acc_x = neutral_x.clone();
+        cols.acc_y = neutral_y.clone();
+        cols.rhs_x = neutral_x;
+        cols.rhs_y = neutral_y;
+        cols.add.populate::<E>(&mut vec![], 0, 0, &x, &y, &x, &y);
+        row
+    }
+}
+
+impl<F, E: EllipticCurve + EdwardsParameters> BaseAir<F> for EdMsmChip<E> {
+    fn width(&self) -> usize {
+        NUM_ED_MSM_COLS
+    }
+}
+
+impl<AB, E: EllipticCurve + EdwardsParameters> Air<AB> for EdMsmChip<E>
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &EdMsmCols<AB::Var> = (*local).borrow();
+        let next: &EdMsmCols<AB::Var> = (*next).borrow();
+        let schedule = &local.schedule;
+
+        schedule.eval(builder, &next.schedule, SyscallCode::ED_MSM);
+
+        // On the first row, the accumulator is the one read from memory.
+        builder.when(schedule.is_first_row()).assert_all_eq(local.acc_x, local.acc_in_x);
+        builder.when(schedule.is_first_row()).assert_all_eq(local.acc_y, local.acc_in_y);
+
+        // The right-hand side is the accumulator on doubling rows, and the multiple read from the
+        // table on add rows.
+        for k in 0..NUM_LIMBS {
+            let entry_x = local.entry_access[k / 4].value()[k % 4];
+            let entry_y = local.entry_access[WORDS_CURVE_POINT / 2 + k / 4].value()[k % 4];
+            let not_add = AB::Expr::one() - schedule.is_add;
+            builder.assert_eq(
+                local.rhs_x[k],
+                schedule.is_add * entry_x + not_add.clone() * local.acc_x[k],
+            );
+            builder.assert_eq(local.rhs_y[k], schedule.is_add * entry_y + not_add * local.acc_y[k]);
+        }
+        builder.eval_memory_access_slice(
+            schedule.shard,
+            schedule.channel,
+            schedule.clk,
+            schedule.entry_ptr::<AB>(WORDS_CURVE_POINT),
+            &local.entry_access,
+            schedule.is_add,
+        );
+
+        // acc + rhs.
+        local.add.eval::<AB, E>(
+            builder,
+            local.acc_x,
+            local.acc_y,
+            local.rhs_x,
+            local.rhs_y,
+            schedule.shard,
+            schedule.channel,
+            schedule.is_real,
+        );
+
+        // The result is carried over to the next row within a window.
+        let mut within = builder.when_transition();
+        let mut within = within.when(schedule.within_window::<AB>());
+        within.assert_all_eq(next.acc_x, local.add.x3_ins.result);
+        within.assert_all_eq(next.acc_y, local.add.y3_ins.result);
+        within.assert_all_eq(next.acc_in_x, local.acc_in_x);
+        within.assert_all_eq(next.acc_in_y, local.acc_in_y);
+
+        // The accumulator is read from and written back to memory on the last row.
+        for k in 0..NUM_LIMBS {
+            let (x_access, y_access) =
+                (&local.acc_access[k / 4], &local.acc_access[WORDS_CURVE_POINT / 2 + k / 4]);
+            let mut when_last_row = builder.when(schedule.is_last_row);
+            when_last_row.assert_eq(x_access.prev_value()[k % 4], local.acc_in_x[k]);
+            when_last_row.assert_eq(y_access.prev_value()[k % 4], local.acc_in_y[k]);
+            when_last_row.assert_eq(x_access.value()[k % 4], local.add.x3_ins.result[k]);
+            when_last_row.assert_eq(y_access.value()[k % 4], local.add.y3_ins.result[k]);
+        }
+        builder.eval_memory_access_slice(
+            schedule.shard,
+            schedule.channel,
+            schedule.clk + AB::F::from_canonical_u32(1),
+            schedule.acc_ptr,
+            &local.acc_access,
+            schedule.is_last_row,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use sp1_core_executor::{
+        events::{MSM_TABLE_SIZE, MSM_WINDOW_BITS},
+        syscalls::SyscallCode,
+        Executor, Program,
+    };
+    use sp1_curves::{
+        edwards::{ed25519::Ed25519, EdwardsParameters},
+        AffinePoint, EllipticCurve,
+    };
+    use sp1_stark::{CpuProver, SP1CoreOpts};
+
+    use crate::{
+        syscall::precompiles::msm::tests::{msm_program, ACC_PTR},
+        utils::{self, run_test},
+    };
+
+    /// The table of the multiples `[0] p, ..., [15] p`.
+    fn table(p: &AffinePoint<Ed25519>) -> Vec<AffinePoint<Ed25519>> {
+        let mut table = vec![Ed25519::ec_neutral().unwrap()];
+        for k in 1..MSM_TABLE_SIZE {
+            table.push(&table[k - 1] + p);
+        }
+        table
+    }
+
+    fn ed_msm_program(digits: &[u32]) -> (Program, Vec<u32>) {
+        let (x, y) = <Ed25519 as EdwardsParameters>::generator();
+        let g = AffinePoint::<Ed25519>::new(x, y);
+        let acc = &g * &BigUint::from(3u32);
+        let points = (1..=digits.len() as u32).map(|k| &g * &BigUint::from(k * 7 + 1));
+        let tables = points.map(|p| table(&p)).collect::<Vec<_>>();
+
+        let mut expected = &acc * &BigUint::from(1u32 << MSM_WINDOW_BITS);
+        for (table, digit) in tables.iter().zip(digits) {
+            expected = &expected + &table[*digit as usize];
+        }
+
+        let tables = tables
+            .iter()
+            .map(|table| table.iter().map(AffinePoint::to_words_le).collect())
+            .collect::<Vec<_>>();
+        let program = msm_program(SyscallCode::ED_MSM, &acc.to_words_le(), &tables, digits);
+        (program, expected.to_words_le())
+    }
+
+    #[test]
+    fn test_ed_msm_execute() {
+        utils::setup_logger();
+        let (program, expected) = ed_msm_program(&[5, 0, 15]);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        let result = (0..16).map(|i| runtime.word(ACC_PTR + i * 4)).collect::<Vec<_>>();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_ed_msm_prove() {
+        utils::setup_logger();
+        let (program, _) = ed_msm_program(&[5, 0, 15]);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_ed_msm_no_points_prove() {
+        utils::setup_logger();
+        let (program, _) = ed_msm_program(&[]);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
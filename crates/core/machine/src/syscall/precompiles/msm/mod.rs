@@ -0,0 +1,327 @@
+//! Precompiles for one window of a multi-scalar multiplication (MSM).
+//!
+//! An MSM `sum_i [s_i] p_i` is computed by the guest with a windowed double-and-add shared by all
+//! the points. The guest writes a table of the multiples `[0] p_i, ..., [15] p_i` of each point,
+//! and for each 4-bit window of the scalars, from the most significant one, invokes the syscall
+//! with the digits of the scalars in that window. A single invocation computes
+//!
+//! `acc = [16] acc + sum_i table_i[digit_i]`
+//!
+//! over `4 + n` rows: the first four rows double the accumulator, and the `i`-th following row adds
+//! the multiple selected by the digit of the `i`-th point. An MSM thus costs one syscall per window
+//! instead of one scalar multiplication per point.
+//!
+//! The tables are inputs like the accumulator, so the precompile proves the accumulation for the
+//! tables the guest wrote to memory.
+
+mod ed_msm;
+mod weierstrass_msm;
+
+pub use ed_msm::*;
+pub use weierstrass_msm::*;
+
+use p3_air::AirBuilder;
+use p3_field::{AbstractField, PrimeField32};
+use sp1_core_executor::{
+    events::{ByteRecord, MsmEvent, MSM_DESCRIPTOR_WORDS, MSM_TABLE_SIZE, MSM_WINDOW_BITS},
+    syscalls::SyscallCode,
+};
+use sp1_derive::AlignedBorrow;
+use sp1_stark::air::SP1AirBuilder;
+
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::{MemoryCols, MemoryReadCols},
+};
+
+/// The columns describing the position of a row within an MSM window, shared by the MSM chips.
+///
+/// The syscall is received and the descriptor is read on the first row, and the digit of a point
+/// is read on the row adding its multiple. The nonce numbers the windows of the trace, since
+/// windows have a variable number of rows.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct MsmScheduleCols<T> {
+    pub is_real: T,
+    pub shard: T,
+    pub channel: T,
+    pub clk: T,
+    pub nonce: T,
+    pub acc_ptr: T,
+    pub desc_ptr: T,
+
+    /// The number of points, read from the descriptor.
+    pub n: T,
+    /// The pointer to the tables of multiples, read from the descriptor.
+    pub table_ptr: T,
+    /// The pointer to the digits, read from the descriptor.
+    pub digits_ptr: T,
+
+    /// Whether this is the last row of a real MSM window.
+    pub is_last_row: T,
+    /// A one-hot encoding of the doubling rows. The first flag is set on the first row.
+    pub double_flags: [T; MSM_WINDOW_BITS],
+    /// Whether this row adds the multiple of a point.
+    pub is_add: T,
+    /// The index of the point on add rows, and zero on doubling rows.
+    pub index: T,
+    /// The digit of the point on add rows, and zero on doubling rows.
+    pub digit: T,
+
+    pub desc_access: [MemoryReadCols<T>; MSM_DESCRIPTOR_WORDS],
+    pub digit_access: MemoryReadCols<T>,
+}
+
+/// The number of rows of the window of `event`.
+pub(crate) fn msm_num_rows(event: &MsmEvent) -> usize {
+    MSM_WINDOW_BITS + event.digits.len()
+}
+
+impl<F: PrimeField32> MsmScheduleCols<F> {
+    /// Populates the columns of the `row`-th row of the window of `event`, the `nonce`-th window of
+    /// the trace.
+    pub(crate) fn populate(
+        &mut self,
+        event: &MsmEvent,
+        nonce: usize,
+        row: usize,
+        blu: &mut impl ByteRecord,
+    ) {
+        let [n, table_ptr, digits_ptr] = event.desc;
+        self.is_real = F::one();
+        self.shard = F::from_canonical_u32(event.shard);
+        self.channel = F::from_canonical_u8(event.channel);
+        self.clk = F::from_canonical_u32(event.clk);
+        self.nonce = F::from_canonical_usize(nonce);
+        self.acc_ptr = F::from_canonical_u32(event.acc_ptr);
+        self.desc_ptr = F::from_canonical_u32(event.desc_ptr);
+        self.n = F::from_canonical_u32(n);
+        self.table_ptr = F::from_canonical_u32(table_ptr);
+        self.digits_ptr = F::from_canonical_u32(digits_ptr);
+        self.is_last_row = F::from_bool(row == msm_num_rows(event) - 1);
+
+        if row < MSM_WINDOW_BITS {
+            self.double_flags[row] = F::one();
+            if row == 0 {
+                for (access, record) in self.desc_access.iter_mut().zip(event.desc_memory_records) {
+                    access.populate(event.channel, record, blu);
+                }
+            }
+        } else {
+            let i = row - MSM_WINDOW_BITS;
+            self.is_add = F::one();
+            self.index = F::from_canonical_usize(i);
+            self.digit = F::from_canonical_u32(event.digits[i]);
+            blu.add_range_check(
+                event.shard,
+                event.channel,
+                event.digits[i] as u16,
+                MSM_WINDOW_BITS as u8,
+            );
+            self.digit_access.populate(event.channel, event.digit_memory_records[i], blu);
+        }
+    }
+}
+
+impl<V: Copy> MsmScheduleCols<V> {
+    /// Whether the next row belongs to the same window as this one.
+    pub(crate) fn within_window<AB: SP1AirBuilder<Var = V>>(&self) -> AB::Expr
+    where
+        V: Into<AB::Expr>,
+    {
+        self.is_real.into() - self.is_last_row.into()
+    }
+
+    /// The first row of a real window.
+    pub(crate) fn is_first_row(&self) -> V {
+        self.double_flags[0]
+    }
+
+    /// The pointer to the multiple selected by the digit on an add row, for multiples of
+    /// `entry_words` words.
+    pub(crate) fn entry_ptr<AB: SP1AirBuilder<Var = V>>(&self, entry_words: usize) -> AB::Expr
+    where
+        V: Into<AB::Expr>,
+    {
+        let entry =
+            self.index.into() * AB::F::from_canonical_usize(MSM_TABLE_SIZE) + self.digit.into();
+        self.table_ptr.into() + entry * AB::F::from_canonical_usize(entry_words * 4)
+    }
+
+    /// Constrains the schedule of the windows, reads the descriptor and the digits, and receives
+    /// the syscall.
+    pub(crate) fn eval<AB: SP1AirBuilder<Var = V>>(
+        &self,
+        builder: &mut AB,
+        next: &Self,
+        syscall_code: SyscallCode,
+    ) where
+        V: Into<AB::Expr>,
+    {
+        let local = self;
+        builder.assert_bool(local.is_real);
+        builder.assert_bool(local.is_last_row);
+        builder.assert_bool(local.is_add);
+
+        // Every real row either doubles or adds.
+        let mut flags_sum = AB::Expr::zero();
+        for i in 0..MSM_WINDOW_BITS {
+            builder.assert_bool(local.double_flags[i]);
+            flags_sum += local.double_flags[i].into();
+        }
+        builder.assert_eq(flags_sum + local.is_add, local.is_real);
+
+        // Real rows are followed by the rows of the same window or by a new window, and padding
+        // rows only by padding rows.
+        let within_window = local.within_window::<AB>();
+        builder.when_first_row().assert_eq(local.is_real, local.is_first_row());
+        builder
+            .when_transition()
+            .when(local.is_last_row)
+            .assert_eq(next.is_real, next.is_first_row());
+        builder.when_transition().when(AB::Expr::one() - local.is_real).assert_zero(next.is_real);
+        builder.when_last_row().assert_eq(local.is_real, local.is_last_row);
+
+        // The windows are numbered from zero.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder
+            .when_transition()
+            .when(local.is_last_row)
+            .assert_eq(next.nonce, local.nonce + AB::Expr::one());
+
+        // Within a window, the doubling rows come first, followed by one add row for each point.
+        let mut within = builder.when_transition();
+        let mut within = within.when(within_window);
+        within.assert_zero(next.double_flags[0]);
+        for i in 1..MSM_WINDOW_BITS {
+            within.assert_eq(next.double_flags[i], local.double_flags[i - 1]);
+        }
+        within.assert_eq(next.is_add, local.is_add + local.double_flags[MSM_WINDOW_BITS - 1]);
+        within.assert_eq(next.index, (local.index + AB::Expr::one()) * local.is_add);
+
+        // The columns describing the syscall are constant within a window.
+        within.assert_eq(next.shard, local.shard);
+        within.assert_eq(next.channel, local.channel);
+        within.assert_eq(next.clk, local.clk);
+        within.assert_eq(next.nonce, local.nonce);
+        within.assert_eq(next.acc_ptr, local.acc_ptr);
+        within.assert_eq(next.desc_ptr, local.desc_ptr);
+        within.assert_eq(next.n, local.n);
+        within.assert_eq(next.table_ptr, local.table_ptr);
+        within.assert_eq(next.digits_ptr, local.digits_ptr);
+
+        // A window ends after its doublings and one add row for each point.
+        let mut doubling_sum = AB::Expr::zero();
+        for i in 0..MSM_WINDOW_BITS - 1 {
+            doubling_sum += local.double_flags[i].into();
+        }
+        builder.when(local.is_last_row).assert_zero(doubling_sum);
+        builder
+            .when(local.is_last_row)
+            .assert_eq(local.n, local.is_add * (local.index + AB::Expr::one()));
+
+        // The descriptor is read on the first row.
+        let desc = [local.n, local.table_ptr, local.digits_ptr];
+        for (access, value) in local.desc_access.iter().zip(desc) {
+            builder.when(local.is_first_row()).assert_eq(access.value().reduce::<AB>(), value);
+        }
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.desc_ptr,
+            &local.desc_access,
+            local.is_first_row(),
+        );
+
+        // The digit of the point is read on its add row, and fits in a window.
+        builder.range_check(local.digit, MSM_WINDOW_BITS, local.shard, local.channel, local.is_add);
+        let digit_word = local.digit_access.value();
+        builder.when(local.is_add).assert_eq(digit_word[0], local.digit);
+        for j in 1..4 {
+            builder.when(local.is_add).assert_zero(digit_word[j]);
+        }
+        builder.eval_memory_access(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.digits_ptr.into() + local.index.into() * AB::F::from_canonical_u32(4),
+            &local.digit_access,
+            local.is_add,
+        );
+
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(syscall_code.syscall_id()),
+            local.acc_ptr,
+            local.desc_ptr,
+            local.is_first_row(),
+        );
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use sp1_core_executor::{syscalls::SyscallCode, Executor, Instruction, Opcode, Program};
+    use sp1_stark::SP1CoreOpts;
+
+    use crate::utils::setup_logger;
+
+    pub(crate) const ACC_PTR: u32 = 0x1000;
+    pub(crate) const DESC_PTR: u32 = 0x2000;
+    pub(crate) const DIGITS_PTR: u32 = 0x3000;
+    pub(crate) const TABLE_PTR: u32 = 0x4000;
+
+    /// The instructions storing `words` at `ptr`.
+    pub(crate) fn store_words(ptr: u32, words: &[u32]) -> Vec<Instruction> {
+        words
+            .iter()
+            .enumerate()
+            .flat_map(|(i, word)| {
+                [
+                    Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr + i as u32 * 4, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]
+            })
+            .collect()
+    }
+
+    /// A program which runs one MSM window with the given accumulator, tables and digits.
+    pub(crate) fn msm_program(
+        syscall: SyscallCode,
+        acc: &[u32],
+        tables: &[Vec<Vec<u32>>],
+        digits: &[u32],
+    ) -> Program {
+        let mut instructions = store_words(ACC_PTR, acc);
+        instructions.extend(store_words(DESC_PTR, &[tables.len() as u32, TABLE_PTR, DIGITS_PTR]));
+        instructions.extend(store_words(DIGITS_PTR, digits));
+        let table_words = tables.iter().flatten().flatten().copied().collect::<Vec<_>>();
+        instructions.extend(store_words(TABLE_PTR, &table_words));
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, syscall as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, ACC_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, DESC_PTR, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    #[ignore = "needs the ELF built by `make` in `tests/`"]
+    fn test_msm_guest_execute() {
+        setup_logger();
+        let elf = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../../tests/msm/elf/riscv32im-succinct-zkvm-elf"
+        ))
+        .unwrap();
+        let program = Program::from(&elf).unwrap();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+    }
+}
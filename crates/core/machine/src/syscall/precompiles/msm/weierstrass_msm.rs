@@ -0,0 +1,647 @@
+use core::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+use std::marker::PhantomData;
+
+use generic_array::GenericArray;
+use hashbrown::HashMap;
+use itertools::Itertools;
+use num::BigUint;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{AbstractField, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::{
+    IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator, ParallelSlice,
+};
+use sp1_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, FieldOperation, MsmEvent, MSM_WINDOW_BITS},
+    syscalls::SyscallCode,
+    ExecutionRecord, Program,
+};
+use sp1_curves::{
+    params::{FieldParameters, Limbs, NumLimbs, NumWords},
+    weierstrass::{projective::ProjectivePoint, WeierstrassParameters},
+    CurveType, EllipticCurve,
+};
+use sp1_derive::AlignedBorrow;
+use sp1_stark::air::{MachineAir, SP1AirBuilder};
+use typenum::Unsigned;
+
+use super::{msm_num_rows, MsmScheduleCols};
+use crate::{
+    air::MemoryAirBuilder,
+    memory::{MemoryCols, MemoryReadCols, MemoryWriteCols},
+    operations::field::{field_inner_product::FieldInnerProductCols, field_op::FieldOpCols},
+};
+
+pub const fn num_weierstrass_msm_cols<P: FieldParameters + NumWords>() -> usize {
+    size_of::<WeierstrassMsmCols<u8, P>>()
+}
+
+/// A set of columns to compute the complete addition `(x3 : y3 : z3) = (x1 : y1 : z1) + (x2 : y2 :
+/// z2)` of two points in projective coordinates on a short Weierstrass curve with `a = 0`.
+///
+/// See [`ProjectivePoint::complete_add`] for the formulas. The result is available in
+/// `x3.result`, `y3.result` and `z3.result`.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct ProjectiveAddCols<T, P: FieldParameters> {
+    pub(crate) x1y2_x2y1: FieldInnerProductCols<T, P>,
+    pub(crate) y1z2_y2z1: FieldInnerProductCols<T, P>,
+    pub(crate) x1z2_x2z1: FieldInnerProductCols<T, P>,
+    pub(crate) y1y2: FieldOpCols<T, P>,
+    pub(crate) z1z2: FieldOpCols<T, P>,
+    pub(crate) x1x2: FieldOpCols<T, P>,
+    pub(crate) z1z2_b3: FieldOpCols<T, P>,
+    pub(crate) y1y2_minus: FieldOpCols<T, P>,
+    pub(crate) y1y2_plus: FieldOpCols<T, P>,
+    pub(crate) x1z2_x2z1_neg_b3: FieldOpCols<T, P>,
+    pub(crate) x1z2_x2z1_b9: FieldOpCols<T, P>,
+    pub(crate) x1x2_3: FieldOpCols<T, P>,
+    pub(crate) x3: FieldInnerProductCols<T, P>,
+    pub(crate) y3: FieldInnerProductCols<T, P>,
+    pub(crate) z3: FieldInnerProductCols<T, P>,
+}
+
+/// The constants `3b`, `-3b` and `9b` of the complete addition formulas, and `3`.
+fn projective_add_constants<E: WeierstrassParameters>() -> [BigUint; 4] {
+    let p = E::BaseField::modulus();
+    let b3 = (E::b_int() * 3u32) % &p;
+    let neg_b3 = (&p - &b3) % &p;
+    let b9 = (&b3 * 3u32) % &p;
+    [b3, neg_b3, b9, BigUint::from(3u32)]
+}
+
+impl<F: PrimeField32, P: FieldParameters> ProjectiveAddCols<F, P> {
+    /// Populates the columns computing `p + q` and returns the result.
+    pub(crate) fn populate<E: WeierstrassParameters<BaseField = P>>(
+        &mut self,
+        record: &mut impl ByteRecord,
+        shard: u32,
+        channel: u8,
+        p: &[BigUint; 3],
+        q: &[BigUint; 3],
+    ) -> [BigUint; 3] {
+        let [x1, y1, z1] = p;
+        let [x2, y2, z2] = q;
+        let [b3, neg_b3, b9, three] = projective_add_constants::<E>();
+
+        let x1y2_x2y1 = self.x1y2_x2y1.populate(
+            record,
+            shard,
+            channel,
+            &[x1.clone(), x2.clone()],
+            &[y2.clone(), y1.clone()],
+        );
+        let y1z2_y2z1 = self.y1z2_y2z1.populate(
+            record,
+            shard,
+            channel,
+            &[y1.clone(), y2.clone()],
+            &[z2.clone(), z1.clone()],
+        );
+        let x1z2_x2z1 = self.x1z2_x2z1.populate(
+            record,
+            shard,
+            channel,
+            &[x1.clone(), x2.clone()],
+            &[z2.clone(), z1.clone()],
+        );
+        let y1y2 = self.y1y2.populate(record, shard, channel, y1, y2, FieldOperation::Mul);
+        let z1z2 = self.z1z2.populate(record, shard, channel, z1, z2, FieldOperation::Mul);
+        let x1x2 = self.x1x2.populate(record, shard, channel, x1, x2, FieldOperation::Mul);
+        let z1z2_b3 =
+            self.z1z2_b3.populate(record, shard, channel, &z1z2, &b3, FieldOperation::Mul);
+        let y1y2_minus =
+            self.y1y2_minus.populate(record, shard, channel, &y1y2, &z1z2_b3, FieldOperation::Sub);
+        let y1y2_plus =
+            self.y1y2_plus.populate(record, shard, channel, &y1y2, &z1z2_b3, FieldOperation::Add);
+        let x1z2_x2z1_neg_b3 = self.x1z2_x2z1_neg_b3.populate(
+            record,
+            shard,
+            channel,
+            &x1z2_x2z1,
+            &neg_b3,
+            FieldOperation::Mul,
+        );
+        let x1z2_x2z1_b9 = self.x1z2_x2z1_b9.populate(
+            record,
+            shard,
+            channel,
+            &x1z2_x2z1,
+            &b9,
+            FieldOperation::Mul,
+        );
+        let x1x2_3 =
+            self.x1x2_3.populate(record, shard, channel, &x1x2, &three, FieldOperation::Mul);
+
+        let x3 = self.x3.populate(
+            record,
+            shard,
+            channel,
+            &[x1y2_x2y1.clone(), y1z2_y2z1.clone()],
+            &[y1y2_minus.clone(), x1z2_x2z1_neg_b3],
+        );
+        let y3 = self.y3.populate(
+            record,
+            shard,
+            channel,
+            &[y1y2_plus.clone(), x1x2],
+            &[y1y2_minus, x1z2_x2z1_b9],
+        );
+        let z3 =
+            self.z3.populate(record, shard, channel, &[y1z2_y2z1, x1x2_3], &[y1y2_plus, x1y2_x2y1]);
+        [x3, y3, z3]
+    }
+}
+
+impl<V: Copy, P: FieldParameters> ProjectiveAddCols<V, P>
+where
+    Limbs<V, P::Limbs>: Copy,
+{
+    /// Constrains the columns to compute `p + q`.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn eval<AB: SP1AirBuilder<Var = V>, E: WeierstrassParameters<BaseField = P>>(
+        &self,
+        builder: &mut AB,
+        p: [Limbs<V, P::Limbs>; 3],
+        q: [Limbs<V, P::Limbs>; 3],
+        shard: impl Into<AB::Expr> + Clone,
+        channel: impl Into<AB::Expr> + Clone,
+        is_real: impl Into<AB::Expr> + Clone,
+    ) where
+        V: Into<AB::Expr>,
+    {
+        let [x1, y1, z1] = p;
+        let [x2, y2, z2] = q;
+        let [b3, neg_b3, b9, three] =
+            projective_add_constants::<E>().map(|c| P::to_limbs_field::<AB::Expr, _>(&c));
+
+        // The sums of cross products.
+        for (cols, a, b) in [
+            (&self.x1y2_x2y1, [x1, x2], [y2, y1]),
+            (&self.y1z2_y2z1, [y1, y2], [z2, z1]),
+            (&self.x1z2_x2z1, [x1, x2], [z2, z1]),
+        ] {
+            cols.eval(builder, &a, &b, shard.clone(), channel.clone(), is_real.clone());
+        }
+
+        // The products of coordinates.
+        for (cols, a, b) in [(&self.y1y2, y1, y2), (&self.z1z2, z1, z2), (&self.x1x2, x1, x2)] {
+            cols.eval(
+                builder,
+                &a,
+                &b,
+                FieldOperation::Mul,
+                shard.clone(),
+                channel.clone(),
+                is_real.clone(),
+            );
+        }
+
+        // y1 y2 - 3b z1 z2 and y1 y2 + 3b z1 z2.
+        self.z1z2_b3.eval(
+            builder,
+            &self.z1z2.result,
+            &b3,
+            FieldOperation::Mul,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        for (cols, op) in
+            [(&self.y1y2_minus, FieldOperation::Sub), (&self.y1y2_plus, FieldOperation::Add)]
+        {
+            cols.eval(
+                builder,
+                &self.y1y2.result,
+                &self.z1z2_b3.result,
+                op,
+                shard.clone(),
+                channel.clone(),
+                is_real.clone(),
+            );
+        }
+
+        // The products by constants.
+        for (cols, a, b) in [
+            (&self.x1z2_x2z1_neg_b3, &self.x1z2_x2z1.result, &neg_b3),
+            (&self.x1z2_x2z1_b9, &self.x1z2_x2z1.result, &b9),
+            (&self.x1x2_3, &self.x1x2.result, &three),
+        ] {
+            cols.eval(
+                builder,
+                a,
+                b,
+                FieldOperation::Mul,
+                shard.clone(),
+                channel.clone(),
+                is_real.clone(),
+            );
+        }
+
+        // x3 = (x1 y2 + x2 y1)(y1 y2 - 3b z1 z2) - 3b (y1 z2 + y2 z1)(x1 z2 + x2 z1).
+        self.x3.eval(
+            builder,
+            &[self.x1y2_x2y1.result, self.y1z2_y2z1.result],
+            &[self.y1y2_minus.result, self.x1z2_x2z1_neg_b3.result],
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+
+        // y3 = (y1 y2 + 3b z1 z2)(y1 y2 - 3b z1 z2) + 9b x1 x2 (x1 z2 + x2 z1).
+        self.y3.eval(
+            builder,
+            &[self.y1y2_plus.result, self.x1x2.result],
+            &[self.y1y2_minus.result, self.x1z2_x2z1_b9.result],
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+
+        // z3 = (y1 z2 + y2 z1)(y1 y2 + 3b z1 z2) + 3 x1 x2 (x1 y2 + x2 y1).
+        self.z3.eval(
+            builder,
+            &[self.y1z2_y2z1.result, self.x1x2_3.result],
+            &[self.y1y2_plus.result, self.x1y2_x2y1.result],
+            shard,
+            channel,
+            is_real,
+        );
+    }
+}
+
+/// A set of columns to compute one row of an MSM window on a short Weierstrass curve with `a = 0`.
+///
+/// Points are in projective coordinates `(x : y : z)`, which have complete addition formulas.
+/// Each row computes `acc + rhs`, where `rhs` is `acc` on doubling rows and the multiple read from
+/// the table on add rows. The result becomes the accumulator of the next row, and is written back
+/// to memory on the last row.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct WeierstrassMsmCols<T, P: FieldParameters + NumWords> {
+    pub schedule: MsmScheduleCols<T>,
+
+    /// The accumulator read from memory, kept constant across the rows of a window.
+    pub acc_in: [Limbs<T, P::Limbs>; 3],
+
+    pub acc: [Limbs<T, P::Limbs>; 3],
+    /// Either `acc` or the multiple read from the table, depending on the row.
+    pub rhs: [Limbs<T, P::Limbs>; 3],
+
+    pub entry_access: [GenericArray<MemoryReadCols<T>, P::WordsFieldElement>; 3],
+    pub acc_access: [GenericArray<MemoryWriteCols<T>, P::WordsFieldElement>; 3],
+
+    pub(crate) add: ProjectiveAddCols<T, P>,
+}
+
+#[derive(Default)]
+pub struct WeierstrassMsmChip<E> {
+    _marker: PhantomData<E>,
+}
+
+impl<E: EllipticCurve + WeierstrassParameters> WeierstrassMsmChip<E> {
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+
+    fn events(input: &ExecutionRecord) -> &Vec<MsmEvent> {
+        match E::CURVE_TYPE {
+            CurveType::Secp256k1 => &input.secp256k1_msm_events,
+            CurveType::Bn254 => &input.bn254_msm_events,
+            _ => panic!("Unsupported curve"),
+        }
+    }
+
+    fn syscall_code() -> SyscallCode {
+        match E::CURVE_TYPE {
+            CurveType::Secp256k1 => SyscallCode::SECP256K1_MSM,
+            CurveType::Bn254 => SyscallCode::BN254_MSM,
+            _ => panic!("Unsupported curve"),
+        }
+    }
+}
+
+impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
+    for WeierstrassMsmChip<E>
+{
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        match E::CURVE_TYPE {
+            CurveType::Secp256k1 => "Secp256k1Msm".to_string(),
+            CurveType::Bn254 => "Bn254Msm".to_string(),
+            _ => panic!("Unsupported curve"),
+        }
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let events = Self::events(input);
+        let mut rows = events
+            .par_iter()
+            .enumerate()
+            .map(|(nonce, event)| {
+                let mut blu = Vec::new();
+                self.event_to_rows(event, nonce, &mut blu)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        // Pad the trace to a power of two.
+        let num_real_rows = rows.len();
+        let num_padded_rows = std::cmp::max(num_real_rows.next_power_of_two(), 16);
+        rows.resize_with(num_padded_rows, || Self::padding_row(events.len()));
+
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            num_weierstrass_msm_cols::<E::BaseField>(),
+        )
+    }
+
+    fn generate_dependencies(&self, input: &Self::Record, output: &mut Self::Record) {
+        let events = Self::events(input);
+        let chunk_size = std::cmp::max(events.len() / num_cpus::get(), 1);
+
+        let blu_batches = events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<u32, HashMap<ByteLookupEvent, usize>> = HashMap::new();
+                events.iter().for_each(|event| {
+                    self.event_to_rows::<F>(event, 0, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_sharded_byte_lookup_events(blu_batches.iter().collect_vec());
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !Self::events(shard).is_empty()
+    }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(Self::events(shard).iter().map(msm_num_rows).sum())
+    }
+}
+
+impl<E: EllipticCurve + WeierstrassParameters> WeierstrassMsmChip<E> {
+    /// Create the rows of an MSM window from an event.
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &MsmEvent,
+        nonce: usize,
+        blu: &mut impl ByteRecord,
+    ) -> Vec<Vec<F>> {
+        let coordinates = |point: ProjectivePoint<E>| [point.x, point.y, point.z];
+        let acc_in = coordinates(ProjectivePoint::from_words_le(&event.acc));
+        let mut acc = acc_in.clone();
+
+        let num_rows = msm_num_rows(event);
+        let mut rows = Vec::with_capacity(num_rows);
+        for row_index in 0..num_rows {
+            let mut row = vec![F::zero(); num_weierstrass_msm_cols::<E::BaseField>()];
+            let cols: &mut WeierstrassMsmCols<F, E::BaseField> = row.as_mut_slice().borrow_mut();
+            cols.schedule.populate(event, nonce, row_index, blu);
+
+            cols.acc_in = acc_in.clone().map(|c| E::BaseField::to_limbs_field::<F, F>(&c));
+            cols.acc = acc.clone().map(|c| E::BaseField::to_limbs_field::<F, F>(&c));
+
+            // Double on the first rows, then add the multiple of each point.
+            let rhs = if row_index < MSM_WINDOW_BITS {
+                acc.clone()
+            } else {
+                let i = row_index - MSM_WINDOW_BITS;
+                let records = event.entry_memory_records[i].chunks_exact(num_words::<E>());
+                for (accesses, records) in cols.entry_access.iter_mut().zip(records) {
+                    for (access, record) in accesses.iter_mut().zip(records) {
+                        access.populate(event.channel, *record, blu);
+                    }
+                }
+                coordinates(ProjectivePoint::from_words_le(&event.entries[i]))
+            };
+            cols.rhs = rhs.clone().map(|c| E::BaseField::to_limbs_field::<F, F>(&c));
+
+            acc = cols.add.populate::<E>(blu, event.shard, event.channel, &acc, &rhs);
+
+            // The result is written back to memory on the last row.
+            if row_index == num_rows - 1 {
+                let records = event.acc_memory_records.chunks_exact(num_words::<E>());
+                for (accesses, records) in cols.acc_access.iter_mut().zip(records) {
+                    for (access, record) in accesses.iter_mut().zip(records) {
+                        access.populate(event.channel, *record, blu);
+                    }
+                }
+            }
+
+            rows.push(row);
+        }
+
+        rows
+    }
+
+    /// Create a padding row, which doubles the point at infinity.
+    fn padding_row<F: PrimeField32>(nonce: usize) -> Vec<F> {
+        let mut row = vec![F::zero(); num_weierstrass_msm_cols::<E::BaseField>()];
+        let cols: &mut WeierstrassMsmCols<F, E::BaseField> = row.as_mut_slice().borrow_mut();
+        cols.schedule.nonce = F::from_canonical_usize(nonce);
+
+        let neutral = ProjectivePoint::<E>::neutral();
+        let neutral = [neutral.x, neutral.y, neutral.z];
+        cols.acc = neutral.clone().map(|c| E::BaseField::to_limbs_field::<F, F>(&c));
+        cols.rhs = cols.acc.clone();
+        cols.add.populate::<E>(&mut vec![], 0, 0, &neutral, &neutral);
+        row
+    }
+}
+
+/// The number of words of a coordinate.
+fn num_words<E: EllipticCurve>() -> usize {
+    <E::BaseField as NumWords>::WordsFieldElement::USIZE
+}
+
+impl<F, E: EllipticCurve + WeierstrassParameters> BaseAir<F> for WeierstrassMsmChip<E> {
+    fn width(&self) -> usize {
+        num_weierstrass_msm_cols::<E::BaseField>()
+    }
+}
+
+impl<AB, E: EllipticCurve + WeierstrassParameters> Air<AB> for WeierstrassMsmChip<E>
+where
+    AB: SP1AirBuilder,
+    Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &WeierstrassMsmCols<AB::Var, E::BaseField> = (*local).borrow();
+        let next: &WeierstrassMsmCols<AB::Var, E::BaseField> = (*next).borrow();
+        let schedule = &local.schedule;
+        let num_limbs = <E::BaseField as NumLimbs>::Limbs::USIZE;
+        let num_words = num_words::<E>();
+
+        schedule.eval(builder, &next.schedule, Self::syscall_code());
+
+        // On the first row, the accumulator is the one read from memory.
+        for c in 0..3 {
+            builder.when(schedule.is_first_row()).assert_all_eq(local.acc[c], local.acc_in[c]);
+        }
+
+        // The right-hand side is the accumulator on doubling rows, and the multiple read from the
+        // table on add rows.
+        for c in 0..3 {
+            for k in 0..num_limbs {
+                let entry = local.entry_access[c][k / 4].value()[k % 4];
+                builder.assert_eq(
+                    local.rhs[c][k],
+                    schedule.is_add * entry + (AB::Expr::one() - schedule.is_add) * local.acc[c][k],
+                );
+            }
+            builder.eval_memory_access_slice(
+                schedule.shard,
+                schedule.channel,
+                schedule.clk,
+                schedule.entry_ptr::<AB>(3 * num_words)
+                    + AB::F::from_canonical_usize(c * num_words * 4),
+                &local.entry_access[c],
+                schedule.is_add,
+            );
+        }
+
+        // acc + rhs.
+        local.add.eval::<AB, E>(
+            builder,
+            local.acc,
+            local.rhs,
+            schedule.shard,
+            schedule.channel,
+            schedule.is_real,
+        );
+        let result = [local.add.x3.result, local.add.y3.result, local.add.z3.result];
+
+        // The result is carried over to the next row within a window.
+        for c in 0..3 {
+            let mut within = builder.when_transition();
+            let mut within = within.when(schedule.within_window::<AB>());
+            within.assert_all_eq(next.acc[c], result[c]);
+            within.assert_all_eq(next.acc_in[c], local.acc_in[c]);
+        }
+
+        // The accumulator is read from and written back to memory on the last row.
+        for c in 0..3 {
+            for k in 0..num_limbs {
+                let access = &local.acc_access[c][k / 4];
+                let mut when_last_row = builder.when(schedule.is_last_row);
+                when_last_row.assert_eq(access.prev_value()[k % 4], local.acc_in[c][k]);
+                when_last_row.assert_eq(access.value()[k % 4], result[c][k]);
+            }
+            builder.eval_memory_access_slice(
+                schedule.shard,
+                schedule.channel,
+                schedule.clk + AB::F::from_canonical_u32(1),
+                schedule.acc_ptr.into() + AB::F::from_canonical_usize(c * num_words * 4),
+                &local.acc_access[c],
+                schedule.is_last_row,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use sp1_core_executor::{
+        events::{MSM_TABLE_SIZE, MSM_WINDOW_BITS},
+        syscalls::SyscallCode,
+        Executor, Program,
+    };
+    use sp1_curves::weierstrass::{
+        bn254::Bn254Parameters, projective::ProjectivePoint, secp256k1::Secp256k1Parameters,
+        SwCurve, WeierstrassParameters,
+    };
+    use sp1_stark::{CpuProver, SP1CoreOpts};
+
+    use crate::{
+        syscall::precompiles::msm::tests::{msm_program, ACC_PTR},
+        utils::{self, run_test},
+    };
+
+    /// The table of the multiples `[0] p, ..., [15] p`.
+    fn table<E: WeierstrassParameters>(
+        p: &ProjectivePoint<SwCurve<E>>,
+    ) -> Vec<ProjectivePoint<SwCurve<E>>> {
+        let mut table = vec![ProjectivePoint::neutral()];
+        for k in 1..MSM_TABLE_SIZE {
+            table.push(table[k - 1].complete_add(p));
+        }
+        table
+    }
+
+    fn weierstrass_msm_program<E: WeierstrassParameters>(
+        syscall: SyscallCode,
+        digits: &[u32],
+    ) -> (Program, Vec<u32>) {
+        let g = SwCurve::<E>::generator();
+        let acc = ProjectivePoint::from_affine(&g.sw_scalar_mul(&BigUint::from(3u32)));
+        let points = (1..=digits.len() as u32)
+            .map(|k| ProjectivePoint::from_affine(&g.sw_scalar_mul(&BigUint::from(k * 7 + 1))));
+        let tables = points.map(|p| table(&p)).collect::<Vec<_>>();
+
+        let mut expected = acc.clone();
+        for _ in 0..MSM_WINDOW_BITS {
+            expected = expected.complete_add(&expected);
+        }
+        for (table, digit) in tables.iter().zip(digits) {
+            expected = expected.complete_add(&table[*digit as usize]);
+        }
+
+        let tables = tables
+            .iter()
+            .map(|table| table.iter().map(ProjectivePoint::to_words_le).collect())
+            .collect::<Vec<_>>();
+        let program = msm_program(syscall, &acc.to_words_le(), &tables, digits);
+        (program, expected.to_words_le())
+    }
+
+    #[test]
+    fn test_weierstrass_msm_execute() {
+        utils::setup_logger();
+        for (program, expected) in [
+            weierstrass_msm_program::<Secp256k1Parameters>(SyscallCode::SECP256K1_MSM, &[5, 0, 15]),
+            weierstrass_msm_program::<Bn254Parameters>(SyscallCode::BN254_MSM, &[9, 1]),
+        ] {
+            let mut runtime = Executor::new(program, SP1CoreOpts::default());
+            runtime.run().unwrap();
+
+            let result = (0..24).map(|i| runtime.word(ACC_PTR + i * 4)).collect::<Vec<_>>();
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn test_secp256k1_msm_prove() {
+        utils::setup_logger();
+        let (program, _) =
+            weierstrass_msm_program::<Secp256k1Parameters>(SyscallCode::SECP256K1_MSM, &[5, 0]);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_bn254_msm_prove() {
+        utils::setup_logger();
+        let (program, _) =
+            weierstrass_msm_program::<Bn254Parameters>(SyscallCode::BN254_MSM, &[15, 3, 0]);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
@@ -0,0 +1,544 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+use sp1_core_executor::syscalls::SyscallCode;
+use sp1_stark::{air::SP1AirBuilder, Word};
+
+use super::{
+    columns::{Ripemd160CompressCols, Ripemd160LineCols, NUM_RIPEMD160_COMPRESS_COLS},
+    Ripemd160CompressChip, Ripemd160Line, RIPEMD160_ROTATIONS,
+};
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::MemoryCols,
+    operations::{
+        Add4Operation, AddOperation, AndOperation, FixedRotateRightOperation, NotOperation,
+        OrOperation, XorOperation,
+    },
+};
+
+impl<F> BaseAir<F> for Ripemd160CompressChip {
+    fn width(&self) -> usize {
+        NUM_RIPEMD160_COMPRESS_COLS
+    }
+}
+
+impl<AB> Air<AB> for Ripemd160CompressChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Ripemd160CompressCols<AB::Var> = (*local).borrow();
+        let next: &Ripemd160CompressCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder.when_transition().assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        self.eval_control_flow_flags(builder, local, next);
+
+        self.eval_memory(builder, local);
+
+        self.eval_line(builder, local, &local.left, &next.left, Ripemd160Line::LEFT);
+        self.eval_line(builder, local, &local.right, &next.right, Ripemd160Line::RIGHT);
+
+        self.eval_finalize_ops(builder, local);
+
+        builder.assert_eq(local.start, local.is_real * local.cycle[0]);
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::RIPEMD160_COMPRESS.syscall_id()),
+            local.w_ptr,
+            local.h_ptr,
+            local.start,
+        );
+    }
+}
+
+impl Ripemd160CompressChip {
+    fn eval_control_flow_flags<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Ripemd160CompressCols<AB::Var>,
+        next: &Ripemd160CompressCols<AB::Var>,
+    ) {
+        // Verify that all of the cycle columns are bool.
+        for i in 0..90 {
+            builder.assert_bool(local.cycle[i]);
+        }
+
+        // Verify that exactly one of the cycle columns is true.
+        let mut cycle_sum = AB::Expr::zero();
+        for i in 0..90 {
+            cycle_sum += local.cycle[i].into();
+        }
+        builder.assert_one(cycle_sum);
+
+        // Verify that the first row's cycle value is correct.
+        builder.when_first_row().assert_one(local.cycle[0]);
+
+        // Verify correct transition for cycle column.
+        for i in 0..90 {
+            builder.when_transition().when(local.cycle[i]).assert_one(next.cycle[(i + 1) % 90]);
+        }
+
+        let is_last_row = local.cycle[89];
+
+        // Assert that the phase flags are correct.
+        let sum_cycles = |range: core::ops::Range<usize>| {
+            range.fold(AB::Expr::zero(), |acc, i| acc + local.cycle[i].into())
+        };
+        builder.assert_eq(local.is_initialize, sum_cycles(0..5) * local.is_real);
+        builder.assert_eq(local.is_compression, sum_cycles(5..85) * local.is_real);
+        builder.assert_eq(local.is_message, sum_cycles(5..21) * local.is_real);
+        builder.assert_eq(local.is_finalize, sum_cycles(85..90) * local.is_real);
+
+        // If this row is real and not the last cycle, then next row should have same inputs.
+        let mut inputs = builder.when_transition();
+        let mut inputs = inputs.when(local.is_real);
+        let mut inputs = inputs.when_not(is_last_row);
+        inputs.assert_eq(local.shard, next.shard);
+        inputs.assert_eq(local.clk, next.clk);
+        inputs.assert_eq(local.channel, next.channel);
+        inputs.assert_eq(local.w_ptr, next.w_ptr);
+        inputs.assert_eq(local.h_ptr, next.h_ptr);
+        for i in 0..5 {
+            inputs.assert_word_eq(local.h[i], next.h[i]);
+        }
+        for i in 0..16 {
+            inputs.assert_word_eq(local.w[i], next.w[i]);
+        }
+
+        // During init, both lines start from the state, and keep it until the first step.
+        for i in 0..5 {
+            builder.when(local.is_initialize).assert_word_eq(local.left.state[i], local.h[i]);
+            builder.when(local.is_initialize).assert_word_eq(local.right.state[i], local.h[i]);
+            builder
+                .when_transition()
+                .when(local.is_initialize)
+                .assert_word_eq(local.left.state[i], next.left.state[i]);
+            builder
+                .when_transition()
+                .when(local.is_initialize)
+                .assert_word_eq(local.right.state[i], next.right.state[i]);
+        }
+
+        // During finalize, the lines keep their final value.
+        for i in 0..5 {
+            builder
+                .when_transition()
+                .when(local.is_finalize)
+                .when_not(is_last_row)
+                .assert_word_eq(local.left.state[i], next.left.state[i]);
+            builder
+                .when_transition()
+                .when(local.is_finalize)
+                .when_not(is_last_row)
+                .assert_word_eq(local.right.state[i], next.right.state[i]);
+        }
+
+        // Assert that is_real is a bool.
+        builder.assert_bool(local.is_real);
+
+        // If this row is real and not the last cycle, then next row should also be real.
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(is_last_row)
+            .assert_one(next.is_real);
+
+        // Once the is_real flag is changed to false, it should not be changed back.
+        builder.when_transition().when_not(local.is_real).assert_zero(next.is_real);
+
+        // Assert that the table ends in nonreal columns. Since each compress ecall is 90 cycles and
+        // the table is padded to a power of 2, the last row of the table should always be padding.
+        builder.when_last_row().assert_zero(local.is_real);
+    }
+
+    /// Constrains that memory address is correct and that memory is correctly written/read.
+    fn eval_memory<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Ripemd160CompressCols<AB::Var>,
+    ) {
+        builder.eval_memory_access(
+            local.shard,
+            local.channel,
+            local.clk + local.is_finalize,
+            local.mem_addr,
+            &local.mem,
+            local.is_initialize + local.is_message + local.is_finalize,
+        );
+
+        // Calculate the index of the word accessed in each phase.
+        let index = |offset: usize, len: usize| {
+            (0..len).fold(AB::Expr::zero(), |acc, i| {
+                acc + local.cycle[offset + i] * AB::Expr::from_canonical_usize(i)
+            })
+        };
+
+        // Verify correct mem address for initialize phase.
+        builder
+            .when(local.is_initialize)
+            .assert_eq(local.mem_addr, local.h_ptr + index(0, 5) * AB::Expr::from_canonical_u32(4));
+
+        // Verify correct mem address for the message reads of the compression phase.
+        builder.when(local.is_message).assert_eq(
+            local.mem_addr,
+            local.w_ptr + index(5, 16) * AB::Expr::from_canonical_u32(4),
+        );
+
+        // Verify correct mem address for finalize phase.
+        builder.when(local.is_finalize).assert_eq(
+            local.mem_addr,
+            local.h_ptr + index(85, 5) * AB::Expr::from_canonical_u32(4),
+        );
+
+        // Verify that the reads do not change memory, and that the state and the message block
+        // are the words read.
+        builder
+            .when(local.is_initialize + local.is_message)
+            .assert_word_eq(*local.mem.prev_value(), *local.mem.value());
+        for i in 0..5 {
+            builder
+                .when(local.is_initialize)
+                .when(local.cycle[i])
+                .assert_word_eq(local.h[i], *local.mem.value());
+        }
+        for i in 0..16 {
+            builder
+                .when(local.is_message)
+                .when(local.cycle[5 + i])
+                .assert_word_eq(local.w[i], *local.mem.value());
+        }
+
+        // In the finalize phase, verify that the correct value is written to memory.
+        builder
+            .when(local.is_finalize)
+            .assert_word_eq(*local.mem.value(), local.finalize_add_right.value);
+    }
+
+    /// Constrains a step of a line, and the transition to the state of the next step.
+    fn eval_line<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Ripemd160CompressCols<AB::Var>,
+        line_cols: &Ripemd160LineCols<AB::Var>,
+        next_line_cols: &Ripemd160LineCols<AB::Var>,
+        line: Ripemd160Line,
+    ) {
+        let [a, b, c, d, e] = line_cols.state;
+        let is_compression = local.is_compression;
+
+        // The flags of the step and of the round of the step.
+        let step = |j: usize| local.cycle[5 + j];
+        let round =
+            |r: usize| (16 * r..16 * (r + 1)).fold(AB::Expr::zero(), |acc, j| acc + step(j).into());
+
+        // Constrain the word of the message block and the constant used by the step.
+        let mut x = Word([AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero()]);
+        for j in 0..80 {
+            for i in 0..4 {
+                x.0[i] += step(j) * local.w[line.r[j]][i];
+            }
+        }
+        builder.when(is_compression).assert_word_eq(line_cols.x, x);
+        let mut k = Word([AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero()]);
+        for r in 0..5 {
+            let k_r = Word::<AB::F>::from(line.k[r]);
+            for i in 0..4 {
+                k.0[i] += round(r) * k_r[i];
+            }
+        }
+        builder.when(is_compression).assert_word_eq(line_cols.k, k);
+
+        // Calculate f0 := b xor c xor d.
+        XorOperation::<AB::F>::eval(
+            builder,
+            b,
+            c,
+            line_cols.b_xor_c,
+            local.shard,
+            local.channel,
+            is_compression,
+        );
+        XorOperation::<AB::F>::eval(
+            builder,
+            line_cols.b_xor_c.value,
+            d,
+            line_cols.f0,
+            local.shard,
+            local.channel,
+            is_compression,
+        );
+
+        // Calculate f1 := (b and c) xor ((not b) and d).
+        AndOperation::<AB::F>::eval(
+            builder,
+            b,
+            c,
+            line_cols.b_and_c,
+            local.shard,
+            local.channel,
+            is_compression,
+        );
+        NotOperation::<AB::F>::eval(
+            builder,
+            b,
+            line_cols.b_not,
+            local.shard,
+            local.channel,
+            is_compression,
+        );
+        AndOperation::<AB::F>::eval(
+            builder,
+            line_cols.b_not.value,
+            d,
+            line_cols.b_not_and_d,
+            local.shard,
+            local.channel,
+            is_compression,
+        );
+        XorOperation::<AB::F>::eval(
+            builder,
+            line_cols.b_and_c.value,
+            line_cols.b_not_and_d.value,
+            line_cols.f1,
+            local.shard,
+            local.channel,
+            is_compression,
+        );
+
+        // Calculate f2 := (b or (not c)) xor d.
+        NotOperation::<AB::F>::eval(
+            builder,
+            c,
+            line_cols.c_not,
+            local.shard,
+            local.channel,
+            is_compression,
+        );
+        OrOperation::<AB::F>::eval(
+            builder,
+            b,
+            line_cols.c_not.value,
+            line_cols.b_or_c_not,
+            local.shard,
+            local.channel,
+            is_compression,
+        );
+        XorOperation::<AB::F>::eval(
+            builder,
+            line_cols.b_or_c_not.value,
+            d,
+            line_cols.f2,
+            local.shard,
+            local.channel,
+            is_compression,
+        );
+
+        // Calculate f3 := (b and d) xor (c and (not d)).
+        AndOperation::<AB::F>::eval(
+            builder,
+            b,
+            d,
+            line_cols.b_and_d,
+            local.shard,
+            local.channel,
+            is_compression,
+        );
+        NotOperation::<AB::F>::eval(
+            builder,
+            d,
+            line_cols.d_not,
+            local.shard,
+            local.channel,
+            is_compression,
+        );
+        AndOperation::<AB::F>::eval(
+            builder,
+            c,
+            line_cols.d_not.value,
+            line_cols.c_and_d_not,
+            local.shard,
+            local.channel,
+            is_compression,
+        );
+        XorOperation::<AB::F>::eval(
+            builder,
+            line_cols.b_and_d.value,
+            line_cols.c_and_d_not.value,
+            line_cols.f3,
+            local.shard,
+            local.channel,
+            is_compression,
+        );
+
+        // Calculate f4 := b xor (c or (not d)).
+        OrOperation::<AB::F>::eval(
+            builder,
+            c,
+            line_cols.d_not.value,
+            line_cols.c_or_d_not,
+            local.shard,
+            local.channel,
+            is_compression,
+        );
+        XorOperation::<AB::F>::eval(
+            builder,
+            b,
+            line_cols.c_or_d_not.value,
+            line_cols.f4,
+            local.shard,
+            local.channel,
+            is_compression,
+        );
+
+        // Select the boolean function of the round.
+        let functions = [
+            line_cols.f0.value,
+            line_cols.f1.value,
+            line_cols.f2.value,
+            line_cols.f3.value,
+            line_cols.f4.value,
+        ];
+        let mut f = Word([AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero()]);
+        for r in 0..5 {
+            for i in 0..4 {
+                f.0[i] += round(r) * functions[line.function(r)][i];
+            }
+        }
+        builder.when(is_compression).assert_word_eq(line_cols.f, f);
+
+        // Calculate sum := a + f + x + k.
+        Add4Operation::<AB::F>::eval(
+            builder,
+            a,
+            line_cols.f,
+            line_cols.x,
+            line_cols.k,
+            local.shard,
+            local.channel,
+            is_compression,
+            line_cols.sum,
+        );
+
+        // Decompose sum into bits.
+        for bit in line_cols.sum_bits {
+            builder.assert_bool(bit);
+        }
+        for i in 0..4 {
+            let byte = (0..8).fold(AB::Expr::zero(), |acc, j| {
+                acc + line_cols.sum_bits[8 * i + j] * AB::Expr::from_canonical_u32(1 << j)
+            });
+            builder.when(is_compression).assert_eq(line_cols.sum.value[i], byte);
+        }
+
+        // Constrain the rotation flags to the rotation of the step.
+        for (flag, rotation) in line_cols.rotation.iter().zip(RIPEMD160_ROTATIONS) {
+            let steps = (0..80)
+                .filter(|&j| line.s[j] == rotation)
+                .fold(AB::Expr::zero(), |acc, j| acc + step(j).into());
+            builder.when(is_compression).assert_eq(*flag, steps);
+        }
+
+        // Calculate sum leftrotate s by moving the bits of sum according to the rotation flags.
+        let mut rotated =
+            Word([AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero()]);
+        for (flag, rotation) in line_cols.rotation.iter().zip(RIPEMD160_ROTATIONS) {
+            for (i, bit) in line_cols.sum_bits.iter().enumerate() {
+                let target = (i + rotation as usize) % 32;
+                rotated.0[target / 8] +=
+                    *flag * *bit * AB::Expr::from_canonical_u32(1 << (target % 8));
+            }
+        }
+        builder.when(is_compression).assert_word_eq(line_cols.rotated, rotated);
+
+        // Calculate t := (sum leftrotate s) + e.
+        AddOperation::<AB::F>::eval(
+            builder,
+            line_cols.rotated,
+            e,
+            line_cols.t,
+            local.shard,
+            local.channel,
+            is_compression.into(),
+        );
+
+        // Calculate c leftrotate 10.
+        FixedRotateRightOperation::<AB::F>::eval(
+            builder,
+            c,
+            22,
+            line_cols.c_rr_22,
+            local.shard,
+            local.channel,
+            is_compression,
+        );
+
+        // a := e
+        // b := t
+        // c := b
+        // d := c leftrotate 10
+        // e := d
+        let next_state = [e, line_cols.t.value, b, line_cols.c_rr_22.value, d];
+        for (next_word, word) in next_line_cols.state.iter().zip(next_state) {
+            builder.when_transition().when(is_compression).assert_word_eq(*next_word, word);
+        }
+    }
+
+    fn eval_finalize_ops<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Ripemd160CompressCols<AB::Var>,
+    ) {
+        // In the finalize phase, the i-th word of the new state is h[i + 1] + left[i + 2] +
+        // right[i + 3], with indices taken modulo 5. We get the operands by selecting them with
+        // the cycle flags of the phase.
+        let zero =
+            || Word([AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero()]);
+        let (mut h, mut left, mut right) = (zero(), zero(), zero());
+        for i in 0..5 {
+            let flag = local.cycle[85 + i];
+            for j in 0..4 {
+                h.0[j] += flag * local.h[(i + 1) % 5][j];
+                left.0[j] += flag * local.left.state[(i + 2) % 5][j];
+                right.0[j] += flag * local.right.state[(i + 3) % 5][j];
+            }
+        }
+        builder.when(local.is_finalize).assert_word_eq(local.finalize_h, h);
+        builder.when(local.is_finalize).assert_word_eq(local.finalize_left, left);
+        builder.when(local.is_finalize).assert_word_eq(local.finalize_right, right);
+
+        // finalize_add_left.result = h[i + 1] + left[i + 2]
+        AddOperation::<AB::F>::eval(
+            builder,
+            local.finalize_h,
+            local.finalize_left,
+            local.finalize_add_left,
+            local.shard,
+            local.channel,
+            local.is_finalize.into(),
+        );
+
+        // finalize_add_right.result = h[i + 1] + left[i + 2] + right[i + 3]
+        AddOperation::<AB::F>::eval(
+            builder,
+            local.finalize_add_left.value,
+            local.finalize_right,
+            local.finalize_add_right,
+            local.shard,
+            local.channel,
+            local.is_finalize.into(),
+        );
+
+        // Memory write is constrained in eval_memory.
+    }
+}
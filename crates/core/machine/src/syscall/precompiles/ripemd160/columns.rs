@@ -0,0 +1,125 @@
+use std::mem::size_of;
+
+use sp1_derive::AlignedBorrow;
+use sp1_stark::Word;
+
+use crate::{
+    memory::MemoryReadWriteCols,
+    operations::{
+        Add4Operation, AddOperation, AndOperation, FixedRotateRightOperation, NotOperation,
+        OrOperation, XorOperation,
+    },
+};
+
+pub const NUM_RIPEMD160_COMPRESS_COLS: usize = size_of::<Ripemd160CompressCols<u8>>();
+
+/// A set of columns needed to compute the RIPEMD-160 compression function.
+///
+/// Each ripemd160 compress syscall is processed over 90 rows. The first 5 rows are for
+/// initialization, the next 80 rows are for compression, and the last 5 rows are for finalize.
+/// During init, the state is read from memory one word at a time. During each compression cycle,
+/// one step of both lines is computed, and the first 16 cycles read the message block one word at
+/// a time. During finalize, the two lines are combined with the state and written back to memory.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Ripemd160CompressCols<T> {
+    /// Inputs.
+    pub shard: T,
+    pub channel: T,
+    pub nonce: T,
+    pub clk: T,
+    pub w_ptr: T,
+    pub h_ptr: T,
+
+    pub start: T,
+
+    /// Which of the 90 cycles of the syscall we are currently processing.
+    pub cycle: [T; 90],
+
+    /// Memory access. During init and the first 16 compression cycles, this is read only. During
+    /// finalize, this is used to write the result into memory.
+    pub mem: MemoryReadWriteCols<T>,
+    /// Current memory address being written/read.
+    pub mem_addr: T,
+
+    /// The state before the compression, read during init.
+    pub h: [Word<T>; 5],
+    /// The message block, read during the first 16 compression cycles.
+    pub w: [Word<T>; 16],
+
+    /// The left line.
+    pub left: Ripemd160LineCols<T>,
+    /// The right line.
+    pub right: Ripemd160LineCols<T>,
+
+    /// During finalize, the words of the state and of the two lines whose sum is written into
+    /// `mem`.
+    pub finalize_h: Word<T>,
+    pub finalize_left: Word<T>,
+    pub finalize_right: Word<T>,
+    pub finalize_add_left: AddOperation<T>,
+    pub finalize_add_right: AddOperation<T>,
+
+    pub is_initialize: T,
+    pub is_compression: T,
+    /// Whether the row reads a word of the message block, during the first 16 compression cycles.
+    pub is_message: T,
+    pub is_finalize: T,
+
+    pub is_real: T,
+}
+
+/// A set of columns needed to compute a step of one line of the RIPEMD-160 compression function.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Ripemd160LineCols<T> {
+    /// The words `a`, `b`, `c`, `d` and `e` of the line before the step.
+    pub state: [Word<T>; 5],
+
+    /// The word of the message block selected by the step.
+    pub x: Word<T>,
+    /// The constant of the current round.
+    pub k: Word<T>,
+
+    pub b_xor_c: XorOperation<T>,
+    /// `f0 := b xor c xor d`.
+    pub f0: XorOperation<T>,
+
+    pub b_and_c: AndOperation<T>,
+    pub b_not: NotOperation<T>,
+    pub b_not_and_d: AndOperation<T>,
+    /// `f1 := (b and c) or ((not b) and d)`, as a xor since the two sides have no common bit.
+    pub f1: XorOperation<T>,
+
+    pub c_not: NotOperation<T>,
+    pub b_or_c_not: OrOperation<T>,
+    /// `f2 := (b or (not c)) xor d`.
+    pub f2: XorOperation<T>,
+
+    pub b_and_d: AndOperation<T>,
+    pub d_not: NotOperation<T>,
+    pub c_and_d_not: AndOperation<T>,
+    /// `f3 := (b and d) or (c and (not d))`, as a xor since the two sides have no common bit.
+    pub f3: XorOperation<T>,
+
+    pub c_or_d_not: OrOperation<T>,
+    /// `f4 := b xor (c or (not d))`.
+    pub f4: XorOperation<T>,
+
+    /// The boolean function of the current round, applied to `b`, `c` and `d`.
+    pub f: Word<T>,
+
+    /// `sum := a + f + x + k`.
+    pub sum: Add4Operation<T>,
+    /// The little-endian bits of `sum`.
+    pub sum_bits: [T; 32],
+    /// Which of `RIPEMD160_ROTATIONS` the step applies.
+    pub rotation: [T; 10],
+    /// `sum leftrotate s`.
+    pub rotated: Word<T>,
+
+    /// `t := (sum leftrotate s) + e`, the next value of `b`.
+    pub t: AddOperation<T>,
+    /// `c leftrotate 10`, the next value of `d`, computed as `c rightrotate 22`.
+    pub c_rr_22: FixedRotateRightOperation<T>,
+}
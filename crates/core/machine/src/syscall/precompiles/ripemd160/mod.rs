@@ -0,0 +1,160 @@
+mod air;
+mod columns;
+mod trace;
+
+/// The message word selected at each step of the left line.
+pub const RIPEMD160_R: [usize; 80] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5,
+    2, 14, 11, 8, 3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12, 1, 9, 11, 10, 0, 8, 12, 4,
+    13, 3, 7, 15, 14, 5, 6, 2, 4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+];
+
+/// The message word selected at each step of the right line.
+pub const RIPEMD160_R_PRIME: [usize; 80] = [
+    5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12, 6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12,
+    4, 9, 1, 2, 15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13, 8, 6, 4, 1, 3, 11, 15, 0, 5,
+    12, 2, 13, 9, 7, 10, 14, 12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+];
+
+/// The left rotation applied at each step of the left line.
+pub const RIPEMD160_S: [u32; 80] = [
+    11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8, 7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15,
+    9, 11, 7, 13, 12, 11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5, 11, 12, 14, 15, 14,
+    15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12, 9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+];
+
+/// The left rotation applied at each step of the right line.
+pub const RIPEMD160_S_PRIME: [u32; 80] = [
+    8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6, 9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12,
+    7, 6, 15, 13, 11, 9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5, 15, 5, 8, 11, 14, 14,
+    6, 14, 6, 9, 12, 9, 12, 5, 15, 8, 8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+];
+
+/// The constant added in each round of the left line.
+pub const RIPEMD160_K: [u32; 5] = [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xa953fd4e];
+
+/// The constant added in each round of the right line.
+pub const RIPEMD160_K_PRIME: [u32; 5] =
+    [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x7a6d76e9, 0x00000000];
+
+/// The distinct left rotations applied by the steps of either line.
+pub const RIPEMD160_ROTATIONS: [u32; 10] = [5, 6, 7, 8, 9, 11, 12, 13, 14, 15];
+
+/// One of the two parallel lines of the RIPEMD-160 compression function.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Ripemd160Line {
+    /// The message word selected at each step.
+    pub r: &'static [usize; 80],
+    /// The left rotation applied at each step.
+    pub s: &'static [u32; 80],
+    /// The constant added in each round.
+    pub k: &'static [u32; 5],
+    /// Whether the line applies the boolean functions of the rounds in reverse order.
+    pub reversed: bool,
+}
+
+impl Ripemd160Line {
+    pub const LEFT: Self =
+        Self { r: &RIPEMD160_R, s: &RIPEMD160_S, k: &RIPEMD160_K, reversed: false };
+
+    pub const RIGHT: Self = Self {
+        r: &RIPEMD160_R_PRIME,
+        s: &RIPEMD160_S_PRIME,
+        k: &RIPEMD160_K_PRIME,
+        reversed: true,
+    };
+
+    /// The index of the boolean function applied in the given round.
+    pub const fn function(&self, round: usize) -> usize {
+        if self.reversed {
+            4 - round
+        } else {
+            round
+        }
+    }
+}
+
+/// Implements the RIPEMD-160 compress operation, which runs 80 steps of two parallel lines over
+/// the state and combines them into the new state. The inputs to the syscall are a pointer to the
+/// 16 word message block W and a pointer to the 5 word state H.
+///
+/// In the AIR, each RIPEMD-160 compress syscall takes up 90 rows. The first and last 5 rows are
+/// for initialization and finalize respectively, and each reads or writes a single word of H. The
+/// middle 80 rows each compute a step of both lines, and the first 16 of them read a word of W.
+#[derive(Default)]
+pub struct Ripemd160CompressChip;
+
+impl Ripemd160CompressChip {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+pub mod compress_tests {
+
+    use sp1_core_executor::{syscalls::SyscallCode, Executor, Instruction, Opcode, Program};
+    use sp1_stark::{CpuProver, SP1CoreOpts};
+
+    use crate::utils::{run_test, setup_logger};
+
+    const W_PTR: u32 = 100;
+    const H_PTR: u32 = 1000;
+
+    /// The initial state of RIPEMD-160.
+    const H0: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+    /// The padded block of the message "abc".
+    fn abc_block() -> [u32; 16] {
+        let mut w = [0; 16];
+        w[0] = 0x80636261;
+        w[14] = 24;
+        w
+    }
+
+    pub fn ripemd160_compress_program(w: [u32; 16], h: [u32; 5]) -> Program {
+        let mut instructions = vec![];
+        for (ptr, words) in [(W_PTR, w.as_slice()), (H_PTR, h.as_slice())] {
+            for (i, word) in words.iter().enumerate() {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr + i as u32 * 4, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+        }
+        instructions.extend(vec![
+            Instruction::new(
+                Opcode::ADD,
+                5,
+                0,
+                SyscallCode::RIPEMD160_COMPRESS as u32,
+                false,
+                true,
+            ),
+            Instruction::new(Opcode::ADD, 10, 0, W_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, H_PTR, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_ripemd160_compress_execute() {
+        setup_logger();
+        let program = ripemd160_compress_program(abc_block(), H0);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        // The digest of "abc" is 8eb208f7e05d987a9b044a8e98c6b087f15a0bfc.
+        let h = (0..5).map(|i| runtime.word(H_PTR + i * 4)).collect::<Vec<_>>();
+        assert_eq!(h, [0xf708b28e, 0x7a985de0, 0x8e4a049b, 0x87b0c698, 0xfc0b5af1]);
+    }
+
+    #[test]
+    fn prove_babybear() {
+        setup_logger();
+        let program = ripemd160_compress_program(abc_block(), H0);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
@@ -0,0 +1,242 @@
+use std::borrow::BorrowMut;
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_field::PrimeField32;
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+use sp1_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, Ripemd160CompressEvent, RIPEMD160_COMPRESS_NUM_ROWS},
+    ExecutionRecord, Program,
+};
+use sp1_stark::{air::MachineAir, Word};
+
+use super::{
+    columns::{Ripemd160CompressCols, Ripemd160LineCols, NUM_RIPEMD160_COMPRESS_COLS},
+    Ripemd160CompressChip, Ripemd160Line, RIPEMD160_ROTATIONS,
+};
+use crate::utils::pad_rows;
+
+impl<F: PrimeField32> MachineAir<F> for Ripemd160CompressChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Ripemd160Compress".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut wrapped_rows = Some(Vec::new());
+        for event in input.ripemd160_compress_events.iter() {
+            self.event_to_rows(event, &mut wrapped_rows, &mut Vec::new());
+        }
+        let mut rows = wrapped_rows.unwrap();
+
+        let num_real_rows = rows.len();
+
+        pad_rows(&mut rows, || [F::zero(); NUM_RIPEMD160_COMPRESS_COLS]);
+
+        // Set the cycle columns for the padded rows.
+        for (i, row) in rows[num_real_rows..].iter_mut().enumerate() {
+            let cols: &mut Ripemd160CompressCols<F> = row.as_mut_slice().borrow_mut();
+            cols.cycle[i % RIPEMD160_COMPRESS_NUM_ROWS] = F::one();
+        }
+
+        // Convert the trace to a row major matrix.
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_RIPEMD160_COMPRESS_COLS,
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut Ripemd160CompressCols<F> = trace.values
+                [i * NUM_RIPEMD160_COMPRESS_COLS..(i + 1) * NUM_RIPEMD160_COMPRESS_COLS]
+                .borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn generate_dependencies(&self, input: &Self::Record, output: &mut Self::Record) {
+        let chunk_size = std::cmp::max(input.ripemd160_compress_events.len() / num_cpus::get(), 1);
+
+        let blu_batches = input
+            .ripemd160_compress_events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<u32, HashMap<ByteLookupEvent, usize>> = HashMap::new();
+                events.iter().for_each(|event| {
+                    self.event_to_rows::<F>(event, &mut None, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_sharded_byte_lookup_events(blu_batches.iter().collect_vec());
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.ripemd160_compress_events.is_empty()
+    }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(RIPEMD160_COMPRESS_NUM_ROWS * shard.ripemd160_compress_events.len())
+    }
+}
+
+impl Ripemd160CompressChip {
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &Ripemd160CompressEvent,
+        rows: &mut Option<Vec<[F; NUM_RIPEMD160_COMPRESS_COLS]>>,
+        blu: &mut impl ByteRecord,
+    ) {
+        let shard = event.shard;
+        let channel = event.channel;
+
+        let mut left = event.h;
+        let mut right = event.h;
+
+        for cycle in 0..RIPEMD160_COMPRESS_NUM_ROWS {
+            let mut row = [F::zero(); NUM_RIPEMD160_COMPRESS_COLS];
+            let cols: &mut Ripemd160CompressCols<F> = row.as_mut_slice().borrow_mut();
+
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.channel = F::from_canonical_u8(event.channel);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.w_ptr = F::from_canonical_u32(event.w_ptr);
+            cols.h_ptr = F::from_canonical_u32(event.h_ptr);
+
+            cols.cycle[cycle] = F::one();
+            cols.h = event.h.map(Word::from);
+            cols.w = core::array::from_fn(|i| Word::from(event.w[i]));
+            cols.left.state = left.map(Word::from);
+            cols.right.state = right.map(Word::from);
+
+            if cycle < 5 {
+                // Read a word of the state.
+                cols.is_initialize = F::one();
+                cols.mem.populate_read(channel, event.h_read_records[cycle], blu);
+                cols.mem_addr = F::from_canonical_u32(event.h_ptr + (cycle * 4) as u32);
+            } else if cycle < 85 {
+                // Compute a step of both lines.
+                let step = cycle - 5;
+                cols.is_compression = F::one();
+                if step < 16 {
+                    cols.is_message = F::one();
+                    cols.mem.populate_read(channel, event.w_i_read_records[step], blu);
+                    cols.mem_addr = F::from_canonical_u32(event.w_ptr + (step * 4) as u32);
+                }
+
+                left = cols.left.populate(
+                    blu,
+                    shard,
+                    channel,
+                    Ripemd160Line::LEFT,
+                    step,
+                    left,
+                    &event.w,
+                );
+                right = cols.right.populate(
+                    blu,
+                    shard,
+                    channel,
+                    Ripemd160Line::RIGHT,
+                    step,
+                    right,
+                    &event.w,
+                );
+            } else {
+                // Combine the lines with a word of the state and write it.
+                let i = cycle - 85;
+                cols.is_finalize = F::one();
+
+                let (h, l, r) = (event.h[(i + 1) % 5], left[(i + 2) % 5], right[(i + 3) % 5]);
+                cols.finalize_h = Word::from(h);
+                cols.finalize_left = Word::from(l);
+                cols.finalize_right = Word::from(r);
+                let h_add_l = cols.finalize_add_left.populate(blu, shard, channel, h, l);
+                cols.finalize_add_right.populate(blu, shard, channel, h_add_l, r);
+
+                cols.mem.populate_write(channel, event.h_write_records[i], blu);
+                cols.mem_addr = F::from_canonical_u32(event.h_ptr + (i * 4) as u32);
+            }
+
+            cols.is_real = F::one();
+            cols.start = cols.is_real * cols.cycle[0];
+
+            if rows.as_ref().is_some() {
+                rows.as_mut().unwrap().push(row);
+            }
+        }
+    }
+}
+
+impl<F: PrimeField32> Ripemd160LineCols<F> {
+    /// Populates the step of a line on the given state, and returns the state after the step.
+    #[allow(clippy::too_many_arguments)]
+    fn populate(
+        &mut self,
+        blu: &mut impl ByteRecord,
+        shard: u32,
+        channel: u8,
+        line: Ripemd160Line,
+        step: usize,
+        state: [u32; 5],
+        w: &[u32],
+    ) -> [u32; 5] {
+        let [a, b, c, d, e] = state;
+        let round = step / 16;
+
+        let b_xor_c = self.b_xor_c.populate(blu, shard, channel, b, c);
+        let f0 = self.f0.populate(blu, shard, channel, b_xor_c, d);
+
+        let b_and_c = self.b_and_c.populate(blu, shard, channel, b, c);
+        let b_not = self.b_not.populate(blu, shard, channel, b);
+        let b_not_and_d = self.b_not_and_d.populate(blu, shard, channel, b_not, d);
+        let f1 = self.f1.populate(blu, shard, channel, b_and_c, b_not_and_d);
+
+        let c_not = self.c_not.populate(blu, shard, channel, c);
+        let b_or_c_not = self.b_or_c_not.populate(blu, shard, channel, b, c_not);
+        let f2 = self.f2.populate(blu, shard, channel, b_or_c_not, d);
+
+        let b_and_d = self.b_and_d.populate(blu, shard, channel, b, d);
+        let d_not = self.d_not.populate(blu, shard, channel, d);
+        let c_and_d_not = self.c_and_d_not.populate(blu, shard, channel, c, d_not);
+        let f3 = self.f3.populate(blu, shard, channel, b_and_d, c_and_d_not);
+
+        let c_or_d_not = self.c_or_d_not.populate(blu, shard, channel, c, d_not);
+        let f4 = self.f4.populate(blu, shard, channel, b, c_or_d_not);
+
+        let f = [f0, f1, f2, f3, f4][line.function(round)];
+        self.f = Word::from(f);
+
+        let x = w[line.r[step]];
+        let k = line.k[round];
+        self.x = Word::from(x);
+        self.k = Word::from(k);
+
+        let sum = self.sum.populate(blu, shard, channel, a, f, x, k);
+        for (i, bit) in self.sum_bits.iter_mut().enumerate() {
+            *bit = F::from_canonical_u32((sum >> i) & 1);
+        }
+
+        let s = line.s[step];
+        let rotation = RIPEMD160_ROTATIONS.iter().position(|&r| r == s).unwrap();
+        self.rotation[rotation] = F::one();
+        let rotated = sum.rotate_left(s);
+        self.rotated = Word::from(rotated);
+
+        let t = self.t.populate(blu, shard, channel, rotated, e);
+        let c_rl_10 = self.c_rr_22.populate(blu, shard, channel, c, 22);
+
+        [e, t, b, c_rl_10, d]
+    }
+}
@@ -6,7 +6,7 @@ use p3_field::PrimeField32;
 use p3_matrix::{dense::RowMajorMatrix, Matrix};
 use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
 use sp1_core_executor::{
-    events::{ByteLookupEvent, ByteRecord, ShaCompressEvent},
+    events::{ByteLookupEvent, ByteRecord, ShaCompressEvent, SHA_COMPRESS_NUM_ROWS},
     ExecutionRecord, Program,
 };
 use sp1_stark::{air::MachineAir, Word};
@@ -105,6 +105,10 @@ impl<F: PrimeField32> MachineAir<F> for ShaCompressChip {
     fn included(&self, shard: &Self::Record) -> bool {
         !shard.sha_compress_events.is_empty()
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(SHA_COMPRESS_NUM_ROWS * shard.sha_compress_events.len())
+    }
 }
 
 impl ShaCompressChip {
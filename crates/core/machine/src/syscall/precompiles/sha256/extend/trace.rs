@@ -4,7 +4,7 @@ use p3_field::PrimeField32;
 use p3_matrix::{dense::RowMajorMatrix, Matrix};
 use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
 use sp1_core_executor::{
-    events::{ByteLookupEvent, ByteRecord, ShaExtendEvent},
+    events::{ByteLookupEvent, ByteRecord, ShaExtendEvent, SHA_EXTEND_NUM_ROWS},
     ExecutionRecord, Program,
 };
 use sp1_stark::air::MachineAir;
@@ -88,6 +88,10 @@ impl<F: PrimeField32> MachineAir<F> for ShaExtendChip {
     fn included(&self, shard: &Self::Record) -> bool {
         !shard.sha_extend_events.is_empty()
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(SHA_EXTEND_NUM_ROWS * shard.sha_extend_events.len())
+    }
 }
 
 impl ShaExtendChip {
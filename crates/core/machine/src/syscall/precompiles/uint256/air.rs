@@ -227,6 +227,10 @@ impl<F: PrimeField32> MachineAir<F> for Uint256MulChip {
     fn included(&self, shard: &Self::Record) -> bool {
         !shard.uint256_mul_events.is_empty()
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.uint256_mul_events.len())
+    }
 }
 
 impl<F> BaseAir<F> for Uint256MulChip {
@@ -301,6 +301,16 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
             _ => panic!("Unsupported curve"),
         }
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        let events = match E::CURVE_TYPE {
+            CurveType::Secp256k1 => &shard.secp256k1_add_events,
+            CurveType::Bn254 => &shard.bn254_add_events,
+            CurveType::Bls12381 => &shard.bls12381_add_events,
+            _ => panic!("Unsupported curve"),
+        };
+        Some(events.len())
+    }
 }
 
 impl<F, E: EllipticCurve> BaseAir<F> for WeierstrassAddAssignChip<E> {
@@ -295,6 +295,15 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
             _ => panic!("Unsupported curve"),
         }
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        let events = match E::CURVE_TYPE {
+            CurveType::Secp256k1 => &shard.k256_decompress_events,
+            CurveType::Bls12381 => &shard.bls12381_decompress_events,
+            _ => panic!("Unsupported curve"),
+        };
+        Some(events.len())
+    }
 }
 
 impl<F, E: EllipticCurve> BaseAir<F> for WeierstrassDecompressChip<E> {
@@ -318,6 +318,16 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
             _ => panic!("Unsupported curve"),
         }
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        let events = match E::CURVE_TYPE {
+            CurveType::Secp256k1 => &shard.secp256k1_double_events,
+            CurveType::Bn254 => &shard.bn254_double_events,
+            CurveType::Bls12381 => &shard.bls12381_double_events,
+            _ => panic!("Unsupported curve"),
+        };
+        Some(events.len())
+    }
 }
 
 impl<F, E: EllipticCurve + WeierstrassParameters> BaseAir<F> for WeierstrassDoubleAssignChip<E> {
@@ -0,0 +1,338 @@
+use core::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+
+use itertools::Itertools;
+use num::BigUint;
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{AbstractField, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use sp1_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, FieldOperation, WideAluOperation, WIDE_ALU_NUM_WORDS},
+    syscalls::SyscallCode,
+    ByteOpcode, ExecutionRecord, Program,
+};
+use sp1_curves::{
+    params::{FieldParameters, Limbs, NumLimbs},
+    uint64::U64Field,
+};
+use sp1_derive::AlignedBorrow;
+use sp1_primitives::consts::WORD_SIZE;
+use sp1_stark::air::{BaseAirBuilder, MachineAir, Polynomial, SP1AirBuilder};
+
+use crate::{
+    air::MemoryAirBuilder,
+    memory::{MemoryCols, MemoryReadCols, MemoryWriteCols},
+    operations::field::field_op::FieldOpCols,
+    utils::{limbs_from_access, limbs_from_prev_access, pad_rows},
+};
+
+pub const NUM_WIDE_ALU_COLS: usize = size_of::<WideAluCols<u8>>();
+
+type U64Limbs<T> = Limbs<T, <U64Field as NumLimbs>::Limbs>;
+
+/// A set of columns for an arithmetic operation on two 64-bit integers.
+///
+/// The add, sub and mul operations write `x op y mod 2^64` back to x. The comparisons compute
+/// `x - y mod 2^64`, whose borrow is `x < y` as unsigned integers, and write the resulting flag
+/// back to x as a 64-bit integer.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct WideAluCols<T> {
+    pub is_real: T,
+    pub shard: T,
+    pub channel: T,
+    pub nonce: T,
+    pub clk: T,
+    pub is_add: T,
+    pub is_sub: T,
+    pub is_mul: T,
+    pub is_ltu: T,
+    pub is_lt: T,
+    pub x_ptr: T,
+    pub y_ptr: T,
+    pub x_access: [MemoryWriteCols<T>; WIDE_ALU_NUM_WORDS],
+    pub y_access: [MemoryReadCols<T>; WIDE_ALU_NUM_WORDS],
+    pub(crate) result: FieldOpCols<T, U64Field>,
+    /// The most significant bit of x, only checked by the signed comparison.
+    pub x_msb: T,
+    /// The most significant bit of y, only checked by the signed comparison.
+    pub y_msb: T,
+    /// Whether x and y have different signs.
+    pub signs_differ: T,
+    /// The result of the comparisons.
+    pub lt: T,
+}
+
+/// A chip for the wide ALU add, sub, mul and comparison precompiles.
+#[derive(Default)]
+pub struct WideAluChip;
+
+impl WideAluChip {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl<F: PrimeField32> MachineAir<F> for WideAluChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "WideAlu".to_string()
+    }
+
+    fn generate_trace(&self, input: &Self::Record, output: &mut Self::Record) -> RowMajorMatrix<F> {
+        let mut rows = Vec::new();
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in input.wide_alu_events.iter() {
+            let mut row = [F::zero(); NUM_WIDE_ALU_COLS];
+            let cols: &mut WideAluCols<F> = row.as_mut_slice().borrow_mut();
+
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.channel = F::from_canonical_u8(event.channel);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.is_add = F::from_bool(event.op == WideAluOperation::Add);
+            cols.is_sub = F::from_bool(event.op == WideAluOperation::Sub);
+            cols.is_mul = F::from_bool(event.op == WideAluOperation::Mul);
+            cols.is_ltu = F::from_bool(event.op == WideAluOperation::Ltu);
+            cols.is_lt = F::from_bool(event.op == WideAluOperation::Lt);
+            cols.x_ptr = F::from_canonical_u32(event.x_ptr);
+            cols.y_ptr = F::from_canonical_u32(event.y_ptr);
+
+            let field_op = match event.op {
+                WideAluOperation::Add => FieldOperation::Add,
+                WideAluOperation::Mul => FieldOperation::Mul,
+                WideAluOperation::Sub | WideAluOperation::Ltu | WideAluOperation::Lt => {
+                    FieldOperation::Sub
+                }
+            };
+            cols.result.populate(
+                &mut new_byte_lookup_events,
+                event.shard,
+                event.channel,
+                &BigUint::from(event.x),
+                &BigUint::from(event.y),
+                field_op,
+            );
+
+            // Populate the sign bits, which are looked up by the signed comparison only.
+            let x_msb = (event.x >> 63) as u8;
+            let y_msb = (event.y >> 63) as u8;
+            cols.x_msb = F::from_canonical_u8(x_msb);
+            cols.y_msb = F::from_canonical_u8(y_msb);
+            cols.signs_differ = F::from_bool(x_msb != y_msb);
+            cols.lt = F::from_bool(event.op.execute(event.x, event.y) == 1);
+            if event.op == WideAluOperation::Lt {
+                for (msb, value) in [(x_msb, event.x), (y_msb, event.y)] {
+                    new_byte_lookup_events.push(ByteLookupEvent {
+                        shard: event.shard,
+                        channel: event.channel,
+                        opcode: ByteOpcode::MSB,
+                        a1: msb as u16,
+                        a2: 0,
+                        b: (value >> 56) as u8,
+                        c: 0,
+                    });
+                }
+            }
+
+            // Populate the memory access columns.
+            for i in 0..WIDE_ALU_NUM_WORDS {
+                cols.y_access[i].populate(
+                    event.channel,
+                    event.y_memory_records[i],
+                    &mut new_byte_lookup_events,
+                );
+                cols.x_access[i].populate(
+                    event.channel,
+                    event.x_memory_records[i],
+                    &mut new_byte_lookup_events,
+                );
+            }
+
+            rows.push(row);
+        }
+
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || {
+            let mut row = [F::zero(); NUM_WIDE_ALU_COLS];
+            let cols: &mut WideAluCols<F> = row.as_mut_slice().borrow_mut();
+            let zero = BigUint::from(0u32);
+            cols.is_add = F::one();
+            cols.result.populate(&mut vec![], 0, 0, &zero, &zero, FieldOperation::Add);
+            row
+        });
+
+        // Convert the trace to a row major matrix.
+        let mut trace =
+            RowMajorMatrix::new(rows.into_iter().flatten().collect::<Vec<_>>(), NUM_WIDE_ALU_COLS);
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut WideAluCols<F> =
+                trace.values[i * NUM_WIDE_ALU_COLS..(i + 1) * NUM_WIDE_ALU_COLS].borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.wide_alu_events.is_empty()
+    }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        Some(shard.wide_alu_events.len())
+    }
+}
+
+impl<F> BaseAir<F> for WideAluChip {
+    fn width(&self) -> usize {
+        NUM_WIDE_ALU_COLS
+    }
+}
+
+impl<AB> Air<AB> for WideAluChip
+where
+    AB: SP1AirBuilder,
+    U64Limbs<AB::Var>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &WideAluCols<AB::Var> = (*local).borrow();
+        let next = main.row_slice(1);
+        let next: &WideAluCols<AB::Var> = (*next).borrow();
+
+        // Check that nonce is incremented.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder.when_transition().assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        // Check that operations flags are boolean.
+        builder.assert_bool(local.is_real);
+        builder.assert_bool(local.is_add);
+        builder.assert_bool(local.is_sub);
+        builder.assert_bool(local.is_mul);
+        builder.assert_bool(local.is_ltu);
+        builder.assert_bool(local.is_lt);
+
+        // Check that only one of them is set.
+        builder.assert_eq(
+            local.is_add + local.is_sub + local.is_mul + local.is_ltu + local.is_lt,
+            AB::Expr::one(),
+        );
+
+        // The modulus `2^64`.
+        let modulus_coeffs =
+            U64Field::MODULUS.iter().map(|&limb| AB::Expr::from_canonical_u8(limb)).collect_vec();
+        let p_modulus = Polynomial::from_coefficients(&modulus_coeffs);
+
+        // The value of x is stored in the "prev_value" of the x_access, since we write to it.
+        let x: U64Limbs<AB::Var> = limbs_from_prev_access(&local.x_access);
+        let y: U64Limbs<AB::Var> = limbs_from_access(&local.y_access);
+
+        // The comparisons compute `x - y mod 2^64`.
+        let is_cmp = local.is_ltu + local.is_lt;
+        local.result.eval_variable(
+            builder,
+            &x,
+            &y,
+            &p_modulus,
+            local.is_add,
+            local.is_sub + is_cmp.clone(),
+            local.is_mul,
+            AB::F::zero(),
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // Since `result + y = x + carry * 2^64` with every term range checked, the carry is the
+        // borrow of the subtraction, which is set exactly when `x < y` as unsigned integers.
+        let borrow = local.result.carry[0];
+
+        // Check the sign bits of x and y for the signed comparison.
+        builder.assert_bool(local.x_msb);
+        builder.assert_bool(local.y_msb);
+        let opcode = AB::F::from_canonical_u32(ByteOpcode::MSB as u32);
+        for (msb, byte) in [
+            (local.x_msb, local.x_access[WIDE_ALU_NUM_WORDS - 1].prev_value()[WORD_SIZE - 1]),
+            (local.y_msb, local.y_access[WIDE_ALU_NUM_WORDS - 1].value()[WORD_SIZE - 1]),
+        ] {
+            builder.send_byte(
+                opcode,
+                msb,
+                byte,
+                AB::Expr::zero(),
+                local.shard,
+                local.channel,
+                local.is_lt,
+            );
+        }
+        builder.assert_eq(
+            local.signs_differ,
+            local.x_msb + local.y_msb - AB::Expr::two() * local.x_msb * local.y_msb,
+        );
+
+        // If the signs are the same, the signed comparison is the unsigned one. Otherwise, x is
+        // less than y exactly when x is negative.
+        builder.when(local.is_ltu).assert_eq(local.lt, borrow);
+        builder.when(local.is_lt).assert_eq(
+            local.lt,
+            borrow * (AB::Expr::one() - local.signs_differ) + local.x_msb * local.signs_differ,
+        );
+
+        // Check the value written to x.
+        let written: U64Limbs<AB::Var> = limbs_from_access(&local.x_access);
+        builder
+            .when(local.is_add + local.is_sub + local.is_mul)
+            .assert_all_eq(local.result.result, written);
+        builder.when(is_cmp.clone()).assert_eq(written[0], local.lt);
+        for limb in written.0.iter().skip(1) {
+            builder.when(is_cmp.clone()).assert_zero(*limb);
+        }
+
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk.into(),
+            local.y_ptr,
+            &local.y_access,
+            local.is_real,
+        );
+        // The x value is accessed at `clk + 1` since x and y could be the same.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk + AB::F::one(),
+            local.x_ptr,
+            &local.x_access,
+            local.is_real,
+        );
+
+        // Select the correct syscall id based on the operation flags.
+        let syscall_id_felt = local.is_add
+            * AB::F::from_canonical_u32(SyscallCode::WIDE_ALU_ADD.syscall_id())
+            + local.is_sub * AB::F::from_canonical_u32(SyscallCode::WIDE_ALU_SUB.syscall_id())
+            + local.is_mul * AB::F::from_canonical_u32(SyscallCode::WIDE_ALU_MUL.syscall_id())
+            + local.is_ltu * AB::F::from_canonical_u32(SyscallCode::WIDE_ALU_LTU.syscall_id())
+            + local.is_lt * AB::F::from_canonical_u32(SyscallCode::WIDE_ALU_LT.syscall_id());
+
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            syscall_id_felt,
+            local.x_ptr,
+            local.y_ptr,
+            local.is_real,
+        );
+    }
+}
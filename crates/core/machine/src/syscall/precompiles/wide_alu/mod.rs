@@ -0,0 +1,84 @@
+mod air;
+
+pub use air::*;
+
+#[cfg(test)]
+mod tests {
+    use sp1_core_executor::{syscalls::SyscallCode, Executor, Instruction, Opcode, Program};
+    use sp1_stark::{CpuProver, SP1CoreOpts};
+
+    use crate::utils::{self, run_test};
+
+    const X_PTR: u32 = 100;
+    const Y_PTR: u32 = 1000;
+
+    const SYSCALLS: [SyscallCode; 5] = [
+        SyscallCode::WIDE_ALU_ADD,
+        SyscallCode::WIDE_ALU_SUB,
+        SyscallCode::WIDE_ALU_MUL,
+        SyscallCode::WIDE_ALU_LTU,
+        SyscallCode::WIDE_ALU_LT,
+    ];
+
+    fn wide_alu_program(syscall: SyscallCode, x: u64, y: u64) -> Program {
+        let mut instructions = vec![];
+        for (ptr, value) in [(X_PTR, x), (Y_PTR, y)] {
+            for (i, word) in [value as u32, (value >> 32) as u32].into_iter().enumerate() {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr + i as u32 * 4, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, syscall as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, X_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, Y_PTR, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+
+        Program::new(instructions, 0, 0)
+    }
+
+    fn execute(syscall: SyscallCode, x: u64, y: u64) -> u64 {
+        let program = wide_alu_program(syscall, x, y);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        u64::from(runtime.word(X_PTR)) | u64::from(runtime.word(X_PTR + 4)) << 32
+    }
+
+    fn operands() -> Vec<(u64, u64)> {
+        vec![
+            (0xdead_beef_0123_4567, 0x89ab_cdef_fedc_ba98),
+            (u64::MAX, 1),
+            (1, u64::MAX),
+            ((-5i64) as u64, 3),
+            (7, 7),
+        ]
+    }
+
+    #[test]
+    fn test_wide_alu_execute() {
+        utils::setup_logger();
+        for (x, y) in operands() {
+            assert_eq!(execute(SyscallCode::WIDE_ALU_ADD, x, y), x.wrapping_add(y));
+            assert_eq!(execute(SyscallCode::WIDE_ALU_SUB, x, y), x.wrapping_sub(y));
+            assert_eq!(execute(SyscallCode::WIDE_ALU_MUL, x, y), x.wrapping_mul(y));
+            assert_eq!(execute(SyscallCode::WIDE_ALU_LTU, x, y), u64::from(x < y));
+            assert_eq!(execute(SyscallCode::WIDE_ALU_LT, x, y), u64::from((x as i64) < (y as i64)));
+        }
+    }
+
+    #[test]
+    fn test_wide_alu_prove() {
+        utils::setup_logger();
+        for (x, y) in operands() {
+            for syscall in SYSCALLS {
+                let program = wide_alu_program(syscall, x, y);
+                run_test::<CpuProver<_, _>>(program).unwrap();
+            }
+        }
+    }
+}
@@ -1,8 +1,10 @@
 use std::sync::Once;
 
+use tracing::Subscriber;
 use tracing_forest::ForestLayer;
 use tracing_subscriber::{
-    fmt::format::FmtSpan, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Registry,
+    fmt::format::FmtSpan, layer::SubscriberExt, registry::LookupSpan, util::SubscriberInitExt,
+    EnvFilter, Layer, Registry,
 };
 
 static INIT: Once = Once::new();
@@ -10,6 +12,13 @@ static INIT: Once = Once::new();
 /// A simple logger.
 ///
 /// Set the `RUST_LOG` environment variable to be set to `info` or `debug`.
+///
+/// With the `otlp` feature, the spans are also exported to the OpenTelemetry collector at
+/// `OTEL_EXPORTER_OTLP_ENDPOINT` if the variable is set, under the service `OTEL_SERVICE_NAME`. The
+/// spans of a proof cover its execution, the commitment and opening of each shard, and the lift,
+/// join, shrink and wrap steps, with the shard index, chip and number of rows as attributes, so
+/// that the lifecycle of a job can be followed across the machines of a proving farm. Call
+/// [`shutdown_logger`] before exiting to flush them.
 pub fn setup_logger() {
     INIT.call_once(|| {
         let default_filter = "off";
@@ -27,17 +36,24 @@ pub fn setup_logger() {
         let logger_type = std::env::var("RUST_LOGGER").unwrap_or_else(|_| "flat".to_string());
         match logger_type.as_str() {
             "forest" => {
-                Registry::default().with(env_filter).with(ForestLayer::default()).init();
+                Registry::default()
+                    .with(env_filter)
+                    .with(ForestLayer::default())
+                    .with(otlp_layer())
+                    .init();
             }
             "flat" => {
-                tracing_subscriber::fmt::Subscriber::builder()
-                    .compact()
-                    .with_file(false)
-                    .with_target(false)
-                    .with_thread_names(false)
-                    .with_env_filter(env_filter)
-                    .with_span_events(FmtSpan::CLOSE)
-                    .finish()
+                Registry::default()
+                    .with(env_filter)
+                    .with(
+                        tracing_subscriber::fmt::layer()
+                            .compact()
+                            .with_file(false)
+                            .with_target(false)
+                            .with_thread_names(false)
+                            .with_span_events(FmtSpan::CLOSE),
+                    )
+                    .with(otlp_layer())
                     .init();
             }
             _ => {
@@ -46,3 +62,44 @@ pub fn setup_logger() {
         }
     });
 }
+
+/// Flushes the spans not yet exported to the OpenTelemetry collector. See [`setup_logger`].
+pub fn shutdown_logger() {
+    #[cfg(feature = "otlp")]
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+/// The layer exporting the spans to the OpenTelemetry collector, if one is configured.
+#[cfg(feature = "otlp")]
+fn otlp_layer<S>() -> Option<impl Layer<S>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    use opentelemetry::trace::TracerProvider;
+
+    std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok()?;
+    // The spans are exported from a thread of their own, since the prover may not run in an async
+    // runtime.
+    let provider = match opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic())
+        .install_batch(opentelemetry_sdk::runtime::TokioCurrentThread)
+    {
+        Ok(provider) => provider,
+        Err(e) => {
+            eprintln!("failed to set up the OTLP exporter: {e}");
+            return None;
+        }
+    };
+    let tracer = provider.tracer("sp1");
+    opentelemetry::global::set_tracer_provider(provider);
+    Some(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+#[cfg(not(feature = "otlp"))]
+fn otlp_layer<S>() -> Option<impl Layer<S>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    None::<tracing_subscriber::layer::Identity>
+}
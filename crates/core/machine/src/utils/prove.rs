@@ -1,5 +1,5 @@
 use std::{
-    collections::VecDeque,
+    collections::{BTreeMap, VecDeque},
     fs::File,
     io::{
         Seek, {self},
@@ -29,15 +29,16 @@ use sp1_core_executor::events::sorted_table_lines;
 use sp1_primitives::io::SP1PublicValues;
 
 use sp1_core_executor::{
-    subproof::NoOpSubproofVerifier, ExecutionError, ExecutionRecord, ExecutionReport, Executor,
-    Program, SP1Context,
+    subproof::NoOpSubproofVerifier, Checkpoint, CheckpointError, ExecutionError, ExecutionRecord,
+    ExecutionReport, Executor, Program, ProofMonitor, SP1Context,
 };
 use sp1_stark::{
     air::{MachineAir, PublicValues},
-    Com, CpuProver, DebugConstraintBuilder, InteractionBuilder, MachineProof, MachineProver,
-    MachineRecord, OpeningProof, PcsProverData, ProverConstraintFolder, SP1CoreOpts,
-    StarkGenericConfig, StarkMachine, StarkProvingKey, StarkVerifyingKey, UniConfig, Val,
-    VerifierConstraintFolder,
+    check_multiplicities, check_padding_rows, BadMultiplicities, Com, CpuProver,
+    DebugConstraintBuilder, InteractionBuilder, LeakyPadding, LookupBalance, MachineProof,
+    MachineProver, MachineRecord, OpeningProof, PcsProverData, ProverConstraintFolder, SP1CoreOpts,
+    ShardTraceDump, StarkGenericConfig, StarkMachine, StarkProvingKey, StarkVerifyingKey,
+    UnbalancedLookups, UniConfig, Val, VerifierConstraintFolder,
 };
 
 #[derive(Error, Debug)]
@@ -48,6 +49,16 @@ pub enum SP1CoreProverError {
     IoError(io::Error),
     #[error("serialization error: {0}")]
     SerializationError(bincode::Error),
+    #[error("checkpoint error: {0}")]
+    CheckpointError(CheckpointError),
+    #[error("proving was cancelled")]
+    Cancelled,
+    #[error("lookups do not balance: {0}")]
+    UnbalancedLookups(UnbalancedLookups),
+    #[error("padding rows are not inert: {0}")]
+    LeakyPadding(LeakyPadding),
+    #[error("multiplicities may wrap around the field: {0}")]
+    BadMultiplicities(BadMultiplicities),
 }
 
 pub fn prove_simple<SC: StarkGenericConfig, P: MachineProver<SC, RiscvAir<SC::Val>>>(
@@ -111,6 +122,32 @@ where
     prove_with_context::<SC, _>(&prover, &pk, program, stdin, opts, Default::default())
 }
 
+/// Writes the traces of a core shard to `dir` for offline debugging.
+fn dump_shard_traces<SC: StarkGenericConfig, P: MachineProver<SC, RiscvAir<SC::Val>>>(
+    prover: &P,
+    pk: &StarkProvingKey<SC>,
+    record: &ExecutionRecord,
+    traces: &[(String, RowMajorMatrix<SC::Val>)],
+    challenger: &SC::Challenger,
+    dir: &std::path::Path,
+) where
+    SC::Val: PrimeField32,
+    SC::Challenger: Clone,
+{
+    let dump = ShardTraceDump::new(
+        record.public_values.shard,
+        prover.machine(),
+        pk,
+        traces,
+        &MachineRecord::public_values::<SC::Val>(record),
+        challenger,
+    );
+    match dump.save(dir) {
+        Ok(path) => tracing::info!("dumped shard traces to {}", path.display()),
+        Err(e) => tracing::warn!("failed to dump shard traces: {}", e),
+    }
+}
+
 pub fn prove_with_context<SC: StarkGenericConfig, P: MachineProver<SC, RiscvAir<SC::Val>>>(
     prover: &P,
     pk: &StarkProvingKey<SC>,
@@ -126,9 +163,29 @@ where
     Com<SC>: Send + Sync,
     PcsProverData<SC>: Send + Sync,
 {
+    // Keep the monitor around to check for cancellation and report progress.
+    let monitor = context.monitor.clone();
+    let monitor = monitor.as_deref();
+    let is_cancelled = move || monitor.is_some_and(ProofMonitor::is_cancelled);
+
+    // Shard traces contain the guest's memory, so they are not dumped when it is private.
+    let dump_traces_dir = opts.dump_traces_dir.as_deref().filter(|_| {
+        let redacted = context.privacy_mode.is_redacted();
+        if redacted {
+            tracing::warn!("not dumping shard traces, since private guest data is redacted");
+        }
+        !redacted
+    });
+
     // Setup the runtime.
-    let mut runtime = Executor::with_context(program.clone(), opts, context);
+    let program_digest = program.digest();
+    let mut runtime = Executor::with_context(program.clone(), opts.clone(), context);
     runtime.write_vecs(&stdin.buffer);
+    runtime.write_paged_inputs(&stdin.paged_inputs);
+    runtime.write_files(&stdin.files);
+    runtime.write_public_values_domain(stdin.public_values_domain);
+    runtime.write_env_vars(&stdin.env_vars);
+    runtime.write_args(&stdin.args);
     for proof in stdin.proofs.iter() {
         runtime.write_proof(proof.0.clone(), proof.1.clone());
     }
@@ -136,6 +193,9 @@ where
     #[cfg(feature = "debug")]
     let (all_records_tx, all_records_rx) = std::sync::mpsc::channel::<Vec<ExecutionRecord>>();
 
+    // The worker threads below share the options.
+    let opts = &opts;
+
     // Record the start of the process.
     let proving_start = Instant::now();
     let span = tracing::Span::current().clone();
@@ -152,6 +212,11 @@ where
                 tracing::debug_span!("checkpoint generator").in_scope(|| {
                     let mut index = 0;
                     loop {
+                        // Stop executing if the job has been cancelled.
+                        if is_cancelled() {
+                            return Err(SP1CoreProverError::Cancelled);
+                        }
+
                         // Enter the span.
                         let span = tracing::debug_span!("batch");
                         let _span = span.enter();
@@ -163,9 +228,10 @@ where
                         // Save the checkpoint to a temp file.
                         let mut checkpoint_file =
                             tempfile::tempfile().map_err(SP1CoreProverError::IoError)?;
-                        checkpoint
-                            .save(&mut checkpoint_file)
-                            .map_err(SP1CoreProverError::IoError)?;
+                        Checkpoint::new(program_digest, index, checkpoint, done)
+                            .write_to(std::io::BufWriter::new(&mut checkpoint_file))
+                            .map_err(SP1CoreProverError::CheckpointError)?;
+                        reset_seek(&mut checkpoint_file);
 
                         // Send the checkpoint.
                         checkpoints_tx.send((index, checkpoint_file, done)).unwrap();
@@ -220,13 +286,22 @@ where
 
                         if let Ok((index, mut checkpoint, done)) = received {
                             // Trace the checkpoint and reconstruct the execution records.
-                            let (mut records, _) = tracing::debug_span!("trace checkpoint")
-                                .in_scope(|| trace_checkpoint(program.clone(), &checkpoint, opts));
+                            let (mut records, _) =
+                                tracing::debug_span!("trace checkpoint", checkpoint = index)
+                                    .in_scope(|| {
+                                        trace_checkpoint(
+                                            program.clone(),
+                                            &checkpoint,
+                                            &stdin.paged_inputs,
+                                            &stdin.files,
+                                            opts.clone(),
+                                        )
+                                    });
                             reset_seek(&mut checkpoint);
 
                             // Generate the dependencies.
                             tracing::debug_span!("generate dependencies").in_scope(|| {
-                                prover.machine().generate_dependencies(&mut records, &opts)
+                                prover.machine().generate_dependencies(&mut records, opts)
                             });
 
                             // Wait for our turn to update the state.
@@ -326,8 +401,20 @@ where
         challenger.observe(pk.commit.clone());
         challenger.observe(pk.pc_start);
 
+        // The lookups of all shards, if they are checked before proving.
+        let lookup_balance = Arc::new(Mutex::new(LookupBalance::new()));
+
+        // The padding leaks of all shards, if their padding is checked before proving.
+        let padding_leaks = Arc::new(Mutex::new(Vec::new()));
+
+        // The multiplicity issues of all shards, if their multiplicities are checked before proving.
+        let multiplicity_issues = Arc::new(Mutex::new(Vec::new()));
+
         // Spawn the phase 1 prover thread.
         let phase_1_prover_span = tracing::Span::current().clone();
+        let phase_1_lookup_balance = Arc::clone(&lookup_balance);
+        let phase_1_padding_leaks = Arc::clone(&padding_leaks);
+        let phase_1_multiplicity_issues = Arc::clone(&multiplicity_issues);
         let phase_1_prover_handle = s.spawn(move || {
             let _span = phase_1_prover_span.enter();
             tracing::debug_span!("phase 1 prover").in_scope(|| {
@@ -349,7 +436,27 @@ where
                             .into_par_iter()
                             .zip(traces.into_par_iter())
                             .map(|(record, traces)| {
-                                let _span = span.enter();
+                                let _span = tracing::debug_span!(
+                                    parent: &span,
+                                    "commit shard",
+                                    shard = record.public_values.shard
+                                )
+                                .entered();
+                                if opts.debug_lookups {
+                                    let mut balance = LookupBalance::new();
+                                    balance.add_shard(prover.machine(), pk, &traces);
+                                    phase_1_lookup_balance.lock().unwrap().merge(balance);
+                                }
+                                if opts.debug_padding {
+                                    let leaks =
+                                        check_padding_rows(prover.machine(), pk, &record, &traces);
+                                    phase_1_padding_leaks.lock().unwrap().extend(leaks);
+                                }
+                                if opts.debug_multiplicities {
+                                    let issues =
+                                        check_multiplicities(prover.machine(), pk, &traces);
+                                    phase_1_multiplicity_issues.lock().unwrap().extend(issues);
+                                }
                                 let data = prover.commit(record, traces);
                                 let main_commit = data.main_commit.clone();
                                 drop(data);
@@ -371,7 +478,7 @@ where
         });
 
         // Wait until the checkpoint generator handle has fully finished.
-        let public_values_stream = checkpoint_generator_handle.join().unwrap().unwrap();
+        let public_values_stream = checkpoint_generator_handle.join().unwrap()?;
 
         // Wait until the records and traces have been fully generated.
         p1_record_and_trace_gen_handles.into_iter().for_each(|handle| handle.join().unwrap());
@@ -379,6 +486,35 @@ where
         // Wait until the phase 1 prover has completely finished.
         let challenger = phase_1_prover_handle.join().unwrap();
 
+        // The number of shards is known once all the records have been generated.
+        if let Some(monitor) = monitor {
+            monitor.set_total_shards(state.lock().unwrap().shard as usize);
+            monitor.set_commit_time(proving_start.elapsed());
+        }
+        if is_cancelled() {
+            return Err(SP1CoreProverError::Cancelled);
+        }
+
+        // Check that the lookups balance across all shards before proving them.
+        if opts.debug_lookups {
+            let balance = lookup_balance.lock().unwrap();
+            balance.check().map_err(SP1CoreProverError::UnbalancedLookups)?;
+        }
+
+        // Check that the padding rows of all shards are inert before proving them.
+        let padding_leaks = std::mem::take(&mut *padding_leaks.lock().unwrap());
+        if !padding_leaks.is_empty() {
+            return Err(SP1CoreProverError::LeakyPadding(LeakyPadding(padding_leaks)));
+        }
+
+        // Check that the multiplicities of all shards cannot wrap around before proving them.
+        let multiplicity_issues = std::mem::take(&mut *multiplicity_issues.lock().unwrap());
+        if !multiplicity_issues.is_empty() {
+            return Err(SP1CoreProverError::BadMultiplicities(BadMultiplicities(
+                multiplicity_issues,
+            )));
+        }
+
         // Spawn the phase 2 record generator thread.
         let p2_record_gen_sync = Arc::new(TurnBasedSync::new());
         let p2_trace_gen_sync = Arc::new(TurnBasedSync::new());
@@ -408,18 +544,32 @@ where
                 let _span = span.enter();
                 tracing::debug_span!("phase 2 trace generation").in_scope(|| {
                     loop {
+                        // Stop generating records if the job has been cancelled.
+                        if is_cancelled() {
+                            break;
+                        }
+
                         // Receive the latest checkpoint.
                         let received = { checkpoints.lock().unwrap().pop_front() };
                         if let Some((index, mut checkpoint, done)) = received {
                             // Trace the checkpoint and reconstruct the execution records.
-                            let (mut records, report) = tracing::debug_span!("trace checkpoint")
-                                .in_scope(|| trace_checkpoint(program.clone(), &checkpoint, opts));
+                            let (mut records, report) =
+                                tracing::debug_span!("trace checkpoint", checkpoint = index)
+                                    .in_scope(|| {
+                                        trace_checkpoint(
+                                            program.clone(),
+                                            &checkpoint,
+                                            &stdin.paged_inputs,
+                                            &stdin.files,
+                                            opts.clone(),
+                                        )
+                                    });
                             *report_aggregate.lock().unwrap() += report;
                             reset_seek(&mut checkpoint);
 
                             // Generate the dependencies.
                             tracing::debug_span!("generate dependencies").in_scope(|| {
-                                prover.machine().generate_dependencies(&mut records, &opts)
+                                prover.machine().generate_dependencies(&mut records, opts)
                             });
 
                             // Wait for our turn to update the state.
@@ -511,14 +661,39 @@ where
             let mut shard_proofs = Vec::new();
             tracing::debug_span!("phase 2 prover").in_scope(|| {
                 for (records, traces) in p2_records_and_traces_rx.into_iter() {
+                    // Drain the remaining batches without proving them if the job has been
+                    // cancelled.
+                    if is_cancelled() {
+                        continue;
+                    }
                     tracing::debug_span!("batch").in_scope(|| {
                         let span = tracing::Span::current().clone();
                         shard_proofs.par_extend(
                             records.into_par_iter().zip(traces.into_par_iter()).map(
                                 |(record, traces)| {
-                                    let _span = span.enter();
+                                    let _span = tracing::debug_span!(
+                                        parent: &span,
+                                        "prove shard",
+                                        shard = record.public_values.shard
+                                    )
+                                    .entered();
+                                    if let Some(dir) = dump_traces_dir {
+                                        dump_shard_traces(
+                                            prover,
+                                            pk,
+                                            &record,
+                                            &traces,
+                                            &challenger,
+                                            dir,
+                                        );
+                                    }
                                     let data = prover.commit(record, traces);
-                                    prover.open(pk, data, &mut challenger.clone()).unwrap()
+                                    let proof =
+                                        prover.open(pk, data, &mut challenger.clone()).unwrap();
+                                    if let Some(monitor) = monitor {
+                                        monitor.shard_proved();
+                                    }
+                                    proof
                                 },
                             ),
                         );
@@ -533,6 +708,9 @@ where
 
         // Wait until the phase 2 prover has finished.
         let shard_proofs = p2_prover_handle.join().unwrap();
+        if is_cancelled() {
+            return Err(SP1CoreProverError::Cancelled);
+        }
 
         // Log some of the `ExecutionReport` information.
         let report_aggregate = report_aggregate.lock().unwrap();
@@ -587,6 +765,11 @@ pub fn run_test_io<P: MachineProver<BabyBearPoseidon2, RiscvAir<BabyBear>>>(
     let runtime = tracing::debug_span!("runtime.run(...)").in_scope(|| {
         let mut runtime = Executor::new(program, SP1CoreOpts::default());
         runtime.write_vecs(&inputs.buffer);
+        runtime.write_paged_inputs(&inputs.paged_inputs);
+        runtime.write_files(&inputs.files);
+        runtime.write_public_values_domain(inputs.public_values_domain);
+        runtime.write_env_vars(&inputs.env_vars);
+        runtime.write_args(&inputs.args);
         runtime.run().unwrap();
         runtime
     });
@@ -694,11 +877,16 @@ where
 fn trace_checkpoint(
     program: Program,
     file: &File,
+    paged_inputs: &[Arc<[u8]>],
+    files: &BTreeMap<String, Arc<[u8]>>,
     opts: SP1CoreOpts,
 ) -> (Vec<ExecutionRecord>, ExecutionReport) {
-    let mut reader = std::io::BufReader::new(file);
-    let state = bincode::deserialize_from(&mut reader).expect("failed to deserialize state");
-    let mut runtime = Executor::recover(program.clone(), state, opts);
+    let reader = std::io::BufReader::new(file);
+    let checkpoint = Checkpoint::read_from(reader).expect("failed to read checkpoint");
+    let mut runtime = checkpoint.recover(program, opts).expect("failed to recover checkpoint");
+    // Paged inputs and files are not serialized into checkpoints, so restore them from the stdin.
+    runtime.write_paged_inputs(paged_inputs);
+    runtime.write_files(files);
     // We already passed the deferred proof verifier when creating checkpoints, so the proofs were
     // already verified. So here we use a noop verifier to not print any warnings.
     runtime.subproof_verifier = Arc::new(NoOpSubproofVerifier);
@@ -0,0 +1,35 @@
+use typenum::{U4, U6};
+
+use serde::{Deserialize, Serialize};
+
+use crate::params::{FieldParameters, NumLimbs};
+
+/// The prime field of the coefficients of Dilithium (ML-DSA) polynomials, `q = 2^23 - 2^13 + 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DilithiumField;
+
+impl FieldParameters for DilithiumField {
+    /// The modulus `q = 8380417`, represented as a little-endian array of 4 bytes.
+    const MODULUS: &'static [u8] = &[0x01, 0xE0, 0x7F, 0x00];
+
+    /// A rough witness-offset estimate given the size of the limbs and the size of the field.
+    const WITNESS_OFFSET: usize = 1usize << 14;
+}
+
+impl NumLimbs for DilithiumField {
+    type Limbs = U4;
+    type Witness = U6;
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+
+    use super::*;
+
+    #[test]
+    fn test_dilithium_modulus() {
+        assert_eq!(DilithiumField::modulus(), BigUint::from(8380417u32));
+        assert_eq!(DilithiumField::nb_bits(), 32);
+    }
+}
@@ -1,8 +1,10 @@
+pub mod dilithium;
 pub mod edwards;
 pub mod params;
 // pub mod polynomial;
 pub mod scalar_mul;
 pub mod uint256;
+pub mod uint64;
 pub mod utils;
 pub mod weierstrass;
 
@@ -0,0 +1,46 @@
+use typenum::{U15, U8};
+
+use num::{BigUint, One};
+use serde::{Deserialize, Serialize};
+
+use crate::params::{FieldParameters, NumLimbs};
+
+/// The ring of 64-bit integers, `Z / 2^64 Z`.
+///
+/// Like [`crate::uint256::U256Field`], this is not a field: the parameters are only used as the
+/// modulus of `FieldOps`, so that they compute wrapping 64-bit arithmetic.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct U64Field;
+
+impl FieldParameters for U64Field {
+    /// The modulus `2^64`, represented as a little-endian array of 9 bytes.
+    const MODULUS: &'static [u8] = &[0, 0, 0, 0, 0, 0, 0, 0, 1];
+
+    /// A rough witness-offset estimate given the size of the limbs and the size of the field.
+    const WITNESS_OFFSET: usize = 1usize << 14;
+
+    /// The modulus of `U64Field` is `2^64`.
+    fn modulus() -> BigUint {
+        BigUint::one() << 64
+    }
+}
+
+impl NumLimbs for U64Field {
+    type Limbs = U8;
+    // As for `U256Field`, we need an extra witness limb since the modulus has one more limb than
+    // the operands.
+    type Witness = U15;
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+
+    use super::*;
+
+    #[test]
+    fn test_u64_modulus() {
+        assert_eq!(U64Field::modulus(), BigUint::from(u64::MAX) + 1u32);
+        assert_eq!(U64Field::nb_bits(), 64);
+    }
+}
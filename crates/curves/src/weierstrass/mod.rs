@@ -11,6 +11,7 @@ use crate::{
 
 pub mod bls12_381;
 pub mod bn254;
+pub mod projective;
 pub mod secp256k1;
 
 /// Parameters that specify a short Weierstrass curve : y^2 = x^3 + ax + b.
@@ -0,0 +1,153 @@
+use std::marker::PhantomData;
+
+use num::{BigUint, One, Zero};
+use typenum::Unsigned;
+
+use super::{SwCurve, WeierstrassParameters};
+use crate::{
+    params::{FieldParameters, NumWords},
+    AffinePoint,
+};
+
+/// A point on a short Weierstrass curve `y^2 = x^3 + b` in projective coordinates `(X : Y : Z)`.
+///
+/// The point represents the affine point `(X / Z, Y / Z)`, or the point at infinity if `Z = 0`.
+/// Unlike affine points, projective points have complete addition formulas, which make them
+/// suitable for the MSM precompiles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectivePoint<E> {
+    pub x: BigUint,
+    pub y: BigUint,
+    pub z: BigUint,
+    _marker: PhantomData<E>,
+}
+
+impl<E: WeierstrassParameters> ProjectivePoint<E> {
+    pub const fn new(x: BigUint, y: BigUint, z: BigUint) -> Self {
+        Self { x, y, z, _marker: PhantomData }
+    }
+
+    /// The point at infinity, `(0 : 1 : 0)`.
+    pub fn neutral() -> Self {
+        Self::new(BigUint::zero(), BigUint::one(), BigUint::zero())
+    }
+
+    pub fn from_words_le(words: &[u32]) -> Self {
+        let n = words.len() / 3;
+        let coordinate = |words: &[u32]| {
+            BigUint::from_bytes_le(&words.iter().flat_map(|w| w.to_le_bytes()).collect::<Vec<_>>())
+        };
+        Self::new(
+            coordinate(&words[..n]),
+            coordinate(&words[n..2 * n]),
+            coordinate(&words[2 * n..]),
+        )
+    }
+
+    pub fn to_words_le(&self) -> Vec<u32> {
+        let num_words = <E::BaseField as NumWords>::WordsFieldElement::USIZE;
+        [&self.x, &self.y, &self.z]
+            .into_iter()
+            .flat_map(|coordinate| {
+                let mut bytes = coordinate.to_bytes_le();
+                bytes.resize(num_words * 4, 0u8);
+                bytes
+                    .chunks_exact(4)
+                    .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Adds two points with the complete formulas of Renes, Costello and Batina for `a = 0`.
+    ///
+    /// The formulas hold for all pairs of points, including doublings and the point at infinity:
+    ///
+    /// ```text
+    /// X3 = (X1 Y2 + X2 Y1)(Y1 Y2 - 3b Z1 Z2) - 3b (Y1 Z2 + Y2 Z1)(X1 Z2 + X2 Z1)
+    /// Y3 = (Y1 Y2 + 3b Z1 Z2)(Y1 Y2 - 3b Z1 Z2) + 9b X1 X2 (X1 Z2 + X2 Z1)
+    /// Z3 = (Y1 Z2 + Y2 Z1)(Y1 Y2 + 3b Z1 Z2) + 3 X1 X2 (X1 Y2 + X2 Y1)
+    /// ```
+    pub fn complete_add(&self, other: &Self) -> Self {
+        assert!(E::a_int().is_zero(), "complete projective addition requires a = 0");
+        let p = E::BaseField::modulus();
+        let b3 = (E::b_int() * 3u32) % &p;
+        let (x1, y1, z1) = (&self.x, &self.y, &self.z);
+        let (x2, y2, z2) = (&other.x, &other.y, &other.z);
+
+        let x1y2_x2y1 = (x1 * y2 + x2 * y1) % &p;
+        let y1z2_y2z1 = (y1 * z2 + y2 * z1) % &p;
+        let x1z2_x2z1 = (x1 * z2 + x2 * z1) % &p;
+        let y1y2 = (y1 * y2) % &p;
+        let z1z2_b3 = (z1 * z2 * &b3) % &p;
+        let x1x2 = (x1 * x2) % &p;
+
+        let y1y2_minus = (&p + &y1y2 - &z1z2_b3) % &p;
+        let y1y2_plus = (&y1y2 + &z1z2_b3) % &p;
+        let x3 = (&x1y2_x2y1 * &y1y2_minus + &y1z2_y2z1 * &x1z2_x2z1 * (&p - &b3)) % &p;
+        let y3 = (&y1y2_plus * &y1y2_minus + &x1x2 * &x1z2_x2z1 * (&b3 * 3u32)) % &p;
+        let z3 = (&y1z2_y2z1 * &y1y2_plus + &x1x2 * 3u32 * &x1y2_x2y1) % &p;
+        Self::new(x3, y3, z3)
+    }
+}
+
+impl<E: WeierstrassParameters> ProjectivePoint<SwCurve<E>> {
+    pub fn from_affine(point: &AffinePoint<SwCurve<E>>) -> Self {
+        Self::new(point.x.clone(), point.y.clone(), BigUint::one())
+    }
+
+    /// The affine point represented by `self`, or `None` for the point at infinity.
+    pub fn to_affine(&self) -> Option<AffinePoint<SwCurve<E>>> {
+        if self.z.is_zero() {
+            return None;
+        }
+        let p = E::BaseField::modulus();
+        let z_inv = self.z.modpow(&(&p - 2u32), &p);
+        Some(AffinePoint::new((&self.x * &z_inv) % &p, (&self.y * &z_inv) % &p))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+
+    use super::ProjectivePoint;
+    use crate::{
+        weierstrass::{
+            bn254::Bn254Parameters, secp256k1::Secp256k1Parameters, SwCurve, WeierstrassParameters,
+        },
+        EllipticCurve,
+    };
+
+    fn check_complete_add<E: WeierstrassParameters>() {
+        let g = SwCurve::<E>::generator();
+        let g2 = g.sw_double();
+        let g3 = g2.sw_add(&g);
+        let neg_g = SwCurve::<E>::ec_neg(&g);
+        let g = ProjectivePoint::from_affine(&g);
+
+        // Scale the coordinates to check that the result does not depend on the representative.
+        let scale = |point: &ProjectivePoint<SwCurve<E>>, s: u32| {
+            ProjectivePoint::new(&point.x * s, &point.y * s, &point.z * s)
+        };
+        let neutral = ProjectivePoint::<SwCurve<E>>::neutral();
+        assert_eq!(g.complete_add(&scale(&g, 5)).to_affine(), Some(g2.clone()));
+        assert_eq!(
+            scale(&ProjectivePoint::from_affine(&g2), 3).complete_add(&g).to_affine(),
+            Some(g3)
+        );
+        assert_eq!(g.complete_add(&neutral).to_affine(), g.to_affine());
+        assert_eq!(neutral.complete_add(&neutral).to_affine(), None);
+        assert_eq!(g.complete_add(&ProjectivePoint::from_affine(&neg_g)).to_affine(), None);
+
+        let words = g.to_words_le();
+        assert_eq!(ProjectivePoint::<SwCurve<E>>::from_words_le(&words), g);
+        assert_eq!(g.z, BigUint::from(1u32));
+    }
+
+    #[test]
+    fn test_complete_add() {
+        check_complete_add::<Secp256k1Parameters>();
+        check_complete_add::<Bn254Parameters>();
+    }
+}
@@ -1,6 +1,28 @@
 /// The maximum size of the memory in bytes.
 pub const MAXIMUM_MEMORY_SIZE: u32 = u32::MAX;
 
+/// The address space of a RISC-V execution mode.
+///
+/// The memory chips are generic over the address space, so that a 64-bit mode can be added next
+/// to [`Rv32`] with its own address columns.
+pub trait AddressSpace: Send + Sync + 'static {
+    /// The type of an address.
+    type Addr: Copy + Ord + Into<u64>;
+
+    /// The number of bits of an address.
+    const ADDR_BITS: usize;
+}
+
+/// The 32-bit address space of RV32IM.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rv32;
+
+impl AddressSpace for Rv32 {
+    type Addr = u32;
+
+    const ADDR_BITS: usize = 32;
+}
+
 /// The size of a word in bytes.
 pub const WORD_SIZE: usize = 4;
 
@@ -42,6 +42,22 @@ impl SP1PublicValues {
         self.buffer.read_slice(slice);
     }
 
+    /// Read the [`PublicValuesDomain`] written at the start of the public values by programs
+    /// built with the `replay-protection` feature of `sp1-zkvm`.
+    pub fn read_domain(&mut self) -> PublicValuesDomain {
+        let mut bytes = [0; PUBLIC_VALUES_DOMAIN_SIZE];
+        self.buffer.read_slice(&mut bytes);
+        PublicValuesDomain::from_bytes(&bytes)
+    }
+
+    /// Read the [`MemoryLayout`] written by programs built with the `aslr` feature of `sp1-zkvm`,
+    /// after the [`PublicValuesDomain`] if the program also has the `replay-protection` feature.
+    pub fn read_layout(&mut self) -> MemoryLayout {
+        let mut bytes = [0; MEMORY_LAYOUT_SIZE];
+        self.buffer.read_slice(&mut bytes);
+        MemoryLayout::from_bytes(&bytes)
+    }
+
     /// Write a value to the buffer.
     pub fn write<T: Serialize>(&mut self, data: &T) {
         self.buffer.write(data);
@@ -52,6 +68,22 @@ impl SP1PublicValues {
         self.buffer.write_slice(slice);
     }
 
+    /// The commitment to stdin appended to the public values by programs built with the
+    /// `commit-stdin` feature of `sp1-zkvm`, i.e. their last [`STDIN_COMMITMENT_SIZE`] bytes.
+    pub fn stdin_commitment(&self) -> Option<[u8; STDIN_COMMITMENT_SIZE]> {
+        let data = self.buffer.data.as_slice();
+        let start = data.len().checked_sub(STDIN_COMMITMENT_SIZE)?;
+        data[start..].try_into().ok()
+    }
+
+    /// The [`PublicValuesDomain`] written at the start of the public values by programs built
+    /// with the `replay-protection` feature of `sp1-zkvm`, i.e. their first
+    /// [`PUBLIC_VALUES_DOMAIN_SIZE`] bytes, without moving the read position.
+    pub fn domain(&self) -> Option<PublicValuesDomain> {
+        let bytes = self.buffer.data.get(..PUBLIC_VALUES_DOMAIN_SIZE)?;
+        Some(PublicValuesDomain::from_bytes(bytes.try_into().ok()?))
+    }
+
     /// Hash the public values.
     pub fn hash(&self) -> Vec<u8> {
         let mut hasher = Sha256::new();
@@ -80,6 +112,109 @@ impl SP1PublicValues {
     }
 }
 
+/// The size of an encoded [`PublicValuesDomain`], in bytes.
+pub const PUBLIC_VALUES_DOMAIN_SIZE: usize = 40;
+
+/// A domain separator and a nonce that programs built with the `replay-protection` feature of
+/// `sp1-zkvm` write at the start of their public values.
+///
+/// Since they are the first bytes of the public values, they are hashed into the committed value
+/// digest of the core proof, which the recursion circuits carry unchanged and the wrap circuits
+/// expose as a public input. A consumer that checks them against its own domain can reject a proof
+/// generated for another chain or context, or one that it already accepted. The zkVM does not
+/// know whether a program has the feature, so the first bytes of the public values of a program
+/// without it are whatever the program commits, and the domain is only meaningful for programs
+/// known to be built with the feature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PublicValuesDomain {
+    /// Identifies the context the proof is generated for, for example a chain and a contract.
+    pub domain_separator: [u8; 32],
+    /// Distinguishes proofs generated for the same context.
+    pub nonce: u64,
+}
+
+impl PublicValuesDomain {
+    /// Create a new `PublicValuesDomain`.
+    pub const fn new(domain_separator: [u8; 32], nonce: u64) -> Self {
+        Self { domain_separator, nonce }
+    }
+
+    /// Encodes the domain as the domain separator followed by the nonce, as a big-endian `u64`
+    /// like the `uint64` of an ABI-encoded `(bytes32, uint64)` after unpadding.
+    pub fn to_bytes(&self) -> [u8; PUBLIC_VALUES_DOMAIN_SIZE] {
+        let mut bytes = [0; PUBLIC_VALUES_DOMAIN_SIZE];
+        bytes[..32].copy_from_slice(&self.domain_separator);
+        bytes[32..].copy_from_slice(&self.nonce.to_be_bytes());
+        bytes
+    }
+
+    /// Decodes a domain encoded with [`Self::to_bytes`].
+    pub fn from_bytes(bytes: &[u8; PUBLIC_VALUES_DOMAIN_SIZE]) -> Self {
+        let mut domain_separator = [0; 32];
+        domain_separator.copy_from_slice(&bytes[..32]);
+        let mut nonce = [0; 8];
+        nonce.copy_from_slice(&bytes[32..]);
+        Self { domain_separator, nonce: u64::from_be_bytes(nonce) }
+    }
+}
+
+/// The size of the seed of the memory layout of programs built with the `aslr` feature of
+/// `sp1-zkvm`, in bytes.
+pub const LAYOUT_SEED_SIZE: usize = 8;
+
+/// The size of an encoded [`MemoryLayout`], in bytes.
+pub const MEMORY_LAYOUT_SIZE: usize = 8;
+
+/// The memory layout that programs built with the `aslr` feature of `sp1-zkvm` derive from the seed
+/// written with `SP1Stdin::write_layout_seed`, and write to their public values.
+///
+/// Running a program under several seeds and checking that its other public values do not change
+/// detects programs that depend on the absolute addresses of their stack or heap.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MemoryLayout {
+    /// The initial stack pointer.
+    pub stack_top: u32,
+    /// The address of the first allocation of the heap.
+    pub heap_start: u32,
+}
+
+impl MemoryLayout {
+    /// Encodes the layout as the stack top followed by the start of the heap, as little-endian
+    /// `u32`s.
+    pub fn to_bytes(&self) -> [u8; MEMORY_LAYOUT_SIZE] {
+        let mut bytes = [0; MEMORY_LAYOUT_SIZE];
+        bytes[..4].copy_from_slice(&self.stack_top.to_le_bytes());
+        bytes[4..].copy_from_slice(&self.heap_start.to_le_bytes());
+        bytes
+    }
+
+    /// Decodes a layout encoded with [`Self::to_bytes`].
+    pub fn from_bytes(bytes: &[u8; MEMORY_LAYOUT_SIZE]) -> Self {
+        let [s0, s1, s2, s3, h0, h1, h2, h3] = *bytes;
+        Self {
+            stack_top: u32::from_le_bytes([s0, s1, s2, s3]),
+            heap_start: u32::from_le_bytes([h0, h1, h2, h3]),
+        }
+    }
+}
+
+/// The size of a commitment to stdin, in bytes.
+pub const STDIN_COMMITMENT_SIZE: usize = 32;
+
+/// The commitment to the elements of the hint stream computed by programs built with the
+/// `commit-stdin` feature of `sp1-zkvm`: the SHA-256 digest of each element, prefixed with its
+/// length as a little-endian `u64`.
+pub fn stdin_commitment<'a>(
+    elements: impl IntoIterator<Item = &'a [u8]>,
+) -> [u8; STDIN_COMMITMENT_SIZE] {
+    let mut hasher = Sha256::new();
+    for element in elements {
+        hasher.update((element.len() as u64).to_le_bytes());
+        hasher.update(element);
+    }
+    hasher.finalize().into()
+}
+
 impl AsRef<[u8]> for SP1PublicValues {
     fn as_ref(&self) -> &[u8] {
         &self.buffer.data
@@ -104,4 +239,47 @@ mod tests {
 
         assert_eq!(hash, expected_hash_biguint);
     }
+
+    #[test]
+    fn test_stdin_commitment() {
+        let commitment = stdin_commitment([&[1, 2, 3][..], &[4]]);
+        assert_ne!(commitment, stdin_commitment([&[1, 2, 3, 4][..]]));
+        assert_ne!(commitment, stdin_commitment([&[1, 2, 3][..]]));
+
+        let mut public_values = SP1PublicValues::new();
+        assert_eq!(public_values.stdin_commitment(), None);
+        public_values.write_slice(&[9; 5]);
+        public_values.write_slice(&commitment);
+        assert_eq!(public_values.stdin_commitment(), Some(commitment));
+    }
+
+    #[test]
+    fn test_read_domain() {
+        let domain = PublicValuesDomain::new([7; 32], 0x0102_0304_0506_0708);
+        let bytes = domain.to_bytes();
+        assert_eq!(bytes[32..], [1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let mut public_values = SP1PublicValues::new();
+        public_values.write_slice(&bytes);
+        public_values.write(&42u32);
+        assert_eq!(public_values.domain(), Some(domain));
+        assert_eq!(public_values.read_domain(), domain);
+        assert_eq!(public_values.read::<u32>(), 42);
+        assert_eq!(public_values.domain(), Some(domain));
+
+        assert_eq!(SP1PublicValues::from(&bytes[..39]).domain(), None);
+    }
+
+    #[test]
+    fn test_read_layout() {
+        let layout = MemoryLayout { stack_top: 0x0020_0000, heap_start: 0x0030_1230 };
+        let bytes = layout.to_bytes();
+        assert_eq!(bytes[..4], [0x00, 0x00, 0x20, 0x00]);
+
+        let mut public_values = SP1PublicValues::new();
+        public_values.write_slice(&bytes);
+        public_values.write(&42u32);
+        assert_eq!(public_values.read_layout(), layout);
+        assert_eq!(public_values.read::<u32>(), 42);
+    }
 }
@@ -0,0 +1,107 @@
+//! End-to-end benchmarks of representative programs.
+//!
+//! Run with `cargo bench -p sp1-prover --features bench`. The programs to run can be selected with
+//! a comma-separated list in `BENCH_PROGRAMS`, and the report is written as JSON to the path in
+//! `BENCH_REPORT`, which defaults to `bench-report.json`.
+
+use std::{fs, path::Path};
+
+use sp1_prover::{bench::BenchReport, SP1Prover, SP1Stdin};
+use sp1_stark::SP1ProverOpts;
+
+/// A program to benchmark, with the path of its ELF relative to the root of the repository.
+struct BenchProgram {
+    name: &'static str,
+    elf: &'static str,
+    stdin: fn() -> SP1Stdin,
+}
+
+const PROGRAMS: &[BenchProgram] = &[
+    BenchProgram {
+        name: "fibonacci",
+        elf: "examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf",
+        stdin: fibonacci_stdin,
+    },
+    BenchProgram {
+        name: "keccak",
+        elf: "tests/keccak256/elf/riscv32im-succinct-zkvm-elf",
+        stdin: keccak_stdin,
+    },
+    BenchProgram {
+        name: "ecdsa-verify",
+        elf: "tests/ecdsa-verify/elf/riscv32im-succinct-zkvm-elf",
+        stdin: ecdsa_verify_stdin,
+    },
+    BenchProgram {
+        name: "json-parse",
+        elf: "tests/json-parse/elf/riscv32im-succinct-zkvm-elf",
+        stdin: json_parse_stdin,
+    },
+];
+
+fn fibonacci_stdin() -> SP1Stdin {
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&300_000u32);
+    stdin
+}
+
+fn keccak_stdin() -> SP1Stdin {
+    let mut stdin = SP1Stdin::new();
+    let num_cases = 200usize;
+    stdin.write(&num_cases);
+    for i in 0..num_cases {
+        stdin.write(&vec![i as u8; 1024]);
+    }
+    stdin
+}
+
+fn ecdsa_verify_stdin() -> SP1Stdin {
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&20u32);
+    stdin
+}
+
+fn json_parse_stdin() -> SP1Stdin {
+    let accounts = (0..500)
+        .map(|i| {
+            serde_json::json!({
+                "id": i,
+                "name": format!("account-{i}"),
+                "balance": i * 1_000,
+                "tags": ["bench", "sp1"],
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&serde_json::to_string(&accounts).unwrap());
+    stdin
+}
+
+fn main() {
+    sp1_core_machine::utils::setup_logger();
+
+    let selected = std::env::var("BENCH_PROGRAMS").ok();
+    let programs = PROGRAMS.iter().filter(|program| {
+        selected.as_ref().map_or(true, |selected| selected.split(',').any(|s| s == program.name))
+    });
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let prover: SP1Prover = SP1Prover::new();
+    let opts = SP1ProverOpts::default();
+    let mut reports: Vec<BenchReport> = Vec::new();
+    for program in programs {
+        let path = root.join(program.elf);
+        let elf = fs::read(&path).unwrap_or_else(|e| {
+            panic!("failed to read {}, build it with `cargo prove build`: {e}", path.display())
+        });
+        let report = prover
+            .bench(program.name, &elf, &(program.stdin)(), opts.clone())
+            .unwrap_or_else(|e| panic!("failed to benchmark {}: {e}", program.name));
+        println!("{report}");
+        reports.push(report);
+    }
+
+    let path = std::env::var("BENCH_REPORT").unwrap_or_else(|_| "bench-report.json".to_string());
+    fs::write(&path, serde_json::to_string_pretty(&reports).unwrap()).unwrap();
+    println!("wrote report to {path}");
+}
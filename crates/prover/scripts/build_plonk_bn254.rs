@@ -2,17 +2,29 @@ use std::path::PathBuf;
 
 use clap::Parser;
 use sp1_core_machine::utils::setup_logger;
-use sp1_prover::build::build_plonk_bn254_artifacts_with_dummy;
+use sp1_prover::build::{
+    build_plonk_bn254_artifacts, dummy_proof_with_shrink_log_degree, shrink_artifacts_dir,
+};
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
     #[clap(short, long)]
     build_dir: PathBuf,
+
+    /// The log-degrees of the shrink proofs to build the artifacts for. The artifacts of the
+    /// default log-degree are written to the build directory, and the others to its
+    /// `shrink-<log-degree>` subdirectories.
+    #[clap(long, value_delimiter = ',', default_value = "3")]
+    shrink_log_degrees: Vec<usize>,
 }
 
 pub fn main() {
     setup_logger();
     let args = Args::parse();
-    build_plonk_bn254_artifacts_with_dummy(args.build_dir);
+    for shrink_log_degree in args.shrink_log_degrees {
+        let (wrap_vk, wrapped_proof) = dummy_proof_with_shrink_log_degree(shrink_log_degree);
+        let build_dir = shrink_artifacts_dir(&args.build_dir, shrink_log_degree);
+        build_plonk_bn254_artifacts(&wrap_vk, &wrapped_proof, build_dir);
+    }
 }
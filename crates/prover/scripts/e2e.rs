@@ -44,13 +44,13 @@ pub fn main() {
 
     tracing::info!("prove core");
     let stdin = SP1Stdin::new();
-    let core_proof = prover.prove_core(&pk, &stdin, opts, context).unwrap();
+    let core_proof = prover.prove_core(&pk, &stdin, opts.clone(), context).unwrap();
 
     tracing::info!("Compress");
-    let reduced_proof = prover.compress(&vk, core_proof, vec![], opts).unwrap();
+    let reduced_proof = prover.compress(&vk, core_proof, vec![], opts.clone()).unwrap();
 
     tracing::info!("Shrink");
-    let compressed_proof = prover.shrink(reduced_proof, opts).unwrap();
+    let compressed_proof = prover.shrink(reduced_proof, opts.clone()).unwrap();
 
     tracing::info!("wrap");
     let wrapped_proof = prover.wrap_bn254(compressed_proof, opts).unwrap();
@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use sp1_core_machine::utils::setup_logger;
+use sp1_prover::ceremony::Groth16Ceremony;
+
+/// Runs a phase 2 trusted setup ceremony for the Groth16 circuit built in a directory.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// The directory of the ceremony.
+    #[clap(short, long)]
+    dir: PathBuf,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Start a ceremony from phase 1 parameters.
+    Init {
+        #[clap(short, long)]
+        build_dir: PathBuf,
+        #[clap(short, long)]
+        phase1: PathBuf,
+    },
+    /// Contribute to the latest parameters.
+    Contribute {
+        #[clap(short, long)]
+        name: String,
+    },
+    /// Add parameters contributed elsewhere.
+    Add {
+        #[clap(short, long)]
+        name: String,
+        #[clap(short, long)]
+        path: PathBuf,
+    },
+    /// Verify all the contributions.
+    Verify {
+        #[clap(short, long)]
+        build_dir: PathBuf,
+    },
+    /// Apply the random beacon and write the keys to the build directory.
+    Finalize {
+        #[clap(short, long)]
+        build_dir: PathBuf,
+        /// The beacon, hex encoded.
+        #[clap(long)]
+        beacon: String,
+        #[clap(long, default_value_t = 10)]
+        iterations: u32,
+    },
+}
+
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
+    setup_logger();
+    let args = Args::parse();
+    match args.command {
+        Command::Init { build_dir, phase1 } => {
+            Groth16Ceremony::init(args.dir, &build_dir, &phase1)?;
+        }
+        Command::Contribute { name } => {
+            let contribution = Groth16Ceremony::open(args.dir)?.contribute(&name)?.clone();
+            println!("contribution hash: {}", contribution.hash);
+        }
+        Command::Add { name, path } => {
+            let contribution =
+                Groth16Ceremony::open(args.dir)?.add_contribution(&name, &path)?.clone();
+            println!("contribution hash: {}", contribution.hash);
+        }
+        Command::Verify { build_dir } => {
+            Groth16Ceremony::open(args.dir)?.verify(&build_dir)?;
+            println!("all contributions are valid");
+        }
+        Command::Finalize { build_dir, beacon, iterations } => {
+            let beacon = hex::decode(beacon)?;
+            Groth16Ceremony::open(args.dir)?.finalize(&build_dir, &beacon, iterations)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use sp1_core_machine::utils::setup_logger;
+use sp1_prover::{program_cache::RecursionProgramCache, SP1Prover};
+
+/// Compiles the recursion programs ahead of time into the program cache, or reports which of them
+/// are already cached.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Compile the programs and write them to the cache.
+    #[clap(long)]
+    prebuild: bool,
+
+    /// The cache directory. Defaults to `$SP1_PROGRAM_CACHE_DIR` or `~/.sp1/programs/<version>`.
+    /// The prover only reads the cache when `SP1_PROGRAM_CACHE_DIR` is set to its directory.
+    #[clap(long)]
+    cache_dir: Option<PathBuf>,
+}
+
+pub fn main() {
+    setup_logger();
+    let args = Args::parse();
+
+    let cache = match args.cache_dir {
+        Some(dir) => RecursionProgramCache::new(dir),
+        None => RecursionProgramCache::from_env()
+            .or_else(RecursionProgramCache::default_location)
+            .expect("failed to find the home directory"),
+    };
+
+    let mut prover: SP1Prover = SP1Prover::uninitialized();
+    if args.prebuild {
+        // Compile from scratch rather than re-serializing programs that are already cached.
+        prover.program_cache = None;
+        for path in prover.prebuild_programs(&cache).expect("failed to write program cache") {
+            println!("wrote {}", path.display());
+        }
+    } else {
+        prover.program_cache = Some(cache.clone());
+        let keys = [
+            ("recursion", prover.recursion_program_key()),
+            ("deferred", prover.deferred_program_key()),
+            ("compress", prover.compress_program_key()),
+            ("shrink", prover.shrink_program_key()),
+            ("wrap", prover.wrap_program_key()),
+        ];
+        for (name, key) in keys {
+            let path = cache.path(name, &key);
+            let status = if path.exists() { "cached" } else { "missing" };
+            println!("{name}: {status} ({})", path.display());
+        }
+    }
+}
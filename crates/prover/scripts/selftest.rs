@@ -0,0 +1,16 @@
+use sp1_prover::SP1Prover;
+
+/// Proves and verifies a small program through every stage of the prover, and reports the time
+/// taken by each stage. Exits with a non-zero code if any stage fails.
+pub fn main() {
+    sp1_core_machine::utils::setup_logger();
+
+    let prover: SP1Prover = SP1Prover::new();
+    match prover.selftest() {
+        Ok(report) => println!("selftest passed\n{report}"),
+        Err(e) => {
+            eprintln!("selftest failed: {e}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -63,6 +63,11 @@ fn main() {
             buffer: vec![bincode::serialize::<u32>(&iterations).unwrap()],
             ptr: 0,
             proofs: vec![],
+            paged_inputs: vec![],
+            files: Default::default(),
+            public_values_domain: None,
+            env_vars: Default::default(),
+            args: Vec::new(),
         };
         let leaf_proving_start = Instant::now();
         let proof = prover
@@ -0,0 +1,418 @@
+//! Storage of the artifacts of the prover in a shared location.
+//!
+//! A prover reads the circuit artifacts and proving keys it needs, and distributed provers also
+//! exchange the shard proofs they produce. By default these live in `~/.sp1`, which a cluster of
+//! provers would have to mount from a shared filesystem. An [`ArtifactStore`] abstracts where they
+//! are kept instead: a [`LocalArtifactStore`] keeps them in a directory, and, with the `s3`
+//! feature, an [`S3ArtifactStore`] keeps them in a bucket of S3 or of any service implementing its
+//! API, such as Google Cloud Storage or MinIO.
+//!
+//! Artifacts are addressed by `/`-separated keys, built with [`circuit_artifacts_prefix`],
+//! [`proving_key_key`] and [`shard_proof_key`] for the artifacts of the prover. A coordinator
+//! shares the proving key of a program with [`save_proving_key`], which workers then read with
+//! [`load_proving_key`] from the hash of its verifying key, and the workers share the shard proofs
+//! of a job with [`save_shard_proofs`] and [`load_shard_proofs`].
+
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use sp1_stark::ShardProof;
+
+use crate::{CoreSC, HashableKey, SP1ProvingKey, SP1_CIRCUIT_VERSION};
+
+/// The environment variable selecting the artifact store returned by [`from_env`], either an
+/// `s3://<bucket>/<prefix>` URL or the path of a directory.
+pub const SP1_ARTIFACT_STORE: &str = "SP1_ARTIFACT_STORE";
+
+/// A key-value store of artifacts.
+pub trait ArtifactStore: Send + Sync {
+    /// Reads the artifact at `key`, returning `None` if there is none.
+    fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>>;
+
+    /// Writes the artifact at `key`, replacing any previous one.
+    fn put(&self, key: &str, bytes: &[u8]) -> io::Result<()>;
+
+    /// Returns the keys of the artifacts starting with `prefix`.
+    fn list(&self, prefix: &str) -> io::Result<Vec<String>>;
+
+    /// Whether there is an artifact at `key`.
+    fn contains(&self, key: &str) -> io::Result<bool> {
+        Ok(self.get(key)?.is_some())
+    }
+}
+
+/// An artifact store backed by a directory, where the artifact at `key` is the file at the path
+/// `key` relative to the directory.
+#[derive(Debug, Clone)]
+pub struct LocalArtifactStore {
+    root: PathBuf,
+}
+
+impl LocalArtifactStore {
+    /// Creates a store backed by the given directory, which is created on the first write.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The directory backing the store.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The path of the artifact at `key`.
+    pub fn path(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+}
+
+impl ArtifactStore for LocalArtifactStore {
+    fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+        match std::fs::read(self.path(key)) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn put(&self, key: &str, bytes: &[u8]) -> io::Result<()> {
+        let path = self.path(key);
+        let dir = path.parent().unwrap_or(&self.root);
+        std::fs::create_dir_all(dir)?;
+        // Write to a temporary file which is then renamed into place, so that concurrent readers
+        // never see a partial artifact.
+        let mut file = tempfile::NamedTempFile::new_in(dir)?;
+        file.write_all(bytes)?;
+        file.persist(&path).map_err(|e| e.error)?;
+        Ok(())
+    }
+
+    fn list(&self, prefix: &str) -> io::Result<Vec<String>> {
+        let mut keys = Vec::new();
+        if self.root.exists() {
+            list_files(&self.root, &self.root, &mut keys)?;
+        }
+        keys.retain(|key| key.starts_with(prefix));
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
+    fn contains(&self, key: &str) -> io::Result<bool> {
+        Ok(self.path(key).is_file())
+    }
+}
+
+/// Appends the keys of the files under `dir` to `keys`, relative to `root`.
+fn list_files(root: &Path, dir: &Path, keys: &mut Vec<String>) -> io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            list_files(root, &path, keys)?;
+        } else {
+            let relative = path.strip_prefix(root).unwrap();
+            let components =
+                relative.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>();
+            keys.push(components.join("/"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "s3")]
+pub use s3_store::S3ArtifactStore;
+
+#[cfg(feature = "s3")]
+mod s3_store {
+    use std::io;
+
+    use s3::{bucket::Bucket, creds::Credentials, region::Region};
+
+    use super::ArtifactStore;
+
+    /// An artifact store backed by a bucket of S3, where the artifact at `key` is the object at
+    /// `<prefix>/key`.
+    ///
+    /// Credentials are read from the standard AWS environment variables and profile files. Other
+    /// services implementing the S3 API are reached through [`S3ArtifactStore::with_endpoint`],
+    /// e.g. Google Cloud Storage at `https://storage.googleapis.com` with HMAC keys.
+    #[derive(Debug, Clone)]
+    pub struct S3ArtifactStore {
+        bucket: Box<Bucket>,
+        prefix: String,
+    }
+
+    impl S3ArtifactStore {
+        /// Creates a store backed by `bucket` in the AWS `region`, under `prefix`.
+        pub fn new(bucket: &str, region: &str, prefix: &str) -> io::Result<Self> {
+            let region = region.parse::<Region>().map_err(io::Error::other)?;
+            Self::with_region(bucket, region, prefix)
+        }
+
+        /// Creates a store backed by `bucket` of the S3-compatible service at `endpoint`, under
+        /// `prefix`. Objects are addressed with path-style URLs, which all such services support.
+        pub fn with_endpoint(
+            bucket: &str,
+            region: &str,
+            endpoint: &str,
+            prefix: &str,
+        ) -> io::Result<Self> {
+            let region =
+                Region::Custom { region: region.to_string(), endpoint: endpoint.to_string() };
+            let mut store = Self::with_region(bucket, region, prefix)?;
+            store.bucket = store.bucket.with_path_style();
+            Ok(store)
+        }
+
+        fn with_region(bucket: &str, region: Region, prefix: &str) -> io::Result<Self> {
+            let credentials = Credentials::default().map_err(io::Error::other)?;
+            let bucket = Bucket::new(bucket, region, credentials).map_err(io::Error::other)?;
+            Ok(Self { bucket, prefix: prefix.trim_matches('/').to_string() })
+        }
+
+        /// The path of the object of the artifact at `key`.
+        fn object(&self, key: &str) -> String {
+            if self.prefix.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}/{}", self.prefix, key)
+            }
+        }
+    }
+
+    impl ArtifactStore for S3ArtifactStore {
+        fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+            let response = self.bucket.get_object(self.object(key)).map_err(io::Error::other)?;
+            match response.status_code() {
+                200 => Ok(Some(response.bytes().to_vec())),
+                404 => Ok(None),
+                status => Err(io::Error::other(format!("failed to get {key}: status {status}"))),
+            }
+        }
+
+        fn put(&self, key: &str, bytes: &[u8]) -> io::Result<()> {
+            let response =
+                self.bucket.put_object(self.object(key), bytes).map_err(io::Error::other)?;
+            match response.status_code() {
+                200 => Ok(()),
+                status => Err(io::Error::other(format!("failed to put {key}: status {status}"))),
+            }
+        }
+
+        fn list(&self, prefix: &str) -> io::Result<Vec<String>> {
+            let results = self.bucket.list(self.object(prefix), None).map_err(io::Error::other)?;
+            let strip = if self.prefix.is_empty() { 0 } else { self.prefix.len() + 1 };
+            let mut keys = results
+                .into_iter()
+                .flat_map(|result| result.contents)
+                .map(|object| object.key[strip..].to_string())
+                .collect::<Vec<_>>();
+            keys.sort_unstable();
+            Ok(keys)
+        }
+    }
+}
+
+/// The artifact store selected by `$SP1_ARTIFACT_STORE`, if it is set.
+///
+/// An `s3://<bucket>/<prefix>` URL selects an [`S3ArtifactStore`] in the region of
+/// `$AWS_REGION`, or at the endpoint of `$AWS_ENDPOINT_URL` if it is set, and requires the `s3`
+/// feature. Any other value is the directory of a [`LocalArtifactStore`].
+pub fn from_env() -> io::Result<Option<Box<dyn ArtifactStore>>> {
+    let Ok(location) = std::env::var(SP1_ARTIFACT_STORE) else {
+        return Ok(None);
+    };
+    match location.strip_prefix("s3://") {
+        Some(path) => s3_from_env(path).map(Some),
+        None => Ok(Some(Box::new(LocalArtifactStore::new(location)))),
+    }
+}
+
+#[cfg(feature = "s3")]
+fn s3_from_env(path: &str) -> io::Result<Box<dyn ArtifactStore>> {
+    let (bucket, prefix) = path.split_once('/').unwrap_or((path, ""));
+    let region = std::env::var("AWS_REGION").unwrap_or_else(|_| "us-east-1".to_string());
+    let store = match std::env::var("AWS_ENDPOINT_URL") {
+        Ok(endpoint) => S3ArtifactStore::with_endpoint(bucket, &region, &endpoint, prefix)?,
+        Err(_) => S3ArtifactStore::new(bucket, &region, prefix)?,
+    };
+    Ok(Box::new(store))
+}
+
+#[cfg(not(feature = "s3"))]
+fn s3_from_env(_: &str) -> io::Result<Box<dyn ArtifactStore>> {
+    Err(io::Error::other(format!(
+        "{SP1_ARTIFACT_STORE} is an S3 URL, but sp1-prover was built without the `s3` feature"
+    )))
+}
+
+/// The prefix of the keys of the circuit artifacts of this version, the files of the directory
+/// used to wrap proofs into Groth16 and PLONK proofs.
+pub fn circuit_artifacts_prefix() -> String {
+    format!("circuits/{SP1_CIRCUIT_VERSION}/")
+}
+
+/// The key of the proving key of the program whose verifying key hashes to `vk_hash`.
+pub fn proving_key_key(vk_hash: &str) -> String {
+    format!("proving-keys/{SP1_CIRCUIT_VERSION}/{vk_hash}.bin")
+}
+
+/// The key of the shard proof at `index` of the proving job `job_id`.
+pub fn shard_proof_key(job_id: &str, index: usize) -> String {
+    format!("{}{index:08}.bin", shard_proofs_prefix(job_id))
+}
+
+/// The prefix of the keys of the shard proofs of the proving job `job_id`.
+fn shard_proofs_prefix(job_id: &str) -> String {
+    format!("shard-proofs/{job_id}/")
+}
+
+/// Reads the bincode-encoded artifact at `key`.
+pub fn load<T: DeserializeOwned>(store: &dyn ArtifactStore, key: &str) -> io::Result<Option<T>> {
+    store.get(key)?.map(|bytes| bincode::deserialize(&bytes).map_err(io::Error::other)).transpose()
+}
+
+/// Writes `value` bincode-encoded at `key`.
+pub fn save<T: Serialize>(store: &dyn ArtifactStore, key: &str, value: &T) -> io::Result<()> {
+    let bytes = bincode::serialize(value).map_err(io::Error::other)?;
+    store.put(key, &bytes)
+}
+
+/// Writes the proving key `pk` at the [`proving_key_key`] of its verifying key.
+pub fn save_proving_key(store: &dyn ArtifactStore, pk: &SP1ProvingKey) -> io::Result<()> {
+    save(store, &proving_key_key(&pk.vk.bytes32()), pk)
+}
+
+/// Reads the proving key of the program whose verifying key hashes to `vk_hash`, as returned by
+/// [`HashableKey::bytes32`].
+pub fn load_proving_key(
+    store: &dyn ArtifactStore,
+    vk_hash: &str,
+) -> io::Result<Option<SP1ProvingKey>> {
+    load(store, &proving_key_key(vk_hash))
+}
+
+/// Writes the shard proofs of the proving job `job_id` at their [`shard_proof_key`], starting
+/// at the shard proof at index `start`.
+pub fn save_shard_proofs(
+    store: &dyn ArtifactStore,
+    job_id: &str,
+    start: usize,
+    proofs: &[ShardProof<CoreSC>],
+) -> io::Result<()> {
+    for (i, proof) in proofs.iter().enumerate() {
+        save(store, &shard_proof_key(job_id, start + i), proof)?;
+    }
+    Ok(())
+}
+
+/// Reads all the shard proofs of the proving job `job_id`, in order.
+///
+/// Fails if a shard proof is missing between the first and the last one in the store.
+pub fn load_shard_proofs(
+    store: &dyn ArtifactStore,
+    job_id: &str,
+) -> io::Result<Vec<ShardProof<CoreSC>>> {
+    let keys = store.list(&shard_proofs_prefix(job_id))?;
+    if let Some(i) = keys.iter().enumerate().position(|(i, key)| *key != shard_proof_key(job_id, i))
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("shard proof {i} of job {job_id} is missing"),
+        ));
+    }
+    keys.iter()
+        .map(|key| {
+            load(store, key)?
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("{key} vanished")))
+        })
+        .collect()
+}
+
+/// Copies the artifacts starting with `prefix` into the directory `dir`, as files at their key
+/// without the prefix.
+///
+/// The artifacts are copied into a temporary directory next to `dir`, which is then renamed to
+/// `dir`, so that an interrupted copy never leaves an incomplete `dir` behind. Fails if `dir`
+/// already exists and is not empty. Nothing is created if there are no such artifacts.
+///
+/// Returns the number of artifacts copied.
+pub fn download_dir(store: &dyn ArtifactStore, prefix: &str, dir: &Path) -> io::Result<usize> {
+    let keys = store.list(prefix)?;
+    if keys.is_empty() {
+        return Ok(0);
+    }
+    let parent = dir.parent().unwrap_or(Path::new("."));
+    std::fs::create_dir_all(parent)?;
+    let tmp = tempfile::Builder::new().prefix(".download-").tempdir_in(parent)?;
+    let local = LocalArtifactStore::new(tmp.path());
+    for key in &keys {
+        let bytes = store
+            .get(key)?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("{key} vanished")))?;
+        local.put(&key[prefix.len()..], &bytes)?;
+    }
+    // The temporary directory is removed when dropped if the rename fails.
+    std::fs::rename(tmp.path(), dir)?;
+    Ok(keys.len())
+}
+
+/// Copies the files of `dir` into the store, at their path relative to `dir` after `prefix`.
+///
+/// Returns the number of artifacts copied.
+pub fn upload_dir(store: &dyn ArtifactStore, dir: &Path, prefix: &str) -> io::Result<usize> {
+    let local = LocalArtifactStore::new(dir);
+    let names = local.list("")?;
+    for name in &names {
+        let bytes = local.get(name)?.unwrap();
+        store.put(&format!("{prefix}{name}"), &bytes)?;
+    }
+    Ok(names.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_artifact_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LocalArtifactStore::new(dir.path());
+        assert_eq!(store.get("shard-proofs/job/00000000.bin").unwrap(), None);
+
+        save(&store, &shard_proof_key("job", 0), &vec![1u32, 2, 3]).unwrap();
+        save(&store, &shard_proof_key("job", 1), &vec![4u32]).unwrap();
+        save(&store, &shard_proof_key("other", 0), &vec![5u32]).unwrap();
+        assert_eq!(
+            load::<Vec<u32>>(&store, &shard_proof_key("job", 0)).unwrap(),
+            Some(vec![1, 2, 3])
+        );
+        assert_eq!(
+            store.list("shard-proofs/job/").unwrap(),
+            vec!["shard-proofs/job/00000000.bin", "shard-proofs/job/00000001.bin"]
+        );
+
+        // A directory round-trips through the store.
+        let parent = tempfile::tempdir().unwrap();
+        let copy = parent.path().join("copy");
+        assert_eq!(download_dir(&store, "missing/", &copy).unwrap(), 0);
+        assert!(!copy.exists());
+        assert_eq!(download_dir(&store, "shard-proofs/", &copy).unwrap(), 3);
+        // A complete copy is never overwritten, and no temporary directory is left behind.
+        assert!(download_dir(&store, "shard-proofs/", &copy).is_err());
+        let entries = std::fs::read_dir(parent.path()).unwrap().collect::<Vec<_>>();
+        assert_eq!(entries.len(), 1);
+        let other_dir = tempfile::tempdir().unwrap();
+        let other = LocalArtifactStore::new(other_dir.path());
+        assert_eq!(upload_dir(&other, &copy, "copy/").unwrap(), 3);
+        assert_eq!(load::<Vec<u32>>(&other, "copy/other/00000000.bin").unwrap(), Some(vec![5]));
+
+        // A gap in the shard proofs of a job is reported before any of them is decoded.
+        store.put(&shard_proof_key("gap", 0), b"").unwrap();
+        store.put(&shard_proof_key("gap", 2), b"").unwrap();
+        let err = load_shard_proofs(&store, "gap").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(load_shard_proofs(&store, "none").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+//! End-to-end benchmarks, which prove a program through every stage of the prover and report the
+//! time taken by each stage and the peak memory usage, so that regressions across releases can be
+//! measured.
+//!
+//! The harness in `benches/e2e.rs` runs a set of representative programs with
+//! `cargo bench -p sp1-prover --features bench`.
+
+use std::{
+    fmt, fs,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use sp1_core_executor::{ExecutionError, ProofMonitor, SP1Context};
+use sp1_core_machine::{io::SP1Stdin, utils::SP1CoreProverError};
+use sp1_stark::SP1ProverOpts;
+use thiserror::Error;
+
+use crate::{components::SP1ProverComponents, SP1Prover, SP1RecursionProverError};
+
+/// The time taken by each stage of an end-to-end proof, in seconds.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct BenchTimes {
+    /// Executing the program, without proving it.
+    pub execute: f64,
+    /// Committing to the traces of every core shard.
+    pub commit: f64,
+    /// Opening the commitments of every core shard, which completes the core proof.
+    pub open: f64,
+    /// Compressing the core proof with the recursion prover.
+    pub compress: f64,
+    /// Shrinking the compressed proof.
+    pub shrink: f64,
+    /// Wrapping the shrunk proof into a BN254 proof.
+    pub wrap: f64,
+}
+
+impl BenchTimes {
+    /// The total time taken, in seconds.
+    pub fn total(&self) -> f64 {
+        self.execute + self.commit + self.open + self.compress + self.shrink + self.wrap
+    }
+}
+
+/// The result of benchmarking a program.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchReport {
+    /// The name of the program.
+    pub program: String,
+    /// The version of the prover.
+    pub version: String,
+    /// The number of cycles of the program.
+    pub cycles: u64,
+    /// The number of core shards.
+    pub shards: usize,
+    /// The time taken by each stage.
+    pub times: BenchTimes,
+    /// The peak resident memory of the process while proving the program, in bytes. Only
+    /// available on Linux.
+    pub peak_memory: Option<u64>,
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let t = &self.times;
+        write!(
+            f,
+            "{}: cycles={}, shards={}, execute={:.2}s, commit={:.2}s, open={:.2}s, \
+             compress={:.2}s, shrink={:.2}s, wrap={:.2}s, total={:.2}s",
+            self.program,
+            self.cycles,
+            self.shards,
+            t.execute,
+            t.commit,
+            t.open,
+            t.compress,
+            t.shrink,
+            t.wrap,
+            t.total(),
+        )?;
+        if let Some(peak_memory) = self.peak_memory {
+            write!(f, ", peak_memory={}MiB", peak_memory >> 20)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum BenchError {
+    #[error("execution failed: {0}")]
+    Execution(#[from] ExecutionError),
+    #[error("core proving failed: {0}")]
+    Core(#[from] SP1CoreProverError),
+    #[error("recursive proving failed: {0}")]
+    Recursion(#[from] SP1RecursionProverError),
+}
+
+impl<C: SP1ProverComponents> SP1Prover<C> {
+    /// Executes and proves `elf` on `stdin` through the core, compress, shrink and wrap stages,
+    /// reporting the time taken by each stage.
+    pub fn bench(
+        &self,
+        program: &str,
+        elf: &[u8],
+        stdin: &SP1Stdin,
+        opts: SP1ProverOpts,
+    ) -> Result<BenchReport, BenchError> {
+        reset_peak_memory();
+        let (pk, vk) = self.setup(elf);
+
+        tracing::info!("bench {}: execute", program);
+        let (result, execute) = timed(|| self.execute(elf, stdin, SP1Context::default()));
+        let (_, report) = result?;
+
+        tracing::info!("bench {}: core", program);
+        let monitor = Arc::new(ProofMonitor::new());
+        let context = SP1Context::builder().monitor(monitor.clone()).build();
+        let (core_proof, core) = timed(|| self.prove_core(&pk, stdin, opts.clone(), context));
+        let core_proof = core_proof?;
+        let shards = core_proof.proof.0.len();
+        let commit = monitor.commit_time().unwrap_or_default().min(core);
+
+        tracing::info!("bench {}: compress", program);
+        let (compressed_proof, compress) =
+            timed(|| self.compress(&vk, core_proof, vec![], opts.clone()));
+
+        tracing::info!("bench {}: shrink", program);
+        let (shrink_proof, shrink) = timed(|| self.shrink(compressed_proof?, opts.clone()));
+
+        tracing::info!("bench {}: wrap", program);
+        let (wrap_proof, wrap) = timed(|| self.wrap_bn254(shrink_proof?, opts));
+        wrap_proof?;
+
+        Ok(BenchReport {
+            program: program.to_string(),
+            version: crate::SP1_CIRCUIT_VERSION.to_string(),
+            cycles: report.total_instruction_count(),
+            shards,
+            times: BenchTimes {
+                execute: execute.as_secs_f64(),
+                commit: commit.as_secs_f64(),
+                open: (core - commit).as_secs_f64(),
+                compress: compress.as_secs_f64(),
+                shrink: shrink.as_secs_f64(),
+                wrap: wrap.as_secs_f64(),
+            },
+            peak_memory: peak_memory(),
+        })
+    }
+}
+
+/// Runs `f`, returning its result and the time it took.
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let time = Instant::now();
+    let result = f();
+    (result, time.elapsed())
+}
+
+/// Resets the peak resident memory of the process, so that [`peak_memory`] only accounts for what
+/// comes next. Does nothing on platforms other than Linux.
+fn reset_peak_memory() {
+    let _ = fs::write("/proc/self/clear_refs", "5");
+}
+
+/// The peak resident memory of the process in bytes, if it is available.
+fn peak_memory() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kib = line.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    Some(kib << 10)
+}
@@ -13,7 +13,7 @@ use sp1_stark::{SP1ProverOpts, ShardProof, StarkVerifyingKey};
 
 use crate::{
     utils::{babybear_bytes_to_bn254, babybears_to_bn254, words_to_bytes},
-    OuterSC, SP1Prover,
+    OuterSC, SP1Prover, DEFAULT_SHRINK_LOG_DEGREE,
 };
 
 /// Tries to build the PLONK artifacts inside the development directory.
@@ -48,6 +48,17 @@ pub fn groth16_bn254_artifacts_dev_dir() -> PathBuf {
     dirs::home_dir().unwrap().join(".sp1").join("circuits").join("dev")
 }
 
+/// Gets the directory of the artifacts for shrink proofs of the given log-degree, inside the
+/// directory of the artifacts for the default log-degree.
+pub fn shrink_artifacts_dir(dir: impl Into<PathBuf>, shrink_log_degree: usize) -> PathBuf {
+    let dir = dir.into();
+    if shrink_log_degree == DEFAULT_SHRINK_LOG_DEGREE {
+        dir
+    } else {
+        dir.join(format!("shrink-{shrink_log_degree}"))
+    }
+}
+
 /// Build the plonk bn254 artifacts to the given directory for the given verification key and
 /// template proof.
 pub fn build_plonk_bn254_artifacts(
@@ -119,10 +130,17 @@ pub fn build_constraints_and_witness(
 /// Generate a dummy proof that we can use to build the circuit. We need this to know the shape of
 /// the proof.
 pub fn dummy_proof() -> (StarkVerifyingKey<OuterSC>, ShardProof<OuterSC>) {
+    dummy_proof_with_shrink_log_degree(DEFAULT_SHRINK_LOG_DEGREE)
+}
+
+/// Generate a dummy proof whose shrink proof has the given log-degree.
+pub fn dummy_proof_with_shrink_log_degree(
+    shrink_log_degree: usize,
+) -> (StarkVerifyingKey<OuterSC>, ShardProof<OuterSC>) {
     let elf = include_bytes!("../elf/riscv32im-succinct-zkvm-elf");
 
     tracing::info!("initializing prover");
-    let prover: SP1Prover = SP1Prover::new();
+    let prover: SP1Prover = SP1Prover::with_shrink_log_degree(shrink_log_degree);
     let opts = SP1ProverOpts::default();
     let context = SP1Context::default();
 
@@ -132,13 +150,13 @@ pub fn dummy_proof() -> (StarkVerifyingKey<OuterSC>, ShardProof<OuterSC>) {
     tracing::info!("prove core");
     let mut stdin = SP1Stdin::new();
     stdin.write(&500u32);
-    let core_proof = prover.prove_core(&pk, &stdin, opts, context).unwrap();
+    let core_proof = prover.prove_core(&pk, &stdin, opts.clone(), context).unwrap();
 
     tracing::info!("compress");
-    let compressed_proof = prover.compress(&vk, core_proof, vec![], opts).unwrap();
+    let compressed_proof = prover.compress(&vk, core_proof, vec![], opts.clone()).unwrap();
 
     tracing::info!("shrink");
-    let shrink_proof = prover.shrink(compressed_proof, opts).unwrap();
+    let shrink_proof = prover.shrink(compressed_proof, opts.clone()).unwrap();
 
     tracing::info!("wrap");
     let wrapped_proof = prover.wrap_bn254(shrink_proof, opts).unwrap();
@@ -0,0 +1,241 @@
+//! A phase 2 trusted setup ceremony for the Groth16 wrap circuit.
+//!
+//! The Groth16 artifacts built by [`crate::build`] come from a setup whose toxic waste is known to
+//! whoever ran it. A ceremony replaces the keys with ones that are secure as long as a single
+//! contributor discarded their randomness, so that users do not have to trust shipped artifacts.
+//!
+//! A ceremony is a directory containing:
+//!
+//! - `phase1.bin`: the phase 1 (powers of tau) parameters, in the format of gnark's `mpcsetup`.
+//! - `contributions/NNNN.bin`: the phase 2 parameters after each contribution, the first one being
+//!   the initial parameters derived from phase 1 and the circuit.
+//! - `transcript.json`: the name and SHA-256 hash of every contribution, in order.
+//!
+//! Contributors run [`Groth16Ceremony::contribute`] on a copy of the ceremony, or the coordinator
+//! imports their parameters with [`Groth16Ceremony::add_contribution`]. Once all contributions are
+//! in, [`Groth16Ceremony::finalize`] applies a public random beacon and writes the keys to the
+//! build directory of the circuit.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sp1_recursion_gnark_ffi::{
+    ffi::{
+        ceremony_contribute_groth16_bn254, ceremony_init_groth16_bn254,
+        ceremony_verify_groth16_bn254,
+    },
+    Groth16Bn254Prover,
+};
+use thiserror::Error;
+
+const PHASE1_FILE: &str = "phase1.bin";
+const TRANSCRIPT_FILE: &str = "transcript.json";
+const CONTRIBUTIONS_DIR: &str = "contributions";
+
+/// A contribution to a [`Groth16Ceremony`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Contribution {
+    /// The name of the contributor.
+    pub name: String,
+    /// The SHA-256 hash of the phase 2 parameters after the contribution, hex encoded.
+    pub hash: String,
+}
+
+/// The record of the contributions to a [`Groth16Ceremony`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CeremonyTranscript {
+    /// The contributions, starting with the initial parameters.
+    pub contributions: Vec<Contribution>,
+}
+
+#[derive(Error, Debug)]
+pub enum CeremonyError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid transcript: {0}")]
+    Transcript(#[from] serde_json::Error),
+    #[error("gnark error: {0}")]
+    Gnark(String),
+    #[error("contribution {0} does not match the transcript")]
+    HashMismatch(usize),
+    #[error("initial parameters do not match the circuit and phase 1 parameters")]
+    InvalidInitialParameters,
+    #[error("contribution {index} is invalid: {error}")]
+    InvalidContribution { index: usize, error: String },
+}
+
+/// A phase 2 ceremony for the Groth16 wrap circuit, stored in a directory.
+#[derive(Debug, Clone)]
+pub struct Groth16Ceremony {
+    dir: PathBuf,
+    transcript: CeremonyTranscript,
+}
+
+impl Groth16Ceremony {
+    /// Starts a ceremony in `dir` for the Groth16 circuit built in `build_dir`, with the phase 1
+    /// parameters at `phase1`.
+    pub fn init(
+        dir: impl Into<PathBuf>,
+        build_dir: &Path,
+        phase1: &Path,
+    ) -> Result<Self, CeremonyError> {
+        let dir = dir.into();
+        fs::create_dir_all(dir.join(CONTRIBUTIONS_DIR))?;
+        fs::copy(phase1, dir.join(PHASE1_FILE))?;
+
+        let mut ceremony = Self { dir, transcript: CeremonyTranscript::default() };
+        let initial = ceremony.contribution_path(0);
+        ceremony_init_groth16_bn254(
+            path_str(build_dir),
+            path_str(&ceremony.dir.join(PHASE1_FILE)),
+            path_str(&initial),
+        )
+        .map_err(CeremonyError::Gnark)?;
+        ceremony.record("initial".to_string(), &initial)?;
+        Ok(ceremony)
+    }
+
+    /// Opens the ceremony in `dir`.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, CeremonyError> {
+        let dir = dir.into();
+        let transcript = serde_json::from_slice(&fs::read(dir.join(TRANSCRIPT_FILE))?)?;
+        Ok(Self { dir, transcript })
+    }
+
+    /// The record of the contributions.
+    pub fn transcript(&self) -> &CeremonyTranscript {
+        &self.transcript
+    }
+
+    /// The path of the phase 2 parameters after the contribution at `index`.
+    pub fn contribution_path(&self, index: usize) -> PathBuf {
+        self.dir.join(CONTRIBUTIONS_DIR).join(format!("{index:04}.bin"))
+    }
+
+    /// Contributes fresh randomness to the latest parameters as `name`.
+    pub fn contribute(&mut self, name: &str) -> Result<&Contribution, CeremonyError> {
+        let index = self.transcript.contributions.len();
+        let (prev, next) = (self.contribution_path(index - 1), self.contribution_path(index));
+        ceremony_contribute_groth16_bn254(path_str(&prev), path_str(&next))
+            .map_err(CeremonyError::Gnark)?;
+        self.record(name.to_string(), &next)
+    }
+
+    /// Adds the parameters at `path`, contributed by `name` on top of the latest parameters.
+    ///
+    /// The contribution is checked before it is added.
+    pub fn add_contribution(
+        &mut self,
+        name: &str,
+        path: &Path,
+    ) -> Result<&Contribution, CeremonyError> {
+        let index = self.transcript.contributions.len();
+        ceremony_verify_groth16_bn254(path_str(&self.contribution_path(index - 1)), path_str(path))
+            .map_err(|error| CeremonyError::InvalidContribution { index, error })?;
+        let next = self.contribution_path(index);
+        fs::copy(path, &next)?;
+        self.record(name.to_string(), &next)
+    }
+
+    /// Checks the whole ceremony for the circuit built in `build_dir`: the initial parameters,
+    /// every contribution on top of the previous one, and the hashes of the transcript.
+    pub fn verify(&self, build_dir: &Path) -> Result<(), CeremonyError> {
+        self.check_hashes()?;
+
+        // The initial parameters are derived deterministically from phase 1 and the circuit.
+        let initial = tempfile::NamedTempFile::new()?;
+        ceremony_init_groth16_bn254(
+            path_str(build_dir),
+            path_str(&self.dir.join(PHASE1_FILE)),
+            path_str(initial.path()),
+        )
+        .map_err(CeremonyError::Gnark)?;
+        if hash_file(initial.path())? != self.transcript.contributions[0].hash {
+            return Err(CeremonyError::InvalidInitialParameters);
+        }
+
+        for index in 1..self.transcript.contributions.len() {
+            ceremony_verify_groth16_bn254(
+                path_str(&self.contribution_path(index - 1)),
+                path_str(&self.contribution_path(index)),
+            )
+            .map_err(|error| CeremonyError::InvalidContribution { index, error })?;
+        }
+        Ok(())
+    }
+
+    /// Verifies the ceremony, applies the random beacon hashed `2^iterations` times as a last
+    /// contribution, and replaces the keys of the circuit in `build_dir` with the resulting ones.
+    pub fn finalize(
+        &self,
+        build_dir: &Path,
+        beacon: &[u8],
+        iterations: u32,
+    ) -> Result<(), CeremonyError> {
+        self.verify(build_dir)?;
+        let last = self.contribution_path(self.transcript.contributions.len() - 1);
+        Groth16Bn254Prover::finalize_ceremony(
+            build_dir,
+            &self.dir.join(PHASE1_FILE),
+            &last,
+            beacon,
+            iterations,
+        )
+        .map_err(CeremonyError::Gnark)
+    }
+
+    /// Checks that the contributions on disk match the hashes of the transcript.
+    fn check_hashes(&self) -> Result<(), CeremonyError> {
+        for (index, contribution) in self.transcript.contributions.iter().enumerate() {
+            if hash_file(&self.contribution_path(index))? != contribution.hash {
+                return Err(CeremonyError::HashMismatch(index));
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the contribution at `path` to the transcript and saves it.
+    fn record(&mut self, name: String, path: &Path) -> Result<&Contribution, CeremonyError> {
+        let hash = hash_file(path)?;
+        self.transcript.contributions.push(Contribution { name, hash });
+        fs::write(self.dir.join(TRANSCRIPT_FILE), serde_json::to_vec_pretty(&self.transcript)?)?;
+        Ok(self.transcript.contributions.last().unwrap())
+    }
+}
+
+fn hash_file(path: &Path) -> Result<String, CeremonyError> {
+    Ok(hex::encode(Sha256::digest(fs::read(path)?)))
+}
+
+fn path_str(path: &Path) -> &str {
+    path.to_str().expect("ceremony paths must be valid UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ceremony_transcript() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(CONTRIBUTIONS_DIR)).unwrap();
+        let mut ceremony =
+            Groth16Ceremony { dir: dir.path().to_path_buf(), transcript: Default::default() };
+        for (index, name) in ["initial", "alice", "bob"].into_iter().enumerate() {
+            let path = ceremony.contribution_path(index);
+            fs::write(&path, name.as_bytes()).unwrap();
+            ceremony.record(name.to_string(), &path).unwrap();
+        }
+
+        let opened = Groth16Ceremony::open(dir.path()).unwrap();
+        assert_eq!(opened.transcript(), ceremony.transcript());
+        opened.check_hashes().unwrap();
+
+        fs::write(ceremony.contribution_path(1), b"mallory").unwrap();
+        assert!(matches!(opened.check_hashes(), Err(CeremonyError::HashMismatch(1))));
+    }
+}
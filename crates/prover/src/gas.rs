@@ -0,0 +1,287 @@
+//! Estimates of the cost of verifying a wrapped proof on the EVM.
+//!
+//! The onchain verifiers exported by gnark spend most of their gas in the BN254 precompiles and in
+//! the calldata of the transaction. The estimate counts the precompile calls each verifier makes
+//! for a given proof, prices them and the calldata with the current gas schedule, and adds a rough
+//! allowance for the rest of the verifier's execution. It is meant to compare deployment options
+//! and to catch regressions, and the gas measured onchain may differ by a few percent.
+
+use std::fmt;
+
+use crate::{ProofSystem, SP1Bn254ProofData};
+
+/// The selector of `verifyProof(bytes32 programVKey, bytes publicValues, bytes proofBytes)`, the
+/// entry point of the onchain verifiers.
+pub const VERIFY_PROOF_SELECTOR: [u8; 4] = [0x41, 0x49, 0x3c, 0x60];
+
+/// The gas paid by every transaction.
+pub const TX_BASE_GAS: u64 = 21_000;
+
+/// The calldata gas of a zero byte (EIP-2028).
+pub const CALLDATA_ZERO_BYTE_GAS: u64 = 4;
+
+/// The calldata gas of a non-zero byte (EIP-2028).
+pub const CALLDATA_NONZERO_BYTE_GAS: u64 = 16;
+
+/// The gas of a BN254 point addition (EIP-1108).
+pub const EC_ADD_GAS: u64 = 150;
+
+/// The gas of a BN254 scalar multiplication (EIP-1108).
+pub const EC_MUL_GAS: u64 = 6_000;
+
+/// The gas of a BN254 pairing check is `PAIRING_BASE_GAS + k * PAIRING_PER_POINT_GAS` for `k`
+/// point pairs (EIP-1108).
+pub const PAIRING_BASE_GAS: u64 = 45_000;
+
+/// See [`PAIRING_BASE_GAS`].
+pub const PAIRING_PER_POINT_GAS: u64 = 34_000;
+
+/// The gas of a modular exponentiation with a 32-byte base, exponent and modulus (EIP-2565), as
+/// used by the verifiers to invert field elements: a multiplication complexity of `(32 / 8)^2`
+/// times 253 iterations for an exponent of `r - 2`, divided by 3.
+pub const MODEXP_GAS: u64 = 16 * 253 / 3;
+
+/// The number of public inputs of a wrapped proof: the verifying key hash and the public values
+/// digest.
+const NUM_PUBLIC_INPUTS: usize = 2;
+
+/// The size of an encoded BN254 G1 point.
+const G1_SIZE: usize = 64;
+
+/// The size of an encoded Groth16 proof without commitments: the points `A`, `B` and `C`.
+const GROTH16_PROOF_SIZE: usize = 4 * G1_SIZE;
+
+/// The gas of the Groth16 verifier besides the precompiles.
+const GROTH16_EXECUTION_GAS: u64 = 25_000;
+
+/// The gas of the PLONK verifier besides the precompiles, mostly spent on the Fiat-Shamir
+/// transcript and on the evaluation of the gate constraints.
+const PLONK_EXECUTION_GAS: u64 = 95_000;
+
+/// The number of calls to each precompile made by an onchain verifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrecompileCalls {
+    /// The number of point pairs of the pairing checks.
+    pub pairings: usize,
+    /// The number of calls to the pairing precompile.
+    pub pairing_checks: usize,
+    pub ec_muls: usize,
+    pub ec_adds: usize,
+    pub modexps: usize,
+}
+
+impl PrecompileCalls {
+    /// The calls of the Groth16 verifier for an encoded proof of `proof_len` bytes.
+    ///
+    /// Each commitment of the proof adds a public input, and the proof of knowledge of the
+    /// commitments is checked with two more pairings.
+    pub fn groth16(proof_len: usize) -> Self {
+        let num_commitments = proof_len.saturating_sub(GROTH16_PROOF_SIZE + G1_SIZE) / G1_SIZE;
+        let num_public_inputs = NUM_PUBLIC_INPUTS + num_commitments;
+        Self {
+            pairings: if num_commitments > 0 { 6 } else { 4 },
+            pairing_checks: if num_commitments > 0 { 2 } else { 1 },
+            ec_muls: num_public_inputs,
+            ec_adds: num_public_inputs + num_commitments,
+            modexps: 0,
+        }
+    }
+
+    /// The calls of the PLONK verifier, which do not depend on the proof.
+    ///
+    /// The KZG openings at `zeta` and `zeta * omega` are batched into a single check of two
+    /// pairings. The linearized polynomial and the folded openings are computed with one scalar
+    /// multiplication and one addition per commitment.
+    pub fn plonk() -> Self {
+        Self { pairings: 2, pairing_checks: 1, ec_muls: 18, ec_adds: 18, modexps: 3 }
+    }
+
+    /// The gas of the calls.
+    pub fn gas(&self) -> u64 {
+        self.pairing_checks as u64 * PAIRING_BASE_GAS
+            + self.pairings as u64 * PAIRING_PER_POINT_GAS
+            + self.ec_muls as u64 * EC_MUL_GAS
+            + self.ec_adds as u64 * EC_ADD_GAS
+            + self.modexps as u64 * MODEXP_GAS
+    }
+}
+
+/// The estimated cost of verifying a wrapped proof on the EVM.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasReport {
+    pub proof_system: ProofSystem,
+    /// The size of the calldata of the `verifyProof` call.
+    pub calldata_size: usize,
+    pub calldata_gas: u64,
+    pub precompile_calls: PrecompileCalls,
+    pub precompile_gas: u64,
+    /// The gas of the verifier besides the precompiles.
+    pub execution_gas: u64,
+}
+
+impl GasReport {
+    /// The gas of a transaction that only verifies the proof.
+    pub fn total_gas(&self) -> u64 {
+        TX_BASE_GAS + self.calldata_gas + self.precompile_gas + self.execution_gas
+    }
+}
+
+impl fmt::Display for GasReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let calls = &self.precompile_calls;
+        writeln!(f, "{:?} verification gas estimate", self.proof_system)?;
+        writeln!(f, "  transaction base:  {:>9}", TX_BASE_GAS)?;
+        writeln!(
+            f,
+            "  calldata:          {:>9} ({} bytes)",
+            self.calldata_gas, self.calldata_size
+        )?;
+        writeln!(
+            f,
+            "  precompiles:       {:>9} ({} pairings, {} ecMul, {} ecAdd, {} modexp)",
+            self.precompile_gas, calls.pairings, calls.ec_muls, calls.ec_adds, calls.modexps
+        )?;
+        writeln!(f, "  verifier execution:{:>9}", self.execution_gas)?;
+        write!(f, "  total:             {:>9}", self.total_gas())
+    }
+}
+
+/// The proof in the byte encoding the onchain verifiers accept: the first four bytes of the hash
+/// of the gnark verifying key, followed by the encoded proof.
+pub fn onchain_proof_bytes(proof: &SP1Bn254ProofData) -> Vec<u8> {
+    let (vkey_hash, encoded_proof) = match proof {
+        SP1Bn254ProofData::Plonk(proof) => (&proof.plonk_vkey_hash, &proof.encoded_proof),
+        SP1Bn254ProofData::Groth16(proof) => (&proof.groth16_vkey_hash, &proof.encoded_proof),
+    };
+    let mut bytes = vkey_hash[..4].to_vec();
+    bytes.extend(hex::decode(encoded_proof).expect("invalid encoded proof"));
+    bytes
+}
+
+/// Appends `bytes` to `out` as a dynamic ABI value: its length, then its content padded to a
+/// multiple of 32 bytes.
+fn abi_encode_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend(abi_encode_uint(bytes.len()));
+    out.extend_from_slice(bytes);
+    out.resize(out.len() + padding(bytes.len()), 0);
+}
+
+fn abi_encode_uint(value: usize) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&(value as u64).to_be_bytes());
+    word
+}
+
+fn padding(len: usize) -> usize {
+    (32 - len % 32) % 32
+}
+
+/// The calldata of `verifyProof(programVKey, publicValues, proofBytes)`.
+///
+/// `proof_bytes` is the proof in the byte encoding of [`onchain_proof_bytes`], and `program_vkey`
+/// the `bytes32` hash of the program's verifying key.
+pub fn abi_encode_proof(
+    program_vkey: [u8; 32],
+    public_values: &[u8],
+    proof_bytes: &[u8],
+) -> Vec<u8> {
+    // The head holds the verifying key and the offsets of the two dynamic arguments.
+    let head_size = 3 * 32;
+    let proof_offset = head_size + 32 + public_values.len() + padding(public_values.len());
+
+    let mut calldata = VERIFY_PROOF_SELECTOR.to_vec();
+    calldata.extend(program_vkey);
+    calldata.extend(abi_encode_uint(head_size));
+    calldata.extend(abi_encode_uint(proof_offset));
+    abi_encode_bytes(&mut calldata, public_values);
+    abi_encode_bytes(&mut calldata, proof_bytes);
+    calldata
+}
+
+/// The calldata gas of `calldata`.
+pub fn calldata_gas(calldata: &[u8]) -> u64 {
+    calldata
+        .iter()
+        .map(|&b| if b == 0 { CALLDATA_ZERO_BYTE_GAS } else { CALLDATA_NONZERO_BYTE_GAS })
+        .sum()
+}
+
+/// Estimates the gas of verifying `proof` on the EVM, in a transaction that calls `verifyProof`
+/// with the given program verifying key and public values.
+pub fn estimate_verification_gas(
+    proof: &SP1Bn254ProofData,
+    program_vkey: [u8; 32],
+    public_values: &[u8],
+) -> GasReport {
+    let proof_bytes = onchain_proof_bytes(proof);
+    let calldata = abi_encode_proof(program_vkey, public_values, &proof_bytes);
+
+    let proof_system = proof.get_proof_system();
+    let (precompile_calls, execution_gas) = match proof_system {
+        // The encoded proof follows the 4-byte verifying key hash prefix.
+        ProofSystem::Groth16 => {
+            (PrecompileCalls::groth16(proof_bytes.len() - 4), GROTH16_EXECUTION_GAS)
+        }
+        ProofSystem::Plonk => (PrecompileCalls::plonk(), PLONK_EXECUTION_GAS),
+    };
+
+    GasReport {
+        proof_system,
+        calldata_size: calldata.len(),
+        calldata_gas: calldata_gas(&calldata),
+        precompile_calls,
+        precompile_gas: precompile_calls.gas(),
+        execution_gas,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_recursion_gnark_ffi::proof::Groth16Bn254Proof;
+
+    use super::*;
+
+    #[test]
+    fn test_abi_encode_proof() {
+        let vkey = [7u8; 32];
+        let public_values = [1u8; 40];
+        let proof_bytes = [2u8; 260];
+        let calldata = abi_encode_proof(vkey, &public_values, &proof_bytes);
+
+        assert_eq!(calldata[..4], VERIFY_PROOF_SELECTOR);
+        let word = |i: usize| &calldata[4 + 32 * i..4 + 32 * (i + 1)];
+        assert_eq!(word(0), vkey);
+        assert_eq!(word(1), abi_encode_uint(0x60));
+        // The public values take a length word and two padded words.
+        assert_eq!(word(2), abi_encode_uint(0x60 + 32 + 64));
+        assert_eq!(word(3), abi_encode_uint(40));
+        assert_eq!(calldata[4 + 128..4 + 168], public_values);
+        assert!(calldata[4 + 168..4 + 192].iter().all(|&b| b == 0));
+        assert_eq!(word(6), abi_encode_uint(260));
+        assert_eq!(calldata.len(), 4 + 7 * 32 + 288);
+    }
+
+    #[test]
+    fn test_estimate_groth16_verification_gas() {
+        let proof = SP1Bn254ProofData::Groth16(Groth16Bn254Proof {
+            encoded_proof: hex::encode([0xffu8; GROTH16_PROOF_SIZE]),
+            groth16_vkey_hash: [0xaa; 32],
+            ..Default::default()
+        });
+        let report = estimate_verification_gas(&proof, [1; 32], &[]);
+
+        assert_eq!(report.precompile_calls, PrecompileCalls::groth16(GROTH16_PROOF_SIZE));
+        assert_eq!(report.precompile_calls.pairings, 4);
+        // Four pairings and one scalar multiplication per public input.
+        assert_eq!(
+            report.precompile_gas,
+            PAIRING_BASE_GAS + 4 * PAIRING_PER_POINT_GAS + 2 * EC_MUL_GAS + 2 * EC_ADD_GAS
+        );
+        assert_eq!(report.calldata_size, 4 + 5 * 32 + 4 + GROTH16_PROOF_SIZE + 28);
+        assert_eq!(
+            report.calldata_gas,
+            calldata_gas(&abi_encode_proof([1; 32], &[], &onchain_proof_bytes(&proof)))
+        );
+        assert!(report.total_gas() > 200_000 && report.total_gas() < 400_000);
+    }
+}
@@ -1,4 +1,9 @@
-use crate::components::SP1ProverComponents;
+use std::{io, path::PathBuf};
+
+use crate::{
+    components::SP1ProverComponents,
+    program_cache::{ProgramKey, ProgramKeyBuilder, RecursionProgramCache},
+};
 use p3_baby_bear::BabyBear;
 pub use sp1_core_machine::io::SP1Stdin;
 pub use sp1_primitives::io::SP1PublicValues;
@@ -24,8 +29,10 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
     /// The program that can recursively verify a set of proofs into a single proof.
     pub fn recursion_program(&self) -> &RecursionProgram<BabyBear> {
         self.recursion_program.get_or_init(|| {
-            debug_span!("init recursion program").in_scope(|| {
-                SP1RecursiveVerifier::<InnerConfig, _>::build(self.core_prover.machine())
+            self.cached_program("recursion", Self::recursion_program_key, || {
+                debug_span!("init recursion program").in_scope(|| {
+                    SP1RecursiveVerifier::<InnerConfig, _>::build(self.core_prover.machine())
+                })
             })
         })
     }
@@ -33,8 +40,10 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
     /// The program that recursively verifies deferred proofs and accumulates the digests.
     pub fn deferred_program(&self) -> &RecursionProgram<BabyBear> {
         self.deferred_program.get_or_init(|| {
-            debug_span!("init deferred program").in_scope(|| {
-                SP1DeferredVerifier::<InnerConfig, _, _>::build(self.compress_prover.machine())
+            self.cached_program("deferred", Self::deferred_program_key, || {
+                debug_span!("init deferred program").in_scope(|| {
+                    SP1DeferredVerifier::<InnerConfig, _, _>::build(self.compress_prover.machine())
+                })
             })
         })
     }
@@ -42,12 +51,14 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
     /// The program that reduces a set of recursive proofs into a single proof.
     pub fn compress_program(&self) -> &RecursionProgram<BabyBear> {
         self.compress_program.get_or_init(|| {
-            debug_span!("init compress program").in_scope(|| {
-                SP1CompressVerifier::<InnerConfig, _, _>::build(
-                    self.compress_prover.machine(),
-                    self.recursion_vk(),
-                    self.deferred_vk(),
-                )
+            self.cached_program("compress", Self::compress_program_key, || {
+                debug_span!("init compress program").in_scope(|| {
+                    SP1CompressVerifier::<InnerConfig, _, _>::build(
+                        self.compress_prover.machine(),
+                        self.recursion_vk(),
+                        self.deferred_vk(),
+                    )
+                })
             })
         })
     }
@@ -55,12 +66,14 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
     /// The shrink program that compresses a proof into a succinct proof.
     pub fn shrink_program(&self) -> &RecursionProgram<BabyBear> {
         self.shrink_program.get_or_init(|| {
-            debug_span!("init shrink program").in_scope(|| {
-                SP1RootVerifier::<InnerConfig, _, _>::build(
-                    self.compress_prover.machine(),
-                    self.compress_vk(),
-                    RecursionProgramType::Shrink,
-                )
+            self.cached_program("shrink", Self::shrink_program_key, || {
+                debug_span!("init shrink program").in_scope(|| {
+                    SP1RootVerifier::<InnerConfig, _, _>::build(
+                        self.compress_prover.machine(),
+                        self.compress_vk(),
+                        RecursionProgramType::Shrink,
+                    )
+                })
             })
         })
     }
@@ -68,12 +81,14 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
     /// The wrap program that wraps a proof into a SNARK-friendly field.
     pub fn wrap_program(&self) -> &RecursionProgram<BabyBear> {
         self.wrap_program.get_or_init(|| {
-            debug_span!("init wrap program").in_scope(|| {
-                SP1RootVerifier::<InnerConfig, _, _>::build(
-                    self.shrink_prover.machine(),
-                    self.shrink_vk(),
-                    RecursionProgramType::Wrap,
-                )
+            self.cached_program("wrap", Self::wrap_program_key, || {
+                debug_span!("init wrap program").in_scope(|| {
+                    SP1RootVerifier::<InnerConfig, _, _>::build(
+                        self.shrink_prover.machine(),
+                        self.shrink_vk(),
+                        RecursionProgramType::Wrap,
+                    )
+                })
             })
         })
     }
@@ -166,4 +181,64 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
     pub fn wrap_vk(&self) -> &StarkVerifyingKey<OuterSC> {
         &self.wrap_keys().1
     }
+
+    /// The key of the recursion program in the program cache.
+    pub fn recursion_program_key(&self) -> ProgramKey {
+        ProgramKeyBuilder::new().machine(self.core_prover.machine()).finish()
+    }
+
+    /// The key of the deferred program in the program cache.
+    pub fn deferred_program_key(&self) -> ProgramKey {
+        ProgramKeyBuilder::new().machine(self.compress_prover.machine()).finish()
+    }
+
+    /// The key of the compress program in the program cache.
+    pub fn compress_program_key(&self) -> ProgramKey {
+        ProgramKeyBuilder::new()
+            .machine(self.compress_prover.machine())
+            .vk(self.recursion_vk())
+            .vk(self.deferred_vk())
+            .finish()
+    }
+
+    /// The key of the shrink program in the program cache.
+    pub fn shrink_program_key(&self) -> ProgramKey {
+        ProgramKeyBuilder::new()
+            .machine(self.compress_prover.machine())
+            .vk(self.compress_vk())
+            .finish()
+    }
+
+    /// The key of the wrap program in the program cache.
+    pub fn wrap_program_key(&self) -> ProgramKey {
+        ProgramKeyBuilder::new().machine(self.shrink_prover.machine()).vk(self.shrink_vk()).finish()
+    }
+
+    /// Compiles every recursion program and writes it to the given cache, returning the paths of
+    /// the written programs.
+    pub fn prebuild_programs(&self, cache: &RecursionProgramCache) -> io::Result<Vec<PathBuf>> {
+        Ok(vec![
+            cache.store("recursion", &self.recursion_program_key(), self.recursion_program())?,
+            cache.store("deferred", &self.deferred_program_key(), self.deferred_program())?,
+            cache.store("compress", &self.compress_program_key(), self.compress_program())?,
+            cache.store("shrink", &self.shrink_program_key(), self.shrink_program())?,
+            cache.store("wrap", &self.wrap_program_key(), self.wrap_program())?,
+        ])
+    }
+
+    /// Loads a program from the program cache, or builds it if it has not been prebuilt.
+    fn cached_program(
+        &self,
+        name: &str,
+        key: fn(&Self) -> ProgramKey,
+        build: impl FnOnce() -> RecursionProgram<BabyBear>,
+    ) -> RecursionProgram<BabyBear> {
+        if let Some(cache) = &self.program_cache {
+            if let Some(program) = cache.load(name, &key(self)) {
+                tracing::debug!("loaded {} program from {}", name, cache.dir().display());
+                return program;
+            }
+        }
+        build()
+    }
 }
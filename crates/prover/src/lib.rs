@@ -11,27 +11,46 @@
 #![allow(clippy::new_without_default)]
 #![allow(clippy::collapsible_else_if)]
 
+pub mod artifact_store;
+#[cfg(feature = "bench")]
+pub mod bench;
 pub mod build;
+pub mod ceremony;
 pub mod components;
+pub mod gas;
 pub mod init;
+pub mod program_cache;
+pub mod reduce;
+pub mod selftest;
 pub mod types;
 pub mod utils;
 pub mod verify;
 
 use std::{
     borrow::Borrow,
+    collections::HashMap,
     path::Path,
-    sync::{mpsc::sync_channel, Arc, Mutex, OnceLock},
+    sync::{
+        mpsc::{sync_channel, RecvTimeoutError},
+        Arc, Mutex, OnceLock,
+    },
     thread,
+    time::Duration,
 };
 
-use crate::init::SP1PublicValues;
+use crate::{
+    init::SP1PublicValues,
+    reduce::{ReduceNode, ReduceTree},
+};
 use components::{DefaultProverComponents, SP1ProverComponents};
 use p3_baby_bear::BabyBear;
 use p3_challenger::CanObserve;
 use p3_field::{AbstractField, PrimeField};
 use p3_matrix::dense::RowMajorMatrix;
-use sp1_core_executor::{ExecutionError, ExecutionReport, Executor, Program, SP1Context};
+use program_cache::RecursionProgramCache;
+use sp1_core_executor::{
+    ExecutionError, ExecutionReport, Executor, Program, ProofMonitor, SP1Context,
+};
 pub use sp1_core_machine::io::SP1Stdin;
 use sp1_core_machine::{
     riscv::RiscvAir,
@@ -54,8 +73,8 @@ pub use sp1_recursion_program::machine::{
 };
 use sp1_stark::{
     air::PublicValues, baby_bear_poseidon2::BabyBearPoseidon2, Challenge, Challenger,
-    MachineProver, MachineVerificationError, SP1CoreOpts, SP1ProverOpts, ShardProof,
-    StarkGenericConfig, StarkProvingKey, StarkVerifyingKey, Val, Word, DIGEST_SIZE,
+    MachineProver, MachineVerificationError, ReduceStrategy, SP1CoreOpts, SP1ProverOpts,
+    ShardProof, StarkGenericConfig, StarkProvingKey, StarkVerifyingKey, Val, Word, DIGEST_SIZE,
 };
 
 use tracing::instrument;
@@ -77,6 +96,19 @@ const COMPRESS_DEGREE: usize = 3;
 const SHRINK_DEGREE: usize = 9;
 const WRAP_DEGREE: usize = 17;
 
+/// The log-degrees the shrink proof can be extended to, see
+/// [SP1Prover::uninitialized_with_shrink_log_degree].
+///
+/// The constraints of the shrink machine have degree [SHRINK_DEGREE], so the extension must be at
+/// least `2^3` times the size of the traces.
+pub const SHRINK_LOG_DEGREES: [usize; 3] = [3, 4, 5];
+
+/// The default log-degree of the shrink proof.
+pub const DEFAULT_SHRINK_LOG_DEGREE: usize = 3;
+
+/// How often the reduction tree checks whether it has been cancelled while waiting for proofs.
+const CANCELLATION_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 pub type CompressAir<F> = RecursionAir<F, COMPRESS_DEGREE>;
 pub type ShrinkAir<F> = RecursionAir<F, SHRINK_DEGREE>;
 pub type WrapAir<F> = RecursionAir<F, WRAP_DEGREE>;
@@ -113,6 +145,9 @@ pub struct SP1Prover<C: SP1ProverComponents = DefaultProverComponents> {
     /// The proving key and verifying key for the wrap step.
     pub wrap_keys: OnceLock<(StarkProvingKey<OuterSC>, StarkVerifyingKey<OuterSC>)>,
 
+    /// The cache of ahead-of-time compiled recursion programs, if `SP1_PROGRAM_CACHE_DIR` is set.
+    pub program_cache: Option<RecursionProgramCache>,
+
     /// The machine used for proving the core step.
     pub core_prover: C::CoreProver,
 
@@ -122,15 +157,24 @@ pub struct SP1Prover<C: SP1ProverComponents = DefaultProverComponents> {
     /// The machine used for proving the shrink step.
     pub shrink_prover: C::ShrinkProver,
 
+    /// The log-degree of the low-degree extension of the shrink proof.
+    pub shrink_log_degree: usize,
+
     /// The machine used for proving the wrapping step.
     pub wrap_prover: C::WrapProver,
 }
 
 impl<C: SP1ProverComponents> SP1Prover<C> {
     /// Initializes a new [SP1Prover].
-    #[instrument(name = "initialize prover", level = "debug", skip_all)]
     pub fn new() -> Self {
-        let prover = Self::uninitialized();
+        Self::with_shrink_log_degree(DEFAULT_SHRINK_LOG_DEGREE)
+    }
+
+    /// Creates a new [SP1Prover] whose shrink proofs have the given log-degree, see
+    /// [Self::uninitialized_with_shrink_log_degree].
+    #[instrument(name = "initialize prover", level = "debug", skip_all)]
+    pub fn with_shrink_log_degree(shrink_log_degree: usize) -> Self {
+        let prover = Self::uninitialized_with_shrink_log_degree(shrink_log_degree);
         // Initialize everything except wrap key which is a bit slow.
         prover.recursion_program();
         prover.deferred_program();
@@ -146,6 +190,25 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
 
     /// Creates a new [SP1Prover] with lazily initialized components.
     pub fn uninitialized() -> Self {
+        Self::uninitialized_with_shrink_log_degree(DEFAULT_SHRINK_LOG_DEGREE)
+    }
+
+    /// Creates a new [SP1Prover] with lazily initialized components, whose shrink proofs are
+    /// extended to `2^shrink_log_degree` times the size of their traces.
+    ///
+    /// A larger log-degree makes the shrink proof slower to produce, but it needs fewer FRI
+    /// queries, so the wrap program that verifies it is smaller and cheaper to prove. The wrap
+    /// verifying key, and therefore the PLONK and Groth16 artifacts, depend on the log-degree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the log-degree is not one of [SHRINK_LOG_DEGREES].
+    pub fn uninitialized_with_shrink_log_degree(shrink_log_degree: usize) -> Self {
+        assert!(
+            SHRINK_LOG_DEGREES.contains(&shrink_log_degree),
+            "unsupported shrink log-degree: {shrink_log_degree}"
+        );
+
         // Initialize the provers.
         let core_machine = RiscvAir::machine(CoreSC::default());
         let core_prover = C::CoreProver::new(core_machine);
@@ -153,7 +216,8 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
         let compress_machine = CompressAir::machine(InnerSC::default());
         let compress_prover = C::CompressProver::new(compress_machine);
 
-        let shrink_machine = ShrinkAir::wrap_machine_dyn(InnerSC::compressed());
+        let shrink_machine =
+            ShrinkAir::wrap_machine_dyn(InnerSC::compressed_with_log_blowup(shrink_log_degree));
         let shrink_prover = C::ShrinkProver::new(shrink_machine);
 
         let wrap_machine = WrapAir::wrap_machine(OuterSC::default());
@@ -170,9 +234,11 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
             shrink_keys: OnceLock::new(),
             wrap_program: OnceLock::new(),
             wrap_keys: OnceLock::new(),
+            program_cache: RecursionProgramCache::from_env(),
             core_prover,
             compress_prover,
             shrink_prover,
+            shrink_log_degree,
             wrap_prover,
         }
     }
@@ -215,6 +281,11 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
         let opts = SP1CoreOpts::default();
         let mut runtime = Executor::with_context(program, opts, context);
         runtime.write_vecs(&stdin.buffer);
+        runtime.write_paged_inputs(&stdin.paged_inputs);
+        runtime.write_files(&stdin.files);
+        runtime.write_public_values_domain(stdin.public_values_domain);
+        runtime.write_env_vars(&stdin.env_vars);
+        runtime.write_args(&stdin.args);
         for (proof, vkey) in stdin.proofs.iter() {
             runtime.write_proof(proof.clone(), vkey.clone());
         }
@@ -364,7 +435,6 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
     }
 
     /// Reduce shards proofs to a single shard proof using the recursion prover.
-    #[instrument(name = "compress", level = "info", skip_all)]
     pub fn compress(
         &self,
         vk: &SP1VerifyingKey,
@@ -372,6 +442,22 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
         deferred_proofs: Vec<ShardProof<InnerSC>>,
         opts: SP1ProverOpts,
     ) -> Result<SP1ReduceProof<InnerSC>, SP1RecursionProverError> {
+        self.compress_with_monitor(vk, proof, deferred_proofs, opts, None)
+    }
+
+    /// Reduce shards proofs to a single shard proof using the recursion prover, stopping between
+    /// the inputs of the reduction tree once `monitor` is cancelled.
+    #[instrument(name = "compress", level = "info", skip_all)]
+    pub fn compress_with_monitor(
+        &self,
+        vk: &SP1VerifyingKey,
+        proof: SP1CoreProof,
+        deferred_proofs: Vec<ShardProof<InnerSC>>,
+        opts: SP1ProverOpts,
+        monitor: Option<&ProofMonitor>,
+    ) -> Result<SP1ReduceProof<InnerSC>, SP1RecursionProverError> {
+        let is_cancelled = move || monitor.is_some_and(ProofMonitor::is_cancelled);
+
         // Set the batch size for the reduction tree.
         let batch_size = 2;
         let shard_proofs = &proof.proof.0;
@@ -393,14 +479,13 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
             batch_size,
         );
 
-        // Calculate the expected height of the tree.
-        let mut expected_height = 1;
+        // Proofs are reduced as they finish with the latency-optimized strategy, so the stages
+        // below do not wait for each other's turn to pass them on.
         let num_first_layer_inputs = first_layer_inputs.len();
-        let mut num_layer_inputs = num_first_layer_inputs;
-        while num_layer_inputs > batch_size {
-            num_layer_inputs = (num_layer_inputs + 1) / 2;
-            expected_height += 1;
-        }
+        let ordered = opts.reduce_strategy != ReduceStrategy::LatencyOptimized;
+
+        // The worker threads below share the options.
+        let opts = &opts;
 
         // Generate the proofs.
         let span = tracing::Span::current().clone();
@@ -409,7 +494,7 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
 
             // Spawn a worker that sends the first layer inputs to a bounded channel.
             let input_sync = Arc::new(TurnBasedSync::new());
-            let (input_tx, input_rx) = sync_channel::<(usize, usize, SP1CompressMemoryLayouts)>(
+            let (input_tx, input_rx) = sync_channel::<(usize, SP1CompressMemoryLayouts)>(
                 opts.recursion_opts.checkpoints_channel_capacity,
             );
             let input_tx = Arc::new(Mutex::new(input_tx));
@@ -419,7 +504,11 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
                 s.spawn(move || {
                     for (index, input) in first_layer_inputs.into_iter().enumerate() {
                         input_sync.wait_for_turn(index);
-                        input_tx.lock().unwrap().send((index, 0, input)).unwrap();
+                        // Checking for cancellation during our turn guarantees that the inputs
+                        // sent are always a prefix of the inputs of the tree.
+                        if !is_cancelled() {
+                            input_tx.lock().unwrap().send((index, input)).unwrap();
+                        }
                         input_sync.advance_turn();
                     }
                 });
@@ -429,7 +518,6 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
             let record_and_trace_sync = Arc::new(TurnBasedSync::new());
             let (record_and_trace_tx, record_and_trace_rx) =
                 sync_channel::<(
-                    usize,
                     usize,
                     ExecutionRecord<BabyBear>,
                     Vec<(String, RowMajorMatrix<BabyBear>)>,
@@ -447,7 +535,7 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
                     let _span = span.enter();
                     loop {
                         let received = { input_rx.lock().unwrap().recv() };
-                        if let Ok((index, height, input)) = received {
+                        if let Ok((index, input)) = received {
                             // Get the program and witness stream.
                             let (program, witness_stream, program_type) = tracing::debug_span!(
                                 "write witness stream"
@@ -483,7 +571,12 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
                             });
 
                             // Execute the runtime.
-                            let record = tracing::debug_span!("execute runtime").in_scope(|| {
+                            let span = tracing::debug_span!(
+                                "execute runtime",
+                                step = program_type.step(),
+                                index
+                            );
+                            let record = span.in_scope(|| {
                                 let mut runtime =
                                     RecursionRuntime::<Val<InnerSC>, Challenge<InnerSC>, _>::new(
                                         program,
@@ -513,17 +606,21 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
                                 .in_scope(|| self.compress_prover.generate_traces(&record));
 
                             // Wait for our turn to update the state.
-                            record_and_trace_sync.wait_for_turn(index);
+                            if ordered {
+                                record_and_trace_sync.wait_for_turn(index);
+                            }
 
                             // Send the record and traces to the worker.
                             record_and_trace_tx
                                 .lock()
                                 .unwrap()
-                                .send((index, height, record, traces, program_type))
+                                .send((index, record, traces, program_type))
                                 .unwrap();
 
                             // Advance the turn.
-                            record_and_trace_sync.advance_turn();
+                            if ordered {
+                                record_and_trace_sync.advance_turn();
+                            }
                         } else {
                             break;
                         }
@@ -534,7 +631,7 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
             // Spawn workers who generate the compress proofs.
             let proofs_sync = Arc::new(TurnBasedSync::new());
             let (proofs_tx, proofs_rx) =
-                sync_channel::<(usize, usize, ShardProof<BabyBearPoseidon2>, ReduceProgramType)>(
+                sync_channel::<(usize, ShardProof<BabyBearPoseidon2>, ReduceProgramType)>(
                     num_first_layer_inputs * 2,
                 );
             let proofs_tx = Arc::new(Mutex::new(proofs_tx));
@@ -549,8 +646,10 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
                     let _span = span.enter();
                     loop {
                         let received = { record_and_trace_rx.lock().unwrap().recv() };
-                        if let Ok((index, height, record, traces, program_type)) = received {
-                            tracing::debug_span!("batch").in_scope(|| {
+                        if let Ok((index, record, traces, program_type)) = received {
+                            let span =
+                                tracing::debug_span!("batch", step = program_type.step(), index);
+                            span.in_scope(|| {
                                 // Get the proving key.
                                 let pk = if program_type == ReduceProgramType::Core {
                                     self.recursion_pk()
@@ -584,17 +683,21 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
                                 });
 
                                 // Wait for our turn to update the state.
-                                prover_sync.wait_for_turn(index);
+                                if ordered {
+                                    prover_sync.wait_for_turn(index);
+                                }
 
                                 // Send the proof.
                                 proofs_tx
                                     .lock()
                                     .unwrap()
-                                    .send((index, height, proof, program_type))
+                                    .send((index, proof, program_type))
                                     .unwrap();
 
                                 // Advance the turn.
-                                prover_sync.advance_turn();
+                                if ordered {
+                                    prover_sync.advance_turn();
+                                }
                             });
                         } else {
                             break;
@@ -612,70 +715,52 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
                 s.spawn(move || {
                     let _span = span.enter();
                     let mut count = num_first_layer_inputs;
-                    let mut batch: Vec<(
-                        usize,
-                        usize,
-                        ShardProof<BabyBearPoseidon2>,
-                        ReduceProgramType,
-                    )> = Vec::new();
+                    let mut tree = ReduceTree::new(opts.reduce_strategy, num_first_layer_inputs);
+                    let mut nodes = HashMap::new();
                     loop {
-                        let received = { proofs_rx.lock().unwrap().recv() };
-                        if let Ok((index, height, proof, program_type)) = received {
-                            batch.push((index, height, proof, program_type));
-
-                            // Compute whether we've reached the root of the tree.
-                            let is_complete = height == expected_height;
-
-                            // If it's not complete, and we haven't reached the batch size,
-                            // continue.
-                            if !is_complete && batch.len() < batch_size {
-                                continue;
-                            }
-
-                            // Compute whether we're at the last input of a layer.
-                            let mut is_last = false;
-                            if let Some(first) = batch.first() {
-                                is_last = first.1 != height;
+                        // Poll for proofs, since the proofs we are waiting for may never be
+                        // generated if the job is cancelled.
+                        let received =
+                            { proofs_rx.lock().unwrap().recv_timeout(CANCELLATION_POLL_INTERVAL) };
+                        if let Err(RecvTimeoutError::Timeout) = received {
+                            if is_cancelled() {
+                                break false;
                             }
+                            continue;
+                        }
+                        if let Ok((index, proof, program_type)) = received {
+                            // Add the proof to the tree, and continue until it completes a group.
+                            let node = nodes.remove(&index).unwrap_or(ReduceNode::leaf(index));
+                            let Some(group) = tree.insert(node, (proof, program_type)) else {
+                                continue;
+                            };
 
-                            // If we're at the last input of a layer, we need to only include the
-                            // first input, otherwise we include all inputs.
-                            let inputs =
-                                if is_last { vec![batch[0].clone()] } else { batch.clone() };
-                            let shard_proofs =
-                                inputs.iter().map(|(_, _, proof, _)| proof.clone()).collect();
-                            let kinds = inputs
-                                .iter()
-                                .map(|(_, _, _, program_type)| *program_type)
-                                .collect();
+                            let (shard_proofs, kinds) = group.proofs.into_iter().unzip();
                             let input =
                                 SP1CompressMemoryLayouts::Compress(SP1CompressMemoryLayout {
                                     compress_vk: self.compress_vk(),
                                     recursive_machine: self.compress_prover.machine(),
                                     shard_proofs,
                                     kinds,
-                                    is_complete,
+                                    is_complete: group.is_complete,
                                 });
 
                             input_sync.wait_for_turn(count);
-                            input_tx.lock().unwrap().send((count, inputs[0].1 + 1, input)).unwrap();
+                            if is_cancelled() {
+                                input_sync.advance_turn();
+                                break false;
+                            }
+                            input_tx.lock().unwrap().send((count, input)).unwrap();
                             input_sync.advance_turn();
+                            nodes.insert(count, group.node);
                             count += 1;
 
                             // If we're at the root of the tree, stop generating inputs.
-                            if is_complete {
-                                break;
-                            }
-
-                            // If we were at the last input of a layer, we keep everything but the
-                            // first input. Otherwise, we empty the batch.
-                            if is_last {
-                                batch = vec![batch[1].clone()];
-                            } else {
-                                batch = Vec::new();
+                            if group.is_complete {
+                                break true;
                             }
                         } else {
-                            break;
+                            break false;
                         }
                     }
                 })
@@ -688,11 +773,14 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
             for handle in prover_handles {
                 handle.join().unwrap();
             }
-            handle.join().unwrap();
+            let completed = handle.join().unwrap();
+            if !completed && is_cancelled() {
+                return Err(SP1RecursionProverError::Cancelled);
+            }
 
             let output = proofs_rx.lock().unwrap().recv().unwrap();
-            output.2
-        });
+            Ok(output.1)
+        })?;
 
         Ok(SP1ReduceProof { proof })
     }
@@ -810,13 +898,16 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
 
         // Prove the wrap program.
         let mut wrap_challenger = self.wrap_prover.config().challenger();
-        let time = std::time::Instant::now();
-        let mut wrap_proof = self
-            .wrap_prover
-            .prove(self.wrap_pk(), vec![runtime.record], &mut wrap_challenger, opts.recursion_opts)
-            .unwrap();
-        let elapsed = time.elapsed();
-        tracing::debug!("Wrap proving time: {:?}", elapsed);
+        let mut wrap_proof = tracing::debug_span!("prove wrap").in_scope(|| {
+            self.wrap_prover
+                .prove(
+                    self.wrap_pk(),
+                    vec![runtime.record],
+                    &mut wrap_challenger,
+                    opts.recursion_opts,
+                )
+                .unwrap()
+        });
         let mut wrap_challenger = self.wrap_prover.config().challenger();
         let result =
             self.wrap_prover.machine().verify(self.wrap_vk(), &wrap_proof, &mut wrap_challenger);
@@ -960,7 +1051,7 @@ pub mod tests {
 
         tracing::info!("prove core");
         let stdin = SP1Stdin::new();
-        let core_proof = prover.prove_core(&pk, &stdin, opts, context)?;
+        let core_proof = prover.prove_core(&pk, &stdin, opts.clone(), context)?;
         let public_values = core_proof.public_values.clone();
 
         tracing::info!("verify core");
@@ -971,7 +1062,7 @@ pub mod tests {
         }
 
         tracing::info!("compress");
-        let compressed_proof = prover.compress(&vk, core_proof, vec![], opts)?;
+        let compressed_proof = prover.compress(&vk, core_proof, vec![], opts.clone())?;
 
         tracing::info!("verify compressed");
         prover.verify_compressed(&compressed_proof, &vk)?;
@@ -981,7 +1072,7 @@ pub mod tests {
         }
 
         tracing::info!("shrink");
-        let shrink_proof = prover.shrink(compressed_proof, opts)?;
+        let shrink_proof = prover.shrink(compressed_proof, opts.clone())?;
 
         tracing::info!("verify shrink");
         prover.verify_shrink(&shrink_proof, &vk)?;
@@ -1062,7 +1153,8 @@ pub mod tests {
         let mut stdin = SP1Stdin::new();
         stdin.write(&1usize);
         stdin.write(&vec![0u8, 0, 0]);
-        let deferred_proof_1 = prover.prove_core(&keccak_pk, &stdin, opts, Default::default())?;
+        let deferred_proof_1 =
+            prover.prove_core(&keccak_pk, &stdin, opts.clone(), Default::default())?;
         let pv_1 = deferred_proof_1.public_values.as_slice().to_vec().clone();
 
         // Generate a second proof of keccak of various inputs.
@@ -1072,16 +1164,19 @@ pub mod tests {
         stdin.write(&vec![0u8, 1, 2]);
         stdin.write(&vec![2, 3, 4]);
         stdin.write(&vec![5, 6, 7]);
-        let deferred_proof_2 = prover.prove_core(&keccak_pk, &stdin, opts, Default::default())?;
+        let deferred_proof_2 =
+            prover.prove_core(&keccak_pk, &stdin, opts.clone(), Default::default())?;
         let pv_2 = deferred_proof_2.public_values.as_slice().to_vec().clone();
 
         // Generate recursive proof of first subproof.
         tracing::info!("compress subproof 1");
-        let deferred_reduce_1 = prover.compress(&keccak_vk, deferred_proof_1, vec![], opts)?;
+        let deferred_reduce_1 =
+            prover.compress(&keccak_vk, deferred_proof_1, vec![], opts.clone())?;
 
         // Generate recursive proof of second subproof.
         tracing::info!("compress subproof 2");
-        let deferred_reduce_2 = prover.compress(&keccak_vk, deferred_proof_2, vec![], opts)?;
+        let deferred_reduce_2 =
+            prover.compress(&keccak_vk, deferred_proof_2, vec![], opts.clone())?;
 
         // Run verify program with keccak vkey, subproofs, and their committed values.
         let mut stdin = SP1Stdin::new();
@@ -1099,7 +1194,8 @@ pub mod tests {
         stdin.write_proof(deferred_reduce_2.proof.clone(), keccak_vk.vk.clone());
 
         tracing::info!("proving verify program (core)");
-        let verify_proof = prover.prove_core(&verify_pk, &stdin, opts, Default::default())?;
+        let verify_proof =
+            prover.prove_core(&verify_pk, &stdin, opts.clone(), Default::default())?;
 
         // Generate recursive proof of verify program
         tracing::info!("compress verify program");
@@ -1146,4 +1242,45 @@ pub mod tests {
         setup_logger();
         test_e2e_with_deferred_proofs_prover::<DefaultProverComponents>()
     }
+
+    /// Tests that the `bytes32` hash of a verifying key is stable across setups and serialization,
+    /// and consistent with its BN254 hash.
+    #[test]
+    fn test_vkey_bytes32() {
+        let elf = include_bytes!("../../../tests/fibonacci/elf/riscv32im-succinct-zkvm-elf");
+        let prover = SP1Prover::<DefaultProverComponents>::new();
+        let (_, vk) = prover.setup(elf);
+        let (_, vk_again) = prover.setup(elf);
+
+        let bytes32 = vk.bytes32();
+        assert_eq!(bytes32.len(), 66);
+        assert!(bytes32.starts_with("0x"));
+        assert_eq!(bytes32, vk_again.bytes32());
+        assert_eq!(bytes32, format!("0x{}", hex::encode(vk.bytes32_raw())));
+        assert_eq!(
+            num_bigint::BigUint::from_bytes_be(&vk.bytes32_raw()),
+            vk.hash_bn254().as_canonical_biguint()
+        );
+
+        let vk_roundtrip: SP1VerifyingKey =
+            bincode::deserialize(&bincode::serialize(&vk).unwrap()).unwrap();
+        assert_eq!(bytes32, vk_roundtrip.bytes32());
+
+        // A different program has a different hash.
+        let (_, other_vk) = prover
+            .setup(include_bytes!("../../../tests/keccak256/elf/riscv32im-succinct-zkvm-elf"));
+        assert_ne!(bytes32, other_vk.bytes32());
+    }
+
+    /// Tests the self-test, which proves and verifies a program through the core, compress, shrink
+    /// and wrap stages.
+    #[test]
+    #[serial]
+    fn test_selftest() {
+        setup_logger();
+        let prover = SP1Prover::<DefaultProverComponents>::new();
+        let report = prover.selftest().unwrap();
+        assert_eq!(report.stages.len(), 4);
+        assert!(report.cycles > 0);
+    }
 }
@@ -0,0 +1,190 @@
+//! An on-disk cache of compiled recursion programs.
+//!
+//! Compiling the lift, join, and compress programs takes several seconds every time a prover
+//! starts. The programs only depend on the machines they verify, i.e. the shapes and constraints of
+//! their chips, and on the verifying keys they are built against, so they can be compiled ahead of
+//! time (see the `recursion_programs --prebuild` script) and decoded when the prover needs them.
+//!
+//! The cache is opt-in: the prover only reads it when `SP1_PROGRAM_CACHE_DIR` is set.
+
+use std::{
+    fmt,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use p3_air::{Air, BaseAir};
+use p3_baby_bear::BabyBear;
+use p3_uni_stark::SymbolicAirBuilder;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sp1_recursion_core::runtime::RecursionProgram;
+use sp1_stark::{air::MachineAir, StarkGenericConfig, StarkMachine, Val};
+
+use crate::SP1_CIRCUIT_VERSION;
+
+/// The environment variable setting the directory of the recursion program cache.
+pub const SP1_PROGRAM_CACHE_DIR: &str = "SP1_PROGRAM_CACHE_DIR";
+
+/// A directory of compiled recursion programs, keyed by the shape of what they verify.
+#[derive(Debug, Clone)]
+pub struct RecursionProgramCache {
+    dir: PathBuf,
+}
+
+impl RecursionProgramCache {
+    /// Creates a cache backed by the given directory.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cache in `$SP1_PROGRAM_CACHE_DIR`, or `None` if the variable is not set.
+    pub fn from_env() -> Option<Self> {
+        std::env::var(SP1_PROGRAM_CACHE_DIR).ok().map(Self::new)
+    }
+
+    /// The cache in `~/.sp1/programs/<circuit version>`, which the `recursion_programs` script
+    /// writes to by default.
+    pub fn default_location() -> Option<Self> {
+        dirs::home_dir()
+            .map(|home| Self::new(home.join(".sp1").join("programs").join(SP1_CIRCUIT_VERSION)))
+    }
+
+    /// The directory backing the cache.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The path of the program with the given name and key.
+    pub fn path(&self, name: &str, key: &ProgramKey) -> PathBuf {
+        self.dir.join(format!("{name}-{}.bin", hex::encode(key.0)))
+    }
+
+    /// Loads a program from the cache, returning `None` if it is missing or cannot be decoded.
+    ///
+    /// The program is decoded into memory: it cannot be used in place, since its instructions are
+    /// not laid out as they are in the serialized file.
+    pub fn load(&self, name: &str, key: &ProgramKey) -> Option<RecursionProgram<BabyBear>> {
+        let path = self.path(name, key);
+        let bytes = std::fs::read(&path).ok()?;
+        match bincode::deserialize(&bytes) {
+            Ok(program) => Some(program),
+            Err(e) => {
+                tracing::warn!("failed to decode cached program {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Stores a program in the cache, returning the path it was written to.
+    pub fn store(
+        &self,
+        name: &str,
+        key: &ProgramKey,
+        program: &RecursionProgram<BabyBear>,
+    ) -> io::Result<PathBuf> {
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.path(name, key);
+        let bytes = bincode::serialize(program).map_err(io::Error::other)?;
+        let mut file = tempfile::NamedTempFile::new_in(&self.dir)?;
+        file.write_all(&bytes)?;
+        file.persist(&path).map_err(|e| e.error)?;
+        Ok(path)
+    }
+}
+
+/// A digest identifying everything a compiled recursion program depends on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgramKey(pub [u8; 32]);
+
+/// Accumulates the inputs of a recursion program into a [`ProgramKey`].
+pub struct ProgramKeyBuilder(Sha256);
+
+impl ProgramKeyBuilder {
+    /// Starts a key for programs compiled by this version of the circuits.
+    pub fn new() -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(SP1_CIRCUIT_VERSION.as_bytes());
+        Self(hasher)
+    }
+
+    /// Adds a machine whose proofs the program verifies: the shapes of its chips, their
+    /// constraints and their interactions.
+    pub fn machine<SC, A>(mut self, machine: &StarkMachine<SC, A>) -> Self
+    where
+        SC: StarkGenericConfig,
+        A: MachineAir<Val<SC>> + Air<SymbolicAirBuilder<Val<SC>>>,
+    {
+        for chip in machine.chips() {
+            self.0.update(chip.name().as_bytes());
+            self.0.update((chip.width() as u64).to_le_bytes());
+            self.0.update((chip.preprocessed_width() as u64).to_le_bytes());
+            // The debug representation of the symbolic constraints changes whenever they do.
+            write!(
+                HashWriter(&mut self.0),
+                "{:?}{:?}{:?}",
+                chip.symbolic_constraints(),
+                chip.sends(),
+                chip.receives()
+            )
+            .unwrap();
+        }
+        self.0.update((machine.num_pv_elts() as u64).to_le_bytes());
+        self
+    }
+
+    /// Adds a verifying key the program is built against.
+    pub fn vk<T: Serialize>(mut self, vk: &T) -> Self {
+        self.0.update(bincode::serialize(vk).expect("failed to serialize verifying key"));
+        self
+    }
+
+    /// Finishes the key.
+    pub fn finish(self) -> ProgramKey {
+        ProgramKey(self.0.finalize().into())
+    }
+}
+
+/// Writes formatted text into a hasher, without buffering it.
+struct HashWriter<'a>(&'a mut Sha256);
+
+impl fmt::Write for HashWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.update(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl Default for ProgramKeyBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_recursion_core::runtime::Instruction;
+
+    use super::*;
+
+    #[test]
+    fn test_program_cache_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = RecursionProgramCache::new(dir.path());
+        let key = ProgramKeyBuilder::new().vk(&1u32).finish();
+        let other = ProgramKeyBuilder::new().vk(&2u32).finish();
+        assert_ne!(key, other);
+
+        let program = RecursionProgram::<BabyBear> {
+            instructions: vec![Instruction::dummy(); 3],
+            traces: Vec::new(),
+        };
+        assert!(cache.load("recursion", &key).is_none());
+        cache.store("recursion", &key, &program).unwrap();
+
+        let loaded = cache.load("recursion", &key).unwrap();
+        assert_eq!(loaded.instructions.len(), program.instructions.len());
+        assert!(cache.load("recursion", &other).is_none());
+        assert!(cache.load("compress", &key).is_none());
+    }
+}
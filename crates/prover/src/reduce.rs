@@ -0,0 +1,211 @@
+//! The scheduling of the reduction tree of the compress stage.
+//!
+//! The leaves of the tree are the proofs of the first layer inputs, each covering a range of
+//! shards. A node reduces the proofs of adjacent ranges into a proof of their union, and the root
+//! covers every leaf. [`ReduceTree`] collects the proofs as they finish and groups them into the
+//! next node to prove according to a [`ReduceStrategy`].
+
+use std::collections::BTreeMap;
+
+use sp1_stark::ReduceStrategy;
+
+/// A node of the reduction tree, covering the leaves `start..end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReduceNode {
+    /// The first leaf covered by the node.
+    pub start: usize,
+    /// The leaf after the last leaf covered by the node.
+    pub end: usize,
+    /// The distance of the node to the leaves.
+    pub height: usize,
+    /// The position of the node within its layer, for layered strategies.
+    pub position: usize,
+}
+
+impl ReduceNode {
+    /// The node of the leaf at `index`.
+    pub const fn leaf(index: usize) -> Self {
+        Self { start: index, end: index + 1, height: 0, position: index }
+    }
+}
+
+/// A group of proofs to reduce into a new node.
+#[derive(Debug)]
+pub struct ReduceGroup<T> {
+    /// The node proven by reducing the group.
+    pub node: ReduceNode,
+    /// Whether the node is the root of the tree.
+    pub is_complete: bool,
+    /// The proofs to reduce, in the order of their ranges.
+    pub proofs: Vec<T>,
+}
+
+/// The proofs of a reduction tree that have not been reduced yet.
+#[derive(Debug)]
+pub struct ReduceTree<T> {
+    strategy: ReduceStrategy,
+    num_leaves: usize,
+    /// The number of nodes in each layer below the root, for layered strategies.
+    layer_lens: Vec<usize>,
+    /// The pending proofs, indexed by the height and position of their node.
+    pending: BTreeMap<(usize, usize), (ReduceNode, T)>,
+}
+
+impl<T> ReduceTree<T> {
+    /// Creates a tree over `num_leaves` leaves.
+    pub fn new(strategy: ReduceStrategy, num_leaves: usize) -> Self {
+        assert!(num_leaves > 0, "the reduction tree must have at least one leaf");
+        let mut layer_lens = vec![num_leaves];
+        loop {
+            let len = *layer_lens.last().unwrap();
+            let arity = strategy.arity(layer_lens.len() - 1);
+            if len <= arity {
+                break;
+            }
+            layer_lens.push(len.div_ceil(arity));
+        }
+        Self { strategy, num_leaves, layer_lens, pending: BTreeMap::new() }
+    }
+
+    /// Whether no proof is waiting to be reduced.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Adds the proof of a node, returning the group it completes, if any.
+    pub fn insert(&mut self, node: ReduceNode, proof: T) -> Option<ReduceGroup<T>> {
+        match self.strategy {
+            ReduceStrategy::BinaryBalanced | ReduceStrategy::WideFirst { .. } => {
+                self.insert_layered(node, proof)
+            }
+            ReduceStrategy::LatencyOptimized => self.insert_adjacent(node, proof),
+        }
+    }
+
+    /// Groups the nodes of each layer in order, `arity` at a time.
+    fn insert_layered(&mut self, node: ReduceNode, proof: T) -> Option<ReduceGroup<T>> {
+        let height = node.height;
+        self.pending.insert((height, node.position), (node, proof));
+
+        let arity = self.strategy.arity(height);
+        let position = node.position / arity;
+        let members = position * arity..((position + 1) * arity).min(self.layer_lens[height]);
+        if !members.clone().all(|p| self.pending.contains_key(&(height, p))) {
+            return None;
+        }
+
+        let (nodes, proofs): (Vec<_>, Vec<_>) =
+            members.map(|p| self.pending.remove(&(height, p)).unwrap()).unzip();
+        let node = ReduceNode {
+            start: nodes.first().unwrap().start,
+            end: nodes.last().unwrap().end,
+            height: height + 1,
+            position,
+        };
+        let is_complete = height + 1 == self.layer_lens.len();
+        Some(ReduceGroup { node, is_complete, proofs })
+    }
+
+    /// Groups a node with the first pending node of an adjacent range.
+    fn insert_adjacent(&mut self, node: ReduceNode, proof: T) -> Option<ReduceGroup<T>> {
+        // A single leaf is reduced on its own to produce the root.
+        if node.start == 0 && node.end == self.num_leaves {
+            return Some(ReduceGroup {
+                node: ReduceNode { height: node.height + 1, ..node },
+                is_complete: true,
+                proofs: vec![proof],
+            });
+        }
+
+        let neighbor = self
+            .pending
+            .iter()
+            .find(|(_, (other, _))| other.end == node.start || other.start == node.end)
+            .map(|(key, _)| *key);
+        let Some(key) = neighbor else {
+            self.pending.insert((node.start, node.end), (node, proof));
+            return None;
+        };
+
+        let (other, other_proof) = self.pending.remove(&key).unwrap();
+        let (nodes, proofs) = if other.end == node.start {
+            ([other, node], vec![other_proof, proof])
+        } else {
+            ([node, other], vec![proof, other_proof])
+        };
+        let node = ReduceNode {
+            start: nodes[0].start,
+            end: nodes[1].end,
+            height: nodes[0].height.max(nodes[1].height) + 1,
+            position: 0,
+        };
+        let is_complete = node.start == 0 && node.end == self.num_leaves;
+        Some(ReduceGroup { node, is_complete, proofs })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reduces `num_leaves` leaves, proving the nodes in the order given by `next`, and returns the
+    /// height of the root and the number of reductions.
+    fn reduce(
+        strategy: ReduceStrategy,
+        num_leaves: usize,
+        mut next: impl FnMut(&mut Vec<ReduceNode>) -> ReduceNode,
+    ) -> (usize, usize) {
+        let mut tree = ReduceTree::new(strategy, num_leaves);
+        let mut proving = (0..num_leaves).map(ReduceNode::leaf).collect::<Vec<_>>();
+        let mut num_reductions = 0;
+        loop {
+            let node = next(&mut proving);
+            let Some(group) = tree.insert(node, node) else {
+                continue;
+            };
+            num_reductions += 1;
+
+            // The proofs of a group cover adjacent ranges, in order.
+            for pair in group.proofs.windows(2) {
+                assert_eq!(pair[0].end, pair[1].start);
+            }
+            assert_eq!(group.node.start, group.proofs.first().unwrap().start);
+            assert_eq!(group.node.end, group.proofs.last().unwrap().end);
+
+            if group.is_complete {
+                assert_eq!((group.node.start, group.node.end), (0, num_leaves));
+                assert!(tree.is_empty() && proving.is_empty());
+                return (group.node.height, num_reductions);
+            }
+            proving.push(group.node);
+        }
+    }
+
+    #[test]
+    fn test_binary_balanced() {
+        for num_leaves in 1..20 {
+            let (height, _) =
+                reduce(ReduceStrategy::BinaryBalanced, num_leaves, |nodes| nodes.remove(0));
+            assert_eq!(height, num_leaves.next_power_of_two().trailing_zeros().max(1) as usize);
+        }
+    }
+
+    #[test]
+    fn test_wide_first() {
+        let strategy = ReduceStrategy::WideFirst { arity: 4 };
+        let (height, num_reductions) = reduce(strategy, 16, |nodes| nodes.remove(0));
+        assert_eq!((height, num_reductions), (3, 4 + 2 + 1));
+    }
+
+    #[test]
+    fn test_latency_optimized() {
+        for num_leaves in 1..20 {
+            // Prove the nodes in an arbitrary order.
+            let mut seed = num_leaves;
+            reduce(ReduceStrategy::LatencyOptimized, num_leaves, |nodes| {
+                seed = (seed * 7 + 3) % 101;
+                nodes.remove(seed % nodes.len())
+            });
+        }
+    }
+}
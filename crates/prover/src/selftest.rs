@@ -0,0 +1,153 @@
+//! A self-test which proves and verifies a small built-in program through every stage of the
+//! prover, to validate an installation and its hardware before accepting jobs.
+
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use sp1_core_executor::SP1Context;
+use sp1_core_machine::{io::SP1Stdin, utils::SP1CoreProverError};
+use sp1_stark::SP1ProverOpts;
+use thiserror::Error;
+
+use crate::{components::SP1ProverComponents, SP1Prover, SP1RecursionProverError};
+
+/// The program proven by the self-test, which computes a Fibonacci number.
+pub const SELFTEST_ELF: &[u8] = include_bytes!("../elf/riscv32im-succinct-zkvm-elf");
+
+/// A stage of the proof generation pipeline exercised by the self-test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelfTestStage {
+    Core,
+    Compress,
+    Shrink,
+    Wrap,
+}
+
+impl fmt::Display for SelfTestStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SelfTestStage::Core => write!(f, "core"),
+            SelfTestStage::Compress => write!(f, "compress"),
+            SelfTestStage::Shrink => write!(f, "shrink"),
+            SelfTestStage::Wrap => write!(f, "wrap"),
+        }
+    }
+}
+
+/// The time taken to prove and verify one stage of the self-test.
+#[derive(Debug, Clone, Copy)]
+pub struct SelfTestStageReport {
+    pub stage: SelfTestStage,
+    pub prove_time: Duration,
+    pub verify_time: Duration,
+}
+
+/// The result of a successful self-test.
+#[derive(Debug, Clone)]
+pub struct SelfTestReport {
+    /// The number of cycles of the program.
+    pub cycles: u64,
+    /// The time taken to generate the proving and verifying keys of the program.
+    pub setup_time: Duration,
+    /// The timings of each stage, in the order they were run.
+    pub stages: Vec<SelfTestStageReport>,
+}
+
+impl SelfTestReport {
+    /// The total time taken by the self-test.
+    pub fn total_time(&self) -> Duration {
+        self.stages.iter().map(|s| s.prove_time + s.verify_time).sum::<Duration>() + self.setup_time
+    }
+}
+
+impl fmt::Display for SelfTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "cycles: {}", self.cycles)?;
+        writeln!(f, "setup: {:?}", self.setup_time)?;
+        for stage in &self.stages {
+            writeln!(
+                f,
+                "{}: prove {:?}, verify {:?}",
+                stage.stage, stage.prove_time, stage.verify_time
+            )?;
+        }
+        write!(f, "total: {:?}", self.total_time())
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum SelfTestError {
+    #[error("core proving failed: {0}")]
+    Core(#[from] SP1CoreProverError),
+    #[error("recursive proving failed: {0}")]
+    Recursion(#[from] SP1RecursionProverError),
+    #[error("{stage} proof failed to verify: {error}")]
+    Verification { stage: SelfTestStage, error: String },
+}
+
+impl SelfTestError {
+    fn verification(stage: SelfTestStage, error: impl fmt::Display) -> Self {
+        Self::Verification { stage, error: error.to_string() }
+    }
+}
+
+impl<C: SP1ProverComponents> SP1Prover<C> {
+    /// Proves and verifies [`SELFTEST_ELF`] through the core, compress, shrink and wrap stages,
+    /// reporting the time taken by each stage.
+    ///
+    /// The PLONK and Groth16 wrappers are not exercised, since they depend on circuit artifacts
+    /// which are installed separately.
+    pub fn selftest(&self) -> Result<SelfTestReport, SelfTestError> {
+        let opts = SP1ProverOpts::default();
+        let mut stages = Vec::new();
+        let mut record = |stage, prove_time, verify_time| {
+            stages.push(SelfTestStageReport { stage, prove_time, verify_time })
+        };
+
+        tracing::info!("selftest: setup");
+        let ((pk, vk), setup_time) = timed(|| self.setup(SELFTEST_ELF));
+
+        tracing::info!("selftest: core");
+        let stdin = SP1Stdin::new();
+        let (core_proof, prove_time) =
+            timed(|| self.prove_core(&pk, &stdin, opts.clone(), SP1Context::default()));
+        let core_proof = core_proof?;
+        let (result, verify_time) = timed(|| self.verify(&core_proof.proof, &vk));
+        result.map_err(|e| SelfTestError::verification(SelfTestStage::Core, e))?;
+        record(SelfTestStage::Core, prove_time, verify_time);
+        let cycles = core_proof.cycles;
+
+        tracing::info!("selftest: compress");
+        let (compressed_proof, prove_time) =
+            timed(|| self.compress(&vk, core_proof, vec![], opts.clone()));
+        let compressed_proof = compressed_proof?;
+        let (result, verify_time) = timed(|| self.verify_compressed(&compressed_proof, &vk));
+        result.map_err(|e| SelfTestError::verification(SelfTestStage::Compress, e))?;
+        record(SelfTestStage::Compress, prove_time, verify_time);
+
+        tracing::info!("selftest: shrink");
+        let (shrink_proof, prove_time) = timed(|| self.shrink(compressed_proof, opts.clone()));
+        let shrink_proof = shrink_proof?;
+        let (result, verify_time) = timed(|| self.verify_shrink(&shrink_proof, &vk));
+        result.map_err(|e| SelfTestError::verification(SelfTestStage::Shrink, e))?;
+        record(SelfTestStage::Shrink, prove_time, verify_time);
+
+        tracing::info!("selftest: wrap");
+        let (wrap_proof, prove_time) = timed(|| self.wrap_bn254(shrink_proof, opts));
+        let wrap_proof = wrap_proof?;
+        let (result, verify_time) = timed(|| self.verify_wrap_bn254(&wrap_proof, &vk));
+        result.map_err(|e| SelfTestError::verification(SelfTestStage::Wrap, e))?;
+        record(SelfTestStage::Wrap, prove_time, verify_time);
+
+        Ok(SelfTestReport { cycles, setup_time, stages })
+    }
+}
+
+/// Runs `f`, returning its result and the time it took.
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let time = Instant::now();
+    let result = f();
+    (result, time.elapsed())
+}
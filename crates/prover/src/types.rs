@@ -13,7 +13,9 @@ use sp1_recursion_gnark_ffi::proof::{Groth16Bn254Proof, PlonkBn254Proof};
 use sp1_recursion_program::machine::{
     SP1CompressMemoryLayout, SP1DeferredMemoryLayout, SP1RecursionMemoryLayout,
 };
-use sp1_stark::{ShardProof, StarkGenericConfig, StarkProvingKey, StarkVerifyingKey, DIGEST_SIZE};
+use sp1_stark::{
+    ShardProof, StarkGenericConfig, StarkProvingKey, StarkVerifyingKey, VkDiff, DIGEST_SIZE,
+};
 use thiserror::Error;
 
 use crate::{
@@ -36,6 +38,22 @@ pub struct SP1VerifyingKey {
     pub vk: StarkVerifyingKey<CoreSC>,
 }
 
+impl SP1VerifyingKey {
+    /// Compares this key with `other`, to report what changed between two builds of a program
+    /// when the hash of its verifying key changed.
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// let diff = old_vk.diff(&new_vk);
+    /// if !diff.is_empty() {
+    ///     println!("{diff}");
+    /// }
+    /// ```
+    pub fn diff(&self, other: &SP1VerifyingKey) -> VkDiff {
+        VkDiff::new(&self.vk, &other.vk)
+    }
+}
+
 /// A trait for keys that can be hashed into a digest.
 pub trait HashableKey {
     /// Hash the key into a digest of BabyBear elements.
@@ -48,9 +66,20 @@ pub trait HashableKey {
         babybears_to_bn254(&self.hash_babybear())
     }
 
+    /// The hash of the key as a hex string, which is the `programVKey` expected by the onchain
+    /// verifiers.
     fn bytes32(&self) -> String {
-        let vkey_digest_bn254 = self.hash_bn254();
-        format!("0x{:0>64}", vkey_digest_bn254.as_canonical_biguint().to_str_radix(16))
+        format!("0x{}", hex::encode(self.bytes32_raw()))
+    }
+
+    /// The hash of the key as a big-endian `bytes32`, which is the `programVKey` expected by the
+    /// onchain verifiers. This is the Poseidon2 hash of the key over BabyBear, packed into a
+    /// single BN254 element.
+    fn bytes32_raw(&self) -> [u8; 32] {
+        let bytes = self.hash_bn254().as_canonical_biguint().to_bytes_be();
+        let mut result = [0u8; 32];
+        result[32 - bytes.len()..].copy_from_slice(&bytes);
+        result
     }
 
     /// Hash the key into a digest of bytes elements.
@@ -235,6 +264,8 @@ pub enum SP1ReduceProofWrapper {
 pub enum SP1RecursionProverError {
     #[error("Runtime error: {0}")]
     RuntimeError(String),
+    #[error("Proving was cancelled")]
+    Cancelled,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -25,6 +25,11 @@ pub fn get_cycles(elf: &[u8], stdin: &SP1Stdin) -> u64 {
     let program = Program::from(elf).unwrap();
     let mut runtime = Executor::new(program, SP1CoreOpts::default());
     runtime.write_vecs(&stdin.buffer);
+    runtime.write_paged_inputs(&stdin.paged_inputs);
+    runtime.write_files(&stdin.files);
+    runtime.write_public_values_domain(stdin.public_values_domain);
+    runtime.write_env_vars(&stdin.env_vars);
+    runtime.write_args(&stdin.args);
     runtime.run_fast().unwrap();
     runtime.state.global_clk
 }
@@ -6,6 +6,7 @@ use p3_bn254_fr::Bn254Fr;
 use p3_field::AbstractField;
 use p3_fri::TwoAdicFriPcsProof;
 use sp1_recursion_compiler::{
+    circuit::CircuitV2Report,
     config::OuterConfig,
     constraints::{Constraint, ConstraintCompiler},
     ir::{Builder, Config, Ext, Felt, SymbolicExt, Var},
@@ -114,6 +115,7 @@ where
         pc_start: builder.eval(*pc_start),
         chip_information: chip_information.clone(),
         chip_ordering: chip_ordering.clone(),
+        fri_params: None,
     };
 
     StarkVerifier::<OuterC, OuterSC, _>::verify_shard(
@@ -140,8 +142,11 @@ where
     //     builder.assert_felt_eq(*expected_elm, *calculated_elm);
     // }
 
+    let counts = builder.circuit_v2_counts();
     let mut backend = ConstraintCompiler::<OuterConfig>::default();
-    backend.emit(builder.operations)
+    let constraints = backend.emit(builder.operations);
+    tracing::info!("{}", CircuitV2Report::new("wrap", counts, constraints.len()));
+    constraints
 }
 
 /// A utility function to convert a `ShardProof` into a `ShardProofVariable`. Should be replaced by
@@ -333,7 +338,7 @@ pub mod tests {
 
     use crate::{
         challenger::CanObserveVariable,
-        fri::verify_two_adic_pcs,
+        fri::{verify_two_adic_pcs, FriParameters},
         hash::BN254_DIGEST_SIZE,
         utils::{babybear_bytes_to_bn254, babybears_to_bn254, words_to_bytes},
         witness::{OuterWitness, Witnessable},
@@ -504,7 +509,7 @@ pub mod tests {
         builder.assert_ext_eq(x1, x2);
         verify_two_adic_pcs::<_, BabyBearPoseidon2Outer>(
             &mut builder,
-            &config,
+            &FriParameters::from(&config),
             &proof,
             &mut challenger,
             rounds,
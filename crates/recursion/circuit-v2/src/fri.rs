@@ -13,16 +13,38 @@ use std::{
 
 use crate::{
     challenger::{CanSampleBitsVariable, FieldChallengerVariable},
-    BabyBearFriConfigVariable, CanObserveVariable, CircuitConfig, Ext, FriChallenges, FriMmcs,
+    BabyBearFriConfigVariable, CanObserveVariable, CircuitConfig, Ext, FriChallenges,
     FriProofVariable, FriQueryProofVariable, TwoAdicPcsProofVariable, TwoAdicPcsRoundVariable,
 };
 
+/// The FRI parameters that determine the shape of a FRI proof and hence of its verifier circuit.
+///
+/// These are the only fields of a [`FriConfig`] that the recursive verifier depends on. Passing
+/// them separately lets a single recursion program verify proofs generated under different
+/// security presets, see [`crate::VerifyingKeyVariable::fri_params`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FriParameters {
+    pub log_blowup: usize,
+    pub num_queries: usize,
+    pub proof_of_work_bits: usize,
+}
+
+impl<M> From<&FriConfig<M>> for FriParameters {
+    fn from(config: &FriConfig<M>) -> Self {
+        Self {
+            log_blowup: config.log_blowup,
+            num_queries: config.num_queries,
+            proof_of_work_bits: config.proof_of_work_bits,
+        }
+    }
+}
+
 pub fn verify_shape_and_sample_challenges<
     C: CircuitConfig<F = BabyBear>,
     SC: BabyBearFriConfigVariable<C>,
 >(
     builder: &mut Builder<C>,
-    config: &FriConfig<FriMmcs<SC>>,
+    config: &FriParameters,
     proof: &FriProofVariable<C, SC>,
     challenger: &mut SC::FriChallengerVariable,
 ) -> FriChallenges<C> {
@@ -55,7 +77,7 @@ pub fn verify_shape_and_sample_challenges<
 
 pub fn verify_two_adic_pcs<C: CircuitConfig<F = SC::Val>, SC: BabyBearFriConfigVariable<C>>(
     builder: &mut Builder<C>,
-    config: &FriConfig<FriMmcs<SC>>,
+    config: &FriParameters,
     proof: &TwoAdicPcsProofVariable<C, SC>,
     challenger: &mut SC::FriChallengerVariable,
     rounds: Vec<TwoAdicPcsRoundVariable<C, SC>>,
@@ -158,7 +180,7 @@ pub fn verify_two_adic_pcs<C: CircuitConfig<F = SC::Val>, SC: BabyBearFriConfigV
 
 pub fn verify_challenges<C: CircuitConfig<F = SC::Val>, SC: BabyBearFriConfigVariable<C>>(
     builder: &mut Builder<C>,
-    config: &FriConfig<FriMmcs<SC>>,
+    config: &FriParameters,
     proof: &FriProofVariable<C, SC>,
     challenges: &FriChallenges<C>,
     reduced_openings: Vec<[Ext<C::F, C::EF>; 32]>,
@@ -543,7 +565,7 @@ mod tests {
         let _ = challenger.sample_ext(&mut builder);
         let fri_challenges = verify_shape_and_sample_challenges::<InnerConfig, BabyBearPoseidon2>(
             &mut builder,
-            &config,
+            &FriParameters::from(&config),
             &fri_proof,
             &mut challenger,
         );
@@ -626,7 +648,7 @@ mod tests {
         builder.assert_ext_eq(x1, x2);
         verify_two_adic_pcs::<_, BabyBearPoseidon2>(
             &mut builder,
-            &config,
+            &FriParameters::from(&config),
             &proof,
             &mut challenger,
             rounds,
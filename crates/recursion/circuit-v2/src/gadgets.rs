@@ -0,0 +1,153 @@
+//! Gadgets for extending recursion programs.
+//!
+//! The verifiers of this crate are written against a few building blocks: conditional swaps,
+//! bit decompositions, exponentiations, hashes and challengers. This module collects them behind
+//! one documented interface, generic over the [`CircuitConfig`] of the recursion program, so that
+//! custom programs (for example, aggregation logic that checks extra public values) can reuse
+//! them and stay compatible with both the inner recursion and the outer wrap circuits.
+
+pub use crate::{
+    challenger::{
+        CanCopyChallenger, CanObserveVariable, CanSampleBitsVariable, CanSampleVariable,
+        DuplexChallengerVariable, FieldChallengerVariable, MultiField32ChallengerVariable,
+    },
+    hash::FieldHasherVariable,
+    select_chain,
+    utils::{
+        commit_recursion_public_values, felt_bytes_to_bn254_var, felts_to_bn254_var, words_to_bytes,
+    },
+};
+
+use sp1_recursion_compiler::ir::{Builder, Ext, Felt};
+
+use crate::CircuitConfig;
+
+/// Returns `[input[1], input[0]]` if `should_swap` is one, and `input` if it is zero.
+///
+/// This is the step of a Merkle path verification that orders a node and its sibling.
+pub fn select_chain_digest<C: CircuitConfig, H: FieldHasherVariable<C>>(
+    builder: &mut Builder<C>,
+    should_swap: C::Bit,
+    input: [H::Digest; 2],
+) -> [H::Digest; 2] {
+    H::select_chain_digest(builder, should_swap, input)
+}
+
+/// Returns `second` followed by `first` if `should_swap` is one, and `first` followed by `second`
+/// if it is zero.
+pub fn select_chain_ef<C: CircuitConfig>(
+    builder: &mut Builder<C>,
+    should_swap: C::Bit,
+    first: impl IntoIterator<Item = Ext<C::F, C::EF>> + Clone,
+    second: impl IntoIterator<Item = Ext<C::F, C::EF>> + Clone,
+) -> Vec<Ext<C::F, C::EF>> {
+    C::select_chain_ef(builder, should_swap, first, second)
+}
+
+/// Decomposes `felt` into its `num_bits` least significant bits, least significant first.
+///
+/// The decomposition is constrained over all the bits of the field, so the bits beyond `num_bits`
+/// are dropped rather than checked to be zero.
+pub fn felt_to_bits<C: CircuitConfig>(
+    builder: &mut Builder<C>,
+    felt: Felt<C::F>,
+    num_bits: usize,
+) -> Vec<C::Bit> {
+    C::num2bits(builder, felt, num_bits)
+}
+
+/// Recomposes a field element from its bits, least significant first.
+pub fn bits_to_felt<C: CircuitConfig>(
+    builder: &mut Builder<C>,
+    bits: impl IntoIterator<Item = C::Bit>,
+) -> Felt<C::F> {
+    C::bits2num(builder, bits)
+}
+
+/// Returns `input` raised to the power whose bits, most significant first, are `power_bits`.
+///
+/// This computes the points of the FRI query domains, whose indices are sampled bit-reversed.
+pub fn exp_reverse_bits<C: CircuitConfig>(
+    builder: &mut Builder<C>,
+    input: Felt<C::F>,
+    power_bits: Vec<C::Bit>,
+) -> Felt<C::F> {
+    C::exp_reverse_bits(builder, input, power_bits)
+}
+
+/// Splits an extension field element into its coordinates over the base field.
+pub fn ext_to_felts<C: CircuitConfig>(
+    builder: &mut Builder<C>,
+    ext: Ext<C::F, C::EF>,
+) -> [Felt<C::F>; sp1_recursion_core_v2::D] {
+    C::ext2felt(builder, ext)
+}
+
+/// Hashes `input` with the hasher `H`.
+pub fn hash<C: CircuitConfig, H: FieldHasherVariable<C>>(
+    builder: &mut Builder<C>,
+    input: &[Felt<C::F>],
+) -> H::Digest {
+    H::hash(builder, input)
+}
+
+/// Compresses two digests into one with the hasher `H`, as for the nodes of a Merkle tree.
+pub fn compress<C: CircuitConfig, H: FieldHasherVariable<C>>(
+    builder: &mut Builder<C>,
+    input: [H::Digest; 2],
+) -> H::Digest {
+    H::compress(builder, input)
+}
+
+/// Constrains two digests of the hasher `H` to be equal.
+pub fn assert_digest_eq<C: CircuitConfig, H: FieldHasherVariable<C>>(
+    builder: &mut Builder<C>,
+    a: H::Digest,
+    b: H::Digest,
+) {
+    H::assert_digest_eq(builder, a, b);
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+    use p3_field::AbstractField;
+    use sp1_recursion_compiler::config::InnerConfig;
+    use sp1_stark::baby_bear_poseidon2::BabyBearPoseidon2;
+
+    use super::*;
+    use crate::utils::tests::run_test_recursion;
+
+    type C = InnerConfig;
+    type H = BabyBearPoseidon2;
+
+    #[test]
+    fn test_gadgets() {
+        let mut builder = Builder::<C>::default();
+
+        // Bit decompositions round trip.
+        let felt: Felt<_> = builder.eval(BabyBear::from_canonical_u32(0b1011_0110));
+        let bits = felt_to_bits(&mut builder, felt, 8);
+        let recomposed = bits_to_felt(&mut builder, bits.clone());
+        builder.assert_felt_eq(felt, recomposed);
+
+        // 3^(0b01101101) with the bits of the exponent given most significant first.
+        let three: Felt<_> = builder.eval(BabyBear::from_canonical_u32(3));
+        let power = exp_reverse_bits(&mut builder, three, bits);
+        builder.assert_felt_eq(power, BabyBear::from_canonical_u32(3).exp_u64(0b0110_1101));
+
+        // Selecting with a one bit swaps the digests.
+        let left = hash::<C, H>(&mut builder, &[felt]);
+        let right = hash::<C, H>(&mut builder, &[three]);
+        let one: Felt<_> = builder.eval(BabyBear::one());
+        let [first, second] = select_chain_digest::<C, H>(&mut builder, one, [left, right]);
+        assert_digest_eq::<C, H>(&mut builder, first, right);
+        assert_digest_eq::<C, H>(&mut builder, second, left);
+
+        let mut challenger = DuplexChallengerVariable::new(&mut builder);
+        challenger.observe_slice(&mut builder, first);
+        challenger.sample_ext(&mut builder);
+
+        run_test_recursion(builder.operations, None);
+    }
+}
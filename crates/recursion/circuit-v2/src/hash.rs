@@ -1,22 +1,38 @@
-use std::iter::zip;
+use std::{fmt::Debug, iter::zip};
 
 use itertools::Itertools;
 use p3_baby_bear::BabyBear;
 use p3_field::{AbstractField, Field};
 
 use p3_bn254_fr::Bn254Fr;
+use p3_symmetric::PseudoCompressionFunction;
 use sp1_recursion_compiler::{
     circuit::CircuitV2Builder,
     ir::{Builder, Config, DslIr, Felt, Var},
 };
 use sp1_recursion_core_v2::{stark::config::BabyBearPoseidon2Outer, DIGEST_SIZE};
-use sp1_stark::baby_bear_poseidon2::BabyBearPoseidon2;
+use sp1_stark::baby_bear_poseidon2::{my_perm, BabyBearPoseidon2, MyCompress};
 
 use crate::{
     challenger::{reduce_32, RATE, SPONGE_SIZE},
     select_chain, CircuitConfig,
 };
 
+/// A native hasher for the digests of a [`crate::merkle_tree::MerkleTree`].
+pub trait FieldHasher<F: Field> {
+    type Digest: Copy + Default + Eq + Debug;
+
+    fn constant_compress(input: [Self::Digest; 2]) -> Self::Digest;
+}
+
+impl FieldHasher<BabyBear> for BabyBearPoseidon2 {
+    type Digest = [BabyBear; DIGEST_SIZE];
+
+    fn constant_compress(input: [Self::Digest; 2]) -> Self::Digest {
+        MyCompress::new(my_perm()).compress(input)
+    }
+}
+
 pub trait FieldHasherVariable<C: CircuitConfig> {
     type Digest: Clone + Copy;
 
@@ -26,21 +26,23 @@ pub mod challenger;
 pub mod constraints;
 pub mod domain;
 pub mod fri;
+pub mod gadgets;
 pub mod hash;
 pub mod machine;
+pub mod merkle_tree;
 pub mod stark;
 pub(crate) mod utils;
 pub mod witness;
 
 use sp1_stark::{
-    baby_bear_poseidon2::{BabyBearPoseidon2, ValMmcs},
+    baby_bear_poseidon2::{BabyBearPoseidon2, Dft, ValMmcs},
     StarkGenericConfig,
 };
 pub use types::*;
 
 use p3_challenger::{CanObserve, CanSample, FieldChallenger, GrindingChallenger};
 use p3_commit::{ExtensionMmcs, Mmcs};
-use p3_dft::Radix2DitParallel;
+use p3_dft::{Radix2DitParallel, TwoAdicSubgroupDft};
 use p3_fri::{FriConfig, TwoAdicFriPcs};
 use sp1_recursion_core_v2::{
     stark::config::{BabyBearPoseidon2Outer, OuterValMmcs},
@@ -70,7 +72,7 @@ pub trait BabyBearFriConfig:
     Challenger = Self::FriChallenger,
     Pcs = TwoAdicFriPcs<
         BabyBear,
-        Radix2DitParallel,
+        Self::Dft,
         Self::ValMmcs,
         ExtensionMmcs<BabyBear, EF, Self::ValMmcs>,
     >,
@@ -80,6 +82,7 @@ pub trait BabyBearFriConfig:
         + Send
         + Sync;
     type RowMajorProverData: Clone + Send + Sync;
+    type Dft: TwoAdicSubgroupDft<BabyBear>;
     type FriChallenger: CanObserve<<Self::ValMmcs as Mmcs<BabyBear>>::Commitment>
         + CanSample<EF>
         + GrindingChallenger<Witness = BabyBear>
@@ -287,6 +290,7 @@ impl CircuitConfig for OuterConfig {
 
 impl BabyBearFriConfig for BabyBearPoseidon2 {
     type ValMmcs = ValMmcs;
+    type Dft = Dft;
     type FriChallenger = <Self as StarkGenericConfig>::Challenger;
     type RowMajorProverData = <ValMmcs as Mmcs<BabyBear>>::ProverData<RowMajorMatrix<BabyBear>>;
 
@@ -297,6 +301,7 @@ impl BabyBearFriConfig for BabyBearPoseidon2 {
 
 impl BabyBearFriConfig for BabyBearPoseidon2Outer {
     type ValMmcs = OuterValMmcs;
+    type Dft = Radix2DitParallel;
     type FriChallenger = <Self as StarkGenericConfig>::Challenger;
 
     type RowMajorProverData =
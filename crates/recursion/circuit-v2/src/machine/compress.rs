@@ -1 +1,331 @@
+use std::{
+    array,
+    borrow::{Borrow, BorrowMut},
+    marker::PhantomData,
+};
 
+use itertools::Itertools;
+use p3_air::Air;
+use p3_baby_bear::BabyBear;
+use p3_commit::Mmcs;
+use p3_field::AbstractField;
+use p3_matrix::dense::RowMajorMatrix;
+use sp1_recursion_compiler::{
+    circuit::CircuitV2Builder,
+    ir::{Builder, Config, Ext, ExtConst, Felt},
+};
+use sp1_recursion_core_v2::{
+    air::{
+        ChallengerPublicValues, RecursionPublicValues, NUM_PV_ELMS_TO_HASH,
+        RECURSIVE_PROOF_NUM_PV_ELTS,
+    },
+    DIGEST_SIZE,
+};
+use sp1_stark::{air::MachineAir, ShardProof, StarkGenericConfig, StarkMachine, StarkVerifyingKey};
+
+use crate::{
+    challenger::{CanObserveVariable, DuplexChallengerVariable},
+    constraints::RecursiveVerifierConstraintFolder,
+    stark::{ShardProofVariable, StarkVerifier},
+    utils::commit_recursion_public_values,
+    BabyBearFriConfigVariable, CircuitConfig, VerifyingKeyVariable,
+};
+
+/// A program to verify a batch of recursive proofs and aggregate their public values.
+#[derive(Debug, Clone, Copy)]
+pub struct SP1CompressVerifier<C, SC, A> {
+    _phantom: PhantomData<(C, SC, A)>,
+}
+
+/// The input of [`SP1CompressVerifier::verify`].
+pub struct SP1CompressWitnessValues<SC: StarkGenericConfig> {
+    /// The recursive proofs to compress, in the order of the shards they prove, with the keys of
+    /// the programs they were generated with.
+    pub vks_and_proofs: Vec<(StarkVerifyingKey<SC>, ShardProof<SC>)>,
+    /// Whether the proofs together prove the whole execution.
+    pub is_complete: bool,
+}
+
+/// The variables of [`SP1CompressWitnessValues`].
+pub struct SP1CompressWitnessVariable<C: CircuitConfig, SC: BabyBearFriConfigVariable<C>> {
+    pub vks_and_proofs: Vec<(VerifyingKeyVariable<C, SC>, ShardProofVariable<C, SC>)>,
+    pub is_complete: Felt<C::F>,
+}
+
+impl<C, SC, A> SP1CompressVerifier<C, SC, A>
+where
+    SC: BabyBearFriConfigVariable<
+        C,
+        FriChallengerVariable = DuplexChallengerVariable<C>,
+        Digest = [Felt<BabyBear>; DIGEST_SIZE],
+    >,
+    C: CircuitConfig<F = SC::Val, EF = SC::Challenge, Bit = Felt<BabyBear>>,
+    <SC::ValMmcs as Mmcs<BabyBear>>::ProverData<RowMajorMatrix<BabyBear>>: Clone,
+    A: MachineAir<SC::Val> + for<'a> Air<RecursiveVerifierConstraintFolder<'a, C>>,
+{
+    /// Verify a batch of recursive proofs and aggregate their public values.
+    ///
+    /// Each proof is verified against its own verifying key, and the public values of the proofs
+    /// are chained with [`aggregate_public_values`], so that the aggregated public values attest to
+    /// the execution of all the shards the proofs verify.
+    pub fn verify(
+        builder: &mut Builder<C>,
+        machine: &StarkMachine<SC, A>,
+        input: SP1CompressWitnessVariable<C, SC>,
+    ) {
+        let SP1CompressWitnessVariable { vks_and_proofs, is_complete } = input;
+
+        // Assert that the number of proofs is not zero.
+        assert!(!vks_and_proofs.is_empty());
+
+        let mut public_values = Vec::with_capacity(vks_and_proofs.len());
+        for (vk, shard_proof) in vks_and_proofs.iter() {
+            // Verify the shard proof with a challenger that observed the key, the commitment and
+            // the public values, as for a machine proof of a single shard.
+            let mut challenger = machine.config().challenger_variable(builder);
+            vk.observe_into(builder, &mut challenger);
+            challenger.observe(builder, shard_proof.commitment.main_commit);
+            challenger.observe_slice(
+                builder,
+                shard_proof.public_values[0..machine.num_pv_elts()].iter().copied(),
+            );
+            StarkVerifier::<C, SC, A>::verify_shard(
+                builder,
+                vk,
+                machine,
+                &mut challenger,
+                shard_proof,
+            );
+
+            let current_public_values: &RecursionPublicValues<Felt<C::F>> =
+                shard_proof.public_values.as_slice().borrow();
+            public_values.push(*current_public_values);
+        }
+
+        // The completeness flag is a boolean.
+        builder.assert_felt_eq(is_complete * (is_complete - C::F::one()), C::F::zero());
+
+        let mut compress_public_values = aggregate_public_values(builder, &public_values);
+        compress_public_values.is_complete = is_complete;
+        commit_recursion_public_values(builder, &compress_public_values);
+    }
+}
+
+/// Asserts that the recursive proofs with public values `public_values` prove consecutive batches
+/// of the shards of the same execution, and returns the public values of the whole sequence, with
+/// an unset digest and completeness flag.
+///
+/// The public values of each proof must hash to its digest, and must start where the proof before
+/// it ended: the program counter, the shard and execution shard, the memory address bits, the
+/// reconstructed challenger and deferred digest of a proof are the next or last ones of the proof
+/// before it. Since the execution shards of each batch increase from its start to its next
+/// execution shard, the timestamps of memory accesses keep increasing across the whole sequence.
+/// The program, leaf challenger and exit code must be the same for all the proofs.
+pub fn aggregate_public_values<C: Config>(
+    builder: &mut Builder<C>,
+    public_values: &[RecursionPublicValues<Felt<C::F>>],
+) -> RecursionPublicValues<Felt<C::F>> {
+    let (first, rest) = public_values.split_first().expect("there must be at least one proof");
+
+    // The aggregated public values start with the public values of the first proof.
+    let mut aggregated: RecursionPublicValues<Felt<C::F>> = *first;
+    let cumulative_sum: Ext<_, _> = builder.eval(C::EF::zero().cons());
+    for current in public_values.iter() {
+        // Check that the public values digest is correct.
+        let mut elements: [Felt<_>; RECURSIVE_PROOF_NUM_PV_ELTS] =
+            array::from_fn(|_| builder.uninit());
+        *elements.as_mut_slice().borrow_mut() = *current;
+        let digest = builder.poseidon2_hash_v2(&elements[0..NUM_PV_ELMS_TO_HASH]);
+        for (digest, current_digest) in digest.iter().zip_eq(current.digest.iter()) {
+            builder.assert_felt_eq(*digest, *current_digest);
+        }
+
+        // Update the cumulative sum.
+        let current_sum = builder.ext_from_base_slice(&current.cumulative_sum);
+        builder.assign(cumulative_sum, cumulative_sum + current_sum);
+    }
+
+    for current in rest.iter() {
+        // Assert that the sp1_vk digest is always the same.
+        for (digest, current) in aggregated.sp1_vk_digest.iter().zip_eq(current.sp1_vk_digest) {
+            builder.assert_felt_eq(*digest, current);
+        }
+
+        // Assert that the start pc is equal to the current pc.
+        builder.assert_felt_eq(aggregated.next_pc, current.start_pc);
+
+        // Verify that the shard is equal to the current shard.
+        builder.assert_felt_eq(aggregated.next_shard, current.start_shard);
+
+        // Verify that the execution shard is equal to the current execution shard.
+        builder.assert_felt_eq(aggregated.next_execution_shard, current.start_execution_shard);
+
+        // Assert that the MemoryInitialize address bits are the same.
+        for (bit, current_bit) in
+            aggregated.last_init_addr_bits.iter().zip_eq(current.previous_init_addr_bits)
+        {
+            builder.assert_felt_eq(*bit, current_bit);
+        }
+
+        // Assert that the MemoryFinalize address bits are the same.
+        for (bit, current_bit) in
+            aggregated.last_finalize_addr_bits.iter().zip_eq(current.previous_finalize_addr_bits)
+        {
+            builder.assert_felt_eq(*bit, current_bit);
+        }
+
+        // Assert that the leaf challenger is always the same.
+        assert_challenger_eq(builder, &aggregated.leaf_challenger, &current.leaf_challenger);
+
+        // Assert that the current challenger matches the start reconstruct challenger.
+        assert_challenger_eq(
+            builder,
+            &aggregated.end_reconstruct_challenger,
+            &current.start_reconstruct_challenger,
+        );
+
+        // Assert that the start deferred digest is equal to the current deferred digest.
+        for (digest, current_digest) in aggregated
+            .end_reconstruct_deferred_digest
+            .iter()
+            .zip_eq(current.start_reconstruct_deferred_digest)
+        {
+            builder.assert_felt_eq(*digest, current_digest);
+        }
+
+        // Assert that the exit code is always the same.
+        builder.assert_felt_eq(aggregated.exit_code, current.exit_code);
+
+        // Update the accumulated values to the end of the current proof.
+        aggregated.next_pc = current.next_pc;
+        aggregated.next_shard = current.next_shard;
+        aggregated.next_execution_shard = current.next_execution_shard;
+        aggregated.last_init_addr_bits = current.last_init_addr_bits;
+        aggregated.last_finalize_addr_bits = current.last_finalize_addr_bits;
+        aggregated.end_reconstruct_challenger = current.end_reconstruct_challenger;
+        aggregated.end_reconstruct_deferred_digest = current.end_reconstruct_deferred_digest;
+        aggregated.committed_value_digest = current.committed_value_digest;
+        aggregated.deferred_proofs_digest = current.deferred_proofs_digest;
+    }
+
+    aggregated.cumulative_sum = builder.ext2felt_v2(cumulative_sum);
+    aggregated.digest = array::from_fn(|_| builder.eval(C::F::zero()));
+    aggregated.is_complete = builder.eval(C::F::zero());
+    aggregated
+}
+
+/// Asserts that two challengers in public values are equal.
+fn assert_challenger_eq<C: Config>(
+    builder: &mut Builder<C>,
+    lhs: &ChallengerPublicValues<Felt<C::F>>,
+    rhs: &ChallengerPublicValues<Felt<C::F>>,
+) {
+    for (a, b) in lhs.sponge_state.iter().zip_eq(rhs.sponge_state.iter()) {
+        builder.assert_felt_eq(*a, *b);
+    }
+    builder.assert_felt_eq(lhs.num_inputs, rhs.num_inputs);
+    for (a, b) in lhs.input_buffer.iter().zip_eq(rhs.input_buffer.iter()) {
+        builder.assert_felt_eq(*a, *b);
+    }
+    builder.assert_felt_eq(lhs.num_outputs, rhs.num_outputs);
+    for (a, b) in lhs.output_buffer.iter().zip_eq(rhs.output_buffer.iter()) {
+        builder.assert_felt_eq(*a, *b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_recursion_compiler::config::InnerConfig;
+    use sp1_stark::InnerVal;
+
+    use super::*;
+    use crate::utils::tests::run_recursion;
+
+    type F = InnerVal;
+
+    /// The public values of a proof of the shards `start..next`, which all have a "CPU".
+    fn proof(start: u32, next: u32) -> RecursionPublicValues<F> {
+        let zeros = [F::zero(); RECURSIVE_PROOF_NUM_PV_ELTS];
+        let mut public_values: RecursionPublicValues<F> = *zeros.as_slice().borrow();
+        public_values.start_shard = F::from_canonical_u32(start);
+        public_values.next_shard = F::from_canonical_u32(next);
+        public_values.start_execution_shard = F::from_canonical_u32(start);
+        public_values.next_execution_shard = F::from_canonical_u32(next);
+        public_values.start_pc = F::from_canonical_u32(0x2000 + start);
+        public_values.next_pc = F::from_canonical_u32(0x2000 + next);
+        public_values
+    }
+
+    /// Aggregates proofs with the given public values, with correct digests.
+    fn run_aggregate(proofs: &[RecursionPublicValues<F>]) -> bool {
+        let mut builder = Builder::<InnerConfig>::default();
+        let public_values = proofs
+            .iter()
+            .map(|proof| {
+                let mut elements: [Felt<F>; RECURSIVE_PROOF_NUM_PV_ELTS] =
+                    proof.to_vec().map(|x| builder.eval(x));
+                let digest = builder.poseidon2_hash_v2(&elements[0..NUM_PV_ELMS_TO_HASH]);
+                let public_values: &mut RecursionPublicValues<Felt<F>> =
+                    elements.as_mut_slice().borrow_mut();
+                public_values.digest = digest;
+                *public_values
+            })
+            .collect::<Vec<_>>();
+        let aggregated = aggregate_public_values(&mut builder, &public_values);
+        let last = public_values.last().unwrap();
+        builder.assert_felt_eq(aggregated.next_execution_shard, last.next_execution_shard);
+        builder.assert_felt_eq(aggregated.next_pc, last.next_pc);
+        run_recursion(builder.operations).is_ok()
+    }
+
+    #[test]
+    fn test_aggregate_public_values() {
+        assert!(run_aggregate(&[proof(1, 3)]));
+        assert!(run_aggregate(&[proof(1, 3), proof(3, 4), proof(4, 8)]));
+    }
+
+    #[test]
+    fn test_aggregate_rejects_execution_shard_gap() {
+        let mut second = proof(3, 5);
+        second.start_execution_shard = F::from_canonical_u32(4);
+        assert!(!run_aggregate(&[proof(1, 3), second]));
+    }
+
+    #[test]
+    fn test_aggregate_rejects_replayed_execution_shards() {
+        // A proof of execution shards that were already proven cannot be appended.
+        let mut second = proof(3, 5);
+        second.start_execution_shard = F::from_canonical_u32(1);
+        second.next_execution_shard = F::from_canonical_u32(3);
+        assert!(!run_aggregate(&[proof(1, 3), second]));
+    }
+
+    #[test]
+    fn test_aggregate_rejects_broken_chain() {
+        let mut second = proof(3, 5);
+        second.start_pc = F::from_canonical_u32(0x1234);
+        assert!(!run_aggregate(&[proof(1, 3), second]));
+
+        let mut second = proof(3, 5);
+        second.previous_init_addr_bits[0] = F::one();
+        assert!(!run_aggregate(&[proof(1, 3), second]));
+
+        let mut second = proof(3, 5);
+        second.start_reconstruct_challenger.num_inputs = F::one();
+        assert!(!run_aggregate(&[proof(1, 3), second]));
+
+        let mut second = proof(3, 5);
+        second.sp1_vk_digest[0] = F::one();
+        assert!(!run_aggregate(&[proof(1, 3), second]));
+    }
+
+    #[test]
+    fn test_aggregate_rejects_wrong_digest() {
+        let mut builder = Builder::<InnerConfig>::default();
+        let elements: [Felt<F>; RECURSIVE_PROOF_NUM_PV_ELTS] =
+            proof(1, 3).to_vec().map(|x| builder.eval(x));
+        let public_values: &RecursionPublicValues<Felt<F>> = elements.as_slice().borrow();
+        aggregate_public_values(&mut builder, &[*public_values]);
+        assert!(run_recursion(builder.operations).is_err());
+    }
+}
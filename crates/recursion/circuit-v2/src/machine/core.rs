@@ -1,8 +1,4 @@
-use std::{
-    array,
-    borrow::{Borrow, BorrowMut},
-    marker::PhantomData,
-};
+use std::{array, borrow::BorrowMut, marker::PhantomData};
 
 use itertools::Itertools;
 use p3_baby_bear::BabyBear;
@@ -13,7 +9,7 @@ use sp1_core_machine::riscv::RiscvAir;
 use sp1_primitives::consts::WORD_SIZE;
 use sp1_recursion_core_v2::air::PV_DIGEST_NUM_WORDS;
 use sp1_stark::{
-    air::{PublicValues, POSEIDON_NUM_WORDS},
+    air::{PublicValues, POSEIDON_NUM_WORDS, PUBLIC_VALUES_LAYOUT},
     StarkMachine, Word,
 };
 
@@ -124,6 +120,14 @@ where
         let current_init_addr_bits: [Felt<_>; 32] = array::from_fn(|_| builder.uninit());
         let current_finalize_addr_bits: [Felt<_>; 32] = array::from_fn(|_| builder.uninit());
 
+        // The values chained from one shard to the next.
+        let chain = ShardChainVariable::<C> {
+            shard: current_shard,
+            execution_shard: current_execution_shard,
+            init_addr_bits: current_init_addr_bits,
+            finalize_addr_bits: current_finalize_addr_bits,
+        };
+
         // Initialize the exit code variable.
         let exit_code: Felt<_> = builder.uninit();
 
@@ -149,12 +153,13 @@ where
         // Verify proofs.
         for (i, shard_proof) in shard_proofs.into_iter().enumerate() {
             let contains_cpu = shard_proof.contains_cpu();
-            let _contains_memory_init = shard_proof.contains_memory_init();
-            let _contains_memory_finalize = shard_proof.contains_memory_finalize();
+            let contains_memory_init = shard_proof.contains_memory_init();
+            let contains_memory_finalize = shard_proof.contains_memory_finalize();
 
             // Get the public values.
-            let public_values: &PublicValues<Word<Felt<_>>, Felt<_>> =
-                shard_proof.public_values.as_slice().borrow();
+            let public_values: &PublicValues<Word<Felt<_>>, Felt<_>> = PUBLIC_VALUES_LAYOUT
+                .view(&shard_proof.public_values)
+                .expect("public values are shorter than the layout");
 
             let _shard = public_values.shard;
 
@@ -264,28 +269,15 @@ where
             //     }
             // }
 
-            // Shard constraints.
-            {
-                // Assert that the shard of the proof is equal to the current shard.
-                builder.assert_felt_eq(current_shard, public_values.shard);
-
-                // Increment the current shard by one.
-                builder.assign(current_shard, current_shard + C::F::one());
-            }
-
-            // Execution shard constraints.
-            // let execution_shard = felt2var(builder, public_values.execution_shard);
-            {
-                // If the shard has a "CPU" chip, then the execution shard should be incremented by
-                // 1.
-                if contains_cpu {
-                    // Assert that the shard of the proof is equal to the current shard.
-                    // builder.assert_felt_eq(current_execution_shard,
-                    // public_values.execution_shard);
-
-                    builder.assign(current_execution_shard, current_execution_shard + C::F::one());
-                }
-            }
+            // Shard, execution shard and memory address bits constraints.
+            chain_shard(
+                builder,
+                &chain,
+                public_values,
+                contains_cpu,
+                contains_memory_init,
+                contains_memory_finalize,
+            );
 
             // Program counter constraints.
             {
@@ -318,79 +310,6 @@ where
                 builder.assert_felt_eq(exit_code, C::F::zero());
             }
 
-            // Memory initialization & finalization constraints.
-            {
-                // // Assert that `init_addr_bits` and `finalize_addr_bits` are zero for the first
-                // execution shard. builder.if_eq(execution_shard,
-                // C::N::one()).then(|builder| {     // Assert that the
-                // MemoryInitialize address bits are zero.     for bit in
-                // current_init_addr_bits.iter() {         builder.assert_felt_eq(*
-                // bit, C::F::zero());     }
-
-                //     // Assert that the MemoryFinalize address bits are zero.
-                //     for bit in current_finalize_addr_bits.iter() {
-                //         builder.assert_felt_eq(*bit, C::F::zero());
-                //     }
-                // });
-
-                // // Assert that the MemoryInitialize address bits match the current loop variable.
-                // for (bit, current_bit) in current_init_addr_bits
-                //     .iter()
-                //     .zip_eq(public_values.previous_init_addr_bits.iter())
-                // {
-                //     builder.assert_felt_eq(*bit, *current_bit);
-                // }
-
-                // // Assert that the MemoryFinalize address bits match the current loop variable.
-                // for (bit, current_bit) in current_finalize_addr_bits
-                //     .iter()
-                //     .zip_eq(public_values.previous_finalize_addr_bits.iter())
-                // {
-                //     builder.assert_felt_eq(*bit, *current_bit);
-                // }
-
-                // // Assert that if MemoryInit is not present, then the address bits are the same.
-                // builder
-                //     .if_ne(contains_memory_init, C::N::one())
-                //     .then(|builder| {
-                //         for (prev_bit, last_bit) in public_values
-                //             .previous_init_addr_bits
-                //             .iter()
-                //             .zip_eq(public_values.last_init_addr_bits.iter())
-                //         {
-                //             builder.assert_felt_eq(*prev_bit, *last_bit);
-                //         }
-                //     });
-
-                // // Assert that if MemoryFinalize is not present, then the address bits are the
-                // same. builder
-                //     .if_ne(contains_memory_finalize, C::N::one())
-                //     .then(|builder| {
-                //         for (prev_bit, last_bit) in public_values
-                //             .previous_finalize_addr_bits
-                //             .iter()
-                //             .zip_eq(public_values.last_finalize_addr_bits.iter())
-                //         {
-                //             builder.assert_felt_eq(*prev_bit, *last_bit);
-                //         }
-                //     });
-
-                // Update the MemoryInitialize address bits.
-                for (bit, pub_bit) in
-                    current_init_addr_bits.iter().zip(public_values.last_init_addr_bits.iter())
-                {
-                    builder.assign(*bit, *pub_bit);
-                }
-
-                // Update the MemoryFinalize address bits.
-                for (bit, pub_bit) in current_finalize_addr_bits
-                    .iter()
-                    .zip(public_values.last_finalize_addr_bits.iter())
-                {
-                    builder.assign(*bit, *pub_bit);
-                }
-            }
-
             // Digest constraints.
             {
                 // // If `commited_value_digest` is not zero, then
@@ -556,3 +475,172 @@ where
         }
     }
 }
+
+/// The values that [`SP1RecursiveVerifier::verify`] chains from one shard to the next.
+#[derive(Debug, Clone, Copy)]
+pub struct ShardChainVariable<C: Config> {
+    /// The shard expected next.
+    pub shard: Felt<C::F>,
+    /// The execution shard expected next.
+    pub execution_shard: Felt<C::F>,
+    /// The MemoryInitialize address bits expected next.
+    pub init_addr_bits: [Felt<C::F>; 32],
+    /// The MemoryFinalize address bits expected next.
+    pub finalize_addr_bits: [Felt<C::F>; 32],
+}
+
+/// Asserts that the shard with public values `public_values` follows the shards chained so far,
+/// and advances `chain` past it.
+///
+/// The shard numbers and, for the shards with a "CPU", the execution shard numbers must increase by
+/// one from shard to shard. Since the timestamps of memory accesses are ordered by execution shard
+/// first, this makes them increase across the shards of a batch, as in
+/// `MachineVerifier::verify_full_proof`.
+pub fn chain_shard<C: Config>(
+    builder: &mut Builder<C>,
+    chain: &ShardChainVariable<C>,
+    public_values: &PublicValues<Word<Felt<C::F>>, Felt<C::F>>,
+    contains_cpu: bool,
+    contains_memory_init: bool,
+    contains_memory_finalize: bool,
+) {
+    // Shard constraints.
+    {
+        // Assert that the shard of the proof is equal to the current shard.
+        builder.assert_felt_eq(chain.shard, public_values.shard);
+
+        // Increment the current shard by one.
+        builder.assign(chain.shard, chain.shard + C::F::one());
+    }
+
+    // Execution shard constraints.
+    {
+        // If the shard has a "CPU" chip, then its execution shard should be the current one, which
+        // is then incremented by 1. The shards without a "CPU" do not advance the execution.
+        if contains_cpu {
+            builder.assert_felt_eq(chain.execution_shard, public_values.execution_shard);
+            builder.assign(chain.execution_shard, chain.execution_shard + C::F::one());
+        }
+    }
+
+    // Memory initialization & finalization constraints.
+    {
+        // Assert that the MemoryInitialize address bits match the current loop variable.
+        for (bit, current_bit) in
+            chain.init_addr_bits.iter().zip_eq(public_values.previous_init_addr_bits.iter())
+        {
+            builder.assert_felt_eq(*bit, *current_bit);
+        }
+
+        // Assert that the MemoryFinalize address bits match the current loop variable.
+        for (bit, current_bit) in
+            chain.finalize_addr_bits.iter().zip_eq(public_values.previous_finalize_addr_bits.iter())
+        {
+            builder.assert_felt_eq(*bit, *current_bit);
+        }
+
+        // Assert that if MemoryInit is not present, then the address bits are the same.
+        if !contains_memory_init {
+            for (prev_bit, last_bit) in public_values
+                .previous_init_addr_bits
+                .iter()
+                .zip_eq(public_values.last_init_addr_bits.iter())
+            {
+                builder.assert_felt_eq(*prev_bit, *last_bit);
+            }
+        }
+
+        // Assert that if MemoryFinalize is not present, then the address bits are the same.
+        if !contains_memory_finalize {
+            for (prev_bit, last_bit) in public_values
+                .previous_finalize_addr_bits
+                .iter()
+                .zip_eq(public_values.last_finalize_addr_bits.iter())
+            {
+                builder.assert_felt_eq(*prev_bit, *last_bit);
+            }
+        }
+
+        // Update the MemoryInitialize address bits.
+        for (bit, pub_bit) in
+            chain.init_addr_bits.iter().zip(public_values.last_init_addr_bits.iter())
+        {
+            builder.assign(*bit, *pub_bit);
+        }
+
+        // Update the MemoryFinalize address bits.
+        for (bit, pub_bit) in
+            chain.finalize_addr_bits.iter().zip(public_values.last_finalize_addr_bits.iter())
+        {
+            builder.assign(*bit, *pub_bit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_recursion_compiler::config::InnerConfig;
+    use sp1_stark::InnerVal;
+
+    use super::*;
+    use crate::utils::tests::run_recursion;
+
+    /// Chains shards with the given public values, of which the first `num_cpu_shards` contain a
+    /// "CPU" and none contains a memory chip.
+    fn run_chain(shards: &[PublicValues<u32, u32>], num_cpu_shards: usize) -> bool {
+        let mut builder = Builder::<InnerConfig>::default();
+        let first: Felt<_> = builder.eval(InnerVal::from_canonical_u32(shards[0].shard));
+        let execution_shard: Felt<_> =
+            builder.eval(InnerVal::from_canonical_u32(shards[0].execution_shard));
+        let chain = ShardChainVariable::<InnerConfig> {
+            shard: first,
+            execution_shard,
+            init_addr_bits: array::from_fn(|_| builder.eval(InnerVal::zero())),
+            finalize_addr_bits: array::from_fn(|_| builder.eval(InnerVal::zero())),
+        };
+        for (i, shard) in shards.iter().enumerate() {
+            let values = shard
+                .to_vec::<InnerVal>()
+                .into_iter()
+                .map(|x| builder.eval(x))
+                .collect::<Vec<Felt<_>>>();
+            let public_values = PUBLIC_VALUES_LAYOUT.view(&values).unwrap();
+            chain_shard(&mut builder, &chain, public_values, i < num_cpu_shards, false, false);
+        }
+        run_recursion(builder.operations).is_ok()
+    }
+
+    fn shard(shard: u32, execution_shard: u32) -> PublicValues<u32, u32> {
+        PublicValues { shard, execution_shard, ..Default::default() }
+    }
+
+    #[test]
+    fn test_chain_shard() {
+        assert!(run_chain(&[shard(1, 1), shard(2, 2), shard(3, 3)], 3));
+        // The shards without a "CPU" keep the execution shard.
+        assert!(run_chain(&[shard(1, 1), shard(2, 2), shard(3, 3), shard(4, 7)], 3));
+    }
+
+    #[test]
+    fn test_chain_shard_rejects_shard_gap() {
+        assert!(!run_chain(&[shard(1, 1), shard(3, 2)], 2));
+    }
+
+    #[test]
+    fn test_chain_shard_rejects_repeated_execution_shard() {
+        assert!(!run_chain(&[shard(1, 1), shard(2, 1)], 2));
+        assert!(!run_chain(&[shard(1, 2), shard(2, 1)], 2));
+    }
+
+    #[test]
+    fn test_chain_shard_rejects_changed_addr_bits() {
+        let mut second = shard(2, 2);
+        second.last_init_addr_bits[0] = 1;
+        assert!(!run_chain(&[shard(1, 1), second], 2));
+
+        let mut second = shard(2, 2);
+        second.previous_finalize_addr_bits[3] = 1;
+        second.last_finalize_addr_bits[3] = 1;
+        assert!(!run_chain(&[shard(1, 1), second], 2));
+    }
+}
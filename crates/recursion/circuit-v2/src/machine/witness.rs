@@ -19,7 +19,10 @@ use crate::{
     CircuitConfig, VerifyingKeyVariable,
 };
 
-use super::{SP1RecursionMemoryLayout, SP1RecursionWitnessVariable};
+use super::{
+    SP1CompressWitnessValues, SP1CompressWitnessVariable, SP1RecursionMemoryLayout,
+    SP1RecursionWitnessVariable,
+};
 
 impl<C> Witnessable<C> for DuplexChallenger<InnerVal, InnerPerm, 16, 8>
 where
@@ -70,7 +73,13 @@ where
         let pc_start = self.pc_start.read(builder);
         let chip_information = self.chip_information.clone();
         let chip_ordering = self.chip_ordering.clone();
-        VerifyingKeyVariable { commitment, pc_start, chip_information, chip_ordering }
+        VerifyingKeyVariable {
+            commitment,
+            pc_start,
+            chip_information,
+            chip_ordering,
+            fri_params: None,
+        }
     }
 
     fn write(&self, witness: &mut impl WitnessWriter<C>) {
@@ -109,3 +118,28 @@ where
         self.is_complete.write(witness);
     }
 }
+
+impl<C> Witnessable<C> for SP1CompressWitnessValues<BabyBearPoseidon2>
+where
+    C: CircuitConfig<F = InnerVal, EF = InnerChallenge, Bit = Felt<InnerVal>>,
+{
+    type WitnessVariable = SP1CompressWitnessVariable<C, BabyBearPoseidon2>;
+
+    fn read(&self, builder: &mut Builder<C>) -> Self::WitnessVariable {
+        let vks_and_proofs = self
+            .vks_and_proofs
+            .iter()
+            .map(|(vk, proof)| (vk.read(builder), proof.read(builder)))
+            .collect();
+        let is_complete = InnerVal::from_bool(self.is_complete).read(builder);
+        SP1CompressWitnessVariable { vks_and_proofs, is_complete }
+    }
+
+    fn write(&self, witness: &mut impl WitnessWriter<C>) {
+        for (vk, proof) in self.vks_and_proofs.iter() {
+            vk.write(witness);
+            proof.write(witness);
+        }
+        self.is_complete.write(witness);
+    }
+}
@@ -0,0 +1,347 @@
+//! A Merkle tree over digests, with single and batched openings which can be verified natively or
+//! in a circuit.
+//!
+//! The leaves are padded with default digests up to a power of two, so any number of leaves can be
+//! committed to. A [`MultiProof`] opens several leaves at once and only contains the siblings which
+//! cannot be computed from the opened paths, so paths which meet share their upper hashes.
+
+use std::marker::PhantomData;
+
+use itertools::Itertools;
+use p3_field::Field;
+use p3_util::log2_ceil_usize;
+use sp1_recursion_compiler::ir::Builder;
+
+use crate::{
+    hash::{FieldHasher, FieldHasherVariable},
+    CircuitConfig,
+};
+
+/// A Merkle tree whose leaves are digests of `HV`.
+#[derive(Debug, Clone)]
+pub struct MerkleTree<F: Field, HV: FieldHasher<F>> {
+    /// The number of leaves committed to, before padding.
+    pub num_leaves: usize,
+    /// The layers of the tree, from the padded leaves up to the root.
+    pub layers: Vec<Vec<HV::Digest>>,
+    _marker: PhantomData<F>,
+}
+
+/// A proof that a leaf is in a [`MerkleTree`].
+#[derive(Debug, Clone)]
+pub struct MerkleProof<F: Field, HV: FieldHasher<F>> {
+    /// The index of the leaf.
+    pub index: usize,
+    /// The siblings of the path from the leaf to the root, from the bottom up.
+    pub path: Vec<HV::Digest>,
+}
+
+/// A proof that several leaves are in a [`MerkleTree`].
+#[derive(Debug, Clone)]
+pub struct MultiProof<F: Field, HV: FieldHasher<F>> {
+    /// The indices of the leaves, sorted and without duplicates.
+    pub indices: Vec<usize>,
+    /// The height of the tree.
+    pub height: usize,
+    /// The siblings which are not on the path of another opened leaf, in the order in which they
+    /// are consumed by [`MerkleTree::verify_batch`].
+    pub siblings: Vec<HV::Digest>,
+}
+
+/// An error returned when a Merkle proof does not verify.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MerkleTreeError {
+    /// The proof does not have the shape expected for its indices and height.
+    InvalidShape,
+    /// The root computed from the proof does not match the commitment.
+    RootMismatch,
+}
+
+impl<F: Field, HV: FieldHasher<F>> MerkleTree<F, HV> {
+    /// Commit to `leaves`, returning the root and the tree.
+    pub fn commit(leaves: Vec<HV::Digest>) -> (HV::Digest, Self) {
+        assert!(!leaves.is_empty(), "cannot commit to an empty set of leaves");
+        let num_leaves = leaves.len();
+        let height = log2_ceil_usize(num_leaves);
+
+        let mut layer = leaves;
+        layer.resize(1 << height, HV::Digest::default());
+        let mut layers = vec![layer];
+        for _ in 0..height {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks_exact(2)
+                .map(|pair| HV::constant_compress([pair[0], pair[1]]))
+                .collect();
+            layers.push(next);
+        }
+
+        let root = layers[height][0];
+        (root, Self { num_leaves, layers, _marker: PhantomData })
+    }
+
+    /// The height of the tree, which is the length of the path of every leaf.
+    pub fn height(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    /// The root of the tree.
+    pub fn root(&self) -> HV::Digest {
+        self.layers[self.height()][0]
+    }
+
+    /// Open the leaf at `index`, returning it with its proof.
+    pub fn open(&self, index: usize) -> (HV::Digest, MerkleProof<F, HV>) {
+        assert!(index < self.num_leaves, "index {} out of bounds", index);
+        let path = (0..self.height()).map(|i| self.layers[i][(index >> i) ^ 1]).collect();
+        (self.layers[0][index], MerkleProof { index, path })
+    }
+
+    /// Verify that `value` is the leaf opened by `proof` in the tree committed to by `root`.
+    pub fn verify(
+        proof: &MerkleProof<F, HV>,
+        value: HV::Digest,
+        root: HV::Digest,
+    ) -> Result<(), MerkleTreeError> {
+        if proof.index >> proof.path.len() != 0 {
+            return Err(MerkleTreeError::InvalidShape);
+        }
+        let computed = proof.path.iter().enumerate().fold(value, |digest, (i, sibling)| {
+            if (proof.index >> i) & 1 == 0 {
+                HV::constant_compress([digest, *sibling])
+            } else {
+                HV::constant_compress([*sibling, digest])
+            }
+        });
+        if computed != root {
+            return Err(MerkleTreeError::RootMismatch);
+        }
+        Ok(())
+    }
+
+    /// Open the leaves at `indices` at once, sharing the siblings of their paths.
+    ///
+    /// The leaves of the proof are in the order of its sorted and deduplicated indices.
+    pub fn open_batch(&self, indices: &[usize]) -> (Vec<HV::Digest>, MultiProof<F, HV>) {
+        let indices = indices.iter().copied().sorted().dedup().collect_vec();
+        assert!(!indices.is_empty(), "cannot open an empty set of leaves");
+        assert!(
+            indices.last().is_some_and(|&index| index < self.num_leaves),
+            "index out of bounds"
+        );
+
+        let leaves = indices.iter().map(|&index| self.layers[0][index]).collect();
+        let mut siblings = Vec::new();
+        let mut level = indices.clone();
+        for layer in &self.layers[..self.height()] {
+            level = fold_level(&level, |index| siblings.push(layer[index ^ 1]));
+        }
+
+        (leaves, MultiProof { indices, height: self.height(), siblings })
+    }
+
+    /// Verify that `leaves` are the leaves opened by `proof` in the tree committed to by `root`.
+    pub fn verify_batch(
+        proof: &MultiProof<F, HV>,
+        leaves: &[HV::Digest],
+        root: HV::Digest,
+    ) -> Result<(), MerkleTreeError> {
+        if leaves.len() != proof.indices.len()
+            || proof.siblings.len() != num_siblings(&proof.indices, proof.height)?
+        {
+            return Err(MerkleTreeError::InvalidShape);
+        }
+        let nodes = proof.indices.iter().copied().zip_eq(leaves.iter().copied()).collect();
+        let computed = fold_multi(
+            nodes,
+            proof.height,
+            &mut proof.siblings.iter().copied(),
+            HV::constant_compress,
+        );
+        if computed != root {
+            return Err(MerkleTreeError::RootMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// Verify in the circuit that `leaves` are the leaves at `indices` of a tree of height `height`
+/// committed to by `root`, given the siblings of a [`MultiProof`].
+///
+/// The indices are constants of the circuit, so only the digests are witnessed, and each shared
+/// node of the opened paths is hashed once.
+pub fn verify_multi<C: CircuitConfig, HV: FieldHasherVariable<C>>(
+    builder: &mut Builder<C>,
+    indices: &[usize],
+    height: usize,
+    leaves: &[HV::Digest],
+    siblings: &[HV::Digest],
+    root: HV::Digest,
+) {
+    assert_eq!(indices.len(), leaves.len(), "expected one leaf per index");
+    assert_eq!(
+        Ok(siblings.len()),
+        num_siblings(indices, height),
+        "siblings do not match the shape of the proof"
+    );
+
+    let nodes = indices.iter().copied().zip_eq(leaves.iter().copied()).collect();
+    let computed = fold_multi(nodes, height, &mut siblings.iter().copied(), |input| {
+        HV::compress(builder, input)
+    });
+    HV::assert_digest_eq(builder, computed, root);
+}
+
+/// The number of siblings in a [`MultiProof`] of `indices` in a tree of height `height`.
+fn num_siblings(indices: &[usize], height: usize) -> Result<usize, MerkleTreeError> {
+    let is_sorted = indices.iter().tuple_windows().all(|(a, b)| a < b);
+    let in_bounds = indices.last().is_some_and(|&index| index >> height == 0);
+    if !is_sorted || !in_bounds {
+        return Err(MerkleTreeError::InvalidShape);
+    }
+
+    let mut count = 0;
+    let mut level = indices.to_vec();
+    for _ in 0..height {
+        level = fold_level(&level, |_| count += 1);
+    }
+    Ok(count)
+}
+
+/// Compute the parents of the sorted node indices `level`, calling `missing_sibling` with every
+/// node whose sibling is not in `level`.
+fn fold_level(level: &[usize], mut missing_sibling: impl FnMut(usize)) -> Vec<usize> {
+    let mut parents = Vec::with_capacity(level.len());
+    let mut i = 0;
+    while i < level.len() {
+        let index = level[i];
+        if index % 2 == 0 && level.get(i + 1) == Some(&(index + 1)) {
+            i += 1;
+        } else {
+            missing_sibling(index);
+        }
+        parents.push(index / 2);
+        i += 1;
+    }
+    parents
+}
+
+/// Hash the sorted `nodes` up to the root of a tree of height `height`, taking the siblings which
+/// are not among the nodes from `siblings`, in the order of [`fold_level`].
+fn fold_multi<D: Copy>(
+    mut nodes: Vec<(usize, D)>,
+    height: usize,
+    siblings: &mut impl Iterator<Item = D>,
+    mut compress: impl FnMut([D; 2]) -> D,
+) -> D {
+    for _ in 0..height {
+        let mut parents = Vec::with_capacity(nodes.len());
+        let mut i = 0;
+        while i < nodes.len() {
+            let (index, digest) = nodes[i];
+            let pair =
+                if index % 2 == 0 && nodes.get(i + 1).is_some_and(|(next, _)| *next == index + 1) {
+                    i += 1;
+                    [digest, nodes[i].1]
+                } else {
+                    let sibling = siblings.next().expect("not enough siblings");
+                    if index % 2 == 0 {
+                        [digest, sibling]
+                    } else {
+                        [sibling, digest]
+                    }
+                };
+            parents.push((index / 2, compress(pair)));
+            i += 1;
+        }
+        nodes = parents;
+    }
+    nodes[0].1
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use sp1_recursion_compiler::{
+        config::InnerConfig,
+        ir::{Builder, Felt},
+    };
+    use sp1_stark::baby_bear_poseidon2::BabyBearPoseidon2;
+
+    use super::{verify_multi, MerkleTree, MerkleTreeError};
+    use crate::utils::tests::run_test_recursion;
+
+    type HV = BabyBearPoseidon2;
+    type Digest = [BabyBear; 8];
+
+    fn random_leaves(n: usize) -> Vec<Digest> {
+        let mut rng = StdRng::seed_from_u64(0xDEADBEEF);
+        (0..n).map(|_| rng.gen()).collect()
+    }
+
+    #[test]
+    fn test_merkle_tree() {
+        let leaves = random_leaves(11);
+        let (root, tree) = MerkleTree::<BabyBear, HV>::commit(leaves.clone());
+        assert_eq!(tree.height(), 4);
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let (value, proof) = tree.open(index);
+            assert_eq!(value, *leaf);
+            MerkleTree::verify(&proof, value, root).unwrap();
+        }
+
+        let (_, proof) = tree.open(3);
+        assert_eq!(MerkleTree::verify(&proof, leaves[4], root), Err(MerkleTreeError::RootMismatch));
+    }
+
+    #[test]
+    fn test_merkle_tree_multi_proof() {
+        let leaves = random_leaves(11);
+        let (root, tree) = MerkleTree::<BabyBear, HV>::commit(leaves.clone());
+
+        let (opened, proof) = tree.open_batch(&[10, 0, 1, 5, 1]);
+        assert_eq!(proof.indices, [0, 1, 5, 10]);
+        assert_eq!(opened, [leaves[0], leaves[1], leaves[5], leaves[10]]);
+        MerkleTree::verify_batch(&proof, &opened, root).unwrap();
+
+        // The paths share their siblings, so the proof is smaller than the individual paths.
+        assert!(proof.siblings.len() < proof.indices.len() * tree.height());
+
+        let mut tampered = opened.clone();
+        tampered.swap(0, 1);
+        assert_eq!(
+            MerkleTree::verify_batch(&proof, &tampered, root),
+            Err(MerkleTreeError::RootMismatch)
+        );
+        assert_eq!(
+            MerkleTree::verify_batch(&proof, &opened[1..], root),
+            Err(MerkleTreeError::InvalidShape)
+        );
+    }
+
+    #[test]
+    fn test_verify_multi() {
+        let leaves = random_leaves(11);
+        let (root, tree) = MerkleTree::<BabyBear, HV>::commit(leaves);
+        let (opened, proof) = tree.open_batch(&[0, 1, 5, 10]);
+
+        let mut builder = Builder::<InnerConfig>::default();
+        let mut constant = |digest: Digest| digest.map(|x| -> Felt<_> { builder.eval(x) });
+        let opened = opened.into_iter().map(&mut constant).collect::<Vec<_>>();
+        let siblings = proof.siblings.into_iter().map(&mut constant).collect::<Vec<_>>();
+        let root = constant(root);
+        verify_multi::<InnerConfig, HV>(
+            &mut builder,
+            &proof.indices,
+            proof.height,
+            &opened,
+            &siblings,
+            root,
+        );
+
+        run_test_recursion(builder.operations, None);
+    }
+}
@@ -6,7 +6,7 @@ use p3_matrix::dense::RowMajorMatrix;
 use p3_air::Air;
 use p3_baby_bear::BabyBear;
 use p3_commit::{Pcs, TwoAdicMultiplicativeCoset};
-use p3_field::TwoAdicField;
+use p3_field::{AbstractField, TwoAdicField};
 use sp1_stark::{ShardCommitment, ShardOpenedValues, Val};
 
 use p3_commit::PolynomialSpace;
@@ -23,9 +23,11 @@ use crate::{
 };
 
 use crate::{
-    challenger::FieldChallengerVariable, constraints::RecursiveVerifierConstraintFolder,
-    domain::PolynomialSpaceVariable, fri::verify_two_adic_pcs, BabyBearFriConfigVariable,
-    TwoAdicPcsRoundVariable, VerifyingKeyVariable,
+    challenger::FieldChallengerVariable,
+    constraints::RecursiveVerifierConstraintFolder,
+    domain::PolynomialSpaceVariable,
+    fri::{verify_two_adic_pcs, FriParameters},
+    BabyBearFriConfigVariable, TwoAdicPcsRoundVariable, VerifyingKeyVariable,
 };
 
 /// Reference: [sp1_core::stark::ShardProof]
@@ -84,6 +86,10 @@ where
         A: for<'a> Air<RecursiveVerifierConstraintFolder<'a, C>>,
     {
         let chips = machine.shard_chips_ordered(&proof.chip_ordering).collect::<Vec<_>>();
+        assert!(
+            machine.uses_one_alternative(&proof.chip_ordering),
+            "shard must use exactly one chip of every group of alternatives"
+        );
 
         let ShardProofVariable {
             commitment,
@@ -105,6 +111,16 @@ where
 
         let ShardCommitment { main_commit, permutation_commit, quotient_commit } = *commitment;
 
+        // Observe the chip set of the machine, and which of its chips are present in the shard.
+        for id in machine.chip_set_id() {
+            let id: Felt<_> = builder.eval(*id);
+            challenger.observe(builder, id);
+        }
+        for present in machine.chip_presence(chip_ordering) {
+            let present: Felt<_> = builder.eval(C::F::from_bool(present));
+            challenger.observe(builder, present);
+        }
+
         let permutation_challenges =
             (0..2).map(|_| challenger.sample_ext(builder)).collect::<Vec<_>>();
 
@@ -120,7 +136,14 @@ where
             .chip_information
             .iter()
             .map(|(name, domain, _)| {
-                let i = chip_ordering[name];
+                // The preprocessed traces of unused alternatives are not opened.
+                let Some(&i) = chip_ordering.get(name) else {
+                    return TwoAdicPcsMatsVariable::<C> {
+                        domain: *domain,
+                        points: vec![],
+                        values: vec![],
+                    };
+                };
                 let values = opened_values.chips[i].preprocessed.clone();
                 TwoAdicPcsMatsVariable::<C> {
                     domain: *domain,
@@ -200,8 +223,9 @@ where
 
         // Verify the pcs proof
         builder.cycle_tracker_v2_enter("stage-d-verify-pcs".to_string());
-        let config = machine.config().fri_config();
-        verify_two_adic_pcs::<C, SC>(builder, config, opening_proof, challenger, rounds);
+        let fri_params =
+            vk.fri_params.unwrap_or_else(|| FriParameters::from(machine.config().fri_config()));
+        verify_two_adic_pcs::<C, SC>(builder, &fri_params, opening_proof, challenger, rounds);
         builder.cycle_tracker_v2_exit();
 
         // Verify the constrtaint evaluations.
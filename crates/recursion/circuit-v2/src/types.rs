@@ -8,8 +8,8 @@ use sp1_recursion_compiler::ir::{Builder, Ext, Felt};
 use sp1_recursion_core_v2::DIGEST_SIZE;
 
 use crate::{
-    challenger::CanObserveVariable, hash::FieldHasherVariable, BabyBearFriConfigVariable,
-    CircuitConfig,
+    challenger::CanObserveVariable, fri::FriParameters, hash::FieldHasherVariable,
+    BabyBearFriConfigVariable, CircuitConfig,
 };
 
 /// Reference: [sp1_core::stark::StarkVerifyingKey]
@@ -19,6 +19,12 @@ pub struct VerifyingKeyVariable<C: CircuitConfig<F = SC::Val>, SC: BabyBearFriCo
     pub pc_start: Felt<C::F>,
     pub chip_information: Vec<(String, TwoAdicMultiplicativeCoset<C::F>, Dimensions)>,
     pub chip_ordering: HashMap<String, usize>,
+    /// The FRI parameters the verified proofs were generated with.
+    ///
+    /// If `None`, the parameters of the verifying machine's configuration are used. Otherwise the
+    /// parameters are bound into [`VerifyingKeyVariable::hash`], so that proofs generated under a
+    /// different security preset cannot be passed off under the same digest.
+    pub fri_params: Option<FriParameters>,
 }
 
 #[derive(Clone)]
@@ -85,8 +91,17 @@ impl<C: CircuitConfig<F = SC::Val>, SC: BabyBearFriConfigVariable<C>> VerifyingK
         challenger.observe(builder, self.pc_start);
     }
 
+    /// Sets the FRI parameters the verified proofs were generated with.
+    pub fn with_fri_params(mut self, fri_params: FriParameters) -> Self {
+        self.fri_params = Some(fri_params);
+        self
+    }
+
     /// Hash the verifying key + prep domains into a single digest.
-    /// poseidon2( commit[0..8] || pc_start || prep_domains[N].{log_n, .size, .shift, .g})
+    /// poseidon2( commit[0..8] || pc_start || prep_domains[N].{log_n, .size, .shift, .g}
+    ///     || [log_blowup, num_queries, proof_of_work_bits])
+    ///
+    /// The FRI parameters are only included if they are set.
     pub fn hash(&self, builder: &mut Builder<C>) -> SC::Digest
     where
         C::F: TwoAdicField,
@@ -105,6 +120,13 @@ impl<C: CircuitConfig<F = SC::Val>, SC: BabyBearFriConfigVariable<C>> VerifyingK
             inputs.push(builder.eval(domain.shift));
             inputs.push(builder.eval(g));
         }
+        if let Some(fri_params) = self.fri_params {
+            for param in
+                [fri_params.log_blowup, fri_params.num_queries, fri_params.proof_of_work_bits]
+            {
+                inputs.push(builder.eval(C::F::from_canonical_usize(param)));
+            }
+        }
 
         SC::hash(builder, &inputs)
     }
@@ -123,7 +123,7 @@ pub(crate) mod tests {
     use sp1_recursion_compiler::{asm::AsmConfig, circuit::AsmCompiler, ir::DslIr};
 
     use sp1_recursion_compiler::ir::TracedVec;
-    use sp1_recursion_core_v2::{machine::RecursionAir, Runtime};
+    use sp1_recursion_core_v2::{machine::RecursionAir, Runtime, RuntimeError};
     use sp1_stark::{
         baby_bear_poseidon2::BabyBearPoseidon2, CpuProver, InnerChallenge, InnerVal, MachineProver,
     };
@@ -171,6 +171,17 @@ pub(crate) mod tests {
         }
     }
 
+    /// Compiles and runs a program without proving it, for the tests of programs whose assertions
+    /// must fail.
+    pub(crate) fn run_recursion(
+        operations: TracedVec<DslIr<AsmConfig<F, EF>>>,
+    ) -> Result<(), RuntimeError<F, EF>> {
+        let mut compiler = AsmCompiler::<AsmConfig<F, EF>>::default();
+        let program = Arc::new(compiler.compile(operations));
+        let mut runtime = Runtime::<F, EF, _>::new(program, SC::default().perm);
+        runtime.run()
+    }
+
     #[allow(dead_code)]
     pub(crate) fn run_test_recursion(
         operations: TracedVec<DslIr<AsmConfig<F, EF>>>,
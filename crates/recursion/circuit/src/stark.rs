@@ -77,6 +77,17 @@ where
 
         let ShardCommitment { main_commit, permutation_commit, quotient_commit } = commitment;
 
+        // Observe the chip set of the machine, and which of its chips are present in the shard.
+        for id in machine.chip_set_id() {
+            let id: Felt<_> = builder.eval(*id);
+            challenger.observe(builder, id);
+        }
+        for chip in machine.chips() {
+            let present: Felt<_> =
+                builder.eval(C::F::from_bool(sorted_chips.contains(&chip.name())));
+            challenger.observe(builder, present);
+        }
+
         let permutation_challenges =
             (0..2).map(|_| challenger.sample_ext(builder)).collect::<Vec<_>>();
 
@@ -79,22 +79,27 @@ impl<C: Config> CircuitV2Builder<C> for Builder<C> {
     /// Applies the Poseidon2 permutation to the given array.
     fn poseidon2_permute_v2(&mut self, array: [Felt<C::F>; WIDTH]) -> [Felt<C::F>; WIDTH] {
         let output: [Felt<C::F>; WIDTH] = core::array::from_fn(|_| self.uninit());
-        self.operations.push(DslIr::CircuitV2Poseidon2PermuteBabyBear(Box::new((output, array))));
+        self.push(DslIr::CircuitV2Poseidon2PermuteBabyBear(Box::new((output, array))));
         output
     }
 
     /// Applies the Poseidon2 permutation to the given array.
     ///
+    /// The sponge state is carried between the permutations by the Poseidon2 chips, so only the
+    /// input is read from memory and only the digest is written to it.
+    ///
     /// Reference: [p3_symmetric::PaddingFreeSponge]
     fn poseidon2_hash_v2(&mut self, input: &[Felt<C::F>]) -> [Felt<C::F>; DIGEST_SIZE] {
-        // static_assert(RATE < WIDTH)
-        let mut state = core::array::from_fn(|_| self.eval(C::F::zero()));
-        for input_chunk in input.chunks(HASH_RATE) {
-            state[..input_chunk.len()].copy_from_slice(input_chunk);
-            state = self.poseidon2_permute_v2(state);
+        // The sponge is never permuted for an empty input.
+        if input.is_empty() {
+            return core::array::from_fn(|_| self.eval(C::F::zero()));
+        }
+        for (i, input_chunk) in input.chunks(HASH_RATE).enumerate() {
+            self.push(DslIr::CircuitV2Poseidon2AbsorbBabyBear(input_chunk.to_vec(), i == 0));
         }
-        let state: [Felt<C::F>; DIGEST_SIZE] = state[..DIGEST_SIZE].try_into().unwrap();
-        state
+        let output: [Felt<C::F>; DIGEST_SIZE] = core::array::from_fn(|_| self.uninit());
+        self.push(DslIr::CircuitV2Poseidon2FinalizeBabyBear(Box::new(output)));
+        output
     }
 
     /// Applies the Poseidon2 compression function to the given array.
@@ -166,14 +171,14 @@ impl<C: Config> CircuitV2Builder<C> for Builder<C> {
     /// Hint a vector of felts.
     fn hint_felts_v2(&mut self, len: usize) -> Vec<Felt<C::F>> {
         let arr = std::iter::from_fn(|| Some(self.uninit())).take(len).collect::<Vec<_>>();
-        self.operations.push(DslIr::CircuitV2HintFelts(arr.clone()));
+        self.push(DslIr::CircuitV2HintFelts(arr.clone()));
         arr
     }
 
     /// Hint a vector of exts.
     fn hint_exts_v2(&mut self, len: usize) -> Vec<Ext<C::F, C::EF>> {
         let arr = std::iter::from_fn(|| Some(self.uninit())).take(len).collect::<Vec<_>>();
-        self.operations.push(DslIr::CircuitV2HintExts(arr.clone()));
+        self.push(DslIr::CircuitV2HintExts(arr.clone()));
         arr
     }
 }
@@ -18,7 +18,7 @@ use vec_map::VecMap;
 
 use sp1_recursion_core_v2::*;
 
-use crate::prelude::*;
+use crate::{circuit::CircuitV2Report, prelude::*};
 
 /// The backend for the circuit compiler.
 #[derive(Debug, Clone, Default)]
@@ -267,6 +267,24 @@ where
         }))
     }
 
+    fn poseidon2_absorb(
+        &mut self,
+        src: impl IntoIterator<Item = impl Reg<C>>,
+        reset: bool,
+    ) -> Instruction<C::F> {
+        Instruction::Poseidon2Absorb(Box::new(Poseidon2AbsorbInstr {
+            reset,
+            input: src.into_iter().map(|r| r.read(self)).collect(),
+        }))
+    }
+
+    fn poseidon2_finalize(&mut self, dst: [impl Reg<C>; DIGEST_SIZE]) -> Instruction<C::F> {
+        Instruction::Poseidon2Finalize(Box::new(Poseidon2FinalizeInstr {
+            output: dst.map(|r| r.write(self)),
+            mults: [C::F::zero(); DIGEST_SIZE],
+        }))
+    }
+
     fn exp_reverse_bits(
         &mut self,
         dst: impl Reg<C>,
@@ -461,6 +479,10 @@ where
             DslIr::CircuitV2Poseidon2PermuteBabyBear(data) => {
                 f(self.poseidon2_permute(data.0, data.1))
             }
+            DslIr::CircuitV2Poseidon2AbsorbBabyBear(src, reset) => {
+                f(self.poseidon2_absorb(src, reset))
+            }
+            DslIr::CircuitV2Poseidon2FinalizeBabyBear(dst) => f(self.poseidon2_finalize(*dst)),
             DslIr::CircuitV2ExpReverseBits(dst, base, exp) => {
                 f(self.exp_reverse_bits(dst, base, exp))
             }
@@ -568,6 +590,10 @@ where
                         } = instr.as_mut();
                         mults.iter_mut().zip(addrs).for_each(&mut backfill);
                     }
+                    Instruction::Poseidon2Finalize(instr) => {
+                        let Poseidon2FinalizeInstr { output: ref addrs, mults } = instr.as_mut();
+                        mults.iter_mut().zip(addrs).for_each(&mut backfill);
+                    }
                     Instruction::ExpReverseBitsLen(ExpReverseBitsInstr {
                         addrs: ExpReverseBitsIo { result: ref addr, .. },
                         mult,
@@ -601,7 +627,9 @@ where
                         panic!("mem instructions should be produced through the `consts` map")
                     }
                     // Instructions that do not write to memory.
-                    Instruction::CommitPublicValues(_) | Instruction::Print(_) => (),
+                    Instruction::CommitPublicValues(_)
+                    | Instruction::Print(_)
+                    | Instruction::Poseidon2Absorb(_) => (),
                 }
             }
         });
@@ -630,7 +658,23 @@ where
             }
         });
         debug_assert!(self.addr_to_mult.is_empty());
-        RecursionProgram { instructions, total_memory, traces }
+        RecursionProgram { instructions, total_memory, traces, ..Default::default() }
+    }
+
+    /// Compile the operations of a builder, reporting the size of the program under `name`.
+    pub fn compile_with_report<F>(
+        &mut self,
+        name: impl Into<String>,
+        builder: Builder<C>,
+    ) -> (RecursionProgram<C::F>, CircuitV2Report)
+    where
+        F: PrimeField + TwoAdicField,
+        C: Config<N = F, F = F> + Debug,
+    {
+        let counts = builder.circuit_v2_counts();
+        let program = self.compile(builder.operations);
+        let report = CircuitV2Report::new(name, counts, program.instructions.len());
+        (program, report)
     }
 }
 
@@ -641,6 +685,8 @@ const fn instr_name<F>(instr: &Instruction<F>) -> &'static str {
         Instruction::ExtAlu(_) => "ExtAlu",
         Instruction::Mem(_) => "Mem",
         Instruction::Poseidon2(_) => "Poseidon2",
+        Instruction::Poseidon2Absorb(_) => "Poseidon2Absorb",
+        Instruction::Poseidon2Finalize(_) => "Poseidon2Finalize",
         Instruction::ExpReverseBitsLen(_) => "ExpReverseBitsLen",
         Instruction::HintBits(_) => "HintBits",
         Instruction::FriFold(_) => "FriFold",
@@ -865,7 +911,8 @@ mod tests {
         }
 
         // Run with the poseidon2 skinny chip.
-        let skinny_machine = RecursionAir::<_, 9, 0>::machine(BabyBearPoseidon2::compressed());
+        let skinny_machine =
+            RecursionAir::<_, 9, 0>::machine_skinny(BabyBearPoseidon2::compressed());
         let (pk, vk) = skinny_machine.setup(&program);
         let result = run_test_machine(vec![record.clone()], skinny_machine, pk, vk);
         if let Err(e) = result {
@@ -940,6 +987,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_poseidon2_hash_lengths() {
+        setup_logger();
+
+        let perm = inner_perm();
+        let hasher = InnerHash::new(perm.clone());
+        let mut rng =
+            StdRng::seed_from_u64(0x5EED5EED).sample_iter::<F, _>(rand::distributions::Standard);
+
+        let mut builder = AsmBuilder::<F, EF>::default();
+        for len in [0, 1, 7, 8, 9, 16, 21] {
+            let input: Vec<F> = (&mut rng).take(len).collect();
+            let expected = hasher.hash_iter(input.clone());
+
+            let input_felts: Vec<Felt<_>> = input.into_iter().map(|x| builder.eval(x)).collect();
+            let result = builder.poseidon2_hash_v2(&input_felts);
+            for (actual_f, expected_f) in zip(result, expected) {
+                builder.assert_felt_eq(actual_f, expected_f);
+            }
+
+            // A permutation between two hashes must not be chained with either of them.
+            let state: [F; WIDTH] = core::array::from_fn(|_| rng.next().unwrap());
+            let expected = perm.permute(state);
+            let state_felts: [Felt<_>; WIDTH] = state.map(|x| builder.eval(x));
+            let result = builder.poseidon2_permute_v2(state_felts);
+            for (actual_f, expected_f) in zip(result, expected) {
+                builder.assert_felt_eq(actual_f, expected_f);
+            }
+        }
+
+        test_operations(builder.operations);
+    }
+
     #[test]
     fn test_exp_reverse_bits() {
         setup_logger();
@@ -1,8 +1,10 @@
 mod builder;
 mod compiler;
+mod report;
 
 pub use builder::*;
 pub use compiler::*;
+pub use report::*;
 
 #[cfg(test)]
 mod tests {
@@ -31,7 +33,7 @@ mod tests {
 
     use crate::{
         asm::AsmBuilder,
-        circuit::{AsmCompiler, CircuitV2Builder},
+        circuit::{AsmCompiler, CircuitV2Builder, CircuitV2Counts},
         ir::*,
     };
 
@@ -105,6 +107,7 @@ mod tests {
             A::Poseidon2Wide(Poseidon2WideChip::<DEGREE> {
                 fixed_log2_rows: Some(((POSEIDON_OPERATIONS - 1).ilog2() + 1) as usize),
                 pad: true,
+                alternative: false,
             }),
             A::ExpReverseBitsLen(ExpReverseBitsLenChip::<DEGREE> {
                 fixed_log2_rows: Some(((EXP_REVERSE_BITS_LEN_OPERATIONS - 1).ilog2() + 1) as usize),
@@ -172,6 +175,29 @@ mod tests {
         tracing::info!("num shard proofs: {}", result.shard_proofs.len());
     }
 
+    #[test]
+    fn test_circuit_v2_report() {
+        let mut builder = AsmBuilder::<F, EF>::default();
+
+        let felts = builder.hint_felts_v2(3);
+        let _ = builder.hint_exts_v2(2);
+        let _ = builder.hint_ext_v2();
+        let state = core::array::from_fn(|i| felts[i % felts.len()]);
+        let _ = builder.poseidon2_permute_v2(state);
+        let _ = builder.poseidon2_hash_v2(&felts);
+
+        let counts = builder.circuit_v2_counts();
+        assert_eq!(
+            counts,
+            CircuitV2Counts { felts_witnessed: 3, exts_witnessed: 3, poseidon2_permutations: 2 }
+        );
+
+        let (program, report) = AsmCompiler::default().compile_with_report("test", builder);
+        assert_eq!(report.counts, counts);
+        assert_eq!(report.num_instructions, program.instructions.len());
+        tracing::info!("{}", report);
+    }
+
     #[test]
     fn test_empty_witness_stream() {
         let mut builder = AsmBuilder::<F, EF>::default();
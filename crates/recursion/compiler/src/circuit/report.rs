@@ -0,0 +1,65 @@
+use core::fmt;
+
+use crate::prelude::*;
+
+/// Counters kept by the [`Builder`] while a circuit-v2 program is being built.
+///
+/// Operations pushed on a sub-builder are not counted, since the circuit-v2 programs are built
+/// without control flow.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CircuitV2Counts {
+    /// The number of felts read from the witness stream or the gnark witness.
+    pub felts_witnessed: usize,
+    /// The number of extension elements read from the witness stream or the gnark witness.
+    pub exts_witnessed: usize,
+    /// The number of Poseidon2 permutations, over BabyBear or BN254.
+    pub poseidon2_permutations: usize,
+}
+
+impl CircuitV2Counts {
+    /// Updates the counters with an operation pushed to the builder.
+    pub fn record<C: Config>(&mut self, op: &DslIr<C>) {
+        match op {
+            DslIr::CircuitV2HintFelts(felts) => self.felts_witnessed += felts.len(),
+            DslIr::CircuitV2HintExts(exts) => self.exts_witnessed += exts.len(),
+            DslIr::WitnessFelt(..) => self.felts_witnessed += 1,
+            DslIr::WitnessExt(..) => self.exts_witnessed += 1,
+            DslIr::CircuitV2Poseidon2PermuteBabyBear(_)
+            | DslIr::CircuitV2Poseidon2AbsorbBabyBear(..)
+            | DslIr::CircuitPoseidon2Permute(_)
+            | DslIr::CircuitPoseidon2PermuteBabyBear(_) => self.poseidon2_permutations += 1,
+            _ => {}
+        }
+    }
+}
+
+/// The size of a compiled circuit-v2 program, such as the lift, join, compress or wrap program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitV2Report {
+    /// The name of the program.
+    pub name: String,
+    /// The counters of the builder that generated the program.
+    pub counts: CircuitV2Counts,
+    /// The number of recursion instructions, or of gnark constraints for the wrap circuit.
+    pub num_instructions: usize,
+}
+
+impl CircuitV2Report {
+    pub fn new(name: impl Into<String>, counts: CircuitV2Counts, num_instructions: usize) -> Self {
+        Self { name: name.into(), counts, num_instructions }
+    }
+}
+
+impl fmt::Display for CircuitV2Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} felts and {} exts witnessed, {} poseidon2 permutations, {} instructions",
+            self.name,
+            self.counts.felts_witnessed,
+            self.counts.exts_witnessed,
+            self.counts.poseidon2_permutations,
+            self.num_instructions
+        )
+    }
+}
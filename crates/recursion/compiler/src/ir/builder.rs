@@ -5,6 +5,8 @@ use p3_field::AbstractField;
 use sp1_core_machine::utils::sp1_debug_mode;
 use sp1_primitives::types::RecursionProgramType;
 
+use crate::circuit::CircuitV2Counts;
+
 use super::{
     Array, Config, DslIr, Ext, Felt, FromConstant, SymbolicExt, SymbolicFelt, SymbolicUsize,
     SymbolicVar, Usize, Var, Variable,
@@ -91,6 +93,7 @@ pub struct Builder<C: Config> {
     pub(crate) debug: bool,
     pub(crate) is_sub_builder: bool,
     pub program_type: RecursionProgramType,
+    pub(crate) circuit_v2_counts: CircuitV2Counts,
 }
 
 impl<C: Config> Default for Builder<C> {
@@ -115,6 +118,7 @@ impl<C: Config> Builder<C> {
             debug: false,
             is_sub_builder: false,
             program_type,
+            circuit_v2_counts: CircuitV2Counts::default(),
         };
 
         new_builder.p2_hash_num = new_builder.uninit();
@@ -143,19 +147,27 @@ impl<C: Config> Builder<C> {
             debug,
             is_sub_builder: true,
             program_type,
+            circuit_v2_counts: CircuitV2Counts::default(),
         }
     }
 
     /// Pushes an operation to the builder.
     pub fn push(&mut self, op: DslIr<C>) {
+        self.circuit_v2_counts.record(&op);
         self.operations.push(op);
     }
 
     /// Pushes an operation to the builder and records a trace if SP1_DEBUG.
     pub fn trace_push(&mut self, op: DslIr<C>) {
+        self.circuit_v2_counts.record(&op);
         self.operations.trace_push(op);
     }
 
+    /// The counters of the witnessed values and Poseidon2 permutations pushed so far.
+    pub fn circuit_v2_counts(&self) -> CircuitV2Counts {
+        self.circuit_v2_counts
+    }
+
     /// Creates an uninitialized variable.
     pub fn uninit<V: Variable<C>>(&mut self) -> V {
         V::uninit(self)
@@ -401,7 +413,7 @@ impl<C: Config> Builder<C> {
     pub fn witness_felt(&mut self) -> Felt<C::F> {
         assert!(!self.is_sub_builder, "Cannot create a witness felt with a sub builder");
         let witness = self.uninit();
-        self.operations.push(DslIr::WitnessFelt(witness, self.witness_felt_count));
+        self.push(DslIr::WitnessFelt(witness, self.witness_felt_count));
         self.witness_felt_count += 1;
         witness
     }
@@ -409,7 +421,7 @@ impl<C: Config> Builder<C> {
     pub fn witness_ext(&mut self) -> Ext<C::F, C::EF> {
         assert!(!self.is_sub_builder, "Cannot create a witness ext with a sub builder");
         let witness = self.uninit();
-        self.operations.push(DslIr::WitnessExt(witness, self.witness_ext_count));
+        self.push(DslIr::WitnessExt(witness, self.witness_ext_count));
         self.witness_ext_count += 1;
         witness
     }
@@ -215,6 +215,11 @@ pub enum DslIr<C: Config> {
     CircuitPoseidon2PermuteBabyBear(Box<[Felt<C::F>; 16]>),
     /// Permutates an array of BabyBear elements in the circuit using the skinny precompile.
     CircuitV2Poseidon2PermuteBabyBear(Box<([Felt<C::F>; 16], [Felt<C::F>; 16])>),
+    /// Absorbs up to 8 BabyBear elements into the Poseidon2 sponge of the circuit, starting a new
+    /// hash if the flag is set.
+    CircuitV2Poseidon2AbsorbBabyBear(Vec<Felt<C::F>>, bool),
+    /// Writes the digest of the Poseidon2 sponge of the circuit.
+    CircuitV2Poseidon2FinalizeBabyBear(Box<[Felt<C::F>; 8]>),
     /// Commits the public values.
     CircuitV2CommitPublicValues(Box<RecursionPublicValues<Felt<C::F>>>),
 
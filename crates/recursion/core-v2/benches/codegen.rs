@@ -0,0 +1,55 @@
+use std::{iter::once, sync::Arc};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use p3_baby_bear::BabyBear;
+use p3_field::{extension::BinomialExtensionField, AbstractField, PrimeField32};
+use sp1_recursion_core_v2::{
+    instruction as instr, BaseAluOpcode, MemAccessKind, RecursionProgram, Runtime,
+};
+use sp1_stark::baby_bear_poseidon2::BabyBearPoseidon2;
+
+type F = BabyBear;
+type EF = BinomialExtensionField<BabyBear, 4>;
+
+/// The module compiled from [`fibonacci_program`].
+mod compiled {
+    include!("../src/runtime/testdata/codegen_fibonacci.rs");
+}
+
+/// The index of the last Fibonacci number computed by [`fibonacci_program`].
+const N: u32 = 256;
+
+/// A program computing the Fibonacci numbers up to the `N`th, one base field addition each.
+fn fibonacci_program() -> RecursionProgram<F> {
+    let mut fib = vec![F::zero(), F::one()];
+    for i in 2..=N as usize {
+        fib.push(fib[i - 2] + fib[i - 1]);
+    }
+    let value = |i: u32| fib[i as usize].as_canonical_u32();
+
+    let instructions = once(instr::mem(MemAccessKind::Write, 1, 0, 0))
+        .chain(once(instr::mem(MemAccessKind::Write, 2, 1, 1)))
+        .chain((2..=N).map(|i| {
+            let mult = if i < N { 2 } else { 1 };
+            instr::base_alu(BaseAluOpcode::AddF, mult, i, i - 2, i - 1)
+        }))
+        .chain(once(instr::mem(MemAccessKind::Read, 1, N - 1, value(N - 1))))
+        .chain(once(instr::mem(MemAccessKind::Read, 1, N, value(N))))
+        .collect();
+    RecursionProgram { instructions, total_memory: N as usize + 1, ..Default::default() }
+}
+
+/// Compares the module compiled by `codegen::compile` with the interpreter of [`Runtime::run`].
+fn run(c: &mut Criterion) {
+    let program = Arc::new(fibonacci_program());
+    let perm = BabyBearPoseidon2::new().perm;
+    let new_runtime = || Runtime::<F, EF, _>::new(program.clone(), perm.clone());
+
+    let mut group = c.benchmark_group("fibonacci");
+    group.bench_function("interpreted", |b| b.iter(|| new_runtime().run().unwrap()));
+    group.bench_function("compiled", |b| b.iter(|| compiled::run(&mut new_runtime()).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, run);
+criterion_main!(benches);
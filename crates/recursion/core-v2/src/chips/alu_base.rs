@@ -139,6 +139,10 @@ impl<F: PrimeField32> MachineAir<F> for BaseAluChip {
     fn included(&self, _record: &Self::Record) -> bool {
         true
     }
+
+    fn num_real_rows(&self, record: &Self::Record) -> Option<usize> {
+        Some(record.base_alu_events.len().div_ceil(NUM_BASE_ALU_ENTRIES_PER_ROW))
+    }
 }
 
 impl<AB> Air<AB> for BaseAluChip
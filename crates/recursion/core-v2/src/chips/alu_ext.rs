@@ -137,6 +137,10 @@ impl<F: PrimeField32 + BinomiallyExtendable<D>> MachineAir<F> for ExtAluChip {
     fn included(&self, _record: &Self::Record) -> bool {
         true
     }
+
+    fn num_real_rows(&self, record: &Self::Record) -> Option<usize> {
+        Some(record.ext_alu_events.len().div_ceil(NUM_EXT_ALU_ENTRIES_PER_ROW))
+    }
 }
 
 impl<AB> Air<AB> for ExtAluChip
@@ -205,6 +205,10 @@ impl<F: PrimeField32, const DEGREE: usize> MachineAir<F> for ExpReverseBitsLenCh
     fn included(&self, _record: &Self::Record) -> bool {
         true
     }
+
+    fn num_real_rows(&self, record: &Self::Record) -> Option<usize> {
+        Some(record.exp_reverse_bits_len_events.iter().map(|event| event.exp.len()).sum())
+    }
 }
 
 impl<const DEGREE: usize> ExpReverseBitsLenChip<DEGREE> {
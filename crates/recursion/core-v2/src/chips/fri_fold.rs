@@ -226,6 +226,10 @@ impl<F: PrimeField32, const DEGREE: usize> MachineAir<F> for FriFoldChip<DEGREE>
     fn included(&self, _record: &Self::Record) -> bool {
         true
     }
+
+    fn num_real_rows(&self, record: &Self::Record) -> Option<usize> {
+        Some(record.fri_fold_events.len())
+    }
 }
 
 impl<const DEGREE: usize> FriFoldChip<DEGREE> {
@@ -119,6 +119,10 @@ impl<F: PrimeField32> MachineAir<F> for MemoryChip<F> {
     fn included(&self, _record: &Self::Record) -> bool {
         true
     }
+
+    fn num_real_rows(&self, record: &Self::Record) -> Option<usize> {
+        Some(record.mem_var_events.len().div_ceil(NUM_MEM_ENTRIES_PER_ROW))
+    }
 }
 
 impl<AB> Air<AB> for MemoryChip<AB::F>
@@ -4,6 +4,7 @@ pub mod dummy;
 pub mod exp_reverse_bits;
 pub mod fri_fold;
 pub mod mem;
+pub mod poseidon2_layout;
 pub mod poseidon2_skinny;
 pub mod poseidon2_wide;
 pub mod public_values;
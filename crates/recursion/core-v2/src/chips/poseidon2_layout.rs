@@ -0,0 +1,159 @@
+//! The layout of the Poseidon2 instructions of a program as permutations of the Poseidon2 chips.
+//!
+//! A `Poseidon2` instruction is one permutation reading its whole input from memory. An absorb is
+//! one permutation reading only the absorbed elements, the other lanes of its input being zero or
+//! the output of the previous permutation, and a finalize writes the digest from the outputs of the
+//! permutation of the last absorb. Both chips lay out one permutation per entry of
+//! [`poseidon2_rows`], in order, which is also the order of the events of the runtime.
+
+use std::array;
+
+use p3_field::Field;
+
+use crate::{
+    chips::{mem::MemoryAccessCols, poseidon2_skinny::WIDTH},
+    instruction::Instruction,
+    Address, Poseidon2AbsorbInstr, Poseidon2FinalizeInstr, DIGEST_SIZE, HASH_RATE,
+};
+
+/// The memory accesses of one permutation, and how its state is chained with its neighbours.
+#[derive(Clone, Copy, Debug)]
+pub struct Poseidon2Row<F: Copy> {
+    /// The input reads. A lane with a zero multiplicity is not read from memory.
+    pub input: [MemoryAccessCols<F>; WIDTH],
+    /// The output writes.
+    pub output: [MemoryAccessCols<F>; WIDTH],
+    /// Whether each input lane is constrained to be zero.
+    pub zero_input: [F; WIDTH],
+    /// Whether each output lane is the same lane of the input of the next permutation.
+    pub chain_output: [F; WIDTH],
+}
+
+impl<F: Field> Default for Poseidon2Row<F> {
+    fn default() -> Self {
+        let access = MemoryAccessCols { addr: Address(F::zero()), mult: F::zero() };
+        Self {
+            input: [access; WIDTH],
+            output: [access; WIDTH],
+            zero_input: [F::zero(); WIDTH],
+            chain_output: [F::zero(); WIDTH],
+        }
+    }
+}
+
+/// Lays out the Poseidon2 instructions of a program, one row per permutation.
+///
+/// # Panics
+/// Panics if an absorb continuing a hash or a finalize does not directly follow an absorb, or if an
+/// absorb does not take between one and `HASH_RATE` elements.
+pub fn poseidon2_rows<F: Field>(instructions: &[Instruction<F>]) -> Vec<Poseidon2Row<F>> {
+    let mut rows: Vec<Poseidon2Row<F>> = Vec::new();
+    // Whether the last row is an absorb that has not been finalized yet.
+    let mut sponge_open = false;
+    for instruction in instructions {
+        match instruction {
+            Instruction::Poseidon2(instr) => {
+                sponge_open = false;
+                rows.push(Poseidon2Row {
+                    input: instr
+                        .addrs
+                        .input
+                        .map(|addr| MemoryAccessCols { addr, mult: F::neg_one() }),
+                    output: array::from_fn(|i| MemoryAccessCols {
+                        addr: instr.addrs.output[i],
+                        mult: instr.mults[i],
+                    }),
+                    ..Default::default()
+                });
+            }
+            Instruction::Poseidon2Absorb(instr) => {
+                let Poseidon2AbsorbInstr { reset, input } = instr.as_ref();
+                assert!(
+                    (1..=HASH_RATE).contains(&input.len()),
+                    "an absorb takes between 1 and {HASH_RATE} elements, got {}",
+                    input.len()
+                );
+                let mut row = Poseidon2Row::default();
+                for (access, &addr) in row.input.iter_mut().zip(input) {
+                    *access = MemoryAccessCols { addr, mult: F::neg_one() };
+                }
+                if *reset {
+                    row.zero_input[input.len()..].fill(F::one());
+                } else {
+                    assert!(sponge_open, "an absorb continuing a hash must follow an absorb");
+                    rows.last_mut().unwrap().chain_output[input.len()..].fill(F::one());
+                }
+                rows.push(row);
+                sponge_open = true;
+            }
+            Instruction::Poseidon2Finalize(instr) => {
+                let Poseidon2FinalizeInstr { output, mults } = instr.as_ref();
+                assert!(sponge_open, "a finalize must follow an absorb");
+                let row = rows.last_mut().unwrap();
+                for i in 0..DIGEST_SIZE {
+                    row.output[i] = MemoryAccessCols { addr: output[i], mult: mults[i] };
+                }
+                sponge_open = false;
+            }
+            _ => {}
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+    use p3_field::AbstractField;
+
+    use super::poseidon2_rows;
+    use crate::{
+        instruction::Instruction, Address, Poseidon2AbsorbInstr, Poseidon2FinalizeInstr,
+        DIGEST_SIZE,
+    };
+
+    type F = BabyBear;
+
+    fn absorb(reset: bool, addrs: impl IntoIterator<Item = u32>) -> Instruction<F> {
+        Instruction::Poseidon2Absorb(Box::new(Poseidon2AbsorbInstr {
+            reset,
+            input: addrs.into_iter().map(|addr| Address(F::from_canonical_u32(addr))).collect(),
+        }))
+    }
+
+    #[test]
+    fn test_poseidon2_rows() {
+        let instructions = vec![
+            absorb(true, 0..8),
+            absorb(false, 8..11),
+            Instruction::Poseidon2Finalize(Box::new(Poseidon2FinalizeInstr {
+                output: core::array::from_fn(|i| Address(F::from_canonical_usize(100 + i))),
+                mults: [F::one(); DIGEST_SIZE],
+            })),
+        ];
+        let rows = poseidon2_rows(&instructions);
+        assert_eq!(rows.len(), 2);
+
+        // The first absorb reads 8 lanes and starts from a zero capacity.
+        assert!(rows[0].input[..8].iter().all(|access| access.mult == F::neg_one()));
+        assert!(rows[0].input[8..].iter().all(|access| access.mult == F::zero()));
+        assert!(rows[0].zero_input[..8].iter().all(|&flag| flag == F::zero()));
+        assert!(rows[0].zero_input[8..].iter().all(|&flag| flag == F::one()));
+
+        // The second absorb overwrites 3 lanes, and keeps the others from the first permutation.
+        assert!(rows[0].chain_output[..3].iter().all(|&flag| flag == F::zero()));
+        assert!(rows[0].chain_output[3..].iter().all(|&flag| flag == F::one()));
+        assert!(rows[1].zero_input.iter().all(|&flag| flag == F::zero()));
+        assert!(rows[1].chain_output.iter().all(|&flag| flag == F::zero()));
+
+        // Only the permutation of the last absorb writes the digest.
+        assert!(rows[0].output.iter().all(|access| access.mult == F::zero()));
+        assert!(rows[1].output[..DIGEST_SIZE].iter().all(|access| access.mult == F::one()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_poseidon2_rows_unopened_sponge() {
+        poseidon2_rows(&[absorb(false, 0..4)]);
+    }
+}
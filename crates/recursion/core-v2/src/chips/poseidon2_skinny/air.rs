@@ -53,6 +53,15 @@ where
             )
         });
 
+        // Constrain the lanes of the sponge state that are not read from memory.
+        (0..WIDTH).for_each(|i| {
+            builder.when(prep_local.zero_input[i]).assert_zero(local_row.state_var[i]);
+            builder
+                .when_transition()
+                .when(prep_local.chain_output[i])
+                .assert_eq(next_row.state_var[i], local_row.state_var[i]);
+        });
+
         self.eval_input_round(builder, local_row, prep_local, next_row);
 
         self.eval_external_round(builder, local_row, prep_local, next_row);
@@ -16,4 +16,10 @@ pub struct RoundCountersPreprocessedCols<T: Copy> {
 pub struct Poseidon2PreprocessedCols<T: Copy> {
     pub memory_preprocessed: [MemoryAccessCols<T>; WIDTH],
     pub round_counters_preprocessed: RoundCountersPreprocessedCols<T>,
+    /// Set on the input row: whether each input lane is zero, for the lanes of the first absorb of
+    /// a hash that are not read from memory.
+    pub zero_input: [T; WIDTH],
+    /// Set on the output row: whether each output lane is the same lane of the next row's input,
+    /// for the lanes carried over to the next absorb of a hash.
+    pub chain_output: [T; WIDTH],
 }
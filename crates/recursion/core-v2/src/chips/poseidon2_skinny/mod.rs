@@ -20,13 +20,16 @@ pub const NUM_ROUNDS: usize = NUM_EXTERNAL_ROUNDS + NUM_INTERNAL_ROUNDS;
 pub struct Poseidon2SkinnyChip<const DEGREE: usize> {
     pub fixed_log2_rows: Option<usize>,
     pub pad: bool,
+    /// Whether the machine also has the wide variant of the chip, in which case each shard is proven
+    /// by whichever of the two has the smaller trace.
+    pub alternative: bool,
 }
 
 impl<const DEGREE: usize> Default for Poseidon2SkinnyChip<DEGREE> {
     fn default() -> Self {
         // We only support machines with degree 9.
         assert!(DEGREE >= 9);
-        Self { fixed_log2_rows: None, pad: true }
+        Self { fixed_log2_rows: None, pad: true, alternative: false }
     }
 }
 pub fn apply_m_4<AF>(x: &mut [AF])
@@ -141,7 +144,7 @@ pub(crate) mod tests {
         runtime.run().unwrap();
 
         let config = SC::new();
-        let machine_deg_9 = B::machine(config);
+        let machine_deg_9 = B::machine_skinny(config);
         let (pk_9, vk_9) = machine_deg_9.setup(&program);
         let result_deg_9 = run_test_machine(vec![runtime.record], machine_deg_9, pk_9, vk_9);
         if let Err(e) = result_deg_9 {
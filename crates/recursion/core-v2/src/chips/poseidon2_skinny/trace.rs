@@ -1,5 +1,4 @@
 use std::{
-    array,
     borrow::{Borrow, BorrowMut},
     mem::size_of,
 };
@@ -14,13 +13,13 @@ use tracing::instrument;
 
 use crate::{
     chips::{
-        mem::MemoryAccessCols,
+        poseidon2_layout::poseidon2_rows,
         poseidon2_skinny::{
             columns::{Poseidon2 as Poseidon2Cols, NUM_POSEIDON2_COLS},
             external_linear_layer, Poseidon2SkinnyChip, NUM_EXTERNAL_ROUNDS, NUM_INTERNAL_ROUNDS,
         },
     },
-    instruction::Instruction::Poseidon2,
+    machine::poseidon2_wide_is_smaller,
     ExecutionRecord, RecursionProgram,
 };
 
@@ -116,8 +115,12 @@ impl<F: PrimeField32, const DEGREE: usize> MachineAir<F> for Poseidon2SkinnyChip
         trace
     }
 
-    fn included(&self, _record: &Self::Record) -> bool {
-        true
+    fn included(&self, record: &Self::Record) -> bool {
+        !self.alternative || !poseidon2_wide_is_smaller::<F, DEGREE>(record.poseidon2_events.len())
+    }
+
+    fn num_real_rows(&self, record: &Self::Record) -> Option<usize> {
+        Some((NUM_EXTERNAL_ROUNDS + 3) * record.poseidon2_events.len())
     }
 
     fn preprocessed_width(&self) -> usize {
@@ -125,24 +128,18 @@ impl<F: PrimeField32, const DEGREE: usize> MachineAir<F> for Poseidon2SkinnyChip
     }
 
     fn generate_preprocessed_trace(&self, program: &Self::Program) -> Option<RowMajorMatrix<F>> {
-        let instructions =
-            program.instructions.iter().filter_map(|instruction| match instruction {
-                Poseidon2(instr) => Some(instr),
-                _ => None,
-            });
-
-        let num_instructions = instructions.clone().count();
+        let perm_rows = poseidon2_rows(&program.instructions);
 
         let mut rows = vec![
             [F::zero(); PREPROCESSED_POSEIDON2_WIDTH];
-            num_instructions * (NUM_EXTERNAL_ROUNDS + 3)
+            perm_rows.len() * (NUM_EXTERNAL_ROUNDS + 3)
         ];
 
-        // Iterate over the instructions and take NUM_EXTERNAL_ROUNDS + 3 rows for each instruction.
+        // Iterate over the permutations and take NUM_EXTERNAL_ROUNDS + 3 rows for each one.
         // We have one extra round for the internal rounds, one extra round for the input,
         // and one extra round for the output.
-        instructions.zip_eq(&rows.iter_mut().chunks(NUM_EXTERNAL_ROUNDS + 3)).for_each(
-            |(instruction, row_add)| {
+        perm_rows.into_iter().zip_eq(&rows.iter_mut().chunks(NUM_EXTERNAL_ROUNDS + 3)).for_each(
+            |(perm_row, row_add)| {
                 row_add.into_iter().enumerate().for_each(|(i, row)| {
                     let cols: &mut Poseidon2PreprocessedCols<_> =
                         (*row).as_mut_slice().borrow_mut();
@@ -177,15 +174,11 @@ impl<F: PrimeField32, const DEGREE: usize> MachineAir<F> for Poseidon2SkinnyChip
                     // Set the memory columns. We read once, at the first iteration,
                     // and write once, at the last iteration.
                     if i == INPUT_ROUND_IDX {
-                        cols.memory_preprocessed = instruction
-                            .addrs
-                            .input
-                            .map(|addr| MemoryAccessCols { addr, mult: F::neg_one() });
+                        cols.memory_preprocessed = perm_row.input;
+                        cols.zero_input = perm_row.zero_input;
                     } else if i == OUTPUT_ROUND_IDX {
-                        cols.memory_preprocessed = array::from_fn(|i| MemoryAccessCols {
-                            addr: instruction.addrs.output[i],
-                            mult: instruction.mults[i],
-                        });
+                        cols.memory_preprocessed = perm_row.output;
+                        cols.chain_output = perm_row.chain_output;
                     }
                 });
             },
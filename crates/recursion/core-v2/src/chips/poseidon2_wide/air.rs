@@ -40,6 +40,7 @@ where
         let main = builder.main();
         let prepr = builder.preprocessed();
         let local_row = Self::convert::<AB::Var>(main.row_slice(0));
+        let next_row = Self::convert::<AB::Var>(main.row_slice(1));
         let prep_local = prepr.row_slice(0);
         let prep_local: &Poseidon2PreprocessedCols<_> = (*prep_local).borrow();
 
@@ -55,9 +56,9 @@ where
         // For now, include only memory constraints.
         (0..WIDTH).for_each(|i| {
             builder.send_single(
-                prep_local.input[i],
+                prep_local.input[i].addr,
                 local_row.external_rounds_state()[0][i],
-                prep_local.is_real_neg,
+                prep_local.input[i].mult,
             )
         });
 
@@ -69,6 +70,17 @@ where
             )
         });
 
+        // Constrain the lanes of the sponge state that are not read from memory.
+        (0..WIDTH).for_each(|i| {
+            builder
+                .when(prep_local.zero_input[i])
+                .assert_zero(local_row.external_rounds_state()[0][i]);
+            builder
+                .when_transition()
+                .when(prep_local.chain_output[i])
+                .assert_eq(next_row.external_rounds_state()[0][i], local_row.perm_output()[i]);
+        });
+
         // Apply the external rounds.
         for r in 0..NUM_EXTERNAL_ROUNDS {
             self.eval_external_round(builder, local_row.as_ref(), r);
@@ -1,14 +1,16 @@
 use sp1_derive::AlignedBorrow;
 
-use crate::{
-    chips::{mem::MemoryAccessCols, poseidon2_wide::WIDTH},
-    Address,
-};
+use crate::chips::{mem::MemoryAccessCols, poseidon2_wide::WIDTH};
 
 #[derive(AlignedBorrow, Clone, Copy, Debug)]
 #[repr(C)]
 pub struct Poseidon2PreprocessedCols<T: Copy> {
-    pub input: [Address<T>; WIDTH],
+    pub input: [MemoryAccessCols<T>; WIDTH],
     pub output: [MemoryAccessCols<T>; WIDTH],
-    pub is_real_neg: T,
+    /// Whether each input lane is zero, for the lanes of the first absorb of a hash that are not
+    /// read from memory.
+    pub zero_input: [T; WIDTH],
+    /// Whether each output lane is the same lane of the next row's input, for the lanes carried
+    /// over to the next absorb of a hash.
+    pub chain_output: [T; WIDTH],
 }
@@ -25,11 +25,14 @@ pub const NUM_ROUNDS: usize = NUM_EXTERNAL_ROUNDS + NUM_INTERNAL_ROUNDS;
 pub struct Poseidon2WideChip<const DEGREE: usize> {
     pub fixed_log2_rows: Option<usize>,
     pub pad: bool,
+    /// Whether the machine also has the skinny variant of the chip, in which case each shard is proven
+    /// by whichever of the two has the smaller trace.
+    pub alternative: bool,
 }
 
 impl<const DEGREE: usize> Default for Poseidon2WideChip<DEGREE> {
     fn default() -> Self {
-        Self { fixed_log2_rows: None, pad: true }
+        Self { fixed_log2_rows: None, pad: true, alternative: false }
     }
 }
 
@@ -14,13 +14,13 @@ use p3_matrix::Matrix;
 
 use crate::{
     chips::{
-        mem::MemoryAccessCols,
+        poseidon2_layout::poseidon2_rows,
         poseidon2_wide::{
             columns::permutation::permutation_mut, external_linear_layer_immut, Poseidon2WideChip,
             NUM_EXTERNAL_ROUNDS, WIDTH,
         },
     },
-    instruction::Instruction::Poseidon2,
+    machine::poseidon2_wide_is_smaller,
     ExecutionRecord, RecursionProgram,
 };
 
@@ -83,8 +83,12 @@ impl<F: PrimeField32, const DEGREE: usize> MachineAir<F> for Poseidon2WideChip<D
         trace
     }
 
-    fn included(&self, _record: &Self::Record) -> bool {
-        true
+    fn included(&self, record: &Self::Record) -> bool {
+        !self.alternative || poseidon2_wide_is_smaller::<F, DEGREE>(record.poseidon2_events.len())
+    }
+
+    fn num_real_rows(&self, record: &Self::Record) -> Option<usize> {
+        Some(record.poseidon2_events.len())
     }
 
     fn preprocessed_width(&self) -> usize {
@@ -92,33 +96,21 @@ impl<F: PrimeField32, const DEGREE: usize> MachineAir<F> for Poseidon2WideChip<D
     }
 
     fn generate_preprocessed_trace(&self, program: &Self::Program) -> Option<RowMajorMatrix<F>> {
-        // Allocating an intermediate `Vec` is faster.
-        let instrs = program
-            .instructions
-            .iter() // Faster than using `rayon` for some reason. Maybe vectorization?
-            .filter_map(|instruction| match instruction {
-                Poseidon2(instr) => Some(instr.as_ref()),
-                _ => None,
-            })
-            .collect::<Vec<_>>();
-
-        let padded_nb_rows = next_power_of_two(instrs.len(), self.fixed_log2_rows);
+        let perm_rows = poseidon2_rows(&program.instructions);
+
+        let padded_nb_rows = next_power_of_two(perm_rows.len(), self.fixed_log2_rows);
         let mut values = vec![F::zero(); padded_nb_rows * PREPROCESSED_POSEIDON2_WIDTH];
 
-        let populate_len = instrs.len() * PREPROCESSED_POSEIDON2_WIDTH;
+        let populate_len = perm_rows.len() * PREPROCESSED_POSEIDON2_WIDTH;
         values[..populate_len]
             .par_chunks_mut(PREPROCESSED_POSEIDON2_WIDTH)
-            .zip_eq(instrs)
-            .for_each(|(row, instr)| {
-                // Set the memory columns. We read once, at the first iteration,
-                // and write once, at the last iteration.
+            .zip_eq(perm_rows)
+            .for_each(|(row, perm_row)| {
                 *row.borrow_mut() = Poseidon2PreprocessedCols {
-                    input: instr.addrs.input,
-                    output: std::array::from_fn(|j| MemoryAccessCols {
-                        addr: instr.addrs.output[j],
-                        mult: instr.mults[j],
-                    }),
-                    is_real_neg: F::neg_one(),
+                    input: perm_row.input,
+                    output: perm_row.output,
+                    zero_input: perm_row.zero_input,
+                    chain_output: perm_row.chain_output,
                 }
             });
         Some(RowMajorMatrix::new(values, PREPROCESSED_POSEIDON2_WIDTH))
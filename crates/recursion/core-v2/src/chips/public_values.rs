@@ -136,6 +136,15 @@ impl<F: PrimeField32> MachineAir<F> for PublicValuesChip {
     fn included(&self, _record: &Self::Record) -> bool {
         true
     }
+
+    fn num_real_rows(&self, record: &Self::Record) -> Option<usize> {
+        Some(
+            record
+                .commit_pv_hash_events
+                .first()
+                .map_or(0, |event| event.public_values.digest.len()),
+        )
+    }
 }
 
 impl<AB> Air<AB> for PublicValuesChip
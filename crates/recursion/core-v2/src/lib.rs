@@ -5,6 +5,10 @@ use serde::{Deserialize, Serialize};
 use sp1_derive::AlignedBorrow;
 use sp1_recursion_core::air::{Block, RecursionPublicValues};
 
+// Lets the modules compiled by `runtime::codegen` be included in the tests of this crate.
+#[cfg(test)]
+extern crate self as sp1_recursion_core_v2;
+
 pub mod air;
 pub mod builder;
 pub mod chips;
@@ -127,6 +131,28 @@ pub struct ExpReverseBitsIo<V> {
 pub type Poseidon2WideEvent<F> = Poseidon2Io<F>;
 pub type Poseidon2Instr<F> = Poseidon2SkinnyInstr<F>;
 
+/// An instruction absorbing between one and `HASH_RATE` elements into the Poseidon2 sponge, with
+/// one permutation, as in [p3_symmetric::PaddingFreeSponge].
+///
+/// The elements overwrite the first lanes of the state. The other lanes are zero if the absorb
+/// starts a new hash, and are otherwise the output of the previous absorb, which the Poseidon2
+/// chips carry over without going through memory. The absorbs of a hash must therefore not be
+/// interleaved with other Poseidon2 instructions.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Poseidon2AbsorbInstr<F> {
+    /// Whether the absorb starts a new hash.
+    pub reset: bool,
+    pub input: Vec<Address<F>>,
+}
+
+/// An instruction writing the digest of the Poseidon2 sponge, that is, the first `DIGEST_SIZE`
+/// lanes of the output of the previous absorb.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Poseidon2FinalizeInstr<F> {
+    pub output: [Address<F>; DIGEST_SIZE],
+    pub mults: [F; DIGEST_SIZE],
+}
+
 /// An instruction invoking the exp-reverse-bits operation.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ExpReverseBitsInstr<F> {
@@ -1,6 +1,8 @@
+use p3_air::BaseAir;
 use p3_field::{extension::BinomiallyExtendable, PrimeField32};
+use sp1_core_machine::utils::next_power_of_two;
 use sp1_recursion_core::runtime::D;
-use sp1_stark::{Chip, StarkGenericConfig, StarkMachine, PROOF_MAX_NUM_PVS};
+use sp1_stark::{air::MachineAir, Chip, StarkGenericConfig, StarkMachine, PROOF_MAX_NUM_PVS};
 
 use crate::chips::{
     alu_base::BaseAluChip,
@@ -9,11 +11,15 @@ use crate::chips::{
     exp_reverse_bits::ExpReverseBitsLenChip,
     fri_fold::FriFoldChip,
     mem::{MemoryConstChip, MemoryVarChip},
-    poseidon2_skinny::Poseidon2SkinnyChip,
+    poseidon2_skinny::{Poseidon2SkinnyChip, NUM_EXTERNAL_ROUNDS},
     poseidon2_wide::Poseidon2WideChip,
     public_values::PublicValuesChip,
 };
 
+/// The number of rows the skinny Poseidon2 chip uses per permutation: one for the input, one per
+/// external round, one for all internal rounds, and one for the output.
+const POSEIDON2_SKINNY_ROWS: usize = NUM_EXTERNAL_ROUNDS + 3;
+
 #[derive(sp1_derive::MachineAir)]
 #[sp1_core_path = "sp1_core_machine"]
 #[execution_record_path = "crate::ExecutionRecord<F>"]
@@ -45,9 +51,22 @@ pub enum RecursionAir<
 impl<F: PrimeField32 + BinomiallyExtendable<D>, const DEGREE: usize, const COL_PADDING: usize>
     RecursionAir<F, DEGREE, COL_PADDING>
 {
-    /// A recursion machine that can have dynamic trace sizes.
+    /// A recursion machine that can have dynamic trace sizes, and proves the Poseidon2
+    /// permutations of each shard with whichever of the wide and skinny chips has the smaller
+    /// trace. Requires a degree of at least 9, like the skinny chip.
     pub fn machine<SC: StarkGenericConfig<Val = F>>(config: SC) -> StarkMachine<SC, Self> {
         let chips = Self::get_all().into_iter().map(Chip::new).collect::<Vec<_>>();
+        let alternatives = vec![vec![
+            MachineAir::<F>::name(&Poseidon2SkinnyChip::<DEGREE>::default()),
+            MachineAir::<F>::name(&Poseidon2WideChip::<DEGREE>::default()),
+        ]];
+        StarkMachine::new(config, chips, PROOF_MAX_NUM_PVS).with_chip_alternatives(alternatives)
+    }
+
+    /// A recursion machine that can have dynamic trace sizes, and uses the skinny variant of
+    /// Poseidon2.
+    pub fn machine_skinny<SC: StarkGenericConfig<Val = F>>(config: SC) -> StarkMachine<SC, Self> {
+        let chips = Self::get_all_skinny().into_iter().map(Chip::new).collect::<Vec<_>>();
         StarkMachine::new(config, chips, PROOF_MAX_NUM_PVS)
     }
 
@@ -97,6 +116,26 @@ impl<F: PrimeField32 + BinomiallyExtendable<D>, const DEGREE: usize, const COL_P
     // }
 
     pub fn get_all() -> Vec<Self> {
+        vec![
+            RecursionAir::MemoryConst(MemoryConstChip::default()),
+            RecursionAir::MemoryVar(MemoryVarChip::default()),
+            RecursionAir::BaseAlu(BaseAluChip::default()),
+            RecursionAir::ExtAlu(ExtAluChip::default()),
+            RecursionAir::Poseidon2Skinny(Poseidon2SkinnyChip::<DEGREE> {
+                alternative: true,
+                ..Default::default()
+            }),
+            RecursionAir::Poseidon2Wide(Poseidon2WideChip::<DEGREE> {
+                alternative: true,
+                ..Default::default()
+            }),
+            RecursionAir::ExpReverseBitsLen(ExpReverseBitsLenChip::<DEGREE>::default()),
+            RecursionAir::FriFold(FriFoldChip::<DEGREE>::default()),
+            RecursionAir::PublicValues(PublicValuesChip::default()),
+        ]
+    }
+
+    pub fn get_all_skinny() -> Vec<Self> {
         vec![
             RecursionAir::MemoryConst(MemoryConstChip::default()),
             RecursionAir::MemoryVar(MemoryVarChip::default()),
@@ -140,6 +179,7 @@ impl<F: PrimeField32 + BinomiallyExtendable<D>, const DEGREE: usize, const COL_P
             RecursionAir::Poseidon2Skinny(Poseidon2SkinnyChip::<DEGREE> {
                 fixed_log2_rows: Some(poseidon2_padding),
                 pad: true,
+                alternative: false,
             }),
             RecursionAir::ExpReverseBitsLen(ExpReverseBitsLenChip::<DEGREE> {
                 fixed_log2_rows: Some(erbl_padding),
@@ -198,6 +238,24 @@ impl<F: PrimeField32 + BinomiallyExtendable<D>, const DEGREE: usize, const COL_P
     // }
 }
 
+/// Whether the wide Poseidon2 chip has a smaller main trace than the skinny one for a shard with
+/// the given number of permutations.
+///
+/// The wide chip uses one row per permutation and the skinny chip uses one row per external round
+/// plus three, so the comparison is between their padded areas. Ties go to the wide chip, which has
+/// fewer rows.
+pub fn poseidon2_wide_is_smaller<F: PrimeField32, const DEGREE: usize>(
+    num_permutations: usize,
+) -> bool {
+    let wide_rows = next_power_of_two(num_permutations, None);
+    let skinny_rows = next_power_of_two(num_permutations * POSEIDON2_SKINNY_ROWS, None);
+    let wide_width =
+        <Poseidon2WideChip<DEGREE> as BaseAir<F>>::width(&Poseidon2WideChip::default());
+    let skinny_width =
+        <Poseidon2SkinnyChip<DEGREE> as BaseAir<F>>::width(&Poseidon2SkinnyChip::default());
+    wide_rows * wide_width <= skinny_rows * skinny_width
+}
+
 #[cfg(test)]
 pub mod tests {
 
@@ -238,9 +296,17 @@ pub mod tests {
         }
 
         // Run with the poseidon2 skinny chip.
-        let skinny_machine = B::machine(BabyBearPoseidon2::compressed());
+        let skinny_machine = B::machine_skinny(BabyBearPoseidon2::compressed());
         let (pk, vk) = skinny_machine.setup(&program);
-        let result = run_test_machine(vec![runtime.record], skinny_machine, pk, vk);
+        let result = run_test_machine(vec![runtime.record.clone()], skinny_machine, pk, vk);
+        if let Err(e) = result {
+            panic!("Verification failed: {:?}", e);
+        }
+
+        // Run with both chips, letting the shard pick one.
+        let auto_machine = B::machine(BabyBearPoseidon2::compressed());
+        let (pk, vk) = auto_machine.setup(&program);
+        let result = run_test_machine(vec![runtime.record], auto_machine, pk, vk);
         if let Err(e) = result {
             panic!("Verification failed: {:?}", e);
         }
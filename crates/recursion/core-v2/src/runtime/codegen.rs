@@ -0,0 +1,477 @@
+//! Compiling recursion programs to Rust, to generate their witnesses without interpreting them.
+//!
+//! [`Runtime::run`] clones every instruction out of the program and dispatches on it. A program
+//! known ahead of time, such as the compress program of a fixed shape, can instead be compiled by
+//! [`compile`] into a Rust module that executes its instructions in order, with their operands
+//! inlined into calls to the [`Runtime`] method of each instruction, and be built into the prover
+//! as native code, typically from a build script:
+//!
+//! ```ignore
+//! // build.rs
+//! let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("compress.rs");
+//! std::fs::write(out, sp1_recursion_core_v2::runtime::codegen::compile(&program)).unwrap();
+//!
+//! // src/lib.rs
+//! mod compress {
+//!     include!(concat!(env!("OUT_DIR"), "/compress.rs"));
+//! }
+//!
+//! let mut runtime = Runtime::<F, EF, _>::new(program, perm);
+//! runtime.witness_stream = witness_stream.into();
+//! compress::run(&mut runtime)?;
+//! ```
+//!
+//! The compiled module fills the execution record exactly as [`Runtime::run`] does, and is only
+//! a faster way to generate the witness: the runtime must still be created with the program it
+//! was compiled from, which the preprocessed traces are built from. The module checks that the
+//! program of the runtime has the [`program_hash`] it was compiled with, and returns
+//! [`RuntimeError::CompiledProgramMismatch`] otherwise, so it must be recompiled whenever the
+//! program changes. The hash of the program of the runtime is cached in the program by
+//! [`RecursionProgram::program_hash`], so it is only computed on the first run of a program. When `RECURSION_EARLY_EXIT_TS` is set, the module defers to [`Runtime::run`],
+//! which stops at that timestamp.
+
+use std::{
+    borrow::Borrow,
+    fmt::{self, Write},
+};
+
+use itertools::Itertools;
+use p3_field::{AbstractField, ExtensionField, PrimeField32};
+use sp1_recursion_core::air::{Block, RecursionPublicValues, RECURSIVE_PROOF_NUM_PV_ELTS};
+
+use crate::{
+    runtime::instruction::{HintBitsInstr, HintExt2FeltsInstr, HintInstr, PrintInstr},
+    *,
+};
+
+/// The number of instructions compiled into each function of the module, which bounds the size of
+/// the functions rustc has to optimize.
+pub const CHUNK_SIZE: usize = 1 << 12;
+
+/// The items the compiled modules refer to.
+pub mod prelude {
+    pub use p3_field::{AbstractField, ExtensionField, PrimeField32};
+    pub use p3_poseidon2::{Poseidon2, Poseidon2ExternalMatrixGeneral};
+    pub use p3_symmetric::CryptographicPermutation;
+    pub use sp1_recursion_core::air::Block;
+
+    pub use super::{addr, check_program, felt, pv_addrs};
+    pub use crate::{
+        runtime::instruction::{
+            FieldEltType, HintBitsInstr, HintExt2FeltsInstr, HintInstr, PrintInstr,
+        },
+        Address, BaseAluInstr, BaseAluIo, BaseAluOpcode, CommitPublicValuesInstr,
+        ExpReverseBitsInstr, ExpReverseBitsIo, ExtAluInstr, ExtAluIo, ExtAluOpcode, FriFoldBaseIo,
+        FriFoldExtSingleIo, FriFoldExtVecIo, FriFoldInstr, MemAccessKind, MemInstr, MemIo,
+        Poseidon2AbsorbInstr, Poseidon2FinalizeInstr, Poseidon2Instr, Poseidon2Io, Runtime,
+        RuntimeError, PERMUTATION_WIDTH, POSEIDON2_SBOX_DEGREE,
+    };
+}
+
+/// The field element of canonical value `x`.
+#[inline(always)]
+pub fn felt<F: AbstractField>(x: u32) -> F {
+    F::from_canonical_u32(x)
+}
+
+/// The address of canonical value `x`.
+#[inline(always)]
+pub fn addr<F: AbstractField>(x: u32) -> Address<F> {
+    Address(F::from_canonical_u32(x))
+}
+
+/// The public values stored at `addrs`.
+#[inline(always)]
+pub fn pv_addrs<F: Copy>(
+    addrs: [Address<F>; RECURSIVE_PROOF_NUM_PV_ELTS],
+) -> RecursionPublicValues<Address<F>> {
+    *addrs.as_slice().borrow()
+}
+
+/// The hash of the instructions of `program`, which a compiled module must have been compiled
+/// from.
+///
+/// This is the 64-bit FNV-1a hash of the statements the instructions compile to, so it changes
+/// whenever the compiled module would.
+pub fn program_hash<F: PrimeField32>(program: &RecursionProgram<F>) -> u64 {
+    let mut hasher = Fnv1a::default();
+    for instruction in &program.instructions {
+        writeln!(hasher, "{}", compile_instruction(instruction)).unwrap();
+    }
+    hasher.0
+}
+
+/// Checks that the program of `rt` is the program a module was compiled from.
+pub fn check_program<F: PrimeField32, EF: ExtensionField<F>, Diffusion>(
+    rt: &Runtime<'_, F, EF, Diffusion>,
+    num_instructions: usize,
+    total_memory: usize,
+    hash: u64,
+) -> Result<(), RuntimeError<F, EF>> {
+    let program = &rt.program;
+    let found_program_hash = program.program_hash();
+    if program.instructions.len() != num_instructions
+        || program.total_memory != total_memory
+        || found_program_hash != hash
+    {
+        return Err(RuntimeError::CompiledProgramMismatch {
+            num_instructions,
+            total_memory,
+            program_hash: hash,
+            found_num_instructions: program.instructions.len(),
+            found_total_memory: program.total_memory,
+            found_program_hash,
+        });
+    }
+    Ok(())
+}
+
+/// A 64-bit FNV-1a hasher of the text written to it.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Write for Fnv1a {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for &byte in s.as_bytes() {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        Ok(())
+    }
+}
+
+const SIGNATURE: &str = "<F, EF, Diffusion>(
+    rt: &mut Runtime<'_, F, EF, Diffusion>,
+) -> Result<(), RuntimeError<F, EF>>
+where
+    F: PrimeField32,
+    EF: ExtensionField<F>,
+    Poseidon2<F, Poseidon2ExternalMatrixGeneral, Diffusion, PERMUTATION_WIDTH, POSEIDON2_SBOX_DEGREE>:
+        CryptographicPermutation<[F; PERMUTATION_WIDTH]>,";
+
+/// Compiles `program` into the source of a Rust module, whose `run` function executes the program
+/// on a [`Runtime`]. See the [module documentation](self).
+pub fn compile<F: PrimeField32>(program: &RecursionProgram<F>) -> String {
+    let mut out = String::new();
+    let num_chunks = program.instructions.len().div_ceil(CHUNK_SIZE);
+    writeln!(out, "// @generated by `sp1_recursion_core_v2::runtime::codegen`. Do not edit.")
+        .unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "#[allow(unused_imports)]").unwrap();
+    writeln!(out, "use ::sp1_recursion_core_v2::runtime::codegen::prelude::*;").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "/// The number of instructions of the compiled program.").unwrap();
+    writeln!(out, "pub const NUM_INSTRUCTIONS: usize = {};", program.instructions.len()).unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "/// The memory size of the compiled program.").unwrap();
+    writeln!(out, "pub const TOTAL_MEMORY: usize = {};", program.total_memory).unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "/// The hash of the instructions of the compiled program.").unwrap();
+    writeln!(out, "pub const PROGRAM_HASH: u64 = {:#018x};", program_hash(program)).unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "/// Executes the compiled program on `rt`, which must run the same program.")
+        .unwrap();
+    writeln!(out, "pub fn run{SIGNATURE}").unwrap();
+    writeln!(out, "{{").unwrap();
+    writeln!(out, "    check_program(rt, NUM_INSTRUCTIONS, TOTAL_MEMORY, PROGRAM_HASH)?;").unwrap();
+    writeln!(out, "    if std::env::var_os(\"RECURSION_EARLY_EXIT_TS\").is_some() {{").unwrap();
+    writeln!(out, "        return rt.run();").unwrap();
+    writeln!(out, "    }}").unwrap();
+    for i in 0..num_chunks {
+        writeln!(out, "    chunk_{i}(rt)?;").unwrap();
+    }
+    writeln!(out, "    Ok(())").unwrap();
+    writeln!(out, "}}").unwrap();
+
+    for (i, chunk) in program.instructions.chunks(CHUNK_SIZE).enumerate() {
+        writeln!(out).unwrap();
+        writeln!(out, "fn chunk_{i}{SIGNATURE}").unwrap();
+        writeln!(out, "{{").unwrap();
+        for instruction in chunk {
+            writeln!(out, "    {}", compile_instruction(instruction)).unwrap();
+            writeln!(out, "    rt.advance();").unwrap();
+        }
+        writeln!(out, "    Ok(())").unwrap();
+        writeln!(out, "}}").unwrap();
+    }
+    out
+}
+
+/// The statement executing `instruction`, with its operands inlined.
+fn compile_instruction<F: PrimeField32>(instruction: &Instruction<F>) -> String {
+    match instruction {
+        Instruction::BaseAlu(BaseAluInstr { opcode, mult, addrs }) => format!(
+            "rt.base_alu(BaseAluInstr {{ opcode: BaseAluOpcode::{opcode:?}, mult: {}, addrs: \
+             BaseAluIo {{ out: {}, in1: {}, in2: {} }} }})?;",
+            felt_expr(*mult),
+            addr_expr(addrs.out),
+            addr_expr(addrs.in1),
+            addr_expr(addrs.in2),
+        ),
+        Instruction::ExtAlu(ExtAluInstr { opcode, mult, addrs }) => format!(
+            "rt.ext_alu(ExtAluInstr {{ opcode: ExtAluOpcode::{opcode:?}, mult: {}, addrs: \
+             ExtAluIo {{ out: {}, in1: {}, in2: {} }} }})?;",
+            felt_expr(*mult),
+            addr_expr(addrs.out),
+            addr_expr(addrs.in1),
+            addr_expr(addrs.in2),
+        ),
+        Instruction::Mem(MemInstr { addrs, vals, mult, kind }) => format!(
+            "rt.mem(MemInstr {{ addrs: MemIo {{ inner: {} }}, vals: MemIo {{ inner: {} }}, mult: \
+             {}, kind: MemAccessKind::{kind:?} }});",
+            addr_expr(addrs.inner),
+            block_expr(&vals.inner),
+            felt_expr(*mult),
+        ),
+        Instruction::Poseidon2(instr) => format!(
+            "rt.poseidon2(Poseidon2Instr {{ addrs: Poseidon2Io {{ input: {}, output: {} }}, \
+             mults: {} }});",
+            array_expr(instr.addrs.input.iter().copied().map(addr_expr)),
+            array_expr(instr.addrs.output.iter().copied().map(addr_expr)),
+            array_expr(instr.mults.iter().copied().map(felt_expr)),
+        ),
+        Instruction::Poseidon2Absorb(instr) => format!(
+            "rt.poseidon2_absorb(Poseidon2AbsorbInstr {{ reset: {}, input: {} }});",
+            instr.reset,
+            vec_expr(instr.input.iter().copied().map(addr_expr)),
+        ),
+        Instruction::Poseidon2Finalize(instr) => format!(
+            "rt.poseidon2_finalize(Poseidon2FinalizeInstr {{ output: {}, mults: {} }});",
+            array_expr(instr.output.iter().copied().map(addr_expr)),
+            array_expr(instr.mults.iter().copied().map(felt_expr)),
+        ),
+        Instruction::ExpReverseBitsLen(ExpReverseBitsInstr { addrs, mult }) => format!(
+            "rt.exp_reverse_bits_len(ExpReverseBitsInstr {{ addrs: ExpReverseBitsIo {{ base: {}, \
+             exp: {}, result: {} }}, mult: {} }});",
+            addr_expr(addrs.base),
+            vec_expr(addrs.exp.iter().copied().map(addr_expr)),
+            addr_expr(addrs.result),
+            felt_expr(*mult),
+        ),
+        Instruction::HintBits(HintBitsInstr { output_addrs_mults, input_addr }) => format!(
+            "rt.hint_bits(HintBitsInstr {{ output_addrs_mults: {}, input_addr: {} }});",
+            vec_expr(output_addrs_mults.iter().copied().map(addr_mult_expr)),
+            addr_expr(*input_addr),
+        ),
+        Instruction::FriFold(instr) => format!(
+            "rt.fri_fold(FriFoldInstr {{ base_single_addrs: FriFoldBaseIo {{ x: {} }}, \
+             ext_single_addrs: FriFoldExtSingleIo {{ z: {}, alpha: {} }}, ext_vec_addrs: \
+             FriFoldExtVecIo {{ mat_opening: {}, ps_at_z: {}, alpha_pow_input: {}, ro_input: {}, \
+             alpha_pow_output: {}, ro_output: {} }}, alpha_pow_mults: {}, ro_mults: {} }});",
+            addr_expr(instr.base_single_addrs.x),
+            addr_expr(instr.ext_single_addrs.z),
+            addr_expr(instr.ext_single_addrs.alpha),
+            vec_expr(instr.ext_vec_addrs.mat_opening.iter().copied().map(addr_expr)),
+            vec_expr(instr.ext_vec_addrs.ps_at_z.iter().copied().map(addr_expr)),
+            vec_expr(instr.ext_vec_addrs.alpha_pow_input.iter().copied().map(addr_expr)),
+            vec_expr(instr.ext_vec_addrs.ro_input.iter().copied().map(addr_expr)),
+            vec_expr(instr.ext_vec_addrs.alpha_pow_output.iter().copied().map(addr_expr)),
+            vec_expr(instr.ext_vec_addrs.ro_output.iter().copied().map(addr_expr)),
+            vec_expr(instr.alpha_pow_mults.iter().copied().map(felt_expr)),
+            vec_expr(instr.ro_mults.iter().copied().map(felt_expr)),
+        ),
+        Instruction::CommitPublicValues(instr) => format!(
+            "rt.commit_public_values(CommitPublicValuesInstr {{ pv_addrs: pv_addrs({}) }});",
+            array_expr(instr.pv_addrs.to_vec().into_iter().map(addr_expr)),
+        ),
+        Instruction::Print(PrintInstr { field_elt_type, addr }) => format!(
+            "rt.print(PrintInstr {{ field_elt_type: FieldEltType::{field_elt_type:?}, addr: {} \
+             }})?;",
+            addr_expr(*addr),
+        ),
+        Instruction::HintExt2Felts(HintExt2FeltsInstr { output_addrs_mults, input_addr }) => {
+            format!(
+                "rt.hint_ext2_felts(HintExt2FeltsInstr {{ output_addrs_mults: {}, input_addr: {} \
+                 }});",
+                array_expr(output_addrs_mults.iter().copied().map(addr_mult_expr)),
+                addr_expr(*input_addr),
+            )
+        }
+        Instruction::Hint(HintInstr { output_addrs_mults }) => format!(
+            "rt.hint(HintInstr {{ output_addrs_mults: {} }})?;",
+            vec_expr(output_addrs_mults.iter().copied().map(addr_mult_expr)),
+        ),
+    }
+}
+
+fn felt_expr<F: PrimeField32>(x: F) -> String {
+    format!("felt({})", x.as_canonical_u32())
+}
+
+fn addr_expr<F: PrimeField32>(x: Address<F>) -> String {
+    format!("addr({})", x.0.as_canonical_u32())
+}
+
+fn addr_mult_expr<F: PrimeField32>((addr, mult): (Address<F>, F)) -> String {
+    format!("({}, {})", addr_expr(addr), felt_expr(mult))
+}
+
+fn block_expr<F: PrimeField32>(block: &Block<F>) -> String {
+    let [a, b, c, d] = block.0.map(felt_expr);
+    format!("Block::from([{a}, {b}, {c}, {d}])")
+}
+
+fn array_expr(items: impl Iterator<Item = String>) -> String {
+    format!("[{}]", items.format(", "))
+}
+
+fn vec_expr(items: impl Iterator<Item = String>) -> String {
+    format!("vec![{}]", items.format(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{array, io::sink, sync::Arc};
+
+    use p3_baby_bear::{BabyBear, DiffusionMatrixBabyBear};
+    use p3_field::extension::BinomialExtensionField;
+    use sp1_stark::baby_bear_poseidon2::BabyBearPoseidon2;
+
+    use super::*;
+    use crate::runtime::instruction::{self as instr, FieldEltType};
+
+    type F = BabyBear;
+    type EF = BinomialExtensionField<BabyBear, 4>;
+
+    /// The module compiled from [`test_program`].
+    mod compiled {
+        include!("testdata/codegen_program.rs");
+    }
+
+    /// A program executing every kind of instruction but `CommitPublicValues`.
+    fn test_program() -> RecursionProgram<F> {
+        let instructions: Vec<Instruction<F>> = vec![
+            instr::mem(MemAccessKind::Write, 3, 0, 5),
+            instr::mem(MemAccessKind::Write, 2, 1, 7),
+            instr::base_alu(BaseAluOpcode::MulF, 1, 2, 0, 1),
+            instr::ext_alu(ExtAluOpcode::AddE, 1, 3, 0, 1),
+            Instruction::HintBits(HintBitsInstr {
+                output_addrs_mults: (4..7).map(|i| (addr(i), felt(1))).collect(),
+                input_addr: addr(0),
+            }),
+            instr::exp_reverse_bits_len(1, felt(1), (4..7).map(felt).collect(), felt(7)),
+            Instruction::Hint(HintInstr {
+                output_addrs_mults: vec![(addr(8), felt(1)), (addr(9), felt(1))],
+            }),
+            Instruction::HintExt2Felts(HintExt2FeltsInstr {
+                output_addrs_mults: array::from_fn(|i| (addr(10 + i as u32), felt(1))),
+                input_addr: addr(3),
+            }),
+            instr::poseidon2(
+                [1; PERMUTATION_WIDTH],
+                array::from_fn(|i| 14 + i as u32),
+                array::from_fn(|i| i as u32 % 14),
+            ),
+            Instruction::Poseidon2Absorb(Box::new(Poseidon2AbsorbInstr {
+                reset: true,
+                input: (0..3).map(addr).collect(),
+            })),
+            Instruction::Poseidon2Finalize(Box::new(Poseidon2FinalizeInstr {
+                output: array::from_fn(|i| addr(30 + i as u32)),
+                mults: [felt(1); DIGEST_SIZE],
+            })),
+            instr::fri_fold(
+                1,
+                3,
+                0,
+                vec![2],
+                vec![3],
+                vec![1],
+                vec![0],
+                vec![38],
+                vec![39],
+                vec![1],
+                vec![1],
+            ),
+            Instruction::Print(PrintInstr { field_elt_type: FieldEltType::Base, addr: addr(2) }),
+            instr::mem(MemAccessKind::Read, 1, 2, 35),
+        ];
+        RecursionProgram { instructions, total_memory: 40, ..Default::default() }
+    }
+
+    fn new_runtime(
+        program: RecursionProgram<F>,
+    ) -> Runtime<'static, F, EF, DiffusionMatrixBabyBear> {
+        let mut runtime = Runtime::new(Arc::new(program), BabyBearPoseidon2::new().perm);
+        runtime.witness_stream =
+            vec![Block::from([felt(1), felt(2), felt(3), felt(4)]), Block::from(felt(5))].into();
+        runtime.debug_stdout = Box::new(sink());
+        runtime
+    }
+
+    #[test]
+    fn test_compile() {
+        let mut instructions = vec![
+            instr::mem(MemAccessKind::Write, 2, 0, 5),
+            instr::base_alu(BaseAluOpcode::MulF, 1, 1, 0, 0),
+            instr::mem(MemAccessKind::Read, 1, 1, 25),
+        ];
+        instructions
+            .extend((0..CHUNK_SIZE).map(|i| instr::mem(MemAccessKind::Write, 0, i as u32 + 2, 1)));
+        let program = RecursionProgram { instructions, total_memory: 7, ..Default::default() };
+
+        let module = compile::<F>(&program);
+        let hash = program_hash(&program);
+        assert!(module.contains(&format!("NUM_INSTRUCTIONS: usize = {};", CHUNK_SIZE + 3)));
+        assert!(module.contains(&format!("PROGRAM_HASH: u64 = {hash:#018x};")));
+        assert!(
+            module.contains("check_program(rt, NUM_INSTRUCTIONS, TOTAL_MEMORY, PROGRAM_HASH)?;")
+        );
+        assert!(module.contains("    chunk_0(rt)?;\n    chunk_1(rt)?;\n"));
+        assert!(!module.contains("chunk_2"));
+        assert!(module.contains(
+            "rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::MulF, mult: felt(1), addrs: \
+             BaseAluIo { out: addr(1), in1: addr(0), in2: addr(0) } })?;\n    rt.advance();"
+        ));
+        assert!(module.contains(
+            "rt.mem(MemInstr { addrs: MemIo { inner: addr(1) }, vals: MemIo { inner: \
+             Block::from([felt(25), felt(0), felt(0), felt(0)]) }, mult: felt(1), kind: \
+             MemAccessKind::Read });"
+        ));
+
+        // The compiled module checks that the runtime runs the program it was compiled from. The
+        // hash of the program is cached, but not in its clones.
+        assert_eq!(program.program_hash(), hash);
+        let mut modified = program.clone();
+        modified.instructions[1] = instr::base_alu(BaseAluOpcode::AddF, 1, 1, 0, 0);
+        assert_ne!(program_hash(&modified), hash);
+        let runtime = new_runtime(program);
+        assert!(check_program(&runtime, CHUNK_SIZE + 3, 7, hash).is_ok());
+        assert!(matches!(
+            check_program(&runtime, CHUNK_SIZE + 3, 8, hash),
+            Err(RuntimeError::CompiledProgramMismatch { found_total_memory: 7, .. })
+        ));
+        let runtime = new_runtime(modified);
+        assert!(matches!(
+            check_program(&runtime, CHUNK_SIZE + 3, 7, hash),
+            Err(RuntimeError::CompiledProgramMismatch { program_hash, found_program_hash, .. })
+                if program_hash == hash && found_program_hash != hash
+        ));
+    }
+
+    #[test]
+    fn test_compiled_program_matches_runtime() {
+        let program = test_program();
+        assert_eq!(
+            compile(&program),
+            include_str!("testdata/codegen_program.rs"),
+            "the compiled test program is stale: write `compile(&test_program())` to \
+             `src/runtime/testdata/codegen_program.rs`"
+        );
+
+        let mut interpreted = new_runtime(program.clone());
+        interpreted.run().unwrap();
+        let mut native = new_runtime(program);
+        compiled::run(&mut native).unwrap();
+
+        assert_eq!(format!("{:?}", native.record), format!("{:?}", interpreted.record));
+        assert_eq!(format!("{:?}", native.memory), format!("{:?}", interpreted.memory));
+        assert_eq!(native.timestamp, interpreted.timestamp);
+        assert_eq!(native.pc, interpreted.pc);
+        assert_eq!(native.clk, interpreted.clk);
+    }
+}
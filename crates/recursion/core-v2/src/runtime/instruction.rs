@@ -11,6 +11,8 @@ pub enum Instruction<F> {
     ExtAlu(ExtAluInstr<F>),
     Mem(MemInstr<F>),
     Poseidon2(Box<Poseidon2Instr<F>>),
+    Poseidon2Absorb(Box<Poseidon2AbsorbInstr<F>>),
+    Poseidon2Finalize(Box<Poseidon2FinalizeInstr<F>>),
     ExpReverseBitsLen(ExpReverseBitsInstr<F>),
     HintBits(HintBitsInstr<F>),
     FriFold(Box<FriFoldInstr<F>>),
@@ -1,3 +1,4 @@
+pub mod codegen;
 pub mod instruction;
 mod memory;
 mod opcode;
@@ -125,6 +126,9 @@ pub struct Runtime<'a, F: PrimeField32, EF: ExtensionField<F>, Diffusion> {
         >,
     >,
 
+    /// The state of the Poseidon2 sponge of the absorb and finalize instructions.
+    sponge_state: [F; PERMUTATION_WIDTH],
+
     _marker_ef: PhantomData<EF>,
 
     _marker_diffusion: PhantomData<Diffusion>,
@@ -158,6 +162,19 @@ pub enum RuntimeError<F: Debug, EF: Debug> {
     DebugPrint(#[from] std::io::Error),
     #[error("attempted to read from empty witness stream")]
     EmptyWitnessStream,
+    #[error(
+        "the compiled program has {num_instructions} instructions, {total_memory} memory cells \
+        and hash {program_hash:#018x}, but the runtime's program has {found_num_instructions}, \
+        {found_total_memory} and {found_program_hash:#018x}"
+    )]
+    CompiledProgramMismatch {
+        num_instructions: usize,
+        total_memory: usize,
+        program_hash: u64,
+        found_num_instructions: usize,
+        found_total_memory: usize,
+        found_program_hash: u64,
+    },
 }
 
 impl<'a, F: PrimeField32, EF: ExtensionField<F>, Diffusion> Runtime<'a, F, EF, Diffusion>
@@ -204,6 +221,7 @@ where
             cycle_tracker: HashMap::new(),
             debug_stdout: Box::new(stdout()),
             perm: Some(perm),
+            sponge_state: [F::zero(); PERMUTATION_WIDTH],
             _marker_ef: PhantomData,
             _marker_diffusion: PhantomData,
         }
@@ -249,275 +267,343 @@ where
         while self.pc < F::from_canonical_u32(self.program.instructions.len() as u32) {
             let idx = self.pc.as_canonical_u32() as usize;
             let instruction = self.program.instructions[idx].clone();
+            self.execute(instruction)?;
+            self.advance();
 
-            let next_clk = self.clk + F::from_canonical_u32(4);
-            let next_pc = self.pc + F::one();
-            match instruction {
-                Instruction::BaseAlu(instr @ BaseAluInstr { opcode, mult, addrs }) => {
-                    self.nb_base_ops += 1;
-                    let in1 = self.memory.mr(addrs.in1).val[0];
-                    let in2 = self.memory.mr(addrs.in2).val[0];
-                    // Do the computation.
-                    let out = match opcode {
-                        BaseAluOpcode::AddF => in1 + in2,
-                        BaseAluOpcode::SubF => in1 - in2,
-                        BaseAluOpcode::MulF => in1 * in2,
-                        BaseAluOpcode::DivF => match in1.try_div(in2) {
-                            Some(x) => x,
-                            None => {
-                                // Check for division exceptions and error. Note that 0/0 is defined
-                                // to be 1.
-                                if in1.is_zero() {
-                                    AbstractField::one()
-                                } else {
-                                    return Err(RuntimeError::DivFOutOfDomain {
-                                        in1,
-                                        in2,
-                                        instr,
-                                        pc: self.pc.as_canonical_u32() as usize,
-                                        trace: self.nearest_pc_backtrace(),
-                                    });
-                                }
-                            }
-                        },
-                    };
-                    self.memory.mw(addrs.out, Block::from(out), mult);
-                    self.record.base_alu_events.push(BaseAluEvent { out, in1, in2 });
-                }
-                Instruction::ExtAlu(instr @ ExtAluInstr { opcode, mult, addrs }) => {
-                    self.nb_ext_ops += 1;
-                    let in1 = self.memory.mr(addrs.in1).val;
-                    let in2 = self.memory.mr(addrs.in2).val;
-                    // Do the computation.
-                    let in1_ef = EF::from_base_slice(&in1.0);
-                    let in2_ef = EF::from_base_slice(&in2.0);
-                    let out_ef = match opcode {
-                        ExtAluOpcode::AddE => in1_ef + in2_ef,
-                        ExtAluOpcode::SubE => in1_ef - in2_ef,
-                        ExtAluOpcode::MulE => in1_ef * in2_ef,
-                        ExtAluOpcode::DivE => match in1_ef.try_div(in2_ef) {
-                            Some(x) => x,
-                            None => {
-                                // Check for division exceptions and error. Note that 0/0 is defined
-                                // to be 1.
-                                if in1_ef.is_zero() {
-                                    AbstractField::one()
-                                } else {
-                                    return Err(RuntimeError::DivEOutOfDomain {
-                                        in1: in1_ef,
-                                        in2: in2_ef,
-                                        instr,
-                                        pc: self.pc.as_canonical_u32() as usize,
-                                        trace: self.nearest_pc_backtrace(),
-                                    });
-                                }
-                            }
-                        },
-                    };
-                    let out = Block::from(out_ef.as_base_slice());
-                    self.memory.mw(addrs.out, out, mult);
-                    self.record.ext_alu_events.push(ExtAluEvent { out, in1, in2 });
-                }
-                Instruction::Mem(MemInstr {
-                    addrs: MemIo { inner: addr },
-                    vals: MemIo { inner: val },
-                    mult,
-                    kind,
-                }) => {
-                    self.nb_memory_ops += 1;
-                    match kind {
-                        MemAccessKind::Read => {
-                            let mem_entry = self.memory.mr_mult(addr, mult);
-                            assert_eq!(
-                                mem_entry.val, val,
-                                "stored memory value should be the specified value"
-                            );
-                        }
-                        MemAccessKind::Write => drop(self.memory.mw(addr, val, mult)),
-                    }
-                    self.record.mem_const_count += 1;
-                }
-                Instruction::Poseidon2(instr) => {
-                    let Poseidon2Instr { addrs: Poseidon2Io { input, output }, mults } = *instr;
-                    self.nb_poseidons += 1;
-                    let in_vals = std::array::from_fn(|i| self.memory.mr(input[i]).val[0]);
-                    let perm_output = self.perm.as_ref().unwrap().permute(in_vals);
-
-                    perm_output.iter().zip(output).zip(mults).for_each(|((&val, addr), mult)| {
-                        self.memory.mw(addr, Block::from(val), mult);
-                    });
-                    self.record
-                        .poseidon2_events
-                        .push(Poseidon2Event { input: in_vals, output: perm_output });
-                }
-                Instruction::ExpReverseBitsLen(ExpReverseBitsInstr {
-                    addrs: ExpReverseBitsIo { base, exp, result },
-                    mult,
-                }) => {
-                    self.nb_exp_reverse_bits += 1;
-                    let base_val = self.memory.mr(base).val[0];
-                    let exp_bits: Vec<_> =
-                        exp.iter().map(|bit| self.memory.mr(*bit).val[0]).collect();
-                    let exp_val = exp_bits
-                        .iter()
-                        .enumerate()
-                        .fold(0, |acc, (i, &val)| acc + val.as_canonical_u32() * (1 << i));
-                    let out =
-                        base_val.exp_u64(reverse_bits_len(exp_val as usize, exp_bits.len()) as u64);
-                    self.memory.mw(result, Block::from(out), mult);
-                    self.record.exp_reverse_bits_len_events.push(ExpReverseBitsEvent {
-                        result: out,
-                        base: base_val,
-                        exp: exp_bits,
-                    });
-                }
-                Instruction::HintBits(HintBitsInstr { output_addrs_mults, input_addr }) => {
-                    self.nb_bit_decompositions += 1;
-                    let num = self.memory.mr_mult(input_addr, F::zero()).val[0].as_canonical_u32();
-                    // Decompose the num into LE bits.
-                    let bits = (0..output_addrs_mults.len())
-                        .map(|i| Block::from(F::from_canonical_u32((num >> i) & 1)))
-                        .collect::<Vec<_>>();
-                    // Write the bits to the array at dst.
-                    for (bit, (addr, mult)) in bits.into_iter().zip(output_addrs_mults) {
-                        self.memory.mw(addr, bit, mult);
-                        self.record.mem_var_events.push(MemEvent { inner: bit });
-                    }
-                }
+            if self.timestamp >= early_exit_ts {
+                break;
+            }
+        }
+        Ok(())
+    }
 
-                Instruction::FriFold(instr) => {
-                    let FriFoldInstr {
-                        base_single_addrs,
-                        ext_single_addrs,
-                        ext_vec_addrs,
-                        alpha_pow_mults,
-                        ro_mults,
-                    } = *instr;
-                    self.nb_fri_fold += 1;
-                    let x = self.memory.mr(base_single_addrs.x).val[0];
-                    let z = self.memory.mr(ext_single_addrs.z).val;
-                    let z: EF = z.ext();
-                    let alpha = self.memory.mr(ext_single_addrs.alpha).val;
-                    let alpha: EF = alpha.ext();
-                    let mat_opening = ext_vec_addrs
-                        .mat_opening
-                        .iter()
-                        .map(|addr| self.memory.mr(*addr).val)
-                        .collect_vec();
-                    let ps_at_z = ext_vec_addrs
-                        .ps_at_z
-                        .iter()
-                        .map(|addr| self.memory.mr(*addr).val)
-                        .collect_vec();
-
-                    for m in 0..ps_at_z.len() {
-                        // let m = F::from_canonical_u32(m);
-                        // Get the opening values.
-                        let p_at_x = mat_opening[m];
-                        let p_at_x: EF = p_at_x.ext();
-                        let p_at_z = ps_at_z[m];
-                        let p_at_z: EF = p_at_z.ext();
-
-                        // Calculate the quotient and update the values
-                        let quotient = (-p_at_z + p_at_x) / (-z + x);
-
-                        // First we peek to get the current value.
-                        let alpha_pow: EF =
-                            self.memory.mr(ext_vec_addrs.alpha_pow_input[m]).val.ext();
-
-                        let ro: EF = self.memory.mr(ext_vec_addrs.ro_input[m]).val.ext();
-
-                        let new_ro = ro + alpha_pow * quotient;
-                        let new_alpha_pow = alpha_pow * alpha;
-
-                        let _ = self.memory.mw(
-                            ext_vec_addrs.ro_output[m],
-                            Block::from(new_ro.as_base_slice()),
-                            ro_mults[m],
-                        );
-
-                        let _ = self.memory.mw(
-                            ext_vec_addrs.alpha_pow_output[m],
-                            Block::from(new_alpha_pow.as_base_slice()),
-                            alpha_pow_mults[m],
-                        );
-
-                        self.record.fri_fold_events.push(FriFoldEvent {
-                            base_single: FriFoldBaseIo { x },
-                            ext_single: FriFoldExtSingleIo {
-                                z: Block::from(z.as_base_slice()),
-                                alpha: Block::from(alpha.as_base_slice()),
-                            },
-                            ext_vec: FriFoldExtVecIo {
-                                mat_opening: Block::from(p_at_x.as_base_slice()),
-                                ps_at_z: Block::from(p_at_z.as_base_slice()),
-                                alpha_pow_input: Block::from(alpha_pow.as_base_slice()),
-                                ro_input: Block::from(ro.as_base_slice()),
-                                alpha_pow_output: Block::from(new_alpha_pow.as_base_slice()),
-                                ro_output: Block::from(new_ro.as_base_slice()),
-                            },
-                        });
-                    }
-                }
+    /// Moves to the next instruction.
+    #[inline]
+    pub fn advance(&mut self) {
+        self.pc += F::one();
+        self.clk += F::from_canonical_u32(4);
+        self.timestamp += 1;
+    }
 
-                Instruction::CommitPublicValues(instr) => {
-                    let pv_addrs = instr.pv_addrs.to_vec();
-                    let pv_values: [F; RECURSIVE_PROOF_NUM_PV_ELTS] =
-                        array::from_fn(|i| self.memory.mr(pv_addrs[i]).val[0]);
-                    self.record.public_values = *pv_values.as_slice().borrow();
-                    self.record
-                        .commit_pv_hash_events
-                        .push(CommitPublicValuesEvent { public_values: self.record.public_values });
-                }
+    /// Executes an instruction at the current pc, without moving to the next one.
+    pub fn execute(&mut self, instruction: Instruction<F>) -> Result<(), RuntimeError<F, EF>> {
+        match instruction {
+            Instruction::BaseAlu(instr) => self.base_alu(instr)?,
+            Instruction::ExtAlu(instr) => self.ext_alu(instr)?,
+            Instruction::Mem(instr) => self.mem(instr),
+            Instruction::Poseidon2(instr) => self.poseidon2(*instr),
+            Instruction::Poseidon2Absorb(instr) => self.poseidon2_absorb(*instr),
+            Instruction::Poseidon2Finalize(instr) => self.poseidon2_finalize(*instr),
+            Instruction::ExpReverseBitsLen(instr) => self.exp_reverse_bits_len(instr),
+            Instruction::HintBits(instr) => self.hint_bits(instr),
+            Instruction::FriFold(instr) => self.fri_fold(*instr),
+            Instruction::CommitPublicValues(instr) => self.commit_public_values(*instr),
+            Instruction::Print(instr) => self.print(instr)?,
+            Instruction::HintExt2Felts(instr) => self.hint_ext2_felts(instr),
+            Instruction::Hint(instr) => self.hint(instr)?,
+        }
+        Ok(())
+    }
 
-                Instruction::Print(PrintInstr { field_elt_type, addr }) => match field_elt_type {
-                    FieldEltType::Base => {
-                        self.nb_print_f += 1;
-                        let f = self.memory.mr_mult(addr, F::zero()).val[0];
-                        writeln!(self.debug_stdout, "PRINTF={f}")
-                    }
-                    FieldEltType::Extension => {
-                        self.nb_print_e += 1;
-                        let ef = self.memory.mr_mult(addr, F::zero()).val;
-                        writeln!(self.debug_stdout, "PRINTEF={ef:?}")
-                    }
-                }
-                .map_err(RuntimeError::DebugPrint)?,
-                Instruction::HintExt2Felts(HintExt2FeltsInstr {
-                    output_addrs_mults,
-                    input_addr,
-                }) => {
-                    self.nb_bit_decompositions += 1;
-                    let fs = self.memory.mr_mult(input_addr, F::zero()).val;
-                    // Write the bits to the array at dst.
-                    for (f, (addr, mult)) in fs.into_iter().zip(output_addrs_mults) {
-                        let felt = Block::from(f);
-                        self.memory.mw(addr, felt, mult);
-                        self.record.mem_var_events.push(MemEvent { inner: felt });
+    /// Executes a Poseidon2 permutation.
+    #[inline]
+    pub fn poseidon2(&mut self, instr: Poseidon2Instr<F>) {
+        let Poseidon2Instr { addrs: Poseidon2Io { input, output }, mults } = instr;
+        self.nb_poseidons += 1;
+        let in_vals = std::array::from_fn(|i| self.memory.mr(input[i]).val[0]);
+        let perm_output = self.perm.as_ref().unwrap().permute(in_vals);
+
+        perm_output.iter().zip(output).zip(mults).for_each(|((&val, addr), mult)| {
+            self.memory.mw(addr, Block::from(val), mult);
+        });
+        self.record.poseidon2_events.push(Poseidon2Event { input: in_vals, output: perm_output });
+    }
+
+    /// Absorbs elements into the Poseidon2 sponge.
+    #[inline]
+    pub fn poseidon2_absorb(&mut self, instr: Poseidon2AbsorbInstr<F>) {
+        let Poseidon2AbsorbInstr { reset, input } = instr;
+        self.nb_poseidons += 1;
+        if reset {
+            self.sponge_state = [F::zero(); PERMUTATION_WIDTH];
+        }
+        for (lane, addr) in self.sponge_state.iter_mut().zip(input) {
+            *lane = self.memory.mr(addr).val[0];
+        }
+        let perm_output = self.perm.as_ref().unwrap().permute(self.sponge_state);
+
+        self.record
+            .poseidon2_events
+            .push(Poseidon2Event { input: self.sponge_state, output: perm_output });
+        self.sponge_state = perm_output;
+    }
+
+    /// Writes the digest of the Poseidon2 sponge.
+    #[inline]
+    pub fn poseidon2_finalize(&mut self, instr: Poseidon2FinalizeInstr<F>) {
+        let Poseidon2FinalizeInstr { output, mults } = instr;
+        self.sponge_state.iter().zip(output).zip(mults).for_each(|((&val, addr), mult)| {
+            self.memory.mw(addr, Block::from(val), mult);
+        });
+    }
+
+    /// Executes an exp-reverse-bits operation.
+    #[inline]
+    pub fn exp_reverse_bits_len(&mut self, instr: ExpReverseBitsInstr<F>) {
+        let ExpReverseBitsInstr { addrs: ExpReverseBitsIo { base, exp, result }, mult } = instr;
+        self.nb_exp_reverse_bits += 1;
+        let base_val = self.memory.mr(base).val[0];
+        let exp_bits: Vec<_> = exp.iter().map(|bit| self.memory.mr(*bit).val[0]).collect();
+        let exp_val = exp_bits
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, &val)| acc + val.as_canonical_u32() * (1 << i));
+        let out = base_val.exp_u64(reverse_bits_len(exp_val as usize, exp_bits.len()) as u64);
+        self.memory.mw(result, Block::from(out), mult);
+        self.record.exp_reverse_bits_len_events.push(ExpReverseBitsEvent {
+            result: out,
+            base: base_val,
+            exp: exp_bits,
+        });
+    }
+
+    /// Writes the bit decomposition of a field element.
+    #[inline]
+    pub fn hint_bits(&mut self, instr: HintBitsInstr<F>) {
+        let HintBitsInstr { output_addrs_mults, input_addr } = instr;
+        self.nb_bit_decompositions += 1;
+        let num = self.memory.mr_mult(input_addr, F::zero()).val[0].as_canonical_u32();
+        // Decompose the num into LE bits.
+        let bits = (0..output_addrs_mults.len())
+            .map(|i| Block::from(F::from_canonical_u32((num >> i) & 1)))
+            .collect::<Vec<_>>();
+        // Write the bits to the array at dst.
+        for (bit, (addr, mult)) in bits.into_iter().zip(output_addrs_mults) {
+            self.memory.mw(addr, bit, mult);
+            self.record.mem_var_events.push(MemEvent { inner: bit });
+        }
+    }
+
+    /// Executes a FRI fold operation.
+    #[inline]
+    pub fn fri_fold(&mut self, instr: FriFoldInstr<F>) {
+        let FriFoldInstr {
+            base_single_addrs,
+            ext_single_addrs,
+            ext_vec_addrs,
+            alpha_pow_mults,
+            ro_mults,
+        } = instr;
+        self.nb_fri_fold += 1;
+        let x = self.memory.mr(base_single_addrs.x).val[0];
+        let z = self.memory.mr(ext_single_addrs.z).val;
+        let z: EF = z.ext();
+        let alpha = self.memory.mr(ext_single_addrs.alpha).val;
+        let alpha: EF = alpha.ext();
+        let mat_opening =
+            ext_vec_addrs.mat_opening.iter().map(|addr| self.memory.mr(*addr).val).collect_vec();
+        let ps_at_z =
+            ext_vec_addrs.ps_at_z.iter().map(|addr| self.memory.mr(*addr).val).collect_vec();
+
+        for m in 0..ps_at_z.len() {
+            // let m = F::from_canonical_u32(m);
+            // Get the opening values.
+            let p_at_x = mat_opening[m];
+            let p_at_x: EF = p_at_x.ext();
+            let p_at_z = ps_at_z[m];
+            let p_at_z: EF = p_at_z.ext();
+
+            // Calculate the quotient and update the values
+            let quotient = (-p_at_z + p_at_x) / (-z + x);
+
+            // First we peek to get the current value.
+            let alpha_pow: EF = self.memory.mr(ext_vec_addrs.alpha_pow_input[m]).val.ext();
+
+            let ro: EF = self.memory.mr(ext_vec_addrs.ro_input[m]).val.ext();
+
+            let new_ro = ro + alpha_pow * quotient;
+            let new_alpha_pow = alpha_pow * alpha;
+
+            let _ = self.memory.mw(
+                ext_vec_addrs.ro_output[m],
+                Block::from(new_ro.as_base_slice()),
+                ro_mults[m],
+            );
+
+            let _ = self.memory.mw(
+                ext_vec_addrs.alpha_pow_output[m],
+                Block::from(new_alpha_pow.as_base_slice()),
+                alpha_pow_mults[m],
+            );
+
+            self.record.fri_fold_events.push(FriFoldEvent {
+                base_single: FriFoldBaseIo { x },
+                ext_single: FriFoldExtSingleIo {
+                    z: Block::from(z.as_base_slice()),
+                    alpha: Block::from(alpha.as_base_slice()),
+                },
+                ext_vec: FriFoldExtVecIo {
+                    mat_opening: Block::from(p_at_x.as_base_slice()),
+                    ps_at_z: Block::from(p_at_z.as_base_slice()),
+                    alpha_pow_input: Block::from(alpha_pow.as_base_slice()),
+                    ro_input: Block::from(ro.as_base_slice()),
+                    alpha_pow_output: Block::from(new_alpha_pow.as_base_slice()),
+                    ro_output: Block::from(new_ro.as_base_slice()),
+                },
+            });
+        }
+    }
+
+    /// Saves the public values to the execution record.
+    #[inline]
+    pub fn commit_public_values(&mut self, instr: CommitPublicValuesInstr<F>) {
+        let pv_addrs = instr.pv_addrs.to_vec();
+        let pv_values: [F; RECURSIVE_PROOF_NUM_PV_ELTS] =
+            array::from_fn(|i| self.memory.mr(pv_addrs[i]).val[0]);
+        self.record.public_values = *pv_values.as_slice().borrow();
+        self.record
+            .commit_pv_hash_events
+            .push(CommitPublicValuesEvent { public_values: self.record.public_values });
+    }
+
+    /// Prints a field element to `debug_stdout`.
+    #[inline]
+    pub fn print(&mut self, instr: PrintInstr<F>) -> Result<(), RuntimeError<F, EF>> {
+        let PrintInstr { field_elt_type, addr } = instr;
+        let result = match field_elt_type {
+            FieldEltType::Base => {
+                self.nb_print_f += 1;
+                let f = self.memory.mr_mult(addr, F::zero()).val[0];
+                writeln!(self.debug_stdout, "PRINTF={f}")
+            }
+            FieldEltType::Extension => {
+                self.nb_print_e += 1;
+                let ef = self.memory.mr_mult(addr, F::zero()).val;
+                writeln!(self.debug_stdout, "PRINTEF={ef:?}")
+            }
+        };
+        result.map_err(RuntimeError::DebugPrint)
+    }
+
+    /// Writes the base field coefficients of an extension field element.
+    #[inline]
+    pub fn hint_ext2_felts(&mut self, instr: HintExt2FeltsInstr<F>) {
+        let HintExt2FeltsInstr { output_addrs_mults, input_addr } = instr;
+        self.nb_bit_decompositions += 1;
+        let fs = self.memory.mr_mult(input_addr, F::zero()).val;
+        // Write the bits to the array at dst.
+        for (f, (addr, mult)) in fs.into_iter().zip(output_addrs_mults) {
+            let felt = Block::from(f);
+            self.memory.mw(addr, felt, mult);
+            self.record.mem_var_events.push(MemEvent { inner: felt });
+        }
+    }
+
+    /// Writes blocks read from the witness stream.
+    #[inline]
+    pub fn hint(&mut self, instr: HintInstr<F>) -> Result<(), RuntimeError<F, EF>> {
+        let HintInstr { output_addrs_mults } = instr;
+        // Check that enough Blocks can be read, so `drain` does not panic.
+        if self.witness_stream.len() < output_addrs_mults.len() {
+            return Err(RuntimeError::EmptyWitnessStream);
+        }
+        let witness = self.witness_stream.drain(0..output_addrs_mults.len());
+        for ((addr, mult), val) in zip(output_addrs_mults, witness) {
+            // Inline [`Self::mw`] to mutably borrow multiple fields of `self`.
+            self.memory.mw(addr, val, mult);
+            self.record.mem_var_events.push(MemEvent { inner: val });
+        }
+        Ok(())
+    }
+
+    /// Executes an instruction of the base field ALU.
+    #[inline]
+    pub fn base_alu(&mut self, instr: BaseAluInstr<F>) -> Result<(), RuntimeError<F, EF>> {
+        let BaseAluInstr { opcode, mult, addrs } = instr;
+        self.nb_base_ops += 1;
+        let in1 = self.memory.mr(addrs.in1).val[0];
+        let in2 = self.memory.mr(addrs.in2).val[0];
+        // Do the computation.
+        let out = match opcode {
+            BaseAluOpcode::AddF => in1 + in2,
+            BaseAluOpcode::SubF => in1 - in2,
+            BaseAluOpcode::MulF => in1 * in2,
+            BaseAluOpcode::DivF => match in1.try_div(in2) {
+                Some(x) => x,
+                None => {
+                    // Check for division exceptions and error. Note that 0/0 is defined
+                    // to be 1.
+                    if in1.is_zero() {
+                        AbstractField::one()
+                    } else {
+                        return Err(RuntimeError::DivFOutOfDomain {
+                            in1,
+                            in2,
+                            instr: BaseAluInstr { opcode, mult, addrs },
+                            pc: self.pc.as_canonical_u32() as usize,
+                            trace: self.nearest_pc_backtrace(),
+                        });
                     }
                 }
-                Instruction::Hint(HintInstr { output_addrs_mults }) => {
-                    // Check that enough Blocks can be read, so `drain` does not panic.
-                    if self.witness_stream.len() < output_addrs_mults.len() {
-                        return Err(RuntimeError::EmptyWitnessStream);
-                    }
-                    let witness = self.witness_stream.drain(0..output_addrs_mults.len());
-                    for ((addr, mult), val) in zip(output_addrs_mults, witness) {
-                        // Inline [`Self::mw`] to mutably borrow multiple fields of `self`.
-                        self.memory.mw(addr, val, mult);
-                        self.record.mem_var_events.push(MemEvent { inner: val });
+            },
+        };
+        self.memory.mw(addrs.out, Block::from(out), mult);
+        self.record.base_alu_events.push(BaseAluEvent { out, in1, in2 });
+        Ok(())
+    }
+
+    /// Executes an instruction of the extension field ALU.
+    #[inline]
+    pub fn ext_alu(&mut self, instr: ExtAluInstr<F>) -> Result<(), RuntimeError<F, EF>> {
+        let ExtAluInstr { opcode, mult, addrs } = instr;
+        self.nb_ext_ops += 1;
+        let in1 = self.memory.mr(addrs.in1).val;
+        let in2 = self.memory.mr(addrs.in2).val;
+        // Do the computation.
+        let in1_ef = EF::from_base_slice(&in1.0);
+        let in2_ef = EF::from_base_slice(&in2.0);
+        let out_ef = match opcode {
+            ExtAluOpcode::AddE => in1_ef + in2_ef,
+            ExtAluOpcode::SubE => in1_ef - in2_ef,
+            ExtAluOpcode::MulE => in1_ef * in2_ef,
+            ExtAluOpcode::DivE => match in1_ef.try_div(in2_ef) {
+                Some(x) => x,
+                None => {
+                    // Check for division exceptions and error. Note that 0/0 is defined
+                    // to be 1.
+                    if in1_ef.is_zero() {
+                        AbstractField::one()
+                    } else {
+                        return Err(RuntimeError::DivEOutOfDomain {
+                            in1: in1_ef,
+                            in2: in2_ef,
+                            instr: ExtAluInstr { opcode, mult, addrs },
+                            pc: self.pc.as_canonical_u32() as usize,
+                            trace: self.nearest_pc_backtrace(),
+                        });
                     }
                 }
-            }
-
-            self.pc = next_pc;
-            self.clk = next_clk;
-            self.timestamp += 1;
+            },
+        };
+        let out = Block::from(out_ef.as_base_slice());
+        self.memory.mw(addrs.out, out, mult);
+        self.record.ext_alu_events.push(ExtAluEvent { out, in1, in2 });
+        Ok(())
+    }
 
-            if self.timestamp >= early_exit_ts {
-                break;
+    /// Executes a read or a write of a constant.
+    #[inline]
+    pub fn mem(&mut self, instr: MemInstr<F>) {
+        let MemInstr { addrs: MemIo { inner: addr }, vals: MemIo { inner: val }, mult, kind } =
+            instr;
+        self.nb_memory_ops += 1;
+        match kind {
+            MemAccessKind::Read => {
+                let mem_entry = self.memory.mr_mult(addr, mult);
+                assert_eq!(mem_entry.val, val, "stored memory value should be the specified value");
             }
+            MemAccessKind::Write => drop(self.memory.mw(addr, val, mult)),
         }
-        Ok(())
+        self.record.mem_const_count += 1;
     }
 }
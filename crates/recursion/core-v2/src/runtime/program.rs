@@ -1,8 +1,11 @@
+use std::{fmt, sync::OnceLock};
+
 use backtrace::Backtrace;
-use p3_field::Field;
+use p3_field::{Field, PrimeField32};
 use serde::{Deserialize, Serialize};
 use sp1_stark::air::MachineProgram;
 
+use super::codegen;
 use crate::*;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -11,6 +14,36 @@ pub struct RecursionProgram<F> {
     pub total_memory: usize,
     #[serde(skip)]
     pub traces: Vec<Option<Backtrace>>,
+    #[serde(skip)]
+    pub hash: ProgramHashCache,
+}
+
+impl<F: PrimeField32> RecursionProgram<F> {
+    /// The [`codegen::program_hash`] of the program, which is only computed on the first call.
+    ///
+    /// The instructions must not be modified after the first call.
+    pub fn program_hash(&self) -> u64 {
+        *self.hash.0.get_or_init(|| codegen::program_hash(self))
+    }
+}
+
+/// The cache of [`RecursionProgram::program_hash`].
+///
+/// A clone of the cache is empty, so that the hash of a modified clone of a program is computed
+/// again.
+#[derive(Default)]
+pub struct ProgramHashCache(OnceLock<u64>);
+
+impl Clone for ProgramHashCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for ProgramHashCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgramHashCache").finish_non_exhaustive()
+    }
 }
 
 impl<F: Field> MachineProgram<F> for RecursionProgram<F> {
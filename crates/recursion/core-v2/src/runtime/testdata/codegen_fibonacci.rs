@@ -0,0 +1,561 @@
+// @generated by `sp1_recursion_core_v2::runtime::codegen`. Do not edit.
+
+#[allow(unused_imports)]
+use ::sp1_recursion_core_v2::runtime::codegen::prelude::*;
+
+/// The number of instructions of the compiled program.
+pub const NUM_INSTRUCTIONS: usize = 259;
+
+/// The memory size of the compiled program.
+pub const TOTAL_MEMORY: usize = 257;
+
+/// The hash of the instructions of the compiled program.
+pub const PROGRAM_HASH: u64 = 0xb46b7422b4ad7e10;
+
+/// Executes the compiled program on `rt`, which must run the same program.
+pub fn run<F, EF, Diffusion>(
+    rt: &mut Runtime<'_, F, EF, Diffusion>,
+) -> Result<(), RuntimeError<F, EF>>
+where
+    F: PrimeField32,
+    EF: ExtensionField<F>,
+    Poseidon2<F, Poseidon2ExternalMatrixGeneral, Diffusion, PERMUTATION_WIDTH, POSEIDON2_SBOX_DEGREE>:
+        CryptographicPermutation<[F; PERMUTATION_WIDTH]>,
+{
+    check_program(rt, NUM_INSTRUCTIONS, TOTAL_MEMORY, PROGRAM_HASH)?;
+    if std::env::var_os("RECURSION_EARLY_EXIT_TS").is_some() {
+        return rt.run();
+    }
+    chunk_0(rt)?;
+    Ok(())
+}
+
+fn chunk_0<F, EF, Diffusion>(
+    rt: &mut Runtime<'_, F, EF, Diffusion>,
+) -> Result<(), RuntimeError<F, EF>>
+where
+    F: PrimeField32,
+    EF: ExtensionField<F>,
+    Poseidon2<F, Poseidon2ExternalMatrixGeneral, Diffusion, PERMUTATION_WIDTH, POSEIDON2_SBOX_DEGREE>:
+        CryptographicPermutation<[F; PERMUTATION_WIDTH]>,
+{
+    rt.mem(MemInstr { addrs: MemIo { inner: addr(0) }, vals: MemIo { inner: Block::from([felt(0), felt(0), felt(0), felt(0)]) }, mult: felt(1), kind: MemAccessKind::Write });
+    rt.advance();
+    rt.mem(MemInstr { addrs: MemIo { inner: addr(1) }, vals: MemIo { inner: Block::from([felt(1), felt(0), felt(0), felt(0)]) }, mult: felt(2), kind: MemAccessKind::Write });
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(2), in1: addr(0), in2: addr(1) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(3), in1: addr(1), in2: addr(2) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(4), in1: addr(2), in2: addr(3) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(5), in1: addr(3), in2: addr(4) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(6), in1: addr(4), in2: addr(5) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(7), in1: addr(5), in2: addr(6) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(8), in1: addr(6), in2: addr(7) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(9), in1: addr(7), in2: addr(8) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(10), in1: addr(8), in2: addr(9) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(11), in1: addr(9), in2: addr(10) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(12), in1: addr(10), in2: addr(11) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(13), in1: addr(11), in2: addr(12) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(14), in1: addr(12), in2: addr(13) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(15), in1: addr(13), in2: addr(14) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(16), in1: addr(14), in2: addr(15) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(17), in1: addr(15), in2: addr(16) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(18), in1: addr(16), in2: addr(17) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(19), in1: addr(17), in2: addr(18) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(20), in1: addr(18), in2: addr(19) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(21), in1: addr(19), in2: addr(20) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(22), in1: addr(20), in2: addr(21) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(23), in1: addr(21), in2: addr(22) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(24), in1: addr(22), in2: addr(23) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(25), in1: addr(23), in2: addr(24) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(26), in1: addr(24), in2: addr(25) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(27), in1: addr(25), in2: addr(26) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(28), in1: addr(26), in2: addr(27) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(29), in1: addr(27), in2: addr(28) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(30), in1: addr(28), in2: addr(29) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(31), in1: addr(29), in2: addr(30) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(32), in1: addr(30), in2: addr(31) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(33), in1: addr(31), in2: addr(32) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(34), in1: addr(32), in2: addr(33) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(35), in1: addr(33), in2: addr(34) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(36), in1: addr(34), in2: addr(35) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(37), in1: addr(35), in2: addr(36) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(38), in1: addr(36), in2: addr(37) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(39), in1: addr(37), in2: addr(38) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(40), in1: addr(38), in2: addr(39) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(41), in1: addr(39), in2: addr(40) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(42), in1: addr(40), in2: addr(41) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(43), in1: addr(41), in2: addr(42) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(44), in1: addr(42), in2: addr(43) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(45), in1: addr(43), in2: addr(44) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(46), in1: addr(44), in2: addr(45) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(47), in1: addr(45), in2: addr(46) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(48), in1: addr(46), in2: addr(47) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(49), in1: addr(47), in2: addr(48) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(50), in1: addr(48), in2: addr(49) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(51), in1: addr(49), in2: addr(50) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(52), in1: addr(50), in2: addr(51) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(53), in1: addr(51), in2: addr(52) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(54), in1: addr(52), in2: addr(53) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(55), in1: addr(53), in2: addr(54) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(56), in1: addr(54), in2: addr(55) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(57), in1: addr(55), in2: addr(56) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(58), in1: addr(56), in2: addr(57) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(59), in1: addr(57), in2: addr(58) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(60), in1: addr(58), in2: addr(59) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(61), in1: addr(59), in2: addr(60) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(62), in1: addr(60), in2: addr(61) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(63), in1: addr(61), in2: addr(62) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(64), in1: addr(62), in2: addr(63) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(65), in1: addr(63), in2: addr(64) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(66), in1: addr(64), in2: addr(65) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(67), in1: addr(65), in2: addr(66) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(68), in1: addr(66), in2: addr(67) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(69), in1: addr(67), in2: addr(68) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(70), in1: addr(68), in2: addr(69) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(71), in1: addr(69), in2: addr(70) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(72), in1: addr(70), in2: addr(71) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(73), in1: addr(71), in2: addr(72) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(74), in1: addr(72), in2: addr(73) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(75), in1: addr(73), in2: addr(74) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(76), in1: addr(74), in2: addr(75) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(77), in1: addr(75), in2: addr(76) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(78), in1: addr(76), in2: addr(77) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(79), in1: addr(77), in2: addr(78) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(80), in1: addr(78), in2: addr(79) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(81), in1: addr(79), in2: addr(80) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(82), in1: addr(80), in2: addr(81) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(83), in1: addr(81), in2: addr(82) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(84), in1: addr(82), in2: addr(83) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(85), in1: addr(83), in2: addr(84) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(86), in1: addr(84), in2: addr(85) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(87), in1: addr(85), in2: addr(86) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(88), in1: addr(86), in2: addr(87) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(89), in1: addr(87), in2: addr(88) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(90), in1: addr(88), in2: addr(89) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(91), in1: addr(89), in2: addr(90) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(92), in1: addr(90), in2: addr(91) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(93), in1: addr(91), in2: addr(92) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(94), in1: addr(92), in2: addr(93) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(95), in1: addr(93), in2: addr(94) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(96), in1: addr(94), in2: addr(95) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(97), in1: addr(95), in2: addr(96) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(98), in1: addr(96), in2: addr(97) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(99), in1: addr(97), in2: addr(98) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(100), in1: addr(98), in2: addr(99) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(101), in1: addr(99), in2: addr(100) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(102), in1: addr(100), in2: addr(101) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(103), in1: addr(101), in2: addr(102) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(104), in1: addr(102), in2: addr(103) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(105), in1: addr(103), in2: addr(104) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(106), in1: addr(104), in2: addr(105) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(107), in1: addr(105), in2: addr(106) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(108), in1: addr(106), in2: addr(107) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(109), in1: addr(107), in2: addr(108) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(110), in1: addr(108), in2: addr(109) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(111), in1: addr(109), in2: addr(110) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(112), in1: addr(110), in2: addr(111) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(113), in1: addr(111), in2: addr(112) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(114), in1: addr(112), in2: addr(113) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(115), in1: addr(113), in2: addr(114) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(116), in1: addr(114), in2: addr(115) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(117), in1: addr(115), in2: addr(116) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(118), in1: addr(116), in2: addr(117) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(119), in1: addr(117), in2: addr(118) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(120), in1: addr(118), in2: addr(119) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(121), in1: addr(119), in2: addr(120) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(122), in1: addr(120), in2: addr(121) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(123), in1: addr(121), in2: addr(122) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(124), in1: addr(122), in2: addr(123) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(125), in1: addr(123), in2: addr(124) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(126), in1: addr(124), in2: addr(125) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(127), in1: addr(125), in2: addr(126) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(128), in1: addr(126), in2: addr(127) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(129), in1: addr(127), in2: addr(128) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(130), in1: addr(128), in2: addr(129) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(131), in1: addr(129), in2: addr(130) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(132), in1: addr(130), in2: addr(131) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(133), in1: addr(131), in2: addr(132) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(134), in1: addr(132), in2: addr(133) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(135), in1: addr(133), in2: addr(134) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(136), in1: addr(134), in2: addr(135) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(137), in1: addr(135), in2: addr(136) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(138), in1: addr(136), in2: addr(137) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(139), in1: addr(137), in2: addr(138) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(140), in1: addr(138), in2: addr(139) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(141), in1: addr(139), in2: addr(140) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(142), in1: addr(140), in2: addr(141) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(143), in1: addr(141), in2: addr(142) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(144), in1: addr(142), in2: addr(143) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(145), in1: addr(143), in2: addr(144) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(146), in1: addr(144), in2: addr(145) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(147), in1: addr(145), in2: addr(146) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(148), in1: addr(146), in2: addr(147) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(149), in1: addr(147), in2: addr(148) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(150), in1: addr(148), in2: addr(149) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(151), in1: addr(149), in2: addr(150) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(152), in1: addr(150), in2: addr(151) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(153), in1: addr(151), in2: addr(152) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(154), in1: addr(152), in2: addr(153) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(155), in1: addr(153), in2: addr(154) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(156), in1: addr(154), in2: addr(155) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(157), in1: addr(155), in2: addr(156) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(158), in1: addr(156), in2: addr(157) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(159), in1: addr(157), in2: addr(158) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(160), in1: addr(158), in2: addr(159) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(161), in1: addr(159), in2: addr(160) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(162), in1: addr(160), in2: addr(161) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(163), in1: addr(161), in2: addr(162) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(164), in1: addr(162), in2: addr(163) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(165), in1: addr(163), in2: addr(164) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(166), in1: addr(164), in2: addr(165) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(167), in1: addr(165), in2: addr(166) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(168), in1: addr(166), in2: addr(167) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(169), in1: addr(167), in2: addr(168) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(170), in1: addr(168), in2: addr(169) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(171), in1: addr(169), in2: addr(170) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(172), in1: addr(170), in2: addr(171) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(173), in1: addr(171), in2: addr(172) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(174), in1: addr(172), in2: addr(173) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(175), in1: addr(173), in2: addr(174) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(176), in1: addr(174), in2: addr(175) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(177), in1: addr(175), in2: addr(176) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(178), in1: addr(176), in2: addr(177) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(179), in1: addr(177), in2: addr(178) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(180), in1: addr(178), in2: addr(179) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(181), in1: addr(179), in2: addr(180) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(182), in1: addr(180), in2: addr(181) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(183), in1: addr(181), in2: addr(182) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(184), in1: addr(182), in2: addr(183) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(185), in1: addr(183), in2: addr(184) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(186), in1: addr(184), in2: addr(185) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(187), in1: addr(185), in2: addr(186) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(188), in1: addr(186), in2: addr(187) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(189), in1: addr(187), in2: addr(188) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(190), in1: addr(188), in2: addr(189) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(191), in1: addr(189), in2: addr(190) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(192), in1: addr(190), in2: addr(191) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(193), in1: addr(191), in2: addr(192) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(194), in1: addr(192), in2: addr(193) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(195), in1: addr(193), in2: addr(194) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(196), in1: addr(194), in2: addr(195) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(197), in1: addr(195), in2: addr(196) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(198), in1: addr(196), in2: addr(197) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(199), in1: addr(197), in2: addr(198) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(200), in1: addr(198), in2: addr(199) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(201), in1: addr(199), in2: addr(200) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(202), in1: addr(200), in2: addr(201) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(203), in1: addr(201), in2: addr(202) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(204), in1: addr(202), in2: addr(203) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(205), in1: addr(203), in2: addr(204) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(206), in1: addr(204), in2: addr(205) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(207), in1: addr(205), in2: addr(206) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(208), in1: addr(206), in2: addr(207) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(209), in1: addr(207), in2: addr(208) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(210), in1: addr(208), in2: addr(209) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(211), in1: addr(209), in2: addr(210) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(212), in1: addr(210), in2: addr(211) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(213), in1: addr(211), in2: addr(212) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(214), in1: addr(212), in2: addr(213) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(215), in1: addr(213), in2: addr(214) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(216), in1: addr(214), in2: addr(215) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(217), in1: addr(215), in2: addr(216) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(218), in1: addr(216), in2: addr(217) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(219), in1: addr(217), in2: addr(218) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(220), in1: addr(218), in2: addr(219) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(221), in1: addr(219), in2: addr(220) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(222), in1: addr(220), in2: addr(221) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(223), in1: addr(221), in2: addr(222) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(224), in1: addr(222), in2: addr(223) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(225), in1: addr(223), in2: addr(224) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(226), in1: addr(224), in2: addr(225) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(227), in1: addr(225), in2: addr(226) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(228), in1: addr(226), in2: addr(227) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(229), in1: addr(227), in2: addr(228) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(230), in1: addr(228), in2: addr(229) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(231), in1: addr(229), in2: addr(230) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(232), in1: addr(230), in2: addr(231) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(233), in1: addr(231), in2: addr(232) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(234), in1: addr(232), in2: addr(233) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(235), in1: addr(233), in2: addr(234) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(236), in1: addr(234), in2: addr(235) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(237), in1: addr(235), in2: addr(236) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(238), in1: addr(236), in2: addr(237) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(239), in1: addr(237), in2: addr(238) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(240), in1: addr(238), in2: addr(239) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(241), in1: addr(239), in2: addr(240) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(242), in1: addr(240), in2: addr(241) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(243), in1: addr(241), in2: addr(242) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(244), in1: addr(242), in2: addr(243) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(245), in1: addr(243), in2: addr(244) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(246), in1: addr(244), in2: addr(245) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(247), in1: addr(245), in2: addr(246) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(248), in1: addr(246), in2: addr(247) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(249), in1: addr(247), in2: addr(248) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(250), in1: addr(248), in2: addr(249) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(251), in1: addr(249), in2: addr(250) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(252), in1: addr(250), in2: addr(251) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(253), in1: addr(251), in2: addr(252) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(254), in1: addr(252), in2: addr(253) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(2), addrs: BaseAluIo { out: addr(255), in1: addr(253), in2: addr(254) } })?;
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::AddF, mult: felt(1), addrs: BaseAluIo { out: addr(256), in1: addr(254), in2: addr(255) } })?;
+    rt.advance();
+    rt.mem(MemInstr { addrs: MemIo { inner: addr(255) }, vals: MemIo { inner: Block::from([felt(1787675748), felt(0), felt(0), felt(0)]) }, mult: felt(1), kind: MemAccessKind::Read });
+    rt.advance();
+    rt.mem(MemInstr { addrs: MemIo { inner: addr(256) }, vals: MemIo { inner: Block::from([felt(1191088769), felt(0), felt(0), felt(0)]) }, mult: felt(1), kind: MemAccessKind::Read });
+    rt.advance();
+    Ok(())
+}
@@ -0,0 +1,71 @@
+// @generated by `sp1_recursion_core_v2::runtime::codegen`. Do not edit.
+
+#[allow(unused_imports)]
+use ::sp1_recursion_core_v2::runtime::codegen::prelude::*;
+
+/// The number of instructions of the compiled program.
+pub const NUM_INSTRUCTIONS: usize = 14;
+
+/// The memory size of the compiled program.
+pub const TOTAL_MEMORY: usize = 40;
+
+/// The hash of the instructions of the compiled program.
+pub const PROGRAM_HASH: u64 = 0xd54bdc44bdfacc16;
+
+/// Executes the compiled program on `rt`, which must run the same program.
+pub fn run<F, EF, Diffusion>(
+    rt: &mut Runtime<'_, F, EF, Diffusion>,
+) -> Result<(), RuntimeError<F, EF>>
+where
+    F: PrimeField32,
+    EF: ExtensionField<F>,
+    Poseidon2<F, Poseidon2ExternalMatrixGeneral, Diffusion, PERMUTATION_WIDTH, POSEIDON2_SBOX_DEGREE>:
+        CryptographicPermutation<[F; PERMUTATION_WIDTH]>,
+{
+    check_program(rt, NUM_INSTRUCTIONS, TOTAL_MEMORY, PROGRAM_HASH)?;
+    if std::env::var_os("RECURSION_EARLY_EXIT_TS").is_some() {
+        return rt.run();
+    }
+    chunk_0(rt)?;
+    Ok(())
+}
+
+fn chunk_0<F, EF, Diffusion>(
+    rt: &mut Runtime<'_, F, EF, Diffusion>,
+) -> Result<(), RuntimeError<F, EF>>
+where
+    F: PrimeField32,
+    EF: ExtensionField<F>,
+    Poseidon2<F, Poseidon2ExternalMatrixGeneral, Diffusion, PERMUTATION_WIDTH, POSEIDON2_SBOX_DEGREE>:
+        CryptographicPermutation<[F; PERMUTATION_WIDTH]>,
+{
+    rt.mem(MemInstr { addrs: MemIo { inner: addr(0) }, vals: MemIo { inner: Block::from([felt(5), felt(0), felt(0), felt(0)]) }, mult: felt(3), kind: MemAccessKind::Write });
+    rt.advance();
+    rt.mem(MemInstr { addrs: MemIo { inner: addr(1) }, vals: MemIo { inner: Block::from([felt(7), felt(0), felt(0), felt(0)]) }, mult: felt(2), kind: MemAccessKind::Write });
+    rt.advance();
+    rt.base_alu(BaseAluInstr { opcode: BaseAluOpcode::MulF, mult: felt(1), addrs: BaseAluIo { out: addr(2), in1: addr(0), in2: addr(1) } })?;
+    rt.advance();
+    rt.ext_alu(ExtAluInstr { opcode: ExtAluOpcode::AddE, mult: felt(1), addrs: ExtAluIo { out: addr(3), in1: addr(0), in2: addr(1) } })?;
+    rt.advance();
+    rt.hint_bits(HintBitsInstr { output_addrs_mults: vec![(addr(4), felt(1)), (addr(5), felt(1)), (addr(6), felt(1))], input_addr: addr(0) });
+    rt.advance();
+    rt.exp_reverse_bits_len(ExpReverseBitsInstr { addrs: ExpReverseBitsIo { base: addr(1), exp: vec![addr(4), addr(5), addr(6)], result: addr(7) }, mult: felt(1) });
+    rt.advance();
+    rt.hint(HintInstr { output_addrs_mults: vec![(addr(8), felt(1)), (addr(9), felt(1))] })?;
+    rt.advance();
+    rt.hint_ext2_felts(HintExt2FeltsInstr { output_addrs_mults: [(addr(10), felt(1)), (addr(11), felt(1)), (addr(12), felt(1)), (addr(13), felt(1))], input_addr: addr(3) });
+    rt.advance();
+    rt.poseidon2(Poseidon2Instr { addrs: Poseidon2Io { input: [addr(0), addr(1), addr(2), addr(3), addr(4), addr(5), addr(6), addr(7), addr(8), addr(9), addr(10), addr(11), addr(12), addr(13), addr(0), addr(1)], output: [addr(14), addr(15), addr(16), addr(17), addr(18), addr(19), addr(20), addr(21), addr(22), addr(23), addr(24), addr(25), addr(26), addr(27), addr(28), addr(29)] }, mults: [felt(1), felt(1), felt(1), felt(1), felt(1), felt(1), felt(1), felt(1), felt(1), felt(1), felt(1), felt(1), felt(1), felt(1), felt(1), felt(1)] });
+    rt.advance();
+    rt.poseidon2_absorb(Poseidon2AbsorbInstr { reset: true, input: vec![addr(0), addr(1), addr(2)] });
+    rt.advance();
+    rt.poseidon2_finalize(Poseidon2FinalizeInstr { output: [addr(30), addr(31), addr(32), addr(33), addr(34), addr(35), addr(36), addr(37)], mults: [felt(1), felt(1), felt(1), felt(1), felt(1), felt(1), felt(1), felt(1)] });
+    rt.advance();
+    rt.fri_fold(FriFoldInstr { base_single_addrs: FriFoldBaseIo { x: addr(0) }, ext_single_addrs: FriFoldExtSingleIo { z: addr(1), alpha: addr(3) }, ext_vec_addrs: FriFoldExtVecIo { mat_opening: vec![addr(2)], ps_at_z: vec![addr(3)], alpha_pow_input: vec![addr(1)], ro_input: vec![addr(0)], alpha_pow_output: vec![addr(38)], ro_output: vec![addr(39)] }, alpha_pow_mults: vec![felt(1)], ro_mults: vec![felt(1)] });
+    rt.advance();
+    rt.print(PrintInstr { field_elt_type: FieldEltType::Base, addr: addr(2) })?;
+    rt.advance();
+    rt.mem(MemInstr { addrs: MemIo { inner: addr(2) }, vals: MemIo { inner: Block::from([felt(35), felt(0), felt(0), felt(0)]) }, mult: felt(1), kind: MemAccessKind::Read });
+    rt.advance();
+    Ok(())
+}
@@ -3,8 +3,10 @@
 
 use sp1_recursion_gnark_ffi::{
     ffi::{
-        build_groth16_bn254, build_plonk_bn254, test_groth16_bn254, test_plonk_bn254,
-        verify_groth16_bn254, verify_plonk_bn254,
+        build_groth16_bn254, build_plonk_bn254, ceremony_contribute_groth16_bn254,
+        ceremony_finalize_groth16_bn254, ceremony_init_groth16_bn254,
+        ceremony_verify_groth16_bn254, test_groth16_bn254, test_plonk_bn254, verify_groth16_bn254,
+        verify_plonk_bn254,
     },
     ProofBn254,
 };
@@ -27,6 +29,10 @@ enum Command {
     Prove(ProveArgs),
     Verify(VerifyArgs),
     Test(TestArgs),
+    CeremonyInit(CeremonyInitArgs),
+    CeremonyContribute(CeremonyContributeArgs),
+    CeremonyVerify(CeremonyVerifyArgs),
+    CeremonyFinalize(CeremonyFinalizeArgs),
 }
 
 #[derive(Debug, Args)]
@@ -64,6 +70,34 @@ struct TestArgs {
     system: String,
 }
 
+#[derive(Debug, Args)]
+struct CeremonyInitArgs {
+    data_dir: String,
+    phase1_path: String,
+    output_path: String,
+}
+
+#[derive(Debug, Args)]
+struct CeremonyContributeArgs {
+    input_path: String,
+    output_path: String,
+}
+
+#[derive(Debug, Args)]
+struct CeremonyVerifyArgs {
+    prev_path: String,
+    next_path: String,
+}
+
+#[derive(Debug, Args)]
+struct CeremonyFinalizeArgs {
+    data_dir: String,
+    phase1_path: String,
+    phase2_path: String,
+    beacon: String,
+    iterations: u32,
+}
+
 fn run_build(args: BuildArgs) {
     match args.system.as_str() {
         "plonk" => build_plonk_bn254(&args.data_dir),
@@ -124,6 +158,12 @@ fn run_test(args: TestArgs) {
     }
 }
 
+fn run_ceremony(result: Result<(), String>) {
+    if let Err(e) = result {
+        panic!("Ceremony failed: {}", e);
+    }
+}
+
 fn main() {
     let cli = Cli::parse();
 
@@ -132,5 +172,26 @@ fn main() {
         Command::Prove(args) => run_prove(args),
         Command::Verify(args) => run_verify(args),
         Command::Test(args) => run_test(args),
+        Command::CeremonyInit(args) => run_ceremony(ceremony_init_groth16_bn254(
+            &args.data_dir,
+            &args.phase1_path,
+            &args.output_path,
+        )),
+        Command::CeremonyContribute(args) => {
+            run_ceremony(ceremony_contribute_groth16_bn254(&args.input_path, &args.output_path))
+        }
+        Command::CeremonyVerify(args) => {
+            run_ceremony(ceremony_verify_groth16_bn254(&args.prev_path, &args.next_path))
+        }
+        Command::CeremonyFinalize(args) => {
+            let beacon = hex::decode(&args.beacon).expect("beacon must be hex");
+            run_ceremony(ceremony_finalize_groth16_bn254(
+                &args.data_dir,
+                &args.phase1_path,
+                &args.phase2_path,
+                &beacon,
+                args.iterations,
+            ))
+        }
     }
 }
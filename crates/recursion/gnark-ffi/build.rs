@@ -11,6 +11,8 @@ fn main() {
     cfg_if! {
         if #[cfg(feature = "native")] {
             println!("cargo:rerun-if-changed=go");
+            println!("cargo:rerun-if-env-changed=CC");
+            println!("cargo:rerun-if-env-changed=CFLAGS");
             // Define the output directory
             let out_dir = env::var("OUT_DIR").unwrap();
             let dest_path = PathBuf::from(&out_dir);
@@ -19,10 +21,26 @@ fn main() {
 
             println!("Building Go library at {}", dest.display());
 
+            // Build the archive for the target rather than the host, so that the library can be
+            // cross-compiled, e.g. for `x86_64-apple-darwin` on an Apple Silicon host.
+            let target_arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
+            let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
+            let (goos, goarch) = go_target(&target_os, &target_arch);
+
+            // Cgo compiles the C parts of the library with `CC`, which must target the same
+            // platform when cross-compiling.
+            let mut cc = cc::Build::new();
+            cc.cargo_metadata(false);
+            let compiler = cc.get_compiler();
+
             // Run the go build command
             let status = Command::new("go")
                 .current_dir("go")
                 .env("CGO_ENABLED", "1")
+                .env("GOOS", goos)
+                .env("GOARCH", goarch)
+                .env("CC", compiler.path())
+                .env("CGO_CFLAGS", cflags(&compiler))
                 .args([
                     "build",
                     "-o",
@@ -62,10 +80,37 @@ fn main() {
             println!("cargo:rustc-link-lib=static={}", lib_name);
 
             // Static linking doesn't really work on macos, so we need to link some system libs
-            if cfg!(target_os = "macos") {
+            if target_os == "macos" {
                 println!("cargo:rustc-link-lib=framework=CoreFoundation");
                 println!("cargo:rustc-link-lib=framework=Security");
             }
         }
     }
 }
+
+/// The `GOOS` and `GOARCH` of a Rust target.
+///
+/// # Panics
+/// Panics if gnark cannot be built for the target.
+fn go_target(target_os: &str, target_arch: &str) -> (&'static str, &'static str) {
+    let goos = match target_os {
+        "linux" => "linux",
+        "macos" => "darwin",
+        _ => panic!("the native gnark library cannot be built for {target_os}"),
+    };
+    let goarch = match target_arch {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        _ => panic!("the native gnark library cannot be built for {target_arch}"),
+    };
+    (goos, goarch)
+}
+
+/// The flags the C compiler is invoked with by cgo: the target flags chosen by `cc`, such as the
+/// macOS deployment target, followed by the default flags of cgo.
+fn cflags(compiler: &cc::Tool) -> String {
+    let mut flags =
+        compiler.args().iter().map(|arg| arg.to_str().unwrap().to_string()).collect::<Vec<_>>();
+    flags.extend(["-O2".to_string(), "-g".to_string()]);
+    flags.join(" ")
+}
@@ -17,6 +17,7 @@ typedef struct {
 */
 import "C"
 import (
+	"encoding/hex"
 	"encoding/json"
 	"fmt"
 	"os"
@@ -138,6 +139,46 @@ func VerifyGroth16Bn254(dataDir *C.char, proof *C.char, vkeyHash *C.char, commit
 	return nil
 }
 
+//export CeremonyInitGroth16Bn254
+func CeremonyInitGroth16Bn254(dataDir *C.char, phase1Path *C.char, outPath *C.char) *C.char {
+	err := sp1.CeremonyInit(C.GoString(dataDir), C.GoString(phase1Path), C.GoString(outPath))
+	if err != nil {
+		return C.CString(err.Error())
+	}
+	return nil
+}
+
+//export CeremonyContributeGroth16Bn254
+func CeremonyContributeGroth16Bn254(inPath *C.char, outPath *C.char) *C.char {
+	err := sp1.CeremonyContribute(C.GoString(inPath), C.GoString(outPath))
+	if err != nil {
+		return C.CString(err.Error())
+	}
+	return nil
+}
+
+//export CeremonyVerifyGroth16Bn254
+func CeremonyVerifyGroth16Bn254(prevPath *C.char, nextPath *C.char) *C.char {
+	err := sp1.CeremonyVerify(C.GoString(prevPath), C.GoString(nextPath))
+	if err != nil {
+		return C.CString(err.Error())
+	}
+	return nil
+}
+
+//export CeremonyFinalizeGroth16Bn254
+func CeremonyFinalizeGroth16Bn254(dataDir *C.char, phase1Path *C.char, phase2Path *C.char, beacon *C.char, iterations C.int) *C.char {
+	beaconBytes, err := hex.DecodeString(C.GoString(beacon))
+	if err != nil {
+		return C.CString(err.Error())
+	}
+	err = sp1.CeremonyFinalize(C.GoString(dataDir), C.GoString(phase1Path), C.GoString(phase2Path), beaconBytes, int(iterations))
+	if err != nil {
+		return C.CString(err.Error())
+	}
+	return nil
+}
+
 //export TestGroth16Bn254
 func TestGroth16Bn254(witnessJson *C.char, constraintsJson *C.char) *C.char {
 	// Because of the global env variables used here, we need to lock this function
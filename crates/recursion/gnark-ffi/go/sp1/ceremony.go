@@ -0,0 +1,268 @@
+package sp1
+
+import (
+	"bytes"
+	"crypto/sha256"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"math/big"
+	"os"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	curve "github.com/consensys/gnark-crypto/ecc/bn254"
+	"github.com/consensys/gnark-crypto/ecc/bn254/fr"
+	groth16 "github.com/consensys/gnark/backend/groth16"
+	"github.com/consensys/gnark/backend/groth16/bn254/mpcsetup"
+	cs "github.com/consensys/gnark/constraint/bn254"
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/frontend/cs/r1cs"
+)
+
+// The domain separation tags used to derive the beacon contribution, and by gnark for δ.
+var beaconDst = []byte("sp1-groth16-ceremony-beacon")
+var deltaDst byte = 1
+
+// CeremonyInit compiles the Groth16 circuit in dataDir and writes the initial phase 2 parameters
+// derived from the phase 1 parameters at phase1Path to outPath.
+func CeremonyInit(dataDir string, phase1Path string, outPath string) error {
+	r1cs, _, err := compileGroth16Circuit(dataDir)
+	if err != nil {
+		return err
+	}
+	phase1, err := readPhase1(phase1Path)
+	if err != nil {
+		return err
+	}
+	phase2, _ := mpcsetup.InitPhase2(r1cs, phase1)
+	return writeArtifact(outPath, &phase2)
+}
+
+// CeremonyContribute reads the phase 2 parameters at inPath, contributes fresh randomness to them
+// and writes the result to outPath.
+func CeremonyContribute(inPath string, outPath string) error {
+	phase2, err := readPhase2(inPath)
+	if err != nil {
+		return err
+	}
+	phase2.Contribute()
+	return writeArtifact(outPath, phase2)
+}
+
+// CeremonyVerify checks that the phase 2 parameters at nextPath are a valid contribution on top of
+// the parameters at prevPath.
+func CeremonyVerify(prevPath string, nextPath string) error {
+	prev, err := readPhase2(prevPath)
+	if err != nil {
+		return err
+	}
+	next, err := readPhase2(nextPath)
+	if err != nil {
+		return err
+	}
+	return mpcsetup.VerifyPhase2(prev, next)
+}
+
+// CeremonyFinalize applies the random beacon to the last phase 2 parameters at phase2Path, and
+// writes the resulting circuit, proving key, verifying key and Solidity verifier to dataDir.
+//
+// The beacon contribution is derived from 2^iterations rounds of SHA-256 over the beacon, so that
+// anyone can recompute it. The keys are checked by proving and verifying the witness in dataDir.
+func CeremonyFinalize(dataDir string, phase1Path string, phase2Path string, beacon []byte, iterations int) error {
+	r1cs, witnessInput, err := compileGroth16Circuit(dataDir)
+	if err != nil {
+		return err
+	}
+	phase1, err := readPhase1(phase1Path)
+	if err != nil {
+		return err
+	}
+	prev, err := readPhase2(phase2Path)
+	if err != nil {
+		return err
+	}
+
+	// Apply the beacon and check the result like any other contribution.
+	final, err := beaconContribution(prev, beacon, iterations)
+	if err != nil {
+		return err
+	}
+	if err := mpcsetup.VerifyPhase2(prev, final); err != nil {
+		return fmt.Errorf("invalid beacon contribution: %w", err)
+	}
+
+	// Extract the keys.
+	_, evals := mpcsetup.InitPhase2(r1cs, phase1)
+	pk, vk := mpcsetup.ExtractKeys(phase1, final, &evals, r1cs.GetNbConstraints())
+
+	// Check the keys with the witness the circuit was built with.
+	assignment := NewCircuit(witnessInput)
+	witness, err := frontend.NewWitness(&assignment, ecc.BN254.ScalarField())
+	if err != nil {
+		return err
+	}
+	proof, err := groth16.Prove(r1cs, &pk, witness)
+	if err != nil {
+		return err
+	}
+	publicWitness, err := witness.Public()
+	if err != nil {
+		return err
+	}
+	if err := groth16.Verify(proof, &vk, publicWitness); err != nil {
+		return fmt.Errorf("failed to verify proof with the ceremony keys: %w", err)
+	}
+
+	// Write the artifacts.
+	solidityVerifierFile, err := os.Create(dataDir + "/" + groth16VerifierContractPath)
+	if err != nil {
+		return err
+	}
+	defer solidityVerifierFile.Close()
+	if err := vk.ExportSolidity(solidityVerifierFile); err != nil {
+		return err
+	}
+	if err := writeArtifact(dataDir+"/"+groth16CircuitPath, r1cs); err != nil {
+		return err
+	}
+	if err := writeArtifact(dataDir+"/"+groth16VkPath, &vk); err != nil {
+		return err
+	}
+	if err := writeArtifact(dataDir+"/"+groth16PkPath, &pk); err != nil {
+		return err
+	}
+	return nil
+}
+
+// beaconContribution returns prev with a contribution deterministically derived from the beacon.
+//
+// This mirrors Phase2.Contribute, with δ and the public key secret taken from the beacon instead
+// of sampled at random.
+func beaconContribution(prev *mpcsetup.Phase2, beacon []byte, iterations int) (*mpcsetup.Phase2, error) {
+	if iterations < 0 || iterations > 63 {
+		return nil, fmt.Errorf("invalid number of beacon iterations: %d", iterations)
+	}
+	seed := beacon
+	for i := uint64(0); i < uint64(1)<<iterations; i++ {
+		digest := sha256.Sum256(seed)
+		seed = digest[:]
+	}
+	elements, err := fr.Hash(seed, beaconDst, 2)
+	if err != nil {
+		return nil, err
+	}
+	delta, s := elements[0], elements[1]
+	if delta.IsZero() || s.IsZero() {
+		return nil, errors.New("degenerate beacon")
+	}
+
+	var deltaInv fr.Element
+	var deltaBI, deltaInvBI, sBI big.Int
+	deltaInv.Inverse(&delta)
+	delta.BigInt(&deltaBI)
+	deltaInv.BigInt(&deltaInvBI)
+	s.BigInt(&sBI)
+
+	next := *prev
+	next.Parameters.G1.L = append([]curve.G1Affine(nil), prev.Parameters.G1.L...)
+	next.Parameters.G1.Z = append([]curve.G1Affine(nil), prev.Parameters.G1.Z...)
+
+	// Set the δ public key.
+	_, _, g1, _ := curve.Generators()
+	next.PublicKey.SG.ScalarMultiplication(&g1, &sBI)
+	next.PublicKey.SXG.ScalarMultiplication(&next.PublicKey.SG, &deltaBI)
+	var challenge bytes.Buffer
+	challenge.Write(next.PublicKey.SG.Marshal())
+	challenge.Write(next.PublicKey.SXG.Marshal())
+	challenge.Write(prev.Hash)
+	r, err := curve.HashToG2(challenge.Bytes(), []byte{deltaDst})
+	if err != nil {
+		return nil, err
+	}
+	next.PublicKey.XR.ScalarMultiplication(&r, &deltaBI)
+
+	// Update δ, and L and Z using δ⁻¹.
+	next.Parameters.G1.Delta.ScalarMultiplication(&prev.Parameters.G1.Delta, &deltaBI)
+	next.Parameters.G2.Delta.ScalarMultiplication(&prev.Parameters.G2.Delta, &deltaBI)
+	for i := range next.Parameters.G1.Z {
+		next.Parameters.G1.Z[i].ScalarMultiplication(&next.Parameters.G1.Z[i], &deltaInvBI)
+	}
+	for i := range next.Parameters.G1.L {
+		next.Parameters.G1.L[i].ScalarMultiplication(&next.Parameters.G1.L[i], &deltaInvBI)
+	}
+
+	// Hash the contribution, which is serialized followed by its previous hash.
+	var serialized bytes.Buffer
+	if _, err := next.WriteTo(&serialized); err != nil {
+		return nil, err
+	}
+	digest := sha256.Sum256(serialized.Bytes()[:serialized.Len()-len(next.Hash)])
+	next.Hash = digest[:]
+	return &next, nil
+}
+
+// compileGroth16Circuit compiles the Groth16 circuit described by the constraints and witness in
+// dataDir, as written by the Groth16 build.
+func compileGroth16Circuit(dataDir string) (*cs.R1CS, WitnessInput, error) {
+	os.Setenv("CONSTRAINTS_JSON", dataDir+"/"+constraintsJsonFile)
+	os.Setenv("GROTH16", "1")
+
+	var witnessInput WitnessInput
+	data, err := os.ReadFile(dataDir + "/" + groth16WitnessPath)
+	if err != nil {
+		return nil, witnessInput, err
+	}
+	if err := json.Unmarshal(data, &witnessInput); err != nil {
+		return nil, witnessInput, err
+	}
+
+	circuit := NewCircuit(witnessInput)
+	ccs, err := frontend.Compile(ecc.BN254.ScalarField(), r1cs.NewBuilder, &circuit)
+	if err != nil {
+		return nil, witnessInput, err
+	}
+	r1cs := ccs.(*cs.R1CS)
+
+	// The phase 2 of gnark does not produce the keys of Pedersen commitments.
+	if len(r1cs.GetCommitments().CommitmentIndexes()) > 0 {
+		return nil, witnessInput, errors.New("circuits with commitments are not supported by the ceremony")
+	}
+	return r1cs, witnessInput, nil
+}
+
+func readPhase1(path string) (*mpcsetup.Phase1, error) {
+	file, err := os.Open(path)
+	if err != nil {
+		return nil, err
+	}
+	defer file.Close()
+	var phase1 mpcsetup.Phase1
+	if _, err := phase1.ReadFrom(file); err != nil {
+		return nil, err
+	}
+	return &phase1, nil
+}
+
+func readPhase2(path string) (*mpcsetup.Phase2, error) {
+	file, err := os.Open(path)
+	if err != nil {
+		return nil, err
+	}
+	defer file.Close()
+	var phase2 mpcsetup.Phase2
+	if _, err := phase2.ReadFrom(file); err != nil {
+		return nil, err
+	}
+	return &phase2, nil
+}
+
+func writeArtifact(path string, value io.WriterTo) error {
+	file, err := os.Create(path)
+	if err != nil {
+		return err
+	}
+	defer file.Close()
+	_, err = value.WriteTo(file)
+	return err
+}
@@ -37,11 +37,27 @@ fn get_docker_image() -> String {
         .unwrap_or_else(|_| format!("ghcr.io/succinctlabs/sp1-gnark:{}", SP1_CIRCUIT_VERSION))
 }
 
+/// The platform of the image to run, which is the architecture of the host unless overridden by
+/// `SP1_GNARK_PLATFORM`.
+///
+/// The image is published for `linux/amd64` and `linux/arm64`. Selecting the native variant
+/// explicitly avoids running the amd64 image under emulation on aarch64 hosts such as Apple
+/// Silicon, where it is too slow to prove.
+fn get_docker_platform() -> String {
+    std::env::var("SP1_GNARK_PLATFORM").unwrap_or_else(|_| {
+        let arch = match std::env::consts::ARCH {
+            "aarch64" => "arm64",
+            _ => "amd64",
+        };
+        format!("linux/{}", arch)
+    })
+}
+
 /// Calls `docker run` with the given arguments and bind mounts.
 fn call_docker(args: &[&str], mounts: &[(&str, &str)]) -> Result<()> {
     log::info!("Running {} in docker", args[0]);
     let mut cmd = Command::new("docker");
-    cmd.args(["run", "--rm"]);
+    cmd.args(["run", "--rm", "--platform", &get_docker_platform()]);
     for (src, dest) in mounts {
         cmd.arg("-v").arg(format!("{}:{}", src, dest));
     }
@@ -175,6 +191,64 @@ pub fn test_groth16_bn254(witness_json: &str, constraints_json: &str) {
     test(ProofSystem::Groth16, witness_json, constraints_json).expect("failed to test with docker");
 }
 
+/// Runs a ceremony command in docker, mounting each `(path, dest)` and creating the output files.
+fn ceremony(
+    args: &[&str],
+    inputs: &[(&str, &str)],
+    outputs: &[(&str, &str)],
+) -> Result<(), String> {
+    for (path, _) in outputs {
+        std::fs::File::create(path).map_err(|e| e.to_string())?;
+    }
+    let mounts = inputs.iter().chain(outputs).copied().collect::<Vec<_>>();
+    assert_docker();
+    call_docker(args, &mounts).map_err(|e| e.to_string())
+}
+
+pub fn ceremony_init_groth16_bn254(
+    data_dir: &str,
+    phase1_path: &str,
+    out_path: &str,
+) -> Result<(), String> {
+    ceremony(
+        &["ceremony-init", "/circuit", "/phase1", "/output"],
+        &[(data_dir, "/circuit"), (phase1_path, "/phase1")],
+        &[(out_path, "/output")],
+    )
+}
+
+pub fn ceremony_contribute_groth16_bn254(in_path: &str, out_path: &str) -> Result<(), String> {
+    ceremony(
+        &["ceremony-contribute", "/input", "/output"],
+        &[(in_path, "/input")],
+        &[(out_path, "/output")],
+    )
+}
+
+pub fn ceremony_verify_groth16_bn254(prev_path: &str, next_path: &str) -> Result<(), String> {
+    ceremony(
+        &["ceremony-verify", "/prev", "/next"],
+        &[(prev_path, "/prev"), (next_path, "/next")],
+        &[],
+    )
+}
+
+pub fn ceremony_finalize_groth16_bn254(
+    data_dir: &str,
+    phase1_path: &str,
+    phase2_path: &str,
+    beacon: &[u8],
+    iterations: u32,
+) -> Result<(), String> {
+    let beacon = hex::encode(beacon);
+    let iterations = iterations.to_string();
+    ceremony(
+        &["ceremony-finalize", "/circuit", "/phase1", "/phase2", &beacon, &iterations],
+        &[(data_dir, "/circuit"), (phase1_path, "/phase1"), (phase2_path, "/phase2")],
+        &[],
+    )
+}
+
 pub fn test_babybear_poseidon2() {
     unimplemented!()
 }
@@ -198,6 +198,80 @@ pub fn test_babybear_poseidon2() {
     }
 }
 
+pub fn ceremony_init_groth16_bn254(
+    data_dir: &str,
+    phase1_path: &str,
+    out_path: &str,
+) -> Result<(), String> {
+    let data_dir = CString::new(data_dir).expect("CString::new failed");
+    let phase1_path = CString::new(phase1_path).expect("CString::new failed");
+    let out_path = CString::new(out_path).expect("CString::new failed");
+    unsafe {
+        err_ptr_to_result(bind::CeremonyInitGroth16Bn254(
+            data_dir.as_ptr() as *mut c_char,
+            phase1_path.as_ptr() as *mut c_char,
+            out_path.as_ptr() as *mut c_char,
+        ))
+    }
+}
+
+pub fn ceremony_contribute_groth16_bn254(in_path: &str, out_path: &str) -> Result<(), String> {
+    let in_path = CString::new(in_path).expect("CString::new failed");
+    let out_path = CString::new(out_path).expect("CString::new failed");
+    unsafe {
+        err_ptr_to_result(bind::CeremonyContributeGroth16Bn254(
+            in_path.as_ptr() as *mut c_char,
+            out_path.as_ptr() as *mut c_char,
+        ))
+    }
+}
+
+pub fn ceremony_verify_groth16_bn254(prev_path: &str, next_path: &str) -> Result<(), String> {
+    let prev_path = CString::new(prev_path).expect("CString::new failed");
+    let next_path = CString::new(next_path).expect("CString::new failed");
+    unsafe {
+        err_ptr_to_result(bind::CeremonyVerifyGroth16Bn254(
+            prev_path.as_ptr() as *mut c_char,
+            next_path.as_ptr() as *mut c_char,
+        ))
+    }
+}
+
+pub fn ceremony_finalize_groth16_bn254(
+    data_dir: &str,
+    phase1_path: &str,
+    phase2_path: &str,
+    beacon: &[u8],
+    iterations: u32,
+) -> Result<(), String> {
+    let data_dir = CString::new(data_dir).expect("CString::new failed");
+    let phase1_path = CString::new(phase1_path).expect("CString::new failed");
+    let phase2_path = CString::new(phase2_path).expect("CString::new failed");
+    let beacon = CString::new(hex::encode(beacon)).expect("CString::new failed");
+    unsafe {
+        err_ptr_to_result(bind::CeremonyFinalizeGroth16Bn254(
+            data_dir.as_ptr() as *mut c_char,
+            phase1_path.as_ptr() as *mut c_char,
+            phase2_path.as_ptr() as *mut c_char,
+            beacon.as_ptr() as *mut c_char,
+            iterations as i32,
+        ))
+    }
+}
+
+/// Converts an error returned by the go code into a result.
+///
+/// # Safety
+/// This function frees the error message, so the caller must ensure that the pointer is not used
+/// after this function is called.
+unsafe fn err_ptr_to_result(err_ptr: *mut c_char) -> Result<(), String> {
+    if err_ptr.is_null() {
+        Ok(())
+    } else {
+        Err(c_char_ptr_to_string(err_ptr))
+    }
+}
+
 /// Converts a C string into a Rust String.
 ///
 /// # Safety
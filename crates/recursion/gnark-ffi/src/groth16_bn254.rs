@@ -5,7 +5,10 @@ use std::{
 };
 
 use crate::{
-    ffi::{build_groth16_bn254, prove_groth16_bn254, test_groth16_bn254, verify_groth16_bn254},
+    ffi::{
+        build_groth16_bn254, ceremony_finalize_groth16_bn254, prove_groth16_bn254,
+        test_groth16_bn254, verify_groth16_bn254,
+    },
     witness::GnarkWitness,
     Groth16Bn254Proof,
 };
@@ -72,10 +75,38 @@ impl Groth16Bn254Prover {
         file.write_all(serialized.as_bytes()).unwrap();
 
         build_groth16_bn254(build_dir.to_str().unwrap());
+        Self::write_verifier_contracts(&build_dir);
+    }
+
+    /// Replaces the keys of the Groth16 circuit built in `build_dir` with the ones produced by a
+    /// phase 2 ceremony.
+    ///
+    /// The random beacon is applied as a last contribution on top of the parameters at `phase2`,
+    /// and the keys are extracted with the phase 1 parameters at `phase1`. The verifier contracts
+    /// are written again for the new verifying key.
+    pub fn finalize_ceremony(
+        build_dir: &Path,
+        phase1: &Path,
+        phase2: &Path,
+        beacon: &[u8],
+        iterations: u32,
+    ) -> Result<(), String> {
+        ceremony_finalize_groth16_bn254(
+            build_dir.to_str().unwrap(),
+            phase1.to_str().unwrap(),
+            phase2.to_str().unwrap(),
+            beacon,
+            iterations,
+        )?;
+        Self::write_verifier_contracts(build_dir);
+        Ok(())
+    }
 
-        // Write the corresponding asset files to the build dir.
+    /// Writes the SP1 verifier contract for the verifying key in `build_dir`, and adapts the
+    /// Groth16 verifier contract exported by Gnark to it.
+    fn write_verifier_contracts(build_dir: &Path) {
         let sp1_verifier_path = build_dir.join("SP1VerifierGroth16.sol");
-        let vkey_hash = Self::get_vkey_hash(&build_dir);
+        let vkey_hash = Self::get_vkey_hash(build_dir);
         let sp1_verifier_str = include_str!("../assets/SP1VerifierGroth16.txt")
             .replace("{SP1_CIRCUIT_VERSION}", SP1_CIRCUIT_VERSION)
             .replace("{VERIFIER_HASH}", format!("0x{}", hex::encode(vkey_hash)).as_str())
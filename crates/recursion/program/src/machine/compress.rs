@@ -62,6 +62,17 @@ pub enum ReduceProgramType {
     Reduce = 2,
 }
 
+impl ReduceProgramType {
+    /// The name of the step of the proving pipeline verifying this type of batch.
+    pub const fn step(&self) -> &'static str {
+        match self {
+            ReduceProgramType::Core => "lift",
+            ReduceProgramType::Deferred => "lift deferred",
+            ReduceProgramType::Reduce => "join",
+        }
+    }
+}
+
 /// An input layout for the reduce verifier.
 pub struct SP1CompressMemoryLayout<'a, SC: StarkGenericConfig, A: MachineAir<SC::Val>> {
     pub compress_vk: &'a StarkVerifyingKey<SC>,
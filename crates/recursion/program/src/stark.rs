@@ -123,6 +123,20 @@ where
         let ShardCommitmentVariable { main_commit, permutation_commit, quotient_commit } =
             commitment;
 
+        // Observe the chip set of the machine, and which of its chips are present in the shard.
+        for id in machine.chip_set_id() {
+            let id: Felt<_> = builder.eval(*id);
+            challenger.observe(builder, id);
+        }
+        for i in 0..machine.chips().len() {
+            let index = builder.get(&proof.sorted_idxs, i);
+            let present: Felt<_> = builder.eval(C::F::zero());
+            builder.if_ne(index, C::N::from_canonical_usize(EMPTY)).then(|builder| {
+                builder.assign(present, C::F::one());
+            });
+            challenger.observe(builder, present);
+        }
+
         let permutation_challenges =
             (0..2).map(|_| challenger.sample_ext(builder)).collect::<Vec<_>>();
 
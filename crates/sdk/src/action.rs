@@ -1,13 +1,17 @@
-use sp1_core_executor::{ExecutionReport, HookEnv, SP1ContextBuilder};
+use sp1_core_executor::{
+    ExecutionObserver, ExecutionReport, HookEnv, InputSource, PrivacyMode, Program, ProofMonitor,
+    ProofProgress, SP1ContextBuilder, StackGuard, SyscallTracer,
+};
 use sp1_core_machine::io::SP1Stdin;
 use sp1_primitives::io::SP1PublicValues;
 use sp1_prover::{components::DefaultProverComponents, SP1ProvingKey};
 
-use anyhow::{Ok, Result};
-use sp1_stark::{SP1CoreOpts, SP1ProverOpts};
-use std::time::Duration;
+use sp1_stark::{ReduceStrategy, SP1CoreOpts, SP1ProverOpts};
+use std::{env, sync::Arc, thread::JoinHandle, time::Duration};
 
-use crate::{provers::ProofOpts, Prover, SP1ProofKind, SP1ProofWithPublicValues};
+use crate::{
+    provers::ProofOpts, Prover, SP1Error, SP1ProofKind, SP1ProofWithPublicValues, SP1Result,
+};
 
 /// Builder to prepare and configure execution of a program on an input.
 /// May be run with [Self::run].
@@ -32,8 +36,9 @@ impl<'a> Execute<'a> {
     }
 
     /// Execute the program on the input, consuming the built action `self`.
-    pub fn run(self) -> Result<(SP1PublicValues, ExecutionReport)> {
+    pub fn run(self) -> SP1Result<(SP1PublicValues, ExecutionReport)> {
         let Self { prover, elf, stdin, mut context_builder } = self;
+        Program::from(elf).map_err(SP1Error::setup)?;
         let context = context_builder.build();
         Ok(prover.sp1_prover().execute(elf, &stdin, context)?)
     }
@@ -69,6 +74,42 @@ impl<'a> Execute<'a> {
         self.context_builder.max_cycles(max_cycles);
         self
     }
+
+    /// Detect stack overflows with a guard region below the guest stack.
+    ///
+    /// If the guest writes into the guard region, execution will return
+    /// [sp1_core_executor::ExecutionError::StackOverflow].
+    pub fn stack_guard(mut self, stack_guard: StackGuard) -> Self {
+        self.context_builder.stack_guard(stack_guard);
+        self
+    }
+
+    /// Write the syscalls made by the guest, and optionally its basic blocks, to the given tracer
+    /// as JSON lines.
+    pub fn trace_syscalls(mut self, tracer: SyscallTracer<'a>) -> Self {
+        self.context_builder.syscall_tracer(tracer);
+        self
+    }
+
+    /// Notify the given observer of the shards, syscalls and memory pages of the execution, to
+    /// stream them into another system as the program runs.
+    pub fn observer(mut self, observer: Arc<dyn ExecutionObserver + 'a>) -> Self {
+        self.context_builder.observer(observer);
+        self
+    }
+
+    /// Redact the guest's output, panic messages and other private data from logs and errors.
+    pub fn privacy_mode(mut self, privacy_mode: PrivacyMode) -> Self {
+        self.context_builder.privacy_mode(privacy_mode);
+        self
+    }
+
+    /// Pull stdin elements from the given source when the guest reads past the elements of the
+    /// stdin. See [ProverClient::execute_streaming](super::ProverClient::execute_streaming).
+    pub fn input_source(mut self, input_source: InputSource<'a>) -> Self {
+        self.context_builder.input_source(input_source);
+        self
+    }
 }
 
 /// Builder to prepare and configure proving execution of a program on an input.
@@ -81,7 +122,9 @@ pub struct Prove<'a> {
     stdin: SP1Stdin,
     core_opts: SP1CoreOpts,
     recursion_opts: SP1CoreOpts,
+    reduce_strategy: ReduceStrategy,
     timeout: Option<Duration>,
+    skip_simulation: bool,
 }
 
 impl<'a> Prove<'a> {
@@ -102,12 +145,14 @@ impl<'a> Prove<'a> {
             context_builder: Default::default(),
             core_opts: SP1CoreOpts::default(),
             recursion_opts: SP1CoreOpts::recursion(),
+            reduce_strategy: ReduceStrategy::default(),
             timeout: None,
+            skip_simulation: env::var("SKIP_SIMULATION").map(|val| val == "true").unwrap_or(false),
         }
     }
 
     /// Prove the execution of the program on the input, consuming the built action `self`.
-    pub fn run(self) -> Result<SP1ProofWithPublicValues> {
+    pub fn run(self) -> SP1Result<SP1ProofWithPublicValues> {
         let Self {
             prover,
             kind,
@@ -116,15 +161,25 @@ impl<'a> Prove<'a> {
             mut context_builder,
             core_opts,
             recursion_opts,
+            reduce_strategy,
             timeout,
+            skip_simulation,
         } = self;
-        let opts = SP1ProverOpts { core_opts, recursion_opts };
-        let proof_opts = ProofOpts { sp1_prover_opts: opts, timeout };
+        let opts = SP1ProverOpts { core_opts, recursion_opts, reduce_strategy };
+        let proof_opts = ProofOpts { sp1_prover_opts: opts, timeout, skip_simulation };
         let context = context_builder.build();
 
         prover.prove(pk, stdin, proof_opts, context, kind)
     }
 
+    /// Set the proof kind, for callers that choose it at runtime.
+    ///
+    /// Equivalent to calling [Self::core], [Self::compressed], [Self::plonk] or [Self::groth16].
+    pub fn kind(mut self, kind: SP1ProofKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
     /// Set the proof kind to the core mode. This is the default.
     pub fn core(mut self) -> Self {
         self.kind = SP1ProofKind::Core;
@@ -190,6 +245,13 @@ impl<'a> Prove<'a> {
         self
     }
 
+    /// Set whether repeated keccak permutations of the same state within a shard reuse the result
+    /// of the first one, and are proven in a single row instead of 24.
+    pub fn memoize_syscalls(mut self, value: bool) -> Self {
+        self.core_opts.memoize_syscalls = value;
+        self
+    }
+
     /// Set the maximum number of cpu cycles to use for execution.
     ///
     /// If the cycle limit is exceeded, execution will return
@@ -199,6 +261,12 @@ impl<'a> Prove<'a> {
         self
     }
 
+    /// Set the order in which the recursive proofs are reduced into a compressed proof.
+    pub fn reduce_strategy(mut self, strategy: ReduceStrategy) -> Self {
+        self.reduce_strategy = strategy;
+        self
+    }
+
     /// Set the timeout for the proof's generation.
     ///
     /// This parameter is only used when the prover is run in network mode.
@@ -206,4 +274,74 @@ impl<'a> Prove<'a> {
         self.timeout = Some(timeout);
         self
     }
+
+    /// Set whether to skip executing the program locally before requesting the proof, which
+    /// catches failing executions before they are sent. Defaults to the value of the
+    /// `SKIP_SIMULATION` environment variable.
+    ///
+    /// This parameter is only used when the prover is run in network mode.
+    pub fn skip_simulation(mut self, value: bool) -> Self {
+        self.skip_simulation = value;
+        self
+    }
+
+    /// Set the monitor through which the proof's generation can be cancelled and its progress
+    /// tracked from another thread.
+    ///
+    /// This parameter is only used when the prover is run in local mode.
+    pub fn monitor(mut self, monitor: Arc<ProofMonitor>) -> Self {
+        self.context_builder.monitor(monitor);
+        self
+    }
+
+    /// Redact the guest's output, panic messages and other private data from logs and errors,
+    /// and skip dumping shard traces.
+    ///
+    /// This parameter is only used when the prover is run in local mode.
+    pub fn privacy_mode(mut self, privacy_mode: PrivacyMode) -> Self {
+        self.context_builder.privacy_mode(privacy_mode);
+        self
+    }
+}
+
+/// A handle to a proof being generated in the background.
+///
+/// Returned by [ProverClient::prove_async](super::ProverClient::prove_async).
+pub struct ProofHandle {
+    monitor: Arc<ProofMonitor>,
+    handle: JoinHandle<SP1Result<SP1ProofWithPublicValues>>,
+}
+
+impl ProofHandle {
+    pub(crate) fn new(
+        monitor: Arc<ProofMonitor>,
+        handle: JoinHandle<SP1Result<SP1ProofWithPublicValues>>,
+    ) -> Self {
+        Self { monitor, handle }
+    }
+
+    /// Cancel the proof's generation.
+    ///
+    /// The prover stops at its next cancellation point, between shards or between recursion
+    /// layers, and [Self::wait] then returns an error.
+    pub fn cancel(&self) {
+        self.monitor.cancel();
+    }
+
+    /// The number of core shards proven so far, and their total once it is known.
+    pub fn progress(&self) -> ProofProgress {
+        self.monitor.progress()
+    }
+
+    /// Whether the proof's generation has finished, either successfully or not.
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    /// Wait for the proof's generation to finish and return the proof.
+    pub fn wait(self) -> SP1Result<SP1ProofWithPublicValues> {
+        self.handle
+            .join()
+            .map_err(|_| SP1Error::Other(anyhow::anyhow!("proving thread panicked")))?
+    }
 }
@@ -0,0 +1,184 @@
+//! The error type returned by the public entry points of the SDK.
+//!
+//! Every failure surfaced by [ProverClient](crate::ProverClient) and its actions is an
+//! [SP1Error]. Its variants and their [codes](SP1Error::code) are stable across releases, so
+//! callers can match on them, and services wrapping the SDK can report them, without parsing
+//! error messages.
+
+use std::io;
+
+use sp1_core_executor::ExecutionError;
+use sp1_core_machine::utils::SP1CoreProverError;
+use sp1_prover::SP1RecursionProverError;
+use thiserror::Error;
+
+use crate::provers::SP1VerificationError;
+
+/// A result whose error is an [SP1Error].
+pub type SP1Result<T> = Result<T, SP1Error>;
+
+/// An error returned by the SDK.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum SP1Error {
+    /// The program could not be set up, for example because its ELF is invalid.
+    #[error("setup failed: {0}")]
+    Setup(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// The execution of the program failed.
+    #[error("execution failed: {0}")]
+    Execution(#[from] ExecutionError),
+    /// The core prover failed for a reason other than the execution of the program.
+    #[error("core proving failed: {0}")]
+    Proving(#[source] SP1CoreProverError),
+    /// The recursion prover failed.
+    #[error("recursive proving failed: {0}")]
+    Recursion(#[source] SP1RecursionProverError),
+    /// A proof was rejected by the verifier.
+    #[error("verification failed: {0}")]
+    Verification(#[from] SP1VerificationError),
+    /// The proof was cancelled through its [ProofMonitor](crate::ProofMonitor).
+    #[error("proving was cancelled")]
+    Cancelled,
+    /// The proof was not generated before the requested timeout.
+    #[error("proving timed out")]
+    Timeout,
+    /// An I/O operation failed.
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    /// A proof or key could not be encoded or decoded.
+    #[error("encoding error: {0}")]
+    Encoding(#[source] anyhow::Error),
+    /// Any other error, such as a failed request to the prover network.
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+impl SP1Error {
+    /// The stable numeric code of the error.
+    ///
+    /// | Code | Variant |
+    /// |------|---------|
+    /// | 1 | [Setup](Self::Setup) |
+    /// | 2 | [Execution](Self::Execution) |
+    /// | 3 | [Proving](Self::Proving) |
+    /// | 4 | [Recursion](Self::Recursion) |
+    /// | 5 | [Verification](Self::Verification) |
+    /// | 6 | [Cancelled](Self::Cancelled) |
+    /// | 7 | [Timeout](Self::Timeout) |
+    /// | 8 | [Io](Self::Io) |
+    /// | 9 | [Encoding](Self::Encoding) |
+    /// | 255 | [Other](Self::Other) |
+    pub const fn code(&self) -> u8 {
+        match self {
+            SP1Error::Setup(_) => 1,
+            SP1Error::Execution(_) => 2,
+            SP1Error::Proving(_) => 3,
+            SP1Error::Recursion(_) => 4,
+            SP1Error::Verification(_) => 5,
+            SP1Error::Cancelled => 6,
+            SP1Error::Timeout => 7,
+            SP1Error::Io(_) => 8,
+            SP1Error::Encoding(_) => 9,
+            SP1Error::Other(_) => 255,
+        }
+    }
+
+    /// The stable name of the error's variant, in snake case.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            SP1Error::Setup(_) => "setup",
+            SP1Error::Execution(_) => "execution",
+            SP1Error::Proving(_) => "proving",
+            SP1Error::Recursion(_) => "recursion",
+            SP1Error::Verification(_) => "verification",
+            SP1Error::Cancelled => "cancelled",
+            SP1Error::Timeout => "timeout",
+            SP1Error::Io(_) => "io",
+            SP1Error::Encoding(_) => "encoding",
+            SP1Error::Other(_) => "other",
+        }
+    }
+
+    /// Wraps the error of a failed setup.
+    pub(crate) fn setup(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        SP1Error::Setup(error.into())
+    }
+}
+
+impl From<SP1CoreProverError> for SP1Error {
+    fn from(error: SP1CoreProverError) -> Self {
+        match error {
+            SP1CoreProverError::ExecutionError(e) => SP1Error::Execution(e),
+            SP1CoreProverError::IoError(e) => SP1Error::Io(e),
+            SP1CoreProverError::Cancelled => SP1Error::Cancelled,
+            e => SP1Error::Proving(e),
+        }
+    }
+}
+
+impl From<SP1RecursionProverError> for SP1Error {
+    fn from(error: SP1RecursionProverError) -> Self {
+        match error {
+            SP1RecursionProverError::Cancelled => SP1Error::Cancelled,
+            e => SP1Error::Recursion(e),
+        }
+    }
+}
+
+impl From<anyhow::Error> for SP1Error {
+    /// Recovers the variant of errors that were passed through [anyhow], falling back to
+    /// [SP1Error::Other].
+    fn from(error: anyhow::Error) -> Self {
+        let error = match error.downcast::<SP1Error>() {
+            Ok(e) => return e,
+            Err(error) => error,
+        };
+        let error = match error.downcast::<ExecutionError>() {
+            Ok(e) => return e.into(),
+            Err(error) => error,
+        };
+        let error = match error.downcast::<SP1CoreProverError>() {
+            Ok(e) => return e.into(),
+            Err(error) => error,
+        };
+        let error = match error.downcast::<SP1RecursionProverError>() {
+            Ok(e) => return e.into(),
+            Err(error) => error,
+        };
+        let error = match error.downcast::<SP1VerificationError>() {
+            Ok(e) => return e.into(),
+            Err(error) => error,
+        };
+        match error.downcast::<io::Error>() {
+            Ok(e) => e.into(),
+            Err(error) => SP1Error::Other(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(SP1Error::Cancelled.code(), 6);
+        assert_eq!(SP1Error::Timeout.name(), "timeout");
+
+        // Errors of the provers map to the SDK's variants.
+        let error: SP1Error = SP1CoreProverError::Cancelled.into();
+        assert!(matches!(error, SP1Error::Cancelled));
+        let error: SP1Error =
+            SP1CoreProverError::ExecutionError(ExecutionError::Breakpoint()).into();
+        assert_eq!(error.code(), 2);
+
+        // Variants survive a round trip through anyhow.
+        let error: SP1Error = anyhow::Error::from(SP1RecursionProverError::Cancelled).into();
+        assert!(matches!(error, SP1Error::Cancelled));
+        let error: SP1Error = anyhow::Error::from(SP1Error::Timeout).into();
+        assert!(matches!(error, SP1Error::Timeout));
+        let error: SP1Error = anyhow::anyhow!("network unreachable").into();
+        assert_eq!(error.name(), "other");
+        assert_eq!(error.to_string(), "network unreachable");
+    }
+}
@@ -21,6 +21,9 @@ pub fn install_circuit_artifacts_dir() -> PathBuf {
 }
 
 /// Tries to install the circuit artifacts if they are not already installed.
+///
+/// They are copied from the artifact store selected by `$SP1_ARTIFACT_STORE` if it is set, so that
+/// provers of a cluster share one copy, and downloaded otherwise.
 pub fn try_install_circuit_artifacts() -> PathBuf {
     let build_dir = install_circuit_artifacts_dir();
 
@@ -29,6 +32,19 @@ pub fn try_install_circuit_artifacts() -> PathBuf {
             "[sp1] circuit artifacts already seem to exist at {}. if you want to re-download them, delete the directory",
             build_dir.display()
         );
+    } else if let Some(store) =
+        sp1_prover::artifact_store::from_env().expect("failed to open the artifact store")
+    {
+        println!(
+            "[sp1] circuit artifacts for version {} do not exist at {}. copying them from ${}...",
+            SP1_CIRCUIT_VERSION,
+            build_dir.display(),
+            sp1_prover::artifact_store::SP1_ARTIFACT_STORE
+        );
+        let prefix = sp1_prover::artifact_store::circuit_artifacts_prefix();
+        let copied = sp1_prover::artifact_store::download_dir(store.as_ref(), &prefix, &build_dir)
+            .expect("failed to copy the circuit artifacts from the artifact store");
+        assert!(copied > 0, "the artifact store has no circuit artifacts under {prefix}");
     } else {
         cfg_if! {
             if #[cfg(feature = "network")] {
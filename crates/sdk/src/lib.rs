@@ -12,7 +12,9 @@ pub mod proto {
 }
 pub mod action;
 pub mod artifacts;
+pub mod error;
 pub mod install;
+pub mod migration;
 #[cfg(feature = "network")]
 pub mod network;
 #[cfg(feature = "network")]
@@ -21,26 +23,42 @@ pub use crate::network::prover::NetworkProver;
 pub use crate::provers::CudaProver;
 
 pub mod proof;
+pub mod provenance;
 pub mod provers;
+pub mod rollup;
 pub mod utils {
-    pub use sp1_core_machine::utils::setup_logger;
+    pub use sp1_core_machine::utils::{setup_logger, shutdown_logger};
 }
 
 use cfg_if::cfg_if;
+pub use error::{SP1Error, SP1Result};
+pub use migration::{MigrationInput, MigrationOutput};
 pub use proof::*;
+pub use provenance::*;
 pub use provers::SP1VerificationError;
+pub use rollup::{BlockTransition, RollupInput, RollupOutput};
+use sp1_core_executor::Program;
 use sp1_prover::components::DefaultProverComponents;
 
-use std::env;
+use std::{env, sync::Arc};
 
 #[cfg(feature = "network")]
 use {std::future::Future, tokio::task::block_in_place};
 
 pub use provers::{CpuProver, MockProver, Prover};
 
-pub use sp1_core_executor::{ExecutionReport, HookEnv, SP1Context, SP1ContextBuilder};
-pub use sp1_core_machine::{io::SP1Stdin, riscv::cost::CostEstimator, SP1_CIRCUIT_VERSION};
-pub use sp1_primitives::io::SP1PublicValues;
+pub use sp1_build::{elf_metadata, include_elf, ElfMetadata};
+pub use sp1_core_executor::{
+    embed_rom, ExecutionError, ExecutionObserver, ExecutionReport, HookEnv, InputSource,
+    PrivacyMode, ProofMonitor, ProofProgress, SP1Context, SP1ContextBuilder, SyscallTracer,
+    TraceEvent,
+};
+pub use sp1_core_machine::{
+    io::{SP1Stdin, SP1StdinBuilder},
+    riscv::cost::CostEstimator,
+    SP1_CIRCUIT_VERSION,
+};
+pub use sp1_primitives::io::{PublicValuesDomain, SP1PublicValues};
 pub use sp1_prover::{
     CoreSC, HashableKey, InnerSC, OuterSC, PlonkBn254Proof, SP1Prover, SP1ProvingKey,
     SP1VerifyingKey,
@@ -101,8 +119,10 @@ impl ProverClient {
 
     /// Creates a new [ProverClient] with the mock prover.
     ///
-    /// Recommended for testing and development. You can also use [ProverClient::new] to set the
-    /// prover to `mock` with the `SP1_PROVER` enviroment variable.
+    /// Recommended for testing and development. The program is executed and its public values are
+    /// checked, but the proofs are returned instantly and can only be verified by the mock prover.
+    /// You can also use [ProverClient::new] to set the prover to `mock` with the `SP1_PROVER`
+    /// enviroment variable.
     ///
     /// ### Examples
     ///
@@ -183,6 +203,61 @@ impl ProverClient {
         action::Execute::new(self.prover.as_ref(), elf, stdin)
     }
 
+    /// Prepare to execute the given program on the given input, writing one JSON line per syscall
+    /// made by the guest to `tracer`. See [Self::execute] and [TraceEvent] for the format.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{ProverClient, SP1Stdin, SyscallTracer};
+    /// use std::{fs::File, io::BufWriter};
+    ///
+    /// let elf = include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    /// let client = ProverClient::new();
+    /// let mut stdin = SP1Stdin::new();
+    /// stdin.write(&10usize);
+    ///
+    /// // Trace the syscalls and basic blocks of the program to a file.
+    /// let file = BufWriter::new(File::create("trace.jsonl").unwrap());
+    /// let tracer = SyscallTracer::new(file).with_basic_blocks();
+    /// let (public_values, report) = client.execute_with_trace(elf, stdin, tracer).run().unwrap();
+    /// ```
+    pub fn execute_with_trace<'a>(
+        &'a self,
+        elf: &'a [u8],
+        stdin: SP1Stdin,
+        tracer: SyscallTracer<'a>,
+    ) -> action::Execute<'a> {
+        self.execute(elf, stdin).trace_syscalls(tracer)
+    }
+
+    /// Prepare to execute the given program, supplying its stdin lazily from `input_stream`. See
+    /// [Self::execute].
+    ///
+    /// Instead of buffering the whole stdin upfront, the next element is pulled from the stream
+    /// when the guest reads past the elements pulled so far. Each element is read by the guest like
+    /// one written with [SP1Stdin::write_vec], so values read with `sp1_zkvm::io::read` must be
+    /// serialized with `bincode`. Use [InputSource::from_channel] to feed the elements from
+    /// another thread or an async task.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{ProverClient, SP1Stdin};
+    ///
+    /// let elf = include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    /// let client = ProverClient::new();
+    ///
+    /// // The input is only serialized when the guest reads it.
+    /// let input_stream = (0..1).map(|_| bincode::serialize(&10usize).unwrap());
+    /// let (public_values, report) = client.execute_streaming(elf, input_stream).run().unwrap();
+    /// ```
+    pub fn execute_streaming<'a, I>(&'a self, elf: &'a [u8], input_stream: I) -> action::Execute<'a>
+    where
+        I: IntoIterator<Item = Vec<u8>>,
+        I::IntoIter: Send + 'a,
+    {
+        self.execute(elf, SP1Stdin::new()).input_source(InputSource::new(input_stream))
+    }
+
     /// Prepare to prove the execution of the given program with the given input in the default
     /// mode. The returned [action::Prove] may be configured via its methods before running.
     /// For example, calling [action::Prove::compress] sets the mode to compressed mode.
@@ -216,6 +291,43 @@ impl ProverClient {
         action::Prove::new(self.prover.as_ref(), pk, stdin)
     }
 
+    /// Start proving the execution of the given program with the given input in the background,
+    /// in the given mode.
+    ///
+    /// The returned [action::ProofHandle] can be used to track the progress of the proof, to
+    /// cancel it if it is no longer needed, and to wait for it to finish.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use std::sync::Arc;
+    ///
+    /// use sp1_sdk::{ProverClient, SP1ProofKind, SP1Stdin};
+    ///
+    /// let elf = include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    /// let client = Arc::new(ProverClient::new());
+    /// let (pk, vk) = client.setup(elf);
+    /// let mut stdin = SP1Stdin::new();
+    /// stdin.write(&10usize);
+    ///
+    /// let handle = client.prove_async(Arc::new(pk), stdin, SP1ProofKind::Compressed);
+    /// println!("progress: {:?}", handle.progress());
+    /// let proof = handle.wait().unwrap();
+    /// ```
+    pub fn prove_async(
+        self: &Arc<Self>,
+        pk: Arc<SP1ProvingKey>,
+        stdin: SP1Stdin,
+        kind: SP1ProofKind,
+    ) -> action::ProofHandle {
+        let monitor = Arc::new(ProofMonitor::new());
+        let client = Arc::clone(self);
+        let handle = {
+            let monitor = Arc::clone(&monitor);
+            std::thread::spawn(move || client.prove(&pk, stdin).kind(kind).monitor(monitor).run())
+        };
+        action::ProofHandle::new(monitor, handle)
+    }
+
     /// Verifies that the given proof is valid and matches the given verification key produced by
     /// [Self::setup].
     ///
@@ -231,12 +343,60 @@ impl ProverClient {
     /// let proof = client.prove(&pk, stdin).run().unwrap();
     /// client.verify(&proof, &vk).unwrap();
     /// ```
-    pub fn verify(
+    pub fn verify(&self, proof: &SP1ProofWithPublicValues, vk: &SP1VerifyingKey) -> SP1Result<()> {
+        Ok(self.prover.verify(proof, vk)?)
+    }
+
+    /// Verifies that the given signed proof was signed by `signer`, and that the proof is valid
+    /// and matches the given verification key produced by [Self::setup].
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{ProverClient, SignedProof, VerifyingKey};
+    ///
+    /// let elf = include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    /// let client = ProverClient::new();
+    /// let (_, vk) = client.setup(elf);
+    /// let signed = SignedProof::load("proof.signed.bin").unwrap();
+    /// let operator = VerifyingKey::from_bytes(&[0u8; 32]).unwrap();
+    /// client.verify_signed(&signed, &vk, &operator).unwrap();
+    /// ```
+    pub fn verify_signed(
+        &self,
+        signed: &SignedProof,
+        vk: &SP1VerifyingKey,
+        signer: &VerifyingKey,
+    ) -> SP1Result<()> {
+        signed.verify_signed_by(signer).map_err(SP1VerificationError::InvalidSignature)?;
+        self.verify(&signed.proof, vk)
+    }
+
+    /// Verifies that the given proof is valid and matches the given verification key produced by
+    /// [Self::setup], and that it was generated for `domain` by a program built with the
+    /// `replay-protection` feature of `sp1-zkvm`.
+    ///
+    /// Checking whether the nonce was used before is left to the caller.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{ProverClient, PublicValuesDomain, SP1Stdin};
+    ///
+    /// let elf = include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    /// let client = ProverClient::new();
+    /// let (pk, vk) = client.setup(elf);
+    /// let mut stdin = SP1Stdin::new();
+    /// stdin.write_public_values_domain([1; 32], 7);
+    /// let proof = client.prove(&pk, stdin).run().unwrap();
+    /// client.verify_with_domain(&proof, &vk, &PublicValuesDomain::new([1; 32], 7)).unwrap();
+    /// ```
+    pub fn verify_with_domain(
         &self,
         proof: &SP1ProofWithPublicValues,
         vk: &SP1VerifyingKey,
-    ) -> Result<(), SP1VerificationError> {
-        self.prover.verify(proof, vk)
+        domain: &PublicValuesDomain,
+    ) -> SP1Result<()> {
+        self.verify(proof, vk)?;
+        Ok(proof.verify_domain(domain)?)
     }
 
     /// Gets the current version of the SP1 zkVM.
@@ -265,6 +425,22 @@ impl ProverClient {
     pub fn setup(&self, elf: &[u8]) -> (SP1ProvingKey, SP1VerifyingKey) {
         self.prover.setup(elf)
     }
+
+    /// Like [Self::setup], but returns an [SP1Error::Setup] instead of panicking if the ELF is
+    /// not a valid SP1 program.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{ProverClient, SP1Error};
+    ///
+    /// let client = ProverClient::new();
+    /// let error = client.try_setup(b"not an elf").unwrap_err();
+    /// assert!(matches!(error, SP1Error::Setup(_)));
+    /// ```
+    pub fn try_setup(&self, elf: &[u8]) -> SP1Result<(SP1ProvingKey, SP1VerifyingKey)> {
+        Program::from(elf).map_err(SP1Error::setup)?;
+        Ok(self.prover.setup(elf))
+    }
 }
 
 impl Default for ProverClient {
@@ -294,7 +470,9 @@ mod tests {
 
     use sp1_prover::init::SP1PublicValues;
 
-    use crate::{utils, CostEstimator, ProverClient, SP1Stdin};
+    use std::sync::Arc;
+
+    use crate::{utils, CostEstimator, ProverClient, SP1Error, SP1ProofKind, SP1Stdin};
 
     #[test]
     fn test_execute() {
@@ -393,6 +571,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_prove_async_cancel() {
+        utils::setup_logger();
+        let client = Arc::new(ProverClient::local());
+        let elf =
+            include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let (pk, _) = client.setup(elf);
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10usize);
+
+        // Cancelling before the first shard is proven aborts the proof.
+        let handle = client.prove_async(Arc::new(pk), stdin, SP1ProofKind::Compressed);
+        handle.cancel();
+        assert!(matches!(handle.wait(), Err(SP1Error::Cancelled)));
+    }
+
     #[test]
     fn test_e2e_prove_plonk_mock() {
         utils::setup_logger();
@@ -405,4 +599,24 @@ mod tests {
         let proof = client.prove(&pk, stdin).plonk().run().unwrap();
         client.verify(&proof, &vk).unwrap();
     }
+    #[test]
+    fn test_e2e_prove_mock() {
+        utils::setup_logger();
+        let client = ProverClient::mock();
+        let elf =
+            include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let (pk, vk) = client.setup(elf);
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10usize);
+
+        let core = client.prove(&pk, stdin.clone()).core().run().unwrap();
+        let compressed = client.prove(&pk, stdin).compressed().run().unwrap();
+        for mut proof in [core, compressed] {
+            client.verify(&proof, &vk).unwrap();
+
+            // Test invalid public values.
+            proof.public_values = SP1PublicValues::from(&[255, 4, 84]);
+            assert!(client.verify(&proof, &vk).is_err());
+        }
+    }
 }
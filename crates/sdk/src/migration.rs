@@ -0,0 +1,308 @@
+//! Migration of compressed proofs across breaking versions of SP1.
+//!
+//! The verifier of a version of SP1 only accepts the proofs of that version, so a system that
+//! upgrades its prover would otherwise have to re-execute every computation it already proved.
+//! Instead, an old compressed proof can be verified inside a *migrator*: a guest program linking
+//! the SDK of the old version, whose execution is then proven with this version. The resulting
+//! proof attests that the old proof was valid, and carries its verifying key hash and public
+//! values.
+//!
+//! The migrator reads the proof and the verifying key, both bincode-encoded by the old version,
+//! as two stdin elements, verifies the proof with the compressed proof verifier of the old
+//! version, and commits a [`MigrationOutput`]. `examples/migration` has a migrator of proofs of
+//! SP1 v1.1, whose main function is:
+//!
+//! ```ignore
+//! use sp1_prover_v1::{HashableKey, SP1Prover, SP1VerifyingKey, SP1_CIRCUIT_VERSION};
+//! use sp1_sdk_v1::{SP1Proof, SP1ProofWithPublicValues};
+//!
+//! pub fn main() {
+//!     let proof: SP1ProofWithPublicValues =
+//!         bincode::deserialize(&sp1_zkvm::io::read_vec()).expect("invalid proof");
+//!     let vk: SP1VerifyingKey =
+//!         bincode::deserialize(&sp1_zkvm::io::read_vec()).expect("invalid verifying key");
+//!     let SP1Proof::Compressed(compressed) = &proof.proof else {
+//!         panic!("only compressed proofs can be migrated");
+//!     };
+//!     assert_eq!(proof.sp1_version, SP1_CIRCUIT_VERSION, "the proof is of another version");
+//!
+//!     // The compress verifying key is derived from the programs of the old version rather than
+//!     // read, so that a proof of another program cannot pass for it.
+//!     SP1Prover::new().verify_compressed(compressed, &vk).expect("the proof is invalid");
+//!
+//!     // Commit the fields of a `MigrationOutput`, in order.
+//!     sp1_zkvm::io::commit(&(SP1_CIRCUIT_VERSION, vk.bytes32(), proof.public_values.to_vec()));
+//! }
+//! ```
+//!
+//! Verifying a compressed proof in the guest costs far more cycles than the programs proofs are
+//! usually aggregated from, but it is done once per historical proof.
+//!
+//! The proof of the migrator only attests that the migrator ran: [`ProverClient::verify_migration`]
+//! also checks that its [`MigrationOutput`] describes the expected proof.
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    action, ProofFrameHeader, ProverClient, SP1Error, SP1ProofKind, SP1ProofWithPublicValues,
+    SP1ProvingKey, SP1Result, SP1Stdin, SP1VerificationError, SP1VerifyingKey, SP1_CIRCUIT_VERSION,
+};
+
+/// A compressed proof of a previous version of SP1, and the verifying key it was proven against.
+///
+/// Both are kept in the encoding of the old version, since the types of this version may not be
+/// able to decode them.
+#[derive(Debug, Clone)]
+pub struct MigrationInput {
+    proof: Vec<u8>,
+    vk: Vec<u8>,
+    header: Option<ProofFrameHeader>,
+}
+
+impl MigrationInput {
+    /// Creates the input of a migration from a proof saved by the old version, with
+    /// `SP1ProofWithPublicValues::save`, and its bincode-encoded verifying key.
+    ///
+    /// The proof must be a compressed proof. If it is framed, its version is checked to differ from
+    /// this version; proofs saved before framing was introduced, such as the proofs of v1.1, are
+    /// accepted as is.
+    pub fn new(proof: Vec<u8>, vk: Vec<u8>) -> SP1Result<Self> {
+        let header = ProofFrameHeader::peek(&proof).map_err(SP1Error::Encoding)?;
+        if let Some(header) = &header {
+            if header.kind != SP1ProofKind::Compressed {
+                return Err(SP1Error::Encoding(anyhow!(
+                    "only compressed proofs can be migrated, got a {:?} proof",
+                    header.kind
+                )));
+            }
+            if header.sp1_version == SP1_CIRCUIT_VERSION {
+                return Err(SP1Error::Other(anyhow!(
+                    "the proof is already of version {SP1_CIRCUIT_VERSION}"
+                )));
+            }
+        }
+        Ok(Self { proof, vk, header })
+    }
+
+    /// The version of SP1 of the proof, if it is framed.
+    pub fn source_version(&self) -> Option<&str> {
+        self.header.as_ref().map(|header| header.sp1_version.as_str())
+    }
+
+    /// The stdin of the migrator: the proof, then the verifying key.
+    pub fn stdin(&self) -> SP1Stdin {
+        let mut stdin = SP1Stdin::new();
+        stdin.write_vec(self.proof.clone());
+        stdin.write_vec(self.vk.clone());
+        stdin
+    }
+}
+
+/// The public values committed by a migrator, describing the proof it verified.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationOutput {
+    /// The version of SP1 of the migrated proof.
+    pub sp1_version: String,
+    /// The hash of the verifying key of the migrated proof, as returned by `bytes32`.
+    pub vkey_hash: String,
+    /// The public values of the migrated proof.
+    pub public_values: Vec<u8>,
+}
+
+impl MigrationOutput {
+    /// Reads the output committed by the migrator from the public values of its proof.
+    pub fn from_proof(proof: &SP1ProofWithPublicValues) -> Self {
+        proof.public_values.clone().read()
+    }
+
+    /// Checks that the migrator verified a proof of a previous version of SP1, of the version of
+    /// `input` if it is known, against the verifying key of hash `vkey_hash`.
+    pub fn check(&self, input: &MigrationInput, vkey_hash: &str) -> SP1Result<()> {
+        if self.sp1_version == SP1_CIRCUIT_VERSION {
+            return Err(SP1VerificationError::VersionMismatch(format!(
+                "the migrator verified a proof of this version, {SP1_CIRCUIT_VERSION}"
+            ))
+            .into());
+        }
+        if let Some(source_version) = input.source_version() {
+            if self.sp1_version != source_version {
+                return Err(SP1VerificationError::VersionMismatch(format!(
+                    "the migrator verified a proof of {}, but the proof is of {source_version}",
+                    self.sp1_version
+                ))
+                .into());
+            }
+        }
+        if self.vkey_hash != vkey_hash {
+            return Err(SP1VerificationError::InvalidPublicValues.into());
+        }
+        Ok(())
+    }
+}
+
+impl ProverClient {
+    /// Prepare to migrate a proof of a previous version of SP1 to this version, by proving the
+    /// execution of a migrator on it. See the [module documentation](crate::migration) for how to
+    /// write a migrator.
+    ///
+    /// The returned [action::Prove] generates a compressed proof by default. Its public values are
+    /// a [`MigrationOutput`], which [Self::verify_migration] checks.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{MigrationInput, MigrationOutput, ProverClient};
+    ///
+    /// let migrator = std::fs::read("migration/program/elf/riscv32im-succinct-zkvm-elf").unwrap();
+    /// let client = ProverClient::new();
+    /// let (pk, vk) = client.setup(&migrator);
+    ///
+    /// // Saved by SP1 v1.1 with `SP1ProofWithPublicValues::save` and `bincode::serialize`.
+    /// let old_proof = std::fs::read("proof.bin").unwrap();
+    /// let old_vk = std::fs::read("vk.bin").unwrap();
+    /// let input = MigrationInput::new(old_proof, old_vk).unwrap();
+    ///
+    /// let proof = client.migrate(&pk, &input).run().unwrap();
+    /// let output = client.verify_migration(&proof, &vk, &input, "0x00a1b2...").unwrap();
+    /// println!("migrated a proof of {}", output.sp1_version);
+    /// ```
+    pub fn migrate<'a>(
+        &'a self,
+        migrator_pk: &'a SP1ProvingKey,
+        input: &MigrationInput,
+    ) -> action::Prove<'a> {
+        self.prove(migrator_pk, input.stdin()).compressed()
+    }
+
+    /// Verifies a proof of a migrator, and checks that it migrated a proof of `input` against the
+    /// verifying key of hash `vkey_hash`, as returned by `bytes32`. Returns the output of the
+    /// migrator.
+    pub fn verify_migration(
+        &self,
+        proof: &SP1ProofWithPublicValues,
+        migrator_vk: &SP1VerifyingKey,
+        input: &MigrationInput,
+        vkey_hash: &str,
+    ) -> SP1Result<MigrationOutput> {
+        self.verify(proof, migrator_vk)?;
+        let output = MigrationOutput::from_proof(proof);
+        output.check(input, vkey_hash)?;
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use sp1_primitives::io::SP1PublicValues;
+
+    use super::*;
+    use crate::{encode_framed, SP1Proof};
+
+    fn framed(kind: SP1ProofKind, sp1_version: &str) -> Vec<u8> {
+        let header = ProofFrameHeader { kind, sp1_version: sp1_version.to_string() };
+        encode_framed(&header, &0u8).unwrap()
+    }
+
+    #[test]
+    fn test_migration_input() {
+        let input =
+            MigrationInput::new(framed(SP1ProofKind::Compressed, "v1.0.0"), vec![]).unwrap();
+        assert_eq!(input.source_version(), Some("v1.0.0"));
+
+        assert!(matches!(
+            MigrationInput::new(framed(SP1ProofKind::Core, "v1.0.0"), vec![]),
+            Err(SP1Error::Encoding(_))
+        ));
+        assert!(matches!(
+            MigrationInput::new(framed(SP1ProofKind::Compressed, SP1_CIRCUIT_VERSION), vec![]),
+            Err(SP1Error::Other(_))
+        ));
+
+        // Unframed proofs predate the frame header, so their version is unknown.
+        let input = MigrationInput::new(vec![1, 2, 3], vec![]).unwrap();
+        assert_eq!(input.source_version(), None);
+    }
+
+    #[test]
+    fn test_migration_output() {
+        let output = MigrationOutput {
+            sp1_version: "v1.0.0".to_string(),
+            vkey_hash: format!("0x{}", "ab".repeat(32)),
+            public_values: vec![1, 2, 3],
+        };
+
+        // The migrator commits the fields as a tuple.
+        let mut public_values = SP1PublicValues::new();
+        public_values.write(&(&output.sp1_version, &output.vkey_hash, &output.public_values));
+        let proof = SP1ProofWithPublicValues {
+            proof: SP1Proof::Core(vec![]),
+            stdin: SP1Stdin::new(),
+            public_values,
+            sp1_version: SP1_CIRCUIT_VERSION.to_string(),
+        };
+        assert_eq!(MigrationOutput::from_proof(&proof), output);
+    }
+
+    #[test]
+    fn test_migration_output_check() {
+        let vkey_hash = format!("0x{}", "ab".repeat(32));
+        let output = MigrationOutput {
+            sp1_version: "v1.0.0".to_string(),
+            vkey_hash: vkey_hash.clone(),
+            public_values: vec![],
+        };
+        let framed_input =
+            MigrationInput::new(framed(SP1ProofKind::Compressed, "v1.0.0"), vec![]).unwrap();
+        let unframed_input = MigrationInput::new(vec![1, 2, 3], vec![]).unwrap();
+        assert!(output.check(&framed_input, &vkey_hash).is_ok());
+        assert!(output.check(&unframed_input, &vkey_hash).is_ok());
+
+        // The migrator must have verified a proof of the version of the input.
+        let other_input =
+            MigrationInput::new(framed(SP1ProofKind::Compressed, "v1.1.0"), vec![]).unwrap();
+        assert!(matches!(
+            output.check(&other_input, &vkey_hash),
+            Err(SP1Error::Verification(SP1VerificationError::VersionMismatch(_)))
+        ));
+        // A migrator verifying proofs of this version would let a proof pass as migrated.
+        let current =
+            MigrationOutput { sp1_version: SP1_CIRCUIT_VERSION.to_string(), ..output.clone() };
+        assert!(matches!(
+            current.check(&unframed_input, &vkey_hash),
+            Err(SP1Error::Verification(SP1VerificationError::VersionMismatch(_)))
+        ));
+        // The migrated proof must be of the expected program.
+        assert!(matches!(
+            output.check(&framed_input, &format!("0x{}", "cd".repeat(32))),
+            Err(SP1Error::Verification(SP1VerificationError::InvalidPublicValues))
+        ));
+    }
+
+    /// Migrates the proof of `examples/migration/fixtures`, generated with SP1 v1.1 by
+    /// `examples/migration/fixtures-v1`, with the migrator of `examples/migration/program`.
+    #[test]
+    #[ignore = "needs the migrator ELF and the SP1 v1.1 fixtures of examples/migration"]
+    fn test_e2e_migrate() {
+        crate::utils::setup_logger();
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/migration");
+        let migrator = fs::read(dir.join("program/elf/riscv32im-succinct-zkvm-elf")).unwrap();
+        let proof = fs::read(dir.join("fixtures/proof.bin")).unwrap();
+        let vk = fs::read(dir.join("fixtures/vk.bin")).unwrap();
+        let vkey_hash = fs::read_to_string(dir.join("fixtures/vkey_hash.txt")).unwrap();
+        let public_values = fs::read(dir.join("fixtures/public_values.bin")).unwrap();
+
+        let client = ProverClient::new();
+        let (pk, migrator_vk) = client.setup(&migrator);
+        let input = MigrationInput::new(proof, vk).unwrap();
+        let proof = client.migrate(&pk, &input).run().unwrap();
+
+        let output =
+            client.verify_migration(&proof, &migrator_vk, &input, vkey_hash.trim()).unwrap();
+        assert_ne!(output.sp1_version, SP1_CIRCUIT_VERSION);
+        assert_eq!(output.public_values, public_values);
+        let other_vkey_hash = format!("0x{}", "00".repeat(32));
+        assert!(client.verify_migration(&proof, &migrator_vk, &input, &other_vkey_hash).is_err());
+    }
+}
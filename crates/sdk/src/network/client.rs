@@ -1,9 +1,14 @@
 use std::{env, time::Duration};
 
 use crate::{
-    network::auth::NetworkAuth,
+    decode_framed,
+    network::{
+        auth::NetworkAuth,
+        transfer::{self, TransferOpts},
+    },
     proto::network::{
-        ModifyCpuCyclesRequest, ModifyCpuCyclesResponse, UnclaimProofRequest, UnclaimReason,
+        ModifyCpuCyclesRequest, ModifyCpuCyclesResponse, MultipartUpload, UnclaimProofRequest,
+        UnclaimReason,
     },
 };
 use anyhow::{Context, Ok, Result};
@@ -35,6 +40,7 @@ pub struct NetworkClient {
     pub rpc: TwirpClient,
     pub http: HttpClientWithMiddleware,
     pub auth: NetworkAuth,
+    pub transfer_opts: TransferOpts,
 }
 
 impl NetworkClient {
@@ -63,7 +69,7 @@ impl NetworkClient {
             .build()
             .unwrap();
 
-        Self { auth, rpc, http: http_client.into() }
+        Self { auth, rpc, http: http_client.into(), transfer_opts: TransferOpts::default() }
     }
 
     /// Gets the latest nonce for this auth's account.
@@ -76,10 +82,20 @@ impl NetworkClient {
         Ok(res.nonce)
     }
 
-    /// Upload a file to the specified url.
-    async fn upload_file(&self, url: &str, data: Vec<u8>) -> Result<()> {
-        self.http.put(url).body(data).send().await?;
-        Ok(())
+    /// Upload a file in parts to the multipart upload if the network provided one, or else to the
+    /// specified url, retrying failed attempts.
+    async fn upload_file(
+        &self,
+        url: &str,
+        multipart: Option<&MultipartUpload>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        match multipart {
+            Some(upload) => {
+                transfer::upload_multipart(&self.http, upload, &data, &self.transfer_opts).await
+            }
+            None => transfer::upload(&self.http, url, data, &self.transfer_opts).await,
+        }
     }
 
     /// Get the status of a given proof. If the status is ProofFulfilled, the proof is also
@@ -98,17 +114,14 @@ impl NetworkClient {
         let proof = match res.status() {
             ProofStatus::ProofFulfilled => {
                 log::info!("Proof request fulfilled");
-                let proof_bytes = self
-                    .http
-                    .get(res.proof_url.as_ref().expect("no proof url"))
-                    .send()
-                    .await
-                    .context("Failed to send HTTP request for proof")?
-                    .bytes()
+                let proof_url = res.proof_url.as_ref().expect("no proof url");
+                let proof_bytes = transfer::download(&self.http, proof_url, &self.transfer_opts)
                     .await
-                    .context("Failed to load proof bytes")?;
+                    .context("Failed to download proof")?;
 
-                Some(bincode::deserialize(&proof_bytes).context("Failed to deserialize proof")?)
+                let (_, proof) =
+                    decode_framed(&proof_bytes[..]).context("Failed to deserialize proof")?;
+                Some(proof)
             }
             _ => None,
         };
@@ -159,8 +172,10 @@ impl NetworkClient {
 
         let program_bytes = bincode::serialize(elf)?;
         let stdin_bytes = bincode::serialize(&stdin)?;
-        let program_promise = self.upload_file(&res.program_url, program_bytes);
-        let stdin_promise = self.upload_file(&res.stdin_url, stdin_bytes);
+        let program_promise =
+            self.upload_file(&res.program_url, res.program_upload.as_ref(), program_bytes);
+        let stdin_promise =
+            self.upload_file(&res.stdin_url, res.stdin_upload.as_ref(), stdin_bytes);
         let v = vec![program_promise, stdin_promise];
         let mut results = join_all(v).await;
         results.pop().expect("Failed to upload stdin")?;
@@ -4,9 +4,13 @@ use std::{
 };
 
 use crate::{
-    network::client::{NetworkClient, DEFAULT_PROVER_NETWORK_RPC},
+    network::{
+        client::{NetworkClient, DEFAULT_PROVER_NETWORK_RPC},
+        transfer::TransferOpts,
+    },
     proto::network::{ProofMode, ProofStatus},
-    Prover, SP1Context, SP1ProofKind, SP1ProofWithPublicValues, SP1ProvingKey, SP1VerifyingKey,
+    Prover, SP1Context, SP1Error, SP1ProofKind, SP1ProofWithPublicValues, SP1ProvingKey, SP1Result,
+    SP1VerifyingKey,
 };
 use anyhow::Result;
 use serde::de::DeserializeOwned;
@@ -42,16 +46,37 @@ impl NetworkProver {
         Self { client: NetworkClient::new(private_key), local_prover }
     }
 
+    /// Sets the retry policy of the uploads of programs and stdins and of the downloads of
+    /// proofs.
+    pub fn with_transfer_opts(mut self, transfer_opts: TransferOpts) -> Self {
+        self.client.transfer_opts = transfer_opts;
+        self
+    }
+
     /// Requests a proof from the prover network, returning the proof ID.
+    ///
+    /// The program is first executed locally, unless the `SKIP_SIMULATION` environment variable is
+    /// set to `true`.
     pub async fn request_proof(
         &self,
         elf: &[u8],
         stdin: SP1Stdin,
         mode: ProofMode,
     ) -> Result<String> {
-        let client = &self.client;
-
         let skip_simulation = env::var("SKIP_SIMULATION").map(|val| val == "true").unwrap_or(false);
+        self.request_proof_with(elf, stdin, mode, skip_simulation).await
+    }
+
+    /// Requests a proof from the prover network, first executing the program locally unless
+    /// `skip_simulation` is set, and returns the proof ID.
+    async fn request_proof_with(
+        &self,
+        elf: &[u8],
+        stdin: SP1Stdin,
+        mode: ProofMode,
+        skip_simulation: bool,
+    ) -> Result<String> {
+        let client = &self.client;
 
         if !skip_simulation {
             let (_, report) =
@@ -83,7 +108,7 @@ impl NetworkProver {
         loop {
             if let Some(timeout) = timeout {
                 if start_time.elapsed() > timeout {
-                    return Err(anyhow::anyhow!("Proof generation timed out."));
+                    return Err(SP1Error::Timeout.into());
                 }
             }
 
@@ -144,9 +169,13 @@ impl Prover<DefaultProverComponents> for NetworkProver {
         opts: ProofOpts,
         context: SP1Context<'a>,
         kind: SP1ProofKind,
-    ) -> Result<SP1ProofWithPublicValues> {
+    ) -> SP1Result<SP1ProofWithPublicValues> {
         warn_if_not_default(&opts.sp1_prover_opts, &context);
-        block_on(self.prove(&pk.elf, stdin, kind.into(), opts.timeout))
+        Ok(block_on(async {
+            let proof_id =
+                self.request_proof_with(&pk.elf, stdin, kind.into(), opts.skip_simulation).await?;
+            self.wait_proof(&proof_id, opts.timeout).await
+        })?)
     }
 }
 
@@ -0,0 +1,373 @@
+//! Transfers of artifacts to and from the storage of the prover network.
+//!
+//! Programs, stdins and proofs are moved through presigned URLs. Large artifacts often fail to
+//! transfer on flaky connections, so every transfer is retried with exponential backoff, and
+//! downloads resume from the last byte received with HTTP range requests.
+//!
+//! Uploads go through a [`MultipartUpload`] when the network provides one: the artifact is split
+//! into parts, each sent with its Content-MD5 checksum so that a part corrupted in transit is
+//! rejected, and each retried on its own, so that a failure only resends the part it interrupted.
+//! Otherwise, a presigned upload URL accepts a single `PUT` of the whole artifact, so an
+//! interrupted upload is restarted from the beginning.
+
+use std::{future::Future, time::Duration};
+
+use anyhow::{anyhow, bail, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::{
+    header::{CONTENT_RANGE, ETAG, RANGE},
+    Response, StatusCode,
+};
+use reqwest_middleware::{
+    ClientWithMiddleware as HttpClientWithMiddleware, RequestBuilder as HttpRequestBuilder,
+};
+use tokio::time::sleep;
+
+use crate::proto::network::MultipartUpload;
+
+/// Options for the transfers of artifacts to and from the prover network.
+#[derive(Debug, Clone)]
+pub struct TransferOpts {
+    /// The number of attempts of a transfer before giving up.
+    pub max_attempts: u32,
+    /// The delay before the first retry, doubled after each failed attempt.
+    pub initial_backoff: Duration,
+    /// The maximum delay between two attempts.
+    pub max_backoff: Duration,
+}
+
+impl Default for TransferOpts {
+    fn default() -> Self {
+        Self {
+            max_attempts: 8,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+}
+
+impl TransferOpts {
+    /// The delay before the attempt following the given failed attempt, counted from zero.
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff.saturating_mul(1u32 << attempt.min(16)).min(self.max_backoff)
+    }
+}
+
+/// Whether a failed request with the given status may succeed if retried.
+fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// The error of an attempt of a transfer.
+enum AttemptError {
+    /// The transfer may succeed if retried.
+    Retryable(anyhow::Error),
+    /// The transfer cannot succeed, e.g. because the URL expired.
+    Fatal(anyhow::Error),
+}
+
+impl AttemptError {
+    /// The error of a request that failed with `status`.
+    fn from_status(status: StatusCode, error: anyhow::Error) -> Self {
+        if is_retryable(status) {
+            Self::Retryable(error)
+        } else {
+            Self::Fatal(error)
+        }
+    }
+}
+
+/// Makes attempts of a transfer until one succeeds, one fails fatally or `opts.max_attempts` have
+/// failed, waiting with exponential backoff between them.
+async fn retry<T, Fut>(
+    opts: &TransferOpts,
+    what: &str,
+    mut attempt: impl FnMut() -> Fut,
+) -> Result<T>
+where
+    Fut: Future<Output = Result<T, AttemptError>>,
+{
+    let mut attempts = 0;
+    loop {
+        let error = match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(AttemptError::Retryable(error)) => error,
+            Err(AttemptError::Fatal(error)) => return Err(error.context(format!("{what} failed"))),
+        };
+        attempts += 1;
+        if attempts >= opts.max_attempts {
+            return Err(error.context(format!("{what} failed {attempts} times")));
+        }
+        let backoff = opts.backoff(attempts - 1);
+        log::warn!("{what} failed, retrying in {backoff:?}: {error:#}");
+        sleep(backoff).await;
+    }
+}
+
+/// Sends `request`, failing the attempt unless the response is a success.
+async fn send(request: HttpRequestBuilder) -> Result<Response, AttemptError> {
+    let response = request.send().await.map_err(|e| AttemptError::Retryable(e.into()))?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    let error = anyhow!("request failed with {status}: {body}");
+    // A part whose Content-MD5 does not match is rejected with `BadDigest`. It was corrupted in
+    // transit, and may arrive intact if sent again.
+    if status == StatusCode::BAD_REQUEST && body.contains("BadDigest") {
+        return Err(AttemptError::Retryable(error));
+    }
+    Err(AttemptError::from_status(status, error))
+}
+
+/// Uploads `data` to a presigned URL, retrying failed attempts.
+pub(crate) async fn upload(
+    http: &HttpClientWithMiddleware,
+    url: &str,
+    data: Vec<u8>,
+    opts: &TransferOpts,
+) -> Result<()> {
+    let len = data.len();
+    // The body is shared between attempts rather than copied.
+    let request = &http.put(url).body(data);
+    retry(opts, &format!("Upload of {len} bytes"), || async move {
+        send(request.try_clone().expect("the body is in memory")).await.map(drop)
+    })
+    .await
+}
+
+/// Uploads `data` in the parts of `upload`, retrying the failed attempts of each part.
+pub(crate) async fn upload_multipart(
+    http: &HttpClientWithMiddleware,
+    upload: &MultipartUpload,
+    data: &[u8],
+    opts: &TransferOpts,
+) -> Result<()> {
+    let parts = split_parts(data, upload)?;
+    let num_parts = parts.len();
+    let mut etags = Vec::with_capacity(num_parts);
+    for (i, (url, part)) in upload.part_urls.iter().zip(parts).enumerate() {
+        let what = format!("Upload of part {} of {num_parts} ({} bytes)", i + 1, part.len());
+        let etag = retry(opts, &what, || async move {
+            let request =
+                http.put(url).header("Content-MD5", content_md5(part)).body(part.to_vec());
+            let response = send(request).await?;
+            let etag = response.headers().get(ETAG).and_then(|etag| etag.to_str().ok());
+            etag.map(str::to_owned)
+                .ok_or_else(|| AttemptError::Fatal(anyhow!("the response has no ETag")))
+        })
+        .await?;
+        etags.push(etag);
+    }
+
+    let body = &complete_multipart_body(&etags);
+    retry(opts, "Completion of the multipart upload", || async move {
+        let response = send(http.post(&upload.complete_url).body(body.clone())).await?;
+        // S3 may report a failure to complete the upload in the body of a successful response.
+        let text = response.text().await.map_err(|e| AttemptError::Retryable(e.into()))?;
+        if text.contains("<Error>") {
+            return Err(AttemptError::Retryable(anyhow!("completion failed: {text}")));
+        }
+        Ok(())
+    })
+    .await
+}
+
+/// Splits `data` into the parts of `upload`. An empty artifact is a single empty part.
+fn split_parts<'a>(data: &'a [u8], upload: &MultipartUpload) -> Result<Vec<&'a [u8]>> {
+    let part_size = match usize::try_from(upload.part_size) {
+        Ok(part_size) if part_size > 0 => part_size,
+        _ => bail!("invalid part size {}", upload.part_size),
+    };
+    let mut parts = data.chunks(part_size).collect::<Vec<_>>();
+    if parts.is_empty() {
+        parts.push(data);
+    }
+    if parts.len() != upload.part_urls.len() {
+        bail!(
+            "{} bytes make {} parts of {part_size} bytes, but the upload has {} parts",
+            data.len(),
+            parts.len(),
+            upload.part_urls.len()
+        );
+    }
+    Ok(parts)
+}
+
+/// The value of the Content-MD5 header of `data`.
+fn content_md5(data: &[u8]) -> String {
+    STANDARD.encode(md5::compute(data).0)
+}
+
+/// The body of the request completing a multipart upload with the ETags of its parts, in order.
+fn complete_multipart_body(etags: &[String]) -> String {
+    let mut body = String::from("<CompleteMultipartUpload>");
+    for (i, etag) in etags.iter().enumerate() {
+        body += &format!("<Part><PartNumber>{}</PartNumber><ETag>{etag}</ETag></Part>", i + 1);
+    }
+    body + "</CompleteMultipartUpload>"
+}
+
+/// Downloads the artifact at `url`, resuming from the last byte received after a failure.
+pub(crate) async fn download(
+    http: &HttpClientWithMiddleware,
+    url: &str,
+    opts: &TransferOpts,
+) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    let mut total = None;
+    let mut attempt = 0;
+    loop {
+        let error = match download_from(http, url, &mut data, &mut total).await {
+            Ok(true) => return Ok(data),
+            Ok(false) => anyhow!("download ended after {} of {:?} bytes", data.len(), total),
+            Err(AttemptError::Retryable(error)) => error,
+            Err(AttemptError::Fatal(error)) => return Err(error),
+        };
+        attempt += 1;
+        if attempt >= opts.max_attempts {
+            return Err(error.context(format!("download failed {attempt} times")));
+        }
+        let backoff = opts.backoff(attempt - 1);
+        log::warn!(
+            "Download interrupted after {} bytes, resuming in {backoff:?}: {error:#}",
+            data.len()
+        );
+        sleep(backoff).await;
+    }
+}
+
+/// Appends the bytes of the artifact at `url` that follow `data` to it, returning whether the
+/// artifact is complete. `total` holds the size of the artifact once it is known.
+async fn download_from(
+    http: &HttpClientWithMiddleware,
+    url: &str,
+    data: &mut Vec<u8>,
+    total: &mut Option<u64>,
+) -> Result<bool, AttemptError> {
+    let mut request = http.get(url);
+    if !data.is_empty() {
+        request = request.header(RANGE, format!("bytes={}-", data.len()));
+    }
+    let mut response = request.send().await.map_err(|e| AttemptError::Retryable(e.into()))?;
+    match response.status() {
+        StatusCode::OK => {
+            // The whole artifact is sent, either because it was requested or because the server
+            // does not support ranges.
+            data.clear();
+            *total = response.content_length();
+        }
+        StatusCode::PARTIAL_CONTENT => {
+            let range = response
+                .headers()
+                .get(CONTENT_RANGE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_content_range);
+            match range {
+                Some(range) if range.start == data.len() as u64 => {
+                    *total = range.total.or(*total);
+                }
+                _ => {
+                    // Start over rather than splice the wrong bytes.
+                    data.clear();
+                    return Err(AttemptError::Retryable(anyhow!("unexpected resumed range")));
+                }
+            }
+        }
+        status if is_retryable(status) => {
+            return Err(AttemptError::Retryable(anyhow!("download failed with {status}")));
+        }
+        status => return Err(AttemptError::Fatal(anyhow!("download failed with {status}"))),
+    }
+    if let Some(total) = *total {
+        data.reserve((total as usize).saturating_sub(data.len()));
+    }
+
+    let retryable = |error: reqwest::Error| AttemptError::Retryable(error.into());
+    while let Some(chunk) = response.chunk().await.map_err(retryable)? {
+        data.extend_from_slice(&chunk);
+    }
+    Ok(total.map_or(true, |total| data.len() as u64 == total))
+}
+
+/// The range of a partial response.
+#[derive(Debug, PartialEq, Eq)]
+struct ContentRange {
+    start: u64,
+    total: Option<u64>,
+}
+
+/// Parses a `Content-Range` header of the form `bytes <start>-<end>/<total>`, where the total may
+/// be `*` if it is unknown.
+fn parse_content_range(value: &str) -> Option<ContentRange> {
+    let (range, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let (start, _) = range.split_once('-')?;
+    let total = if total == "*" { None } else { Some(total.parse().ok()?) };
+    Some(ContentRange { start: start.parse().ok()?, total })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let opts = TransferOpts {
+            max_attempts: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+        };
+        assert_eq!(opts.backoff(0), Duration::from_millis(100));
+        assert_eq!(opts.backoff(2), Duration::from_millis(400));
+        assert_eq!(opts.backoff(4), Duration::from_secs(1));
+        assert_eq!(opts.backoff(100), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_split_parts() {
+        let upload = |part_size, num_parts| MultipartUpload {
+            part_size,
+            part_urls: vec![String::new(); num_parts],
+            complete_url: String::new(),
+        };
+        let data = [1, 2, 3, 4, 5];
+        assert_eq!(split_parts(&data, &upload(2, 3)).unwrap(), [&[1, 2][..], &[3, 4], &[5]]);
+        assert_eq!(split_parts(&data, &upload(5, 1)).unwrap(), [&data[..]]);
+        assert_eq!(split_parts(&[], &upload(5, 1)).unwrap(), [&[][..]]);
+        assert!(split_parts(&data, &upload(2, 2)).is_err());
+        assert!(split_parts(&data, &upload(0, 1)).is_err());
+    }
+
+    #[test]
+    fn test_content_md5() {
+        assert_eq!(content_md5(b""), "1B2M2Y8AsgTpgAmY7PhCfg==");
+        assert_eq!(content_md5(b"hello"), "XUFAKrxLKna5cZ2REBfFkg==");
+    }
+
+    #[test]
+    fn test_complete_multipart_body() {
+        let etags = ["\"a\"".to_string(), "\"b\"".to_string()];
+        assert_eq!(
+            complete_multipart_body(&etags),
+            "<CompleteMultipartUpload><Part><PartNumber>1</PartNumber><ETag>\"a\"</ETag></Part>\
+             <Part><PartNumber>2</PartNumber><ETag>\"b\"</ETag></Part></CompleteMultipartUpload>"
+        );
+    }
+
+    #[test]
+    fn test_parse_content_range() {
+        assert_eq!(
+            parse_content_range("bytes 100-199/1000"),
+            Some(ContentRange { start: 100, total: Some(1000) })
+        );
+        assert_eq!(
+            parse_content_range("bytes 5-9/*"),
+            Some(ContentRange { start: 5, total: None })
+        );
+        assert_eq!(parse_content_range("bytes */1000"), None);
+        assert_eq!(parse_content_range("items 0-1/2"), None);
+    }
+}
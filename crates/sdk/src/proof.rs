@@ -1,13 +1,25 @@
-use std::{fmt::Debug, fs::File, path::Path};
+use std::{
+    fmt::Debug,
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+    str::FromStr,
+};
 
-use anyhow::Result;
-use serde::{Deserialize, Serialize};
+use anyhow::{bail, Context, Result};
+use num_bigint::BigUint;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use sp1_core_machine::io::SP1Stdin;
-use sp1_primitives::io::SP1PublicValues;
+use sp1_primitives::io::{PublicValuesDomain, SP1PublicValues};
 use strum_macros::{EnumDiscriminants, EnumTryAs};
 
 use sp1_prover::{CoreSC, Groth16Bn254Proof, InnerSC, PlonkBn254Proof};
-use sp1_stark::{MachineVerificationError, ShardProof};
+use sp1_stark::{MachineVerificationError, ShardProof, ShardProofJson, EXPLORER_SCHEMA_VERSION};
+
+use crate::{
+    provers::{verify_public_values_digest, SP1VerificationError},
+    SP1Error, SP1Result,
+};
 
 /// A proof generated with SP1 of a particular proof mode.
 #[derive(Debug, Clone, Serialize, Deserialize, EnumDiscriminants, EnumTryAs)]
@@ -21,6 +33,131 @@ pub enum SP1Proof {
     Groth16(Groth16Bn254Proof),
 }
 
+/// The magic bytes at the start of a framed proof artifact.
+pub const PROOF_FRAME_MAGIC: [u8; 4] = *b"SP1Z";
+
+/// The version of the proof frame layout.
+pub const PROOF_FRAME_VERSION: u8 = 1;
+
+/// The zstd compression level used for proof artifacts.
+const PROOF_COMPRESSION_LEVEL: i32 = 3;
+
+/// The header of a framed proof artifact.
+///
+/// A frame is laid out as the [`PROOF_FRAME_MAGIC`], the frame version, the proof kind, the
+/// length-prefixed SP1 version string, and finally the zstd-compressed bincode encoding of the
+/// artifact. The header is left uncompressed so it can be inspected without decoding the proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofFrameHeader {
+    pub kind: SP1ProofKind,
+    pub sp1_version: String,
+}
+
+impl ProofFrameHeader {
+    /// Writes the header to the given writer.
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        let version = self.sp1_version.as_bytes();
+        writer.write_all(&PROOF_FRAME_MAGIC)?;
+        writer.write_all(&[PROOF_FRAME_VERSION, self.kind as u8])?;
+        writer.write_all(&u16::try_from(version.len())?.to_le_bytes())?;
+        writer.write_all(version)?;
+        Ok(())
+    }
+
+    /// Reads the header of a framed artifact without decoding the artifact, or returns `None` if
+    /// the bytes are not framed.
+    pub fn peek(bytes: &[u8]) -> Result<Option<Self>> {
+        match bytes.strip_prefix(&PROOF_FRAME_MAGIC[..]) {
+            Some(mut rest) => Ok(Some(Self::read_after_magic(&mut rest)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Reads a header from the given reader, assuming the magic bytes were already consumed.
+    fn read_after_magic(reader: &mut impl Read) -> Result<Self> {
+        let mut prefix = [0u8; 4];
+        reader.read_exact(&mut prefix)?;
+        let [frame_version, kind, len_lo, len_hi] = prefix;
+        if frame_version != PROOF_FRAME_VERSION {
+            bail!("unsupported proof frame version {}", frame_version);
+        }
+        let kind = match kind {
+            k if k == SP1ProofKind::Core as u8 => SP1ProofKind::Core,
+            k if k == SP1ProofKind::Compressed as u8 => SP1ProofKind::Compressed,
+            k if k == SP1ProofKind::Plonk as u8 => SP1ProofKind::Plonk,
+            k if k == SP1ProofKind::Groth16 as u8 => SP1ProofKind::Groth16,
+            _ => bail!("unknown proof kind {} in proof frame", kind),
+        };
+        let mut version = vec![0u8; u16::from_le_bytes([len_lo, len_hi]) as usize];
+        reader.read_exact(&mut version)?;
+        Ok(Self { kind, sp1_version: String::from_utf8(version)? })
+    }
+}
+
+/// Encodes a value as a framed, zstd-compressed artifact.
+pub fn encode_framed<T: Serialize>(header: &ProofFrameHeader, value: &T) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    header.write(&mut bytes)?;
+    let mut encoder = zstd::Encoder::new(bytes, PROOF_COMPRESSION_LEVEL)?;
+    // The checksum is verified when decoding, so corrupted transfers are detected.
+    encoder.include_checksum(true)?;
+    bincode::serialize_into(&mut encoder, value)?;
+    Ok(encoder.finish()?)
+}
+
+/// Decodes an artifact produced by [`encode_framed`].
+///
+/// Unframed bytes are decoded as plain bincode, so that artifacts written by older SDK versions
+/// can still be read.
+pub fn decode_framed<T: DeserializeOwned>(
+    reader: impl Read,
+) -> Result<(Option<ProofFrameHeader>, T)> {
+    let mut reader = BufReader::new(reader);
+    let mut magic = [0u8; PROOF_FRAME_MAGIC.len()];
+    let mut read = 0;
+    while read < magic.len() {
+        match reader.read(&mut magic[read..])? {
+            0 => break,
+            n => read += n,
+        }
+    }
+    if read == magic.len() && magic == PROOF_FRAME_MAGIC {
+        let header = ProofFrameHeader::read_after_magic(&mut reader)?;
+        let value = bincode::deserialize_from(zstd::Decoder::new(reader)?)
+            .context("failed to decode compressed proof")?;
+        Ok((Some(header), value))
+    } else {
+        let value = bincode::deserialize_from((&magic[..read]).chain(reader))?;
+        Ok((None, value))
+    }
+}
+
+/// The JSON export of an [`SP1ProofWithPublicValues`], for tooling that does not link the SDK.
+///
+/// Shard proofs are exported with the schema of [`ShardProofJson`]. The stdin is left out, since
+/// it may contain private inputs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SP1ProofJson {
+    /// The version of the schema.
+    pub schema_version: u32,
+    /// The version of SP1 that generated the proof.
+    pub sp1_version: String,
+    /// The public values, as a hex string.
+    pub public_values: String,
+    /// The proof.
+    pub proof: SP1ProofDataJson,
+}
+
+/// The proof of an [`SP1ProofJson`], tagged with its kind.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SP1ProofDataJson {
+    Core { shards: Vec<ShardProofJson> },
+    Compressed { shard: ShardProofJson },
+    Plonk { public_inputs: [String; 2], encoded_proof: String, vkey_hash: String },
+    Groth16 { public_inputs: [String; 2], encoded_proof: String, vkey_hash: String },
+}
+
 /// A proof generated with SP1, bundled together with stdin, public values, and the SP1 version.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SP1ProofWithPublicValues {
@@ -31,16 +168,78 @@ pub struct SP1ProofWithPublicValues {
 }
 
 impl SP1ProofWithPublicValues {
-    /// Saves the proof to a path.
-    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
-        bincode::serialize_into(File::create(path).expect("failed to open file"), self)
-            .map_err(Into::into)
+    /// Saves the proof to a path as a zstd-compressed frame.
+    pub fn save(&self, path: impl AsRef<Path>) -> SP1Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(&self.to_bytes()?)?;
+        file.flush()?;
+        Ok(())
     }
 
-    /// Loads a proof from a path.
-    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
-        bincode::deserialize_from(File::open(path).expect("failed to open file"))
-            .map_err(Into::into)
+    /// Loads a proof from a path, accepting both framed and legacy uncompressed encodings.
+    pub fn load(path: impl AsRef<Path>) -> SP1Result<Self> {
+        Self::from_reader(File::open(path)?).map_err(SP1Error::Encoding)
+    }
+
+    /// Encodes the proof as a zstd-compressed frame.
+    pub fn to_bytes(&self) -> SP1Result<Vec<u8>> {
+        let header = ProofFrameHeader {
+            kind: SP1ProofKind::from(&self.proof),
+            sp1_version: self.sp1_version.clone(),
+        };
+        encode_framed(&header, self).map_err(SP1Error::Encoding)
+    }
+
+    /// Decodes a proof from bytes, accepting both framed and legacy uncompressed encodings.
+    pub fn from_bytes(bytes: &[u8]) -> SP1Result<Self> {
+        Self::from_reader(bytes).map_err(SP1Error::Encoding)
+    }
+
+    fn from_reader(reader: impl Read) -> Result<Self> {
+        let (header, proof): (_, Self) = decode_framed(reader)?;
+        if let Some(header) = header {
+            if header.kind != SP1ProofKind::from(&proof.proof) {
+                bail!(
+                    "proof frame header declares a {:?} proof but contains a {:?} proof",
+                    header.kind,
+                    SP1ProofKind::from(&proof.proof)
+                );
+            }
+        }
+        Ok(proof)
+    }
+
+    /// The JSON export of the proof. See [`SP1ProofJson`] for the schema.
+    pub fn to_json_export(&self) -> SP1ProofJson {
+        let proof = match &self.proof {
+            SP1Proof::Core(shards) => SP1ProofDataJson::Core {
+                shards: shards.iter().map(ShardProof::to_json_export).collect(),
+            },
+            SP1Proof::Compressed(shard) => {
+                SP1ProofDataJson::Compressed { shard: shard.to_json_export() }
+            }
+            SP1Proof::Plonk(plonk) => SP1ProofDataJson::Plonk {
+                public_inputs: plonk.public_inputs.clone(),
+                encoded_proof: plonk.encoded_proof.clone(),
+                vkey_hash: format!("0x{}", hex::encode(plonk.plonk_vkey_hash)),
+            },
+            SP1Proof::Groth16(groth16) => SP1ProofDataJson::Groth16 {
+                public_inputs: groth16.public_inputs.clone(),
+                encoded_proof: groth16.encoded_proof.clone(),
+                vkey_hash: format!("0x{}", hex::encode(groth16.groth16_vkey_hash)),
+            },
+        };
+        SP1ProofJson {
+            schema_version: EXPLORER_SCHEMA_VERSION,
+            sp1_version: self.sp1_version.clone(),
+            public_values: self.public_values.raw(),
+            proof,
+        }
+    }
+
+    /// The proof as a JSON string. See [`SP1ProofJson`] for the schema.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.to_json_export()).unwrap()
     }
 
     /// Returns the raw proof as a string.
@@ -76,8 +275,142 @@ impl SP1ProofWithPublicValues {
             _ => unimplemented!("only Plonk and Groth16 proofs are verifiable onchain"),
         }
     }
+
+    /// Checks that the public values of the bundle are the ones the proof commits to, without
+    /// verifying the proof itself.
+    ///
+    /// Core and compressed proofs commit to the SHA-256 digest of the public values, and Plonk and
+    /// Groth16 proofs take it as a public input with its top 3 bits masked. The digest is
+    /// recomputed from the raw public values and compared to the one in the proof, so that public
+    /// values which were replaced or tampered with after proving are rejected. This is already
+    /// checked by [`Prover::verify`](crate::Prover::verify), and is meant for integrators which
+    /// read the public values from a bundle whose proof is verified elsewhere, e.g. onchain.
+    pub fn verify_public_values(&self) -> Result<(), SP1VerificationError> {
+        match &self.proof {
+            SP1Proof::Core(shards) => {
+                let last = shards.last().ok_or(SP1VerificationError::InvalidPublicValues)?;
+                verify_public_values_digest(&last.public_values, &self.public_values)
+            }
+            SP1Proof::Compressed(shard) => {
+                verify_public_values_digest(&shard.public_values, &self.public_values)
+            }
+            SP1Proof::Plonk(PlonkBn254Proof { public_inputs, .. })
+            | SP1Proof::Groth16(Groth16Bn254Proof { public_inputs, .. }) => {
+                match BigUint::from_str(&public_inputs[1]) {
+                    Ok(digest) if digest == self.public_values.hash_bn254() => Ok(()),
+                    _ => Err(SP1VerificationError::InvalidPublicValues),
+                }
+            }
+        }
+    }
+
+    /// Checks that the bundle was generated for `domain` by a program built with the
+    /// `replay-protection` feature of `sp1-zkvm`, without verifying the proof itself.
+    ///
+    /// The domain is the first bytes of the public values, so it is checked after checking that
+    /// the public values are the ones the proof commits to with [`Self::verify_public_values`].
+    /// This does not check whether the nonce was used before.
+    pub fn verify_domain(&self, domain: &PublicValuesDomain) -> Result<(), SP1VerificationError> {
+        self.verify_public_values()?;
+        match self.public_values.domain() {
+            Some(found) if found == *domain => Ok(()),
+            found => Err(SP1VerificationError::DomainMismatch(found)),
+        }
+    }
 }
 
 pub type SP1CoreProofVerificationError = MachineVerificationError<CoreSC>;
 
 pub type SP1CompressedProofVerificationError = MachineVerificationError<InnerSC>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn groth16_proof() -> SP1ProofWithPublicValues {
+        SP1ProofWithPublicValues {
+            proof: SP1Proof::Groth16(Groth16Bn254Proof {
+                raw_proof: "ab".repeat(1024),
+                ..Default::default()
+            }),
+            stdin: SP1Stdin::new(),
+            public_values: SP1PublicValues::from(&[1, 2, 3]),
+            sp1_version: "v2.0.0".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_verify_public_values() {
+        let mut proof = groth16_proof();
+        assert!(proof.verify_public_values().is_err());
+
+        let SP1Proof::Groth16(groth16) = &mut proof.proof else { unreachable!() };
+        groth16.public_inputs[1] = proof.public_values.hash_bn254().to_string();
+        assert!(proof.verify_public_values().is_ok());
+
+        proof.public_values = SP1PublicValues::from(&[1, 2, 4]);
+        assert!(proof.verify_public_values().is_err());
+    }
+
+    #[test]
+    fn test_verify_domain() {
+        let domain = PublicValuesDomain::new([1; 32], 7);
+        let mut proof = groth16_proof();
+        proof.public_values = SP1PublicValues::from(&domain.to_bytes());
+        let SP1Proof::Groth16(groth16) = &mut proof.proof else { unreachable!() };
+        groth16.public_inputs[1] = proof.public_values.hash_bn254().to_string();
+        assert!(proof.verify_domain(&domain).is_ok());
+
+        // A proof for another domain or nonce is rejected.
+        for other in [PublicValuesDomain::new([2; 32], 7), PublicValuesDomain::new([1; 32], 8)] {
+            assert!(matches!(
+                proof.verify_domain(&other),
+                Err(SP1VerificationError::DomainMismatch(Some(found))) if found == domain
+            ));
+        }
+
+        // Forging the domain in the public values breaks the committed digest.
+        proof.public_values =
+            SP1PublicValues::from(&PublicValuesDomain::new([2; 32], 7).to_bytes());
+        assert!(matches!(
+            proof.verify_domain(&PublicValuesDomain::new([2; 32], 7)),
+            Err(SP1VerificationError::InvalidPublicValues)
+        ));
+    }
+
+    #[test]
+    fn test_framed_roundtrip() {
+        let proof = groth16_proof();
+        let bytes = proof.to_bytes().unwrap();
+        assert_eq!(bytes[..4], PROOF_FRAME_MAGIC);
+        assert!(bytes.len() < bincode::serialize(&proof).unwrap().len());
+
+        let (header, _): (_, SP1ProofWithPublicValues) = decode_framed(&bytes[..]).unwrap();
+        let header = header.unwrap();
+        assert_eq!(header.kind, SP1ProofKind::Groth16);
+        assert_eq!(header.sp1_version, "v2.0.0");
+
+        let decoded = SP1ProofWithPublicValues::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.raw(), proof.raw());
+        assert_eq!(decoded.public_values.as_slice(), proof.public_values.as_slice());
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json: serde_json::Value = serde_json::from_str(&groth16_proof().to_json()).unwrap();
+        assert_eq!(json["schema_version"], EXPLORER_SCHEMA_VERSION);
+        assert_eq!(json["sp1_version"], "v2.0.0");
+        assert_eq!(json["public_values"], "0x010203");
+        assert_eq!(json["proof"]["kind"], "groth16");
+        assert_eq!(json["proof"]["vkey_hash"], format!("0x{}", "00".repeat(32)));
+        assert!(json.get("stdin").is_none());
+    }
+
+    #[test]
+    fn test_load_legacy_encoding() {
+        let proof = groth16_proof();
+        let bytes = bincode::serialize(&proof).unwrap();
+        let decoded = SP1ProofWithPublicValues::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.raw(), proof.raw());
+    }
+}
@@ -34,6 +34,29 @@ pub struct CreateProofResponse {
     /// The URL to upload the standard input (stdin).
     #[prost(string, tag = "3")]
     pub stdin_url: ::prost::alloc::string::String,
+    /// Optional multipart upload of the ELF file, used instead of the program URL if present.
+    #[prost(message, optional, tag = "4")]
+    pub program_upload: ::core::option::Option<MultipartUpload>,
+    /// Optional multipart upload of the standard input (stdin), used instead of the stdin URL if
+    /// present.
+    #[prost(message, optional, tag = "5")]
+    pub stdin_upload: ::core::option::Option<MultipartUpload>,
+}
+/// A multipart upload of an artifact, whose parts are uploaded and retried independently.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MultipartUpload {
+    /// The size in bytes of every part but the last one.
+    #[prost(uint64, tag = "1")]
+    pub part_size: u64,
+    /// The presigned URLs to upload the parts to, in order. Each part MUST be uploaded with its
+    /// Content-MD5 header.
+    #[prost(string, repeated, tag = "2")]
+    pub part_urls: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// The presigned URL to complete the upload with the list of the ETags of the parts.
+    #[prost(string, tag = "3")]
+    pub complete_url: ::prost::alloc::string::String,
 }
 /// The request to submit a proof, the second step in requesting a proof. MUST be called when the
 /// proof is in a PROOF_REQUESTED state and MUST be called after uploading the program and stdin to
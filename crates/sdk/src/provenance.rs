@@ -0,0 +1,224 @@
+//! Signed proof envelopes, which attribute a proof to the operator of the prover that produced it.
+//!
+//! A [`SignedProof`] bundles a proof with the [`ProofProvenance`] of its generation, and an ed25519
+//! signature of both by the operator. The signature only attributes the proof: its validity is
+//! still checked by verifying the proof itself, for example with
+//! [`crate::ProverClient::verify_signed`].
+
+use std::{
+    fs::File,
+    io::{BufWriter, Read, Write},
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{bail, Result};
+pub use ed25519_dalek::{Signature, SignatureError, SigningKey, VerifyingKey};
+use ed25519_dalek::{Signer, Verifier};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    decode_framed, encode_framed, ProofFrameHeader, SP1Error, SP1ProofKind,
+    SP1ProofWithPublicValues, SP1Result,
+};
+
+/// The magic bytes at the start of a signed proof envelope, which are followed by a proof frame.
+pub const SIGNED_PROOF_MAGIC: [u8; 4] = *b"SP1S";
+
+/// The domain separator of the messages signed by [`SignedProof::sign`].
+const SIGNED_PROOF_DOMAIN: &[u8] = b"SP1_SIGNED_PROOF_V1";
+
+/// Metadata about the generation of a proof, attested by the signature of a [`SignedProof`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofProvenance {
+    /// The version of the SDK of the prover.
+    pub prover_version: String,
+    /// The git commit the SDK of the prover was built from.
+    pub git_commit: String,
+    /// When proving started, in seconds since the Unix epoch.
+    pub started_at: u64,
+    /// How long proving took, in milliseconds.
+    pub proving_time_ms: u64,
+}
+
+impl ProofProvenance {
+    /// The provenance of a proof generated with this build of the SDK, which started proving at
+    /// `started_at` and took `proving_time`.
+    pub fn new(started_at: SystemTime, proving_time: Duration) -> Self {
+        Self {
+            prover_version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: env!("VERGEN_GIT_SHA").to_string(),
+            started_at: started_at.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+            proving_time_ms: proving_time.as_millis() as u64,
+        }
+    }
+}
+
+/// A proof signed by the operator of the prover that produced it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedProof {
+    pub proof: SP1ProofWithPublicValues,
+    pub provenance: ProofProvenance,
+    /// The public key of the operator.
+    pub signer: VerifyingKey,
+    /// The signature of the proof and its provenance by the operator.
+    pub signature: Signature,
+}
+
+impl SignedProof {
+    /// Signs a proof and its provenance with the key of the operator.
+    pub fn sign(
+        proof: SP1ProofWithPublicValues,
+        provenance: ProofProvenance,
+        key: &SigningKey,
+    ) -> Self {
+        let signature = key.sign(&signed_message(&proof, &provenance));
+        Self { proof, provenance, signer: key.verifying_key(), signature }
+    }
+
+    /// Checks that the proof and its provenance were signed by [`Self::signer`].
+    pub fn verify_signature(&self) -> Result<(), SignatureError> {
+        self.signer.verify(&signed_message(&self.proof, &self.provenance), &self.signature)
+    }
+
+    /// Checks that the proof and its provenance were signed by `signer`.
+    pub fn verify_signed_by(&self, signer: &VerifyingKey) -> Result<(), SignatureError> {
+        if self.signer != *signer {
+            return Err(SignatureError::new());
+        }
+        self.verify_signature()
+    }
+
+    /// Saves the envelope to a path.
+    pub fn save(&self, path: impl AsRef<Path>) -> SP1Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(&self.to_bytes()?)?;
+        file.flush()?;
+        Ok(())
+    }
+
+    /// Loads an envelope from a path.
+    pub fn load(path: impl AsRef<Path>) -> SP1Result<Self> {
+        let mut bytes = Vec::new();
+        File::open(path)?.read_to_end(&mut bytes)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Encodes the envelope as the [`SIGNED_PROOF_MAGIC`] followed by a proof frame, whose header
+    /// describes the signed proof.
+    pub fn to_bytes(&self) -> SP1Result<Vec<u8>> {
+        let header = ProofFrameHeader {
+            kind: SP1ProofKind::from(&self.proof.proof),
+            sp1_version: self.proof.sp1_version.clone(),
+        };
+        let mut bytes = SIGNED_PROOF_MAGIC.to_vec();
+        bytes.extend(encode_framed(&header, self).map_err(SP1Error::Encoding)?);
+        Ok(bytes)
+    }
+
+    /// Decodes an envelope produced by [`Self::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> SP1Result<Self> {
+        Self::decode(bytes).map_err(SP1Error::Encoding)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        let Some(frame) = bytes.strip_prefix(&SIGNED_PROOF_MAGIC) else {
+            bail!("not a signed proof envelope");
+        };
+        let (header, signed): (_, Self) = decode_framed(frame)?;
+        let Some(header) = header else {
+            bail!("signed proof envelope does not contain a proof frame");
+        };
+        if header.kind != SP1ProofKind::from(&signed.proof.proof) {
+            bail!(
+                "proof frame header declares a {:?} proof but contains a {:?} proof",
+                header.kind,
+                SP1ProofKind::from(&signed.proof.proof)
+            );
+        }
+        Ok(signed)
+    }
+}
+
+/// The message signed for a proof and its provenance: the domain separator, followed by the
+/// SHA-256 digests of the bincode encodings of the proof and of the provenance.
+fn signed_message(proof: &SP1ProofWithPublicValues, provenance: &ProofProvenance) -> Vec<u8> {
+    let proof = bincode::serialize(proof).expect("failed to serialize proof");
+    let provenance = bincode::serialize(provenance).expect("failed to serialize provenance");
+    let mut message = SIGNED_PROOF_DOMAIN.to_vec();
+    message.extend(Sha256::digest(proof));
+    message.extend(Sha256::digest(provenance));
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_core_machine::io::SP1Stdin;
+    use sp1_primitives::io::SP1PublicValues;
+    use sp1_prover::Groth16Bn254Proof;
+
+    use super::*;
+    use crate::SP1Proof;
+
+    fn signed_proof(key: &SigningKey) -> SignedProof {
+        let proof = SP1ProofWithPublicValues {
+            proof: SP1Proof::Groth16(Groth16Bn254Proof {
+                raw_proof: "ab".repeat(1024),
+                ..Default::default()
+            }),
+            stdin: SP1Stdin::new(),
+            public_values: SP1PublicValues::from(&[1, 2, 3]),
+            sp1_version: "v2.0.0".to_string(),
+        };
+        let provenance = ProofProvenance {
+            prover_version: "2.0.0".to_string(),
+            git_commit: "0123456789abcdef".to_string(),
+            started_at: 1_700_000_000,
+            proving_time_ms: 42_000,
+        };
+        SignedProof::sign(proof, provenance, key)
+    }
+
+    #[test]
+    fn test_signed_proof_roundtrip() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let signed = signed_proof(&key);
+        signed.verify_signed_by(&key.verifying_key()).unwrap();
+
+        let bytes = signed.to_bytes().unwrap();
+        assert_eq!(bytes[..4], SIGNED_PROOF_MAGIC);
+        let decoded = SignedProof::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.provenance, signed.provenance);
+        assert_eq!(decoded.proof.raw(), signed.proof.raw());
+        decoded.verify_signed_by(&key.verifying_key()).unwrap();
+    }
+
+    #[test]
+    fn test_tampered_provenance() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let mut signed = signed_proof(&key);
+        signed.provenance.proving_time_ms = 1;
+        assert!(signed.verify_signature().is_err());
+    }
+
+    #[test]
+    fn test_wrong_signer() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let other = SigningKey::from_bytes(&[8; 32]);
+        let signed = signed_proof(&key);
+        assert!(signed.verify_signed_by(&other.verifying_key()).is_err());
+
+        // Replacing the signer does not make the signature valid for the new signer.
+        let mut forged = signed.clone();
+        forged.signer = other.verifying_key();
+        assert!(forged.verify_signature().is_err());
+    }
+
+    #[test]
+    fn test_unsigned_proof_is_not_an_envelope() {
+        let signed = signed_proof(&SigningKey::from_bytes(&[7; 32]));
+        let bytes = signed.proof.to_bytes().unwrap();
+        assert!(SignedProof::from_bytes(&bytes).is_err());
+    }
+}
@@ -1,10 +1,11 @@
-use anyhow::Result;
-use sp1_core_executor::SP1Context;
-use sp1_prover::{components::DefaultProverComponents, SP1Prover, SP1Stdin};
+use sp1_core_executor::{ProofMonitor, SP1Context};
+use sp1_prover::{
+    build::shrink_artifacts_dir, components::DefaultProverComponents, SP1Prover, SP1Stdin,
+};
 
 use crate::{
-    install::try_install_circuit_artifacts, provers::ProofOpts, Prover, SP1Proof, SP1ProofKind,
-    SP1ProofWithPublicValues, SP1ProvingKey, SP1VerifyingKey,
+    install::try_install_circuit_artifacts, provers::ProofOpts, Prover, SP1Error, SP1Proof,
+    SP1ProofKind, SP1ProofWithPublicValues, SP1ProvingKey, SP1Result, SP1VerifyingKey,
 };
 
 use super::ProverType;
@@ -22,6 +23,9 @@ impl CpuProver {
     }
 
     /// Creates a new [LocalProver] from an existing [SP1Prover].
+    ///
+    /// Use a prover created with [SP1Prover::with_shrink_log_degree] to produce PLONK and Groth16
+    /// proofs that are cheaper to wrap.
     pub fn from_prover(prover: SP1Prover<DefaultProverComponents>) -> Self {
         Self { prover }
     }
@@ -47,9 +51,13 @@ impl Prover<DefaultProverComponents> for CpuProver {
         opts: ProofOpts,
         context: SP1Context<'a>,
         kind: SP1ProofKind,
-    ) -> Result<SP1ProofWithPublicValues> {
+    ) -> SP1Result<SP1ProofWithPublicValues> {
+        // Keep the monitor around to check for cancellation between the stages of the proof.
+        let monitor = context.monitor.clone();
+        let monitor = monitor.as_deref();
+
         // Generate the core proof.
-        let proof = self.prover.prove_core(pk, &stdin, opts.sp1_prover_opts, context)?;
+        let proof = self.prover.prove_core(pk, &stdin, opts.sp1_prover_opts.clone(), context)?;
         if kind == SP1ProofKind::Core {
             return Ok(SP1ProofWithPublicValues {
                 proof: SP1Proof::Core(proof.proof.0),
@@ -63,8 +71,13 @@ impl Prover<DefaultProverComponents> for CpuProver {
         let public_values = proof.public_values.clone();
 
         // Generate the compressed proof.
-        let reduce_proof =
-            self.prover.compress(&pk.vk, proof, deferred_proofs, opts.sp1_prover_opts)?;
+        let reduce_proof = self.prover.compress_with_monitor(
+            &pk.vk,
+            proof,
+            deferred_proofs,
+            opts.sp1_prover_opts.clone(),
+            monitor,
+        )?;
         if kind == SP1ProofKind::Compressed {
             return Ok(SP1ProofWithPublicValues {
                 proof: SP1Proof::Compressed(reduce_proof.proof),
@@ -75,10 +88,13 @@ impl Prover<DefaultProverComponents> for CpuProver {
         }
 
         // Generate the shrink proof.
-        let compress_proof = self.prover.shrink(reduce_proof, opts.sp1_prover_opts)?;
+        check_cancelled(monitor)?;
+        let compress_proof = self.prover.shrink(reduce_proof, opts.sp1_prover_opts.clone())?;
 
         // Genenerate the wrap proof.
+        check_cancelled(monitor)?;
         let outer_proof = self.prover.wrap_bn254(compress_proof, opts.sp1_prover_opts)?;
+        check_cancelled(monitor)?;
 
         if kind == SP1ProofKind::Plonk {
             let plonk_bn254_aritfacts = if sp1_prover::build::sp1_dev_mode() {
@@ -87,7 +103,7 @@ impl Prover<DefaultProverComponents> for CpuProver {
                     &outer_proof.proof,
                 )
             } else {
-                try_install_circuit_artifacts()
+                shrink_artifacts_dir(try_install_circuit_artifacts(), self.prover.shrink_log_degree)
             };
             let proof = self.prover.wrap_plonk_bn254(outer_proof, &plonk_bn254_aritfacts);
 
@@ -104,7 +120,7 @@ impl Prover<DefaultProverComponents> for CpuProver {
                     &outer_proof.proof,
                 )
             } else {
-                try_install_circuit_artifacts()
+                shrink_artifacts_dir(try_install_circuit_artifacts(), self.prover.shrink_log_degree)
             };
 
             let proof = self.prover.wrap_groth16_bn254(outer_proof, &groth16_bn254_artifacts);
@@ -120,6 +136,14 @@ impl Prover<DefaultProverComponents> for CpuProver {
     }
 }
 
+/// Stops the proof between two of its stages if it has been cancelled.
+fn check_cancelled(monitor: Option<&ProofMonitor>) -> SP1Result<()> {
+    if monitor.is_some_and(ProofMonitor::is_cancelled) {
+        return Err(SP1Error::Cancelled);
+    }
+    Ok(())
+}
+
 impl Default for CpuProver {
     fn default() -> Self {
         Self::new()
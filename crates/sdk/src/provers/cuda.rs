@@ -1,11 +1,10 @@
-use anyhow::Result;
 use sp1_cuda::SP1CudaProver;
 use sp1_prover::{components::DefaultProverComponents, SP1Prover, SP1Stdin};
 
 use super::ProverType;
 use crate::{
     provers::{try_install_circuit_artifacts, ProofOpts},
-    Prover, SP1Context, SP1Proof, SP1ProofKind, SP1ProofWithPublicValues, SP1ProvingKey,
+    Prover, SP1Context, SP1Proof, SP1ProofKind, SP1ProofWithPublicValues, SP1ProvingKey, SP1Result,
     SP1VerifyingKey,
 };
 
@@ -43,7 +42,7 @@ impl Prover<DefaultProverComponents> for CudaProver {
         _opts: ProofOpts,
         _context: SP1Context<'a>,
         kind: SP1ProofKind,
-    ) -> Result<SP1ProofWithPublicValues> {
+    ) -> SP1Result<SP1ProofWithPublicValues> {
         tracing::warn!("opts and context are ignored for the cuda prover");
 
         // Generate the core proof.
@@ -1,26 +1,31 @@
 #![allow(unused_variables)]
+use std::array;
+
 use hashbrown::HashMap;
 use sp1_core_executor::SP1Context;
 use sp1_core_machine::io::SP1Stdin;
-use sp1_stark::{ShardCommitment, ShardOpenedValues, ShardProof};
+use sp1_stark::{air::PublicValues, ShardCommitment, ShardOpenedValues, ShardProof};
 
 use crate::{
-    Prover, SP1Proof, SP1ProofKind, SP1ProofWithPublicValues, SP1ProvingKey, SP1VerificationError,
-    SP1VerifyingKey,
+    Prover, SP1Proof, SP1ProofKind, SP1ProofWithPublicValues, SP1ProvingKey, SP1PublicValues,
+    SP1Result, SP1VerificationError, SP1VerifyingKey,
 };
-use anyhow::Result;
 use p3_baby_bear::BabyBear;
 use p3_field::{AbstractField, PrimeField};
 use p3_fri::{FriProof, TwoAdicFriPcsProof};
 use sp1_prover::{
     components::DefaultProverComponents,
     verify::{verify_groth16_bn254_public_inputs, verify_plonk_bn254_public_inputs},
-    Groth16Bn254Proof, HashableKey, PlonkBn254Proof, SP1Prover,
+    CoreSC, Groth16Bn254Proof, HashableKey, PlonkBn254Proof, SP1Prover,
 };
 
-use super::{ProofOpts, ProverType};
+use super::{verify_public_values_digest, ProofOpts, ProverType};
 
 /// An implementation of [crate::ProverClient] that can generate mock proofs.
+///
+/// The mock prover executes the program and returns a proof of the requested kind instantly. The
+/// proof commits to the public values of the execution, which [MockProver::verify] checks, but it
+/// is not a valid proof and is rejected by any other prover.
 pub struct MockProver {
     pub(crate) prover: SP1Prover,
 }
@@ -53,12 +58,12 @@ impl Prover<DefaultProverComponents> for MockProver {
         opts: ProofOpts,
         context: SP1Context<'a>,
         kind: SP1ProofKind,
-    ) -> Result<SP1ProofWithPublicValues> {
+    ) -> SP1Result<SP1ProofWithPublicValues> {
         match kind {
             SP1ProofKind::Core => {
                 let (public_values, _) = self.prover.execute(&pk.elf, &stdin, context)?;
                 Ok(SP1ProofWithPublicValues {
-                    proof: SP1Proof::Core(vec![]),
+                    proof: SP1Proof::Core(vec![mock_shard_proof(&public_values)]),
                     stdin,
                     public_values,
                     sp1_version: self.version().to_string(),
@@ -67,25 +72,7 @@ impl Prover<DefaultProverComponents> for MockProver {
             SP1ProofKind::Compressed => {
                 let (public_values, _) = self.prover.execute(&pk.elf, &stdin, context)?;
                 Ok(SP1ProofWithPublicValues {
-                    proof: SP1Proof::Compressed(ShardProof {
-                        commitment: ShardCommitment {
-                            main_commit: [BabyBear::zero(); 8].into(),
-                            permutation_commit: [BabyBear::zero(); 8].into(),
-                            quotient_commit: [BabyBear::zero(); 8].into(),
-                        },
-                        opened_values: ShardOpenedValues { chips: vec![] },
-                        opening_proof: TwoAdicFriPcsProof {
-                            fri_proof: FriProof {
-                                commit_phase_commits: vec![],
-                                query_proofs: vec![],
-                                final_poly: Default::default(),
-                                pow_witness: BabyBear::zero(),
-                            },
-                            query_openings: vec![],
-                        },
-                        chip_ordering: HashMap::new(),
-                        public_values: vec![],
-                    }),
+                    proof: SP1Proof::Compressed(mock_shard_proof(&public_values)),
                     stdin,
                     public_values,
                     sp1_version: self.version().to_string(),
@@ -133,7 +120,17 @@ impl Prover<DefaultProverComponents> for MockProver {
         bundle: &SP1ProofWithPublicValues,
         vkey: &SP1VerifyingKey,
     ) -> Result<(), SP1VerificationError> {
+        if bundle.sp1_version != self.version() {
+            return Err(SP1VerificationError::VersionMismatch(bundle.sp1_version.clone()));
+        }
         match &bundle.proof {
+            SP1Proof::Core(proof) => {
+                let proof = proof.last().ok_or(SP1VerificationError::InvalidPublicValues)?;
+                verify_public_values_digest(&proof.public_values, &bundle.public_values)
+            }
+            SP1Proof::Compressed(proof) => {
+                verify_public_values_digest(&proof.public_values, &bundle.public_values)
+            }
             SP1Proof::Plonk(PlonkBn254Proof { public_inputs, .. }) => {
                 verify_plonk_bn254_public_inputs(vkey, &bundle.public_values, public_inputs)
                     .map_err(SP1VerificationError::Plonk)
@@ -142,11 +139,44 @@ impl Prover<DefaultProverComponents> for MockProver {
                 verify_groth16_bn254_public_inputs(vkey, &bundle.public_values, public_inputs)
                     .map_err(SP1VerificationError::Groth16)
             }
-            _ => Ok(()),
         }
     }
 }
 
+/// A shard proof with no commitments nor openings, whose public values commit to the digest of
+/// `public_values`.
+///
+/// The proof is deterministic and has the shape of a real shard proof, but it cannot be verified.
+fn mock_shard_proof(public_values: &SP1PublicValues) -> ShardProof<CoreSC> {
+    let digest = public_values.hash();
+    let pv = PublicValues::<u32, u32> {
+        committed_value_digest: array::from_fn(|i| {
+            u32::from_le_bytes(digest[i * 4..(i + 1) * 4].try_into().unwrap())
+        }),
+        ..Default::default()
+    };
+    ShardProof {
+        commitment: ShardCommitment {
+            main_commit: [BabyBear::zero(); 8].into(),
+            permutation_commit: [BabyBear::zero(); 8].into(),
+            quotient_commit: [BabyBear::zero(); 8].into(),
+        },
+        opened_values: ShardOpenedValues { chips: vec![] },
+        opening_proof: TwoAdicFriPcsProof {
+            fri_proof: FriProof {
+                commit_phase_commits: vec![],
+                query_proofs: vec![],
+                final_poly: Default::default(),
+                pow_witness: BabyBear::zero(),
+            },
+            query_openings: vec![],
+        },
+        chip_ordering: HashMap::new(),
+        public_values: pv.to_vec(),
+        masking: None,
+    }
+}
+
 impl Default for MockProver {
     fn default() -> Self {
         Self::new()
@@ -9,23 +9,23 @@ pub use cuda::CudaProver;
 pub use mock::MockProver;
 
 use itertools::Itertools;
+use p3_baby_bear::BabyBear;
 use p3_field::PrimeField32;
-use std::borrow::Borrow;
 use std::time::Duration;
 
-use anyhow::Result;
 use sp1_core_executor::SP1Context;
 use sp1_core_machine::{io::SP1Stdin, SP1_CIRCUIT_VERSION};
 use sp1_prover::{
     components::SP1ProverComponents, CoreSC, InnerSC, SP1CoreProofData, SP1Prover, SP1ProvingKey,
     SP1ReduceProof, SP1VerifyingKey,
 };
-use sp1_stark::{air::PublicValues, MachineVerificationError, SP1ProverOpts, Word};
+use sp1_stark::{air::PUBLIC_VALUES_LAYOUT, MachineVerificationError, SP1ProverOpts};
 use strum_macros::EnumString;
 use thiserror::Error;
 
 use crate::{
-    install::try_install_circuit_artifacts, SP1Proof, SP1ProofKind, SP1ProofWithPublicValues,
+    install::try_install_circuit_artifacts, PublicValuesDomain, SP1Proof, SP1ProofKind,
+    SP1ProofWithPublicValues, SP1PublicValues, SP1Result,
 };
 
 /// The type of prover.
@@ -44,6 +44,8 @@ pub struct ProofOpts {
     pub sp1_prover_opts: SP1ProverOpts,
     /// Optional timeout duration for proof generation.
     pub timeout: Option<Duration>,
+    /// Whether to skip executing the program locally before requesting a proof from the network.
+    pub skip_simulation: bool,
 }
 
 #[derive(Error, Debug)]
@@ -60,6 +62,10 @@ pub enum SP1VerificationError {
     Plonk(anyhow::Error),
     #[error("Groth16 verification error: {0}")]
     Groth16(anyhow::Error),
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(ed25519_dalek::SignatureError),
+    #[error("Public values domain mismatch: found {0:?}")]
+    DomainMismatch(Option<PublicValuesDomain>),
 }
 
 /// An implementation of [crate::ProverClient].
@@ -82,7 +88,7 @@ pub trait Prover<C: SP1ProverComponents>: Send + Sync {
         opts: ProofOpts,
         context: SP1Context<'a>,
         kind: SP1ProofKind,
-    ) -> Result<SP1ProofWithPublicValues>;
+    ) -> SP1Result<SP1ProofWithPublicValues>;
 
     /// Verify that an SP1 proof is valid given its vkey and metadata.
     /// For Plonk proofs, verifies that the public inputs of the PlonkBn254 proof match
@@ -95,54 +101,18 @@ pub trait Prover<C: SP1ProverComponents>: Send + Sync {
         if bundle.sp1_version != self.version() {
             return Err(SP1VerificationError::VersionMismatch(bundle.sp1_version.clone()));
         }
+        bundle.verify_public_values()?;
         match &bundle.proof {
             SP1Proof::Core(proof) => {
-                let public_values: &PublicValues<Word<_>, _> =
-                    proof.last().unwrap().public_values.as_slice().borrow();
-
-                // Get the commited value digest bytes.
-                let commited_value_digest_bytes = public_values
-                    .committed_value_digest
-                    .iter()
-                    .flat_map(|w| w.0.iter().map(|x| x.as_canonical_u32() as u8))
-                    .collect_vec();
-
-                // Make sure the commited value digest matches the public values hash.
-                for (a, b) in commited_value_digest_bytes.iter().zip_eq(bundle.public_values.hash())
-                {
-                    if *a != b {
-                        return Err(SP1VerificationError::InvalidPublicValues);
-                    }
-                }
-
                 // Verify the core proof.
                 self.sp1_prover()
                     .verify(&SP1CoreProofData(proof.clone()), vkey)
                     .map_err(SP1VerificationError::Core)
             }
-            SP1Proof::Compressed(proof) => {
-                let public_values: &PublicValues<Word<_>, _> =
-                    proof.public_values.as_slice().borrow();
-
-                // Get the commited value digest bytes.
-                let commited_value_digest_bytes = public_values
-                    .committed_value_digest
-                    .iter()
-                    .flat_map(|w| w.0.iter().map(|x| x.as_canonical_u32() as u8))
-                    .collect_vec();
-
-                // Make sure the commited value digest matches the public values hash.
-                for (a, b) in commited_value_digest_bytes.iter().zip_eq(bundle.public_values.hash())
-                {
-                    if *a != b {
-                        return Err(SP1VerificationError::InvalidPublicValues);
-                    }
-                }
-
-                self.sp1_prover()
-                    .verify_compressed(&SP1ReduceProof { proof: proof.clone() }, vkey)
-                    .map_err(SP1VerificationError::Recursion)
-            }
+            SP1Proof::Compressed(proof) => self
+                .sp1_prover()
+                .verify_compressed(&SP1ReduceProof { proof: proof.clone() }, vkey)
+                .map_err(SP1VerificationError::Recursion),
             SP1Proof::Plonk(proof) => self
                 .sp1_prover()
                 .verify_plonk_bn254(
@@ -152,7 +122,10 @@ pub trait Prover<C: SP1ProverComponents>: Send + Sync {
                     &if sp1_prover::build::sp1_dev_mode() {
                         sp1_prover::build::plonk_bn254_artifacts_dev_dir()
                     } else {
-                        try_install_circuit_artifacts()
+                        sp1_prover::build::shrink_artifacts_dir(
+                            try_install_circuit_artifacts(),
+                            self.sp1_prover().shrink_log_degree,
+                        )
                     },
                 )
                 .map_err(SP1VerificationError::Plonk),
@@ -165,10 +138,39 @@ pub trait Prover<C: SP1ProverComponents>: Send + Sync {
                     &if sp1_prover::build::sp1_dev_mode() {
                         sp1_prover::build::groth16_bn254_artifacts_dev_dir()
                     } else {
-                        try_install_circuit_artifacts()
+                        sp1_prover::build::shrink_artifacts_dir(
+                            try_install_circuit_artifacts(),
+                            self.sp1_prover().shrink_log_degree,
+                        )
                     },
                 )
                 .map_err(SP1VerificationError::Groth16),
         }
     }
 }
+
+/// Checks that the committed value digest in the public values of a shard proof is the hash of the
+/// public values of the bundle.
+pub(crate) fn verify_public_values_digest(
+    proof_public_values: &[BabyBear],
+    public_values: &SP1PublicValues,
+) -> Result<(), SP1VerificationError> {
+    let proof_public_values = PUBLIC_VALUES_LAYOUT
+        .view(proof_public_values)
+        .ok_or(SP1VerificationError::InvalidPublicValues)?;
+
+    // Get the commited value digest bytes.
+    let commited_value_digest_bytes = proof_public_values
+        .committed_value_digest
+        .iter()
+        .flat_map(|w| w.0.iter().map(|x| x.as_canonical_u32() as u8))
+        .collect_vec();
+
+    // Make sure the commited value digest matches the public values hash.
+    for (a, b) in commited_value_digest_bytes.iter().zip_eq(public_values.hash()) {
+        if *a != b {
+            return Err(SP1VerificationError::InvalidPublicValues);
+        }
+    }
+    Ok(())
+}
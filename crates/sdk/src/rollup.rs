@@ -0,0 +1,264 @@
+//! Proving a chain of blocks as separate proofs aggregated into one.
+//!
+//! A rollup proves the state transition function (STF) of each of its blocks with a *block
+//! program*, whose proofs are independent and can be generated in parallel. An *aggregator*
+//! program then verifies the compressed proofs of a range of blocks, checks that each block starts
+//! from the state the previous one ended in, and commits a single [`RollupOutput`], which can be
+//! wrapped in a Plonk or Groth16 proof for onchain verification.
+//!
+//! The block program commits a [`BlockTransition`]:
+//!
+//! ```ignore
+//! sp1_zkvm::io::commit(&(parent_state_root, state_root, block_hash));
+//! ```
+//!
+//! The aggregator reads the hash of the verifying key of the block program and the public values
+//! of each block proof as two stdin elements, as written by [`RollupInput::stdin`], verifies the
+//! proofs, and commits the fields of a [`RollupOutput`], in order. The proofs themselves are
+//! witnessed by the prover during the recursive aggregation, as for any proof verified with
+//! `sp1_zkvm::lib::verify::verify_sp1_proof`. The `examples/rollup` directory holds both programs
+//! and a script driving them.
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    action, HashableKey, ProverClient, SP1Error, SP1Proof, SP1ProofWithPublicValues, SP1ProvingKey,
+    SP1Result, SP1Stdin, SP1VerifyingKey,
+};
+
+/// The public values committed by the block program for one block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockTransition {
+    /// The state root before the block.
+    pub parent_state_root: [u8; 32],
+    /// The state root after the block.
+    pub state_root: [u8; 32],
+    /// The hash of the block.
+    pub block_hash: [u8; 32],
+}
+
+impl BlockTransition {
+    /// Reads the transition committed by the block program from the public values of its proof.
+    pub fn from_proof(proof: &SP1ProofWithPublicValues) -> SP1Result<Self> {
+        bincode::deserialize(proof.public_values.as_slice())
+            .map_err(|e| SP1Error::Encoding(anyhow!("invalid block public values: {e}")))
+    }
+}
+
+/// Extends the chain commitment `commitment` with the block `block_hash`.
+///
+/// The chain commitment of a range of blocks starts at zero, and is extended with
+/// `sha256(commitment || block_hash)` for each block, in order. It binds the aggregated proof to
+/// the exact sequence of blocks, which the state roots alone do not.
+pub fn extend_chain_commitment(commitment: [u8; 32], block_hash: &[u8; 32]) -> [u8; 32] {
+    Sha256::new().chain_update(commitment).chain_update(block_hash).finalize().into()
+}
+
+/// The chain commitment of a range of blocks. See [`extend_chain_commitment`].
+pub fn chain_commitment<'a>(
+    transitions: impl IntoIterator<Item = &'a BlockTransition>,
+) -> [u8; 32] {
+    transitions.into_iter().fold([0; 32], |commitment, transition| {
+        extend_chain_commitment(commitment, &transition.block_hash)
+    })
+}
+
+/// The public values committed by the aggregator, describing the range of blocks it verified.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RollupOutput {
+    /// The hash of the verifying key of the block program, as returned by `hash_u32`.
+    pub block_vkey_hash: [u32; 8],
+    /// The state root before the first block.
+    pub parent_state_root: [u8; 32],
+    /// The state root after the last block.
+    pub state_root: [u8; 32],
+    /// The number of blocks.
+    pub num_blocks: u64,
+    /// The chain commitment of the blocks. See [`chain_commitment`].
+    pub chain_commitment: [u8; 32],
+}
+
+impl RollupOutput {
+    /// Reads the output committed by the aggregator from the public values of its proof.
+    pub fn from_proof(proof: &SP1ProofWithPublicValues) -> SP1Result<Self> {
+        bincode::deserialize(proof.public_values.as_slice())
+            .map_err(|e| SP1Error::Encoding(anyhow!("invalid rollup public values: {e}")))
+    }
+
+    /// The output the aggregator is expected to commit for the given blocks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no blocks.
+    pub fn expected(block_vk: &SP1VerifyingKey, transitions: &[BlockTransition]) -> Self {
+        let (first, last) = (transitions.first().unwrap(), transitions.last().unwrap());
+        Self {
+            block_vkey_hash: block_vk.hash_u32(),
+            parent_state_root: first.parent_state_root,
+            state_root: last.state_root,
+            num_blocks: transitions.len() as u64,
+            chain_commitment: chain_commitment(transitions),
+        }
+    }
+}
+
+/// The compressed proofs of a range of consecutive blocks, checked to chain before being
+/// aggregated.
+#[derive(Clone)]
+pub struct RollupInput {
+    block_vk: SP1VerifyingKey,
+    proofs: Vec<SP1ProofWithPublicValues>,
+    transitions: Vec<BlockTransition>,
+}
+
+impl RollupInput {
+    /// Creates the input of an aggregation from the proofs of the blocks, in order.
+    ///
+    /// Fails if there are no proofs, if a proof is not compressed, or if a block does not start
+    /// from the state root the previous block ended in, since the aggregator would reject it.
+    pub fn new(
+        block_vk: SP1VerifyingKey,
+        proofs: Vec<SP1ProofWithPublicValues>,
+    ) -> SP1Result<Self> {
+        if proofs.is_empty() {
+            return Err(SP1Error::Other(anyhow!("no block proofs to aggregate")));
+        }
+        let mut transitions: Vec<BlockTransition> = Vec::with_capacity(proofs.len());
+        for (i, proof) in proofs.iter().enumerate() {
+            if !matches!(proof.proof, SP1Proof::Compressed(_)) {
+                return Err(SP1Error::Encoding(anyhow!("block proof {i} is not compressed")));
+            }
+            let transition = BlockTransition::from_proof(proof)?;
+            if let Some(previous) = transitions.last() {
+                if previous.state_root != transition.parent_state_root {
+                    return Err(SP1Error::Other(anyhow!(
+                        "block {i} starts from state root 0x{}, but block {} ended in 0x{}",
+                        hex::encode(transition.parent_state_root),
+                        i - 1,
+                        hex::encode(previous.state_root)
+                    )));
+                }
+            }
+            transitions.push(transition);
+        }
+        Ok(Self { block_vk, proofs, transitions })
+    }
+
+    /// The transitions of the blocks, in order.
+    pub fn transitions(&self) -> &[BlockTransition] {
+        &self.transitions
+    }
+
+    /// The output the aggregator is expected to commit for these blocks.
+    pub fn expected_output(&self) -> RollupOutput {
+        RollupOutput::expected(&self.block_vk, &self.transitions)
+    }
+
+    /// The stdin of the aggregator: the hash of the block verifying key and the public values of
+    /// each block, with the block proofs registered for the aggregator to verify.
+    pub fn stdin(&self) -> SP1Stdin {
+        let public_values =
+            self.proofs.iter().map(|proof| proof.public_values.to_vec()).collect::<Vec<_>>();
+        SP1Stdin::builder()
+            .write(&self.block_vk.hash_u32())
+            .write(&public_values)
+            .write_proofs(self.proofs.iter().map(|proof| {
+                let SP1Proof::Compressed(proof) = &proof.proof else {
+                    unreachable!("block proofs are checked to be compressed")
+                };
+                (proof.clone(), self.block_vk.vk.clone())
+            }))
+            .build()
+    }
+}
+
+impl ProverClient {
+    /// Proves each block with the block program, one compressed proof per stdin, in order.
+    ///
+    /// The proofs are independent, so a pipeline proving many blocks would rather spread them over
+    /// several provers; this runs them one after the other.
+    pub fn prove_blocks(
+        &self,
+        block_pk: &SP1ProvingKey,
+        blocks: impl IntoIterator<Item = SP1Stdin>,
+    ) -> SP1Result<Vec<SP1ProofWithPublicValues>> {
+        blocks.into_iter().map(|stdin| self.prove(block_pk, stdin).compressed().run()).collect()
+    }
+
+    /// Prepare to aggregate the proofs of a range of blocks, by proving the execution of an
+    /// aggregator on them. See the [module documentation](crate::rollup) for how to write the
+    /// block program and the aggregator.
+    ///
+    /// The returned [action::Prove] generates a compressed proof by default. Its public values are
+    /// a [`RollupOutput`], which should be checked against [`RollupInput::expected_output`].
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{ProverClient, RollupInput, RollupOutput, SP1Stdin};
+    ///
+    /// let block_elf = std::fs::read("block.elf").unwrap();
+    /// let aggregator_elf = std::fs::read("aggregator.elf").unwrap();
+    /// let client = ProverClient::new();
+    /// let (block_pk, block_vk) = client.setup(&block_elf);
+    /// let (aggregator_pk, aggregator_vk) = client.setup(&aggregator_elf);
+    ///
+    /// let blocks: Vec<SP1Stdin> = vec![];
+    /// let proofs = client.prove_blocks(&block_pk, blocks).unwrap();
+    /// let input = RollupInput::new(block_vk, proofs).unwrap();
+    ///
+    /// let proof = client.aggregate_blocks(&aggregator_pk, &input).plonk().run().unwrap();
+    /// client.verify(&proof, &aggregator_vk).unwrap();
+    /// assert_eq!(RollupOutput::from_proof(&proof).unwrap(), input.expected_output());
+    /// ```
+    pub fn aggregate_blocks<'a>(
+        &'a self,
+        aggregator_pk: &'a SP1ProvingKey,
+        input: &RollupInput,
+    ) -> action::Prove<'a> {
+        self.prove(aggregator_pk, input.stdin()).compressed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_primitives::io::SP1PublicValues;
+
+    use super::*;
+
+    fn transition(parent: u8, state: u8) -> BlockTransition {
+        BlockTransition {
+            parent_state_root: [parent; 32],
+            state_root: [state; 32],
+            block_hash: [state.wrapping_mul(3); 32],
+        }
+    }
+
+    #[test]
+    fn test_chain_commitment() {
+        let transitions = [transition(0, 1), transition(1, 2)];
+        let first = Sha256::new().chain_update([0; 32]).chain_update([3; 32]).finalize();
+        let second = Sha256::new().chain_update(first).chain_update([6; 32]).finalize();
+        assert_eq!(chain_commitment(&transitions), <[u8; 32]>::from(second));
+        assert_eq!(chain_commitment(&[]), [0; 32]);
+    }
+
+    #[test]
+    fn test_block_transition_from_proof() {
+        let expected = transition(4, 5);
+        let mut public_values = SP1PublicValues::new();
+        public_values.write(&(
+            expected.parent_state_root,
+            expected.state_root,
+            expected.block_hash,
+        ));
+        let proof = SP1ProofWithPublicValues {
+            proof: SP1Proof::Core(vec![]),
+            stdin: SP1Stdin::new(),
+            public_values,
+            sp1_version: crate::SP1_CIRCUIT_VERSION.to_string(),
+        };
+        assert_eq!(BlockTransition::from_proof(&proof).unwrap(), expected);
+    }
+}
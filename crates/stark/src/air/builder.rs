@@ -35,6 +35,27 @@ pub trait BaseAirBuilder: AirBuilder + MessageBuilder<AirInteraction<Self::Expr>
         self.when_ne(condition, Self::F::one())
     }
 
+    /// Sends an interaction of the given kind. See [`send!`](crate::send) for a shorthand.
+    fn send_interaction(
+        &mut self,
+        kind: InteractionKind,
+        values: Vec<Self::Expr>,
+        multiplicity: impl Into<Self::Expr>,
+    ) {
+        self.send(AirInteraction::new(values, multiplicity.into(), kind));
+    }
+
+    /// Receives an interaction of the given kind. See [`receive!`](crate::receive) for a
+    /// shorthand.
+    fn receive_interaction(
+        &mut self,
+        kind: InteractionKind,
+        values: Vec<Self::Expr>,
+        multiplicity: impl Into<Self::Expr>,
+    ) {
+        self.receive(AirInteraction::new(values, multiplicity.into(), kind));
+    }
+
     /// Asserts that an iterator of expressions are all equal.
     fn assert_all_eq<I1: Into<Self::Expr>, I2: Into<Self::Expr>>(
         &mut self,
@@ -16,3 +16,36 @@ impl<E> AirInteraction<E> {
         Self { values, multiplicity, kind }
     }
 }
+
+/// Sends an interaction from an AIR, converting each value and the multiplicity into an
+/// expression of the builder.
+///
+/// ```ignore
+/// // Request that `a + b` is looked up in a table of a custom chip, once per real row.
+/// sp1_stark::send!(builder, InteractionKind::Custom(0), [local.a, local.b], local.is_real);
+/// ```
+#[macro_export]
+macro_rules! send {
+    ($builder:expr, $kind:expr, [$($value:expr),* $(,)?], $multiplicity:expr $(,)?) => {
+        $crate::air::BaseAirBuilder::send_interaction(
+            $builder,
+            $kind,
+            vec![$(::core::convert::Into::into($value)),*],
+            $multiplicity,
+        )
+    };
+}
+
+/// Receives an interaction in an AIR, converting each value and the multiplicity into an
+/// expression of the builder. The counterpart of [`send!`](crate::send).
+#[macro_export]
+macro_rules! receive {
+    ($builder:expr, $kind:expr, [$($value:expr),* $(,)?], $multiplicity:expr $(,)?) => {
+        $crate::air::BaseAirBuilder::receive_interaction(
+            $builder,
+            $kind,
+            vec![$(::core::convert::Into::into($value)),*],
+            $multiplicity,
+        )
+    };
+}
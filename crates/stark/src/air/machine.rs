@@ -32,6 +32,14 @@ pub trait MachineAir<F: Field>: BaseAir<F> + 'static + Send + Sync {
     /// Whether this execution record contains events for this air.
     fn included(&self, shard: &Self::Record) -> bool;
 
+    /// The number of non-padding rows in the trace generated for the given record, if known.
+    ///
+    /// This is only used to report padding overhead and to check that padding rows are inert, and
+    /// does not affect the generated trace.
+    fn num_real_rows(&self, _shard: &Self::Record) -> Option<usize> {
+        None
+    }
+
     /// The width of the preprocessed trace.
     fn preprocessed_width(&self) -> usize {
         0
@@ -1,4 +1,7 @@
-use core::{fmt::Debug, mem::size_of};
+use core::{
+    fmt::Debug,
+    mem::{size_of, transmute},
+};
 use std::borrow::{Borrow, BorrowMut};
 
 use itertools::Itertools;
@@ -16,8 +19,99 @@ pub const PV_DIGEST_NUM_WORDS: usize = 8;
 /// The number of field elements in the poseidon2 digest.
 pub const POSEIDON_NUM_WORDS: usize = 8;
 
+/// The version of [`PUBLIC_VALUES_LAYOUT`], bumped whenever a field of [`PublicValues`] is added,
+/// removed or moved.
+pub const PUBLIC_VALUES_LAYOUT_VERSION: u32 = 1;
+
+/// The layout of the public values of core shard proofs.
+pub const PUBLIC_VALUES_LAYOUT: PublicValuesLayout = PublicValuesLayout {
+    version: PUBLIC_VALUES_LAYOUT_VERSION,
+    indices: make_indices(),
+    num_elements: SP1_PROOF_NUM_PV_ELTS,
+    padded_len: PROOF_MAX_NUM_PVS,
+};
+
+/// The layout of the public values of a shard proof.
+///
+/// The public values of a shard proof are the fields of [`PublicValues`] flattened into a vector
+/// of field elements, and padded with zeros. The core machine, the verifiers and the recursion
+/// circuits read and write this vector through a layout rather than through hardcoded offsets and
+/// lengths, so that adding a public value only requires adding a field to [`PublicValues`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicValuesLayout {
+    /// The version of the layout.
+    pub version: u32,
+    /// The index of every public value in the flattened vector.
+    pub indices: PublicValues<Word<usize>, usize>,
+    /// The number of public values, before padding.
+    pub num_elements: usize,
+    /// The length to which the flattened vector is padded.
+    pub padded_len: usize,
+}
+
+const fn make_indices() -> PublicValues<Word<usize>, usize> {
+    let mut indices = [0; SP1_PROOF_NUM_PV_ELTS];
+    let mut i = 0;
+    while i < SP1_PROOF_NUM_PV_ELTS {
+        indices[i] = i;
+        i += 1;
+    }
+    unsafe {
+        transmute::<[usize; SP1_PROOF_NUM_PV_ELTS], PublicValues<Word<usize>, usize>>(indices)
+    }
+}
+
+impl PublicValuesLayout {
+    /// The same layout, padded to `padded_len` elements instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `padded_len` is smaller than the number of public values.
+    #[must_use]
+    pub const fn with_padded_len(mut self, padded_len: usize) -> Self {
+        assert!(padded_len >= self.num_elements, "padded length is smaller than the layout");
+        self.padded_len = padded_len;
+        self
+    }
+
+    /// Flattens `public_values` into a vector of field elements, padded with zeros.
+    #[must_use]
+    pub fn to_padded_vec<F: AbstractField>(
+        &self,
+        public_values: &PublicValues<u32, u32>,
+    ) -> Vec<F> {
+        let mut ret = vec![F::zero(); self.padded_len];
+        let ret_ref_mut: &mut PublicValues<Word<F>, F> = ret.as_mut_slice().borrow_mut();
+        *ret_ref_mut = PublicValues::<Word<F>, F>::from(*public_values);
+        ret
+    }
+
+    /// Views the flattened public values `values` as [`PublicValues`], if there are enough of
+    /// them.
+    #[must_use]
+    pub fn view<'a, T: Clone>(&self, values: &'a [T]) -> Option<&'a PublicValues<Word<T>, T>> {
+        (values.len() >= self.num_elements).then(|| values.borrow())
+    }
+
+    /// Reads the flattened public values `values` as [`PublicValues`], converting each of them.
+    ///
+    /// This is meant for constraint code, where the public values of the builder have to be
+    /// converted to expressions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer values than the number of public values of the layout.
+    #[must_use]
+    pub fn read<T: Clone, V: Clone + Into<T>>(&self, values: &[V]) -> PublicValues<Word<T>, T> {
+        let values =
+            values[..self.num_elements].iter().cloned().map(Into::into).collect::<Vec<T>>();
+        let public_values: &PublicValues<Word<T>, T> = values.as_slice().borrow();
+        public_values.clone()
+    }
+}
+
 /// Stores all of a shard proof's public values.
-#[derive(Serialize, Deserialize, Clone, Copy, Default, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
 #[repr(C)]
 pub struct PublicValues<W, T> {
     /// The hash of all the bytes that the guest program has written to public values.
@@ -58,15 +152,10 @@ pub struct PublicValues<W, T> {
 
 impl PublicValues<u32, u32> {
     /// Convert the public values into a vector of field elements.  This function will pad the
-    /// vector to the maximum number of public values.
+    /// vector as specified by [`PUBLIC_VALUES_LAYOUT`].
     #[must_use]
     pub fn to_vec<F: AbstractField>(&self) -> Vec<F> {
-        let mut ret = vec![F::zero(); PROOF_MAX_NUM_PVS];
-
-        let field_values = PublicValues::<Word<F>, F>::from(*self);
-        let ret_ref_mut: &mut PublicValues<Word<F>, F> = ret.as_mut_slice().borrow_mut();
-        *ret_ref_mut = field_values;
-        ret
+        PUBLIC_VALUES_LAYOUT.to_padded_vec(self)
     }
 
     /// Resets the public values to zero.
@@ -169,8 +258,30 @@ impl<F: AbstractField> From<PublicValues<u32, u32>> for PublicValues<Word<F>, F>
 
 #[cfg(test)]
 mod tests {
+    use p3_baby_bear::BabyBear;
+
+    use super::*;
     use crate::air::public_values;
 
+    #[test]
+    fn test_public_values_layout() {
+        let layout = PUBLIC_VALUES_LAYOUT;
+        assert_eq!(layout.indices.committed_value_digest[0].0, [0, 1, 2, 3]);
+        assert_eq!(layout.indices.deferred_proofs_digest[0], 4 * PV_DIGEST_NUM_WORDS);
+        assert_eq!(layout.indices.last_finalize_addr_bits[31], layout.num_elements - 1);
+
+        let public_values =
+            PublicValues::<u32, u32> { exit_code: 7, shard: 3, ..Default::default() };
+        let values = layout
+            .with_padded_len(layout.num_elements + 1)
+            .to_padded_vec::<BabyBear>(&public_values);
+        assert_eq!(values.len(), layout.num_elements + 1);
+        assert_eq!(values[layout.indices.exit_code], BabyBear::from_canonical_u32(7));
+        assert_eq!(layout.view(&values).unwrap().shard, BabyBear::from_canonical_u32(3));
+        assert!(layout.view(&values[..layout.num_elements - 1]).is_none());
+        assert_eq!(layout.read::<BabyBear, _>(&values), *layout.view(&values).unwrap());
+    }
+
     /// Check that the [`PI_DIGEST_NUM_WORDS`] number match the zkVM crate's.
     #[test]
     fn test_public_values_digest_num_words_consistency_zkvm() {
@@ -0,0 +1,111 @@
+#![allow(missing_docs)]
+
+/// A configuration committing to traces with Blake3 Merkle trees.
+///
+/// Hashing the traces with Poseidon2 dominates the commit time of core shards. Blake3 is much
+/// faster to compute natively, but is not supported by the recursion VM, so this configuration is
+/// only for proofs that are verified natively and never recursed.
+pub mod baby_bear_blake3 {
+    use p3_baby_bear::BabyBear;
+    use p3_blake3::Blake3;
+    use p3_challenger::{HashChallenger, SerializingChallenger32};
+    use p3_commit::ExtensionMmcs;
+    use p3_dft::Radix2DitParallel;
+    use p3_field::extension::BinomialExtensionField;
+    use p3_fri::{FriConfig, TwoAdicFriPcs};
+    use p3_merkle_tree::FieldMerkleTreeMmcs;
+    use p3_symmetric::{CompressionFunctionFromHasher, SerializingHasher32};
+    use serde::{Deserialize, Serialize};
+
+    use crate::StarkGenericConfig;
+
+    pub type Val = BabyBear;
+    pub type Challenge = BinomialExtensionField<Val, 4>;
+
+    pub type ByteHash = Blake3;
+    pub type FieldHash = SerializingHasher32<ByteHash>;
+    pub type MyCompress = CompressionFunctionFromHasher<u8, ByteHash, 2, 32>;
+    pub type ValMmcs = FieldMerkleTreeMmcs<Val, u8, FieldHash, MyCompress, 32>;
+    pub type ChallengeMmcs = ExtensionMmcs<Val, Challenge, ValMmcs>;
+    pub type Dft = Radix2DitParallel;
+    pub type Challenger = SerializingChallenger32<Val, HashChallenger<u8, ByteHash, 32>>;
+    type Pcs = TwoAdicFriPcs<Val, Dft, ValMmcs, ChallengeMmcs>;
+
+    /// The Merkle tree commitment scheme of the traces.
+    #[must_use]
+    pub fn val_mmcs() -> ValMmcs {
+        ValMmcs::new(FieldHash::new(ByteHash {}), MyCompress::new(ByteHash {}))
+    }
+
+    /// The FRI parameters, which are those of
+    /// [`default_fri_config`](crate::baby_bear_poseidon2::default_fri_config) with Blake3 Merkle
+    /// trees.
+    #[must_use]
+    pub fn default_fri_config() -> FriConfig<ChallengeMmcs> {
+        let challenge_mmcs = ChallengeMmcs::new(val_mmcs());
+        let num_queries = match std::env::var("FRI_QUERIES") {
+            Ok(value) => value.parse().unwrap(),
+            Err(_) => 100,
+        };
+        FriConfig { log_blowup: 1, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+    }
+
+    /// A configuration for core proofs that are verified natively, see the
+    /// [module documentation](crate::baby_bear_blake3).
+    #[derive(Deserialize)]
+    #[serde(from = "std::marker::PhantomData<BabyBearBlake3>")]
+    pub struct BabyBearBlake3 {
+        pcs: Pcs,
+    }
+
+    impl BabyBearBlake3 {
+        #[must_use]
+        pub fn new() -> Self {
+            let pcs = Pcs::new(27, Dft {}, val_mmcs(), default_fri_config());
+            Self { pcs }
+        }
+    }
+
+    impl Clone for BabyBearBlake3 {
+        fn clone(&self) -> Self {
+            Self::new()
+        }
+    }
+
+    impl Default for BabyBearBlake3 {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Serialize for BabyBearBlake3 {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            std::marker::PhantomData::<BabyBearBlake3>.serialize(serializer)
+        }
+    }
+
+    impl From<std::marker::PhantomData<BabyBearBlake3>> for BabyBearBlake3 {
+        fn from(_: std::marker::PhantomData<BabyBearBlake3>) -> Self {
+            Self::new()
+        }
+    }
+
+    impl StarkGenericConfig for BabyBearBlake3 {
+        type Val = Val;
+        type Domain = <Pcs as p3_commit::Pcs<Challenge, Challenger>>::Domain;
+        type Pcs = Pcs;
+        type Challenge = Challenge;
+        type Challenger = Challenger;
+
+        fn pcs(&self) -> &Self::Pcs {
+            &self.pcs
+        }
+
+        fn challenger(&self) -> Self::Challenger {
+            Challenger::from_hasher(vec![], ByteHash {})
+        }
+    }
+}
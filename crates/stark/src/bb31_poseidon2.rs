@@ -146,6 +146,8 @@ impl StarkGenericConfig for BabyBearPoseidon2Inner {
 
 pub mod baby_bear_poseidon2 {
 
+    use std::sync::OnceLock;
+
     use p3_baby_bear::{BabyBear, DiffusionMatrixBabyBear};
     use p3_challenger::DuplexChallenger;
     use p3_commit::ExtensionMmcs;
@@ -158,7 +160,7 @@ pub mod baby_bear_poseidon2 {
     use serde::{Deserialize, Serialize};
     use sp1_primitives::RC_16_30;
 
-    use crate::StarkGenericConfig;
+    use crate::{CachedDft, StarkGenericConfig, ZkConfig};
 
     pub type Val = BabyBear;
     pub type Challenge = BinomialExtensionField<Val, 4>;
@@ -174,7 +176,7 @@ pub mod baby_bear_poseidon2 {
         8,
     >;
     pub type ChallengeMmcs = ExtensionMmcs<Val, Challenge, ValMmcs>;
-    pub type Dft = Radix2DitParallel;
+    pub type Dft = CachedDft<Val, Radix2DitParallel>;
     pub type Challenger = DuplexChallenger<Val, Perm, 16, 8>;
     type Pcs = TwoAdicFriPcs<Val, Dft, ValMmcs, ChallengeMmcs>;
 
@@ -200,6 +202,14 @@ pub mod baby_bear_poseidon2 {
         )
     }
 
+    /// The DFT of the PCS. It is shared by all the configurations, so that the coset shift powers
+    /// computed for one shard are reused by the next, even across clones of a configuration.
+    #[must_use]
+    pub fn dft() -> Dft {
+        static DFT: OnceLock<Dft> = OnceLock::new();
+        DFT.get_or_init(Dft::default).clone()
+    }
+
     #[must_use]
     pub fn default_fri_config() -> FriConfig<ChallengeMmcs> {
         let perm = my_perm();
@@ -215,20 +225,44 @@ pub mod baby_bear_poseidon2 {
 
     #[must_use]
     pub fn compressed_fri_config() -> FriConfig<ChallengeMmcs> {
+        compressed_fri_config_with_log_blowup(3)
+    }
+
+    /// The FRI parameters of compressed proofs with a blowup of `2^log_blowup`.
+    ///
+    /// A larger blowup needs fewer queries for the same security, which makes proofs slower to
+    /// produce but smaller and cheaper to verify recursively.
+    #[must_use]
+    pub fn compressed_fri_config_with_log_blowup(log_blowup: usize) -> FriConfig<ChallengeMmcs> {
+        let perm = my_perm();
+        let hash = MyHash::new(perm.clone());
+        let compress = MyCompress::new(perm.clone());
+        let challenge_mmcs = ChallengeMmcs::new(ValMmcs::new(hash, compress));
+        let num_queries = match std::env::var("FRI_QUERIES") {
+            Ok(value) => value.parse().unwrap(),
+            Err(_) => 100 / log_blowup,
+        };
+        FriConfig { log_blowup, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+    }
+
+    /// The FRI parameters for hiding proofs of AIRs with constraints of degree at most 3.
+    #[must_use]
+    pub fn zk_fri_config() -> FriConfig<ChallengeMmcs> {
         let perm = my_perm();
         let hash = MyHash::new(perm.clone());
         let compress = MyCompress::new(perm.clone());
         let challenge_mmcs = ChallengeMmcs::new(ValMmcs::new(hash, compress));
         let num_queries = match std::env::var("FRI_QUERIES") {
             Ok(value) => value.parse().unwrap(),
-            Err(_) => 33,
+            Err(_) => 50,
         };
-        FriConfig { log_blowup: 3, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+        FriConfig { log_blowup: 2, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
     }
 
     enum BabyBearPoseidon2Type {
         Default,
-        Compressed,
+        Compressed(usize),
+        Zk,
     }
 
     #[derive(Deserialize)]
@@ -237,6 +271,7 @@ pub mod baby_bear_poseidon2 {
         pub perm: Perm,
         pcs: Pcs,
         config_type: BabyBearPoseidon2Type,
+        zk: Option<ZkConfig>,
     }
 
     impl BabyBearPoseidon2 {
@@ -246,22 +281,45 @@ pub mod baby_bear_poseidon2 {
             let hash = MyHash::new(perm.clone());
             let compress = MyCompress::new(perm.clone());
             let val_mmcs = ValMmcs::new(hash, compress);
-            let dft = Dft {};
+            let dft = dft();
             let fri_config = default_fri_config();
             let pcs = Pcs::new(27, dft, val_mmcs, fri_config);
-            Self { pcs, perm, config_type: BabyBearPoseidon2Type::Default }
+            Self { pcs, perm, config_type: BabyBearPoseidon2Type::Default, zk: None }
         }
 
         #[must_use]
         pub fn compressed() -> Self {
+            Self::compressed_with_log_blowup(3)
+        }
+
+        /// A configuration for compressed proofs with a blowup of `2^log_blowup`, see
+        /// [compressed_fri_config_with_log_blowup].
+        #[must_use]
+        pub fn compressed_with_log_blowup(log_blowup: usize) -> Self {
             let perm = my_perm();
             let hash = MyHash::new(perm.clone());
             let compress = MyCompress::new(perm.clone());
             let val_mmcs = ValMmcs::new(hash, compress);
-            let dft = Dft {};
-            let fri_config = compressed_fri_config();
+            let dft = dft();
+            let fri_config = compressed_fri_config_with_log_blowup(log_blowup);
             let pcs = Pcs::new(27, dft, val_mmcs, fri_config);
-            Self { pcs, perm, config_type: BabyBearPoseidon2Type::Compressed }
+            Self { pcs, perm, config_type: BabyBearPoseidon2Type::Compressed(log_blowup), zk: None }
+        }
+
+        /// A configuration producing hiding shard proofs.
+        ///
+        /// The larger blowup leaves room for the quotient of the blinded traces.
+        #[must_use]
+        pub fn zk() -> Self {
+            let perm = my_perm();
+            let hash = MyHash::new(perm.clone());
+            let compress = MyCompress::new(perm.clone());
+            let val_mmcs = ValMmcs::new(hash, compress);
+            let dft = dft();
+            let fri_config = zk_fri_config();
+            let zk = ZkConfig::for_num_queries(fri_config.num_queries, 4);
+            let pcs = Pcs::new(27, dft, val_mmcs, fri_config);
+            Self { pcs, perm, config_type: BabyBearPoseidon2Type::Zk, zk: Some(zk) }
         }
     }
 
@@ -269,7 +327,10 @@ pub mod baby_bear_poseidon2 {
         fn clone(&self) -> Self {
             match self.config_type {
                 BabyBearPoseidon2Type::Default => Self::new(),
-                BabyBearPoseidon2Type::Compressed => Self::compressed(),
+                BabyBearPoseidon2Type::Compressed(log_blowup) => {
+                    Self::compressed_with_log_blowup(log_blowup)
+                }
+                BabyBearPoseidon2Type::Zk => Self::zk(),
             }
         }
     }
@@ -310,5 +371,9 @@ pub mod baby_bear_poseidon2 {
         fn challenger(&self) -> Self::Challenger {
             Challenger::new(self.perm.clone())
         }
+
+        fn zk(&self) -> Option<ZkConfig> {
+            self.zk
+        }
     }
 }
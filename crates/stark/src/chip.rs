@@ -1,9 +1,11 @@
-use std::hash::Hash;
+use std::{borrow::Borrow, hash::Hash};
 
-use p3_air::{Air, BaseAir, PairBuilder};
+use p3_air::{Air, AirBuilder, BaseAir, PairBuilder};
 use p3_field::{ExtensionField, Field, PrimeField, PrimeField32};
-use p3_matrix::dense::RowMajorMatrix;
-use p3_uni_stark::{get_max_constraint_degree, SymbolicAirBuilder};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_uni_stark::{
+    get_max_constraint_degree, get_symbolic_constraints, SymbolicAirBuilder, SymbolicExpression,
+};
 use p3_util::log2_ceil_usize;
 
 use crate::{
@@ -12,8 +14,8 @@ use crate::{
 };
 
 use super::{
-    eval_permutation_constraints, generate_permutation_trace, permutation_trace_width,
-    PROOF_MAX_NUM_PVS,
+    eval_permutation_constraints, generate_flattened_permutation_trace, generate_permutation_trace,
+    permutation_trace_width, PROOF_MAX_NUM_PVS,
 };
 
 /// An Air that encodes lookups based on interactions.
@@ -26,6 +28,8 @@ pub struct Chip<F: Field, A> {
     receives: Vec<Interaction<F>>,
     /// The relative log degree of the quotient polynomial, i.e. `log2(max_constraint_degree - 1)`.
     log_quotient_degree: usize,
+    /// Whether the multiplicity of every interaction is constrained to be boolean.
+    boolean_multiplicities: bool,
 }
 
 impl<F: Field, A> Chip<F, A> {
@@ -43,6 +47,28 @@ impl<F: Field, A> Chip<F, A> {
     pub const fn log_quotient_degree(&self) -> usize {
         self.log_quotient_degree
     }
+
+    /// The underlying AIR of the chip.
+    pub const fn air(&self) -> &A {
+        &self.air
+    }
+
+    /// Whether the multiplicity of every interaction is constrained to be boolean.
+    pub const fn boolean_multiplicities(&self) -> bool {
+        self.boolean_multiplicities
+    }
+
+    /// Constrains the multiplicity of every interaction of the chip to be zero or one.
+    ///
+    /// A multiplicity of zero or one can neither flip sign nor, since a trace has fewer rows than
+    /// the order of the field, add up to the order over the rows of a shard. This makes the faults
+    /// reported by [`check_multiplicities`](crate::check_multiplicities) impossible in the proof
+    /// itself, for chips whose interactions happen at most once per row.
+    #[must_use]
+    pub fn with_boolean_multiplicities(mut self) -> Self {
+        self.boolean_multiplicities = true;
+        self
+    }
 }
 
 impl<F: PrimeField32, A: MachineAir<F>> Chip<F, A> {
@@ -82,7 +108,15 @@ where
         }
         let log_quotient_degree = log2_ceil_usize(max_constraint_degree - 1);
 
-        Self { air, sends, receives, log_quotient_degree }
+        Self { air, sends, receives, log_quotient_degree, boolean_multiplicities: false }
+    }
+
+    /// Returns the symbolic constraints of the chip, not including those of its interactions.
+    pub fn symbolic_constraints(&self) -> Vec<SymbolicExpression<F>>
+    where
+        A: Air<SymbolicAirBuilder<F>>,
+    {
+        get_symbolic_constraints(&self.air, self.air.preprocessed_width(), PROOF_MAX_NUM_PVS)
     }
 
     /// Returns the number of interactions in the chip.
@@ -124,6 +158,30 @@ where
         )
     }
 
+    /// Generates a permutation trace for the given matrix, flattened to the base field and blinded,
+    /// along with its cumulative sum. See [`generate_flattened_permutation_trace`].
+    pub fn generate_flattened_permutation_trace<EF: ExtensionField<F>>(
+        &self,
+        preprocessed: Option<&RowMajorMatrix<F>>,
+        main: &RowMajorMatrix<F>,
+        random_elements: &[EF],
+        log_blinding_factor: usize,
+    ) -> (RowMajorMatrix<F>, EF)
+    where
+        F: PrimeField,
+    {
+        let batch_size = self.logup_batch_size();
+        generate_flattened_permutation_trace(
+            &self.sends,
+            &self.receives,
+            preprocessed,
+            main,
+            random_elements,
+            batch_size,
+            log_blinding_factor,
+        )
+    }
+
     /// Returns the width of the permutation trace.
     #[inline]
     pub fn permutation_width(&self) -> usize {
@@ -195,6 +253,10 @@ where
     fn included(&self, shard: &Self::Record) -> bool {
         self.air.included(shard)
     }
+
+    fn num_real_rows(&self, shard: &Self::Record) -> Option<usize> {
+        self.air.num_real_rows(shard)
+    }
 }
 
 // Implement AIR directly on Chip, evaluating both execution and permutation constraints.
@@ -210,6 +272,32 @@ where
         // Evaluate permutation constraints.
         let batch_size = self.logup_batch_size();
         eval_permutation_constraints(&self.sends, &self.receives, batch_size, builder);
+        // Evaluate the optional multiplicity constraints.
+        if self.boolean_multiplicities {
+            eval_boolean_multiplicities(&self.sends, &self.receives, builder);
+        }
+    }
+}
+
+/// Constrains the multiplicities of the given interactions to be boolean on every row.
+pub fn eval_boolean_multiplicities<F, AB>(
+    sends: &[Interaction<F>],
+    receives: &[Interaction<F>],
+    builder: &mut AB,
+) where
+    F: Field,
+    AB: AirBuilder<F = F> + PairBuilder,
+{
+    let preprocessed = builder.preprocessed();
+    let main = builder.main();
+    let preprocessed_local = preprocessed.row_slice(0);
+    let main_local = main.to_row_major_matrix();
+    let main_local = main_local.row_slice(0);
+    let main_local: &[AB::Var] = (*main_local).borrow();
+    for interaction in sends.iter().chain(receives) {
+        let multiplicity =
+            interaction.multiplicity.apply::<AB::Expr, AB::Var>(&preprocessed_local, main_local);
+        builder.assert_bool(multiplicity);
     }
 }
 
@@ -234,3 +322,75 @@ where
         self.air.hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Borrow;
+
+    use p3_baby_bear::BabyBear;
+
+    use super::*;
+    use crate::air::AirInteraction;
+
+    const NUM_COLS: usize = 2;
+
+    /// An AIR sending and receiving a value with the multiplicities of its columns.
+    struct CountingAir;
+
+    impl<F: Field> BaseAir<F> for CountingAir {
+        fn width(&self) -> usize {
+            NUM_COLS
+        }
+    }
+
+    impl<AB: SP1AirBuilder> Air<AB> for CountingAir {
+        fn eval(&self, builder: &mut AB) {
+            let main = builder.main();
+            let local = main.row_slice(0);
+            let local: &[AB::Var] = (*local).borrow();
+
+            builder.send(AirInteraction::new(
+                vec![local[1].into()],
+                local[0].into(),
+                InteractionKind::Alu,
+            ));
+            builder.receive(AirInteraction::new(
+                vec![local[0].into()],
+                local[1].into(),
+                InteractionKind::Byte,
+            ));
+        }
+    }
+
+    /// The multiplicity constraints of [`CountingAir`], on their own.
+    struct CountingMultiplicities {
+        sends: Vec<Interaction<BabyBear>>,
+        receives: Vec<Interaction<BabyBear>>,
+    }
+
+    impl BaseAir<BabyBear> for CountingMultiplicities {
+        fn width(&self) -> usize {
+            NUM_COLS
+        }
+    }
+
+    impl Air<SymbolicAirBuilder<BabyBear>> for CountingMultiplicities {
+        fn eval(&self, builder: &mut SymbolicAirBuilder<BabyBear>) {
+            eval_boolean_multiplicities(&self.sends, &self.receives, builder);
+        }
+    }
+
+    #[test]
+    fn test_boolean_multiplicities() {
+        let mut builder = InteractionBuilder::<BabyBear>::new(0, NUM_COLS);
+        CountingAir.eval(&mut builder);
+        let (sends, receives) = builder.interactions();
+        let air = CountingMultiplicities { sends, receives };
+
+        // Every interaction gets a quadratic constraint on its multiplicity.
+        let constraints = get_symbolic_constraints(&air, 0, 0);
+        assert_eq!(constraints.len(), 2);
+        assert!(constraints.iter().all(|constraint| constraint.degree_multiple() == 2));
+        assert_eq!(get_max_constraint_degree(&air, 0, 0), 2);
+    }
+}
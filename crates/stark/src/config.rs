@@ -5,6 +5,8 @@ use p3_commit::{Pcs, PolynomialSpace};
 use p3_field::{ExtensionField, Field, PrimeField};
 use serde::{de::DeserializeOwned, Serialize};
 
+use crate::ZkConfig;
+
 pub type Domain<SC> = <<SC as StarkGenericConfig>::Pcs as Pcs<
     <SC as StarkGenericConfig>::Challenge,
     <SC as StarkGenericConfig>::Challenger,
@@ -69,6 +71,11 @@ pub trait StarkGenericConfig: 'static + Send + Sync + Serialize + DeserializeOwn
 
     /// Initialize a new challenger.
     fn challenger(&self) -> Self::Challenger;
+
+    /// The parameters of the hiding mode, or `None` if shard proofs are not zero-knowledge.
+    fn zk(&self) -> Option<ZkConfig> {
+        None
+    }
 }
 
 pub struct UniConfig<SC>(pub SC);
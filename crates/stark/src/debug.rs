@@ -89,6 +89,8 @@ pub fn debug_constraints<SC, A>(
             is_last_row: Val::<SC>::zero(),
             is_transition: Val::<SC>::one(),
             public_values: &public_values,
+            panic_on_failure: true,
+            failed: false,
         };
         if i == 0 {
             builder.is_first_row = Val::<SC>::one();
@@ -109,6 +111,51 @@ pub fn debug_constraints<SC, A>(
     });
 }
 
+/// Whether the constraints of `air`, excluding those of the permutation argument, hold on row `i`
+/// of the given traces.
+pub fn constraints_hold_on_row<F, EF, A>(
+    air: &A,
+    preprocessed: Option<&RowMajorMatrix<F>>,
+    main: &RowMajorMatrix<F>,
+    i: usize,
+    public_values: &[F],
+) -> bool
+where
+    F: Field,
+    EF: ExtensionField<F>,
+    A: for<'a> Air<DebugConstraintBuilder<'a, F, EF>>,
+{
+    let height = main.height();
+    let i_next = (i + 1) % height;
+    let row = |trace: Option<&RowMajorMatrix<F>>, i: usize| {
+        trace.map(|trace| trace.row_slice(i).to_vec()).unwrap_or_default()
+    };
+    let (preprocessed_local, preprocessed_next) = (row(preprocessed, i), row(preprocessed, i_next));
+    let (main_local, main_next) = (row(Some(main), i), row(Some(main), i_next));
+
+    let mut builder = DebugConstraintBuilder {
+        preprocessed: VerticalPair::new(
+            RowMajorMatrixView::new_row(&preprocessed_local),
+            RowMajorMatrixView::new_row(&preprocessed_next),
+        ),
+        main: VerticalPair::new(
+            RowMajorMatrixView::new_row(&main_local),
+            RowMajorMatrixView::new_row(&main_next),
+        ),
+        perm: VerticalPair::new(RowMajorMatrixView::new_row(&[]), RowMajorMatrixView::new_row(&[])),
+        perm_challenges: &[],
+        cumulative_sum: EF::zero(),
+        is_first_row: if i == 0 { F::one() } else { F::zero() },
+        is_last_row: if i == height - 1 { F::one() } else { F::zero() },
+        is_transition: if i == height - 1 { F::zero() } else { F::one() },
+        public_values,
+        panic_on_failure: false,
+        failed: false,
+    };
+    air.eval(&mut builder);
+    !builder.failed
+}
+
 fn catch_unwind_silent<F: FnOnce() -> R + panic::UnwindSafe, R>(f: F) -> std::thread::Result<R> {
     let prev_hook = panic::take_hook();
     panic::set_hook(Box::new(|_| {}));
@@ -136,6 +183,9 @@ pub struct DebugConstraintBuilder<'a, F: Field, EF: ExtensionField<F>> {
     pub(crate) is_last_row: F,
     pub(crate) is_transition: F,
     pub(crate) public_values: &'a [F],
+    /// Whether a failed constraint panics, or only sets `failed`.
+    pub(crate) panic_on_failure: bool,
+    pub(crate) failed: bool,
 }
 
 impl<'a, F, EF> ExtensionBuilder for DebugConstraintBuilder<'a, F, EF>
@@ -151,7 +201,12 @@ where
     where
         I: Into<Self::ExprEF>,
     {
-        assert_eq!(x.into(), EF::zero(), "constraints must evaluate to zero");
+        let x = x.into();
+        if self.panic_on_failure {
+            assert_eq!(x, EF::zero(), "constraints must evaluate to zero");
+        } else if x != EF::zero() {
+            self.failed = true;
+        }
     }
 }
 
@@ -188,10 +243,13 @@ where
     F: Field,
     EF: ExtensionField<F>,
 {
-    #[allow(clippy::unused_self)]
     #[inline]
-    fn debug_constraint(&self, x: F, y: F) {
+    fn debug_constraint(&mut self, x: F, y: F) {
         if x != y {
+            if !self.panic_on_failure {
+                self.failed = true;
+                return;
+            }
             let backtrace = std::backtrace::Backtrace::force_capture();
             eprintln!("constraint failed: {x:?} != {y:?}\n{backtrace}");
             panic!();
@@ -245,6 +303,10 @@ where
     fn assert_bool<I: Into<Self::Expr>>(&mut self, x: I) {
         let x = x.into();
         if x != F::zero() && x != F::one() {
+            if !self.panic_on_failure {
+                self.failed = true;
+                return;
+            }
             let backtrace = std::backtrace::Backtrace::force_capture();
             eprintln!("constraint failed: {x:?} is not a bool\n{backtrace}");
             panic!();
@@ -0,0 +1,172 @@
+use std::sync::{Arc, RwLock};
+
+use p3_dft::TwoAdicSubgroupDft;
+use p3_field::{AbstractField, Field, TwoAdicField};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::*;
+
+/// A DFT that caches the powers of the coset shift, which are otherwise recomputed for every
+/// matrix of every commit.
+///
+/// The PCS extends each trace to a coset of a larger domain by interpolating it, multiplying the
+/// `i`-th coefficient by `shift^i`, and evaluating it. The powers of the shift are the same for
+/// every trace and every shard, so they are computed once, for the tallest trace seen so far, and
+/// shared between the clones of the DFT. Having them up front also lets the coefficients be scaled
+/// in parallel, in the same pass as the division by the height of the interpolation, and skips the
+/// zero coefficients of the extension.
+///
+/// The evaluations are those of the inner DFT, which computes the twiddle factors of the transforms
+/// themselves.
+#[derive(Debug, Clone)]
+pub struct CachedDft<F, D> {
+    inner: D,
+    shift_powers: Arc<RwLock<Option<ShiftPowers<F>>>>,
+}
+
+/// The powers of a coset shift, from `shift^0`.
+#[derive(Debug)]
+struct ShiftPowers<F> {
+    shift: F,
+    powers: Arc<Vec<F>>,
+}
+
+impl<F, D: Default> Default for CachedDft<F, D> {
+    fn default() -> Self {
+        Self { inner: D::default(), shift_powers: Arc::default() }
+    }
+}
+
+impl<F: Field, D> CachedDft<F, D> {
+    /// Wraps the DFT `inner`.
+    #[must_use]
+    pub fn new(inner: D) -> Self {
+        Self { inner, shift_powers: Arc::default() }
+    }
+
+    /// Computes the powers of `shift` for coefficient matrices of height up to `2^log_n`, such as
+    /// the extension of the tallest trace a prover expects, so that no commit has to.
+    pub fn precompute(&self, shift: F, log_n: usize) {
+        self.shift_powers(shift, 1 << log_n);
+    }
+
+    /// The first `n` or more powers of `shift`, computing them if they are not cached.
+    fn shift_powers(&self, shift: F, n: usize) -> Arc<Vec<F>> {
+        if let Some(cached) = self.shift_powers.read().unwrap().as_ref() {
+            if cached.shift == shift && cached.powers.len() >= n {
+                return cached.powers.clone();
+            }
+        }
+
+        let mut cached = self.shift_powers.write().unwrap();
+        match cached.as_ref() {
+            // Another thread may have computed the powers in the meantime.
+            Some(c) if c.shift == shift && c.powers.len() >= n => c.powers.clone(),
+            _ => {
+                let powers = Arc::new(shift.powers().take(n).collect::<Vec<_>>());
+                *cached = Some(ShiftPowers { shift, powers: powers.clone() });
+                powers
+            }
+        }
+    }
+}
+
+impl<F, D> TwoAdicSubgroupDft<F> for CachedDft<F, D>
+where
+    F: TwoAdicField,
+    D: TwoAdicSubgroupDft<F>,
+{
+    type Evaluations = D::Evaluations;
+
+    fn dft_batch(&self, mat: RowMajorMatrix<F>) -> Self::Evaluations {
+        self.inner.dft_batch(mat)
+    }
+
+    fn idft_batch(&self, mat: RowMajorMatrix<F>) -> RowMajorMatrix<F> {
+        self.inner.idft_batch(mat)
+    }
+
+    fn lde_batch(&self, mat: RowMajorMatrix<F>, added_bits: usize) -> Self::Evaluations {
+        self.inner.lde_batch(mat, added_bits)
+    }
+
+    fn coset_dft_batch(&self, mut mat: RowMajorMatrix<F>, shift: F) -> Self::Evaluations {
+        let powers = self.shift_powers(shift, mat.height());
+        scale_rows(&mut mat, |i| powers[i]);
+        self.inner.dft_batch(mat)
+    }
+
+    fn coset_lde_batch(
+        &self,
+        mat: RowMajorMatrix<F>,
+        added_bits: usize,
+        shift: F,
+    ) -> Self::Evaluations {
+        let h = mat.height();
+        let powers = self.shift_powers(shift, h);
+
+        // The coefficients are the evaluations of the inverse transform, divided by the height
+        // and in reverse order after the first one.
+        let mut coeffs = self.inner.dft_batch(mat).to_row_major_matrix();
+        let w = coeffs.width();
+        for i in 1..h / 2 {
+            let (low, high) = coeffs.values.split_at_mut((h - i) * w);
+            low[i * w..(i + 1) * w].swap_with_slice(&mut high[..w]);
+        }
+        let h_inv = F::from_canonical_usize(h).inverse();
+        scale_rows(&mut coeffs, |i| powers[i] * h_inv);
+
+        coeffs.values.resize(coeffs.values.len() << added_bits, F::zero());
+        self.inner.dft_batch(coeffs)
+    }
+}
+
+/// Multiplies the `i`-th row of `mat` by `weight(i)`, in parallel.
+fn scale_rows<F: Field>(mat: &mut RowMajorMatrix<F>, weight: impl Fn(usize) -> F + Sync) {
+    let w = mat.width();
+    if w == 0 {
+        return;
+    }
+    mat.values.par_chunks_mut(w).enumerate().for_each(|(i, row)| {
+        let weight = weight(i);
+        row.iter_mut().for_each(|coeff| *coeff *= weight);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+    use p3_dft::Radix2DitParallel;
+
+    use super::*;
+
+    fn matrix(height: usize, width: usize) -> RowMajorMatrix<BabyBear> {
+        let values = (0..height * width)
+            .map(|i| BabyBear::from_canonical_usize(i * 7919 + 3).exp_u64(5))
+            .collect();
+        RowMajorMatrix::new(values, width)
+    }
+
+    #[test]
+    fn test_cached_dft_matches_inner() {
+        let inner = Radix2DitParallel::default();
+        let dft = CachedDft::<BabyBear, _>::new(inner.clone());
+        let shift = BabyBear::generator();
+        for (log_h, width, added_bits) in [(0, 3, 1), (1, 1, 2), (4, 5, 1), (6, 2, 3), (3, 4, 0)] {
+            let mat = matrix(1 << log_h, width);
+            assert_eq!(
+                dft.coset_lde_batch(mat.clone(), added_bits, shift).to_row_major_matrix().values,
+                inner.coset_lde_batch(mat.clone(), added_bits, shift).to_row_major_matrix().values
+            );
+            assert_eq!(
+                dft.coset_dft_batch(mat.clone(), shift).to_row_major_matrix().values,
+                inner.coset_dft_batch(mat, shift).to_row_major_matrix().values
+            );
+        }
+
+        // The powers are kept for the tallest matrix, and shared with the clones.
+        let powers = dft.clone().shift_powers(shift, 1);
+        assert_eq!(powers.len(), 1 << 6);
+        assert!(Arc::ptr_eq(&powers, &dft.shift_powers(shift, 1 << 5)));
+        assert_eq!(dft.shift_powers(BabyBear::two(), 4)[3], BabyBear::from_canonical_u32(8));
+    }
+}
@@ -0,0 +1,342 @@
+//! Persistent dumps of shard traces for offline debugging.
+//!
+//! When [`SP1CoreOpts::dump_traces_dir`](crate::SP1CoreOpts::dump_traces_dir) is set, the prover
+//! writes one file per shard with every chip's preprocessed, main and permutation traces, the
+//! public values and the permutation challenges. These are all the inputs needed to re-evaluate
+//! the constraints of a shard, so a failing proof can be inspected with [`ShardTraceDump::load`]
+//! without rerunning the program.
+//!
+//! # Format
+//!
+//! All integers are little-endian `u32`s, and field elements are stored in canonical form.
+//!
+//! ```text
+//! magic            b"SP1TRACE"
+//! version          u32 (currently 1)
+//! shard            u32
+//! num_pv           u32, followed by `num_pv` public values
+//! ext_degree       u32, the degree `D` of the challenge field
+//! num_challenges   u32, followed by `num_challenges * D` base field coefficients
+//! num_chips        u32, followed by `num_chips` chip entries
+//!
+//! chip entry:
+//!   name_len       u32, followed by `name_len` bytes of UTF-8
+//!   main           matrix
+//!   has_prep       u32 (0 or 1), followed by a matrix if 1
+//!   permutation    matrix over the base field, `D` columns per extension column
+//!
+//! matrix:
+//!   width          u32
+//!   height         u32, followed by `width * height` values in row-major order
+//! ```
+
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use p3_challenger::FieldChallenger;
+use p3_field::{AbstractExtensionField, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+
+use crate::{air::MachineAir, StarkGenericConfig, StarkMachine, StarkProvingKey, Val};
+
+/// The magic bytes at the start of a trace dump.
+pub const TRACE_DUMP_MAGIC: [u8; 8] = *b"SP1TRACE";
+
+/// The version of the trace dump format.
+pub const TRACE_DUMP_VERSION: u32 = 1;
+
+/// A trace matrix loaded from a dump.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DumpedTrace {
+    /// The number of columns.
+    pub width: usize,
+    /// The values in row-major order.
+    pub values: Vec<u32>,
+}
+
+impl DumpedTrace {
+    fn from_matrix<F: PrimeField32>(matrix: &RowMajorMatrix<F>) -> Self {
+        Self {
+            width: matrix.width(),
+            values: matrix.values.iter().map(PrimeField32::as_canonical_u32).collect(),
+        }
+    }
+
+    /// The number of rows.
+    #[must_use]
+    pub fn height(&self) -> usize {
+        if self.width == 0 {
+            0
+        } else {
+            self.values.len() / self.width
+        }
+    }
+
+    /// The value at the given row and column.
+    #[must_use]
+    pub fn get(&self, row: usize, col: usize) -> Option<u32> {
+        if col < self.width {
+            self.values.get(row * self.width + col).copied()
+        } else {
+            None
+        }
+    }
+
+    /// The values of the given row.
+    #[must_use]
+    pub fn row(&self, row: usize) -> Option<&[u32]> {
+        self.values.get(row * self.width..(row + 1) * self.width)
+    }
+}
+
+/// The traces of a single chip in a shard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChipTraceDump {
+    /// The name of the chip.
+    pub name: String,
+    /// The main trace.
+    pub main: DumpedTrace,
+    /// The preprocessed trace, if the chip has one.
+    pub preprocessed: Option<DumpedTrace>,
+    /// The permutation trace, flattened to the base field.
+    pub permutation: DumpedTrace,
+}
+
+/// The traces, public values and challenges of a shard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShardTraceDump {
+    /// The index of the shard.
+    pub shard: u32,
+    /// The public values of the shard.
+    pub public_values: Vec<u32>,
+    /// The degree of the challenge field over the base field.
+    pub extension_degree: usize,
+    /// The permutation challenges, as base field coefficients.
+    pub permutation_challenges: Vec<Vec<u32>>,
+    /// The traces of each chip, in the order in which they were committed.
+    pub chips: Vec<ChipTraceDump>,
+}
+
+impl ShardTraceDump {
+    /// Collects the dump of a shard from its named main traces.
+    ///
+    /// The permutation challenges are sampled from a copy of `challenger`, which must be in the
+    /// same state as the one passed to [`MachineProver::open`](crate::MachineProver::open).
+    #[must_use]
+    pub fn new<SC: StarkGenericConfig, A: MachineAir<Val<SC>>>(
+        shard: u32,
+        machine: &StarkMachine<SC, A>,
+        pk: &StarkProvingKey<SC>,
+        traces: &[(String, RowMajorMatrix<Val<SC>>)],
+        public_values: &[Val<SC>],
+        challenger: &SC::Challenger,
+    ) -> Self
+    where
+        Val<SC>: PrimeField32,
+        SC::Challenger: Clone,
+    {
+        let mut challenger = challenger.clone();
+        let permutation_challenges: Vec<SC::Challenge> =
+            (0..2).map(|_| challenger.sample_ext_element()).collect();
+
+        let chips = traces
+            .iter()
+            .map(|(name, main)| {
+                let chip = machine.chips().iter().find(|chip| chip.name() == *name).unwrap();
+                let preprocessed = pk.chip_ordering.get(name).map(|&index| &pk.traces[index]);
+                let permutation =
+                    chip.generate_permutation_trace(preprocessed, main, &permutation_challenges);
+                ChipTraceDump {
+                    name: name.clone(),
+                    main: DumpedTrace::from_matrix(main),
+                    preprocessed: preprocessed.map(DumpedTrace::from_matrix),
+                    permutation: DumpedTrace::from_matrix(&permutation.flatten_to_base()),
+                }
+            })
+            .collect();
+
+        Self {
+            shard,
+            public_values: public_values.iter().map(PrimeField32::as_canonical_u32).collect(),
+            extension_degree: <SC::Challenge as AbstractExtensionField<Val<SC>>>::D,
+            permutation_challenges: permutation_challenges
+                .iter()
+                .map(|c| c.as_base_slice().iter().map(PrimeField32::as_canonical_u32).collect())
+                .collect(),
+            chips,
+        }
+    }
+
+    /// The traces of the chip with the given name.
+    #[must_use]
+    pub fn chip(&self, name: &str) -> Option<&ChipTraceDump> {
+        self.chips.iter().find(|chip| chip.name == name)
+    }
+
+    /// The coefficients of the permutation trace cell at the given row and extension column.
+    #[must_use]
+    pub fn permutation_cell(&self, chip: &str, row: usize, col: usize) -> Option<&[u32]> {
+        let d = self.extension_degree;
+        let row = self.chip(chip)?.permutation.row(row)?;
+        row.get(col * d..(col + 1) * d)
+    }
+
+    /// The path of the dump of the given shard inside `dir`.
+    #[must_use]
+    pub fn path(dir: &Path, shard: u32) -> PathBuf {
+        dir.join(format!("shard_{shard}.bin"))
+    }
+
+    /// Writes the dump to `dir`, returning the path of the file.
+    pub fn save(&self, dir: &Path) -> io::Result<PathBuf> {
+        std::fs::create_dir_all(dir)?;
+        let path = Self::path(dir, self.shard);
+        let mut writer = BufWriter::new(File::create(&path)?);
+        self.write(&mut writer)?;
+        writer.flush()?;
+        Ok(path)
+    }
+
+    /// Loads a dump from the given file.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::read(&mut BufReader::new(File::open(path)?))
+    }
+
+    /// Serializes the dump.
+    pub fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&TRACE_DUMP_MAGIC)?;
+        write_u32(writer, TRACE_DUMP_VERSION)?;
+        write_u32(writer, self.shard)?;
+        write_values(writer, &self.public_values)?;
+        write_u32(writer, self.extension_degree as u32)?;
+        write_u32(writer, self.permutation_challenges.len() as u32)?;
+        for challenge in &self.permutation_challenges {
+            for &coefficient in challenge {
+                write_u32(writer, coefficient)?;
+            }
+        }
+        write_u32(writer, self.chips.len() as u32)?;
+        for chip in &self.chips {
+            write_u32(writer, chip.name.len() as u32)?;
+            writer.write_all(chip.name.as_bytes())?;
+            write_trace(writer, &chip.main)?;
+            write_u32(writer, u32::from(chip.preprocessed.is_some()))?;
+            if let Some(preprocessed) = &chip.preprocessed {
+                write_trace(writer, preprocessed)?;
+            }
+            write_trace(writer, &chip.permutation)?;
+        }
+        Ok(())
+    }
+
+    /// Deserializes a dump.
+    pub fn read<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if magic != TRACE_DUMP_MAGIC {
+            return Err(invalid_data("not an SP1 trace dump"));
+        }
+        let version = read_u32(reader)?;
+        if version != TRACE_DUMP_VERSION {
+            return Err(invalid_data(format!("unsupported trace dump version {version}")));
+        }
+        let shard = read_u32(reader)?;
+        let public_values = read_values(reader)?;
+        let extension_degree = read_u32(reader)? as usize;
+        let num_challenges = read_u32(reader)? as usize;
+        let permutation_challenges = (0..num_challenges)
+            .map(|_| {
+                (0..extension_degree).map(|_| read_u32(reader)).collect::<io::Result<Vec<_>>>()
+            })
+            .collect::<io::Result<_>>()?;
+        let num_chips = read_u32(reader)? as usize;
+        let chips = (0..num_chips)
+            .map(|_| {
+                let mut name = vec![0u8; read_u32(reader)? as usize];
+                reader.read_exact(&mut name)?;
+                let name = String::from_utf8(name).map_err(invalid_data)?;
+                let main = read_trace(reader)?;
+                let preprocessed =
+                    if read_u32(reader)? == 1 { Some(read_trace(reader)?) } else { None };
+                let permutation = read_trace(reader)?;
+                Ok(ChipTraceDump { name, main, preprocessed, permutation })
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(Self { shard, public_values, extension_degree, permutation_challenges, chips })
+    }
+}
+
+fn invalid_data(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+fn write_u32<W: Write>(writer: &mut W, value: u32) -> io::Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn write_values<W: Write>(writer: &mut W, values: &[u32]) -> io::Result<()> {
+    write_u32(writer, values.len() as u32)?;
+    values.iter().try_for_each(|&value| write_u32(writer, value))
+}
+
+fn read_values<R: Read>(reader: &mut R) -> io::Result<Vec<u32>> {
+    let len = read_u32(reader)? as usize;
+    (0..len).map(|_| read_u32(reader)).collect()
+}
+
+fn write_trace<W: Write>(writer: &mut W, trace: &DumpedTrace) -> io::Result<()> {
+    write_u32(writer, trace.width as u32)?;
+    write_u32(writer, trace.height() as u32)?;
+    trace.values.iter().try_for_each(|&value| write_u32(writer, value))
+}
+
+fn read_trace<R: Read>(reader: &mut R) -> io::Result<DumpedTrace> {
+    let width = read_u32(reader)? as usize;
+    let height = read_u32(reader)? as usize;
+    let values = (0..width * height).map(|_| read_u32(reader)).collect::<io::Result<_>>()?;
+    Ok(DumpedTrace { width, values })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_dump_roundtrip() {
+        let dump = ShardTraceDump {
+            shard: 3,
+            public_values: vec![1, 2, 3],
+            extension_degree: 4,
+            permutation_challenges: vec![vec![5, 6, 7, 8], vec![9, 10, 11, 12]],
+            chips: vec![ChipTraceDump {
+                name: "CPU".to_string(),
+                main: DumpedTrace { width: 2, values: vec![1, 2, 3, 4] },
+                preprocessed: None,
+                permutation: DumpedTrace { width: 8, values: (0..16).collect() },
+            }],
+        };
+
+        let mut bytes = Vec::new();
+        dump.write(&mut bytes).unwrap();
+        let loaded = ShardTraceDump::read(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded, dump);
+
+        let cpu = loaded.chip("CPU").unwrap();
+        assert_eq!(cpu.main.height(), 2);
+        assert_eq!(cpu.main.get(1, 0), Some(3));
+        assert_eq!(cpu.main.get(0, 2), None);
+        assert_eq!(loaded.permutation_cell("CPU", 1, 1), Some(&[12, 13, 14, 15][..]));
+
+        bytes[0] = b'X';
+        assert!(ShardTraceDump::read(&mut bytes.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,185 @@
+//! JSON exports of shard proofs and verifying keys, for tooling that does not link this crate.
+//!
+//! The exports follow a versioned schema, [`EXPLORER_SCHEMA_VERSION`]. Field elements are written
+//! as their canonical `u32` values, extension field elements as the list of their base field
+//! coefficients, and commitments as the list of their digest elements:
+//!
+//! ```text
+//! verifying key:
+//! {
+//!   "schema_version": 1,
+//!   "commit": [u32; 8],
+//!   "pc_start": u32,
+//!   "chips": [{ "name": string, "log_degree": usize, "width": usize }]
+//! }
+//!
+//! shard proof:
+//! {
+//!   "schema_version": 1,
+//!   "commitments": { "main": [u32; 8], "permutation": [u32; 8], "quotient": [u32; 8] },
+//!   "chips": [{ "name": string, "index": usize, "log_degree": usize, "cumulative_sum": [u32] }],
+//!   "public_values": [u32]
+//! }
+//! ```
+//!
+//! The chips of a verifying key are its preprocessed chips, and the chips of a shard proof are
+//! listed in the order of its openings.
+
+use itertools::Itertools;
+use p3_field::{AbstractExtensionField, PrimeField32};
+use p3_util::log2_strict_usize;
+use serde::{Deserialize, Serialize};
+
+use crate::{Com, ShardProof, StarkGenericConfig, StarkVerifyingKey, Val, DIGEST_SIZE};
+
+/// The version of the schema of the JSON exports.
+pub const EXPLORER_SCHEMA_VERSION: u32 = 1;
+
+/// The JSON export of a [`StarkVerifyingKey`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyingKeyJson {
+    /// The version of the schema.
+    pub schema_version: u32,
+    /// The commitment to the preprocessed traces.
+    pub commit: Vec<u32>,
+    /// The start pc of the program.
+    pub pc_start: u32,
+    /// The preprocessed chips.
+    pub chips: Vec<PreprocessedChipJson>,
+}
+
+/// A preprocessed chip of a [`VerifyingKeyJson`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreprocessedChipJson {
+    /// The name of the chip.
+    pub name: String,
+    /// The log degree of the preprocessed trace.
+    pub log_degree: usize,
+    /// The width of the preprocessed trace.
+    pub width: usize,
+}
+
+/// The JSON export of a [`ShardProof`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardProofJson {
+    /// The version of the schema.
+    pub schema_version: u32,
+    /// The commitments of the shard.
+    pub commitments: ShardCommitmentJson,
+    /// The chips of the shard, in the order of the openings.
+    pub chips: Vec<ChipJson>,
+    /// The public values of the shard.
+    pub public_values: Vec<u32>,
+}
+
+/// The commitments of a [`ShardProofJson`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardCommitmentJson {
+    /// The commitment to the main traces.
+    pub main: Vec<u32>,
+    /// The commitment to the permutation traces.
+    pub permutation: Vec<u32>,
+    /// The commitment to the quotient chunks.
+    pub quotient: Vec<u32>,
+}
+
+/// A chip of a [`ShardProofJson`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChipJson {
+    /// The name of the chip.
+    pub name: String,
+    /// The index of the opening of the chip.
+    pub index: usize,
+    /// The log degree of the trace.
+    pub log_degree: usize,
+    /// The cumulative sum of the permutation trace.
+    pub cumulative_sum: Vec<u32>,
+}
+
+fn commitment_to_u32s<SC: StarkGenericConfig>(commit: &Com<SC>) -> Vec<u32>
+where
+    Val<SC>: PrimeField32,
+    Com<SC>: Into<[Val<SC>; DIGEST_SIZE]>,
+{
+    let digest: [Val<SC>; DIGEST_SIZE] = commit.clone().into();
+    digest.iter().map(PrimeField32::as_canonical_u32).collect()
+}
+
+impl<SC: StarkGenericConfig> StarkVerifyingKey<SC>
+where
+    Val<SC>: PrimeField32,
+    Com<SC>: Into<[Val<SC>; DIGEST_SIZE]>,
+{
+    /// The JSON export of this verifying key. See [the module documentation](self) for the schema.
+    #[must_use]
+    pub fn to_json_export(&self) -> VerifyingKeyJson {
+        let chips = self
+            .chip_information
+            .iter()
+            .map(|(name, _, dimensions)| PreprocessedChipJson {
+                name: name.clone(),
+                log_degree: log2_strict_usize(dimensions.height),
+                width: dimensions.width,
+            })
+            .collect();
+        VerifyingKeyJson {
+            schema_version: EXPLORER_SCHEMA_VERSION,
+            commit: commitment_to_u32s::<SC>(&self.commit),
+            pc_start: self.pc_start.as_canonical_u32(),
+            chips,
+        }
+    }
+
+    /// This verifying key as a JSON string. See [the module documentation](self) for the schema.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.to_json_export()).unwrap()
+    }
+}
+
+impl<SC: StarkGenericConfig> ShardProof<SC>
+where
+    Val<SC>: PrimeField32,
+    Com<SC>: Into<[Val<SC>; DIGEST_SIZE]>,
+{
+    /// The JSON export of this proof. See [the module documentation](self) for the schema.
+    #[must_use]
+    pub fn to_json_export(&self) -> ShardProofJson {
+        let chips = self
+            .chip_ordering
+            .iter()
+            .sorted_by_key(|(_, &index)| index)
+            .filter_map(|(name, &index)| {
+                let opening = self.opened_values.chips.get(index)?;
+                let cumulative_sum = opening
+                    .cumulative_sum
+                    .as_base_slice()
+                    .iter()
+                    .map(PrimeField32::as_canonical_u32)
+                    .collect();
+                Some(ChipJson {
+                    name: name.clone(),
+                    index,
+                    log_degree: opening.log_degree,
+                    cumulative_sum,
+                })
+            })
+            .collect();
+        ShardProofJson {
+            schema_version: EXPLORER_SCHEMA_VERSION,
+            commitments: ShardCommitmentJson {
+                main: commitment_to_u32s::<SC>(&self.commitment.main_commit),
+                permutation: commitment_to_u32s::<SC>(&self.commitment.permutation_commit),
+                quotient: commitment_to_u32s::<SC>(&self.commitment.quotient_commit),
+            },
+            chips,
+            public_values: self.public_values.iter().map(PrimeField32::as_canonical_u32).collect(),
+        }
+    }
+
+    /// This proof as a JSON string. See [the module documentation](self) for the schema.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.to_json_export()).unwrap()
+    }
+}
@@ -21,35 +21,57 @@
 #![warn(missing_docs)]
 
 pub mod air;
+mod bb31_blake3;
+mod bb31_keccak;
 mod bb31_poseidon2;
 mod chip;
 mod config;
 mod debug;
+mod dft;
+mod dump;
+mod explorer;
 mod folder;
 mod lookup;
 mod machine;
+mod machine_verifier;
+mod multiplicity;
 mod opts;
+mod padding;
 mod permutation;
 mod prover;
 mod quotient;
 mod record;
+mod shape;
 mod types;
 mod util;
-mod verifier;
+pub mod verifier;
+mod vk_diff;
 mod word;
+mod zk;
 
+pub use bb31_blake3::*;
+pub use bb31_keccak::*;
 pub use bb31_poseidon2::*;
 pub use chip::*;
 pub use config::*;
 pub use debug::*;
+pub use dft::*;
+pub use dump::*;
+pub use explorer::*;
 pub use folder::*;
 pub use lookup::*;
 pub use machine::*;
+pub use machine_verifier::*;
+pub use multiplicity::*;
 pub use opts::*;
+pub use padding::*;
 pub use permutation::*;
 pub use prover::*;
 pub use quotient::*;
 pub use record::*;
+pub use shape::*;
 pub use types::*;
 pub use verifier::*;
+pub use vk_diff::*;
 pub use word::*;
+pub use zk::*;
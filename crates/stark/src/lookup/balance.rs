@@ -0,0 +1,196 @@
+use std::{collections::BTreeMap, fmt};
+
+use p3_field::{Field, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+
+use super::{chip_traces, InteractionKind};
+use crate::{air::MachineAir, StarkGenericConfig, StarkMachine, StarkProvingKey, Val};
+
+/// An interaction whose sends and receives do not balance across all chips and shards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LookupImbalance {
+    /// The kind of the interaction.
+    pub kind: InteractionKind,
+    /// The values of the interaction, in canonical form.
+    pub values: Vec<u32>,
+    /// The multiplicity of the sends minus the multiplicity of the receives.
+    pub balance: i64,
+    /// The contribution of each chip to the balance, for the chips which send or receive it.
+    pub chips: BTreeMap<String, i64>,
+}
+
+impl fmt::Display for LookupImbalance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:?}: balance {}", self.kind, self.values, self.balance)?;
+        let chips = self.chips.iter().map(|(chip, count)| format!("{chip} {count:+}"));
+        write!(f, " ({})", chips.collect::<Vec<_>>().join(", "))
+    }
+}
+
+/// The interactions which do not balance after proving a set of shards. See [`LookupBalance`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnbalancedLookups(pub Vec<LookupImbalance>);
+
+impl fmt::Display for UnbalancedLookups {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} unbalanced interactions", self.0.len())?;
+        for imbalance in &self.0 {
+            write!(f, "\n  {imbalance}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for UnbalancedLookups {}
+
+/// The multiplicities of the interactions of a machine, summed over the main traces of its shards.
+///
+/// The sends and receives of every interaction must balance over all the shards of a proof, which
+/// the verifier only checks through the sum of the cumulative sums of the permutation traces. When
+/// [`SP1CoreOpts::debug_lookups`](crate::SP1CoreOpts::debug_lookups()) is set, the prover sums
+/// the multiplicities before committing to the permutation traces instead, so that an unbalanced
+/// interaction is reported with its kind, its values and the chips involved.
+///
+/// Multiplicities are counted as signed integers, so that sends and receives cannot wrap around
+/// the field and cancel by accident.
+#[derive(Debug, Clone, Default)]
+pub struct LookupBalance {
+    counts: BTreeMap<(InteractionKind, Vec<u32>), BTreeMap<String, i64>>,
+}
+
+impl LookupBalance {
+    /// Create an empty balance.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the interactions of the main traces of a shard.
+    pub fn add_shard<SC, A>(
+        &mut self,
+        machine: &StarkMachine<SC, A>,
+        pk: &StarkProvingKey<SC>,
+        traces: &[(String, RowMajorMatrix<Val<SC>>)],
+    ) where
+        SC: StarkGenericConfig,
+        Val<SC>: PrimeField32,
+        A: MachineAir<Val<SC>>,
+    {
+        for traces in chip_traces(machine, pk, traces) {
+            let name = traces.chip.name();
+            for row in 0..traces.main.height() {
+                traces.for_each_interaction(row, |interaction| {
+                    if interaction.multiplicity.is_zero() {
+                        return;
+                    }
+                    let values =
+                        interaction.values().map(|value| value.as_canonical_u32()).collect();
+                    let count = signed(interaction.multiplicity);
+                    let count = if interaction.is_send { count } else { -count };
+                    *self
+                        .counts
+                        .entry((interaction.kind, values))
+                        .or_default()
+                        .entry(name.clone())
+                        .or_default() += count;
+                });
+            }
+        }
+    }
+
+    /// Add the interactions counted by `other`.
+    pub fn merge(&mut self, other: Self) {
+        for (key, chips) in other.counts {
+            let entry = self.counts.entry(key).or_default();
+            for (chip, count) in chips {
+                *entry.entry(chip).or_default() += count;
+            }
+        }
+    }
+
+    /// The interactions which do not balance, ordered by kind and values.
+    #[must_use]
+    pub fn imbalances(&self) -> Vec<LookupImbalance> {
+        self.counts
+            .iter()
+            .filter_map(|((kind, values), chips)| {
+                let balance = chips.values().sum::<i64>();
+                (balance != 0).then(|| LookupImbalance {
+                    kind: *kind,
+                    values: values.clone(),
+                    balance,
+                    chips: chips
+                        .iter()
+                        .filter(|(_, c)| **c != 0)
+                        .map(|(n, c)| (n.clone(), *c))
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+
+    /// Check that every interaction balances.
+    ///
+    /// # Errors
+    ///
+    /// Returns the interactions which do not balance.
+    pub fn check(&self) -> Result<(), UnbalancedLookups> {
+        let imbalances = self.imbalances();
+        if imbalances.is_empty() {
+            Ok(())
+        } else {
+            Err(UnbalancedLookups(imbalances))
+        }
+    }
+}
+
+/// A multiplicity as a signed integer on the range `[-modulus/2, modulus/2]`.
+pub(crate) fn signed<F: PrimeField32>(x: F) -> i64 {
+    let value = i64::from(x.as_canonical_u32());
+    let order = i64::from(F::ORDER_U32);
+    if value > order / 2 {
+        value - order
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{LookupBalance, LookupImbalance};
+    use crate::lookup::InteractionKind;
+
+    #[test]
+    fn test_lookup_balance() {
+        let mut a = LookupBalance::new();
+        let mut b = LookupBalance::new();
+        let add = |balance: &mut LookupBalance, kind, values: &[u32], chip: &str, count| {
+            *balance
+                .counts
+                .entry((kind, values.to_vec()))
+                .or_default()
+                .entry(chip.to_string())
+                .or_default() += count;
+        };
+        add(&mut a, InteractionKind::Byte, &[1, 2], "Cpu", 2);
+        add(&mut b, InteractionKind::Byte, &[1, 2], "Byte", -2);
+        add(&mut a, InteractionKind::Memory, &[0, 4], "Cpu", 1);
+        add(&mut b, InteractionKind::Memory, &[0, 4], "MemoryInit", -1);
+        add(&mut b, InteractionKind::Alu, &[7], "AddSub", -1);
+
+        a.merge(b);
+        let imbalances = a.imbalances();
+        assert_eq!(
+            imbalances,
+            [LookupImbalance {
+                kind: InteractionKind::Alu,
+                values: vec![7],
+                balance: -1,
+                chips: BTreeMap::from([("AddSub".to_string(), -1)]),
+            }]
+        );
+        assert_eq!(a.check().unwrap_err().0, imbalances);
+    }
+}
@@ -255,6 +255,52 @@ mod tests {
         }
     }
 
+    pub struct CustomLookupTestAir;
+
+    impl<F: Field> BaseAir<F> for CustomLookupTestAir {
+        fn width(&self) -> usize {
+            NUM_COLS
+        }
+    }
+
+    impl<AB: SP1AirBuilder> Air<AB> for CustomLookupTestAir {
+        fn eval(&self, builder: &mut AB) {
+            let main = builder.main();
+            let local = main.row_slice(0);
+            let local: &[AB::Var] = (*local).borrow();
+
+            crate::send!(
+                builder,
+                InteractionKind::Custom(1),
+                [local[0], local[1] + local[2]],
+                local[2]
+            );
+            crate::receive!(builder, InteractionKind::Byte, [local[0]], AB::F::one());
+        }
+    }
+
+    #[test]
+    fn test_custom_interactions() {
+        let mut builder = InteractionBuilder::<BabyBear>::new(0, NUM_COLS);
+        CustomLookupTestAir.eval(&mut builder);
+        let (sends, receives) = builder.interactions();
+
+        assert_eq!(sends.len(), 1);
+        assert_eq!(sends[0].kind, InteractionKind::Custom(1));
+        assert_eq!(sends[0].values.len(), 2);
+        assert_eq!(sends[0].argument_index(), InteractionKind::FIRST_CUSTOM_ARGUMENT_INDEX + 1);
+        assert_eq!(receives.len(), 1);
+        assert_eq!(receives[0].kind, InteractionKind::Byte);
+
+        // Every kind has its own argument index.
+        let indices = InteractionKind::all_kinds()
+            .into_iter()
+            .chain((0..=u8::MAX).map(InteractionKind::Custom))
+            .map(|kind| kind.argument_index())
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(indices.len(), InteractionKind::all_kinds().len() + 256);
+    }
+
     #[test]
     fn test_lookup_interactions() {
         let air = LookupTestAir {};
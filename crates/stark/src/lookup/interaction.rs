@@ -14,34 +14,47 @@ pub struct Interaction<F: Field> {
 }
 
 /// The type of interaction for a lookup argument.
+///
+/// Chips defined outside of this crate use [`InteractionKind::Custom`] for the interactions they
+/// exchange among themselves, so that they take part in the lookup argument of a machine without
+/// adding a variant to this enum. They can also send and receive the built-in kinds, for example
+/// to request byte lookups.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum InteractionKind {
     /// Interaction with the memory table, such as read and write.
-    Memory = 1,
+    Memory,
 
     /// Interaction with the program table, loading an instruction at a given pc address.
-    Program = 2,
+    Program,
 
     /// Interaction with instruction oracle.
-    Instruction = 3,
+    Instruction,
 
     /// Interaction with the ALU operations.
-    Alu = 4,
+    Alu,
 
     /// Interaction with the byte lookup table for byte operations.
-    Byte = 5,
+    Byte,
 
     /// Requesting a range check for a given value and range.
-    Range = 6,
+    Range,
 
     /// Interaction with the field op table for field operations.
-    Field = 7,
+    Field,
 
     /// Interaction with a syscall.
-    Syscall = 8,
+    Syscall,
+
+    /// An interaction defined by chips outside of this crate. The identifier separates the
+    /// custom kinds of a machine: the sends and receives of a custom kind only balance each
+    /// other.
+    Custom(u8),
 }
 
 impl InteractionKind {
+    /// The argument index of the first custom kind, following those of the built-in kinds.
+    pub const FIRST_CUSTOM_ARGUMENT_INDEX: usize = 9;
+
     /// Returns all kinds of interactions.
     #[must_use]
     pub fn all_kinds() -> Vec<InteractionKind> {
@@ -56,6 +69,23 @@ impl InteractionKind {
             InteractionKind::Syscall,
         ]
     }
+
+    /// The index of the argument of this kind in the lookup table. Interactions of different
+    /// kinds are separated by their index, so no two kinds share one.
+    #[must_use]
+    pub const fn argument_index(&self) -> usize {
+        match self {
+            InteractionKind::Memory => 1,
+            InteractionKind::Program => 2,
+            InteractionKind::Instruction => 3,
+            InteractionKind::Alu => 4,
+            InteractionKind::Byte => 5,
+            InteractionKind::Range => 6,
+            InteractionKind::Field => 7,
+            InteractionKind::Syscall => 8,
+            InteractionKind::Custom(id) => Self::FIRST_CUSTOM_ARGUMENT_INDEX + *id as usize,
+        }
+    }
 }
 
 impl<F: Field> Interaction<F> {
@@ -70,7 +100,7 @@ impl<F: Field> Interaction<F> {
 
     /// The index of the argument in the lookup table.
     pub const fn argument_index(&self) -> usize {
-        self.kind as usize
+        self.kind.argument_index()
     }
 }
 
@@ -91,6 +121,7 @@ impl Display for InteractionKind {
             InteractionKind::Range => write!(f, "Range"),
             InteractionKind::Field => write!(f, "Field"),
             InteractionKind::Syscall => write!(f, "Syscall"),
+            InteractionKind::Custom(id) => write!(f, "Custom({id})"),
         }
     }
 }
@@ -1,7 +1,11 @@
+mod balance;
 mod builder;
 mod debug;
 mod interaction;
+mod rows;
 
+pub use balance::*;
 pub use builder::InteractionBuilder;
 pub use debug::*;
 pub use interaction::*;
+pub(crate) use rows::chip_traces;
@@ -0,0 +1,85 @@
+use p3_field::Field;
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+
+use super::{Interaction, InteractionKind};
+use crate::{air::MachineAir, MachineChip, StarkGenericConfig, StarkMachine, StarkProvingKey, Val};
+
+/// The main trace of a chip of a shard, with the preprocessed trace of the chip if it has one.
+pub(crate) struct ChipTraces<'a, SC: StarkGenericConfig, A> {
+    /// The chip.
+    pub chip: &'a MachineChip<SC, A>,
+    /// The preprocessed trace of the chip.
+    pub preprocessed: Option<&'a RowMajorMatrix<Val<SC>>>,
+    /// The main trace of the chip.
+    pub main: &'a RowMajorMatrix<Val<SC>>,
+}
+
+/// An interaction of a chip evaluated on a row of its traces.
+pub(crate) struct RowInteraction<'a, F: Field> {
+    /// The kind of the interaction.
+    pub kind: InteractionKind,
+    /// Whether the interaction is a send or a receive.
+    pub is_send: bool,
+    /// The index of the interaction among the sends or the receives of the chip.
+    pub index: usize,
+    /// The index of the interaction among the sends followed by the receives of the chip.
+    pub position: usize,
+    /// The multiplicity of the interaction on the row.
+    pub multiplicity: F,
+    interaction: &'a Interaction<F>,
+    preprocessed_row: &'a [F],
+    main_row: &'a [F],
+}
+
+impl<F: Field> RowInteraction<'_, F> {
+    /// The values of the interaction on the row.
+    pub fn values(&self) -> impl Iterator<Item = F> + '_ {
+        self.interaction
+            .values
+            .iter()
+            .map(|value| value.apply::<F, _>(self.preprocessed_row, self.main_row))
+    }
+}
+
+/// The main traces of a shard with their chips and preprocessed traces.
+///
+/// Traces of chips which are not in the machine are skipped.
+pub(crate) fn chip_traces<'a, SC, A>(
+    machine: &'a StarkMachine<SC, A>,
+    pk: &'a StarkProvingKey<SC>,
+    traces: &'a [(String, RowMajorMatrix<Val<SC>>)],
+) -> impl Iterator<Item = ChipTraces<'a, SC, A>>
+where
+    SC: StarkGenericConfig,
+    A: MachineAir<Val<SC>>,
+{
+    traces.iter().filter_map(move |(name, main)| {
+        let chip = machine.chips().iter().find(|chip| chip.name() == *name)?;
+        let preprocessed = pk.chip_ordering.get(name).map(|&index| &pk.traces[index]);
+        Some(ChipTraces { chip, preprocessed, main })
+    })
+}
+
+impl<SC: StarkGenericConfig, A> ChipTraces<'_, SC, A> {
+    /// Calls `f` on every interaction of the chip evaluated on `row`, the sends first.
+    pub fn for_each_interaction(&self, row: usize, mut f: impl FnMut(RowInteraction<'_, Val<SC>>)) {
+        let main_row = self.main.row_slice(row);
+        let preprocessed_row = self.preprocessed.map(|trace| trace.row_slice(row));
+        let preprocessed_row = preprocessed_row.as_deref().unwrap_or(&[]);
+        let num_sends = self.chip.sends().len();
+        let interactions = self.chip.sends().iter().chain(self.chip.receives());
+        for (position, interaction) in interactions.enumerate() {
+            let is_send = position < num_sends;
+            f(RowInteraction {
+                kind: interaction.kind,
+                is_send,
+                index: if is_send { position } else { position - num_sends },
+                position,
+                multiplicity: interaction.multiplicity.apply(preprocessed_row, &main_row),
+                interaction,
+                preprocessed_row,
+                main_row: &main_row,
+            });
+        }
+    }
+}
@@ -7,7 +7,7 @@ use p3_field::{AbstractExtensionField, AbstractField, Field, PrimeField32};
 use p3_matrix::{dense::RowMajorMatrix, Dimensions, Matrix};
 use p3_maybe_rayon::prelude::*;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::{cmp::Reverse, fmt::Debug, time::Instant};
+use std::{cmp::Reverse, fmt::Debug};
 use tracing::instrument;
 
 use super::{debug_constraints, Dom};
@@ -15,7 +15,7 @@ use crate::{
     air::{MachineAir, MachineProgram},
     lookup::{debug_interactions_with_all_chips, InteractionKind},
     record::MachineRecord,
-    DebugConstraintBuilder, ShardProof, VerifierConstraintFolder,
+    DebugConstraintBuilder, ProofShapeError, ShardInvariant, ShardProof, VerifierConstraintFolder,
 };
 
 use super::{
@@ -34,12 +34,65 @@ pub struct StarkMachine<SC: StarkGenericConfig, A> {
 
     /// The number of public values elements that the machine uses
     num_pv_elts: usize,
+
+    /// Field elements identifying the set of chips the machine was composed from, which are
+    /// observed before the chip presence of every shard. Empty for machines with a fixed set of
+    /// chips.
+    chip_set_id: Vec<Val<SC>>,
+
+    /// Groups of chips that prove the same operations in different layouts. Every shard uses
+    /// exactly one chip of each group, so the chips of a group may be omitted from a shard even if
+    /// they have preprocessed traces.
+    chip_alternatives: Vec<Vec<String>>,
 }
 
 impl<SC: StarkGenericConfig, A> StarkMachine<SC, A> {
     /// Creates a new [`StarkMachine`].
     pub const fn new(config: SC, chips: Vec<Chip<Val<SC>, A>>, num_pv_elts: usize) -> Self {
-        Self { config, chips, num_pv_elts }
+        Self { config, chips, num_pv_elts, chip_set_id: Vec::new(), chip_alternatives: Vec::new() }
+    }
+
+    /// Sets the field elements identifying the set of chips of the machine.
+    ///
+    /// Machines whose chips are chosen at construction time should set an identifier, so that a
+    /// shard proven with one set of chips does not verify against a machine with another.
+    #[must_use]
+    pub fn with_chip_set_id(mut self, chip_set_id: Vec<Val<SC>>) -> Self {
+        self.chip_set_id = chip_set_id;
+        self
+    }
+
+    /// The field elements identifying the set of chips of the machine.
+    pub fn chip_set_id(&self) -> &[Val<SC>] {
+        &self.chip_set_id
+    }
+
+    /// Declares groups of chips, by name, of which every shard uses exactly one.
+    ///
+    /// The chips of a group should decide in [`MachineAir::included`] which of them proves a
+    /// shard, for example by comparing the area of their traces.
+    #[must_use]
+    pub fn with_chip_alternatives(mut self, chip_alternatives: Vec<Vec<String>>) -> Self {
+        self.chip_alternatives = chip_alternatives;
+        self
+    }
+
+    /// The groups of chips of which every shard uses exactly one.
+    pub fn chip_alternatives(&self) -> &[Vec<String>] {
+        &self.chip_alternatives
+    }
+
+    /// Whether the chip with the given name belongs to a group of alternatives.
+    pub fn is_alternative_chip(&self, name: &str) -> bool {
+        self.chip_alternatives.iter().flatten().any(|alternative| alternative == name)
+    }
+
+    /// Whether a shard with the given chip ordering uses exactly one chip of every group of
+    /// alternatives.
+    pub fn uses_one_alternative(&self, chip_ordering: &HashMap<String, usize>) -> bool {
+        self.chip_alternatives.iter().all(|group| {
+            group.iter().filter(|name| chip_ordering.contains_key(name.as_str())).count() == 1
+        })
     }
 }
 
@@ -148,6 +201,15 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> StarkMachine<SC, A> {
         self.chips().iter().map(|chip| proof.chip_ordering.get(&chip.name()).copied()).collect()
     }
 
+    /// Returns, for each chip of the machine, whether a shard with the given chip ordering has
+    /// opened values for it.
+    ///
+    /// Chips without events in a shard are omitted from its proof. This bitmap is observed by the
+    /// prover and the verifier before sampling the permutation challenges of the shard.
+    pub fn chip_presence(&self, chip_ordering: &HashMap<String, usize>) -> Vec<bool> {
+        self.chips.iter().map(|chip| chip_ordering.contains_key(&chip.name())).collect()
+    }
+
     /// The setup preprocessing phase.
     ///
     /// Given a program, this function generates the proving and verifying keys. The keys correspond
@@ -162,14 +224,17 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> StarkMachine<SC, A> {
                 .par_iter()
                 .filter_map(|chip| {
                     let chip_name = chip.name();
-                    let begin = Instant::now();
-                    let prep_trace = chip.generate_preprocessed_trace(program);
-                    tracing::debug!(
+                    let span = tracing::debug_span!(
                         parent: &parent_span,
-                        "generated preprocessed trace for chip {} in {:?}",
-                        chip_name,
-                        begin.elapsed()
-                    );
+                        "generate preprocessed trace",
+                        chip = %chip_name,
+                        rows = tracing::field::Empty
+                    )
+                    .entered();
+                    let prep_trace = chip.generate_preprocessed_trace(program);
+                    if let Some(trace) = &prep_trace {
+                        span.record("rows", trace.height());
+                    }
                     // Assert that the chip width data is correct.
                     let expected_width = prep_trace.as_ref().map(|t| t.width()).unwrap_or(0);
                     assert_eq!(
@@ -262,6 +327,12 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> StarkMachine<SC, A> {
         SC::Challenger: Clone,
         A: for<'a> Air<VerifierConstraintFolder<'a, SC>>,
     {
+        // Check that every shard can be verified with this verifying key.
+        for (i, shard_proof) in proof.shard_proofs.iter().enumerate() {
+            self.check_shape(vk, &shard_proof.shape())
+                .map_err(|e| MachineVerificationError::IncompatibleShape(i, e))?;
+        }
+
         // Observe the preprocessed commitment.
         vk.observe_into(challenger);
         tracing::debug_span!("observe challenges for all shards").in_scope(|| {
@@ -279,16 +350,8 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> StarkMachine<SC, A> {
         tracing::debug_span!("verify shard proofs").in_scope(|| {
             for (i, shard_proof) in proof.shard_proofs.iter().enumerate() {
                 tracing::debug_span!("verifying shard", shard = i).in_scope(|| {
-                    let chips =
-                        self.shard_chips_ordered(&shard_proof.chip_ordering).collect::<Vec<_>>();
-                    Verifier::verify_shard(
-                        &self.config,
-                        vk,
-                        &chips,
-                        &mut challenger.clone(),
-                        shard_proof,
-                    )
-                    .map_err(MachineVerificationError::InvalidShardProof)
+                    Verifier::verify_shard(self, vk, &mut challenger.clone(), shard_proof)
+                        .map_err(MachineVerificationError::InvalidShardProof)
                 })?;
             }
 
@@ -405,12 +468,16 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> StarkMachine<SC, A> {
 
         // If the cumulative sum is not zero, debug the interactions.
         if !cumulative_sum.is_zero() {
-            debug_interactions_with_all_chips::<SC, A>(
-                self,
-                pk,
-                &records,
-                InteractionKind::all_kinds(),
+            let mut interaction_kinds = InteractionKind::all_kinds();
+            interaction_kinds.extend(
+                self.chips()
+                    .iter()
+                    .flat_map(|chip| chip.sends().iter().chain(chip.receives()))
+                    .map(|interaction| interaction.kind)
+                    .filter(|kind| matches!(kind, InteractionKind::Custom(_)))
+                    .unique(),
             );
+            debug_interactions_with_all_chips::<SC, A>(self, pk, &records, interaction_kinds);
             panic!("Cumulative sum is not zero");
         }
     }
@@ -436,10 +503,10 @@ pub enum MachineVerificationError<SC: StarkGenericConfig> {
     TooManyShards,
     /// The chip occurrence is invalid.
     InvalidChipOccurence(String),
-    /// The CPU is missing in the first shard.
-    MissingCpuInFirstShard,
-    /// The CPU log degree is too large.
-    CpuLogDegreeTooLarge(usize),
+    /// A cross-shard invariant is violated at the given shard index.
+    ShardInvariantViolated(usize, ShardInvariant),
+    /// The shape of the shard proof at the given index is incompatible with the verifying key.
+    IncompatibleShape(usize, ProofShapeError),
 }
 
 impl<SC: StarkGenericConfig> Debug for MachineVerificationError<SC> {
@@ -473,11 +540,11 @@ impl<SC: StarkGenericConfig> Debug for MachineVerificationError<SC> {
             MachineVerificationError::InvalidChipOccurence(s) => {
                 write!(f, "Invalid chip occurence: {}", s)
             }
-            MachineVerificationError::MissingCpuInFirstShard => {
-                write!(f, "Missing CPU in first shard")
+            MachineVerificationError::ShardInvariantViolated(shard, invariant) => {
+                write!(f, "Shard invariant violated at shard {}: {}", shard, invariant)
             }
-            MachineVerificationError::CpuLogDegreeTooLarge(log_degree) => {
-                write!(f, "CPU log degree too large: {}", log_degree)
+            MachineVerificationError::IncompatibleShape(shard, e) => {
+                write!(f, "Shard {} cannot be verified with this key: {}", shard, e)
             }
         }
     }
@@ -0,0 +1,286 @@
+use std::fmt::Display;
+
+use p3_air::Air;
+use p3_challenger::CanObserve;
+use p3_field::AbstractField;
+use sp1_primitives::consts::WORD_SIZE;
+
+use crate::{
+    air::{MachineAir, POSEIDON_NUM_WORDS, PUBLIC_VALUES_LAYOUT, PV_DIGEST_NUM_WORDS},
+    MachineVerificationError, ShardProof, StarkGenericConfig, StarkMachine, StarkVerifyingKey, Val,
+    Verifier, VerifierConstraintFolder, Word,
+};
+
+/// The maximum number of shards a full proof may contain.
+pub const MAX_NUM_SHARDS: usize = 1 << 16;
+
+/// A cross-shard invariant that a full proof must satisfy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShardInvariant {
+    /// The public values cover every field of the public values layout.
+    PublicValuesLayout,
+    /// The first shard must contain a "CPU" chip.
+    CpuInFirstShard,
+    /// The "CPU" log degree must not exceed the configured maximum.
+    CpuLogDegree,
+    /// The shard index starts at one and increments by one for each shard.
+    ShardNumbering,
+    /// The execution shard index starts at one and increments by one for each "CPU" shard.
+    ExecutionShardNumbering,
+    /// The `start_pc` of the first shard equals `vk.pc_start`.
+    StartPc,
+    /// The `start_pc` of each shard equals the `next_pc` of the previous shard.
+    PcChaining,
+    /// Shards without a "CPU" chip do not change the program counter.
+    NonCpuPcUnchanged,
+    /// Shards with a "CPU" chip never start at the halted state.
+    CpuStartNotHalted,
+    /// The `next_pc` of the last shard is zero.
+    Halted,
+    /// The exit code is zero in every shard.
+    ExitCode,
+    /// `previous_init_addr_bits` equals `last_init_addr_bits` of the previous shard.
+    InitAddrBitsChaining,
+    /// `previous_finalize_addr_bits` equals `last_finalize_addr_bits` of the previous shard.
+    FinalizeAddrBitsChaining,
+    /// Shards without "MemoryInit" do not change the initialized address bits.
+    InitAddrBitsUnchanged,
+    /// Shards without "MemoryFinalize" do not change the finalized address bits.
+    FinalizeAddrBitsUnchanged,
+    /// Once non-zero, `committed_value_digest` never changes, and only "CPU" shards may set it.
+    CommittedValueDigest,
+    /// Once non-zero, `deferred_proofs_digest` never changes, and only "CPU" shards may set it.
+    DeferredProofsDigest,
+}
+
+impl Display for ShardInvariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            ShardInvariant::PublicValuesLayout => "public values are shorter than the layout",
+            ShardInvariant::CpuInFirstShard => "the first shard should contain a cpu",
+            ShardInvariant::CpuLogDegree => "cpu log degree should not exceed the maximum",
+            ShardInvariant::ShardNumbering => {
+                "shard index should be the previous shard index + 1 and start at 1"
+            }
+            ShardInvariant::ExecutionShardNumbering => {
+                "execution shard index should be the previous execution shard index + 1 if cpu \
+                 exists and start at 1"
+            }
+            ShardInvariant::StartPc => "start_pc != vk.start_pc",
+            ShardInvariant::PcChaining => "start_pc != next_pc_prev",
+            ShardInvariant::NonCpuPcUnchanged => "start_pc != next_pc for non-cpu shard",
+            ShardInvariant::CpuStartNotHalted => "start_pc == 0 for cpu shard",
+            ShardInvariant::Halted => "next_pc != 0 in the last shard",
+            ShardInvariant::ExitCode => "exit_code != 0",
+            ShardInvariant::InitAddrBitsChaining => {
+                "previous_init_addr_bits != last_init_addr_bits_prev"
+            }
+            ShardInvariant::FinalizeAddrBitsChaining => {
+                "previous_finalize_addr_bits != last_finalize_addr_bits_prev"
+            }
+            ShardInvariant::InitAddrBitsUnchanged => {
+                "previous_init_addr_bits != last_init_addr_bits without memory init"
+            }
+            ShardInvariant::FinalizeAddrBitsUnchanged => {
+                "previous_finalize_addr_bits != last_finalize_addr_bits without memory finalize"
+            }
+            ShardInvariant::CommittedValueDigest => {
+                "committed_value_digest != committed_value_digest_prev"
+            }
+            ShardInvariant::DeferredProofsDigest => {
+                "deferred_proofs_digest != deferred_proofs_digest_prev"
+            }
+        };
+        write!(f, "{msg}")
+    }
+}
+
+/// Verifies a full sequence of shard proofs produced for a single execution.
+///
+/// In addition to verifying each shard proof, the verifier checks the invariants that tie
+/// consecutive shards together, see [`ShardInvariant`].
+pub struct MachineVerifier<'a, SC: StarkGenericConfig, A> {
+    machine: &'a StarkMachine<SC, A>,
+    max_cpu_log_degree: usize,
+}
+
+/// The running state of the cross-shard invariants.
+struct InvariantState<F> {
+    shard: F,
+    execution_shard: F,
+    next_pc: F,
+    last_init_addr_bits: [F; 32],
+    last_finalize_addr_bits: [F; 32],
+    committed_value_digest: [Word<F>; PV_DIGEST_NUM_WORDS],
+    deferred_proofs_digest: [F; POSEIDON_NUM_WORDS],
+}
+
+impl<F: AbstractField + Copy> InvariantState<F> {
+    fn new(pc_start: F) -> Self {
+        Self {
+            shard: F::zero(),
+            execution_shard: F::zero(),
+            next_pc: pc_start,
+            last_init_addr_bits: [F::zero(); 32],
+            last_finalize_addr_bits: [F::zero(); 32],
+            committed_value_digest: [Word([F::zero(); WORD_SIZE]); PV_DIGEST_NUM_WORDS],
+            deferred_proofs_digest: [F::zero(); POSEIDON_NUM_WORDS],
+        }
+    }
+}
+
+impl<'a, SC: StarkGenericConfig, A: MachineAir<Val<SC>>> MachineVerifier<'a, SC, A> {
+    /// Creates a new [`MachineVerifier`] that bounds the "CPU" log degree by `max_cpu_log_degree`.
+    pub const fn new(machine: &'a StarkMachine<SC, A>, max_cpu_log_degree: usize) -> Self {
+        Self { machine, max_cpu_log_degree }
+    }
+
+    /// Verifies every shard proof together with the cross-shard invariants, and that the
+    /// cumulative sums of all shards add up to zero.
+    pub fn verify_full_proof(
+        &self,
+        vk: &StarkVerifyingKey<SC>,
+        shard_proofs: &[ShardProof<SC>],
+    ) -> Result<(), MachineVerificationError<SC>>
+    where
+        SC::Challenger: Clone,
+        A: for<'b> Air<VerifierConstraintFolder<'b, SC>>,
+    {
+        if shard_proofs.is_empty() {
+            return Err(MachineVerificationError::EmptyProof);
+        }
+        if shard_proofs.len() > MAX_NUM_SHARDS {
+            return Err(MachineVerificationError::TooManyShards);
+        }
+
+        for (i, shard_proof) in shard_proofs.iter().enumerate() {
+            self.machine
+                .check_shape(vk, &shard_proof.shape())
+                .map_err(|e| MachineVerificationError::IncompatibleShape(i, e))?;
+        }
+
+        // Observe the preprocessed commitment and the commitments of all shards before sampling
+        // any challenges.
+        let mut challenger = self.machine.config().challenger();
+        vk.observe_into(&mut challenger);
+        for shard_proof in shard_proofs.iter() {
+            challenger.observe(shard_proof.commitment.main_commit.clone());
+            challenger.observe_slice(&shard_proof.public_values[0..self.machine.num_pv_elts()]);
+        }
+
+        let mut state = InvariantState::new(vk.pc_start);
+        let mut cumulative_sum = SC::Challenge::zero();
+        for (i, shard_proof) in shard_proofs.iter().enumerate() {
+            let is_last = i == shard_proofs.len() - 1;
+            self.check_invariants(i, is_last, shard_proof, &mut state).map_err(|invariant| {
+                MachineVerificationError::ShardInvariantViolated(i, invariant)
+            })?;
+
+            tracing::debug_span!("verifying shard", shard = i).in_scope(|| {
+                Verifier::verify_shard(self.machine, vk, &mut challenger.clone(), shard_proof)
+                    .map_err(MachineVerificationError::InvalidShardProof)
+            })?;
+
+            cumulative_sum += shard_proof.cumulative_sum();
+        }
+
+        if !cumulative_sum.is_zero() {
+            return Err(MachineVerificationError::NonZeroCumulativeSum);
+        }
+
+        Ok(())
+    }
+
+    /// Checks the invariants of the `i`-th shard against the running state and advances it.
+    fn check_invariants(
+        &self,
+        i: usize,
+        is_last: bool,
+        shard_proof: &ShardProof<SC>,
+        state: &mut InvariantState<Val<SC>>,
+    ) -> Result<(), ShardInvariant> {
+        let public_values = PUBLIC_VALUES_LAYOUT
+            .view(&shard_proof.public_values)
+            .ok_or(ShardInvariant::PublicValuesLayout)?;
+        let contains_cpu = shard_proof.contains_cpu();
+        let zero = Val::<SC>::zero();
+
+        // Assert that the first shard has a "CPU", and that the CPU log degree does not exceed
+        // the maximum so that the lookup argument's multiplicities do not overflow.
+        if i == 0 && !contains_cpu {
+            return Err(ShardInvariant::CpuInFirstShard);
+        }
+        if contains_cpu && shard_proof.log_degree_cpu() > self.max_cpu_log_degree {
+            return Err(ShardInvariant::CpuLogDegree);
+        }
+
+        // Shard and execution shard numbering.
+        state.shard += Val::<SC>::one();
+        if public_values.shard != state.shard {
+            return Err(ShardInvariant::ShardNumbering);
+        }
+        if contains_cpu {
+            state.execution_shard += Val::<SC>::one();
+            if public_values.execution_shard != state.execution_shard {
+                return Err(ShardInvariant::ExecutionShardNumbering);
+            }
+        }
+
+        // Program counter chaining. The running `next_pc` is seeded with `vk.pc_start`.
+        if public_values.start_pc != state.next_pc {
+            return Err(if i == 0 { ShardInvariant::StartPc } else { ShardInvariant::PcChaining });
+        }
+        if !contains_cpu && public_values.start_pc != public_values.next_pc {
+            return Err(ShardInvariant::NonCpuPcUnchanged);
+        }
+        if contains_cpu && public_values.start_pc == zero {
+            return Err(ShardInvariant::CpuStartNotHalted);
+        }
+        if is_last && public_values.next_pc != zero {
+            return Err(ShardInvariant::Halted);
+        }
+        if public_values.exit_code != zero {
+            return Err(ShardInvariant::ExitCode);
+        }
+
+        // Memory initialization and finalization address bits.
+        if public_values.previous_init_addr_bits != state.last_init_addr_bits {
+            return Err(ShardInvariant::InitAddrBitsChaining);
+        }
+        if public_values.previous_finalize_addr_bits != state.last_finalize_addr_bits {
+            return Err(ShardInvariant::FinalizeAddrBitsChaining);
+        }
+        if !shard_proof.contains_memory_init()
+            && public_values.previous_init_addr_bits != public_values.last_init_addr_bits
+        {
+            return Err(ShardInvariant::InitAddrBitsUnchanged);
+        }
+        if !shard_proof.contains_memory_finalize()
+            && public_values.previous_finalize_addr_bits != public_values.last_finalize_addr_bits
+        {
+            return Err(ShardInvariant::FinalizeAddrBitsUnchanged);
+        }
+
+        // Digests: once set they never change, and only "CPU" shards may set them.
+        let zero_committed_value_digest = [Word([zero; WORD_SIZE]); PV_DIGEST_NUM_WORDS];
+        let zero_deferred_proofs_digest = [zero; POSEIDON_NUM_WORDS];
+        if (state.committed_value_digest != zero_committed_value_digest || !contains_cpu)
+            && public_values.committed_value_digest != state.committed_value_digest
+        {
+            return Err(ShardInvariant::CommittedValueDigest);
+        }
+        if (state.deferred_proofs_digest != zero_deferred_proofs_digest || !contains_cpu)
+            && public_values.deferred_proofs_digest != state.deferred_proofs_digest
+        {
+            return Err(ShardInvariant::DeferredProofsDigest);
+        }
+
+        state.next_pc = public_values.next_pc;
+        state.last_init_addr_bits = public_values.last_init_addr_bits;
+        state.last_finalize_addr_bits = public_values.last_finalize_addr_bits;
+        state.committed_value_digest = public_values.committed_value_digest;
+        state.deferred_proofs_digest = public_values.deferred_proofs_digest;
+
+        Ok(())
+    }
+}
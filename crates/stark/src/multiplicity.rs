@@ -0,0 +1,239 @@
+use std::fmt::{Display, Formatter};
+
+use p3_field::PrimeField32;
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+
+use crate::{
+    air::MachineAir,
+    lookup::{chip_traces, signed},
+    InteractionKind, StarkGenericConfig, StarkMachine, StarkProvingKey, Val,
+};
+
+/// What is wrong with the multiplicities of an interaction. See [`MultiplicityIssue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiplicityFault {
+    /// The multiplicity has the opposite sign of the first nonzero multiplicity of the
+    /// interaction, such as a negative send in a chip whose sends are counts.
+    SignFlip {
+        /// The first nonzero multiplicity of the interaction.
+        expected: i64,
+        /// The multiplicity on the row.
+        found: i64,
+    },
+    /// The sum of the absolute multiplicities of the interaction reaches the order of the field on
+    /// the row, so that the multiplicities of a single value may wrap around to zero.
+    Overflow {
+        /// The sum of the absolute multiplicities up to and including the row.
+        total: u64,
+    },
+}
+
+/// An interaction of a chip whose multiplicities are suspicious.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiplicityIssue {
+    /// The name of the chip.
+    pub chip: String,
+    /// Whether the interaction is a send or a receive.
+    pub is_send: bool,
+    /// The index of the interaction among the sends or the receives of the chip.
+    pub index: usize,
+    /// The kind of the interaction.
+    pub kind: InteractionKind,
+    /// The first faulty row.
+    pub row: usize,
+    /// The number of faulty rows.
+    pub rows: usize,
+    /// What is wrong with the multiplicities.
+    pub fault: MultiplicityFault,
+}
+
+impl Display for MultiplicityIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let side = if self.is_send { "send" } else { "receive" };
+        write!(f, "{}: {side} {} interaction #{} ", self.chip, self.kind, self.index)?;
+        match self.fault {
+            MultiplicityFault::SignFlip { expected, found } => write!(
+                f,
+                "has multiplicity {found} on {} rows from row {}, after {expected}",
+                self.rows, self.row
+            ),
+            MultiplicityFault::Overflow { total } => {
+                write!(f, "reaches a total multiplicity of {total} on row {}", self.row)
+            }
+        }
+    }
+}
+
+/// The multiplicity issues found in a set of shards. See [`check_multiplicities`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadMultiplicities(pub Vec<MultiplicityIssue>);
+
+impl Display for BadMultiplicities {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} interactions with bad multiplicities", self.0.len())?;
+        for issue in &self.0 {
+            write!(f, "\n  {issue}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BadMultiplicities {}
+
+/// Tracks the multiplicities of one interaction over the rows of a trace.
+#[derive(Debug, Clone, Default)]
+struct MultiplicityTracker {
+    /// The first nonzero multiplicity.
+    first: Option<i64>,
+    /// The sum of the absolute multiplicities so far.
+    total: u64,
+    /// The first row, the number of rows and the fault of the sign flips.
+    sign_flips: Option<(usize, usize, MultiplicityFault)>,
+    /// The row and the fault of the overflow.
+    overflow: Option<(usize, MultiplicityFault)>,
+}
+
+impl MultiplicityTracker {
+    /// Add the multiplicity of the next row, given as a signed integer.
+    fn add(&mut self, row: usize, multiplicity: i64, order: u64) {
+        if multiplicity == 0 {
+            return;
+        }
+        let expected = *self.first.get_or_insert(multiplicity);
+        if expected.signum() != multiplicity.signum() {
+            match &mut self.sign_flips {
+                Some((_, rows, _)) => *rows += 1,
+                None => {
+                    let fault = MultiplicityFault::SignFlip { expected, found: multiplicity };
+                    self.sign_flips = Some((row, 1, fault));
+                }
+            }
+        }
+        self.total += multiplicity.unsigned_abs();
+        if self.overflow.is_none() && self.total >= order {
+            self.overflow = Some((row, MultiplicityFault::Overflow { total: self.total }));
+        }
+    }
+
+    /// The faults found, with their first row and number of rows.
+    fn faults(self) -> impl Iterator<Item = (usize, usize, MultiplicityFault)> {
+        self.sign_flips.into_iter().chain(self.overflow.map(|(row, fault)| (row, 1, fault)))
+    }
+}
+
+/// Checks that the multiplicities of the interactions of the main traces of a shard are sound.
+///
+/// The sends and receives of a value only cancel modulo the order of the field, so a chip whose
+/// multiplicities of a single value add up to the order, or a chip sending a negative multiplicity
+/// where it should send a count, can balance interactions which did not happen. Neither shows up
+/// as a failure: the cumulative sums still vanish. Shards with many events are the most exposed,
+/// so [`SP1CoreOpts::debug_multiplicities`](crate::SP1CoreOpts::debug_multiplicities()) runs
+/// this check before committing to the traces, and reports the chip, the interaction and the
+/// first row of every interaction
+/// - whose multiplicities change sign, the sign of the first nonzero multiplicity being the
+///   expected one, and
+/// - whose absolute multiplicities add up to the order of the field, which bounds the
+///   multiplicity of any of its values.
+///
+/// Multiplicities are read as signed integers, those above half the order being negative. The
+/// traces must be in the same order as the chips of the shard. Chips whose interactions happen at
+/// most once per row can rule both faults out in the proof with
+/// [`Chip::with_boolean_multiplicities`](crate::Chip::with_boolean_multiplicities).
+pub fn check_multiplicities<SC, A>(
+    machine: &StarkMachine<SC, A>,
+    pk: &StarkProvingKey<SC>,
+    traces: &[(String, RowMajorMatrix<Val<SC>>)],
+) -> Vec<MultiplicityIssue>
+where
+    SC: StarkGenericConfig,
+    Val<SC>: PrimeField32,
+    A: MachineAir<Val<SC>>,
+{
+    let order = u64::from(Val::<SC>::ORDER_U32);
+    let mut issues = Vec::new();
+    for traces in chip_traces(machine, pk, traces) {
+        let chip = traces.chip;
+        let num_sends = chip.sends().len();
+        let mut trackers = vec![MultiplicityTracker::default(); num_sends + chip.receives().len()];
+
+        for row in 0..traces.main.height() {
+            traces.for_each_interaction(row, |interaction| {
+                trackers[interaction.position].add(row, signed(interaction.multiplicity), order);
+            });
+        }
+
+        let interactions = chip.sends().iter().chain(chip.receives());
+        for (i, (tracker, interaction)) in trackers.into_iter().zip(interactions).enumerate() {
+            issues.extend(tracker.faults().map(|(row, rows, fault)| MultiplicityIssue {
+                chip: chip.name(),
+                is_send: i < num_sends,
+                index: if i < num_sends { i } else { i - num_sends },
+                kind: interaction.kind,
+                row,
+                rows,
+                fault,
+            }));
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multiplicity_tracker() {
+        let order = 97;
+
+        // Counts of a single sign never fault until they reach the order.
+        let mut tracker = MultiplicityTracker::default();
+        for (row, multiplicity) in [0, 1, 3, 0, 2].into_iter().enumerate() {
+            tracker.add(row, multiplicity, order);
+        }
+        assert_eq!(tracker.faults().count(), 0);
+
+        let mut tracker = MultiplicityTracker::default();
+        for (row, multiplicity) in [0, 2, -1, 40, -3, 60].into_iter().enumerate() {
+            tracker.add(row, multiplicity, order);
+        }
+        assert_eq!(
+            tracker.faults().collect::<Vec<_>>(),
+            vec![
+                (2, 2, MultiplicityFault::SignFlip { expected: 2, found: -1 }),
+                (5, 1, MultiplicityFault::Overflow { total: 106 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multiplicity_issue_display() {
+        let issues = BadMultiplicities(vec![
+            MultiplicityIssue {
+                chip: "Byte".into(),
+                is_send: false,
+                index: 0,
+                kind: InteractionKind::Byte,
+                row: 7,
+                rows: 1,
+                fault: MultiplicityFault::Overflow { total: 2013265921 },
+            },
+            MultiplicityIssue {
+                chip: "Cpu".into(),
+                is_send: true,
+                index: 3,
+                kind: InteractionKind::Alu,
+                row: 12,
+                rows: 2,
+                fault: MultiplicityFault::SignFlip { expected: 1, found: -1 },
+            },
+        ]);
+        assert_eq!(
+            issues.to_string(),
+            "2 interactions with bad multiplicities\n  \
+             Byte: receive Byte interaction #0 reaches a total multiplicity of 2013265921 on row \
+             7\n  \
+             Cpu: send Alu interaction #3 has multiplicity -1 on 2 rows from row 12, after 1"
+        );
+    }
+}
@@ -1,6 +1,10 @@
-use std::env;
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
 
 use serde::{Deserialize, Serialize};
+use sp1_primitives::consts::{AddressSpace, Rv32};
 use sysinfo::System;
 
 const MAX_SHARD_SIZE: usize = 1 << 22;
@@ -10,22 +14,98 @@ const DEFAULT_CHECKPOINTS_CHANNEL_CAPACITY: usize = 128;
 const DEFAULT_RECORDS_AND_TRACES_CHANNEL_CAPACITY: usize = 1;
 
 /// Options to configure the SP1 prover for core and recursive proofs.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SP1ProverOpts {
     /// Options for the core prover.
     pub core_opts: SP1CoreOpts,
     /// Options for the recursion prover.
     pub recursion_opts: SP1CoreOpts,
+    /// The shape of the tree in which the compress stage reduces the recursive proofs.
+    #[serde(default)]
+    pub reduce_strategy: ReduceStrategy,
 }
 
 impl Default for SP1ProverOpts {
     fn default() -> Self {
-        Self { core_opts: SP1CoreOpts::default(), recursion_opts: SP1CoreOpts::recursion() }
+        Self {
+            core_opts: SP1CoreOpts::default(),
+            recursion_opts: SP1CoreOpts::recursion(),
+            reduce_strategy: ReduceStrategy::default(),
+        }
+    }
+}
+
+/// The order in which the compress stage reduces the recursive proofs into a single proof.
+///
+/// Every strategy only reduces proofs of adjacent ranges of shards, so they all produce a valid
+/// proof; they differ in the number of reductions and in how long they wait for each other.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReduceStrategy {
+    /// Reduce the proofs of each layer of the tree in pairs, in order.
+    #[default]
+    BinaryBalanced,
+    /// Reduce the proofs of the first layer in groups of `arity`, then the following layers in
+    /// pairs. The tree is shallower, at the cost of wider reductions at the bottom.
+    WideFirst {
+        /// The number of proofs reduced together in the first layer.
+        arity: usize,
+    },
+    /// Reduce any two proofs of adjacent ranges as soon as both are available, in the order in
+    /// which they finish rather than layer by layer.
+    LatencyOptimized,
+}
+
+impl ReduceStrategy {
+    /// The number of proofs reduced together into a node at `height + 1` of a layered tree.
+    #[must_use]
+    pub fn arity(&self, height: usize) -> usize {
+        match *self {
+            Self::WideFirst { arity } if height == 0 => arity.max(2),
+            _ => 2,
+        }
+    }
+}
+
+impl SP1ProverOpts {
+    /// Dump the traces of every core shard to `dir` while proving.
+    #[must_use]
+    pub fn dump_traces(mut self, dir: impl AsRef<Path>) -> Self {
+        self.core_opts = self.core_opts.dump_traces(dir);
+        self
+    }
+
+    /// Reuse the results of repeated keccak permutations within a core shard.
+    #[must_use]
+    pub fn memoize_syscalls(mut self) -> Self {
+        self.core_opts = self.core_opts.memoize_syscalls();
+        self
+    }
+
+    /// Check that the lookups of the core shards balance before proving them.
+    #[must_use]
+    pub fn debug_lookups(mut self) -> Self {
+        self.core_opts = self.core_opts.debug_lookups();
+        self
+    }
+
+    /// Check that the padding rows of the core shards are inert before proving them.
+    #[must_use]
+    pub fn debug_padding(mut self) -> Self {
+        self.core_opts = self.core_opts.debug_padding();
+        self
+    }
+
+    /// Check that the interaction multiplicities of the core shards cannot wrap around the field
+    /// before proving them.
+    #[must_use]
+    pub fn debug_multiplicities(mut self) -> Self {
+        self.core_opts = self.core_opts.debug_multiplicities();
+        self
     }
 }
 
 /// Options for the core prover.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SP1CoreOpts {
     /// The size of a shard in terms of cycles.
     pub shard_size: usize,
@@ -41,6 +121,32 @@ pub struct SP1CoreOpts {
     pub checkpoints_channel_capacity: usize,
     /// The capacity of the channel for records and traces.
     pub records_and_traces_channel_capacity: usize,
+    /// The number of bits of the usable address space. Execution fails on accesses to addresses
+    /// at or above `1 << max_memory_addr_bits`, which the memory chips of a machine built with
+    /// the same ceiling reject as well.
+    pub max_memory_addr_bits: usize,
+    /// The maximum number of bytes of memory the guest may touch, if any. Execution fails once the
+    /// words of memory accessed by the program, including its memory image, take more space.
+    pub max_memory_bytes: Option<u64>,
+    /// Whether a keccak permutation of a state already permuted earlier in the shard reuses the
+    /// result of the earlier call, and is proven in a single row instead of 24.
+    #[serde(default)]
+    pub memoize_syscalls: bool,
+    /// The directory to dump the traces of each shard to, if any. See [`crate::ShardTraceDump`].
+    #[serde(skip)]
+    pub dump_traces_dir: Option<PathBuf>,
+    /// Whether to check that the lookups of all shards balance before proving them. See
+    /// [`crate::LookupBalance`].
+    #[serde(skip)]
+    pub debug_lookups: bool,
+    /// Whether to check that the padding rows of all shards are inert before proving them. See
+    /// [`crate::check_padding_rows`].
+    #[serde(skip)]
+    pub debug_padding: bool,
+    /// Whether to check that the interaction multiplicities of all shards cannot wrap around the
+    /// field before proving them. See [`crate::check_multiplicities`].
+    #[serde(skip)]
+    pub debug_multiplicities: bool,
 }
 
 /// Calculate the default shard size using an empirically determined formula.
@@ -109,6 +215,15 @@ impl Default for SP1CoreOpts {
                     |_| DEFAULT_RECORDS_AND_TRACES_CHANNEL_CAPACITY,
                     |s| s.parse::<usize>().unwrap_or(DEFAULT_RECORDS_AND_TRACES_CHANNEL_CAPACITY),
                 ),
+            max_memory_addr_bits: env::var("MAX_MEMORY_ADDR_BITS").map_or(Rv32::ADDR_BITS, |s| {
+                s.parse::<usize>().map_or(Rv32::ADDR_BITS, |bits| bits.min(Rv32::ADDR_BITS))
+            }),
+            max_memory_bytes: env::var("MAX_MEMORY_BYTES").ok().and_then(|s| s.parse().ok()),
+            memoize_syscalls: false,
+            dump_traces_dir: None,
+            debug_lookups: false,
+            debug_padding: false,
+            debug_multiplicities: false,
         }
     }
 }
@@ -124,6 +239,60 @@ impl SP1CoreOpts {
         opts.shard_size = MAX_SHARD_SIZE;
         opts
     }
+
+    /// Dump the traces of every shard to `dir` while proving.
+    #[must_use]
+    pub fn dump_traces(mut self, dir: impl AsRef<Path>) -> Self {
+        self.dump_traces_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Reuse the results of repeated keccak permutations within a shard.
+    ///
+    /// A permutation of a state which was already permuted earlier in the same shard, as is common
+    /// in Merkle verification loops, copies the result of the earlier call. Its memory accesses
+    /// are proven by a chip with a single row per call, which receives the pair of states from
+    /// the rows of the earlier call instead of proving the 24 rounds again.
+    #[must_use]
+    pub fn memoize_syscalls(mut self) -> Self {
+        self.memoize_syscalls = true;
+        self
+    }
+
+    /// Check that the lookups of all shards balance before proving them.
+    ///
+    /// The multiplicities of every interaction are summed over the main traces of all shards, and
+    /// proving fails with the interactions which do not balance. This is slow, and only meant to
+    /// find the cause of an unbalanced cumulative sum.
+    #[must_use]
+    pub fn debug_lookups(mut self) -> Self {
+        self.debug_lookups = true;
+        self
+    }
+
+    /// Check that the padding rows of all shards are inert before proving them.
+    ///
+    /// The padding rows of every chip which reports its real rows must satisfy the constraints of
+    /// the chip and have a multiplicity of zero in all of its interactions, and proving fails with
+    /// the chips whose padding leaks. This is slow, and meant for developing new chips.
+    #[must_use]
+    pub fn debug_padding(mut self) -> Self {
+        self.debug_padding = true;
+        self
+    }
+
+    /// Check that the interaction multiplicities of all shards cannot wrap around the field before
+    /// proving them.
+    ///
+    /// Every interaction of every chip must keep the sign of its first nonzero multiplicity, and
+    /// its absolute multiplicities must add up to less than the order of the field, and proving
+    /// fails with the chip, the interaction and the first row of the interactions which do not.
+    /// This is slow, and meant for developing new chips and testing large shard sizes.
+    #[must_use]
+    pub fn debug_multiplicities(mut self) -> Self {
+        self.debug_multiplicities = true;
+        self
+    }
 }
 
 /// Options for splitting deferred events.
@@ -137,6 +306,20 @@ pub struct SplitOpts {
     pub sha_extend: usize,
     /// The threshold for sha compress events.
     pub sha_compress: usize,
+    /// The threshold for ripemd160 compress events.
+    pub ripemd160_compress: usize,
+    /// The threshold for edwards scalar mul events.
+    pub ed_scalar_mul: usize,
+    /// The threshold for MSM events.
+    pub msm: usize,
+    /// The threshold for bigint events.
+    pub bigint: usize,
+    /// The threshold for field batch inversion events.
+    pub fp_batch_inv: usize,
+    /// The threshold for byte scan events.
+    pub byte_scan: usize,
+    /// The threshold for hint words events.
+    pub hint_words: usize,
     /// The threshold for memory events.
     pub memory: usize,
 }
@@ -150,6 +333,13 @@ impl SplitOpts {
             keccak: deferred_shift_threshold / 24,
             sha_extend: deferred_shift_threshold / 48,
             sha_compress: deferred_shift_threshold / 80,
+            ripemd160_compress: deferred_shift_threshold / 90,
+            ed_scalar_mul: deferred_shift_threshold / 256,
+            msm: deferred_shift_threshold / 256,
+            bigint: deferred_shift_threshold / 256,
+            fp_batch_inv: deferred_shift_threshold / 64,
+            byte_scan: deferred_shift_threshold / 32,
+            hint_words: deferred_shift_threshold / 32,
             memory: deferred_shift_threshold * 4,
         }
     }
@@ -0,0 +1,299 @@
+use std::fmt::{Display, Formatter};
+
+use p3_air::Air;
+use p3_field::{Field, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+
+use crate::{
+    air::MachineAir, debug::constraints_hold_on_row, lookup::chip_traces, Chip,
+    DebugConstraintBuilder, InteractionKind, MachineRecord, StarkGenericConfig, StarkMachine,
+    StarkProvingKey, Val,
+};
+
+/// The padding overhead of a single chip's main trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChipPadding {
+    /// The name of the chip.
+    pub name: String,
+    /// The width of the main trace.
+    pub width: usize,
+    /// The number of rows holding events, if the chip reports it.
+    pub real_rows: Option<usize>,
+    /// The height of the trace after padding.
+    pub padded_rows: usize,
+}
+
+impl ChipPadding {
+    /// The number of padding rows, if the number of real rows is known.
+    #[must_use]
+    pub fn padding_rows(&self) -> Option<usize> {
+        self.real_rows.map(|real| self.padded_rows.saturating_sub(real))
+    }
+
+    /// The number of trace cells spent on padding, if the number of real rows is known.
+    #[must_use]
+    pub fn wasted_cells(&self) -> Option<usize> {
+        self.padding_rows().map(|rows| rows * self.width)
+    }
+
+    /// The fraction of the trace occupied by real rows, if the number of real rows is known.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn utilization(&self) -> Option<f64> {
+        self.real_rows.map(|real| real as f64 / self.padded_rows.max(1) as f64)
+    }
+}
+
+/// The padding overhead of the main traces of a shard.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PaddingReport {
+    /// The padding of each chip, sorted by decreasing wasted cells.
+    pub chips: Vec<ChipPadding>,
+}
+
+impl PaddingReport {
+    /// Builds a report for the given chips and the main traces generated for `record`.
+    ///
+    /// The traces must be in the same order as the chips.
+    pub fn new<F: Field, A: MachineAir<F>>(
+        chips: &[&Chip<F, A>],
+        record: &A::Record,
+        traces: &[(String, RowMajorMatrix<F>)],
+    ) -> Self {
+        let mut chips = chips
+            .iter()
+            .zip(traces.iter())
+            .map(|(chip, (name, trace))| ChipPadding {
+                name: name.clone(),
+                width: trace.width(),
+                real_rows: chip.num_real_rows(record),
+                padded_rows: trace.height(),
+            })
+            .collect::<Vec<_>>();
+        chips.sort_by_key(|chip| std::cmp::Reverse(chip.wasted_cells().unwrap_or(0)));
+        Self { chips }
+    }
+
+    /// The total number of main trace cells in the shard.
+    #[must_use]
+    pub fn total_cells(&self) -> usize {
+        self.chips.iter().map(|chip| chip.padded_rows * chip.width).sum()
+    }
+
+    /// The total number of cells spent on padding, over the chips that report their real rows.
+    #[must_use]
+    pub fn wasted_cells(&self) -> usize {
+        self.chips.iter().filter_map(ChipPadding::wasted_cells).sum()
+    }
+
+    /// The chips whose real rows occupy at most `threshold` of their padded trace.
+    ///
+    /// These are the chips that would benefit most from a smaller shard size or from having their
+    /// events split across fewer shards.
+    pub fn underutilized(&self, threshold: f64) -> impl Iterator<Item = &ChipPadding> {
+        self.chips.iter().filter(move |chip| chip.utilization().is_some_and(|u| u <= threshold))
+    }
+}
+
+impl Display for PaddingReport {
+    #[allow(clippy::cast_precision_loss)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let total = self.total_cells();
+        let wasted = self.wasted_cells();
+        writeln!(
+            f,
+            "padding: {} of {} main trace cells ({:.2}%)",
+            wasted,
+            total,
+            100.0 * wasted as f64 / total.max(1) as f64
+        )?;
+        for chip in &self.chips {
+            match chip.real_rows {
+                Some(real) => writeln!(
+                    f,
+                    "  {:<24} {:>10} / {:>10} rows, {:>12} wasted cells",
+                    chip.name,
+                    real,
+                    chip.padded_rows,
+                    chip.wasted_cells().unwrap_or(0)
+                )?,
+                None => {
+                    writeln!(f, "  {:<24} {:>10} / {:>10} rows", chip.name, "?", chip.padded_rows)?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What a padding row leaks into the proof. See [`PaddingLeak`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaddingLeakSource {
+    /// The constraints of the chip do not hold on the row.
+    Constraints,
+    /// The send interaction at the given index has a nonzero multiplicity on the row.
+    Send(usize, InteractionKind),
+    /// The receive interaction at the given index has a nonzero multiplicity on the row.
+    Receive(usize, InteractionKind),
+}
+
+/// Padding rows of a chip which are not inert, either because they do not satisfy the constraints
+/// of the chip or because they take part in an interaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaddingLeak {
+    /// The name of the chip.
+    pub chip: String,
+    /// What the rows leak.
+    pub source: PaddingLeakSource,
+    /// The first leaking row.
+    pub first_row: usize,
+    /// The number of leaking rows.
+    pub rows: usize,
+}
+
+impl Display for PaddingLeak {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} padding rows from row {} ", self.chip, self.rows, self.first_row)?;
+        match self.source {
+            PaddingLeakSource::Constraints => write!(f, "fail the constraints"),
+            PaddingLeakSource::Send(i, kind) => write!(f, "send {kind} interaction #{i}"),
+            PaddingLeakSource::Receive(i, kind) => write!(f, "receive {kind} interaction #{i}"),
+        }
+    }
+}
+
+/// The padding leaks found in a set of shards. See [`check_padding_rows`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeakyPadding(pub Vec<PaddingLeak>);
+
+impl Display for LeakyPadding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} leaks from padding rows", self.0.len())?;
+        for leak in &self.0 {
+            write!(f, "\n  {leak}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for LeakyPadding {}
+
+/// Checks that the padding rows of the main traces of a shard are inert: they must satisfy the
+/// constraints of their chip, and have a multiplicity of zero in every interaction.
+///
+/// A padding row which only satisfies the constraints because of a nonzero multiplicity, or which
+/// sends or receives a dummy value, is balanced by nothing else in the shard. Such bugs show up as
+/// an unbalanced cumulative sum or as a failed constraint far from the chip that caused them, so
+/// [`SP1CoreOpts::debug_padding`](crate::SP1CoreOpts::debug_padding()) runs this check before
+/// committing to the traces instead.
+///
+/// The padding rows of a chip are those after [`MachineAir::num_real_rows`], so chips which do not
+/// report their real rows are not checked. The traces must be in the same order as the chips of
+/// the shard.
+pub fn check_padding_rows<SC, A>(
+    machine: &StarkMachine<SC, A>,
+    pk: &StarkProvingKey<SC>,
+    record: &A::Record,
+    traces: &[(String, RowMajorMatrix<Val<SC>>)],
+) -> Vec<PaddingLeak>
+where
+    SC: StarkGenericConfig,
+    Val<SC>: PrimeField32,
+    A: MachineAir<Val<SC>> + for<'a> Air<DebugConstraintBuilder<'a, Val<SC>, SC::Challenge>>,
+{
+    let public_values = record.public_values::<Val<SC>>();
+    let mut leaks = Vec::new();
+    for traces in chip_traces(machine, pk, traces) {
+        let chip = traces.chip;
+        let Some(real_rows) = chip.num_real_rows(record) else {
+            continue;
+        };
+
+        // The first leaking row and the number of leaking rows, for each source.
+        let mut found: Vec<(PaddingLeakSource, usize, usize)> = Vec::new();
+        let mut record_leak = |source, row| match found.iter_mut().find(|(s, _, _)| *s == source) {
+            Some((_, _, rows)) => *rows += 1,
+            None => found.push((source, row, 1)),
+        };
+
+        for row in real_rows..traces.main.height() {
+            if !constraints_hold_on_row::<_, SC::Challenge, _>(
+                chip.air(),
+                traces.preprocessed,
+                traces.main,
+                row,
+                &public_values,
+            ) {
+                record_leak(PaddingLeakSource::Constraints, row);
+            }
+
+            traces.for_each_interaction(row, |interaction| {
+                if !interaction.multiplicity.is_zero() {
+                    let source = if interaction.is_send {
+                        PaddingLeakSource::Send(interaction.index, interaction.kind)
+                    } else {
+                        PaddingLeakSource::Receive(interaction.index, interaction.kind)
+                    };
+                    record_leak(source, row);
+                }
+            });
+        }
+
+        leaks.extend(found.into_iter().map(|(source, first_row, rows)| PaddingLeak {
+            chip: chip.name(),
+            source,
+            first_row,
+            rows,
+        }));
+    }
+    leaks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_padding_accounting() {
+        let report = PaddingReport {
+            chips: vec![
+                ChipPadding { name: "Cpu".into(), width: 10, real_rows: Some(5), padded_rows: 16 },
+                ChipPadding {
+                    name: "Byte".into(),
+                    width: 3,
+                    real_rows: None,
+                    padded_rows: 1 << 16,
+                },
+                ChipPadding { name: "Mul".into(), width: 4, real_rows: Some(16), padded_rows: 16 },
+            ],
+        };
+        assert_eq!(report.total_cells(), 160 + 3 * (1 << 16) + 64);
+        assert_eq!(report.wasted_cells(), 110);
+        let underutilized = report.underutilized(0.5).map(|c| c.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(underutilized, vec!["Cpu"]);
+    }
+
+    #[test]
+    fn test_padding_leak_display() {
+        let leaks = LeakyPadding(vec![
+            PaddingLeak {
+                chip: "Poseidon2Skinny".into(),
+                source: PaddingLeakSource::Constraints,
+                first_row: 12,
+                rows: 4,
+            },
+            PaddingLeak {
+                chip: "Poseidon2Skinny".into(),
+                source: PaddingLeakSource::Receive(0, InteractionKind::Memory),
+                first_row: 12,
+                rows: 4,
+            },
+        ]);
+        assert_eq!(
+            leaks.to_string(),
+            "2 leaks from padding rows\n  \
+             Poseidon2Skinny: 4 padding rows from row 12 fail the constraints\n  \
+             Poseidon2Skinny: 4 padding rows from row 12 receive Memory interaction #0"
+        );
+    }
+}
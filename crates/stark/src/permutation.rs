@@ -7,7 +7,10 @@ use p3_matrix::{dense::RowMajorMatrix, Matrix};
 use p3_maybe_rayon::prelude::*;
 use rayon_scan::ScanParallelIterator;
 
-use crate::{air::MultiTableAirBuilder, lookup::Interaction};
+use crate::{air::MultiTableAirBuilder, lookup::Interaction, zk::random_values};
+
+/// The number of rows generated together by [`generate_flattened_permutation_trace`].
+const PERMUTATION_CHUNK_ROWS: usize = 1 << 10;
 
 /// Computes the width of the permutation trace.
 #[inline]
@@ -148,6 +151,89 @@ pub fn generate_permutation_trace<F: PrimeField, EF: ExtensionField<F>>(
     permutation_trace
 }
 
+/// Generates the permutation trace of [`generate_permutation_trace`] flattened to the base field
+/// and blinded as by [`blind_trace`](crate::blind_trace), and returns it with the cumulative sum.
+///
+/// The rows are generated in chunks, each of which keeps a running sum while its rows are written
+/// directly into the flattened trace, so the trace over the extension field and its flattened copy
+/// are never both in memory. The running sums are then offset by the sums of the previous chunks,
+/// which only touches the last column.
+pub fn generate_flattened_permutation_trace<F: PrimeField, EF: ExtensionField<F>>(
+    sends: &[Interaction<F>],
+    receives: &[Interaction<F>],
+    preprocessed: Option<&RowMajorMatrix<F>>,
+    main: &RowMajorMatrix<F>,
+    random_elements: &[EF],
+    batch_size: usize,
+    log_blinding_factor: usize,
+) -> (RowMajorMatrix<F>, EF) {
+    let alpha = random_elements[0];
+    let betas = random_elements[1].powers();
+
+    let degree = <EF as AbstractExtensionField<F>>::D;
+    let width = permutation_trace_width(sends.len() + receives.len(), batch_size);
+    let flat_width = width * degree;
+    let height = main.height();
+    let row_stride = flat_width << log_blinding_factor;
+
+    // The rows between the rows of the trace are uniformly random when blinding.
+    let mut values = if log_blinding_factor == 0 {
+        vec![F::zero(); row_stride * height]
+    } else {
+        random_values(row_stride * height)
+    };
+
+    // Write the rows of each chunk with the cumulative sum since the start of the chunk.
+    let chunk_sums = values
+        .par_chunks_mut(row_stride * PERMUTATION_CHUNK_ROWS)
+        .enumerate()
+        .map(|(chunk, values)| {
+            let mut row = vec![EF::zero(); width];
+            let mut sum = EF::zero();
+            for (i, flat_row) in values.chunks_exact_mut(row_stride).enumerate() {
+                let r = chunk * PERMUTATION_CHUNK_ROWS + i;
+                let preprocessed_row = preprocessed.map(|trace| trace.row_slice(r));
+                let preprocessed_row = preprocessed_row.as_deref().unwrap_or(&[]);
+                populate_permutation_row(
+                    &mut row,
+                    preprocessed_row,
+                    &main.row_slice(r),
+                    sends,
+                    receives,
+                    alpha,
+                    betas.clone(),
+                    batch_size,
+                );
+                sum += row[0..width - 1].iter().copied().sum::<EF>();
+                row[width - 1] = sum;
+                for (dst, value) in flat_row[0..flat_width].chunks_exact_mut(degree).zip(&row) {
+                    dst.copy_from_slice(value.as_base_slice());
+                }
+            }
+            sum
+        })
+        .collect::<Vec<_>>();
+
+    // Offset the running sums of each chunk by the sum of the chunks before it.
+    let mut offsets = Vec::with_capacity(chunk_sums.len());
+    let mut cumulative_sum = EF::zero();
+    for sum in chunk_sums {
+        offsets.push(cumulative_sum);
+        cumulative_sum += sum;
+    }
+    values.par_chunks_mut(row_stride * PERMUTATION_CHUNK_ROWS).zip_eq(offsets).skip(1).for_each(
+        |(values, offset)| {
+            for flat_row in values.chunks_exact_mut(row_stride) {
+                let last = &mut flat_row[flat_width - degree..flat_width];
+                let value = EF::from_base_slice(last) + offset;
+                last.copy_from_slice(value.as_base_slice());
+            }
+        },
+    );
+
+    (RowMajorMatrix::new(values, flat_width), cumulative_sum)
+}
+
 /// Evaluates the permutation constraints for the given chip.
 ///
 /// In particular, the constraints checked here are:
@@ -265,3 +351,51 @@ pub fn eval_permutation_constraints<F, AB>(
     // Assert that the cumulative sum is constrained to `phi_local` on the last row.
     builder.when_last_row().assert_eq_ext(*perm_local.last().unwrap(), cumulative_sum);
 }
+
+#[cfg(test)]
+mod tests {
+    use p3_air::VirtualPairCol;
+    use p3_field::AbstractField;
+    use p3_matrix::{dense::RowMajorMatrix, Matrix};
+
+    use super::{generate_flattened_permutation_trace, generate_permutation_trace};
+    use crate::{
+        lookup::{Interaction, InteractionKind},
+        InnerChallenge, InnerVal,
+    };
+
+    #[test]
+    fn test_flattened_permutation_trace() {
+        let height = 3 * super::PERMUTATION_CHUNK_ROWS + 5;
+        let width = 3;
+        let main = RowMajorMatrix::new(
+            (0..height * width).map(InnerVal::from_canonical_usize).collect(),
+            width,
+        );
+        let interaction = |value: usize| {
+            Interaction::new(
+                vec![VirtualPairCol::single_main(value)],
+                VirtualPairCol::single_main(2),
+                InteractionKind::Byte,
+            )
+        };
+        let sends = [interaction(0), interaction(1)];
+        let receives = [interaction(1)];
+        let challenges =
+            [InnerChallenge::from_canonical_u32(7), InnerChallenge::from_canonical_u32(11)];
+
+        let expected = generate_permutation_trace(&sends, &receives, None, &main, &challenges, 2);
+        let (flattened, cumulative_sum) =
+            generate_flattened_permutation_trace(&sends, &receives, None, &main, &challenges, 2, 0);
+        assert_eq!(flattened, expected.clone().flatten_to_base());
+        assert_eq!(cumulative_sum, *expected.row_slice(height - 1).last().unwrap());
+
+        // With blinding, the rows of the trace are interleaved with random rows.
+        let (blinded, _) =
+            generate_flattened_permutation_trace(&sends, &receives, None, &main, &challenges, 2, 1);
+        assert_eq!(blinded.height(), 2 * height);
+        for i in 0..height {
+            assert_eq!(*blinded.row_slice(2 * i), *flattened.row_slice(i));
+        }
+    }
+}
@@ -1,25 +1,29 @@
 use core::fmt::Display;
 use itertools::Itertools;
 use serde::{de::DeserializeOwned, Serialize};
-use std::{cmp::Reverse, error::Error, time::Instant};
+use std::{cmp::Reverse, error::Error};
 
 use crate::{AirOpenedValues, ChipOpenedValues, ShardOpenedValues};
 use p3_air::Air;
 use p3_challenger::{CanObserve, FieldChallenger};
 use p3_commit::{Pcs, PolynomialSpace};
-use p3_field::{AbstractExtensionField, AbstractField, PrimeField32};
+use p3_field::{AbstractExtensionField, AbstractField, PrimeField32, TwoAdicField};
 use p3_matrix::{dense::RowMajorMatrix, Matrix};
 use p3_maybe_rayon::prelude::*;
 use p3_util::log2_strict_usize;
+use tracing::instrument;
 
 use super::{
-    quotient_values, Com, OpeningProof, StarkGenericConfig, StarkMachine, StarkProvingKey, Val,
-    VerifierConstraintFolder,
+    batched_quotient_values, Com, OpeningProof, QuotientChipData, StarkGenericConfig, StarkMachine,
+    StarkProvingKey, Val, VerifierConstraintFolder,
 };
 use crate::{
-    air::MachineAir, lookup::InteractionBuilder, opts::SP1CoreOpts, record::MachineRecord,
-    DebugConstraintBuilder, MachineChip, MachineProof, PackedChallenge, PcsProverData,
-    ProverConstraintFolder, ShardCommitment, ShardMainData, ShardProof, StarkVerifyingKey,
+    air::MachineAir, blind_quotient_chunks, blind_trace, check_multiplicities, check_padding_rows,
+    log_blinding_factor, log_quotient_degree, lookup::InteractionBuilder, opts::SP1CoreOpts,
+    record::MachineRecord, zk::random_values, BadMultiplicities, DebugConstraintBuilder,
+    LeakyPadding, LookupBalance, MachineChip, MachineProof, MaskingOpening, PackedChallenge,
+    PaddingReport, PcsProverData, ProverConstraintFolder, ShardCommitment, ShardMainData,
+    ShardProof, ShardTraceDump, StarkVerifyingKey, UnbalancedLookups,
 };
 
 /// An algorithmic & hardware independent prover implementation for any [`MachineAir`].
@@ -53,23 +57,30 @@ pub trait MachineProver<SC: StarkGenericConfig, A: MachineAir<SC::Val>>:
 
         // For each chip, generate the trace.
         let parent_span = tracing::debug_span!("generate traces for shard");
-        parent_span.in_scope(|| {
+        let traces = parent_span.in_scope(|| {
             shard_chips
                 .par_iter()
                 .map(|chip| {
                     let chip_name = chip.name();
-                    let begin = Instant::now();
-                    let trace = chip.generate_trace(record, &mut A::Record::default());
-                    tracing::debug!(
+                    let span = tracing::debug_span!(
                         parent: &parent_span,
-                        "generated trace for chip {} in {:?}",
-                        chip_name,
-                        begin.elapsed()
-                    );
+                        "generate trace",
+                        chip = %chip_name,
+                        rows = tracing::field::Empty
+                    )
+                    .entered();
+                    let trace = chip.generate_trace(record, &mut A::Record::default());
+                    span.record("rows", trace.height());
                     (chip_name, trace)
                 })
                 .collect::<Vec<_>>()
-        })
+        });
+
+        if tracing::enabled!(tracing::Level::DEBUG) {
+            tracing::debug!("{}", PaddingReport::new(&shard_chips, record, &traces));
+        }
+
+        traces
     }
 
     /// Commit to the main traces.
@@ -154,8 +165,18 @@ pub struct CpuProver<SC: StarkGenericConfig, A> {
 }
 
 /// An error that occurs during the execution of the [`CpuProver`].
-#[derive(Debug, Clone, Copy)]
-pub struct CpuProverError;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CpuProverError {
+    /// The lookups of the shards do not balance, if
+    /// [`SP1CoreOpts::debug_lookups`](SP1CoreOpts::debug_lookups()) is set.
+    UnbalancedLookups(UnbalancedLookups),
+    /// The padding rows of some chips are not inert, if
+    /// [`SP1CoreOpts::debug_padding`](SP1CoreOpts::debug_padding()) is set.
+    LeakyPadding(LeakyPadding),
+    /// The multiplicities of some interactions may wrap around the field, if
+    /// [`SP1CoreOpts::debug_multiplicities`](SP1CoreOpts::debug_multiplicities()) is set.
+    BadMultiplicities(BadMultiplicities),
+}
 
 impl<SC, A> MachineProver<SC, A> for CpuProver<SC, A>
 where
@@ -165,7 +186,7 @@ where
         + Air<InteractionBuilder<Val<SC>>>
         + for<'a> Air<VerifierConstraintFolder<'a, SC>>,
     A::Record: MachineRecord<Config = SP1CoreOpts>,
-    SC::Val: PrimeField32,
+    SC::Val: PrimeField32 + TwoAdicField,
     Com<SC>: Send + Sync,
     PcsProverData<SC>: Send + Sync + Serialize + DeserializeOwned,
     OpeningProof<SC>: Send + Sync,
@@ -183,6 +204,15 @@ where
         &self.machine
     }
 
+    #[instrument(
+        "commit to main traces",
+        level = "debug",
+        skip_all,
+        fields(
+            chips = named_traces.len(),
+            rows = named_traces.iter().map(|(_, trace)| trace.height()).sum::<usize>()
+        )
+    )]
     fn commit(
         &self,
         record: A::Record,
@@ -192,12 +222,16 @@ where
         named_traces.sort_by_key(|(_, trace)| Reverse(trace.height()));
 
         let pcs = self.config().pcs();
+        let zk = self.config().zk();
 
+        // In hiding mode, the committed traces are interleaved with random rows.
         let domains_and_traces = named_traces
             .iter()
             .map(|(_, trace)| {
-                let domain = pcs.natural_domain_for_degree(trace.height());
-                (domain, trace.to_owned())
+                let log_blinding_factor =
+                    log_blinding_factor(zk.as_ref(), log2_strict_usize(trace.height()));
+                let domain = pcs.natural_domain_for_degree(trace.height() << log_blinding_factor);
+                (domain, blind_trace(trace, log_blinding_factor))
             })
             .collect::<Vec<_>>();
 
@@ -223,6 +257,15 @@ where
     #[allow(clippy::too_many_lines)]
     #[allow(clippy::redundant_closure_for_method_calls)]
     #[allow(clippy::map_unwrap_or)]
+    #[instrument(
+        "open shard",
+        level = "debug",
+        skip_all,
+        fields(
+            chips = data.traces.len(),
+            rows = data.traces.iter().map(|trace| trace.height()).sum::<usize>()
+        )
+    )]
     fn open(
         &self,
         pk: &StarkProvingKey<SC>,
@@ -239,13 +282,26 @@ where
         let log_degrees =
             degrees.iter().map(|degree| log2_strict_usize(*degree)).collect::<Vec<_>>();
 
-        let log_quotient_degrees =
-            chips.iter().map(|chip| chip.log_quotient_degree()).collect::<Vec<_>>();
+        let zk = config.zk();
+        let log_quotient_degrees = chips
+            .iter()
+            .zip_eq(log_degrees.iter())
+            .map(|(chip, log_degree)| {
+                log_quotient_degree(zk.as_ref(), chip.log_quotient_degree(), *log_degree)
+            })
+            .collect::<Vec<_>>();
 
         let pcs = config.pcs();
         let trace_domains =
             degrees.iter().map(|degree| pcs.natural_domain_for_degree(*degree)).collect::<Vec<_>>();
 
+        // Observe the chip set of the machine, and which of its chips are present in the shard.
+        challenger.observe_slice(self.machine().chip_set_id());
+        let chip_presence = self.machine().chip_presence(&data.chip_ordering);
+        challenger.observe_slice(
+            &chip_presence.into_iter().map(Val::<SC>::from_bool).collect::<Vec<_>>(),
+        );
+
         // Obtain the challenges used for the permutation argument.
         let mut permutation_challenges: Vec<SC::Challenge> = Vec::new();
         for _ in 0..2 {
@@ -256,22 +312,23 @@ where
             .map(|c| PackedChallenge::<SC>::from_f(*c))
             .collect::<Vec<_>>();
 
-        // Generate the permutation traces.
+        // Generate the permutation traces, flattened to the base field and blinded.
         let ((permutation_traces, prep_traces), cumulative_sums): ((Vec<_>, Vec<_>), Vec<_>) =
             tracing::debug_span!("generate permutation traces").in_scope(|| {
                 chips
                     .par_iter()
                     .zip(traces.par_iter_mut())
-                    .map(|(chip, main_trace): (&&MachineChip<SC, A>, _)| {
+                    .zip(log_degrees.par_iter())
+                    .map(|((chip, main_trace), log_degree): ((&&MachineChip<SC, A>, _), _)| {
                         let preprocessed_trace =
                             pk.chip_ordering.get(&chip.name()).map(|&index| &pk.traces[index]);
-                        let perm_trace = chip.generate_permutation_trace(
-                            preprocessed_trace,
-                            main_trace,
-                            &permutation_challenges,
-                        );
-                        let cumulative_sum =
-                            perm_trace.row_slice(main_trace.height() - 1).last().copied().unwrap();
+                        let (perm_trace, cumulative_sum) = chip
+                            .generate_flattened_permutation_trace(
+                                preprocessed_trace,
+                                main_trace,
+                                &permutation_challenges,
+                                log_blinding_factor(zk.as_ref(), *log_degree),
+                            );
                         ((perm_trace, preprocessed_trace), cumulative_sum)
                     })
                     .unzip()
@@ -282,31 +339,22 @@ where
             let trace_width = traces[i].width();
             let prep_width = prep_traces[i].map_or(0, |x| x.width());
             let permutation_width = permutation_traces[i].width();
-            let total_width = trace_width
-                + prep_width
-                + permutation_width * <SC::Challenge as AbstractExtensionField<SC::Val>>::D;
+            let total_width = trace_width + prep_width + permutation_width;
             tracing::debug!(
                 "{:<15} | Main Cols = {:<5} | Pre Cols = {:<5}  | Perm Cols = {:<5} | Rows = {:<5} | Cells = {:<10}",
                 chips[i].name(),
                 trace_width,
                 prep_width,
-                permutation_width * <SC::Challenge as AbstractExtensionField<SC::Val>>::D,
+                permutation_width,
                 traces[i].height(),
                 total_width * traces[i].height(),
             );
         }
 
-        let domains_and_perm_traces =
-            tracing::debug_span!("flatten permutation traces and collect domains").in_scope(|| {
-                permutation_traces
-                    .into_iter()
-                    .zip(trace_domains.iter())
-                    .map(|(perm_trace, domain)| {
-                        let trace = perm_trace.flatten_to_base();
-                        (*domain, trace.clone())
-                    })
-                    .collect::<Vec<_>>()
-            });
+        let domains_and_perm_traces = permutation_traces
+            .into_iter()
+            .map(|trace| (pcs.natural_domain_for_degree(trace.height()), trace))
+            .collect::<Vec<_>>();
 
         let pcs = config.pcs();
 
@@ -326,32 +374,49 @@ where
             })
             .collect::<Vec<_>>();
 
-        // Compute the quotient values.
+        // Compute the quotient values. Chips with the same trace and quotient domains are batched,
+        // so that each quotient domain is walked once.
         let alpha: SC::Challenge = challenger.sample_ext_element::<SC::Challenge>();
+        let mut batches = Vec::<Vec<usize>>::new();
+        for (i, key) in log_degrees.iter().zip_eq(log_quotient_degrees.iter()).enumerate() {
+            match batches
+                .iter_mut()
+                .find(|batch| (&log_degrees[batch[0]], &log_quotient_degrees[batch[0]]) == key)
+            {
+                Some(batch) => batch.push(i),
+                None => batches.push(vec![i]),
+            }
+        }
+        let chip_data = |i: usize, quotient_domain| QuotientChipData {
+            chip: chips[i],
+            cumulative_sum: cumulative_sums[i],
+            preprocessed_trace_on_quotient_domain: pk
+                .chip_ordering
+                .get(&chips[i].name())
+                .map(|&index| pcs.get_evaluations_on_domain(&pk.data, index, quotient_domain)),
+            main_trace_on_quotient_domain: pcs.get_evaluations_on_domain(
+                &data.main_data,
+                i,
+                quotient_domain,
+            ),
+            permutation_trace_on_quotient_domain: pcs.get_evaluations_on_domain(
+                &permutation_data,
+                i,
+                quotient_domain,
+            ),
+        };
         let parent_span = tracing::debug_span!("compute quotient values");
-        let quotient_values = parent_span.in_scope(|| {
-            quotient_domains
-                .into_par_iter()
-                .enumerate()
-                .map(|(i, quotient_domain)| {
+        let batched_values = parent_span.in_scope(|| {
+            batches
+                .par_iter()
+                .map(|batch| {
                     tracing::debug_span!(parent: &parent_span, "compute quotient values for domain")
                         .in_scope(|| {
-                            let preprocessed_trace_on_quotient_domains =
-                                pk.chip_ordering.get(&chips[i].name()).map(|&index| {
-                                    pcs.get_evaluations_on_domain(&pk.data, index, *quotient_domain)
-                                });
-                            let main_trace_on_quotient_domains =
-                                pcs.get_evaluations_on_domain(&data.main_data, i, *quotient_domain);
-                            let permutation_trace_on_quotient_domains = pcs
-                                .get_evaluations_on_domain(&permutation_data, i, *quotient_domain);
-                            quotient_values(
-                                chips[i],
-                                cumulative_sums[i],
-                                trace_domains[i],
-                                *quotient_domain,
-                                preprocessed_trace_on_quotient_domains,
-                                main_trace_on_quotient_domains,
-                                permutation_trace_on_quotient_domains,
+                            let quotient_domain = quotient_domains[batch[0]];
+                            batched_quotient_values(
+                                batch.iter().map(|&i| chip_data(i, quotient_domain)).collect(),
+                                trace_domains[batch[0]],
+                                quotient_domain,
                                 &packed_perm_challenges,
                                 alpha,
                                 &data.public_values,
@@ -360,39 +425,79 @@ where
                 })
                 .collect::<Vec<_>>()
         });
+        let mut quotient_values = vec![Vec::new(); chips.len()];
+        for (batch, values) in batches.iter().zip_eq(batched_values) {
+            for (&i, values) in batch.iter().zip_eq(values) {
+                quotient_values[i] = values;
+            }
+        }
 
-        // Split the quotient values and commit to them.
+        // Split the quotient values and commit to them. In hiding mode, the chunks are blinded.
         let quotient_domains_and_chunks = quotient_domains
             .into_iter()
             .zip_eq(quotient_values)
             .zip_eq(log_quotient_degrees.iter())
-            .flat_map(|((quotient_domain, quotient_values), log_quotient_degree)| {
+            .zip_eq(log_degrees.iter())
+            .flat_map(|(((quotient_domain, quotient_values), log_quotient_degree), log_degree)| {
                 let quotient_degree = 1 << *log_quotient_degree;
                 let quotient_flat = RowMajorMatrix::new_col(quotient_values).flatten_to_base();
                 let quotient_chunks = quotient_domain.split_evals(quotient_degree, quotient_flat);
                 let qc_domains = quotient_domain.split_domains(quotient_degree);
-                qc_domains.into_iter().zip_eq(quotient_chunks)
+                match zk.as_ref() {
+                    Some(zk) => blind_quotient_chunks(
+                        qc_domains,
+                        quotient_chunks,
+                        zk.log_blinding_factor(*log_degree),
+                    ),
+                    None => qc_domains.into_iter().zip_eq(quotient_chunks).collect(),
+                }
             })
             .collect::<Vec<_>>();
 
         let num_quotient_chunks = quotient_domains_and_chunks.len();
         assert_eq!(
             num_quotient_chunks,
-            chips.iter().map(|c| 1 << c.log_quotient_degree()).sum::<usize>()
+            log_quotient_degrees.iter().map(|log_degree| 1 << log_degree).sum::<usize>()
         );
 
         let (quotient_commit, quotient_data) = tracing::debug_span!("commit to quotient traces")
             .in_scope(|| pcs.commit(quotient_domains_and_chunks));
         challenger.observe(quotient_commit.clone());
 
+        // In hiding mode, commit to the masking polynomial of the opening proof.
+        let masking = zk.map(|zk| {
+            let preprocessed_log_degrees =
+                pk.traces.iter().map(|trace| log2_strict_usize(trace.height()));
+            let log_degree = zk.masking_log_degree(preprocessed_log_degrees, &log_degrees);
+            let width = SC::Challenge::D;
+            let values = RowMajorMatrix::new(random_values(width << log_degree), width);
+            let domain = pcs.natural_domain_for_degree(1 << log_degree);
+            tracing::debug_span!("commit to masking polynomial")
+                .in_scope(|| pcs.commit(vec![(domain, values)]))
+        });
+        if let Some((masking_commit, _)) = &masking {
+            challenger.observe(masking_commit.clone());
+        }
+
         // Compute the quotient argument.
         let zeta: SC::Challenge = challenger.sample_ext_element();
 
+        // Preprocessed traces of alternative chips that are not used by this shard are not opened.
+        let mut preprocessed_in_shard = vec![false; pk.traces.len()];
+        for chip in chips.iter() {
+            if let Some(&index) = pk.chip_ordering.get(&chip.name()) {
+                preprocessed_in_shard[index] = true;
+            }
+        }
         let preprocessed_opening_points =
             tracing::debug_span!("compute preprocessed opening points").in_scope(|| {
                 pk.traces
                     .iter()
-                    .map(|trace| {
+                    .zip_eq(preprocessed_in_shard)
+                    .map(|(trace, in_shard)| {
+                        if !in_shard {
+                            return vec![];
+                        }
                         let domain = pcs.natural_domain_for_degree(trace.height());
                         vec![zeta, domain.next_point(zeta).unwrap()]
                     })
@@ -411,27 +516,31 @@ where
         let quotient_opening_points =
             (0..num_quotient_chunks).map(|_| vec![zeta]).collect::<Vec<_>>();
 
-        let (openings, opening_proof) = tracing::debug_span!("open multi batches").in_scope(|| {
-            pcs.open(
-                vec![
-                    (&pk.data, preprocessed_opening_points),
-                    (&data.main_data, trace_opening_points.clone()),
-                    (&permutation_data, trace_opening_points),
-                    (&quotient_data, quotient_opening_points),
-                ],
-                challenger,
-            )
-        });
+        let mut rounds = vec![
+            (&pk.data, preprocessed_opening_points),
+            (&data.main_data, trace_opening_points.clone()),
+            (&permutation_data, trace_opening_points),
+            (&quotient_data, quotient_opening_points),
+        ];
+        if let Some((_, masking_data)) = &masking {
+            rounds.push((masking_data, vec![vec![zeta]]));
+        }
+        let (mut openings, opening_proof) =
+            tracing::debug_span!("open multi batches").in_scope(|| pcs.open(rounds, challenger));
 
-        // Collect the opened values for each chip.
+        // Collect the opened values of the masking polynomial, and for each chip.
+        let masking = masking.map(|(commit, _)| {
+            let mut values = openings.pop().unwrap();
+            MaskingOpening { commit, values: values.pop().unwrap().pop().unwrap() }
+        });
         let [preprocessed_values, main_values, permutation_values, mut quotient_values] =
             openings.try_into().unwrap();
         assert!(main_values.len() == chips.len());
         let preprocessed_opened_values = preprocessed_values
             .into_iter()
-            .map(|op| {
-                let [local, next] = op.try_into().unwrap();
-                AirOpenedValues { local, next }
+            .map(|op| match <[_; 2]>::try_from(op) {
+                Ok([local, next]) => AirOpenedValues { local, next },
+                Err(_) => AirOpenedValues { local: vec![], next: vec![] },
             })
             .collect::<Vec<_>>();
 
@@ -490,6 +599,7 @@ where
             opening_proof,
             chip_ordering: data.chip_ordering,
             public_values: data.public_values,
+            masking,
         })
     }
 
@@ -514,14 +624,58 @@ where
         // Observe the preprocessed commitment.
         pk.observe_into(challenger);
 
-        // Generate and commit the traces for each shard.
-        let shard_data = records
+        // Generate and commit the traces for each shard, keeping a copy if they will be dumped and
+        // counting their lookups and checking their padding and multiplicities if they will be
+        // checked.
+        #[allow(clippy::type_complexity)]
+        let (shard_data, (dumped_traces, (balances, (leaks, issues)))): (
+            Vec<_>,
+            (Vec<_>, (Vec<_>, (Vec<_>, Vec<_>))),
+        ) = records
             .into_par_iter()
             .map(|record| {
                 let named_traces = self.generate_traces(&record);
-                self.commit(record, named_traces)
+                let dumped = opts
+                    .dump_traces_dir
+                    .as_ref()
+                    .map(|_| (named_traces.clone(), record.public_values::<Val<SC>>()));
+                let balance = opts.debug_lookups.then(|| {
+                    let mut balance = LookupBalance::new();
+                    balance.add_shard(self.machine(), pk, &named_traces);
+                    balance
+                });
+                let leaks = if opts.debug_padding {
+                    check_padding_rows(self.machine(), pk, &record, &named_traces)
+                } else {
+                    Vec::new()
+                };
+                let issues = if opts.debug_multiplicities {
+                    check_multiplicities(self.machine(), pk, &named_traces)
+                } else {
+                    Vec::new()
+                };
+                (self.commit(record, named_traces), (dumped, (balance, (leaks, issues))))
             })
-            .collect::<Vec<_>>();
+            .unzip();
+
+        // Check that the multiplicities of all shards cannot wrap around before proving them.
+        let issues = issues.into_iter().flatten().collect::<Vec<_>>();
+        if !issues.is_empty() {
+            return Err(CpuProverError::BadMultiplicities(BadMultiplicities(issues)));
+        }
+
+        // Check that the padding rows of all shards are inert before proving them.
+        let leaks = leaks.into_iter().flatten().collect::<Vec<_>>();
+        if !leaks.is_empty() {
+            return Err(CpuProverError::LeakyPadding(LeakyPadding(leaks)));
+        }
+
+        // Check that the lookups balance across all shards before proving them.
+        if opts.debug_lookups {
+            let mut balance = LookupBalance::new();
+            balances.into_iter().flatten().for_each(|shard| balance.merge(shard));
+            balance.check().map_err(CpuProverError::UnbalancedLookups)?;
+        }
 
         // Observe the challenges for each segment.
         tracing::debug_span!("observing all challenges").in_scope(|| {
@@ -531,6 +685,25 @@ where
             });
         });
 
+        // Dump the traces with the challenges that will be used to open them.
+        if let Some(dir) = &opts.dump_traces_dir {
+            for (shard, (traces, public_values)) in dumped_traces.into_iter().flatten().enumerate()
+            {
+                let dump = ShardTraceDump::new(
+                    shard as u32 + 1,
+                    self.machine(),
+                    pk,
+                    &traces,
+                    &public_values,
+                    challenger,
+                );
+                match dump.save(dir) {
+                    Ok(path) => tracing::info!("dumped shard traces to {}", path.display()),
+                    Err(e) => tracing::warn!("failed to dump shard traces: {}", e),
+                }
+            }
+        }
+
         let shard_proofs = tracing::info_span!("prove_shards").in_scope(|| {
             shard_data
                 .into_par_iter()
@@ -544,7 +717,13 @@ where
 
 impl Display for CpuProverError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "DefaultProverError")
+        match self {
+            CpuProverError::UnbalancedLookups(e) => write!(f, "lookups do not balance: {e}"),
+            CpuProverError::LeakyPadding(e) => write!(f, "padding rows are not inert: {e}"),
+            CpuProverError::BadMultiplicities(e) => {
+                write!(f, "multiplicities may wrap around the field: {e}")
+            }
+        }
     }
 }
 
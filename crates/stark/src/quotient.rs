@@ -1,6 +1,6 @@
 use p3_air::Air;
 use p3_commit::PolynomialSpace;
-use p3_field::{AbstractExtensionField, AbstractField, PackedValue};
+use p3_field::{AbstractExtensionField, AbstractField, Field, PackedValue};
 use p3_matrix::{dense::RowMajorMatrixView, stack::VerticalPair, Matrix};
 use p3_maybe_rayon::prelude::*;
 use p3_util::log2_strict_usize;
@@ -12,10 +12,38 @@ use super::{
     Val,
 };
 
+/// The values of column `col` of `matrix` in the `F::Packing::WIDTH` rows starting at `start`,
+/// wrapping around the end of the domain.
+fn load_packed<F: Field>(matrix: &impl Matrix<F>, start: usize, col: usize) -> F::Packing {
+    let height = matrix.height();
+    F::Packing::from_fn(|offset| matrix.get((start + offset) % height, col))
+}
+
+/// The evaluations of the traces of a chip on its quotient domain, and the values its constraints
+/// depend on.
+pub struct QuotientChipData<'a, SC: StarkGenericConfig, A, Mat> {
+    /// The chip.
+    pub chip: &'a Chip<Val<SC>, A>,
+    /// The cumulative sum of the permutation trace of the chip.
+    pub cumulative_sum: SC::Challenge,
+    /// The evaluations of the preprocessed trace, if the chip has one.
+    pub preprocessed_trace_on_quotient_domain: Option<Mat>,
+    /// The evaluations of the main trace.
+    pub main_trace_on_quotient_domain: Mat,
+    /// The evaluations of the permutation trace.
+    pub permutation_trace_on_quotient_domain: Mat,
+}
+
+/// The selectors of the trace domain at a packed row of the quotient domain.
+struct PackedSelectors<P> {
+    is_first_row: P,
+    is_last_row: P,
+    is_transition: P,
+}
+
 /// Computes the quotient values.
 #[allow(clippy::needless_pass_by_value)]
 #[allow(clippy::too_many_arguments)]
-#[allow(clippy::too_many_lines)]
 pub fn quotient_values<SC, A, Mat>(
     chip: &Chip<Val<SC>, A>,
     cumulative_sum: SC::Challenge,
@@ -33,133 +61,192 @@ where
     SC: StarkGenericConfig,
     Mat: Matrix<Val<SC>> + Sync,
 {
-    let quotient_size = quotient_domain.size();
-    let prep_width =
-        preprocessed_trace_on_quotient_domain.as_ref().map_or(1, p3_matrix::Matrix::width);
-    let main_width = main_trace_on_quotient_domain.width();
-    let perm_width = permutation_trace_on_quotient_domain.width();
-    let sels = trace_domain.selectors_on_coset(quotient_domain);
+    let chip = QuotientChipData {
+        chip,
+        cumulative_sum,
+        preprocessed_trace_on_quotient_domain,
+        main_trace_on_quotient_domain,
+        permutation_trace_on_quotient_domain,
+    };
+    batched_quotient_values(
+        vec![chip],
+        trace_domain,
+        quotient_domain,
+        perm_challenges,
+        alpha,
+        public_values,
+    )
+    .pop()
+    .unwrap()
+}
 
+/// Computes the quotient values of chips that share the same trace and quotient domains, in a
+/// single pass over the quotient domain.
+///
+/// The selectors of the trace domain are computed once for all the chips, and the constraints of
+/// every chip are evaluated at a packed row before moving to the next one, so that the coset is
+/// walked once rather than once per chip. Returns the quotient values of each chip, in order.
+pub fn batched_quotient_values<SC, A, Mat>(
+    chips: Vec<QuotientChipData<'_, SC, A, Mat>>,
+    trace_domain: Domain<SC>,
+    quotient_domain: Domain<SC>,
+    perm_challenges: &[PackedChallenge<SC>],
+    alpha: SC::Challenge,
+    public_values: &[Val<SC>],
+) -> Vec<Vec<SC::Challenge>>
+where
+    A: for<'a> Air<ProverConstraintFolder<'a, SC>> + MachineAir<Val<SC>>,
+    SC: StarkGenericConfig,
+    Mat: Matrix<Val<SC>> + Sync,
+{
+    let quotient_size = quotient_domain.size();
     let qdb = log2_strict_usize(quotient_domain.size()) - log2_strict_usize(trace_domain.size());
     let next_step = 1 << qdb;
-
-    let ext_degree = SC::Challenge::D;
+    let packing_width = PackedVal::<SC>::WIDTH;
 
     assert!(
-        quotient_size >= PackedVal::<SC>::WIDTH,
-        "quotient size is too small: got {}, expected at least {} for chip {}",
+        quotient_size >= packing_width,
+        "quotient size is too small: got {}, expected at least {} for chips {:?}",
         quotient_size,
-        PackedVal::<SC>::WIDTH,
-        chip.name()
+        packing_width,
+        chips.iter().map(|data| data.chip.name()).collect::<Vec<_>>()
     );
 
-    (0..quotient_size)
-        .into_par_iter()
-        .step_by(PackedVal::<SC>::WIDTH)
-        .flat_map_iter(|i_start| {
-            let wrap = |i| i % quotient_size;
-            let i_range = i_start..i_start + PackedVal::<SC>::WIDTH;
-
-            let is_first_row = *PackedVal::<SC>::from_slice(&sels.is_first_row[i_range.clone()]);
-            let is_last_row = *PackedVal::<SC>::from_slice(&sels.is_last_row[i_range.clone()]);
-            let is_transition = *PackedVal::<SC>::from_slice(&sels.is_transition[i_range.clone()]);
-            let inv_zeroifier = *PackedVal::<SC>::from_slice(&sels.inv_zeroifier[i_range.clone()]);
-
-            let prep_local: Vec<_> = (0..prep_width)
-                .map(|col| {
-                    PackedVal::<SC>::from_fn(|offset| {
-                        preprocessed_trace_on_quotient_domain
-                            .as_ref()
-                            .map_or(Val::<SC>::zero(), |x| x.get(wrap(i_start + offset), col))
-                    })
-                })
-                .collect();
-            let prep_next: Vec<_> = (0..prep_width)
-                .map(|col| {
-                    PackedVal::<SC>::from_fn(|offset| {
-                        preprocessed_trace_on_quotient_domain
-                            .as_ref()
-                            .map_or(Val::<SC>::zero(), |x| {
-                                x.get(wrap(i_start + next_step + offset), col)
-                            })
-                    })
-                })
-                .collect();
-
-            let local: Vec<_> = (0..main_width)
-                .map(|col| {
-                    PackedVal::<SC>::from_fn(|offset| {
-                        main_trace_on_quotient_domain.get(wrap(i_start + offset), col)
-                    })
-                })
-                .collect();
-            let next: Vec<_> = (0..main_width)
-                .map(|col| {
-                    PackedVal::<SC>::from_fn(|offset| {
-                        main_trace_on_quotient_domain.get(wrap(i_start + next_step + offset), col)
-                    })
-                })
-                .collect();
-
-            let perm_local: Vec<_> = (0..perm_width)
-                .step_by(ext_degree)
-                .map(|col| {
-                    PackedChallenge::<SC>::from_base_fn(|i| {
-                        PackedVal::<SC>::from_fn(|offset| {
-                            permutation_trace_on_quotient_domain
-                                .get(wrap(i_start + offset), col + i)
-                        })
-                    })
-                })
-                .collect();
-
-            let perm_next: Vec<_> = (0..perm_width)
-                .step_by(ext_degree)
-                .map(|col| {
-                    PackedChallenge::<SC>::from_base_fn(|i| {
-                        PackedVal::<SC>::from_fn(|offset| {
-                            permutation_trace_on_quotient_domain
-                                .get(wrap(i_start + next_step + offset), col + i)
-                        })
-                    })
-                })
-                .collect();
-
-            let accumulator = PackedChallenge::<SC>::zero();
-            let mut folder = ProverConstraintFolder {
-                preprocessed: VerticalPair::new(
-                    RowMajorMatrixView::new_row(&prep_local),
-                    RowMajorMatrixView::new_row(&prep_next),
-                ),
-                main: VerticalPair::new(
-                    RowMajorMatrixView::new_row(&local),
-                    RowMajorMatrixView::new_row(&next),
-                ),
-                perm: VerticalPair::new(
-                    RowMajorMatrixView::new_row(&perm_local),
-                    RowMajorMatrixView::new_row(&perm_next),
-                ),
+    let num_chips = chips.len();
+
+    let sels = trace_domain.selectors_on_coset(quotient_domain);
+
+    // The values are laid out by packed row: the values of each chip at the rows of the packed row
+    // follow each other, and are split by chip at the end.
+    let mut values = vec![SC::Challenge::zero(); quotient_size * num_chips];
+    values.par_chunks_mut(packing_width * num_chips).enumerate().for_each(|(i, chunk)| {
+        let i_start = i * packing_width;
+        let i_range = i_start..i_start + packing_width;
+
+        let selectors = PackedSelectors {
+            is_first_row: *PackedVal::<SC>::from_slice(&sels.is_first_row[i_range.clone()]),
+            is_last_row: *PackedVal::<SC>::from_slice(&sels.is_last_row[i_range.clone()]),
+            is_transition: *PackedVal::<SC>::from_slice(&sels.is_transition[i_range.clone()]),
+        };
+        let inv_zeroifier = *PackedVal::<SC>::from_slice(&sels.inv_zeroifier[i_range]);
+
+        for (chip, values) in chips.iter().zip(chunk.chunks_exact_mut(packing_width)) {
+            let constraints = eval_packed_row(
+                chip,
+                i_start,
+                next_step,
+                &selectors,
                 perm_challenges,
-                cumulative_sum,
-                is_first_row,
-                is_last_row,
-                is_transition,
                 alpha,
-                accumulator,
                 public_values,
-            };
-            chip.eval(&mut folder);
+            );
 
             // quotient(x) = constraints(x) / Z_H(x)
-            let quotient = folder.accumulator * inv_zeroifier;
+            let quotient = constraints * inv_zeroifier;
 
             // "Transpose" D packed base coefficients into WIDTH scalar extension coefficients.
-            (0..PackedVal::<SC>::WIDTH).map(move |idx_in_packing| {
+            for (idx_in_packing, value) in values.iter_mut().enumerate() {
                 let quotient_value = (0..<SC::Challenge as AbstractExtensionField<Val<SC>>>::D)
                     .map(|coeff_idx| quotient.as_base_slice()[coeff_idx].as_slice()[idx_in_packing])
                     .collect::<Vec<_>>();
-                SC::Challenge::from_base_slice(&quotient_value)
-            })
+                *value = SC::Challenge::from_base_slice(&quotient_value);
+            }
+        }
+    });
+
+    if num_chips == 1 {
+        return vec![values];
+    }
+    let row_len = packing_width * num_chips;
+    (0..num_chips)
+        .into_par_iter()
+        .map(|chip| {
+            values
+                .chunks_exact(row_len)
+                .flat_map(|row| &row[chip * packing_width..(chip + 1) * packing_width])
+                .copied()
+                .collect()
         })
         .collect()
 }
+
+/// Evaluates the constraints of a chip, folded with `alpha`, at the packed row starting at
+/// `i_start`.
+fn eval_packed_row<SC, A, Mat>(
+    chip: &QuotientChipData<'_, SC, A, Mat>,
+    i_start: usize,
+    next_step: usize,
+    selectors: &PackedSelectors<PackedVal<SC>>,
+    perm_challenges: &[PackedChallenge<SC>],
+    alpha: SC::Challenge,
+    public_values: &[Val<SC>],
+) -> PackedChallenge<SC>
+where
+    A: for<'a> Air<ProverConstraintFolder<'a, SC>> + MachineAir<Val<SC>>,
+    SC: StarkGenericConfig,
+    Mat: Matrix<Val<SC>> + Sync,
+{
+    let ext_degree = SC::Challenge::D;
+    let preprocessed = chip.preprocessed_trace_on_quotient_domain.as_ref();
+    let main = &chip.main_trace_on_quotient_domain;
+    let permutation = &chip.permutation_trace_on_quotient_domain;
+    let prep_width = preprocessed.map_or(1, Matrix::width);
+    let main_width = main.width();
+    let perm_width = permutation.width();
+
+    let prep_local: Vec<_> = (0..prep_width)
+        .map(|col| preprocessed.map_or(PackedVal::<SC>::zero(), |x| load_packed(x, i_start, col)))
+        .collect();
+    let prep_next: Vec<_> = (0..prep_width)
+        .map(|col| {
+            preprocessed
+                .map_or(PackedVal::<SC>::zero(), |x| load_packed(x, i_start + next_step, col))
+        })
+        .collect();
+
+    let local: Vec<_> = (0..main_width).map(|col| load_packed(main, i_start, col)).collect();
+    let next: Vec<_> =
+        (0..main_width).map(|col| load_packed(main, i_start + next_step, col)).collect();
+
+    let perm_local: Vec<_> = (0..perm_width)
+        .step_by(ext_degree)
+        .map(|col| {
+            PackedChallenge::<SC>::from_base_fn(|i| load_packed(permutation, i_start, col + i))
+        })
+        .collect();
+
+    let perm_next: Vec<_> = (0..perm_width)
+        .step_by(ext_degree)
+        .map(|col| {
+            PackedChallenge::<SC>::from_base_fn(|i| {
+                load_packed(permutation, i_start + next_step, col + i)
+            })
+        })
+        .collect();
+
+    let accumulator = PackedChallenge::<SC>::zero();
+    let mut folder = ProverConstraintFolder {
+        preprocessed: VerticalPair::new(
+            RowMajorMatrixView::new_row(&prep_local),
+            RowMajorMatrixView::new_row(&prep_next),
+        ),
+        main: VerticalPair::new(
+            RowMajorMatrixView::new_row(&local),
+            RowMajorMatrixView::new_row(&next),
+        ),
+        perm: VerticalPair::new(
+            RowMajorMatrixView::new_row(&perm_local),
+            RowMajorMatrixView::new_row(&perm_next),
+        ),
+        perm_challenges,
+        cumulative_sum: chip.cumulative_sum,
+        is_first_row: selectors.is_first_row,
+        is_last_row: selectors.is_last_row,
+        is_transition: selectors.is_transition,
+        alpha,
+        accumulator,
+        public_values,
+    };
+    chip.chip.eval(&mut folder);
+    folder.accumulator
+}
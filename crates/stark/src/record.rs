@@ -5,7 +5,7 @@ use p3_field::AbstractField;
 /// A record that can be proven by a machine.
 pub trait MachineRecord: Default + Sized + Send + Sync + Clone {
     /// The configuration of the machine.
-    type Config: 'static + Copy + Send + Sync;
+    type Config: 'static + Clone + Send + Sync;
 
     /// The statistics of the record.
     fn stats(&self) -> HashMap<String, usize>;
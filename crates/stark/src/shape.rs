@@ -0,0 +1,196 @@
+//! Shapes of shard proofs, and their compatibility with verifying keys and machines.
+//!
+//! A verifier can only check a shard proof whose chips are part of its machine, and whose
+//! preprocessed chips have the trace heights committed to in the verifying key. A proof produced
+//! by a different version of the prover, or for a different program, usually fails verification
+//! much later with an opaque error. Checking the [`ProofShape`] of the proof first reports the
+//! chips that do not match instead.
+
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
+use p3_util::log2_strict_usize;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    air::MachineAir, ShardProof, StarkGenericConfig, StarkMachine, StarkVerifyingKey, Val,
+};
+
+/// The shape of a shard proof: the chips it contains, their trace heights, and the number of its
+/// public values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofShape {
+    /// The name and log degree of each chip, in the order of the openings of the proof.
+    pub chips: Vec<(String, usize)>,
+    /// The number of public values.
+    pub num_public_values: usize,
+}
+
+/// The ways in which a [`ProofShape`] is incompatible with a verifying key or a machine.
+///
+/// Every mismatch is listed, so that all of them can be reported at once.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProofShapeError {
+    /// The chips of the proof that are not part of the machine.
+    pub unknown_chips: Vec<String>,
+    /// The preprocessed chips of the verifying key that are missing from the proof.
+    pub missing_chips: Vec<String>,
+    /// The preprocessed chips whose log degree in the proof differs from the verifying key, with
+    /// the expected and actual log degrees.
+    pub log_degree_mismatches: Vec<(String, usize, usize)>,
+    /// The minimum and actual number of public values, if the proof has too few.
+    pub public_values_mismatch: Option<(usize, usize)>,
+}
+
+impl<SC: StarkGenericConfig> ShardProof<SC> {
+    /// The shape of this proof.
+    ///
+    /// Chips whose index in the chip ordering has no opening are left out, since they have no
+    /// log degree.
+    #[must_use]
+    pub fn shape(&self) -> ProofShape {
+        let chips = self
+            .chip_ordering
+            .iter()
+            .sorted_by_key(|(_, &index)| index)
+            .filter_map(|(name, &index)| {
+                let opening = self.opened_values.chips.get(index)?;
+                Some((name.clone(), opening.log_degree))
+            })
+            .collect();
+        ProofShape { chips, num_public_values: self.public_values.len() }
+    }
+}
+
+impl ProofShape {
+    /// The log degree of the chip with the given name, if the shape contains it.
+    #[must_use]
+    pub fn log_degree(&self, name: &str) -> Option<usize> {
+        self.chips.iter().find(|(chip, _)| chip == name).map(|(_, log_degree)| *log_degree)
+    }
+}
+
+impl<SC: StarkGenericConfig> StarkVerifyingKey<SC> {
+    /// Checks that a proof of the given shape contains every preprocessed chip of this key, with
+    /// the trace height committed to in the key.
+    pub fn accepts(&self, shape: &ProofShape) -> Result<(), ProofShapeError> {
+        let mut error = ProofShapeError::default();
+        for (name, _, dimensions) in self.chip_information.iter() {
+            let expected = log2_strict_usize(dimensions.height);
+            match shape.log_degree(name) {
+                None => error.missing_chips.push(name.clone()),
+                Some(actual) if actual != expected => {
+                    error.log_degree_mismatches.push((name.clone(), expected, actual));
+                }
+                Some(_) => {}
+            }
+        }
+        error.into_result()
+    }
+}
+
+impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> StarkMachine<SC, A> {
+    /// Checks that a proof of the given shape can be verified by this machine with the given
+    /// verifying key.
+    ///
+    /// In addition to [`StarkVerifyingKey::accepts`], every chip of the proof must be a chip of
+    /// the machine, and the proof must have at least as many public values as the machine uses.
+    pub fn check_shape(
+        &self,
+        vk: &StarkVerifyingKey<SC>,
+        shape: &ProofShape,
+    ) -> Result<(), ProofShapeError> {
+        let mut error = vk.accepts(shape).err().unwrap_or_default();
+        error.unknown_chips = shape
+            .chips
+            .iter()
+            .filter(|(name, _)| !self.chips().iter().any(|chip| chip.name() == *name))
+            .map(|(name, _)| name.clone())
+            .collect();
+        if shape.num_public_values < self.num_pv_elts() {
+            error.public_values_mismatch = Some((self.num_pv_elts(), shape.num_public_values));
+        }
+        error.into_result()
+    }
+}
+
+impl ProofShapeError {
+    /// Whether there is no mismatch.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.unknown_chips.is_empty()
+            && self.missing_chips.is_empty()
+            && self.log_degree_mismatches.is_empty()
+            && self.public_values_mismatch.is_none()
+    }
+
+    fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl Display for ProofShapeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut mismatches = Vec::new();
+        if !self.unknown_chips.is_empty() {
+            mismatches.push(format!("unknown chips [{}]", self.unknown_chips.join(", ")));
+        }
+        if !self.missing_chips.is_empty() {
+            mismatches
+                .push(format!("missing preprocessed chips [{}]", self.missing_chips.join(", ")));
+        }
+        if !self.log_degree_mismatches.is_empty() {
+            let chips = self
+                .log_degree_mismatches
+                .iter()
+                .map(|(name, expected, actual)| {
+                    format!("{name}: expected {expected}, got {actual}")
+                })
+                .join(", ");
+            mismatches.push(format!("preprocessed log degree mismatches [{chips}]"));
+        }
+        if let Some((expected, actual)) = self.public_values_mismatch {
+            mismatches.push(format!("expected at least {expected} public values, got {actual}"));
+        }
+        write!(f, "incompatible proof shape: {}", mismatches.join("; "))
+    }
+}
+
+impl std::error::Error for ProofShapeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proof_shape_error_display() {
+        let error = ProofShapeError {
+            unknown_chips: vec!["Foo".to_string()],
+            missing_chips: vec!["Byte".to_string()],
+            log_degree_mismatches: vec![("Program".to_string(), 10, 12)],
+            public_values_mismatch: Some((8, 4)),
+        };
+        assert!(!error.is_empty());
+        assert_eq!(
+            error.to_string(),
+            "incompatible proof shape: unknown chips [Foo]; missing preprocessed chips [Byte]; \
+             preprocessed log degree mismatches [Program: expected 10, got 12]; expected at least \
+             8 public values, got 4"
+        );
+        assert!(ProofShapeError::default().is_empty());
+    }
+
+    #[test]
+    fn test_proof_shape_log_degree() {
+        let shape = ProofShape {
+            chips: vec![("CPU".to_string(), 20), ("Program".to_string(), 12)],
+            num_public_values: 8,
+        };
+        assert_eq!(shape.log_degree("Program"), Some(12));
+        assert_eq!(shape.log_degree("Byte"), None);
+    }
+}
@@ -58,6 +58,14 @@ pub struct ShardOpenedValues<T: Serialize> {
     pub chips: Vec<ChipOpenedValues<T>>,
 }
 
+/// The commitment to the masking polynomial of a shard proof in hiding mode, and its opening at the
+/// out-of-domain point.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaskingOpening<C, T> {
+    pub commit: C,
+    pub values: Vec<T>,
+}
+
 /// The maximum number of elements that can be stored in the public values vec.  Both SP1 and
 /// recursive proofs need to pad their public values vec to this length.  This is required since the
 /// recursion verification program expects the public values vec to be fixed length.
@@ -71,6 +79,7 @@ pub struct ShardProof<SC: StarkGenericConfig> {
     pub opening_proof: OpeningProof<SC>,
     pub chip_ordering: HashMap<String, usize>,
     pub public_values: Vec<Val<SC>>,
+    pub masking: Option<MaskingOpening<Com<SC>, Challenge<SC>>>,
 }
 
 impl<SC: StarkGenericConfig> Debug for ShardProof<SC> {
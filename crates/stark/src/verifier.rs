@@ -9,13 +9,19 @@ use p3_air::{Air, BaseAir};
 use p3_challenger::{CanObserve, FieldChallenger};
 use p3_commit::{LagrangeSelectors, Pcs, PolynomialSpace};
 use p3_field::{AbstractExtensionField, AbstractField};
+use p3_util::log2_strict_usize;
 
 use super::{
     folder::VerifierConstraintFolder,
     types::{AirOpenedValues, ChipOpenedValues, ShardCommitment, ShardProof},
     Domain, OpeningError, StarkGenericConfig, StarkVerifyingKey, Val,
 };
-use crate::{air::MachineAir, MachineChip};
+use crate::{
+    air::MachineAir, committed_chunk_domain, log_blinding_factor, log_quotient_degree, MachineChip,
+    StarkMachine,
+};
+
+pub mod test_utils;
 
 /// A verifier for a collection of air chips.
 pub struct Verifier<SC, A>(PhantomData<SC>, PhantomData<A>);
@@ -24,9 +30,8 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
     /// Verify a proof for a collection of air chips.
     #[allow(clippy::too_many_lines)]
     pub fn verify_shard(
-        config: &SC,
+        machine: &StarkMachine<SC, A>,
         vk: &StarkVerifyingKey<SC>,
-        chips: &[&MachineChip<SC, A>],
         challenger: &mut SC::Challenger,
         proof: &ShardProof<SC>,
     ) -> Result<(), VerificationError<SC>>
@@ -41,25 +46,87 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
             opening_proof,
             chip_ordering,
             public_values,
+            masking,
             ..
         } = proof;
 
+        let config = machine.config();
+        let machine_chips = machine.chips();
         let pcs = config.pcs();
 
+        // Chips without events are omitted from the proof. Check that the chip ordering assigns
+        // each opening to exactly one chip of the machine, that every preprocessed chip is present
+        // unless it is one of a group of alternatives, and that exactly one chip of every such
+        // group is present.
+        let chip_presence = machine_chips
+            .iter()
+            .map(|chip| chip_ordering.contains_key(&chip.name()))
+            .collect::<Vec<_>>();
+        let chips = machine_chips
+            .iter()
+            .filter(|chip| chip_ordering.contains_key(&chip.name()))
+            .sorted_by_key(|chip| chip_ordering[&chip.name()])
+            .collect::<Vec<_>>();
+        if chips.len() != chip_ordering.len()
+            || chips.iter().enumerate().any(|(i, chip)| chip_ordering[&chip.name()] != i)
+            || vk.chip_information.iter().any(|(name, _, _)| {
+                !chip_ordering.contains_key(name) && !machine.is_alternative_chip(name)
+            })
+            || !machine.uses_one_alternative(chip_ordering)
+        {
+            return Err(VerificationError::InvalidChipOrdering);
+        }
+
         if chips.len() != opened_values.chips.len() {
             return Err(VerificationError::ChipOpeningLengthMismatch);
         }
 
+        // Observe the chip set of the machine, and which of its chips are present in the shard.
+        challenger.observe_slice(machine.chip_set_id());
+        challenger.observe_slice(
+            &chip_presence.into_iter().map(Val::<SC>::from_bool).collect::<Vec<_>>(),
+        );
+
         let log_degrees = opened_values.chips.iter().map(|val| val.log_degree).collect::<Vec<_>>();
 
-        let log_quotient_degrees =
-            chips.iter().map(|chip| chip.log_quotient_degree()).collect::<Vec<_>>();
+        let zk = config.zk();
+        let log_quotient_degrees = chips
+            .iter()
+            .zip_eq(log_degrees.iter())
+            .map(|(chip, log_degree)| {
+                log_quotient_degree(zk.as_ref(), chip.log_quotient_degree(), *log_degree)
+            })
+            .collect::<Vec<_>>();
 
         let trace_domains = log_degrees
             .iter()
             .map(|log_degree| pcs.natural_domain_for_degree(1 << log_degree))
             .collect::<Vec<_>>();
 
+        // In hiding mode, the main and permutation traces are committed over a larger domain.
+        let committed_domains = log_degrees
+            .iter()
+            .map(|log_degree| {
+                let log_blinding_factor = log_blinding_factor(zk.as_ref(), *log_degree);
+                pcs.natural_domain_for_degree(1 << (log_degree + log_blinding_factor))
+            })
+            .collect::<Vec<_>>();
+
+        // Proofs in hiding mode, and only those, open a masking polynomial as tall as the tallest
+        // committed matrix.
+        let masking = match (zk.as_ref(), masking) {
+            (None, None) => None,
+            (Some(zk), Some(masking)) if masking.values.len() == SC::Challenge::D => {
+                let preprocessed_log_degrees = vk
+                    .chip_information
+                    .iter()
+                    .map(|(_, _, dimensions)| log2_strict_usize(dimensions.height));
+                let log_degree = zk.masking_log_degree(preprocessed_log_degrees, &log_degrees);
+                Some((masking, pcs.natural_domain_for_degree(1 << log_degree)))
+            }
+            _ => return Err(VerificationError::InvalidMasking),
+        };
+
         let ShardCommitment { main_commit, permutation_commit, quotient_commit } = commitment;
 
         let permutation_challenges =
@@ -69,8 +136,11 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
 
         let alpha = challenger.sample_ext_element::<SC::Challenge>();
 
-        // Observe the quotient commitments.
+        // Observe the quotient commitments, and the masking commitment in hiding mode.
         challenger.observe(quotient_commit.clone());
+        if let Some((masking, _)) = &masking {
+            challenger.observe(masking.commit.clone());
+        }
 
         let zeta = challenger.sample_ext_element::<SC::Challenge>();
 
@@ -78,7 +148,10 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
             .chip_information
             .iter()
             .map(|(name, domain, _)| {
-                let i = chip_ordering[name];
+                // The preprocessed traces of unused alternatives are not opened.
+                let Some(&i) = chip_ordering.get(name) else {
+                    return (*domain, vec![]);
+                };
                 let values = opened_values.chips[i].preprocessed.clone();
                 (
                     *domain,
@@ -89,10 +162,11 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
 
         let main_domains_points_and_opens = trace_domains
             .iter()
+            .zip_eq(committed_domains.iter())
             .zip_eq(opened_values.chips.iter())
-            .map(|(domain, values)| {
+            .map(|((domain, committed_domain), values)| {
                 (
-                    *domain,
+                    *committed_domain,
                     vec![
                         (zeta, values.main.local.clone()),
                         (domain.next_point(zeta).unwrap(), values.main.next.clone()),
@@ -103,10 +177,11 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
 
         let perm_domains_points_and_opens = trace_domains
             .iter()
+            .zip_eq(committed_domains.iter())
             .zip_eq(opened_values.chips.iter())
-            .map(|(domain, values)| {
+            .map(|((domain, committed_domain), values)| {
                 (
-                    *domain,
+                    *committed_domain,
                     vec![
                         (zeta, values.permutation.local.clone()),
                         (domain.next_point(zeta).unwrap(), values.permutation.next.clone()),
@@ -117,8 +192,8 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
 
         let quotient_chunk_domains = trace_domains
             .iter()
-            .zip_eq(log_degrees)
-            .zip_eq(log_quotient_degrees)
+            .zip_eq(log_degrees.iter().copied())
+            .zip_eq(log_quotient_degrees.iter().copied())
             .map(|((domain, log_degree), log_quotient_degree)| {
                 let quotient_degree = 1 << log_quotient_degree;
                 let quotient_domain =
@@ -127,40 +202,49 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
             })
             .collect::<Vec<_>>();
 
+        // In hiding mode, the quotient chunks are committed over a larger domain.
         let quotient_domains_points_and_opens = proof
             .opened_values
             .chips
             .iter()
             .zip_eq(quotient_chunk_domains.iter())
-            .flat_map(|(values, qc_domains)| {
-                values
-                    .quotient
-                    .iter()
-                    .zip_eq(qc_domains)
-                    .map(move |(values, q_domain)| (*q_domain, vec![(zeta, values.clone())]))
+            .zip_eq(log_degrees.iter())
+            .flat_map(|((values, qc_domains), log_degree)| {
+                let log_blinding_factor = log_blinding_factor(zk.as_ref(), *log_degree);
+                values.quotient.iter().zip_eq(qc_domains).map(move |(values, q_domain)| {
+                    let domain = committed_chunk_domain(*q_domain, log_blinding_factor);
+                    (domain, vec![(zeta, values.clone())])
+                })
             })
             .collect::<Vec<_>>();
 
+        let mut rounds = vec![
+            (vk.commit.clone(), preprocessed_domains_points_and_opens),
+            (main_commit.clone(), main_domains_points_and_opens),
+            (permutation_commit.clone(), perm_domains_points_and_opens),
+            (quotient_commit.clone(), quotient_domains_points_and_opens),
+        ];
+        if let Some((masking, domain)) = masking {
+            rounds.push((
+                masking.commit.clone(),
+                vec![(domain, vec![(zeta, masking.values.clone())])],
+            ));
+        }
         config
             .pcs()
-            .verify(
-                vec![
-                    (vk.commit.clone(), preprocessed_domains_points_and_opens),
-                    (main_commit.clone(), main_domains_points_and_opens),
-                    (permutation_commit.clone(), perm_domains_points_and_opens),
-                    (quotient_commit.clone(), quotient_domains_points_and_opens),
-                ],
-                opening_proof,
-                challenger,
-            )
+            .verify(rounds, opening_proof, challenger)
             .map_err(|e| VerificationError::InvalidopeningArgument(e))?;
 
         // Verify the constrtaint evaluations.
-        for (chip, trace_domain, qc_domains, values) in
-            izip!(chips.iter(), trace_domains, quotient_chunk_domains, opened_values.chips.iter(),)
-        {
+        for (chip, trace_domain, qc_domains, values, log_quotient_degree) in izip!(
+            chips.iter(),
+            trace_domains,
+            quotient_chunk_domains,
+            opened_values.chips.iter(),
+            log_quotient_degrees,
+        ) {
             // Verify the shape of the opening arguments matches the expected values.
-            Self::verify_opening_shape(chip, values)
+            Self::verify_opening_shape(chip, values, 1 << log_quotient_degree)
                 .map_err(|e| VerificationError::OpeningShapeError(chip.name(), e))?;
             // Verify the constraint evaluation.
             Self::verify_constraints(
@@ -182,6 +266,7 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
     fn verify_opening_shape(
         chip: &MachineChip<SC, A>,
         opening: &ChipOpenedValues<SC::Challenge>,
+        quotient_width: usize,
     ) -> Result<(), OpeningShapeError> {
         // Verify that the preprocessed width matches the expected value for the chip.
         if opening.preprocessed.local.len() != chip.preprocessed_width() {
@@ -226,9 +311,9 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
         }
 
         // Verift that the number of quotient chunks matches the expected value for the chip.
-        if opening.quotient.len() != chip.quotient_width() {
+        if opening.quotient.len() != quotient_width {
             return Err(OpeningShapeError::QuotientWidthMismatch(
-                chip.quotient_width(),
+                quotient_width,
                 opening.quotient.len(),
             ));
         }
@@ -400,6 +485,12 @@ pub enum VerificationError<SC: StarkGenericConfig> {
     MissingCpuChip,
     /// The length of the chip opening does not match the expected length.
     ChipOpeningLengthMismatch,
+    /// The chip ordering does not assign each opening to a distinct chip of the machine, or omits
+    /// a preprocessed chip.
+    InvalidChipOrdering,
+    /// The masking polynomial is missing in hiding mode, present otherwise, or has an opening of
+    /// the wrong width.
+    InvalidMasking,
 }
 
 impl Debug for OpeningShapeError {
@@ -450,6 +541,12 @@ impl<SC: StarkGenericConfig> Debug for VerificationError<SC> {
             VerificationError::ChipOpeningLengthMismatch => {
                 write!(f, "Chip opening length mismatch")
             }
+            VerificationError::InvalidChipOrdering => {
+                write!(f, "Invalid chip ordering")
+            }
+            VerificationError::InvalidMasking => {
+                write!(f, "Invalid masking polynomial")
+            }
         }
     }
 }
@@ -473,6 +570,12 @@ impl<SC: StarkGenericConfig> Display for VerificationError<SC> {
             VerificationError::ChipOpeningLengthMismatch => {
                 write!(f, "Chip opening length mismatch")
             }
+            VerificationError::InvalidChipOrdering => {
+                write!(f, "Invalid chip ordering")
+            }
+            VerificationError::InvalidMasking => {
+                write!(f, "Invalid masking polynomial")
+            }
         }
     }
 }
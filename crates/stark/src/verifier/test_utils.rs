@@ -0,0 +1,156 @@
+//! Helpers to corrupt parts of a [`ShardProof`], for negative tests of verification paths.
+//!
+//! [`shard_proof_corruptions`] lists one corruption of every kind for every chip of a shard, and
+//! [`corrupt_shard_proof`] applies one of them. A sound verifier rejects the proof after any of
+//! them:
+//!
+//! ```ignore
+//! for corruption in shard_proof_corruptions(&proof.shard_proofs[0]) {
+//!     let mut corrupted = proof.clone();
+//!     corrupt_shard_proof(&mut corrupted.shard_proofs[0], &corruption);
+//!     assert!(machine.verify(&vk, &corrupted, &mut challenger.clone()).is_err(), "{corruption}");
+//! }
+//! ```
+
+use core::fmt::Display;
+
+use p3_field::AbstractField;
+
+use crate::{AirOpenedValues, ShardProof, StarkGenericConfig};
+
+/// The trace of a chip whose opened values are corrupted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenedTrace {
+    /// The preprocessed trace.
+    Preprocessed,
+    /// The main trace.
+    Main,
+    /// The permutation trace.
+    Permutation,
+}
+
+/// A corruption of one part of a [`ShardProof`]. Chips are referred to by their index in the
+/// opened values of the shard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofCorruption {
+    /// Adds one to an element of a quotient chunk.
+    QuotientChunk {
+        /// The index of the chip.
+        chip: usize,
+        /// The index of the chunk.
+        chunk: usize,
+        /// The index of the element in the chunk.
+        index: usize,
+    },
+    /// Adds one to an opened value of a trace.
+    OpenedValue {
+        /// The index of the chip.
+        chip: usize,
+        /// The trace the value was opened from.
+        trace: OpenedTrace,
+        /// Whether the value was opened at the next row rather than the local one.
+        next: bool,
+        /// The column of the value.
+        column: usize,
+    },
+    /// Adds one to the cumulative sum of a chip.
+    CumulativeSum {
+        /// The index of the chip.
+        chip: usize,
+    },
+    /// Exchanges the positions of two chips in the chip ordering.
+    SwapChips(String, String),
+    /// Removes a chip from the chip ordering.
+    RemoveChip(String),
+}
+
+impl Display for ProofCorruption {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ProofCorruption::QuotientChunk { chip, chunk, index } => {
+                write!(f, "quotient chunk {chunk} of chip {chip}, element {index}")
+            }
+            ProofCorruption::OpenedValue { chip, trace, next, column } => {
+                let row = if *next { "next" } else { "local" };
+                write!(f, "{trace:?} opened value of chip {chip}, {row} row, column {column}")
+            }
+            ProofCorruption::CumulativeSum { chip } => write!(f, "cumulative sum of chip {chip}"),
+            ProofCorruption::SwapChips(a, b) => write!(f, "chip ordering of {a} and {b} swapped"),
+            ProofCorruption::RemoveChip(name) => write!(f, "chip {name} removed from the ordering"),
+        }
+    }
+}
+
+/// Applies a corruption to a shard proof.
+///
+/// # Panics
+///
+/// Panics if the corruption refers to a chip, chunk or column that the proof does not have.
+pub fn corrupt_shard_proof<SC: StarkGenericConfig>(
+    proof: &mut ShardProof<SC>,
+    corruption: &ProofCorruption,
+) {
+    match corruption {
+        ProofCorruption::QuotientChunk { chip, chunk, index } => {
+            proof.opened_values.chips[*chip].quotient[*chunk][*index] += SC::Challenge::one();
+        }
+        ProofCorruption::OpenedValue { chip, trace, next, column } => {
+            let values = &mut proof.opened_values.chips[*chip];
+            let values = match trace {
+                OpenedTrace::Preprocessed => &mut values.preprocessed,
+                OpenedTrace::Main => &mut values.main,
+                OpenedTrace::Permutation => &mut values.permutation,
+            };
+            let row = if *next { &mut values.next } else { &mut values.local };
+            row[*column] += SC::Challenge::one();
+        }
+        ProofCorruption::CumulativeSum { chip } => {
+            proof.opened_values.chips[*chip].cumulative_sum += SC::Challenge::one();
+        }
+        ProofCorruption::SwapChips(a, b) => {
+            let index_a = proof.chip_ordering[a];
+            let index_b = proof.chip_ordering.insert(b.clone(), index_a).expect("chip not found");
+            proof.chip_ordering.insert(a.clone(), index_b);
+        }
+        ProofCorruption::RemoveChip(name) => {
+            proof.chip_ordering.remove(name).expect("chip not found");
+        }
+    }
+}
+
+/// Lists, for every chip of a shard proof, a corruption of its first quotient chunk, of the first
+/// local and next values of each of its opened traces, and of its cumulative sum, followed by
+/// corruptions of the chip ordering.
+pub fn shard_proof_corruptions<SC: StarkGenericConfig>(
+    proof: &ShardProof<SC>,
+) -> Vec<ProofCorruption> {
+    let mut corruptions = Vec::new();
+    for (chip, values) in proof.opened_values.chips.iter().enumerate() {
+        if values.quotient.first().is_some_and(|chunk| !chunk.is_empty()) {
+            corruptions.push(ProofCorruption::QuotientChunk { chip, chunk: 0, index: 0 });
+        }
+        let traces: [(OpenedTrace, &AirOpenedValues<SC::Challenge>); 3] = [
+            (OpenedTrace::Preprocessed, &values.preprocessed),
+            (OpenedTrace::Main, &values.main),
+            (OpenedTrace::Permutation, &values.permutation),
+        ];
+        for (trace, opened) in traces {
+            for (next, row) in [(false, &opened.local), (true, &opened.next)] {
+                if !row.is_empty() {
+                    corruptions.push(ProofCorruption::OpenedValue { chip, trace, next, column: 0 });
+                }
+            }
+        }
+        corruptions.push(ProofCorruption::CumulativeSum { chip });
+    }
+
+    let mut names = proof.chip_ordering.iter().collect::<Vec<_>>();
+    names.sort_by_key(|(_, index)| **index);
+    if let [(first, _), (second, _), ..] = names.as_slice() {
+        corruptions.push(ProofCorruption::SwapChips((*first).clone(), (*second).clone()));
+    }
+    if let Some((last, _)) = names.last() {
+        corruptions.push(ProofCorruption::RemoveChip((*last).clone()));
+    }
+    corruptions
+}
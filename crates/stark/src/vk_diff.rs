@@ -0,0 +1,290 @@
+use std::fmt::{Display, Formatter};
+
+use p3_commit::PolynomialSpace;
+use p3_matrix::Dimensions;
+
+use crate::{StarkGenericConfig, StarkVerifyingKey};
+
+/// The domain of the preprocessed trace of a chip, as it enters the hash of a verifying key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VkDomain {
+    /// The size of the domain, which is the height of the trace.
+    pub size: usize,
+    /// The first point of the domain, which is its shift.
+    pub shift: String,
+}
+
+impl Display for VkDomain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "size {} shift {}", self.size, self.shift)
+    }
+}
+
+/// A difference between two verifying keys. See [`VkDiff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VkChange {
+    /// The commitment to the preprocessed traces changed.
+    Commit {
+        /// The old commitment.
+        old: String,
+        /// The new commitment.
+        new: String,
+    },
+    /// The start pc of the program changed.
+    PcStart {
+        /// The old start pc.
+        old: String,
+        /// The new start pc.
+        new: String,
+    },
+    /// A chip has a preprocessed trace in the new key only.
+    ChipAdded {
+        /// The name of the chip.
+        chip: String,
+        /// The dimensions of its preprocessed trace.
+        dimensions: Dimensions,
+    },
+    /// A chip has a preprocessed trace in the old key only.
+    ChipRemoved {
+        /// The name of the chip.
+        chip: String,
+        /// The dimensions of its preprocessed trace.
+        dimensions: Dimensions,
+    },
+    /// The dimensions of the preprocessed trace of a chip changed.
+    Dimensions {
+        /// The name of the chip.
+        chip: String,
+        /// The old dimensions.
+        old: Dimensions,
+        /// The new dimensions.
+        new: Dimensions,
+    },
+    /// The domain of the preprocessed trace of a chip changed.
+    Domain {
+        /// The name of the chip.
+        chip: String,
+        /// The old domain.
+        old: VkDomain,
+        /// The new domain.
+        new: VkDomain,
+    },
+    /// The position of a chip among the preprocessed traces changed, which are ordered by height.
+    Order {
+        /// The name of the chip.
+        chip: String,
+        /// The old position.
+        old: usize,
+        /// The new position.
+        new: usize,
+    },
+}
+
+impl Display for VkChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let dims = |d: &Dimensions| format!("{}x{}", d.height, d.width);
+        match self {
+            VkChange::Commit { old, new } => {
+                write!(f, "preprocessed commitment changed from {old} to {new}")
+            }
+            VkChange::PcStart { old, new } => write!(f, "pc start changed from {old} to {new}"),
+            VkChange::ChipAdded { chip, dimensions } => {
+                write!(f, "{chip}: preprocessed trace added ({})", dims(dimensions))
+            }
+            VkChange::ChipRemoved { chip, dimensions } => {
+                write!(f, "{chip}: preprocessed trace removed ({})", dims(dimensions))
+            }
+            VkChange::Dimensions { chip, old, new } => {
+                write!(f, "{chip}: preprocessed trace resized from {} to {}", dims(old), dims(new))
+            }
+            VkChange::Domain { chip, old, new } => {
+                write!(f, "{chip}: domain changed from {old} to {new}")
+            }
+            VkChange::Order { chip, old, new } => {
+                write!(f, "{chip}: moved from position {old} to {new}")
+            }
+        }
+    }
+}
+
+/// The differences between two verifying keys, to explain why the hash of a program's verifying key
+/// changed, for example after upgrading the toolchain or the crates.
+///
+/// The hash of a verifying key covers the preprocessed commitment, the start pc and the domains of
+/// the preprocessed traces, in order. The commitment changes with any change to the preprocessed
+/// traces, so a commitment change that comes alone means that the content of the traces changed,
+/// such as the instructions of the program or its initial memory, but not their shape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VkDiff(pub Vec<VkChange>);
+
+impl VkDiff {
+    /// Compares the verifying keys `old` and `new`.
+    pub fn new<SC: StarkGenericConfig>(
+        old: &StarkVerifyingKey<SC>,
+        new: &StarkVerifyingKey<SC>,
+    ) -> Self {
+        let mut changes = Vec::new();
+
+        let (old_commit, new_commit) =
+            (commit_to_string(&old.commit), commit_to_string(&new.commit));
+        if old_commit != new_commit {
+            changes.push(VkChange::Commit { old: old_commit, new: new_commit });
+        }
+        if old.pc_start != new.pc_start {
+            changes.push(VkChange::PcStart {
+                old: old.pc_start.to_string(),
+                new: new.pc_start.to_string(),
+            });
+        }
+
+        for (i, (chip, old_domain, old_dims)) in old.chip_information.iter().enumerate() {
+            let Some(j) = new.chip_information.iter().position(|(name, _, _)| name == chip) else {
+                changes.push(VkChange::ChipRemoved { chip: chip.clone(), dimensions: *old_dims });
+                continue;
+            };
+            let (_, new_domain, new_dims) = &new.chip_information[j];
+            if old_dims != new_dims {
+                changes.push(VkChange::Dimensions {
+                    chip: chip.clone(),
+                    old: *old_dims,
+                    new: *new_dims,
+                });
+            }
+            let (old_domain, new_domain) = (domain::<SC>(old_domain), domain::<SC>(new_domain));
+            if old_domain != new_domain {
+                changes.push(VkChange::Domain {
+                    chip: chip.clone(),
+                    old: old_domain,
+                    new: new_domain,
+                });
+            }
+            if i != j {
+                changes.push(VkChange::Order { chip: chip.clone(), old: i, new: j });
+            }
+        }
+        for (chip, _, dims) in &new.chip_information {
+            if !old.chip_information.iter().any(|(name, _, _)| name == chip) {
+                changes.push(VkChange::ChipAdded { chip: chip.clone(), dimensions: *dims });
+            }
+        }
+
+        Self(changes)
+    }
+
+    /// Whether the verifying keys are the same.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Display for VkDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "the verifying keys are the same");
+        }
+        write!(f, "the verifying keys differ in:")?;
+        for change in &self.0 {
+            write!(f, "\n  {change}")?;
+        }
+        if let [VkChange::Commit { .. }] = self.0.as_slice() {
+            write!(
+                f,
+                "\nthe preprocessed traces kept their shape, so the program or its initial memory \
+                 changed"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The commitment as a string, since commitments can only be compared through their encoding.
+fn commit_to_string<C: serde::Serialize>(commit: &C) -> String {
+    serde_json::to_string(commit).expect("failed to serialize the commitment")
+}
+
+fn domain<SC: StarkGenericConfig>(domain: &SC::Domain) -> VkDomain {
+    VkDomain { size: domain.size(), shift: domain.first_point().to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+    use p3_commit::TwoAdicMultiplicativeCoset;
+    use p3_field::AbstractField;
+
+    use super::*;
+    use crate::baby_bear_poseidon2::BabyBearPoseidon2;
+
+    fn vk(
+        commit: u32,
+        pc_start: u32,
+        chips: &[(&str, usize, usize)],
+    ) -> StarkVerifyingKey<BabyBearPoseidon2> {
+        let chip_information = chips
+            .iter()
+            .map(|&(name, log_n, width)| {
+                let domain = TwoAdicMultiplicativeCoset { log_n, shift: BabyBear::one() };
+                (name.to_string(), domain, Dimensions { width, height: 1 << log_n })
+            })
+            .collect::<Vec<_>>();
+        StarkVerifyingKey {
+            commit: [BabyBear::from_canonical_u32(commit); 8].into(),
+            pc_start: BabyBear::from_canonical_u32(pc_start),
+            chip_information,
+            chip_ordering: chips
+                .iter()
+                .enumerate()
+                .map(|(i, (name, _, _))| (name.to_string(), i))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_vk_diff() {
+        let old = vk(1, 0x2000, &[("Program", 10, 14), ("MemoryProgram", 8, 6)]);
+        assert!(VkDiff::new(&old, &old).is_empty());
+        assert_eq!(VkDiff::new(&old, &old).to_string(), "the verifying keys are the same");
+
+        let new = vk(2, 0x2000, &[("Program", 10, 14), ("MemoryProgram", 8, 6)]);
+        let diff = VkDiff::new(&old, &new);
+        assert_eq!(diff.0.len(), 1);
+        assert!(diff.to_string().ends_with("so the program or its initial memory changed"));
+
+        let new = vk(2, 0x2004, &[("MemoryProgram", 11, 6), ("Program", 10, 15), ("Rom", 4, 1)]);
+        let diff = VkDiff::new(&old, &new);
+        assert_eq!(
+            diff.0[1..],
+            [
+                VkChange::PcStart { old: "8192".into(), new: "8196".into() },
+                VkChange::Dimensions {
+                    chip: "Program".into(),
+                    old: Dimensions { width: 14, height: 1024 },
+                    new: Dimensions { width: 15, height: 1024 },
+                },
+                VkChange::Order { chip: "Program".into(), old: 0, new: 1 },
+                VkChange::Dimensions {
+                    chip: "MemoryProgram".into(),
+                    old: Dimensions { width: 6, height: 256 },
+                    new: Dimensions { width: 6, height: 2048 },
+                },
+                VkChange::Domain {
+                    chip: "MemoryProgram".into(),
+                    old: VkDomain { size: 256, shift: "1".into() },
+                    new: VkDomain { size: 2048, shift: "1".into() },
+                },
+                VkChange::Order { chip: "MemoryProgram".into(), old: 1, new: 0 },
+                VkChange::ChipAdded {
+                    chip: "Rom".into(),
+                    dimensions: Dimensions { width: 1, height: 16 },
+                },
+            ]
+        );
+        let report = diff.to_string();
+        assert!(
+            report.starts_with("the verifying keys differ in:\n  preprocessed commitment changed")
+        );
+        assert!(report.contains("\n  Program: preprocessed trace resized from 1024x14 to 1024x15"));
+        assert!(report.contains("\n  Rom: preprocessed trace added (16x1)"));
+        assert!(!report.contains("initial memory"));
+    }
+}
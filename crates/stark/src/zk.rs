@@ -0,0 +1,358 @@
+//! Hiding mode for shard proofs.
+//!
+//! By default, the openings of the committed polynomials at the out-of-domain point and at the FRI
+//! query points are evaluations of the traces and of the quotients, and leak information about the
+//! trace. When [`StarkGenericConfig::zk`] returns a [`ZkConfig`], the prover blinds the main and
+//! permutation traces and the quotient chunks before committing to them, and masks the polynomial
+//! batched by FRI, so that the openings and the FRI codewords are uniformly distributed.
+//!
+//! A trace `t` of height `n` is interpolated over the subgroup `H` of order `n`. In hiding mode,
+//! the prover instead commits to a matrix of height `n * 2^e` over the subgroup `K` of that order,
+//! whose rows at multiples of `2^e` are the rows of `t` and whose remaining rows are uniformly
+//! random. The committed polynomial is then `t + Z_H * r` for a uniformly random `r` of degree
+//! less than `n * (2^e - 1)`: it agrees with `t` on `H`, so the constraints still vanish there,
+//! and any `n * (2^e - 1)` of its evaluations outside of `H` are uniformly distributed. The
+//! blinding factor `2^e` is the smallest one covering [`ZkConfig::num_hidden_evaluations`].
+//!
+//! Since the committed polynomials have degree less than `n * 2^e`, a chip whose constraints have
+//! degree at most `2^d + 1` has a quotient of degree less than `2^(d + e + 1) * n`, which is split
+//! into as many chunks of degree less than `n`. The quotient domain must fit in the low-degree
+//! extension of the committed traces, so the PCS blowup must be at least `2^(d + 1)`.
+//!
+//! The chunk `q_i` is the interpolation of the quotient over the `i`-th coset `D_i` of the
+//! quotient domain, and the verifier recombines the quotient as the sum of the `L_i * q_i`, where
+//! `L_i` is the product of the `Z_{D_j} / Z_{D_j}(s_i)` for `j != i` and `s_i` is the first point
+//! of `D_i`. In hiding mode, the prover commits to `q_i + Z_{D_i} * r_i` instead, over a coset
+//! `2^e` times larger, for random `r_i` of degree less than `n * (2^e - 1)`. Each `L_i * Z_{D_i}`
+//! is the vanishing polynomial of the whole quotient domain divided by the product of the
+//! `Z_{D_j}(s_i)`, so the blinding cancels out in the recombination when the `r_i` weighted by
+//! these products sum to zero. All the `r_i` but the last are uniformly random: the openings of
+//! the chunks at a point are uniformly distributed, up to the recombined quotient, which is already
+//! determined by the openings of the traces.
+//!
+//! Finally, the prover commits to a masking polynomial with one uniformly random column per
+//! coordinate of the challenge field, over a domain as large as the tallest committed matrix, and
+//! opens it at the out-of-domain point along with the other polynomials. FRI batches it into the first codeword of
+//! its commit phase, which randomizes the codewords folded from it. The verifier only checks the
+//! opening of the masking polynomial as part of the opening proof.
+//!
+//! The preprocessed traces are part of the verifying key and are not blinded. The cumulative sums
+//! of the permutation traces are still revealed, as they are needed to balance the interactions
+//! across shards. The recursive verifiers only support configurations without hiding.
+//!
+//! [`StarkGenericConfig::zk`]: crate::StarkGenericConfig::zk
+
+use p3_commit::PolynomialSpace;
+use p3_dft::{Radix2DitParallel, TwoAdicSubgroupDft};
+use p3_field::{AbstractField, Field, TwoAdicField};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use serde::{Deserialize, Serialize};
+
+/// The parameters of the hiding mode of shard proofs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ZkConfig {
+    /// The number of evaluations of each committed trace and quotient chunk outside of its domain
+    /// that are guaranteed to be uniformly distributed.
+    pub num_hidden_evaluations: usize,
+}
+
+impl ZkConfig {
+    /// The parameters hiding every evaluation revealed by a proof with `num_queries` FRI queries.
+    ///
+    /// Each query opens the low-degree extensions at a point and, through the first folding
+    /// round, at its negation, and the quotient recombined at both points depends on the traces at
+    /// these points and their successors. The out-of-domain openings of the traces at `zeta` and
+    /// its successor reveal one extension field element each. The quotient chunks are only opened
+    /// at `zeta` and at the query points, so they are covered as well.
+    #[must_use]
+    pub const fn for_num_queries(num_queries: usize, extension_degree: usize) -> Self {
+        Self { num_hidden_evaluations: 4 * num_queries + 2 * extension_degree }
+    }
+
+    /// The log of the blinding factor for a trace of height `2^log_degree`.
+    ///
+    /// This is the smallest `e >= 1` such that `2^log_degree * (2^e - 1)` random rows cover the
+    /// number of hidden evaluations.
+    #[must_use]
+    pub fn log_blinding_factor(&self, log_degree: usize) -> usize {
+        let mut log_factor = 1;
+        while ((1 << log_factor) - 1) << log_degree < self.num_hidden_evaluations {
+            log_factor += 1;
+        }
+        log_factor
+    }
+
+    /// The log of the height of the masking polynomial of a shard, which is that of its tallest
+    /// committed matrix, given the log heights of the preprocessed traces and the log degrees of
+    /// the chips of the shard.
+    #[must_use]
+    pub fn masking_log_degree(
+        &self,
+        preprocessed_log_degrees: impl IntoIterator<Item = usize>,
+        log_degrees: &[usize],
+    ) -> usize {
+        log_degrees
+            .iter()
+            .map(|&log_degree| log_degree + self.log_blinding_factor(log_degree))
+            .chain(preprocessed_log_degrees)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// The log of the blinding factor of a trace of height `2^log_degree`, which is zero without
+/// hiding.
+#[must_use]
+pub fn log_blinding_factor(zk: Option<&ZkConfig>, log_degree: usize) -> usize {
+    zk.map_or(0, |zk| zk.log_blinding_factor(log_degree))
+}
+
+/// The log of the number of quotient chunks of a chip of height `2^log_degree`, given the log
+/// quotient degree of the chip without hiding.
+#[must_use]
+pub fn log_quotient_degree(
+    zk: Option<&ZkConfig>,
+    log_quotient_degree: usize,
+    log_degree: usize,
+) -> usize {
+    match zk {
+        Some(zk) => log_quotient_degree + zk.log_blinding_factor(log_degree) + 1,
+        None => log_quotient_degree,
+    }
+}
+
+/// Interleaves the rows of `trace` with `2^log_blinding_factor - 1` uniformly random rows each.
+///
+/// Row `i` of `trace` becomes row `i << log_blinding_factor` of the result. A blinding factor of
+/// zero returns the trace unchanged.
+#[must_use]
+pub fn blind_trace<F: Field>(
+    trace: &RowMajorMatrix<F>,
+    log_blinding_factor: usize,
+) -> RowMajorMatrix<F> {
+    if log_blinding_factor == 0 {
+        return trace.clone();
+    }
+    let width = trace.width();
+    let factor = 1 << log_blinding_factor;
+    let mut values = random_values::<F>(trace.height() * factor * width);
+    for (i, row) in trace.rows().enumerate() {
+        let start = i * factor * width;
+        for (value, x) in values[start..start + width].iter_mut().zip(row) {
+            *value = x;
+        }
+    }
+    RowMajorMatrix::new(values, width)
+}
+
+/// The domain over which the quotient chunk over `qc_domain` is committed, which is
+/// `2^log_blinding_factor` times larger than `qc_domain`.
+#[must_use]
+pub fn committed_chunk_domain<D: PolynomialSpace>(qc_domain: D, log_blinding_factor: usize) -> D {
+    if log_blinding_factor == 0 {
+        return qc_domain;
+    }
+    qc_domain.create_disjoint_domain(qc_domain.size() << log_blinding_factor)
+}
+
+/// Blinds the quotient chunks of a chip, given their evaluations over the cosets `qc_domains` of
+/// its quotient domain.
+///
+/// Returns the domains over which the blinded chunks are committed, with the evaluations of the
+/// blinded chunks over them. The blinded chunks agree with the chunks on their cosets, and
+/// recombine into the same quotient.
+#[must_use]
+pub fn blind_quotient_chunks<D>(
+    qc_domains: Vec<D>,
+    chunks: Vec<RowMajorMatrix<D::Val>>,
+    log_blinding_factor: usize,
+) -> Vec<(D, RowMajorMatrix<D::Val>)>
+where
+    D: PolynomialSpace,
+    D::Val: TwoAdicField,
+{
+    assert_eq!(qc_domains.len(), chunks.len());
+    let Some(first_domain) = qc_domains.first() else {
+        return vec![];
+    };
+
+    // The weight of the blinding polynomial `r_i` in the recombined quotient, which is the inverse
+    // of the product of the `Z_{D_j}(s_i)` for `j != i`.
+    let weights = qc_domains
+        .iter()
+        .enumerate()
+        .map(|(i, domain)| {
+            qc_domains
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, other)| other.zp_at_point(domain.first_point()))
+                .product::<D::Val>()
+                .inverse()
+        })
+        .collect::<Vec<_>>();
+
+    // Sample the coefficients of the blinding polynomials, the last one cancelling the others.
+    let width = chunks[0].width();
+    let len = first_domain.size() * ((1 << log_blinding_factor) - 1) * width;
+    let mut blindings =
+        (1..qc_domains.len()).map(|_| random_values::<D::Val>(len)).collect::<Vec<_>>();
+    let scale = -weights[weights.len() - 1].inverse();
+    let mut last = vec![D::Val::zero(); len];
+    for (blinding, &weight) in blindings.iter().zip(weights.iter()) {
+        let weight = weight * scale;
+        for (x, &r) in last.iter_mut().zip(blinding.iter()) {
+            *x += weight * r;
+        }
+    }
+    blindings.push(last);
+
+    let dft = Radix2DitParallel;
+    qc_domains
+        .into_iter()
+        .zip(chunks)
+        .zip(blindings)
+        .map(|((domain, chunk), blinding)| {
+            let n = domain.size();
+            let shift = domain.first_point();
+
+            // Interpolate the chunk over its coset.
+            let mut coeffs = dft.idft_batch(chunk);
+            for (row, weight) in coeffs.values.chunks_exact_mut(width).zip(shift.inverse().powers())
+            {
+                row.iter_mut().for_each(|x| *x *= weight);
+            }
+
+            // Add `Z_{D_i} * r_i`, where `Z_{D_i}(X) = (X / s_i)^n - 1`.
+            coeffs.values.resize((n << log_blinding_factor) * width, D::Val::zero());
+            let shift_inv_n = shift.exp_u64(n as u64).inverse();
+            for (x, &r) in coeffs.values.iter_mut().zip(blinding.iter()) {
+                *x -= r;
+            }
+            for (x, &r) in coeffs.values[n * width..].iter_mut().zip(blinding.iter()) {
+                *x += shift_inv_n * r;
+            }
+
+            let committed_domain = committed_chunk_domain(domain, log_blinding_factor);
+            let evals = dft.coset_dft_batch(coeffs, committed_domain.first_point());
+            (committed_domain, evals.to_row_major_matrix())
+        })
+        .collect()
+}
+
+/// Samples `len` field elements from the operating system's randomness.
+///
+/// Each element is reduced from 64 random bits, so the distribution is statistically close to
+/// uniform for fields of up to 32 bits.
+pub(crate) fn random_values<F: Field>(len: usize) -> Vec<F> {
+    let mut bytes = vec![0u8; 8 * len];
+    getrandom::getrandom(&mut bytes).expect("failed to sample blinding randomness");
+    bytes
+        .chunks_exact(8)
+        .map(|chunk| F::from_wrapped_u64(u64::from_le_bytes(chunk.try_into().unwrap())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+    use p3_commit::TwoAdicMultiplicativeCoset;
+
+    use super::*;
+
+    /// Evaluates the polynomials interpolating the columns of `evals` over `domain` at `point`.
+    fn evaluate<F: TwoAdicField>(
+        domain: TwoAdicMultiplicativeCoset<F>,
+        evals: RowMajorMatrix<F>,
+        point: F,
+    ) -> Vec<F> {
+        let coeffs = Radix2DitParallel.idft_batch(evals);
+        let scale = point * domain.first_point().inverse();
+        let mut values = vec![F::zero(); coeffs.width()];
+        for (row, x) in coeffs.rows().zip(scale.powers()) {
+            values.iter_mut().zip(row).for_each(|(value, c)| *value += c * x);
+        }
+        values
+    }
+
+    #[test]
+    fn test_blind_trace() {
+        let trace = RowMajorMatrix::new((0..24).map(BabyBear::from_canonical_u32).collect(), 3);
+        let blinded = blind_trace(&trace, 2);
+        assert_eq!(blinded.width(), 3);
+        assert_eq!(blinded.height(), 32);
+        for i in 0..trace.height() {
+            assert_eq!(blinded.row_slice(i << 2).to_vec(), trace.row_slice(i).to_vec());
+        }
+    }
+
+    #[test]
+    fn test_blinding_factor() {
+        let zk = ZkConfig { num_hidden_evaluations: 100 };
+        assert_eq!(zk.log_blinding_factor(10), 1);
+        assert_eq!(zk.log_blinding_factor(7), 1);
+        assert_eq!(zk.log_blinding_factor(6), 2);
+        assert_eq!(zk.log_blinding_factor(5), 3);
+        assert_eq!(zk.log_blinding_factor(2), 5);
+        assert_eq!(log_quotient_degree(Some(&zk), 1, 10), 3);
+        assert_eq!(log_quotient_degree(None, 1, 10), 1);
+        assert_eq!(zk.masking_log_degree([9], &[10, 5]), 11);
+        assert_eq!(zk.masking_log_degree([12], &[10, 5]), 12);
+    }
+
+    #[test]
+    fn test_blind_quotient_chunks() {
+        let quotient_domain = TwoAdicMultiplicativeCoset { log_n: 5, shift: BabyBear::generator() };
+        let qc_domains = quotient_domain.split_domains(4);
+        let chunks = qc_domains
+            .iter()
+            .map(|domain| RowMajorMatrix::new(random_values(domain.size() * 2), 2))
+            .collect::<Vec<_>>();
+        let blinded = blind_quotient_chunks(qc_domains.clone(), chunks.clone(), 2);
+
+        // The blinded chunks agree with the chunks on their cosets.
+        for ((domain, chunk), (committed_domain, blinded)) in
+            qc_domains.iter().zip(chunks.iter()).zip(blinded.iter())
+        {
+            assert_eq!(blinded.height(), 32);
+            let mut point = domain.first_point();
+            for row in chunk.rows() {
+                assert_eq!(
+                    evaluate(*committed_domain, blinded.clone(), point),
+                    row.collect::<Vec<_>>()
+                );
+                point = domain.next_point(point).unwrap();
+            }
+        }
+
+        // The blinded chunks differ from the chunks elsewhere, but recombine into the same
+        // quotient.
+        let zeta = BabyBear::from_canonical_u32(123_456_789);
+        let recombine = |values: Vec<Vec<BabyBear>>| {
+            let mut quotient = vec![BabyBear::zero(); 2];
+            for (i, (domain, values)) in qc_domains.iter().zip(values).enumerate() {
+                let weight = qc_domains
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, other)| {
+                        other.zp_at_point(zeta) * other.zp_at_point(domain.first_point()).inverse()
+                    })
+                    .product::<BabyBear>();
+                quotient.iter_mut().zip(values).for_each(|(q, v)| *q += weight * v);
+            }
+            quotient
+        };
+        let chunk_values = qc_domains
+            .iter()
+            .zip(chunks)
+            .map(|(domain, chunk)| evaluate(*domain, chunk, zeta))
+            .collect::<Vec<_>>();
+        let blinded_values = blinded
+            .into_iter()
+            .map(|(domain, blinded)| evaluate(domain, blinded, zeta))
+            .collect::<Vec<_>>();
+        assert_ne!(chunk_values, blinded_values);
+        assert_eq!(recombine(chunk_values), recombine(blinded_values));
+    }
+}
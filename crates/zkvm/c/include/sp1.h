@@ -0,0 +1,86 @@
+/*
+ * C bindings of the SP1 zkVM runtime.
+ *
+ * Programs define `int main(void)` and link against `libsp1_zkvm_c.a`, built from the
+ * `sp1-zkvm-c` crate for the `riscv32im-succinct-zkvm-elf` target, which provides their entrypoint.
+ * See the "C and C++ Programs" chapter of the SP1 book.
+ */
+
+#ifndef SP1_H
+#define SP1_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The file descriptors of stdout and stderr, for `sp1_write`. */
+#define SP1_FD_STDOUT 1
+#define SP1_FD_STDERR 2
+
+/*
+ * Input and output.
+ */
+
+/* Reads the next input written with `SP1Stdin::write_slice` into a fresh, 4 byte aligned buffer,
+ * which is never freed, and stores its length in `len`. */
+uint8_t *sp1_read(size_t *len);
+
+/* Appends `len` bytes to the public values. */
+void sp1_commit(const void *data, size_t len);
+
+/* Appends `len` bytes to the hint stream, to be read back with `sp1_read`. */
+void sp1_hint(const void *data, size_t len);
+
+/* Writes `len` bytes to the file descriptor `fd`. */
+void sp1_write(uint32_t fd, const void *data, size_t len);
+
+/* Halts the program with the given exit code, after committing to the public values. */
+_Noreturn void sp1_halt(uint8_t exit_code);
+
+/*
+ * Precompiles. Points and field elements are arrays of little-endian 32 bit limbs, the result is
+ * written to the first argument, and every argument must be aligned on a 4 byte boundary.
+ */
+
+/* Extends the first 16 words of a SHA-256 message schedule to 64 words. */
+void syscall_sha256_extend(uint32_t w[64]);
+
+/* Runs the SHA-256 compression function on `state` with the message schedule `w`. */
+void syscall_sha256_compress(uint32_t w[64], uint32_t state[8]);
+
+/* Applies the Keccak-f[1600] permutation to `state`. */
+void syscall_keccak_permute(uint64_t state[25]);
+
+/* Adds two secp256k1 points in affine coordinates. */
+void syscall_secp256k1_add(uint32_t p[16], uint32_t q[16]);
+
+/* Doubles a secp256k1 point in affine coordinates. */
+void syscall_secp256k1_double(uint32_t p[16]);
+
+/* Decompresses a secp256k1 point whose big-endian x coordinate is in the first 32 bytes. */
+void syscall_secp256k1_decompress(uint8_t point[64], bool is_odd);
+
+/* Adds two ed25519 points in affine coordinates. */
+void syscall_ed_add(uint32_t p[16], const uint32_t q[16]);
+
+/* Decompresses an ed25519 point whose compressed encoding is in the last 32 bytes. */
+void syscall_ed_decompress(uint8_t point[64]);
+
+/* Adds two bn254 points in affine coordinates. */
+void syscall_bn254_add(uint32_t p[16], const uint32_t q[16]);
+
+/* Doubles a bn254 point in affine coordinates. */
+void syscall_bn254_double(uint32_t p[16]);
+
+/* Computes `x * y mod m`, where the modulus `m` follows `y` in memory. */
+void syscall_uint256_mulmod(uint32_t x[8], const uint32_t y[8]);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* SP1_H */
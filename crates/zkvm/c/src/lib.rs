@@ -0,0 +1,114 @@
+//! C bindings of the SP1 zkVM runtime, for programs written in C or C++.
+//!
+//! Built for the `riscv32im-succinct-zkvm-elf` target, the crate is a static library,
+//! `libsp1_zkvm_c.a`, which provides the entrypoint of the program, its allocator and panic
+//! handler, and the functions declared in `include/sp1.h`. A C program defines `int main(void)`
+//! and is linked against the library without the startup files of its C library.
+//!
+//! The precompiles are the `syscall_*` functions of `sp1-zkvm`, which the header declares with C
+//! types. On the zkVM, the library also provides the `_write`, `_sbrk` and `_exit` stubs of
+//! newlib, so that `printf` and `malloc` work when the program is linked against it.
+
+#![cfg_attr(target_os = "zkvm", no_std)]
+
+use core::ffi::c_void;
+
+use sp1_zkvm::syscalls::{
+    sys_alloc_aligned, syscall_halt, syscall_hint_len, syscall_hint_read, syscall_write,
+};
+
+/// The file descriptor of the public values.
+const FD_PUBLIC_VALUES: u32 = 3;
+
+/// The file descriptor of the hint stream.
+const FD_HINT: u32 = 4;
+
+#[cfg(target_os = "zkvm")]
+#[global_allocator]
+static HEAP: sp1_zkvm::heap::SimpleAlloc = sp1_zkvm::heap::SimpleAlloc;
+
+/// Reads the next input written with `SP1Stdin::write_slice`, and stores its length in `len`.
+///
+/// The input is read into a fresh, 4 byte aligned buffer, which is never freed.
+///
+/// # Safety
+///
+/// `len` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn sp1_read(len: *mut usize) -> *mut u8 {
+    let n = syscall_hint_len();
+    // The syscall writes to uninitialized memory, so the input needs a fresh allocation, rounded
+    // up to whole words.
+    let ptr = sys_alloc_aligned(n.next_multiple_of(4), 4);
+    syscall_hint_read(ptr, n);
+    *len = n;
+    ptr
+}
+
+/// Appends `len` bytes to the public values.
+///
+/// # Safety
+///
+/// `data` must be valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sp1_commit(data: *const c_void, len: usize) {
+    syscall_write(FD_PUBLIC_VALUES, data.cast(), len);
+}
+
+/// Appends `len` bytes to the hint stream, to be read back with [`sp1_read`].
+///
+/// # Safety
+///
+/// `data` must be valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sp1_hint(data: *const c_void, len: usize) {
+    syscall_write(FD_HINT, data.cast(), len);
+}
+
+/// Writes `len` bytes to the file descriptor `fd`: stdout, stderr, or the file descriptor of a
+/// hook registered on the host.
+///
+/// # Safety
+///
+/// `data` must be valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sp1_write(fd: u32, data: *const c_void, len: usize) {
+    syscall_write(fd, data.cast(), len);
+}
+
+/// Halts the program with the given exit code, after committing to the public values.
+#[no_mangle]
+pub extern "C" fn sp1_halt(exit_code: u8) -> ! {
+    syscall_halt(exit_code)
+}
+
+/// The `_write` stub of newlib, which backs `printf` and `fwrite`.
+///
+/// # Safety
+///
+/// `data` must be valid for reads of `len` bytes.
+#[cfg(target_os = "zkvm")]
+#[no_mangle]
+pub unsafe extern "C" fn _write(fd: i32, data: *const c_void, len: usize) -> isize {
+    syscall_write(fd as u32, data.cast(), len);
+    len as isize
+}
+
+/// The `_sbrk` stub of newlib, which backs `malloc`.
+///
+/// The heap only grows, so negative increments fail.
+#[cfg(target_os = "zkvm")]
+#[no_mangle]
+pub extern "C" fn _sbrk(increment: isize) -> *mut c_void {
+    if increment < 0 {
+        return usize::MAX as *mut c_void;
+    }
+    unsafe { sys_alloc_aligned(increment as usize, 8).cast() }
+}
+
+/// The `_exit` stub of newlib, which backs `exit` and `abort`.
+#[cfg(target_os = "zkvm")]
+#[no_mangle]
+pub extern "C" fn _exit(status: i32) -> ! {
+    syscall_halt(status as u8)
+}
@@ -1,3 +1,11 @@
+//! The entrypoint of SP1 zkVM programs, and the syscalls they use to interact with the zkVM.
+//!
+//! Without the default `std` feature, the crate is `no_std` and only depends on `alloc`, so it
+//! can be used by `#![no_std]` programs. It then provides the panic handler of the program, which
+//! reports panics to the host like the panic hook installed with `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
 extern crate alloc;
 
 pub mod heap;
@@ -8,6 +16,31 @@ pub mod io {
     pub use sp1_lib::io::*;
 }
 
+#[cfg(all(feature = "lib", feature = "std"))]
+pub mod env {
+    pub use sp1_lib::env::*;
+}
+
+#[cfg(all(feature = "lib", feature = "std"))]
+pub mod fs {
+    pub use sp1_lib::fs::*;
+}
+
+#[cfg(feature = "lib")]
+pub mod hints {
+    pub use sp1_lib::hints::*;
+}
+
+#[cfg(feature = "lib")]
+pub mod thread {
+    pub use sp1_lib::thread::*;
+}
+
+#[cfg(feature = "lib")]
+pub mod time {
+    pub use sp1_lib::time::*;
+}
+
 #[cfg(feature = "lib")]
 pub mod lib {
     pub use sp1_lib::*;
@@ -16,6 +49,29 @@ pub mod lib {
 #[cfg(all(target_os = "zkvm", feature = "libm"))]
 mod libm;
 
+// Replaces the floating-point arithmetic intrinsics of `compiler-builtins`.
+#[cfg(all(target_os = "zkvm", feature = "softfloat"))]
+use sp1_softfloat as _;
+
+/// Stops the execution right away with the reason `code` and `message`, which the host returns as
+/// an `ExecutionError::GuestAbort`.
+///
+/// An aborted execution cannot be proven. Use it to reject invalid inputs early, so that the host
+/// fails fast instead of proving a run whose only output is that its inputs are invalid. Unlike a
+/// panic, the abort carries a code the host can match on, and skips the panic machinery. The host
+/// only reads the first 1024 bytes of the message.
+///
+/// ### Examples
+/// ```ignore
+/// let block = sp1_zkvm::io::read::<Block>();
+/// if !block.is_well_formed() {
+///     sp1_zkvm::abort(1, "malformed block");
+/// }
+/// ```
+pub fn abort(code: u32, message: &str) -> ! {
+    syscalls::syscall_abort(code, message.as_ptr(), message.len())
+}
+
 /// The number of 32 bit words that the public values digest is composed of.
 pub const PV_DIGEST_NUM_WORDS: usize = 8;
 pub const POSEIDON_NUM_WORDS: usize = 8;
@@ -38,11 +94,48 @@ mod zkvm {
 
     pub static mut PUBLIC_VALUES_HASHER: Option<Sha256> = None;
 
+    /// The hasher of the elements of the hint stream read by the program, whose digest is
+    /// committed to the public values when the program halts.
+    #[cfg(feature = "commit-stdin")]
+    pub static mut STDIN_HASHER: Option<Sha256> = None;
+
+    /// Hashes an element of the hint stream, prefixed with its length, into [`STDIN_HASHER`].
+    ///
+    /// Every element read after the hasher is initialized is hashed, whether it comes from stdin,
+    /// from a hook or from the program itself: the number of elements of the hint stream is
+    /// reported by the prover, so the program cannot tell them apart soundly.
+    #[cfg(feature = "commit-stdin")]
+    pub unsafe fn hash_hint(ptr: *const u8, len: usize) {
+        if let Some(hasher) = (*core::ptr::addr_of_mut!(STDIN_HASHER)).as_mut() {
+            hasher.update(&(len as u64).to_le_bytes());
+            hasher.update(core::slice::from_raw_parts(ptr, len));
+        }
+    }
+
+    /// The number of elements of the hint stream that were not read yet.
+    #[cfg(feature = "commit-stdin")]
+    pub unsafe fn hint_remaining() -> u32 {
+        // The syscall writes to uninitialized memory, so the result needs a fresh allocation.
+        let remaining = crate::syscalls::sys_alloc_aligned(4, 4) as *mut u32;
+        crate::syscalls::syscall_hint_remaining(remaining);
+        *remaining
+    }
+
     #[cfg(not(feature = "interface"))]
     #[no_mangle]
     unsafe extern "C" fn __start() {
         {
             PUBLIC_VALUES_HASHER = Some(Sha256::new());
+            #[cfg(feature = "commit-stdin")]
+            {
+                STDIN_HASHER = Some(Sha256::new());
+            }
+            #[cfg(feature = "replay-protection")]
+            commit_public_values_domain();
+            #[cfg(feature = "aslr")]
+            commit_memory_layout();
+            #[cfg(feature = "std")]
+            std::panic::set_hook(Box::new(panic_hook));
             #[cfg(feature = "verify")]
             {
                 DEFERRED_PROOFS_DIGEST = Some([BabyBear::zero(); 8]);
@@ -57,11 +150,158 @@ mod zkvm {
         syscall_halt(0);
     }
 
+    /// The size of the domain separator and nonce, which must match
+    /// `sp1_primitives::io::PUBLIC_VALUES_DOMAIN_SIZE`.
+    #[cfg(feature = "replay-protection")]
+    const PUBLIC_VALUES_DOMAIN_SIZE: usize = 40;
+
+    /// Commits the domain separator and nonce set with `SP1Stdin::write_public_values_domain` as the
+    /// first bytes of the public values, so that a proof cannot be replayed in another domain.
+    #[cfg(feature = "replay-protection")]
+    unsafe fn commit_public_values_domain() {
+        crate::syscalls::syscall_write(sp1_lib::io::FD_PUBLIC_VALUES_DOMAIN, core::ptr::null(), 0);
+        let len = crate::syscalls::syscall_hint_len();
+        assert_eq!(len, PUBLIC_VALUES_DOMAIN_SIZE, "invalid public values domain");
+        // The hint is written to uninitialized memory, so it needs a fresh allocation.
+        let domain = crate::syscalls::sys_alloc_aligned(PUBLIC_VALUES_DOMAIN_SIZE, 4);
+        crate::syscalls::syscall_hint_read(domain, PUBLIC_VALUES_DOMAIN_SIZE);
+        crate::syscalls::syscall_write(3, domain, PUBLIC_VALUES_DOMAIN_SIZE);
+    }
+
+    /// The size of the seed of the memory layout, which must match
+    /// `sp1_primitives::io::LAYOUT_SEED_SIZE`.
+    #[cfg(feature = "aslr")]
+    const LAYOUT_SEED_SIZE: usize = 8;
+
+    /// The number of 16-byte slots by which the stack top may be moved down.
+    #[cfg(feature = "aslr")]
+    const STACK_SLOTS: u32 = 1 << 12;
+
+    /// The number of 16-byte slots by which the start of the heap may be moved up.
+    #[cfg(feature = "aslr")]
+    const HEAP_SLOTS: u32 = 1 << 16;
+
+    /// The seed of the memory layout, read from stdin by `__randomize_layout`.
+    #[cfg(feature = "aslr")]
+    static mut LAYOUT_SEED: u64 = 0;
+
+    /// The stack top and the start of the heap chosen by `__randomize_layout`.
+    #[cfg(feature = "aslr")]
+    static mut LAYOUT: [u32; 2] = [0; 2];
+
+    /// Reads the seed written with `SP1Stdin::write_layout_seed` as the first element of stdin,
+    /// moves the start of the heap up by a random offset, and returns a stack top moved down by a
+    /// random offset, to which `_start` switches before calling `__start`.
+    ///
+    /// It runs on the default stack, before the program's state is initialized.
+    #[cfg(feature = "aslr")]
+    #[no_mangle]
+    unsafe extern "C" fn __randomize_layout() -> u32 {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let len = crate::syscalls::syscall_hint_len();
+        assert_eq!(len, LAYOUT_SEED_SIZE, "the first element of stdin must be the layout seed");
+        // The hint is written to uninitialized memory, so it needs a fresh allocation.
+        let seed = crate::syscalls::sys_alloc_aligned(LAYOUT_SEED_SIZE, 4);
+        crate::syscalls::syscall_hint_read(seed, LAYOUT_SEED_SIZE);
+        LAYOUT_SEED = u64::from_le_bytes(*(seed as *const [u8; LAYOUT_SEED_SIZE]));
+
+        let mut rng = StdRng::seed_from_u64(LAYOUT_SEED);
+        let stack_top = STACK_TOP - 16 * rng.gen_range(0..STACK_SLOTS);
+        // Skip the heap offset with an allocation that is never used.
+        let heap_offset = 16 * rng.gen_range(0..HEAP_SLOTS) as usize;
+        let heap_start =
+            crate::syscalls::sys_alloc_aligned(heap_offset, 16) as u32 + heap_offset as u32;
+
+        LAYOUT = [stack_top, heap_start];
+        stack_top
+    }
+
+    /// Commits the stack top and the start of the heap, as little-endian `u32`s, to the public
+    /// values, after the domain of the `replay-protection` feature if it is enabled.
+    ///
+    /// With `commit-stdin`, the seed is hashed here, after the public values domain, since it was
+    /// read before the stdin hasher was initialized.
+    #[cfg(feature = "aslr")]
+    unsafe fn commit_memory_layout() {
+        #[cfg(feature = "commit-stdin")]
+        hash_hint(LAYOUT_SEED.to_le_bytes().as_ptr(), LAYOUT_SEED_SIZE);
+
+        let [stack_top, heap_start] = LAYOUT;
+        let mut layout = [0u8; 8];
+        layout[..4].copy_from_slice(&stack_top.to_le_bytes());
+        layout[4..].copy_from_slice(&heap_start.to_le_bytes());
+        crate::syscalls::syscall_write(3, layout.as_ptr(), layout.len());
+    }
+
+    /// Reports the location and message of a panic to the host, and prints them to stderr.
+    ///
+    /// The host surfaces the report as an `ExecutionError::GuestPanic` once the guest halts.
+    fn report_panic(
+        location: Option<&core::panic::Location<'_>>,
+        message: &dyn core::fmt::Display,
+    ) {
+        let location = match location {
+            Some(location) => {
+                alloc::format!("{}:{}:{}", location.file(), location.line(), location.column())
+            }
+            None => alloc::string::String::from("<unknown>"),
+        };
+
+        let report = alloc::format!("{}\n{}", location, message);
+        crate::syscalls::syscall_write(sp1_lib::io::FD_PANIC, report.as_ptr(), report.len());
+
+        let stderr = alloc::format!("panicked at {}:\n{}\n", location, message);
+        crate::syscalls::syscall_write(2, stderr.as_ptr(), stderr.len());
+    }
+
+    /// The panic hook installed with `std`, which reports the panic before the program aborts.
+    #[cfg(feature = "std")]
+    #[allow(deprecated)]
+    fn panic_hook(info: &std::panic::PanicInfo<'_>) {
+        let message = match info.payload().downcast_ref::<&str>() {
+            Some(s) => *s,
+            None => match info.payload().downcast_ref::<String>() {
+                Some(s) => s.as_str(),
+                None => "Box<dyn Any>",
+            },
+        };
+        report_panic(info.location(), &message);
+    }
+
+    /// The panic handler of `no_std` programs, which reports the panic and halts with a non-zero
+    /// exit code.
+    #[cfg(not(feature = "std"))]
+    #[panic_handler]
+    fn panic_handler(info: &core::panic::PanicInfo<'_>) -> ! {
+        report_panic(info.location(), &info.message());
+        syscall_halt(1)
+    }
+
+    /// The initial stack pointer, which must match `STACK_TOP` in `sp1-core-executor`.
     static STACK_TOP: u32 = 0x0020_0400;
 
     core::arch::global_asm!(include_str!("memset.s"));
     core::arch::global_asm!(include_str!("memcpy.s"));
 
+    #[cfg(not(feature = "aslr"))]
+    core::arch::global_asm!(
+        r#"
+    .section .text._start;
+    .globl _start;
+    _start:
+        .option push;
+        .option norelax;
+        la gp, __global_pointer$;
+        .option pop;
+        la sp, {0}
+        lw sp, 0(sp)
+        call __start;
+    "#,
+        sym STACK_TOP
+    );
+
+    #[cfg(feature = "aslr")]
     core::arch::global_asm!(
         r#"
     .section .text._start;
@@ -73,6 +313,8 @@ mod zkvm {
         .option pop;
         la sp, {0}
         lw sp, 0(sp)
+        call __randomize_layout;
+        mv sp, a0
         call __start;
     "#,
         sym STACK_TOP
@@ -1,3 +1,6 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
 use super::syscall_uint256_mulmod;
 
 /// The number of limbs in a "uint256".
@@ -46,3 +49,81 @@ pub extern "C" fn sys_bigint(
         syscall_uint256_mulmod(result_ptr, concat_ptr);
     }
 }
+
+/// Variable-width bigint addition, `z = (x + y) % 2^(256 * n)`.
+///
+/// `operands` points to the number of 256-bit limbs `n` (between 1 and 16), followed by the `8 * n`
+/// words of `x` and the `8 * n` words of `y`, all little-endian. The `8 * n` words of the result are
+/// written to `z`.
+///
+/// ### Safety
+///
+/// The caller must ensure that `z` and `operands` are valid pointers to data that is aligned along
+/// a four byte boundary, and that the result does not overlap the operands.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_bigint_add(z: *mut u32, operands: *const u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BIGINT_ADD,
+            in("a0") z,
+            in("a1") operands,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Variable-width bigint subtraction, `z = (x - y) % 2^(256 * n)`.
+///
+/// The operands are laid out as for [`syscall_bigint_add`].
+///
+/// ### Safety
+///
+/// The caller must ensure that `z` and `operands` are valid pointers to data that is aligned along
+/// a four byte boundary, and that the result does not overlap the operands.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_bigint_sub(z: *mut u32, operands: *const u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BIGINT_SUB,
+            in("a0") z,
+            in("a1") operands,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Variable-width bigint multiplication, `z = x * y`.
+///
+/// The operands are laid out as for [`syscall_bigint_add`], and the `16 * n` words of the full
+/// product are written to `z`.
+///
+/// ### Safety
+///
+/// The caller must ensure that `z` and `operands` are valid pointers to data that is aligned along
+/// a four byte boundary, and that the result does not overlap the operands.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_bigint_mul(z: *mut u32, operands: *const u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BIGINT_MUL,
+            in("a0") z,
+            in("a1") operands,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
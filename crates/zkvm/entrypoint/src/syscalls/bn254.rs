@@ -50,3 +50,30 @@ pub extern "C" fn syscall_bn254_double(p: *mut [u32; 16]) {
     #[cfg(not(target_os = "zkvm"))]
     unreachable!()
 }
+
+/// Computes one window of a multi-scalar multiplication on Bn254 points.
+///
+/// Points are in projective coordinates `(x : y : z)`. `desc` is `[n, table, digits]`, where
+/// `table` points to `n` tables of the 16 multiples `[0] P_i, ..., [15] P_i` and `digits` points to
+/// `n` words each smaller than 16. The accumulator is updated to `[16] acc + sum_i [digit_i] P_i`.
+///
+/// ### Safety
+///
+/// The caller must ensure that `acc`, `desc` and the memory they reference are valid and aligned
+/// along a four byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_bn254_msm(acc: *mut [u32; 24], desc: *const [u32; 3]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BN254_MSM,
+            in("a0") acc,
+            in("a1") desc
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
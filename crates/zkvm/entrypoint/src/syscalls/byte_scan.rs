@@ -0,0 +1,83 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Byte scan find, writes to `dst` the number of bytes before the first delimiter.
+///
+/// `operands` points to the number of words `n` (between 1 and 32), the pointer to the `n` source
+/// words and the delimiter byte. If the source does not contain the delimiter, `4 * n` is written.
+///
+/// ### Safety
+///
+/// The caller must ensure that `dst`, `operands` and the source are valid pointers to data that is
+/// aligned along a four byte boundary, and that the result does not overlap the operands or the
+/// source.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_byte_scan_find(dst: *mut u32, operands: *const u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BYTE_SCAN_FIND,
+            in("a0") dst,
+            in("a1") operands,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Byte scan copy, writes the `n` source words to `dst`.
+///
+/// The operands are laid out as for [`syscall_byte_scan_find`], and the delimiter is ignored.
+///
+/// ### Safety
+///
+/// The caller must ensure that `dst`, `operands` and the source are valid pointers to data that is
+/// aligned along a four byte boundary, and that the result does not overlap the operands or the
+/// source.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_byte_scan_copy(dst: *mut u32, operands: *const u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BYTE_SCAN_COPY,
+            in("a0") dst,
+            in("a1") operands,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Byte scan hex decode, decodes the `2 * n` source words of ASCII hex digits into the `n` words
+/// of `dst`, with the high nibble of each byte first.
+///
+/// The operands are laid out as for [`syscall_byte_scan_find`], and the delimiter is ignored. The
+/// digits may be in lower or upper case, and any other character halts the execution.
+///
+/// ### Safety
+///
+/// The caller must ensure that `dst`, `operands` and the source are valid pointers to data that is
+/// aligned along a four byte boundary, and that the result does not overlap the operands or the
+/// source.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_byte_scan_hex_decode(dst: *mut u32, operands: *const u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BYTE_SCAN_HEX_DECODE,
+            in("a0") dst,
+            in("a1") operands,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
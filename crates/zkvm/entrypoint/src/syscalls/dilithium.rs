@@ -0,0 +1,102 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Lane-wise Dilithium addition, `x[i] = (x[i] + y[i]) % q`.
+///
+/// `x` and `y` are blocks of eight coefficients modulo `q = 8380417`, which must be reduced.
+///
+/// ### Safety
+///
+/// The caller must ensure that `x` and `y` are valid pointers to data that is aligned along a four
+/// byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_dilithium_add(x: *mut [u32; 8], y: *const [u32; 8]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::DILITHIUM_ADD,
+            in("a0") x,
+            in("a1") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Lane-wise Dilithium subtraction, `x[i] = (x[i] - y[i]) % q`.
+///
+/// `x` and `y` are blocks of eight coefficients modulo `q = 8380417`, which must be reduced.
+///
+/// ### Safety
+///
+/// The caller must ensure that `x` and `y` are valid pointers to data that is aligned along a four
+/// byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_dilithium_sub(x: *mut [u32; 8], y: *const [u32; 8]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::DILITHIUM_SUB,
+            in("a0") x,
+            in("a1") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Lane-wise Dilithium multiplication, `x[i] = (x[i] * y[i]) % q`.
+///
+/// `x` and `y` are blocks of eight coefficients modulo `q = 8380417`, which must be reduced.
+///
+/// ### Safety
+///
+/// The caller must ensure that `x` and `y` are valid pointers to data that is aligned along a four
+/// byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_dilithium_mul(x: *mut [u32; 8], y: *const [u32; 8]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::DILITHIUM_MUL,
+            in("a0") x,
+            in("a1") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Lane-wise Dilithium butterfly, `(x[i], y[i]) = ((x[i] + y[i]) % q, (x[i] - y[i]) % q)`.
+///
+/// `x` and `y` are blocks of eight coefficients modulo `q = 8380417`, which must be reduced.
+///
+/// ### Safety
+///
+/// The caller must ensure that `x` and `y` are valid pointers to data that is aligned along a four
+/// byte boundary, and that the blocks do not overlap.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_dilithium_butterfly(x: *mut [u32; 8], y: *mut [u32; 8]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::DILITHIUM_BUTTERFLY,
+            in("a0") x,
+            in("a1") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
@@ -26,6 +26,32 @@ pub extern "C" fn syscall_ed_add(p: *mut [u32; 16], q: *const [u32; 16]) {
     unreachable!()
 }
 
+/// Multiplies an Edwards point by a 256-bit scalar.
+///
+/// The result is stored in the point. The scalar is interpreted as a little-endian integer and is
+/// not reduced modulo the group order.
+///
+/// ### Safety
+///
+/// The caller must ensure that `p` and `scalar` are valid pointers to data that is aligned along a
+/// four byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_ed_scalar_mul(p: *mut [u32; 16], scalar: *const [u32; 8]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::ED_SCALAR_MUL,
+            in("a0") p,
+            in("a1") scalar
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
 /// Decompresses a compressed Edwards point.
 ///
 /// The second half of the input array should contain the compressed Y point with the final bit as
@@ -57,3 +83,30 @@ pub extern "C" fn syscall_ed_decompress(point: &mut [u8; 64]) {
     #[cfg(not(target_os = "zkvm"))]
     unreachable!()
 }
+
+/// Computes one window of a multi-scalar multiplication on Edwards points.
+///
+/// `desc` is `[n, table, digits]`, where `table` points to `n` tables of the 16 multiples
+/// `[0] P_i, ..., [15] P_i` and `digits` points to `n` words each smaller than 16. The accumulator
+/// is updated to `[16] acc + sum_i [digit_i] P_i`.
+///
+/// ### Safety
+///
+/// The caller must ensure that `acc`, `desc` and the memory they reference are valid and aligned
+/// along a four byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_ed_msm(acc: *mut [u32; 16], desc: *const [u32; 3]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::ED_MSM,
+            in("a0") acc,
+            in("a1") desc
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
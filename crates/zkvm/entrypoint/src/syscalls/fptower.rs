@@ -121,6 +121,27 @@ pub extern "C" fn syscall_bls12381_fp2_mulmod(x: *mut u32, y: *const u32) {
     unreachable!()
 }
 
+/// BLS12-381 Fp batch inversion operation.
+///
+/// Each of the `len` field elements at `x` is replaced by its inverse. `len` must be between 1 and
+/// 64, and the elements must be non-zero.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_bls12381_fp_batch_inv(x: *mut u32, len: u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BLS12381_FP_BATCH_INV,
+            in("a0") x,
+            in("a1") len,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
 /// Fp addition operation.
 ///
 /// The result is written over the first input.
@@ -240,3 +261,24 @@ pub extern "C" fn syscall_bn254_fp2_mulmod(x: *mut u32, y: *const u32) {
     #[cfg(not(target_os = "zkvm"))]
     unreachable!()
 }
+
+/// BN254 Fp batch inversion operation.
+///
+/// Each of the `len` field elements at `x` is replaced by its inverse. `len` must be between 1 and
+/// 64, and the elements must be non-zero.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_bn254_fp_batch_inv(x: *mut u32, len: u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BN254_FP_BATCH_INV,
+            in("a0") x,
+            in("a1") len,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
@@ -4,6 +4,10 @@ cfg_if::cfg_if! {
         use sha2::Digest;
         use crate::zkvm;
         use crate::{PV_DIGEST_NUM_WORDS, POSEIDON_NUM_WORDS};
+
+        /// The file descriptor of the public values.
+        #[cfg(feature = "commit-stdin")]
+        const FD_PUBLIC_VALUES: u32 = 3;
     }
 }
 
@@ -20,6 +24,27 @@ cfg_if::cfg_if! {
 pub extern "C" fn syscall_halt(exit_code: u8) -> ! {
     #[cfg(target_os = "zkvm")]
     unsafe {
+        // When committing to stdin, read the elements of the hint stream the program did not
+        // read, so that the digest covers all of stdin, and append the digest to the public values.
+        // The number of remaining elements is reported by the prover, but every element read is
+        // hashed, so that a digest of any other elements does not match the expected commitment.
+        #[cfg(feature = "commit-stdin")]
+        {
+            while zkvm::hint_remaining() > 0 {
+                let len = crate::syscalls::syscall_hint_len();
+                let ptr = crate::syscalls::sys_alloc_aligned(((len + 3) / 4 * 4).max(4), 4);
+                crate::syscalls::syscall_hint_read(ptr, len);
+            }
+            let stdin_digest = core::mem::take(&mut *core::ptr::addr_of_mut!(zkvm::STDIN_HASHER))
+                .unwrap()
+                .finalize();
+            crate::syscalls::syscall_write(
+                FD_PUBLIC_VALUES,
+                stdin_digest.as_ptr(),
+                stdin_digest.len(),
+            );
+        }
+
         // When we halt, we retrieve the public values finalized digest.  This is the hash of all
         // the bytes written to the public values fd.
         let pv_digest_bytes =
@@ -62,3 +87,26 @@ pub extern "C" fn syscall_halt(exit_code: u8) -> ! {
     #[cfg(not(target_os = "zkvm"))]
     unreachable!()
 }
+
+/// Aborts the execution with the reason `code` and the message of `len` bytes at `message`.
+///
+/// Unlike [`syscall_halt`], the public values are not committed: the host stops executing right
+/// away, so the execution cannot be proven.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_abort(code: u32, message: *const u8, len: usize) -> ! {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::ABORT,
+            in("a0") code,
+            in("a1") message,
+            in("a2") len,
+        );
+        unreachable!()
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
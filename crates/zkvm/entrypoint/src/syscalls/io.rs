@@ -13,6 +13,7 @@ pub extern "C" fn syscall_write(fd: u32, write_buf: *const u8, nbytes: usize) {
     cfg_if::cfg_if! {
         if #[cfg(target_os = "zkvm")] {
             const FD_PUBLIC_VALUES: u32 = 3;
+
             unsafe {
                 asm!(
                     "ecall",
@@ -67,6 +68,131 @@ pub extern "C" fn syscall_hint_read(ptr: *mut u8, len: usize) {
             in("a0") ptr,
             in("a1") len,
         );
+
+        // When committing to stdin, every element of the hint stream is hashed with its length as
+        // it is read, and the digest is committed to the public values when the program halts.
+        #[cfg(feature = "commit-stdin")]
+        zkvm::hash_hint(ptr, len);
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Writes the next element in the hint stream, made of `num_words` words, to the given buffer.
+///
+/// Unlike [`syscall_hint_read`], the buffer may already be initialized: the words are written
+/// through the `HINT_READ_WORDS` precompile, so `num_words` must be between 1 and 32.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_hint_read_words(ptr: *mut u32, num_words: usize) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::HINT_READ_WORDS,
+            in("a0") ptr,
+            in("a1") num_words,
+        );
+
+        // Hash the element as `syscall_hint_read` does.
+        #[cfg(feature = "commit-stdin")]
+        zkvm::hash_hint(ptr as *const u8, num_words * 4);
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Writes the number of elements of the hint stream that were not read yet to `ptr`.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_hint_remaining(ptr: *mut u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::HINT_REMAINING,
+            in("a0") ptr,
+            in("a1") 0,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Writes the number of cycles executed so far, as a little-endian `u64`, and the number of extra
+/// cycles of the last precompile call to `ptr`.
+///
+/// The counters are reported by the prover and are not constrained by the proof.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_cycle_info(ptr: *mut u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::CYCLE_INFO,
+            in("a0") ptr,
+            in("a1") 0,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Writes the length of the given paged input to `ptr`, as a little-endian `u64`.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_paged_input_len(index: u32, ptr: *mut u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::PAGED_INPUT_LEN,
+            in("a0") index,
+            in("a1") ptr,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Maps a page of the ROM at its address in the ROM region, and writes the siblings of the page on
+/// its path to the root of the ROM to the given buffer.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_rom_map(page: u32, path: *mut u8) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::ROM_MAP,
+            in("a0") page,
+            in("a1") path,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Maps a page of a paged input into the given buffer. The upper bits of `page` hold the index of
+/// the paged input, and its lower bits the index of the page.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_paged_input_map(ptr: *mut u8, page: u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::PAGED_INPUT_MAP,
+            in("a0") ptr,
+            in("a1") page,
+        );
     }
 
     #[cfg(not(target_os = "zkvm"))]
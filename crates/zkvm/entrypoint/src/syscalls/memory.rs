@@ -15,6 +15,9 @@
 // Memory addresses must be lower than BabyBear prime.
 const MAX_MEMORY: usize = 0x78000000;
 
+// The start of the read-only data segment, which the heap must not overlap if the program has one.
+const ROM_START: usize = 0x70000000;
+
 #[allow(clippy::missing_safety_doc)]
 #[no_mangle]
 pub unsafe extern "C" fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u8 {
@@ -45,6 +48,15 @@ pub unsafe extern "C" fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u
     if overflowed || MAX_MEMORY < heap_pos {
         panic!("Memory limit exceeded (0x78000000)");
     }
+    // The rom header holds its length and root, which read as zero if there is no rom.
+    if ROM_START < heap_pos
+        && unsafe {
+            core::ptr::read_volatile(ROM_START as *const u32) != 0
+                || core::ptr::read_volatile((ROM_START + 4) as *const u32) != 0
+        }
+    {
+        panic!("Memory limit exceeded (0x70000000), the rest is reserved for the rom");
+    }
 
     unsafe { HEAP_POS = heap_pos };
     ptr
@@ -1,12 +1,15 @@
 mod bigint;
 mod bls12381;
 mod bn254;
+mod byte_scan;
+mod dilithium;
 mod ed25519;
 mod fptower;
 mod halt;
 mod io;
 mod keccak_permute;
 mod memory;
+mod ripemd160_compress;
 mod secp256k1;
 mod sha_compress;
 mod sha_extend;
@@ -15,16 +18,20 @@ mod uint256_mul;
 mod unconstrained;
 #[cfg(feature = "verify")]
 mod verify;
+mod wide_alu;
 
 pub use bigint::*;
 pub use bls12381::*;
 pub use bn254::*;
+pub use byte_scan::*;
+pub use dilithium::*;
 pub use ed25519::*;
 pub use fptower::*;
 pub use halt::*;
 pub use io::*;
 pub use keccak_permute::*;
 pub use memory::*;
+pub use ripemd160_compress::*;
 pub use secp256k1::*;
 pub use sha_compress::*;
 pub use sha_extend::*;
@@ -33,6 +40,7 @@ pub use uint256_mul::*;
 pub use unconstrained::*;
 #[cfg(feature = "verify")]
 pub use verify::*;
+pub use wide_alu::*;
 
 /// These codes MUST match the codes in `core/src/runtime/syscall.rs`. There is a derived test
 /// that checks that the enum is consistent with the syscalls.
@@ -94,6 +102,24 @@ pub const HINT_LEN: u32 = 0x00_00_00_F0;
 /// Executes `HINT_READ`.
 pub const HINT_READ: u32 = 0x00_00_00_F1;
 
+/// Executes `PAGED_INPUT_LEN`.
+pub const PAGED_INPUT_LEN: u32 = 0x00_00_00_F2;
+
+/// Executes `PAGED_INPUT_MAP`.
+pub const PAGED_INPUT_MAP: u32 = 0x00_00_00_F3;
+
+/// Executes `HINT_REMAINING`.
+pub const HINT_REMAINING: u32 = 0x00_00_00_F4;
+
+/// Executes `CYCLE_INFO`.
+pub const CYCLE_INFO: u32 = 0x00_00_00_F5;
+
+/// Executes `ABORT`.
+pub const ABORT: u32 = 0x00_00_00_F6;
+
+/// Executes `ROM_MAP`.
+pub const ROM_MAP: u32 = 0x00_00_00_F7;
+
 /// Executes `BLS12381_DECOMPRESS`.
 pub const BLS12381_DECOMPRESS: u32 = 0x00_00_01_1C;
 
@@ -141,3 +167,72 @@ pub const BN254_FP2_SUB: u32 = 0x00_01_01_2A;
 
 /// Executes the `BN254_FP2_MUL` precompile.
 pub const BN254_FP2_MUL: u32 = 0x00_01_01_2B;
+
+/// Executes the `ED_SCALAR_MUL` precompile.
+pub const ED_SCALAR_MUL: u32 = 0x00_01_01_2C;
+
+/// Executes the `BIGINT_ADD` precompile.
+pub const BIGINT_ADD: u32 = 0x00_01_01_2D;
+
+/// Executes the `BIGINT_SUB` precompile.
+pub const BIGINT_SUB: u32 = 0x00_01_01_2E;
+
+/// Executes the `BIGINT_MUL` precompile.
+pub const BIGINT_MUL: u32 = 0x00_10_01_2F;
+
+/// Executes the `DILITHIUM_ADD` precompile.
+pub const DILITHIUM_ADD: u32 = 0x00_01_01_30;
+
+/// Executes the `DILITHIUM_SUB` precompile.
+pub const DILITHIUM_SUB: u32 = 0x00_01_01_31;
+
+/// Executes the `DILITHIUM_MUL` precompile.
+pub const DILITHIUM_MUL: u32 = 0x00_01_01_32;
+
+/// Executes the `DILITHIUM_BUTTERFLY` precompile.
+pub const DILITHIUM_BUTTERFLY: u32 = 0x00_01_01_33;
+
+/// Executes the `WIDE_ALU_ADD` precompile.
+pub const WIDE_ALU_ADD: u32 = 0x00_01_01_34;
+
+/// Executes the `WIDE_ALU_SUB` precompile.
+pub const WIDE_ALU_SUB: u32 = 0x00_01_01_35;
+
+/// Executes the `WIDE_ALU_MUL` precompile.
+pub const WIDE_ALU_MUL: u32 = 0x00_01_01_36;
+
+/// Executes the `WIDE_ALU_LTU` precompile.
+pub const WIDE_ALU_LTU: u32 = 0x00_01_01_37;
+
+/// Executes the `WIDE_ALU_LT` precompile.
+pub const WIDE_ALU_LT: u32 = 0x00_01_01_38;
+
+/// Executes the `ED_MSM` precompile.
+pub const ED_MSM: u32 = 0x00_01_01_39;
+
+/// Executes the `SECP256K1_MSM` precompile.
+pub const SECP256K1_MSM: u32 = 0x00_01_01_3A;
+
+/// Executes the `BN254_MSM` precompile.
+pub const BN254_MSM: u32 = 0x00_01_01_3B;
+
+/// Executes `RIPEMD160_COMPRESS`.
+pub const RIPEMD160_COMPRESS: u32 = 0x00_01_01_3C;
+
+/// Executes the `BN254_FP_BATCH_INV` precompile.
+pub const BN254_FP_BATCH_INV: u32 = 0x00_01_01_3D;
+
+/// Executes the `BLS12381_FP_BATCH_INV` precompile.
+pub const BLS12381_FP_BATCH_INV: u32 = 0x00_01_01_3E;
+
+/// Executes the `BYTE_SCAN_FIND` precompile.
+pub const BYTE_SCAN_FIND: u32 = 0x00_01_01_3F;
+
+/// Executes the `BYTE_SCAN_COPY` precompile.
+pub const BYTE_SCAN_COPY: u32 = 0x00_01_01_40;
+
+/// Executes the `BYTE_SCAN_HEX_DECODE` precompile.
+pub const BYTE_SCAN_HEX_DECODE: u32 = 0x00_01_01_41;
+
+/// Executes the `HINT_READ_WORDS` precompile.
+pub const HINT_READ_WORDS: u32 = 0x00_01_01_42;
@@ -0,0 +1,27 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Executes the RIPEMD-160 compress operation on the given message block and a given state.
+///
+/// The message block and the state are little-endian words, as in the RIPEMD-160 specification.
+///
+/// ### Safety
+///
+/// The caller must ensure that `w` and `state` are valid pointers to data that is aligned along a
+/// four byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_ripemd160_compress(w: *const [u32; 16], state: *mut [u32; 5]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::RIPEMD160_COMPRESS,
+            in("a0") w,
+            in("a1") state,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
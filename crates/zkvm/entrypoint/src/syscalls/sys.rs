@@ -1,22 +1,21 @@
-use std::sync::Mutex;
-
-use lazy_static::lazy_static;
 use rand::{rngs::StdRng, Rng, SeedableRng};
+use sp1_lib::io::{FD_ARGS, FD_ENV_VAR};
 
-use crate::syscalls::{syscall_halt, syscall_write};
+use crate::syscalls::{
+    sys_alloc_aligned, syscall_halt, syscall_hint_len, syscall_hint_read, syscall_write,
+};
 
 /// The random number generator seed for the zkVM.
 ///
 /// In the future, we can pass in this seed from the host or have the verifier generate it.
 const PRNG_SEED: u64 = 0x123456789abcdef0;
 
-lazy_static! {
-    /// A lazy static to generate a global random number generator.
-    static ref RNG: Mutex<StdRng> = Mutex::new(StdRng::seed_from_u64(PRNG_SEED));
-}
+/// The global random number generator, created on first use. The zkVM is single threaded, so it
+/// does not need a lock, which keeps this module usable without `std`.
+static mut RNG: Option<StdRng> = None;
 
-/// A lazy static to print a warning once for using the `sys_rand` system call.
-static SYS_RAND_WARNING: std::sync::Once = std::sync::Once::new();
+/// The warning printed when the `sys_rand` system call is first used.
+const SYS_RAND_WARNING: &str = "WARNING: Using insecure random number generator.\n";
 
 /// Generates random bytes.
 ///
@@ -25,10 +24,10 @@ static SYS_RAND_WARNING: std::sync::Once = std::sync::Once::new();
 /// Make sure that `buf` has at least `nwords` words.
 #[no_mangle]
 pub unsafe extern "C" fn sys_rand(recv_buf: *mut u8, words: usize) {
-    SYS_RAND_WARNING.call_once(|| {
-        println!("WARNING: Using insecure random number generator.");
+    let rng = (*core::ptr::addr_of_mut!(RNG)).get_or_insert_with(|| {
+        sys_write(1, SYS_RAND_WARNING.as_ptr(), SYS_RAND_WARNING.len());
+        StdRng::seed_from_u64(PRNG_SEED)
     });
-    let mut rng = RNG.lock().unwrap();
     for i in 0..words {
         let element = recv_buf.add(i);
         *element = rng.gen();
@@ -42,21 +41,81 @@ pub unsafe extern "C" fn sys_panic(msg_ptr: *const u8, len: usize) -> ! {
     syscall_halt(1);
 }
 
-#[allow(unused_variables)]
+/// Reads the next element of the hint stream into a fresh allocation, which is never freed.
+unsafe fn read_hint() -> &'static [u8] {
+    let len = syscall_hint_len();
+    // The syscall writes to uninitialized memory, so the element needs a fresh allocation.
+    let ptr = sys_alloc_aligned(len, 4);
+    syscall_hint_read(ptr, len);
+    core::slice::from_raw_parts(ptr, len)
+}
+
+/// Copies as much of `value` as fits in the `words` words at `recv_buf`, and returns its length.
+unsafe fn copy_to_words(value: &[u8], recv_buf: *mut u32, words: usize) -> usize {
+    let len = value.len().min(words * 4);
+    core::ptr::copy_nonoverlapping(value.as_ptr(), recv_buf as *mut u8, len);
+    value.len()
+}
+
+/// Looks up the environment variable `varname` set with `SP1Stdin::env`, which backs
+/// `std::env::var`. Copies its value to `recv_buf`, up to `words` words, and returns its length in
+/// bytes, or `usize::MAX` if it is not set.
+///
+/// # Safety
+///
+/// `varname` must point to `varname_len` bytes, and `recv_buf` to `words` words.
 #[no_mangle]
-pub const fn sys_getenv(
+pub unsafe extern "C" fn sys_getenv(
     recv_buf: *mut u32,
     words: usize,
     varname: *const u8,
     varname_len: usize,
 ) -> usize {
-    0
+    syscall_write(FD_ENV_VAR, varname, varname_len);
+    if read_hint() != [1] {
+        return usize::MAX;
+    }
+    copy_to_words(read_hint(), recv_buf, words)
+}
+
+/// The arguments set by the host, fetched on first use.
+static mut ARGS: Option<alloc::vec::Vec<&'static [u8]>> = None;
+
+/// Returns the arguments set with `SP1Stdin::args`, fetching them from the host on first use.
+unsafe fn args() -> &'static [&'static [u8]] {
+    (*core::ptr::addr_of_mut!(ARGS)).get_or_insert_with(|| {
+        syscall_write(FD_ARGS, core::ptr::null(), 0);
+        let count = u32::from_le_bytes(read_hint().try_into().expect("invalid argument count"));
+        (0..count).map(|_| read_hint()).collect()
+    })
 }
 
-#[allow(unused_variables)]
+/// Returns the number of arguments set with `SP1Stdin::args`, which backs `std::env::args`.
+#[allow(clippy::missing_safety_doc)]
+#[no_mangle]
+pub unsafe extern "C" fn sys_argc() -> usize {
+    args().len()
+}
+
+/// Copies the argument at `arg_index` to `recv_buf`, up to `words` words, and returns its length in
+/// bytes.
+///
+/// # Safety
+///
+/// `recv_buf` must point to `words` words, and `arg_index` must be less than [`sys_argc`].
+#[no_mangle]
+pub unsafe extern "C" fn sys_argv(recv_buf: *mut u32, words: usize, arg_index: usize) -> usize {
+    copy_to_words(args()[arg_index], recv_buf, words)
+}
+
+/// Allocates `nwords` words on the heap.
+///
+/// # Safety
+///
+/// The memory is uninitialized.
 #[no_mangle]
-pub const fn sys_alloc_words(nwords: usize) -> *mut u32 {
-    core::ptr::null_mut()
+pub unsafe extern "C" fn sys_alloc_words(nwords: usize) -> *mut u32 {
+    sys_alloc_aligned(nwords * 4, 4) as *mut u32
 }
 
 #[allow(unused_unsafe)]
@@ -0,0 +1,127 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Wrapping 64-bit addition, `x = x + y mod 2^64`.
+///
+/// `x` and `y` are 64-bit integers stored as two little-endian words.
+///
+/// ### Safety
+///
+/// The caller must ensure that `x` and `y` are valid pointers to data that is aligned along a four
+/// byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_wide_alu_add(x: *mut [u32; 2], y: *const [u32; 2]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::WIDE_ALU_ADD,
+            in("a0") x,
+            in("a1") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Wrapping 64-bit subtraction, `x = x - y mod 2^64`.
+///
+/// `x` and `y` are 64-bit integers stored as two little-endian words.
+///
+/// ### Safety
+///
+/// The caller must ensure that `x` and `y` are valid pointers to data that is aligned along a four
+/// byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_wide_alu_sub(x: *mut [u32; 2], y: *const [u32; 2]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::WIDE_ALU_SUB,
+            in("a0") x,
+            in("a1") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Wrapping 64-bit multiplication, `x = x * y mod 2^64`.
+///
+/// `x` and `y` are 64-bit integers stored as two little-endian words.
+///
+/// ### Safety
+///
+/// The caller must ensure that `x` and `y` are valid pointers to data that is aligned along a four
+/// byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_wide_alu_mul(x: *mut [u32; 2], y: *const [u32; 2]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::WIDE_ALU_MUL,
+            in("a0") x,
+            in("a1") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Unsigned 64-bit comparison, `x = 1` if `x < y` and `x = 0` otherwise.
+///
+/// `x` and `y` are 64-bit integers stored as two little-endian words.
+///
+/// ### Safety
+///
+/// The caller must ensure that `x` and `y` are valid pointers to data that is aligned along a four
+/// byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_wide_alu_ltu(x: *mut [u32; 2], y: *const [u32; 2]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::WIDE_ALU_LTU,
+            in("a0") x,
+            in("a1") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Signed 64-bit comparison, `x = 1` if `x < y` and `x = 0` otherwise.
+///
+/// `x` and `y` are 64-bit integers stored as two little-endian words.
+///
+/// ### Safety
+///
+/// The caller must ensure that `x` and `y` are valid pointers to data that is aligned along a four
+/// byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_wide_alu_lt(x: *mut [u32; 2], y: *const [u32; 2]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::WIDE_ALU_LT,
+            in("a0") x,
+            in("a1") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
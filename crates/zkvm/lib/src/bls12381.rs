@@ -1,12 +1,20 @@
+#[cfg(feature = "std")]
 use std::io::ErrorKind;
 
 use crate::{
-    syscall_bls12381_add, syscall_bls12381_decompress, syscall_bls12381_double, utils::AffinePoint,
+    syscall_bls12381_add, syscall_bls12381_decompress, syscall_bls12381_double,
+    syscall_bls12381_fp_batch_inv, utils::AffinePoint,
 };
 
 /// The number of limbs in [Bls12381AffinePoint].
 pub const N: usize = 24;
 
+/// The number of words of an element of the base field.
+pub const FP_NUM_WORDS: usize = 12;
+
+/// The maximum number of elements inverted by one call to the `bls12381_fp_batch_inv` precompile.
+const FP_BATCH_INV_MAX_LEN: usize = 64;
+
 /// An affine point on the BLS12-381 curve.
 #[derive(Copy, Clone)]
 #[repr(align(4))]
@@ -52,6 +60,7 @@ impl AffinePoint<N> for Bls12381AffinePoint {
 }
 
 /// Decompresses a compressed public key using bls12381_decompress precompile.
+#[cfg(feature = "std")]
 pub fn decompress_pubkey(compressed_key: &[u8; 48]) -> Result<[u8; 96], ErrorKind> {
     let mut decompressed_key = [0u8; 96];
     decompressed_key[..48].copy_from_slice(compressed_key);
@@ -64,3 +73,19 @@ pub fn decompress_pubkey(compressed_key: &[u8; 48]) -> Result<[u8; 96], ErrorKin
 
     Ok(decompressed_key)
 }
+
+/// Replaces every element of the base field by its inverse, or returns `false` without modifying
+/// them if one of them is zero.
+///
+/// The elements are reduced little endian words. The inverses are computed with Montgomery's trick
+/// by the `bls12381_fp_batch_inv` precompile, which costs much less than inverting the elements one
+/// by one.
+pub fn fp_batch_inverse(elements: &mut [[u32; FP_NUM_WORDS]]) -> bool {
+    if elements.iter().any(|x| x.iter().all(|&word| word == 0)) {
+        return false;
+    }
+    for chunk in elements.chunks_mut(FP_BATCH_INV_MAX_LEN) {
+        unsafe { syscall_bls12381_fp_batch_inv(chunk.as_mut_ptr().cast(), chunk.len() as u32) };
+    }
+    true
+}
@@ -0,0 +1,159 @@
+//! The BN254 precompiles of the EVM: `ecAdd` (0x06), `ecMul` (0x07) and `ecPairing` (0x08), as
+//! specified in EIP-196 and EIP-197.
+//!
+//! Field elements are encoded as 32 big endian bytes, `G1` points as `x || y`, and `G2` points
+//! as `x_c1 || x_c0 || y_c1 || y_c0`. The point at infinity is encoded as all zeros. Like in the
+//! EVM, the inputs of `ecAdd` and `ecMul` are padded with zeros or truncated to their expected
+//! length.
+
+use alloc::vec::Vec;
+
+use super::{
+    field::{Fp, Fp2, NUM_WORDS},
+    pairing::{pairing_check, reduce_scalar, G1Affine, G2Affine},
+    Bn254AffinePoint,
+};
+use crate::utils::AffinePoint;
+
+/// The length of an encoded `G1` point.
+const G1_LEN: usize = 64;
+
+/// The length of an encoded `G2` point.
+const G2_LEN: usize = 128;
+
+/// The length of each pair of the input of `ecPairing`.
+const PAIR_LEN: usize = G1_LEN + G2_LEN;
+
+/// The reasons for which a BN254 precompile call fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bn254Error {
+    /// The length of the input of `ecPairing` is not a multiple of 192 bytes.
+    InvalidInputLength,
+    /// A coordinate is not smaller than the modulus of the base field.
+    InvalidFieldElement,
+    /// A point is not on its curve.
+    NotOnCurve,
+    /// A `G2` point is not in the subgroup of order `r`.
+    NotInSubgroup,
+}
+
+/// Pads `input` with zeros, or truncates it, to `N` bytes.
+fn padded<const N: usize>(input: &[u8]) -> [u8; N] {
+    let mut bytes = [0; N];
+    let len = input.len().min(N);
+    bytes[..len].copy_from_slice(&input[..len]);
+    bytes
+}
+
+fn decode_fp(bytes: &[u8]) -> Result<Fp, Bn254Error> {
+    Fp::from_be_bytes(bytes.try_into().unwrap()).ok_or(Bn254Error::InvalidFieldElement)
+}
+
+/// Decodes a `G1` point, returning `None` for the point at infinity.
+fn decode_g1(bytes: &[u8]) -> Result<Option<G1Affine>, Bn254Error> {
+    let point = G1Affine { x: decode_fp(&bytes[..32])?, y: decode_fp(&bytes[32..G1_LEN])? };
+    if point.x.is_zero() && point.y.is_zero() {
+        return Ok(None);
+    }
+    if !point.is_on_curve() {
+        return Err(Bn254Error::NotOnCurve);
+    }
+    Ok(Some(point))
+}
+
+/// Decodes a `G2` point, returning `None` for the point at infinity.
+fn decode_g2(bytes: &[u8]) -> Result<Option<G2Affine>, Bn254Error> {
+    let x = Fp2::new(decode_fp(&bytes[32..64])?, decode_fp(&bytes[..32])?);
+    let y = Fp2::new(decode_fp(&bytes[96..128])?, decode_fp(&bytes[64..96])?);
+    let point = G2Affine { x, y };
+    if x.is_zero() && y.is_zero() {
+        return Ok(None);
+    }
+    if !point.is_on_curve() {
+        return Err(Bn254Error::NotOnCurve);
+    }
+    if !point.is_in_subgroup() {
+        return Err(Bn254Error::NotInSubgroup);
+    }
+    Ok(Some(point))
+}
+
+fn to_affine_point(point: &G1Affine) -> Bn254AffinePoint {
+    let mut limbs = [0; 2 * NUM_WORDS];
+    limbs[..NUM_WORDS].copy_from_slice(&point.x.0);
+    limbs[NUM_WORDS..].copy_from_slice(&point.y.0);
+    Bn254AffinePoint(limbs)
+}
+
+fn encode_g1(point: Option<Bn254AffinePoint>) -> [u8; G1_LEN] {
+    let mut bytes = [0; G1_LEN];
+    if let Some(point) = point {
+        let x = Fp(point.0[..NUM_WORDS].try_into().unwrap());
+        let y = Fp(point.0[NUM_WORDS..].try_into().unwrap());
+        bytes[..32].copy_from_slice(&x.to_be_bytes());
+        bytes[32..].copy_from_slice(&y.to_be_bytes());
+    }
+    bytes
+}
+
+/// The `ecAdd` precompile: adds two `G1` points.
+pub fn ec_add(input: &[u8]) -> Result<[u8; G1_LEN], Bn254Error> {
+    let input = padded::<{ 2 * G1_LEN }>(input);
+    let a = decode_g1(&input[..G1_LEN])?;
+    let b = decode_g1(&input[G1_LEN..])?;
+    let sum = match (a, b) {
+        (None, None) => None,
+        (Some(p), None) | (None, Some(p)) => Some(to_affine_point(&p)),
+        // The addition precompile does not handle doubling nor opposite points.
+        (Some(a), Some(b)) if a.x == b.x => (a.y == b.y).then(|| {
+            let mut a = to_affine_point(&a);
+            a.double();
+            a
+        }),
+        (Some(a), Some(b)) => {
+            let mut a = to_affine_point(&a);
+            a.add_assign(&to_affine_point(&b));
+            Some(a)
+        }
+    };
+    Ok(encode_g1(sum))
+}
+
+/// The `ecMul` precompile: multiplies a `G1` point by a scalar.
+pub fn ec_mul(input: &[u8]) -> Result<[u8; G1_LEN], Bn254Error> {
+    let input = padded::<{ G1_LEN + 32 }>(input);
+    let point = decode_g1(&input[..G1_LEN])?;
+    let mut scalar = [0; NUM_WORDS];
+    for (word, chunk) in scalar.iter_mut().zip(input[G1_LEN..].rchunks_exact(4)) {
+        *word = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+    // Since the reduced scalar is smaller than the order of the point, the double-and-add never
+    // adds two equal or opposite points.
+    let scalar = reduce_scalar(scalar);
+    let product = point.and_then(|point| {
+        let mut point = to_affine_point(&point);
+        point.mul_assign(&scalar).ok()?;
+        Some(point)
+    });
+    Ok(encode_g1(product))
+}
+
+/// The `ecPairing` precompile: checks that the product of the pairings of the given pairs of
+/// points is one, returning the result as a big endian word.
+pub fn ec_pairing(input: &[u8]) -> Result<[u8; 32], Bn254Error> {
+    if input.len() % PAIR_LEN != 0 {
+        return Err(Bn254Error::InvalidInputLength);
+    }
+    let mut pairs = Vec::with_capacity(input.len() / PAIR_LEN);
+    for pair in input.chunks_exact(PAIR_LEN) {
+        let p = decode_g1(&pair[..G1_LEN])?;
+        let q = decode_g2(&pair[G1_LEN..])?;
+        // Pairs with a point at infinity do not contribute to the product.
+        if let (Some(p), Some(q)) = (p, q) {
+            pairs.push((p, q));
+        }
+    }
+    let mut output = [0; 32];
+    output[31] = pairing_check(&pairs) as u8;
+    Ok(output)
+}
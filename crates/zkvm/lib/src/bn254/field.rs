@@ -0,0 +1,386 @@
+//! Arithmetic in the BN254 base field and its extensions, on top of the `bn254_fp` and
+//! `bn254_fp2` precompiles.
+//!
+//! The extension fields form the usual tower `Fp2 = Fp[u] / (u^2 + 1)`,
+//! `Fp6 = Fp2[v] / (v^3 - (9 + u))` and `Fp12 = Fp6[w] / (w^2 - v)`. All elements are kept
+//! reduced, and every precompile call writes to a copy of its first operand, so that the operands
+//! of a call never alias.
+
+use core::ops::{Add, Mul, Neg, Sub};
+
+use crate::{
+    syscall_bn254_fp2_addmod, syscall_bn254_fp2_mulmod, syscall_bn254_fp2_submod,
+    syscall_bn254_fp_addmod, syscall_bn254_fp_batch_inv, syscall_bn254_fp_mulmod,
+    syscall_bn254_fp_submod,
+};
+
+/// The number of words of an element of the base field.
+pub const NUM_WORDS: usize = 8;
+
+/// The modulus of the base field, as little endian words.
+pub const MODULUS: [u32; NUM_WORDS] = [
+    0xd87cfd47, 0x3c208c16, 0x6871ca8d, 0x97816a91, 0x8181585d, 0xb85045b6, 0xe131a029, 0x30644e72,
+];
+
+/// The maximum number of elements inverted by one call to the `bn254_fp_batch_inv` precompile.
+const BATCH_INV_MAX_LEN: usize = 64;
+
+/// An element of the base field, as reduced little endian words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct Fp(pub [u32; NUM_WORDS]);
+
+/// An element of `Fp2`, as `c0 + c1 * u`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct Fp2 {
+    pub c0: Fp,
+    pub c1: Fp,
+}
+
+/// An element of `Fp6`, as `c0 + c1 * v + c2 * v^2`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fp6 {
+    pub c0: Fp2,
+    pub c1: Fp2,
+    pub c2: Fp2,
+}
+
+/// An element of `Fp12`, as `c0 + c1 * w`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fp12 {
+    pub c0: Fp6,
+    pub c1: Fp6,
+}
+
+/// The non-residue `9 + u` defining `Fp6`.
+const XI: Fp2 = Fp2::new(Fp::from_u32(9), Fp::ONE);
+
+/// Raises `x` to the power `exp`, given as little endian words.
+fn pow<T: Copy + Mul<Output = T>>(x: T, one: T, exp: &[u32]) -> T {
+    let mut result = one;
+    for word in exp.iter().rev() {
+        for i in (0..32).rev() {
+            result = result * result;
+            if (word >> i) & 1 == 1 {
+                result = result * x;
+            }
+        }
+    }
+    result
+}
+
+/// Whether `a < b`, for little endian words of the same length.
+pub(crate) fn words_lt(a: &[u32], b: &[u32]) -> bool {
+    for (a, b) in a.iter().rev().zip(b.iter().rev()) {
+        if a != b {
+            return a < b;
+        }
+    }
+    false
+}
+
+impl Fp {
+    pub const ZERO: Self = Self([0; NUM_WORDS]);
+    pub const ONE: Self = Self::from_u32(1);
+
+    /// Creates a field element from a small integer.
+    pub const fn from_u32(value: u32) -> Self {
+        let mut words = [0; NUM_WORDS];
+        words[0] = value;
+        Self(words)
+    }
+
+    /// Decodes a big endian field element, or returns `None` if it is not reduced.
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let mut words = [0; NUM_WORDS];
+        for (word, chunk) in words.iter_mut().zip(bytes.rchunks_exact(4)) {
+            *word = u32::from_be_bytes(chunk.try_into().unwrap());
+        }
+        words_lt(&words, &MODULUS).then_some(Self(words))
+    }
+
+    /// Encodes the field element in big endian.
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        for (chunk, word) in bytes.rchunks_exact_mut(4).zip(self.0.iter()) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        bytes
+    }
+
+    pub fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
+    pub fn square(&self) -> Self {
+        *self * *self
+    }
+
+    /// The inverse of the field element, or `None` if it is zero.
+    pub fn inverse(&self) -> Option<Self> {
+        let mut inverse = [*self];
+        Self::batch_inverse(&mut inverse).then_some(inverse[0])
+    }
+
+    /// Replaces every element by its inverse, or returns `false` without modifying them if one of
+    /// them is zero.
+    ///
+    /// The inverses are computed with Montgomery's trick by the `bn254_fp_batch_inv` precompile,
+    /// which costs much less than inverting the elements one by one.
+    pub fn batch_inverse(elements: &mut [Self]) -> bool {
+        if elements.iter().any(Self::is_zero) {
+            return false;
+        }
+        for chunk in elements.chunks_mut(BATCH_INV_MAX_LEN) {
+            unsafe { syscall_bn254_fp_batch_inv(chunk.as_mut_ptr().cast(), chunk.len() as u32) };
+        }
+        true
+    }
+}
+
+impl Add for Fp {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        unsafe { syscall_bn254_fp_addmod(self.0.as_mut_ptr(), rhs.0.as_ptr()) };
+        self
+    }
+}
+
+impl Sub for Fp {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self {
+        unsafe { syscall_bn254_fp_submod(self.0.as_mut_ptr(), rhs.0.as_ptr()) };
+        self
+    }
+}
+
+impl Mul for Fp {
+    type Output = Self;
+
+    fn mul(mut self, rhs: Self) -> Self {
+        unsafe { syscall_bn254_fp_mulmod(self.0.as_mut_ptr(), rhs.0.as_ptr()) };
+        self
+    }
+}
+
+impl Neg for Fp {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::ZERO - self
+    }
+}
+
+impl Fp2 {
+    pub const ZERO: Self = Self::new(Fp::ZERO, Fp::ZERO);
+    pub const ONE: Self = Self::new(Fp::ONE, Fp::ZERO);
+
+    pub const fn new(c0: Fp, c1: Fp) -> Self {
+        Self { c0, c1 }
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut u32 {
+        self.c0.0.as_mut_ptr()
+    }
+
+    fn as_ptr(&self) -> *const u32 {
+        self.c0.0.as_ptr()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
+    pub fn square(&self) -> Self {
+        *self * *self
+    }
+
+    /// The conjugate `c0 - c1 * u`, which is also the image of the Frobenius map.
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.c0, -self.c1)
+    }
+
+    /// Multiplies both coefficients by an element of the base field.
+    pub fn scale(&self, k: Fp) -> Self {
+        Self::new(self.c0 * k, self.c1 * k)
+    }
+
+    /// The inverse of the element, or `None` if it is zero.
+    pub fn inverse(&self) -> Option<Self> {
+        let norm = (self.c0.square() + self.c1.square()).inverse()?;
+        Some(self.conjugate().scale(norm))
+    }
+}
+
+impl From<Fp> for Fp2 {
+    fn from(c0: Fp) -> Self {
+        Self::new(c0, Fp::ZERO)
+    }
+}
+
+impl Add for Fp2 {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        unsafe { syscall_bn254_fp2_addmod(self.as_mut_ptr(), rhs.as_ptr()) };
+        self
+    }
+}
+
+impl Sub for Fp2 {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self {
+        unsafe { syscall_bn254_fp2_submod(self.as_mut_ptr(), rhs.as_ptr()) };
+        self
+    }
+}
+
+impl Mul for Fp2 {
+    type Output = Self;
+
+    fn mul(mut self, rhs: Self) -> Self {
+        unsafe { syscall_bn254_fp2_mulmod(self.as_mut_ptr(), rhs.as_ptr()) };
+        self
+    }
+}
+
+impl Neg for Fp2 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::ZERO - self
+    }
+}
+
+impl Fp6 {
+    pub const ZERO: Self = Self::new(Fp2::ZERO, Fp2::ZERO, Fp2::ZERO);
+    pub const ONE: Self = Self::new(Fp2::ONE, Fp2::ZERO, Fp2::ZERO);
+
+    pub const fn new(c0: Fp2, c1: Fp2, c2: Fp2) -> Self {
+        Self { c0, c1, c2 }
+    }
+
+    /// Multiplies the element by `v`.
+    fn mul_by_v(&self) -> Self {
+        Self::new(self.c2 * XI, self.c0, self.c1)
+    }
+
+    /// The inverse of the element, or `None` if it is zero.
+    pub fn inverse(&self) -> Option<Self> {
+        let t0 = self.c0.square() - self.c1 * self.c2 * XI;
+        let t1 = self.c2.square() * XI - self.c0 * self.c1;
+        let t2 = self.c1.square() - self.c0 * self.c2;
+        let norm = (self.c0 * t0 + (self.c2 * t1 + self.c1 * t2) * XI).inverse()?;
+        Some(Self::new(t0 * norm, t1 * norm, t2 * norm))
+    }
+}
+
+impl Add for Fp6 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.c0 + rhs.c0, self.c1 + rhs.c1, self.c2 + rhs.c2)
+    }
+}
+
+impl Sub for Fp6 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.c0 - rhs.c0, self.c1 - rhs.c1, self.c2 - rhs.c2)
+    }
+}
+
+impl Mul for Fp6 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let (a0, a1, a2) = (self.c0, self.c1, self.c2);
+        let (b0, b1, b2) = (rhs.c0, rhs.c1, rhs.c2);
+        Self::new(
+            a0 * b0 + (a1 * b2 + a2 * b1) * XI,
+            a0 * b1 + a1 * b0 + a2 * b2 * XI,
+            a0 * b2 + a1 * b1 + a2 * b0,
+        )
+    }
+}
+
+impl Neg for Fp6 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.c0, -self.c1, -self.c2)
+    }
+}
+
+/// The coefficients `(9 + u)^(k * (p^2 - 1) / 6)` of the `p^2`-power Frobenius map on `w^k`, for
+/// `k = 1..=5`. They all lie in the base field.
+const FROBENIUS_SQUARED_COEFFS: [Fp; 5] = [
+    Fp([
+        0x607cfd49, 0xe4bd44e5, 0xbb966e3d, 0xc28f069f, 0xe0acccb0, 0x5e6dd9e7, 0xe131a029,
+        0x30644e72,
+    ]),
+    Fp([
+        0x607cfd48, 0xe4bd44e5, 0xbb966e3d, 0xc28f069f, 0xe0acccb0, 0x5e6dd9e7, 0xe131a029,
+        0x30644e72,
+    ]),
+    Fp([
+        0xd87cfd46, 0x3c208c16, 0x6871ca8d, 0x97816a91, 0x8181585d, 0xb85045b6, 0xe131a029,
+        0x30644e72,
+    ]),
+    Fp([0x77fffffe, 0x57634731, 0xacdb5c4f, 0xd4f263f1, 0xa0d48bac, 0x59e26bce, 0, 0]),
+    Fp([0x77ffffff, 0x57634731, 0xacdb5c4f, 0xd4f263f1, 0xa0d48bac, 0x59e26bce, 0, 0]),
+];
+
+impl Fp12 {
+    pub const ONE: Self = Self::new(Fp6::ONE, Fp6::ZERO);
+
+    pub const fn new(c0: Fp6, c1: Fp6) -> Self {
+        Self { c0, c1 }
+    }
+
+    pub fn square(&self) -> Self {
+        *self * *self
+    }
+
+    /// The conjugate `c0 - c1 * w`, which is the image of the `p^6`-power Frobenius map.
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.c0, -self.c1)
+    }
+
+    /// The image of the `p^2`-power Frobenius map, which fixes `Fp2`.
+    pub fn frobenius_squared(&self) -> Self {
+        let [g1, g2, g3, g4, g5] = FROBENIUS_SQUARED_COEFFS;
+        // `c0` holds the coefficients of `1, w^2, w^4` and `c1` those of `w, w^3, w^5`.
+        Self::new(
+            Fp6::new(self.c0.c0, self.c0.c1.scale(g2), self.c0.c2.scale(g4)),
+            Fp6::new(self.c1.c0.scale(g1), self.c1.c1.scale(g3), self.c1.c2.scale(g5)),
+        )
+    }
+
+    /// The inverse of the element, or `None` if it is zero.
+    pub fn inverse(&self) -> Option<Self> {
+        let norm = (self.c0 * self.c0 - (self.c1 * self.c1).mul_by_v()).inverse()?;
+        Some(Self::new(self.c0 * norm, -(self.c1 * norm)))
+    }
+
+    /// Raises the element to the power `exp`, given as little endian words.
+    pub fn pow(&self, exp: &[u32]) -> Self {
+        pow(*self, Self::ONE, exp)
+    }
+}
+
+impl Mul for Fp12 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.c0 * rhs.c0 + (self.c1 * rhs.c1).mul_by_v(),
+            self.c0 * rhs.c1 + self.c1 * rhs.c0,
+        )
+    }
+}
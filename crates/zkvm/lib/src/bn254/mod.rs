@@ -1,3 +1,17 @@
+//! Operations on the BN254 (alt_bn128) curve, accelerated with the BN254 precompiles.
+//!
+//! Besides the affine points used with [AffinePoint], this module implements the optimal ate
+//! pairing, and the `ecAdd`, `ecMul` and `ecPairing` precompiles of the EVM as [ec_add],
+//! [ec_mul] and [ec_pairing].
+
+mod evm;
+mod field;
+mod pairing;
+
+pub use evm::{ec_add, ec_mul, ec_pairing, Bn254Error};
+pub use field::{Fp, Fp12, Fp2, Fp6};
+pub use pairing::{final_exponentiation, multi_miller_loop, pairing_check, G1Affine, G2Affine};
+
 use crate::{syscall_bn254_add, syscall_bn254_double, utils::AffinePoint};
 
 /// The number of limbs in [Bn254AffinePoint].
@@ -0,0 +1,263 @@
+//! The optimal ate pairing on BN254, and the groups `G1` and `G2` it is defined on.
+//!
+//! `G2` is represented on the sextic twist `y^2 = x^3 + 3 / (9 + u)` over `Fp2`. The Miller loop
+//! works in affine coordinates, since inverses are cheap compared to the extra multiplications of
+//! projective formulas when every multiplication is a precompile call.
+
+use alloc::vec::Vec;
+
+use super::field::{words_lt, Fp, Fp12, Fp2, Fp6, NUM_WORDS};
+
+/// The order `r` of `G1` and `G2`, as little endian words.
+pub const ORDER: [u32; NUM_WORDS] = [
+    0xf0000001, 0x43e1f593, 0x79b97091, 0x2833e848, 0x8181585d, 0xb85045b6, 0xe131a029, 0x30644e72,
+];
+
+/// The parameter `6x + 2` of the Miller loop, where `x` is the BN parameter of the curve.
+const ATE_LOOP_COUNT: u128 = 0x19d797039be763ba8;
+
+/// The exponent `(p^4 - p^2 + 1) / r` of the hard part of the final exponentiation.
+const HARD_EXPONENT: [u32; 24] = [
+    0xccdf42b1, 0xe81bb482, 0xf49c36d4, 0x5abf5cc4, 0x1da014fd, 0xf1154e7e, 0x87cdbacf, 0xdcc7b44c,
+    0x954bcf8a, 0xaaa441e3, 0xd5095f23, 0x6b887d56, 0xf3fd90c6, 0x79581e16, 0xd189227d, 0x3b1b1355,
+    0x61876f6b, 0x4e529a58, 0xd5b12278, 0x6c0eb522, 0x83177faf, 0x331ec151, 0x0b0759ad, 0x01baaa71,
+];
+
+/// The coefficient `3 / (9 + u)` of the twist.
+const TWIST_B: Fp2 = Fp2::new(
+    Fp([
+        0x24a138e5, 0x3267e6dc, 0x59dbefa3, 0xb5b4c5e5, 0x1be06ac3, 0x81be1899, 0xceb8aaae,
+        0x2b149d40,
+    ]),
+    Fp([
+        0x85c315d2, 0xe4a2bd06, 0xe52d1852, 0xa74fa084, 0xeed8fdf4, 0xcd2cafad, 0x3af0fed4,
+        0x009713b0,
+    ]),
+);
+
+/// The coefficients `(9 + u)^((p - 1) / 3)` and `(9 + u)^((p - 1) / 2)` of the Frobenius map on
+/// the twist.
+const FROBENIUS_X: Fp2 = Fp2::new(
+    Fp([
+        0x176f553d, 0x99e39557, 0xc2c3330c, 0xb78cc310, 0xf559b143, 0x4c0bec3c, 0x4f7911f7,
+        0x2fb34798,
+    ]),
+    Fp([
+        0x640fcba2, 0x1665d51c, 0x0b7c9dce, 0x32ae2a1d, 0xd75a0794, 0x4ba4cc8b, 0x61ebae20,
+        0x16c9e550,
+    ]),
+);
+const FROBENIUS_Y: Fp2 = Fp2::new(
+    Fp([
+        0x71a0135a, 0xdc540146, 0xa9c95998, 0xdbaae0ed, 0xb6e2f9b9, 0xdc5ec698, 0x489af5dc,
+        0x063cf305,
+    ]),
+    Fp([
+        0x2623b0e3, 0x82d37f63, 0x8fa25bd2, 0x21807dc9, 0xec796f2b, 0x0704b5a7, 0xac41049a,
+        0x07c03cbc,
+    ]),
+);
+
+/// The coefficient `(9 + u)^((p^2 - 1) / 3)` of the squared Frobenius map on the twist. The
+/// coefficient of `y` is `(9 + u)^((p^2 - 1) / 2) = -1`.
+const FROBENIUS_SQUARED_X: Fp = Fp([
+    0x607cfd48, 0xe4bd44e5, 0xbb966e3d, 0xc28f069f, 0xe0acccb0, 0x5e6dd9e7, 0xe131a029, 0x30644e72,
+]);
+
+/// A point of `G1` in affine coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct G1Affine {
+    pub x: Fp,
+    pub y: Fp,
+}
+
+/// A point of the twist in affine coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct G2Affine {
+    pub x: Fp2,
+    pub y: Fp2,
+}
+
+/// A point of the twist in Jacobian coordinates, which is the point at infinity when `z` is zero.
+#[derive(Clone, Copy, Debug)]
+struct G2Jacobian {
+    x: Fp2,
+    y: Fp2,
+    z: Fp2,
+}
+
+impl G1Affine {
+    /// Whether the point is on the curve `y^2 = x^3 + 3`. Since `G1` has cofactor one, this also
+    /// means that it is in `G1`.
+    pub fn is_on_curve(&self) -> bool {
+        self.y.square() == self.x.square() * self.x + Fp::from_u32(3)
+    }
+}
+
+impl G2Affine {
+    /// Whether the point is on the twist.
+    pub fn is_on_curve(&self) -> bool {
+        self.y.square() == self.x.square() * self.x + TWIST_B
+    }
+
+    /// Whether the point, assumed to be on the twist, is in `G2`, i.e. whether `r * self` is the
+    /// point at infinity.
+    pub fn is_in_subgroup(&self) -> bool {
+        let mut acc: Option<G2Jacobian> = None;
+        for word in ORDER.iter().rev() {
+            for i in (0..32).rev() {
+                acc = acc.and_then(|acc| acc.double());
+                if (word >> i) & 1 == 1 {
+                    acc = match acc {
+                        Some(acc) => acc.add_affine(self),
+                        None => Some(G2Jacobian { x: self.x, y: self.y, z: Fp2::ONE }),
+                    };
+                }
+            }
+        }
+        acc.is_none()
+    }
+
+    fn neg(&self) -> Self {
+        Self { x: self.x, y: -self.y }
+    }
+
+    /// The image of the point by the endomorphism induced by the `p`-power Frobenius map.
+    fn frobenius(&self) -> Self {
+        Self { x: self.x.conjugate() * FROBENIUS_X, y: self.y.conjugate() * FROBENIUS_Y }
+    }
+
+    /// The image of the point by the endomorphism induced by the `p^2`-power Frobenius map.
+    fn frobenius_squared(&self) -> Self {
+        Self { x: self.x.scale(FROBENIUS_SQUARED_X), y: -self.y }
+    }
+}
+
+impl G2Jacobian {
+    /// Doubles the point, returning `None` for the point at infinity.
+    fn double(&self) -> Option<Self> {
+        if self.y.is_zero() {
+            return None;
+        }
+        let a = self.x.square();
+        let b = self.y.square();
+        let c = b.square();
+        let d = (self.x + b).square() - a - c;
+        let d = d + d;
+        let e = a + a + a;
+        let x = e.square() - d - d;
+        let c8 = c + c;
+        let c8 = c8 + c8;
+        let y = e * (d - x) - (c8 + c8);
+        let z = self.y * self.z;
+        Some(Self { x, y, z: z + z })
+    }
+
+    /// Adds an affine point to the point, returning `None` for the point at infinity.
+    fn add_affine(&self, other: &G2Affine) -> Option<Self> {
+        let z1z1 = self.z.square();
+        let u2 = other.x * z1z1;
+        let s2 = other.y * self.z * z1z1;
+        let h = u2 - self.x;
+        let r = s2 - self.y;
+        if h.is_zero() {
+            return if r.is_zero() { self.double() } else { None };
+        }
+        let hh = h.square();
+        let i = hh + hh;
+        let i = i + i;
+        let j = h * i;
+        let r = r + r;
+        let v = self.x * i;
+        let x = r.square() - j - v - v;
+        let y1j = self.y * j;
+        let y = r * (v - x) - y1j - y1j;
+        let z = (self.z + h).square() - z1z1 - hh;
+        Some(Self { x, y, z })
+    }
+}
+
+/// The line through `t` with slope `lambda`, evaluated at `p`.
+///
+/// Mapping the twist to the curve sends `(x, y)` to `(x * w^2, y * w^3)`, so the line is
+/// `y_p - lambda * x_p * w + (lambda * x_t - y_t) * w^3`, up to a factor in `Fp2` which is killed
+/// by the final exponentiation.
+fn line(lambda: Fp2, t: &G2Affine, p: &G1Affine) -> Fp12 {
+    Fp12::new(
+        Fp6::new(p.y.into(), Fp2::ZERO, Fp2::ZERO),
+        Fp6::new(-lambda.scale(p.x), lambda * t.x - t.y, Fp2::ZERO),
+    )
+}
+
+/// Doubles `t`, returning the tangent line at `t` evaluated at `p`.
+fn double_step(t: &mut G2Affine, p: &G1Affine) -> Fp12 {
+    let x2 = t.x.square();
+    let lambda = (x2 + x2 + x2) * (t.y + t.y).inverse().expect("point of order two in G2");
+    let line = line(lambda, t, p);
+    let x = lambda.square() - t.x - t.x;
+    t.y = lambda * (t.x - x) - t.y;
+    t.x = x;
+    line
+}
+
+/// Adds `q` to `t`, returning the line through `t` and `q` evaluated at `p`.
+fn add_step(t: &mut G2Affine, q: &G2Affine, p: &G1Affine) -> Fp12 {
+    let lambda = (q.y - t.y) * (q.x - t.x).inverse().expect("unexpected equal points in G2");
+    let line = line(lambda, t, p);
+    let x = lambda.square() - t.x - q.x;
+    t.y = lambda * (t.x - x) - t.y;
+    t.x = x;
+    line
+}
+
+/// Computes the product of the Miller loops of the optimal ate pairing of each pair.
+///
+/// The points must be in `G1` and `G2`, and not at infinity.
+pub fn multi_miller_loop(pairs: &[(G1Affine, G2Affine)]) -> Fp12 {
+    let mut f = Fp12::ONE;
+    let mut ts = pairs.iter().map(|(_, q)| *q).collect::<Vec<_>>();
+    for i in (0..ATE_LOOP_COUNT.ilog2()).rev() {
+        f = f.square();
+        for ((p, q), t) in pairs.iter().zip(ts.iter_mut()) {
+            f = f * double_step(t, p);
+            if (ATE_LOOP_COUNT >> i) & 1 == 1 {
+                f = f * add_step(t, q, p);
+            }
+        }
+    }
+    for ((p, q), t) in pairs.iter().zip(ts.iter_mut()) {
+        f = f * add_step(t, &q.frobenius(), p);
+        f = f * add_step(t, &q.frobenius_squared().neg(), p);
+    }
+    f
+}
+
+/// Raises the output of a Miller loop to the power `(p^12 - 1) / r`.
+pub fn final_exponentiation(f: &Fp12) -> Fp12 {
+    // The easy part: f^((p^6 - 1) * (p^2 + 1)).
+    let f = f.conjugate() * f.inverse().expect("the Miller loop output is not zero");
+    let f = f.frobenius_squared() * f;
+    // The hard part.
+    f.pow(&HARD_EXPONENT)
+}
+
+/// Checks that the product of the pairings of the given pairs is one.
+///
+/// The points must be in `G1` and `G2`, and pairs with a point at infinity must be left out.
+pub fn pairing_check(pairs: &[(G1Affine, G2Affine)]) -> bool {
+    pairs.is_empty() || final_exponentiation(&multi_miller_loop(pairs)) == Fp12::ONE
+}
+
+/// Reduces a scalar modulo the order `r` of `G1` and `G2`.
+pub(crate) fn reduce_scalar(mut scalar: [u32; NUM_WORDS]) -> [u32; NUM_WORDS] {
+    while !words_lt(&scalar, &ORDER) {
+        let mut borrow = false;
+        for (s, r) in scalar.iter_mut().zip(ORDER.iter()) {
+            let (diff, b1) = s.overflowing_sub(*r);
+            let (diff, b2) = diff.overflowing_sub(borrow as u32);
+            *s = diff;
+            borrow = b1 || b2;
+        }
+    }
+    scalar
+}
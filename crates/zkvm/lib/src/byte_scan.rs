@@ -0,0 +1,135 @@
+//! Byte processing for parsers, accelerated by the byte scan precompiles.
+//!
+//! Decoders for formats like JSON and RLP spend most of their cycles looking for delimiters,
+//! copying ranges of the input and decoding hex strings, one byte per instruction. These helpers
+//! process up to 32 words per precompile call instead. The precompiles only handle whole words at
+//! aligned addresses, so the unaligned parts of a buffer are processed in software.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{syscall_byte_scan_copy, syscall_byte_scan_find, syscall_byte_scan_hex_decode};
+
+/// The number of bytes in a word.
+const WORD_SIZE: usize = 4;
+
+/// The maximum number of words processed by one precompile call.
+const MAX_WORDS: usize = 32;
+
+/// Returns the number of bytes at the start of `bytes` before the first word boundary.
+fn unaligned_prefix(bytes: &[u8]) -> usize {
+    bytes.as_ptr().align_offset(WORD_SIZE).min(bytes.len())
+}
+
+/// Returns the index of the first occurrence of `delimiter` in `haystack`.
+pub fn find(haystack: &[u8], delimiter: u8) -> Option<usize> {
+    let prefix = unaligned_prefix(haystack);
+    if let Some(i) = haystack[..prefix].iter().position(|&b| b == delimiter) {
+        return Some(i);
+    }
+
+    let mut offset = prefix;
+    let mut num_words = (haystack.len() - prefix) / WORD_SIZE;
+    while num_words > 0 {
+        let n = num_words.min(MAX_WORDS);
+        let header = [n as u32, haystack[offset..].as_ptr() as u32, delimiter as u32];
+        let mut count = 0u32;
+        unsafe {
+            syscall_byte_scan_find(&mut count, header.as_ptr());
+        }
+        if (count as usize) < n * WORD_SIZE {
+            return Some(offset + count as usize);
+        }
+        offset += n * WORD_SIZE;
+        num_words -= n;
+    }
+
+    haystack[offset..].iter().position(|&b| b == delimiter).map(|i| offset + i)
+}
+
+/// Copies `src` to `dst`.
+///
+/// The precompile is used when `src` and `dst` are equally misaligned, which is always the case
+/// for ranges that start at the same offset of two aligned buffers.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+pub fn copy(dst: &mut [u8], src: &[u8]) {
+    assert_eq!(dst.len(), src.len(), "source and destination lengths differ");
+    let prefix = unaligned_prefix(src);
+    if unaligned_prefix(dst) != prefix {
+        dst.copy_from_slice(src);
+        return;
+    }
+    dst[..prefix].copy_from_slice(&src[..prefix]);
+
+    let mut offset = prefix;
+    let mut num_words = (src.len() - prefix) / WORD_SIZE;
+    while num_words > 0 {
+        let n = num_words.min(MAX_WORDS);
+        let header = [n as u32, src[offset..].as_ptr() as u32, 0];
+        unsafe {
+            syscall_byte_scan_copy(dst[offset..].as_mut_ptr() as *mut u32, header.as_ptr());
+        }
+        offset += n * WORD_SIZE;
+        num_words -= n;
+    }
+
+    dst[offset..].copy_from_slice(&src[offset..]);
+}
+
+/// Decodes an ASCII hex digit.
+fn hex_digit(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        b'A'..=b'F' => c - b'A' + 10,
+        _ => panic!("invalid hex digit: {c:#04x}"),
+    }
+}
+
+/// Decodes the hex string `hex` to `out`, with the high nibble of each byte first.
+///
+/// The precompile is used when `hex` and `out` are aligned.
+///
+/// # Panics
+///
+/// Panics if `hex` is not twice as long as `out`, or if it contains a character that is not a hex
+/// digit, in lower or upper case. The precompile halts the execution on an invalid digit.
+pub fn hex_decode_into(hex: &[u8], out: &mut [u8]) {
+    assert_eq!(hex.len(), 2 * out.len(), "hex string length must be twice the output length");
+
+    let mut offset = 0;
+    if unaligned_prefix(hex) == 0 && unaligned_prefix(out) == 0 {
+        let mut num_words = out.len() / WORD_SIZE;
+        while num_words > 0 {
+            let n = num_words.min(MAX_WORDS);
+            let header = [n as u32, hex[2 * offset..].as_ptr() as u32, 0];
+            unsafe {
+                syscall_byte_scan_hex_decode(
+                    out[offset..].as_mut_ptr() as *mut u32,
+                    header.as_ptr(),
+                );
+            }
+            offset += n * WORD_SIZE;
+            num_words -= n;
+        }
+    }
+
+    for (byte, pair) in out[offset..].iter_mut().zip(hex[2 * offset..].chunks_exact(2)) {
+        *byte = hex_digit(pair[0]) << 4 | hex_digit(pair[1]);
+    }
+}
+
+/// Decodes the hex string `hex`, with the high nibble of each byte first.
+///
+/// # Panics
+///
+/// Panics if `hex` has an odd length, or if it contains a character that is not a hex digit, in
+/// lower or upper case.
+pub fn hex_decode(hex: &[u8]) -> Vec<u8> {
+    assert!(hex.len() % 2 == 0, "hex string has an odd length");
+    let mut out = vec![0u8; hex.len() / 2];
+    hex_decode_into(hex, &mut out);
+    out
+}
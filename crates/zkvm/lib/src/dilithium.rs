@@ -0,0 +1,158 @@
+//! Arithmetic on Dilithium (ML-DSA) polynomials, accelerated by the Dilithium precompiles.
+//!
+//! Polynomials are elements of `Z_q[X] / (X^256 + 1)`, represented by their [`N`] coefficients,
+//! which must be reduced modulo [`Q`]. The number-theoretic transform follows the reference
+//! implementation of Dilithium without the Montgomery representation, so that
+//! `inv_ntt(ntt(a) * ntt(b))` with a pointwise product is the product of `a` and `b`.
+//!
+//! These are the polynomial operations that dominate the cost of verifying a Dilithium signature.
+//! The hashing (SHAKE-128 and SHAKE-256) can be accelerated with the Keccak precompile, through the
+//! patched `sha3` crate.
+
+use crate::{
+    syscall_dilithium_add, syscall_dilithium_butterfly, syscall_dilithium_mul,
+    syscall_dilithium_sub,
+};
+
+/// The number of coefficients of a polynomial.
+pub const N: usize = 256;
+
+/// The modulus of the coefficients, `q = 2^23 - 2^13 + 1`.
+pub const Q: u32 = 8_380_417;
+
+/// The number of coefficients processed by a single precompile call.
+const BLOCK: usize = 8;
+
+/// A primitive 512-th root of unity modulo [`Q`].
+const ROOT_OF_UNITY: u32 = 1753;
+
+/// The inverse of [`N`] modulo [`Q`], which scales the result of the inverse transform.
+const N_INV: u32 = 8_347_681;
+
+/// The powers of [`ROOT_OF_UNITY`] used by the transform, in bit-reversed order.
+const ZETAS: [u32; N] = zetas();
+
+/// Computes `base^exp mod Q`.
+const fn pow_mod(base: u32, mut exp: u32) -> u32 {
+    let mut result = 1u64;
+    let mut base = base as u64;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % Q as u64;
+        }
+        base = base * base % Q as u64;
+        exp >>= 1;
+    }
+    result as u32
+}
+
+const fn zetas() -> [u32; N] {
+    let mut zetas = [0u32; N];
+    let mut i = 0;
+    while i < N {
+        zetas[i] = pow_mod(ROOT_OF_UNITY, (i as u8).reverse_bits() as u32);
+        i += 1;
+    }
+    zetas
+}
+
+/// Applies a lane-wise precompile to every block of `a` and `b`.
+fn blockwise(
+    a: &mut [u32; N],
+    b: &[u32; N],
+    op: unsafe extern "C" fn(*mut [u32; 8], *const [u32; 8]),
+) {
+    for (a, b) in a.chunks_exact_mut(BLOCK).zip(b.chunks_exact(BLOCK)) {
+        let a: &mut [u32; BLOCK] = a.try_into().unwrap();
+        let b: &[u32; BLOCK] = b.try_into().unwrap();
+        unsafe {
+            op(a, b);
+        }
+    }
+}
+
+/// Computes `a = a + b`.
+pub fn add_assign(a: &mut [u32; N], b: &[u32; N]) {
+    blockwise(a, b, syscall_dilithium_add);
+}
+
+/// Computes `a = a - b`.
+pub fn sub_assign(a: &mut [u32; N], b: &[u32; N]) {
+    blockwise(a, b, syscall_dilithium_sub);
+}
+
+/// Computes the pointwise product `a = a * b` of two polynomials in the NTT domain.
+pub fn pointwise_mul_assign(a: &mut [u32; N], b: &[u32; N]) {
+    blockwise(a, b, syscall_dilithium_mul);
+}
+
+/// Applies one layer of butterflies between the coefficients at distance `len`.
+///
+/// The `N / 2` butterflies of the layer are gathered into blocks of eight, so that the layers with
+/// `len < 8` also use the precompiles. The `g`-th group of `2 * len` coefficients uses the twiddle
+/// factor `twiddle(g)`. The forward transform multiplies before the butterfly, and the inverse
+/// transform after it.
+fn ntt_layer(a: &mut [u32; N], len: usize, inverse: bool, twiddle: impl Fn(usize) -> u32) {
+    for batch in (0..N / 2).step_by(BLOCK) {
+        let mut lo = [0u32; BLOCK];
+        let mut hi = [0u32; BLOCK];
+        let mut zetas = [0u32; BLOCK];
+        for lane in 0..BLOCK {
+            let i = batch + lane;
+            let j = 2 * len * (i / len) + i % len;
+            lo[lane] = a[j];
+            hi[lane] = a[j + len];
+            zetas[lane] = twiddle(i / len);
+        }
+        unsafe {
+            if inverse {
+                syscall_dilithium_butterfly(&mut lo, &mut hi);
+                syscall_dilithium_mul(&mut hi, &zetas);
+            } else {
+                syscall_dilithium_mul(&mut hi, &zetas);
+                syscall_dilithium_butterfly(&mut lo, &mut hi);
+            }
+        }
+        for lane in 0..BLOCK {
+            let i = batch + lane;
+            let j = 2 * len * (i / len) + i % len;
+            a[j] = lo[lane];
+            a[j + len] = hi[lane];
+        }
+    }
+}
+
+/// Computes the forward number-theoretic transform of `a` in place.
+///
+/// The output is in bit-reversed order, as in the reference implementation.
+pub fn ntt(a: &mut [u32; N]) {
+    let mut k = 1;
+    let mut len = N / 2;
+    while len > 0 {
+        ntt_layer(a, len, false, |g| ZETAS[k + g]);
+        k += N / (2 * len);
+        len >>= 1;
+    }
+}
+
+/// Computes the inverse number-theoretic transform of `a` in place.
+pub fn inv_ntt(a: &mut [u32; N]) {
+    let mut len = 1;
+    while len < N {
+        let groups = N / (2 * len);
+        ntt_layer(a, len, true, |g| Q - ZETAS[2 * groups - 1 - g]);
+        len <<= 1;
+    }
+    blockwise(a, &[N_INV; N], syscall_dilithium_mul);
+}
+
+/// Computes the product of `a` and `b` in `Z_q[X] / (X^256 + 1)`.
+pub fn mul(a: &[u32; N], b: &[u32; N]) -> [u32; N] {
+    let mut a_hat = *a;
+    let mut b_hat = *b;
+    ntt(&mut a_hat);
+    ntt(&mut b_hat);
+    pointwise_mul_assign(&mut a_hat, &b_hat);
+    inv_ntt(&mut a_hat);
+    a_hat
+}
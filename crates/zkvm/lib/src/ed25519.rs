@@ -1,4 +1,7 @@
-use crate::{syscall_ed_add, utils::AffinePoint};
+use crate::{
+    syscall_ed_add, syscall_ed_scalar_mul,
+    utils::{AffinePoint, MulAssignError},
+};
 
 /// The number of limbs in [Ed25519AffinePoint].
 pub const N: usize = 16;
@@ -43,6 +46,25 @@ impl AffinePoint<N> for Ed25519AffinePoint {
             syscall_ed_add(a, a);
         }
     }
+
+    /// Multiplies `self` by the given scalar using the `ED_SCALAR_MUL` precompile.
+    ///
+    /// Ristretto255 elements are represented by Edwards points, so this can be used directly on
+    /// the internal representative of a Ristretto point.
+    fn mul_assign(&mut self, scalar: &[u32]) -> Result<(), MulAssignError> {
+        debug_assert!(scalar.len() == N / 2);
+
+        if scalar.iter().all(|&words| words == 0) {
+            return Err(MulAssignError::ScalarIsZero);
+        }
+
+        let scalar: &[u32; N / 2] = scalar.try_into().unwrap();
+        let a = self.limbs_mut();
+        unsafe {
+            syscall_ed_scalar_mul(a, scalar);
+        }
+        Ok(())
+    }
 }
 
 impl Ed25519AffinePoint {
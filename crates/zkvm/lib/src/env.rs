@@ -0,0 +1,44 @@
+//! Environment variables and arguments provided by the host.
+//!
+//! They are set on the host with `SP1Stdin::env(key, value)` and `SP1Stdin::args(args)`, so that
+//! programs ported from command-line tools can keep reading their configuration with accessors
+//! shaped like those of [`std::env`].
+//!
+//! Like files, environment variables and arguments are provided as hints: they are not constrained
+//! by the zkVM, so a program that depends on them must commit to them.
+
+use std::env::VarError;
+
+use crate::io::{read_vec, write, FD_ARGS, FD_ENV_VAR};
+
+/// Fetches the environment variable `key` set by the host.
+///
+/// Returns [`VarError::NotPresent`] if the host did not set the variable.
+///
+/// ### Examples
+/// ```ignore
+/// let level = sp1_zkvm::env::var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string());
+/// ```
+pub fn var<K: AsRef<str>>(key: K) -> Result<String, VarError> {
+    write(FD_ENV_VAR, key.as_ref().as_bytes());
+    match read_vec().as_slice() {
+        [1] => Ok(String::from_utf8(read_vec()).expect("variable is not valid UTF-8")),
+        _ => Err(VarError::NotPresent),
+    }
+}
+
+/// Returns the arguments set by the host. Unlike [`std::env::args`], the first argument is not the
+/// path of the program, unless the host passed one.
+///
+/// ### Examples
+/// ```ignore
+/// let args = sp1_zkvm::env::args();
+/// let verbose = args.iter().any(|arg| arg == "--verbose");
+/// ```
+pub fn args() -> Vec<String> {
+    write(FD_ARGS, &[]);
+    let count = u32::from_le_bytes(read_vec().try_into().expect("invalid argument count"));
+    (0..count)
+        .map(|_| String::from_utf8(read_vec()).expect("argument is not valid UTF-8"))
+        .collect()
+}
@@ -0,0 +1,47 @@
+//! Read-only access to files provided by the host.
+//!
+//! Files are registered on the host with `SP1Stdin::write_file(path, bytes)` and looked up by
+//! their path, so programs ported from `std` can keep reading their inputs as files instead of
+//! reading them from the input stream in a fixed order.
+//!
+//! The contents of a file are provided as a hint: they are not constrained by the zkVM, so a
+//! program that depends on them must commit to them or check them against a committed digest.
+
+use std::{
+    io::{Error, ErrorKind, Result},
+    path::Path,
+};
+
+use crate::io::{read_vec, write, FD_FS_READ};
+
+/// Read the entire contents of a file into a bytes vector.
+///
+/// Returns an error of kind [`ErrorKind::NotFound`] if the host did not register a file at `path`.
+///
+/// ### Examples
+/// ```ignore
+/// let bytes = sp1_zkvm::fs::read("data/input.bin").unwrap();
+/// ```
+pub fn read<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+    let path = path.as_ref();
+    let name = path
+        .to_str()
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "path is not valid UTF-8"))?;
+    write(FD_FS_READ, name.as_bytes());
+    match read_vec().as_slice() {
+        [1] => Ok(read_vec()),
+        _ => Err(Error::new(ErrorKind::NotFound, format!("no file at {}", path.display()))),
+    }
+}
+
+/// Read the entire contents of a file into a string.
+///
+/// Returns an error of kind [`ErrorKind::InvalidData`] if the file is not valid UTF-8.
+///
+/// ### Examples
+/// ```ignore
+/// let config = sp1_zkvm::fs::read_to_string("config.toml").unwrap();
+/// ```
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> Result<String> {
+    String::from_utf8(read(path)?).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+}
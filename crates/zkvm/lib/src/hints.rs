@@ -0,0 +1,100 @@
+//! Checks of common nondeterministic hints.
+//!
+//! Work that is expensive to do in the program but cheap to check, such as sorting a list or
+//! inverting a field element, can be done outside of the VM in an [`unconstrained!`] block, and its
+//! result passed back to the program with [`io::hint`](crate::io::hint). The hint is not
+//! constrained by the proof, so the program must check it before using it. Each helper of this
+//! module pairs a kind of hint with its check, and panics if the check fails, so that an invalid
+//! hint cannot reach the public values.
+//!
+//! ### Examples
+//! ```ignore
+//! use sp1_zkvm::{hints, io};
+//!
+//! let items: Vec<u32> = io::read();
+//! sp1_zkvm::lib::unconstrained! {
+//!     io::hint(&hints::sort_hint(&items));
+//! }
+//! let sorted = hints::read_sorted(&items);
+//! ```
+//!
+//! [`unconstrained!`]: crate::unconstrained
+
+use alloc::{vec, vec::Vec};
+use core::ops::Mul;
+
+/// Checks that `items` are sorted in non-decreasing order.
+///
+/// # Panics
+///
+/// Panics if two consecutive items are out of order.
+pub fn verify_sorted<T: Ord>(items: &[T]) {
+    if let Some(i) = items.windows(2).position(|pair| pair[0] > pair[1]) {
+        panic!("hint is not sorted: items {i} and {} are out of order", i + 1);
+    }
+}
+
+/// Checks that `items` is a permutation of `original`, where `items[i]` is
+/// `original[permutation[i]]`.
+///
+/// The check is linear in the number of items, whereas finding the permutation is not: the
+/// permutation is a hint, like the items themselves.
+///
+/// # Panics
+///
+/// Panics if `permutation` is not a permutation of the indices of `original`, or if an item
+/// differs from the item of `original` it is mapped to.
+pub fn verify_permutation_of<T: PartialEq>(items: &[T], original: &[T], permutation: &[u32]) {
+    assert_eq!(items.len(), original.len(), "hint has a different number of items");
+    assert_eq!(permutation.len(), original.len(), "permutation has a different number of indices");
+    let mut seen = vec![false; original.len()];
+    for (i, (item, &index)) in items.iter().zip(permutation).enumerate() {
+        let index = index as usize;
+        assert!(index < original.len(), "permutation index {index} is out of bounds");
+        assert!(!seen[index], "permutation index {index} is repeated");
+        seen[index] = true;
+        assert!(item == &original[index], "hint item {i} differs from original item {index}");
+    }
+}
+
+/// Checks that `inverse` is the multiplicative inverse of `value`, that is, that their product is
+/// `one`. Returns the inverse.
+///
+/// This applies to any type with a multiplication, such as the field elements of a curve or the
+/// integers modulo a prime.
+///
+/// # Panics
+///
+/// Panics if the product of `value` and `inverse` is not `one`.
+pub fn verify_inverse<T: Copy + Mul<Output = T> + PartialEq>(value: T, inverse: T, one: T) -> T {
+    assert!(value * inverse == one, "hint is not the inverse of the value");
+    inverse
+}
+
+/// Sorts `items`, returning the sorted items and the permutation mapping them to `items`, as
+/// expected by [`verify_permutation_of`].
+///
+/// This is the computation to run in an unconstrained block, whose result is checked by
+/// [`read_sorted`].
+pub fn sort_hint<T: Ord + Clone>(items: &[T]) -> (Vec<T>, Vec<u32>) {
+    let mut permutation = (0..items.len() as u32).collect::<Vec<_>>();
+    permutation.sort_by(|&a, &b| items[a as usize].cmp(&items[b as usize]));
+    let sorted = permutation.iter().map(|&i| items[i as usize].clone()).collect();
+    (sorted, permutation)
+}
+
+/// Reads the hint of [`sort_hint`] for `original` from the input stream, and checks that it is
+/// `original` sorted. Returns the sorted items.
+///
+/// Requires the `std` feature, since the hint is deserialized with `bincode`.
+///
+/// # Panics
+///
+/// Panics if the hint is not a sorted permutation of `original`.
+#[cfg(feature = "std")]
+pub fn read_sorted<T: Ord + serde::de::DeserializeOwned>(original: &[T]) -> Vec<T> {
+    let (sorted, permutation): (Vec<T>, Vec<u32>) = crate::io::read();
+    verify_sorted(&sorted);
+    verify_permutation_of(&sorted, original, &permutation);
+    sorted
+}
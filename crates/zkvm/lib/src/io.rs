@@ -1,10 +1,18 @@
 #![allow(unused_unsafe)]
-use crate::{syscall_hint_len, syscall_hint_read, syscall_write};
-use serde::{de::DeserializeOwned, Serialize};
-use std::{
-    alloc::Layout,
-    io::{Result, Write},
+use crate::{
+    syscall_hint_len, syscall_hint_read, syscall_hint_read_words, syscall_paged_input_len,
+    syscall_paged_input_map, syscall_rom_map, syscall_write,
+};
+use alloc::{
+    alloc::{alloc, Layout},
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
 };
+#[cfg(feature = "std")]
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+#[cfg(feature = "std")]
+use std::io::{Result, Write};
 
 /// The file descriptor for public values.
 pub const FD_PUBLIC_VALUES: u32 = 3;
@@ -15,19 +23,59 @@ pub const FD_HINT: u32 = 4;
 /// The file descriptor for the `ecreover` hook.
 pub const FD_ECRECOVER_HOOK: u32 = 5;
 
+/// The file descriptor through which panics are reported to the host.
+pub const FD_PANIC: u32 = 6;
+
+/// The file descriptor for the hook that looks up files registered with `SP1Stdin::write_file`.
+pub const FD_FS_READ: u32 = 7;
+
+/// The file descriptor for the hook that returns the domain separator and nonce of the public
+/// values, set with `SP1Stdin::write_public_values_domain`.
+pub const FD_PUBLIC_VALUES_DOMAIN: u32 = 8;
+
+/// The file descriptor for the hook that looks up environment variables set with `SP1Stdin::env`.
+pub const FD_ENV_VAR: u32 = 9;
+
+/// The file descriptor for the hook that returns the arguments set with `SP1Stdin::args`.
+pub const FD_ARGS: u32 = 10;
+
+/// The file descriptor for the hook that returns the number of cycles executed so far.
+pub const FD_CYCLE_COUNT: u32 = 11;
+
+/// The maximum number of words written by one `HINT_READ_WORDS` syscall, which must match
+/// `sp1_core_executor::events::HINT_WORDS_MAX_WORDS`.
+pub const HINT_WORDS_MAX_WORDS: usize = 32;
+
+/// The address at which the ROM header is mapped, which must match
+/// `sp1_core_executor::ROM_START`.
+pub const ROM_START: u32 = 0x7000_0000;
+
+/// The size of a page of the ROM, which must match `sp1_core_executor::ROM_PAGE_SIZE`.
+pub const ROM_PAGE_SIZE: usize = 4096;
+
+/// The address at which the pages of the ROM are mapped, which must match
+/// `sp1_core_executor::ROM_DATA_START`.
+const ROM_DATA_START: u32 = ROM_START + ROM_PAGE_SIZE as u32;
+
+/// The size of a page of a paged input, which must match
+/// `sp1_core_executor::syscalls::PAGED_INPUT_PAGE_SIZE`.
+pub const PAGED_INPUT_PAGE_SIZE: usize = 4096;
+
+/// The number of bits of the page index in the argument of the `PAGED_INPUT_MAP` syscall, which
+/// must match `sp1_core_executor::syscalls::PAGED_INPUT_PAGE_BITS`.
+const PAGED_INPUT_PAGE_BITS: u32 = 20;
+
 /// A writer that writes to a file descriptor inside the zkVM.
+#[cfg(feature = "std")]
 struct SyscallWriter {
     fd: u32,
 }
 
+#[cfg(feature = "std")]
 impl Write for SyscallWriter {
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
-        let nbytes = buf.len();
-        let write_buf = buf.as_ptr();
-        unsafe {
-            syscall_write(self.fd, write_buf, nbytes);
-        }
-        Ok(nbytes)
+        write(self.fd, buf);
+        Ok(buf.len())
     }
 
     fn flush(&mut self) -> Result<()> {
@@ -48,7 +96,7 @@ pub fn read_vec() -> Vec<u8> {
 
     // Allocate a buffer of the required length that is 4 byte aligned
     let layout = Layout::from_size_align(capacity, 4).expect("vec is too large");
-    let ptr = unsafe { std::alloc::alloc(layout) };
+    let ptr = unsafe { alloc(layout) };
 
     // SAFETY:
     // 1. `ptr` was allocated using alloc
@@ -67,8 +115,145 @@ pub fn read_vec() -> Vec<u8> {
     vec
 }
 
+/// Read words written with `SP1Stdin::write_words` from the input stream into `buf`, which may
+/// already be initialized, so that a buffer can be refilled without allocating.
+///
+/// # Panics
+///
+/// Panics if the number of words written does not match the length of `buf`.
+///
+/// ### Examples
+/// ```ignore
+/// let mut buf = [0u32; 64];
+/// sp1_zkvm::io::read_words_into(&mut buf);
+/// ```
+pub fn read_words_into(buf: &mut [u32]) {
+    let len = read_words_len();
+    assert_eq!(len, buf.len(), "read_words_into: length mismatch");
+    for chunk in buf.chunks_mut(HINT_WORDS_MAX_WORDS) {
+        unsafe { syscall_hint_read_words(chunk.as_mut_ptr(), chunk.len()) };
+    }
+}
+
+/// Read words written with `SP1Stdin::write_words` from the input stream.
+///
+/// ### Examples
+/// ```ignore
+/// let words: Vec<u32> = sp1_zkvm::io::read_words();
+/// ```
+pub fn read_words() -> Vec<u32> {
+    let len = read_words_len();
+    let mut vec = alloc::vec![0; len];
+    for chunk in vec.chunks_mut(HINT_WORDS_MAX_WORDS) {
+        unsafe { syscall_hint_read_words(chunk.as_mut_ptr(), chunk.len()) };
+    }
+    vec
+}
+
+/// Read the number of words written with `SP1Stdin::write_words`, which precedes them.
+fn read_words_len() -> usize {
+    let mut len = 0u32;
+    unsafe { syscall_hint_read_words(&mut len, 1) };
+    len as usize
+}
+
+/// Allocate a fresh, 4 byte aligned buffer of `len` bytes. Syscalls that write to uninitialized
+/// memory can write to it, since the VM global allocator does not dealloc.
+pub(crate) fn alloc_uninitialized(len: usize) -> *mut u8 {
+    let layout = Layout::from_size_align(len, 4).expect("buffer is too large");
+    unsafe { alloc(layout) }
+}
+
+/// A large input registered with `SP1Stdin::write_paged`, which is mapped into memory one page at
+/// a time as it is read.
+///
+/// Unlike [`read_vec`], which copies the whole input into memory, only the pages that are read are
+/// part of the proof, so the program can access inputs far larger than it could copy.
+///
+/// ### Examples
+/// ```ignore
+/// let index: u32 = sp1_zkvm::io::read();
+/// let mut input = sp1_zkvm::io::PagedInput::open(index);
+/// let mut header = [0u8; 32];
+/// input.read_at(0, &mut header);
+/// ```
+pub struct PagedInput {
+    index: u32,
+    len: u64,
+    pages: BTreeMap<u32, &'static [u8]>,
+}
+
+impl PagedInput {
+    /// Open the paged input at the given index.
+    pub fn open(index: u32) -> Self {
+        assert!(index < 1 << (32 - PAGED_INPUT_PAGE_BITS), "paged input index is too large");
+        let ptr = alloc_uninitialized(8) as *mut u32;
+        let len = unsafe {
+            syscall_paged_input_len(index, ptr);
+            u64::from(*ptr) | u64::from(*ptr.add(1)) << 32
+        };
+        Self { index, len, pages: BTreeMap::new() }
+    }
+
+    /// The length of the input, in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Whether the input is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of pages of the input.
+    pub fn num_pages(&self) -> u32 {
+        self.len.div_ceil(PAGED_INPUT_PAGE_SIZE as u64) as u32
+    }
+
+    /// The contents of the given page, mapping it on first access. Every page but the last one is
+    /// [`PAGED_INPUT_PAGE_SIZE`] bytes long.
+    pub fn page(&mut self, page: u32) -> &'static [u8] {
+        assert!(page < self.num_pages(), "page {page} is past the end of the paged input");
+        let (index, len) = (self.index, self.len);
+        self.pages.entry(page).or_insert_with(|| {
+            let ptr = alloc_uninitialized(PAGED_INPUT_PAGE_SIZE);
+            let start = page as u64 * PAGED_INPUT_PAGE_SIZE as u64;
+            let page_len = (len - start).min(PAGED_INPUT_PAGE_SIZE as u64) as usize;
+            unsafe {
+                syscall_paged_input_map(ptr, index << PAGED_INPUT_PAGE_BITS | page);
+                core::slice::from_raw_parts(ptr, page_len)
+            }
+        })
+    }
+
+    /// Read `buf.len()` bytes of the input starting at `offset` into `buf`, mapping the pages they
+    /// span.
+    pub fn read_at(&mut self, offset: u64, buf: &mut [u8]) {
+        let end = offset.checked_add(buf.len() as u64).filter(|&end| end <= self.len);
+        if end.is_none() {
+            panic!(
+                "paged input read out of bounds: offset={offset}, len={}, input_len={}",
+                buf.len(),
+                self.len
+            );
+        }
+        let mut written = 0;
+        while written < buf.len() {
+            let position = offset + written as u64;
+            let page = (position / PAGED_INPUT_PAGE_SIZE as u64) as u32;
+            let start = (position % PAGED_INPUT_PAGE_SIZE as u64) as usize;
+            let contents = &self.page(page)[start..];
+            let n = contents.len().min(buf.len() - written);
+            buf[written..written + n].copy_from_slice(&contents[..n]);
+            written += n;
+        }
+    }
+}
+
 /// Read a deserializable object from the input stream.
 ///
+/// Requires the `std` feature, since the object is deserialized with `bincode`.
+///
 /// ### Examples
 /// ```ignore
 /// use serde::{Deserialize, Serialize};
@@ -81,6 +266,7 @@ pub fn read_vec() -> Vec<u8> {
 ///
 /// let data: MyStruct = sp1_zkvm::io::read();
 /// ```
+#[cfg(feature = "std")]
 pub fn read<T: DeserializeOwned>() -> T {
     let vec = read_vec();
     bincode::deserialize(&vec).expect("deserialization failed")
@@ -88,6 +274,8 @@ pub fn read<T: DeserializeOwned>() -> T {
 
 /// Commit a serializable object to the public values stream.
 ///
+/// Requires the `std` feature, since the object is serialized with `bincode`.
+///
 /// ### Examples
 /// ```ignore
 /// use serde::{Deserialize, Serialize};
@@ -104,6 +292,7 @@ pub fn read<T: DeserializeOwned>() -> T {
 /// };
 /// sp1_zkvm::io::commit(&data);
 /// ```
+#[cfg(feature = "std")]
 pub fn commit<T: Serialize>(value: &T) {
     let writer = SyscallWriter { fd: FD_PUBLIC_VALUES };
     bincode::serialize_into(writer, value).expect("serialization failed");
@@ -117,12 +306,13 @@ pub fn commit<T: Serialize>(value: &T) {
 /// sp1_zkvm::io::commit_slice(&data);
 /// ```
 pub fn commit_slice(buf: &[u8]) {
-    let mut my_writer = SyscallWriter { fd: FD_PUBLIC_VALUES };
-    my_writer.write_all(buf).unwrap();
+    write(FD_PUBLIC_VALUES, buf);
 }
 
 /// Hint a serializable object to the hint stream.
 ///
+/// Requires the `std` feature, since the object is serialized with `bincode`.
+///
 /// ### Examples
 /// ```ignore
 /// use serde::{Deserialize, Serialize};
@@ -139,6 +329,7 @@ pub fn commit_slice(buf: &[u8]) {
 /// };
 /// sp1_zkvm::io::hint(&data);
 /// ```
+#[cfg(feature = "std")]
 pub fn hint<T: Serialize>(value: &T) {
     let writer = SyscallWriter { fd: FD_HINT };
     bincode::serialize_into(writer, value).expect("serialization failed");
@@ -152,8 +343,7 @@ pub fn hint<T: Serialize>(value: &T) {
 /// sp1_zkvm::io::hint_slice(&data);
 /// ```
 pub fn hint_slice(buf: &[u8]) {
-    let mut my_reader = SyscallWriter { fd: FD_HINT };
-    my_reader.write_all(buf).unwrap();
+    write(FD_HINT, buf);
 }
 
 /// Write the data `buf` to the file descriptor `fd`.
@@ -164,5 +354,101 @@ pub fn hint_slice(buf: &[u8]) {
 /// sp1_zkvm::io::write(3, &data);
 /// ```
 pub fn write(fd: u32, buf: &[u8]) {
-    SyscallWriter { fd }.write_all(buf).unwrap();
+    unsafe {
+        syscall_write(fd, buf.as_ptr(), buf.len());
+    }
+}
+
+/// The length of the read-only data segment (ROM) embedded in the program, in bytes, or zero if
+/// there is none.
+pub fn rom_len() -> usize {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        // The length of the ROM is the first word of its header, and reads as zero if the program
+        // has no ROM.
+        core::ptr::read_volatile(ROM_START as *const u32) as usize
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// The pages of the ROM mapped so far.
+static mut ROM_PAGES: BTreeSet<u32> = BTreeSet::new();
+
+/// Maps the given page of the ROM of `len` bytes at its address, if it is not mapped yet, and
+/// checks it against the root in the ROM header.
+///
+/// The page and its Merkle path are provided by the prover, but the root is part of the program,
+/// so a page that does not match it cannot be read.
+fn map_rom_page(page: u32, len: usize) {
+    if !unsafe { (*core::ptr::addr_of_mut!(ROM_PAGES)).insert(page) } {
+        return;
+    }
+    let depth = len.div_ceil(ROM_PAGE_SIZE).max(1).next_power_of_two().trailing_zeros() as usize;
+    let path = alloc_uninitialized((32 * depth).max(4));
+    let (contents, path, root) = unsafe {
+        syscall_rom_map(page, path);
+        let contents = (ROM_DATA_START as usize + page as usize * ROM_PAGE_SIZE) as *const u8;
+        (
+            core::slice::from_raw_parts(contents, ROM_PAGE_SIZE),
+            core::slice::from_raw_parts(path, 32 * depth),
+            core::slice::from_raw_parts((ROM_START + 4) as *const u8, 32),
+        )
+    };
+
+    let mut node = Sha256::digest(contents);
+    for (level, sibling) in path.chunks_exact(32).enumerate() {
+        let mut hasher = Sha256::new();
+        if (page >> level) & 1 == 0 {
+            hasher.update(node);
+            hasher.update(sibling);
+        } else {
+            hasher.update(sibling);
+            hasher.update(node);
+        }
+        node = hasher.finalize();
+    }
+    assert!(node.as_slice() == root, "page {page} of the rom does not match its root");
+}
+
+/// Read `len` bytes of the read-only data segment (ROM) embedded in the program, starting at
+/// `offset`.
+///
+/// Only the header of the ROM, its length and the root of the Merkle tree of its pages, is part of
+/// the program's initial memory image, and so of the verifying key. The pages that are read are
+/// mapped into memory on first access and checked against the root, so only the words that are
+/// read are part of the proof. Unlike [`read_vec`], the ROM does not need to be provided as input
+/// to every execution.
+///
+/// ### Examples
+/// ```ignore
+/// let header = sp1_zkvm::io::read_rom(0, 32);
+/// ```
+pub fn read_rom(offset: usize, len: usize) -> &'static [u8] {
+    let rom_len = rom_len();
+    let end = offset.checked_add(len).filter(|&end| end <= rom_len);
+    let Some(end) = end else {
+        panic!("rom read out of bounds: offset={offset}, len={len}, rom_len={rom_len}");
+    };
+    if len == 0 {
+        return &[];
+    }
+    for page in offset / ROM_PAGE_SIZE..=(end - 1) / ROM_PAGE_SIZE {
+        map_rom_page(page as u32, rom_len);
+    }
+    unsafe { core::slice::from_raw_parts((ROM_DATA_START as usize + offset) as *const u8, len) }
+}
+
+/// The read-only data segment (ROM) embedded in the program, or an empty slice if there is none.
+///
+/// Every page of the ROM is mapped and checked, so [`read_rom`] should be preferred to read parts
+/// of a large ROM. See [`read_rom`].
+///
+/// ### Examples
+/// ```ignore
+/// let database = sp1_zkvm::io::rom();
+/// ```
+pub fn rom() -> &'static [u8] {
+    read_rom(0, rom_len())
 }
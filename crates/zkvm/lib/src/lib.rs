@@ -2,21 +2,44 @@
 //!
 //! Documentation for these syscalls can be found in the zkVM entrypoint
 //! `sp1_zkvm::syscalls` module.
+//!
+//! Without the default `std` feature, the crate is `no_std` and only depends on `alloc`. The
+//! functions that serialize objects with `bincode`, and the [`env`] and [`fs`] modules, require
+//! `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 pub mod bls12381;
 pub mod bn254;
+pub mod byte_scan;
+pub mod dilithium;
 pub mod ed25519;
+#[cfg(feature = "std")]
+pub mod env;
+#[cfg(feature = "std")]
+pub mod fs;
+pub mod hints;
 pub mod io;
+pub mod msm;
+pub mod ripemd160;
 pub mod secp256k1;
+pub mod thread;
+pub mod time;
 pub mod unconstrained;
 pub mod utils;
 #[cfg(feature = "verify")]
 pub mod verify;
+pub mod wide_alu;
 
 extern "C" {
     /// Halts the program with the given exit code.
     pub fn syscall_halt(exit_code: u8) -> !;
 
+    /// Aborts the execution with the given reason code and message.
+    pub fn syscall_abort(code: u32, message: *const u8, len: usize) -> !;
+
     /// Writes the bytes in the given buffer to the given file descriptor.
     pub fn syscall_write(fd: u32, write_buf: *const u8, nbytes: usize);
 
@@ -29,12 +52,21 @@ extern "C" {
     /// Executes the SHA-256 compress operation on the given word array and a given state.
     pub fn syscall_sha256_compress(w: *mut [u32; 64], state: *mut [u32; 8]);
 
+    /// Executes the RIPEMD-160 compress operation on the given message block and a given state.
+    pub fn syscall_ripemd160_compress(w: *const [u32; 16], state: *mut [u32; 5]);
+
     /// Executes an Ed25519 curve addition on the given points.
     pub fn syscall_ed_add(p: *mut [u32; 16], q: *const [u32; 16]);
 
+    /// Executes an Ed25519 scalar multiplication on the given point.
+    pub fn syscall_ed_scalar_mul(p: *mut [u32; 16], scalar: *const [u32; 8]);
+
     /// Executes an Ed25519 curve decompression on the given point.
     pub fn syscall_ed_decompress(point: &mut [u8; 64]);
 
+    /// Executes a window of an Ed25519 multi-scalar multiplication.
+    pub fn syscall_ed_msm(acc: *mut [u32; 16], desc: *const [u32; 3]);
+
     /// Executes an Sepc256k1 curve addition on the given points.
     pub fn syscall_secp256k1_add(p: *mut [u32; 16], q: *const [u32; 16]);
 
@@ -44,12 +76,18 @@ extern "C" {
     /// Executes an Secp256k1 curve decompression on the given point.
     pub fn syscall_secp256k1_decompress(point: &mut [u8; 64], is_odd: bool);
 
+    /// Executes a window of a Secp256k1 multi-scalar multiplication.
+    pub fn syscall_secp256k1_msm(acc: *mut [u32; 24], desc: *const [u32; 3]);
+
     /// Executes a Bn254 curve addition on the given points.
     pub fn syscall_bn254_add(p: *mut [u32; 16], q: *const [u32; 16]);
 
     /// Executes a Bn254 curve doubling on the given point.
     pub fn syscall_bn254_double(p: *mut [u32; 16]);
 
+    /// Executes a window of a Bn254 multi-scalar multiplication.
+    pub fn syscall_bn254_msm(acc: *mut [u32; 24], desc: *const [u32; 3]);
+
     /// Executes a BLS12-381 curve addition on the given points.
     pub fn syscall_bls12381_add(p: *mut [u32; 24], q: *const [u32; 24]);
 
@@ -77,6 +115,26 @@ extern "C" {
     /// Reads the next element in the hint stream into the given buffer.
     pub fn syscall_hint_read(ptr: *mut u8, len: usize);
 
+    /// Writes the next element in the hint stream, made of `num_words` words, to the given buffer,
+    /// which may already be initialized.
+    pub fn syscall_hint_read_words(ptr: *mut u32, num_words: usize);
+
+    /// Writes the number of elements of the hint stream that were not read yet to the given buffer.
+    pub fn syscall_hint_remaining(ptr: *mut u32);
+
+    /// Writes the number of cycles executed so far and the number of extra cycles of the last
+    /// precompile call to the given buffer. The counters are not constrained by the proof.
+    pub fn syscall_cycle_info(ptr: *mut u32);
+
+    /// Writes the length of the given paged input to the given buffer.
+    pub fn syscall_paged_input_len(index: u32, ptr: *mut u32);
+
+    /// Maps a page of a paged input into the given buffer.
+    pub fn syscall_paged_input_map(ptr: *mut u8, page: u32);
+
+    /// Maps a page of the ROM at its address, and writes its Merkle path to the given buffer.
+    pub fn syscall_rom_map(page: u32, path: *mut u8);
+
     /// Allocates a buffer aligned to the given alignment.
     pub fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u8;
 
@@ -92,6 +150,42 @@ extern "C" {
         modulus: *const [u32; 8],
     );
 
+    /// Executes a variable-width bigint addition.
+    pub fn syscall_bigint_add(z: *mut u32, operands: *const u32);
+
+    /// Executes a variable-width bigint subtraction.
+    pub fn syscall_bigint_sub(z: *mut u32, operands: *const u32);
+
+    /// Executes a variable-width bigint multiplication.
+    pub fn syscall_bigint_mul(z: *mut u32, operands: *const u32);
+
+    /// Executes a lane-wise Dilithium addition on the given blocks of coefficients.
+    pub fn syscall_dilithium_add(x: *mut [u32; 8], y: *const [u32; 8]);
+
+    /// Executes a lane-wise Dilithium subtraction on the given blocks of coefficients.
+    pub fn syscall_dilithium_sub(x: *mut [u32; 8], y: *const [u32; 8]);
+
+    /// Executes a lane-wise Dilithium multiplication on the given blocks of coefficients.
+    pub fn syscall_dilithium_mul(x: *mut [u32; 8], y: *const [u32; 8]);
+
+    /// Executes a lane-wise Dilithium butterfly on the given blocks of coefficients.
+    pub fn syscall_dilithium_butterfly(x: *mut [u32; 8], y: *mut [u32; 8]);
+
+    /// Executes a wrapping 64-bit addition on the given inputs.
+    pub fn syscall_wide_alu_add(x: *mut [u32; 2], y: *const [u32; 2]);
+
+    /// Executes a wrapping 64-bit subtraction on the given inputs.
+    pub fn syscall_wide_alu_sub(x: *mut [u32; 2], y: *const [u32; 2]);
+
+    /// Executes a wrapping 64-bit multiplication on the given inputs.
+    pub fn syscall_wide_alu_mul(x: *mut [u32; 2], y: *const [u32; 2]);
+
+    /// Executes an unsigned 64-bit comparison on the given inputs.
+    pub fn syscall_wide_alu_ltu(x: *mut [u32; 2], y: *const [u32; 2]);
+
+    /// Executes a signed 64-bit comparison on the given inputs.
+    pub fn syscall_wide_alu_lt(x: *mut [u32; 2], y: *const [u32; 2]);
+
     /// Executes a BLS12-381 field addition on the given inputs.
     pub fn syscall_bls12381_fp_addmod(p: *mut u32, q: *const u32);
 
@@ -110,6 +204,9 @@ extern "C" {
     /// Executes a BLS12-381 Fp2 multiplication on the given inputs.
     pub fn syscall_bls12381_fp2_mulmod(p: *mut u32, q: *const u32);
 
+    /// Executes a BLS12-381 field batch inversion on the given elements.
+    pub fn syscall_bls12381_fp_batch_inv(x: *mut u32, len: u32);
+
     /// Executes a BN254 field addition on the given inputs.
     pub fn syscall_bn254_fp_addmod(p: *mut u32, q: *const u32);
 
@@ -128,4 +225,16 @@ extern "C" {
     /// Executes a BN254 Fp2 multiplication on the given inputs.
     pub fn syscall_bn254_fp2_mulmod(p: *mut u32, q: *const u32);
 
+    /// Executes a BN254 field batch inversion on the given elements.
+    pub fn syscall_bn254_fp_batch_inv(x: *mut u32, len: u32);
+
+    /// Writes the number of bytes of the source before the first delimiter.
+    pub fn syscall_byte_scan_find(dst: *mut u32, operands: *const u32);
+
+    /// Copies the source words to the destination.
+    pub fn syscall_byte_scan_copy(dst: *mut u32, operands: *const u32);
+
+    /// Decodes the source words of ASCII hex digits to the destination.
+    pub fn syscall_byte_scan_hex_decode(dst: *mut u32, operands: *const u32);
+
 }
@@ -0,0 +1,264 @@
+//! Multi-scalar multiplications accelerated with the windowed MSM precompiles.
+//!
+//! An MSM syscall consumes one window of [`MSM_WINDOW_BITS`] bits of every scalar: it multiplies
+//! the accumulator by `2^MSM_WINDOW_BITS` and adds the multiple of each point selected by its
+//! digit from a table of its `2^MSM_WINDOW_BITS` first multiples. The functions of this module
+//! build the tables and the digits, split the points into batches the syscall accepts and combine
+//! their sums, so that callers only pass points and scalars.
+//!
+//! Scalars are 256-bit integers given as little-endian words, like the scalars of
+//! [`AffinePoint::mul_assign`].
+
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    bn254::Bn254AffinePoint, ed25519::Ed25519AffinePoint, secp256k1::Secp256k1AffinePoint,
+    syscall_bn254_msm, syscall_ed_add, syscall_ed_msm, syscall_secp256k1_msm,
+    syscall_uint256_mulmod, utils::AffinePoint,
+};
+
+/// The number of bits of the scalars consumed by a single MSM syscall, which must match
+/// `sp1_core_executor::events::MSM_WINDOW_BITS`.
+pub const MSM_WINDOW_BITS: usize = 4;
+
+/// The number of multiples in the table of each point.
+const MSM_TABLE_SIZE: usize = 1 << MSM_WINDOW_BITS;
+
+/// The maximum number of points of a single MSM syscall, which must match
+/// `sp1_core_executor::events::MSM_MAX_POINTS`.
+pub const MSM_MAX_POINTS: usize = 252;
+
+/// The number of words of a scalar.
+const SCALAR_WORDS: usize = 8;
+
+/// The number of words of a coordinate of a point.
+const COORDINATE_WORDS: usize = 8;
+
+/// The number of windows of a scalar.
+const NUM_WINDOWS: usize = SCALAR_WORDS * 32 / MSM_WINDOW_BITS;
+
+/// The identity of the Edwards curves in affine coordinates, `(0, 1)`.
+const EDWARDS_IDENTITY: [u32; 16] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0];
+
+/// The identity of the Weierstrass curves in projective coordinates, `(0 : 1 : 0)`.
+const WEIERSTRASS_IDENTITY: [u32; 24] = {
+    let mut identity = [0; 24];
+    identity[COORDINATE_WORDS] = 1;
+    identity
+};
+
+/// The modulus of the base field of Secp256k1, `2^256 - 2^32 - 977`.
+const SECP256K1_MODULUS: [u32; 8] = [
+    0xFFFF_FC2F,
+    0xFFFF_FFFE,
+    0xFFFF_FFFF,
+    0xFFFF_FFFF,
+    0xFFFF_FFFF,
+    0xFFFF_FFFF,
+    0xFFFF_FFFF,
+    0xFFFF_FFFF,
+];
+
+/// The modulus of the base field of Bn254.
+const BN254_MODULUS: [u32; 8] = [
+    0xD87C_FD47,
+    0x3C20_8C16,
+    0x6871_CA8D,
+    0x9781_6A91,
+    0x8181_585D,
+    0xB850_45B6,
+    0xE131_A029,
+    0x3064_4E72,
+];
+
+/// A syscall running one window of an MSM on points of `W` words.
+type MsmSyscall<const W: usize> = unsafe extern "C" fn(*mut [u32; W], *const [u32; 3]);
+
+/// Computes `sum_i [scalars[i]] points[i]` on the Ed25519 curve.
+///
+/// ### Panics
+///
+/// Panics if there are not as many scalars as points.
+pub fn ed25519_msm(points: &[Ed25519AffinePoint], scalars: &[[u32; 8]]) -> Ed25519AffinePoint {
+    let tables = points
+        .iter()
+        .map(|point| {
+            let mut table = [EDWARDS_IDENTITY; MSM_TABLE_SIZE];
+            for k in 1..MSM_TABLE_SIZE {
+                table[k] = table[k - 1];
+                unsafe { syscall_ed_add(&mut table[k], &point.0) };
+            }
+            table
+        })
+        .collect::<Vec<_>>();
+    Ed25519AffinePoint(msm(syscall_ed_msm, EDWARDS_IDENTITY, &tables, scalars))
+}
+
+/// Computes `sum_i [scalars[i]] points[i]` on the Secp256k1 curve, or `None` if the sum is the
+/// identity, which has no affine coordinates.
+///
+/// ### Panics
+///
+/// Panics if there are not as many scalars as points.
+pub fn secp256k1_msm(
+    points: &[Secp256k1AffinePoint],
+    scalars: &[[u32; 8]],
+) -> Option<Secp256k1AffinePoint> {
+    let tables = points.iter().map(weierstrass_table).collect::<Vec<_>>();
+    let sum = msm(syscall_secp256k1_msm, WEIERSTRASS_IDENTITY, &tables, scalars);
+    to_affine(&sum, &SECP256K1_MODULUS).map(Secp256k1AffinePoint)
+}
+
+/// Computes `sum_i [scalars[i]] points[i]` on the Bn254 curve, or `None` if the sum is the
+/// identity, which has no affine coordinates.
+///
+/// ### Panics
+///
+/// Panics if there are not as many scalars as points.
+pub fn bn254_msm(points: &[Bn254AffinePoint], scalars: &[[u32; 8]]) -> Option<Bn254AffinePoint> {
+    let tables = points.iter().map(weierstrass_table).collect::<Vec<_>>();
+    let sum = msm(syscall_bn254_msm, WEIERSTRASS_IDENTITY, &tables, scalars);
+    to_affine(&sum, &BN254_MODULUS).map(Bn254AffinePoint)
+}
+
+/// Computes `sum_i [scalars[i]] P_i` with `syscall`, given the tables of the multiples of each
+/// point `P_i` and the identity of the curve.
+fn msm<const W: usize>(
+    syscall: MsmSyscall<W>,
+    identity: [u32; W],
+    tables: &[[[u32; W]; MSM_TABLE_SIZE]],
+    scalars: &[[u32; SCALAR_WORDS]],
+) -> [u32; W] {
+    assert_eq!(tables.len(), scalars.len(), "an MSM takes as many scalars as points");
+    let sums = tables
+        .chunks(MSM_MAX_POINTS)
+        .zip(scalars.chunks(MSM_MAX_POINTS))
+        .map(|(tables, scalars)| {
+            let digits = |window: usize| {
+                let bit = window * MSM_WINDOW_BITS;
+                scalars
+                    .iter()
+                    .map(|scalar| (scalar[bit / 32] >> (bit % 32)) & (MSM_TABLE_SIZE as u32 - 1))
+                    .collect::<Vec<_>>()
+            };
+            // The accumulator is the identity until the first window with a non-zero digit.
+            let mut acc = identity;
+            let windows = (0..NUM_WINDOWS)
+                .rev()
+                .map(digits)
+                .skip_while(|digits| digits.iter().all(|&digit| digit == 0));
+            for digits in windows {
+                run_window(syscall, &mut acc, tables, &digits);
+            }
+            acc
+        })
+        .collect::<Vec<_>>();
+    sum(syscall, identity, sums)
+}
+
+/// Adds up `points` with `syscall`, using the digit one to select each point.
+fn sum<const W: usize>(
+    syscall: MsmSyscall<W>,
+    identity: [u32; W],
+    mut points: Vec<[u32; W]>,
+) -> [u32; W] {
+    while points.len() > 1 {
+        points = points
+            .chunks(MSM_MAX_POINTS)
+            .map(|points| {
+                let tables = points
+                    .iter()
+                    .map(|point| {
+                        let mut table = [identity; MSM_TABLE_SIZE];
+                        table[1] = *point;
+                        table
+                    })
+                    .collect::<Vec<_>>();
+                // The accumulator starts at the identity, which the doublings of the window keep.
+                let mut acc = identity;
+                run_window(syscall, &mut acc, &tables, &vec![1; points.len()]);
+                acc
+            })
+            .collect();
+    }
+    points.pop().unwrap_or(identity)
+}
+
+/// Runs one window of an MSM on `acc`, with the digit of each table in `digits`.
+fn run_window<const W: usize>(
+    syscall: MsmSyscall<W>,
+    acc: &mut [u32; W],
+    tables: &[[[u32; W]; MSM_TABLE_SIZE]],
+    digits: &[u32],
+) {
+    debug_assert!(tables.len() <= MSM_MAX_POINTS && tables.len() == digits.len());
+    let desc = [tables.len() as u32, tables.as_ptr() as u32, digits.as_ptr() as u32];
+    // SAFETY: the tables and the digits are word-aligned arrays of `desc[0]` entries, and every
+    // digit is below `MSM_TABLE_SIZE`.
+    unsafe { syscall(acc, &desc) };
+}
+
+/// The table of the multiples `[0] P, ..., [15] P` of `point` in projective coordinates.
+///
+/// The multiples are computed in affine coordinates, which is sound since the points of the
+/// Secp256k1 and Bn254 curves have prime order, so none of them is the identity.
+fn weierstrass_table<P: AffinePoint<16>>(point: &P) -> [[u32; 24]; MSM_TABLE_SIZE] {
+    let projective = |point: &P| {
+        let mut words = [0; 24];
+        words[..16].copy_from_slice(point.limbs_ref());
+        words[2 * COORDINATE_WORDS] = 1;
+        words
+    };
+    let mut table = [WEIERSTRASS_IDENTITY; MSM_TABLE_SIZE];
+    table[1] = projective(point);
+    let mut multiple = point.clone();
+    multiple.double();
+    table[2] = projective(&multiple);
+    for entry in table.iter_mut().skip(3) {
+        multiple.add_assign(point);
+        *entry = projective(&multiple);
+    }
+    table
+}
+
+/// The affine coordinates of the projective point `point` of a curve over the field of `modulus`,
+/// or `None` if it is the identity.
+fn to_affine(point: &[u32; 24], modulus: &[u32; 8]) -> Option<[u32; 16]> {
+    let coordinate = |i: usize| -> [u32; 8] {
+        point[i * COORDINATE_WORDS..(i + 1) * COORDINATE_WORDS].try_into().unwrap()
+    };
+    let z = coordinate(2);
+    if z == [0; 8] {
+        return None;
+    }
+
+    // z^-1 = z^(p - 2), where the modulus is odd and larger than two.
+    let mut exponent = *modulus;
+    exponent[0] -= 2;
+    let mut z_inv = [0; 8];
+    z_inv[0] = 1;
+    for i in (0..256).rev().skip_while(|i| (exponent[i / 32] >> (i % 32)) & 1 == 0) {
+        let square = z_inv;
+        mulmod(&mut z_inv, &square, modulus);
+        if (exponent[i / 32] >> (i % 32)) & 1 == 1 {
+            mulmod(&mut z_inv, &z, modulus);
+        }
+    }
+
+    let mut affine = [0; 16];
+    for (i, words) in affine.chunks_exact_mut(COORDINATE_WORDS).enumerate() {
+        let mut value = coordinate(i);
+        mulmod(&mut value, &z_inv, modulus);
+        words.copy_from_slice(&value);
+    }
+    Some(affine)
+}
+
+/// Sets `x` to `x * y` modulo `modulus` with the `UINT256_MULMOD` precompile.
+fn mulmod(x: &mut [u32; 8], y: &[u32; 8], modulus: &[u32; 8]) {
+    // The precompile reads the modulus right after `y`.
+    let mut y_and_modulus = [0; 16];
+    y_and_modulus[..8].copy_from_slice(y);
+    y_and_modulus[8..].copy_from_slice(modulus);
+    unsafe { syscall_uint256_mulmod(x, y_and_modulus.as_ptr() as *const [u32; 8]) };
+}
@@ -0,0 +1,83 @@
+//! RIPEMD-160 hashing, accelerated by the RIPEMD-160 compress precompile.
+//!
+//! Bitcoin derives addresses with `RIPEMD-160(SHA-256(x))`, so a guest can combine [`Ripemd160`]
+//! with the patched `sha2` crate to compute them with precompiles only.
+
+use crate::syscall_ripemd160_compress;
+
+/// The size of a message block, in bytes.
+const BLOCK_SIZE: usize = 64;
+
+/// The initial state of RIPEMD-160.
+const INITIAL_STATE: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// Compresses a message block into the state.
+pub fn compress(state: &mut [u32; 5], block: &[u8; BLOCK_SIZE]) {
+    let mut w = [0u32; 16];
+    for (word, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+    unsafe {
+        syscall_ripemd160_compress(&w, state);
+    }
+}
+
+/// An incremental RIPEMD-160 hasher.
+#[derive(Debug, Clone)]
+pub struct Ripemd160 {
+    state: [u32; 5],
+    buffer: [u8; BLOCK_SIZE],
+    buffer_len: usize,
+    len: u64,
+}
+
+impl Default for Ripemd160 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Ripemd160 {
+    /// Creates a hasher of the empty message.
+    pub const fn new() -> Self {
+        Self { state: INITIAL_STATE, buffer: [0; BLOCK_SIZE], buffer_len: 0, len: 0 }
+    }
+
+    /// Appends `data` to the message.
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.len += data.len() as u64;
+        while !data.is_empty() {
+            let n = (BLOCK_SIZE - self.buffer_len).min(data.len());
+            self.buffer[self.buffer_len..self.buffer_len + n].copy_from_slice(&data[..n]);
+            self.buffer_len += n;
+            data = &data[n..];
+            if self.buffer_len == BLOCK_SIZE {
+                compress(&mut self.state, &self.buffer);
+                self.buffer_len = 0;
+            }
+        }
+    }
+
+    /// Pads the message and returns its digest.
+    pub fn finalize(mut self) -> [u8; 20] {
+        let bit_len = self.len.wrapping_mul(8);
+        self.update(&[0x80]);
+        while self.buffer_len != BLOCK_SIZE - 8 {
+            self.update(&[0]);
+        }
+        self.update(&bit_len.to_le_bytes());
+
+        let mut digest = [0u8; 20];
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        digest
+    }
+}
+
+/// Returns the RIPEMD-160 digest of `data`.
+pub fn ripemd160(data: &[u8]) -> [u8; 20] {
+    let mut hasher = Ripemd160::new();
+    hasher.update(data);
+    hasher.finalize()
+}
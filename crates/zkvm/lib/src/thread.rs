@@ -0,0 +1,54 @@
+//! Threads for single-threaded programs.
+//!
+//! The zkVM executes a single thread, so [`std::thread::spawn`] is not supported inside a program.
+//! [`spawn`] is a replacement that runs the closure inline, to completion, before returning its
+//! handle. Programs that spawn threads only to parallelize work, and join them before using their
+//! results, behave the same. Programs whose threads wait on each other, for example through
+//! channels, deadlock.
+
+use alloc::boxed::Box;
+use core::any::Any;
+
+/// The result of joining a thread, like [`std::thread::Result`].
+pub type Result<T> = core::result::Result<T, Box<dyn Any + Send + 'static>>;
+
+/// Runs `f` to completion, and returns a handle holding its result.
+///
+/// A panic in `f` is not caught: it halts the program, as panics do in the zkVM.
+///
+/// ### Examples
+/// ```ignore
+/// let handles = (0..4).map(|i| sp1_zkvm::thread::spawn(move || i * i)).collect::<Vec<_>>();
+/// let squares = handles.into_iter().map(|h| h.join().unwrap()).collect::<Vec<_>>();
+/// ```
+pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    JoinHandle { result: f() }
+}
+
+/// The number of threads that can run in parallel, which is always one.
+pub fn available_parallelism() -> core::num::NonZeroUsize {
+    core::num::NonZeroUsize::MIN
+}
+
+/// A handle to a thread spawned with [`spawn`], which has already finished.
+#[derive(Debug)]
+pub struct JoinHandle<T> {
+    result: T,
+}
+
+impl<T> JoinHandle<T> {
+    /// Returns the result of the thread. It never fails, since a panicking thread halts the
+    /// program.
+    pub fn join(self) -> Result<T> {
+        Ok(self.result)
+    }
+
+    /// Whether the thread has finished, which is always the case.
+    pub fn is_finished(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,133 @@
+//! A monotonic clock measuring time in cycles of the zkVM.
+//!
+//! The zkVM has no wall clock, so [`std::time::Instant::now`] is not supported inside a program.
+//! [`Instant`] is a replacement derived from the number of cycles executed so far, which the host
+//! returns through a hook. Durations are converted from cycles at a nominal
+//! [`CYCLES_PER_SECOND`], so that code measuring elapsed time, such as progress logging or
+//! benchmarks, keeps working unchanged.
+//!
+//! Like every hook, the cycle count is not constrained by the zkVM, so it must not affect the
+//! public values of a program.
+//!
+//! [`cycle_info`] also reports the cost of the last precompile call, so that a program can choose
+//! between a precompile and a software implementation of an operation by measuring both. Its
+//! counters are not constrained either: a proof does not attest to any cycle count it outputs.
+
+use core::{ops::Add, time::Duration};
+
+use crate::{
+    io::{alloc_uninitialized, read_vec, write, FD_CYCLE_COUNT},
+    syscall_cycle_info,
+};
+
+/// The number of cycles per second at which cycles are converted to durations, such that one cycle
+/// lasts one nanosecond.
+pub const CYCLES_PER_SECOND: u64 = 1_000_000_000;
+
+/// Returns the number of cycles executed by the program so far.
+pub fn cycle_count() -> u64 {
+    write(FD_CYCLE_COUNT, &[]);
+    u64::from_le_bytes(read_vec().try_into().expect("invalid cycle count"))
+}
+
+/// The cycle counters of the executor, as returned by [`cycle_info`].
+///
+/// ### Soundness
+///
+/// The counters are supplied by the prover and are not constrained by the proof, so a malicious
+/// prover can report any value. A verifier must not trust a cycle count or budget that a program
+/// derives from them, even if it is committed to the public values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleInfo {
+    /// The number of cycles executed before the call to [`cycle_info`], as reported by the prover.
+    pub cycles: u64,
+    /// The number of extra cycles of the last precompile call, or zero if there was none, as
+    /// reported by the prover.
+    pub last_precompile_cycles: u32,
+}
+
+/// Returns the cycle counters of the executor.
+///
+/// Unlike [`cycle_count`], this is a syscall rather than a hook, so it cannot be used in an
+/// unconstrained block.
+///
+/// ### Soundness
+///
+/// The counters are reported by the prover and not constrained by the proof. They are fit to
+/// choose between implementations of an operation, since every choice computes the same result,
+/// but a cycle count or budget committed to the public values is only the claim of the prover:
+/// a verifier must not rely on it, and a program must not let it change its other outputs.
+///
+/// ### Examples
+/// ```ignore
+/// use sp1_zkvm::time::cycle_info;
+///
+/// let before = cycle_info();
+/// let digest = hash_with_precompile(&data);
+/// let after = cycle_info();
+/// // Not attested by the proof.
+/// sp1_zkvm::io::commit(&(after.cycles - before.cycles));
+/// ```
+pub fn cycle_info() -> CycleInfo {
+    // The syscall writes to uninitialized memory, so the result needs a fresh allocation.
+    let ptr = alloc_uninitialized(12) as *mut u32;
+    unsafe {
+        syscall_cycle_info(ptr);
+        CycleInfo {
+            cycles: u64::from(*ptr) | u64::from(*ptr.add(1)) << 32,
+            last_precompile_cycles: *ptr.add(2),
+        }
+    }
+}
+
+/// A point in the execution of the program, measured in cycles.
+///
+/// ### Examples
+/// ```ignore
+/// use sp1_zkvm::time::Instant;
+///
+/// let start = Instant::now();
+/// expensive_computation();
+/// println!("took {:?} ({} cycles)", start.elapsed(), start.elapsed_cycles());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Instant {
+    cycles: u64,
+}
+
+impl Instant {
+    /// Returns the instant of the current cycle.
+    pub fn now() -> Self {
+        Self { cycles: cycle_count() }
+    }
+
+    /// Returns the number of cycles since this instant.
+    pub fn elapsed_cycles(&self) -> u64 {
+        cycle_count().saturating_sub(self.cycles)
+    }
+
+    /// Returns the duration since this instant.
+    pub fn elapsed(&self) -> Duration {
+        cycles_to_duration(self.elapsed_cycles())
+    }
+
+    /// Returns the duration from `earlier` to this instant, or zero if `earlier` is later.
+    pub fn duration_since(&self, earlier: Instant) -> Duration {
+        cycles_to_duration(self.cycles.saturating_sub(earlier.cycles))
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, duration: Duration) -> Instant {
+        let cycles = duration.as_nanos() * u128::from(CYCLES_PER_SECOND) / 1_000_000_000;
+        Instant { cycles: self.cycles + u64::try_from(cycles).expect("instant overflow") }
+    }
+}
+
+/// Converts a number of cycles to a duration at [`CYCLES_PER_SECOND`].
+fn cycles_to_duration(cycles: u64) -> Duration {
+    let nanos = u128::from(cycles) * 1_000_000_000 / u128::from(CYCLES_PER_SECOND);
+    Duration::from_nanos(nanos as u64)
+}
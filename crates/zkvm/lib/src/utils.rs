@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 pub trait AffinePoint<const N: usize>: Clone + Sized {
     /// The generator.
     const GENERATOR: [u32; N];
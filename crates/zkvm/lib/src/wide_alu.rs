@@ -0,0 +1,62 @@
+//! 64-bit integer arithmetic, accelerated by the wide ALU precompiles.
+//!
+//! The zkVM is a 32-bit machine, so the compiler lowers `u64` and `i64` arithmetic to several
+//! 32-bit instructions, and a 64-bit multiplication to four multiplications with carries. Each of
+//! these helpers is a single precompile call instead.
+
+use crate::{
+    syscall_wide_alu_add, syscall_wide_alu_lt, syscall_wide_alu_ltu, syscall_wide_alu_mul,
+    syscall_wide_alu_sub,
+};
+
+/// Applies a wide ALU precompile to `x` and `y`.
+fn apply(x: u64, y: u64, op: unsafe extern "C" fn(*mut [u32; 2], *const [u32; 2])) -> u64 {
+    let mut x = [x as u32, (x >> 32) as u32];
+    let y = [y as u32, (y >> 32) as u32];
+    unsafe {
+        op(&mut x, &y);
+    }
+    u64::from(x[0]) | u64::from(x[1]) << 32
+}
+
+/// Computes `x + y`, wrapping around at the boundary of the type.
+pub fn wrapping_add(x: u64, y: u64) -> u64 {
+    apply(x, y, syscall_wide_alu_add)
+}
+
+/// Computes `x - y`, wrapping around at the boundary of the type.
+pub fn wrapping_sub(x: u64, y: u64) -> u64 {
+    apply(x, y, syscall_wide_alu_sub)
+}
+
+/// Computes `x * y`, wrapping around at the boundary of the type.
+pub fn wrapping_mul(x: u64, y: u64) -> u64 {
+    apply(x, y, syscall_wide_alu_mul)
+}
+
+/// Returns whether `x < y`.
+pub fn lt(x: u64, y: u64) -> bool {
+    apply(x, y, syscall_wide_alu_ltu) == 1
+}
+
+/// Computes `x + y` on signed integers, wrapping around at the boundary of the type.
+pub fn wrapping_add_signed(x: i64, y: i64) -> i64 {
+    wrapping_add(x as u64, y as u64) as i64
+}
+
+/// Computes `x - y` on signed integers, wrapping around at the boundary of the type.
+pub fn wrapping_sub_signed(x: i64, y: i64) -> i64 {
+    wrapping_sub(x as u64, y as u64) as i64
+}
+
+/// Computes `x * y` on signed integers, wrapping around at the boundary of the type.
+///
+/// The low 64 bits of a product do not depend on the signedness of its operands.
+pub fn wrapping_mul_signed(x: i64, y: i64) -> i64 {
+    wrapping_mul(x as u64, y as u64) as i64
+}
+
+/// Returns whether `x < y` on signed integers.
+pub fn lt_signed(x: i64, y: i64) -> bool {
+    apply(x as u64, y as u64, syscall_wide_alu_lt) == 1
+}
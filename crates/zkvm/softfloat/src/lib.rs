@@ -0,0 +1,465 @@
+//! IEEE 754 floating-point arithmetic for SP1 zkVM guests.
+//!
+//! The zkVM implements RV32IM, which has no floating-point instructions, so the compiler lowers
+//! every `f32` and `f64` operation to a call to an intrinsic of `compiler-builtins`, such as
+//! `__adddf3`. This crate implements the addition, subtraction and multiplication intrinsics, which
+//! dominate numerical code, with integer operations that are cheap in the zkVM: the significands
+//! are multiplied with a single widening multiplication, and rounding uses three guard bits rather
+//! than a loop.
+//!
+//! On the zkVM, linking this crate replaces the intrinsics of `compiler-builtins`, so floating-point
+//! programs use it without changes. It is linked by the `softfloat` feature of `sp1-zkvm`. On other
+//! targets, the functions can be called directly, which is how they are tested against the
+//! hardware.
+//!
+//! Results are rounded to nearest, ties to even, and subnormals are supported. NaN results are
+//! quiet, but their payload is not specified.
+
+#![cfg_attr(not(test), no_std)]
+
+/// Implements addition and multiplication for a binary floating-point format.
+macro_rules! impl_float {
+    ($float:ty, $bits:ty, $wide:ty, $sig_bits:expr, $add:ident, $sub:ident, $mul:ident) => {
+        /// Adds two floating-point numbers.
+        #[must_use]
+        pub fn $add(a: $float, b: $float) -> $float {
+            <$float>::from_bits(add_bits(a.to_bits(), b.to_bits()))
+        }
+
+        /// Subtracts `b` from `a`.
+        #[must_use]
+        pub fn $sub(a: $float, b: $float) -> $float {
+            <$float>::from_bits(add_bits(a.to_bits(), b.to_bits() ^ sign_bit::<$bits>()))
+        }
+
+        /// Multiplies two floating-point numbers.
+        #[must_use]
+        pub fn $mul(a: $float, b: $float) -> $float {
+            <$float>::from_bits(mul_bits(a.to_bits(), b.to_bits()))
+        }
+
+        impl Bits for $bits {
+            const BITS: u32 = <$bits>::BITS;
+            const SIG_BITS: u32 = $sig_bits;
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn leading_zeros(self) -> u32 {
+                <$bits>::leading_zeros(self)
+            }
+
+            fn from_i32(value: i32) -> Self {
+                value as Self
+            }
+
+            fn to_i32(self) -> i32 {
+                self as i32
+            }
+
+            fn widen_mul(self, other: Self) -> (Self, Self) {
+                let product = self as $wide * other as $wide;
+                ((product >> <$bits>::BITS) as Self, product as Self)
+            }
+        }
+    };
+}
+
+use core::ops::{Add, BitAnd, BitOr, BitXor, Shl, Shr, Sub};
+
+/// The unsigned integer representation of a floating-point format.
+trait Bits:
+    Copy
+    + Eq
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Shl<u32, Output = Self>
+    + Shr<u32, Output = Self>
+{
+    /// The width of the representation.
+    const BITS: u32;
+    /// The number of explicit bits of the significand.
+    const SIG_BITS: u32;
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn leading_zeros(self) -> u32;
+    fn from_i32(value: i32) -> Self;
+    fn to_i32(self) -> i32;
+
+    /// The high and low halves of the full product of `self` and `other`.
+    fn widen_mul(self, other: Self) -> (Self, Self);
+
+    fn from_bool(value: bool) -> Self {
+        if value {
+            Self::ONE
+        } else {
+            Self::ZERO
+        }
+    }
+}
+
+impl_float!(f32, u32, u64, 23, add_f32, sub_f32, mul_f32);
+impl_float!(f64, u64, u128, 52, add_f64, sub_f64, mul_f64);
+
+fn sign_bit<B: Bits>() -> B {
+    B::ONE << (B::BITS - 1)
+}
+
+fn implicit_bit<B: Bits>() -> B {
+    B::ONE << B::SIG_BITS
+}
+
+fn sig_mask<B: Bits>() -> B {
+    implicit_bit::<B>() - B::ONE
+}
+
+fn exp_bits<B: Bits>() -> u32 {
+    B::BITS - B::SIG_BITS - 1
+}
+
+fn max_exp<B: Bits>() -> i32 {
+    (1 << exp_bits::<B>()) - 1
+}
+
+fn bias<B: Bits>() -> i32 {
+    max_exp::<B>() >> 1
+}
+
+fn inf<B: Bits>() -> B {
+    B::from_i32(max_exp::<B>()) << B::SIG_BITS
+}
+
+fn quiet_bit<B: Bits>() -> B {
+    implicit_bit::<B>() >> 1
+}
+
+fn exponent<B: Bits>(bits: B) -> i32 {
+    ((bits >> B::SIG_BITS) & B::from_i32(max_exp::<B>())).to_i32()
+}
+
+/// Shifts the significand of a subnormal number so that its leading bit is the implicit bit, and
+/// returns its exponent.
+fn normalize<B: Bits>(sig: &mut B) -> i32 {
+    let shift = sig.leading_zeros() - implicit_bit::<B>().leading_zeros();
+    *sig = *sig << shift;
+    1 - shift as i32
+}
+
+/// Shifts `sig` right by `shift` bits, setting its lowest bit if any bit set is shifted out.
+fn shr_sticky<B: Bits>(sig: B, shift: u32) -> B {
+    if shift >= B::BITS {
+        return B::from_bool(sig != B::ZERO);
+    }
+    let sticky = shift > 0 && (sig << (B::BITS - shift)) != B::ZERO;
+    (sig >> shift) | B::from_bool(sticky)
+}
+
+fn add_bits<B: Bits>(mut a: B, mut b: B) -> B {
+    let abs_mask = sign_bit::<B>() - B::ONE;
+    let inf = inf::<B>();
+    let mut a_abs = a & abs_mask;
+    let mut b_abs = b & abs_mask;
+
+    // Zeros, infinities and NaNs.
+    if a_abs == B::ZERO || a_abs >= inf || b_abs == B::ZERO || b_abs >= inf {
+        if a_abs > inf {
+            return a | quiet_bit::<B>();
+        }
+        if b_abs > inf {
+            return b | quiet_bit::<B>();
+        }
+        if a_abs == inf {
+            // The sum of infinities of opposite signs is a NaN.
+            return if a ^ b == sign_bit::<B>() { inf | quiet_bit::<B>() } else { a };
+        }
+        if b_abs == inf {
+            return b;
+        }
+        if a_abs == B::ZERO {
+            // The sum of zeros is negative only if both are.
+            return if b_abs == B::ZERO { a & b } else { b };
+        }
+        return a;
+    }
+
+    // Order the operands by magnitude.
+    if b_abs > a_abs {
+        core::mem::swap(&mut a, &mut b);
+        core::mem::swap(&mut a_abs, &mut b_abs);
+    }
+
+    let mut a_exp = exponent(a);
+    let mut b_exp = exponent(b);
+    let mut a_sig = a & sig_mask::<B>();
+    let mut b_sig = b & sig_mask::<B>();
+    if a_exp == 0 {
+        a_exp = normalize(&mut a_sig);
+    }
+    if b_exp == 0 {
+        b_exp = normalize(&mut b_sig);
+    }
+
+    let sign = a & sign_bit::<B>();
+    let subtraction = (a ^ b) & sign_bit::<B>() != B::ZERO;
+
+    // Keep three bits below the significand to round the result: guard, round and sticky.
+    a_sig = (a_sig | implicit_bit::<B>()) << 3;
+    b_sig = shr_sticky((b_sig | implicit_bit::<B>()) << 3, (a_exp - b_exp) as u32);
+
+    if subtraction {
+        a_sig = a_sig - b_sig;
+        if a_sig == B::ZERO {
+            return B::ZERO;
+        }
+        // Cancellation may clear the leading bits.
+        let implicit = implicit_bit::<B>() << 3;
+        if a_sig < implicit {
+            let shift = a_sig.leading_zeros() - implicit.leading_zeros();
+            a_sig = a_sig << shift;
+            a_exp -= shift as i32;
+        }
+    } else {
+        a_sig = a_sig + b_sig;
+        // The sum may carry into the next bit.
+        if a_sig & (implicit_bit::<B>() << 4) != B::ZERO {
+            a_sig = shr_sticky(a_sig, 1);
+            a_exp += 1;
+        }
+    }
+
+    if a_exp >= max_exp::<B>() {
+        return inf | sign;
+    }
+    if a_exp <= 0 {
+        // The result is subnormal.
+        a_sig = shr_sticky(a_sig, (1 - a_exp) as u32);
+        a_exp = 0;
+    }
+
+    let round_bits = (a_sig & B::from_i32(7)).to_i32();
+    let result = ((a_sig >> 3) & sig_mask::<B>()) | (B::from_i32(a_exp) << B::SIG_BITS) | sign;
+    round(result, round_bits.cmp(&4))
+}
+
+fn mul_bits<B: Bits>(a: B, b: B) -> B {
+    let abs_mask = sign_bit::<B>() - B::ONE;
+    let inf = inf::<B>();
+    let a_abs = a & abs_mask;
+    let b_abs = b & abs_mask;
+    let sign = (a ^ b) & sign_bit::<B>();
+    let mut a_exp = exponent(a);
+    let mut b_exp = exponent(b);
+    let mut a_sig = a & sig_mask::<B>();
+    let mut b_sig = b & sig_mask::<B>();
+
+    // Zeros, infinities and NaNs.
+    if a_abs == B::ZERO || a_abs >= inf || b_abs == B::ZERO || b_abs >= inf {
+        if a_abs > inf {
+            return a | quiet_bit::<B>();
+        }
+        if b_abs > inf {
+            return b | quiet_bit::<B>();
+        }
+        if a_abs == inf || b_abs == inf {
+            // The product of an infinity and a zero is a NaN.
+            return if a_abs == B::ZERO || b_abs == B::ZERO {
+                inf | quiet_bit::<B>()
+            } else {
+                inf | sign
+            };
+        }
+        return sign;
+    }
+
+    if a_exp == 0 {
+        a_exp = normalize(&mut a_sig);
+    }
+    if b_exp == 0 {
+        b_exp = normalize(&mut b_sig);
+    }
+    a_sig = a_sig | implicit_bit::<B>();
+    b_sig = b_sig | implicit_bit::<B>();
+
+    // Shift `b` so that the significand of the product ends in the high half, with its leading bit
+    // at the implicit bit or the bit above it.
+    let (mut hi, mut lo) = a_sig.widen_mul(b_sig << exp_bits::<B>());
+    let mut exp = a_exp + b_exp - bias::<B>();
+    if hi & implicit_bit::<B>() != B::ZERO {
+        exp += 1;
+    } else {
+        hi = (hi << 1) | (lo >> (B::BITS - 1));
+        lo = lo << 1;
+    }
+
+    if exp >= max_exp::<B>() {
+        return inf | sign;
+    }
+    if exp <= 0 {
+        // The result is subnormal: shift the significand so that the round bit is the high bit of
+        // the low half.
+        let shift = (1 - exp) as u32;
+        if shift >= B::BITS {
+            return sign;
+        }
+        let sticky = B::from_bool(lo << (B::BITS - shift) != B::ZERO);
+        lo = (hi << (B::BITS - shift)) | (lo >> shift) | sticky;
+        hi = hi >> shift;
+    } else {
+        hi = (hi & sig_mask::<B>()) | (B::from_i32(exp) << B::SIG_BITS);
+    }
+
+    round(hi | sign, lo.cmp(&sign_bit::<B>()))
+}
+
+/// Rounds a result to nearest, ties to even, given how the discarded bits compare to a half.
+fn round<B: Bits>(result: B, discarded: core::cmp::Ordering) -> B {
+    match discarded {
+        core::cmp::Ordering::Greater => result + B::ONE,
+        core::cmp::Ordering::Equal => result + (result & B::ONE),
+        core::cmp::Ordering::Less => result,
+    }
+}
+
+/// The intrinsics that the compiler calls for floating-point arithmetic.
+#[cfg(target_os = "zkvm")]
+mod intrinsics {
+    #[no_mangle]
+    pub extern "C" fn __addsf3(a: f32, b: f32) -> f32 {
+        crate::add_f32(a, b)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn __subsf3(a: f32, b: f32) -> f32 {
+        crate::sub_f32(a, b)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn __mulsf3(a: f32, b: f32) -> f32 {
+        crate::mul_f32(a, b)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn __adddf3(a: f64, b: f64) -> f64 {
+        crate::add_f64(a, b)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn __subdf3(a: f64, b: f64) -> f64 {
+        crate::sub_f64(a, b)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn __muldf3(a: f64, b: f64) -> f64 {
+        crate::mul_f64(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A xorshift generator, biased towards the exponents and significands where rounding is
+    /// hardest: extremes, subnormals and near ties.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn f64(&mut self) -> f64 {
+            let bits = self.next();
+            match bits % 4 {
+                0 => f64::from_bits(bits),
+                1 => f64::from_bits(bits & 0x800f_ffff_ffff_ffff),
+                2 => f64::from_bits((bits & 0x800f_ffff_ffff_ffff) | (0x3ff << 52)),
+                _ => f64::from_bits(bits | 0x7fe0_0000_0000_0000),
+            }
+        }
+
+        fn f32(&mut self) -> f32 {
+            let bits = self.next() as u32;
+            match bits % 4 {
+                0 => f32::from_bits(bits),
+                1 => f32::from_bits(bits & 0x807f_ffff),
+                2 => f32::from_bits((bits & 0x807f_ffff) | (0x7f << 23)),
+                _ => f32::from_bits(bits | 0x7f00_0000),
+            }
+        }
+    }
+
+    const SPECIAL_F64: [f64; 10] = [
+        0.0,
+        -0.0,
+        1.0,
+        -1.5,
+        f64::MIN_POSITIVE,
+        f64::MAX,
+        f64::EPSILON,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NAN,
+    ];
+
+    fn assert_same_f64(actual: f64, expected: f64, op: &str, a: f64, b: f64) {
+        assert!(
+            actual.to_bits() == expected.to_bits() || (actual.is_nan() && expected.is_nan()),
+            "{a:e} {op} {b:e} = {actual:e}, expected {expected:e}"
+        );
+    }
+
+    fn assert_same_f32(actual: f32, expected: f32, op: &str, a: f32, b: f32) {
+        assert!(
+            actual.to_bits() == expected.to_bits() || (actual.is_nan() && expected.is_nan()),
+            "{a:e} {op} {b:e} = {actual:e}, expected {expected:e}"
+        );
+    }
+
+    fn check_f64(a: f64, b: f64) {
+        assert_same_f64(add_f64(a, b), a + b, "+", a, b);
+        assert_same_f64(sub_f64(a, b), a - b, "-", a, b);
+        assert_same_f64(mul_f64(a, b), a * b, "*", a, b);
+    }
+
+    fn check_f32(a: f32, b: f32) {
+        assert_same_f32(add_f32(a, b), a + b, "+", a, b);
+        assert_same_f32(sub_f32(a, b), a - b, "-", a, b);
+        assert_same_f32(mul_f32(a, b), a * b, "*", a, b);
+    }
+
+    #[test]
+    fn test_special_values() {
+        for a in SPECIAL_F64 {
+            for b in SPECIAL_F64 {
+                check_f64(a, b);
+                check_f64(-a, b);
+                check_f32(a as f32, b as f32);
+                check_f32(-a as f32, b as f32);
+            }
+        }
+        // Subnormal results, and the tie between the largest subnormal and the smallest normal.
+        check_f64(f64::MIN_POSITIVE, -f64::MIN_POSITIVE / 3.0);
+        check_f64(f64::MIN_POSITIVE, 0.5);
+        check_f64(f64::MIN_POSITIVE / 4.0, 3.0);
+        check_f32(f32::MIN_POSITIVE, 0.75);
+        check_f32(f32::from_bits(1), 0.5);
+    }
+
+    #[test]
+    fn test_random_values() {
+        let mut rng = Rng(0x0123_4567_89ab_cdef);
+        for _ in 0..1_000_000 {
+            let (a, b) = (rng.f64(), rng.f64());
+            check_f64(a, b);
+            let (a, b) = (rng.f32(), rng.f32());
+            check_f32(a, b);
+        }
+    }
+}
@@ -0,0 +1,346 @@
+//! Unsigned big integers with the modular arithmetic of RSA and of the ECDSA curves.
+//!
+//! Products are computed with the `BIGINT_MUL` precompile whenever the operands fit in its maximum
+//! width, and modular reduction uses Barrett reduction so that a modular multiplication costs two
+//! extra precompile calls rather than a long division.
+
+use core::cmp::Ordering;
+
+/// The number of words in a single limb of the `BIGINT_MUL` precompile.
+const PRECOMPILE_LIMB_WORDS: usize = 8;
+
+/// The maximum number of limbs accepted by the `BIGINT_MUL` precompile.
+const PRECOMPILE_MAX_LIMBS: usize = 16;
+
+/// An unsigned integer stored as little-endian 32-bit words with no trailing zero words.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BigUint {
+    words: Vec<u32>,
+}
+
+impl BigUint {
+    /// Creates a new [`BigUint`] from little-endian words.
+    pub fn from_words(words: Vec<u32>) -> Self {
+        let mut value = Self { words };
+        value.normalize();
+        value
+    }
+
+    /// Creates a new [`BigUint`] from big-endian bytes.
+    pub fn from_be_bytes(bytes: &[u8]) -> Self {
+        let words = bytes
+            .rchunks(4)
+            .map(|chunk| {
+                let mut word = [0u8; 4];
+                word[4 - chunk.len()..].copy_from_slice(chunk);
+                u32::from_be_bytes(word)
+            })
+            .collect();
+        Self::from_words(words)
+    }
+
+    /// Encodes `self` as exactly `len` big-endian bytes, or `None` if it does not fit.
+    pub fn to_be_bytes(&self, len: usize) -> Option<Vec<u8>> {
+        let mut bytes = vec![0u8; len];
+        for (i, word) in self.words.iter().enumerate() {
+            for (j, byte) in word.to_le_bytes().into_iter().enumerate() {
+                let pos = 4 * i + j;
+                if pos < len {
+                    bytes[len - 1 - pos] = byte;
+                } else if byte != 0 {
+                    return None;
+                }
+            }
+        }
+        Some(bytes)
+    }
+
+    /// Returns the little-endian words of `self`.
+    pub fn words(&self) -> &[u32] {
+        &self.words
+    }
+
+    /// Returns whether `self` is zero.
+    pub fn is_zero(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns the number of significant bits of `self`.
+    pub fn bits(&self) -> usize {
+        match self.words.last() {
+            Some(top) => 32 * self.words.len() - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    /// Returns the `i`-th bit of `self`.
+    pub fn bit(&self, i: usize) -> bool {
+        self.words.get(i / 32).map_or(false, |word| (word >> (i % 32)) & 1 == 1)
+    }
+
+    /// Computes `self * other`.
+    pub fn mul(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::default();
+        }
+        let len = self.words.len().max(other.words.len());
+        let num_limbs = len.div_ceil(PRECOMPILE_LIMB_WORDS);
+        if cfg!(target_os = "zkvm") && num_limbs <= PRECOMPILE_MAX_LIMBS {
+            Self::from_words(precompile_mul(&self.words, &other.words, num_limbs))
+        } else {
+            Self::from_words(schoolbook_mul(&self.words, &other.words))
+        }
+    }
+
+    /// Computes `self + other`.
+    pub fn add(&self, other: &Self) -> Self {
+        let len = self.words.len().max(other.words.len());
+        let mut words = Vec::with_capacity(len + 1);
+        let mut carry = 0u64;
+        for i in 0..len {
+            let t = self.words.get(i).copied().unwrap_or(0) as u64
+                + other.words.get(i).copied().unwrap_or(0) as u64
+                + carry;
+            words.push(t as u32);
+            carry = t >> 32;
+        }
+        words.push(carry as u32);
+        Self::from_words(words)
+    }
+
+    /// Computes `self - other`, assuming `self >= other`.
+    pub fn sub(&self, other: &Self) -> Self {
+        debug_assert!(*self >= *other);
+        let mut words = self.words.clone();
+        let mut borrow = 0u64;
+        for (i, word) in words.iter_mut().enumerate() {
+            let rhs = other.words.get(i).copied().unwrap_or(0) as u64 + borrow;
+            let lhs = *word as u64;
+            if lhs >= rhs {
+                *word = (lhs - rhs) as u32;
+                borrow = 0;
+            } else {
+                *word = ((1u64 << 32) + lhs - rhs) as u32;
+                borrow = 1;
+            }
+        }
+        Self::from_words(words)
+    }
+
+    /// Returns `self mod 2^(32 * n)`.
+    fn low_words(&self, n: usize) -> Self {
+        Self::from_words(self.words[..n.min(self.words.len())].to_vec())
+    }
+
+    /// Returns `self >> (32 * n)`.
+    fn shr_words(&self, n: usize) -> Self {
+        Self::from_words(self.words.get(n..).map_or_else(Vec::new, <[u32]>::to_vec))
+    }
+
+    /// Returns `self << 1`.
+    fn shl1(&self) -> Self {
+        let mut words = Vec::with_capacity(self.words.len() + 1);
+        let mut carry = 0;
+        for &word in self.words.iter() {
+            words.push((word << 1) | carry);
+            carry = word >> 31;
+        }
+        words.push(carry);
+        Self::from_words(words)
+    }
+
+    /// Computes `self / divisor` with bitwise long division.
+    ///
+    /// This is only used once per modulus to compute the Barrett constant.
+    fn div(&self, divisor: &Self) -> Self {
+        assert!(!divisor.is_zero(), "division by zero");
+        let mut quotient = vec![0u32; self.words.len()];
+        let mut remainder = Self::default();
+        for i in (0..self.bits()).rev() {
+            remainder = remainder.shl1();
+            if self.bit(i) {
+                if remainder.words.is_empty() {
+                    remainder.words.push(1);
+                } else {
+                    remainder.words[0] |= 1;
+                }
+            }
+            if remainder >= *divisor {
+                remainder = remainder.sub(divisor);
+                quotient[i / 32] |= 1 << (i % 32);
+            }
+        }
+        Self::from_words(quotient)
+    }
+
+    fn normalize(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.words
+            .len()
+            .cmp(&other.words.len())
+            .then_with(|| self.words.iter().rev().cmp(other.words.iter().rev()))
+    }
+}
+
+/// A modulus together with its precomputed Barrett reduction constant.
+#[derive(Debug, Clone)]
+pub struct Modulus {
+    m: BigUint,
+    /// `floor(2^(64 * k) / m)` where `k` is the number of words of `m`.
+    mu: BigUint,
+}
+
+impl Modulus {
+    /// Precomputes the reduction constant for `m`, which must be nonzero.
+    pub fn new(m: BigUint) -> Self {
+        let k = m.words.len();
+        let mut power = vec![0u32; 2 * k + 1];
+        power[2 * k] = 1;
+        let mu = BigUint::from_words(power).div(&m);
+        Self { m, mu }
+    }
+
+    /// Returns the modulus.
+    pub fn value(&self) -> &BigUint {
+        &self.m
+    }
+
+    /// Reduces `x`, which must be less than `m^2`, modulo `m`.
+    pub fn reduce(&self, x: &BigUint) -> BigUint {
+        let k = self.m.words.len();
+        let q = x.shr_words(k - 1).mul(&self.mu).shr_words(k + 1);
+        let r1 = x.low_words(k + 1);
+        let r2 = q.mul(&self.m).low_words(k + 1);
+        let mut r = if r1 >= r2 {
+            r1.sub(&r2)
+        } else {
+            let mut power = vec![0u32; k + 2];
+            power[k + 1] = 1;
+            BigUint::from_words(power).sub(&r2.sub(&r1))
+        };
+        while r >= self.m {
+            r = r.sub(&self.m);
+        }
+        r
+    }
+
+    /// Computes `a + b mod m` for reduced `a` and `b`.
+    pub fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        let sum = a.add(b);
+        if sum >= self.m {
+            sum.sub(&self.m)
+        } else {
+            sum
+        }
+    }
+
+    /// Computes `a - b mod m` for reduced `a` and `b`.
+    pub fn sub(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        if a >= b {
+            a.sub(b)
+        } else {
+            a.add(&self.m).sub(b)
+        }
+    }
+
+    /// Computes `a * b mod m` for reduced `a` and `b`.
+    pub fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        self.reduce(&a.mul(b))
+    }
+
+    /// Computes `base^exp mod m` with left-to-right square-and-multiply.
+    pub fn pow(&self, base: &BigUint, exp: &BigUint) -> BigUint {
+        let base = if *base >= self.m { self.reduce(base) } else { base.clone() };
+        let mut result = self.reduce(&BigUint::from_words(vec![1]));
+        for i in (0..exp.bits()).rev() {
+            result = self.mul(&result, &result);
+            if exp.bit(i) {
+                result = self.mul(&result, &base);
+            }
+        }
+        result
+    }
+}
+
+/// Multiplies two word slices with the `BIGINT_MUL` precompile, padding both to `num_limbs` limbs.
+fn precompile_mul(a: &[u32], b: &[u32], num_limbs: usize) -> Vec<u32> {
+    let width = num_limbs * PRECOMPILE_LIMB_WORDS;
+    let mut operands = vec![0u32; 1 + 2 * width];
+    operands[0] = num_limbs as u32;
+    operands[1..1 + a.len()].copy_from_slice(a);
+    operands[1 + width..1 + width + b.len()].copy_from_slice(b);
+    let mut z = vec![0u32; 2 * width];
+    unsafe {
+        sp1_lib::syscall_bigint_mul(z.as_mut_ptr(), operands.as_ptr());
+    }
+    z
+}
+
+/// Multiplies two word slices in software.
+fn schoolbook_mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut z = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let t = x as u64 * y as u64 + z[i + j] as u64 + carry;
+            z[i + j] = t as u32;
+            carry = t >> 32;
+        }
+        z[i + b.len()] = carry as u32;
+    }
+    z
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_u128(x: u128) -> BigUint {
+        BigUint::from_words(vec![x as u32, (x >> 32) as u32, (x >> 64) as u32, (x >> 96) as u32])
+    }
+
+    #[test]
+    fn test_be_bytes_roundtrip() {
+        let bytes = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07];
+        let x = BigUint::from_be_bytes(&bytes);
+        assert_eq!(x.words(), &[0x04050607, 0x010203]);
+        assert_eq!(x.to_be_bytes(7).unwrap(), bytes);
+        assert_eq!(x.to_be_bytes(9).unwrap()[2..], bytes);
+        assert!(x.to_be_bytes(6).is_none());
+    }
+
+    #[test]
+    fn test_modular_add_sub() {
+        let p = (1u128 << 89) - 1;
+        let modulus = Modulus::new(from_u128(p));
+        assert_eq!(modulus.add(&from_u128(p - 1), &from_u128(3)), from_u128(2));
+        assert_eq!(modulus.add(&from_u128(u64::MAX as u128), &from_u128(1)), from_u128(1 << 64));
+        assert_eq!(modulus.sub(&from_u128(2), &from_u128(3)), from_u128(p - 1));
+        assert_eq!(modulus.sub(&from_u128(1 << 64), &from_u128(1)), from_u128(u64::MAX as u128));
+    }
+
+    #[test]
+    fn test_modular_pow() {
+        let p = (1u128 << 89) - 1;
+        let modulus = Modulus::new(from_u128(p));
+        // By Fermat's little theorem, a^(p - 1) = 1 mod p for prime p.
+        for a in [2u128, 3, 0xdead_beef_cafe, p - 2] {
+            assert_eq!(modulus.pow(&from_u128(a), &from_u128(p - 1)), from_u128(1));
+        }
+        // 2^89 = 1 mod p, so 2^100 = 2^11 mod p.
+        assert_eq!(modulus.pow(&from_u128(2), &from_u128(100)), from_u128(1 << 11));
+    }
+}
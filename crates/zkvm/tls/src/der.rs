@@ -0,0 +1,157 @@
+//! A minimal zero-copy DER reader covering the subset of ASN.1 used by X.509 certificates.
+
+use crate::TlsError;
+
+/// The tag of an ASN.1 `INTEGER`.
+pub const TAG_INTEGER: u8 = 0x02;
+
+/// The tag of an ASN.1 `BIT STRING`.
+pub const TAG_BIT_STRING: u8 = 0x03;
+
+/// The tag of an ASN.1 `OCTET STRING`.
+pub const TAG_OCTET_STRING: u8 = 0x04;
+
+/// The tag of an ASN.1 `NULL`.
+pub const TAG_NULL: u8 = 0x05;
+
+/// The tag of an ASN.1 `OBJECT IDENTIFIER`.
+pub const TAG_OID: u8 = 0x06;
+
+/// The tag of an ASN.1 `SEQUENCE`.
+pub const TAG_SEQUENCE: u8 = 0x30;
+
+/// The tag of the explicit `[0]` context-specific constructed element.
+pub const TAG_CONTEXT_0: u8 = 0xa0;
+
+/// A single DER element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Element<'a> {
+    /// The tag byte.
+    pub tag: u8,
+    /// The contents, excluding the tag and length.
+    pub contents: &'a [u8],
+    /// The full encoding, including the tag and length.
+    pub raw: &'a [u8],
+}
+
+/// A cursor over a sequence of DER elements.
+#[derive(Debug, Clone, Copy)]
+pub struct Reader<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Creates a new [`Reader`] over the given bytes.
+    pub const fn new(input: &'a [u8]) -> Self {
+        Self { input }
+    }
+
+    /// Returns whether all input has been consumed.
+    pub fn is_empty(&self) -> bool {
+        self.input.is_empty()
+    }
+
+    /// Returns the tag of the next element without consuming it.
+    pub fn peek_tag(&self) -> Option<u8> {
+        self.input.first().copied()
+    }
+
+    /// Reads the next element.
+    pub fn read(&mut self) -> Result<Element<'a>, TlsError> {
+        let (&tag, rest) = self.input.split_first().ok_or(TlsError::MalformedDer)?;
+        let (&first, mut rest) = rest.split_first().ok_or(TlsError::MalformedDer)?;
+        let len = if first < 0x80 {
+            first as usize
+        } else {
+            // Long form: the low bits give the number of length bytes that follow.
+            let num_bytes = (first & 0x7f) as usize;
+            if num_bytes == 0 || num_bytes > 4 || rest.len() < num_bytes {
+                return Err(TlsError::MalformedDer);
+            }
+            let len = rest[..num_bytes].iter().fold(0usize, |acc, &b| (acc << 8) | b as usize);
+            rest = &rest[num_bytes..];
+            len
+        };
+        if rest.len() < len {
+            return Err(TlsError::MalformedDer);
+        }
+        let header_len = self.input.len() - rest.len();
+        let raw = &self.input[..header_len + len];
+        let contents = &rest[..len];
+        self.input = &rest[len..];
+        Ok(Element { tag, contents, raw })
+    }
+
+    /// Reads the next element and checks that it has the expected tag.
+    pub fn expect(&mut self, tag: u8) -> Result<Element<'a>, TlsError> {
+        let element = self.read()?;
+        if element.tag != tag {
+            return Err(TlsError::UnexpectedTag { expected: tag, found: element.tag });
+        }
+        Ok(element)
+    }
+
+    /// Reads a `SEQUENCE` and returns a reader over its contents.
+    pub fn sequence(&mut self) -> Result<Reader<'a>, TlsError> {
+        Ok(Reader::new(self.expect(TAG_SEQUENCE)?.contents))
+    }
+
+    /// Reads an `INTEGER` and returns its big-endian magnitude without the sign padding byte.
+    pub fn unsigned_integer(&mut self) -> Result<&'a [u8], TlsError> {
+        let contents = self.expect(TAG_INTEGER)?.contents;
+        match contents {
+            [] => Err(TlsError::MalformedDer),
+            [first, ..] if first & 0x80 != 0 => Err(TlsError::MalformedDer),
+            [0, rest @ ..] => Ok(rest),
+            _ => Ok(contents),
+        }
+    }
+
+    /// Reads a `BIT STRING` with no unused bits and returns its bytes.
+    pub fn bit_string(&mut self) -> Result<&'a [u8], TlsError> {
+        match self.expect(TAG_BIT_STRING)?.contents {
+            [0, rest @ ..] => Ok(rest),
+            _ => Err(TlsError::MalformedDer),
+        }
+    }
+
+    /// Skips the next element if it has the given tag.
+    pub fn skip_optional(&mut self, tag: u8) -> Result<(), TlsError> {
+        if self.peek_tag() == Some(tag) {
+            self.read()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_nested() {
+        // SEQUENCE { INTEGER 0x00ff, BIT STRING 0x00 0xab }
+        let bytes = [0x30, 0x08, 0x02, 0x02, 0x00, 0xff, 0x03, 0x02, 0x00, 0xab];
+        let mut reader = Reader::new(&bytes);
+        let mut seq = reader.sequence().unwrap();
+        assert!(reader.is_empty());
+        assert_eq!(seq.unsigned_integer().unwrap(), &[0xff]);
+        assert_eq!(seq.bit_string().unwrap(), &[0xab]);
+        assert!(seq.is_empty());
+    }
+
+    #[test]
+    fn test_long_form_length() {
+        let mut bytes = vec![0x04, 0x81, 0x80];
+        bytes.extend([0x11; 0x80]);
+        let element = Reader::new(&bytes).expect(TAG_OCTET_STRING).unwrap();
+        assert_eq!(element.contents.len(), 0x80);
+        assert_eq!(element.raw.len(), bytes.len());
+    }
+
+    #[test]
+    fn test_truncated() {
+        let bytes = [0x30, 0x05, 0x02, 0x01];
+        assert_eq!(Reader::new(&bytes).read(), Err(TlsError::MalformedDer));
+    }
+}
@@ -0,0 +1,379 @@
+//! ECDSA signature verification over secp256k1 and P-256.
+//!
+//! Secp256k1 uses the curve precompiles, so its point arithmetic is only available inside the
+//! zkVM. P-256 has no precompile: its points are added in Jacobian coordinates with the modular
+//! arithmetic of [`crate::bigint`], whose products use the `BIGINT_MUL` precompile in the zkVM.
+
+use sp1_lib::{
+    secp256k1::{Secp256k1AffinePoint, N},
+    utils::AffinePoint,
+};
+
+use crate::{
+    bigint::{BigUint, Modulus},
+    der::Reader,
+    TlsError,
+};
+
+/// The order of the secp256k1 group, big-endian.
+const SECP256K1_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// The modulus of the base field of P-256, big-endian.
+const P256_MODULUS: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+];
+
+/// The order of the P-256 group, big-endian.
+const P256_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xbc, 0xe6, 0xfa, 0xad, 0xa7, 0x17, 0x9e, 0x84, 0xf3, 0xb9, 0xca, 0xc2, 0xfc, 0x63, 0x25, 0x51,
+];
+
+/// The coefficient `b` of the P-256 curve `y^2 = x^3 - 3x + b`, big-endian.
+const P256_B: [u8; 32] = [
+    0x5a, 0xc6, 0x35, 0xd8, 0xaa, 0x3a, 0x93, 0xe7, 0xb3, 0xeb, 0xbd, 0x55, 0x76, 0x98, 0x86, 0xbc,
+    0x65, 0x1d, 0x06, 0xb0, 0xcc, 0x53, 0xb0, 0xf6, 0x3b, 0xce, 0x3c, 0x3e, 0x27, 0xd2, 0x60, 0x4b,
+];
+
+/// The generator of the P-256 group, as big-endian `x || y`.
+const P256_GENERATOR: [u8; 64] = [
+    0x6b, 0x17, 0xd1, 0xf2, 0xe1, 0x2c, 0x42, 0x47, 0xf8, 0xbc, 0xe6, 0xe5, 0x63, 0xa4, 0x40, 0xf2,
+    0x77, 0x03, 0x7d, 0x81, 0x2d, 0xeb, 0x33, 0xa0, 0xf4, 0xa1, 0x39, 0x45, 0xd8, 0x98, 0xc2, 0x96,
+    0x4f, 0xe3, 0x42, 0xe2, 0xfe, 0x1a, 0x7f, 0x9b, 0x8e, 0xe7, 0xeb, 0x4a, 0x7c, 0x0f, 0x9e, 0x16,
+    0x2b, 0xce, 0x33, 0x57, 0x6b, 0x31, 0x5e, 0xce, 0xcb, 0xb6, 0x40, 0x68, 0x37, 0xbf, 0x51, 0xf5,
+];
+
+/// Parses a DER-encoded ECDSA signature `(r, s)` and checks that both are in `[1, n)`.
+fn parse_signature(signature: &[u8], n: &Modulus) -> Result<(BigUint, BigUint), TlsError> {
+    let mut seq = Reader::new(signature).sequence()?;
+    let r = BigUint::from_be_bytes(seq.unsigned_integer()?);
+    let s = BigUint::from_be_bytes(seq.unsigned_integer()?);
+    if !seq.is_empty() {
+        return Err(TlsError::MalformedDer);
+    }
+    if r.is_zero() || s.is_zero() || r >= *n.value() || s >= *n.value() {
+        return Err(TlsError::InvalidSignature);
+    }
+    Ok((r, s))
+}
+
+/// Computes the scalars `u1 = z / s` and `u2 = r / s` of the verification equation
+/// `R = u1 G + u2 Q`, where `z` is the digest truncated to the 256 bits of the group order `n`.
+fn verification_scalars(
+    n: &Modulus,
+    digest: &[u8],
+    r: &BigUint,
+    s: &BigUint,
+) -> (BigUint, BigUint) {
+    let z = BigUint::from_be_bytes(&digest[..digest.len().min(32)]);
+    let z = if z >= *n.value() { z.sub(n.value()) } else { z };
+
+    // Since the group order is prime, `s^-1 = s^(n - 2)`.
+    let s_inv = n.pow(s, &n.value().sub(&BigUint::from_words(vec![2])));
+    (n.mul(&z, &s_inv), n.mul(r, &s_inv))
+}
+
+/// Checks that the x-coordinate of `R`, reduced modulo `n`, is `r`.
+fn check_x_coordinate(x: BigUint, n: &Modulus, r: &BigUint) -> Result<(), TlsError> {
+    let x = if x >= *n.value() { x.sub(n.value()) } else { x };
+    if x == *r {
+        Ok(())
+    } else {
+        Err(TlsError::InvalidSignature)
+    }
+}
+
+/// A secp256k1 public key.
+#[derive(Clone)]
+pub struct Secp256k1PublicKey {
+    point: Secp256k1AffinePoint,
+}
+
+impl Secp256k1PublicKey {
+    /// Parses an uncompressed SEC1 point, `0x04 || x || y`.
+    pub fn from_sec1(bytes: &[u8]) -> Result<Self, TlsError> {
+        if bytes.len() != 65 || bytes[0] != 0x04 {
+            return Err(TlsError::UnsupportedAlgorithm);
+        }
+        let mut x = bytes[1..33].to_vec();
+        let mut y = bytes[33..65].to_vec();
+        x.reverse();
+        y.reverse();
+        Ok(Self { point: <Secp256k1AffinePoint as AffinePoint<N>>::from(&x, &y) })
+    }
+
+    /// Verifies a DER-encoded ECDSA signature over a message with the given digest.
+    pub fn verify_prehashed(&self, digest: &[u8], signature: &[u8]) -> Result<(), TlsError> {
+        let n = Modulus::new(BigUint::from_be_bytes(&SECP256K1_ORDER));
+        let (r, s) = parse_signature(signature, &n)?;
+        let (u1, u2) = verification_scalars(&n, digest, &r, &s);
+
+        let bits = |x: &BigUint| (0..256).map(|i| x.bit(i)).collect::<Vec<_>>();
+        let generator = Secp256k1AffinePoint::new(Secp256k1AffinePoint::GENERATOR);
+        let point = Secp256k1AffinePoint::multi_scalar_multiplication(
+            &bits(&u1),
+            generator,
+            &bits(&u2),
+            self.point.clone(),
+        )
+        .ok_or(TlsError::InvalidSignature)?;
+
+        check_x_coordinate(BigUint::from_words(point.limbs_ref()[..8].to_vec()), &n, &r)
+    }
+}
+
+impl core::fmt::Debug for Secp256k1PublicKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Secp256k1PublicKey").field("point", &self.point.limbs_ref()).finish()
+    }
+}
+
+/// A P-256 (secp256r1, prime256v1) public key.
+#[derive(Debug, Clone)]
+pub struct P256PublicKey {
+    x: BigUint,
+    y: BigUint,
+}
+
+impl P256PublicKey {
+    /// Parses an uncompressed SEC1 point, `0x04 || x || y`, and checks that it is on the curve.
+    pub fn from_sec1(bytes: &[u8]) -> Result<Self, TlsError> {
+        if bytes.len() != 65 || bytes[0] != 0x04 {
+            return Err(TlsError::UnsupportedAlgorithm);
+        }
+        let x = BigUint::from_be_bytes(&bytes[1..33]);
+        let y = BigUint::from_be_bytes(&bytes[33..65]);
+
+        let curve = P256::new();
+        let p = curve.p.value();
+        if x >= *p || y >= *p || !curve.is_on_curve(&x, &y) {
+            return Err(TlsError::InvalidPublicKey);
+        }
+        Ok(Self { x, y })
+    }
+
+    /// Verifies a DER-encoded ECDSA signature over a message with the given digest.
+    pub fn verify_prehashed(&self, digest: &[u8], signature: &[u8]) -> Result<(), TlsError> {
+        let n = Modulus::new(BigUint::from_be_bytes(&P256_ORDER));
+        let (r, s) = parse_signature(signature, &n)?;
+        let (u1, u2) = verification_scalars(&n, digest, &r, &s);
+
+        let curve = P256::new();
+        let generator = JacobianPoint::from_affine(
+            BigUint::from_be_bytes(&P256_GENERATOR[..32]),
+            BigUint::from_be_bytes(&P256_GENERATOR[32..]),
+        );
+        let key = JacobianPoint::from_affine(self.x.clone(), self.y.clone());
+        let point = curve.double_scalar_mul(&u1, &generator, &u2, &key);
+        let x = curve.affine_x(&point).ok_or(TlsError::InvalidSignature)?;
+
+        check_x_coordinate(x, &n, &r)
+    }
+}
+
+/// A point of P-256 in Jacobian coordinates, `(X / Z^2, Y / Z^3)`, the identity having `Z = 0`.
+#[derive(Debug, Clone)]
+struct JacobianPoint {
+    x: BigUint,
+    y: BigUint,
+    z: BigUint,
+}
+
+impl JacobianPoint {
+    fn identity() -> Self {
+        Self {
+            x: BigUint::from_words(vec![1]),
+            y: BigUint::from_words(vec![1]),
+            z: BigUint::default(),
+        }
+    }
+
+    fn from_affine(x: BigUint, y: BigUint) -> Self {
+        Self { x, y, z: BigUint::from_words(vec![1]) }
+    }
+
+    fn is_identity(&self) -> bool {
+        self.z.is_zero()
+    }
+}
+
+/// The arithmetic of the P-256 curve `y^2 = x^3 - 3x + b` over the field of `p`.
+struct P256 {
+    p: Modulus,
+}
+
+impl P256 {
+    fn new() -> Self {
+        Self { p: Modulus::new(BigUint::from_be_bytes(&P256_MODULUS)) }
+    }
+
+    /// Returns `k * a` for a small constant `k`.
+    fn small_mul(&self, k: u32, a: &BigUint) -> BigUint {
+        self.p.mul(a, &BigUint::from_words(vec![k]))
+    }
+
+    /// Returns whether the reduced affine point `(x, y)` is on the curve.
+    fn is_on_curve(&self, x: &BigUint, y: &BigUint) -> bool {
+        let p = &self.p;
+        let x3 = p.mul(&p.mul(x, x), x);
+        let rhs = p.add(&p.sub(&x3, &self.small_mul(3, x)), &BigUint::from_be_bytes(&P256_B));
+        p.mul(y, y) == rhs
+    }
+
+    /// Doubles `a`, with the `dbl-2001-b` formulas for curves with `a = -3`.
+    fn double(&self, a: &JacobianPoint) -> JacobianPoint {
+        if a.is_identity() || a.y.is_zero() {
+            return JacobianPoint::identity();
+        }
+        let p = &self.p;
+        let delta = p.mul(&a.z, &a.z);
+        let gamma = p.mul(&a.y, &a.y);
+        let beta = p.mul(&a.x, &gamma);
+        let alpha = self.small_mul(3, &p.mul(&p.sub(&a.x, &delta), &p.add(&a.x, &delta)));
+
+        let x = p.sub(&p.mul(&alpha, &alpha), &self.small_mul(8, &beta));
+        let y_plus_z = p.add(&a.y, &a.z);
+        let z = p.sub(&p.sub(&p.mul(&y_plus_z, &y_plus_z), &gamma), &delta);
+        let y = p.sub(
+            &p.mul(&alpha, &p.sub(&self.small_mul(4, &beta), &x)),
+            &self.small_mul(8, &p.mul(&gamma, &gamma)),
+        );
+        JacobianPoint { x, y, z }
+    }
+
+    /// Adds `a` and `b`, with the `add-1998-cmo-2` formulas.
+    fn add(&self, a: &JacobianPoint, b: &JacobianPoint) -> JacobianPoint {
+        if a.is_identity() {
+            return b.clone();
+        }
+        if b.is_identity() {
+            return a.clone();
+        }
+        let p = &self.p;
+        let z1z1 = p.mul(&a.z, &a.z);
+        let z2z2 = p.mul(&b.z, &b.z);
+        let u1 = p.mul(&a.x, &z2z2);
+        let u2 = p.mul(&b.x, &z1z1);
+        let s1 = p.mul(&p.mul(&a.y, &b.z), &z2z2);
+        let s2 = p.mul(&p.mul(&b.y, &a.z), &z1z1);
+        let h = p.sub(&u2, &u1);
+        let r = p.sub(&s2, &s1);
+        if h.is_zero() {
+            return if r.is_zero() { self.double(a) } else { JacobianPoint::identity() };
+        }
+
+        let hh = p.mul(&h, &h);
+        let hhh = p.mul(&h, &hh);
+        let v = p.mul(&u1, &hh);
+        let x = p.sub(&p.sub(&p.mul(&r, &r), &hhh), &p.add(&v, &v));
+        let y = p.sub(&p.mul(&r, &p.sub(&v, &x)), &p.mul(&s1, &hhh));
+        let z = p.mul(&p.mul(&a.z, &b.z), &h);
+        JacobianPoint { x, y, z }
+    }
+
+    /// Computes `u1 * g + u2 * q` with a single chain of doublings.
+    fn double_scalar_mul(
+        &self,
+        u1: &BigUint,
+        g: &JacobianPoint,
+        u2: &BigUint,
+        q: &JacobianPoint,
+    ) -> JacobianPoint {
+        let g_plus_q = self.add(g, q);
+        let mut acc = JacobianPoint::identity();
+        for i in (0..u1.bits().max(u2.bits())).rev() {
+            acc = self.double(&acc);
+            match (u1.bit(i), u2.bit(i)) {
+                (true, true) => acc = self.add(&acc, &g_plus_q),
+                (true, false) => acc = self.add(&acc, g),
+                (false, true) => acc = self.add(&acc, q),
+                (false, false) => {}
+            }
+        }
+        acc
+    }
+
+    /// Returns the affine x-coordinate of `a`, or `None` if it is the identity.
+    fn affine_x(&self, a: &JacobianPoint) -> Option<BigUint> {
+        if a.is_identity() {
+            return None;
+        }
+        let p = &self.p;
+        // Since the modulus is prime, `z^-2 = z^(p - 3)`.
+        let z_inv2 = p.pow(&a.z, &p.value().sub(&BigUint::from_words(vec![3])));
+        Some(p.mul(&a.x, &z_inv2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::{Digest, Sha256};
+
+    use super::*;
+
+    /// Decodes a hexadecimal string.
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+    }
+
+    /// Encodes `(r, s)` as a DER `SEQUENCE` of two `INTEGER`s.
+    fn der_signature(r: &[u8], s: &[u8]) -> Vec<u8> {
+        let integer = |x: &[u8]| {
+            let mut contents = x.to_vec();
+            if contents[0] & 0x80 != 0 {
+                contents.insert(0, 0);
+            }
+            let mut der = vec![0x02, contents.len() as u8];
+            der.extend(contents);
+            der
+        };
+        let contents = [integer(r), integer(s)].concat();
+        let mut der = vec![0x30, contents.len() as u8];
+        der.extend(contents);
+        der
+    }
+
+    /// The P-256 key and SHA-256 signature of "sample" from RFC 6979, appendix A.2.5.
+    fn rfc6979_vector() -> (P256PublicKey, Vec<u8>, Vec<u8>, Vec<u8>) {
+        let key = [
+            vec![0x04],
+            hex("60FED4BA255A9D31C961EB74C6356D68C049B8923B61FA6CE669622E60F29FB6"),
+            hex("7903FE1008B8BC99A41AE9E95628BC64F2F1B20C2D7E9F5177A3C294D4462299"),
+        ]
+        .concat();
+        let r = hex("EFD48B2AACB6A8FD1140DD9CD45E81D69D2C877B56AAF991C34D0EA84EAF3716");
+        let s = hex("F7CB1C942D657C41D436C7A1B6E29F65F3E900DBB9AFF4064DC4AB2F843ACDA8");
+        (P256PublicKey::from_sec1(&key).unwrap(), Sha256::digest(b"sample").to_vec(), r, s)
+    }
+
+    #[test]
+    fn test_p256_verify() {
+        let (key, digest, r, s) = rfc6979_vector();
+        key.verify_prehashed(&digest, &der_signature(&r, &s)).unwrap();
+
+        // Another message, or a signature of another message, does not verify.
+        let other = Sha256::digest(b"test").to_vec();
+        assert_eq!(
+            key.verify_prehashed(&other, &der_signature(&r, &s)),
+            Err(TlsError::InvalidSignature)
+        );
+        let mut tampered = s.clone();
+        tampered[31] ^= 1;
+        assert_eq!(
+            key.verify_prehashed(&digest, &der_signature(&r, &tampered)),
+            Err(TlsError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_p256_rejects_points_off_the_curve() {
+        let mut key = [vec![0x04], P256_GENERATOR.to_vec()].concat();
+        P256PublicKey::from_sec1(&key).unwrap();
+        key[64] ^= 1;
+        assert_eq!(P256PublicKey::from_sec1(&key).unwrap_err(), TlsError::InvalidPublicKey);
+    }
+}
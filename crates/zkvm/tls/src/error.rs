@@ -0,0 +1,48 @@
+use core::fmt;
+
+/// Errors that can occur while parsing or verifying certificates and signatures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TlsError {
+    /// The DER encoding was truncated or malformed.
+    MalformedDer,
+    /// A DER element had a different tag than expected.
+    UnexpectedTag { expected: u8, found: u8 },
+    /// The algorithm identified by the given OID is not supported.
+    UnsupportedAlgorithm,
+    /// The public key does not match the signature algorithm of the certificate it verifies.
+    KeyAlgorithmMismatch,
+    /// The issuer of a certificate does not match the subject of the next certificate.
+    IssuerMismatch { index: usize },
+    /// The public key is not a valid point of its curve.
+    InvalidPublicKey,
+    /// The signature failed to verify.
+    InvalidSignature,
+    /// The root certificate is not self-signed.
+    RootNotSelfSigned,
+    /// The certificate chain is empty.
+    EmptyChain,
+}
+
+impl fmt::Display for TlsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TlsError::MalformedDer => write!(f, "malformed DER encoding"),
+            TlsError::UnexpectedTag { expected, found } => {
+                write!(f, "unexpected DER tag: expected {:#04x}, found {:#04x}", expected, found)
+            }
+            TlsError::UnsupportedAlgorithm => write!(f, "unsupported algorithm"),
+            TlsError::KeyAlgorithmMismatch => {
+                write!(f, "public key does not match the signature algorithm")
+            }
+            TlsError::IssuerMismatch { index } => {
+                write!(f, "issuer of certificate {} does not match its parent's subject", index)
+            }
+            TlsError::InvalidPublicKey => write!(f, "invalid public key"),
+            TlsError::InvalidSignature => write!(f, "invalid signature"),
+            TlsError::RootNotSelfSigned => write!(f, "root certificate is not self-signed"),
+            TlsError::EmptyChain => write!(f, "empty certificate chain"),
+        }
+    }
+}
+
+impl std::error::Error for TlsError {}
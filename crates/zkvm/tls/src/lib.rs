@@ -0,0 +1,17 @@
+//! X.509 certificate chain verification for SP1 zkVM guests.
+//!
+//! RSA-PKCS1v1.5 signatures are checked with modular exponentiation backed by the variable-width
+//! `BIGINT_MUL` precompile. ECDSA signatures over secp256k1 use the curve precompiles exposed by
+//! `sp1-lib`, and ECDSA signatures over P-256 use the same modular arithmetic as RSA. On non-zkvm
+//! targets the bigint arithmetic falls back to a software implementation so that the same code
+//! can be exercised on the host.
+
+pub mod bigint;
+pub mod der;
+pub mod ecdsa;
+mod error;
+pub mod rsa;
+pub mod x509;
+
+pub use error::TlsError;
+pub use x509::{verify_chain, Certificate, PublicKey, SignatureAlgorithm};
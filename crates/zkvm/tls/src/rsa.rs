@@ -0,0 +1,163 @@
+//! RSASSA-PKCS1-v1_5 signature verification (RFC 8017, section 8.2.2).
+
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use crate::{
+    bigint::{BigUint, Modulus},
+    der::Reader,
+    TlsError,
+};
+
+/// The smallest modulus accepted by [`RsaPublicKey::new`], in bits.
+pub const MIN_MODULUS_BITS: usize = 1024;
+
+/// The largest modulus accepted by [`RsaPublicKey::new`], in bits.
+pub const MAX_MODULUS_BITS: usize = 4096;
+
+/// The hash functions supported for PKCS#1 v1.5 signatures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    /// Hashes the given message.
+    pub fn digest(&self, message: &[u8]) -> Vec<u8> {
+        match self {
+            HashAlgorithm::Sha256 => Sha256::digest(message).to_vec(),
+            HashAlgorithm::Sha384 => Sha384::digest(message).to_vec(),
+            HashAlgorithm::Sha512 => Sha512::digest(message).to_vec(),
+        }
+    }
+
+    /// The DER encoding of the `DigestInfo` prefix that precedes the hash in the encoded message.
+    fn digest_info_prefix(&self) -> &'static [u8] {
+        match self {
+            HashAlgorithm::Sha256 => &[
+                0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+                0x01, 0x05, 0x00, 0x04, 0x20,
+            ],
+            HashAlgorithm::Sha384 => &[
+                0x30, 0x41, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+                0x02, 0x05, 0x00, 0x04, 0x30,
+            ],
+            HashAlgorithm::Sha512 => &[
+                0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+                0x03, 0x05, 0x00, 0x04, 0x40,
+            ],
+        }
+    }
+}
+
+/// An RSA public key.
+#[derive(Debug, Clone)]
+pub struct RsaPublicKey {
+    n: Modulus,
+    e: BigUint,
+    /// The length of the modulus in bytes.
+    len: usize,
+}
+
+impl RsaPublicKey {
+    /// Creates a new [`RsaPublicKey`] from the big-endian modulus and public exponent.
+    pub fn new(n: &[u8], e: &[u8]) -> Result<Self, TlsError> {
+        let n = BigUint::from_be_bytes(n);
+        let e = BigUint::from_be_bytes(e);
+        let bits = n.bits();
+        if !(MIN_MODULUS_BITS..=MAX_MODULUS_BITS).contains(&bits) || !n.bit(0) || e.bits() < 2 {
+            return Err(TlsError::UnsupportedAlgorithm);
+        }
+        Ok(Self { n: Modulus::new(n), e, len: bits.div_ceil(8) })
+    }
+
+    /// Parses a DER-encoded `RSAPublicKey` (RFC 8017, appendix A.1.1).
+    pub fn from_der(der: &[u8]) -> Result<Self, TlsError> {
+        let mut seq = Reader::new(der).sequence()?;
+        let n = seq.unsigned_integer()?;
+        let e = seq.unsigned_integer()?;
+        Self::new(n, e)
+    }
+
+    /// Verifies a PKCS#1 v1.5 signature over `message`.
+    pub fn verify(
+        &self,
+        hash: HashAlgorithm,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), TlsError> {
+        self.verify_prehashed(hash, &hash.digest(message), signature)
+    }
+
+    /// Verifies a PKCS#1 v1.5 signature over a message with the given digest.
+    pub fn verify_prehashed(
+        &self,
+        hash: HashAlgorithm,
+        digest: &[u8],
+        signature: &[u8],
+    ) -> Result<(), TlsError> {
+        if signature.len() != self.len {
+            return Err(TlsError::InvalidSignature);
+        }
+        let s = BigUint::from_be_bytes(signature);
+        if s >= *self.n.value() {
+            return Err(TlsError::InvalidSignature);
+        }
+        let m = self.n.pow(&s, &self.e);
+        let encoded = m.to_be_bytes(self.len).ok_or(TlsError::InvalidSignature)?;
+
+        // The encoded message is `0x00 || 0x01 || 0xff.. || 0x00 || DigestInfo || digest`.
+        let prefix = hash.digest_info_prefix();
+        let t_len = prefix.len() + digest.len();
+        if self.len < t_len + 11 {
+            return Err(TlsError::InvalidSignature);
+        }
+        let ps_len = self.len - t_len - 3;
+        let valid = encoded[0] == 0x00
+            && encoded[1] == 0x01
+            && encoded[2..2 + ps_len].iter().all(|&b| b == 0xff)
+            && encoded[2 + ps_len] == 0x00
+            && &encoded[3 + ps_len..3 + ps_len + prefix.len()] == prefix
+            && &encoded[3 + ps_len + prefix.len()..] == digest;
+        if valid {
+            Ok(())
+        } else {
+            Err(TlsError::InvalidSignature)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODULUS: &str = "b626cfaceccf7b4facee9cc4632b8d8f48d7f226ab76efa69aed3890cd6d376d3d22238d57d2f3d73efe2b64126cb682d59e5a5c165df8b75f755143eec3aa4bea000d1e446035e512ea65b9a32914e816205a387808e9639f85a3098cbd576098aa38a0b70e2bec4f028de42560dd6d4332ea1784f3d44b458163012b2ea895";
+    const SIGNATURE: &str = "68e51320116e9a1cf76ae4f741eada16ee2f2df665921977f8ffb2426e910bf07d6d2084618b6fa0fecbbaee7b80db0d59ed3840f8507a5f1bef82212461827d8af18a087a63e380e406530b8cdb783c687e8ec825f7de2da524fedc6729afb0ab1aea2eeeb2fe045d8369f1f6e26cce95c75291cd754d0ba9f4737dfec67f18";
+
+    fn decode(hex: &str) -> Vec<u8> {
+        (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_verify_pkcs1v15_sha256() {
+        let key = RsaPublicKey::new(&decode(MODULUS), &[0x01, 0x00, 0x01]).unwrap();
+        let signature = decode(SIGNATURE);
+        key.verify(HashAlgorithm::Sha256, b"sp1 zk tls", &signature).unwrap();
+
+        assert_eq!(
+            key.verify(HashAlgorithm::Sha256, b"sp1 zk tl5", &signature),
+            Err(TlsError::InvalidSignature)
+        );
+        assert_eq!(
+            key.verify(HashAlgorithm::Sha384, b"sp1 zk tls", &signature),
+            Err(TlsError::InvalidSignature)
+        );
+        let mut tampered = signature.clone();
+        tampered[17] ^= 1;
+        assert_eq!(
+            key.verify(HashAlgorithm::Sha256, b"sp1 zk tls", &tampered),
+            Err(TlsError::InvalidSignature)
+        );
+    }
+}
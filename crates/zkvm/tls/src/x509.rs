@@ -0,0 +1,194 @@
+//! X.509 certificate parsing and chain verification (RFC 5280).
+//!
+//! Only the fields needed to check signatures along a chain are extracted. Validity periods,
+//! extensions and revocation are not checked, since the guest has no trusted clock; callers that
+//! need them should inspect [`Certificate::tbs`] or commit the relevant fields as public values.
+
+use crate::{
+    der::{Reader, TAG_CONTEXT_0, TAG_NULL, TAG_OID, TAG_SEQUENCE},
+    ecdsa::{P256PublicKey, Secp256k1PublicKey},
+    rsa::{HashAlgorithm, RsaPublicKey},
+    TlsError,
+};
+
+/// `rsaEncryption` (1.2.840.113549.1.1.1).
+const OID_RSA_ENCRYPTION: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+
+/// `sha256WithRSAEncryption` (1.2.840.113549.1.1.11).
+const OID_SHA256_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b];
+
+/// `sha384WithRSAEncryption` (1.2.840.113549.1.1.12).
+const OID_SHA384_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0c];
+
+/// `sha512WithRSAEncryption` (1.2.840.113549.1.1.13).
+const OID_SHA512_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0d];
+
+/// `id-ecPublicKey` (1.2.840.10045.2.1).
+const OID_EC_PUBLIC_KEY: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+
+/// `ecdsa-with-SHA256` (1.2.840.10045.4.3.2).
+const OID_ECDSA_WITH_SHA256: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02];
+
+/// `secp256k1` (1.3.132.0.10).
+const OID_SECP256K1: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x0a];
+
+/// `prime256v1`, also known as P-256 (1.2.840.10045.3.1.7).
+const OID_PRIME256V1: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+
+/// The signature algorithms supported for certificates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureAlgorithm {
+    /// RSASSA-PKCS1-v1_5 with the given hash.
+    RsaPkcs1(HashAlgorithm),
+    /// ECDSA with SHA-256, over the curve of the issuer's key.
+    EcdsaSha256,
+}
+
+impl SignatureAlgorithm {
+    /// Parses an `AlgorithmIdentifier`.
+    fn parse(reader: &mut Reader<'_>) -> Result<Self, TlsError> {
+        let mut seq = reader.sequence()?;
+        let oid = seq.expect(TAG_OID)?.contents;
+        let algorithm = match oid {
+            OID_SHA256_WITH_RSA => SignatureAlgorithm::RsaPkcs1(HashAlgorithm::Sha256),
+            OID_SHA384_WITH_RSA => SignatureAlgorithm::RsaPkcs1(HashAlgorithm::Sha384),
+            OID_SHA512_WITH_RSA => SignatureAlgorithm::RsaPkcs1(HashAlgorithm::Sha512),
+            OID_ECDSA_WITH_SHA256 => SignatureAlgorithm::EcdsaSha256,
+            _ => return Err(TlsError::UnsupportedAlgorithm),
+        };
+        // RSA identifiers carry an explicit NULL parameter, ECDSA identifiers carry none.
+        seq.skip_optional(TAG_NULL)?;
+        if !seq.is_empty() {
+            return Err(TlsError::MalformedDer);
+        }
+        Ok(algorithm)
+    }
+}
+
+/// The subject public key of a certificate.
+#[derive(Debug, Clone)]
+pub enum PublicKey {
+    Rsa(RsaPublicKey),
+    Secp256k1(Secp256k1PublicKey),
+    P256(P256PublicKey),
+}
+
+impl PublicKey {
+    /// Parses a `SubjectPublicKeyInfo`.
+    fn parse(reader: &mut Reader<'_>) -> Result<Self, TlsError> {
+        let mut spki = reader.sequence()?;
+        let mut algorithm = spki.sequence()?;
+        let oid = algorithm.expect(TAG_OID)?.contents;
+        let key = spki.bit_string()?;
+        match oid {
+            OID_RSA_ENCRYPTION => Ok(PublicKey::Rsa(RsaPublicKey::from_der(key)?)),
+            OID_EC_PUBLIC_KEY => match algorithm.expect(TAG_OID)?.contents {
+                OID_SECP256K1 => Ok(PublicKey::Secp256k1(Secp256k1PublicKey::from_sec1(key)?)),
+                OID_PRIME256V1 => Ok(PublicKey::P256(P256PublicKey::from_sec1(key)?)),
+                _ => Err(TlsError::UnsupportedAlgorithm),
+            },
+            _ => Err(TlsError::UnsupportedAlgorithm),
+        }
+    }
+
+    /// Verifies `signature` over `message` with this key.
+    pub fn verify(
+        &self,
+        algorithm: SignatureAlgorithm,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), TlsError> {
+        match (self, algorithm) {
+            (PublicKey::Rsa(key), SignatureAlgorithm::RsaPkcs1(hash)) => {
+                key.verify(hash, message, signature)
+            }
+            (PublicKey::Secp256k1(key), SignatureAlgorithm::EcdsaSha256) => {
+                key.verify_prehashed(&HashAlgorithm::Sha256.digest(message), signature)
+            }
+            (PublicKey::P256(key), SignatureAlgorithm::EcdsaSha256) => {
+                key.verify_prehashed(&HashAlgorithm::Sha256.digest(message), signature)
+            }
+            _ => Err(TlsError::KeyAlgorithmMismatch),
+        }
+    }
+}
+
+/// A parsed X.509 certificate borrowing from its DER encoding.
+#[derive(Debug, Clone)]
+pub struct Certificate<'a> {
+    /// The DER encoding of the `TBSCertificate`, which is the signed message.
+    pub tbs: &'a [u8],
+    /// The DER encoding of the issuer `Name`.
+    pub issuer: &'a [u8],
+    /// The DER encoding of the subject `Name`.
+    pub subject: &'a [u8],
+    /// The subject public key.
+    pub public_key: PublicKey,
+    /// The algorithm the issuer used to sign this certificate.
+    pub signature_algorithm: SignatureAlgorithm,
+    /// The signature over [`Certificate::tbs`].
+    pub signature: &'a [u8],
+}
+
+impl<'a> Certificate<'a> {
+    /// Parses a DER-encoded certificate.
+    pub fn from_der(der: &'a [u8]) -> Result<Self, TlsError> {
+        let mut outer = Reader::new(der);
+        let mut cert = outer.sequence()?;
+        if !outer.is_empty() {
+            return Err(TlsError::MalformedDer);
+        }
+
+        let tbs = cert.expect(TAG_SEQUENCE)?;
+        let signature_algorithm = SignatureAlgorithm::parse(&mut cert)?;
+        let signature = cert.bit_string()?;
+
+        let mut fields = Reader::new(tbs.contents);
+        fields.skip_optional(TAG_CONTEXT_0)?;
+        let _serial_number = fields.read()?;
+        if SignatureAlgorithm::parse(&mut fields)? != signature_algorithm {
+            return Err(TlsError::KeyAlgorithmMismatch);
+        }
+        let issuer = fields.expect(TAG_SEQUENCE)?.raw;
+        let _validity = fields.expect(TAG_SEQUENCE)?;
+        let subject = fields.expect(TAG_SEQUENCE)?.raw;
+        let public_key = PublicKey::parse(&mut fields)?;
+
+        Ok(Self { tbs: tbs.raw, issuer, subject, public_key, signature_algorithm, signature })
+    }
+
+    /// Verifies that this certificate was signed by `issuer`.
+    pub fn verify_signed_by(&self, issuer: &Certificate<'_>) -> Result<(), TlsError> {
+        issuer.public_key.verify(self.signature_algorithm, self.tbs, self.signature)
+    }
+}
+
+/// Verifies a certificate chain against a trusted root and returns the parsed leaf.
+///
+/// `chain` is ordered from the leaf to the last intermediate, each certificate being issued by the
+/// next one, and the last one being issued by `root`, which must be self-signed.
+///
+/// A self-signature only shows that the root is well-formed, since anyone can sign their own
+/// certificate: the trust comes from `root` being pinned, either compiled into the guest or
+/// committed to the public values for the verifier to compare with the roots it trusts.
+pub fn verify_chain<'a>(chain: &[&'a [u8]], root: &[u8]) -> Result<Certificate<'a>, TlsError> {
+    if chain.is_empty() {
+        return Err(TlsError::EmptyChain);
+    }
+    let root = Certificate::from_der(root)?;
+    if root.issuer != root.subject || root.verify_signed_by(&root).is_err() {
+        return Err(TlsError::RootNotSelfSigned);
+    }
+    let certs =
+        chain.iter().map(|der| Certificate::from_der(der)).collect::<Result<Vec<_>, _>>()?;
+
+    for (index, cert) in certs.iter().enumerate() {
+        let issuer = certs.get(index + 1).unwrap_or(&root);
+        if cert.issuer != issuer.subject {
+            return Err(TlsError::IssuerMismatch { index });
+        }
+        cert.verify_signed_by(issuer)?;
+    }
+
+    Ok(certs.into_iter().next().unwrap())
+}
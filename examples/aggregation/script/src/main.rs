@@ -55,25 +55,21 @@ fn main() {
 
     // Aggregate the proofs.
     tracing::info_span!("aggregate the proofs").in_scope(|| {
-        let mut stdin = SP1Stdin::new();
-
-        // Write the verification keys.
+        // Write the verification keys and the public values, and register the proofs.
+        //
+        // Note: the proofs will not actually be read by the aggregation program, instead they will
+        // be witnessed by the prover during the recursive aggregation process inside SP1 itself.
         let vkeys = inputs.iter().map(|input| input.vk.hash_u32()).collect::<Vec<_>>();
-        stdin.write::<Vec<[u32; 8]>>(&vkeys);
-
-        // Write the public values.
         let public_values =
             inputs.iter().map(|input| input.proof.public_values.to_vec()).collect::<Vec<_>>();
-        stdin.write::<Vec<Vec<u8>>>(&public_values);
-
-        // Write the proofs.
-        //
-        // Note: this data will not actually be read by the aggregation program, instead it will be
-        // witnessed by the prover during the recursive aggregation process inside SP1 itself.
-        for input in inputs {
-            let SP1Proof::Compressed(proof) = input.proof.proof else { panic!() };
-            stdin.write_proof(proof, input.vk.vk);
-        }
+        let stdin = SP1Stdin::builder()
+            .write::<Vec<[u32; 8]>>(&vkeys)
+            .write::<Vec<Vec<u8>>>(&public_values)
+            .write_proofs(inputs.into_iter().map(|input| {
+                let SP1Proof::Compressed(proof) = input.proof.proof else { panic!() };
+                (proof, input.vk.vk)
+            }))
+            .build();
 
         // Generate the plonk bn254 proof.
         client.prove(&aggregation_pk, stdin).plonk().run().expect("proving failed");
@@ -0,0 +1,31 @@
+// A C program that commits the Adler-32 checksum of a message, and the first lane of the
+// Keccak-f[1600] permutation of the zero state computed with the precompile.
+#include <stdio.h>
+#include <string.h>
+
+#include "sp1.h"
+
+static uint32_t adler32(const uint8_t *data, size_t len) {
+    uint32_t a = 1, b = 0;
+    for (size_t i = 0; i < len; i++) {
+        a = (a + data[i]) % 65521;
+        b = (b + a) % 65521;
+    }
+    return (b << 16) | a;
+}
+
+int main(void) {
+    size_t len;
+    const uint8_t *message = sp1_read(&len);
+    printf("read a message of %u bytes\n", (unsigned)len);
+
+    uint32_t checksum = adler32(message, len);
+    sp1_commit(&checksum, sizeof(checksum));
+
+    uint64_t state[25];
+    memset(state, 0, sizeof(state));
+    syscall_keccak_permute(state);
+    sp1_commit(&state[0], sizeof(state[0]));
+
+    return 0;
+}
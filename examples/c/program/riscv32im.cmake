@@ -0,0 +1,14 @@
+# A toolchain file for the RV32IM target of the SP1 zkVM, with a bare-metal GCC toolchain such
+# as `riscv32-unknown-elf-gcc` with newlib.
+set(CMAKE_SYSTEM_NAME Generic)
+set(CMAKE_SYSTEM_PROCESSOR riscv32)
+
+set(RISCV_PREFIX "riscv32-unknown-elf-" CACHE STRING "The prefix of the RISC-V toolchain")
+set(CMAKE_C_COMPILER "${RISCV_PREFIX}gcc")
+set(CMAKE_CXX_COMPILER "${RISCV_PREFIX}g++")
+
+# The zkVM implements RV32IM without compressed instructions or floating point registers.
+set(CMAKE_C_FLAGS_INIT "-march=rv32ima -mabi=ilp32")
+set(CMAKE_CXX_FLAGS_INIT "-march=rv32ima -mabi=ilp32")
+
+set(CMAKE_TRY_COMPILE_TARGET_TYPE STATIC_LIBRARY)
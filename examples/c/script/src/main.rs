@@ -0,0 +1,39 @@
+//! A script that proves a program written in C computes the checksum of a message.
+//!
+//! Build the program first:
+//!
+//! ```sh
+//! cmake -S ../program -B ../program/build -DCMAKE_TOOLCHAIN_FILE=riscv32im.cmake
+//! cmake --build ../program/build
+//! ```
+use sp1_sdk::{utils, ProverClient, SP1Stdin};
+
+const ELF_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../program/build/c-program");
+
+fn main() {
+    // Setup a tracer for logging.
+    utils::setup_logger();
+
+    // The program is built with CMake rather than by a build script.
+    let elf = std::fs::read(ELF_PATH).expect("build the program with CMake first");
+
+    let message = b"Wikipedia";
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(message);
+
+    // Generate and verify the proof.
+    let client = ProverClient::new();
+    let (pk, vk) = client.setup(&elf);
+    let mut proof = client.prove(&pk, stdin).run().expect("proving failed");
+    client.verify(&proof, &vk).expect("verification failed");
+
+    // Check the committed checksum, and the first lane of the permutation of the zero state.
+    let mut checksum = [0u8; 4];
+    proof.public_values.read_slice(&mut checksum);
+    assert_eq!(u32::from_le_bytes(checksum), 0x11e60398);
+    let mut lane = [0u8; 8];
+    proof.public_values.read_slice(&mut lane);
+    assert_eq!(u64::from_le_bytes(lane), 0xf1258f7940e1dde7);
+
+    println!("successfully generated and verified proof for the program!")
+}
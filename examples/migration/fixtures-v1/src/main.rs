@@ -0,0 +1,28 @@
+//! Generates a compressed proof of the fibonacci program with SP1 v1.1, and saves it to
+//! `../fixtures` with its verifying key, the hash of the verifying key and its public values.
+
+use std::{fs, path::Path};
+
+use sp1_sdk::{HashableKey, ProverClient, SP1Stdin};
+
+/// The fibonacci program.
+const FIBONACCI_ELF: &[u8] =
+    include_bytes!("../../../fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+
+fn main() {
+    sp1_sdk::utils::setup_logger();
+
+    let client = ProverClient::new();
+    let (pk, vk) = client.setup(FIBONACCI_ELF);
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&20u32);
+    let proof = client.prove(&pk, stdin).compressed().run().expect("proving failed");
+    client.verify(&proof, &vk).expect("verification failed");
+
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("../fixtures");
+    fs::create_dir_all(&fixtures).unwrap();
+    proof.save(fixtures.join("proof.bin")).expect("failed to save the proof");
+    fs::write(fixtures.join("vk.bin"), bincode::serialize(&vk).unwrap()).unwrap();
+    fs::write(fixtures.join("vkey_hash.txt"), vk.bytes32()).unwrap();
+    fs::write(fixtures.join("public_values.bin"), proof.public_values.to_vec()).unwrap();
+}
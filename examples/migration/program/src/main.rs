@@ -0,0 +1,27 @@
+//! A migrator of compressed proofs of SP1 v1.1, which verifies a proof with the verifier of v1.1
+//! and commits the version, the verifying key hash and the public values of the proof.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sp1_prover_v1::{HashableKey, SP1Prover, SP1VerifyingKey, SP1_CIRCUIT_VERSION};
+use sp1_sdk_v1::{SP1Proof, SP1ProofWithPublicValues};
+
+pub fn main() {
+    // Read the proof and its verifying key, encoded by v1.1.
+    let proof: SP1ProofWithPublicValues =
+        bincode::deserialize(&sp1_zkvm::io::read_vec()).expect("invalid proof");
+    let vk: SP1VerifyingKey =
+        bincode::deserialize(&sp1_zkvm::io::read_vec()).expect("invalid verifying key");
+    let SP1Proof::Compressed(compressed) = &proof.proof else {
+        panic!("only compressed proofs can be migrated");
+    };
+    assert_eq!(proof.sp1_version, SP1_CIRCUIT_VERSION, "the proof is of another version");
+
+    // The compress verifying key is derived from the programs of v1.1 rather than read, so that a
+    // proof of another program cannot pass for it.
+    SP1Prover::new().verify_compressed(compressed, &vk).expect("the proof is invalid");
+
+    // Commit the fields of a `MigrationOutput`, in order.
+    sp1_zkvm::io::commit(&(SP1_CIRCUIT_VERSION, vk.bytes32(), proof.public_values.to_vec()));
+}
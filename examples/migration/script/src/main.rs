@@ -0,0 +1,36 @@
+//! An example showing how to migrate a compressed proof of SP1 v1.1 to this version.
+//!
+//! The proof is read from `../fixtures`, where `../fixtures-v1` saves a proof of the fibonacci
+//! program generated with SP1 v1.1.
+
+use std::{fs, path::Path};
+
+use sp1_sdk::{MigrationInput, ProverClient};
+
+/// The migrator of proofs of SP1 v1.1.
+const MIGRATION_ELF: &[u8] = include_bytes!("../../program/elf/riscv32im-succinct-zkvm-elf");
+
+fn main() {
+    // Setup the logger.
+    sp1_sdk::utils::setup_logger();
+
+    // Read the proof of v1.1, its verifying key, and the hash of the verifying key it must have.
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("../fixtures");
+    let proof = fs::read(fixtures.join("proof.bin")).expect("failed to read the proof");
+    let vk = fs::read(fixtures.join("vk.bin")).expect("failed to read the verifying key");
+    let vkey_hash =
+        fs::read_to_string(fixtures.join("vkey_hash.txt")).expect("failed to read the vkey hash");
+    let input = MigrationInput::new(proof, vk).expect("the proof cannot be migrated");
+
+    // Prove the execution of the migrator on the proof.
+    let client = ProverClient::new();
+    let (pk, vk) = client.setup(MIGRATION_ELF);
+    let proof = client.migrate(&pk, &input).run().expect("migration failed");
+
+    // Verify the migrated proof, and check that it describes the proof of v1.1.
+    let output = client
+        .verify_migration(&proof, &vk, &input, vkey_hash.trim())
+        .expect("verification failed");
+    println!("migrated a proof of {} of program {}", output.sp1_version, output.vkey_hash);
+    println!("public values: 0x{}", hex::encode(&output.public_values));
+}
@@ -0,0 +1,24 @@
+//! A `no_std` library, shared by the program and the script, which computes the Adler-32
+//! checksum of a message.
+
+#![no_std]
+
+/// The largest prime smaller than 2^16.
+const MOD_ADLER: u32 = 65521;
+
+/// The number of bytes that can be summed before the sums must be reduced to not overflow.
+const NMAX: usize = 5552;
+
+/// Computes the Adler-32 checksum of `data`.
+pub fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(NMAX) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= MOD_ADLER;
+        b %= MOD_ADLER;
+    }
+    (b << 16) | a
+}
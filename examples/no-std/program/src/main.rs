@@ -0,0 +1,18 @@
+//! A `no_std` program that reads a message as input, and writes its Adler-32 checksum as an
+//! output.
+//!
+//! Without its default `std` feature, `sp1-zkvm` provides the panic handler of the program, and
+//! reads and commits raw bytes with `io::read_vec` and `io::commit_slice`.
+
+#![no_std]
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+pub fn main() {
+    // Read the message. The returned buffer is allocated with `alloc`.
+    let message = sp1_zkvm::io::read_vec();
+
+    // Compute and commit its checksum.
+    let checksum = lib::adler32(&message);
+    sp1_zkvm::io::commit_slice(&checksum.to_le_bytes());
+}
@@ -0,0 +1,8 @@
+use sp1_build::{build_program_with_args, BuildArgs};
+
+fn main() {
+    build_program_with_args(
+        "../program",
+        BuildArgs { output_directory: "no-std/program/elf".into(), ..Default::default() },
+    );
+}
@@ -0,0 +1,30 @@
+//! A script that proves the `no_std` program computes the checksum of a message.
+use sp1_sdk::{utils, ProverClient, SP1Stdin};
+
+const ELF: &[u8] = include_bytes!("../../program/elf/riscv32im-succinct-zkvm-elf");
+
+fn main() {
+    // Setup a tracer for logging.
+    utils::setup_logger();
+
+    // The program reads the message as raw bytes, since it can't deserialize it without `std`.
+    let message = b"Wikipedia";
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(message);
+
+    // Generate and verify the proof.
+    let client = ProverClient::new();
+    let (pk, vk) = client.setup(ELF);
+    let mut proof = client.prove(&pk, stdin).run().expect("proving failed");
+    client.verify(&proof, &vk).expect("verification failed");
+
+    // Check the committed checksum against the one computed by the same library on the host.
+    let mut checksum = [0u8; 4];
+    proof.public_values.read_slice(&mut checksum);
+    let checksum = u32::from_le_bytes(checksum);
+    assert_eq!(checksum, lib::adler32(message));
+    assert_eq!(checksum, 0x11e60398);
+    println!("checksum of the message: {:#010x}", checksum);
+
+    println!("successfully generated and verified proof for the program!")
+}
@@ -0,0 +1,42 @@
+//! A program aggregating the proofs of consecutive blocks of the rollup.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use lib::{extend_chain_commitment, BlockTransition};
+use sha2::{Digest, Sha256};
+
+pub fn main() {
+    // Read the verification key of the block program and the public values of each block.
+    let block_vkey = sp1_zkvm::io::read::<[u32; 8]>();
+    let public_values = sp1_zkvm::io::read::<Vec<Vec<u8>>>();
+    assert!(!public_values.is_empty(), "no blocks to aggregate");
+
+    let mut parent_state_root = None;
+    let mut state_root = [0u8; 32];
+    let mut chain_commitment = [0u8; 32];
+    for public_values in &public_values {
+        // Verify the proof of the block.
+        let public_values_digest = Sha256::digest(public_values);
+        sp1_zkvm::lib::verify::verify_sp1_proof(&block_vkey, &public_values_digest.into());
+
+        // Check that the block starts from the state the previous block ended in.
+        let (parent, state, block_hash): BlockTransition =
+            bincode::deserialize(public_values).unwrap();
+        match parent_state_root {
+            None => parent_state_root = Some(parent),
+            Some(_) => assert_eq!(parent, state_root, "blocks do not chain"),
+        }
+        state_root = state;
+        chain_commitment = extend_chain_commitment(chain_commitment, &block_hash);
+    }
+
+    // Commit the fields of `sp1_sdk::rollup::RollupOutput`, in order.
+    sp1_zkvm::io::commit(&(
+        block_vkey,
+        parent_state_root.unwrap(),
+        state_root,
+        public_values.len() as u64,
+        chain_commitment,
+    ));
+}
@@ -0,0 +1,20 @@
+//! A program proving the state transition of one block of the rollup.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use lib::{Block, BlockTransition, State};
+
+pub fn main() {
+    // Read the state before the block, and the block.
+    let mut state = sp1_zkvm::io::read::<State>();
+    let block = sp1_zkvm::io::read::<Block>();
+
+    // Apply the block.
+    let parent_state_root = state.root();
+    state.apply(&block);
+
+    // Commit to the transition.
+    let transition: BlockTransition = (parent_state_root, state.root(), block.hash());
+    sp1_zkvm::io::commit(&transition);
+}
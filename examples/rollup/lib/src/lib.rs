@@ -0,0 +1,65 @@
+//! A toy rollup whose state is the balances of its accounts, shared by the block program, the
+//! aggregator and the script.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// A transfer of `amount` from the account `from` to the account `to`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Transfer {
+    pub from: u32,
+    pub to: u32,
+    pub amount: u64,
+}
+
+/// A block of transfers.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Block {
+    pub number: u64,
+    pub transfers: Vec<Transfer>,
+}
+
+impl Block {
+    /// The hash of the block.
+    pub fn hash(&self) -> [u8; 32] {
+        Sha256::digest(bincode::serialize(self).unwrap()).into()
+    }
+}
+
+/// The balances of the accounts.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct State {
+    pub balances: BTreeMap<u32, u64>,
+}
+
+impl State {
+    /// The root of the state, committing to all the balances.
+    pub fn root(&self) -> [u8; 32] {
+        Sha256::digest(bincode::serialize(self).unwrap()).into()
+    }
+
+    /// The state transition function: applies the transfers of `block`, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an account does not have the balance to cover a transfer.
+    pub fn apply(&mut self, block: &Block) {
+        for transfer in &block.transfers {
+            let from = self.balances.entry(transfer.from).or_default();
+            *from = from.checked_sub(transfer.amount).expect("insufficient balance");
+            *self.balances.entry(transfer.to).or_default() += transfer.amount;
+        }
+    }
+}
+
+/// The public values of the block program, as `sp1_sdk::rollup::BlockTransition`: the state root
+/// before the block, the state root after it, and the hash of the block.
+pub type BlockTransition = ([u8; 32], [u8; 32], [u8; 32]);
+
+/// Extends the chain commitment of the blocks with the next block, as
+/// `sp1_sdk::rollup::extend_chain_commitment`.
+pub fn extend_chain_commitment(commitment: [u8; 32], block_hash: &[u8; 32]) -> [u8; 32] {
+    Sha256::new().chain_update(commitment).chain_update(block_hash).finalize().into()
+}
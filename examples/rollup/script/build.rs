@@ -0,0 +1,12 @@
+use sp1_build::{build_program_with_args, BuildArgs};
+
+fn main() {
+    build_program_with_args(
+        "../block",
+        BuildArgs { output_directory: "rollup/block/elf".into(), ..Default::default() },
+    );
+    build_program_with_args(
+        "../aggregator",
+        BuildArgs { output_directory: "rollup/aggregator/elf".into(), ..Default::default() },
+    );
+}
@@ -0,0 +1,67 @@
+//! An example proving blocks of a rollup separately and aggregating their proofs into one.
+
+use lib::{Block, State, Transfer};
+use sp1_sdk::{ProverClient, RollupInput, RollupOutput, SP1Stdin};
+
+/// The program proving the state transition of a block.
+const BLOCK_ELF: &[u8] = include_bytes!("../../block/elf/riscv32im-succinct-zkvm-elf");
+
+/// The program aggregating the proofs of consecutive blocks.
+const AGGREGATOR_ELF: &[u8] = include_bytes!("../../aggregator/elf/riscv32im-succinct-zkvm-elf");
+
+fn main() {
+    // Setup the logger.
+    sp1_sdk::utils::setup_logger();
+
+    // Initialize the proving client.
+    let client = ProverClient::new();
+
+    // Setup the proving and verifying keys.
+    let (block_pk, block_vk) = client.setup(BLOCK_ELF);
+    let (aggregator_pk, aggregator_vk) = client.setup(AGGREGATOR_ELF);
+
+    // Derive the blocks, and the stdin of the block program for each, from the genesis state.
+    let mut state = State { balances: [(0, 1000), (1, 500)].into_iter().collect() };
+    let genesis_state_root = state.root();
+    let blocks = (0..3u64)
+        .map(|number| Block {
+            number,
+            transfers: vec![
+                Transfer { from: 0, to: 1, amount: 100 },
+                Transfer { from: 1, to: 2, amount: 50 + number },
+            ],
+        })
+        .collect::<Vec<_>>();
+    let stdins = blocks
+        .iter()
+        .map(|block| {
+            let mut stdin = SP1Stdin::new();
+            stdin.write(&state);
+            stdin.write(block);
+            state.apply(block);
+            stdin
+        })
+        .collect::<Vec<_>>();
+
+    // Prove each block.
+    let proofs = tracing::info_span!("prove the blocks")
+        .in_scope(|| client.prove_blocks(&block_pk, stdins).expect("proving failed"));
+
+    // Aggregate the proofs of the blocks, checking that they chain.
+    let input = RollupInput::new(block_vk, proofs).expect("blocks do not chain");
+    let proof = tracing::info_span!("aggregate the blocks").in_scope(|| {
+        client.aggregate_blocks(&aggregator_pk, &input).run().expect("proving failed")
+    });
+    client.verify(&proof, &aggregator_vk).expect("verification failed");
+
+    // Check the output of the aggregator.
+    let output = RollupOutput::from_proof(&proof).expect("invalid public values");
+    assert_eq!(output, input.expected_output());
+    assert_eq!(output.parent_state_root, genesis_state_root);
+    assert_eq!(output.state_root, state.root());
+    println!(
+        "aggregated {} blocks, chain commitment 0x{}",
+        output.num_blocks,
+        hex::encode(output.chain_commitment)
+    );
+}
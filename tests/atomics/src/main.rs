@@ -0,0 +1,38 @@
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use core::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, Ordering};
+
+static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+pub fn main() {
+    // `fetch_add` and the other read-modify-write operations compile to the AMO instructions.
+    for _ in 0..10 {
+        COUNTER.fetch_add(3, Ordering::SeqCst);
+    }
+    assert_eq!(COUNTER.load(Ordering::SeqCst), 30);
+    assert_eq!(COUNTER.swap(7, Ordering::AcqRel), 30);
+    assert_eq!(COUNTER.fetch_xor(0b101, Ordering::Relaxed), 7);
+    assert_eq!(COUNTER.fetch_or(0b1000, Ordering::Relaxed), 0b010);
+    assert_eq!(COUNTER.fetch_and(0b1010, Ordering::Relaxed), 0b1010);
+    assert_eq!(COUNTER.fetch_max(u32::MAX, Ordering::Relaxed), 0b1010);
+    assert_eq!(COUNTER.fetch_min(1, Ordering::Relaxed), u32::MAX);
+
+    let signed = AtomicI32::new(-5);
+    assert_eq!(signed.fetch_max(-7, Ordering::Relaxed), -5);
+    assert_eq!(signed.fetch_min(-7, Ordering::Relaxed), -5);
+    assert_eq!(signed.load(Ordering::Relaxed), -7);
+
+    // `compare_exchange` compiles to a loop of LR and SC.
+    assert_eq!(COUNTER.compare_exchange(1, 2, Ordering::SeqCst, Ordering::SeqCst), Ok(1));
+    assert_eq!(COUNTER.compare_exchange(1, 3, Ordering::SeqCst, Ordering::SeqCst), Err(2));
+    assert_eq!(COUNTER.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |x| Some(x * 5)), Ok(2));
+    assert_eq!(COUNTER.load(Ordering::SeqCst), 10);
+
+    // Sub-word atomics are emulated with LR and SC on the enclosing word.
+    let flag = AtomicBool::new(false);
+    assert!(!flag.swap(true, Ordering::AcqRel));
+    assert!(flag.load(Ordering::Acquire));
+
+    sp1_zkvm::io::commit(&COUNTER.load(Ordering::SeqCst));
+}
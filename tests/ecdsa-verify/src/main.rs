@@ -0,0 +1,22 @@
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use k256::ecdsa::{
+    signature::{Signer, Verifier},
+    Signature, SigningKey, VerifyingKey,
+};
+
+/// Verifies a secp256k1 ECDSA signature the given number of times.
+pub fn main() {
+    let num_cases = sp1_zkvm::io::read::<u32>();
+
+    let signing_key = SigningKey::from_slice(&[0x42; 32]).unwrap();
+    let verifying_key = VerifyingKey::from(&signing_key);
+    let message = b"sp1 ecdsa benchmark";
+    let signature: Signature = signing_key.sign(message);
+
+    for _ in 0..num_cases {
+        verifying_key.verify(message, &signature).unwrap();
+    }
+    sp1_zkvm::io::commit(&num_cases);
+}
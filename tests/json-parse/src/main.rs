@@ -0,0 +1,18 @@
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use serde_json::Value;
+
+/// Parses a JSON array of objects and commits the sum of their `balance` fields.
+pub fn main() {
+    let data = sp1_zkvm::io::read::<String>();
+    let value: Value = serde_json::from_str(&data).unwrap();
+
+    let total: u64 = value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["balance"].as_u64().unwrap_or_default())
+        .sum();
+    sp1_zkvm::io::commit(&total);
+}
@@ -0,0 +1,52 @@
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sp1_zkvm::lib::{
+    bn254::Bn254AffinePoint,
+    ed25519::Ed25519AffinePoint,
+    msm::{bn254_msm, ed25519_msm, secp256k1_msm, MSM_MAX_POINTS},
+    secp256k1::Secp256k1AffinePoint,
+    utils::AffinePoint,
+};
+
+/// The little-endian words of `value`.
+fn scalar(value: u128) -> [u32; 8] {
+    let mut words = [0; 8];
+    for (i, word) in words.iter_mut().take(4).enumerate() {
+        *word = (value >> (32 * i)) as u32;
+    }
+    words
+}
+
+/// Checks an MSM of `n` points, more than a single syscall takes, against a scalar multiplication
+/// of the generator. The `i`-th point is `[i + 1] G`.
+fn check<P: AffinePoint<16>>(msm: impl Fn(&[P], &[[u32; 8]]) -> Option<P>, n: usize) {
+    let generator = P::new(P::GENERATOR);
+    let mut points = vec![generator.clone()];
+    let mut next = generator.clone();
+    next.double();
+    while points.len() < n {
+        points.push(next.clone());
+        next.add_assign(&generator);
+    }
+    let values = (1..=n as u128).map(|i| i * 0x9E37_79B9_7F4A_7C15 % (1 << 64)).collect::<Vec<_>>();
+    let scalars = values.iter().map(|&value| scalar(value)).collect::<Vec<_>>();
+
+    let sum = msm(&points, &scalars).unwrap();
+    let mut expected = generator;
+    let total = values.iter().enumerate().map(|(i, value)| (i as u128 + 1) * value).sum();
+    expected.mul_assign(&scalar(total)).unwrap();
+    assert_eq!(sum.limbs_ref(), expected.limbs_ref());
+}
+
+pub fn main() {
+    let n = MSM_MAX_POINTS + 3;
+    check::<Ed25519AffinePoint>(|points, scalars| Some(ed25519_msm(points, scalars)), n);
+    check::<Secp256k1AffinePoint>(secp256k1_msm, n);
+    check::<Bn254AffinePoint>(bn254_msm, n);
+
+    // A sum that is the identity has no affine coordinates.
+    let generator = Secp256k1AffinePoint::new(Secp256k1AffinePoint::GENERATOR);
+    assert!(secp256k1_msm(&[generator], &[[0; 8]]).is_none());
+    assert!(secp256k1_msm(&[], &[]).is_none());
+}